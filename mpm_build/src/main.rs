@@ -47,6 +47,10 @@ struct MpmBuildPackage {
     package_desc: String,
     ddl_sql: String,
     initdb_sql: String,
+    /// Optional SQL run once against the app database when the package is
+    /// installed as an upgrade of an older version.
+    #[serde(default)]
+    migrate_sql: Option<String>,
     wasm_files: Vec<String>,
     output_path: String,
 }
@@ -66,6 +70,14 @@ impl MpmBuildPackage {
                 return Err(anyhow!("Required file '{}' not found at: {}", name, path));
             }
         }
+        if let Some(path) = &self.migrate_sql
+            && !sync_path_exists(path)
+        {
+            return Err(anyhow!(
+                "Migration file 'migrate.sql' not found at: {}",
+                path
+            ));
+        }
 
         // Check if we have at least one WASM file
         if self.wasm_files.is_empty() {
@@ -166,6 +178,14 @@ fn build_command() -> Command {
                         .help("Path to database initializing SQL file")
                         .required(true),
                 )
+                .arg(
+                    Arg::new("migrate-sql")
+                        .long("migrate-sql")
+                        .short('m')
+                        .value_name("FILE")
+                        .help("Path to SQL file run when the package upgrades an older version")
+                        .required(false),
+                )
                 .arg(
                     Arg::new("wasm-files")
                         .long("wasm-files")
@@ -238,6 +258,7 @@ fn parse_from_matches(matches: &clap::ArgMatches) -> Result<MpmBuildCommand> {
                     .get_one::<String>("initdb-sql")
                     .ok_or_else(|| anyhow!("No initdb-sql specified"))?
                     .clone(),
+                migrate_sql: sub_matches.get_one::<String>("migrate-sql").cloned(),
                 wasm_files: sub_matches
                     .get_many::<String>("wasm-files")
                     .ok_or_else(|| anyhow!("No wasm-files specified"))?
@@ -348,6 +369,9 @@ fn write_package_archive(config: &MpmBuildPackage) -> Result<()> {
         "ddl.sql".to_string(),
         "initdb.sql".to_string(),
     ];
+    if config.migrate_sql.is_some() {
+        file_list.push("migrate.sql".to_string());
+    }
     for wasm_path in &config.wasm_files {
         let wasm_path = PathBuf::from(wasm_path);
         let file_name = wasm_path
@@ -369,6 +393,9 @@ fn write_package_archive(config: &MpmBuildPackage) -> Result<()> {
     add_file_to_zip(&mut zip, &config.package_desc, "package.desc.json")?;
     add_file_to_zip(&mut zip, &config.ddl_sql, "ddl.sql")?;
     add_file_to_zip(&mut zip, &config.initdb_sql, "initdb.sql")?;
+    if let Some(migrate_sql) = &config.migrate_sql {
+        add_file_to_zip(&mut zip, migrate_sql, "migrate.sql")?;
+    }
     add_bytes_to_zip(&mut zip, manifest_text.as_bytes(), "package.manifest.json")?;
 
     // Add WASM files with their original names
@@ -406,6 +433,9 @@ fn build_package(config: MpmBuildPackage) -> Result<()> {
     println!("Procedure desc: {}", config.package_desc);
     println!("DDL SQL: {}", config.ddl_sql);
     println!("DB initializing SQL: {}", config.initdb_sql);
+    if let Some(migrate_sql) = &config.migrate_sql {
+        println!("Migration SQL: {}", migrate_sql);
+    }
     println!("WASM files: {}", config.wasm_files.len());
     for wasm_file in &config.wasm_files {
         println!("  - {}", wasm_file);
@@ -53,6 +53,7 @@ mod tests {
                 .join("initdb.sql")
                 .to_string_lossy()
                 .into_owned(),
+            migrate_sql: None,
             wasm_files: vec![
                 temp_dir
                     .path()
@@ -104,6 +105,47 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_package_includes_migrate_sql_when_given() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        create_test_files(temp_dir.path())?;
+        let migrate_path = temp_dir.path().join("migrate.sql");
+        let mut file = SFile::create(&migrate_path)?;
+        write!(file, "ALTER TABLE test ADD COLUMN v INT;")?;
+
+        let mut config = base_config(&temp_dir);
+        config.migrate_sql = Some(migrate_path.to_string_lossy().into_owned());
+        config.validate()?;
+        write_package_archive(&config)?;
+
+        let package_file = SFile::open(&config.output_path)?;
+        let mut zip_archive = zip::ZipArchive::new(package_file)?;
+        assert!(zip_archive.by_name("migrate.sql").is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_rejects_missing_migrate_sql() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        create_test_files(temp_dir.path())?;
+
+        let mut config = base_config(&temp_dir);
+        config.migrate_sql = Some(
+            temp_dir
+                .path()
+                .join("missing.sql")
+                .to_string_lossy()
+                .into_owned(),
+        );
+
+        let err = match config.validate() {
+            Err(e) => e,
+            Ok(_) => panic!("expected validation to fail"),
+        };
+        assert!(err.to_string().contains("migrate.sql"));
+        Ok(())
+    }
+
     #[test]
     fn test_validate_rejects_missing_package_cfg() -> Result<()> {
         let temp_dir = TempDir::new()?;
@@ -1,9 +1,131 @@
+use crate::common::result::RS;
+use crate::error::ErrorCode;
+use crate::mudu_error;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AppInfo {
     pub name: String,
     pub lang: String,
+    /// Dotted numeric version, see [`AppVersion`]. An upgrade must carry a
+    /// higher version than the installed package.
     pub version: String,
     pub use_async: bool,
+    /// Resource limits enforced on the app's procedure instances. Packages
+    /// built before limits existed get the defaults.
+    #[serde(default)]
+    pub limits: AppLimits,
+}
+
+impl AppInfo {
+    pub fn app_version(&self) -> RS<AppVersion> {
+        AppVersion::parse(&self.version)
+    }
+}
+
+/// Package version such as `1.2.0`: dot separated unsigned integers,
+/// optionally followed by a `-`/`+` suffix that is ignored when comparing.
+/// Missing trailing numbers count as zero, so `1.2` equals `1.2.0`.
+#[derive(Debug, Clone)]
+pub struct AppVersion {
+    numbers: Vec<u64>,
+}
+
+impl AppVersion {
+    pub fn parse(text: &str) -> RS<Self> {
+        let core = text.split(['-', '+']).next().unwrap_or_default().trim();
+        let numbers = core
+            .split('.')
+            .map(|n| n.parse::<u64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| {
+                mudu_error!(
+                    ErrorCode::InvalidArgument,
+                    format!("invalid app version {:?}", text),
+                    e
+                )
+            })?;
+        Ok(Self { numbers })
+    }
+
+    fn number(&self, index: usize) -> u64 {
+        self.numbers.get(index).copied().unwrap_or(0)
+    }
+}
+
+impl Ord for AppVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.numbers.len().max(other.numbers.len());
+        (0..len)
+            .map(|i| self.number(i).cmp(&other.number(i)))
+            .find(|o| o.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for AppVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for AppVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for AppVersion {}
+
+impl Display for AppVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let text = self
+            .numbers
+            .iter()
+            .map(u64::to_string)
+            .collect::<Vec<_>>()
+            .join(".");
+        f.write_str(&text)
+    }
+}
+
+/// Per-app resource limits for WASM procedure instances. `None` leaves the
+/// resource unbounded.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct AppLimits {
+    /// Wall-clock time one procedure invocation may take, in milliseconds.
+    /// Time the invocation spends blocked (e.g. on a host call) counts too.
+    pub max_wall_clock_millis: Option<u64>,
+    /// Fuel (roughly one unit per WASM instruction) one invocation may burn.
+    pub max_fuel: Option<u64>,
+    /// Linear memory one instance may grow to, in bytes.
+    pub max_memory_bytes: Option<u64>,
+    /// Elements one table of an instance may grow to.
+    pub max_table_elements: Option<u64>,
+}
+
+impl Default for AppLimits {
+    fn default() -> Self {
+        Self {
+            max_wall_clock_millis: Some(10_000),
+            max_fuel: None,
+            max_memory_bytes: Some(1 << 30),
+            max_table_elements: Some(100_000),
+        }
+    }
+}
+
+impl AppLimits {
+    /// No limit on any resource.
+    pub fn unbounded() -> Self {
+        Self {
+            max_wall_clock_millis: None,
+            max_fuel: None,
+            max_memory_bytes: None,
+            max_table_elements: None,
+        }
+    }
 }
@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod tests {
+    use crate::common::app_info::{AppInfo, AppLimits, AppVersion};
+
+    #[test]
+    fn package_cfg_without_limits_gets_default_limits() {
+        let info: AppInfo = serde_json::from_str(
+            r#"{"name":"app1","lang":"rust","version":"0.1.0","use_async":true}"#,
+        )
+        .unwrap();
+        assert_eq!(info.limits, AppLimits::default());
+    }
+
+    #[test]
+    fn partial_limits_keep_defaults_for_missing_fields() {
+        let info: AppInfo = serde_json::from_str(
+            r#"{"name":"app1","lang":"rust","version":"0.1.0","use_async":true,
+                "limits":{"max_wall_clock_millis":500,"max_fuel":1000000,"max_memory_bytes":null}}"#,
+        )
+        .unwrap();
+        assert_eq!(info.limits.max_wall_clock_millis, Some(500));
+        assert_eq!(info.limits.max_fuel, Some(1_000_000));
+        assert_eq!(info.limits.max_memory_bytes, None);
+        assert_eq!(
+            info.limits.max_table_elements,
+            AppLimits::default().max_table_elements
+        );
+    }
+
+    #[test]
+    fn app_versions_compare_numerically() {
+        let v = |s: &str| AppVersion::parse(s).unwrap();
+        assert!(v("0.10.0") > v("0.9.3"));
+        assert!(v("1.0.0") > v("0.99"));
+        assert_eq!(v("1.2"), v("1.2.0"));
+        assert_eq!(v("1.2.0-beta.1"), v("1.2.0"));
+        assert_eq!(v("2.0.1").to_string(), "2.0.1");
+    }
+
+    #[test]
+    fn invalid_app_version_is_rejected() {
+        assert!(AppVersion::parse("").is_err());
+        assert!(AppVersion::parse("1.x").is_err());
+        assert!(AppVersion::parse("v1.0").is_err());
+    }
+}
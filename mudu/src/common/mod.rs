@@ -38,6 +38,8 @@ pub mod result_of;
 pub mod slice;
 
 pub mod app_info;
+#[cfg(test)]
+mod app_info_test;
 pub mod cmp_equal;
 pub mod cmp_order;
 pub mod default_value;
@@ -0,0 +1,59 @@
+/// Formats a UUID as lowercase 8-4-4-4-12 hex groups.
+pub fn format_uuid(value: u128) -> String {
+    let hex = format!("{:032x}", value);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+/// Parses a UUID in the hyphenated 8-4-4-4-12 form or as 32 bare hex
+/// digits, in either case.
+pub fn parse_uuid(value: &str) -> Result<u128, String> {
+    let invalid = || format!("invalid uuid \"{}\"", value);
+    let hex: String = match value.len() {
+        32 => value.to_string(),
+        36 => {
+            let bytes = value.as_bytes();
+            if [8, 13, 18, 23].iter().any(|i| bytes[*i] != b'-') {
+                return Err(invalid());
+            }
+            value.chars().filter(|c| *c != '-').collect()
+        }
+        _ => return Err(invalid()),
+    };
+    if hex.len() != 32 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    u128::from_str_radix(&hex, 16).map_err(|_| invalid())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_uuid, parse_uuid};
+
+    #[test]
+    fn uuid_parses_hyphenated_and_bare_forms() {
+        let text = "123e4567-e89b-12d3-a456-426614174000";
+        let v = parse_uuid(text).unwrap();
+        assert_eq!(v, 0x123e4567_e89b_12d3_a456_426614174000);
+        assert_eq!(parse_uuid("123E4567E89B12D3A456426614174000").unwrap(), v);
+        assert_eq!(format_uuid(v), text);
+    }
+
+    #[test]
+    fn uuid_rejects_malformed_text() {
+        for bad in [
+            "",
+            "123e4567-e89b-12d3-a456-42661417400",
+            "123e4567_e89b_12d3_a456_426614174000",
+            "g23e4567-e89b-12d3-a456-426614174000",
+        ] {
+            assert!(parse_uuid(bad).is_err(), "{bad:?} should be rejected");
+        }
+    }
+}
@@ -208,6 +208,20 @@ pub enum ErrorCode {
     CorruptedData = 50038,
     #[strum(message = "Incompatible protocol version")]
     IncompatibleProtocolVersion = 50039,
+    /// A serializable transaction was aborted to avoid a non-serializable
+    /// schedule; the client should retry it.
+    #[strum(message = "Serialization failure")]
+    SerializationFailure = 50040,
+    /// A WASM procedure invocation exceeded one of its app's resource
+    /// limits (wall-clock time, fuel, memory or table size).
+    #[strum(message = "Procedure resource limit exceeded")]
+    ProcedureLimitExceeded = 50041,
+    /// A write was attempted on a read-only standby server.
+    #[strum(message = "Read-only transaction")]
+    ReadOnlyTransaction = 50042,
+    /// A write violated a UNIQUE or FOREIGN KEY constraint.
+    #[strum(message = "Constraint violation")]
+    ConstraintViolation = 50043,
 }
 
 impl Display for ErrorCode {
@@ -270,7 +284,10 @@ impl ErrorCode {
             | ErrorCode::IndexOutOfRange
             | ErrorCode::UnsupportedOperation
             | ErrorCode::EntityNotFound
-            | ErrorCode::EntityAlreadyExists => Severity::User,
+            | ErrorCode::EntityAlreadyExists
+            | ErrorCode::ProcedureLimitExceeded
+            | ErrorCode::ReadOnlyTransaction
+            | ErrorCode::ConstraintViolation => Severity::User,
 
             ErrorCode::TimedOut
             | ErrorCode::WouldBlock
@@ -292,7 +309,8 @@ impl ErrorCode {
             | ErrorCode::ResourceBusy
             | ErrorCode::Deadlock
             | ErrorCode::Mutex
-            | ErrorCode::Synchronization => Severity::Transient,
+            | ErrorCode::Synchronization
+            | ErrorCode::SerializationFailure => Severity::Transient,
 
             _ => Severity::Internal,
         }
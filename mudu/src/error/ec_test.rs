@@ -79,8 +79,15 @@ mod tests {
     fn severity_classification_is_consistent() {
         assert_eq!(ErrorCode::InvalidArgument.severity(), Severity::User);
         assert_eq!(ErrorCode::EntityNotFound.severity(), Severity::User);
+        assert_eq!(ErrorCode::ProcedureLimitExceeded.severity(), Severity::User);
+        assert_eq!(ErrorCode::ReadOnlyTransaction.severity(), Severity::User);
+        assert_eq!(ErrorCode::ConstraintViolation.severity(), Severity::User);
         assert_eq!(ErrorCode::TimedOut.severity(), Severity::Transient);
         assert_eq!(ErrorCode::NetworkDown.severity(), Severity::Transient);
+        assert_eq!(
+            ErrorCode::SerializationFailure.severity(),
+            Severity::Transient
+        );
         assert_eq!(ErrorCode::Internal.severity(), Severity::Internal);
         assert_eq!(ErrorCode::Decode.severity(), Severity::Internal);
     }
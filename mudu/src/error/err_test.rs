@@ -79,7 +79,13 @@ mod tests {
             assert_eq!(ErrorCode::from_u32(ec.to_u32()), Some(ec));
         }
         assert_eq!(ErrorCode::from_u32(0), None);
-        assert_eq!(ErrorCode::from_u32(50040), None);
+        let next = all_error_codes()
+            .iter()
+            .map(|ec| ec.to_u32())
+            .max()
+            .unwrap()
+            + 1;
+        assert_eq!(ErrorCode::from_u32(next), None);
     }
 
     #[test]
@@ -59,6 +59,9 @@ pub enum ConnectionConfig {
         app_name: String,
         /// Whether to run the Mudud session loop asynchronously.
         async_session_loop: bool,
+        /// User name and password for servers with authentication enabled,
+        /// taken from the `user:password@` part of the URL.
+        credential: Option<(String, String)>,
     },
 }
 
@@ -170,6 +173,14 @@ pub fn mudud_async_session_loop() -> bool {
     }
 }
 
+/// Returns the Mudud user name and password if configured.
+pub fn mudud_credential() -> Option<(String, String)> {
+    match connection() {
+        ConnectionConfig::Mudud { credential, .. } => credential,
+        _ => None,
+    }
+}
+
 /// Returns the parsed connection configuration.
 pub fn connection() -> ConnectionConfig {
     #[expect(
@@ -232,6 +243,14 @@ fn parse_mudud_connection(raw: &str) -> ConnectionConfig {
         .split_once('?')
         .map(|(path, query)| (path.trim(), Some(query.trim())))
         .unwrap_or((without_scheme.trim(), None));
+    let (userinfo, path_part) = path_part
+        .split_once('@')
+        .map(|(userinfo, path)| (Some(userinfo), path))
+        .unwrap_or((None, path_part));
+    let credential = userinfo.map(|userinfo| {
+        let (user, password) = userinfo.split_once(':').unwrap_or((userinfo, ""));
+        (user.to_string(), password.to_string())
+    });
     let (addr, app_name) = path_part
         .split_once('/')
         .map(|(addr, app_name)| (addr.trim(), app_name.trim()))
@@ -253,6 +272,7 @@ fn parse_mudud_connection(raw: &str) -> ConnectionConfig {
         http_addr,
         app_name,
         async_session_loop,
+        credential,
     }
 }
 
@@ -1,7 +1,7 @@
 //! Remote Mudud protocol backend implementation.
 
 use crate::config;
-use crate::result_set::LocalResultSet;
+use crate::result_set::{CursorSource, LocalResultSet, PagedResultSet};
 use crate::sql::to_data_values;
 use crate::state;
 use lazy_static::lazy_static;
@@ -21,14 +21,14 @@ use mudu_contract::database::entity_set::RecordSet;
 use mudu_contract::database::sql_params::SQLParams;
 use mudu_contract::database::sql_stmt::SQLStmt;
 use mudu_contract::protocol::{
-    ClientRequest, GetRequest, PutRequest, RangeScanRequest, SessionCloseRequest,
-    SessionCreateRequest,
+    ClientRequest, CloseCursorRequest, FetchRequest, GetRequest, KeyValue, PutRequest,
+    RangeScanRequest, SessionCloseRequest, SessionCreateRequest,
 };
 use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
 use mudu_contract::tuple::tuple_value::TupleValue;
-use mudu_sys::sync::SMutex;
 use mudu_sys::sync::async_::mutex::AMutex;
 use mudu_sys::sync::async_::rwlock::ARwLock;
+use mudu_sys::sync::{SMutex, SMutexGuard};
 use mudu_sys::task::sync::spawn_thread_named;
 use mudu_type::data_value::DataValue;
 use mudu_utils::task_async::build_current_thread_runtime;
@@ -38,6 +38,9 @@ use std::sync::Arc;
 use std::sync::OnceLock;
 use std::sync::mpsc::{self, Receiver, Sender, SyncSender};
 
+/// Rows a query pulls per round trip, and keys a range scan reads per page.
+const PAGE_SIZE: u32 = 1000;
+
 struct MududSession {
     client: SyncClient,
     remote_session_id: u128,
@@ -62,6 +65,7 @@ type RangeResult = Vec<(Vec<u8>, Vec<u8>)>;
 struct QueryRows {
     row_desc: TupleFieldDesc,
     rows: Vec<TupleValue>,
+    cursor_id: Option<u64>,
 }
 
 enum AsyncCommand {
@@ -98,6 +102,16 @@ enum AsyncCommand {
         params: Vec<DataValue>,
         response: SyncSender<RS<QueryRows>>,
     },
+    Fetch {
+        session_id: OID,
+        cursor_id: u64,
+        response: SyncSender<RS<(Vec<TupleValue>, Option<u64>)>>,
+    },
+    CloseCursor {
+        session_id: OID,
+        cursor_id: u64,
+        response: SyncSender<RS<()>>,
+    },
     Command {
         session_id: OID,
         app_name: String,
@@ -232,6 +246,9 @@ pub fn mudu_open(argv: &UniSessionOpenArgv) -> RS<OID> {
         .parse()
         .map_err(|e| mudu_error!(ErrorCode::Database, "invalid mudud tcp address", e))?;
     let mut client = SyncClient::connect(addr)?;
+    if let Some((user, password)) = config::mudud_credential() {
+        client.login(&user, &password)?;
+    }
     let remote_session_id = client.create_session(session_open_config_json(argv.worker_oid()))?;
     let session_id = state::next_session_id();
     let session = Arc::new(SMutex::new(MududSession {
@@ -247,6 +264,9 @@ pub async fn mudu_open_async(argv: &UniSessionOpenArgv) -> RS<OID> {
     let _trace = mudu_utils::task_trace!();
     let addr = resolve_worker_addr_async(argv.worker_oid()).await?;
     let mut client = AsyncClientImpl::connect(addr.as_str()).await?;
+    if let Some((user, password)) = config::mudud_credential() {
+        client.login(&user, &password).await?;
+    }
     let remote_session_id = client
         .create_session(SessionCreateRequest::new(session_open_config_json(
             argv.worker_oid(),
@@ -380,15 +400,25 @@ pub fn mudu_range(
     }
 
     with_session(session_id, |session| {
-        let items = session.client.range_scan(
-            session.remote_session_id,
-            start_key.to_vec(),
-            end_key.to_vec(),
-        )?;
-        Ok(items
-            .into_iter()
-            .map(|kv| (kv.key().to_vec(), kv.value().to_vec()))
-            .collect())
+        let mut items = Vec::new();
+        let mut continuation = None;
+        loop {
+            let (page, next) = session
+                .client
+                .range_scan_page(
+                    session.remote_session_id,
+                    start_key,
+                    end_key,
+                    PAGE_SIZE,
+                    continuation,
+                )?
+                .into_parts();
+            items.extend(page.into_iter().map(KeyValue::into_parts));
+            match next {
+                Some(next) => continuation = Some(next),
+                None => return Ok(items),
+            }
+        }
     })
 }
 
@@ -402,19 +432,34 @@ pub async fn mudu_range_async(
     let session = async_session(session_id).await?;
     let mut session = session.lock().await;
     let remote_session_id = session.remote_session_id;
-    let items = session
-        .client
-        .range_scan(RangeScanRequest::new(
-            remote_session_id,
-            start_key.to_vec(),
-            end_key.to_vec(),
-        ))
-        .await?;
-    Ok(items
-        .into_items()
-        .into_iter()
-        .map(|kv| (kv.key().to_vec(), kv.value().to_vec()))
-        .collect())
+    range_pages(
+        &mut session.client,
+        remote_session_id,
+        start_key.to_vec(),
+        end_key.to_vec(),
+    )
+    .await
+}
+
+/// Scan `[start_key, end_key)` page by page, following each continuation.
+async fn range_pages(
+    client: &mut AsyncClientImpl,
+    remote_session_id: u128,
+    start_key: Vec<u8>,
+    end_key: Vec<u8>,
+) -> RS<RangeResult> {
+    let mut items = Vec::new();
+    let mut continuation = None;
+    loop {
+        let request = RangeScanRequest::new(remote_session_id, start_key.clone(), end_key.clone())
+            .with_page(PAGE_SIZE, continuation);
+        let (page, next) = client.range_scan(request).await?.into_parts();
+        items.extend(page.into_iter().map(KeyValue::into_parts));
+        match next {
+            Some(next) => continuation = Some(next),
+            None => return Ok(items),
+        }
+    }
 }
 
 /// Executes a query on a remote Mudud session and returns the resulting record set.
@@ -435,20 +480,24 @@ pub fn mudu_query<R: Entity>(
         return async_query(session_id, app_name, sql_text, params);
     }
 
-    with_session(session_id, |session| {
-        let response = session.client.query_with_oid_params(
-            session.remote_session_id,
-            app_name.clone(),
-            sql_text.clone(),
-            params,
-        )?;
-        let desc = response.row_desc().clone();
-        let rows = response.rows().to_vec();
-        Ok(RecordSet::new(
-            Arc::new(LocalResultSet::new(rows)),
-            Arc::new(desc),
-        ))
-    })
+    let session_ref = session_ref(session_id)?;
+    let (desc, rows, cursor_id, remote_session_id) = {
+        let mut session = lock_session(&session_ref)?;
+        let remote_session_id = session.remote_session_id;
+        let (desc, rows, cursor_id) = session
+            .client
+            .query_paged(remote_session_id, app_name, sql_text, params, PAGE_SIZE)?
+            .into_parts();
+        (desc, rows, cursor_id, remote_session_id)
+    };
+    let source = SessionCursor {
+        session: session_ref,
+        remote_session_id,
+    };
+    Ok(RecordSet::new(
+        Arc::new(PagedResultSet::new(source, rows, cursor_id)),
+        Arc::new(desc),
+    ))
 }
 
 /// Asynchronous version of [`mudu_query`].
@@ -464,15 +513,26 @@ pub async fn mudu_query_async<R: Entity>(
     let session = async_session(session_id).await?;
     let mut session = session.lock().await;
     let remote_session_id = session.remote_session_id;
-    let response = session
+    // `ResultSet::next` cannot await the session lock, so the pages are read
+    // here, one bounded frame at a time, rather than on demand.
+    let (desc, mut rows, mut cursor_id) = session
         .client
-        .query(
+        .query_paged(
             ClientRequest::new_with_oid(remote_session_id, &app_name, &sql_text)
-                .with_params(params),
+                .with_params(params)
+                .with_fetch_size(PAGE_SIZE),
         )
-        .await?;
-    let desc = response.row_desc().clone();
-    let rows = response.rows().to_vec();
+        .await?
+        .into_parts();
+    while let Some(id) = cursor_id {
+        let (_, page, next) = session
+            .client
+            .fetch(FetchRequest::new(remote_session_id, id, 0))
+            .await?
+            .into_parts();
+        rows.extend(page);
+        cursor_id = next;
+    }
     Ok(RecordSet::new(
         Arc::new(LocalResultSet::new(rows)),
         Arc::new(desc),
@@ -709,10 +769,16 @@ fn with_session<R, F>(session_id: OID, f: F) -> RS<R>
 where
     F: FnOnce(&mut MududSession) -> RS<R>,
 {
+    let session_ref = session_ref(session_id)?;
+    let mut session = lock_session(&session_ref)?;
+    f(&mut session)
+}
+
+fn session_ref(session_id: OID) -> RS<SessionRef> {
     // Clone the session reference out of the map and drop the scc entry
     // immediately. An scc entry holds its bucket lock, so keeping it alive
-    // across `f` (a blocking network call) serializes every session hashing
-    // to the same bucket and can deadlock: one thread blocks in `f` waiting
+    // across the caller's blocking network call serializes every session
+    // hashing to the same bucket and can deadlock: one thread blocks waiting
     // on a server-side lock held by another session, whose thread in turn
     // blocks acquiring the same bucket lock.
     let session_ref = {
@@ -724,10 +790,79 @@ where
         })?;
         entry.get().clone()
     };
-    let mut session = session_ref
+    Ok(session_ref)
+}
+
+fn lock_session(session_ref: &SessionRef) -> RS<SMutexGuard<'_, MududSession>> {
+    session_ref
         .lock()
-        .map_err(|_| mudu_error!(ErrorCode::Internal, "mudud session lock poisoned"))?;
-    f(&mut session)
+        .map_err(|_| mudu_error!(ErrorCode::Internal, "mudud session lock poisoned"))
+}
+
+/// Pages of a query cursor read through a blocking session.
+struct SessionCursor {
+    session: SessionRef,
+    remote_session_id: u128,
+}
+
+impl CursorSource for SessionCursor {
+    fn fetch(&self, cursor_id: u64) -> RS<(Vec<TupleValue>, Option<u64>)> {
+        let mut session = lock_session(&self.session)?;
+        let (_, rows, next) = session
+            .client
+            .fetch(self.remote_session_id, cursor_id, 0)?
+            .into_parts();
+        Ok((rows, next))
+    }
+
+    fn close(&self, cursor_id: u64) {
+        // A session busy with another request is left to drop the cursor
+        // itself when it closes.
+        if let Some(mut session) = self.session.try_lock() {
+            let _ = session
+                .client
+                .close_cursor(self.remote_session_id, cursor_id);
+        }
+    }
+}
+
+/// Pages of a query cursor read through the async session loop.
+struct ManagerCursor {
+    session_id: OID,
+}
+
+impl CursorSource for ManagerCursor {
+    fn fetch(&self, cursor_id: u64) -> RS<(Vec<TupleValue>, Option<u64>)> {
+        let (tx, rx) = mpsc::sync_channel(1);
+        async_manager()?
+            .sender
+            .send(AsyncCommand::Fetch {
+                session_id: self.session_id,
+                cursor_id,
+                response: tx,
+            })
+            .map_err(|e| {
+                mudu_error!(
+                    ErrorCode::ChannelClosed,
+                    "send mudud async fetch command error",
+                    e
+                )
+            })?;
+        recv_response(rx)
+    }
+
+    fn close(&self, cursor_id: u64) {
+        // The response is not awaited; the loop closes the cursor in order
+        // with the session's other requests.
+        let (tx, _rx) = mpsc::sync_channel(1);
+        if let Ok(manager) = async_manager() {
+            let _ = manager.sender.send(AsyncCommand::CloseCursor {
+                session_id: self.session_id,
+                cursor_id,
+                response: tx,
+            });
+        }
+    }
 }
 
 async fn async_session(session_id: OID) -> RS<Arc<AMutex<AsyncMududSession>>> {
@@ -866,11 +1001,14 @@ fn async_query<R: Entity>(
             )
         })?;
     let response = recv_response(rx)?;
-    let desc = response.row_desc;
-    let rows = response.rows;
+    let source = ManagerCursor { session_id };
     Ok(RecordSet::new(
-        Arc::new(LocalResultSet::new(rows)),
-        Arc::new(desc),
+        Arc::new(PagedResultSet::new(
+            source,
+            response.rows,
+            response.cursor_id,
+        )),
+        Arc::new(response.row_desc),
     ))
 }
 
@@ -982,6 +1120,9 @@ async fn handle_async_command(
                 let addr = config::mudud_addr()
                     .ok_or_else(|| mudu_error!(ErrorCode::Database, "missing mudud tcp address"))?;
                 let mut client = AsyncClientImpl::connect(addr.as_str()).await?;
+                if let Some((user, password)) = config::mudud_credential() {
+                    client.login(&user, &password).await?;
+                }
                 let remote_session_id = client
                     .create_session(SessionCreateRequest::new(session_open_config_json(
                         worker_id,
@@ -1083,18 +1224,13 @@ async fn handle_async_command(
                         format!("session {} does not exist", session_id)
                     )
                 })?;
-                Ok(session
-                    .client
-                    .range_scan(RangeScanRequest::new(
-                        session.remote_session_id,
-                        start_key,
-                        end_key,
-                    ))
-                    .await?
-                    .into_items()
-                    .into_iter()
-                    .map(|kv| (kv.key().to_vec(), kv.value().to_vec()))
-                    .collect())
+                range_pages(
+                    &mut session.client,
+                    session.remote_session_id,
+                    start_key,
+                    end_key,
+                )
+                .await
             }
             .await;
             let _ = response.send(result);
@@ -1113,21 +1249,70 @@ async fn handle_async_command(
                         format!("session {} does not exist", session_id)
                     )
                 })?;
-                let response_data = session
+                let (row_desc, rows, cursor_id) = session
                     .client
-                    .query(
+                    .query_paged(
                         ClientRequest::new_with_oid(session.remote_session_id, app_name, sql_text)
-                            .with_params(params),
+                            .with_params(params)
+                            .with_fetch_size(PAGE_SIZE),
                     )
-                    .await?;
+                    .await?
+                    .into_parts();
                 Ok(QueryRows {
-                    row_desc: response_data.row_desc().clone(),
-                    rows: response_data.rows().to_vec(),
+                    row_desc,
+                    rows,
+                    cursor_id,
                 })
             }
             .await;
             let _ = response.send(result);
         }
+        AsyncCommand::Fetch {
+            session_id,
+            cursor_id,
+            response,
+        } => {
+            let result = async {
+                let session = sessions.get_mut(&session_id).ok_or_else(|| {
+                    mudu_error!(
+                        ErrorCode::EntityNotFound,
+                        format!("session {} does not exist", session_id)
+                    )
+                })?;
+                let (_, rows, next) = session
+                    .client
+                    .fetch(FetchRequest::new(session.remote_session_id, cursor_id, 0))
+                    .await?
+                    .into_parts();
+                Ok((rows, next))
+            }
+            .await;
+            let _ = response.send(result);
+        }
+        AsyncCommand::CloseCursor {
+            session_id,
+            cursor_id,
+            response,
+        } => {
+            let result = async {
+                let session = sessions.get_mut(&session_id).ok_or_else(|| {
+                    mudu_error!(
+                        ErrorCode::EntityNotFound,
+                        format!("session {} does not exist", session_id)
+                    )
+                })?;
+                session
+                    .client
+                    .close_cursor(CloseCursorRequest::new(
+                        session.remote_session_id,
+                        cursor_id,
+                    ))
+                    .await?;
+                Ok(())
+            }
+            .await;
+            let _ = response.send(result);
+        }
         AsyncCommand::Command {
             session_id,
             app_name,
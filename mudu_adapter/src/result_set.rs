@@ -1,4 +1,5 @@
-//! In-memory result set used to materialize query rows from any backend.
+//! Result sets handed out by the backends: [`LocalResultSet`] over rows
+//! already in memory, and [`PagedResultSet`] over a server-side cursor.
 
 use mudu::common::result::RS;
 use mudu::error::ErrorCode;
@@ -6,6 +7,7 @@ use mudu::mudu_error;
 use mudu_contract::database::result_set::ResultSet;
 use mudu_contract::tuple::tuple_value::TupleValue;
 use mudu_sys::sync::SMutex;
+use std::collections::VecDeque;
 
 /// A result set that stores all rows locally in a [`Vec`].
 pub struct LocalResultSet {
@@ -37,3 +39,131 @@ impl ResultSet for LocalResultSet {
         Ok(Some(row))
     }
 }
+
+/// Where a [`PagedResultSet`] pulls its pages from.
+pub trait CursorSource: Send + Sync {
+    /// Read the next page of `cursor_id`, returning its rows and the cursor
+    /// to keep reading, or `None` once the result is drained.
+    fn fetch(&self, cursor_id: u64) -> RS<(Vec<TupleValue>, Option<u64>)>;
+
+    /// Release `cursor_id` before it is drained. Best effort: called from
+    /// `Drop`, so it must not block on the session.
+    fn close(&self, cursor_id: u64);
+}
+
+struct Page {
+    rows: VecDeque<TupleValue>,
+    cursor_id: Option<u64>,
+}
+
+/// A result set that holds one page of rows and fetches the next from a
+/// server-side cursor once the page is consumed. A cursor still open when
+/// the result set is dropped is closed.
+pub struct PagedResultSet<S: CursorSource> {
+    source: S,
+    page: SMutex<Page>,
+}
+
+impl<S: CursorSource> PagedResultSet<S> {
+    /// Creates a result set from the first page of a query and the cursor
+    /// holding the rest, if any.
+    pub fn new(source: S, rows: Vec<TupleValue>, cursor_id: Option<u64>) -> Self {
+        Self {
+            source,
+            page: SMutex::new(Page {
+                rows: rows.into(),
+                cursor_id,
+            }),
+        }
+    }
+}
+
+impl<S: CursorSource> ResultSet for PagedResultSet<S> {
+    fn next(&self) -> RS<Option<TupleValue>> {
+        let mut page = self
+            .page
+            .lock()
+            .map_err(|_| mudu_error!(ErrorCode::Internal, "result set page lock poisoned"))?;
+        loop {
+            if let Some(row) = page.rows.pop_front() {
+                return Ok(Some(row));
+            }
+            let Some(cursor_id) = page.cursor_id.take() else {
+                return Ok(None);
+            };
+            let (rows, next) = self.source.fetch(cursor_id)?;
+            page.rows = rows.into();
+            page.cursor_id = next;
+        }
+    }
+}
+
+impl<S: CursorSource> Drop for PagedResultSet<S> {
+    fn drop(&mut self) {
+        let cursor_id = self
+            .page
+            .try_lock()
+            .and_then(|mut page| page.cursor_id.take());
+        if let Some(cursor_id) = cursor_id {
+            self.source.close(cursor_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use mudu_type::data_value::DataValue;
+    use std::sync::Arc;
+
+    /// Serves cursor 1 as pages `[2]`, `[3]` and records closed cursors.
+    struct TwoPages {
+        closed: Arc<SMutex<Vec<u64>>>,
+    }
+
+    impl CursorSource for TwoPages {
+        fn fetch(&self, cursor_id: u64) -> RS<(Vec<TupleValue>, Option<u64>)> {
+            let row = TupleValue::from(vec![DataValue::from_i32(cursor_id as i32 + 1)]);
+            Ok((vec![row], (cursor_id == 1).then_some(2)))
+        }
+
+        fn close(&self, cursor_id: u64) {
+            if let Ok(mut closed) = self.closed.lock() {
+                closed.push(cursor_id);
+            }
+        }
+    }
+
+    fn result_set(closed: &Arc<SMutex<Vec<u64>>>) -> PagedResultSet<TwoPages> {
+        let first = TupleValue::from(vec![DataValue::from_i32(1)]);
+        let source = TwoPages {
+            closed: closed.clone(),
+        };
+        PagedResultSet::new(source, vec![first], Some(1))
+    }
+
+    #[test]
+    fn paged_result_set_fetches_until_drained() {
+        let closed = Arc::new(SMutex::new(Vec::new()));
+        let result_set = result_set(&closed);
+        let mut ids = Vec::new();
+        while let Some(row) = result_set.next().unwrap() {
+            ids.push(*row.values()[0].expect_i32());
+        }
+        assert_eq!(ids, vec![1, 2, 3]);
+        drop(result_set);
+        assert!(closed.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn paged_result_set_closes_an_open_cursor_on_drop() {
+        let closed = Arc::new(SMutex::new(Vec::new()));
+        let result_set = result_set(&closed);
+        assert!(result_set.next().unwrap().is_some());
+        assert!(result_set.next().unwrap().is_some());
+        drop(result_set);
+        assert_eq!(*closed.lock().unwrap(), vec![2]);
+    }
+}
@@ -167,14 +167,16 @@ impl MockSqliteMuduSysCall {
             UniScalarValue::F64(v) => Ok(Value::Real(v)),
             UniScalarValue::Char(v) => Ok(Value::Text(v.to_string())),
             UniScalarValue::String(v) => Ok(Value::Text(v)),
-            UniScalarValue::U128(v) | UniScalarValue::I128(v) | UniScalarValue::Blob(v) => {
-                Ok(Value::Blob(v))
-            }
+            UniScalarValue::U128(v)
+            | UniScalarValue::I128(v)
+            | UniScalarValue::Blob(v)
+            | UniScalarValue::Uuid(v) => Ok(Value::Blob(v)),
             UniScalarValue::Numeric(v)
             | UniScalarValue::Date(v)
             | UniScalarValue::Time(v)
             | UniScalarValue::Timestamp(v)
-            | UniScalarValue::TimestampTz(v) => Ok(Value::Text(v)),
+            | UniScalarValue::TimestampTz(v)
+            | UniScalarValue::Json(v) => Ok(Value::Text(v)),
         }
     }
 
@@ -258,6 +260,8 @@ impl MockSqliteMuduSysCall {
             UniDataValue::Scalar(UniScalarValue::TimestampTz(_)) => {
                 UniDataType::Scalar(UniScalar::TimestampTz)
             }
+            UniDataValue::Scalar(UniScalarValue::Uuid(_)) => UniDataType::Scalar(UniScalar::Uuid),
+            UniDataValue::Scalar(UniScalarValue::Json(_)) => UniDataType::Scalar(UniScalar::Json),
             UniDataValue::Binary(_) => UniDataType::Scalar(UniScalar::Blob),
             UniDataValue::Array(_) | UniDataValue::Record(_) => {
                 UniDataType::Scalar(UniScalar::String)
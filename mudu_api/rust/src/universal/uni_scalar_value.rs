@@ -42,6 +42,10 @@ pub enum UniScalarValue {
     Timestamp(String),
 
     TimestampTz(String),
+
+    Uuid(Vec<u8>),
+
+    Json(String),
 }
 
 impl Default for UniScalarValue {
@@ -424,6 +428,42 @@ impl UniScalarValue {
             _ => unsafe { std::hint::unreachable_unchecked() },
         }
     }
+
+    pub fn from_uuid(inner: Vec<u8>) -> Self {
+        Self::Uuid(inner)
+    }
+
+    pub fn as_uuid(&self) -> Option<&Vec<u8>> {
+        match self {
+            Self::Uuid(inner) => Some(inner),
+            _ => None,
+        }
+    }
+
+    pub fn expect_uuid(&self) -> &Vec<u8> {
+        match self {
+            Self::Uuid(inner) => inner,
+            _ => unsafe { std::hint::unreachable_unchecked() },
+        }
+    }
+
+    pub fn from_json(inner: String) -> Self {
+        Self::Json(inner)
+    }
+
+    pub fn as_json(&self) -> Option<&String> {
+        match self {
+            Self::Json(inner) => Some(inner),
+            _ => None,
+        }
+    }
+
+    pub fn expect_json(&self) -> &String {
+        match self {
+            Self::Json(inner) => inner,
+            _ => unsafe { std::hint::unreachable_unchecked() },
+        }
+    }
 }
 
 impl serde::Serialize for UniScalarValue {
@@ -538,6 +578,16 @@ impl serde::Serialize for UniScalarValue {
                 serialize_seq.serialize_element(&20u32)?;
                 serialize_seq.serialize_element(&inner)?;
             }
+
+            UniScalarValue::Uuid(inner) => {
+                serialize_seq.serialize_element(&21u32)?;
+                serialize_seq.serialize_element(&inner)?;
+            }
+
+            UniScalarValue::Json(inner) => {
+                serialize_seq.serialize_element(&22u32)?;
+                serialize_seq.serialize_element(&inner)?;
+            }
         }
         serialize_seq.end()
     }
@@ -714,6 +764,20 @@ impl<'de> serde::de::Visitor<'de> for UniScalarValueVisitor {
                 Ok(Self::Value::TimestampTz(value))
             }
 
+            21 => {
+                let value = seq
+                    .next_element::<Vec<u8>>()?
+                    .map_or_else(|| Err(A::Error::invalid_length(1, &self)), Ok)?;
+                Ok(Self::Value::Uuid(value))
+            }
+
+            22 => {
+                let value = seq
+                    .next_element::<String>()?
+                    .map_or_else(|| Err(A::Error::invalid_length(1, &self)), Ok)?;
+                Ok(Self::Value::Json(value))
+            }
+
             _ => Err(Error::invalid_value(Unexpected::Map, &self)),
         }
     }
@@ -8,6 +8,7 @@ use mudu::data_type::timestamp::TimestampValue;
 use mudu::data_type::timestamptz::TimestampTzValue;
 use mudu::error::ErrorCode;
 use mudu::mudu_error;
+use mudu::utils::json::{JsonValue, from_json_str};
 use mudu_type::data_value::DataValue;
 use mudu_type::datum::DatumDyn;
 use mudu_type::type_family::TypeFamily;
@@ -16,36 +17,21 @@ impl UniDataValue {
     pub fn uni_to(self) -> RS<DataValue> {
         let value = match self {
             UniDataValue::Scalar(value) => match value {
-                UniScalarValue::Bool(_) => {
-                    return Err(mudu_error!(
-                        ErrorCode::InvalidType,
-                        "scalar bool is not supported"
-                    ));
-                }
+                UniScalarValue::Bool(v) => DataValue::from_bool(v),
                 UniScalarValue::U8(_) => {
                     return Err(mudu_error!(
                         ErrorCode::InvalidType,
                         "scalar u8 is not supported"
                     ));
                 }
-                UniScalarValue::I8(_) => {
-                    return Err(mudu_error!(
-                        ErrorCode::InvalidType,
-                        "scalar i8 is not supported"
-                    ));
-                }
+                UniScalarValue::I8(v) => DataValue::from_i8(v as i8),
                 UniScalarValue::U16(_) => {
                     return Err(mudu_error!(
                         ErrorCode::InvalidType,
                         "scalar u16 is not supported"
                     ));
                 }
-                UniScalarValue::I16(_) => {
-                    return Err(mudu_error!(
-                        ErrorCode::InvalidType,
-                        "scalar i16 is not supported"
-                    ));
-                }
+                UniScalarValue::I16(v) => DataValue::from_i16(v),
                 UniScalarValue::U32(_) => {
                     return Err(mudu_error!(
                         ErrorCode::InvalidType,
@@ -128,6 +114,24 @@ impl UniDataValue {
                         )
                     })?,
                 ),
+                UniScalarValue::Uuid(v) => {
+                    let bytes: [u8; 16] = v.as_slice().try_into().map_err(|_| {
+                        mudu_error!(
+                            ErrorCode::TypeConversionFailed,
+                            "uuid payload must be 16 bytes"
+                        )
+                    })?;
+                    DataValue::from_uuid(u128::from_be_bytes(bytes))
+                }
+                UniScalarValue::Json(v) => {
+                    let json = from_json_str::<JsonValue>(v.as_str()).map_err(|e| {
+                        mudu_error!(
+                            ErrorCode::TypeConversionFailed,
+                            format!("invalid json {}", e)
+                        )
+                    })?;
+                    DataValue::from_json(json.to_string())
+                }
             },
             UniDataValue::Array(inner) => {
                 let mut vec = Vec::with_capacity(inner.len());
@@ -153,6 +157,15 @@ impl UniDataValue {
     pub fn uni_from(data_value: DataValue) -> RS<UniDataValue> {
         let id = data_value.type_family()?;
         let mu_v = match id {
+            TypeFamily::Bool => {
+                UniDataValue::from_scalar(UniScalarValue::Bool(*data_value.expect_bool()))
+            }
+            TypeFamily::I8 => {
+                UniDataValue::from_scalar(UniScalarValue::I8(*data_value.expect_i8() as u8))
+            }
+            TypeFamily::I16 => {
+                UniDataValue::from_scalar(UniScalarValue::I16(*data_value.expect_i16()))
+            }
             TypeFamily::I32 => {
                 UniDataValue::from_scalar(UniScalarValue::I32(*data_value.expect_i32()))
             }
@@ -195,6 +208,12 @@ impl UniDataValue {
                     .format(6)
                     .map_err(|e| mudu_error!(ErrorCode::TypeConversionFailed, e))?,
             )),
+            TypeFamily::Uuid => UniDataValue::from_scalar(UniScalarValue::Uuid(
+                data_value.expect_uuid().to_be_bytes().to_vec(),
+            )),
+            TypeFamily::Json => {
+                UniDataValue::from_scalar(UniScalarValue::Json(data_value.into_json()))
+            }
             TypeFamily::Array => {
                 let array = data_value.into_array();
                 let mut vec = Vec::with_capacity(array.len());
@@ -271,6 +290,15 @@ mod tests {
 
     #[test]
     fn supported_scalar_uni_to_from_roundtrip() {
+        assert_scalar_uni_to_from_roundtrip(UniDataValue::Scalar(UniScalarValue::from_bool(true)));
+        assert_scalar_uni_to_from_roundtrip(UniDataValue::Scalar(UniScalarValue::from_i8(0xfe)));
+        assert_scalar_uni_to_from_roundtrip(UniDataValue::Scalar(UniScalarValue::from_i16(-16)));
+        assert_scalar_uni_to_from_roundtrip(UniDataValue::Scalar(UniScalarValue::from_uuid(
+            (7u128 << 64 | 9).to_be_bytes().to_vec(),
+        )));
+        assert_scalar_uni_to_from_roundtrip(UniDataValue::Scalar(UniScalarValue::from_json(
+            "{\"a\":[1,null]}".to_string(),
+        )));
         assert_scalar_uni_to_from_roundtrip(UniDataValue::Scalar(UniScalarValue::from_i32(-42)));
         assert_scalar_uni_to_from_roundtrip(UniDataValue::Scalar(UniScalarValue::from_i64(-64)));
         assert_scalar_uni_to_from_roundtrip(UniDataValue::Scalar(UniScalarValue::from_i128(-128)));
@@ -288,11 +316,8 @@ mod tests {
     #[test]
     fn unsupported_scalar_uni_to_returns_invalid_type() {
         let unsupported = vec![
-            UniDataValue::Scalar(UniScalarValue::from_bool(true)),
             UniDataValue::Scalar(UniScalarValue::from_u8(1)),
-            UniDataValue::Scalar(UniScalarValue::from_i8(1)),
             UniDataValue::Scalar(UniScalarValue::from_u16(1)),
-            UniDataValue::Scalar(UniScalarValue::from_i16(-1)),
             UniDataValue::Scalar(UniScalarValue::from_u32(1)),
             UniDataValue::Scalar(UniScalarValue::from_u64(1)),
             UniDataValue::Scalar(UniScalarValue::from_char('x')),
@@ -53,4 +53,8 @@ pub enum UniScalar {
     Timestamp = 19,
 
     TimestampTz = 20,
+
+    Uuid = 21,
+
+    Json = 22,
 }
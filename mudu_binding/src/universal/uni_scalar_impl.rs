@@ -10,36 +10,21 @@ use mudu_type::type_family::TypeFamily;
 impl UniScalar {
     pub fn uni_to(self) -> RS<DataType> {
         let ty = match self {
-            UniScalar::Bool => {
-                return Err(mudu_error!(
-                    ErrorCode::InvalidType,
-                    "scalar bool is not supported"
-                ));
-            }
+            UniScalar::Bool => DataType::default_for(TypeFamily::Bool),
             UniScalar::U8 => {
                 return Err(mudu_error!(
                     ErrorCode::InvalidType,
                     "scalar u8 is not supported"
                 ));
             }
-            UniScalar::I8 => {
-                return Err(mudu_error!(
-                    ErrorCode::InvalidType,
-                    "scalar i8 is not supported"
-                ));
-            }
+            UniScalar::I8 => DataType::default_for(TypeFamily::I8),
             UniScalar::U16 => {
                 return Err(mudu_error!(
                     ErrorCode::InvalidType,
                     "scalar u16 is not supported"
                 ));
             }
-            UniScalar::I16 => {
-                return Err(mudu_error!(
-                    ErrorCode::InvalidType,
-                    "scalar i16 is not supported"
-                ));
-            }
+            UniScalar::I16 => DataType::default_for(TypeFamily::I16),
             UniScalar::U32 => {
                 return Err(mudu_error!(
                     ErrorCode::InvalidType,
@@ -71,12 +56,17 @@ impl UniScalar {
             UniScalar::Time => DataType::default_for(TypeFamily::Time),
             UniScalar::Timestamp => DataType::default_for(TypeFamily::Timestamp),
             UniScalar::TimestampTz => DataType::default_for(TypeFamily::TimestampTz),
+            UniScalar::Uuid => DataType::default_for(TypeFamily::Uuid),
+            UniScalar::Json => DataType::default_for(TypeFamily::Json),
         };
         Ok(ty)
     }
 
     pub fn uni_from(ty: DataType) -> RS<Self> {
         let uni_scalar = match ty.type_family() {
+            TypeFamily::Bool => Self::Bool,
+            TypeFamily::I8 => Self::I8,
+            TypeFamily::I16 => Self::I16,
             TypeFamily::I32 => Self::I32,
             TypeFamily::I64 => Self::I64,
             TypeFamily::I128 => Self::I128,
@@ -89,6 +79,8 @@ impl UniScalar {
             TypeFamily::Time => Self::Time,
             TypeFamily::Timestamp => Self::Timestamp,
             TypeFamily::TimestampTz => Self::TimestampTz,
+            TypeFamily::Uuid => Self::Uuid,
+            TypeFamily::Json => Self::Json,
             TypeFamily::Array => {
                 return Err(mudu_error!(
                     ErrorCode::InvalidType,
@@ -127,6 +119,9 @@ mod tests {
     #[test]
     fn supported_uni_to_mudu_roundtrip() {
         let cases = [
+            (UniScalar::Bool, TypeFamily::Bool),
+            (UniScalar::I8, TypeFamily::I8),
+            (UniScalar::I16, TypeFamily::I16),
             (UniScalar::I32, TypeFamily::I32),
             (UniScalar::I64, TypeFamily::I64),
             (UniScalar::I128, TypeFamily::I128),
@@ -140,6 +135,8 @@ mod tests {
             (UniScalar::Time, TypeFamily::Time),
             (UniScalar::Timestamp, TypeFamily::Timestamp),
             (UniScalar::TimestampTz, TypeFamily::TimestampTz),
+            (UniScalar::Uuid, TypeFamily::Uuid),
+            (UniScalar::Json, TypeFamily::Json),
         ];
         for (uni, expected_id) in cases {
             let dat = uni.uni_to().unwrap();
@@ -151,11 +148,8 @@ mod tests {
     #[test]
     fn unsupported_uni_to_returns_invalid_type() {
         let unsupported = [
-            UniScalar::Bool,
             UniScalar::U8,
-            UniScalar::I8,
             UniScalar::U16,
-            UniScalar::I16,
             UniScalar::U32,
             UniScalar::U64,
             UniScalar::Char,
@@ -42,6 +42,10 @@ pub enum UniScalarValue {
     Timestamp(String),
 
     TimestampTz(String),
+
+    Uuid(Vec<u8>),
+
+    Json(String),
 }
 
 impl Default for UniScalarValue {
@@ -424,6 +428,42 @@ impl UniScalarValue {
             _ => unsafe { std::hint::unreachable_unchecked() },
         }
     }
+
+    pub fn from_uuid(inner: Vec<u8>) -> Self {
+        Self::Uuid(inner)
+    }
+
+    pub fn as_uuid(&self) -> Option<&Vec<u8>> {
+        match self {
+            Self::Uuid(inner) => Some(inner),
+            _ => None,
+        }
+    }
+
+    pub fn expect_uuid(&self) -> &Vec<u8> {
+        match self {
+            Self::Uuid(inner) => inner,
+            _ => unsafe { std::hint::unreachable_unchecked() },
+        }
+    }
+
+    pub fn from_json(inner: String) -> Self {
+        Self::Json(inner)
+    }
+
+    pub fn as_json(&self) -> Option<&String> {
+        match self {
+            Self::Json(inner) => Some(inner),
+            _ => None,
+        }
+    }
+
+    pub fn expect_json(&self) -> &String {
+        match self {
+            Self::Json(inner) => inner,
+            _ => unsafe { std::hint::unreachable_unchecked() },
+        }
+    }
 }
 
 impl serde::Serialize for UniScalarValue {
@@ -538,6 +578,16 @@ impl serde::Serialize for UniScalarValue {
                 serialize_seq.serialize_element(&20u32)?;
                 serialize_seq.serialize_element(&inner)?;
             }
+
+            UniScalarValue::Uuid(inner) => {
+                serialize_seq.serialize_element(&21u32)?;
+                serialize_seq.serialize_element(&inner)?;
+            }
+
+            UniScalarValue::Json(inner) => {
+                serialize_seq.serialize_element(&22u32)?;
+                serialize_seq.serialize_element(&inner)?;
+            }
         }
         serialize_seq.end()
     }
@@ -714,6 +764,20 @@ impl<'de> serde::de::Visitor<'de> for UniScalarValueVisitor {
                 Ok(Self::Value::TimestampTz(value))
             }
 
+            21 => {
+                let value = seq
+                    .next_element::<Vec<u8>>()?
+                    .map_or_else(|| Err(A::Error::invalid_length(1, &self)), Ok)?;
+                Ok(Self::Value::Uuid(value))
+            }
+
+            22 => {
+                let value = seq
+                    .next_element::<String>()?
+                    .map_or_else(|| Err(A::Error::invalid_length(1, &self)), Ok)?;
+                Ok(Self::Value::Json(value))
+            }
+
             _ => Err(Error::invalid_value(Unexpected::Map, &self)),
         }
     }
@@ -849,6 +913,14 @@ mod tests {
                 UniScalarValue::from_timestamptz("2026-05-20T14:30:00+08:00".to_string()),
                 Box::new(|v| v.as_timestamptz() == Some(&"2026-05-20T14:30:00+08:00".to_string())),
             ),
+            (
+                UniScalarValue::from_uuid(vec![0xab; 16]),
+                Box::new(|v| v.as_uuid() == Some(&vec![0xab; 16])),
+            ),
+            (
+                UniScalarValue::from_json("{\"a\":1}".to_string()),
+                Box::new(|v| v.as_json() == Some(&"{\"a\":1}".to_string())),
+            ),
         ];
 
         for (value, predicate) in cases {
@@ -882,6 +954,8 @@ mod tests {
         assert!(i32_value.as_time().is_none());
         assert!(i32_value.as_timestamp().is_none());
         assert!(i32_value.as_timestamptz().is_none());
+        assert!(i32_value.as_uuid().is_none());
+        assert!(i32_value.as_json().is_none());
 
         let blob_value = UniScalarValue::from_blob(vec![0, 1]);
         assert!(blob_value.as_string().is_none());
@@ -934,6 +1008,8 @@ mod tests {
             UniScalarValue::from_time("12:34:56.123456".to_string()),
             UniScalarValue::from_timestamp("2026-05-20 14:30:45.123456".to_string()),
             UniScalarValue::from_timestamptz("2026-05-20T14:30:45.123456+08:00".to_string()),
+            UniScalarValue::from_uuid((0u8..16).collect()),
+            UniScalarValue::from_json("[1,{\"k\":null}]".to_string()),
         ];
 
         for value in cases {
@@ -4,17 +4,22 @@ use async_trait::async_trait;
 use mudu::common::result::RS;
 use mudu::error::ErrorCode;
 use mudu::mudu_error;
+use mudu_contract::protocol::change_feed::{ChangeFeedRequest, ChangeFeedResponse};
 use mudu_contract::protocol::{
-    ClientRequest, Frame, FrameHeader, GetRequest, GetResponse, HEADER_LEN, MessageType,
-    ProcedureInvokeRequest, ProcedureInvokeResponse, PutRequest, PutResponse, RangeScanRequest,
-    RangeScanResponse, ServerPerfDigest, ServerResponse, SessionCloseRequest, SessionCloseResponse,
-    SessionCreateRequest, SessionCreateResponse, decode_error_response, decode_get_response,
-    decode_procedure_invoke_response, decode_put_response, decode_range_scan_response,
-    decode_server_response, decode_session_close_response, decode_session_create_response,
-    encode_batch_request, encode_client_request_with_message_type,
-    encode_client_request_with_message_type_and_trace, encode_get_request,
-    encode_procedure_invoke_request_with_trace, encode_put_request, encode_range_scan_request,
-    encode_session_close_request, encode_session_create_request,
+    ClientRequest, CloseCursorRequest, CloseCursorResponse, FetchRequest, FetchResponse, Frame,
+    FrameHeader, GetRequest, GetResponse, HEADER_LEN, MessageType, ProcedureInvokeRequest,
+    ProcedureInvokeResponse, PutRequest, PutResponse, RangeScanRequest, RangeScanResponse,
+    ServerPerfDigest, ServerResponse, SessionCloseRequest, SessionCloseResponse,
+    SessionCreateRequest, SessionCreateResponse, decode_auth_response, decode_change_feed_response,
+    decode_close_cursor_response, decode_error_response, decode_fetch_response,
+    decode_get_response, decode_procedure_invoke_response, decode_put_response,
+    decode_range_scan_response, decode_server_response, decode_session_close_response,
+    decode_session_create_response, encode_auth_request, encode_batch_request,
+    encode_change_feed_request, encode_client_request_with_message_type,
+    encode_client_request_with_message_type_and_trace, encode_close_cursor_request,
+    encode_fetch_request, encode_get_request, encode_procedure_invoke_request_with_trace,
+    encode_put_request, encode_range_scan_request, encode_session_close_request,
+    encode_session_create_request, scram::ScramClient,
 };
 use mudu_sys::net::AsyncTcpStream;
 use mudu_sys::perf::{PerfSpan, TraceContext, TxnStage, next_trace_id, should_sample};
@@ -45,6 +50,32 @@ pub trait AsyncClient: Send {
     async fn create_session(&mut self, request: SessionCreateRequest) -> RS<SessionCreateResponse>;
     /// Close an existing session.
     async fn close_session(&mut self, request: SessionCloseRequest) -> RS<SessionCloseResponse>;
+    /// Send a SQL query whose rows come back in pages of
+    /// `request.fetch_size()`; a returned cursor id is read on with
+    /// [`AsyncClient::fetch`]. Without cursor support the whole result comes
+    /// back as one page.
+    async fn query_paged(&mut self, request: ClientRequest) -> RS<FetchResponse> {
+        let response = self.query(request).await?;
+        Ok(FetchResponse::new(
+            response.row_desc().clone(),
+            response.rows().to_vec(),
+            None,
+        ))
+    }
+    /// Read the next page of a query cursor.
+    async fn fetch(&mut self, _request: FetchRequest) -> RS<FetchResponse> {
+        Err(mudu_error!(
+            ErrorCode::NotImplemented,
+            "query cursors are not supported by this client"
+        ))
+    }
+    /// Close a query cursor before it is drained.
+    async fn close_cursor(&mut self, _request: CloseCursorRequest) -> RS<CloseCursorResponse> {
+        Err(mudu_error!(
+            ErrorCode::NotImplemented,
+            "query cursors are not supported by this client"
+        ))
+    }
 }
 
 /// Async TCP client implementation using io_uring.
@@ -76,6 +107,25 @@ impl AsyncClientImpl {
         })
     }
 
+    /// Authenticate the connection with SCRAM-SHA-256; required before any
+    /// other request when the server runs with authentication enabled.
+    pub async fn login(&mut self, user: &str, password: &str) -> RS<()> {
+        let mut scram = ScramClient::new(user, password);
+        let payload = encode_auth_request(self.take_request_id(), &scram.client_first())?;
+        let server_first = decode_auth_response(&self.send_and_receive(&payload, 0).await?)?;
+        let payload =
+            encode_auth_request(self.take_request_id(), &scram.client_final(server_first)?)?;
+        let server_final = decode_auth_response(&self.send_and_receive(&payload, 0).await?)?;
+        scram.verify_server_final(server_final)
+    }
+
+    /// Read the committed row changes of a worker log.
+    pub async fn read_changes(&mut self, request: ChangeFeedRequest) -> RS<ChangeFeedResponse> {
+        let payload = encode_change_feed_request(self.take_request_id(), &request)?;
+        let frame = self.send_and_receive(&payload, 0).await?;
+        decode_change_feed_response(&frame)
+    }
+
     fn take_request_id(&mut self) -> u64 {
         let request_id = self.next_request_id;
         self.next_request_id += 1;
@@ -315,6 +365,37 @@ impl AsyncClient for AsyncClientImpl {
         let frame = self.send_and_receive(&payload, 0).await?;
         decode_session_close_response(&frame)
     }
+
+    async fn query_paged(&mut self, request: ClientRequest) -> RS<FetchResponse> {
+        if request.fetch_size() == 0 {
+            // Without a fetch size the server answers with the whole result.
+            let response = self.query(request).await?;
+            return Ok(FetchResponse::new(
+                response.row_desc().clone(),
+                response.rows().to_vec(),
+                None,
+            ));
+        }
+        let payload = encode_client_request_with_message_type(
+            MessageType::Query,
+            self.take_request_id(),
+            &request,
+        )?;
+        let frame = self.send_and_receive(&payload, 0).await?;
+        decode_fetch_response(&frame)
+    }
+
+    async fn fetch(&mut self, request: FetchRequest) -> RS<FetchResponse> {
+        let payload = encode_fetch_request(self.take_request_id(), &request)?;
+        let frame = self.send_and_receive(&payload, 0).await?;
+        decode_fetch_response(&frame)
+    }
+
+    async fn close_cursor(&mut self, request: CloseCursorRequest) -> RS<CloseCursorResponse> {
+        let payload = encode_close_cursor_request(self.take_request_id(), &request)?;
+        let frame = self.send_and_receive(&payload, 0).await?;
+        decode_close_cursor_response(&frame)
+    }
 }
 
 #[cfg(test)]
@@ -322,11 +403,12 @@ mod tests {
     use super::*;
     use mudu_contract::protocol::{
         GetResponse, KeyValue, PutResponse, SessionCloseResponse, SessionCreateResponse,
-        decode_client_request, decode_get_request, decode_procedure_invoke_request,
-        decode_put_request, decode_range_scan_request, decode_session_close_request,
-        decode_session_create_request, encode_get_response, encode_procedure_invoke_response,
-        encode_put_response, encode_range_scan_response, encode_server_response,
-        encode_session_close_response, encode_session_create_response,
+        decode_client_request, decode_close_cursor_request, decode_fetch_request,
+        decode_get_request, decode_procedure_invoke_request, decode_put_request,
+        decode_range_scan_request, decode_session_close_request, decode_session_create_request,
+        encode_close_cursor_response, encode_fetch_response, encode_get_response,
+        encode_procedure_invoke_response, encode_put_response, encode_range_scan_response,
+        encode_server_response, encode_session_close_response, encode_session_create_response,
     };
     use mudu_contract::tuple::datum_desc::DatumDesc;
     use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
@@ -417,6 +499,92 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn tokio_client_pages_query_through_cursor() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async {
+            let Some(listener) = bind_test_listener() else {
+                return;
+            };
+            let addr = listener.local_addr().unwrap();
+            let server = spawn_thread(move || {
+                let (mut socket, _) = listener.accept().unwrap();
+                let desc = TupleFieldDesc::new(vec![DatumDesc::new(
+                    "id".to_string(),
+                    DataType::default_for(TypeFamily::I32),
+                )]);
+                let row = |id| TupleValue::from(vec![DataValue::from_i32(id)]);
+
+                let query_frame = read_frame(&mut socket);
+                assert_eq!(query_frame.header().message_type(), MessageType::Query);
+                assert_eq!(decode_client_request(&query_frame).unwrap().fetch_size(), 2);
+                socket
+                    .write_all(
+                        &encode_fetch_response(
+                            query_frame.header().request_id(),
+                            &FetchResponse::new(desc.clone(), vec![row(1), row(2)], Some(5)),
+                        )
+                        .unwrap(),
+                    )
+                    .unwrap();
+
+                let fetch_frame = read_frame(&mut socket);
+                assert_eq!(fetch_frame.header().message_type(), MessageType::Fetch);
+                let fetch = decode_fetch_request(&fetch_frame).unwrap();
+                assert_eq!((fetch.session_id(), fetch.cursor_id()), (88, 5));
+                socket
+                    .write_all(
+                        &encode_fetch_response(
+                            fetch_frame.header().request_id(),
+                            &FetchResponse::new(desc, vec![row(3)], Some(5)),
+                        )
+                        .unwrap(),
+                    )
+                    .unwrap();
+
+                let close_frame = read_frame(&mut socket);
+                assert_eq!(
+                    close_frame.header().message_type(),
+                    MessageType::CloseCursor
+                );
+                assert_eq!(
+                    decode_close_cursor_request(&close_frame)
+                        .unwrap()
+                        .cursor_id(),
+                    5
+                );
+                socket
+                    .write_all(
+                        &encode_close_cursor_response(
+                            close_frame.header().request_id(),
+                            &CloseCursorResponse::new(true),
+                        )
+                        .unwrap(),
+                    )
+                    .unwrap();
+            });
+
+            let mut client = AsyncClientImpl::connect(&addr.to_string()).await.unwrap();
+            let page = client
+                .query_paged(
+                    ClientRequest::new_with_oid(88, "demo", "select id from t").with_fetch_size(2),
+                )
+                .await
+                .unwrap();
+            assert_eq!(page.rows().len(), 2);
+            assert_eq!(page.cursor_id(), Some(5));
+            let page = client.fetch(FetchRequest::new(88, 5, 0)).await.unwrap();
+            assert_eq!(page.rows()[0].values()[0].expect_i32(), &3);
+            let close = client
+                .close_cursor(CloseCursorRequest::new(88, 5))
+                .await
+                .unwrap();
+            assert!(close.closed());
+
+            server.unwrap().join().unwrap();
+        })
+        .unwrap();
+    }
+
     #[test]
     fn tokio_client_supports_kv_and_invoke_roundtrip() {
         mudu_sys::task::async_::block_on_tokio_current_thread(async {
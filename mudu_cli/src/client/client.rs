@@ -3,15 +3,20 @@
 use mudu::common::result::RS;
 use mudu::error::ErrorCode;
 use mudu::mudu_error;
+use mudu_contract::protocol::change_feed::{ChangeFeedRequest, ChangeFeedResponse};
 use mudu_contract::protocol::{
-    ClientRequest, Frame, FrameHeader, GetRequest, HEADER_LEN, KeyValue, MessageType,
-    ProcedureInvokeRequest, PutRequest, RangeScanRequest, ServerPerfDigest, ServerResponse,
-    SessionCloseRequest, SessionCreateRequest, decode_error_response, decode_get_response,
+    ClientRequest, CloseCursorRequest, FetchRequest, FetchResponse, Frame, FrameHeader, GetRequest,
+    HEADER_LEN, KeyValue, MessageType, ProcedureInvokeRequest, PutRequest, RangeScanRequest,
+    RangeScanResponse, ServerPerfDigest, ServerResponse, SessionCloseRequest, SessionCreateRequest,
+    decode_auth_response, decode_change_feed_response, decode_close_cursor_response,
+    decode_error_response, decode_fetch_response, decode_get_response,
     decode_procedure_invoke_response, decode_put_response, decode_range_scan_response,
     decode_server_response, decode_session_close_response, decode_session_create_response,
-    encode_batch_request, encode_client_request_with_message_type_and_trace, encode_get_request,
+    encode_auth_request, encode_batch_request, encode_change_feed_request,
+    encode_client_request_with_message_type, encode_client_request_with_message_type_and_trace,
+    encode_close_cursor_request, encode_fetch_request, encode_get_request,
     encode_procedure_invoke_request, encode_put_request, encode_range_scan_request,
-    encode_session_close_request, encode_session_create_request,
+    encode_session_close_request, encode_session_create_request, scram::ScramClient,
 };
 use mudu_sys::net::sync::{SStdTcpStream, connect_tcp};
 use mudu_sys::perf::{PerfSpan, TraceContext, TxnStage, next_trace_id, should_sample};
@@ -76,6 +81,56 @@ impl SyncClient {
         )
     }
 
+    /// Execute a parameterized SQL query within the given session and return
+    /// its first `fetch_size` rows; when more remain the response carries a
+    /// cursor id to [`SyncClient::fetch`] the rest from.
+    pub fn query_paged(
+        &mut self,
+        oid: u128,
+        app_name: impl Into<String>,
+        sql: impl Into<String>,
+        params: Vec<DataValue>,
+        fetch_size: u32,
+    ) -> RS<FetchResponse> {
+        let request = ClientRequest::new_with_oid(oid, app_name, sql)
+            .with_params(params)
+            .with_fetch_size(fetch_size.max(1));
+        let payload = encode_client_request_with_message_type(
+            MessageType::Query,
+            self.take_request_id(),
+            &request,
+        )?;
+        let frame = self.send_and_receive(&payload)?;
+        self.ensure_success_frame(&frame)?;
+        decode_fetch_response(&frame)
+    }
+
+    /// Read the next page of a query cursor; `max_rows` 0 keeps the fetch
+    /// size of the query that opened it.
+    pub fn fetch(&mut self, session_id: u128, cursor_id: u64, max_rows: u32) -> RS<FetchResponse> {
+        let request_id = self.take_request_id();
+        let payload = encode_fetch_request(
+            request_id,
+            &FetchRequest::new(session_id, cursor_id, max_rows),
+        )?;
+        let frame = self.send_and_receive(&payload)?;
+        self.ensure_success_frame(&frame)?;
+        decode_fetch_response(&frame)
+    }
+
+    /// Close a query cursor before it is drained and return whether it was
+    /// still open.
+    pub fn close_cursor(&mut self, session_id: u128, cursor_id: u64) -> RS<bool> {
+        let request_id = self.take_request_id();
+        let payload = encode_close_cursor_request(
+            request_id,
+            &CloseCursorRequest::new(session_id, cursor_id),
+        )?;
+        let frame = self.send_and_receive(&payload)?;
+        self.ensure_success_frame(&frame)?;
+        Ok(decode_close_cursor_response(&frame)?.closed())
+    }
+
     /// Execute a SQL statement and return the server response.
     pub fn execute(
         &mut self,
@@ -176,6 +231,39 @@ impl SyncClient {
         Ok(decode_range_scan_response(&frame)?.into_items())
     }
 
+    /// Scan at most `limit` items of a key range, resuming after the page
+    /// that returned `continuation`. The response carries the continuation of
+    /// the next page while the range may hold more.
+    pub fn range_scan_page(
+        &mut self,
+        session_id: u128,
+        start_key: impl Into<Vec<u8>>,
+        end_key: impl Into<Vec<u8>>,
+        limit: u32,
+        continuation: Option<Vec<u8>>,
+    ) -> RS<RangeScanResponse> {
+        let request_id = self.take_request_id();
+        let payload = encode_range_scan_request(
+            request_id,
+            &RangeScanRequest::new(session_id, start_key.into(), end_key.into())
+                .with_page(limit, continuation),
+        )?;
+        let frame = self.send_and_receive(&payload)?;
+        self.ensure_success_frame(&frame)?;
+        decode_range_scan_response(&frame)
+    }
+
+    /// Read the committed row changes of a worker log; see
+    /// [`ChangeFeedRequest`]. Blocks up to its `wait_ms` when nothing new
+    /// was committed.
+    pub fn read_changes(&mut self, request: &ChangeFeedRequest) -> RS<ChangeFeedResponse> {
+        let request_id = self.take_request_id();
+        let payload = encode_change_feed_request(request_id, request)?;
+        let frame = self.send_and_receive(&payload)?;
+        self.ensure_success_frame(&frame)?;
+        decode_change_feed_response(&frame)
+    }
+
     /// Invoke a stored procedure.
     pub fn invoke_procedure(
         &mut self,
@@ -213,6 +301,22 @@ impl SyncClient {
         Ok(decode_session_close_response(&frame)?.closed())
     }
 
+    /// Authenticate the connection with SCRAM-SHA-256; required before any
+    /// other request when the server runs with authentication enabled.
+    pub fn login(&mut self, user: &str, password: &str) -> RS<()> {
+        let mut scram = ScramClient::new(user, password);
+        let request_id = self.take_request_id();
+        let payload = encode_auth_request(request_id, &scram.client_first())?;
+        let frame = self.send_and_receive(&payload)?;
+        self.ensure_success_frame(&frame)?;
+        let client_final = scram.client_final(decode_auth_response(&frame)?)?;
+        let request_id = self.take_request_id();
+        let payload = encode_auth_request(request_id, &client_final)?;
+        let frame = self.send_and_receive(&payload)?;
+        self.ensure_success_frame(&frame)?;
+        scram.verify_server_final(decode_auth_response(&frame)?)
+    }
+
     fn take_request_id(&mut self) -> u64 {
         let request_id = self.next_request_id;
         self.next_request_id += 1;
@@ -349,7 +453,7 @@ impl SyncClient {
 mod tests {
     use super::*;
     use mudu_contract::protocol::{
-        GetResponse, ProcedureInvokeResponse, PutResponse, RangeScanResponse, SessionCloseResponse,
+        GetResponse, ProcedureInvokeResponse, PutResponse, SessionCloseResponse,
         SessionCreateResponse, encode_get_response, encode_procedure_invoke_response,
         encode_put_response, encode_range_scan_response, encode_session_close_response,
         encode_session_create_response,
@@ -458,6 +562,83 @@ mod tests {
         server.join().unwrap();
     }
 
+    #[test]
+    fn client_paged_query_and_range_scan_decode() {
+        use mudu_contract::protocol::{
+            decode_client_request, decode_fetch_request, decode_range_scan_request,
+            encode_fetch_response,
+        };
+        use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
+
+        fn read_request(socket: &mut SStdTcpStream) -> Frame {
+            let mut header = [0u8; HEADER_LEN];
+            socket.read_exact(&mut header).unwrap();
+            let payload_len = FrameHeader::decode_header_bytes(&header)
+                .unwrap()
+                .payload_len() as usize;
+            let mut body = vec![0u8; payload_len];
+            socket.read_exact(&mut body).unwrap();
+            let mut request = Vec::from(header);
+            request.extend_from_slice(&body);
+            Frame::decode(&request).unwrap()
+        }
+
+        let Some(listener) = bind_test_listener() else {
+            return;
+        };
+        let addr = listener.local_addr().unwrap();
+        let server = spawn_thread(move || {
+            let (mut socket, _) = listener.accept().unwrap();
+
+            let frame = read_request(&mut socket);
+            assert_eq!(decode_client_request(&frame).unwrap().fetch_size(), 10);
+            let response = encode_fetch_response(
+                frame.header().request_id(),
+                &FetchResponse::new(TupleFieldDesc::new(vec![]), vec![], Some(3)),
+            )
+            .unwrap();
+            socket.write_all(&response).unwrap();
+
+            let frame = read_request(&mut socket);
+            assert_eq!(decode_fetch_request(&frame).unwrap().cursor_id(), 3);
+            let response = encode_fetch_response(
+                frame.header().request_id(),
+                &FetchResponse::new(TupleFieldDesc::new(vec![]), vec![], None),
+            )
+            .unwrap();
+            socket.write_all(&response).unwrap();
+
+            let frame = read_request(&mut socket);
+            let request = decode_range_scan_request(&frame).unwrap();
+            let page = request.page().unwrap();
+            assert_eq!(page.limit(), 1);
+            assert_eq!(page.continuation(), Some(&b"a"[..]));
+            let response = encode_range_scan_response(
+                frame.header().request_id(),
+                &RangeScanResponse::new(vec![KeyValue::new(b"b".to_vec(), b"2".to_vec())])
+                    .with_continuation(Some(b"b".to_vec())),
+            )
+            .unwrap();
+            socket.write_all(&response).unwrap();
+        })
+        .unwrap();
+
+        let mut client = SyncClient::connect(addr).unwrap();
+        let page = client
+            .query_paged(7, "app", "select 1", Vec::new(), 10)
+            .unwrap();
+        assert_eq!(page.cursor_id(), Some(3));
+        let page = client.fetch(7, 3, 0).unwrap();
+        assert_eq!(page.cursor_id(), None);
+        let (items, continuation) = client
+            .range_scan_page(7, b"a".to_vec(), b"z".to_vec(), 1, Some(b"a".to_vec()))
+            .unwrap()
+            .into_parts();
+        assert_eq!(items, vec![KeyValue::new(b"b".to_vec(), b"2".to_vec())]);
+        assert_eq!(continuation, Some(b"b".to_vec()));
+        server.join().unwrap();
+    }
+
     #[test]
     fn client_procedure_invoke_decode() {
         let Some(listener) = bind_test_listener() else {
@@ -6,21 +6,27 @@
 use crate::client::async_client::{AsyncClient, AsyncClientImpl};
 use base64::Engine;
 use mudu::common::result::RS;
+use mudu::data_type::uuid::format_uuid;
 use mudu::error::ErrorCode;
 use mudu::mudu_error;
 use mudu_binding::universal::uni_data_value::UniDataValue;
 use mudu_binding::universal::uni_oid::UniOid;
 use mudu_binding::universal::uni_scalar_value::UniScalarValue;
 use mudu_contract::protocol::{
-    ClientRequest, GetRequest, KeyValue, ProcedureInvokeRequest, PutRequest, RangeScanRequest,
-    ServerResponse,
+    ClientRequest, FetchRequest, GetRequest, KeyValue, ProcedureInvokeRequest, PutRequest,
+    RangeScanRequest, ServerResponse,
 };
+use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
+use mudu_contract::tuple::tuple_value::TupleValue;
 use mudu_type::datum::DatumDyn;
 use mudu_type::type_family::TypeFamily;
 use serde::Deserialize;
 use serde::de::{self, Deserializer};
 use serde_json::{Value, json};
 
+/// Rows a query pulls per page, and keys a range scan reads per page.
+const PAGE_SIZE: u32 = 1000;
+
 /// JSON wrapper around an async client.
 ///
 /// `C` is the underlying client type (usually `AsyncClientImpl`).
@@ -56,12 +62,33 @@ where
         let request = serde_json::from_value::<JsonCommandRequest>(request)
             .map_err(|e| mudu_error!(ErrorCode::Decode, "decode json command request error", e))?;
         let client_request = ClientRequest::new(request.app_name, request.sql);
-        let response = if request.kind == Some(CommandKind::Execute) {
-            self.inner.execute(client_request).await?
-        } else {
-            self.inner.query(client_request).await?
-        };
-        server_response_to_json(&response)
+        if request.kind == Some(CommandKind::Execute) {
+            let response = self.inner.execute(client_request).await?;
+            return server_response_to_json(&response);
+        }
+        let session_id = client_request.oid();
+        let page = self
+            .inner
+            .query_paged(client_request.with_fetch_size(PAGE_SIZE))
+            .await?;
+        let (row_desc, rows, mut cursor_id) = page.into_parts();
+        let columns = columns_to_json(&row_desc);
+        let mut json_rows = rows_to_json(&row_desc, &rows)?;
+        while let Some(id) = cursor_id {
+            let page = self
+                .inner
+                .fetch(FetchRequest::new(session_id, id, 0))
+                .await?;
+            let (_, rows, next) = page.into_parts();
+            json_rows.extend(rows_to_json(&row_desc, &rows)?);
+            cursor_id = next;
+        }
+        Ok(json!({
+            "columns": columns,
+            "rows": json_rows,
+            "affected_rows": 0,
+            "error": Value::Null,
+        }))
     }
 
     /// Put a key-value item from a JSON request body.
@@ -100,20 +127,23 @@ where
     pub async fn range(&mut self, request: Value) -> RS<Value> {
         let request = serde_json::from_value::<JsonRangeRequest>(request)
             .map_err(|e| mudu_error!(ErrorCode::Decode, "decode json range request error", e))?;
-        let response = self
-            .inner
-            .range_scan(RangeScanRequest::new(
-                request.oid.to_oid(),
-                json_value_to_universal_bytes(request.start_key)?,
-                json_value_to_universal_bytes(request.end_key)?,
-            ))
-            .await?;
-        let items = response
-            .into_items()
-            .into_iter()
-            .map(key_value_to_json)
-            .collect::<RS<Vec<_>>>()?;
-        Ok(Value::Array(items))
+        let session_id = request.oid.to_oid();
+        let start_key = json_value_to_universal_bytes(request.start_key)?;
+        let end_key = json_value_to_universal_bytes(request.end_key)?;
+        let mut items = Vec::new();
+        let mut continuation = None;
+        loop {
+            let request = RangeScanRequest::new(session_id, start_key.clone(), end_key.clone())
+                .with_page(PAGE_SIZE, continuation);
+            let (page, next) = self.inner.range_scan(request).await?.into_parts();
+            for item in page {
+                items.push(key_value_to_json(item)?);
+            }
+            match next {
+                Some(next) => continuation = Some(next),
+                None => return Ok(Value::Array(items)),
+            }
+        }
     }
 
     /// Invoke a stored procedure from a JSON request body.
@@ -287,6 +317,14 @@ fn uni_data_value_to_json_value(value: UniDataValue) -> RS<Value> {
                 UniScalarValue::Time(v) => Ok(Value::String(v)),
                 UniScalarValue::Timestamp(v) => Ok(Value::String(v)),
                 UniScalarValue::TimestampTz(v) => Ok(Value::String(v)),
+                UniScalarValue::Uuid(v) => {
+                    let bytes: [u8; 16] = v.as_slice().try_into().map_err(|_| {
+                        mudu_error!(ErrorCode::Decode, "uuid payload must be 16 bytes")
+                    })?;
+                    Ok(Value::String(format_uuid(u128::from_be_bytes(bytes))))
+                }
+                UniScalarValue::Json(v) => serde_json::from_str(&v)
+                    .map_err(|e| mudu_error!(ErrorCode::Decode, "invalid json payload", e)),
             }
         }
         UniDataValue::Array(items) => items
@@ -341,22 +379,21 @@ fn key_value_to_json(key_value: KeyValue) -> RS<Value> {
     }))
 }
 
-fn server_response_to_json(response: &ServerResponse) -> RS<Value> {
-    let columns = response
-        .row_desc()
+fn columns_to_json(row_desc: &TupleFieldDesc) -> Vec<Value> {
+    row_desc
         .fields()
         .iter()
         .map(|field| Value::String(field.name().to_string()))
-        .collect::<Vec<_>>();
+        .collect()
+}
 
-    let rows = response
-        .rows()
-        .iter()
+fn rows_to_json(row_desc: &TupleFieldDesc, rows: &[TupleValue]) -> RS<Vec<Value>> {
+    rows.iter()
         .map(|row| {
             let values = row
                 .values()
                 .iter()
-                .zip(response.row_desc().fields().iter())
+                .zip(row_desc.fields().iter())
                 .map(|(value, field_desc)| {
                     if value.is_null() {
                         Ok(Value::Null)
@@ -371,11 +408,13 @@ fn server_response_to_json(response: &ServerResponse) -> RS<Value> {
                 .collect::<RS<Vec<_>>>()?;
             Ok(Value::Array(values))
         })
-        .collect::<RS<Vec<_>>>()?;
+        .collect()
+}
 
+fn server_response_to_json(response: &ServerResponse) -> RS<Value> {
     Ok(json!({
-        "columns": columns,
-        "rows": rows,
+        "columns": columns_to_json(response.row_desc()),
+        "rows": rows_to_json(response.row_desc(), response.rows())?,
         "affected_rows": response.affected_rows(),
         "error": response.error(),
     }))
@@ -387,13 +426,11 @@ mod tests {
     use crate::client::async_client::AsyncClient;
     use async_trait::async_trait;
     use mudu_contract::protocol::{
-        GetResponse, KeyValue, ProcedureInvokeResponse, PutResponse, RangeScanResponse,
-        ServerResponse, SessionCloseRequest, SessionCloseResponse, SessionCreateRequest,
-        SessionCreateResponse,
+        FetchResponse, GetResponse, KeyValue, ProcedureInvokeResponse, PutResponse,
+        RangeScanResponse, ServerResponse, SessionCloseRequest, SessionCloseResponse,
+        SessionCreateRequest, SessionCreateResponse,
     };
     use mudu_contract::tuple::datum_desc::DatumDesc;
-    use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
-    use mudu_contract::tuple::tuple_value::TupleValue;
     use mudu_type::data_type::DataType;
     use mudu_type::data_value::DataValue;
     use mudu_type::type_family::TypeFamily;
@@ -644,4 +681,155 @@ mod tests {
         })
         .unwrap();
     }
+
+    /// Serves every query and range scan one row or key per page.
+    struct PagingClient {
+        fetches: Vec<FetchRequest>,
+        scans: Vec<RangeScanRequest>,
+    }
+
+    fn string_rows(values: &[&str]) -> (TupleFieldDesc, Vec<TupleValue>) {
+        (
+            TupleFieldDesc::new(vec![DatumDesc::new(
+                "value".to_string(),
+                DataType::default_for(TypeFamily::String),
+            )]),
+            values
+                .iter()
+                .map(|value| TupleValue::from(vec![DataValue::from_string(value.to_string())]))
+                .collect(),
+        )
+    }
+
+    #[async_trait]
+    impl AsyncClient for PagingClient {
+        async fn query(&mut self, _request: ClientRequest) -> RS<ServerResponse> {
+            Err(mudu_error!(ErrorCode::Internal, "unexpected unpaged query"))
+        }
+
+        async fn query_paged(&mut self, request: ClientRequest) -> RS<FetchResponse> {
+            assert_eq!(request.fetch_size(), PAGE_SIZE);
+            let (desc, rows) = string_rows(&["1"]);
+            Ok(FetchResponse::new(desc, rows, Some(9)))
+        }
+
+        async fn fetch(&mut self, request: FetchRequest) -> RS<FetchResponse> {
+            self.fetches.push(request);
+            let (desc, rows) = string_rows(&["2"]);
+            let cursor_id = (self.fetches.len() < 2).then_some(9);
+            Ok(FetchResponse::new(desc, rows, cursor_id))
+        }
+
+        async fn execute(&mut self, _request: ClientRequest) -> RS<ServerResponse> {
+            Err(mudu_error!(ErrorCode::Internal, "unexpected execute"))
+        }
+
+        async fn batch(&mut self, _request: ClientRequest) -> RS<ServerResponse> {
+            Err(mudu_error!(ErrorCode::Internal, "unexpected batch"))
+        }
+
+        async fn get(&mut self, _request: GetRequest) -> RS<GetResponse> {
+            Err(mudu_error!(ErrorCode::Internal, "unexpected get"))
+        }
+
+        async fn put(&mut self, _request: PutRequest) -> RS<PutResponse> {
+            Err(mudu_error!(ErrorCode::Internal, "unexpected put"))
+        }
+
+        async fn range_scan(&mut self, request: RangeScanRequest) -> RS<RangeScanResponse> {
+            let page = self.scans.len();
+            self.scans.push(request);
+            let key = json_value_to_universal_bytes(json!(page)).unwrap();
+            let response = RangeScanResponse::new(vec![KeyValue::new(key.clone(), key.clone())]);
+            Ok(if page < 2 {
+                response.with_continuation(Some(key))
+            } else {
+                response
+            })
+        }
+
+        async fn invoke_procedure(
+            &mut self,
+            _request: ProcedureInvokeRequest,
+        ) -> RS<ProcedureInvokeResponse> {
+            Err(mudu_error!(
+                ErrorCode::Internal,
+                "unexpected invoke procedure"
+            ))
+        }
+
+        async fn create_session(
+            &mut self,
+            _request: SessionCreateRequest,
+        ) -> RS<SessionCreateResponse> {
+            Err(mudu_error!(
+                ErrorCode::Internal,
+                "unexpected create session"
+            ))
+        }
+
+        async fn close_session(
+            &mut self,
+            _request: SessionCloseRequest,
+        ) -> RS<SessionCloseResponse> {
+            Err(mudu_error!(ErrorCode::Internal, "unexpected close session"))
+        }
+    }
+
+    #[test]
+    fn json_client_follows_cursors_and_continuations() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let mut client = JsonClient::new(PagingClient {
+                fetches: Vec::new(),
+                scans: Vec::new(),
+            });
+            let response = client
+                .command(json!({
+                    "app_name": "demo",
+                    "sql": "select value from t"
+                }))
+                .await
+                .unwrap();
+            assert_eq!(response["columns"], json!(["value"]));
+            assert_eq!(response["rows"], json!([["1"], ["2"], ["2"]]));
+
+            let range = client
+                .range(json!({
+                    "oid": {"h": 0, "l": 7},
+                    "start_key": "a",
+                    "end_key": "z"
+                }))
+                .await
+                .unwrap();
+            assert_eq!(
+                range,
+                json!([
+                    {"key": 0, "value": 0},
+                    {"key": 1, "value": 1},
+                    {"key": 2, "value": 2}
+                ])
+            );
+
+            let inner = client.into_inner();
+            assert!(inner.fetches.iter().all(|fetch| fetch.cursor_id() == 9));
+            let continuations = inner
+                .scans
+                .iter()
+                .map(|scan| {
+                    let page = scan.page().unwrap();
+                    assert_eq!(page.limit(), PAGE_SIZE);
+                    page.continuation().map(<[u8]>::to_vec)
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                continuations,
+                vec![
+                    None,
+                    Some(json_value_to_universal_bytes(json!(0)).unwrap()),
+                    Some(json_value_to_universal_bytes(json!(1)).unwrap()),
+                ]
+            );
+        })
+        .unwrap();
+    }
 }
@@ -20,8 +20,10 @@ use mudu_binding::procedure::procedure_invoke;
 use mudu_cli::client::async_client::{AsyncClient, AsyncClientImpl};
 use mudu_cli::client::json_client::JsonClient;
 use mudu_cli::management::{
-    fetch_app_detail, fetch_app_list, fetch_proc_desc, fetch_server_topology, install_app_package,
-    route_partition, uninstall_app,
+    backup_with_token, fetch_app_detail, fetch_app_list, fetch_auth_token, fetch_proc_desc,
+    fetch_server_topology, install_app_package_with_token, promote_with_token,
+    rollback_app_with_token, route_partition, uninstall_app_with_token,
+    upgrade_app_package_with_token,
 };
 use mudu_contract::procedure::procedure_param::ProcedureParam;
 use mudu_contract::protocol::{ProcedureInvokeRequest, SessionCloseRequest, SessionCreateRequest};
@@ -40,12 +42,18 @@ Examples:
   mcli --addr 127.0.0.1:9527 put --json-file put.json
   cat invoke.json | mcli --addr 127.0.0.1:9527 invoke --json-file -
   mcli --http-addr 127.0.0.1:8300 app-install --mpk target/wasm32-wasip2/release/key-value.mpk
+  mcli --http-addr 127.0.0.1:8300 app-upgrade --mpk target/wasm32-wasip2/release/key-value.mpk
+  mcli --http-addr 127.0.0.1:8300 app-rollback --app kv
   mcli --addr 127.0.0.1:9527 --http-addr 127.0.0.1:8300 app-invoke --app kv --module key_value --proc kv_read --json '{\"user_key\":\"user-1\"}'
   mcli --http-addr 127.0.0.1:8300 app-list
   mcli --http-addr 127.0.0.1:8300 app-detail --app wallet
   mcli --http-addr 127.0.0.1:8300 app-uninstall --app wallet
+  mcli --user admin --password secret --http-addr 127.0.0.1:8300 app-uninstall --app wallet
   mcli --http-addr 127.0.0.1:8300 server-topology
-  mcli --http-addr 127.0.0.1:8300 partition-route --rule-name user_rule --key user-100";
+  mcli --user admin --password secret --http-addr 127.0.0.1:8300 backup --dir /var/backups/mudu-20261018
+  mcli --user admin --password secret --http-addr 127.0.0.1:8301 promote
+  mcli --http-addr 127.0.0.1:8300 partition-route --rule-name user_rule --key user-100
+  mcli --http-addr 127.0.0.1:8300 partition-route --rule-name user_hash_rule --key 42";
 
 /// Top-level command-line arguments for `mcli`.
 #[derive(Parser, Debug)]
@@ -78,6 +86,15 @@ struct Cli {
         help = "Print compact JSON instead of pretty JSON."
     )]
     compact: bool,
+    #[arg(
+        long,
+        global = true,
+        requires = "password",
+        help = "User to authenticate as, for servers with authentication enabled."
+    )]
+    user: Option<String>,
+    #[arg(long, global = true, requires = "user", help = "Password of --user.")]
+    password: Option<String>,
     #[command(subcommand)]
     command: Commands,
 }
@@ -99,6 +116,12 @@ enum Commands {
     Invoke(JsonRequestArgs),
     /// Install a .mpk package through the HTTP management API.
     AppInstall(AppInstallArgs),
+    /// Upgrade an installed app to the newer .mpk package version through the
+    /// HTTP management API; in-flight calls finish on the old version.
+    AppUpgrade(AppInstallArgs),
+    /// Roll an app back to the version before its last upgrade via HTTP
+    /// management API.
+    AppRollback(AppRollbackArgs),
     /// Invoke an installed procedure through the TCP protocol.
     AppInvoke(AppInvokeArgs),
     /// List installed apps via HTTP management API.
@@ -109,6 +132,12 @@ enum Commands {
     AppUninstall(AppUninstallArgs),
     /// Get worker topology via HTTP management API.
     ServerTopology,
+    /// Take an online backup into a directory on the server host via HTTP
+    /// management API.
+    Backup(BackupArgs),
+    /// Promote a standby server to a writable primary via HTTP management
+    /// API.
+    Promote,
     /// Route a partition key/range via HTTP management API.
     PartitionRoute(PartitionRouteArgs),
 }
@@ -126,13 +155,20 @@ struct JsonRequestArgs {
     json_file: Option<PathBuf>,
 }
 
-/// Arguments for the `app-install` subcommand.
+/// Arguments for the `app-install` and `app-upgrade` subcommands.
 #[derive(Args, Debug)]
 struct AppInstallArgs {
-    #[arg(long, help = "Path to the .mpk package file to install.")]
+    #[arg(long, help = "Path to the .mpk package file to install or upgrade to.")]
     mpk: PathBuf,
 }
 
+/// Arguments for the `app-rollback` subcommand.
+#[derive(Args, Debug)]
+struct AppRollbackArgs {
+    #[arg(long)]
+    app: String,
+}
+
 /// Arguments for the `app-invoke` subcommand.
 #[derive(Args, Debug)]
 struct AppInvokeArgs {
@@ -157,6 +193,16 @@ struct ShellArgs {
     app: String,
 }
 
+/// Arguments for the `backup` subcommand.
+#[derive(Args, Debug)]
+struct BackupArgs {
+    #[arg(
+        long,
+        help = "Directory on the server host to write the backup to; must be missing or empty."
+    )]
+    dir: String,
+}
+
 /// Arguments for the `app-detail` subcommand.
 #[derive(Args, Debug)]
 struct AppDetailArgs {
@@ -206,25 +252,43 @@ pub(crate) trait AsyncClientConnect: Send + Sync {
     async fn connect(&self, addr: &str) -> RS<Self::Client>;
 }
 
-struct RealJsonConnector;
+/// User name and password from `--user` / `--password`.
+type Credential = (String, String);
+
+/// Connect and, when a credential is given, authenticate the connection.
+async fn connect_and_login(addr: &str, credential: Option<&Credential>) -> RS<AsyncClientImpl> {
+    let mut client = AsyncClientImpl::connect(addr).await?;
+    if let Some((user, password)) = credential {
+        client.login(user, password).await?;
+    }
+    Ok(client)
+}
+
+struct RealJsonConnector {
+    credential: Option<Credential>,
+}
 
 #[async_trait]
 impl JsonClientConnect for RealJsonConnector {
     type Client = AsyncClientImpl;
 
     async fn connect(&self, addr: &str) -> RS<JsonClient<Self::Client>> {
-        JsonClient::connect(addr).await
+        Ok(JsonClient::new(
+            connect_and_login(addr, self.credential.as_ref()).await?,
+        ))
     }
 }
 
-struct RealAsyncConnector;
+struct RealAsyncConnector {
+    credential: Option<Credential>,
+}
 
 #[async_trait]
 impl AsyncClientConnect for RealAsyncConnector {
     type Client = AsyncClientImpl;
 
     async fn connect(&self, addr: &str) -> RS<Self::Client> {
-        AsyncClientImpl::connect(addr).await
+        connect_and_login(addr, self.credential.as_ref()).await
     }
 }
 
@@ -242,7 +306,12 @@ async fn run(cli: Cli) -> RS<()> {
     let compact = cli.compact;
     let table = cli.table;
     let no_table = cli.no_table;
-    let output = run_with_connectors(cli, &RealJsonConnector, &RealAsyncConnector).await?;
+    let credential = cli.user.clone().zip(cli.password.clone());
+    let json_connector = RealJsonConnector {
+        credential: credential.clone(),
+    };
+    let async_connector = RealAsyncConnector { credential };
+    let output = run_with_connectors(cli, &json_connector, &async_connector).await?;
     print_output(&output, compact, table, no_table)?;
     Ok(())
 }
@@ -262,8 +331,11 @@ where
         compact,
         table,
         no_table,
+        user,
+        password,
         command,
     } = cli;
+    let credential = user.zip(password);
 
     let output = match command {
         Commands::Command(args) => {
@@ -420,7 +492,8 @@ where
                     format!("read {} failed: {}", args.mpk.display(), e)
                 )
             })?;
-            install_app_package(&http_addr, mpk_binary)
+            let token = http_auth_token(&http_addr, credential.as_ref()).await?;
+            install_app_package_with_token(&http_addr, mpk_binary, token.as_deref())
                 .await
                 .map_err(|e| mudu_error!(ErrorCode::Network, e))?;
             let mut response = json!({
@@ -434,6 +507,32 @@ where
             }
             response
         }
+        Commands::AppUpgrade(args) => {
+            let mpk_binary = mudu_sys::fs::sync::sync_read_all(&args.mpk).map_err(|e| {
+                mudu_error!(
+                    ErrorCode::Io,
+                    format!("read {} failed: {}", args.mpk.display(), e)
+                )
+            })?;
+            let token = http_auth_token(&http_addr, credential.as_ref()).await?;
+            upgrade_app_package_with_token(&http_addr, mpk_binary, token.as_deref())
+                .await
+                .map_err(|e| mudu_error!(ErrorCode::Network, e))?;
+            json!({
+                "status": "ok",
+                "mpk_path": args.mpk.display().to_string(),
+            })
+        }
+        Commands::AppRollback(args) => {
+            let token = http_auth_token(&http_addr, credential.as_ref()).await?;
+            rollback_app_with_token(&http_addr, &args.app, token.as_deref())
+                .await
+                .map_err(|e| mudu_error!(ErrorCode::Network, e))?;
+            json!({
+                "status": "ok",
+                "app": args.app,
+            })
+        }
         Commands::AppInvoke(args) => {
             let request = load_json_request(args.request)?;
             let proc_desc = fetch_proc_desc(&http_addr, &args.app, &args.module, &args.proc)
@@ -513,7 +612,8 @@ where
             .map_err(|e| mudu_error!(ErrorCode::Network, e))?
         }
         Commands::AppUninstall(args) => {
-            uninstall_app(&http_addr, &args.app)
+            let token = http_auth_token(&http_addr, credential.as_ref()).await?;
+            uninstall_app_with_token(&http_addr, &args.app, token.as_deref())
                 .await
                 .map_err(|e| mudu_error!(ErrorCode::Network, e))?;
             json!({
@@ -532,6 +632,19 @@ where
                 format!("serialize server topology failed: {}", e)
             )
         })?,
+        Commands::Backup(args) => {
+            let token = http_auth_token(&http_addr, credential.as_ref()).await?;
+            backup_with_token(&http_addr, &args.dir, token.as_deref())
+                .await
+                .map_err(|e| mudu_error!(ErrorCode::Network, e))?
+        }
+        Commands::Promote => {
+            let token = http_auth_token(&http_addr, credential.as_ref()).await?;
+            promote_with_token(&http_addr, token.as_deref())
+                .await
+                .map_err(|e| mudu_error!(ErrorCode::Network, e))?;
+            json!({ "status": "ok" })
+        }
         Commands::PartitionRoute(args) => {
             if args.key.is_some() && (args.start.is_some() || args.end.is_some()) {
                 return Err(mudu_error!(
@@ -562,6 +675,18 @@ where
     Ok(output)
 }
 
+/// Fetch a bearer token for the HTTP management API when a credential is
+/// given.
+async fn http_auth_token(http_addr: &str, credential: Option<&Credential>) -> RS<Option<String>> {
+    match credential {
+        Some((user, password)) => fetch_auth_token(http_addr, user, password)
+            .await
+            .map(Some)
+            .map_err(|e| mudu_error!(ErrorCode::PermissionDenied, e)),
+        None => Ok(None),
+    }
+}
+
 fn load_json_request(args: JsonRequestArgs) -> RS<Value> {
     let raw = load_required_text(args.json, args.json_file)?;
     serde_json::from_str(&raw)
@@ -214,6 +214,8 @@ fn cli(command: Commands) -> Cli {
         compact: false,
         table: false,
         no_table: false,
+        user: None,
+        password: None,
         command,
     }
 }
@@ -496,6 +498,110 @@ fn run_app_install_subcommand_succeeds() {
     .unwrap();
 }
 
+#[cfg_attr(miri, ignore)]
+#[test]
+fn run_app_upgrade_subcommand_succeeds() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async {
+        let http_addr = start_mock_http_server(json!({"ok": true, "data": null}));
+        let path = temp_json_path("app_upgrade.mpk");
+        mudu_sys::fs::sync::sync_write(&path, b"fake mpk").unwrap();
+
+        let mut c = cli(Commands::AppUpgrade(AppInstallArgs { mpk: path.clone() }));
+        c.http_addr = http_addr;
+
+        run_with_connectors(
+            c,
+            &MockJsonConnector {
+                client: MockAsyncClient::new(),
+            },
+            &MockAsyncConnector {
+                client: MockAsyncClient::new(),
+            },
+        )
+        .await
+        .unwrap();
+
+        let _ = mudu_sys::fs::sync::sync_remove_file(path);
+    })
+    .unwrap();
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn run_app_rollback_subcommand_succeeds() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async {
+        let http_addr = start_mock_http_server(json!({"ok": true, "data": null}));
+        let mut c = cli(Commands::AppRollback(AppRollbackArgs {
+            app: "wallet".to_string(),
+        }));
+        c.http_addr = http_addr;
+
+        run_with_connectors(
+            c,
+            &MockJsonConnector {
+                client: MockAsyncClient::new(),
+            },
+            &MockAsyncConnector {
+                client: MockAsyncClient::new(),
+            },
+        )
+        .await
+        .unwrap();
+    })
+    .unwrap();
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn run_backup_subcommand_succeeds() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async {
+        let http_addr = start_mock_http_server(json!({
+            "ok": true,
+            "data": {"finished_at_unix_micros": 1, "worker_log_dir": "", "workers": []}
+        }));
+        let mut c = cli(Commands::Backup(BackupArgs {
+            dir: "/tmp/mudu-backup".to_string(),
+        }));
+        c.http_addr = http_addr;
+
+        run_with_connectors(
+            c,
+            &MockJsonConnector {
+                client: MockAsyncClient::new(),
+            },
+            &MockAsyncConnector {
+                client: MockAsyncClient::new(),
+            },
+        )
+        .await
+        .unwrap();
+    })
+    .unwrap();
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn run_promote_subcommand_succeeds() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async {
+        let http_addr = start_mock_http_server(json!({"ok": true, "data": null}));
+        let mut c = cli(Commands::Promote);
+        c.http_addr = http_addr;
+
+        run_with_connectors(
+            c,
+            &MockJsonConnector {
+                client: MockAsyncClient::new(),
+            },
+            &MockAsyncConnector {
+                client: MockAsyncClient::new(),
+            },
+        )
+        .await
+        .unwrap();
+    })
+    .unwrap();
+}
+
 #[cfg_attr(miri, ignore)]
 #[test]
 fn run_app_list_subcommand_succeeds() {
@@ -16,6 +16,7 @@ type AppResult<T> = Result<T, String>;
 
 const HTTP_TIMEOUT_DEFAULT_SECS: u64 = 10;
 const HTTP_INSTALL_TIMEOUT_DEFAULT_SECS: u64 = 600;
+const HTTP_BACKUP_TIMEOUT_DEFAULT_SECS: u64 = 3600;
 const HTTP_RETRY_COUNT: usize = 5;
 const HTTP_RETRY_INITIAL_DELAY: Duration = Duration::from_millis(100);
 
@@ -41,6 +42,16 @@ pub fn http_install_timeout() -> Duration {
         .unwrap_or(Duration::from_secs(HTTP_INSTALL_TIMEOUT_DEFAULT_SECS))
 }
 
+/// Returns the HTTP request timeout for an online backup, which copies the
+/// whole database before the server answers. Override with
+/// `MUDU_CLI_HTTP_BACKUP_TIMEOUT_SECS`.
+pub fn http_backup_timeout() -> Duration {
+    mudu_sys::env_var::var("MUDU_CLI_HTTP_BACKUP_TIMEOUT_SECS")
+        .and_then(|s| s.parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(Duration::from_secs(HTTP_BACKUP_TIMEOUT_DEFAULT_SECS))
+}
+
 static HTTP_CLIENT: LazyLock<Result<reqwest::Client, String>> = LazyLock::new(|| {
     reqwest::Client::builder()
         .no_proxy()
@@ -173,6 +184,9 @@ pub struct PartitionRouteEntry {
 /// Response from the partition route endpoint.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct PartitionRouteResponse {
+    /// Rule kind: `range`, `hash` or `list`.
+    #[serde(default)]
+    pub kind: String,
     /// Resolved partition routes.
     pub routes: Vec<PartitionRouteEntry>,
 }
@@ -213,21 +227,121 @@ pub fn is_server_topology_unsupported(err: &str) -> bool {
     err.contains("server topology is not supported") || err.contains("\"code\":\"NotImplemented\"")
 }
 
+/// Exchange a user name and password for a bearer token, for servers that
+/// run with authentication enabled.
+pub async fn fetch_auth_token(http_addr: &str, user: &str, password: &str) -> AppResult<String> {
+    let payload = json!({
+        "user": user,
+        "password": password,
+    });
+    let response = post_http_json(http_addr, "/mudu/auth/token", payload).await?;
+    let data = extract_http_api_data(response)?;
+    data.get("token")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| "auth token response missing token".to_string())
+}
+
 /// Install an app package from its raw `.mpk` bytes.
 pub async fn install_app_package(http_addr: &str, mpk_binary: Vec<u8>) -> AppResult<()> {
+    install_app_package_with_token(http_addr, mpk_binary, None).await
+}
+
+/// Install an app package, sending `token` as the bearer token if given.
+pub async fn install_app_package_with_token(
+    http_addr: &str,
+    mpk_binary: Vec<u8>,
+    token: Option<&str>,
+) -> AppResult<()> {
+    post_app_package(http_addr, "/mudu/app/install", mpk_binary, token).await
+}
+
+/// Upgrade an installed app to the newer version in the raw `.mpk` bytes,
+/// sending `token` as the bearer token if given.
+pub async fn upgrade_app_package_with_token(
+    http_addr: &str,
+    mpk_binary: Vec<u8>,
+    token: Option<&str>,
+) -> AppResult<()> {
+    post_app_package(http_addr, "/mudu/app/upgrade", mpk_binary, token).await
+}
+
+async fn post_app_package(
+    http_addr: &str,
+    path: &str,
+    mpk_binary: Vec<u8>,
+    token: Option<&str>,
+) -> AppResult<()> {
     let payload = json!({
         "mpk_base64": base64::engine::general_purpose::STANDARD.encode(mpk_binary),
     });
-    let url = format!("http://{}{}", http_addr, "/mudu/app/install");
+    let url = format!("http://{}{}", http_addr, path);
+    let client = http_client()?;
+    // Install and upgrade compile wasm components server-side, so they use
+    // their own, much larger per-request timeout instead of the shared default.
+    let response = send_json_request("POST", &url, || {
+        with_bearer(
+            client
+                .post(&url)
+                .json(&payload)
+                .timeout(http_install_timeout()),
+            token,
+        )
+        .send()
+    })
+    .await?;
+    let _ = extract_http_api_data(response)?;
+    Ok(())
+}
+
+/// Roll an app back to the version it had before its last upgrade, sending
+/// `token` as the bearer token if given.
+pub async fn rollback_app_with_token(
+    http_addr: &str,
+    app_name: &str,
+    token: Option<&str>,
+) -> AppResult<()> {
+    let url = format!("http://{}/mudu/app/rollback/{}", http_addr, app_name);
     let client = http_client()?;
-    // Install compiles wasm components server-side, so it uses its own, much
-    // larger per-request timeout instead of the shared default.
     let response = send_json_request("POST", &url, || {
-        client
-            .post(&url)
-            .json(&payload)
-            .timeout(http_install_timeout())
-            .send()
+        with_bearer(client.post(&url).timeout(http_install_timeout()), token).send()
+    })
+    .await?;
+    let _ = extract_http_api_data(response)?;
+    Ok(())
+}
+
+/// Take an online backup into `target_dir` on the server host, sending
+/// `token` as the bearer token if given. Returns the backup manifest.
+pub async fn backup_with_token(
+    http_addr: &str,
+    target_dir: &str,
+    token: Option<&str>,
+) -> AppResult<Value> {
+    let url = format!("http://{}/mudu/admin/backup", http_addr);
+    let payload = json!({ "target_dir": target_dir });
+    let client = http_client()?;
+    let response = send_json_request("POST", &url, || {
+        with_bearer(
+            client
+                .post(&url)
+                .json(&payload)
+                .timeout(http_backup_timeout()),
+            token,
+        )
+        .send()
+    })
+    .await?;
+    extract_http_api_data(response)
+}
+
+/// Promote the standby server to a writable primary, sending `token` as the
+/// bearer token if given.
+pub async fn promote_with_token(http_addr: &str, token: Option<&str>) -> AppResult<()> {
+    let url = format!("http://{}/mudu/admin/promote", http_addr);
+    let client = http_client()?;
+    let response = send_json_request("POST", &url, || {
+        with_bearer(client.post(&url).timeout(http_install_timeout()), token).send()
     })
     .await?;
     let _ = extract_http_api_data(response)?;
@@ -236,8 +350,21 @@ pub async fn install_app_package(http_addr: &str, mpk_binary: Vec<u8>) -> AppRes
 
 /// Uninstall an app by name.
 pub async fn uninstall_app(http_addr: &str, app_name: &str) -> AppResult<()> {
-    let response =
-        delete_http_json(http_addr, &format!("/mudu/app/uninstall/{}", app_name)).await?;
+    uninstall_app_with_token(http_addr, app_name, None).await
+}
+
+/// Uninstall an app by name, sending `token` as the bearer token if given.
+pub async fn uninstall_app_with_token(
+    http_addr: &str,
+    app_name: &str,
+    token: Option<&str>,
+) -> AppResult<()> {
+    let url = format!("http://{}/mudu/app/uninstall/{}", http_addr, app_name);
+    let client = http_client()?;
+    let response = send_json_request("DELETE", &url, || {
+        with_bearer(client.delete(&url), token).send()
+    })
+    .await?;
     let _ = extract_http_api_data(response)?;
     Ok(())
 }
@@ -288,10 +415,11 @@ async fn post_http_json(http_addr: &str, path: &str, payload: Value) -> AppResul
     send_json_request("POST", &url, || client.post(&url).json(&payload).send()).await
 }
 
-async fn delete_http_json(http_addr: &str, path: &str) -> AppResult<Value> {
-    let url = format!("http://{}{}", http_addr, path);
-    let client = http_client()?;
-    send_json_request("DELETE", &url, || client.delete(&url).send()).await
+fn with_bearer(request: reqwest::RequestBuilder, token: Option<&str>) -> reqwest::RequestBuilder {
+    match token {
+        Some(token) => request.bearer_auth(token),
+        None => request,
+    }
 }
 
 fn http_client() -> AppResult<&'static reqwest::Client> {
@@ -4,8 +4,9 @@
 //! functions can be exercised without a real MuduDB server.
 
 use crate::management::{
-    PartitionRouteResponse, WorkerTopology, fetch_app_detail, fetch_app_list, fetch_proc_desc,
-    fetch_server_topology, install_app_package, route_partition, uninstall_app,
+    PartitionRouteResponse, WorkerTopology, fetch_app_detail, fetch_app_list, fetch_auth_token,
+    fetch_proc_desc, fetch_server_topology, install_app_package, install_app_package_with_token,
+    route_partition, uninstall_app,
 };
 use mudu::common::id::OID;
 use serde_json::json;
@@ -210,6 +211,36 @@ fn uninstall_app_deletes_and_succeeds() {
     .unwrap();
 }
 
+#[cfg_attr(miri, ignore)]
+#[test]
+fn fetch_auth_token_returns_token() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async {
+        let addr = start_mock_http_server(json!({
+            "ok": true,
+            "data": {"token": "t0k3n", "expires_in_secs": 3600},
+        }));
+        let token = fetch_auth_token(&addr, "admin", "secret").await.unwrap();
+        assert_eq!(token, "t0k3n");
+        install_app_package_with_token(&addr, vec![1, 2, 3], Some(&token))
+            .await
+            .unwrap();
+    })
+    .unwrap();
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn fetch_auth_token_reports_rejection() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async {
+        let addr = start_mock_http_server(json!({
+            "ok": false,
+            "message": "fail to authenticate user admin",
+        }));
+        assert!(fetch_auth_token(&addr, "admin", "wrong").await.is_err());
+    })
+    .unwrap();
+}
+
 #[cfg_attr(miri, ignore)]
 #[test]
 fn route_partition_with_key_returns_routes() {
@@ -284,6 +315,14 @@ fn partition_route_response_round_trips_oid() {
         OID::from(((1u128) << 64) + 2)
     );
     assert_eq!(response.routes[0].worker_id, OID::from(((3u128) << 64) + 4));
+    assert!(response.kind.is_empty());
+
+    let response: PartitionRouteResponse = serde_json::from_value(json!({
+        "kind": "hash",
+        "routes": []
+    }))
+    .unwrap();
+    assert_eq!(response.kind, "hash");
 }
 
 #[test]
@@ -0,0 +1,321 @@
+//! Change data capture over [`MessageType::ChangeFeed`](super::MessageType::ChangeFeed)
+//! frames and the `/mudu/cdc/{worker_index}` HTTP route.
+//!
+//! A subscriber reads the committed row changes of one worker log in commit
+//! order. Every response carries a `last_lsn` cursor; passing it back as
+//! `after_lsn` resumes the feed right after the last transaction returned, so
+//! a consumer that stores the cursor together with what it applied gets each
+//! change once across restarts. Transactions are never split across
+//! responses.
+
+use crate::tuple::datum_desc::DatumDesc;
+use crate::tuple::tuple_field_desc::TupleFieldDesc;
+use crate::tuple::tuple_value::TupleValue;
+use mudu::common::result::RS;
+use mudu::error::ErrorCode;
+use mudu::mudu_error;
+use mudu::utils::json::JsonValue;
+use mudu_type::data_type::DataType;
+use mudu_type::data_value::DataValue;
+use mudu_type::type_family::TypeFamily;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, json};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChangeFeedRequest {
+    worker_id: u128,
+    /// Resume cursor: the `last_lsn` of a previous response, or `None` to
+    /// start at the oldest change the server still retains.
+    after_lsn: Option<u64>,
+    max_events: u32,
+    /// How long the server may hold the request open waiting for a change
+    /// when there is none yet; 0 returns at once.
+    wait_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeFeedResponse {
+    events: Vec<ChangeEvent>,
+    last_lsn: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// A full row image. UPDATE statements that rewrite a row are logged the
+    /// same way, so consumers apply it as an upsert by key.
+    Insert,
+    /// Deferred column deltas applied to the row with the given key.
+    Update,
+    /// The row with the given key was removed.
+    Delete,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ChangeDeltaOp {
+    Add,
+    Sub,
+    /// Subtract the operand; a result below `floor` wraps by adding `wrap`.
+    SubWrap {
+        floor: i64,
+        wrap: i64,
+    },
+}
+
+/// One column delta of a [`ChangeKind::Update`] event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeDelta {
+    column: DatumDesc,
+    op: ChangeDeltaOp,
+    operand: DataValue,
+}
+
+/// One committed row change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeEvent {
+    /// LSN of the last log frame of the transaction; events of one
+    /// transaction share it.
+    lsn: u64,
+    xid: u64,
+    commit_time_micros: Option<u64>,
+    table_id: u128,
+    table_name: String,
+    partition_id: u128,
+    kind: ChangeKind,
+    /// Columns of `row`: every column for inserts, the key columns otherwise.
+    row_desc: TupleFieldDesc,
+    row: TupleValue,
+    #[serde(default)]
+    deltas: Vec<ChangeDelta>,
+}
+
+impl ChangeFeedRequest {
+    pub fn new(worker_id: u128, after_lsn: Option<u64>, max_events: u32, wait_ms: u64) -> Self {
+        Self {
+            worker_id,
+            after_lsn,
+            max_events,
+            wait_ms,
+        }
+    }
+
+    pub fn worker_id(&self) -> u128 {
+        self.worker_id
+    }
+
+    pub fn after_lsn(&self) -> Option<u64> {
+        self.after_lsn
+    }
+
+    pub fn max_events(&self) -> u32 {
+        self.max_events
+    }
+
+    pub fn wait_ms(&self) -> u64 {
+        self.wait_ms
+    }
+}
+
+impl ChangeFeedResponse {
+    pub fn new(events: Vec<ChangeEvent>, last_lsn: Option<u64>) -> Self {
+        Self { events, last_lsn }
+    }
+
+    pub fn events(&self) -> &[ChangeEvent] {
+        &self.events
+    }
+
+    pub fn into_events(self) -> Vec<ChangeEvent> {
+        self.events
+    }
+
+    /// Cursor to pass as `after_lsn` of the next request.
+    pub fn last_lsn(&self) -> Option<u64> {
+        self.last_lsn
+    }
+
+    pub fn to_json(&self) -> RS<JsonValue> {
+        let events = self
+            .events
+            .iter()
+            .map(ChangeEvent::to_json)
+            .collect::<RS<Vec<_>>>()?;
+        Ok(json!({
+            "events": events,
+            "last_lsn": self.last_lsn,
+        }))
+    }
+}
+
+impl ChangeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeKind::Insert => "insert",
+            ChangeKind::Update => "update",
+            ChangeKind::Delete => "delete",
+        }
+    }
+}
+
+impl ChangeDelta {
+    pub fn new(column: DatumDesc, op: ChangeDeltaOp, operand: DataValue) -> Self {
+        Self {
+            column,
+            op,
+            operand,
+        }
+    }
+
+    pub fn column(&self) -> &DatumDesc {
+        &self.column
+    }
+
+    pub fn op(&self) -> ChangeDeltaOp {
+        self.op
+    }
+
+    pub fn operand(&self) -> &DataValue {
+        &self.operand
+    }
+
+    fn to_json(&self) -> RS<JsonValue> {
+        let mut object = Map::new();
+        object.insert("column".to_string(), json!(self.column.name()));
+        let operand = match self.op {
+            ChangeDeltaOp::Add => {
+                object.insert("op".to_string(), json!("add"));
+                value_to_json(&self.operand, self.column.data_type())?
+            }
+            ChangeDeltaOp::Sub => {
+                object.insert("op".to_string(), json!("sub"));
+                value_to_json(&self.operand, self.column.data_type())?
+            }
+            ChangeDeltaOp::SubWrap { floor, wrap } => {
+                object.insert("op".to_string(), json!("sub_wrap"));
+                object.insert("floor".to_string(), json!(floor));
+                object.insert("wrap".to_string(), json!(wrap));
+                value_to_json(&self.operand, &DataType::default_for(TypeFamily::I64))?
+            }
+        };
+        object.insert("operand".to_string(), operand);
+        Ok(JsonValue::Object(object))
+    }
+}
+
+impl ChangeEvent {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        lsn: u64,
+        xid: u64,
+        commit_time_micros: Option<u64>,
+        table_id: u128,
+        table_name: String,
+        partition_id: u128,
+        kind: ChangeKind,
+        row_desc: TupleFieldDesc,
+        row: TupleValue,
+        deltas: Vec<ChangeDelta>,
+    ) -> Self {
+        Self {
+            lsn,
+            xid,
+            commit_time_micros,
+            table_id,
+            table_name,
+            partition_id,
+            kind,
+            row_desc,
+            row,
+            deltas,
+        }
+    }
+
+    pub fn lsn(&self) -> u64 {
+        self.lsn
+    }
+
+    pub fn xid(&self) -> u64 {
+        self.xid
+    }
+
+    pub fn commit_time_micros(&self) -> Option<u64> {
+        self.commit_time_micros
+    }
+
+    pub fn table_id(&self) -> u128 {
+        self.table_id
+    }
+
+    pub fn table_name(&self) -> &str {
+        &self.table_name
+    }
+
+    pub fn partition_id(&self) -> u128 {
+        self.partition_id
+    }
+
+    pub fn kind(&self) -> ChangeKind {
+        self.kind
+    }
+
+    pub fn row_desc(&self) -> &TupleFieldDesc {
+        &self.row_desc
+    }
+
+    pub fn row(&self) -> &TupleValue {
+        &self.row
+    }
+
+    pub fn deltas(&self) -> &[ChangeDelta] {
+        &self.deltas
+    }
+
+    /// Renders the event with its row as a column-name keyed object. OIDs
+    /// are rendered as strings since JSON numbers cannot hold them.
+    pub fn to_json(&self) -> RS<JsonValue> {
+        let fields = self.row_desc.fields();
+        let values = self.row.values();
+        if fields.len() != values.len() {
+            return Err(mudu_error!(
+                ErrorCode::Internal,
+                format!(
+                    "change event of table {} has {} columns but {} values",
+                    self.table_name,
+                    fields.len(),
+                    values.len()
+                )
+            ));
+        }
+        let mut row = Map::new();
+        for (field, value) in fields.iter().zip(values) {
+            row.insert(
+                field.name().to_string(),
+                value_to_json(value, field.data_type())?,
+            );
+        }
+        let deltas = self
+            .deltas
+            .iter()
+            .map(ChangeDelta::to_json)
+            .collect::<RS<Vec<_>>>()?;
+        Ok(json!({
+            "lsn": self.lsn,
+            "xid": self.xid,
+            "commit_time_micros": self.commit_time_micros,
+            "table_id": self.table_id.to_string(),
+            "table": self.table_name,
+            "partition_id": self.partition_id.to_string(),
+            "kind": self.kind.as_str(),
+            "row": row,
+            "deltas": deltas,
+        }))
+    }
+}
+
+fn value_to_json(value: &DataValue, data_type: &DataType) -> RS<JsonValue> {
+    if value.is_null() {
+        return Ok(JsonValue::Null);
+    }
+    let json =
+        data_type.type_family().fn_output_json()(value, data_type).map_err(|e| e.to_m_err())?;
+    Ok(json.into_json_value())
+}
@@ -1,6 +1,7 @@
 //! `protocol::mod` module.
 #![allow(missing_docs)]
 
+use crate::protocol::change_feed::{ChangeFeedRequest, ChangeFeedResponse};
 use crate::tuple::tuple_field_desc::TupleFieldDesc;
 use crate::tuple::tuple_value::TupleValue;
 use mudu::common::result::RS;
@@ -12,8 +13,10 @@ use mudu_type::data_value::DataValue;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
+pub mod change_feed;
 pub mod format;
 pub mod migrate;
+pub mod scram;
 pub use format::latest::HEADER_LEN;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -31,6 +34,9 @@ pub enum MessageType {
     ProcedureInvoke = 11,
     SessionCreate = 12,
     SessionClose = 13,
+    ChangeFeed = 14,
+    Fetch = 15,
+    CloseCursor = 16,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -51,6 +57,36 @@ pub struct HandshakeResponse {
     pub capabilities: Vec<String>,
 }
 
+/// Capability tag a server advertises in [`HandshakeResponse::capabilities`]
+/// when it requires SCRAM-SHA-256 authentication before any other request.
+pub const AUTH_SCRAM_SHA_256_CAPABILITY: &str = "auth.scram-sha-256";
+
+/// Client message of the SCRAM-SHA-256 exchange carried by
+/// [`MessageType::Auth`] frames; see [`scram`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AuthRequest {
+    /// First client message: the user name and a fresh client nonce.
+    ClientFirst { user: String, client_nonce: String },
+    /// Final client message: the combined nonce echoed back and the client
+    /// proof.
+    ClientFinal { nonce: String, proof: Vec<u8> },
+}
+
+/// Server message of the SCRAM-SHA-256 exchange.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AuthResponse {
+    /// Challenge: the combined nonce (client nonce followed by the server
+    /// nonce) and the user's salt and iteration count.
+    ServerFirst {
+        nonce: String,
+        salt: Vec<u8>,
+        iterations: u32,
+    },
+    /// Outcome of a successful exchange: the server signature the client
+    /// verifies to authenticate the server in turn.
+    ServerFinal { server_signature: Vec<u8> },
+}
+
 impl From<MessageType> for u32 {
     fn from(value: MessageType) -> Self {
         value as u32
@@ -75,6 +111,9 @@ impl TryFrom<u32> for MessageType {
             11 => Ok(MessageType::ProcedureInvoke),
             12 => Ok(MessageType::SessionCreate),
             13 => Ok(MessageType::SessionClose),
+            14 => Ok(MessageType::ChangeFeed),
+            15 => Ok(MessageType::Fetch),
+            16 => Ok(MessageType::CloseCursor),
             _ => Err(mudu_error!(
                 ErrorCode::Parse,
                 format!("unknown message type {}", value)
@@ -104,6 +143,12 @@ pub struct ClientRequest {
     /// them to the target NUMERIC type during bind.
     #[serde(default)]
     params: Vec<DataValue>,
+    /// Rows per page for a [`MessageType::Query`]; zero returns the whole
+    /// result in one [`ServerResponse`]. A non-zero size answers with a
+    /// [`FetchResponse`] holding the first page and, while rows remain, the
+    /// server-side cursor that [`FetchRequest`] reads on.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    fetch_size: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -158,6 +203,19 @@ pub struct RangeScanRequest {
     session_id: u128,
     start_key: Vec<u8>,
     end_key: Vec<u8>,
+    /// Set to page the scan; absent returns the whole range at once.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    page: Option<RangeScanPage>,
+}
+
+/// Paging of a [`RangeScanRequest`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RangeScanPage {
+    /// Most items to return.
+    limit: u32,
+    /// [`RangeScanResponse::continuation`] of the previous page; the scan
+    /// resumes right after the items that page returned.
+    continuation: Option<Vec<u8>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -206,6 +264,40 @@ pub struct PutResponse {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct RangeScanResponse {
     items: Vec<KeyValue>,
+    /// Token for the next page of a paged scan; absent once the range is
+    /// exhausted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    continuation: Option<Vec<u8>>,
+}
+
+/// Read the next page of a query cursor; see [`ClientRequest::fetch_size`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FetchRequest {
+    session_id: u128,
+    cursor_id: u64,
+    /// Rows to return; zero keeps the fetch size the query was opened with.
+    max_rows: u32,
+}
+
+/// One page of query rows. `cursor_id` is set while more rows remain; the
+/// server drops the cursor once its last page was returned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FetchResponse {
+    row_desc: TupleFieldDesc,
+    rows: Vec<TupleValue>,
+    cursor_id: Option<u64>,
+}
+
+/// Release a query cursor before its last page was fetched.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CloseCursorRequest {
+    session_id: u128,
+    cursor_id: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CloseCursorResponse {
+    closed: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -361,6 +453,7 @@ impl ClientRequest {
             app_name: app_name.into(),
             sql: sql.into(),
             params: Vec::new(),
+            fetch_size: 0,
         }
     }
 
@@ -370,6 +463,7 @@ impl ClientRequest {
             app_name: app_name.into(),
             sql: sql.into(),
             params: Vec::new(),
+            fetch_size: 0,
         }
     }
 
@@ -378,6 +472,11 @@ impl ClientRequest {
         self
     }
 
+    pub fn with_fetch_size(mut self, fetch_size: u32) -> Self {
+        self.fetch_size = fetch_size;
+        self
+    }
+
     pub fn oid(&self) -> u128 {
         self.oid
     }
@@ -393,6 +492,10 @@ impl ClientRequest {
     pub fn params(&self) -> &[DataValue] {
         &self.params
     }
+
+    pub fn fetch_size(&self) -> u32 {
+        self.fetch_size
+    }
 }
 
 impl ServerResponse {
@@ -483,9 +586,20 @@ impl RangeScanRequest {
             session_id,
             start_key,
             end_key,
+            page: None,
         }
     }
 
+    /// Page the scan: return at most `limit` items, resuming after the page
+    /// that returned `continuation`.
+    pub fn with_page(mut self, limit: u32, continuation: Option<Vec<u8>>) -> Self {
+        self.page = Some(RangeScanPage {
+            limit,
+            continuation,
+        });
+        self
+    }
+
     pub fn session_id(&self) -> u128 {
         self.session_id
     }
@@ -497,6 +611,20 @@ impl RangeScanRequest {
     pub fn end_key(&self) -> &[u8] {
         &self.end_key
     }
+
+    pub fn page(&self) -> Option<&RangeScanPage> {
+        self.page.as_ref()
+    }
+}
+
+impl RangeScanPage {
+    pub fn limit(&self) -> u32 {
+        self.limit
+    }
+
+    pub fn continuation(&self) -> Option<&[u8]> {
+        self.continuation.as_deref()
+    }
 }
 
 impl ProcedureInvokeRequest {
@@ -579,16 +707,107 @@ impl PutResponse {
 
 impl RangeScanResponse {
     pub fn new(items: Vec<KeyValue>) -> Self {
-        Self { items }
+        Self {
+            items,
+            continuation: None,
+        }
+    }
+
+    pub fn with_continuation(mut self, continuation: Option<Vec<u8>>) -> Self {
+        self.continuation = continuation;
+        self
     }
 
     pub fn items(&self) -> &[KeyValue] {
         &self.items
     }
 
+    pub fn continuation(&self) -> Option<&[u8]> {
+        self.continuation.as_deref()
+    }
+
     pub fn into_items(self) -> Vec<KeyValue> {
         self.items
     }
+
+    pub fn into_parts(self) -> (Vec<KeyValue>, Option<Vec<u8>>) {
+        (self.items, self.continuation)
+    }
+}
+
+impl FetchRequest {
+    pub fn new(session_id: u128, cursor_id: u64, max_rows: u32) -> Self {
+        Self {
+            session_id,
+            cursor_id,
+            max_rows,
+        }
+    }
+
+    pub fn session_id(&self) -> u128 {
+        self.session_id
+    }
+
+    pub fn cursor_id(&self) -> u64 {
+        self.cursor_id
+    }
+
+    pub fn max_rows(&self) -> u32 {
+        self.max_rows
+    }
+}
+
+impl FetchResponse {
+    pub fn new(row_desc: TupleFieldDesc, rows: Vec<TupleValue>, cursor_id: Option<u64>) -> Self {
+        Self {
+            row_desc,
+            rows,
+            cursor_id,
+        }
+    }
+
+    pub fn row_desc(&self) -> &TupleFieldDesc {
+        &self.row_desc
+    }
+
+    pub fn rows(&self) -> &[TupleValue] {
+        &self.rows
+    }
+
+    pub fn cursor_id(&self) -> Option<u64> {
+        self.cursor_id
+    }
+
+    pub fn into_parts(self) -> (TupleFieldDesc, Vec<TupleValue>, Option<u64>) {
+        (self.row_desc, self.rows, self.cursor_id)
+    }
+}
+
+impl CloseCursorRequest {
+    pub fn new(session_id: u128, cursor_id: u64) -> Self {
+        Self {
+            session_id,
+            cursor_id,
+        }
+    }
+
+    pub fn session_id(&self) -> u128 {
+        self.session_id
+    }
+
+    pub fn cursor_id(&self) -> u64 {
+        self.cursor_id
+    }
+}
+
+impl CloseCursorResponse {
+    pub fn new(closed: bool) -> Self {
+        Self { closed }
+    }
+
+    pub fn closed(&self) -> bool {
+        self.closed
+    }
 }
 
 impl ProcedureInvokeResponse {
@@ -707,6 +926,24 @@ pub fn decode_handshake_response(frame: &Frame) -> RS<HandshakeResponse> {
     decode_payload(frame.payload(), "decode handshake response error")
 }
 
+pub fn encode_auth_request(request_id: u64, request: &AuthRequest) -> RS<Vec<u8>> {
+    let payload = encode_payload(request, "encode auth request error")?;
+    Ok(Frame::new(MessageType::Auth, request_id, payload).encode())
+}
+
+pub fn decode_auth_request(frame: &Frame) -> RS<AuthRequest> {
+    decode_payload(frame.payload(), "decode auth request error")
+}
+
+pub fn encode_auth_response(request_id: u64, response: &AuthResponse) -> RS<Vec<u8>> {
+    let payload = encode_payload(response, "encode auth response error")?;
+    Ok(Frame::new(MessageType::Response, request_id, payload).encode())
+}
+
+pub fn decode_auth_response(frame: &Frame) -> RS<AuthResponse> {
+    decode_payload(frame.payload(), "decode auth response error")
+}
+
 pub fn encode_client_request_with_message_type(
     message_type: MessageType,
     request_id: u64,
@@ -891,6 +1128,63 @@ pub fn decode_session_close_response(frame: &Frame) -> RS<SessionCloseResponse>
     decode_payload(frame.payload(), "decode session close response error")
 }
 
+pub fn encode_change_feed_request(request_id: u64, request: &ChangeFeedRequest) -> RS<Vec<u8>> {
+    let payload = encode_payload(request, "encode change feed request error")?;
+    Ok(Frame::new(MessageType::ChangeFeed, request_id, payload).encode())
+}
+
+pub fn decode_change_feed_request(frame: &Frame) -> RS<ChangeFeedRequest> {
+    decode_payload(frame.payload(), "decode change feed request error")
+}
+
+pub fn encode_change_feed_response(request_id: u64, response: &ChangeFeedResponse) -> RS<Vec<u8>> {
+    let payload = encode_payload(response, "encode change feed response error")?;
+    Ok(Frame::new(MessageType::Response, request_id, payload).encode())
+}
+
+pub fn decode_change_feed_response(frame: &Frame) -> RS<ChangeFeedResponse> {
+    decode_payload(frame.payload(), "decode change feed response error")
+}
+
+pub fn encode_fetch_request(request_id: u64, request: &FetchRequest) -> RS<Vec<u8>> {
+    let payload = encode_payload(request, "encode fetch request error")?;
+    Ok(Frame::new(MessageType::Fetch, request_id, payload).encode())
+}
+
+pub fn decode_fetch_request(frame: &Frame) -> RS<FetchRequest> {
+    decode_payload(frame.payload(), "decode fetch request error")
+}
+
+pub fn encode_fetch_response(request_id: u64, response: &FetchResponse) -> RS<Vec<u8>> {
+    let payload = encode_payload(response, "encode fetch response error")?;
+    Ok(Frame::new(MessageType::Response, request_id, payload).encode())
+}
+
+pub fn decode_fetch_response(frame: &Frame) -> RS<FetchResponse> {
+    decode_payload(frame.payload(), "decode fetch response error")
+}
+
+pub fn encode_close_cursor_request(request_id: u64, request: &CloseCursorRequest) -> RS<Vec<u8>> {
+    let payload = encode_payload(request, "encode close cursor request error")?;
+    Ok(Frame::new(MessageType::CloseCursor, request_id, payload).encode())
+}
+
+pub fn decode_close_cursor_request(frame: &Frame) -> RS<CloseCursorRequest> {
+    decode_payload(frame.payload(), "decode close cursor request error")
+}
+
+pub fn encode_close_cursor_response(
+    request_id: u64,
+    response: &CloseCursorResponse,
+) -> RS<Vec<u8>> {
+    let payload = encode_payload(response, "encode close cursor response error")?;
+    Ok(Frame::new(MessageType::Response, request_id, payload).encode())
+}
+
+pub fn decode_close_cursor_response(frame: &Frame) -> RS<CloseCursorResponse> {
+    decode_payload(frame.payload(), "decode close cursor response error")
+}
+
 pub fn encode_error_response(request_id: u64, message: impl Into<String>) -> RS<Vec<u8>> {
     let payload = encode_payload(&ErrorResponse::new(message), "encode error response error")?;
     Ok(Frame::new(MessageType::Error, request_id, payload).encode())
@@ -906,6 +1200,12 @@ pub fn decode_error_response(frame: &Frame) -> RS<ErrorResponse> {
     decode_payload(frame.payload(), "decode error response error")
 }
 
+// Payloads are positional, so a new trailing field is left out while it holds
+// its default to stay readable by peers that predate it.
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
 fn encode_payload<T: Serialize>(value: &T, err_msg: &'static str) -> RS<Vec<u8>> {
     rmp_serde::to_vec(value).map_err(|e| mudu_error!(ErrorCode::Encode, err_msg, e))
 }
@@ -916,3 +1216,5 @@ fn decode_payload<T: DeserializeOwned>(payload: &[u8], err_msg: &'static str) ->
 
 #[cfg(test)]
 mod mod_test;
+#[cfg(test)]
+mod scram_test;
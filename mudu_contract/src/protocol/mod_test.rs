@@ -186,6 +186,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn change_feed_roundtrip_and_json() {
+        use crate::protocol::change_feed::*;
+        use crate::tuple::datum_desc::DatumDesc;
+        use crate::tuple::tuple_value::TupleValue;
+        use mudu_type::data_type::DataType;
+        use mudu_type::data_value::DataValue;
+        use mudu_type::type_family::TypeFamily;
+
+        let request = ChangeFeedRequest::new(7, Some(41), 100, 500);
+        let frame = Frame::decode(&encode_change_feed_request(3, &request).unwrap()).unwrap();
+        assert_eq!(frame.header().message_type(), MessageType::ChangeFeed);
+        assert_eq!(decode_change_feed_request(&frame).unwrap(), request);
+
+        let int = DataType::default_for(TypeFamily::I32);
+        let row_desc = TupleFieldDesc::new(vec![
+            DatumDesc::new("id".to_string(), int.clone()),
+            DatumDesc::new("qty".to_string(), int.clone()),
+        ]);
+        let insert = ChangeEvent::new(
+            52,
+            9,
+            Some(1_000),
+            u128::MAX,
+            "stock".to_string(),
+            1,
+            ChangeKind::Insert,
+            row_desc,
+            TupleValue::from(vec![DataValue::from_i32(1), DataValue::null()]),
+            vec![],
+        );
+        let update = ChangeEvent::new(
+            52,
+            9,
+            Some(1_000),
+            u128::MAX,
+            "stock".to_string(),
+            1,
+            ChangeKind::Update,
+            TupleFieldDesc::new(vec![DatumDesc::new("id".to_string(), int.clone())]),
+            TupleValue::from(vec![DataValue::from_i32(1)]),
+            vec![ChangeDelta::new(
+                DatumDesc::new("qty".to_string(), int),
+                ChangeDeltaOp::SubWrap { floor: 0, wrap: 10 },
+                DataValue::from_i64(3),
+            )],
+        );
+        let response = ChangeFeedResponse::new(vec![insert, update], Some(52));
+        let frame = Frame::decode(&encode_change_feed_response(3, &response).unwrap()).unwrap();
+        let decoded = decode_change_feed_response(&frame).unwrap();
+        assert_eq!(decoded.last_lsn(), Some(52));
+        assert_eq!(decoded.events().len(), 2);
+        assert_eq!(decoded.events()[1].kind(), ChangeKind::Update);
+
+        let json = decoded.to_json().unwrap();
+        assert_eq!(json["last_lsn"], 52);
+        let insert = &json["events"][0];
+        assert_eq!(insert["kind"], "insert");
+        assert_eq!(insert["table_id"], u128::MAX.to_string());
+        assert_eq!(insert["row"]["id"], 1);
+        assert!(insert["row"]["qty"].is_null());
+        let delta = &json["events"][1]["deltas"][0];
+        assert_eq!(delta["column"], "qty");
+        assert_eq!(delta["op"], "sub_wrap");
+        assert_eq!(delta["operand"], 3);
+        assert_eq!(delta["wrap"], 10);
+    }
+
     #[test]
     fn error_response_roundtrip() {
         let frame = Frame::decode(&encode_error_response(99, "boom").unwrap()).unwrap();
@@ -222,13 +290,16 @@ mod tests {
             (11, MessageType::ProcedureInvoke),
             (12, MessageType::SessionCreate),
             (13, MessageType::SessionClose),
+            (14, MessageType::ChangeFeed),
+            (15, MessageType::Fetch),
+            (16, MessageType::CloseCursor),
         ];
         for (value, expected) in cases {
             assert_eq!(MessageType::try_from(value).unwrap(), expected);
             assert_eq!(u32::from(expected), value);
         }
         assert!(MessageType::try_from(0).is_err());
-        assert!(MessageType::try_from(14).is_err());
+        assert!(MessageType::try_from(17).is_err());
     }
 
     #[test]
@@ -343,6 +414,108 @@ mod tests {
         assert!(decoded.params().is_empty());
     }
 
+    #[test]
+    fn client_request_fetch_size_roundtrip_and_legacy_layout() {
+        let request = ClientRequest::new_with_oid(5, "app", "select 1").with_fetch_size(100);
+        let frame = Frame::decode(&encode_client_request(1, &request).unwrap()).unwrap();
+        assert_eq!(decode_client_request(&frame).unwrap().fetch_size(), 100);
+
+        // Without a fetch size the payload keeps the layout older servers
+        // decode.
+        let request = ClientRequest::new_with_oid(5, "app", "select 1");
+        let frame = Frame::decode(&encode_client_request(1, &request).unwrap()).unwrap();
+        let legacy = rmp_serde::to_vec(&(
+            5u128,
+            "app".to_string(),
+            "select 1".to_string(),
+            Vec::<DataValue>::new(),
+        ))
+        .unwrap();
+        assert_eq!(frame.payload(), legacy.as_slice());
+        assert_eq!(decode_client_request(&frame).unwrap().fetch_size(), 0);
+    }
+
+    #[test]
+    fn range_scan_page_roundtrip() {
+        let request = RangeScanRequest::new(9, b"a".to_vec(), b"z".to_vec())
+            .with_page(2, Some(b"b".to_vec()));
+        let frame = Frame::decode(&encode_range_scan_request(1, &request).unwrap()).unwrap();
+        let decoded = decode_range_scan_request(&frame).unwrap();
+        let page = decoded.page().unwrap();
+        assert_eq!(page.limit(), 2);
+        assert_eq!(page.continuation(), Some(&b"b"[..]));
+
+        let legacy = rmp_serde::to_vec(&(9u128, b"a".to_vec(), b"z".to_vec())).unwrap();
+        let decoded =
+            decode_range_scan_request(&Frame::new(MessageType::RangeScan, 2, legacy)).unwrap();
+        assert!(decoded.page().is_none());
+
+        let response = RangeScanResponse::new(vec![KeyValue::new(b"a".to_vec(), b"1".to_vec())])
+            .with_continuation(Some(b"a".to_vec()));
+        let frame = Frame::decode(&encode_range_scan_response(3, &response).unwrap()).unwrap();
+        let (items, continuation) = decode_range_scan_response(&frame).unwrap().into_parts();
+        assert_eq!(items.len(), 1);
+        assert_eq!(continuation, Some(b"a".to_vec()));
+
+        let frame = Frame::decode(
+            &encode_range_scan_response(4, &RangeScanResponse::new(Vec::new())).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            decode_range_scan_response(&frame).unwrap().continuation(),
+            None
+        );
+    }
+
+    #[test]
+    fn fetch_and_close_cursor_roundtrip() {
+        use crate::tuple::datum_desc::DatumDesc;
+        use crate::tuple::tuple_field_desc::TupleFieldDesc;
+        use crate::tuple::tuple_value::TupleValue;
+        use mudu_type::data_type::DataType;
+        use mudu_type::type_family::TypeFamily;
+
+        let frame =
+            Frame::decode(&encode_fetch_request(1, &FetchRequest::new(9, 3, 50)).unwrap()).unwrap();
+        assert_eq!(frame.header().message_type(), MessageType::Fetch);
+        let request = decode_fetch_request(&frame).unwrap();
+        assert_eq!(
+            (
+                request.session_id(),
+                request.cursor_id(),
+                request.max_rows()
+            ),
+            (9, 3, 50)
+        );
+
+        let row_desc = TupleFieldDesc::new(vec![DatumDesc::new(
+            "id".to_string(),
+            DataType::default_for(TypeFamily::I32),
+        )]);
+        let response = FetchResponse::new(
+            row_desc,
+            vec![TupleValue::from(vec![DataValue::from_i32(7)])],
+            Some(3),
+        );
+        let frame = Frame::decode(&encode_fetch_response(2, &response).unwrap()).unwrap();
+        let decoded = decode_fetch_response(&frame).unwrap();
+        assert_eq!(decoded.row_desc().fields()[0].name(), "id");
+        assert_eq!(decoded.rows()[0].values()[0].expect_i32(), &7);
+        assert_eq!(decoded.cursor_id(), Some(3));
+
+        let frame =
+            Frame::decode(&encode_close_cursor_request(3, &CloseCursorRequest::new(9, 3)).unwrap())
+                .unwrap();
+        assert_eq!(frame.header().message_type(), MessageType::CloseCursor);
+        let request = decode_close_cursor_request(&frame).unwrap();
+        assert_eq!((request.session_id(), request.cursor_id()), (9, 3));
+        let frame = Frame::decode(
+            &encode_close_cursor_response(4, &CloseCursorResponse::new(true)).unwrap(),
+        )
+        .unwrap();
+        assert!(decode_close_cursor_response(&frame).unwrap().closed());
+    }
+
     #[test]
     fn server_response_accessors_and_perf_digest() {
         use crate::tuple::datum_desc::DatumDesc;
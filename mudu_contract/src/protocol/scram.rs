@@ -0,0 +1,278 @@
+//! SCRAM-SHA-256 authentication (RFC 5802 / RFC 7677).
+//!
+//! The exchange rides on [`MessageType::Auth`](super::MessageType::Auth)
+//! frames as msgpack-encoded [`AuthRequest`] / [`AuthResponse`] values
+//! instead of the RFC's text attributes, but the key derivation and the
+//! signed auth message follow the RFC, so a password never crosses the wire
+//! and the server only stores the derived [`ScramCredential`]:
+//!
+//! 1. client → `ClientFirst { user, client_nonce }`
+//! 2. server → `ServerFirst { nonce, salt, iterations }`, where `nonce`
+//!    starts with the client nonce
+//! 3. client → `ClientFinal { nonce, proof }`
+//! 4. server → `ServerFinal { server_signature }` once the proof verifies
+//!
+//! [`ScramClient`] drives the client side; the server side is
+//! [`ScramCredential::verify_client_proof`] plus
+//! [`ScramCredential::server_signature`].
+
+use super::{AuthRequest, AuthResponse};
+use hmac::digest::KeyInit;
+use hmac::{Hmac, Mac};
+use mudu::common::result::RS;
+use mudu::error::ErrorCode;
+use mudu::mudu_error;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Mechanism name, for logs and error messages.
+pub const SCRAM_SHA_256: &str = "SCRAM-SHA-256";
+/// Iteration count used for new credentials.
+pub const DEFAULT_SCRAM_ITERATIONS: u32 = 4096;
+/// Lowest iteration count a client accepts from a server (RFC 7677).
+pub const MIN_SCRAM_ITERATIONS: u32 = 4096;
+
+const SALT_LEN: usize = 16;
+
+/// Server-side SCRAM-SHA-256 credential of one user.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScramCredential {
+    salt: Vec<u8>,
+    iterations: u32,
+    stored_key: Vec<u8>,
+    server_key: Vec<u8>,
+}
+
+impl ScramCredential {
+    /// Derive a credential for `password` with a fresh random salt.
+    pub fn generate(password: &str, iterations: u32) -> RS<Self> {
+        Self::from_password(password, new_salt(), iterations)
+    }
+
+    /// Derive a credential for `password` with the given salt.
+    pub fn from_password(password: &str, salt: Vec<u8>, iterations: u32) -> RS<Self> {
+        if iterations == 0 {
+            return Err(mudu_error!(
+                ErrorCode::InvalidArgument,
+                "scram iteration count must be positive"
+            ));
+        }
+        let salted = salted_password(password, &salt, iterations)?;
+        let client_key = hmac_sha256(&salted, b"Client Key")?;
+        Ok(Self {
+            salt,
+            iterations,
+            stored_key: sha256(&client_key),
+            server_key: hmac_sha256(&salted, b"Server Key")?,
+        })
+    }
+
+    pub fn salt(&self) -> &[u8] {
+        &self.salt
+    }
+
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    /// Check a client proof against `auth_message`: the proof XOR the client
+    /// signature must hash to the stored key.
+    pub fn verify_client_proof(&self, auth_message: &[u8], proof: &[u8]) -> RS<bool> {
+        let client_signature = hmac_sha256(&self.stored_key, auth_message)?;
+        if proof.len() != client_signature.len() {
+            return Ok(false);
+        }
+        let client_key = xor(proof, &client_signature);
+        Ok(constant_time_eq(&sha256(&client_key), &self.stored_key))
+    }
+
+    /// Check a clear-text password, for transports that carry one (the HTTP
+    /// token endpoint).
+    pub fn verify_password(&self, password: &str) -> RS<bool> {
+        let derived = Self::from_password(password, self.salt.clone(), self.iterations)?;
+        Ok(constant_time_eq(&derived.stored_key, &self.stored_key))
+    }
+
+    /// Server signature over `auth_message`, returned in `ServerFinal`.
+    pub fn server_signature(&self, auth_message: &[u8]) -> RS<Vec<u8>> {
+        hmac_sha256(&self.server_key, auth_message)
+    }
+}
+
+/// Build the auth message both sides sign:
+/// `client-first-bare,server-first,client-final-without-proof`.
+pub fn auth_message(
+    user: &str,
+    client_nonce: &str,
+    nonce: &str,
+    salt: &[u8],
+    iterations: u32,
+) -> Vec<u8> {
+    format!(
+        "n={},r={},r={},s={},i={},c=biws,r={}",
+        escape_user(user),
+        client_nonce,
+        nonce,
+        hex(salt),
+        iterations,
+        nonce
+    )
+    .into_bytes()
+}
+
+/// A fresh random nonce.
+pub fn new_nonce() -> String {
+    format!(
+        "{:032x}{:032x}",
+        mudu_sys::random::uuid_v4().as_u128(),
+        mudu_sys::random::uuid_v4().as_u128()
+    )
+}
+
+/// A fresh random salt.
+pub fn new_salt() -> Vec<u8> {
+    mudu_sys::random::uuid_v4().as_u128().to_be_bytes()[..SALT_LEN].to_vec()
+}
+
+/// A salt derived from `key` and `user`, sent for users that do not exist so
+/// a server answers every `ClientFirst` alike and a repeated probe for the
+/// same name sees the same salt.
+pub fn mock_salt(key: &[u8], user: &str) -> RS<Vec<u8>> {
+    let mut salt = hmac_sha256(key, user.as_bytes())?;
+    salt.truncate(SALT_LEN);
+    Ok(salt)
+}
+
+/// Client side of the exchange; see the module docs.
+pub struct ScramClient {
+    user: String,
+    password: String,
+    client_nonce: String,
+    expected_server_signature: Option<Vec<u8>>,
+}
+
+impl ScramClient {
+    pub fn new(user: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            user: user.into(),
+            password: password.into(),
+            client_nonce: new_nonce(),
+            expected_server_signature: None,
+        }
+    }
+
+    pub fn client_first(&self) -> AuthRequest {
+        AuthRequest::ClientFirst {
+            user: self.user.clone(),
+            client_nonce: self.client_nonce.clone(),
+        }
+    }
+
+    /// Answer the server challenge with the client proof.
+    pub fn client_final(&mut self, server_first: AuthResponse) -> RS<AuthRequest> {
+        let AuthResponse::ServerFirst {
+            nonce,
+            salt,
+            iterations,
+        } = server_first
+        else {
+            return Err(mudu_error!(
+                ErrorCode::PermissionDenied,
+                "expected scram server-first message"
+            ));
+        };
+        if !nonce.starts_with(&self.client_nonce) || nonce.len() == self.client_nonce.len() {
+            return Err(mudu_error!(
+                ErrorCode::PermissionDenied,
+                "scram server nonce does not extend the client nonce"
+            ));
+        }
+        if iterations < MIN_SCRAM_ITERATIONS {
+            return Err(mudu_error!(
+                ErrorCode::PermissionDenied,
+                format!(
+                    "scram iteration count {} is below the minimum {}",
+                    iterations, MIN_SCRAM_ITERATIONS
+                )
+            ));
+        }
+        let salted = salted_password(&self.password, &salt, iterations)?;
+        let client_key = hmac_sha256(&salted, b"Client Key")?;
+        let stored_key = sha256(&client_key);
+        let server_key = hmac_sha256(&salted, b"Server Key")?;
+        let message = auth_message(&self.user, &self.client_nonce, &nonce, &salt, iterations);
+        let client_signature = hmac_sha256(&stored_key, &message)?;
+        self.expected_server_signature = Some(hmac_sha256(&server_key, &message)?);
+        Ok(AuthRequest::ClientFinal {
+            nonce,
+            proof: xor(&client_key, &client_signature),
+        })
+    }
+
+    /// Authenticate the server by its signature.
+    pub fn verify_server_final(&self, server_final: AuthResponse) -> RS<()> {
+        let AuthResponse::ServerFinal { server_signature } = server_final else {
+            return Err(mudu_error!(
+                ErrorCode::PermissionDenied,
+                "expected scram server-final message"
+            ));
+        };
+        match &self.expected_server_signature {
+            Some(expected) if constant_time_eq(expected, &server_signature) => Ok(()),
+            Some(_) => Err(mudu_error!(
+                ErrorCode::PermissionDenied,
+                "scram server signature mismatch"
+            )),
+            None => Err(mudu_error!(
+                ErrorCode::PermissionDenied,
+                "scram server-final received before client-final"
+            )),
+        }
+    }
+}
+
+/// `Hi()` of RFC 5802, i.e. PBKDF2-HMAC-SHA-256 with one output block.
+pub(crate) fn salted_password(password: &str, salt: &[u8], iterations: u32) -> RS<Vec<u8>> {
+    let mut block = salt.to_vec();
+    block.extend_from_slice(&1u32.to_be_bytes());
+    let mut u = hmac_sha256(password.as_bytes(), &block)?;
+    let mut result = u.clone();
+    for _ in 1..iterations {
+        u = hmac_sha256(password.as_bytes(), &u)?;
+        result.iter_mut().zip(u.iter()).for_each(|(r, x)| *r ^= x);
+    }
+    Ok(result)
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> RS<Vec<u8>> {
+    let mut mac = <HmacSha256 as KeyInit>::new_from_slice(key)
+        .map_err(|e| mudu_error!(ErrorCode::Internal, format!("hmac key error: {}", e)))?;
+    mac.update(data);
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+fn sha256(data: &[u8]) -> Vec<u8> {
+    Sha256::digest(data).to_vec()
+}
+
+fn xor(a: &[u8], b: &[u8]) -> Vec<u8> {
+    a.iter().zip(b.iter()).map(|(x, y)| x ^ y).collect()
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b.iter())
+            .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+            == 0
+}
+
+fn escape_user(user: &str) -> String {
+    user.replace('=', "=3D").replace(',', "=2C")
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
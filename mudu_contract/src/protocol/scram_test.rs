@@ -0,0 +1,123 @@
+//! Tests for the SCRAM-SHA-256 exchange.
+#![allow(missing_docs, clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+use super::scram::*;
+use super::*;
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Plays the server side of the exchange against `credential`.
+fn server_first(credential: &ScramCredential, request: &AuthRequest) -> (AuthResponse, Vec<u8>) {
+    let AuthRequest::ClientFirst { user, client_nonce } = request else {
+        panic!("expected client-first");
+    };
+    let nonce = format!("{}{}", client_nonce, new_nonce());
+    let message = auth_message(
+        user,
+        client_nonce,
+        &nonce,
+        credential.salt(),
+        credential.iterations(),
+    );
+    (
+        AuthResponse::ServerFirst {
+            nonce,
+            salt: credential.salt().to_vec(),
+            iterations: credential.iterations(),
+        },
+        message,
+    )
+}
+
+#[test]
+fn salted_password_matches_pbkdf2_hmac_sha256_vectors() {
+    assert_eq!(
+        hex(&salted_password("password", b"salt", 1).unwrap()),
+        "120fb6cffcf8b32c43e7225256c4f837a86548c92ccc35480805987cb70be17b"
+    );
+    assert_eq!(
+        hex(&salted_password("password", b"salt", 4096).unwrap()),
+        "c5e478d59288c841aa530db6845c4c8d962893a001ce4e11a4963873aa98134a"
+    );
+}
+
+#[test]
+fn scram_exchange_authenticates_both_sides() {
+    let credential = ScramCredential::generate("pencil", DEFAULT_SCRAM_ITERATIONS).unwrap();
+    let mut client = ScramClient::new("user", "pencil");
+    let (challenge, message) = server_first(&credential, &client.client_first());
+
+    let AuthRequest::ClientFinal { nonce, proof } = client.client_final(challenge).unwrap() else {
+        panic!("expected client-final");
+    };
+    assert!(message.ends_with(nonce.as_bytes()));
+    assert!(credential.verify_client_proof(&message, &proof).unwrap());
+
+    let server_final = AuthResponse::ServerFinal {
+        server_signature: credential.server_signature(&message).unwrap(),
+    };
+    client.verify_server_final(server_final).unwrap();
+    assert!(credential.verify_password("pencil").unwrap());
+}
+
+#[test]
+fn scram_rejects_wrong_password_and_forged_server() {
+    let credential = ScramCredential::generate("pencil", DEFAULT_SCRAM_ITERATIONS).unwrap();
+    let mut client = ScramClient::new("user", "crayon");
+    let (challenge, message) = server_first(&credential, &client.client_first());
+    let AuthRequest::ClientFinal { proof, .. } = client.client_final(challenge).unwrap() else {
+        panic!("expected client-final");
+    };
+    assert!(!credential.verify_client_proof(&message, &proof).unwrap());
+    assert!(!credential.verify_client_proof(&message, &[]).unwrap());
+    assert!(!credential.verify_password("crayon").unwrap());
+
+    let forged = AuthResponse::ServerFinal {
+        server_signature: vec![0; 32],
+    };
+    assert!(client.verify_server_final(forged).is_err());
+}
+
+#[test]
+fn scram_client_rejects_bad_challenges() {
+    let mut client = ScramClient::new("user", "pencil");
+    let AuthRequest::ClientFirst { client_nonce, .. } = client.client_first() else {
+        panic!("expected client-first");
+    };
+    let foreign_nonce = AuthResponse::ServerFirst {
+        nonce: format!("x{}", client_nonce),
+        salt: new_salt(),
+        iterations: DEFAULT_SCRAM_ITERATIONS,
+    };
+    assert!(client.client_final(foreign_nonce).is_err());
+
+    let weak = AuthResponse::ServerFirst {
+        nonce: format!("{}{}", client_nonce, new_nonce()),
+        salt: new_salt(),
+        iterations: MIN_SCRAM_ITERATIONS - 1,
+    };
+    assert!(client.client_final(weak).is_err());
+}
+
+#[test]
+fn auth_messages_roundtrip() {
+    let request = AuthRequest::ClientFinal {
+        nonce: "abc".to_string(),
+        proof: vec![1, 2, 3],
+    };
+    let frame = Frame::decode(&encode_auth_request(5, &request).unwrap()).unwrap();
+    assert_eq!(frame.header().message_type(), MessageType::Auth);
+    assert_eq!(frame.header().request_id(), 5);
+    assert_eq!(decode_auth_request(&frame).unwrap(), request);
+
+    let response = AuthResponse::ServerFirst {
+        nonce: "abcdef".to_string(),
+        salt: vec![9; 16],
+        iterations: 4096,
+    };
+    let frame = Frame::decode(&encode_auth_response(5, &response).unwrap()).unwrap();
+    assert_eq!(frame.header().message_type(), MessageType::Response);
+    assert_eq!(decode_auth_response(&frame).unwrap(), response);
+}
@@ -31,6 +31,8 @@ impl_scalar! {
     (Time, "string"),
     (Timestamp, "string"),
     (TimestampTz, "string"),
+    (Uuid, "Uint8Array"),
+    (Json, "string"),
 }
 
 impl_non_scalar! {
@@ -40,6 +40,8 @@ pub fn csharp_default_value_expr(wit_ty: &UniDataType) -> RS<String> {
             UniScalar::Time => "string.Empty".to_string(),
             UniScalar::Timestamp => "string.Empty".to_string(),
             UniScalar::TimestampTz => "string.Empty".to_string(),
+            UniScalar::Uuid => "[]".to_string(),
+            UniScalar::Json => "string.Empty".to_string(),
         }),
         UniDataType::Tuple(_) => Ok("default".to_string()),
         UniDataType::Array(_) => Ok("[]".to_string()),
@@ -76,12 +78,13 @@ pub fn assemblyscript_default_value_expr(wit_ty: &UniDataType) -> RS<String> {
             | UniScalar::F32
             | UniScalar::F64 => "0".to_string(),
             UniScalar::Char | UniScalar::String => "\"\"".to_string(),
-            UniScalar::Blob => "new Uint8Array(0)".to_string(),
+            UniScalar::Blob | UniScalar::Uuid => "new Uint8Array(0)".to_string(),
             UniScalar::Numeric
             | UniScalar::Date
             | UniScalar::Time
             | UniScalar::Timestamp
-            | UniScalar::TimestampTz => "\"\"".to_string(),
+            | UniScalar::TimestampTz
+            | UniScalar::Json => "\"\"".to_string(),
         }),
         UniDataType::Tuple(_) => Ok("[]".to_string()),
         UniDataType::Array(_) => Ok("[]".to_string()),
@@ -113,6 +116,8 @@ pub fn csharp_is_reference_type(wit_ty: &UniDataType) -> bool {
                     | UniScalar::Time
                     | UniScalar::Timestamp
                     | UniScalar::TimestampTz
+                    | UniScalar::Uuid
+                    | UniScalar::Json
             )
         }
         UniDataType::Tuple(_) => false,
@@ -0,0 +1,66 @@
+use crate::contract::cmd_exec::CmdExec;
+use crate::contract::meta_mgr::MetaMgr;
+use crate::x_engine::api::XContract;
+use crate::x_engine::x_param::PAlterPartitionRule;
+use async_trait::async_trait;
+use mudu::common::result::RS;
+use mudu::error::ErrorCode as ER;
+use mudu::mudu_error;
+use std::sync::Arc;
+
+pub struct AlterPartitionRule {
+    param: PAlterPartitionRule,
+    x_contract: Arc<dyn XContract>,
+    meta_mgr: Arc<dyn MetaMgr>,
+}
+
+impl AlterPartitionRule {
+    pub fn new(
+        param: PAlterPartitionRule,
+        x_contract: Arc<dyn XContract>,
+        meta_mgr: Arc<dyn MetaMgr>,
+    ) -> Self {
+        Self {
+            param,
+            x_contract,
+            meta_mgr,
+        }
+    }
+}
+
+#[async_trait]
+impl CmdExec for AlterPartitionRule {
+    async fn prepare(&self) -> RS<()> {
+        let current = self
+            .meta_mgr
+            .get_partition_rule_by_id(self.param.rule.oid)
+            .await?;
+        if current.version >= self.param.rule.version {
+            return Err(mudu_error!(
+                ER::InvalidState,
+                format!(
+                    "partition rule {} changed concurrently",
+                    self.param.rule.name
+                )
+            ));
+        }
+        Ok(())
+    }
+
+    // Rows are moved by the x-contract; the rule is switched in the catalog
+    // only once they have been copied.
+    async fn run(&self) -> RS<()> {
+        mudu_utils::scoped_task_trace!();
+        self.x_contract
+            .alter_partition_rule(
+                self.param.tx_mgr.clone(),
+                &self.param.rule,
+                &self.param.placements,
+            )
+            .await
+    }
+
+    async fn affected_rows(&self) -> RS<u64> {
+        Ok(0)
+    }
+}
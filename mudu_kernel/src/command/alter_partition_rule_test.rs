@@ -0,0 +1,309 @@
+#![allow(clippy::unwrap_used)]
+use crate::command::alter_partition_rule::AlterPartitionRule;
+use crate::contract::cmd_exec::CmdExec;
+
+use crate::contract::meta_mgr::MetaMgr;
+use crate::contract::partition_rule::{PartitionBound, PartitionRuleDesc, RangePartitionDef};
+use crate::contract::partition_rule_binding::PartitionPlacement;
+use crate::contract::schema_table::SchemaTable;
+use crate::contract::table_desc::TableDesc;
+use crate::server::worker_snapshot::WorkerSnapshot;
+use crate::wal::xl_batch::XLBatch;
+use crate::x_engine::api::{
+    AlterTable, OptDelete, OptInsert, OptRead, OptUpdate, Predicate, RSCursor, RangeData, VecDatum,
+    VecSelTerm, XContract,
+};
+use crate::x_engine::tx_mgr::{PhysicalRelationId, TxMgr};
+use crate::x_engine::x_param::PAlterPartitionRule;
+use async_trait::async_trait;
+use mudu::common::buf::Buf;
+use mudu::common::id::OID;
+use mudu::common::result::RS;
+use mudu_sys::sync::SMutex;
+use mudu_type::type_family::TypeFamily;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+fn block_on<F>(fut: F) -> F::Output
+where
+    F: std::future::Future,
+{
+    mudu_sys::task::async_::build_current_thread_runtime()
+        .unwrap()
+        .block_on(fut)
+}
+
+fn sample_rule() -> PartitionRuleDesc {
+    PartitionRuleDesc::new_range(
+        "r".to_string(),
+        vec![TypeFamily::I64],
+        vec![RangePartitionDef::new(
+            "p0".to_string(),
+            PartitionBound::Unbounded,
+            PartitionBound::Unbounded,
+        )],
+    )
+}
+
+fn make_param(rule: PartitionRuleDesc, placements: Vec<PartitionPlacement>) -> PAlterPartitionRule {
+    PAlterPartitionRule {
+        tx_mgr: Arc::new(MockTxMgr),
+        rule,
+        placements,
+    }
+}
+
+struct MockTxMgr;
+
+impl TxMgr for MockTxMgr {
+    fn xid(&self) -> u64 {
+        1
+    }
+    fn snapshot(&self) -> WorkerSnapshot {
+        WorkerSnapshot::new(1, Vec::new())
+    }
+    fn put(&self, _key: Vec<u8>, _value: Vec<u8>) {}
+    fn delete(&self, _key: Vec<u8>) {}
+    fn get(&self, _key: &[u8]) -> Option<Option<Vec<u8>>> {
+        None
+    }
+    fn put_relation(&self, _relation_id: PhysicalRelationId, _key: Vec<u8>, _value: Vec<u8>) {}
+    fn delete_relation(&self, _relation_id: PhysicalRelationId, _key: Vec<u8>) {}
+    fn get_relation(
+        &self,
+        _relation_id: PhysicalRelationId,
+        _key: &[u8],
+    ) -> Option<Option<Vec<u8>>> {
+        None
+    }
+    fn staged_relation_items_in_range(
+        &self,
+        _relation_id: PhysicalRelationId,
+        _start_key: &[u8],
+        _end_key: &[u8],
+    ) -> Vec<(Vec<u8>, Option<Vec<u8>>)> {
+        Vec::new()
+    }
+    fn staged_relation_ops(
+        &self,
+    ) -> BTreeMap<PhysicalRelationId, BTreeMap<Vec<u8>, Option<Vec<u8>>>> {
+        BTreeMap::new()
+    }
+    fn staged_items_in_range(
+        &self,
+        _start_key: &[u8],
+        _end_key: &[u8],
+    ) -> Vec<(Vec<u8>, Option<Vec<u8>>)> {
+        Vec::new()
+    }
+    fn staged_put_items(&self) -> BTreeMap<Vec<u8>, Option<Vec<u8>>> {
+        BTreeMap::new()
+    }
+    fn is_empty(&self) -> bool {
+        true
+    }
+    fn write_ops(&self) -> Vec<(PhysicalRelationId, Vec<u8>)> {
+        Vec::new()
+    }
+    fn build_write_ops(&self) {}
+    fn xl_batch(&self) -> XLBatch {
+        XLBatch::new(Vec::new())
+    }
+}
+
+struct MockXContract {
+    altered: SMutex<Vec<(PartitionRuleDesc, Vec<PartitionPlacement>)>>,
+}
+
+impl MockXContract {
+    fn new() -> Self {
+        Self {
+            altered: SMutex::new(Vec::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl XContract for MockXContract {
+    async fn create_table(&self, _tx_mgr: Arc<dyn TxMgr>, _schema: &SchemaTable) -> RS<()> {
+        Ok(())
+    }
+    async fn drop_table(&self, _tx_mgr: Arc<dyn TxMgr>, _oid: OID) -> RS<()> {
+        Ok(())
+    }
+    async fn alter_table(
+        &self,
+        _tx_mgr: Arc<dyn TxMgr>,
+        _oid: OID,
+        _alter_table: &[AlterTable],
+    ) -> RS<()> {
+        Ok(())
+    }
+    async fn alter_partition_rule(
+        &self,
+        _tx_mgr: Arc<dyn TxMgr>,
+        rule: &PartitionRuleDesc,
+        placements: &[PartitionPlacement],
+    ) -> RS<()> {
+        self.altered
+            .lock()
+            .unwrap()
+            .push((rule.clone(), placements.to_vec()));
+        Ok(())
+    }
+    async fn begin_tx(&self) -> RS<Arc<dyn TxMgr>> {
+        Ok(Arc::new(MockTxMgr))
+    }
+    async fn commit_tx(&self, _tx_mgr: Arc<dyn TxMgr>) -> RS<()> {
+        Ok(())
+    }
+    async fn abort_tx(&self, _tx_mgr: Arc<dyn TxMgr>) -> RS<()> {
+        Ok(())
+    }
+    async fn update(
+        &self,
+        _tx_mgr: Arc<dyn TxMgr>,
+        _table_id: OID,
+        _pred_key: &VecDatum,
+        _pred_non_key: &Predicate,
+        _values: &VecDatum,
+        _opt_update: &OptUpdate,
+    ) -> RS<usize> {
+        Ok(0)
+    }
+    async fn read_key(
+        &self,
+        _tx_mgr: Arc<dyn TxMgr>,
+        _table_id: OID,
+        _pred_key: &VecDatum,
+        _select: &VecSelTerm,
+        _opt_read: &OptRead,
+    ) -> RS<Option<Vec<Option<Buf>>>> {
+        Ok(None)
+    }
+    async fn read_range(
+        &self,
+        _tx_mgr: Arc<dyn TxMgr>,
+        _table_id: OID,
+        _pred_key: &RangeData,
+        _pred_non_key: &Predicate,
+        _select: &VecSelTerm,
+        _opt_read: &OptRead,
+    ) -> RS<Arc<dyn RSCursor>> {
+        Err(mudu::mudu_error!(
+            mudu::error::ErrorCode::NotImplemented,
+            "mock read_range"
+        ))
+    }
+    async fn delete(
+        &self,
+        _tx_mgr: Arc<dyn TxMgr>,
+        _table_id: OID,
+        _pred_key: &VecDatum,
+        _pred_non_key: &Predicate,
+        _opt_delete: &OptDelete,
+    ) -> RS<usize> {
+        Ok(0)
+    }
+    async fn insert(
+        &self,
+        _tx_mgr: Arc<dyn TxMgr>,
+        _table_id: OID,
+        _keys: &VecDatum,
+        _values: &VecDatum,
+        _opt_insert: &OptInsert,
+    ) -> RS<()> {
+        Ok(())
+    }
+}
+
+struct MockMetaMgr {
+    rule: Option<PartitionRuleDesc>,
+}
+
+#[async_trait]
+impl MetaMgr for MockMetaMgr {
+    async fn initialize(&self) -> RS<()> {
+        Ok(())
+    }
+    async fn get_table_by_id(&self, oid: OID) -> RS<Arc<TableDesc>> {
+        Err(mudu::mudu_error!(
+            mudu::error::ErrorCode::EntityNotFound,
+            format!("no such table {}", oid)
+        ))
+    }
+    async fn get_table_by_name(&self, _name: &str) -> RS<Option<Arc<TableDesc>>> {
+        Ok(None)
+    }
+    async fn create_table(&self, _schema: &SchemaTable) -> RS<()> {
+        Ok(())
+    }
+    async fn drop_table(&self, _table_id: OID) -> RS<()> {
+        Ok(())
+    }
+    async fn get_partition_rule_by_id(&self, oid: OID) -> RS<PartitionRuleDesc> {
+        self.rule
+            .clone()
+            .filter(|rule| rule.oid == oid)
+            .ok_or_else(|| {
+                mudu::mudu_error!(
+                    mudu::error::ErrorCode::EntityNotFound,
+                    format!("no such partition rule {}", oid)
+                )
+            })
+    }
+}
+
+#[test]
+fn prepare_fails_when_rule_does_not_exist() {
+    let cmd = AlterPartitionRule::new(
+        make_param(sample_rule(), Vec::new()),
+        Arc::new(MockXContract::new()),
+        Arc::new(MockMetaMgr { rule: None }),
+    );
+    let err = block_on(async { cmd.prepare().await }).unwrap_err();
+    assert_eq!(err.ec(), mudu::error::ErrorCode::EntityNotFound);
+}
+
+#[test]
+fn prepare_fails_when_rule_changed_since_binding() {
+    let current = sample_rule();
+    let cmd = AlterPartitionRule::new(
+        make_param(current.clone(), Vec::new()),
+        Arc::new(MockXContract::new()),
+        Arc::new(MockMetaMgr {
+            rule: Some(current),
+        }),
+    );
+    let err = block_on(async { cmd.prepare().await }).unwrap_err();
+    assert_eq!(err.ec(), mudu::error::ErrorCode::InvalidState);
+}
+
+#[test]
+fn run_hands_rule_and_placements_to_x_contract() {
+    let current = sample_rule();
+    let mut altered = current.clone();
+    altered.version += 1;
+    let placements = vec![PartitionPlacement {
+        partition_id: current.partitions[0].partition_id,
+        worker_id: 42,
+    }];
+    let x_contract = Arc::new(MockXContract::new());
+    let cmd = AlterPartitionRule::new(
+        make_param(altered.clone(), placements.clone()),
+        x_contract.clone(),
+        Arc::new(MockMetaMgr {
+            rule: Some(current),
+        }),
+    );
+    block_on(async {
+        cmd.prepare().await?;
+        cmd.run().await
+    })
+    .unwrap();
+    assert_eq!(
+        x_contract.altered.lock().unwrap().as_slice(),
+        &[(altered, placements)]
+    );
+    assert_eq!(block_on(async { cmd.affected_rows().await }).unwrap(), 0);
+}
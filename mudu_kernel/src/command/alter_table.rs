@@ -0,0 +1,57 @@
+use crate::contract::cmd_exec::CmdExec;
+use crate::contract::meta_mgr::MetaMgr;
+use crate::x_engine::api::XContract;
+use crate::x_engine::x_param::PAlterTable;
+use async_trait::async_trait;
+use mudu::common::result::RS;
+use std::sync::Arc;
+
+pub struct AlterTable {
+    alter_param: PAlterTable,
+    x_contract: Arc<dyn XContract>,
+    meta_mgr: Arc<dyn MetaMgr>,
+}
+
+impl AlterTable {
+    pub fn new(
+        alter_param: PAlterTable,
+        x_contract: Arc<dyn XContract>,
+        meta_mgr: Arc<dyn MetaMgr>,
+    ) -> Self {
+        Self {
+            alter_param,
+            x_contract,
+            meta_mgr,
+        }
+    }
+}
+
+#[async_trait]
+impl CmdExec for AlterTable {
+    async fn prepare(&self) -> RS<()> {
+        if let Some(table_id) = self.alter_param.oid {
+            let _ = self.meta_mgr.get_table_by_id(table_id).await?;
+        }
+        Ok(())
+    }
+
+    // Actions are applied in statement order as one catalog change: either
+    // all of them take effect or none does.
+    async fn run(&self) -> RS<()> {
+        mudu_utils::scoped_task_trace!();
+        if let Some(table_id) = self.alter_param.oid {
+            self.x_contract
+                .alter_table(
+                    self.alter_param.tx_mgr.clone(),
+                    table_id,
+                    &self.alter_param.actions,
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn affected_rows(&self) -> RS<u64> {
+        Ok(0)
+    }
+}
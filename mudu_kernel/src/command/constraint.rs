@@ -0,0 +1,665 @@
+//! DML hooks that enforce column defaults and table constraints.
+//!
+//! These hooks run inside the key/value DML executors and
+//! - fill the columns an `INSERT` omits from their `DEFAULT`,
+//! - reject a row whose `UNIQUE` columns match another row,
+//! - reject a row whose `FOREIGN KEY` columns reference no parent row,
+//! - reject a row for which a `CHECK` condition is FALSE,
+//! - restrict or cascade the delete of a parent row that is referenced.
+//!
+//! A key constraint whose columns hold a NULL is not checked, and a `CHECK`
+//! condition that is UNKNOWN passes. Lookups go through the secondary index
+//! backing the constraint, so they see the writes staged by the current
+//! transaction.
+//!
+//! Before a lookup the looked-up value is locked until the transaction ends
+//! (see [`XContract::lock_constraint_value`]): a unique value under its
+//! index, a referenced value under the parent table. Two transactions
+//! writing the same unique value, or a child row and the delete of its
+//! parent row, thus run their checks one after the other, and the second
+//! sees the rows the first committed.
+
+use std::future::Future;
+use std::ops::Bound;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+use mudu::common::id::{AttrIndex, OID};
+use mudu::common::result::RS;
+use mudu::data_type::timestamp::TimestampValue;
+use mudu::data_type::timestamptz::TimestampTzValue;
+use mudu::error::{ErrorCode, MuduError};
+use mudu::mudu_error;
+use mudu_sys::time::system_time_now;
+use mudu_type::data_value::DataValue;
+use mudu_type::datum::DatumDyn;
+use mudu_type::type_family::TypeFamily;
+
+use crate::command::fs_hook;
+use crate::contract::meta_mgr::MetaMgr;
+use crate::contract::schema_column::ColumnDefault;
+use crate::contract::schema_foreign_key::{ReferentialAction, SchemaForeignKey};
+use crate::contract::table_desc::{IndexDesc, TableDesc};
+use crate::server::x_contract::utils::eval_delta_value;
+use crate::x_engine::api::{
+    DeltaAssign, OptDelete, OptRead, Predicate, RangeData, VecDatum, VecSelTerm, XContract,
+};
+use crate::x_engine::data_bin::DataBin;
+use crate::x_engine::tx_mgr::TxMgr;
+
+/// The services the constraint hooks read and write through.
+pub(crate) struct ConstraintCtx<'a> {
+    pub meta_mgr: &'a Arc<dyn MetaMgr>,
+    pub x_contract: &'a Arc<dyn XContract>,
+    pub tx_mgr: &'a Arc<dyn TxMgr>,
+}
+
+/// A row referencing a deleted parent row through an `ON DELETE CASCADE`
+/// foreign key.
+pub(crate) struct CascadeRow {
+    desc: Arc<TableDesc>,
+    key: VecDatum,
+}
+
+/// Return true when `desc` has a column default, a unique index, a
+/// foreign key or a check constraint.
+pub(crate) fn has_constraints(desc: &TableDesc) -> bool {
+    desc.fields()
+        .iter()
+        .any(|field| field.default_value().is_some())
+        || desc.indexes().iter().any(|index| index.unique)
+        || !desc.foreign_keys().is_empty()
+        || !desc.checks().is_empty()
+}
+
+/// The columns of `desc` an update must check: those of a unique index,
+/// a foreign key or a check constraint, and those a foreign key of any
+/// table references.
+pub(crate) async fn constrained_attrs(
+    meta_mgr: &dyn MetaMgr,
+    desc: &TableDesc,
+) -> RS<Vec<AttrIndex>> {
+    let children = referencing_foreign_keys(meta_mgr, desc.id()).await?;
+    Ok((0..desc.fields().len())
+        .filter(|attr| is_constrained(desc, &children, *attr))
+        .collect())
+}
+
+/// Return true when a foreign key of any table references `table_id`.
+pub(crate) async fn is_referenced(meta_mgr: &dyn MetaMgr, table_id: OID) -> RS<bool> {
+    Ok(meta_mgr.list_schemas().await?.iter().any(|schema| {
+        schema
+            .foreign_keys()
+            .iter()
+            .any(|foreign_key| foreign_key.ref_table_oid() == table_id)
+    }))
+}
+
+/// Write the `DEFAULT` of every column in `default_attrs` into the key or
+/// value datum of the row.
+pub(crate) fn fill_defaults(
+    desc: &TableDesc,
+    default_attrs: &[AttrIndex],
+    key: &mut VecDatum,
+    value: &mut VecDatum,
+) -> RS<()> {
+    for attr in default_attrs {
+        let field = desc.get_attr(*attr);
+        let Some(default) = field.default_value() else {
+            continue;
+        };
+        let datum = match default {
+            ColumnDefault::Value(datum) => datum.clone(),
+            ColumnDefault::Now => {
+                let micros = system_time_now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_micros()
+                    .min(i64::MAX as u128) as i64;
+                let value = match field.type_desc().type_family() {
+                    TypeFamily::TimestampTz => {
+                        DataValue::from_timestamptz(TimestampTzValue::from_epoch_micros_utc(micros))
+                    }
+                    _ => DataValue::from_timestamp(TimestampValue::from_epoch_micros(micros)),
+                };
+                value.to_binary(field.type_desc())?.into()
+            }
+            ColumnDefault::GenOid => {
+                let oid = mudu_utils::oid::gen_oid();
+                let value = match field.type_desc().type_family() {
+                    TypeFamily::Uuid => DataValue::from_uuid(oid),
+                    _ => DataValue::from_u128(oid),
+                };
+                value.to_binary(field.type_desc())?.into()
+            }
+        };
+        let row = if field.is_primary() {
+            &mut *key
+        } else {
+            &mut *value
+        };
+        let mut data = std::mem::take(row).into_data();
+        data.retain(|(existing, _)| existing != attr);
+        data.push((*attr, datum));
+        *row = VecDatum::new(data);
+    }
+    Ok(())
+}
+
+/// Check the unique indexes and foreign keys of `desc` for a row about to
+/// be inserted.
+pub(crate) async fn check_insert(
+    ctx: &ConstraintCtx<'_>,
+    desc: &TableDesc,
+    key: &VecDatum,
+    value: &VecDatum,
+) -> RS<()> {
+    let mut row = vec![None; desc.fields().len()];
+    overlay(&mut row, key);
+    overlay(&mut row, value);
+    check_row(ctx, desc, &row, None).await
+}
+
+/// Check the constraints an update of the row `key` to `value` and
+/// `deltas` touches.
+///
+/// A delta assignment to a constrained column is evaluated on the current
+/// row, and the constraints are checked on the value it produces. Deferred
+/// deltas are applied at commit, after the check, so they cannot target a
+/// constrained column. Changing referenced columns of a parent row is
+/// rejected while referencing rows exist.
+pub(crate) async fn check_update(
+    ctx: &ConstraintCtx<'_>,
+    desc: &TableDesc,
+    key: &VecDatum,
+    value: &VecDatum,
+    deltas: &[DeltaAssign],
+) -> RS<()> {
+    let children = referencing_foreign_keys(ctx.meta_mgr.as_ref(), desc.id()).await?;
+    let constrained_deltas: Vec<&DeltaAssign> = deltas
+        .iter()
+        .filter(|delta| is_constrained(desc, &children, delta.attr))
+        .collect();
+    if let Some(delta) = constrained_deltas
+        .iter()
+        .find(|delta| delta.op.is_deferred())
+    {
+        return Err(mudu_error!(
+            ErrorCode::NotImplemented,
+            format!(
+                "deferred relative assignment to constrained column {} is not implemented",
+                desc.get_attr(delta.attr).name()
+            )
+        ));
+    }
+    let touched: Vec<AttrIndex> = value
+        .data()
+        .iter()
+        .map(|(attr, _)| *attr)
+        .chain(constrained_deltas.iter().map(|delta| delta.attr))
+        .collect();
+    if !touched
+        .iter()
+        .any(|attr| is_constrained(desc, &children, *attr))
+    {
+        return Ok(());
+    }
+    let select = VecSelTerm::new((0..desc.fields().len()).collect());
+    let Some(old_row) = ctx
+        .x_contract
+        .read_key(
+            ctx.tx_mgr.clone(),
+            desc.id(),
+            key,
+            &select,
+            &OptRead::default(),
+        )
+        .await?
+    else {
+        return Ok(());
+    };
+    let mut row = old_row.clone();
+    overlay(&mut row, value);
+    for delta in constrained_deltas {
+        let current = old_row.get(delta.attr).cloned().flatten();
+        row[delta.attr] = Some(eval_delta_value(current.as_deref(), delta, desc)?);
+    }
+    check_row(ctx, desc, &row, Some(touched.as_slice())).await?;
+    for (child_id, foreign_key) in &children {
+        let attrs = column_attrs(desc, foreign_key.ref_column_oids())?;
+        if !attrs.iter().any(|attr| touched.contains(attr)) {
+            continue;
+        }
+        let Some(old_values) = values_of(&old_row, &attrs) else {
+            continue;
+        };
+        if values_of(&row, &attrs).as_ref() == Some(&old_values) {
+            continue;
+        }
+        lock_values(ctx, desc.id(), &old_values).await?;
+        let child = ctx.meta_mgr.get_table_by_id(*child_id).await?;
+        let keys = referencing_keys(ctx, &child, foreign_key, &old_row, &attrs).await?;
+        if keys
+            .iter()
+            .any(|child_key| child.id() != desc.id() || !same_key(child_key, key))
+        {
+            return Err(foreign_key_violation(desc, &child, foreign_key));
+        }
+    }
+    Ok(())
+}
+
+/// Collect the rows referencing the row `key` about to be deleted.
+///
+/// An `ON DELETE RESTRICT` foreign key with referencing rows rejects the
+/// delete; the rows of `ON DELETE CASCADE` foreign keys are returned and
+/// shall be deleted with [`delete_cascade`] once the parent row is gone.
+pub(crate) async fn check_delete(
+    ctx: &ConstraintCtx<'_>,
+    desc: &TableDesc,
+    key: &VecDatum,
+) -> RS<Vec<CascadeRow>> {
+    let children = referencing_foreign_keys(ctx.meta_mgr.as_ref(), desc.id()).await?;
+    if children.is_empty() {
+        return Ok(Vec::new());
+    }
+    let select = VecSelTerm::new((0..desc.fields().len()).collect());
+    let Some(row) = ctx
+        .x_contract
+        .read_key(
+            ctx.tx_mgr.clone(),
+            desc.id(),
+            key,
+            &select,
+            &OptRead::default(),
+        )
+        .await?
+    else {
+        return Ok(Vec::new());
+    };
+    let mut cascade = Vec::new();
+    for (child_id, foreign_key) in &children {
+        let attrs = column_attrs(desc, foreign_key.ref_column_oids())?;
+        let Some(values) = values_of(&row, &attrs) else {
+            continue;
+        };
+        lock_values(ctx, desc.id(), &values).await?;
+        let child = ctx.meta_mgr.get_table_by_id(*child_id).await?;
+        let keys: Vec<VecDatum> = referencing_keys(ctx, &child, foreign_key, &row, &attrs)
+            .await?
+            .into_iter()
+            // A row referencing itself does not block its own delete.
+            .filter(|child_key| child.id() != desc.id() || !same_key(child_key, key))
+            .collect();
+        if keys.is_empty() {
+            continue;
+        }
+        match foreign_key.on_delete() {
+            ReferentialAction::Restrict => {
+                return Err(foreign_key_violation(desc, &child, foreign_key));
+            }
+            ReferentialAction::Cascade => cascade.extend(keys.into_iter().map(|key| CascadeRow {
+                desc: child.clone(),
+                key,
+            })),
+        }
+    }
+    Ok(cascade)
+}
+
+/// Delete the rows collected by [`check_delete`], and in turn the rows
+/// referencing them.
+pub(crate) fn delete_cascade<'a>(
+    ctx: &'a ConstraintCtx<'a>,
+    rows: Vec<CascadeRow>,
+) -> Pin<Box<dyn Future<Output = RS<()>> + Send + 'a>> {
+    Box::pin(async move {
+        for row in rows {
+            let desc = row.desc.as_ref();
+            let cascade = check_delete(ctx, desc, &row.key).await?;
+            let staged = fs_hook::unbind_fs_columns_on_delete(
+                ctx.meta_mgr,
+                ctx.x_contract,
+                ctx.tx_mgr,
+                desc.id(),
+                desc,
+                &row.key,
+            )
+            .await?;
+            let deleted = ctx
+                .x_contract
+                .delete(
+                    ctx.tx_mgr.clone(),
+                    desc.id(),
+                    &row.key,
+                    &Predicate::CNF(Vec::new()),
+                    &OptDelete::default(),
+                )
+                .await?;
+            if deleted > 0 {
+                fs_hook::stage_fs_ops(ctx.tx_mgr, staged);
+                delete_cascade(ctx, cascade).await?;
+            }
+        }
+        Ok(())
+    })
+}
+
+// Checks the check constraints, unique indexes and foreign keys of `row`, a
+// full row indexed by attribute. `touched` limits the check to constraints
+// over those columns.
+async fn check_row(
+    ctx: &ConstraintCtx<'_>,
+    desc: &TableDesc,
+    row: &[Option<DataBin>],
+    touched: Option<&[AttrIndex]>,
+) -> RS<()> {
+    let is_touched = |attrs: &[AttrIndex]| {
+        touched.is_none_or(|touched| attrs.iter().any(|attr| touched.contains(attr)))
+    };
+    for check in desc.checks() {
+        if !is_touched(&check.attrs) {
+            continue;
+        }
+        if check.condition.truth(row)? == Some(false) {
+            return Err(mudu_error!(
+                ErrorCode::ConstraintViolation,
+                format!(
+                    "new row for table {} violates check constraint {}",
+                    desc.name(),
+                    check.name
+                )
+            ));
+        }
+    }
+    for index in desc.indexes().iter().filter(|index| index.unique) {
+        let attrs: Vec<AttrIndex> = index.fields.iter().map(|f| f.column_index()).collect();
+        if !is_touched(&attrs) {
+            continue;
+        }
+        let Some(prefix) = index_prefix(index, row) else {
+            continue;
+        };
+        let values: Vec<DataBin> = prefix.iter().map(|(_, value)| value.clone()).collect();
+        lock_values(ctx, index.oid, &values).await?;
+        let own_key = values_of(row, desc.key_indices());
+        let keys = lookup_index(ctx, desc, index.oid, prefix).await?;
+        if keys.iter().any(|key| Some(key) != own_key.as_ref()) {
+            return Err(mudu_error!(
+                ErrorCode::ConstraintViolation,
+                format!(
+                    "duplicate key value violates unique constraint {}",
+                    index.name
+                )
+            ));
+        }
+    }
+    for foreign_key in desc.foreign_keys() {
+        let attrs = column_attrs(desc, foreign_key.column_oids())?;
+        if !is_touched(&attrs) {
+            continue;
+        }
+        let Some(values) = values_of(row, &attrs) else {
+            continue;
+        };
+        if foreign_key.ref_table_oid() == desc.id() {
+            // A row may reference itself.
+            let ref_attrs = column_attrs(desc, foreign_key.ref_column_oids())?;
+            if values_of(row, &ref_attrs).as_ref() == Some(&values) {
+                continue;
+            }
+        }
+        lock_values(ctx, foreign_key.ref_table_oid(), &values).await?;
+        let parent = ctx
+            .meta_mgr
+            .get_table_by_id(foreign_key.ref_table_oid())
+            .await?;
+        if !parent_exists(ctx, &parent, foreign_key, values).await? {
+            return Err(mudu_error!(
+                ErrorCode::ConstraintViolation,
+                format!(
+                    "insert or update on table {} violates foreign key {}: no referenced row in table {}",
+                    desc.name(),
+                    foreign_key.name(),
+                    parent.name()
+                )
+            ));
+        }
+    }
+    Ok(())
+}
+
+// Locks `values` of the unique index or referenced table `oid` until the
+// transaction ends. The values are length-prefixed, so distinct value lists
+// never share a lock key.
+async fn lock_values(ctx: &ConstraintCtx<'_>, oid: OID, values: &[DataBin]) -> RS<()> {
+    let mut key = Vec::new();
+    for value in values {
+        key.extend_from_slice(&(value.len() as u32).to_be_bytes());
+        key.extend_from_slice(value);
+    }
+    ctx.x_contract
+        .lock_constraint_value(ctx.tx_mgr.clone(), oid, key)
+        .await
+}
+
+// Whether `parent` has a row whose referenced columns equal `values`, given
+// in the order of the foreign key's referenced columns.
+async fn parent_exists(
+    ctx: &ConstraintCtx<'_>,
+    parent: &TableDesc,
+    foreign_key: &SchemaForeignKey,
+    values: Vec<DataBin>,
+) -> RS<bool> {
+    let attrs = column_attrs(parent, foreign_key.ref_column_oids())?;
+    let by_key = attrs.len() == parent.key_indices().len()
+        && attrs.iter().all(|attr| parent.key_indices().contains(attr));
+    if by_key {
+        let key = VecDatum::new(attrs.into_iter().zip(values).collect());
+        let select = VecSelTerm::new(parent.key_indices().clone());
+        let row = ctx
+            .x_contract
+            .read_key(
+                ctx.tx_mgr.clone(),
+                parent.id(),
+                &key,
+                &select,
+                &OptRead::default(),
+            )
+            .await?;
+        return Ok(row.is_some());
+    }
+    let index = parent
+        .indexes()
+        .iter()
+        .find(|index| {
+            index.unique
+                && index.fields.len() == attrs.len()
+                && index
+                    .fields
+                    .iter()
+                    .all(|field| attrs.contains(&field.column_index()))
+        })
+        .ok_or_else(|| {
+            mudu_error!(
+                ErrorCode::EntityNotFound,
+                format!("no unique index backs foreign key {}", foreign_key.name())
+            )
+        })?;
+    let mut row = vec![None; parent.fields().len()];
+    for (attr, value) in attrs.iter().zip(values) {
+        row[*attr] = Some(value);
+    }
+    let Some(prefix) = index_prefix(index, &row) else {
+        return Ok(false);
+    };
+    Ok(!lookup_index(ctx, parent, index.oid, prefix)
+        .await?
+        .is_empty())
+}
+
+// Keys of the `child` rows whose foreign key columns equal the columns
+// `parent_attrs` of the parent row `parent_row`.
+async fn referencing_keys(
+    ctx: &ConstraintCtx<'_>,
+    child: &TableDesc,
+    foreign_key: &SchemaForeignKey,
+    parent_row: &[Option<DataBin>],
+    parent_attrs: &[AttrIndex],
+) -> RS<Vec<VecDatum>> {
+    let Some(values) = values_of(parent_row, parent_attrs) else {
+        return Ok(Vec::new());
+    };
+    let index = child.index_by_oid(foreign_key.index_oid()).ok_or_else(|| {
+        mudu_error!(
+            ErrorCode::EntityNotFound,
+            format!("no index backs foreign key {}", foreign_key.name())
+        )
+    })?;
+    let attrs = column_attrs(child, foreign_key.column_oids())?;
+    let mut row = vec![None; child.fields().len()];
+    for (attr, value) in attrs.iter().zip(values) {
+        row[*attr] = Some(value);
+    }
+    let Some(prefix) = index_prefix(index, &row) else {
+        return Ok(Vec::new());
+    };
+    let keys = lookup_index(ctx, child, index.oid, prefix).await?;
+    Ok(keys
+        .into_iter()
+        .map(|key| VecDatum::new(child.key_indices().iter().copied().zip(key).collect()))
+        .collect())
+}
+
+// Primary key values of the rows of `desc` matching `prefix` on the index.
+async fn lookup_index(
+    ctx: &ConstraintCtx<'_>,
+    desc: &TableDesc,
+    index_oid: OID,
+    prefix: Vec<(AttrIndex, DataBin)>,
+) -> RS<Vec<Vec<DataBin>>> {
+    let cursor = ctx
+        .x_contract
+        .read_range(
+            ctx.tx_mgr.clone(),
+            desc.id(),
+            &RangeData::new(Bound::Unbounded, Bound::Unbounded),
+            &Predicate::IndexEq { index_oid, prefix },
+            &VecSelTerm::new(desc.key_indices().clone()),
+            &OptRead::default(),
+        )
+        .await?;
+    let mut keys = Vec::new();
+    while let Some(row) = cursor.next().await? {
+        keys.push(row.into_fields().into_iter().flatten().collect());
+    }
+    Ok(keys)
+}
+
+// Foreign keys of other tables, or of the table itself, that reference
+// `table_id`, with the id of the referencing table.
+async fn referencing_foreign_keys(
+    meta_mgr: &dyn MetaMgr,
+    table_id: OID,
+) -> RS<Vec<(OID, SchemaForeignKey)>> {
+    let mut foreign_keys = Vec::new();
+    for schema in meta_mgr.list_schemas().await? {
+        for foreign_key in schema.foreign_keys() {
+            if foreign_key.ref_table_oid() == table_id {
+                foreign_keys.push((schema.id(), foreign_key.clone()));
+            }
+        }
+    }
+    Ok(foreign_keys)
+}
+
+fn is_constrained(desc: &TableDesc, children: &[(OID, SchemaForeignKey)], attr: AttrIndex) -> bool {
+    let oid = desc.get_attr(attr).id();
+    desc.indexes()
+        .iter()
+        .filter(|index| index.unique)
+        .any(|index| index.fields.iter().any(|field| field.id() == oid))
+        || desc
+            .foreign_keys()
+            .iter()
+            .any(|foreign_key| foreign_key.column_oids().contains(&oid))
+        || desc
+            .checks()
+            .iter()
+            .any(|check| check.attrs.contains(&attr))
+        || children
+            .iter()
+            .any(|(_, foreign_key)| foreign_key.ref_column_oids().contains(&oid))
+}
+
+fn foreign_key_violation(
+    parent: &TableDesc,
+    child: &TableDesc,
+    foreign_key: &SchemaForeignKey,
+) -> MuduError {
+    mudu_error!(
+        ErrorCode::ConstraintViolation,
+        format!(
+            "update or delete on table {} violates foreign key {} on table {}",
+            parent.name(),
+            foreign_key.name(),
+            child.name()
+        )
+    )
+}
+
+fn column_attrs(desc: &TableDesc, column_oids: &[OID]) -> RS<Vec<AttrIndex>> {
+    column_oids
+        .iter()
+        .map(|oid| {
+            desc.oid2col()
+                .get(oid)
+                .map(|field| field.column_index())
+                .ok_or_else(|| {
+                    mudu_error!(
+                        ErrorCode::EntityNotFound,
+                        format!("no column {} in table {}", oid, desc.name())
+                    )
+                })
+        })
+        .collect()
+}
+
+// The index lookup prefix of `row`, or `None` when an indexed column is NULL.
+fn index_prefix(index: &IndexDesc, row: &[Option<DataBin>]) -> Option<Vec<(AttrIndex, DataBin)>> {
+    index
+        .fields
+        .iter()
+        .map(|field| {
+            let attr = field.column_index();
+            row.get(attr).cloned().flatten().map(|value| (attr, value))
+        })
+        .collect()
+}
+
+// The values of `attrs` in `row`, or `None` when one of them is NULL.
+fn values_of(row: &[Option<DataBin>], attrs: &[AttrIndex]) -> Option<Vec<DataBin>> {
+    attrs
+        .iter()
+        .map(|attr| row.get(*attr).cloned().flatten())
+        .collect()
+}
+
+// Key datums are compared by attribute, whatever their order.
+fn same_key(left: &VecDatum, right: &VecDatum) -> bool {
+    let sorted = |datum: &VecDatum| {
+        let mut data = datum.data().clone();
+        data.sort_by_key(|(attr, _)| *attr);
+        data
+    };
+    sorted(left) == sorted(right)
+}
+
+fn overlay(row: &mut [Option<DataBin>], datum: &VecDatum) {
+    for (attr, value) in datum.data() {
+        if let Some(slot) = row.get_mut(*attr) {
+            *slot = Some(value.clone());
+        }
+    }
+}
@@ -145,7 +145,7 @@ impl XContract for MockXContract {
         &self,
         _tx_mgr: Arc<dyn TxMgr>,
         _oid: OID,
-        _alter_table: &crate::x_engine::api::AlterTable,
+        _alter_table: &[crate::x_engine::api::AlterTable],
     ) -> RS<()> {
         Ok(())
     }
@@ -1,5 +1,7 @@
+use crate::command::constraint::{self, ConstraintCtx};
 use crate::command::fs_hook;
-use crate::contract::cmd_exec::CmdExec;
+use crate::command::returning::{read_returning_row, returned_rows};
+use crate::contract::cmd_exec::{CmdExec, ReturnedRows};
 use crate::contract::meta_mgr::MetaMgr;
 use crate::x_engine::api::{OptDelete, Predicate, XContract};
 use crate::x_engine::x_param::PDeleteKeyValue;
@@ -7,6 +9,7 @@ use async_trait::async_trait;
 use mudu::common::result::RS;
 use mudu::error::ErrorCode as ER;
 use mudu::mudu_error;
+use mudu_contract::tuple::tuple_field::TupleField;
 use mudu_sys::sync::async_::futures_mutex::FMutex;
 use mudu_utils::task_trace;
 use std::sync::Arc;
@@ -20,6 +23,7 @@ struct _DeleteKeyValue {
     x_contract: Arc<dyn XContract>,
     meta_mgr: Arc<dyn MetaMgr>,
     affected_rows: u64,
+    returned: Option<ReturnedRows>,
 }
 
 impl DeleteKeyValue {
@@ -45,6 +49,7 @@ impl _DeleteKeyValue {
             x_contract,
             meta_mgr,
             affected_rows: 0,
+            returned: None,
         }
     }
 
@@ -59,6 +64,26 @@ impl _DeleteKeyValue {
     async fn run(&mut self) -> RS<()> {
         // Delete currently stays on the exact-key path to keep semantics explicit.
         let desc = self.meta_mgr.get_table_by_id(self.param.table_id).await?;
+        let ctx = ConstraintCtx {
+            meta_mgr: &self.meta_mgr,
+            x_contract: &self.x_contract,
+            tx_mgr: &self.param.tx_mgr,
+        };
+        let cascade = constraint::check_delete(&ctx, desc.as_ref(), &self.param.key).await?;
+        // The `RETURNING` row is read before it is deleted.
+        let returning_row = match &self.param.returning {
+            Some(returning) => {
+                read_returning_row(
+                    &self.x_contract,
+                    &self.param.tx_mgr,
+                    self.param.table_id,
+                    &self.param.key,
+                    returning,
+                )
+                .await?
+            }
+            None => None,
+        };
         if fs_hook::has_fs_bound_columns(desc.as_ref()) {
             let staged = fs_hook::unbind_fs_columns_on_delete(
                 &self.meta_mgr,
@@ -81,8 +106,10 @@ impl _DeleteKeyValue {
                 .await?;
             if deleted > 0 {
                 fs_hook::stage_fs_ops(&self.param.tx_mgr, staged);
+                constraint::delete_cascade(&ctx, cascade).await?;
             }
             self.affected_rows = deleted as u64;
+            self.set_returned(returning_row);
             return Ok(());
         }
         let deleted = self
@@ -95,10 +122,21 @@ impl _DeleteKeyValue {
                 &OptDelete::default(),
             )
             .await?;
+        if deleted > 0 {
+            constraint::delete_cascade(&ctx, cascade).await?;
+        }
         self.affected_rows = deleted as u64;
+        self.set_returned(returning_row);
         Ok(())
     }
 
+    fn set_returned(&mut self, row: Option<TupleField>) {
+        if let Some(returning) = &self.param.returning {
+            let rows = row.filter(|_| self.affected_rows > 0).into_iter().collect();
+            self.returned = Some(returned_rows(returning, rows));
+        }
+    }
+
     fn affected_rows(&self) -> u64 {
         self.affected_rows
     }
@@ -132,4 +170,8 @@ impl CmdExec for DeleteKeyValue {
         trace.watch("cmd.stage", "affected_rows_done");
         Ok(inner.affected_rows())
     }
+
+    async fn returning(&self) -> RS<Option<ReturnedRows>> {
+        Ok(self.inner.lock().await.returned.clone())
+    }
 }
@@ -4,6 +4,8 @@ use crate::x_engine::api::XContract;
 use crate::x_engine::x_param::PDropTable;
 use async_trait::async_trait;
 use mudu::common::result::RS;
+use mudu::error::ErrorCode as ER;
+use mudu::mudu_error;
 use std::sync::Arc;
 
 pub struct DropTable {
@@ -30,7 +32,29 @@ impl DropTable {
 impl CmdExec for DropTable {
     async fn prepare(&self) -> RS<()> {
         if let Some(table_id) = self.drop_param.oid {
-            let _ = self.meta_mgr.get_table_by_id(table_id).await?;
+            let desc = self.meta_mgr.get_table_by_id(table_id).await?;
+            // A table may reference itself; other referencing tables shall be
+            // dropped first.
+            for schema in self.meta_mgr.list_schemas().await? {
+                if schema.id() == table_id {
+                    continue;
+                }
+                if let Some(foreign_key) = schema
+                    .foreign_keys()
+                    .iter()
+                    .find(|foreign_key| foreign_key.ref_table_oid() == table_id)
+                {
+                    return Err(mudu_error!(
+                        ER::ConstraintViolation,
+                        format!(
+                            "cannot drop table {}, foreign key {} on table {} references it",
+                            desc.name(),
+                            foreign_key.name(),
+                            schema.table_name()
+                        )
+                    ));
+                }
+            }
         }
         Ok(())
     }
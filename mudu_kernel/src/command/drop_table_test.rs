@@ -141,7 +141,7 @@ impl XContract for MockXContract {
         &self,
         _tx_mgr: Arc<dyn TxMgr>,
         _oid: OID,
-        _alter_table: &AlterTable,
+        _alter_table: &[AlterTable],
     ) -> RS<()> {
         Ok(())
     }
@@ -243,7 +243,7 @@ impl XContract for MockXContract {
         &self,
         _tx_mgr: Arc<dyn TxMgr>,
         _oid: OID,
-        _alter_table: &crate::x_engine::api::AlterTable,
+        _alter_table: &[crate::x_engine::api::AlterTable],
     ) -> RS<()> {
         Ok(())
     }
@@ -389,6 +389,9 @@ fn insert_assigns_fs_oid_and_stages_pending_row() {
                     VecDatum::new(vec![(0, i64_datum(1))]),
                     VecDatum::new(vec![(2, b"note".to_vec())]),
                 )],
+                default_attrs: vec![],
+                on_conflict: None,
+                returning: None,
             },
             x_contract.clone(),
             Arc::new(MockMetaMgr::new(desc)),
@@ -440,6 +443,9 @@ fn insert_rejects_explicit_fs_value() {
                     VecDatum::new(vec![(0, i64_datum(1))]),
                     VecDatum::new(vec![(1, encode_fs_oid_datum(gen_fs_oid()))]),
                 )],
+                default_attrs: vec![],
+                on_conflict: None,
+                returning: None,
             },
             x_contract.clone(),
             Arc::new(MockMetaMgr::new(desc)),
@@ -465,6 +471,9 @@ fn insert_on_remote_partition_is_rejected() {
                     VecDatum::new(vec![(0, i64_datum(1))]),
                     VecDatum::new(vec![(2, b"note".to_vec())]),
                 )],
+                default_attrs: vec![],
+                on_conflict: None,
+                returning: None,
             },
             x_contract.clone(),
             Arc::new(MockMetaMgr::new(desc).with_partition_worker(9)),
@@ -493,6 +502,8 @@ fn update_rebinds_fs_column_and_unbinds_old_oid() {
                 // Empty datum: the column is touched, the value is system assigned.
                 value: VecDatum::new(vec![(1, Vec::new())]),
                 delta_assignments: Vec::new(),
+                expr_assignments: Vec::new(),
+                returning: None,
             },
             x_contract.clone(),
             Arc::new(MockMetaMgr::new(desc)),
@@ -534,6 +545,8 @@ fn update_rejects_explicit_fs_value() {
                 key: VecDatum::new(vec![(0, i64_datum(1))]),
                 value: VecDatum::new(vec![(1, encode_fs_oid_datum(gen_fs_oid()))]),
                 delta_assignments: Vec::new(),
+                expr_assignments: Vec::new(),
+                returning: None,
             },
             x_contract.clone(),
             Arc::new(MockMetaMgr::new(desc)),
@@ -559,6 +572,8 @@ fn update_not_touching_fs_columns_is_unchanged() {
                 key: VecDatum::new(vec![(0, i64_datum(1))]),
                 value: value.clone(),
                 delta_assignments: Vec::new(),
+                expr_assignments: Vec::new(),
+                returning: None,
             },
             x_contract.clone(),
             Arc::new(MockMetaMgr::new(desc)),
@@ -585,6 +600,7 @@ fn delete_unbinds_referenced_fs_objects() {
                 tx_mgr: tx_mgr.clone(),
                 table_id: desc.id(),
                 key: VecDatum::new(vec![(0, i64_datum(1))]),
+                returning: None,
             },
             x_contract.clone(),
             Arc::new(MockMetaMgr::new(desc)),
@@ -612,6 +628,7 @@ fn delete_of_missing_row_stages_nothing() {
                 tx_mgr: tx_mgr.clone(),
                 table_id: desc.id(),
                 key: VecDatum::new(vec![(0, i64_datum(1))]),
+                returning: None,
             },
             x_contract.clone(),
             Arc::new(MockMetaMgr::new(desc)),
@@ -637,6 +654,9 @@ fn plain_table_dml_stages_no_fs_ops() {
                 tx_mgr: tx_mgr.clone(),
                 table_id,
                 rows: vec![(VecDatum::new(vec![(0, i64_datum(1))]), value.clone())],
+                default_attrs: vec![],
+                on_conflict: None,
+                returning: None,
             },
             x_contract.clone(),
             meta_mgr.clone(),
@@ -650,6 +670,8 @@ fn plain_table_dml_stages_no_fs_ops() {
                 key: VecDatum::new(vec![(0, i64_datum(1))]),
                 value: value.clone(),
                 delta_assignments: Vec::new(),
+                expr_assignments: Vec::new(),
+                returning: None,
             },
             x_contract.clone(),
             meta_mgr.clone(),
@@ -661,6 +683,7 @@ fn plain_table_dml_stages_no_fs_ops() {
                 tx_mgr: tx_mgr.clone(),
                 table_id,
                 key: VecDatum::new(vec![(0, i64_datum(1))]),
+                returning: None,
             },
             x_contract.clone(),
             meta_mgr,
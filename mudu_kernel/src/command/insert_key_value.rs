@@ -1,12 +1,20 @@
+use crate::command::constraint::{self, ConstraintCtx};
 use crate::command::fs_hook;
-use crate::contract::cmd_exec::CmdExec;
+use crate::command::returning::{read_returning_row, returned_rows};
+use crate::command::update_key_value::push_expr_values;
+use crate::contract::cmd_exec::{CmdExec, ReturnedRows};
 use crate::contract::meta_mgr::MetaMgr;
-use crate::x_engine::api::{OptInsert, XContract};
-use crate::x_engine::x_param::PInsertKeyValue;
+use crate::contract::table_desc::TableDesc;
+use crate::x_engine::api::{
+    OptInsert, OptRead, OptUpdate, Predicate, VecDatum, VecSelTerm, XContract,
+};
+use crate::x_engine::x_param::{PConflictAction, PInsertKeyValue};
 use async_trait::async_trait;
 use mudu::common::result::RS;
 use mudu::error::ErrorCode as ER;
 use mudu::mudu_error;
+use mudu_sys::sync::async_::futures_mutex::FMutex;
+use std::borrow::Cow;
 use std::sync::{
     atomic::{AtomicU64, Ordering},
     Arc,
@@ -17,6 +25,7 @@ pub struct InsertKeyValue {
     x_contract: Arc<dyn XContract>,
     meta_mgr: Arc<dyn MetaMgr>,
     affected_rows: AtomicU64,
+    returned: FMutex<Option<ReturnedRows>>,
 }
 
 impl InsertKeyValue {
@@ -30,6 +39,7 @@ impl InsertKeyValue {
             x_contract,
             meta_mgr,
             affected_rows: AtomicU64::new(0),
+            returned: FMutex::new(None),
         }
     }
 }
@@ -47,13 +57,22 @@ impl CmdExec for InsertKeyValue {
     async fn affected_rows(&self) -> RS<u64> {
         Ok(self.affected_rows.load(Ordering::Relaxed))
     }
+
+    async fn returning(&self) -> RS<Option<ReturnedRows>> {
+        Ok(self.returned.lock().await.clone())
+    }
 }
 
 impl InsertKeyValue {
     async fn prepare_inner(&self) -> RS<()> {
-        let _ = self.meta_mgr.get_table_by_id(self.param.table_id).await?;
+        let desc = self.meta_mgr.get_table_by_id(self.param.table_id).await?;
+        let default_key = self
+            .param
+            .default_attrs
+            .iter()
+            .any(|attr| desc.get_attr(*attr).is_primary());
         for (key, _value) in &self.param.rows {
-            if key.data().is_empty() {
+            if key.data().is_empty() && !default_key {
                 return Err(mudu_error!(ER::EntityNotFound, "key is empty"));
             }
         }
@@ -63,43 +82,218 @@ impl InsertKeyValue {
     async fn insert_inner(&self) -> RS<()> {
         mudu_utils::scoped_task_trace!();
         let desc = self.meta_mgr.get_table_by_id(self.param.table_id).await?;
+        let ctx = ConstraintCtx {
+            meta_mgr: &self.meta_mgr,
+            x_contract: &self.x_contract,
+            tx_mgr: &self.param.tx_mgr,
+        };
         let mut affected_rows = 0;
+        let mut returned = Vec::new();
         for (key, value) in &self.param.rows {
-            if fs_hook::has_fs_bound_columns(desc.as_ref()) {
-                let mut value = value.clone();
-                let staged = fs_hook::bind_fs_columns_on_insert(
-                    &self.meta_mgr,
-                    &self.x_contract,
-                    self.param.table_id,
+            let (key, value) = if constraint::has_constraints(desc.as_ref()) {
+                let (mut key, mut value) = (key.clone(), value.clone());
+                constraint::fill_defaults(
                     desc.as_ref(),
-                    key,
+                    &self.param.default_attrs,
+                    &mut key,
                     &mut value,
-                )
-                .await?;
-                self.x_contract
-                    .insert(
-                        self.param.tx_mgr.clone(),
-                        self.param.table_id,
-                        key,
-                        &value,
-                        &OptInsert::default(),
-                    )
-                    .await?;
-                fs_hook::stage_fs_ops(&self.param.tx_mgr, staged);
+                )?;
+                (Cow::Owned(key), Cow::Owned(value))
             } else {
-                self.x_contract
-                    .insert(
-                        self.param.tx_mgr.clone(),
-                        self.param.table_id,
-                        key,
-                        value,
-                        &OptInsert::default(),
-                    )
-                    .await?;
+                (Cow::Borrowed(key), Cow::Borrowed(value))
+            };
+            let (key, value) = (key.as_ref(), value.as_ref());
+            let written = match &self.param.on_conflict {
+                Some(action) => {
+                    self.upsert_row(&ctx, desc.as_ref(), key, value, action)
+                        .await?
+                }
+                None => {
+                    self.insert_row(&ctx, desc.as_ref(), key, value).await?;
+                    true
+                }
+            };
+            if !written {
+                continue;
             }
             affected_rows += 1;
+            if let Some(returning) = &self.param.returning {
+                if let Some(row) = read_returning_row(
+                    &self.x_contract,
+                    &self.param.tx_mgr,
+                    self.param.table_id,
+                    key,
+                    returning,
+                )
+                .await?
+                {
+                    returned.push(row);
+                }
+            }
         }
         self.affected_rows.store(affected_rows, Ordering::Relaxed);
+        if let Some(returning) = &self.param.returning {
+            *self.returned.lock().await = Some(returned_rows(returning, returned));
+        }
+        Ok(())
+    }
+
+    async fn insert_row(
+        &self,
+        ctx: &ConstraintCtx<'_>,
+        desc: &TableDesc,
+        key: &VecDatum,
+        value: &VecDatum,
+    ) -> RS<()> {
+        if constraint::has_constraints(desc) {
+            constraint::check_insert(ctx, desc, key, value).await?;
+        }
+        if fs_hook::has_fs_bound_columns(desc) {
+            let mut value = value.clone();
+            let staged = fs_hook::bind_fs_columns_on_insert(
+                &self.meta_mgr,
+                &self.x_contract,
+                self.param.table_id,
+                desc,
+                key,
+                &mut value,
+            )
+            .await?;
+            self.x_contract
+                .insert(
+                    self.param.tx_mgr.clone(),
+                    self.param.table_id,
+                    key,
+                    &value,
+                    &OptInsert::default(),
+                )
+                .await?;
+            fs_hook::stage_fs_ops(&self.param.tx_mgr, staged);
+        } else {
+            self.x_contract
+                .insert(
+                    self.param.tx_mgr.clone(),
+                    self.param.table_id,
+                    key,
+                    value,
+                    &OptInsert::default(),
+                )
+                .await?;
+        }
         Ok(())
     }
+
+    // Inserts the row, or applies the `ON CONFLICT` action when the key is
+    // taken; returns whether a row was written.
+    async fn upsert_row(
+        &self,
+        ctx: &ConstraintCtx<'_>,
+        desc: &TableDesc,
+        key: &VecDatum,
+        value: &VecDatum,
+        action: &PConflictAction,
+    ) -> RS<bool> {
+        // The probe spares an existing row the insert-side checks, but a
+        // concurrent session may insert the key right after it. The insert
+        // re-reads the key under its statement lock and reports that race as
+        // `EntityAlreadyExists`, which takes the conflict branch as well.
+        if !self.key_exists(key).await? {
+            match self.insert_row(ctx, desc, key, value).await {
+                Ok(()) => return Ok(true),
+                Err(e) if e.ec() != ER::EntityAlreadyExists => return Err(e),
+                Err(_) => {}
+            }
+        }
+        self.resolve_conflict(ctx, desc, key, value, action).await
+    }
+
+    async fn key_exists(&self, key: &VecDatum) -> RS<bool> {
+        let select = VecSelTerm::new(key.data().iter().map(|(attr, _)| *attr).collect());
+        let row = self
+            .x_contract
+            .read_key(
+                self.param.tx_mgr.clone(),
+                self.param.table_id,
+                key,
+                &select,
+                &OptRead::default(),
+            )
+            .await?;
+        Ok(row.is_some())
+    }
+
+    // Applies the `ON CONFLICT` action to the existing row `key`, given the
+    // proposed row; returns whether the existing row was updated.
+    async fn resolve_conflict(
+        &self,
+        ctx: &ConstraintCtx<'_>,
+        desc: &TableDesc,
+        key: &VecDatum,
+        proposed: &VecDatum,
+        action: &PConflictAction,
+    ) -> RS<bool> {
+        let PConflictAction::DoUpdate {
+            value,
+            delta_assignments,
+            expr_assignments,
+            excluded,
+        } = action
+        else {
+            return Ok(false);
+        };
+        let mut data = value.data().clone();
+        if !expr_assignments.is_empty() {
+            let width = desc.fields().len();
+            let Some(mut row) = self
+                .x_contract
+                .read_key(
+                    self.param.tx_mgr.clone(),
+                    self.param.table_id,
+                    key,
+                    &VecSelTerm::new((0..width).collect()),
+                    &OptRead::default(),
+                )
+                .await?
+            else {
+                // The row is gone; there is nothing to update.
+                return Ok(false);
+            };
+            // The `EXCLUDED` columns follow the existing ones.
+            row.resize(2 * width, None);
+            for (attr, binary) in key.data().iter().chain(proposed.data()) {
+                row[width + attr] = Some(binary.clone());
+            }
+            push_expr_values(desc, &row, expr_assignments, &mut data)?;
+        }
+        for (attr, source) in excluded {
+            // A NULL proposed value leaves the column unchanged, as
+            // `SET col = NULL` does.
+            if let Some((_, binary)) = proposed
+                .data()
+                .iter()
+                .find(|(proposed_attr, _)| proposed_attr == source)
+            {
+                data.push((*attr, binary.clone()));
+            }
+        }
+        let value = VecDatum::new(data);
+        if value.data().is_empty() && delta_assignments.is_empty() {
+            return Ok(true);
+        }
+        constraint::check_update(ctx, desc, key, &value, delta_assignments).await?;
+        let updated = self
+            .x_contract
+            .update(
+                self.param.tx_mgr.clone(),
+                self.param.table_id,
+                key,
+                &Predicate::CNF(Vec::new()),
+                &value,
+                &OptUpdate {
+                    delta_assignments: delta_assignments.clone(),
+                },
+            )
+            .await?;
+        Ok(updated > 0)
+    }
 }
@@ -59,6 +59,9 @@ fn make_param(rows: Vec<(VecDatum, VecDatum)>) -> PInsertKeyValue {
         tx_mgr: Arc::new(MockTxMgr),
         table_id: 1,
         rows,
+        default_attrs: vec![],
+        on_conflict: None,
+        returning: None,
     }
 }
 
@@ -144,7 +147,7 @@ impl XContract for MockXContract {
         &self,
         _tx_mgr: Arc<dyn TxMgr>,
         _oid: OID,
-        _alter_table: &crate::x_engine::api::AlterTable,
+        _alter_table: &[crate::x_engine::api::AlterTable],
     ) -> RS<()> {
         Ok(())
     }
@@ -1,3 +1,10 @@
+pub mod alter_partition_rule;
+#[cfg(test)]
+pub mod alter_partition_rule_test;
+pub mod alter_table;
+#[cfg(test)]
+pub mod alter_table_test;
+pub(crate) mod constraint;
 pub mod create_fs_type;
 #[cfg(all(test, not(miri)))]
 pub mod create_fs_type_test;
@@ -28,9 +35,11 @@ pub mod insert_key_value_test;
 pub mod load_from_file;
 #[cfg(test)]
 pub mod load_from_file_test;
+pub(crate) mod returning;
 pub mod save_to_file;
 #[cfg(test)]
 pub mod save_to_file_test;
+pub mod scanned_write;
 pub mod update_key_value;
 #[cfg(test)]
 pub mod update_key_value_test;
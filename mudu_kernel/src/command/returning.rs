@@ -0,0 +1,46 @@
+//! `RETURNING` support for the key/value DML executors.
+//!
+//! An affected row is read back by key inside the statement's transaction,
+//! so the projection sees column defaults, delta assignments and fs-bound
+//! object ids exactly as they were written. `DELETE` reads its row before
+//! the delete.
+
+use std::sync::Arc;
+
+use mudu::common::id::OID;
+use mudu::common::result::RS;
+use mudu_contract::tuple::tuple_field::TupleField;
+
+use crate::contract::cmd_exec::ReturnedRows;
+use crate::x_engine::api::{OptRead, VecDatum, XContract};
+use crate::x_engine::tx_mgr::TxMgr;
+use crate::x_engine::x_param::PReturning;
+
+/// Reads row `key` of `table_id` and projects the `RETURNING` columns;
+/// `None` when the row does not exist.
+pub(crate) async fn read_returning_row(
+    x_contract: &Arc<dyn XContract>,
+    tx_mgr: &Arc<dyn TxMgr>,
+    table_id: OID,
+    key: &VecDatum,
+    returning: &PReturning,
+) -> RS<Option<TupleField>> {
+    let row = x_contract
+        .read_key(
+            tx_mgr.clone(),
+            table_id,
+            key,
+            &returning.select,
+            &OptRead::default(),
+        )
+        .await?;
+    Ok(row.map(TupleField::new_nullable))
+}
+
+/// The result set of a command with a `RETURNING` clause.
+pub(crate) fn returned_rows(returning: &PReturning, rows: Vec<TupleField>) -> ReturnedRows {
+    ReturnedRows {
+        tuple_desc: returning.tuple_desc.clone(),
+        rows,
+    }
+}
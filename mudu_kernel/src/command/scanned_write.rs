@@ -0,0 +1,112 @@
+//! `UPDATE` and `DELETE` of the rows a scan selects.
+//!
+//! The scan returns the primary key of every row the `WHERE` clause
+//! matches. All keys are read before the first write, so the statement
+//! never sees its own writes, and each row is then written by the executor
+//! of the single-row statement on that key.
+
+use crate::contract::cmd_exec::{CmdExec, ReturnedRows};
+use crate::contract::query_exec::QueryExec;
+use crate::x_engine::api::VecDatum;
+use async_trait::async_trait;
+use mudu::common::id::AttrIndex;
+use mudu::common::result::RS;
+use mudu::common::result_of::rs_option;
+use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
+use mudu_sys::sync::async_::futures_mutex::FMutex;
+use mudu_utils::task_trace;
+use std::sync::Arc;
+
+/// Builds the executor writing the row with the given key.
+pub type WriteRow = Box<dyn Fn(VecDatum) -> Arc<dyn CmdExec> + Send + Sync>;
+
+pub struct ScannedWrite {
+    inner: FMutex<_ScannedWrite>,
+}
+
+struct _ScannedWrite {
+    scan: Arc<dyn QueryExec>,
+    /// The primary key attribute of each scanned column.
+    key_attrs: Vec<AttrIndex>,
+    write_row: WriteRow,
+    /// Layout of the `RETURNING` rows, when the statement has the clause.
+    returning: Option<TupleFieldDesc>,
+    affected_rows: u64,
+    returned: Option<ReturnedRows>,
+}
+
+impl ScannedWrite {
+    pub fn new(
+        scan: Arc<dyn QueryExec>,
+        key_attrs: Vec<AttrIndex>,
+        returning: Option<TupleFieldDesc>,
+        write_row: WriteRow,
+    ) -> Self {
+        Self {
+            inner: FMutex::new(_ScannedWrite {
+                scan,
+                key_attrs,
+                write_row,
+                returning,
+                affected_rows: 0,
+                returned: None,
+            }),
+        }
+    }
+}
+
+impl _ScannedWrite {
+    async fn run(&mut self) -> RS<()> {
+        self.scan.open().await?;
+        let mut keys = Vec::new();
+        while let Some(row) = self.scan.next().await? {
+            let key = self
+                .key_attrs
+                .iter()
+                .zip(row.into_fields())
+                .map(|(attr, datum)| Ok((*attr, rs_option(datum, "NULL primary key column")?)))
+                .collect::<RS<Vec<_>>>()?;
+            keys.push(VecDatum::new(key));
+        }
+        let mut rows = Vec::new();
+        self.affected_rows = 0;
+        for key in keys {
+            let write = (self.write_row)(key);
+            write.prepare().await?;
+            write.run().await?;
+            self.affected_rows += write.affected_rows().await?;
+            if let Some(returned) = write.returning().await? {
+                rows.extend(returned.rows);
+            }
+        }
+        self.returned = self.returning.as_ref().map(|tuple_desc| ReturnedRows {
+            tuple_desc: tuple_desc.clone(),
+            rows,
+        });
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CmdExec for ScannedWrite {
+    async fn prepare(&self) -> RS<()> {
+        Ok(())
+    }
+
+    async fn run(&self) -> RS<()> {
+        let trace = task_trace!();
+        trace.watch("cmd.kind", "scanned_write");
+        trace.watch("cmd.stage", "run_lock");
+        let mut inner = self.inner.lock().await;
+        trace.watch("cmd.stage", "run_inner");
+        inner.run().await
+    }
+
+    async fn affected_rows(&self) -> RS<u64> {
+        Ok(self.inner.lock().await.affected_rows)
+    }
+
+    async fn returning(&self) -> RS<Option<ReturnedRows>> {
+        Ok(self.inner.lock().await.returned.clone())
+    }
+}
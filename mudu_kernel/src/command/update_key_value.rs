@@ -1,9 +1,14 @@
+use crate::command::constraint::{self, ConstraintCtx};
 use crate::command::fs_hook;
-use crate::contract::cmd_exec::CmdExec;
+use crate::command::returning::{read_returning_row, returned_rows};
+use crate::contract::cmd_exec::{CmdExec, ReturnedRows};
 use crate::contract::meta_mgr::MetaMgr;
-use crate::x_engine::api::{OptUpdate, Predicate, XContract};
+use crate::contract::table_desc::TableDesc;
+use crate::executor::scalar_expr::{encode, ScalarExpr};
+use crate::x_engine::api::{OptRead, OptUpdate, Predicate, VecDatum, VecSelTerm, XContract};
 use crate::x_engine::x_param::PUpdateKeyValue;
 use async_trait::async_trait;
+use mudu::common::id::AttrIndex;
 use mudu::common::result::RS;
 use mudu::error::ErrorCode as ER;
 use mudu::mudu_error;
@@ -20,6 +25,7 @@ struct _UpdateKeyValue {
     x_contract: Arc<dyn XContract>,
     meta_mgr: Arc<dyn MetaMgr>,
     affected_rows: u64,
+    returned: Option<ReturnedRows>,
 }
 
 impl UpdateKeyValue {
@@ -45,6 +51,7 @@ impl _UpdateKeyValue {
             x_contract,
             meta_mgr,
             affected_rows: 0,
+            returned: None,
         }
     }
 
@@ -53,7 +60,10 @@ impl _UpdateKeyValue {
         if self.param.key.data().is_empty() {
             return Err(mudu_error!(ER::EntityNotFound, "update key is empty"));
         }
-        if self.param.value.data().is_empty() && self.param.delta_assignments.is_empty() {
+        if self.param.value.data().is_empty()
+            && self.param.delta_assignments.is_empty()
+            && self.param.expr_assignments.is_empty()
+        {
             return Err(mudu_error!(ER::EntityNotFound, "update value is empty"));
         }
         Ok(())
@@ -62,11 +72,29 @@ impl _UpdateKeyValue {
     async fn run(&mut self) -> RS<()> {
         // The SQL binder only emits key-equality updates for now.
         let desc = self.meta_mgr.get_table_by_id(self.param.table_id).await?;
+        let ctx = ConstraintCtx {
+            meta_mgr: &self.meta_mgr,
+            x_contract: &self.x_contract,
+            tx_mgr: &self.param.tx_mgr,
+        };
+        let Some(value) = self.assigned_value(desc.as_ref()).await? else {
+            // No row to evaluate the expressions over.
+            self.affected_rows = 0;
+            return self.read_returning().await;
+        };
+        constraint::check_update(
+            &ctx,
+            desc.as_ref(),
+            &self.param.key,
+            &value,
+            &self.param.delta_assignments,
+        )
+        .await?;
         let opt_update = OptUpdate {
             delta_assignments: self.param.delta_assignments.clone(),
         };
-        if fs_hook::update_touches_fs_columns(desc.as_ref(), &self.param.value) {
-            let mut value = self.param.value.clone();
+        if fs_hook::update_touches_fs_columns(desc.as_ref(), &value) {
+            let mut value = value;
             let staged = fs_hook::rebind_fs_columns_on_update(
                 &self.meta_mgr,
                 &self.x_contract,
@@ -92,7 +120,7 @@ impl _UpdateKeyValue {
                 fs_hook::stage_fs_ops(&self.param.tx_mgr, staged);
             }
             self.affected_rows = updated as u64;
-            return Ok(());
+            return self.read_returning().await;
         }
         let updated = self
             .x_contract
@@ -101,11 +129,59 @@ impl _UpdateKeyValue {
                 self.param.table_id,
                 &self.param.key,
                 &Predicate::CNF(Vec::new()),
-                &self.param.value,
+                &value,
                 &opt_update,
             )
             .await?;
         self.affected_rows = updated as u64;
+        self.read_returning().await
+    }
+
+    // The absolute values plus the values of the expression assignments,
+    // evaluated over the current row; `None` when the row does not exist.
+    // A NULL result leaves a nullable column unchanged, like `SET col =
+    // NULL`.
+    async fn assigned_value(&self, desc: &TableDesc) -> RS<Option<VecDatum>> {
+        if self.param.expr_assignments.is_empty() {
+            return Ok(Some(self.param.value.clone()));
+        }
+        let row = self
+            .x_contract
+            .read_key(
+                self.param.tx_mgr.clone(),
+                self.param.table_id,
+                &self.param.key,
+                &VecSelTerm::new((0..desc.fields().len()).collect()),
+                &OptRead::default(),
+            )
+            .await?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let mut value = self.param.value.data().clone();
+        push_expr_values(desc, &row, &self.param.expr_assignments, &mut value)?;
+        Ok(Some(VecDatum::new(value)))
+    }
+
+    // Reads back the updated row for the `RETURNING` clause.
+    async fn read_returning(&mut self) -> RS<()> {
+        let Some(returning) = &self.param.returning else {
+            return Ok(());
+        };
+        let mut rows = Vec::new();
+        if self.affected_rows > 0 {
+            rows.extend(
+                read_returning_row(
+                    &self.x_contract,
+                    &self.param.tx_mgr,
+                    self.param.table_id,
+                    &self.param.key,
+                    returning,
+                )
+                .await?,
+            );
+        }
+        self.returned = Some(returned_rows(returning, rows));
         Ok(())
     }
 
@@ -114,6 +190,30 @@ impl _UpdateKeyValue {
     }
 }
 
+/// Appends the values of the expression assignments evaluated over `row`
+/// to `value`. A NULL result leaves a nullable column unchanged.
+pub(crate) fn push_expr_values(
+    desc: &TableDesc,
+    row: &[Option<Vec<u8>>],
+    expr_assignments: &[(AttrIndex, ScalarExpr)],
+    value: &mut Vec<(AttrIndex, Vec<u8>)>,
+) -> RS<()> {
+    for (attr, expr) in expr_assignments {
+        let field = desc.get_attr(*attr);
+        match encode(expr.eval(row)?, field.type_desc(), field.type_desc())? {
+            Some(binary) => value.push((*attr, binary)),
+            None if field.nullable() => {}
+            None => {
+                return Err(mudu_error!(
+                    ER::InvalidTuple,
+                    format!("cannot update NOT NULL column {} to NULL", field.name())
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
 #[async_trait]
 impl CmdExec for UpdateKeyValue {
     async fn prepare(&self) -> RS<()> {
@@ -142,4 +242,8 @@ impl CmdExec for UpdateKeyValue {
         trace.watch("cmd.stage", "affected_rows_done");
         Ok(inner.affected_rows())
     }
+
+    async fn returning(&self) -> RS<Option<ReturnedRows>> {
+        Ok(self.inner.lock().await.returned.clone())
+    }
 }
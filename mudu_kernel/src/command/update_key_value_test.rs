@@ -61,6 +61,8 @@ fn make_param(key: VecDatum, value: VecDatum) -> PUpdateKeyValue {
         key,
         value,
         delta_assignments: Vec::new(),
+        expr_assignments: Vec::new(),
+        returning: None,
     }
 }
 
@@ -146,7 +148,7 @@ impl XContract for MockXContract {
         &self,
         _tx_mgr: Arc<dyn TxMgr>,
         _oid: OID,
-        _alter_table: &crate::x_engine::api::AlterTable,
+        _alter_table: &[crate::x_engine::api::AlterTable],
     ) -> RS<()> {
         Ok(())
     }
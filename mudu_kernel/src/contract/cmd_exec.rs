@@ -1,9 +1,24 @@
 use async_trait::async_trait;
 use mudu::common::result::RS;
+use mudu_contract::tuple::tuple_field::TupleField;
+use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
+
+/// Rows a command produced for its `RETURNING` clause.
+#[derive(Clone)]
+pub struct ReturnedRows {
+    pub tuple_desc: TupleFieldDesc,
+    pub rows: Vec<TupleField>,
+}
 
 #[async_trait]
 pub trait CmdExec: Send + Sync {
     async fn prepare(&self) -> RS<()>;
     async fn run(&self) -> RS<()>;
     async fn affected_rows(&self) -> RS<u64>;
+
+    /// Rows of the `RETURNING` clause, available after `run`; `None` for
+    /// commands without one.
+    async fn returning(&self) -> RS<Option<ReturnedRows>> {
+        Ok(None)
+    }
 }
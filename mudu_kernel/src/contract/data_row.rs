@@ -98,6 +98,40 @@ impl DataRowInner {
         Ok(self.tuple.last().cloned())
     }
 
+    /// Drops every version older than the newest version committed below
+    /// `low_water`. That version is visible to every snapshot the low-water
+    /// mark covers, so readers stop there and never walk past it.
+    ///
+    /// Returns the number of dropped versions and the commit timestamp of
+    /// the oldest retained version.
+    fn prune_versions(&mut self, low_water: u64) -> (usize, Option<u64>) {
+        if self.tuple.is_empty() {
+            return (0, None);
+        }
+        let total = self.delta.len() + 1;
+        let window_start = total - self.tuple.len();
+        let c_min_at = |index: usize| {
+            if index < window_start {
+                self.delta[index].timestamp().c_min()
+            } else {
+                self.tuple[index - window_start].timestamp().c_min()
+            }
+        };
+        let Some(keep_from) = (0..total).rev().find(|index| c_min_at(*index) < low_water) else {
+            return (0, Some(c_min_at(0)));
+        };
+        if keep_from > window_start {
+            self.tuple.drain(..keep_from - window_start);
+        }
+        self.delta.drain(..keep_from);
+        let oldest = if keep_from < window_start {
+            self.delta[0].timestamp().c_min()
+        } else {
+            self.tuple[0].timestamp().c_min()
+        };
+        (keep_from, Some(oldest))
+    }
+
     fn read_version(&self, snapshot: &Snapshot) -> RS<Option<VersionTuple>> {
         Ok(self
             .read_version_detailed(snapshot)?
@@ -196,6 +230,14 @@ impl DataRow {
         guard.read_latest()
     }
 
+    /// Drops the versions no snapshot at or above `low_water` can read; see
+    /// [`DataRowInner::prune_versions`]. Returns the number of dropped
+    /// versions and the commit timestamp of the oldest retained version.
+    pub fn prune_versions_sync(&self, low_water: u64) -> RS<(usize, Option<u64>)> {
+        let mut guard = self.inner.lock()?;
+        Ok(guard.prune_versions(low_water))
+    }
+
     pub async fn write(&self, version: VersionTuple, prev_version: Option<VersionDelta>) -> RS<()> {
         scoped_task_trace!();
         self.write_sync(version, prev_version)
@@ -324,4 +366,43 @@ mod tests {
         assert_eq!(visible.tuple(), &vec![2]);
         assert_eq!(visible.timestamp().c_min(), 2);
     }
+
+    #[test]
+    fn prune_versions_keeps_newest_version_below_low_water() {
+        let row = DataRow::new(1);
+        for xid in 1..=6 {
+            row.write_sync(version(xid, &[xid as u8]), None).unwrap();
+        }
+
+        assert_eq!(row.prune_versions_sync(1).unwrap(), (0, Some(1)));
+        // Version 2 is inside the delta-only part of the chain.
+        assert_eq!(row.prune_versions_sync(3).unwrap(), (1, Some(2)));
+        let visible = row.read_sync(&snapshot(2)).unwrap().unwrap();
+        assert_eq!(visible.tuple(), &vec![2]);
+        assert_eq!(visible.timestamp().c_min(), 2);
+        assert!(row.read_sync(&snapshot(1)).unwrap().is_none());
+
+        // Version 5 is inside the retained full-version window.
+        assert_eq!(row.prune_versions_sync(6).unwrap(), (3, Some(5)));
+        {
+            let guard = row.inner.lock().unwrap();
+            assert_eq!(guard.delta.len(), 1);
+            assert_eq!(guard.tuple.len(), 2);
+        }
+        assert_eq!(
+            row.read_sync(&snapshot(5)).unwrap().unwrap().tuple(),
+            &vec![5]
+        );
+        assert_eq!(
+            row.read_sync(&snapshot(9)).unwrap().unwrap().tuple(),
+            &vec![6]
+        );
+
+        row.write_sync(version(7, &[7]), None).unwrap();
+        assert_eq!(row.prune_versions_sync(u64::MAX).unwrap(), (2, Some(7)));
+        assert_eq!(
+            row.read_sync(&snapshot(9)).unwrap().unwrap().tuple(),
+            &vec![7]
+        );
+    }
 }
@@ -0,0 +1,98 @@
+use std::time::Duration;
+
+/// Counters `EXPLAIN ANALYZE` collects for one operator. Each counter
+/// includes the work of the operator's inputs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExplainStats {
+    /// Rows the operator returned.
+    pub rows: u64,
+    /// Wall time spent in `open` and `next`.
+    pub time: Duration,
+    /// Time-series pages read on this worker, page cache hits included.
+    pub pages: u64,
+}
+
+/// One operator of a query plan, as `EXPLAIN` reports it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExplainNode {
+    operator: String,
+    detail: String,
+    children: Vec<ExplainNode>,
+    stats: Option<ExplainStats>,
+}
+
+impl ExplainNode {
+    pub fn new(operator: impl Into<String>) -> Self {
+        Self {
+            operator: operator.into(),
+            detail: String::new(),
+            children: Vec::new(),
+            stats: None,
+        }
+    }
+
+    /// Operator arguments shown after the operator name, e.g. the table and
+    /// the key access of a scan.
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = detail.into();
+        self
+    }
+
+    pub fn with_child(mut self, child: ExplainNode) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub fn with_stats(mut self, stats: ExplainStats) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    pub fn operator(&self) -> &str {
+        &self.operator
+    }
+
+    pub fn detail(&self) -> &str {
+        &self.detail
+    }
+
+    pub fn children(&self) -> &[ExplainNode] {
+        &self.children
+    }
+
+    pub fn stats(&self) -> Option<&ExplainStats> {
+        self.stats.as_ref()
+    }
+
+    /// The tree as text lines, one operator per line, each input indented
+    /// below the operator consuming it.
+    pub fn render(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        self.render_into(0, &mut lines);
+        lines
+    }
+
+    fn render_into(&self, depth: usize, lines: &mut Vec<String>) {
+        let mut line = if depth == 0 {
+            String::new()
+        } else {
+            format!("{}-> ", "  ".repeat(depth))
+        };
+        line.push_str(&self.operator);
+        if !self.detail.is_empty() {
+            line.push_str(&format!(" ({})", self.detail));
+        }
+        if let Some(stats) = &self.stats {
+            line.push_str(&format!(
+                " [rows={} time={:.3}ms pages={}]",
+                stats.rows,
+                stats.time.as_secs_f64() * 1000.0,
+                stats.pages
+            ));
+        }
+        lines.push(line);
+        for child in &self.children {
+            child.render_into(depth + 1, lines);
+        }
+    }
+}
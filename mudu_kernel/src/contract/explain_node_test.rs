@@ -0,0 +1,34 @@
+use crate::contract::explain_node::{ExplainNode, ExplainStats};
+use std::time::Duration;
+
+#[test]
+fn render_indents_inputs_below_their_consumer() {
+    let plan = ExplainNode::new("Limit")
+        .with_detail("offset=0 limit=10")
+        .with_child(
+            ExplainNode::new("Filter")
+                .with_detail("filters=1")
+                .with_child(ExplainNode::new("IndexAccessRange").with_detail("table=t full scan")),
+        );
+    assert_eq!(
+        plan.render(),
+        vec![
+            "Limit (offset=0 limit=10)".to_string(),
+            "  -> Filter (filters=1)".to_string(),
+            "    -> IndexAccessRange (table=t full scan)".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn render_appends_analyze_counters() {
+    let plan = ExplainNode::new("IndexAccessKey").with_stats(ExplainStats {
+        rows: 1,
+        time: Duration::from_micros(1500),
+        pages: 2,
+    });
+    assert_eq!(
+        plan.render(),
+        vec!["IndexAccessKey [rows=1 time=1.500ms pages=2]".to_string()]
+    );
+}
@@ -2,6 +2,7 @@ use mudu::common::id::{AttrIndex, DatumIndex, OID};
 use mudu_type::data_type_fn_param::DataType;
 
 use crate::contract::fs_type::FsColumnBinding;
+use crate::contract::schema_column::ColumnDefault;
 
 #[derive(Clone, Debug, Default)]
 pub struct FieldInfo {
@@ -15,6 +16,7 @@ pub struct FieldInfo {
     primary_index: Option<AttrIndex>,
     nullable: bool,
     fs_binding: Option<FsColumnBinding>,
+    default: Option<ColumnDefault>,
 }
 
 impl FieldInfo {
@@ -36,6 +38,7 @@ impl FieldInfo {
             primary_index,
             nullable,
             fs_binding: None,
+            default: None,
         }
     }
 
@@ -82,4 +85,12 @@ impl FieldInfo {
     pub fn set_fs_binding(&mut self, fs_binding: Option<FsColumnBinding>) {
         self.fs_binding = fs_binding;
     }
+
+    pub fn default_value(&self) -> Option<&ColumnDefault> {
+        self.default.as_ref()
+    }
+
+    pub fn set_default_value(&mut self, default: Option<ColumnDefault>) {
+        self.default = default;
+    }
 }
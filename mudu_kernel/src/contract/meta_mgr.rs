@@ -8,6 +8,8 @@ use crate::contract::partition_rule::PartitionRuleDesc;
 use crate::contract::partition_rule_binding::{PartitionPlacement, TablePartitionBinding};
 use crate::contract::schema_table::SchemaTable;
 use crate::contract::table_desc::TableDesc;
+use crate::contract::user::UserDesc;
+use crate::x_engine::api::AlterTable;
 use mudu::common::result::RS;
 
 #[async_trait]
@@ -37,6 +39,13 @@ pub trait MetaMgr: Send + Sync {
 
     async fn drop_table(&self, table_id: OID) -> RS<()>;
 
+    async fn alter_table(&self, _table_id: OID, _alters: &[AlterTable]) -> RS<()> {
+        Err(mudu::mudu_error!(
+            ErrorCode::NotImplemented,
+            "alter table is not implemented"
+        ))
+    }
+
     async fn create_partition_rule(&self, _rule: &PartitionRuleDesc) -> RS<()> {
         Err(mudu::mudu_error!(
             ErrorCode::NotImplemented,
@@ -44,6 +53,15 @@ pub trait MetaMgr: Send + Sync {
         ))
     }
 
+    /// Replace the partition rule with the same oid by `rule`, which must
+    /// carry a higher version.
+    async fn update_partition_rule(&self, _rule: &PartitionRuleDesc) -> RS<()> {
+        Err(mudu::mudu_error!(
+            ErrorCode::NotImplemented,
+            "partition rule update is not implemented"
+        ))
+    }
+
     async fn get_partition_rule_by_id(&self, oid: OID) -> RS<PartitionRuleDesc> {
         Err(mudu::mudu_error!(
             ErrorCode::EntityNotFound,
@@ -117,4 +135,26 @@ pub trait MetaMgr: Send + Sync {
             "filesystem type catalog is not implemented"
         ))
     }
+
+    async fn create_user(&self, _desc: &UserDesc) -> RS<()> {
+        Err(mudu::mudu_error!(
+            ErrorCode::NotImplemented,
+            "user catalog is not implemented"
+        ))
+    }
+
+    async fn get_user_by_name(&self, _name: &str) -> RS<Option<UserDesc>> {
+        Ok(None)
+    }
+
+    async fn list_users(&self) -> RS<Vec<UserDesc>> {
+        Ok(Vec::new())
+    }
+
+    async fn drop_user(&self, _name: &str) -> RS<()> {
+        Err(mudu::mudu_error!(
+            ErrorCode::NotImplemented,
+            "user catalog is not implemented"
+        ))
+    }
 }
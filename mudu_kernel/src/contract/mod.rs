@@ -23,14 +23,20 @@ pub mod partition_rule_test;
 
 pub mod cmd_exec;
 pub mod data_row;
+pub mod explain_node;
+#[cfg(test)]
+pub mod explain_node_test;
 mod field_info;
 #[cfg(test)]
 pub mod field_info_test;
 pub mod fs_type;
 pub mod query_exec;
+pub mod schema_check;
 pub mod schema_column;
 #[cfg(test)]
 pub mod schema_column_test;
+pub mod schema_foreign_key;
+pub mod schema_index;
 pub mod schema_table;
 #[cfg(test)]
 pub mod schema_table_test;
@@ -49,6 +55,7 @@ pub use self::test_schema::_fuzz::_schema_table;
 pub mod timestamp;
 #[cfg(test)]
 pub mod timestamp_test;
+pub mod user;
 pub mod version_delta;
 pub mod version_tuple;
 pub mod waiter;
@@ -6,6 +6,13 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum PartitionRuleKind {
     Range,
+    /// A key goes to partition `hash(key) % partitions.len()`; `columns`
+    /// names the key columns the rule was declared with.
+    Hash {
+        columns: Vec<String>,
+    },
+    /// A key goes to the partition listing its value.
+    List,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -20,6 +27,10 @@ pub struct RangePartitionDef {
     pub name: String,
     pub start: PartitionBound,
     pub end: PartitionBound,
+    /// Key tuples of a `LIST` partition, in the textual form of range
+    /// bounds; empty for range and hash partitions.
+    #[serde(default)]
+    pub values: Vec<Vec<Vec<u8>>>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -39,8 +50,19 @@ impl RangePartitionDef {
             name,
             start,
             end,
+            values: Vec::new(),
         }
     }
+
+    pub fn new_hash(name: String) -> Self {
+        Self::new(name, PartitionBound::Unbounded, PartitionBound::Unbounded)
+    }
+
+    pub fn new_list(name: String, values: Vec<Vec<Vec<u8>>>) -> Self {
+        let mut partition = Self::new_hash(name);
+        partition.values = values;
+        partition
+    }
 }
 
 impl PartitionRuleDesc {
@@ -58,4 +80,34 @@ impl PartitionRuleDesc {
             version: 1,
         }
     }
+
+    /// A hash rule over `columns` with `partition_count` partitions named
+    /// `p0`, `p1`, ...
+    pub fn new_hash(name: String, columns: Vec<String>, partition_count: usize) -> Self {
+        Self {
+            oid: gen_oid(),
+            name,
+            kind: PartitionRuleKind::Hash { columns },
+            key_types: Vec::new(),
+            partitions: (0..partition_count)
+                .map(|index| RangePartitionDef::new_hash(format!("p{}", index)))
+                .collect(),
+            version: 1,
+        }
+    }
+
+    pub fn new_list(
+        name: String,
+        key_types: Vec<TypeFamily>,
+        partitions: Vec<RangePartitionDef>,
+    ) -> Self {
+        Self {
+            oid: gen_oid(),
+            name,
+            kind: PartitionRuleKind::List,
+            key_types,
+            partitions,
+            version: 1,
+        }
+    }
 }
@@ -56,3 +56,33 @@ fn serde_roundtrip() {
     let decoded: PartitionRuleDesc = serde_json::from_str(&json).unwrap();
     assert_eq!(rule, decoded);
 }
+
+#[test]
+fn partition_rule_desc_new_hash_names_partitions() {
+    let rule = PartitionRuleDesc::new_hash("h".to_string(), vec!["user_id".to_string()], 3);
+    assert_eq!(
+        rule.kind,
+        PartitionRuleKind::Hash {
+            columns: vec!["user_id".to_string()]
+        }
+    );
+    let names: Vec<&str> = rule.partitions.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, vec!["p0", "p1", "p2"]);
+    assert!(rule.key_types.is_empty());
+}
+
+#[test]
+fn list_rule_serde_roundtrip() {
+    let rule = PartitionRuleDesc::new_list(
+        "l".to_string(),
+        vec![TypeFamily::String],
+        vec![RangePartitionDef::new_list(
+            "east".to_string(),
+            vec![vec![b"'ny'".to_vec()], vec![b"'ma'".to_vec()]],
+        )],
+    );
+    let bytes = rmp_serde::to_vec(&rule).unwrap();
+    let decoded: PartitionRuleDesc = rmp_serde::from_slice(&bytes).unwrap();
+    assert_eq!(rule, decoded);
+    assert_eq!(decoded.partitions[0].values.len(), 2);
+}
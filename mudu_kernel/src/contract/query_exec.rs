@@ -1,3 +1,4 @@
+use crate::contract::explain_node::ExplainNode;
 use async_trait::async_trait;
 use mudu::common::result::RS;
 use mudu_contract::tuple::tuple_field::TupleField;
@@ -8,4 +9,9 @@ pub trait QueryExec: Send + Sync {
     async fn open(&self) -> RS<()>;
     async fn next(&self) -> RS<Option<TupleField>>;
     fn tuple_desc(&self) -> RS<TupleFieldDesc>;
+
+    /// The operator and its inputs, as `EXPLAIN` reports them.
+    fn explain(&self) -> ExplainNode {
+        ExplainNode::new("Rows")
+    }
 }
@@ -0,0 +1,36 @@
+use mudu::common::id::OID;
+use serde::{Deserialize, Serialize};
+
+/// `CHECK` constraint of a table as stored in the catalog.
+///
+/// The condition is kept as written and bound again when the table
+/// descriptor is built, see [`crate::contract::table_desc::CheckDesc`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SchemaCheck {
+    name: String,
+    condition: String,
+    column_oids: Vec<OID>,
+}
+
+impl SchemaCheck {
+    pub fn new(name: String, condition: String, column_oids: Vec<OID>) -> Self {
+        Self {
+            name,
+            condition,
+            column_oids,
+        }
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn condition(&self) -> &String {
+        &self.condition
+    }
+
+    // Columns the condition reads.
+    pub fn column_oids(&self) -> &Vec<OID> {
+        &self.column_oids
+    }
+}
@@ -8,6 +8,17 @@ use serde::{Deserialize, Serialize};
 
 use crate::contract::fs_type::FsColumnBinding;
 
+/// `DEFAULT` of a column, filled in when an `INSERT` omits the column.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColumnDefault {
+    /// A constant encoded in the column's binary format.
+    Value(Vec<u8>),
+    /// The wall-clock time of the inserting statement.
+    Now,
+    /// A freshly generated object id per row.
+    GenOid,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SchemaColumn {
     oid: OID,
@@ -20,6 +31,8 @@ pub struct SchemaColumn {
     nullable: bool,
     #[serde(default)]
     fs_binding: Option<FsColumnBinding>,
+    #[serde(default)]
+    default: Option<ColumnDefault>,
 }
 
 impl SchemaColumn {
@@ -34,6 +47,7 @@ impl SchemaColumn {
             is_primary: None,
             nullable: true,
             fs_binding: None,
+            default: None,
         }
     }
 
@@ -53,6 +67,7 @@ impl SchemaColumn {
             is_primary: None,
             nullable: true,
             fs_binding: None,
+            default: None,
         }
     }
 
@@ -64,6 +79,10 @@ impl SchemaColumn {
         &self.name
     }
 
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
     pub fn is_primary(&self) -> bool {
         self.is_primary.is_some()
     }
@@ -107,6 +126,11 @@ impl SchemaColumn {
         &self.type_param
     }
 
+    pub fn set_type(&mut self, type_id: TypeID, type_param: DataTypeInfo) {
+        self.type_id = type_id;
+        self.type_param = type_param;
+    }
+
     pub fn fs_binding(&self) -> Option<FsColumnBinding> {
         self.fs_binding
     }
@@ -114,6 +138,14 @@ impl SchemaColumn {
     pub fn set_fs_binding(&mut self, fs_binding: Option<FsColumnBinding>) {
         self.fs_binding = fs_binding;
     }
+
+    pub fn default_value(&self) -> Option<&ColumnDefault> {
+        self.default.as_ref()
+    }
+
+    pub fn set_default_value(&mut self, default: Option<ColumnDefault>) {
+        self.default = default;
+    }
 }
 
 fn default_nullable() -> bool {
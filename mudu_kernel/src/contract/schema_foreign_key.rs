@@ -0,0 +1,71 @@
+use mudu::common::id::OID;
+use serde::{Deserialize, Serialize};
+
+/// Action taken on referencing rows when a referenced row is deleted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReferentialAction {
+    /// Reject the delete while referencing rows exist.
+    Restrict,
+    /// Delete the referencing rows in the same transaction.
+    Cascade,
+}
+
+/// `FOREIGN KEY` constraint of a table as stored in the catalog.
+///
+/// The referencing columns are backed by the table's secondary index
+/// `index_oid`, which finds the rows referencing a deleted parent row. The
+/// referenced columns are the primary key or a unique index of the parent.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SchemaForeignKey {
+    name: String,
+    column_oids: Vec<OID>,
+    index_oid: OID,
+    ref_table_oid: OID,
+    ref_column_oids: Vec<OID>,
+    on_delete: ReferentialAction,
+}
+
+impl SchemaForeignKey {
+    pub fn new(
+        name: String,
+        column_oids: Vec<OID>,
+        index_oid: OID,
+        ref_table_oid: OID,
+        ref_column_oids: Vec<OID>,
+        on_delete: ReferentialAction,
+    ) -> Self {
+        Self {
+            name,
+            column_oids,
+            index_oid,
+            ref_table_oid,
+            ref_column_oids,
+            on_delete,
+        }
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    // Referencing columns, paired with `ref_column_oids` by position.
+    pub fn column_oids(&self) -> &Vec<OID> {
+        &self.column_oids
+    }
+
+    pub fn index_oid(&self) -> OID {
+        self.index_oid
+    }
+
+    pub fn ref_table_oid(&self) -> OID {
+        self.ref_table_oid
+    }
+
+    pub fn ref_column_oids(&self) -> &Vec<OID> {
+        &self.ref_column_oids
+    }
+
+    pub fn on_delete(&self) -> ReferentialAction {
+        self.on_delete
+    }
+}
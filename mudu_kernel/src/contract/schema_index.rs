@@ -0,0 +1,52 @@
+use mudu::common::id::OID;
+use mudu_utils::oid::gen_oid;
+use serde::{Deserialize, Serialize};
+
+/// Secondary index of a table as stored in the catalog.
+///
+/// Indexed columns are referenced by column OID, so renaming a column keeps
+/// the index valid. A unique index backs a `UNIQUE` constraint.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SchemaIndex {
+    oid: OID,
+    name: String,
+    column_oids: Vec<OID>,
+    #[serde(default)]
+    unique: bool,
+}
+
+impl SchemaIndex {
+    pub fn new(name: String, column_oids: Vec<OID>) -> Self {
+        Self::new_with_oid(gen_oid(), name, column_oids)
+    }
+
+    pub fn new_with_oid(oid: OID, name: String, column_oids: Vec<OID>) -> Self {
+        Self {
+            oid,
+            name,
+            column_oids,
+            unique: false,
+        }
+    }
+
+    pub fn id(&self) -> OID {
+        self.oid
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    // Indexed columns in index order.
+    pub fn column_oids(&self) -> &Vec<OID> {
+        &self.column_oids
+    }
+
+    pub fn is_unique(&self) -> bool {
+        self.unique
+    }
+
+    pub fn set_unique(&mut self, unique: bool) {
+        self.unique = unique;
+    }
+}
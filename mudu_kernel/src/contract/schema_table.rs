@@ -1,9 +1,15 @@
 use crate::contract::field_info::FieldInfo;
+use crate::contract::schema_check::SchemaCheck;
 use crate::contract::schema_column::SchemaColumn;
+use crate::contract::schema_foreign_key::SchemaForeignKey;
+use crate::contract::schema_index::SchemaIndex;
+use crate::x_engine::api::AlterTable;
 #[cfg(any(test, feature = "test", fuzzing))]
 use arbitrary::{Arbitrary, Unstructured};
 use mudu::common::id::{AttrIndex, DatumIndex, OID};
 use mudu::common::result::RS;
+use mudu::error::ErrorCode;
+use mudu::mudu_error;
 use mudu_contract::tuple::tuple_binary_desc::TupleBinaryDesc as TupleDesc;
 use mudu_utils::oid::gen_oid;
 use serde::{Deserialize, Serialize};
@@ -17,6 +23,41 @@ pub struct SchemaTable {
     columns: Vec<SchemaColumn>,
     key_indices: Vec<AttrIndex>,
     value_indices: Vec<AttrIndex>,
+    // Bumped by every ALTER TABLE that changes the value tuple layout.
+    #[serde(default)]
+    layout_version: u32,
+    #[serde(default)]
+    retired_layouts: Vec<RetiredLayout>,
+    #[serde(default)]
+    indexes: Vec<SchemaIndex>,
+    #[serde(default)]
+    foreign_keys: Vec<SchemaForeignKey>,
+    #[serde(default)]
+    checks: Vec<SchemaCheck>,
+}
+
+/// Value columns of a layout replaced by `ALTER TABLE`.
+///
+/// Rows written under a retired layout stay in that layout's value file and
+/// are upgraded to the current layout when they are read.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RetiredLayout {
+    version: u32,
+    value_columns: Vec<SchemaColumn>,
+}
+
+impl RetiredLayout {
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn value_columns(&self) -> &Vec<SchemaColumn> {
+        &self.value_columns
+    }
+
+    pub fn value_tuple_desc(&self) -> RS<(TupleDesc, Vec<FieldInfo>)> {
+        schema_columns_to_tuple_desc(self.value_columns.iter().enumerate().collect())
+    }
 }
 
 // Build a tuple descriptor from a key/value column slice.
@@ -39,6 +80,7 @@ pub fn schema_columns_to_tuple_desc(
             sc.nullable(),
         );
         field_info.set_fs_binding(sc.fs_binding());
+        field_info.set_default_value(sc.default_value().cloned());
         desc.push((ty, field_info, sc.nullable()))
     }
 
@@ -133,6 +175,11 @@ impl SchemaTable {
             columns,
             key_indices,
             value_indices,
+            layout_version: 0,
+            retired_layouts: Vec::new(),
+            indexes: Vec::new(),
+            foreign_keys: Vec::new(),
+            checks: Vec::new(),
         };
         for (i, index) in s.key_indices.iter().copied().enumerate() {
             let sc = &mut s.columns[index];
@@ -185,6 +232,265 @@ impl SchemaTable {
             .collect()
     }
 
+    pub fn layout_version(&self) -> u32 {
+        self.layout_version
+    }
+
+    pub fn retired_layouts(&self) -> &Vec<RetiredLayout> {
+        &self.retired_layouts
+    }
+
+    pub fn indexes(&self) -> &Vec<SchemaIndex> {
+        &self.indexes
+    }
+
+    pub fn index_by_name(&self, name: &str) -> Option<&SchemaIndex> {
+        self.indexes.iter().find(|index| index.name() == name)
+    }
+
+    // Adds an index while building a new schema; an existing table gains
+    // indexes through `altered`.
+    pub fn add_index(&mut self, index: SchemaIndex) {
+        self.indexes.push(index);
+    }
+
+    pub fn foreign_keys(&self) -> &Vec<SchemaForeignKey> {
+        &self.foreign_keys
+    }
+
+    // Adds a foreign key while building a new schema. Its backing index shall
+    // be added with `add_index`.
+    pub fn add_foreign_key(&mut self, foreign_key: SchemaForeignKey) {
+        self.foreign_keys.push(foreign_key);
+    }
+
+    pub fn checks(&self) -> &Vec<SchemaCheck> {
+        &self.checks
+    }
+
+    // Adds a check constraint while building a new schema.
+    pub fn add_check(&mut self, check: SchemaCheck) {
+        self.checks.push(check);
+    }
+
+    // Returns the schema produced by applying `alter` to this one; `self` is
+    // left untouched. Key columns are immutable, so the key tuple layout never
+    // changes. A change to the value tuple layout retires the current layout.
+    pub fn altered(&self, alter: &AlterTable) -> RS<SchemaTable> {
+        let mut columns = self.columns.clone();
+        let mut key_indices = self.key_indices.clone();
+        let mut value_indices = self.value_indices.clone();
+        let mut indexes = self.indexes.clone();
+        let layout_changed = match alter {
+            AlterTable::AddColumn(column) => {
+                if self.find_column(column.get_name()).is_some() {
+                    return Err(mudu_error!(
+                        ErrorCode::EntityAlreadyExists,
+                        format!("column {} already exists", column.get_name())
+                    ));
+                }
+                if column.is_primary() {
+                    return Err(mudu_error!(
+                        ErrorCode::UnsupportedOperation,
+                        "cannot add a primary key column"
+                    ));
+                }
+                value_indices.push(columns.len());
+                columns.push(column.clone());
+                true
+            }
+            AlterTable::DropColumn(name) => {
+                let index = self.non_key_column(name, "drop")?;
+                let column_oid = self.columns[index].get_oid();
+                if let Some(used_by) = self
+                    .indexes
+                    .iter()
+                    .find(|index| index.column_oids().contains(&column_oid))
+                {
+                    return Err(mudu_error!(
+                        ErrorCode::UnsupportedOperation,
+                        format!(
+                            "cannot drop {}, it is used by index {}",
+                            name,
+                            used_by.name()
+                        )
+                    ));
+                }
+                self.refuse_checked_column(name, column_oid, "drop")?;
+                if value_indices.len() == 1 {
+                    return Err(mudu_error!(
+                        ErrorCode::UnsupportedOperation,
+                        format!("cannot drop {}, the only non-key column", name)
+                    ));
+                }
+                let _ = columns.remove(index);
+                let shift = |i: &AttrIndex| if *i > index { *i - 1 } else { *i };
+                key_indices = key_indices.iter().map(shift).collect();
+                value_indices = value_indices
+                    .iter()
+                    .filter(|i| **i != index)
+                    .map(shift)
+                    .collect();
+                true
+            }
+            AlterTable::RenameColumn { old_name, new_name } => {
+                let index = self.find_column(old_name).ok_or_else(|| {
+                    mudu_error!(
+                        ErrorCode::EntityNotFound,
+                        format!("column {} does not exist", old_name)
+                    )
+                })?;
+                if self.find_column(new_name).is_some() {
+                    return Err(mudu_error!(
+                        ErrorCode::EntityAlreadyExists,
+                        format!("column {} already exists", new_name)
+                    ));
+                }
+                // The condition of a check names its columns.
+                self.refuse_checked_column(old_name, self.columns[index].get_oid(), "rename")?;
+                columns[index].set_name(new_name.clone());
+                false
+            }
+            AlterTable::AlterColumnType {
+                name,
+                type_id,
+                type_param,
+            } => {
+                let index = self.non_key_column(name, "alter type of")?;
+                self.refuse_checked_column(name, self.columns[index].get_oid(), "alter type of")?;
+                columns[index].set_type(*type_id, type_param.clone());
+                true
+            }
+            AlterTable::AddIndex(index) => {
+                if self.index_by_name(index.name()).is_some() {
+                    return Err(mudu_error!(
+                        ErrorCode::EntityAlreadyExists,
+                        format!("index {} already exists", index.name())
+                    ));
+                }
+                if index.column_oids().is_empty() {
+                    return Err(mudu_error!(
+                        ErrorCode::UnsupportedOperation,
+                        format!("index {} has no columns", index.name())
+                    ));
+                }
+                for oid in index.column_oids() {
+                    if !self.columns.iter().any(|c| c.get_oid() == *oid) {
+                        return Err(mudu_error!(
+                            ErrorCode::EntityNotFound,
+                            format!("index {} references unknown column {}", index.name(), oid)
+                        ));
+                    }
+                }
+                indexes.push(index.clone());
+                false
+            }
+            AlterTable::DropIndex(name) => {
+                let position = indexes
+                    .iter()
+                    .position(|index| index.name() == name)
+                    .ok_or_else(|| {
+                        mudu_error!(
+                            ErrorCode::EntityNotFound,
+                            format!("index {} does not exist", name)
+                        )
+                    })?;
+                let index = &indexes[position];
+                if index.is_unique()
+                    || self
+                        .foreign_keys
+                        .iter()
+                        .any(|foreign_key| foreign_key.index_oid() == index.id())
+                {
+                    return Err(mudu_error!(
+                        ErrorCode::UnsupportedOperation,
+                        format!("cannot drop {}, it backs a table constraint", name)
+                    ));
+                }
+                let _ = indexes.remove(position);
+                false
+            }
+        };
+        let mut schema = SchemaTable::new_with_oid(
+            self.oid,
+            self.table_name.clone(),
+            columns,
+            key_indices,
+            value_indices,
+        );
+        schema.layout_version = self.layout_version;
+        schema.retired_layouts = self.retired_layouts.clone();
+        schema.indexes = indexes;
+        schema.foreign_keys = self.foreign_keys.clone();
+        schema.checks = self.checks.clone();
+        if layout_changed {
+            schema.retired_layouts.push(RetiredLayout {
+                version: self.layout_version,
+                value_columns: self.value_columns().into_iter().cloned().collect(),
+            });
+            schema.layout_version = self.layout_version + 1;
+        }
+        Ok(schema)
+    }
+
+    // Returns the schema produced by applying the actions of one ALTER TABLE
+    // statement in order. The statement is one layout change: it retires at
+    // most the layout current before it.
+    pub fn altered_all(&self, alters: &[AlterTable]) -> RS<SchemaTable> {
+        let mut schema = self.clone();
+        for alter in alters {
+            schema = schema.altered(alter)?;
+        }
+        if schema.layout_version != self.layout_version {
+            schema
+                .retired_layouts
+                .truncate(self.retired_layouts.len() + 1);
+            schema.layout_version = self.layout_version + 1;
+        }
+        Ok(schema)
+    }
+
+    // Check conditions are bound against the columns they read, so those
+    // columns cannot change.
+    fn refuse_checked_column(&self, name: &str, column_oid: OID, action: &str) -> RS<()> {
+        match self
+            .checks
+            .iter()
+            .find(|check| check.column_oids().contains(&column_oid))
+        {
+            Some(check) => Err(mudu_error!(
+                ErrorCode::UnsupportedOperation,
+                format!(
+                    "cannot {} {}, it is used by check constraint {}",
+                    action,
+                    name,
+                    check.name()
+                )
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn find_column(&self, name: &str) -> Option<AttrIndex> {
+        self.columns.iter().position(|c| c.get_name() == name)
+    }
+
+    fn non_key_column(&self, name: &str, action: &str) -> RS<AttrIndex> {
+        let index = self.find_column(name).ok_or_else(|| {
+            mudu_error!(
+                ErrorCode::EntityNotFound,
+                format!("column {} does not exist", name)
+            )
+        })?;
+        if self.columns[index].is_primary() {
+            return Err(mudu_error!(
+                ErrorCode::UnsupportedOperation,
+                format!("cannot {} primary key column {}", action, name)
+            ));
+        }
+        Ok(index)
+    }
+
     pub fn key_tuple_desc(&self) -> RS<(TupleDesc, Vec<FieldInfo>)> {
         schema_columns_to_tuple_desc(
             self.key_indices
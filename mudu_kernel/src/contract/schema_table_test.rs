@@ -1,7 +1,10 @@
 #![allow(clippy::unwrap_used)]
 
 use crate::contract::schema_column::SchemaColumn;
+use crate::contract::schema_index::SchemaIndex;
 use crate::contract::schema_table::{schema_columns_to_tuple_desc, SchemaTable};
+use crate::x_engine::api::AlterTable;
+use mudu::error::ErrorCode;
 use mudu_type::data_type::DataType;
 use mudu_type::type_family::TypeFamily;
 
@@ -114,3 +117,203 @@ fn serde_roundtrip_preserves_schema() {
     assert_eq!(decoded.columns().len(), schema.columns().len());
     assert_eq!(decoded.id(), schema.id());
 }
+
+#[test]
+fn altered_retires_value_layout_only_on_layout_change() {
+    let schema = SchemaTable::new(
+        "t4".to_string(),
+        vec![
+            make_col("id", TypeFamily::I32),
+            make_col("a", TypeFamily::I32),
+            make_col("b", TypeFamily::F64),
+        ],
+        vec![0],
+        vec![1, 2],
+    );
+    let renamed = schema
+        .altered(&AlterTable::RenameColumn {
+            old_name: "a".to_string(),
+            new_name: "a2".to_string(),
+        })
+        .unwrap();
+    assert_eq!(renamed.layout_version(), 0);
+    assert!(renamed.retired_layouts().is_empty());
+    assert_eq!(renamed.column_by_index(1).get_name(), "a2");
+    assert_eq!(
+        renamed.column_by_index(1).get_oid(),
+        schema.column_by_index(1).get_oid()
+    );
+
+    let dropped = renamed
+        .altered(&AlterTable::DropColumn("a2".to_string()))
+        .unwrap();
+    assert_eq!(dropped.id(), schema.id());
+    assert_eq!(dropped.layout_version(), 1);
+    assert_eq!(dropped.columns().len(), 2);
+    assert_eq!(dropped.key_indices(), &vec![0]);
+    assert_eq!(dropped.value_indices(), &vec![1]);
+    assert_eq!(dropped.column_by_index(1).get_name(), "b");
+    assert_eq!(dropped.column_by_index(1).get_index(), 0);
+    let retired = &dropped.retired_layouts()[0];
+    assert_eq!(retired.version(), 0);
+    assert_eq!(retired.value_columns().len(), 2);
+    assert_eq!(retired.value_tuple_desc().unwrap().1.len(), 2);
+
+    let added = dropped
+        .altered(&AlterTable::AddColumn(make_col("c", TypeFamily::I64)))
+        .unwrap();
+    assert_eq!(added.layout_version(), 2);
+    assert_eq!(added.retired_layouts().len(), 2);
+    assert_eq!(added.value_indices(), &vec![1, 2]);
+}
+
+#[test]
+fn altered_all_retires_one_layout_per_statement() {
+    let schema = SchemaTable::new(
+        "t7".to_string(),
+        vec![
+            make_col("id", TypeFamily::I32),
+            make_col("a", TypeFamily::I32),
+            make_col("b", TypeFamily::F64),
+        ],
+        vec![0],
+        vec![1, 2],
+    );
+    let altered = schema
+        .altered_all(&[
+            AlterTable::DropColumn("a".to_string()),
+            AlterTable::AddColumn(make_col("c", TypeFamily::I64)),
+        ])
+        .unwrap();
+    assert_eq!(altered.layout_version(), 1);
+    assert_eq!(altered.retired_layouts().len(), 1);
+    assert_eq!(altered.retired_layouts()[0].version(), 0);
+    assert_eq!(altered.retired_layouts()[0].value_columns().len(), 2);
+    assert_eq!(altered.value_indices(), &vec![1, 2]);
+
+    let renamed = schema
+        .altered_all(&[AlterTable::RenameColumn {
+            old_name: "a".to_string(),
+            new_name: "a2".to_string(),
+        }])
+        .unwrap();
+    assert_eq!(renamed.layout_version(), 0);
+    assert!(renamed.retired_layouts().is_empty());
+
+    // A failing action leaves no partial change behind.
+    assert!(schema
+        .altered_all(&[
+            AlterTable::AddColumn(make_col("c", TypeFamily::I64)),
+            AlterTable::DropColumn("missing".to_string()),
+        ])
+        .is_err());
+}
+
+#[test]
+fn altered_rejects_invalid_actions() {
+    let schema = SchemaTable::new(
+        "t5".to_string(),
+        vec![
+            make_col("id", TypeFamily::I32),
+            make_col("v", TypeFamily::I32),
+        ],
+        vec![0],
+        vec![1],
+    );
+    let retype_key = AlterTable::AlterColumnType {
+        name: "id".to_string(),
+        type_id: TypeFamily::I64,
+        type_param: DataType::new_no_param(TypeFamily::I64).to_info(),
+    };
+    let cases = [
+        (retype_key, ErrorCode::UnsupportedOperation),
+        (
+            AlterTable::DropColumn("id".to_string()),
+            ErrorCode::UnsupportedOperation,
+        ),
+        (
+            AlterTable::DropColumn("v".to_string()),
+            ErrorCode::UnsupportedOperation,
+        ),
+        (
+            AlterTable::DropColumn("missing".to_string()),
+            ErrorCode::EntityNotFound,
+        ),
+        (
+            AlterTable::AddColumn(make_col("v", TypeFamily::I64)),
+            ErrorCode::EntityAlreadyExists,
+        ),
+        (
+            AlterTable::RenameColumn {
+                old_name: "v".to_string(),
+                new_name: "id".to_string(),
+            },
+            ErrorCode::EntityAlreadyExists,
+        ),
+    ];
+    for (alter, ec) in cases {
+        assert_eq!(schema.altered(&alter).unwrap_err().ec(), ec);
+    }
+}
+
+#[test]
+fn altered_adds_and_drops_indexes() {
+    let schema = SchemaTable::new(
+        "t6".to_string(),
+        vec![
+            make_col("id", TypeFamily::I32),
+            make_col("a", TypeFamily::I32),
+            make_col("b", TypeFamily::I32),
+        ],
+        vec![0],
+        vec![1, 2],
+    );
+    let a_oid = schema.column_by_index(1).get_oid();
+    let indexed = schema
+        .altered(&AlterTable::AddIndex(SchemaIndex::new(
+            "t6_a".to_string(),
+            vec![a_oid],
+        )))
+        .unwrap();
+    assert_eq!(indexed.layout_version(), 0);
+    assert!(indexed.retired_layouts().is_empty());
+    assert_eq!(
+        indexed.index_by_name("t6_a").unwrap().column_oids(),
+        &vec![a_oid]
+    );
+
+    let cases = [
+        (
+            AlterTable::AddIndex(SchemaIndex::new("t6_a".to_string(), vec![a_oid])),
+            ErrorCode::EntityAlreadyExists,
+        ),
+        (
+            AlterTable::AddIndex(SchemaIndex::new("t6_x".to_string(), Vec::new())),
+            ErrorCode::UnsupportedOperation,
+        ),
+        (
+            AlterTable::AddIndex(SchemaIndex::new("t6_x".to_string(), vec![0])),
+            ErrorCode::EntityNotFound,
+        ),
+        (
+            AlterTable::DropIndex("missing".to_string()),
+            ErrorCode::EntityNotFound,
+        ),
+        (
+            AlterTable::DropColumn("a".to_string()),
+            ErrorCode::UnsupportedOperation,
+        ),
+    ];
+    for (alter, ec) in cases {
+        assert_eq!(indexed.altered(&alter).unwrap_err().ec(), ec);
+    }
+
+    let dropped = indexed
+        .altered(&AlterTable::DropIndex("t6_a".to_string()))
+        .unwrap();
+    assert!(dropped.indexes().is_empty());
+    let dropped = dropped
+        .altered(&AlterTable::DropColumn("a".to_string()))
+        .unwrap();
+    assert_eq!(dropped.columns().len(), 2);
+}
@@ -1,8 +1,11 @@
 use mudu::common::id::{AttrIndex, OID};
 
 use crate::contract::field_info::FieldInfo;
+use crate::contract::schema_foreign_key::SchemaForeignKey;
+use crate::executor::scalar_expr::ScalarExpr;
 use mudu_contract::tuple::tuple_binary_desc::TupleBinaryDesc as TupleDesc;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 pub struct TableDesc {
     name: String,
@@ -19,6 +22,41 @@ pub struct TableDesc {
     name2oid: HashMap<String, OID>,
     oid2col: HashMap<OID, FieldInfo>,
     column_oid: Vec<OID>,
+    layout_version: u32,
+    retired_layouts: Arc<Vec<RetiredValueLayout>>,
+    indexes: Arc<Vec<IndexDesc>>,
+    foreign_keys: Arc<Vec<SchemaForeignKey>>,
+    checks: Arc<Vec<CheckDesc>>,
+}
+
+/// Secondary index of a table, resolved against the current tuple layouts.
+pub struct IndexDesc {
+    pub oid: OID,
+    pub name: String,
+    // Indexed columns in index order. FieldInfo.is_primary() tells whether
+    // the column is read from the key tuple or from the value tuple.
+    pub fields: Vec<FieldInfo>,
+    // Whether the index backs a UNIQUE constraint.
+    pub unique: bool,
+}
+
+/// `CHECK` constraint of a table, bound against the current columns.
+pub struct CheckDesc {
+    pub name: String,
+    // Columns the condition reads.
+    pub attrs: Vec<AttrIndex>,
+    // Evaluated over a full row indexed by attribute; the row violates the
+    // constraint when it is FALSE.
+    pub condition: ScalarExpr,
+}
+
+/// Value tuple descriptor of a layout retired by `ALTER TABLE`.
+pub struct RetiredValueLayout {
+    pub version: u32,
+    pub value_desc: TupleDesc,
+    // FieldInfo.datum_index() locates the field inside `value_desc`; the
+    // field id is the column OID, which is stable across layouts.
+    pub value_fields: Vec<FieldInfo>,
 }
 
 pub struct TableDescParams {
@@ -33,6 +71,11 @@ pub struct TableDescParams {
     pub value_desc: TupleDesc,
     pub name2oid: HashMap<String, OID>,
     pub oid2col: HashMap<OID, FieldInfo>,
+    pub layout_version: u32,
+    pub retired_layouts: Arc<Vec<RetiredValueLayout>>,
+    pub indexes: Arc<Vec<IndexDesc>>,
+    pub foreign_keys: Arc<Vec<SchemaForeignKey>>,
+    pub checks: Arc<Vec<CheckDesc>>,
 }
 
 impl TableDesc {
@@ -51,6 +94,11 @@ impl TableDesc {
             oid2col: params.oid2col,
             name2oid: params.name2oid,
             column_oid,
+            layout_version: params.layout_version,
+            retired_layouts: params.retired_layouts,
+            indexes: params.indexes,
+            foreign_keys: params.foreign_keys,
+            checks: params.checks,
         }
     }
 
@@ -118,4 +166,29 @@ impl TableDesc {
     pub fn original_column_oid(&self) -> &Vec<OID> {
         &self.column_oid
     }
+
+    pub fn layout_version(&self) -> u32 {
+        self.layout_version
+    }
+
+    // Ordered by ascending layout version.
+    pub fn retired_layouts(&self) -> &Vec<RetiredValueLayout> {
+        &self.retired_layouts
+    }
+
+    pub fn indexes(&self) -> &Vec<IndexDesc> {
+        &self.indexes
+    }
+
+    pub fn index_by_oid(&self, oid: OID) -> Option<&IndexDesc> {
+        self.indexes.iter().find(|index| index.oid == oid)
+    }
+
+    pub fn foreign_keys(&self) -> &Vec<SchemaForeignKey> {
+        &self.foreign_keys
+    }
+
+    pub fn checks(&self) -> &Vec<CheckDesc> {
+        &self.checks
+    }
 }
@@ -1,12 +1,17 @@
 use crate::contract::field_info::FieldInfo;
+use crate::contract::schema_foreign_key::SchemaForeignKey;
 use crate::contract::schema_table::SchemaTable;
-use crate::contract::table_desc::{TableDesc, TableDescParams};
+use crate::contract::table_desc::{
+    CheckDesc, IndexDesc, RetiredValueLayout, TableDesc, TableDescParams,
+};
+use crate::sql::expr_binding::bind_check;
 use mudu::common::id::{AttrIndex, OID};
 use mudu::common::result::RS;
 use mudu::error::ErrorCode;
 use mudu::mudu_error;
 use mudu_contract::tuple::tuple_binary_desc::TupleBinaryDesc as TupleDesc;
 use mudu_sys::sync::SMutex;
+use sql_parser::ast::parser::SQLParser;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -26,31 +31,23 @@ struct TableInner {
     value_indices: Vec<AttrIndex>,
     key_tuple_desc: TupleDesc,
     value_tuple_desc: TupleDesc,
+    retired_layouts: Arc<Vec<RetiredValueLayout>>,
+    indexes: Arc<Vec<IndexDesc>>,
+    foreign_keys: Arc<Vec<SchemaForeignKey>>,
+    checks: Arc<Vec<CheckDesc>>,
 }
 
 impl TableInfo {
     pub fn new(table_schema: SchemaTable) -> RS<Self> {
+        let mut inner = TableInner::new(table_schema)?;
+        inner.checks = Arc::new(inner.bind_checks()?);
         Ok(Self {
-            inner: Arc::new(SMutex::new(TableInner::new(table_schema)?)),
+            inner: Arc::new(SMutex::new(inner)),
         })
     }
 
     pub fn table_desc(&self) -> RS<Arc<TableDesc>> {
-        let inner = self.inner.lock()?;
-        let ret = Arc::new(TableDesc::new(TableDescParams {
-            name: inner.name().clone(),
-            oid: inner.id(),
-            key_oid: inner.key_oid.clone(),
-            value_oid: inner.value_oid.clone(),
-            key_indices: inner.key_indices.clone(),
-            value_indices: inner.value_indices.clone(),
-            fields: inner.fields.clone(),
-            key_desc: inner.key_tuple_desc.clone(),
-            value_desc: inner.value_tuple_desc.clone(),
-            name2oid: inner.name2oid.clone(),
-            oid2col: inner.oid2column.clone(),
-        }));
-        Ok(ret)
+        Ok(Arc::new(self.inner.lock()?.table_desc()))
     }
 
     pub fn schema(&self) -> RS<Arc<SchemaTable>> {
@@ -74,6 +71,15 @@ impl TableInner {
                 )
             ));
         }
+        let mut retired_layouts = Vec::with_capacity(table_schema.retired_layouts().len());
+        for layout in table_schema.retired_layouts() {
+            let (value_desc, value_fields) = layout.value_tuple_desc()?;
+            retired_layouts.push(RetiredValueLayout {
+                version: layout.version(),
+                value_desc,
+                value_fields,
+            });
+        }
         let mut name2oid = HashMap::new();
         let mut oid2column = HashMap::new();
         let mut fields = vec![FieldInfo::default(); table_schema.columns().len()];
@@ -93,7 +99,29 @@ impl TableInner {
             oid2column.insert(field_info.id(), field_info.clone());
             fields[column_index] = field_info;
         }
+        let mut indexes = Vec::with_capacity(table_schema.indexes().len());
+        for index in table_schema.indexes() {
+            let fields = index
+                .column_oids()
+                .iter()
+                .map(|oid| {
+                    oid2column.get(oid).cloned().ok_or_else(|| {
+                        mudu_error!(
+                            ErrorCode::EntityNotFound,
+                            format!("index {} references unknown column {}", index.name(), oid)
+                        )
+                    })
+                })
+                .collect::<RS<Vec<_>>>()?;
+            indexes.push(IndexDesc {
+                oid: index.id(),
+                name: index.name().clone(),
+                fields,
+                unique: index.is_unique(),
+            });
+        }
 
+        let foreign_keys = Arc::new(table_schema.foreign_keys().clone());
         Ok(Self {
             schema_table: Arc::new(table_schema),
             name2oid,
@@ -105,9 +133,57 @@ impl TableInner {
             value_indices,
             key_tuple_desc,
             value_tuple_desc,
+            retired_layouts: Arc::new(retired_layouts),
+            indexes: Arc::new(indexes),
+            foreign_keys,
+            checks: Arc::new(Vec::new()),
+        })
+    }
+
+    fn table_desc(&self) -> TableDesc {
+        TableDesc::new(TableDescParams {
+            name: self.name().clone(),
+            oid: self.id(),
+            key_oid: self.key_oid.clone(),
+            value_oid: self.value_oid.clone(),
+            key_indices: self.key_indices.clone(),
+            value_indices: self.value_indices.clone(),
+            fields: self.fields.clone(),
+            key_desc: self.key_tuple_desc.clone(),
+            value_desc: self.value_tuple_desc.clone(),
+            name2oid: self.name2oid.clone(),
+            oid2col: self.oid2column.clone(),
+            layout_version: self.schema_table.layout_version(),
+            retired_layouts: self.retired_layouts.clone(),
+            indexes: self.indexes.clone(),
+            foreign_keys: self.foreign_keys.clone(),
+            checks: self.checks.clone(),
         })
     }
 
+    // The catalog keeps the condition text of a check, which is bound here
+    // once per schema version rather than on every write.
+    fn bind_checks(&self) -> RS<Vec<CheckDesc>> {
+        let checks = self.schema_table.checks();
+        if checks.is_empty() {
+            return Ok(Vec::new());
+        }
+        let parser = SQLParser::new()?;
+        let desc = self.table_desc();
+        checks
+            .iter()
+            .map(|check| {
+                let condition = parser.parse_check_condition(check.condition())?;
+                let (condition, attrs) = bind_check(&desc, &condition)?;
+                Ok(CheckDesc {
+                    name: check.name().clone(),
+                    attrs,
+                    condition,
+                })
+            })
+            .collect()
+    }
+
     pub fn id(&self) -> OID {
         self.schema_table.id()
     }
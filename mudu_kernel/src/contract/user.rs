@@ -0,0 +1,76 @@
+use mudu::common::result::RS;
+use mudu::error::ErrorCode;
+use mudu::mudu_error;
+use mudu_contract::protocol::scram::ScramCredential;
+use serde::{Deserialize, Serialize};
+
+/// Role of a user registered in the catalog.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UserRole {
+    /// May run DDL on system objects (e.g. filesystem types) and manage
+    /// apps through the HTTP API.
+    Admin = 1,
+    /// Regular user.
+    User = 2,
+}
+
+impl UserRole {
+    /// Return the stable `u32` code of this role.
+    pub fn as_u32(&self) -> u32 {
+        match self {
+            UserRole::Admin => 1,
+            UserRole::User => 2,
+        }
+    }
+
+    /// Convert a `u32` code back into a role.
+    pub fn from_u32(value: u32) -> RS<Self> {
+        match value {
+            1 => Ok(UserRole::Admin),
+            2 => Ok(UserRole::User),
+            _ => Err(mudu_error!(
+                ErrorCode::InvalidArgument,
+                format!("invalid user role {}", value)
+            )),
+        }
+    }
+
+    pub fn is_admin(&self) -> bool {
+        matches!(self, UserRole::Admin)
+    }
+}
+
+/// Descriptor of a user registered in the catalog. Only the derived SCRAM
+/// credential is kept, never the password.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserDesc {
+    name: String,
+    role: UserRole,
+    credential: ScramCredential,
+}
+
+impl UserDesc {
+    /// Create a new user descriptor.
+    pub fn new(name: String, role: UserRole, credential: ScramCredential) -> Self {
+        Self {
+            name,
+            role,
+            credential,
+        }
+    }
+
+    /// Return the user name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Return the user role.
+    pub fn role(&self) -> UserRole {
+        self.role
+    }
+
+    /// Return the SCRAM credential of the user.
+    pub fn credential(&self) -> &ScramCredential {
+        &self.credential
+    }
+}
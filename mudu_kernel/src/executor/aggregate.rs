@@ -1,10 +1,13 @@
-//! Whole-set aggregate executor (no `GROUP BY`).
+//! Aggregate executor, over the whole input or per `GROUP BY` group.
 //!
 //! Wraps a child executor, drains it on `open()`, accumulates the
-//! aggregates, and emits exactly one result row. NULL inputs are skipped
-//! (except by `COUNT(*)`); on an empty input set COUNT yields 0 and every
-//! other aggregate yields NULL.
+//! aggregates, and emits one result row per group in first-seen order. NULL
+//! inputs are skipped (except by `COUNT(*)`). Without grouping there is
+//! exactly one group: on an empty input set COUNT yields 0 and every other
+//! aggregate yields NULL. With grouping an empty input yields no rows, and
+//! NULL group keys form one group.
 
+use crate::contract::explain_node::ExplainNode;
 use crate::contract::query_exec::QueryExec;
 use crate::executor::value_compare::compare_values;
 use crate::sql::bound_stmt::AggregateFunc;
@@ -23,6 +26,7 @@ use mudu_type::data_value::DataValue;
 use mudu_type::datum::DatumDyn;
 use mudu_type::type_family::TypeFamily;
 use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 
 /// An aggregate call resolved against the child executor's row layout.
@@ -39,6 +43,15 @@ pub struct AggregateSpec {
     pub result_type: DataType,
 }
 
+/// One column of an aggregate result row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AggregateOutput {
+    /// The value of the `i`-th `GROUP BY` column.
+    Group(usize),
+    /// The result of the `i`-th aggregate spec.
+    Aggregate(usize),
+}
+
 enum Accumulator {
     Count(i64),
     /// SUM/AVG over exact numeric families (I32/I64/NUMERIC).
@@ -219,29 +232,77 @@ pub struct AggregateExec {
     tuple_desc: TupleFieldDesc,
     child: Arc<dyn QueryExec>,
     specs: Vec<AggregateSpec>,
-    inner: FMutex<AggregateInner>,
+    /// `GROUP BY` column positions in the child row.
+    group_by: Vec<usize>,
+    outputs: Vec<AggregateOutput>,
+    inner: FMutex<VecDeque<TupleRow>>,
 }
 
-struct AggregateInner {
-    result: Option<TupleRow>,
-    emitted: bool,
+/// Accumulators of one group, keyed by its `GROUP BY` column binaries.
+struct Group {
+    key: Vec<Option<Vec<u8>>>,
+    accumulators: Vec<Accumulator>,
 }
 
 impl AggregateExec {
+    /// Aggregates the whole input; the result row holds `specs` in order.
     pub fn new(
         tuple_desc: TupleFieldDesc,
         child: Arc<dyn QueryExec>,
         specs: Vec<AggregateSpec>,
     ) -> Self {
+        let outputs = (0..specs.len()).map(AggregateOutput::Aggregate).collect();
         Self {
             tuple_desc,
             child,
             specs,
-            inner: FMutex::new(AggregateInner {
-                result: None,
-                emitted: false,
-            }),
+            group_by: Vec::new(),
+            outputs,
+            inner: FMutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Aggregates per group of the `group_by` child columns and lays the
+    /// result rows out as `outputs`.
+    pub fn with_groups(mut self, group_by: Vec<usize>, outputs: Vec<AggregateOutput>) -> Self {
+        self.group_by = group_by;
+        self.outputs = outputs;
+        self
+    }
+
+    fn new_group(&self, key: Vec<Option<Vec<u8>>>) -> RS<Group> {
+        Ok(Group {
+            key,
+            accumulators: self
+                .specs
+                .iter()
+                .map(Accumulator::new)
+                .collect::<RS<Vec<_>>>()?,
+        })
+    }
+
+    fn finish_group(&self, group: Group) -> RS<TupleRow> {
+        let mut results: Vec<Option<Vec<u8>>> = Vec::with_capacity(self.specs.len());
+        for (spec, acc) in self.specs.iter().zip(group.accumulators) {
+            let value = acc.finish(spec)?;
+            if value.is_null() {
+                results.push(None);
+            } else {
+                let binary = value.to_binary(&spec.result_type)?;
+                results.push(Some(binary.into()));
+            }
+        }
+        let mut fields = Vec::with_capacity(self.outputs.len());
+        for output in &self.outputs {
+            let field = match output {
+                AggregateOutput::Group(index) => group.key.get(*index),
+                AggregateOutput::Aggregate(index) => results.get(*index),
+            };
+            let field = field
+                .ok_or_else(|| mudu_error!(ER::InvalidState, "aggregate output out of bounds"))?;
+            fields.push(field.clone());
         }
+        Ok(TupleRow::new_nullable(fields))
     }
 
     fn decode_arg(spec: &AggregateSpec, row: &TupleRow) -> RS<Option<DataValue>> {
@@ -270,13 +331,32 @@ impl QueryExec for AggregateExec {
     async fn open(&self) -> RS<()> {
         self.child.open().await?;
 
-        let mut accumulators = self
-            .specs
-            .iter()
-            .map(Accumulator::new)
-            .collect::<RS<Vec<_>>>()?;
+        let mut groups: Vec<Group> = Vec::new();
+        let mut index: HashMap<Vec<Option<Vec<u8>>>, usize> = HashMap::new();
+        if self.group_by.is_empty() {
+            // The whole input is one group, even when it is empty.
+            groups.push(self.new_group(Vec::new())?);
+            index.insert(Vec::new(), 0);
+        }
         while let Some(row) = self.child.next().await? {
-            for (spec, acc) in self.specs.iter().zip(accumulators.iter_mut()) {
+            let key = self
+                .group_by
+                .iter()
+                .map(|pos| {
+                    row.fields().get(*pos).cloned().ok_or_else(|| {
+                        mudu_error!(ER::InvalidState, "group column out of row bounds")
+                    })
+                })
+                .collect::<RS<Vec<_>>>()?;
+            let slot = match index.get(&key) {
+                Some(slot) => *slot,
+                None => {
+                    groups.push(self.new_group(key.clone())?);
+                    index.insert(key, groups.len() - 1);
+                    groups.len() - 1
+                }
+            };
+            for (spec, acc) in self.specs.iter().zip(groups[slot].accumulators.iter_mut()) {
                 let value = match spec.arg_pos {
                     // COUNT(*) counts every row; feed a placeholder.
                     None => Some(DataValue::from_i64(1)),
@@ -286,34 +366,36 @@ impl QueryExec for AggregateExec {
             }
         }
 
-        let mut fields = Vec::with_capacity(self.specs.len());
-        for (spec, acc) in self.specs.iter().zip(accumulators) {
-            let value = acc.finish(spec)?;
-            if value.is_null() {
-                fields.push(None);
-            } else {
-                let binary = value.to_binary(&spec.result_type)?;
-                fields.push(Some(binary.into()));
-            }
-        }
-
-        let mut inner = self.inner.lock().await;
-        inner.result = Some(TupleRow::new_nullable(fields));
+        let rows = groups
+            .into_iter()
+            .map(|group| self.finish_group(group))
+            .collect::<RS<VecDeque<_>>>()?;
+        *self.inner.lock().await = rows;
         Ok(())
     }
 
     async fn next(&self) -> RS<Option<TupleRow>> {
-        let mut inner = self.inner.lock().await;
-        if inner.emitted {
-            return Ok(None);
-        }
-        inner.emitted = true;
-        Ok(inner.result.clone())
+        Ok(self.inner.lock().await.pop_front())
     }
 
     fn tuple_desc(&self) -> RS<TupleFieldDesc> {
         Ok(self.tuple_desc.clone())
     }
+
+    fn explain(&self) -> ExplainNode {
+        let funcs = self
+            .specs
+            .iter()
+            .map(|spec| format!("{:?}", spec.func).to_ascii_lowercase())
+            .collect::<Vec<_>>();
+        ExplainNode::new("Aggregate")
+            .with_detail(format!(
+                "aggregates={} groups={}",
+                funcs.join(","),
+                self.group_by.len()
+            ))
+            .with_child(self.child.explain())
+    }
 }
 
 unsafe impl Send for AggregateExec {}
@@ -512,4 +594,51 @@ mod tests {
         assert_eq!(decode(&row, 0, &i64_type()).to_i64(), 2);
         assert_eq!(decode(&row, 1, &i64_type()).to_i64(), 20);
     }
+
+    #[test]
+    fn grouped_aggregate_emits_one_row_per_group() {
+        let rows = vec![
+            TupleRow::new_nullable(vec![Some(i32_bin(1)), Some(i32_bin(10))]),
+            TupleRow::new_nullable(vec![None, Some(i32_bin(5))]),
+            TupleRow::new_nullable(vec![Some(i32_bin(1)), Some(i32_bin(20))]),
+            TupleRow::new_nullable(vec![None, Some(i32_bin(7))]),
+        ];
+        let results = run(async {
+            let agg = AggregateExec::new(
+                desc(),
+                exec_with_rows(rows),
+                vec![spec(AggregateFunc::Sum, Some(1), i64_type())],
+            )
+            .with_groups(
+                vec![0],
+                vec![AggregateOutput::Group(0), AggregateOutput::Aggregate(0)],
+            );
+            agg.open().await.unwrap();
+            let mut results = Vec::new();
+            while let Some(row) = agg.next().await.unwrap() {
+                results.push(row);
+            }
+            results
+        });
+        assert_eq!(results.len(), 2);
+        assert_eq!(decode(&results[0], 0, &i32_type()).to_i32(), 1);
+        assert_eq!(decode(&results[0], 1, &i64_type()).to_i64(), 30);
+        assert!(results[1].fields()[0].is_none());
+        assert_eq!(decode(&results[1], 1, &i64_type()).to_i64(), 12);
+    }
+
+    #[test]
+    fn grouped_aggregate_over_empty_input_yields_no_rows() {
+        let (first, _) = run(async {
+            let agg = AggregateExec::new(
+                desc(),
+                exec_with_rows(vec![]),
+                vec![spec(AggregateFunc::Count, None, i64_type())],
+            )
+            .with_groups(vec![0], vec![AggregateOutput::Aggregate(0)]);
+            agg.open().await.unwrap();
+            (agg.next().await.unwrap(), ())
+        });
+        assert!(first.is_none());
+    }
 }
@@ -0,0 +1,218 @@
+//! `EXPLAIN` and `EXPLAIN ANALYZE` executors.
+//!
+//! [`ExplainExec`] returns the operator tree of its child as a one-column
+//! result set, one row per operator. For `EXPLAIN ANALYZE` the planner wraps
+//! every operator in an [`AnalyzeExec`], which counts the rows, wall time
+//! and page reads of its operator, and the explain executor runs the plan to
+//! completion before reporting it.
+
+use crate::contract::explain_node::{ExplainNode, ExplainStats};
+use crate::contract::query_exec::QueryExec;
+use crate::storage::page::page_reads::page_reads;
+use crate::x_engine::api::TupleRow;
+use async_trait::async_trait;
+use mudu::common::result::RS;
+use mudu_contract::tuple::datum_desc::DatumDesc;
+use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
+use mudu_sys::sync::async_::futures_mutex::FMutex;
+use mudu_sys::time::instant_now;
+use mudu_type::data_type_fn_param::DataType;
+use mudu_type::data_value::DataValue;
+use mudu_type::datum::DatumDyn;
+use mudu_type::type_family::TypeFamily;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// Name of the single column of an `EXPLAIN` result set.
+pub const EXPLAIN_COLUMN: &str = "plan";
+
+/// Row layout of an `EXPLAIN` result set.
+pub fn explain_tuple_desc() -> TupleFieldDesc {
+    TupleFieldDesc::new(vec![DatumDesc::new(
+        EXPLAIN_COLUMN.to_string(),
+        DataType::default_for(TypeFamily::String),
+    )])
+}
+
+pub struct ExplainExec {
+    child: Arc<dyn QueryExec>,
+    analyze: bool,
+    lines: FMutex<VecDeque<String>>,
+}
+
+impl ExplainExec {
+    pub fn new(child: Arc<dyn QueryExec>, analyze: bool) -> Self {
+        Self {
+            child,
+            analyze,
+            lines: FMutex::new(VecDeque::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl QueryExec for ExplainExec {
+    async fn open(&self) -> RS<()> {
+        if self.analyze {
+            self.child.open().await?;
+            while self.child.next().await?.is_some() {}
+        }
+        *self.lines.lock().await = self.child.explain().render().into();
+        Ok(())
+    }
+
+    async fn next(&self) -> RS<Option<TupleRow>> {
+        let Some(line) = self.lines.lock().await.pop_front() else {
+            return Ok(None);
+        };
+        let binary =
+            DataValue::from_string(line).to_binary(&DataType::default_for(TypeFamily::String))?;
+        Ok(Some(TupleRow::new_nullable(vec![Some(binary.into())])))
+    }
+
+    fn tuple_desc(&self) -> RS<TupleFieldDesc> {
+        Ok(explain_tuple_desc())
+    }
+
+    fn explain(&self) -> ExplainNode {
+        self.child.explain()
+    }
+}
+
+/// Passes the rows of its child through unchanged and counts them, along
+/// with the time and page reads spent in the child.
+pub struct AnalyzeExec {
+    child: Arc<dyn QueryExec>,
+    rows: AtomicU64,
+    nanos: AtomicU64,
+    pages: AtomicU64,
+}
+
+impl AnalyzeExec {
+    pub fn new(child: Arc<dyn QueryExec>) -> Self {
+        Self {
+            child,
+            rows: AtomicU64::new(0),
+            nanos: AtomicU64::new(0),
+            pages: AtomicU64::new(0),
+        }
+    }
+
+    async fn metered<T, F>(&self, future: F) -> RS<T>
+    where
+        F: Future<Output = RS<T>> + Unpin,
+    {
+        let start = instant_now();
+        let (result, pages) = PageMeter { future, pages: 0 }.await;
+        self.nanos
+            .fetch_add(start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+        self.pages.fetch_add(pages, Ordering::Relaxed);
+        result
+    }
+}
+
+#[async_trait]
+impl QueryExec for AnalyzeExec {
+    async fn open(&self) -> RS<()> {
+        self.metered(self.child.open()).await
+    }
+
+    async fn next(&self) -> RS<Option<TupleRow>> {
+        let row = self.metered(self.child.next()).await?;
+        if row.is_some() {
+            self.rows.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(row)
+    }
+
+    fn tuple_desc(&self) -> RS<TupleFieldDesc> {
+        self.child.tuple_desc()
+    }
+
+    fn explain(&self) -> ExplainNode {
+        self.child.explain().with_stats(ExplainStats {
+            rows: self.rows.load(Ordering::Relaxed),
+            time: Duration::from_nanos(self.nanos.load(Ordering::Relaxed)),
+            pages: self.pages.load(Ordering::Relaxed),
+        })
+    }
+}
+
+/// Counts the page reads of the calling thread during each poll of
+/// `future`. Other tasks on the same worker thread only run between polls,
+/// so their reads are not counted.
+struct PageMeter<F> {
+    future: F,
+    pages: u64,
+}
+
+impl<F: Future + Unpin> Future for PageMeter<F> {
+    type Output = (F::Output, u64);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let before = page_reads();
+        let poll = Pin::new(&mut self.future).poll(cx);
+        self.pages += page_reads().wrapping_sub(before);
+        let pages = self.pages;
+        poll.map(|output| (output, pages))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+    use super::*;
+    use crate::executor::join_test_util::{exec_with_rows, i32_bin, run};
+    use crate::executor::limit::LimitExec;
+    use mudu_contract::tuple::typed_bin::TypedBin;
+
+    fn plan_lines(exec: ExplainExec) -> Vec<String> {
+        run(async move {
+            exec.open().await.unwrap();
+            let mut lines = Vec::new();
+            while let Some(row) = exec.next().await.unwrap() {
+                let field = row.fields()[0].clone().unwrap();
+                let value = TypedBin::new(TypeFamily::String, field)
+                    .to_value(&DataType::default_for(TypeFamily::String))
+                    .unwrap();
+                lines.push(value.expect_string().clone());
+            }
+            lines
+        })
+    }
+
+    fn rows(count: i32) -> Arc<dyn QueryExec> {
+        exec_with_rows((1..=count).map(|i| vec![Some(i32_bin(i))]).collect())
+    }
+
+    #[test]
+    fn explain_lists_operators_without_running_them() {
+        let limit = LimitExec::new(TupleFieldDesc::new(vec![]), rows(5), 1, Some(2));
+        let exec = ExplainExec::new(Arc::new(limit), false);
+        assert_eq!(
+            plan_lines(exec),
+            vec![
+                "Limit (offset=1 limit=2)".to_string(),
+                "  -> Rows".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn explain_analyze_counts_rows_per_operator() {
+        let scan: Arc<dyn QueryExec> = Arc::new(AnalyzeExec::new(rows(5)));
+        let limit = LimitExec::new(TupleFieldDesc::new(vec![]), scan, 1, Some(2));
+        let exec = ExplainExec::new(Arc::new(AnalyzeExec::new(Arc::new(limit))), true);
+        let lines = plan_lines(exec);
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("Limit (offset=1 limit=2) [rows=2 "));
+        assert!(lines[1].starts_with("  -> Rows [rows=3 "));
+        assert!(lines[1].ends_with(" pages=0]"));
+    }
+}
@@ -1,11 +1,18 @@
 //! Residual (non-key) filter executor.
 //!
-//! Wraps a child executor and evaluates non-key predicates row-by-row in
-//! memory. Rows failing any predicate (including SQL UNKNOWN results from
-//! NULL comparisons) are skipped; surviving rows are projected down to the
-//! output columns.
+//! Wraps a child executor and evaluates non-key predicates, comparisons
+//! between columns and `WHERE` conditions (`OR`, `NOT`, `IN`, `BETWEEN`,
+//! `LIKE`, `IS NULL`, boolean expressions) row-by-row in memory. Rows
+//! failing any predicate (including SQL UNKNOWN results from NULL
+//! comparisons) are skipped; surviving rows are projected down to the
+//! output columns. JSON path columns are computed first and appended to the
+//! child row, so filters and the projection can refer to them; computed
+//! select-list expressions are appended after them for surviving rows.
 
+use crate::contract::explain_node::ExplainNode;
 use crate::contract::query_exec::QueryExec;
+use crate::executor::json_path::{extract, JsonPathColumn};
+use crate::executor::scalar_expr::{encode, ScalarExpr};
 use crate::executor::value_compare::compare_values;
 use crate::x_engine::api::TupleRow;
 use async_trait::async_trait;
@@ -15,6 +22,7 @@ use mudu::mudu_error;
 use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
 use mudu_contract::tuple::typed_bin::TypedBin;
 use mudu_type::data_type_fn_param::DataType;
+use mudu_type::data_value::DataValue;
 use mudu_type::datum::DatumDyn;
 use sql_parser::ast::expr_operator::ValueCompare;
 use std::cmp::Ordering;
@@ -32,10 +40,62 @@ pub struct ResidualFilter {
     pub literal: Option<Vec<u8>>,
 }
 
+/// A comparison between two columns of the child row. Both columns belong
+/// to the same type family.
+pub struct ColumnFilter {
+    pub left_pos: usize,
+    pub left_type: DataType,
+    pub op: ValueCompare,
+    pub right_pos: usize,
+    pub right_type: DataType,
+}
+
+/// A `WHERE` condition resolved against the child executor's row layout.
+/// Literals are encoded in the column's binary format; `None` is NULL.
+pub enum ConditionFilter {
+    And(Vec<ConditionFilter>),
+    Or(Vec<ConditionFilter>),
+    Not(Box<ConditionFilter>),
+    Compare(ResidualFilter),
+    InList {
+        input_pos: usize,
+        data_type: DataType,
+        list: Vec<Option<Vec<u8>>>,
+        negated: bool,
+    },
+    Between {
+        input_pos: usize,
+        data_type: DataType,
+        low: Option<Vec<u8>>,
+        high: Option<Vec<u8>>,
+        negated: bool,
+    },
+    Like {
+        input_pos: usize,
+        data_type: DataType,
+        pattern: Option<Vec<u8>>,
+        case_insensitive: bool,
+        negated: bool,
+    },
+    IsNull {
+        input_pos: usize,
+        negated: bool,
+    },
+    /// Any other boolean expression.
+    Expr(ScalarExpr),
+}
+
 pub struct FilterExec {
     tuple_desc: TupleFieldDesc,
     child: Arc<dyn QueryExec>,
     filters: Vec<ResidualFilter>,
+    column_filters: Vec<ColumnFilter>,
+    conditions: Vec<ConditionFilter>,
+    /// JSON path columns appended to the child row before filtering.
+    json_columns: Vec<JsonPathColumn>,
+    /// Expressions and their result types, appended after the JSON path
+    /// columns of rows that pass the filters.
+    expressions: Vec<(ScalarExpr, DataType)>,
     /// Output column positions within the child row, in output order.
     projection: Vec<usize>,
 }
@@ -51,44 +111,285 @@ impl FilterExec {
             tuple_desc,
             child,
             filters,
+            column_filters: Vec::new(),
+            conditions: Vec::new(),
+            json_columns: Vec::new(),
+            expressions: Vec::new(),
             projection,
         }
     }
 
-    fn row_matches(&self, row: &TupleRow) -> RS<bool> {
-        for filter in &self.filters {
-            let field = row.fields().get(filter.input_pos).ok_or_else(|| {
-                mudu_error!(ER::InvalidState, "residual filter column out of row bounds")
+    /// Adds column/column comparisons, evaluated after the residual filters.
+    pub fn with_column_filters(mut self, column_filters: Vec<ColumnFilter>) -> Self {
+        self.column_filters = column_filters;
+        self
+    }
+
+    /// Adds `WHERE` conditions, evaluated after the other filters.
+    pub fn with_conditions(mut self, conditions: Vec<ConditionFilter>) -> Self {
+        self.conditions = conditions;
+        self
+    }
+
+    /// Adds JSON path columns; the child row is extended with their values
+    /// in order, so the first one is at the position after the child's last
+    /// column.
+    pub fn with_json_columns(mut self, json_columns: Vec<JsonPathColumn>) -> Self {
+        self.json_columns = json_columns;
+        self
+    }
+
+    /// Adds computed columns; they follow the JSON path columns in the
+    /// extended row.
+    pub fn with_expressions(mut self, expressions: Vec<(ScalarExpr, DataType)>) -> Self {
+        self.expressions = expressions;
+        self
+    }
+
+    // The child row followed by the values of the JSON path columns.
+    fn extend_json_columns(&self, fields: &[Option<Vec<u8>>]) -> RS<Vec<Option<Vec<u8>>>> {
+        let mut extended = Vec::with_capacity(fields.len() + self.json_columns.len());
+        extended.extend_from_slice(fields);
+        for column in &self.json_columns {
+            let document = fields.get(column.input_pos).ok_or_else(|| {
+                mudu_error!(ER::InvalidState, "JSON path column out of row bounds")
             })?;
-            let Some(binary) = field else {
-                // NULL column value: every comparison is UNKNOWN.
-                return Ok(false);
-            };
-            let Some(literal) = &filter.literal else {
-                // NULL literal: every comparison is UNKNOWN.
-                return Ok(false);
-            };
-            let value = TypedBin::new(filter.data_type.type_family(), binary.clone())
-                .to_value(&filter.data_type)?;
-            let literal_value = TypedBin::new(filter.data_type.type_family(), literal.clone())
-                .to_value(&filter.data_type)?;
-            let Some(ordering) = compare_values(&value, &literal_value)? else {
-                return Ok(false);
-            };
-            let matches = match filter.op {
-                ValueCompare::EQ => ordering == Ordering::Equal,
-                ValueCompare::NE => ordering != Ordering::Equal,
-                ValueCompare::LT => ordering == Ordering::Less,
-                ValueCompare::LE => ordering != Ordering::Greater,
-                ValueCompare::GT => ordering == Ordering::Greater,
-                ValueCompare::GE => ordering != Ordering::Less,
-            };
-            if !matches {
-                return Ok(false);
+            extended.push(extract(document.as_ref(), &column.path)?);
+        }
+        Ok(extended)
+    }
+}
+
+/// Whether `fields` passes every residual and column filter. A comparison
+/// involving NULL is UNKNOWN and fails the row.
+pub(crate) fn row_matches(
+    fields: &[Option<Vec<u8>>],
+    filters: &[ResidualFilter],
+    column_filters: &[ColumnFilter],
+) -> RS<bool> {
+    for filter in filters {
+        let field = fields.get(filter.input_pos).ok_or_else(|| {
+            mudu_error!(ER::InvalidState, "residual filter column out of row bounds")
+        })?;
+        let Some(binary) = field else {
+            // NULL column value: every comparison is UNKNOWN.
+            return Ok(false);
+        };
+        let Some(literal) = &filter.literal else {
+            // NULL literal: every comparison is UNKNOWN.
+            return Ok(false);
+        };
+        let value = TypedBin::new(filter.data_type.type_family(), binary.clone())
+            .to_value(&filter.data_type)?;
+        let literal_value = TypedBin::new(filter.data_type.type_family(), literal.clone())
+            .to_value(&filter.data_type)?;
+        if !compare_matches(filter.op, &value, &literal_value)? {
+            return Ok(false);
+        }
+    }
+    for filter in column_filters {
+        let column = |pos: usize| {
+            fields
+                .get(pos)
+                .ok_or_else(|| mudu_error!(ER::InvalidState, "column filter out of row bounds"))
+        };
+        let (Some(left), Some(right)) = (column(filter.left_pos)?, column(filter.right_pos)?)
+        else {
+            return Ok(false);
+        };
+        let left = TypedBin::new(filter.left_type.type_family(), left.clone())
+            .to_value(&filter.left_type)?;
+        let right = TypedBin::new(filter.right_type.type_family(), right.clone())
+            .to_value(&filter.right_type)?;
+        if !compare_matches(filter.op, &left, &right)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Whether `fields` satisfies every condition. A condition that is UNKNOWN
+/// under SQL three-valued logic fails the row.
+pub(crate) fn conditions_match(
+    fields: &[Option<Vec<u8>>],
+    conditions: &[ConditionFilter],
+) -> RS<bool> {
+    for condition in conditions {
+        if evaluate(fields, condition)? != Some(true) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+// Three-valued result of `condition`; `None` is UNKNOWN.
+fn evaluate(fields: &[Option<Vec<u8>>], condition: &ConditionFilter) -> RS<Option<bool>> {
+    let field = |pos: usize| {
+        fields
+            .get(pos)
+            .ok_or_else(|| mudu_error!(ER::InvalidState, "condition column out of row bounds"))
+    };
+    let decode = |data_type: &DataType, binary: &Vec<u8>| {
+        TypedBin::new(data_type.type_family(), binary.clone()).to_value(data_type)
+    };
+    let negate = |result: Option<bool>, negated: bool| result.map(|value| value != negated);
+    let result = match condition {
+        ConditionFilter::And(conditions) => {
+            let mut result = Some(true);
+            for condition in conditions {
+                match evaluate(fields, condition)? {
+                    Some(false) => return Ok(Some(false)),
+                    None => result = None,
+                    Some(true) => {}
+                }
+            }
+            result
+        }
+        ConditionFilter::Or(conditions) => {
+            let mut result = Some(false);
+            for condition in conditions {
+                match evaluate(fields, condition)? {
+                    Some(true) => return Ok(Some(true)),
+                    None => result = None,
+                    Some(false) => {}
+                }
+            }
+            result
+        }
+        ConditionFilter::Not(condition) => evaluate(fields, condition)?.map(|value| !value),
+        ConditionFilter::Compare(filter) => match (field(filter.input_pos)?, &filter.literal) {
+            (Some(binary), Some(literal)) => {
+                let value = decode(&filter.data_type, binary)?;
+                let literal = decode(&filter.data_type, literal)?;
+                compare_values(&value, &literal)?
+                    .map(|_| compare_matches(filter.op, &value, &literal))
+                    .transpose()?
+            }
+            _ => None,
+        },
+        ConditionFilter::InList {
+            input_pos,
+            data_type,
+            list,
+            negated,
+        } => match field(*input_pos)? {
+            None => None,
+            Some(binary) => {
+                let value = decode(data_type, binary)?;
+                // No match is FALSE, or UNKNOWN when the list holds a NULL.
+                let mut result = Some(false);
+                for literal in list {
+                    let Some(literal) = literal else {
+                        result = None;
+                        continue;
+                    };
+                    if compare_values(&value, &decode(data_type, literal)?)?
+                        == Some(Ordering::Equal)
+                    {
+                        result = Some(true);
+                        break;
+                    }
+                }
+                negate(result, *negated)
+            }
+        },
+        ConditionFilter::Between {
+            input_pos,
+            data_type,
+            low,
+            high,
+            negated,
+        } => match (field(*input_pos)?, low, high) {
+            (Some(binary), Some(low), Some(high)) => {
+                let value = decode(data_type, binary)?;
+                let above = compare_values(&value, &decode(data_type, low)?)?;
+                let below = compare_values(&value, &decode(data_type, high)?)?;
+                let result = match (above, below) {
+                    (Some(above), Some(below)) => {
+                        Some(above != Ordering::Less && below != Ordering::Greater)
+                    }
+                    _ => None,
+                };
+                negate(result, *negated)
+            }
+            _ => None,
+        },
+        ConditionFilter::Like {
+            input_pos,
+            data_type,
+            pattern,
+            case_insensitive,
+            negated,
+        } => match (field(*input_pos)?, pattern) {
+            (Some(binary), Some(pattern)) => {
+                let value = decode(data_type, binary)?;
+                let pattern = decode(data_type, pattern)?;
+                let (Some(value), Some(pattern)) = (value.as_string(), pattern.as_string()) else {
+                    return Err(mudu_error!(
+                        ER::NotImplemented,
+                        "LIKE is only implemented for string columns"
+                    ));
+                };
+                let matched = if *case_insensitive {
+                    like_matches(&value.to_lowercase(), &pattern.to_lowercase())
+                } else {
+                    like_matches(value, pattern)
+                };
+                negate(Some(matched), *negated)
+            }
+            _ => None,
+        },
+        ConditionFilter::IsNull { input_pos, negated } => {
+            Some(field(*input_pos)?.is_none() != *negated)
+        }
+        ConditionFilter::Expr(expr) => expr.truth(fields)?,
+    };
+    Ok(result)
+}
+
+// SQL `LIKE` match: `%` matches any run of characters, `_` exactly one.
+pub(crate) fn like_matches(value: &str, pattern: &str) -> bool {
+    let value: Vec<char> = value.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+    let (mut v, mut p) = (0, 0);
+    // Pattern position after the last `%` and the value position it was
+    // tried against, for backtracking.
+    let mut star: Option<(usize, usize)> = None;
+    while v < value.len() {
+        match pattern.get(p) {
+            Some('%') => {
+                star = Some((p + 1, v));
+                p += 1;
             }
+            Some(c) if *c == '_' || *c == value[v] => {
+                v += 1;
+                p += 1;
+            }
+            _ => match star {
+                Some((star_p, star_v)) => {
+                    p = star_p;
+                    v = star_v + 1;
+                    star = Some((star_p, star_v + 1));
+                }
+                None => return false,
+            },
         }
-        Ok(true)
     }
+    pattern[p..].iter().all(|c| *c == '%')
+}
+
+fn compare_matches(op: ValueCompare, left: &DataValue, right: &DataValue) -> RS<bool> {
+    let Some(ordering) = compare_values(left, right)? else {
+        return Ok(false);
+    };
+    Ok(match op {
+        ValueCompare::EQ => ordering == Ordering::Equal,
+        ValueCompare::NE => ordering != Ordering::Equal,
+        ValueCompare::LT => ordering == Ordering::Less,
+        ValueCompare::LE => ordering != Ordering::Greater,
+        ValueCompare::GT => ordering == Ordering::Greater,
+        ValueCompare::GE => ordering != Ordering::Less,
+    })
 }
 
 #[async_trait]
@@ -99,12 +400,32 @@ impl QueryExec for FilterExec {
 
     async fn next(&self) -> RS<Option<TupleRow>> {
         while let Some(row) = self.child.next().await? {
-            if self.row_matches(&row)? {
+            let extended;
+            let fields = if self.json_columns.is_empty() {
+                row.fields().as_slice()
+            } else {
+                extended = self.extend_json_columns(row.fields())?;
+                extended.as_slice()
+            };
+            if row_matches(fields, &self.filters, &self.column_filters)?
+                && conditions_match(fields, &self.conditions)?
+            {
+                let computed;
+                let fields = if self.expressions.is_empty() {
+                    fields
+                } else {
+                    let mut row = fields.to_vec();
+                    for (expr, data_type) in &self.expressions {
+                        row.push(encode(expr.eval(fields)?, data_type, data_type)?);
+                    }
+                    computed = row;
+                    computed.as_slice()
+                };
                 let projected = self
                     .projection
                     .iter()
                     .map(|pos| {
-                        row.fields().get(*pos).cloned().ok_or_else(|| {
+                        fields.get(*pos).cloned().ok_or_else(|| {
                             mudu_error!(ER::InvalidState, "projection column out of row bounds")
                         })
                     })
@@ -118,6 +439,29 @@ impl QueryExec for FilterExec {
     fn tuple_desc(&self) -> RS<TupleFieldDesc> {
         Ok(self.tuple_desc.clone())
     }
+
+    fn explain(&self) -> ExplainNode {
+        let mut detail = Vec::new();
+        if !self.filters.is_empty() {
+            detail.push(format!("filters={}", self.filters.len()));
+        }
+        if !self.column_filters.is_empty() {
+            detail.push(format!("column_filters={}", self.column_filters.len()));
+        }
+        if !self.conditions.is_empty() {
+            detail.push(format!("conditions={}", self.conditions.len()));
+        }
+        if !self.json_columns.is_empty() {
+            detail.push(format!("json_columns={}", self.json_columns.len()));
+        }
+        if !self.expressions.is_empty() {
+            detail.push(format!("expressions={}", self.expressions.len()));
+        }
+        detail.push(format!("columns={}", self.projection.len()));
+        ExplainNode::new("Filter")
+            .with_detail(detail.join(" "))
+            .with_child(self.child.explain())
+    }
 }
 
 unsafe impl Send for FilterExec {}
@@ -132,6 +476,7 @@ mod tests {
     use mudu_contract::tuple::datum_desc::DatumDesc;
     use mudu_sys::sync::SMutex;
     use mudu_type::type_family::TypeFamily;
+    use sql_parser::ast::expr_json_path::{JsonPath, JsonPathStep};
     use std::collections::VecDeque;
 
     struct TestQueryExec {
@@ -284,4 +629,145 @@ mod tests {
             assert_eq!(value.to_i32(), 8);
         })
     }
+
+    #[test]
+    fn filter_compares_columns() {
+        run(async {
+            let rows = vec![
+                TupleRow::new_nullable(vec![Some(i32_bin(1)), Some(i32_bin(1))]),
+                TupleRow::new_nullable(vec![Some(i32_bin(2)), Some(i32_bin(3))]),
+                TupleRow::new_nullable(vec![None, Some(i32_bin(4))]),
+            ];
+            let filter = FilterExec::new(desc(), exec_with_rows(rows), vec![], vec![1])
+                .with_column_filters(vec![ColumnFilter {
+                    left_pos: 0,
+                    left_type: i32_type(),
+                    op: ValueCompare::LE,
+                    right_pos: 1,
+                    right_type: i32_type(),
+                }]);
+            filter.open().await.unwrap();
+            let mut values = Vec::new();
+            while let Some(row) = filter.next().await.unwrap() {
+                let value = TypedBin::new(TypeFamily::I32, row.fields()[0].clone().unwrap())
+                    .to_value(&i32_type())
+                    .unwrap();
+                values.push(value.to_i32());
+            }
+            // A NULL operand makes the comparison UNKNOWN.
+            assert_eq!(values, vec![1, 3]);
+        })
+    }
+    #[test]
+    fn filter_evaluates_conditions_with_three_valued_logic() {
+        run(async {
+            let rows = (1..=6)
+                .map(|value| TupleRow::new_nullable(vec![Some(i32_bin(value))]))
+                .chain([TupleRow::new_nullable(vec![None])])
+                .collect::<Vec<_>>();
+            let in_list = |negated| ConditionFilter::InList {
+                input_pos: 0,
+                data_type: i32_type(),
+                list: vec![Some(i32_bin(1)), Some(i32_bin(5)), None],
+                negated,
+            };
+            // a IN (1, 5, NULL) OR (a BETWEEN 2 AND 3 AND NOT a IS NULL)
+            let filter = FilterExec::new(desc(), exec_with_rows(rows), vec![], vec![0])
+                .with_conditions(vec![
+                    ConditionFilter::Or(vec![
+                        in_list(false),
+                        ConditionFilter::And(vec![
+                            ConditionFilter::Between {
+                                input_pos: 0,
+                                data_type: i32_type(),
+                                low: Some(i32_bin(2)),
+                                high: Some(i32_bin(3)),
+                                negated: false,
+                            },
+                            ConditionFilter::Not(Box::new(ConditionFilter::IsNull {
+                                input_pos: 0,
+                                negated: false,
+                            })),
+                        ]),
+                    ]),
+                    // NOT NOT (a IS NOT NULL)
+                    ConditionFilter::Not(Box::new(ConditionFilter::Not(Box::new(
+                        ConditionFilter::IsNull {
+                            input_pos: 0,
+                            negated: true,
+                        },
+                    )))),
+                ]);
+            filter.open().await.unwrap();
+            let mut values = Vec::new();
+            while let Some(row) = filter.next().await.unwrap() {
+                let value = TypedBin::new(TypeFamily::I32, row.fields()[0].clone().unwrap())
+                    .to_value(&i32_type())
+                    .unwrap();
+                values.push(value.to_i32());
+            }
+            assert_eq!(values, vec![1, 2, 3, 5]);
+            // NOT IN with a NULL in the list is never TRUE.
+            assert!(!conditions_match(&[Some(i32_bin(4))], &[in_list(true)]).unwrap());
+        })
+    }
+
+    #[test]
+    fn like_matches_wildcards() {
+        assert!(like_matches("alice", "a%"));
+        assert!(like_matches("alice", "%li%"));
+        assert!(like_matches("alice", "_lic_"));
+        assert!(like_matches("", "%"));
+        assert!(!like_matches("alice", "a_"));
+        assert!(!like_matches("alice", "%x%"));
+        assert!(like_matches("aXbXc", "a%b%c"));
+    }
+
+    #[test]
+    fn filter_computes_json_path_columns_before_filtering() {
+        run(async {
+            let json_type = DataType::default_for(TypeFamily::Json);
+            let text_type = DataType::default_for(TypeFamily::String);
+            let document = |text: &str| -> Option<Vec<u8>> {
+                Some(
+                    DataValue::from_json(text.to_string())
+                        .to_binary(&json_type)
+                        .unwrap()
+                        .into(),
+                )
+            };
+            let rows = vec![
+                TupleRow::new_nullable(vec![Some(i32_bin(1)), document(r#"{"kind":"a"}"#)]),
+                TupleRow::new_nullable(vec![Some(i32_bin(2)), document(r#"{"kind":"b"}"#)]),
+                TupleRow::new_nullable(vec![Some(i32_bin(3)), None]),
+            ];
+            let mut path = JsonPath::new();
+            path.push(JsonPathStep::Key("kind".to_string()), true);
+            let literal: Vec<u8> = DataValue::from_string("b".to_string())
+                .to_binary(&text_type)
+                .unwrap()
+                .into();
+            // The path column is appended at position 2.
+            let filter = FilterExec::new(
+                desc(),
+                exec_with_rows(rows),
+                vec![ResidualFilter {
+                    input_pos: 2,
+                    data_type: text_type,
+                    op: ValueCompare::NE,
+                    literal: Some(literal),
+                }],
+                vec![0],
+            )
+            .with_json_columns(vec![JsonPathColumn { input_pos: 1, path }]);
+            filter.open().await.unwrap();
+            let row = filter.next().await.unwrap().unwrap();
+            let value = TypedBin::new(TypeFamily::I32, row.fields()[0].clone().unwrap())
+                .to_value(&i32_type())
+                .unwrap();
+            assert_eq!(value.to_i32(), 1);
+            // A NULL document yields a NULL path value, which fails the filter.
+            assert!(filter.next().await.unwrap().is_none());
+        })
+    }
 }
@@ -0,0 +1,228 @@
+//! Hash join executor.
+//!
+//! Builds a hash table over the inner child on open, keyed by the binary
+//! encoding of the inner equi-join columns, and probes it with each outer
+//! row. Key columns on both sides share one data type, so equal values have
+//! equal encodings; every candidate is still checked against the full `ON`
+//! condition. Rows with a NULL key column never match.
+
+use crate::contract::explain_node::ExplainNode;
+use crate::contract::query_exec::QueryExec;
+use crate::executor::join::{join_kind_name, JoinCondition, JoinCursor};
+use crate::x_engine::api::TupleRow;
+use async_trait::async_trait;
+use mudu::common::result::RS;
+use mudu::error::ErrorCode as ER;
+use mudu::mudu_error;
+use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
+use mudu_sys::sync::async_::futures_mutex::FMutex;
+use sql_parser::ast::select_join::JoinKind;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// One equi-join column pair.
+pub struct HashJoinKey {
+    /// Position in the outer row.
+    pub outer_pos: usize,
+    /// Position in the inner row.
+    pub inner_pos: usize,
+}
+
+pub struct HashJoinExec {
+    tuple_desc: TupleFieldDesc,
+    /// Join kind and key count, and the outer and inner inputs, for
+    /// `EXPLAIN`.
+    detail: String,
+    inputs: [Arc<dyn QueryExec>; 2],
+    inner: FMutex<_HashJoinExec>,
+}
+
+struct _HashJoinExec {
+    outer: Arc<dyn QueryExec>,
+    inner: Arc<dyn QueryExec>,
+    keys: Vec<HashJoinKey>,
+    table: HashMap<Vec<Vec<u8>>, Vec<Vec<Option<Vec<u8>>>>>,
+    cursor: JoinCursor,
+}
+
+impl HashJoinExec {
+    pub fn new(
+        tuple_desc: TupleFieldDesc,
+        kind: JoinKind,
+        outer: Arc<dyn QueryExec>,
+        inner: Arc<dyn QueryExec>,
+        keys: Vec<HashJoinKey>,
+        inner_width: usize,
+        condition: JoinCondition,
+    ) -> Self {
+        Self {
+            tuple_desc,
+            detail: format!("kind={} keys={}", join_kind_name(kind), keys.len()),
+            inputs: [outer.clone(), inner.clone()],
+            inner: FMutex::new(_HashJoinExec {
+                outer,
+                inner,
+                keys,
+                table: HashMap::new(),
+                cursor: JoinCursor::new(kind, inner_width, condition),
+            }),
+        }
+    }
+}
+
+#[async_trait]
+impl QueryExec for HashJoinExec {
+    async fn open(&self) -> RS<()> {
+        let mut inner = self.inner.lock().await;
+        inner.open().await
+    }
+
+    async fn next(&self) -> RS<Option<TupleRow>> {
+        let mut inner = self.inner.lock().await;
+        inner.next().await
+    }
+
+    fn tuple_desc(&self) -> RS<TupleFieldDesc> {
+        Ok(self.tuple_desc.clone())
+    }
+
+    fn explain(&self) -> ExplainNode {
+        let [outer, inner] = &self.inputs;
+        ExplainNode::new("HashJoin")
+            .with_detail(self.detail.clone())
+            .with_child(outer.explain())
+            .with_child(inner.explain())
+    }
+}
+
+/// The key columns at `positions`; `None` when any of them is NULL.
+fn hash_key(
+    fields: &[Option<Vec<u8>>],
+    positions: impl Iterator<Item = usize>,
+) -> RS<Option<Vec<Vec<u8>>>> {
+    let mut key = Vec::new();
+    for pos in positions {
+        let field = fields
+            .get(pos)
+            .ok_or_else(|| mudu_error!(ER::InvalidState, "join key column out of row bounds"))?;
+        match field {
+            Some(binary) => key.push(binary.clone()),
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(key))
+}
+
+impl _HashJoinExec {
+    async fn open(&mut self) -> RS<()> {
+        self.outer.open().await?;
+        self.inner.open().await?;
+        self.table.clear();
+        while let Some(row) = self.inner.next().await? {
+            let fields = row.into_fields();
+            let key = hash_key(&fields, self.keys.iter().map(|key| key.inner_pos))?;
+            if let Some(key) = key {
+                self.table.entry(key).or_default().push(fields);
+            }
+        }
+        Ok(())
+    }
+
+    async fn next(&mut self) -> RS<Option<TupleRow>> {
+        loop {
+            if let Some(row) = self.cursor.next_row()? {
+                return Ok(Some(row));
+            }
+            let Some(outer) = self.outer.next().await? else {
+                return Ok(None);
+            };
+            let key = hash_key(outer.fields(), self.keys.iter().map(|key| key.outer_pos))?;
+            let candidates = key
+                .and_then(|key| self.table.get(&key).cloned())
+                .unwrap_or_default();
+            self.cursor.reset(outer, candidates);
+        }
+    }
+}
+
+unsafe impl Send for HashJoinExec {}
+
+unsafe impl Sync for HashJoinExec {}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+    use super::*;
+    use crate::executor::filter::ColumnFilter;
+    use crate::executor::join_test_util::{exec_with_rows, i32_bin, i32_type, run, values};
+    use sql_parser::ast::expr_operator::ValueCompare;
+
+    fn equal_keys() -> JoinCondition {
+        JoinCondition {
+            filters: Vec::new(),
+            column_filters: vec![ColumnFilter {
+                left_pos: 0,
+                left_type: i32_type(),
+                op: ValueCompare::EQ,
+                right_pos: 1,
+                right_type: i32_type(),
+            }],
+        }
+    }
+
+    fn join(kind: JoinKind) -> HashJoinExec {
+        HashJoinExec::new(
+            TupleFieldDesc::new(vec![]),
+            kind,
+            exec_with_rows(vec![
+                vec![Some(i32_bin(1))],
+                vec![None],
+                vec![Some(i32_bin(2))],
+            ]),
+            exec_with_rows(vec![
+                vec![Some(i32_bin(2)), Some(i32_bin(20))],
+                vec![Some(i32_bin(2)), Some(i32_bin(21))],
+                vec![None, Some(i32_bin(30))],
+            ]),
+            vec![HashJoinKey {
+                outer_pos: 0,
+                inner_pos: 0,
+            }],
+            2,
+            equal_keys(),
+        )
+    }
+
+    #[test]
+    fn hash_join_matches_equal_keys() {
+        run(async {
+            let join = join(JoinKind::Inner);
+            join.open().await.unwrap();
+            assert_eq!(
+                values(&join).await,
+                vec![
+                    vec![Some(2), Some(2), Some(20)],
+                    vec![Some(2), Some(2), Some(21)],
+                ]
+            );
+        })
+    }
+
+    #[test]
+    fn hash_left_join_keeps_rows_with_null_keys() {
+        run(async {
+            let join = join(JoinKind::LeftOuter);
+            join.open().await.unwrap();
+            assert_eq!(
+                values(&join).await,
+                vec![
+                    vec![Some(1), None, None],
+                    vec![None, None, None],
+                    vec![Some(2), Some(2), Some(20)],
+                    vec![Some(2), Some(2), Some(21)],
+                ]
+            );
+        })
+    }
+}
@@ -1,23 +1,28 @@
+use crate::contract::explain_node::ExplainNode;
 use crate::contract::meta_mgr::MetaMgr;
 use crate::contract::query_exec::QueryExec;
 use crate::executor::project_tuple_desc;
-use crate::x_engine::api::{TupleRow, XContract};
+use crate::x_engine::api::{TupleRow, VecDatum, XContract};
 use crate::x_engine::x_param::PAccessKey;
 use async_trait::async_trait;
 use mudu::common::result::RS;
 use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc as TupleDesc;
 use mudu_sys::sync::async_::futures_mutex::FMutex;
+use std::collections::VecDeque;
 use std::sync::Arc;
 
 pub struct IndexAccessKey {
     tuple_desc: TupleDesc,
+    table_name: String,
+    /// Keys read in order instead of `param.pred_key`, when set.
+    keys: Option<Vec<VecDatum>>,
     inner: FMutex<_IndexAccessKey>,
 }
 
 struct _IndexAccessKey {
     param: PAccessKey,
     x_contract: Arc<dyn XContract>,
-    fetched: bool,
+    pending: VecDeque<VecDatum>,
 }
 
 impl IndexAccessKey {
@@ -30,16 +35,29 @@ impl IndexAccessKey {
         let tuple_desc = project_tuple_desc(&table_desc, &param.select);
         Ok(Self {
             tuple_desc,
+            table_name: table_desc.name().clone(),
+            keys: None,
             inner: FMutex::new(_IndexAccessKey::new(param, x_contract)),
         })
     }
+
+    /// Reads each of `keys` in turn, skipping keys with no row, instead of
+    /// the single key of the access parameters.
+    pub fn with_keys(mut self, keys: Vec<VecDatum>) -> Self {
+        self.keys = Some(keys);
+        self
+    }
 }
 
 #[async_trait]
 impl QueryExec for IndexAccessKey {
     async fn open(&self) -> RS<()> {
         let mut inner = self.inner.lock().await;
-        inner.open().await
+        inner.pending = match &self.keys {
+            Some(keys) => keys.iter().cloned().collect(),
+            None => VecDeque::from([inner.param.pred_key.clone()]),
+        };
+        Ok(())
     }
 
     async fn next(&self) -> RS<Option<TupleRow>> {
@@ -50,40 +68,36 @@ impl QueryExec for IndexAccessKey {
     fn tuple_desc(&self) -> RS<TupleDesc> {
         Ok(self.tuple_desc.clone())
     }
+
+    fn explain(&self) -> ExplainNode {
+        let keys = self.keys.as_ref().map_or(1, Vec::len);
+        ExplainNode::new("IndexAccessKey")
+            .with_detail(format!("table={} keys={}", self.table_name, keys))
+    }
 }
 
 impl _IndexAccessKey {
     fn new(param: PAccessKey, x_contract: Arc<dyn XContract>) -> Self {
+        let pending = VecDeque::from([param.pred_key.clone()]);
         Self {
             param,
             x_contract,
-            fetched: false,
+            pending,
         }
     }
 
-    async fn open(&mut self) -> RS<()> {
-        self.fetched = false;
-        Ok(())
-    }
-
     async fn next(&mut self) -> RS<Option<TupleRow>> {
-        if self.fetched {
-            return Ok(None);
+        while let Some(key) = self.pending.pop_front() {
+            let p = &self.param;
+            let row = self
+                .x_contract
+                .read_key(p.tx_mgr.clone(), p.table_id, &key, &p.select, &p.opt_read)
+                .await?;
+            if let Some(row) = row {
+                return Ok(Some(TupleRow::new_nullable(row)));
+            }
         }
-        self.fetched = true;
-
-        let p = &self.param;
-        let row = self
-            .x_contract
-            .read_key(
-                p.tx_mgr.clone(),
-                p.table_id,
-                &p.pred_key,
-                &p.select,
-                &p.opt_read,
-            )
-            .await?;
-        Ok(row.map(TupleRow::new_nullable))
+        Ok(None)
     }
 }
 
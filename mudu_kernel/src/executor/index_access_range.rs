@@ -1,16 +1,22 @@
+use crate::contract::explain_node::ExplainNode;
 use crate::contract::meta_mgr::MetaMgr;
 use crate::contract::query_exec::QueryExec;
 use crate::executor::project_tuple_desc;
-use crate::x_engine::api::{RSCursor, TupleRow, XContract};
+use crate::x_engine::api::{Predicate, RSCursor, TupleRow, XContract};
 use crate::x_engine::x_param::PAccessRange;
 use async_trait::async_trait;
 use mudu::common::result::RS;
 use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc as TupleDesc;
 use mudu_sys::sync::async_::futures_mutex::FMutex;
+use std::ops::Bound;
 use std::sync::Arc;
 
 pub struct IndexAccessRange {
     tuple_desc: TupleDesc,
+    /// Table and key access, for `EXPLAIN`.
+    access: String,
+    offset: u64,
+    limit: Option<u64>,
     inner: FMutex<_IndexAccessRange>,
 }
 
@@ -18,6 +24,10 @@ struct _IndexAccessRange {
     param: PAccessRange,
     cursor: Option<Arc<dyn RSCursor>>,
     x_contract: Arc<dyn XContract>,
+    /// Rows still to skip before the first returned row.
+    skip: u64,
+    /// Rows still to return; `None` is unbounded.
+    remaining: Option<u64>,
 }
 
 impl IndexAccessRange {
@@ -30,15 +40,29 @@ impl IndexAccessRange {
         let tuple_desc = project_tuple_desc(&table_desc, &param.select);
         Ok(Self {
             tuple_desc,
+            access: format!("table={} {}", table_desc.name(), describe_access(&param)),
+            offset: 0,
+            limit: None,
             inner: FMutex::new(_IndexAccessRange::new(param, x_contract)),
         })
     }
+
+    /// Skips the first `offset` rows of the range and stops the cursor after
+    /// `limit` rows. Only valid when the rows are consumed in key order with
+    /// no filter in between.
+    pub fn with_limit(mut self, offset: u64, limit: Option<u64>) -> Self {
+        self.offset = offset;
+        self.limit = limit;
+        self
+    }
 }
 
 #[async_trait]
 impl QueryExec for IndexAccessRange {
     async fn open(&self) -> RS<()> {
         let mut inner = self.inner.lock().await;
+        inner.skip = self.offset;
+        inner.remaining = self.limit;
         inner.open().await
     }
 
@@ -50,6 +74,45 @@ impl QueryExec for IndexAccessRange {
     fn tuple_desc(&self) -> RS<TupleDesc> {
         Ok(self.tuple_desc.clone())
     }
+
+    fn explain(&self) -> ExplainNode {
+        let mut detail = self.access.clone();
+        if self.offset > 0 {
+            detail.push_str(&format!(" offset={}", self.offset));
+        }
+        if let Some(limit) = self.limit {
+            detail.push_str(&format!(" limit={}", limit));
+        }
+        ExplainNode::new("IndexAccessRange").with_detail(detail)
+    }
+}
+
+/// How the range is read: through a secondary index, by a primary key
+/// prefix or range, or as a scan of the whole table.
+fn describe_access(param: &PAccessRange) -> String {
+    match &param.pred_non_key {
+        Predicate::IndexEq { index_oid, prefix } => {
+            return format!("index={} eq_columns={}", index_oid, prefix.len());
+        }
+        Predicate::KeyPrefixEq(prefix) => return format!("key_prefix={}", prefix.len()),
+        Predicate::CNF(_) | Predicate::DNF(_) => {}
+    }
+    match (param.pred_key.start(), param.pred_key.end()) {
+        (Bound::Unbounded, Bound::Unbounded) => "full scan".to_string(),
+        (start, end) => format!(
+            "key range start={} end={}",
+            bound_kind(start),
+            bound_kind(end)
+        ),
+    }
+}
+
+fn bound_kind<T>(bound: &Bound<T>) -> &'static str {
+    match bound {
+        Bound::Included(_) => "inclusive",
+        Bound::Excluded(_) => "exclusive",
+        Bound::Unbounded => "unbounded",
+    }
 }
 
 impl _IndexAccessRange {
@@ -58,6 +121,8 @@ impl _IndexAccessRange {
             param,
             cursor: None,
             x_contract,
+            skip: 0,
+            remaining: None,
         }
     }
 
@@ -79,6 +144,25 @@ impl _IndexAccessRange {
     }
 
     async fn next(&mut self) -> RS<Option<TupleRow>> {
+        if self.remaining == Some(0) {
+            self.cursor = None;
+        }
+        while self.skip > 0 {
+            if self.next_from_cursor().await?.is_none() {
+                return Ok(None);
+            }
+            self.skip -= 1;
+        }
+        let row = self.next_from_cursor().await?;
+        if row.is_some() {
+            if let Some(remaining) = &mut self.remaining {
+                *remaining -= 1;
+            }
+        }
+        Ok(row)
+    }
+
+    async fn next_from_cursor(&mut self) -> RS<Option<TupleRow>> {
         match &self.cursor {
             Some(cursor) => {
                 let row = cursor.next().await?;
@@ -0,0 +1,147 @@
+//! Index nested-loop join executor.
+//!
+//! For each outer row, reads the inner row by exact primary key through
+//! `XContract::read_key`. Used when the `ON` equalities fix every primary
+//! key column of the inner table. An outer row with a NULL key column has no
+//! match.
+
+use crate::contract::explain_node::ExplainNode;
+use crate::contract::query_exec::QueryExec;
+use crate::executor::join::{join_kind_name, JoinCondition, JoinCursor};
+use crate::x_engine::api::{OptRead, TupleRow, VecDatum, VecSelTerm, XContract};
+use crate::x_engine::tx_mgr::TxMgr;
+use async_trait::async_trait;
+use mudu::common::id::{AttrIndex, OID};
+use mudu::common::result::RS;
+use mudu::error::ErrorCode as ER;
+use mudu::mudu_error;
+use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
+use mudu_sys::sync::async_::futures_mutex::FMutex;
+use sql_parser::ast::select_join::JoinKind;
+use std::sync::Arc;
+
+/// How the inner table is read for one outer row.
+pub struct IndexLookup {
+    pub tx_mgr: Arc<dyn TxMgr>,
+    pub table_id: OID,
+    /// Inner primary key attributes and the outer row positions that
+    /// supply them.
+    pub key: Vec<(AttrIndex, usize)>,
+    /// Inner attributes to read, in inner row order.
+    pub select: VecSelTerm,
+}
+
+pub struct IndexNestedLoopJoinExec {
+    tuple_desc: TupleFieldDesc,
+    /// Join kind and inner table, and the outer input, for `EXPLAIN`.
+    detail: String,
+    outer: Arc<dyn QueryExec>,
+    inner: FMutex<_IndexNestedLoopJoinExec>,
+}
+
+struct _IndexNestedLoopJoinExec {
+    outer: Arc<dyn QueryExec>,
+    lookup: IndexLookup,
+    x_contract: Arc<dyn XContract>,
+    cursor: JoinCursor,
+}
+
+impl IndexNestedLoopJoinExec {
+    pub fn new(
+        tuple_desc: TupleFieldDesc,
+        kind: JoinKind,
+        outer: Arc<dyn QueryExec>,
+        lookup: IndexLookup,
+        x_contract: Arc<dyn XContract>,
+        condition: JoinCondition,
+    ) -> Self {
+        let inner_width = lookup.select.vec().len();
+        Self {
+            tuple_desc,
+            detail: format!(
+                "kind={} table_id={} key_columns={}",
+                join_kind_name(kind),
+                lookup.table_id,
+                lookup.key.len()
+            ),
+            outer: outer.clone(),
+            inner: FMutex::new(_IndexNestedLoopJoinExec {
+                outer,
+                lookup,
+                x_contract,
+                cursor: JoinCursor::new(kind, inner_width, condition),
+            }),
+        }
+    }
+}
+
+#[async_trait]
+impl QueryExec for IndexNestedLoopJoinExec {
+    async fn open(&self) -> RS<()> {
+        let mut inner = self.inner.lock().await;
+        inner.open().await
+    }
+
+    async fn next(&self) -> RS<Option<TupleRow>> {
+        let mut inner = self.inner.lock().await;
+        inner.next().await
+    }
+
+    fn tuple_desc(&self) -> RS<TupleFieldDesc> {
+        Ok(self.tuple_desc.clone())
+    }
+
+    fn explain(&self) -> ExplainNode {
+        ExplainNode::new("IndexNestedLoopJoin")
+            .with_detail(self.detail.clone())
+            .with_child(self.outer.explain())
+    }
+}
+
+impl _IndexNestedLoopJoinExec {
+    async fn open(&mut self) -> RS<()> {
+        self.outer.open().await
+    }
+
+    async fn next(&mut self) -> RS<Option<TupleRow>> {
+        loop {
+            if let Some(row) = self.cursor.next_row()? {
+                return Ok(Some(row));
+            }
+            let Some(outer) = self.outer.next().await? else {
+                return Ok(None);
+            };
+            let candidates = self.probe(&outer).await?;
+            self.cursor.reset(outer, candidates);
+        }
+    }
+
+    async fn probe(&self, outer: &TupleRow) -> RS<Vec<Vec<Option<Vec<u8>>>>> {
+        let mut key = Vec::with_capacity(self.lookup.key.len());
+        for (attr, pos) in &self.lookup.key {
+            let field = outer.fields().get(*pos).ok_or_else(|| {
+                mudu_error!(ER::InvalidState, "join key column out of row bounds")
+            })?;
+            match field {
+                Some(binary) => key.push((*attr, binary.clone())),
+                None => return Ok(Vec::new()),
+            }
+        }
+        let lookup = &self.lookup;
+        let row = self
+            .x_contract
+            .read_key(
+                lookup.tx_mgr.clone(),
+                lookup.table_id,
+                &VecDatum::new(key),
+                &lookup.select,
+                &OptRead::default(),
+            )
+            .await?;
+        Ok(row.into_iter().collect())
+    }
+}
+
+unsafe impl Send for IndexNestedLoopJoinExec {}
+
+unsafe impl Sync for IndexNestedLoopJoinExec {}
@@ -0,0 +1,89 @@
+//! State shared by the join executors.
+//!
+//! Every join executor reads one outer row at a time, finds the inner rows
+//! that may match it and hands both to a [`JoinCursor`], which concatenates
+//! them, evaluates the `ON` conditions and NULL-extends unmatched outer rows
+//! of a LEFT JOIN.
+
+use crate::executor::filter::{row_matches, ColumnFilter, ResidualFilter};
+use crate::x_engine::api::TupleRow;
+use mudu::common::result::RS;
+use sql_parser::ast::select_join::JoinKind;
+
+/// `ON` conditions of a join, resolved against the joined row layout (the
+/// outer columns followed by the inner columns).
+#[derive(Default)]
+pub struct JoinCondition {
+    pub filters: Vec<ResidualFilter>,
+    pub column_filters: Vec<ColumnFilter>,
+}
+
+/// `kind` as `EXPLAIN` shows it.
+pub(crate) fn join_kind_name(kind: JoinKind) -> &'static str {
+    match kind {
+        JoinKind::Inner => "inner",
+        JoinKind::LeftOuter => "left outer",
+    }
+}
+
+pub(crate) struct JoinCursor {
+    kind: JoinKind,
+    inner_width: usize,
+    condition: JoinCondition,
+    outer: Option<TupleRow>,
+    candidates: Vec<Vec<Option<Vec<u8>>>>,
+    next_candidate: usize,
+    matched: bool,
+}
+
+impl JoinCursor {
+    pub(crate) fn new(kind: JoinKind, inner_width: usize, condition: JoinCondition) -> Self {
+        Self {
+            kind,
+            inner_width,
+            condition,
+            outer: None,
+            candidates: Vec::new(),
+            next_candidate: 0,
+            matched: false,
+        }
+    }
+
+    /// Starts matching `outer` against the inner rows `candidates`.
+    pub(crate) fn reset(&mut self, outer: TupleRow, candidates: Vec<Vec<Option<Vec<u8>>>>) {
+        self.outer = Some(outer);
+        self.candidates = candidates;
+        self.next_candidate = 0;
+        self.matched = false;
+    }
+
+    /// The next joined row for the current outer row; `None` once the outer
+    /// row is exhausted and a new one must be supplied.
+    pub(crate) fn next_row(&mut self) -> RS<Option<TupleRow>> {
+        let Some(outer) = &self.outer else {
+            return Ok(None);
+        };
+        while let Some(inner) = self.candidates.get(self.next_candidate) {
+            self.next_candidate += 1;
+            let mut fields = outer.fields().clone();
+            fields.extend(inner.iter().cloned());
+            if row_matches(
+                &fields,
+                &self.condition.filters,
+                &self.condition.column_filters,
+            )? {
+                self.matched = true;
+                return Ok(Some(TupleRow::new_nullable(fields)));
+            }
+        }
+        let outer = self.outer.take();
+        match outer {
+            Some(outer) if self.kind == JoinKind::LeftOuter && !self.matched => {
+                let mut fields = outer.into_fields();
+                fields.resize(fields.len() + self.inner_width, None);
+                Ok(Some(TupleRow::new_nullable(fields)))
+            }
+            _ => Ok(None),
+        }
+    }
+}
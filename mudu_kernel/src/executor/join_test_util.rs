@@ -0,0 +1,82 @@
+//! Fixtures shared by the join executor tests.
+
+use crate::contract::query_exec::QueryExec;
+use crate::x_engine::api::TupleRow;
+use async_trait::async_trait;
+use mudu::common::result::RS;
+use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
+use mudu_contract::tuple::typed_bin::TypedBin;
+use mudu_sys::sync::SMutex;
+use mudu_type::data_type_fn_param::DataType;
+use mudu_type::datum::DatumDyn;
+use mudu_type::type_family::TypeFamily;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+struct TestQueryExec {
+    rows: Vec<TupleRow>,
+    pending: SMutex<VecDeque<TupleRow>>,
+}
+
+#[async_trait]
+impl QueryExec for TestQueryExec {
+    async fn open(&self) -> RS<()> {
+        *self.pending.lock().unwrap() = self.rows.iter().cloned().collect();
+        Ok(())
+    }
+
+    async fn next(&self) -> RS<Option<TupleRow>> {
+        Ok(self.pending.lock().unwrap().pop_front())
+    }
+
+    fn tuple_desc(&self) -> RS<TupleFieldDesc> {
+        Ok(TupleFieldDesc::new(vec![]))
+    }
+}
+
+pub(crate) fn exec_with_rows(rows: Vec<Vec<Option<Vec<u8>>>>) -> Arc<dyn QueryExec> {
+    Arc::new(TestQueryExec {
+        rows: rows.into_iter().map(TupleRow::new_nullable).collect(),
+        pending: SMutex::new(VecDeque::new()),
+    })
+}
+
+pub(crate) fn i32_type() -> DataType {
+    DataType::default_for(TypeFamily::I32)
+}
+
+pub(crate) fn i32_bin(value: i32) -> Vec<u8> {
+    mudu_type::data_value::DataValue::from_i32(value)
+        .to_binary(&i32_type())
+        .unwrap()
+        .into()
+}
+
+/// Drains `exec`, decoding every column as `I32`.
+pub(crate) async fn values(exec: &dyn QueryExec) -> Vec<Vec<Option<i32>>> {
+    let mut rows = Vec::new();
+    while let Some(row) = exec.next().await.unwrap() {
+        rows.push(
+            row.fields()
+                .iter()
+                .map(|field| {
+                    field.as_ref().map(|binary| {
+                        TypedBin::new(TypeFamily::I32, binary.clone())
+                            .to_value(&i32_type())
+                            .unwrap()
+                            .to_i32()
+                    })
+                })
+                .collect(),
+        );
+    }
+    rows
+}
+
+pub(crate) fn run<F, T>(future: F) -> T
+where
+    F: std::future::Future<Output = T> + 'static,
+    T: 'static,
+{
+    mudu_sys::task::async_::block_on_tokio_current_thread(future).unwrap()
+}
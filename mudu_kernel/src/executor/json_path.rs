@@ -0,0 +1,166 @@
+//! Evaluation of the `->` and `->>` JSON path operators.
+//!
+//! A path is applied to a JSON column of the child row and yields a virtual
+//! column: a JSON document for `->` and text for `->>`. A missing key, an
+//! out-of-range index or a step into a scalar yields NULL.
+
+use mudu::common::result::RS;
+use mudu::error::ErrorCode as ER;
+use mudu::mudu_error;
+use mudu::utils::json::{from_json_str, JsonValue};
+use mudu_contract::tuple::typed_bin::TypedBin;
+use mudu_type::data_type_fn_param::DataType;
+use mudu_type::data_value::DataValue;
+use mudu_type::datum::DatumDyn;
+use mudu_type::type_family::TypeFamily;
+use sql_parser::ast::expr_json_path::{JsonPath, JsonPathStep};
+
+/// A JSON path applied to a column of the child row; the result is appended
+/// to the row after the child's columns.
+pub struct JsonPathColumn {
+    /// Position of the JSON column in the child row.
+    pub input_pos: usize,
+    pub path: JsonPath,
+}
+
+/// Type of the value `path` yields: JSON for `->`, text for `->>`.
+pub fn json_path_type(path: &JsonPath) -> DataType {
+    if path.as_text() {
+        DataType::default_for(TypeFamily::String)
+    } else {
+        DataType::default_for(TypeFamily::Json)
+    }
+}
+
+/// Applies `path` to the binary JSON document `binary`; `None` is NULL.
+pub fn extract(binary: Option<&Vec<u8>>, path: &JsonPath) -> RS<Option<Vec<u8>>> {
+    let Some(binary) = binary else {
+        return Ok(None);
+    };
+    let json_type = DataType::default_for(TypeFamily::Json);
+    let value = TypedBin::new(TypeFamily::Json, binary.clone()).to_value(&json_type)?;
+    let text = value
+        .as_json()
+        .ok_or_else(|| mudu_error!(ER::InvalidType, "JSON path applied to a non-JSON value"))?;
+    let document = from_json_str::<JsonValue>(text)?;
+    let mut current = &document;
+    for step in path.steps() {
+        let next = match (step, current) {
+            (JsonPathStep::Key(key), JsonValue::Object(object)) => object.get(key),
+            (JsonPathStep::Index(index), JsonValue::Array(array)) => {
+                let index = if *index < 0 {
+                    array.len() as i64 + *index
+                } else {
+                    *index
+                };
+                usize::try_from(index)
+                    .ok()
+                    .and_then(|index| array.get(index))
+            }
+            _ => None,
+        };
+        match next {
+            Some(next) => current = next,
+            None => return Ok(None),
+        }
+    }
+    let result = if path.as_text() {
+        let text = match current {
+            JsonValue::Null => return Ok(None),
+            JsonValue::String(text) => text.clone(),
+            other => other.to_string(),
+        };
+        DataValue::from_string(text).to_binary(&json_path_type(path))?
+    } else {
+        DataValue::from_json(current.to_string()).to_binary(&json_type)?
+    };
+    Ok(Some(result.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+    use super::*;
+
+    fn document(text: &str) -> Vec<u8> {
+        DataValue::from_json(text.to_string())
+            .to_binary(&DataType::default_for(TypeFamily::Json))
+            .unwrap()
+            .into()
+    }
+
+    fn path(steps: Vec<JsonPathStep>, as_text: bool) -> JsonPath {
+        let mut path = JsonPath::new();
+        let last = steps.len().saturating_sub(1);
+        for (index, step) in steps.into_iter().enumerate() {
+            path.push(step, as_text && index == last);
+        }
+        path
+    }
+
+    fn decode(binary: Vec<u8>, path: &JsonPath) -> DataValue {
+        let data_type = json_path_type(path);
+        TypedBin::new(data_type.type_family(), binary)
+            .to_value(&data_type)
+            .unwrap()
+    }
+
+    #[test]
+    fn arrow_yields_a_json_document() {
+        let doc = document(r#"{"a":{"b":[1,2,3]}}"#);
+        let path = path(
+            vec![
+                JsonPathStep::Key("a".to_string()),
+                JsonPathStep::Key("b".to_string()),
+            ],
+            false,
+        );
+        let value = decode(extract(Some(&doc), &path).unwrap().unwrap(), &path);
+        assert_eq!(value.expect_json(), "[1,2,3]");
+    }
+
+    #[test]
+    fn double_arrow_yields_text() {
+        let doc = document(r#"{"name":"ann","tags":["x","y"],"n":7}"#);
+        let name = path(vec![JsonPathStep::Key("name".to_string())], true);
+        let value = decode(extract(Some(&doc), &name).unwrap().unwrap(), &name);
+        assert_eq!(value.expect_string(), "ann");
+        let last_tag = path(
+            vec![
+                JsonPathStep::Key("tags".to_string()),
+                JsonPathStep::Index(-1),
+            ],
+            true,
+        );
+        let value = decode(extract(Some(&doc), &last_tag).unwrap().unwrap(), &last_tag);
+        assert_eq!(value.expect_string(), "y");
+        let number = path(vec![JsonPathStep::Key("n".to_string())], true);
+        let value = decode(extract(Some(&doc), &number).unwrap().unwrap(), &number);
+        assert_eq!(value.expect_string(), "7");
+    }
+
+    #[test]
+    fn missing_steps_and_json_null_yield_null() {
+        let doc = document(r#"{"a":[1],"b":null}"#);
+        for path in [
+            path(vec![JsonPathStep::Key("z".to_string())], false),
+            path(
+                vec![JsonPathStep::Key("a".to_string()), JsonPathStep::Index(5)],
+                false,
+            ),
+            path(
+                vec![
+                    JsonPathStep::Key("a".to_string()),
+                    JsonPathStep::Key("x".to_string()),
+                ],
+                false,
+            ),
+            path(vec![JsonPathStep::Key("b".to_string())], true),
+        ] {
+            assert!(extract(Some(&doc), &path).unwrap().is_none());
+        }
+        let path = path(vec![JsonPathStep::Key("a".to_string())], false);
+        assert!(extract(None, &path).unwrap().is_none());
+    }
+}
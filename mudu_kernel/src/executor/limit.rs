@@ -0,0 +1,127 @@
+//! `LIMIT`/`OFFSET` executor.
+//!
+//! Skips the first `offset` child rows and then passes at most `limit`
+//! rows through. Once the limit is reached the child is not read again.
+
+use crate::contract::explain_node::ExplainNode;
+use crate::contract::query_exec::QueryExec;
+use crate::x_engine::api::TupleRow;
+use async_trait::async_trait;
+use mudu::common::result::RS;
+use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
+use mudu_sys::sync::async_::futures_mutex::FMutex;
+use std::sync::Arc;
+
+pub struct LimitExec {
+    tuple_desc: TupleFieldDesc,
+    child: Arc<dyn QueryExec>,
+    offset: u64,
+    limit: Option<u64>,
+    inner: FMutex<LimitInner>,
+}
+
+struct LimitInner {
+    skipped: u64,
+    emitted: u64,
+}
+
+impl LimitExec {
+    pub fn new(
+        tuple_desc: TupleFieldDesc,
+        child: Arc<dyn QueryExec>,
+        offset: u64,
+        limit: Option<u64>,
+    ) -> Self {
+        Self {
+            tuple_desc,
+            child,
+            offset,
+            limit,
+            inner: FMutex::new(LimitInner {
+                skipped: 0,
+                emitted: 0,
+            }),
+        }
+    }
+}
+
+#[async_trait]
+impl QueryExec for LimitExec {
+    async fn open(&self) -> RS<()> {
+        self.child.open().await?;
+        let mut inner = self.inner.lock().await;
+        inner.skipped = 0;
+        inner.emitted = 0;
+        Ok(())
+    }
+
+    async fn next(&self) -> RS<Option<TupleRow>> {
+        let mut inner = self.inner.lock().await;
+        if self.limit.is_some_and(|limit| inner.emitted >= limit) {
+            return Ok(None);
+        }
+        while inner.skipped < self.offset {
+            if self.child.next().await?.is_none() {
+                return Ok(None);
+            }
+            inner.skipped += 1;
+        }
+        let row = self.child.next().await?;
+        if row.is_some() {
+            inner.emitted += 1;
+        }
+        Ok(row)
+    }
+
+    fn tuple_desc(&self) -> RS<TupleFieldDesc> {
+        Ok(self.tuple_desc.clone())
+    }
+
+    fn explain(&self) -> ExplainNode {
+        let mut detail = format!("offset={}", self.offset);
+        if let Some(limit) = self.limit {
+            detail.push_str(&format!(" limit={}", limit));
+        }
+        ExplainNode::new("Limit")
+            .with_detail(detail)
+            .with_child(self.child.explain())
+    }
+}
+
+unsafe impl Send for LimitExec {}
+
+unsafe impl Sync for LimitExec {}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+    use super::*;
+    use crate::executor::join_test_util::{exec_with_rows, i32_bin, run, values};
+
+    fn limit(offset: u64, limit: Option<u64>) -> LimitExec {
+        LimitExec::new(
+            TupleFieldDesc::new(vec![]),
+            exec_with_rows((1..=5).map(|i| vec![Some(i32_bin(i))]).collect()),
+            offset,
+            limit,
+        )
+    }
+
+    #[test]
+    fn limit_skips_offset_and_caps_rows() {
+        run(async {
+            let exec = limit(1, Some(2));
+            exec.open().await.unwrap();
+            assert_eq!(values(&exec).await, vec![vec![Some(2)], vec![Some(3)]]);
+
+            let exec = limit(3, None);
+            exec.open().await.unwrap();
+            assert_eq!(values(&exec).await, vec![vec![Some(4)], vec![Some(5)]]);
+
+            let exec = limit(9, Some(1));
+            exec.open().await.unwrap();
+            assert!(values(&exec).await.is_empty());
+        })
+    }
+}
@@ -4,9 +4,21 @@ use mudu_contract::tuple::datum_desc::DatumDesc;
 use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
 
 pub mod aggregate;
+pub mod explain;
 pub mod filter;
+pub mod hash_join;
 pub mod index_access_key;
 pub mod index_access_range;
+pub mod index_nested_loop_join;
+pub mod join;
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod join_test_util;
+pub mod json_path;
+pub mod limit;
+pub mod nested_loop_join;
+pub mod scalar_expr;
+pub mod sort;
 pub(crate) mod value_compare;
 
 pub(crate) fn project_tuple_desc(table_desc: &TableDesc, select: &VecSelTerm) -> TupleFieldDesc {
@@ -0,0 +1,171 @@
+//! Nested-loop join executor.
+//!
+//! Materializes the inner child on open and compares every outer row with
+//! every inner row. Used when the `ON` condition has no usable equality.
+
+use crate::contract::explain_node::ExplainNode;
+use crate::contract::query_exec::QueryExec;
+use crate::executor::join::{join_kind_name, JoinCondition, JoinCursor};
+use crate::x_engine::api::TupleRow;
+use async_trait::async_trait;
+use mudu::common::result::RS;
+use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
+use mudu_sys::sync::async_::futures_mutex::FMutex;
+use sql_parser::ast::select_join::JoinKind;
+use std::sync::Arc;
+
+pub struct NestedLoopJoinExec {
+    tuple_desc: TupleFieldDesc,
+    kind: JoinKind,
+    /// The outer and inner inputs, for `EXPLAIN`.
+    inputs: [Arc<dyn QueryExec>; 2],
+    inner: FMutex<_NestedLoopJoinExec>,
+}
+
+struct _NestedLoopJoinExec {
+    outer: Arc<dyn QueryExec>,
+    inner: Arc<dyn QueryExec>,
+    inner_rows: Vec<Vec<Option<Vec<u8>>>>,
+    cursor: JoinCursor,
+}
+
+impl NestedLoopJoinExec {
+    pub fn new(
+        tuple_desc: TupleFieldDesc,
+        kind: JoinKind,
+        outer: Arc<dyn QueryExec>,
+        inner: Arc<dyn QueryExec>,
+        inner_width: usize,
+        condition: JoinCondition,
+    ) -> Self {
+        Self {
+            tuple_desc,
+            kind,
+            inputs: [outer.clone(), inner.clone()],
+            inner: FMutex::new(_NestedLoopJoinExec {
+                outer,
+                inner,
+                inner_rows: Vec::new(),
+                cursor: JoinCursor::new(kind, inner_width, condition),
+            }),
+        }
+    }
+}
+
+#[async_trait]
+impl QueryExec for NestedLoopJoinExec {
+    async fn open(&self) -> RS<()> {
+        let mut inner = self.inner.lock().await;
+        inner.open().await
+    }
+
+    async fn next(&self) -> RS<Option<TupleRow>> {
+        let mut inner = self.inner.lock().await;
+        inner.next().await
+    }
+
+    fn tuple_desc(&self) -> RS<TupleFieldDesc> {
+        Ok(self.tuple_desc.clone())
+    }
+
+    fn explain(&self) -> ExplainNode {
+        let [outer, inner] = &self.inputs;
+        ExplainNode::new("NestedLoopJoin")
+            .with_detail(format!("kind={}", join_kind_name(self.kind)))
+            .with_child(outer.explain())
+            .with_child(inner.explain())
+    }
+}
+
+impl _NestedLoopJoinExec {
+    async fn open(&mut self) -> RS<()> {
+        self.outer.open().await?;
+        self.inner.open().await?;
+        self.inner_rows.clear();
+        while let Some(row) = self.inner.next().await? {
+            self.inner_rows.push(row.into_fields());
+        }
+        Ok(())
+    }
+
+    async fn next(&mut self) -> RS<Option<TupleRow>> {
+        loop {
+            if let Some(row) = self.cursor.next_row()? {
+                return Ok(Some(row));
+            }
+            let Some(outer) = self.outer.next().await? else {
+                return Ok(None);
+            };
+            self.cursor.reset(outer, self.inner_rows.clone());
+        }
+    }
+}
+
+unsafe impl Send for NestedLoopJoinExec {}
+
+unsafe impl Sync for NestedLoopJoinExec {}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+    use super::*;
+    use crate::executor::filter::ColumnFilter;
+    use crate::executor::join_test_util::{exec_with_rows, i32_bin, i32_type, run, values};
+    use sql_parser::ast::expr_operator::ValueCompare;
+
+    fn less_than() -> JoinCondition {
+        JoinCondition {
+            filters: Vec::new(),
+            column_filters: vec![ColumnFilter {
+                left_pos: 0,
+                left_type: i32_type(),
+                op: ValueCompare::LT,
+                right_pos: 1,
+                right_type: i32_type(),
+            }],
+        }
+    }
+
+    #[test]
+    fn nested_loop_join_matches_non_equality_conditions() {
+        run(async {
+            let join = NestedLoopJoinExec::new(
+                TupleFieldDesc::new(vec![]),
+                JoinKind::Inner,
+                exec_with_rows(vec![vec![Some(i32_bin(1))], vec![Some(i32_bin(2))]]),
+                exec_with_rows(vec![vec![Some(i32_bin(2))], vec![Some(i32_bin(3))]]),
+                1,
+                less_than(),
+            );
+            join.open().await.unwrap();
+            assert_eq!(
+                values(&join).await,
+                vec![
+                    vec![Some(1), Some(2)],
+                    vec![Some(1), Some(3)],
+                    vec![Some(2), Some(3)],
+                ]
+            );
+        })
+    }
+
+    #[test]
+    fn nested_loop_left_join_null_extends_unmatched_rows() {
+        run(async {
+            let join = NestedLoopJoinExec::new(
+                TupleFieldDesc::new(vec![]),
+                JoinKind::LeftOuter,
+                exec_with_rows(vec![vec![Some(i32_bin(5))], vec![Some(i32_bin(1))]]),
+                exec_with_rows(vec![vec![Some(i32_bin(2))]]),
+                1,
+                less_than(),
+            );
+            join.open().await.unwrap();
+            assert_eq!(
+                values(&join).await,
+                vec![vec![Some(5), None], vec![Some(1), Some(2)]]
+            );
+        })
+    }
+}
@@ -0,0 +1,1150 @@
+//! Row-at-a-time evaluation of scalar expressions.
+//!
+//! A [`ScalarExpr`] is a [`BoundExpr`] resolved against an executor row:
+//! columns become row positions and constants are decoded once. NULL
+//! propagates through operators and functions (except `coalesce`),
+//! predicates follow SQL three-valued logic (a NULL boolean is UNKNOWN), and
+//! integer overflow, division by zero and failed conversions are errors.
+
+use crate::executor::filter::like_matches;
+use crate::executor::value_compare::compare_values;
+use crate::sql::bound_stmt::{BoundExpr, ScalarFunc, TimeField};
+use bigdecimal::{BigDecimal, RoundingMode, ToPrimitive};
+use chrono::{DateTime, Datelike, Days, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use mudu::common::id::AttrIndex;
+use mudu::common::result::RS;
+use mudu::data_type::date::DateValue;
+use mudu::data_type::numeric::Numeric;
+use mudu::data_type::time::TimeValue;
+use mudu::data_type::timestamp::TimestampValue;
+use mudu::data_type::timestamptz::TimestampTzValue;
+use mudu::data_type::uuid::parse_uuid;
+use mudu::error::ErrorCode as ER;
+use mudu::error::MuduError;
+use mudu::mudu_error;
+use mudu::utils::json::{from_json_str, JsonValue};
+use mudu_contract::tuple::typed_bin::TypedBin;
+use mudu_type::data_type_fn_param::DataType;
+use mudu_type::data_value::DataValue;
+use mudu_type::datum::DatumDyn;
+use mudu_type::type_family::TypeFamily;
+use sql_parser::ast::expr_operator::{Arithmetic, ValueCompare};
+use std::cmp::Ordering;
+
+const MICROS_PER_DAY: i64 = 86_400_000_000;
+
+/// A scalar expression over the fields of an executor row.
+#[derive(Clone, Debug)]
+pub enum ScalarExpr {
+    Column {
+        pos: usize,
+        data_type: DataType,
+    },
+    Const(DataValue),
+    Arithmetic {
+        op: Arithmetic,
+        left: Box<ScalarExpr>,
+        right: Box<ScalarExpr>,
+        data_type: DataType,
+    },
+    Compare {
+        op: ValueCompare,
+        left: Box<ScalarExpr>,
+        right: Box<ScalarExpr>,
+    },
+    And(Vec<ScalarExpr>),
+    Or(Vec<ScalarExpr>),
+    Not(Box<ScalarExpr>),
+    IsNull {
+        operand: Box<ScalarExpr>,
+        negated: bool,
+    },
+    InList {
+        operand: Box<ScalarExpr>,
+        list: Vec<ScalarExpr>,
+        negated: bool,
+    },
+    Between {
+        operand: Box<ScalarExpr>,
+        low: Box<ScalarExpr>,
+        high: Box<ScalarExpr>,
+        negated: bool,
+    },
+    Like {
+        operand: Box<ScalarExpr>,
+        pattern: Box<ScalarExpr>,
+        case_insensitive: bool,
+        negated: bool,
+    },
+    Case {
+        branches: Vec<(ScalarExpr, ScalarExpr)>,
+        else_result: Option<Box<ScalarExpr>>,
+    },
+    Cast {
+        operand: Box<ScalarExpr>,
+        from: DataType,
+        to: DataType,
+    },
+    Function {
+        func: ScalarFunc,
+        args: Vec<ScalarExpr>,
+        data_type: DataType,
+    },
+}
+
+impl ScalarExpr {
+    /// Resolve `expr` for rows that hold column `attr` at position
+    /// `pos(attr)`.
+    pub fn resolve(expr: &BoundExpr, pos: &dyn Fn(AttrIndex) -> RS<usize>) -> RS<ScalarExpr> {
+        let boxed =
+            |expr: &BoundExpr| -> RS<Box<ScalarExpr>> { Ok(Box::new(Self::resolve(expr, pos)?)) };
+        let all = |exprs: &[BoundExpr]| -> RS<Vec<ScalarExpr>> {
+            exprs.iter().map(|expr| Self::resolve(expr, pos)).collect()
+        };
+        Ok(match expr {
+            BoundExpr::Column { attr, data_type } => ScalarExpr::Column {
+                pos: pos(*attr)?,
+                data_type: data_type.clone(),
+            },
+            BoundExpr::Const { value, data_type } => {
+                ScalarExpr::Const(decode(value.as_ref(), data_type)?)
+            }
+            BoundExpr::Arithmetic {
+                op,
+                left,
+                right,
+                data_type,
+            } => ScalarExpr::Arithmetic {
+                op: *op,
+                left: boxed(left)?,
+                right: boxed(right)?,
+                data_type: data_type.clone(),
+            },
+            BoundExpr::Compare { op, left, right } => ScalarExpr::Compare {
+                op: *op,
+                left: boxed(left)?,
+                right: boxed(right)?,
+            },
+            BoundExpr::And(operands) => ScalarExpr::And(all(operands)?),
+            BoundExpr::Or(operands) => ScalarExpr::Or(all(operands)?),
+            BoundExpr::Not(operand) => ScalarExpr::Not(boxed(operand)?),
+            BoundExpr::IsNull { operand, negated } => ScalarExpr::IsNull {
+                operand: boxed(operand)?,
+                negated: *negated,
+            },
+            BoundExpr::InList {
+                operand,
+                list,
+                negated,
+            } => ScalarExpr::InList {
+                operand: boxed(operand)?,
+                list: all(list)?,
+                negated: *negated,
+            },
+            BoundExpr::Between {
+                operand,
+                low,
+                high,
+                negated,
+            } => ScalarExpr::Between {
+                operand: boxed(operand)?,
+                low: boxed(low)?,
+                high: boxed(high)?,
+                negated: *negated,
+            },
+            BoundExpr::Like {
+                operand,
+                pattern,
+                case_insensitive,
+                negated,
+            } => ScalarExpr::Like {
+                operand: boxed(operand)?,
+                pattern: boxed(pattern)?,
+                case_insensitive: *case_insensitive,
+                negated: *negated,
+            },
+            BoundExpr::Case {
+                branches,
+                else_result,
+                ..
+            } => ScalarExpr::Case {
+                branches: branches
+                    .iter()
+                    .map(|(condition, result)| {
+                        Ok((Self::resolve(condition, pos)?, Self::resolve(result, pos)?))
+                    })
+                    .collect::<RS<Vec<_>>>()?,
+                else_result: else_result.as_deref().map(boxed).transpose()?,
+            },
+            BoundExpr::Cast { operand, data_type } => ScalarExpr::Cast {
+                from: operand.data_type(),
+                operand: boxed(operand)?,
+                to: data_type.clone(),
+            },
+            BoundExpr::Function {
+                func,
+                args,
+                data_type,
+            } => ScalarExpr::Function {
+                func: *func,
+                args: all(args)?,
+                data_type: data_type.clone(),
+            },
+        })
+    }
+
+    /// Evaluate the expression over `row`; SQL NULL is a null value.
+    pub fn eval(&self, row: &[Option<Vec<u8>>]) -> RS<DataValue> {
+        match self {
+            ScalarExpr::Column { pos, data_type } => {
+                let field = row.get(*pos).ok_or_else(|| {
+                    mudu_error!(ER::InvalidState, "expression column out of row bounds")
+                })?;
+                decode(field.as_ref(), data_type)
+            }
+            ScalarExpr::Const(value) => Ok(value.clone()),
+            ScalarExpr::Arithmetic {
+                op,
+                left,
+                right,
+                data_type,
+            } => arithmetic(*op, &left.eval(row)?, &right.eval(row)?, data_type),
+            ScalarExpr::Compare { op, left, right } => Ok(from_truth(compare(
+                *op,
+                &left.eval(row)?,
+                &right.eval(row)?,
+            )?)),
+            ScalarExpr::And(operands) => {
+                // FALSE decides the conjunction even next to UNKNOWN.
+                let mut result = Some(true);
+                for operand in operands {
+                    match operand.truth(row)? {
+                        Some(false) => return Ok(DataValue::from_bool(false)),
+                        Some(true) => {}
+                        None => result = None,
+                    }
+                }
+                Ok(from_truth(result))
+            }
+            ScalarExpr::Or(operands) => {
+                let mut result = Some(false);
+                for operand in operands {
+                    match operand.truth(row)? {
+                        Some(true) => return Ok(DataValue::from_bool(true)),
+                        Some(false) => {}
+                        None => result = None,
+                    }
+                }
+                Ok(from_truth(result))
+            }
+            ScalarExpr::Not(operand) => Ok(from_truth(operand.truth(row)?.map(|b| !b))),
+            ScalarExpr::IsNull { operand, negated } => Ok(DataValue::from_bool(
+                operand.eval(row)?.is_null() != *negated,
+            )),
+            ScalarExpr::InList {
+                operand,
+                list,
+                negated,
+            } => {
+                let value = operand.eval(row)?;
+                let mut result = Some(false);
+                for item in list {
+                    match compare(ValueCompare::EQ, &value, &item.eval(row)?)? {
+                        Some(true) => {
+                            result = Some(true);
+                            break;
+                        }
+                        Some(false) => {}
+                        None => result = None,
+                    }
+                }
+                Ok(from_truth(result.map(|b| b != *negated)))
+            }
+            ScalarExpr::Between {
+                operand,
+                low,
+                high,
+                negated,
+            } => {
+                let value = operand.eval(row)?;
+                let above = compare(ValueCompare::GE, &value, &low.eval(row)?)?;
+                let below = compare(ValueCompare::LE, &value, &high.eval(row)?)?;
+                let result = match (above, below) {
+                    (Some(false), _) | (_, Some(false)) => Some(false),
+                    (Some(true), Some(true)) => Some(true),
+                    _ => None,
+                };
+                Ok(from_truth(result.map(|b| b != *negated)))
+            }
+            ScalarExpr::Like {
+                operand,
+                pattern,
+                case_insensitive,
+                negated,
+            } => {
+                let value = operand.eval(row)?;
+                let pattern = pattern.eval(row)?;
+                if value.is_null() || pattern.is_null() {
+                    return Ok(DataValue::null());
+                }
+                let (Some(value), Some(pattern)) = (value.as_string(), pattern.as_string()) else {
+                    return Err(mudu_error!(ER::InvalidState, "LIKE requires text operands"));
+                };
+                let matched = if *case_insensitive {
+                    like_matches(&value.to_lowercase(), &pattern.to_lowercase())
+                } else {
+                    like_matches(value, pattern)
+                };
+                Ok(DataValue::from_bool(matched != *negated))
+            }
+            ScalarExpr::Case {
+                branches,
+                else_result,
+            } => {
+                for (condition, result) in branches {
+                    if condition.truth(row)? == Some(true) {
+                        return result.eval(row);
+                    }
+                }
+                match else_result {
+                    Some(else_result) => else_result.eval(row),
+                    None => Ok(DataValue::null()),
+                }
+            }
+            ScalarExpr::Cast { operand, from, to } => cast_value(operand.eval(row)?, from, to),
+            ScalarExpr::Function {
+                func,
+                args,
+                data_type,
+            } => {
+                let values = args
+                    .iter()
+                    .map(|arg| arg.eval(row))
+                    .collect::<RS<Vec<_>>>()?;
+                function(*func, values, data_type)
+            }
+        }
+    }
+
+    /// Evaluate a boolean expression; `None` is UNKNOWN.
+    pub fn truth(&self, row: &[Option<Vec<u8>>]) -> RS<Option<bool>> {
+        let value = self.eval(row)?;
+        if value.is_null() {
+            return Ok(None);
+        }
+        value
+            .as_bool()
+            .map(|b| Some(*b))
+            .ok_or_else(|| mudu_error!(ER::InvalidState, "expression is not boolean"))
+    }
+}
+
+/// Convert `value` of type `from` to type `to` with SQL `CAST` semantics:
+/// numbers round half away from zero, text is parsed, and anything that
+/// does not fit is a `TypeConversionFailed` error.
+pub(crate) fn cast_value(value: DataValue, from: &DataType, to: &DataType) -> RS<DataValue> {
+    if value.is_null() {
+        return Ok(value);
+    }
+    let failed = || {
+        mudu_error!(
+            ER::TypeConversionFailed,
+            format!("cannot cast {} to {}", from.name(), to.name())
+        )
+    };
+    let target = to.type_family();
+    if value.type_family()? == target {
+        // Only NUMERIC needs adjusting, to the target scale.
+        return Ok(match (value.as_numeric(), to.as_numeric_param()) {
+            (Some(numeric), Some(param)) => DataValue::from_numeric(
+                numeric.with_scale_round(param.scale() as i64, RoundingMode::HalfUp),
+            ),
+            _ => value,
+        });
+    }
+    let text = value.as_string().map(|text| text.trim().to_string());
+    Ok(match target {
+        TypeFamily::I8 | TypeFamily::I16 | TypeFamily::I32 | TypeFamily::I64 => {
+            let wide = if let Some(v) = integer_of(&value) {
+                v
+            } else if let Some(v) = value.as_numeric() {
+                v.with_scale_round(0, RoundingMode::HalfUp)
+                    .as_bigdecimal()
+                    .to_i64()
+                    .ok_or_else(failed)?
+            } else if let Some(v) = float_of(&value) {
+                let v = v.round();
+                if !v.is_finite() || v < i64::MIN as f64 || v >= i64::MAX as f64 {
+                    return Err(failed());
+                }
+                v as i64
+            } else if let Some(text) = &text {
+                text.parse::<i64>().map_err(|_| failed())?
+            } else if let Some(v) = value.as_bool() {
+                *v as i64
+            } else {
+                return Err(failed());
+            };
+            match target {
+                TypeFamily::I8 => DataValue::from_i8(i8::try_from(wide).map_err(|_| failed())?),
+                TypeFamily::I16 => DataValue::from_i16(i16::try_from(wide).map_err(|_| failed())?),
+                TypeFamily::I32 => DataValue::from_i32(i32::try_from(wide).map_err(|_| failed())?),
+                _ => DataValue::from_i64(wide),
+            }
+        }
+        TypeFamily::F32 | TypeFamily::F64 => {
+            let v = if let Some(v) = float_of(&value) {
+                v
+            } else if let Some(text) = &text {
+                text.parse::<f64>().map_err(|_| failed())?
+            } else {
+                return Err(failed());
+            };
+            if target == TypeFamily::F32 {
+                DataValue::from_f32(v as f32)
+            } else {
+                DataValue::from_f64(v)
+            }
+        }
+        TypeFamily::Numeric => {
+            let v = if let Some(v) = decimal_of(&value) {
+                Numeric::new(v)
+            } else if let Some(v) = float_of(&value) {
+                if !v.is_finite() {
+                    return Err(failed());
+                }
+                Numeric::parse(&v.to_string()).map_err(|_| failed())?
+            } else if let Some(text) = &text {
+                Numeric::parse(text).map_err(|_| failed())?
+            } else {
+                return Err(failed());
+            };
+            DataValue::from_numeric(match to.as_numeric_param() {
+                Some(param) => v.with_scale_round(param.scale() as i64, RoundingMode::HalfUp),
+                None => v,
+            })
+        }
+        TypeFamily::String => DataValue::from_string(text_of(&value, from)?),
+        TypeFamily::Bool => {
+            if let Some(v) = integer_of(&value) {
+                DataValue::from_bool(v != 0)
+            } else if let Some(text) = &text {
+                DataValue::from_bool(match text.to_lowercase().as_str() {
+                    "t" | "true" | "y" | "yes" | "on" | "1" => true,
+                    "f" | "false" | "n" | "no" | "off" | "0" => false,
+                    _ => return Err(failed()),
+                })
+            } else {
+                return Err(failed());
+            }
+        }
+        TypeFamily::Uuid => match &text {
+            Some(text) => DataValue::from_uuid(parse_uuid(text).map_err(|_| failed())?),
+            None => return Err(failed()),
+        },
+        TypeFamily::Json => match &text {
+            Some(text) => DataValue::from_json(
+                from_json_str::<JsonValue>(text)
+                    .map_err(|_| failed())?
+                    .to_string(),
+            ),
+            None => DataValue::from_json(json_of(&value, from)?.to_string()),
+        },
+        TypeFamily::Date | TypeFamily::Time | TypeFamily::Timestamp | TypeFamily::TimestampTz => {
+            match &text {
+                Some(text) => target.fn_input_json()(&JsonValue::String(text.clone()), to)
+                    .map_err(|e| {
+                        mudu_error!(
+                            ER::TypeConversionFailed,
+                            format!("cannot cast {:?} to {}", text, to.name()),
+                            e
+                        )
+                    })?,
+                None => temporal_cast(&value, target).map_err(|_| failed())?,
+            }
+        }
+        _ => return Err(failed()),
+    })
+}
+
+/// Encode `value` of type `from` as a binary of column type `to`; NULL is
+/// `None`.
+pub(crate) fn encode(value: DataValue, from: &DataType, to: &DataType) -> RS<Option<Vec<u8>>> {
+    if value.is_null() {
+        return Ok(None);
+    }
+    let value = cast_value(value, from, to)?;
+    Ok(Some(value.to_binary(to)?.into()))
+}
+
+fn decode(binary: Option<&Vec<u8>>, data_type: &DataType) -> RS<DataValue> {
+    match binary {
+        Some(binary) => TypedBin::new(data_type.type_family(), binary.clone()).to_value(data_type),
+        None => Ok(DataValue::null()),
+    }
+}
+
+fn from_truth(truth: Option<bool>) -> DataValue {
+    match truth {
+        Some(b) => DataValue::from_bool(b),
+        None => DataValue::null(),
+    }
+}
+
+fn division_by_zero() -> MuduError {
+    mudu_error!(ER::InvalidArgument, "division by zero")
+}
+
+pub(crate) fn integer_of(value: &DataValue) -> Option<i64> {
+    if let Some(v) = value.as_i64() {
+        Some(*v)
+    } else if let Some(v) = value.as_i32() {
+        Some(*v as i64)
+    } else if let Some(v) = value.as_i16() {
+        Some(*v as i64)
+    } else {
+        value.as_i8().map(|v| *v as i64)
+    }
+}
+
+fn float_of(value: &DataValue) -> Option<f64> {
+    if let Some(v) = value.as_f64() {
+        Some(*v)
+    } else if let Some(v) = value.as_f32() {
+        Some(*v as f64)
+    } else if let Some(v) = value.as_numeric() {
+        v.as_bigdecimal().to_f64()
+    } else {
+        integer_of(value).map(|v| v as f64)
+    }
+}
+
+fn decimal_of(value: &DataValue) -> Option<BigDecimal> {
+    match value.as_numeric() {
+        Some(v) => Some(v.as_bigdecimal().clone()),
+        None => integer_of(value).map(BigDecimal::from),
+    }
+}
+
+fn numeric_scale(data_type: &DataType) -> i64 {
+    data_type
+        .as_numeric_param()
+        .map(|param| param.scale() as i64)
+        .unwrap_or(0)
+}
+
+fn compare(op: ValueCompare, left: &DataValue, right: &DataValue) -> RS<Option<bool>> {
+    if left.is_null() || right.is_null() {
+        return Ok(None);
+    }
+    // Integers of different widths compare by value.
+    let ordering = match (integer_of(left), integer_of(right)) {
+        (Some(l), Some(r)) => l.cmp(&r),
+        _ => match compare_values(left, right)? {
+            Some(ordering) => ordering,
+            None => return Ok(None),
+        },
+    };
+    Ok(Some(match op {
+        ValueCompare::EQ => ordering == Ordering::Equal,
+        ValueCompare::NE => ordering != Ordering::Equal,
+        ValueCompare::LT => ordering == Ordering::Less,
+        ValueCompare::LE => ordering != Ordering::Greater,
+        ValueCompare::GT => ordering == Ordering::Greater,
+        ValueCompare::GE => ordering != Ordering::Less,
+    }))
+}
+
+fn arithmetic(
+    op: Arithmetic,
+    left: &DataValue,
+    right: &DataValue,
+    data_type: &DataType,
+) -> RS<DataValue> {
+    if left.is_null() || right.is_null() {
+        return Ok(DataValue::null());
+    }
+    let mismatch = || {
+        mudu_error!(
+            ER::InvalidState,
+            format!("operands of {:?} do not fit {}", op, data_type.name())
+        )
+    };
+    match data_type.type_family() {
+        TypeFamily::String => {
+            let (Some(l), Some(r)) = (left.as_string(), right.as_string()) else {
+                return Err(mismatch());
+            };
+            Ok(DataValue::from_string(format!("{l}{r}")))
+        }
+        TypeFamily::I64 => {
+            let (Some(l), Some(r)) = (integer_of(left), integer_of(right)) else {
+                return Err(mismatch());
+            };
+            let result = match op {
+                Arithmetic::PLUS => l.checked_add(r),
+                Arithmetic::MINUS => l.checked_sub(r),
+                Arithmetic::MULTIPLE => l.checked_mul(r),
+                Arithmetic::DIVIDE | Arithmetic::MODULO if r == 0 => return Err(division_by_zero()),
+                Arithmetic::DIVIDE => l.checked_div(r),
+                Arithmetic::MODULO => l.checked_rem(r),
+                Arithmetic::CONCAT => return Err(mismatch()),
+            };
+            result
+                .map(DataValue::from_i64)
+                .ok_or_else(|| mudu_error!(ER::TypeConversionFailed, "bigint out of range"))
+        }
+        TypeFamily::F64 => {
+            let (Some(l), Some(r)) = (float_of(left), float_of(right)) else {
+                return Err(mismatch());
+            };
+            let result = match op {
+                Arithmetic::PLUS => l + r,
+                Arithmetic::MINUS => l - r,
+                Arithmetic::MULTIPLE => l * r,
+                Arithmetic::DIVIDE if r == 0.0 => return Err(division_by_zero()),
+                Arithmetic::DIVIDE => l / r,
+                Arithmetic::MODULO | Arithmetic::CONCAT => return Err(mismatch()),
+            };
+            if !result.is_finite() {
+                return Err(mudu_error!(
+                    ER::TypeConversionFailed,
+                    "double precision out of range"
+                ));
+            }
+            Ok(DataValue::from_f64(result))
+        }
+        TypeFamily::Numeric => {
+            let (Some(l), Some(r)) = (decimal_of(left), decimal_of(right)) else {
+                return Err(mismatch());
+            };
+            let result = match op {
+                Arithmetic::PLUS => l + r,
+                Arithmetic::MINUS => l - r,
+                Arithmetic::MULTIPLE => l * r,
+                Arithmetic::DIVIDE | Arithmetic::MODULO if r.is_zero() => {
+                    return Err(division_by_zero())
+                }
+                Arithmetic::DIVIDE => l / r,
+                Arithmetic::MODULO => l % r,
+                Arithmetic::CONCAT => return Err(mismatch()),
+            };
+            Ok(DataValue::from_numeric(
+                Numeric::new(result)
+                    .with_scale_round(numeric_scale(data_type), RoundingMode::HalfUp),
+            ))
+        }
+        _ => Err(mismatch()),
+    }
+}
+
+fn function(func: ScalarFunc, args: Vec<DataValue>, data_type: &DataType) -> RS<DataValue> {
+    let any_null = args.iter().any(DataValue::is_null);
+    let arg = |index: usize| {
+        args.get(index).ok_or_else(|| {
+            mudu_error!(
+                ER::InvalidState,
+                format!("{:?} is missing argument {}", func, index + 1)
+            )
+        })
+    };
+    match func {
+        ScalarFunc::Coalesce => Ok(args
+            .iter()
+            .find(|value| !value.is_null())
+            .cloned()
+            .unwrap_or_else(DataValue::null)),
+        // Every other function is strict: a NULL argument yields NULL.
+        _ if any_null => Ok(DataValue::null()),
+        ScalarFunc::Lower => Ok(DataValue::from_string(text_arg(arg(0)?)?.to_lowercase())),
+        ScalarFunc::Upper => Ok(DataValue::from_string(text_arg(arg(0)?)?.to_uppercase())),
+        ScalarFunc::Substr => {
+            let length = match args.get(2) {
+                Some(length) => Some(integer_arg(length)?),
+                None => None,
+            };
+            Ok(DataValue::from_string(substr(
+                text_arg(arg(0)?)?,
+                integer_arg(arg(1)?)?,
+                length,
+            )?))
+        }
+        ScalarFunc::Abs => abs(arg(0)?),
+        ScalarFunc::Round => {
+            let digits = match args.get(1) {
+                Some(digits) => integer_arg(digits)?,
+                None => 0,
+            };
+            round(arg(0)?, digits, data_type)
+        }
+        ScalarFunc::DateTrunc(field) => date_trunc(field, arg(0)?),
+        ScalarFunc::Extract(field) => extract(field, arg(0)?, data_type),
+    }
+}
+
+fn text_arg(value: &DataValue) -> RS<&String> {
+    value
+        .as_string()
+        .ok_or_else(|| mudu_error!(ER::InvalidState, "function expects a text argument"))
+}
+
+fn integer_arg(value: &DataValue) -> RS<i64> {
+    integer_of(value)
+        .ok_or_else(|| mudu_error!(ER::InvalidState, "function expects an integer argument"))
+}
+
+// Characters `start ..< start + length` counting from 1; positions before
+// the first character count against the length, as in PostgreSQL.
+fn substr(text: &str, start: i64, length: Option<i64>) -> RS<String> {
+    let end = match length {
+        Some(length) if length < 0 => {
+            return Err(mudu_error!(
+                ER::InvalidArgument,
+                "negative substring length not allowed"
+            ))
+        }
+        Some(length) => Some(start.saturating_add(length)),
+        None => None,
+    };
+    Ok(text
+        .chars()
+        .zip(1_i64..)
+        .filter(|(_, pos)| {
+            *pos >= start
+                && match end {
+                    Some(end) => *pos < end,
+                    None => true,
+                }
+        })
+        .map(|(c, _)| c)
+        .collect())
+}
+
+fn abs(value: &DataValue) -> RS<DataValue> {
+    if let Some(v) = integer_of(value) {
+        v.checked_abs()
+            .map(DataValue::from_i64)
+            .ok_or_else(|| mudu_error!(ER::TypeConversionFailed, "bigint out of range"))
+    } else if let Some(v) = value.as_numeric() {
+        Ok(DataValue::from_numeric(Numeric::new(
+            v.as_bigdecimal().abs(),
+        )))
+    } else if let Some(v) = float_of(value) {
+        Ok(DataValue::from_f64(v.abs()))
+    } else {
+        Err(mudu_error!(ER::InvalidState, "abs expects a number"))
+    }
+}
+
+fn round(value: &DataValue, digits: i64, data_type: &DataType) -> RS<DataValue> {
+    if data_type.type_family() == TypeFamily::F64 {
+        let v = float_of(value)
+            .ok_or_else(|| mudu_error!(ER::InvalidState, "round expects a number"))?;
+        let factor = 10_f64.powi(digits.clamp(-308, 308) as i32);
+        return Ok(DataValue::from_f64((v * factor).round() / factor));
+    }
+    let v =
+        decimal_of(value).ok_or_else(|| mudu_error!(ER::InvalidState, "round expects a number"))?;
+    Ok(DataValue::from_numeric(
+        Numeric::new(v)
+            .with_scale_round(digits, RoundingMode::HalfUp)
+            .with_scale(numeric_scale(data_type)),
+    ))
+}
+
+fn datetime_of(micros: i64) -> RS<NaiveDateTime> {
+    DateTime::from_timestamp_micros(micros)
+        .map(|datetime| datetime.naive_utc())
+        .ok_or_else(|| mudu_error!(ER::TypeConversionFailed, "timestamp out of range"))
+}
+
+// Microseconds since the Unix epoch of a date (at midnight) or timestamp;
+// a timestamptz counts in UTC.
+fn epoch_micros_of(value: &DataValue) -> RS<i64> {
+    if let Some(v) = value.as_timestamp() {
+        Ok(v.epoch_micros())
+    } else if let Some(v) = value.as_timestamptz() {
+        Ok(v.epoch_micros_utc())
+    } else if let Some(v) = value.as_date() {
+        Ok(v.days_since_epoch() as i64 * MICROS_PER_DAY)
+    } else {
+        Err(mudu_error!(
+            ER::InvalidState,
+            "expected a date or timestamp value"
+        ))
+    }
+}
+
+fn temporal_cast(value: &DataValue, target: TypeFamily) -> RS<DataValue> {
+    let micros = epoch_micros_of(value)?;
+    Ok(match target {
+        TypeFamily::Date => DataValue::from_date(DateValue::from_days_since_epoch(
+            i32::try_from(micros.div_euclid(MICROS_PER_DAY))
+                .map_err(|_| mudu_error!(ER::TypeConversionFailed, "date out of range"))?,
+        )),
+        TypeFamily::Time => DataValue::from_time(
+            TimeValue::from_micros_since_midnight(micros.rem_euclid(MICROS_PER_DAY))
+                .map_err(|e| mudu_error!(ER::TypeConversionFailed, e))?,
+        ),
+        TypeFamily::Timestamp => {
+            DataValue::from_timestamp(TimestampValue::from_epoch_micros(micros))
+        }
+        _ => DataValue::from_timestamptz(TimestampTzValue::from_epoch_micros_utc(micros)),
+    })
+}
+
+fn date_trunc(field: TimeField, value: &DataValue) -> RS<DataValue> {
+    if let Some(v) = value.as_timestamptz() {
+        let micros = truncate_micros(field, v.epoch_micros_utc())?;
+        return Ok(DataValue::from_timestamptz(
+            TimestampTzValue::from_epoch_micros_utc(micros),
+        ));
+    }
+    let micros = truncate_micros(field, epoch_micros_of(value)?)?;
+    Ok(DataValue::from_timestamp(
+        TimestampValue::from_epoch_micros(micros),
+    ))
+}
+
+fn truncate_micros(field: TimeField, micros: i64) -> RS<i64> {
+    let unit = match field {
+        TimeField::Microsecond => 1,
+        TimeField::Millisecond => 1_000,
+        TimeField::Second => 1_000_000,
+        TimeField::Minute => 60_000_000,
+        TimeField::Hour => 3_600_000_000,
+        TimeField::Day => MICROS_PER_DAY,
+        _ => 0,
+    };
+    if unit > 0 {
+        return Ok(micros.div_euclid(unit) * unit);
+    }
+    let date = datetime_of(micros)?.date();
+    let first = match field {
+        TimeField::Week => {
+            date.checked_sub_days(Days::new(date.weekday().num_days_from_monday() as u64))
+        }
+        TimeField::Month => date.with_day(1),
+        TimeField::Quarter => NaiveDate::from_ymd_opt(date.year(), date.month0() / 3 * 3 + 1, 1),
+        TimeField::Year => NaiveDate::from_ymd_opt(date.year(), 1, 1),
+        _ => {
+            return Err(mudu_error!(
+                ER::InvalidArgument,
+                format!("date_trunc does not support {:?}", field)
+            ))
+        }
+    }
+    .ok_or_else(|| mudu_error!(ER::TypeConversionFailed, "timestamp out of range"))?;
+    Ok(first.and_time(NaiveTime::MIN).and_utc().timestamp_micros())
+}
+
+fn extract(field: TimeField, value: &DataValue, data_type: &DataType) -> RS<DataValue> {
+    // A time of day is read as a timestamp on 1970-01-01.
+    let micros = match value.as_time() {
+        Some(time) => {
+            if !matches!(
+                field,
+                TimeField::Microsecond
+                    | TimeField::Millisecond
+                    | TimeField::Second
+                    | TimeField::Minute
+                    | TimeField::Hour
+                    | TimeField::Epoch
+            ) {
+                return Err(mudu_error!(
+                    ER::InvalidArgument,
+                    format!("{:?} is not a field of time", field)
+                ));
+            }
+            time.micros_since_midnight()
+        }
+        None => epoch_micros_of(value)?,
+    };
+    let datetime = datetime_of(micros)?;
+    let second_micros = datetime.second() as i64 * 1_000_000 + datetime.nanosecond() as i64 / 1_000;
+    let part = match field {
+        TimeField::Microsecond => BigDecimal::from(second_micros),
+        TimeField::Millisecond => BigDecimal::from(second_micros) / BigDecimal::from(1_000),
+        TimeField::Second => BigDecimal::from(second_micros) / BigDecimal::from(1_000_000),
+        TimeField::Epoch => BigDecimal::from(micros) / BigDecimal::from(1_000_000),
+        TimeField::Minute => BigDecimal::from(datetime.minute()),
+        TimeField::Hour => BigDecimal::from(datetime.hour()),
+        TimeField::Day => BigDecimal::from(datetime.day()),
+        TimeField::DayOfWeek => BigDecimal::from(datetime.weekday().num_days_from_sunday()),
+        TimeField::DayOfYear => BigDecimal::from(datetime.ordinal()),
+        TimeField::Week => BigDecimal::from(datetime.iso_week().week()),
+        TimeField::Month => BigDecimal::from(datetime.month()),
+        TimeField::Quarter => BigDecimal::from(datetime.month0() / 3 + 1),
+        TimeField::Year => BigDecimal::from(datetime.year()),
+    };
+    if data_type.type_family() == TypeFamily::F64 {
+        let v = part.to_f64().ok_or_else(|| {
+            mudu_error!(ER::TypeConversionFailed, "double precision out of range")
+        })?;
+        return Ok(DataValue::from_f64(v));
+    }
+    Ok(DataValue::from_numeric(
+        Numeric::new(part).with_scale(numeric_scale(data_type)),
+    ))
+}
+
+fn json_of(value: &DataValue, data_type: &DataType) -> RS<JsonValue> {
+    let json = value.type_family()?.fn_output_json()(value, data_type)
+        .map_err(|e| mudu_error!(ER::TypeConversionFailed, "", e))?;
+    Ok(json.into_json_value())
+}
+
+// Text form of a value, as `CAST(x AS TEXT)` spells it.
+fn text_of(value: &DataValue, data_type: &DataType) -> RS<String> {
+    if let Some(v) = value.as_json() {
+        return Ok(v.clone());
+    }
+    if let Some(v) = value.as_numeric() {
+        return Ok(v.to_plain_string());
+    }
+    Ok(match json_of(value, data_type)? {
+        JsonValue::String(text) => text,
+        other => other.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+    use super::*;
+    use mudu_type::data_type_param_numeric::DataTypeParamNumeric;
+
+    fn ty(family: TypeFamily) -> DataType {
+        DataType::default_for(family)
+    }
+
+    fn numeric_type(scale: u8) -> DataType {
+        DataType::from_numeric(DataTypeParamNumeric::new(38, scale))
+    }
+
+    fn int(value: i64) -> ScalarExpr {
+        ScalarExpr::Const(DataValue::from_i64(value))
+    }
+
+    fn text(value: &str) -> ScalarExpr {
+        ScalarExpr::Const(DataValue::from_string(value.to_string()))
+    }
+
+    fn null() -> ScalarExpr {
+        ScalarExpr::Const(DataValue::null())
+    }
+
+    fn boolean(value: Option<bool>) -> ScalarExpr {
+        ScalarExpr::Const(from_truth(value))
+    }
+
+    fn op(op: Arithmetic, left: ScalarExpr, right: ScalarExpr, data_type: DataType) -> ScalarExpr {
+        ScalarExpr::Arithmetic {
+            op,
+            left: Box::new(left),
+            right: Box::new(right),
+            data_type,
+        }
+    }
+
+    fn call(func: ScalarFunc, args: Vec<ScalarExpr>, data_type: DataType) -> ScalarExpr {
+        ScalarExpr::Function {
+            func,
+            args,
+            data_type,
+        }
+    }
+
+    fn eval(expr: &ScalarExpr) -> DataValue {
+        expr.eval(&[]).unwrap()
+    }
+
+    fn timestamp(text: &str) -> ScalarExpr {
+        ScalarExpr::Const(DataValue::from_timestamp(
+            TimestampValue::parse(text).unwrap(),
+        ))
+    }
+
+    fn numeric_text(value: &DataValue) -> String {
+        value.as_numeric().unwrap().to_plain_string()
+    }
+
+    #[test]
+    fn arithmetic_over_columns_and_constants() {
+        let i32_type = ty(TypeFamily::I32);
+        let row: Vec<Option<Vec<u8>>> = vec![Some(
+            DataValue::from_i32(7).to_binary(&i32_type).unwrap().into(),
+        )];
+        let column = ScalarExpr::Column {
+            pos: 0,
+            data_type: i32_type,
+        };
+        let expr = op(Arithmetic::MULTIPLE, column, int(6), ty(TypeFamily::I64));
+        assert_eq!(*expr.eval(&row).unwrap().as_i64().unwrap(), 42);
+        let modulo = op(Arithmetic::MODULO, int(7), int(3), ty(TypeFamily::I64));
+        assert_eq!(*eval(&modulo).as_i64().unwrap(), 1);
+        let with_null = op(Arithmetic::PLUS, int(1), null(), ty(TypeFamily::I64));
+        assert!(eval(&with_null).is_null());
+    }
+
+    #[test]
+    fn arithmetic_errors_on_overflow_and_division_by_zero() {
+        let overflow = op(Arithmetic::PLUS, int(i64::MAX), int(1), ty(TypeFamily::I64));
+        let err = overflow.eval(&[]).unwrap_err();
+        assert_eq!(err.ec(), ER::TypeConversionFailed);
+        let divide = op(Arithmetic::DIVIDE, int(1), int(0), ty(TypeFamily::I64));
+        let err = divide.eval(&[]).unwrap_err();
+        assert_eq!(err.ec(), ER::InvalidArgument);
+    }
+
+    #[test]
+    fn numeric_division_rounds_to_result_scale() {
+        let expr = op(Arithmetic::DIVIDE, int(2), int(3), numeric_type(6));
+        assert_eq!(numeric_text(&eval(&expr)), "0.666667");
+    }
+
+    #[test]
+    fn concat_joins_text() {
+        let expr = op(
+            Arithmetic::CONCAT,
+            text("ab"),
+            text("cd"),
+            ty(TypeFamily::String),
+        );
+        assert_eq!(eval(&expr).as_string().unwrap(), "abcd");
+    }
+
+    #[test]
+    fn logic_is_three_valued() {
+        let and = ScalarExpr::And(vec![boolean(None), boolean(Some(false))]);
+        assert!(!*eval(&and).as_bool().unwrap());
+        let and = ScalarExpr::And(vec![boolean(None), boolean(Some(true))]);
+        assert!(eval(&and).is_null());
+        let or = ScalarExpr::Or(vec![boolean(None), boolean(Some(true))]);
+        assert!(*eval(&or).as_bool().unwrap());
+        let not = ScalarExpr::Not(Box::new(boolean(None)));
+        assert!(eval(&not).is_null());
+        let in_list = ScalarExpr::InList {
+            operand: Box::new(int(3)),
+            list: vec![int(1), null()],
+            negated: false,
+        };
+        assert!(eval(&in_list).is_null());
+    }
+
+    #[test]
+    fn case_takes_first_true_branch() {
+        let compare = |value: i64| ScalarExpr::Compare {
+            op: ValueCompare::GT,
+            left: Box::new(int(value)),
+            right: Box::new(int(10)),
+        };
+        let case = |value: i64| ScalarExpr::Case {
+            branches: vec![(compare(value), text("big"))],
+            else_result: Some(Box::new(text("small"))),
+        };
+        assert_eq!(eval(&case(11)).as_string().unwrap(), "big");
+        assert_eq!(eval(&case(3)).as_string().unwrap(), "small");
+    }
+
+    #[test]
+    fn string_functions() {
+        let string = ty(TypeFamily::String);
+        let upper = call(ScalarFunc::Upper, vec![text("Bob")], string.clone());
+        assert_eq!(eval(&upper).as_string().unwrap(), "BOB");
+        let substr = |args: Vec<ScalarExpr>| {
+            eval(&call(ScalarFunc::Substr, args, string.clone()))
+                .as_string()
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(substr(vec![text("hello"), int(2), int(3)]), "ell");
+        assert_eq!(substr(vec![text("hello"), int(0), int(3)]), "he");
+        assert_eq!(substr(vec![text("hello"), int(4)]), "lo");
+        let coalesce = call(
+            ScalarFunc::Coalesce,
+            vec![null(), text("x"), text("y")],
+            string.clone(),
+        );
+        assert_eq!(eval(&coalesce).as_string().unwrap(), "x");
+        let lower = call(ScalarFunc::Lower, vec![null()], string);
+        assert!(eval(&lower).is_null());
+    }
+
+    #[test]
+    fn abs_and_round() {
+        let abs = call(ScalarFunc::Abs, vec![int(-5)], ty(TypeFamily::I64));
+        assert_eq!(*eval(&abs).as_i64().unwrap(), 5);
+        let price = ScalarExpr::Const(DataValue::from_numeric(Numeric::parse("2.345").unwrap()));
+        let round = call(ScalarFunc::Round, vec![price, int(2)], numeric_type(2));
+        assert_eq!(numeric_text(&eval(&round)), "2.35");
+        let round = call(ScalarFunc::Round, vec![int(1250), int(-2)], numeric_type(0));
+        assert_eq!(numeric_text(&eval(&round)), "1300");
+    }
+
+    #[test]
+    fn date_trunc_and_extract() {
+        let trunc = call(
+            ScalarFunc::DateTrunc(TimeField::Month),
+            vec![timestamp("2024-05-17 13:45:30")],
+            ty(TypeFamily::Timestamp),
+        );
+        let truncated = eval(&trunc);
+        let expected = TimestampValue::parse("2024-05-01 00:00:00").unwrap();
+        assert_eq!(
+            truncated.as_timestamp().unwrap().epoch_micros(),
+            expected.epoch_micros()
+        );
+        let week = call(
+            ScalarFunc::DateTrunc(TimeField::Week),
+            vec![timestamp("2024-05-17 13:45:30")],
+            ty(TypeFamily::Timestamp),
+        );
+        let monday = TimestampValue::parse("2024-05-13 00:00:00").unwrap();
+        assert_eq!(
+            eval(&week).as_timestamp().unwrap().epoch_micros(),
+            monday.epoch_micros()
+        );
+        let year = call(
+            ScalarFunc::Extract(TimeField::Year),
+            vec![timestamp("2024-05-17 13:45:30")],
+            numeric_type(0),
+        );
+        assert_eq!(numeric_text(&eval(&year)), "2024");
+        let second = call(
+            ScalarFunc::Extract(TimeField::Second),
+            vec![timestamp("2024-05-17 13:45:30.25")],
+            ty(TypeFamily::F64),
+        );
+        assert_eq!(*eval(&second).as_f64().unwrap(), 30.25);
+    }
+
+    #[test]
+    fn cast_parses_and_rejects_text() {
+        let string = ty(TypeFamily::String);
+        let cast = |value: &str, to: DataType| {
+            cast_value(DataValue::from_string(value.to_string()), &string, &to)
+        };
+        assert_eq!(
+            *cast(" 42 ", ty(TypeFamily::I32)).unwrap().as_i32().unwrap(),
+            42
+        );
+        assert_eq!(
+            numeric_text(&cast("1.005", numeric_type(2)).unwrap()),
+            "1.01"
+        );
+        assert!(cast("2024-02-30", ty(TypeFamily::Date)).is_err());
+        let err = cast("abc", ty(TypeFamily::I64)).unwrap_err();
+        assert_eq!(err.ec(), ER::TypeConversionFailed);
+        let err = cast_value(
+            DataValue::from_i64(300),
+            &ty(TypeFamily::I64),
+            &ty(TypeFamily::I8),
+        )
+        .unwrap_err();
+        assert_eq!(err.ec(), ER::TypeConversionFailed);
+        let text = cast_value(DataValue::from_i64(7), &ty(TypeFamily::I64), &string).unwrap();
+        assert_eq!(text.as_string().unwrap(), "7");
+    }
+}
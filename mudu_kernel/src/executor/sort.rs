@@ -0,0 +1,436 @@
+//! Sort executor (`ORDER BY`).
+//!
+//! Drains the child on `open()`. Rows are sorted in memory until their
+//! estimated size exceeds the memory limit; past that, each full buffer is
+//! sorted and spilled as a run file under the system temp directory, and the
+//! runs are k-way merged on `next()`. Run files are removed when the
+//! executor is dropped. Ties keep their input order.
+//!
+//! NULLs sort before or after every value as each key specifies,
+//! independently of the sort direction.
+
+use crate::contract::explain_node::ExplainNode;
+use crate::contract::query_exec::QueryExec;
+use crate::executor::value_compare::compare_values;
+use crate::x_engine::api::TupleRow;
+use async_trait::async_trait;
+use mudu::common::result::RS;
+use mudu::error::ErrorCode as ER;
+use mudu::mudu_error;
+use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
+use mudu_contract::tuple::typed_bin::TypedBin;
+use mudu_sys::fs::sync::SFile;
+use mudu_sys::sync::async_::futures_mutex::FMutex;
+use mudu_type::data_type_fn_param::DataType;
+use mudu_type::data_value::DataValue;
+use mudu_utils::oid::gen_oid;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Rows buffered in memory before a sorted run is spilled, in bytes.
+const DEFAULT_MEMORY_LIMIT: usize = 64 << 20;
+
+/// Runs merged at once; more runs are first merged into larger runs.
+const MERGE_FAN_IN: usize = 64;
+
+/// One `ORDER BY` key resolved against the child row layout.
+#[derive(Clone)]
+pub struct SortKey {
+    pub pos: usize,
+    pub data_type: DataType,
+    pub descending: bool,
+    pub nulls_first: bool,
+}
+
+pub struct SortExec {
+    tuple_desc: TupleFieldDesc,
+    child: Arc<dyn QueryExec>,
+    keys: Vec<SortKey>,
+    memory_limit: usize,
+    spill_dir: PathBuf,
+    inner: FMutex<SortOutput>,
+}
+
+enum SortOutput {
+    Memory(VecDeque<TupleRow>),
+    Merge(RunMerger),
+}
+
+/// A row with its decoded sort key.
+type KeyedRow = (Vec<Option<DataValue>>, TupleRow);
+
+impl SortExec {
+    pub fn new(tuple_desc: TupleFieldDesc, child: Arc<dyn QueryExec>, keys: Vec<SortKey>) -> Self {
+        Self {
+            tuple_desc,
+            child,
+            keys,
+            memory_limit: DEFAULT_MEMORY_LIMIT,
+            spill_dir: mudu_sys::env_var::temp_dir(),
+            inner: FMutex::new(SortOutput::Memory(VecDeque::new())),
+        }
+    }
+
+    /// Sets the in-memory budget in bytes; larger inputs spill to disk.
+    pub fn with_memory_limit(mut self, memory_limit: usize) -> Self {
+        self.memory_limit = memory_limit;
+        self
+    }
+}
+
+#[async_trait]
+impl QueryExec for SortExec {
+    async fn open(&self) -> RS<()> {
+        self.child.open().await?;
+        let mut buffer: Vec<KeyedRow> = Vec::new();
+        let mut buffered = 0;
+        let mut runs = Vec::new();
+        while let Some(row) = self.child.next().await? {
+            buffered += row_size(&row);
+            buffer.push((decode_key(&self.keys, &row)?, row));
+            if buffered > self.memory_limit {
+                sort_rows(&self.keys, &mut buffer)?;
+                let mut rows = buffer.drain(..).map(|(_, row)| row);
+                runs.push(write_run(&self.spill_dir, || Ok(rows.next()))?);
+                buffered = 0;
+            }
+        }
+        sort_rows(&self.keys, &mut buffer)?;
+
+        let output = if runs.is_empty() {
+            SortOutput::Memory(buffer.into_iter().map(|(_, row)| row).collect())
+        } else {
+            if !buffer.is_empty() {
+                let mut rows = buffer.into_iter().map(|(_, row)| row);
+                runs.push(write_run(&self.spill_dir, || Ok(rows.next()))?);
+            }
+            while runs.len() > MERGE_FAN_IN {
+                let mut merger = RunMerger::new(&self.keys, runs.drain(..MERGE_FAN_IN).collect())?;
+                let run = write_run(&self.spill_dir, || merger.next(&self.keys))?;
+                // The merged run holds the earliest input rows; keep it first
+                // so ties stay in input order.
+                runs.insert(0, run);
+            }
+            SortOutput::Merge(RunMerger::new(&self.keys, runs)?)
+        };
+        *self.inner.lock().await = output;
+        Ok(())
+    }
+
+    async fn next(&self) -> RS<Option<TupleRow>> {
+        let mut inner = self.inner.lock().await;
+        match &mut *inner {
+            SortOutput::Memory(rows) => Ok(rows.pop_front()),
+            SortOutput::Merge(merger) => merger.next(&self.keys),
+        }
+    }
+
+    fn tuple_desc(&self) -> RS<TupleFieldDesc> {
+        Ok(self.tuple_desc.clone())
+    }
+
+    fn explain(&self) -> ExplainNode {
+        let keys = self
+            .keys
+            .iter()
+            .map(|key| {
+                let name = self
+                    .tuple_desc
+                    .fields()
+                    .get(key.pos)
+                    .map_or_else(|| format!("#{}", key.pos), |field| field.name().to_string());
+                if key.descending {
+                    format!("{} desc", name)
+                } else {
+                    name
+                }
+            })
+            .collect::<Vec<_>>();
+        ExplainNode::new("Sort")
+            .with_detail(format!("keys={}", keys.join(", ")))
+            .with_child(self.child.explain())
+    }
+}
+
+unsafe impl Send for SortExec {}
+
+unsafe impl Sync for SortExec {}
+
+/// Estimated in-memory size of a row.
+fn row_size(row: &TupleRow) -> usize {
+    row.fields()
+        .iter()
+        .map(|field| size_of::<Option<Vec<u8>>>() + field.as_ref().map_or(0, Vec::len))
+        .sum()
+}
+
+fn decode_key(keys: &[SortKey], row: &TupleRow) -> RS<Vec<Option<DataValue>>> {
+    keys.iter()
+        .map(|key| {
+            let field = row
+                .fields()
+                .get(key.pos)
+                .ok_or_else(|| mudu_error!(ER::InvalidState, "sort key out of row bounds"))?;
+            field
+                .as_ref()
+                .map(|binary| {
+                    TypedBin::new(key.data_type.type_family(), binary.clone())
+                        .to_value(&key.data_type)
+                })
+                .transpose()
+        })
+        .collect()
+}
+
+fn compare_keys(
+    keys: &[SortKey],
+    left: &[Option<DataValue>],
+    right: &[Option<DataValue>],
+) -> RS<Ordering> {
+    for (key, (left, right)) in keys.iter().zip(left.iter().zip(right)) {
+        let ordering = match (left, right) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) if key.nulls_first => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) if key.nulls_first => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            (Some(left), Some(right)) => {
+                let ordering = compare_values(left, right)?.unwrap_or(Ordering::Equal);
+                if key.descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            }
+        };
+        if ordering != Ordering::Equal {
+            return Ok(ordering);
+        }
+    }
+    Ok(Ordering::Equal)
+}
+
+/// Stable sort by key; the first comparison error is returned.
+fn sort_rows(keys: &[SortKey], rows: &mut [KeyedRow]) -> RS<()> {
+    let mut error = None;
+    rows.sort_by(|left, right| {
+        compare_keys(keys, &left.0, &right.0).unwrap_or_else(|e| {
+            error.get_or_insert(e);
+            Ordering::Equal
+        })
+    });
+    match error {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// A spilled run file, removed on drop.
+struct SpillRun {
+    path: PathBuf,
+}
+
+impl Drop for SpillRun {
+    fn drop(&mut self) {
+        let _ = mudu_sys::fs::sync::remove_file(&self.path);
+    }
+}
+
+/// Writes the rows produced by `next` to a new run file. Each row is a
+/// little-endian `u32` length followed by its MessagePack encoding.
+fn write_run(
+    dir: &std::path::Path,
+    mut next: impl FnMut() -> RS<Option<TupleRow>>,
+) -> RS<SpillRun> {
+    let run = SpillRun {
+        path: dir.join(format!("mudu_sort_{}.run", gen_oid())),
+    };
+    let mut writer = BufWriter::new(SFile::create(&run.path)?);
+    while let Some(row) = next()? {
+        let payload = rmp_serde::to_vec(&row)
+            .map_err(|e| mudu_error!(ER::Encode, "encode spilled sort row error", e))?;
+        let len = u32::try_from(payload.len())
+            .map_err(|_| mudu_error!(ER::Encode, "spilled sort row is too large"))?;
+        writer
+            .write_all(&len.to_le_bytes())
+            .and_then(|_| writer.write_all(&payload))
+            .map_err(|e| mudu_error!(ER::Io, "write sort run error", e))?;
+    }
+    writer
+        .flush()
+        .map_err(|e| mudu_error!(ER::Io, "write sort run error", e))?;
+    Ok(run)
+}
+
+struct RunReader {
+    reader: BufReader<SFile>,
+    // Declared after the reader so the file is closed before removal.
+    _run: SpillRun,
+}
+
+impl RunReader {
+    fn open(run: SpillRun) -> RS<Self> {
+        Ok(Self {
+            reader: BufReader::new(SFile::open(&run.path)?),
+            _run: run,
+        })
+    }
+
+    fn next_row(&mut self) -> RS<Option<TupleRow>> {
+        let mut len = [0u8; 4];
+        match self.reader.read_exact(&mut len) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(mudu_error!(ER::Io, "read sort run error", e)),
+        }
+        let mut payload = vec![0u8; u32::from_le_bytes(len) as usize];
+        self.reader
+            .read_exact(&mut payload)
+            .map_err(|e| mudu_error!(ER::Io, "read sort run error", e))?;
+        let row = rmp_serde::from_slice(&payload)
+            .map_err(|e| mudu_error!(ER::Decode, "decode spilled sort row error", e))?;
+        Ok(Some(row))
+    }
+}
+
+/// Merges sorted runs by repeatedly taking the smallest head row. Runs are
+/// few (at most `MERGE_FAN_IN`), so the heads are scanned linearly; ties go
+/// to the earlier run.
+struct RunMerger {
+    readers: Vec<RunReader>,
+    heads: Vec<Option<KeyedRow>>,
+}
+
+impl RunMerger {
+    fn new(keys: &[SortKey], runs: Vec<SpillRun>) -> RS<Self> {
+        let mut readers = Vec::with_capacity(runs.len());
+        let mut heads = Vec::with_capacity(runs.len());
+        for run in runs {
+            let mut reader = RunReader::open(run)?;
+            heads.push(Self::read_head(keys, &mut reader)?);
+            readers.push(reader);
+        }
+        Ok(Self { readers, heads })
+    }
+
+    fn read_head(keys: &[SortKey], reader: &mut RunReader) -> RS<Option<KeyedRow>> {
+        reader
+            .next_row()?
+            .map(|row| Ok((decode_key(keys, &row)?, row)))
+            .transpose()
+    }
+
+    fn next(&mut self, keys: &[SortKey]) -> RS<Option<TupleRow>> {
+        let mut best: Option<usize> = None;
+        for (index, head) in self.heads.iter().enumerate() {
+            let Some((key, _)) = head else {
+                continue;
+            };
+            let smaller = match best.and_then(|best| self.heads[best].as_ref()) {
+                Some((best_key, _)) => compare_keys(keys, key, best_key)? == Ordering::Less,
+                None => true,
+            };
+            if smaller {
+                best = Some(index);
+            }
+        }
+        let Some(best) = best else {
+            return Ok(None);
+        };
+        let head = Self::read_head(keys, &mut self.readers[best])?;
+        let row = std::mem::replace(&mut self.heads[best], head).map(|(_, row)| row);
+        Ok(row)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+    use super::*;
+    use crate::executor::join_test_util::{exec_with_rows, i32_bin, i32_type, run, values};
+
+    fn key(descending: bool, nulls_first: bool) -> SortKey {
+        SortKey {
+            pos: 0,
+            data_type: i32_type(),
+            descending,
+            nulls_first,
+        }
+    }
+
+    fn input() -> Arc<dyn QueryExec> {
+        exec_with_rows(vec![
+            vec![Some(i32_bin(3)), Some(i32_bin(0))],
+            vec![None, Some(i32_bin(1))],
+            vec![Some(i32_bin(1)), Some(i32_bin(2))],
+            vec![Some(i32_bin(3)), Some(i32_bin(3))],
+            vec![Some(i32_bin(2)), Some(i32_bin(4))],
+        ])
+    }
+
+    #[test]
+    fn sort_orders_rows_and_places_nulls() {
+        run(async {
+            let sort = SortExec::new(
+                TupleFieldDesc::new(vec![]),
+                input(),
+                vec![key(false, false)],
+            );
+            sort.open().await.unwrap();
+            assert_eq!(
+                values(&sort).await,
+                vec![
+                    vec![Some(1), Some(2)],
+                    vec![Some(2), Some(4)],
+                    vec![Some(3), Some(0)],
+                    vec![Some(3), Some(3)],
+                    vec![None, Some(1)],
+                ]
+            );
+
+            let sort = SortExec::new(TupleFieldDesc::new(vec![]), input(), vec![key(true, true)]);
+            sort.open().await.unwrap();
+            assert_eq!(
+                values(&sort).await,
+                vec![
+                    vec![None, Some(1)],
+                    vec![Some(3), Some(0)],
+                    vec![Some(3), Some(3)],
+                    vec![Some(2), Some(4)],
+                    vec![Some(1), Some(2)],
+                ]
+            );
+        })
+    }
+
+    #[test]
+    fn sort_spills_runs_and_merges_them() {
+        run(async {
+            let rows: Vec<_> = (0..200)
+                .map(|i| vec![Some(i32_bin((i * 37) % 101)), Some(i32_bin(i))])
+                .collect();
+            let mut expected: Vec<_> = (0..200).map(|i| ((i * 37) % 101, i)).collect();
+            expected.sort_by_key(|(key, _)| *key);
+
+            // A tiny budget spills every few rows and forces a multi-pass
+            // merge.
+            let sort = SortExec::new(
+                TupleFieldDesc::new(vec![]),
+                exec_with_rows(rows),
+                vec![key(false, false)],
+            )
+            .with_memory_limit(1);
+            sort.open().await.unwrap();
+            let sorted: Vec<_> = values(&sort)
+                .await
+                .into_iter()
+                .map(|row| (row[0].unwrap(), row[1].unwrap()))
+                .collect();
+            assert_eq!(sorted, expected);
+        })
+    }
+}
@@ -8,6 +8,7 @@ use mudu::common::result::RS;
 use mudu::error::ErrorCode as ER;
 use mudu::mudu_error;
 use mudu_type::data_value::DataValue;
+use mudu_type::type_family::TypeFamily;
 use std::cmp::Ordering;
 
 /// Compare two decoded values of the same type.
@@ -20,6 +21,21 @@ pub(crate) fn compare_values(left: &DataValue, right: &DataValue) -> RS<Option<O
     }
     let ordering = if let (Some(l), Some(r)) = (left.as_i32(), right.as_i32()) {
         l.cmp(r)
+    } else if let (Some(l), Some(r)) = (left.as_bool(), right.as_bool()) {
+        l.cmp(r)
+    } else if let (Some(l), Some(r)) = (left.as_i8(), right.as_i8()) {
+        l.cmp(r)
+    } else if let (Some(l), Some(r)) = (left.as_i16(), right.as_i16()) {
+        l.cmp(r)
+    } else if let (Some(l), Some(r)) = (left.as_uuid(), right.as_uuid()) {
+        l.cmp(r)
+    } else if left.as_json().is_some() && right.as_json().is_some() {
+        // JSON documents compare structurally, not by their text.
+        let order = TypeFamily::Json
+            .fn_order()
+            .ok_or_else(|| mudu_error!(ER::InvalidState, "json has no ordering function"))?;
+        order(left, right)
+            .map_err(|e| mudu_error!(ER::ComparisonFailed, "compare json values error", e))?
     } else if let (Some(l), Some(r)) = (left.as_i64(), right.as_i64()) {
         l.cmp(r)
     } else if let (Some(l), Some(r)) = (left.as_f32(), right.as_f32()) {
@@ -30,6 +46,14 @@ pub(crate) fn compare_values(left: &DataValue, right: &DataValue) -> RS<Option<O
         l.as_bigdecimal().cmp(r.as_bigdecimal())
     } else if let (Some(l), Some(r)) = (left.as_string(), right.as_string()) {
         l.cmp(r)
+    } else if let (Some(l), Some(r)) = (left.as_date(), right.as_date()) {
+        l.days_since_epoch().cmp(&r.days_since_epoch())
+    } else if let (Some(l), Some(r)) = (left.as_time(), right.as_time()) {
+        l.micros_since_midnight().cmp(&r.micros_since_midnight())
+    } else if let (Some(l), Some(r)) = (left.as_timestamp(), right.as_timestamp()) {
+        l.epoch_micros().cmp(&r.epoch_micros())
+    } else if let (Some(l), Some(r)) = (left.as_timestamptz(), right.as_timestamptz()) {
+        l.epoch_micros_utc().cmp(&r.epoch_micros_utc())
     } else if let (Some(l), Some(r)) = (left.as_binary(), right.as_binary()) {
         l.cmp(r)
     } else {
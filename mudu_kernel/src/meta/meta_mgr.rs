@@ -22,6 +22,7 @@ use crate::contract::partition_rule_binding::{PartitionPlacement, TablePartition
 use crate::contract::schema_table::SchemaTable;
 use crate::contract::table_desc::TableDesc;
 use crate::contract::table_info::TableInfo;
+use crate::contract::user::UserDesc;
 use crate::meta::fs_object::{fs_object_schema, FS_OBJECT_TABLE_NAME};
 use crate::meta::fs_type_catalog::{
     delete_fs_type_from_catalog, load_fs_types_from_catalog, open_fs_type_catalog,
@@ -42,7 +43,11 @@ use crate::meta::schema_catalog::{
     delete_schema_from_catalog, load_schemas_from_catalog, open_schema_catalog,
     write_schema_to_catalog,
 };
+use crate::meta::user_catalog::{
+    delete_user_from_catalog, load_users_from_catalog, open_user_catalog, write_user_to_catalog,
+};
 use crate::storage::relation::relation::Relation;
+use crate::x_engine::api::AlterTable;
 
 type MetaMgrRegistry = HashMap<String, Vec<Weak<MetaMgrImpl>>>;
 type DdlLockRegistry = HashMap<String, Weak<AMutex<()>>>;
@@ -74,6 +79,7 @@ struct CatalogRelation {
     partition_binding_catalog: Arc<Relation>,
     partition_placement_catalog: Arc<Relation>,
     fs_type: Arc<Relation>,
+    user: Arc<Relation>,
 }
 
 impl CatalogRelation {
@@ -93,6 +99,8 @@ impl CatalogRelation {
         self.partition_placement_catalog.flush_dirty_pages().await?;
         crate::common::yield_now::cooperative_yield_now().await;
         self.fs_type.flush_dirty_pages().await?;
+        crate::common::yield_now::cooperative_yield_now().await;
+        self.user.flush_dirty_pages().await?;
         Ok(())
     }
 }
@@ -116,6 +124,7 @@ pub struct MetaMgrImpl {
     fs_type_by_name: scc::HashMap<String, FsTypeDesc>,
     fs_type_by_id: scc::HashMap<u64, FsTypeDesc>,
     next_fs_id: AtomicU64,
+    user_by_name: scc::HashMap<String, UserDesc>,
 }
 
 impl MetaMgrImpl {
@@ -141,6 +150,7 @@ impl MetaMgrImpl {
         let partition_placement_catalog =
             open_partition_placement_catalog(&self.path, self.async_runtime.clone()).await?;
         let fs_type_catalog = open_fs_type_catalog(&self.path, self.async_runtime.clone()).await?;
+        let user_catalog = open_user_catalog(&self.path, self.async_runtime.clone()).await?;
         for schema in load_schemas_from_catalog(&schema_catalog).await? {
             self.apply_create_table_local(&schema)?;
         }
@@ -158,6 +168,9 @@ impl MetaMgrImpl {
         for desc in load_fs_types_from_catalog(&fs_type_catalog).await? {
             self.apply_create_fs_type_local(&desc);
         }
+        for desc in load_users_from_catalog(&user_catalog).await? {
+            self.apply_create_user_local(&desc);
+        }
         // Register the built-in `_fs_object` system table so it resolves like
         // any user table and its relations get bootstrapped from the schema
         // list. It is not persisted in the schema catalog; a user table with
@@ -171,6 +184,7 @@ impl MetaMgrImpl {
             partition_placement_catalog: Arc::new(partition_placement_catalog),
             partition_binding_catalog: Arc::new(partition_binding_catalog),
             fs_type: Arc::new(fs_type_catalog),
+            user: Arc::new(user_catalog),
         };
         let mut guard = self.catalog.lock()?;
         *guard = Some(catalog);
@@ -205,6 +219,7 @@ impl MetaMgrImpl {
             fs_type_by_name: Default::default(),
             fs_type_by_id: Default::default(),
             next_fs_id: AtomicU64::new(1),
+            user_by_name: Default::default(),
         };
         // this.initialize_inner().await?;
         Ok(this)
@@ -294,6 +309,20 @@ impl MetaMgrImpl {
         self.fs_type_by_id.read_sync(&fs_id, |_, desc| desc.clone())
     }
 
+    pub fn lookup_user_by_name(&self, name: &str) -> Option<UserDesc> {
+        self.user_by_name.read_sync(name, |_, desc| desc.clone())
+    }
+
+    pub fn list_users_inner(&self) -> Vec<UserDesc> {
+        let mut users = Vec::new();
+        self.user_by_name.iter_sync(|_name, desc| {
+            users.push(desc.clone());
+            true
+        });
+        users.sort_by(|a, b| a.name().cmp(b.name()));
+        users
+    }
+
     pub fn list_fs_types_inner(&self) -> Vec<FsTypeDesc> {
         let mut fs_types = Vec::new();
         self.fs_type_by_id.iter_sync(|_fs_id, desc| {
@@ -332,6 +361,19 @@ impl MetaMgrImpl {
         self.broadcast_drop(table.schema()?.table_name(), oid)
     }
 
+    pub async fn alter_table_inner(&self, oid: OID, alters: &[AlterTable]) -> RS<()> {
+        let _ddl_guard = self.ddl_lock.lock().await;
+        let table = self
+            .lookup_table_info_by_id(oid)
+            .ok_or_else(|| mudu_error!(ER::EntityNotFound, format!("no such table {}", oid)))?;
+        let schema = table.schema()?;
+        let altered = schema.altered_all(alters)?;
+        let schema_catalog = self.catalog_relation()?.schema_catalog;
+        // The catalog is keyed by table oid, so this overwrites the old schema.
+        write_schema_to_catalog(&schema_catalog, &altered, self.next_catalog_xid()).await?;
+        self.broadcast_alter(schema.table_name(), &altered)
+    }
+
     pub async fn create_partition_rule_inner(&self, rule: &PartitionRuleDesc) -> RS<()> {
         let _ddl_guard = self.ddl_lock.lock().await;
         if self.rule_name2id.contains_sync(&rule.name) {
@@ -347,6 +389,36 @@ impl MetaMgrImpl {
         self.broadcast_create_partition_rule(rule)
     }
 
+    pub async fn update_partition_rule_inner(&self, rule: &PartitionRuleDesc) -> RS<()> {
+        let _ddl_guard = self.ddl_lock.lock().await;
+        let current = self.lookup_partition_rule_by_id(rule.oid).ok_or_else(|| {
+            mudu_error!(
+                ER::EntityNotFound,
+                format!("no such partition rule {}", rule.oid)
+            )
+        })?;
+        if current.name != rule.name {
+            return Err(mudu_error!(
+                ER::UnsupportedOperation,
+                format!("cannot rename partition rule {}", current.name)
+            ));
+        }
+        if rule.version <= current.version {
+            return Err(mudu_error!(
+                ER::InvalidState,
+                format!(
+                    "partition rule {} is at version {}, cannot update to version {}",
+                    rule.name, current.version, rule.version
+                )
+            ));
+        }
+        let partition_rule_catalog = self.catalog_relation()?.partition_rule_catalog;
+        // The catalog is keyed by rule oid, so this overwrites the old rule.
+        write_partition_rule_to_catalog(&partition_rule_catalog, rule, self.next_catalog_xid())
+            .await?;
+        self.broadcast_create_partition_rule(rule)
+    }
+
     pub async fn bind_table_partition_inner(&self, binding: &TablePartitionBinding) -> RS<()> {
         let _ddl_guard = self.ddl_lock.lock().await;
         if self.lookup_table_info_by_id(binding.table_id).is_none() {
@@ -422,6 +494,34 @@ impl MetaMgrImpl {
         self.broadcast_drop_fs_type(name)
     }
 
+    pub async fn create_user_inner(&self, desc: &UserDesc) -> RS<()> {
+        let _ddl_guard = self.ddl_lock.lock().await;
+        if self.user_by_name.contains_sync(desc.name()) {
+            return Err(mudu_error!(
+                ER::AlreadyExists,
+                format!("user {} already exists", desc.name())
+            ));
+        }
+        let user_catalog = self.catalog_relation()?.user;
+
+        write_user_to_catalog(&user_catalog, desc, self.next_catalog_xid()).await?;
+        self.broadcast_create_user(desc)
+    }
+
+    pub async fn drop_user_inner(&self, name: &str) -> RS<()> {
+        let _ddl_guard = self.ddl_lock.lock().await;
+        if !self.user_by_name.contains_sync(name) {
+            return Err(mudu_error!(
+                ER::EntityNotFound,
+                format!("no such user {}", name)
+            ));
+        }
+        let user_catalog = self.catalog_relation()?.user;
+
+        delete_user_from_catalog(&user_catalog, name, self.next_catalog_xid()).await?;
+        self.broadcast_drop_user(name)
+    }
+
     // Reject dropping a filesystem type that is still referenced by an
     // FS-bound table column (SchemaColumn::fs_binding).
     fn check_fs_type_not_referenced(&self, desc: &FsTypeDesc) -> RS<()> {
@@ -502,9 +602,22 @@ impl MetaMgrImpl {
         let _ = self.table.remove_sync(table_name);
     }
 
+    fn apply_alter_table_local(&self, old_table_name: &str, schema: &SchemaTable) -> RS<()> {
+        self.apply_drop_table_local(old_table_name, schema.id());
+        self.apply_create_table_local(schema)
+    }
+
+    // Also applies rule updates: an existing entry for the oid is replaced.
     fn apply_create_partition_rule_local(&self, rule: &PartitionRuleDesc) {
         let _ = self.rule_name2id.insert_sync(rule.name.clone(), rule.oid);
-        let _ = self.rule_by_id.insert_sync(rule.oid, rule.clone());
+        match self.rule_by_id.entry_sync(rule.oid) {
+            scc::hash_map::Entry::Occupied(mut entry) => {
+                *entry.get_mut() = rule.clone();
+            }
+            scc::hash_map::Entry::Vacant(slot) => {
+                slot.insert_entry(rule.clone());
+            }
+        }
     }
 
     fn apply_bind_table_partition_local(&self, binding: &TablePartitionBinding) {
@@ -514,9 +627,17 @@ impl MetaMgrImpl {
     }
 
     fn apply_partition_placement_local(&self, placement: &PartitionPlacement) {
-        let _ = self
+        match self
             .placement_by_partition_id
-            .insert_sync(placement.partition_id, placement.worker_id);
+            .entry_sync(placement.partition_id)
+        {
+            scc::hash_map::Entry::Occupied(mut entry) => {
+                *entry.get_mut() = placement.worker_id;
+            }
+            scc::hash_map::Entry::Vacant(slot) => {
+                slot.insert_entry(placement.worker_id);
+            }
+        }
     }
 
     fn apply_create_fs_type_local(&self, desc: &FsTypeDesc) {
@@ -535,6 +656,16 @@ impl MetaMgrImpl {
         }
     }
 
+    fn apply_create_user_local(&self, desc: &UserDesc) {
+        let _ = self
+            .user_by_name
+            .insert_sync(desc.name().to_string(), desc.clone());
+    }
+
+    fn apply_drop_user_local(&self, name: &str) {
+        let _ = self.user_by_name.remove_sync(name);
+    }
+
     fn broadcast_create(&self, schema: &SchemaTable) -> RS<()> {
         let peers = self.peer_instances()?;
         if peers.is_empty() {
@@ -563,6 +694,20 @@ impl MetaMgrImpl {
         Ok(())
     }
 
+    fn broadcast_alter(&self, old_table_name: &str, schema: &SchemaTable) -> RS<()> {
+        let peers = self.peer_instances()?;
+        if peers.is_empty() {
+            self.apply_alter_table_local(old_table_name, schema)?;
+            self.bump_catalog_version();
+            return Ok(());
+        }
+        for mgr in peers {
+            mgr.apply_alter_table_local(old_table_name, schema)?;
+            mgr.bump_catalog_version();
+        }
+        Ok(())
+    }
+
     fn broadcast_create_partition_rule(&self, rule: &PartitionRuleDesc) -> RS<()> {
         let peers = self.peer_instances()?;
         if peers.is_empty() {
@@ -637,6 +782,32 @@ impl MetaMgrImpl {
         Ok(())
     }
 
+    // Users do not affect compiled plans, so unlike the other catalogs these
+    // changes leave the catalog version alone.
+    fn broadcast_create_user(&self, desc: &UserDesc) -> RS<()> {
+        let peers = self.peer_instances()?;
+        if peers.is_empty() {
+            self.apply_create_user_local(desc);
+            return Ok(());
+        }
+        for mgr in peers {
+            mgr.apply_create_user_local(desc);
+        }
+        Ok(())
+    }
+
+    fn broadcast_drop_user(&self, name: &str) -> RS<()> {
+        let peers = self.peer_instances()?;
+        if peers.is_empty() {
+            self.apply_drop_user_local(name);
+            return Ok(());
+        }
+        for mgr in peers {
+            mgr.apply_drop_user_local(name);
+        }
+        Ok(())
+    }
+
     fn peer_instances(&self) -> RS<Vec<Arc<MetaMgrImpl>>> {
         let mut guard = registry().lock()?;
         let peers = guard.entry(self.path.clone()).or_default();
@@ -701,10 +872,18 @@ impl MetaMgr for MetaMgrImpl {
         self.drop_table_inner(table_id).await
     }
 
+    async fn alter_table(&self, table_id: OID, alters: &[AlterTable]) -> RS<()> {
+        self.alter_table_inner(table_id, alters).await
+    }
+
     async fn create_partition_rule(&self, rule: &PartitionRuleDesc) -> RS<()> {
         self.create_partition_rule_inner(rule).await
     }
 
+    async fn update_partition_rule(&self, rule: &PartitionRuleDesc) -> RS<()> {
+        self.update_partition_rule_inner(rule).await
+    }
+
     async fn get_partition_rule_by_id(&self, oid: OID) -> RS<PartitionRuleDesc> {
         self.lookup_partition_rule_by_id(oid).ok_or_else(|| {
             mudu_error!(
@@ -771,6 +950,31 @@ impl MetaMgr for MetaMgrImpl {
     async fn drop_fs_type(&self, name: &str) -> RS<()> {
         self.drop_fs_type_inner(name).await
     }
+
+    async fn create_user(&self, desc: &UserDesc) -> RS<()> {
+        self.create_user_inner(desc).await
+    }
+
+    async fn get_user_by_name(&self, name: &str) -> RS<Option<UserDesc>> {
+        if let Some(desc) = self.lookup_user_by_name(name) {
+            return Ok(Some(desc));
+        }
+        // An instance registered without replaying the catalog (the HTTP
+        // management API's) only sees users created after it registered, so
+        // ask the peers sharing this catalog path.
+        Ok(self
+            .peer_instances()?
+            .iter()
+            .find_map(|peer| peer.lookup_user_by_name(name)))
+    }
+
+    async fn list_users(&self) -> RS<Vec<UserDesc>> {
+        Ok(self.list_users_inner())
+    }
+
+    async fn drop_user(&self, name: &str) -> RS<()> {
+        self.drop_user_inner(name).await
+    }
 }
 
 unsafe impl Sync for MetaMgrImpl {}
@@ -789,8 +993,11 @@ mod tests {
     )]
 
     use crate::contract::fs_type::FsColumnBinding;
+    use crate::contract::partition_rule::{PartitionBound, RangePartitionDef};
     use crate::contract::schema_column::SchemaColumn;
+    use crate::contract::user::UserRole;
     use crate::meta::fs_object::FS_OBJECT_TABLE_ID;
+    use mudu_contract::protocol::scram::{ScramCredential, DEFAULT_SCRAM_ITERATIONS};
     use mudu_sys::env_var::temp_dir;
     use mudu_type::data_type_info::DataTypeInfo;
     use mudu_type::type_family::TypeFamily;
@@ -882,6 +1089,69 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn meta_mgr_alter_table_updates_peers_and_survives_reopen() {
+        block_on(async move {
+            let r = _meta_mgr_alter_table_updates_peers_and_survives_reopen().await;
+            assert!(r.is_ok());
+        });
+    }
+    async fn _meta_mgr_alter_table_updates_peers_and_survives_reopen() -> RS<()> {
+        let dir = temp_dir().join(format!("meta_mgr_alter_{}", mudu_utils::oid::gen_oid()));
+        let mgr1 = Arc::new(MetaMgrImpl::new(&dir).await?);
+        mgr1.register_global()?;
+        mgr1.initialize().await?;
+        let mgr2 = Arc::new(MetaMgrImpl::new(&dir).await?);
+        mgr2.register_global()?;
+        mgr2.initialize().await?;
+
+        let schema = test_schema();
+        mgr1.create_table(&schema).await?;
+        let version = mgr2.catalog_version();
+        mgr1.alter_table(
+            schema.id(),
+            &[AlterTable::AddColumn(SchemaColumn::new(
+                "w".to_string(),
+                TypeFamily::I64,
+                DataTypeInfo::from_text(TypeFamily::I64, String::new()),
+            ))],
+        )
+        .await?;
+        mgr1.alter_table(
+            schema.id(),
+            &[AlterTable::RenameColumn {
+                old_name: "v".to_string(),
+                new_name: "v2".to_string(),
+            }],
+        )
+        .await?;
+        assert_eq!(mgr2.catalog_version(), version + 2);
+        let table = mgr2.get_table_by_id(schema.id()).await?;
+        assert_eq!(table.layout_version(), 1);
+        assert_eq!(table.retired_layouts().len(), 1);
+        assert!(table.name2oid().contains_key("v2"));
+        assert!(table.name2oid().contains_key("w"));
+        assert!(!table.name2oid().contains_key("v"));
+        assert!(mgr2
+            .alter_table(schema.id(), &[AlterTable::DropColumn("id".to_string())])
+            .await
+            .is_err());
+
+        mgr1.catalog_relation()?
+            .schema_catalog
+            .flush_wal_async()
+            .await?;
+        drop(mgr1);
+        drop(mgr2);
+        let reopened = MetaMgrImpl::new(&dir).await?;
+        reopened.initialize().await?;
+        let table = reopened.get_table_by_id(schema.id()).await?;
+        assert_eq!(table.layout_version(), 1);
+        assert_eq!(table.value_desc().field_count(), 2);
+        assert!(table.name2oid().contains_key("v2"));
+        Ok(())
+    }
+
     #[test]
     fn meta_mgr_fs_type_create_query_and_drop() {
         block_on(async move {
@@ -931,6 +1201,42 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn meta_mgr_user_create_reload_and_drop() {
+        block_on(async move {
+            let r = _meta_mgr_user_create_reload_and_drop().await;
+            assert!(r.is_ok());
+        });
+    }
+    async fn _meta_mgr_user_create_reload_and_drop() -> RS<()> {
+        let dir = temp_dir().join(format!("meta_mgr_user_{}", mudu_utils::oid::gen_oid()));
+        let mgr = Arc::new(MetaMgrImpl::new(&dir).await?);
+        mgr.register_global()?;
+        mgr.initialize().await?;
+
+        let credential = ScramCredential::generate("secret", DEFAULT_SCRAM_ITERATIONS)?;
+        let alice = UserDesc::new("alice".to_string(), UserRole::Admin, credential.clone());
+        let bob = UserDesc::new("bob".to_string(), UserRole::User, credential);
+        mgr.create_user(&alice).await?;
+        mgr.create_user(&bob).await?;
+        assert_eq!(mgr.get_user_by_name("alice").await?, Some(alice.clone()));
+        assert!(mgr.get_user_by_name("missing").await?.is_none());
+        let dup = mgr.create_user(&alice).await;
+        assert_eq!(dup.unwrap_err().ec(), ER::AlreadyExists);
+
+        mgr.drop_user("bob").await?;
+        let missing = mgr.drop_user("bob").await;
+        assert_eq!(missing.unwrap_err().ec(), ER::EntityNotFound);
+        drop(mgr);
+
+        let reopened = MetaMgrImpl::new(&dir).await?;
+        reopened.initialize().await?;
+        let users = reopened.list_users().await?;
+        assert_eq!(users, vec![alice]);
+        assert!(users[0].credential().verify_password("secret")?);
+        Ok(())
+    }
+
     #[test]
     fn meta_mgr_drop_fs_type_rejects_still_referenced_type() {
         block_on(async move {
@@ -1152,4 +1458,85 @@ mod tests {
         assert_eq!(mgr2.catalog_version(), 2);
         Ok(())
     }
+
+    #[test]
+    fn meta_mgr_update_partition_rule_and_move_placement_reach_peers() {
+        block_on(async move {
+            let r = _meta_mgr_update_partition_rule_and_move_placement_reach_peers().await;
+            assert!(r.is_ok());
+        });
+    }
+    async fn _meta_mgr_update_partition_rule_and_move_placement_reach_peers() -> RS<()> {
+        let dir = temp_dir().join(format!(
+            "meta_mgr_update_rule_{}",
+            mudu_utils::oid::gen_oid()
+        ));
+        let mgr1 = Arc::new(MetaMgrImpl::new(&dir).await?);
+        mgr1.register_global()?;
+        mgr1.initialize().await?;
+        let mgr2 = Arc::new(MetaMgrImpl::new(&dir).await?);
+        mgr2.register_global()?;
+        mgr2.initialize().await?;
+
+        let rule = PartitionRuleDesc::new_range(
+            "split_rule".to_string(),
+            vec![TypeFamily::I32],
+            vec![RangePartitionDef::new(
+                "p0".to_string(),
+                PartitionBound::Unbounded,
+                PartitionBound::Unbounded,
+            )],
+        );
+        mgr1.create_partition_rule(&rule).await?;
+        let partition_id = rule.partitions[0].partition_id;
+        mgr1.upsert_partition_placements(&[PartitionPlacement {
+            partition_id,
+            worker_id: 1,
+        }])
+        .await?;
+
+        // Moving a partition overwrites its placement everywhere.
+        mgr1.upsert_partition_placements(&[PartitionPlacement {
+            partition_id,
+            worker_id: 2,
+        }])
+        .await?;
+        assert_eq!(mgr2.get_partition_worker(partition_id).await?, Some(2));
+
+        let mut split = rule.clone();
+        split.partitions[0].end = PartitionBound::Value(vec![b"10".to_vec()]);
+        split.partitions.push(RangePartitionDef::new(
+            "p1".to_string(),
+            PartitionBound::Value(vec![b"10".to_vec()]),
+            PartitionBound::Unbounded,
+        ));
+        split.version = rule.version + 1;
+        mgr1.update_partition_rule(&split).await?;
+        assert_eq!(mgr2.get_partition_rule_by_id(rule.oid).await?, split);
+        assert_eq!(
+            mgr2.get_partition_rule_by_name("split_rule").await?,
+            Some(split.clone())
+        );
+
+        // A stale version is rejected and leaves the rule untouched.
+        let err = mgr2.update_partition_rule(&rule).await.unwrap_err();
+        assert_eq!(err.ec(), ER::InvalidState);
+        assert_eq!(mgr1.get_partition_rule_by_id(rule.oid).await?, split);
+
+        // The catalogs keep the latest rule and placement across a reopen.
+        let catalogs = mgr1.catalog_relation()?;
+        catalogs.partition_rule_catalog.flush_wal_async().await?;
+        catalogs
+            .partition_placement_catalog
+            .flush_wal_async()
+            .await?;
+        drop(catalogs);
+        drop(mgr1);
+        drop(mgr2);
+        let reopened = MetaMgrImpl::new(&dir).await?;
+        reopened.initialize().await?;
+        assert_eq!(reopened.get_partition_rule_by_id(rule.oid).await?, split);
+        assert_eq!(reopened.get_partition_worker(partition_id).await?, Some(2));
+        Ok(())
+    }
 }
@@ -12,3 +12,4 @@ pub mod partition_binding_catalog;
 pub mod partition_placement_catalog;
 pub mod partition_rule_catalog;
 pub mod schema_catalog;
+pub mod user_catalog;
@@ -0,0 +1,175 @@
+use mudu_sys::contract::async_io_provider::AsyncIoProvider;
+use mudu_sys::time::system_time_now;
+use std::ops::Bound;
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+use mudu::common::id::OID;
+use mudu::common::result::RS;
+use mudu_contract::tuple::build_tuple::build_tuple;
+use mudu_type::data_type::DataType;
+use mudu_type::data_type_function::send_binary;
+use mudu_type::data_type_info::DataTypeInfo;
+use mudu_type::data_value::DataValue;
+use mudu_type::type_family::TypeFamily;
+
+use crate::contract::schema_column::SchemaColumn;
+use crate::contract::schema_table::SchemaTable;
+use crate::contract::table_desc::TableDesc;
+use crate::contract::table_info::TableInfo;
+use crate::contract::user::UserDesc;
+use crate::server::worker_snapshot::WorkerSnapshot;
+use crate::storage::relation::relation::Relation;
+
+/// Partition id shared by all meta catalog relations.
+pub const USER_CATALOG_PARTITION_ID: OID = 0;
+/// Fixed table oid of the user catalog.
+pub const USER_CATALOG_TABLE_ID: OID = 0x6;
+const USER_CATALOG_TABLE_NAME: &str = "__meta_user";
+const USER_CATALOG_NAME_COLUMN_ID: OID = 0x60001;
+const USER_CATALOG_ENTRY_COLUMN_ID: OID = 0x60002;
+
+/// Build the schema of the user catalog table.
+pub fn user_catalog_schema() -> SchemaTable {
+    SchemaTable::new_with_oid(
+        USER_CATALOG_TABLE_ID,
+        USER_CATALOG_TABLE_NAME.to_string(),
+        vec![
+            SchemaColumn::new_with_oid(
+                USER_CATALOG_NAME_COLUMN_ID,
+                "name".to_string(),
+                TypeFamily::String,
+                DataType::default_for(TypeFamily::String).to_info(),
+            ),
+            SchemaColumn::new_with_oid(
+                USER_CATALOG_ENTRY_COLUMN_ID,
+                "entry".to_string(),
+                TypeFamily::Binary,
+                DataTypeInfo::from_text(TypeFamily::Binary, String::new()),
+            ),
+        ],
+        vec![0],
+        vec![1],
+    )
+}
+
+/// Build the table descriptor of the user catalog table.
+pub fn user_catalog_desc() -> RS<Arc<TableDesc>> {
+    TableInfo::new(user_catalog_schema())?.table_desc()
+}
+
+/// Open (or create) the user catalog relation rooted at `path`.
+pub async fn open_user_catalog(
+    path: &str,
+    async_runtime: Option<Arc<dyn AsyncIoProvider>>,
+) -> RS<Relation> {
+    let desc = user_catalog_desc()?;
+    match async_runtime {
+        Some(provider) => {
+            Relation::new_with_provider(
+                provider,
+                USER_CATALOG_TABLE_ID,
+                USER_CATALOG_PARTITION_ID,
+                path.to_string(),
+                desc.as_ref(),
+            )
+            .await
+        }
+        None => {
+            Relation::new(
+                USER_CATALOG_TABLE_ID,
+                USER_CATALOG_PARTITION_ID,
+                path.to_string(),
+                desc.as_ref(),
+            )
+            .await
+        }
+    }
+}
+
+/// Encode a user name into a catalog key tuple.
+pub fn encode_user_catalog_key(name: &str) -> RS<Vec<u8>> {
+    let desc = user_catalog_desc()?;
+    let datum = send_binary(
+        &DataValue::from_string(name.to_string()),
+        &DataType::default_for(TypeFamily::String),
+    )
+    .map_err(|e| {
+        mudu::mudu_error!(
+            mudu::error::ErrorCode::Encode,
+            "encode user catalog key error",
+            e
+        )
+    })?;
+    build_tuple(&[datum], desc.key_desc())
+}
+
+/// Encode a user descriptor into a catalog value.
+pub fn encode_user_catalog_value(desc: &UserDesc) -> RS<Vec<u8>> {
+    rmp_serde::to_vec(desc).map_err(|e| {
+        mudu::mudu_error!(
+            mudu::error::ErrorCode::Encode,
+            "encode user catalog value error",
+            e
+        )
+    })
+}
+
+/// Decode a catalog value back into a user descriptor.
+pub fn decode_user_catalog_value(tuple: &[u8]) -> RS<UserDesc> {
+    rmp_serde::from_slice(tuple).map_err(|e| {
+        mudu::mudu_error!(
+            mudu::error::ErrorCode::Decode,
+            "decode user catalog value error",
+            e
+        )
+    })
+}
+
+/// Replay all user descriptors stored in the catalog relation.
+pub async fn load_users_from_catalog(relation: &Relation) -> RS<Vec<UserDesc>> {
+    let rows = relation
+        .visible_range(
+            (Bound::Unbounded, Bound::Unbounded),
+            &WorkerSnapshot::new(visible_snapshot_xid(), vec![]),
+        )
+        .await?;
+    let mut users = Vec::with_capacity(rows.len());
+    for (key, value) in rows {
+        let user = decode_user_catalog_value(&value)?;
+        if encode_user_catalog_key(user.name())? != key {
+            return Err(mudu::mudu_error!(
+                mudu::error::ErrorCode::Decode,
+                format!("user catalog key does not match user name {}", user.name())
+            ));
+        }
+        users.push(user);
+    }
+    Ok(users)
+}
+
+fn visible_snapshot_xid() -> u64 {
+    let base = system_time_now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+        .min((u64::MAX - 2) as u128) as u64;
+    base.saturating_add(1)
+}
+
+/// Persist a user descriptor into the catalog relation at `xid`.
+pub async fn write_user_to_catalog(relation: &Relation, desc: &UserDesc, xid: u64) -> RS<()> {
+    let key = encode_user_catalog_key(desc.name())?;
+    let value = encode_user_catalog_value(desc)?;
+    relation.write_value(key, value, xid).await?;
+    // Catalog relations have no background flush driver: drain the queued
+    // PL frames so the DDL is durable when it returns.
+    relation.flush_wal_async().await
+}
+
+/// Delete a user entry from the catalog relation at `xid`.
+pub async fn delete_user_from_catalog(relation: &Relation, name: &str, xid: u64) -> RS<()> {
+    let key = encode_user_catalog_key(name)?;
+    relation.write_delete(key, xid).await?;
+    relation.flush_wal_async().await
+}
@@ -8,10 +8,12 @@ use mudu_contract::database::prepared_stmt::PreparedStmt;
 use mudu_contract::database::result_set::ResultSetAsync;
 use mudu_contract::database::sql_params::SQLParams;
 use mudu_contract::database::sql_stmt::SQLStmt;
+use mudu_contract::protocol::scram::ScramClient;
 use mudu_contract::protocol::{
-    decode_error_response, decode_server_response, decode_session_create_response,
-    encode_batch_request, encode_client_request_with_message_type, encode_session_create_request,
-    ClientRequest, Frame, FrameHeader, MessageType, SessionCreateRequest, HEADER_LEN,
+    decode_auth_response, decode_error_response, decode_server_response,
+    decode_session_create_response, encode_auth_request, encode_batch_request,
+    encode_client_request_with_message_type, encode_session_create_request, ClientRequest, Frame,
+    FrameHeader, MessageType, SessionCreateRequest, HEADER_LEN,
 };
 use mudu_sys::common::provider_type::ProviderType;
 use mudu_sys::contract::async_mode::AsyncMode;
@@ -35,6 +37,10 @@ static DEFAULT_REMOTE_ADDR: OnceLock<SMutex<Option<String>>> = OnceLock::new();
 static DEFAULT_REMOTE_WORKER_ID: OnceLock<SMutex<Option<OID>>> = OnceLock::new();
 static DEFAULT_REMOTE_ASYNC_RUNTIME: OnceLock<SMutex<Option<Arc<dyn AsyncIoProvider>>>> =
     OnceLock::new();
+static DEFAULT_REMOTE_CREDENTIAL: OnceLock<SMutex<Option<RemoteCredential>>> = OnceLock::new();
+
+/// User name and password used to authenticate remote connections.
+pub type RemoteCredential = (String, String);
 
 enum ConnBackend {
     WorkerLocal(WorkerLocalRef),
@@ -45,6 +51,7 @@ struct RemoteWorkerConn {
     addr: String,
     worker_id: Option<OID>,
     async_runtime: Option<Arc<dyn AsyncIoProvider>>,
+    credential: Option<RemoteCredential>,
     session_id: SMutex<Option<OID>>,
     stream: AMutex<Option<RemoteProtocolClient>>,
 }
@@ -81,6 +88,15 @@ pub fn set_default_remote_async_runtime(async_runtime: Option<Arc<dyn AsyncIoPro
     }
 }
 
+/// Sets the credential remote connections authenticate with, for servers
+/// that require authentication.
+pub fn set_default_remote_credential(credential: Option<RemoteCredential>) {
+    let slot = DEFAULT_REMOTE_CREDENTIAL.get_or_init(|| SMutex::new(None));
+    if let Ok(mut guard) = slot.lock() {
+        *guard = credential;
+    }
+}
+
 pub fn clear_default_remote_if_current(addr: &str, worker_id: Option<OID>) {
     let current_addr = default_remote_addr();
     let current_worker_id = default_remote_worker_id();
@@ -90,6 +106,7 @@ pub fn clear_default_remote_if_current(addr: &str, worker_id: Option<OID>) {
     set_default_remote_addr(None);
     set_default_remote_worker_id(None);
     set_default_remote_async_runtime(None);
+    set_default_remote_credential(None);
 }
 
 fn default_remote_addr() -> Option<String> {
@@ -110,6 +127,14 @@ fn default_remote_async_runtime() -> Option<Arc<dyn AsyncIoProvider>> {
         .and_then(|slot| slot.lock().ok().and_then(|guard| guard.clone()))
 }
 
+/// Credential set by [`set_default_remote_credential`], for in-process
+/// clients that talk to the local server over TCP.
+pub fn default_remote_credential() -> Option<RemoteCredential> {
+    DEFAULT_REMOTE_CREDENTIAL
+        .get()
+        .and_then(|slot| slot.lock().ok().and_then(|guard| guard.clone()))
+}
+
 impl MuduConnAsync {
     pub fn new() -> RS<Self> {
         Self::new_with_runtime(default_remote_async_runtime())
@@ -130,6 +155,7 @@ impl MuduConnAsync {
             addr,
             worker_id: default_remote_worker_id(),
             async_runtime,
+            credential: default_remote_credential(),
             session_id: SMutex::new(None),
             stream: AMutex::new(None),
         });
@@ -268,8 +294,12 @@ impl RemoteWorkerConn {
     async fn client(&self) -> RS<AMutexGuard<'_, Option<RemoteProtocolClient>>> {
         let mut guard = self.stream.lock().await;
         if guard.is_none() {
-            *guard =
-                Some(RemoteProtocolClient::connect(&self.addr, self.async_runtime.clone()).await?);
+            let mut client =
+                RemoteProtocolClient::connect(&self.addr, self.async_runtime.clone()).await?;
+            if let Some((user, password)) = &self.credential {
+                client.authenticate(user, password).await?;
+            }
+            *guard = Some(client);
         }
         Ok(guard)
     }
@@ -361,6 +391,17 @@ impl RemoteProtocolClient {
         request_id
     }
 
+    /// Run the SCRAM exchange; see `mudu_contract::protocol::scram`.
+    async fn authenticate(&mut self, user: &str, password: &str) -> RS<()> {
+        let mut scram = ScramClient::new(user, password);
+        let payload = encode_auth_request(self.take_request_id(), &scram.client_first())?;
+        let server_first = decode_auth_response(&self.send_and_receive(&payload).await?)?;
+        let payload =
+            encode_auth_request(self.take_request_id(), &scram.client_final(server_first)?)?;
+        let server_final = decode_auth_response(&self.send_and_receive(&payload).await?)?;
+        scram.verify_server_final(server_final)
+    }
+
     async fn send_and_receive(&mut self, payload: &[u8]) -> RS<Frame> {
         self.stream
             .write_all(payload)
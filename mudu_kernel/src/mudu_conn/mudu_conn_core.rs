@@ -1,6 +1,7 @@
 use mudu::common::result::RS;
 use mudu::error::ErrorCode;
 use mudu::mudu_error;
+use mudu_contract::database::result_set::ResultSetAsync;
 use mudu_contract::database::sql_params::SQLParams;
 use mudu_contract::database::sql_stmt::SQLStmt;
 use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
@@ -12,9 +13,10 @@ use sql_parser::ast::parser::SQLParser;
 use sql_parser::ast::stmt_type::StmtType;
 use std::sync::Arc;
 
+use crate::contract::cmd_exec::CmdExec;
 use crate::contract::meta_mgr::MetaMgr;
 use crate::contract::query_exec::QueryExec;
-use crate::mudu_conn::mudu_result_set_async::MuduResultSetAsync;
+use crate::mudu_conn::mudu_result_set_async::{MuduResultSetAsync, QueryExecResultSet};
 use crate::sql::binder::Binder;
 use crate::sql::bound_stmt::{BoundCommand, BoundStmt};
 use crate::sql::describer::Describer;
@@ -81,7 +83,7 @@ impl MuduConnCore {
         params: Box<dyn SQLParams>,
         tx_mgr: Arc<dyn TxMgr>,
         x_contract: Arc<dyn XContract>,
-    ) -> RS<Arc<dyn mudu_contract::database::result_set::ResultSetAsync>> {
+    ) -> RS<Arc<dyn ResultSetAsync>> {
         let (rows, desc) = self.query_rows(stmt, params, tx_mgr, x_contract).await?;
         Ok(Arc::new(MuduResultSetAsync::from_rows(rows, desc)))
     }
@@ -93,6 +95,20 @@ impl MuduConnCore {
         tx_mgr: Arc<dyn TxMgr>,
         x_contract: Arc<dyn XContract>,
     ) -> RS<(Vec<TupleValue>, TupleFieldDesc)> {
+        self.query_inner(stmt, params, tx_mgr, x_contract)
+            .await?
+            .into_rows()
+            .await
+    }
+
+    /// Bind and plan a query without reading its rows; see [`QueryOutput`].
+    pub async fn open_query(
+        &self,
+        stmt: &StmtType,
+        params: Box<dyn SQLParams>,
+        tx_mgr: Arc<dyn TxMgr>,
+        x_contract: Arc<dyn XContract>,
+    ) -> RS<QueryOutput> {
         self.query_inner(stmt, params, tx_mgr, x_contract).await
     }
 
@@ -113,7 +129,7 @@ impl MuduConnCore {
         params: Box<dyn SQLParams>,
         tx_mgr: Arc<dyn TxMgr>,
         x_contract: Arc<dyn XContract>,
-    ) -> RS<(Vec<TupleValue>, TupleFieldDesc)> {
+    ) -> RS<QueryOutput> {
         let trace = task_trace!();
         trace.watch("query.stage", "bind");
         let bound = {
@@ -124,18 +140,34 @@ impl MuduConnCore {
                 .bind_ref(stmt, params.as_ref())
                 .await?
         };
-        let BoundStmt::Query(bound_query) = bound else {
-            return Err(mudu_error!(
-                ErrorCode::InvalidType,
-                "statement is not a query"
-            ));
-        };
         let planner = Planner::new(PlanCtx {
             tx_mgr,
             meta_mgr: self.meta_mgr.clone(),
             x_contract,
             async_runtime: self.async_runtime.clone(),
         });
+        let bound_query = match bound {
+            BoundStmt::Query(bound_query) => bound_query,
+            // A command with a `RETURNING` clause answers with its rows.
+            BoundStmt::Command(bound_command) if bound_command.has_returning() => {
+                trace.watch("query.stage", "plan_command");
+                let cmd = {
+                    let _stage = crate::server::stage_stats::StageGuard::new(
+                        crate::server::stage_stats::Stage::SqlPlan,
+                    );
+                    planner.plan_command(bound_command).await?
+                };
+                trace.watch("query.stage", "exec_command");
+                let (rows, desc) = command_exec_to_rows(cmd).await?;
+                return Ok(QueryOutput::Rows(rows, desc));
+            }
+            BoundStmt::Command(_) => {
+                return Err(mudu_error!(
+                    ErrorCode::InvalidType,
+                    "statement is not a query"
+                ));
+            }
+        };
         trace.watch("query.stage", "plan");
         let exec = {
             let _stage = crate::server::stage_stats::StageGuard::new(
@@ -143,10 +175,7 @@ impl MuduConnCore {
             );
             planner.plan_query(bound_query).await?
         };
-        trace.watch("query.stage", "exec_rows");
-        let _stage =
-            crate::server::stage_stats::StageGuard::new(crate::server::stage_stats::Stage::SqlRun);
-        query_exec_to_rows(exec).await
+        Ok(QueryOutput::Exec(exec))
     }
 
     async fn execute_inner(
@@ -218,6 +247,42 @@ impl MuduConnCore {
     }
 }
 
+/// What running a query leaves to read.
+pub enum QueryOutput {
+    /// Rows already read: those a command's `RETURNING` clause produced, or
+    /// a point read.
+    Rows(Vec<TupleValue>, TupleFieldDesc),
+    /// A planned query whose rows are read from its executor.
+    Exec(Arc<dyn QueryExec>),
+}
+
+impl QueryOutput {
+    /// Read every row.
+    pub async fn into_rows(self) -> RS<(Vec<TupleValue>, TupleFieldDesc)> {
+        match self {
+            QueryOutput::Rows(rows, desc) => Ok((rows, desc)),
+            QueryOutput::Exec(exec) => {
+                let trace = task_trace!();
+                trace.watch("query.stage", "exec_rows");
+                let _stage = crate::server::stage_stats::StageGuard::new(
+                    crate::server::stage_stats::Stage::SqlRun,
+                );
+                query_exec_to_rows(exec).await
+            }
+        }
+    }
+
+    /// A result set that reads the rows as they are asked for.
+    pub async fn into_result_set(self) -> RS<Arc<dyn ResultSetAsync>> {
+        match self {
+            QueryOutput::Rows(rows, desc) => {
+                Ok(Arc::new(MuduResultSetAsync::from_rows(rows, desc)))
+            }
+            QueryOutput::Exec(exec) => Ok(Arc::new(QueryExecResultSet::open(exec).await?)),
+        }
+    }
+}
+
 pub async fn query_exec_to_rows(exec: Arc<dyn QueryExec>) -> RS<(Vec<TupleValue>, TupleFieldDesc)> {
     let trace = task_trace!();
     trace.watch("query.exec.stage", "open");
@@ -243,6 +308,34 @@ pub async fn query_exec_to_rows(exec: Arc<dyn QueryExec>) -> RS<(Vec<TupleValue>
     Ok((rows, desc))
 }
 
+/// Runs a command with a `RETURNING` clause and materializes its rows.
+pub async fn command_exec_to_rows(cmd: Arc<dyn CmdExec>) -> RS<(Vec<TupleValue>, TupleFieldDesc)> {
+    {
+        let _stage = crate::server::stage_stats::StageGuard::new(
+            crate::server::stage_stats::Stage::SqlPrepare,
+        );
+        cmd.prepare().await?;
+    }
+    {
+        let _stage =
+            crate::server::stage_stats::StageGuard::new(crate::server::stage_stats::Stage::SqlRun);
+        cmd.run().await?;
+    }
+    let returned = cmd
+        .returning()
+        .await?
+        .ok_or_else(|| mudu_error!(ErrorCode::InvalidType, "statement is not a query"))?;
+    let _stage = crate::server::stage_stats::StageGuard::new(
+        crate::server::stage_stats::Stage::ResultDecode,
+    );
+    let rows = returned
+        .rows
+        .into_iter()
+        .map(|row| tuple_field_to_value(row, &returned.tuple_desc))
+        .collect::<RS<Vec<_>>>()?;
+    Ok((rows, returned.tuple_desc))
+}
+
 pub(crate) fn tuple_field_to_value(
     row: mudu_contract::tuple::tuple_field::TupleField,
     desc: &TupleFieldDesc,
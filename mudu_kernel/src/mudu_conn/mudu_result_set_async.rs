@@ -7,6 +7,7 @@ use mudu_sys::sync::async_::futures_mutex::FMutex;
 use std::sync::Arc;
 
 use crate::contract::query_exec::QueryExec;
+use crate::mudu_conn::mudu_conn_core::tuple_field_to_value;
 
 pub struct MuduResultSetAsync {
     desc: Arc<TupleFieldDesc>,
@@ -49,6 +50,38 @@ impl ResultSetAsync for MuduResultSetAsync {
     }
 }
 
+/// A result set reading the rows of a query executor one at a time, as they
+/// are asked for.
+pub struct QueryExecResultSet {
+    desc: TupleFieldDesc,
+    exec: Arc<dyn QueryExec>,
+}
+
+impl QueryExecResultSet {
+    pub async fn open(exec: Arc<dyn QueryExec>) -> RS<Self> {
+        exec.open().await?;
+        let desc = exec.tuple_desc()?;
+        Ok(Self { desc, exec })
+    }
+}
+
+#[async_trait]
+impl ResultSetAsync for QueryExecResultSet {
+    async fn next(&self) -> RS<Option<TupleValue>> {
+        let Some(row) = self.exec.next().await? else {
+            return Ok(None);
+        };
+        let _stage = crate::server::stage_stats::StageGuard::new(
+            crate::server::stage_stats::Stage::ResultDecode,
+        );
+        Ok(Some(tuple_field_to_value(row, &self.desc)?))
+    }
+
+    fn desc(&self) -> &TupleFieldDesc {
+        &self.desc
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(
@@ -212,4 +245,31 @@ mod tests {
         })
         .unwrap()
     }
+
+    #[test]
+    fn query_exec_result_set_reads_rows_as_asked() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let exec = Arc::new(TestQueryExec {
+                rows: SMutex::new(VecDeque::from(vec![
+                    TupleField::new(vec![i32_bin(10)]),
+                    TupleField::new(vec![i32_bin(20)]),
+                ])),
+                tuple_desc: test_desc(),
+                open_error: false,
+            });
+            let rs = QueryExecResultSet::open(exec.clone()).await.unwrap();
+            assert_eq!(
+                rs.next().await.unwrap().unwrap().values()[0].as_i32(),
+                Some(&10)
+            );
+            // The second row is still in the executor.
+            assert_eq!(exec.rows.lock().unwrap().len(), 1);
+            assert_eq!(
+                rs.next().await.unwrap().unwrap().values()[0].as_i32(),
+                Some(&20)
+            );
+            assert!(rs.next().await.unwrap().is_none());
+        })
+        .unwrap()
+    }
 }
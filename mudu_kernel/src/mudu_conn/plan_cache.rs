@@ -22,7 +22,7 @@
 //! allocating a key string.
 
 use crate::contract::meta_mgr::MetaMgr;
-use crate::mudu_conn::mudu_conn_core::{query_exec_to_rows, tuple_field_to_value};
+use crate::mudu_conn::mudu_conn_core::{command_exec_to_rows, tuple_field_to_value, QueryOutput};
 use crate::sql::bound_stmt::BoundStmt;
 use crate::sql::bound_template::{
     fill_pairs, BoundTemplate, PlanClass, PredicateTemplate, SetValueTemplate, StmtTemplate,
@@ -92,45 +92,58 @@ impl CachedPlan {
 
     /// Executes the template as a query: a point read issues one
     /// `XContract::read_key` and materializes the row like
-    /// `query_exec_to_rows` does; anything else is filled and run through
-    /// the regular planner and executors.
-    pub(crate) async fn run_query(
+    /// `query_exec_to_rows` does; anything else is filled and planned
+    /// through the regular planner, leaving its rows to the executors.
+    pub(crate) async fn open_query(
         &self,
         params: &dyn SQLParams,
         tx_mgr: Arc<dyn TxMgr>,
         x_contract: Arc<dyn XContract>,
         meta_mgr: Arc<dyn MetaMgr>,
         async_runtime: Option<Arc<dyn AsyncIoProvider>>,
-    ) -> RS<(Vec<TupleValue>, TupleFieldDesc)> {
+    ) -> RS<QueryOutput> {
         if let (PlanClass::PointRead { select }, StmtTemplate::Select(template)) =
             (&self.class, &self.template.stmt)
         {
-            return self
+            let (rows, desc) = self
                 .run_point_read(template, select, params, tx_mgr, x_contract)
-                .await;
+                .await?;
+            return Ok(QueryOutput::Rows(rows, desc));
         }
         let bound = self.template.fill(params)?;
-        let BoundStmt::Query(query) = bound else {
-            return Err(mudu_error!(
-                ErrorCode::InvalidType,
-                "statement is not a query"
-            ));
-        };
         let planner = Planner::new(PlanCtx {
             tx_mgr,
             meta_mgr,
             x_contract,
             async_runtime,
         });
+        let query = match bound {
+            BoundStmt::Query(query) => query,
+            // A command with a `RETURNING` clause answers with its rows.
+            BoundStmt::Command(command) if command.has_returning() => {
+                let cmd = {
+                    let _stage = crate::server::stage_stats::StageGuard::new(
+                        crate::server::stage_stats::Stage::SqlPlan,
+                    );
+                    planner.plan_command(command).await?
+                };
+                let (rows, desc) = command_exec_to_rows(cmd).await?;
+                return Ok(QueryOutput::Rows(rows, desc));
+            }
+            BoundStmt::Command(_) => {
+                return Err(mudu_error!(
+                    ErrorCode::InvalidType,
+                    "statement is not a query"
+                ));
+            }
+        };
         let exec = {
             let _stage = crate::server::stage_stats::StageGuard::new(
                 crate::server::stage_stats::Stage::SqlPlan,
             );
             planner.plan_query(query).await?
         };
-        let _stage =
-            crate::server::stage_stats::StageGuard::new(crate::server::stage_stats::Stage::SqlRun);
-        query_exec_to_rows(exec).await
+        Ok(QueryOutput::Exec(exec))
     }
 
     /// Executes the template as a command: point updates/inserts issue direct
@@ -154,7 +167,7 @@ impl CachedPlan {
                 self.run_point_insert(template, params, tx_mgr, x_contract)
                     .await
             }
-            (_, StmtTemplate::Select(_)) => Err(mudu_error!(
+            (_, StmtTemplate::Select(_) | StmtTemplate::Join(_)) => Err(mudu_error!(
                 ErrorCode::InvalidType,
                 "statement is not a command"
             )),
@@ -270,6 +283,14 @@ impl CachedPlan {
                     op: *op,
                     literal: operand.fill_some(slots, params)?,
                 }),
+                // Expression assignments read the row; `classify` keeps them
+                // off this path.
+                SetValueTemplate::Expr(_) => {
+                    return Err(mudu_error!(
+                        ErrorCode::Internal,
+                        "expression assignment on the point update path"
+                    ))
+                }
             }
         }
         if absolute.is_empty() && delta_assignments.is_empty() {
@@ -430,6 +451,8 @@ mod tests {
             StmtTemplate::Delete(DeleteTemplate {
                 table_id,
                 key: Vec::new(),
+                returning: None,
+                scan: None,
             }),
             Vec::new(),
         )
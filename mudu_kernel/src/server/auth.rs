@@ -0,0 +1,243 @@
+//! Connection authentication for the binary protocol.
+//!
+//! With [`AuthCfg`] set on the server config, a connection must finish a
+//! SCRAM-SHA-256 exchange on `Auth` frames (see
+//! `mudu_contract::protocol::scram`) before any request other than
+//! `Handshake` or `Auth` is served. Users live in the meta catalog; the
+//! configured bootstrap admin is created on first start.
+//!
+//! One [`ServerAuth`] is shared by every worker of a server: connection ids
+//! are allocated per server, and a connection may reach other workers
+//! through routed requests.
+
+use mudu::common::result::RS;
+use mudu::error::ErrorCode;
+use mudu::mudu_error;
+use mudu_contract::protocol::scram::{
+    auth_message, mock_salt, new_nonce, new_salt, ScramCredential, DEFAULT_SCRAM_ITERATIONS,
+};
+use mudu_contract::protocol::{AuthResponse, MessageType};
+use tracing::debug;
+
+use crate::contract::meta_mgr::MetaMgr;
+use crate::contract::user::{UserDesc, UserRole};
+
+/// Authentication settings of a server.
+#[derive(Debug, Clone)]
+pub struct AuthCfg {
+    admin_user: String,
+    admin_password: String,
+}
+
+impl AuthCfg {
+    /// Require authentication and bootstrap `admin_user` with
+    /// `admin_password` when the catalog has no such user yet.
+    pub fn new(admin_user: String, admin_password: String) -> Self {
+        Self {
+            admin_user,
+            admin_password,
+        }
+    }
+
+    pub fn admin_user(&self) -> &str {
+        &self.admin_user
+    }
+
+    pub fn admin_password(&self) -> &str {
+        &self.admin_password
+    }
+}
+
+enum ConnAuthState {
+    /// `ServerFirst` was sent; `credential` is `None` for an unknown user,
+    /// whose exchange is carried to the end and then rejected.
+    ChallengeSent {
+        user: String,
+        nonce: String,
+        auth_message: Vec<u8>,
+        credential: Option<(ScramCredential, UserRole)>,
+    },
+    Authenticated {
+        role: UserRole,
+    },
+}
+
+/// Per-server authentication state; see the module docs.
+pub struct ServerAuth {
+    cfg: Option<AuthCfg>,
+    mock_salt_key: Vec<u8>,
+    conns: scc::HashMap<u64, ConnAuthState>,
+}
+
+impl ServerAuth {
+    pub fn new(cfg: Option<AuthCfg>) -> Self {
+        Self {
+            cfg,
+            mock_salt_key: new_salt(),
+            conns: Default::default(),
+        }
+    }
+
+    /// A server that serves every connection without authentication.
+    pub fn disabled() -> Self {
+        Self::new(None)
+    }
+
+    /// Whether connections must authenticate before issuing requests.
+    pub fn required(&self) -> bool {
+        self.cfg.is_some()
+    }
+
+    /// Create the configured bootstrap admin if the catalog lacks it. Each
+    /// worker calls this on start; losing the race to another one is fine.
+    pub async fn seed_bootstrap_admin(&self, meta_mgr: &dyn MetaMgr) -> RS<()> {
+        let Some(cfg) = &self.cfg else {
+            return Ok(());
+        };
+        if meta_mgr.get_user_by_name(cfg.admin_user()).await?.is_some() {
+            return Ok(());
+        }
+        let credential = ScramCredential::generate(cfg.admin_password(), DEFAULT_SCRAM_ITERATIONS)?;
+        let desc = UserDesc::new(cfg.admin_user().to_string(), UserRole::Admin, credential);
+        match meta_mgr.create_user(&desc).await {
+            Err(e) if e.ec() != ErrorCode::AlreadyExists => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Answer a `ClientFirst` message of `conn_id`. `desc` is the catalog
+    /// entry of `user`, if any.
+    pub fn server_first(
+        &self,
+        conn_id: u64,
+        user: String,
+        client_nonce: String,
+        desc: Option<UserDesc>,
+    ) -> RS<AuthResponse> {
+        if client_nonce.is_empty() {
+            return Err(mudu_error!(
+                ErrorCode::PermissionDenied,
+                "scram client nonce is empty"
+            ));
+        }
+        let nonce = format!("{}{}", client_nonce, new_nonce());
+        let (salt, iterations, credential) = match desc {
+            Some(desc) => (
+                desc.credential().salt().to_vec(),
+                desc.credential().iterations(),
+                Some((desc.credential().clone(), desc.role())),
+            ),
+            None => (
+                mock_salt(&self.mock_salt_key, &user)?,
+                DEFAULT_SCRAM_ITERATIONS,
+                None,
+            ),
+        };
+        let state = ConnAuthState::ChallengeSent {
+            auth_message: auth_message(&user, &client_nonce, &nonce, &salt, iterations),
+            user,
+            nonce: nonce.clone(),
+            credential,
+        };
+        self.set_state(conn_id, state);
+        Ok(AuthResponse::ServerFirst {
+            nonce,
+            salt,
+            iterations,
+        })
+    }
+
+    /// Verify the `ClientFinal` proof of `conn_id`. The connection state is
+    /// only replaced once the proof verifies: a rejected proof leaves the
+    /// pending challenge in place, and a `ClientFinal` on an authenticated
+    /// connection keeps its authentication.
+    pub fn server_final(&self, conn_id: u64, nonce: &str, proof: &[u8]) -> RS<AuthResponse> {
+        let denied = || mudu_error!(ErrorCode::PermissionDenied, "authentication failed");
+        let challenge = self.conns.read_sync(&conn_id, |_, state| match state {
+            ConnAuthState::ChallengeSent {
+                user,
+                nonce,
+                auth_message,
+                credential,
+            } => Some((
+                user.clone(),
+                nonce.clone(),
+                auth_message.clone(),
+                credential.clone(),
+            )),
+            ConnAuthState::Authenticated { .. } => None,
+        });
+        let Some(challenge) = challenge else {
+            return Err(mudu_error!(
+                ErrorCode::PermissionDenied,
+                "scram client-final received before client-first"
+            ));
+        };
+        let Some((user, expected_nonce, auth_message, credential)) = challenge else {
+            return Err(mudu_error!(
+                ErrorCode::PermissionDenied,
+                "connection is already authenticated"
+            ));
+        };
+        let Some((credential, role)) = credential else {
+            debug!(conn_id, user = %user, "authentication of unknown user rejected");
+            return Err(denied());
+        };
+        if nonce != expected_nonce || !credential.verify_client_proof(&auth_message, proof)? {
+            debug!(conn_id, user = %user, "authentication rejected");
+            return Err(denied());
+        }
+        let server_signature = credential.server_signature(&auth_message)?;
+        self.set_state(conn_id, ConnAuthState::Authenticated { role });
+        debug!(conn_id, user = %user, "connection authenticated");
+        Ok(AuthResponse::ServerFinal { server_signature })
+    }
+
+    /// Reject requests of a connection that has not authenticated yet.
+    pub fn check_authorized(&self, conn_id: u64, message_type: MessageType) -> RS<()> {
+        if !self.required()
+            || matches!(message_type, MessageType::Handshake | MessageType::Auth)
+            || self.role(conn_id).is_some()
+        {
+            return Ok(());
+        }
+        Err(mudu_error!(
+            ErrorCode::PermissionDenied,
+            format!(
+                "connection must authenticate before sending {:?}",
+                message_type
+            )
+        ))
+    }
+
+    /// Whether sessions opened by `conn_id` get admin privileges. Without
+    /// authentication every connection keeps the historical admin sessions.
+    pub fn is_admin(&self, conn_id: u64) -> bool {
+        !self.required() || self.role(conn_id).is_some_and(|role| role.is_admin())
+    }
+
+    /// Drop the state of a closed connection.
+    pub fn forget(&self, conn_id: u64) {
+        let _ = self.conns.remove_sync(&conn_id);
+    }
+
+    fn set_state(&self, conn_id: u64, state: ConnAuthState) {
+        match self.conns.entry_sync(conn_id) {
+            scc::hash_map::Entry::Occupied(mut occupied) => {
+                *occupied.get_mut() = state;
+            }
+            scc::hash_map::Entry::Vacant(slot) => {
+                slot.insert_entry(state);
+            }
+        }
+    }
+
+    fn role(&self, conn_id: u64) -> Option<UserRole> {
+        self.conns
+            .read_sync(&conn_id, |_, state| match state {
+                ConnAuthState::Authenticated { role } => Some(*role),
+                ConnAuthState::ChallengeSent { .. } => None,
+            })
+            .flatten()
+    }
+}
@@ -0,0 +1,204 @@
+#![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+//! Tests for [`super::auth::ServerAuth`], driven by the contract-side
+//! [`ScramClient`].
+
+use mudu::error::ErrorCode;
+use mudu_contract::protocol::scram::{ScramClient, ScramCredential, DEFAULT_SCRAM_ITERATIONS};
+use mudu_contract::protocol::{AuthRequest, AuthResponse, MessageType};
+
+use crate::contract::user::{UserDesc, UserRole};
+use crate::server::auth::{AuthCfg, ServerAuth};
+
+fn required_auth() -> ServerAuth {
+    ServerAuth::new(Some(AuthCfg::new(
+        "admin".to_string(),
+        "secret".to_string(),
+    )))
+}
+
+fn user(name: &str, role: UserRole, password: &str) -> UserDesc {
+    UserDesc::new(
+        name.to_string(),
+        role,
+        ScramCredential::generate(password, DEFAULT_SCRAM_ITERATIONS).unwrap(),
+    )
+}
+
+/// Run the full exchange for `conn_id`; `desc` is what the catalog holds
+/// for `name`.
+fn login(
+    auth: &ServerAuth,
+    conn_id: u64,
+    name: &str,
+    password: &str,
+    desc: Option<UserDesc>,
+) -> mudu::common::result::RS<()> {
+    let mut client = ScramClient::new(name, password);
+    let AuthRequest::ClientFirst { user, client_nonce } = client.client_first() else {
+        panic!("expected client-first");
+    };
+    let server_first = auth.server_first(conn_id, user, client_nonce, desc)?;
+    let AuthRequest::ClientFinal { nonce, proof } = client.client_final(server_first)? else {
+        panic!("expected client-final");
+    };
+    let server_final = auth.server_final(conn_id, &nonce, &proof)?;
+    client.verify_server_final(server_final)
+}
+
+#[test]
+fn unauthenticated_connection_is_limited_to_handshake_and_auth() {
+    let auth = required_auth();
+    assert!(auth.check_authorized(1, MessageType::Handshake).is_ok());
+    assert!(auth.check_authorized(1, MessageType::Auth).is_ok());
+    let err = auth.check_authorized(1, MessageType::Query).unwrap_err();
+    assert_eq!(err.ec(), ErrorCode::PermissionDenied);
+    assert!(!auth.is_admin(1));
+
+    login(
+        &auth,
+        1,
+        "admin",
+        "secret",
+        Some(user("admin", UserRole::Admin, "secret")),
+    )
+    .unwrap();
+    assert!(auth.check_authorized(1, MessageType::Query).is_ok());
+    assert!(auth.is_admin(1));
+    // Other connections stay locked.
+    assert!(auth.check_authorized(2, MessageType::Query).is_err());
+
+    auth.forget(1);
+    assert!(auth.check_authorized(1, MessageType::Query).is_err());
+}
+
+#[test]
+fn regular_user_sessions_are_not_admin() {
+    let auth = required_auth();
+    login(
+        &auth,
+        3,
+        "bob",
+        "pw",
+        Some(user("bob", UserRole::User, "pw")),
+    )
+    .unwrap();
+    assert!(auth.check_authorized(3, MessageType::Execute).is_ok());
+    assert!(!auth.is_admin(3));
+}
+
+#[test]
+fn wrong_password_and_unknown_user_are_rejected_alike() {
+    let auth = required_auth();
+    let err = login(
+        &auth,
+        4,
+        "bob",
+        "nope",
+        Some(user("bob", UserRole::User, "pw")),
+    )
+    .unwrap_err();
+    assert_eq!(err.ec(), ErrorCode::PermissionDenied);
+    assert!(auth.check_authorized(4, MessageType::Query).is_err());
+
+    let err = login(&auth, 5, "ghost", "pw", None).unwrap_err();
+    assert_eq!(err.ec(), ErrorCode::PermissionDenied);
+    assert!(auth.check_authorized(5, MessageType::Query).is_err());
+}
+
+#[test]
+fn unknown_user_gets_a_stable_salt() {
+    let auth = required_auth();
+    let salt_of = |conn_id: u64| {
+        let client = ScramClient::new("ghost", "pw");
+        let AuthRequest::ClientFirst { user, client_nonce } = client.client_first() else {
+            panic!("expected client-first");
+        };
+        match auth
+            .server_first(conn_id, user, client_nonce, None)
+            .unwrap()
+        {
+            AuthResponse::ServerFirst { salt, .. } => salt,
+            AuthResponse::ServerFinal { .. } => panic!("expected server-first"),
+        }
+    };
+    assert_eq!(salt_of(6), salt_of(7));
+}
+
+#[test]
+fn client_final_without_challenge_is_rejected() {
+    let auth = required_auth();
+    let err = auth.server_final(8, "nonce", &[0; 32]).unwrap_err();
+    assert_eq!(err.ec(), ErrorCode::PermissionDenied);
+}
+
+#[test]
+fn repeated_client_final_keeps_the_connection_authenticated() {
+    let auth = required_auth();
+    let mut client = ScramClient::new("admin", "secret");
+    let AuthRequest::ClientFirst {
+        user: name,
+        client_nonce,
+    } = client.client_first()
+    else {
+        panic!("expected client-first");
+    };
+    let server_first = auth
+        .server_first(
+            10,
+            name,
+            client_nonce,
+            Some(user("admin", UserRole::Admin, "secret")),
+        )
+        .unwrap();
+    let AuthRequest::ClientFinal { nonce, proof } = client.client_final(server_first).unwrap()
+    else {
+        panic!("expected client-final");
+    };
+    let server_final = auth.server_final(10, &nonce, &proof).unwrap();
+    client.verify_server_final(server_final).unwrap();
+
+    let err = auth.server_final(10, &nonce, &proof).unwrap_err();
+    assert_eq!(err.ec(), ErrorCode::PermissionDenied);
+    assert!(auth.check_authorized(10, MessageType::Query).is_ok());
+    assert!(auth.is_admin(10));
+}
+
+#[test]
+fn rejected_proof_keeps_the_pending_challenge() {
+    let auth = required_auth();
+    let mut client = ScramClient::new("admin", "secret");
+    let AuthRequest::ClientFirst {
+        user: name,
+        client_nonce,
+    } = client.client_first()
+    else {
+        panic!("expected client-first");
+    };
+    let server_first = auth
+        .server_first(
+            11,
+            name,
+            client_nonce,
+            Some(user("admin", UserRole::Admin, "secret")),
+        )
+        .unwrap();
+    let AuthRequest::ClientFinal { nonce, proof } = client.client_final(server_first).unwrap()
+    else {
+        panic!("expected client-final");
+    };
+    let err = auth.server_final(11, &nonce, &[0; 32]).unwrap_err();
+    assert_eq!(err.ec(), ErrorCode::PermissionDenied);
+    assert!(auth.check_authorized(11, MessageType::Query).is_err());
+
+    // The challenge survived the bad proof, so the real one still verifies.
+    auth.server_final(11, &nonce, &proof).unwrap();
+    assert!(auth.check_authorized(11, MessageType::Query).is_ok());
+}
+
+#[test]
+fn disabled_auth_allows_everything() {
+    let auth = ServerAuth::disabled();
+    assert!(!auth.required());
+    assert!(auth.check_authorized(9, MessageType::Query).is_ok());
+    assert!(auth.is_admin(9));
+}
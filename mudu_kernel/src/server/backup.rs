@@ -0,0 +1,402 @@
+//! Online backup and point-in-time restore.
+//!
+//! A backup is taken while the server keeps serving. [`backup_async`]
+//! registers a job that every worker picks up in its next dirty-page flush
+//! round (see `WorkerXContract::backup_if_requested`): the worker takes a
+//! checkpoint, copies the data files and PL WALs of the relations it hosts,
+//! then copies its worker log. Once every worker reported, the remaining
+//! files of the data directory (catalogs, fs objects, registry markers) are
+//! copied and the manifest [`BACKUP_MANIFEST_FILE`] is written last; a
+//! backup directory without a manifest is incomplete.
+//!
+//! Opening a restored directory is crash recovery: the relation PL WALs
+//! repair the data files, then every worker log entry after the worker
+//! checkpoint is replayed. [`restore_backup_sync`] additionally brings in
+//! the worker log chunks archived after the backup and can cut the logs at
+//! a target LSN or commit time.
+//!
+//! Schema changes while a backup runs are not supported: the catalogs are
+//! copied after the workers finished and may not match the copied
+//! relations.
+
+use crate::wal::worker_log::{copy_file_async, WorkerLogBackup, WorkerLogLayout};
+use crate::wal::xl_batch::XLBatch;
+use mudu::common::id::OID;
+use mudu::common::result::RS;
+use mudu::error::{ErrorCode, MuduError};
+use mudu::mudu_error;
+use mudu_sys::default_sys_io_context;
+use mudu_sys::fs::sync as sync_fs;
+use mudu_sys::sync::async_::ANotify;
+use mudu_sys::sync::SMutex;
+use mudu_sys::time::system_time_now;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::UNIX_EPOCH;
+use tracing::info;
+
+/// Manifest of a finished backup, written at the backup root.
+pub const BACKUP_MANIFEST_FILE: &str = "backup.json";
+
+/// Directory of a backup holding the worker logs when the server keeps
+/// them outside of its data directory.
+const BACKUP_WORKER_LOG_DIR: &str = "worker_log";
+
+/// Data directory entries the workers copy themselves; the final sweep
+/// leaves them out.
+const WORKER_COPIED_DIRS: [&str; 2] = ["relation", "relation_wal"];
+
+/// Worker log chunk size assumed by restore. Restore only reads and cuts
+/// existing chunks, so the value does not have to match the server's.
+const RESTORE_LOG_CHUNK_SIZE: u64 = 64 * 1024 * 1024;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    /// Wall-clock time the last worker finished copying, in microseconds
+    /// since the Unix epoch. A restore cannot target an earlier time.
+    pub finished_at_unix_micros: u64,
+    /// Directory of the worker logs relative to the backup root; empty when
+    /// they sit with the data files.
+    pub worker_log_dir: String,
+    pub workers: Vec<WorkerLogBackup>,
+}
+
+/// Where a point-in-time restore cuts the worker logs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RestoreTarget {
+    /// Keep the entries up to this LSN in every worker log.
+    Lsn(u64),
+    /// Keep the entries committed at or before this time, in microseconds
+    /// since the Unix epoch.
+    UnixMicros(u64),
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RestoreReport {
+    /// Archived worker log chunks copied in after the backup.
+    pub archived_chunks: usize,
+    /// Worker logs cut at the restore target.
+    pub truncated_logs: usize,
+}
+
+pub(crate) struct BackupJob {
+    target: PathBuf,
+    log_target: PathBuf,
+    state: SMutex<BackupJobState>,
+    done: ANotify,
+}
+
+struct BackupJobState {
+    unclaimed: BTreeSet<OID>,
+    unfinished: usize,
+    logs: Vec<WorkerLogBackup>,
+    first_err: Option<MuduError>,
+}
+
+impl BackupJob {
+    /// Root of the backup; relation files go to their paths relative to the
+    /// data directory under it.
+    pub(crate) fn target(&self) -> &Path {
+        &self.target
+    }
+
+    /// Directory the worker logs are copied to.
+    pub(crate) fn log_target(&self) -> &Path {
+        &self.log_target
+    }
+
+    /// Records the outcome of one worker; the last report wakes
+    /// [`backup_async`].
+    pub(crate) fn report(&self, result: RS<Option<WorkerLogBackup>>) -> RS<()> {
+        let finished = {
+            let mut state = self.state.lock()?;
+            match result {
+                Ok(Some(log)) => state.logs.push(log),
+                Ok(None) => {}
+                Err(err) => {
+                    if state.first_err.is_none() {
+                        state.first_err = Some(err);
+                    }
+                }
+            }
+            state.unfinished = state.unfinished.saturating_sub(1);
+            state.unfinished == 0
+        };
+        if finished {
+            self.done.notify_waiters();
+        }
+        Ok(())
+    }
+}
+
+fn backup_jobs() -> &'static SMutex<HashMap<PathBuf, Arc<BackupJob>>> {
+    static JOBS: OnceLock<SMutex<HashMap<PathBuf, Arc<BackupJob>>>> = OnceLock::new();
+    JOBS.get_or_init(|| SMutex::new(HashMap::new()))
+}
+
+/// The pending backup of the server whose worker logs live in `log_dir`,
+/// if worker `worker_id` has not taken its part yet. The caller must
+/// [`BackupJob::report`] once it is done.
+pub(crate) fn claim_backup(log_dir: &Path, worker_id: OID) -> RS<Option<Arc<BackupJob>>> {
+    let jobs = backup_jobs().lock()?;
+    let Some(job) = jobs.get(log_dir) else {
+        return Ok(None);
+    };
+    if !job.state.lock()?.unclaimed.remove(&worker_id) {
+        return Ok(None);
+    }
+    Ok(Some(job.clone()))
+}
+
+/// Takes an online backup of the server with the given directories and
+/// workers into `target`, which must be missing or empty and outside of
+/// both directories. Returns once the manifest is written.
+pub async fn backup_async(
+    data_dir: &Path,
+    log_dir: &Path,
+    worker_ids: Vec<OID>,
+    target: &Path,
+) -> RS<BackupManifest> {
+    if target.starts_with(data_dir) || target.starts_with(log_dir) {
+        return Err(mudu_error!(
+            ErrorCode::InvalidArgument,
+            format!(
+                "backup directory {} is inside of the server directories",
+                target.display()
+            )
+        ));
+    }
+    if sync_fs::path_exists(target) && !sync_fs::read_dir(target)?.is_empty() {
+        return Err(mudu_error!(
+            ErrorCode::DirectoryNotEmpty,
+            format!("backup directory {} is not empty", target.display())
+        ));
+    }
+    sync_fs::create_dir_all(target)?;
+    let worker_log_dir = if log_dir == data_dir {
+        String::new()
+    } else {
+        BACKUP_WORKER_LOG_DIR.to_string()
+    };
+    let job = Arc::new(BackupJob {
+        target: target.to_path_buf(),
+        log_target: target.join(&worker_log_dir),
+        state: SMutex::new(BackupJobState {
+            unclaimed: worker_ids.iter().copied().collect(),
+            unfinished: worker_ids.len(),
+            logs: Vec::new(),
+            first_err: None,
+        }),
+        done: ANotify::new(),
+    });
+    {
+        let mut jobs = backup_jobs().lock()?;
+        if jobs.contains_key(log_dir) {
+            return Err(mudu_error!(
+                ErrorCode::ResourceBusy,
+                "another backup of this server is in progress"
+            ));
+        }
+        jobs.insert(log_dir.to_path_buf(), job.clone());
+    }
+    info!(target = %target.display(), workers = worker_ids.len(), "backup started");
+    if !worker_ids.is_empty() {
+        job.done.notified().await;
+    }
+    backup_jobs().lock()?.remove(log_dir);
+
+    let (mut logs, first_err) = {
+        let mut state = job.state.lock()?;
+        (std::mem::take(&mut state.logs), state.first_err.take())
+    };
+    if let Some(err) = first_err {
+        return Err(err);
+    }
+    logs.sort_by_key(|log| log.log_oid);
+    let finished_at_unix_micros = unix_micros_now();
+
+    let layouts = logs
+        .iter()
+        .map(|log| WorkerLogLayout::new_inner(log_dir, log.log_oid, RESTORE_LOG_CHUNK_SIZE))
+        .collect::<Vec<_>>();
+    let skip = |path: &Path| layouts.iter().any(|layout| layout.is_log_file(path));
+    sweep_async(data_dir, data_dir, target, &skip, true).await?;
+    if log_dir != data_dir {
+        sweep_async(log_dir, log_dir, job.log_target(), &skip, false).await?;
+    }
+
+    let manifest = BackupManifest {
+        finished_at_unix_micros,
+        worker_log_dir,
+        workers: logs,
+    };
+    let payload = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| mudu_error!(ErrorCode::Encode, "encode backup manifest error", e))?;
+    sync_fs::write(target.join(BACKUP_MANIFEST_FILE), payload)?;
+    info!(target = %target.display(), "backup finished");
+    Ok(manifest)
+}
+
+/// Copies the files under `dir` that are not in the backup yet, keeping
+/// their paths relative to `root`.
+async fn sweep_async(
+    root: &Path,
+    dir: &Path,
+    target: &Path,
+    skip: &dyn Fn(&Path) -> bool,
+    skip_worker_dirs: bool,
+) -> RS<()> {
+    if !sync_fs::path_exists(dir) {
+        return Ok(());
+    }
+    let fs = default_sys_io_context().provider_arc().fs_arc();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for path in sync_fs::read_dir(&dir)? {
+            let relative = path
+                .strip_prefix(root)
+                .map_err(|e| mudu_error!(ErrorCode::Internal, "backup sweep left its root", e))?;
+            if sync_fs::metadata(&path)?.is_dir() {
+                let worker_dir = WORKER_COPIED_DIRS
+                    .iter()
+                    .any(|name| relative == Path::new(name));
+                if !(skip_worker_dirs && worker_dir) {
+                    dirs.push(path);
+                }
+                continue;
+            }
+            let to = target.join(relative);
+            if skip(&path) || sync_fs::path_exists(&to) {
+                continue;
+            }
+            copy_file_async(fs.as_ref(), &path, &to).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Reads the manifest of a finished backup.
+pub fn read_backup_manifest(backup_dir: &Path) -> RS<BackupManifest> {
+    let path = backup_dir.join(BACKUP_MANIFEST_FILE);
+    if !sync_fs::path_exists(&path) {
+        return Err(mudu_error!(
+            ErrorCode::NotFound,
+            format!("{} has no backup manifest", backup_dir.display())
+        ));
+    }
+    let bytes = sync_fs::read(&path)?;
+    serde_json::from_slice(&bytes)
+        .map_err(|e| mudu_error!(ErrorCode::Decode, "decode backup manifest error", e))
+}
+
+/// Restores the backup in `backup_dir` into `data_dir`, which must be
+/// missing or empty. The worker logs go to the directory the manifest
+/// names relative to `data_dir`.
+///
+/// With `archive_dir`, the worker log chunks archived after the backup are
+/// copied in, so the restored server replays the transactions committed
+/// after the backup too. With `until`, every worker log is then cut before
+/// the first entry past the target. A target before the end of the backup
+/// is rejected: the copied relation files may already hold later writes.
+pub fn restore_backup_sync(
+    backup_dir: &Path,
+    data_dir: &Path,
+    archive_dir: Option<&Path>,
+    until: Option<RestoreTarget>,
+) -> RS<RestoreReport> {
+    let manifest = read_backup_manifest(backup_dir)?;
+    match until {
+        Some(RestoreTarget::UnixMicros(micros)) if micros < manifest.finished_at_unix_micros => {
+            return Err(mudu_error!(
+                ErrorCode::InvalidArgument,
+                format!(
+                    "restore target {} is before the end of the backup at {}",
+                    micros, manifest.finished_at_unix_micros
+                )
+            ));
+        }
+        Some(RestoreTarget::Lsn(lsn)) => {
+            if let Some(log) = manifest.workers.iter().find(|log| lsn < log.end_lsn) {
+                return Err(mudu_error!(
+                    ErrorCode::InvalidArgument,
+                    format!(
+                        "restore target LSN {} is before the end {} of worker log {}",
+                        lsn, log.end_lsn, log.log_oid
+                    )
+                ));
+            }
+        }
+        _ => {}
+    }
+    if sync_fs::path_exists(data_dir) && !sync_fs::read_dir(data_dir)?.is_empty() {
+        return Err(mudu_error!(
+            ErrorCode::DirectoryNotEmpty,
+            format!("restore directory {} is not empty", data_dir.display())
+        ));
+    }
+    copy_tree_sync(backup_dir, backup_dir, data_dir)?;
+
+    let log_dir = data_dir.join(&manifest.worker_log_dir);
+    let mut report = RestoreReport::default();
+    for log in &manifest.workers {
+        let layout = WorkerLogLayout::new(&log_dir, log.log_oid, RESTORE_LOG_CHUNK_SIZE)?;
+        if let Some(archive_dir) = archive_dir {
+            let from_sequence = layout
+                .chunk_paths_in_sync(&log_dir)?
+                .first()
+                .map(|(sequence, _)| *sequence)
+                .unwrap_or(0);
+            report.archived_chunks +=
+                layout.restore_archived_chunks_sync(archive_dir, from_sequence)?;
+        }
+        let cut = match until {
+            None => None,
+            Some(RestoreTarget::Lsn(lsn)) => {
+                layout.truncate_log_sync::<XLBatch, _>(lsn, |_, _| true)?
+            }
+            Some(RestoreTarget::UnixMicros(micros)) => {
+                layout.truncate_log_sync::<XLBatch, _>(log.end_lsn, |_, batch| {
+                    batch
+                        .entries
+                        .iter()
+                        .filter_map(|entry| entry.commit_time())
+                        .any(|commit_time| commit_time > micros)
+                })?
+            }
+        };
+        if cut.is_some() {
+            report.truncated_logs += 1;
+        }
+    }
+    Ok(report)
+}
+
+fn copy_tree_sync(root: &Path, dir: &Path, target: &Path) -> RS<()> {
+    sync_fs::create_dir_all(target)?;
+    for path in sync_fs::read_dir(dir)? {
+        let relative = path
+            .strip_prefix(root)
+            .map_err(|e| mudu_error!(ErrorCode::Internal, "restore copy left its root", e))?;
+        if dir == root && relative == Path::new(BACKUP_MANIFEST_FILE) {
+            continue;
+        }
+        let to = target.join(relative);
+        if sync_fs::metadata(&path)?.is_dir() {
+            sync_fs::create_dir_all(&to)?;
+            copy_tree_sync(root, &path, target)?;
+        } else {
+            sync_fs::copy(&path, &to)?;
+        }
+    }
+    Ok(())
+}
+
+fn unix_micros_now() -> u64 {
+    system_time_now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_micros()
+        .min(u64::MAX as u128) as u64
+}
@@ -0,0 +1,239 @@
+#![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+//! Tests for [`super::backup`]: the backup job handshake with the workers,
+//! the final sweep and the offline restore.
+
+use std::path::{Path, PathBuf};
+
+use mudu::error::ErrorCode;
+use mudu_sys::env_var::temp_dir;
+use mudu_sys::fs::sync as sync_fs;
+use mudu_utils::oid::gen_oid;
+
+use crate::server::backup::{
+    backup_async, claim_backup, read_backup_manifest, restore_backup_sync, RestoreTarget,
+    BACKUP_MANIFEST_FILE,
+};
+use crate::wal::lsn::LSN;
+use crate::wal::worker_log::{
+    decode_frames, ChunkedWorkerLogBackend, WorkerLogBackup, WorkerLogLayout,
+};
+use crate::wal::xl_batch::{append_xl_batch_async, decode_xl_batches, XLBatch};
+use crate::wal::xl_entry::{TxOp, XLEntry};
+
+fn test_dir(name: &str) -> PathBuf {
+    temp_dir().join(format!("backup_test_{}_{}", name, gen_oid()))
+}
+
+fn committed_at(xid: u64, unix_micros: u64) -> XLBatch {
+    XLBatch::new(vec![XLEntry {
+        xid,
+        ops: vec![TxOp::Begin, TxOp::Commit, TxOp::CommitTime { unix_micros }],
+    }])
+}
+
+fn write_file(path: &Path, data: &[u8]) {
+    sync_fs::create_dir_all(path.parent().unwrap()).unwrap();
+    sync_fs::write(path, data).unwrap();
+}
+
+#[test]
+fn backup_waits_for_workers_and_sweeps_the_rest() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let data_dir = test_dir("data");
+        let target = test_dir("target");
+        write_file(&data_dir.join("meta").join("catalog.dat"), b"catalog");
+        write_file(&data_dir.join("relation").join("1.2.0.dat"), b"stale");
+        let worker_id = gen_oid();
+        let log_backup = WorkerLogBackup {
+            log_oid: worker_id,
+            checkpoint_lsn: None,
+            end_lsn: 7,
+        };
+        let worker = async {
+            loop {
+                if let Some(job) = claim_backup(&data_dir, worker_id).unwrap() {
+                    assert_eq!(job.target(), target.as_path());
+                    // Every worker takes its part once.
+                    assert!(claim_backup(&data_dir, worker_id).unwrap().is_none());
+                    job.report(Ok(Some(log_backup))).unwrap();
+                    break;
+                }
+                crate::common::yield_now::cooperative_yield_now().await;
+            }
+        };
+        let (manifest, ()) = futures::join!(
+            backup_async(&data_dir, &data_dir, vec![worker_id], &target),
+            worker
+        );
+        let manifest = manifest.unwrap();
+        assert_eq!(manifest.workers, vec![log_backup]);
+        assert_eq!(manifest.worker_log_dir, "");
+        assert_eq!(read_backup_manifest(&target).unwrap(), manifest);
+        assert_eq!(
+            sync_fs::read(target.join("meta").join("catalog.dat")).unwrap(),
+            b"catalog"
+        );
+        // Relation files are copied by the workers, never by the sweep.
+        assert!(!sync_fs::path_exists(target.join("relation")));
+        // The job is gone once the backup finished.
+        assert!(claim_backup(&data_dir, worker_id).unwrap().is_none());
+    })
+    .unwrap()
+}
+
+#[test]
+fn backup_reports_the_first_worker_error() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let data_dir = test_dir("data");
+        let target = test_dir("target");
+        let worker_id = gen_oid();
+        let worker = async {
+            loop {
+                if let Some(job) = claim_backup(&data_dir, worker_id).unwrap() {
+                    job.report(Err(mudu::mudu_error!(ErrorCode::Storage, "disk gone")))
+                        .unwrap();
+                    break;
+                }
+                crate::common::yield_now::cooperative_yield_now().await;
+            }
+        };
+        let (result, ()) = futures::join!(
+            backup_async(&data_dir, &data_dir, vec![worker_id], &target),
+            worker
+        );
+        assert_eq!(result.unwrap_err().ec(), ErrorCode::Storage);
+        assert!(!sync_fs::path_exists(target.join(BACKUP_MANIFEST_FILE)));
+    })
+    .unwrap()
+}
+
+#[test]
+fn backup_rejects_unusable_targets() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let data_dir = test_dir("data");
+        let inside = backup_async(&data_dir, &data_dir, Vec::new(), &data_dir.join("backup"))
+            .await
+            .unwrap_err();
+        assert_eq!(inside.ec(), ErrorCode::InvalidArgument);
+
+        let target = test_dir("target");
+        write_file(&target.join("leftover"), b"x");
+        let not_empty = backup_async(&data_dir, &data_dir, Vec::new(), &target)
+            .await
+            .unwrap_err();
+        assert_eq!(not_empty.ec(), ErrorCode::DirectoryNotEmpty);
+    })
+    .unwrap()
+}
+
+/// Writes a backup holding one worker log with an entry per commit time,
+/// and returns the backup directory and the log id.
+async fn backup_with_commits(commit_times: &[u64], end_lsn: u64) -> (PathBuf, u128) {
+    let backup_dir = test_dir("backup");
+    let log_oid = gen_oid();
+    let layout = WorkerLogLayout::new(&backup_dir, log_oid, 4096).unwrap();
+    let log = ChunkedWorkerLogBackend::new(layout).await.unwrap();
+    for (xid, unix_micros) in commit_times.iter().enumerate() {
+        append_xl_batch_async(&log, &committed_at(xid as u64 + 1, *unix_micros))
+            .await
+            .unwrap();
+    }
+    log.flush_async().await.unwrap();
+    drop(log);
+    write_file(&backup_dir.join("meta").join("catalog.dat"), b"catalog");
+    let manifest = serde_json::json!({
+        "finished_at_unix_micros": 100,
+        "worker_log_dir": "",
+        "workers": [{"log_oid": log_oid.to_string(), "checkpoint_lsn": null, "end_lsn": end_lsn}],
+    });
+    write_file(
+        &backup_dir.join(BACKUP_MANIFEST_FILE),
+        manifest.to_string().as_bytes(),
+    );
+    (backup_dir, log_oid)
+}
+
+fn restored_commit_times(data_dir: &Path, log_oid: u128) -> Vec<u64> {
+    let layout = WorkerLogLayout::new(data_dir, log_oid, 4096).unwrap();
+    let frames = decode_frames(&sync_fs::read(layout.chunk_path(0)).unwrap()).unwrap();
+    decode_xl_batches(&frames)
+        .unwrap()
+        .iter()
+        .flat_map(|batch| batch.entries.iter().filter_map(XLEntry::commit_time))
+        .collect()
+}
+
+#[test]
+fn restore_copies_the_backup_without_its_manifest() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let (backup_dir, log_oid) = backup_with_commits(&[90, 150, 250], 0).await;
+        let data_dir = test_dir("restored");
+        let report = restore_backup_sync(&backup_dir, &data_dir, None, None).unwrap();
+        assert_eq!(report.truncated_logs, 0);
+        assert_eq!(
+            sync_fs::read(data_dir.join("meta").join("catalog.dat")).unwrap(),
+            b"catalog"
+        );
+        assert!(!sync_fs::path_exists(data_dir.join(BACKUP_MANIFEST_FILE)));
+        assert_eq!(
+            restored_commit_times(&data_dir, log_oid),
+            vec![90, 150, 250]
+        );
+
+        // A second restore into the now populated directory is refused.
+        let again = restore_backup_sync(&backup_dir, &data_dir, None, None).unwrap_err();
+        assert_eq!(again.ec(), ErrorCode::DirectoryNotEmpty);
+    })
+    .unwrap()
+}
+
+#[test]
+fn restore_stops_at_the_target_time() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let (backup_dir, log_oid) = backup_with_commits(&[90, 150, 250], 0).await;
+        let data_dir = test_dir("restored");
+        let report = restore_backup_sync(
+            &backup_dir,
+            &data_dir,
+            None,
+            Some(RestoreTarget::UnixMicros(200)),
+        )
+        .unwrap();
+        assert_eq!(report.truncated_logs, 1);
+        assert_eq!(restored_commit_times(&data_dir, log_oid), vec![90, 150]);
+
+        // The backup may already hold writes committed up to its end.
+        let too_early = restore_backup_sync(
+            &backup_dir,
+            &test_dir("restored"),
+            None,
+            Some(RestoreTarget::UnixMicros(99)),
+        )
+        .unwrap_err();
+        assert_eq!(too_early.ec(), ErrorCode::InvalidArgument);
+    })
+    .unwrap()
+}
+
+#[test]
+fn restore_stops_at_the_target_lsn() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let (backup_dir, log_oid) = backup_with_commits(&[90, 150, 250], 1).await;
+        let data_dir = test_dir("restored");
+        restore_backup_sync(&backup_dir, &data_dir, None, Some(RestoreTarget::Lsn(1))).unwrap();
+        assert_eq!(restored_commit_times(&data_dir, log_oid), vec![90, 150]);
+        let layout = WorkerLogLayout::new(&data_dir, log_oid, 4096).unwrap();
+        let reopened = ChunkedWorkerLogBackend::new(layout).await.unwrap();
+        assert_eq!(reopened.last_allocated_lsn(), LSN::new(1));
+
+        let too_early = restore_backup_sync(
+            &backup_dir,
+            &test_dir("restored"),
+            None,
+            Some(RestoreTarget::Lsn(0)),
+        )
+        .unwrap_err();
+        assert_eq!(too_early.ec(), ErrorCode::InvalidArgument);
+    })
+    .unwrap()
+}
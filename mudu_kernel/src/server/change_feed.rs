@@ -0,0 +1,510 @@
+//! Change data capture: the committed row changes of a worker log, decoded
+//! into column values.
+//!
+//! A [`ChangeFeed`] reads a worker log through the same [`LogTail`] standby
+//! streams use and turns the `TxOp::Write` records of committed transactions
+//! into [`ChangeEvent`]s, in log order. A transaction counts as committed by
+//! the entry that commits it: a local `Commit`, a coordinator's
+//! `CommitDecision`, or a participant's committing `Resolve`, which carries
+//! the writes of its earlier `Prepare`. A `Prepare` a checkpoint reclaimed
+//! is read from the checkpoint; when neither has it, the read fails so the
+//! subscriber resyncs instead of missing the commit. Aborted and
+//! still-prepared writes, worker-local KV rows and rows of dropped tables
+//! are left out.
+//!
+//! The feed is stateless on the server: every response carries the LSN of
+//! the last log frame it covered, and a subscriber resumes by sending it
+//! back. The log does not tell an INSERT from an UPDATE statement, both log
+//! the full row image, so [`ChangeKind::Insert`] is an upsert by key; only
+//! deferred column deltas come as [`ChangeKind::Update`]. Rows are decoded
+//! with the table's current layout: a row or delta logged under a layout
+//! ALTER TABLE retired since is upgraded from the layout stamped on it, the
+//! way replay does. Only fsynced frames are read, so the feed never reports
+//! a change a crash can still lose.
+
+use crate::contract::field_info::FieldInfo;
+use crate::contract::meta_mgr::MetaMgr;
+use crate::contract::table_desc::TableDesc;
+use crate::server::replication::LogTail;
+use crate::server::x_contract::checkpoint::WorkerLogCheckpoint;
+use crate::server::x_contract::utils::{decode_delta_assigns, decode_sub_wrap_literal};
+use crate::storage::relation::layout_upgrade::{retired_layout, upgrade_deltas, LayoutUpgrade};
+use crate::wal::log_frame::frame_lsn;
+use crate::wal::lsn::LSN;
+use crate::wal::worker_log::{decode_frames, WorkerLogLayout};
+use crate::wal::xl_batch::{decode_xl_batches_with_pending, XLBatch};
+use crate::wal::xl_data_op::XLWrite;
+use crate::wal::xl_entry::{TxOp, XLEntry};
+use crate::x_engine::api::{DeltaAssign, DeltaOp};
+use mudu::common::id::OID;
+use mudu::common::result::RS;
+use mudu::error::ErrorCode;
+use mudu::mudu_error;
+use mudu_contract::protocol::change_feed::{
+    ChangeDelta, ChangeDeltaOp, ChangeEvent, ChangeFeedRequest, ChangeFeedResponse, ChangeKind,
+};
+use mudu_contract::tuple::datum_desc::DatumDesc;
+use mudu_contract::tuple::nullable_tuple::{read_value, NullableValue};
+use mudu_contract::tuple::tuple_binary_desc::TupleBinaryDesc;
+use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
+use mudu_contract::tuple::tuple_value::TupleValue;
+use mudu_sys::contract::async_fs::AsyncFs;
+use mudu_sys::default_sys_io_context;
+use mudu_sys::time::instant_now;
+use mudu_type::data_value::DataValue;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Most events one response carries; a transaction is never split, so the
+/// last one may push a response past it.
+pub const MAX_CHANGE_EVENTS: u32 = 10_000;
+
+/// Longest a request waits for a change before it returns empty.
+pub const MAX_CHANGE_WAIT: Duration = Duration::from_secs(30);
+
+/// How often a waiting request looks for new frames.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Reads the committed row changes of the worker logs of one server.
+pub struct ChangeFeed {
+    log_dir: PathBuf,
+    chunk_size: u64,
+    archive_dir: Option<PathBuf>,
+    worker_ids: Vec<OID>,
+    meta_mgr: Arc<dyn MetaMgr>,
+}
+
+impl ChangeFeed {
+    pub fn new(
+        log_dir: PathBuf,
+        chunk_size: u64,
+        archive_dir: Option<PathBuf>,
+        worker_ids: Vec<OID>,
+        meta_mgr: Arc<dyn MetaMgr>,
+    ) -> Self {
+        Self {
+            log_dir,
+            chunk_size,
+            archive_dir,
+            worker_ids,
+            meta_mgr,
+        }
+    }
+
+    /// Returns the changes committed after `request.after_lsn()`, waiting
+    /// up to `request.wait_ms()` for one when there is none yet.
+    pub async fn read_async(&self, request: &ChangeFeedRequest) -> RS<ChangeFeedResponse> {
+        let log_oid = request.worker_id();
+        if !self.worker_ids.contains(&log_oid) {
+            return Err(mudu_error!(
+                ErrorCode::EntityNotFound,
+                format!("unknown worker log {}", log_oid)
+            ));
+        }
+        let layout = WorkerLogLayout::new(&self.log_dir, log_oid, self.chunk_size)?
+            .with_archive_dir(self.archive_dir.clone());
+        let mut reader = ChangeReader {
+            meta_mgr: self.meta_mgr.as_ref(),
+            layout,
+            fs: default_sys_io_context().provider_arc().fs_arc(),
+            after_lsn: request.after_lsn(),
+            max_events: request.max_events().clamp(1, MAX_CHANGE_EVENTS) as usize,
+            tail: None,
+            pending: Vec::new(),
+            pending_start_lsn: None,
+            prepared: HashMap::new(),
+            tables: HashMap::new(),
+            events: Vec::new(),
+            last_lsn: request.after_lsn(),
+        };
+        let deadline =
+            instant_now() + Duration::from_millis(request.wait_ms()).min(MAX_CHANGE_WAIT);
+        loop {
+            reader.read().await?;
+            let now = instant_now();
+            if !reader.events.is_empty() || now >= deadline {
+                break;
+            }
+            mudu_sys::task::async_::sleep(
+                POLL_INTERVAL.min(deadline.saturating_duration_since(now)),
+            )
+            .await;
+        }
+        Ok(ChangeFeedResponse::new(reader.events, reader.last_lsn))
+    }
+}
+
+/// The state of one request.
+struct ChangeReader<'a> {
+    meta_mgr: &'a dyn MetaMgr,
+    layout: WorkerLogLayout,
+    fs: Arc<dyn AsyncFs>,
+    after_lsn: Option<u64>,
+    max_events: usize,
+    /// `None` until the first frame to read is known.
+    tail: Option<LogTail>,
+    pending: Vec<Vec<u8>>,
+    pending_start_lsn: Option<LSN>,
+    /// Writes of the prepared transactions read so far, by coordinator and
+    /// xid.
+    prepared: HashMap<(OID, u64), Vec<XLWrite>>,
+    /// Tables by id; `None` for a dropped one.
+    tables: HashMap<OID, Option<Arc<TableDesc>>>,
+    events: Vec<ChangeEvent>,
+    last_lsn: Option<u64>,
+}
+
+impl ChangeReader<'_> {
+    /// Reads the frames fsynced so far, until the events are full.
+    async fn read(&mut self) -> RS<()> {
+        if self.tail.is_none() {
+            let start = match self.after_lsn {
+                Some(lsn) => lsn + 1,
+                None => match self.log_tail(0).oldest_lsn().await? {
+                    Some(oldest) => oldest,
+                    None => return Ok(()),
+                },
+            };
+            self.tail = Some(self.log_tail(start));
+        }
+        while self.events.len() < self.max_events {
+            let Some(tail) = self.tail.as_mut() else {
+                return Ok(());
+            };
+            let payload = tail.read_next().await?;
+            if payload.is_empty() {
+                return Ok(());
+            }
+            for frame in decode_frames(&payload)? {
+                let lsn = frame_lsn(&frame)?.as_u64();
+                for batch in decode_xl_batches_with_pending(
+                    std::slice::from_ref(&frame),
+                    &mut self.pending,
+                    &mut self.pending_start_lsn,
+                )? {
+                    self.apply_batch(batch, lsn).await?;
+                }
+                if self.pending.is_empty() && self.events.len() >= self.max_events {
+                    // The tail read past this frame; the next request
+                    // starts over from `last_lsn`.
+                    return Ok(());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn log_tail(&self, expected: u64) -> LogTail {
+        LogTail::new(self.layout.clone(), self.fs.clone(), expected)
+    }
+
+    /// Emits the committed writes of a batch whose last frame is `lsn`.
+    async fn apply_batch(&mut self, batch: XLBatch, lsn: u64) -> RS<()> {
+        for entry in batch.entries {
+            let xid = entry.xid;
+            let commit_time = entry.commit_time();
+            let writes = match two_phase_marker(&entry).cloned() {
+                Some(TxOp::Prepare {
+                    coordinator_worker_id,
+                }) => {
+                    self.prepared
+                        .insert((coordinator_worker_id, xid), entry_writes(entry));
+                    continue;
+                }
+                Some(TxOp::Resolve {
+                    coordinator_worker_id,
+                    commit,
+                }) => {
+                    let key = (coordinator_worker_id, xid);
+                    let prepared = self.prepared.remove(&key);
+                    if !commit {
+                        continue;
+                    }
+                    match prepared {
+                        Some(writes) => writes,
+                        None => match self.find_prepared(key, lsn).await? {
+                            Some(writes) => writes,
+                            None => self.checkpointed_prepared(key).await?.ok_or_else(|| {
+                                mudu_error!(
+                                    ErrorCode::InvalidState,
+                                    format!(
+                                        "prepared writes of committed transaction {} of \
+                                         coordinator {} are no longer in worker log {} or \
+                                         its checkpoint; the change feed must resync",
+                                        xid,
+                                        key.0,
+                                        self.layout.log_oid()
+                                    )
+                                )
+                            })?,
+                        },
+                    }
+                }
+                Some(TxOp::CommitDecision { .. }) => entry_writes(entry),
+                Some(_) => continue,
+                None if entry.ops.contains(&TxOp::Commit) => entry_writes(entry),
+                None => continue,
+            };
+            for write in writes {
+                if let Some(event) = self.change_event(lsn, xid, commit_time, write).await? {
+                    self.events.push(event);
+                }
+            }
+        }
+        self.last_lsn = Some(lsn);
+        Ok(())
+    }
+
+    /// Looks for the `Prepare` of a transaction resolved at `before` that
+    /// was logged before the first frame of this request.
+    async fn find_prepared(&self, key: (OID, u64), before: u64) -> RS<Option<Vec<XLWrite>>> {
+        let Some(oldest) = self.log_tail(0).oldest_lsn().await? else {
+            return Ok(None);
+        };
+        let mut tail = self.log_tail(oldest);
+        let mut pending = Vec::new();
+        let mut pending_start_lsn = None;
+        loop {
+            let payload = tail.read_next().await?;
+            if payload.is_empty() {
+                return Ok(None);
+            }
+            for frame in decode_frames(&payload)? {
+                if frame_lsn(&frame)?.as_u64() >= before {
+                    return Ok(None);
+                }
+                for batch in decode_xl_batches_with_pending(
+                    std::slice::from_ref(&frame),
+                    &mut pending,
+                    &mut pending_start_lsn,
+                )? {
+                    for entry in batch.entries {
+                        let prepared_by = match two_phase_marker(&entry) {
+                            Some(TxOp::Prepare {
+                                coordinator_worker_id,
+                            }) => Some(*coordinator_worker_id),
+                            _ => None,
+                        };
+                        if entry.xid == key.1 && prepared_by == Some(key.0) {
+                            return Ok(Some(entry_writes(entry)));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Looks for the `Prepare` of a transaction in the newest checkpoint of
+    /// the log, which records the transactions still prepared when the
+    /// chunks holding their `Prepare` were reclaimed.
+    async fn checkpointed_prepared(&self, key: (OID, u64)) -> RS<Option<Vec<XLWrite>>> {
+        let Some(checkpoint) = self.layout.read_checkpoint_async(self.fs.as_ref()).await? else {
+            return Ok(None);
+        };
+        Ok(WorkerLogCheckpoint::decode(&checkpoint)?
+            .prepared_txs
+            .into_iter()
+            .find(|record| (record.coordinator_worker_id, record.tx_id) == key)
+            .map(|record| record.writes))
+    }
+
+    async fn change_event(
+        &mut self,
+        lsn: u64,
+        xid: u64,
+        commit_time: Option<u64>,
+        write: XLWrite,
+    ) -> RS<Option<ChangeEvent>> {
+        // Table 0 holds the worker-local KV rows.
+        if write.table_id() == 0 {
+            return Ok(None);
+        }
+        let Some(desc) = self.table(write.table_id()).await? else {
+            return Ok(None);
+        };
+        let (kind, partition_id, (columns, values), deltas) = match write {
+            XLWrite::Insert(insert) => {
+                let value = match insert.layout_version {
+                    Some(version) => match retired_layout(&desc, version)? {
+                        Some(retired) => {
+                            LayoutUpgrade::new(retired, &desc)?.upgrade(&insert.value)?
+                        }
+                        None => insert.value,
+                    },
+                    None => insert.value,
+                };
+                (
+                    ChangeKind::Insert,
+                    insert.partition_id,
+                    row_columns(&desc, &insert.key, &value)?,
+                    Vec::new(),
+                )
+            }
+            XLWrite::Update(update) => {
+                let mut assigns = decode_delta_assigns(&update.delta)?;
+                if let Some(layout) = &update.layout {
+                    if let Some(retired) = retired_layout(&desc, layout.version)? {
+                        assigns = upgrade_deltas(retired, &desc, &layout.column_oids, assigns)?;
+                    }
+                }
+                (
+                    ChangeKind::Update,
+                    update.partition_id,
+                    key_columns(&desc, &update.key)?,
+                    assigns
+                        .into_iter()
+                        .map(|assign| change_delta(&desc, assign))
+                        .collect::<RS<Vec<_>>>()?,
+                )
+            }
+            XLWrite::Delete(delete) => (
+                ChangeKind::Delete,
+                delete.partition_id,
+                key_columns(&desc, &delete.key)?,
+                Vec::new(),
+            ),
+        };
+        Ok(Some(ChangeEvent::new(
+            lsn,
+            xid,
+            commit_time,
+            desc.id(),
+            desc.name().clone(),
+            partition_id,
+            kind,
+            TupleFieldDesc::new(columns),
+            TupleValue::from(values),
+            deltas,
+        )))
+    }
+
+    async fn table(&mut self, table_id: OID) -> RS<Option<Arc<TableDesc>>> {
+        if let Some(desc) = self.tables.get(&table_id) {
+            return Ok(desc.clone());
+        }
+        let desc = match self.meta_mgr.get_table_by_id(table_id).await {
+            Ok(desc) => Some(desc),
+            Err(err) if err.ec() == ErrorCode::EntityNotFound => None,
+            Err(err) => return Err(err),
+        };
+        self.tables.insert(table_id, desc.clone());
+        Ok(desc)
+    }
+}
+
+/// The two-phase commit record of an entry, if it has one.
+fn two_phase_marker(entry: &XLEntry) -> Option<&TxOp> {
+    entry.ops.iter().find(|op| {
+        matches!(
+            op,
+            TxOp::Prepare { .. }
+                | TxOp::Resolve { .. }
+                | TxOp::CommitDecision { .. }
+                | TxOp::DecisionDelivered
+        )
+    })
+}
+
+fn entry_writes(entry: XLEntry) -> Vec<XLWrite> {
+    entry
+        .ops
+        .into_iter()
+        .filter_map(|op| match op {
+            TxOp::Write(write) => Some(write),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Every column of a row, in table order.
+fn row_columns(desc: &TableDesc, key: &[u8], value: &[u8]) -> RS<(Vec<DatumDesc>, Vec<DataValue>)> {
+    let mut columns = Vec::with_capacity(desc.fields().len());
+    let mut values = Vec::with_capacity(desc.fields().len());
+    for field in desc.fields() {
+        let (tuple, tuple_desc) = if field.is_primary() {
+            (key, desc.key_desc())
+        } else {
+            (value, desc.value_desc())
+        };
+        values.push(column_value(desc, field, tuple, tuple_desc)?);
+        columns.push(datum_desc(field));
+    }
+    Ok((columns, values))
+}
+
+/// The primary key columns of a row, in key order.
+fn key_columns(desc: &TableDesc, key: &[u8]) -> RS<(Vec<DatumDesc>, Vec<DataValue>)> {
+    let fields = desc.key_info();
+    let mut columns = Vec::with_capacity(fields.len());
+    let mut values = Vec::with_capacity(fields.len());
+    for field in fields {
+        values.push(column_value(desc, field, key, desc.key_desc())?);
+        columns.push(datum_desc(field));
+    }
+    Ok((columns, values))
+}
+
+fn column_value(
+    desc: &TableDesc,
+    field: &FieldInfo,
+    tuple: &[u8],
+    tuple_desc: &TupleBinaryDesc,
+) -> RS<DataValue> {
+    match read_value(&tuple.to_vec(), tuple_desc, field.datum_index()).map_err(|e| {
+        mudu_error!(
+            ErrorCode::Decode,
+            format!(
+                "decode column {} of table {} error",
+                field.name(),
+                desc.name()
+            ),
+            e
+        )
+    })? {
+        NullableValue::Null => Ok(DataValue::null()),
+        NullableValue::Value(value) => Ok(value),
+    }
+}
+
+fn datum_desc(field: &FieldInfo) -> DatumDesc {
+    DatumDesc::new_nullable(
+        field.name().clone(),
+        field.type_desc().clone(),
+        field.nullable(),
+    )
+}
+
+fn change_delta(desc: &TableDesc, assign: DeltaAssign) -> RS<ChangeDelta> {
+    let field = desc.fields().get(assign.attr).ok_or_else(|| {
+        mudu_error!(
+            ErrorCode::Decode,
+            format!(
+                "delta on column {} of table {} with {} columns",
+                assign.attr,
+                desc.name(),
+                desc.fields().len()
+            )
+        )
+    })?;
+    let (op, operand) = match assign.op {
+        DeltaOp::SubWrapDeferred => {
+            let (quantity, floor, wrap) = decode_sub_wrap_literal(&assign.literal)?;
+            (
+                ChangeDeltaOp::SubWrap { floor, wrap },
+                DataValue::from_i64(quantity),
+            )
+        }
+        op => {
+            let data_type = field.type_desc();
+            let (operand, _) = data_type.type_family().fn_recv()(&assign.literal, data_type)
+                .map_err(|e| e.to_m_err())?;
+            match op {
+                DeltaOp::Add | DeltaOp::AddDeferred => (ChangeDeltaOp::Add, operand),
+                _ => (ChangeDeltaOp::Sub, operand),
+            }
+        }
+    };
+    Ok(ChangeDelta::new(datum_desc(field), op, operand))
+}
@@ -0,0 +1,454 @@
+#![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+//! Tests for [`super::change_feed`]: decoding the committed writes of a
+//! worker log into change events and resuming from a returned LSN.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use mudu::common::id::OID;
+use mudu::error::ErrorCode;
+use mudu_contract::protocol::change_feed::{ChangeDeltaOp, ChangeFeedRequest, ChangeKind};
+use mudu_contract::tuple::nullable_tuple::{NullableValue, TupleBuilder};
+use mudu_contract::tuple::tuple_binary_desc::TupleBinaryDesc;
+use mudu_sys::default_sys_io_context;
+use mudu_sys::env_var::temp_dir;
+use mudu_type::data_type::DataType;
+use mudu_type::data_type_function::send_binary;
+use mudu_type::data_type_info::DataTypeInfo;
+use mudu_type::data_value::DataValue;
+use mudu_type::type_family::TypeFamily;
+use mudu_utils::oid::gen_oid;
+
+use crate::contract::meta_mgr::MetaMgr;
+use crate::contract::schema_column::SchemaColumn;
+use crate::contract::schema_table::SchemaTable;
+use crate::contract::table_desc::TableDesc;
+use crate::server::change_feed::ChangeFeed;
+use crate::server::test_meta_mgr::TestMetaMgr;
+use crate::server::x_contract::checkpoint::WorkerLogCheckpoint;
+use crate::server::x_contract::two_phase::PreparedTxRecord;
+use crate::server::x_contract::utils::encode_delta_assigns;
+use crate::wal::lsn::LSN;
+use crate::wal::worker_log::{ChunkedWorkerLogBackend, WalCheckpoint, WorkerLogLayout};
+use crate::wal::xl_batch::{append_xl_batch_async, XLBatch};
+use crate::wal::xl_data_op::{XLDelete, XLDeltaLayout, XLInsert, XLUpdate, XLWrite};
+use crate::wal::xl_entry::{TxOp, XLEntry};
+use crate::x_engine::api::{AlterTable, DeltaAssign, DeltaOp};
+
+const CHUNK_SIZE: u64 = 4096;
+const PARTITION: OID = 7;
+
+fn test_dir() -> PathBuf {
+    temp_dir().join(format!("change_feed_test_{}", gen_oid()))
+}
+
+fn column(name: &str) -> SchemaColumn {
+    SchemaColumn::new(
+        name.to_string(),
+        TypeFamily::I32,
+        DataTypeInfo::from_text(TypeFamily::I32, String::new()),
+    )
+}
+
+fn tuple(desc: &TupleBinaryDesc, v: i32) -> Vec<u8> {
+    TupleBuilder::new(desc)
+        .build(&[NullableValue::Value(DataValue::from_i32(v))])
+        .unwrap()
+}
+
+fn insert(desc: &TableDesc, id: i32, qty: i32) -> TxOp {
+    TxOp::Write(XLWrite::Insert(XLInsert {
+        table_id: desc.id(),
+        partition_id: PARTITION,
+        tuple_id: 0,
+        key: tuple(desc.key_desc(), id),
+        value: tuple(desc.value_desc(), qty),
+        layout_version: None,
+    }))
+}
+
+fn committed(xid: u64, mut ops: Vec<TxOp>) -> XLBatch {
+    ops.insert(0, TxOp::Begin);
+    ops.push(TxOp::Commit);
+    ops.push(TxOp::CommitTime {
+        unix_micros: xid * 10,
+    });
+    XLBatch::new(vec![XLEntry { xid, ops }])
+}
+
+/// Writes a log with committed, aborted and two-phase transactions on table
+/// `stock(id, qty)` plus writes the feed has to leave out.
+async fn write_log() -> (ChunkedWorkerLogBackend, ChangeFeed, OID) {
+    let meta = Arc::new(TestMetaMgr::new());
+    let schema = SchemaTable::new(
+        "stock".to_string(),
+        vec![column("id"), column("qty")],
+        vec![0],
+        vec![1],
+    );
+    meta.create_table(&schema).await.unwrap();
+    let desc = meta.get_table_by_id(schema.id()).await.unwrap();
+
+    let dir = test_dir();
+    let log_oid = gen_oid();
+    let layout = WorkerLogLayout::new(&dir, log_oid, CHUNK_SIZE).unwrap();
+    let log = ChunkedWorkerLogBackend::new(layout).await.unwrap();
+    let delta = encode_delta_assigns(&[DeltaAssign {
+        attr: 1,
+        op: DeltaOp::AddDeferred,
+        literal: send_binary(&DataValue::from_i32(5), desc.get_attr(1).type_desc()).unwrap(),
+    }])
+    .unwrap();
+    let batches = vec![
+        committed(1, vec![insert(&desc, 1, 10), insert(&desc, 2, 20)]),
+        XLBatch::new(vec![XLEntry {
+            xid: 2,
+            ops: vec![TxOp::Begin, insert(&desc, 3, 30), TxOp::Abort],
+        }]),
+        // A worker-local KV row.
+        committed(
+            3,
+            vec![TxOp::Write(XLWrite::Insert(XLInsert {
+                table_id: 0,
+                partition_id: 0,
+                tuple_id: 0,
+                key: b"k".to_vec(),
+                value: b"v".to_vec(),
+                layout_version: None,
+            }))],
+        ),
+        XLBatch::new(vec![XLEntry {
+            xid: 4,
+            ops: vec![
+                TxOp::Begin,
+                insert(&desc, 4, 40),
+                TxOp::Prepare {
+                    coordinator_worker_id: 9,
+                },
+            ],
+        }]),
+        committed(
+            5,
+            vec![
+                TxOp::Write(XLWrite::Update(XLUpdate {
+                    table_id: desc.id(),
+                    partition_id: PARTITION,
+                    tuple_id: 0,
+                    key: tuple(desc.key_desc(), 1),
+                    delta,
+                    layout: None,
+                })),
+                TxOp::Write(XLWrite::Delete(XLDelete {
+                    table_id: desc.id(),
+                    partition_id: PARTITION,
+                    tuple_id: 0,
+                    key: tuple(desc.key_desc(), 2),
+                })),
+            ],
+        ),
+        // A table that was dropped since.
+        committed(
+            6,
+            vec![TxOp::Write(XLWrite::Delete(XLDelete {
+                table_id: gen_oid(),
+                partition_id: PARTITION,
+                tuple_id: 0,
+                key: tuple(desc.key_desc(), 1),
+            }))],
+        ),
+        XLBatch::new(vec![XLEntry {
+            xid: 4,
+            ops: vec![TxOp::Resolve {
+                coordinator_worker_id: 9,
+                commit: true,
+            }],
+        }]),
+    ];
+    for batch in &batches {
+        append_xl_batch_async(&log, batch).await.unwrap();
+    }
+    log.flush_async().await.unwrap();
+    let feed = ChangeFeed::new(dir, CHUNK_SIZE, None, vec![log_oid], meta);
+    (log, feed, log_oid)
+}
+
+fn int(value: &DataValue) -> i32 {
+    *value.expect_i32()
+}
+
+#[test]
+fn change_feed_emits_committed_row_changes() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let (log, feed, log_oid) = write_log().await;
+        let response = feed
+            .read_async(&ChangeFeedRequest::new(log_oid, None, 100, 0))
+            .await
+            .unwrap();
+        let events = response.events();
+        let kinds: Vec<_> = events.iter().map(|e| (e.xid(), e.kind())).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (1, ChangeKind::Insert),
+                (1, ChangeKind::Insert),
+                (5, ChangeKind::Update),
+                (5, ChangeKind::Delete),
+                (4, ChangeKind::Insert),
+            ]
+        );
+        assert_eq!(response.last_lsn(), Some(log.next_lsn().as_u64() - 1));
+
+        let first = &events[0];
+        assert_eq!(first.table_name(), "stock");
+        assert_eq!(first.partition_id(), PARTITION);
+        assert_eq!(first.commit_time_micros(), Some(10));
+        let names: Vec<_> = first.row_desc().fields().iter().map(|f| f.name()).collect();
+        assert_eq!(names, vec!["id", "qty"]);
+        assert_eq!(
+            first.row().values().iter().map(int).collect::<Vec<_>>(),
+            [1, 10]
+        );
+        assert!(events.windows(2).all(|w| w[0].lsn() <= w[1].lsn()));
+
+        let update = &events[2];
+        assert_eq!(
+            update.row().values().iter().map(int).collect::<Vec<_>>(),
+            [1]
+        );
+        let delta = &update.deltas()[0];
+        assert_eq!(delta.column().name(), "qty");
+        assert_eq!(delta.op(), ChangeDeltaOp::Add);
+        assert_eq!(int(delta.operand()), 5);
+        assert_eq!(int(&events[3].row().values()[0]), 2);
+        assert_eq!(
+            events[4].row().values().iter().map(int).collect::<Vec<_>>(),
+            [4, 40]
+        );
+        assert!(events[4].to_json().is_ok());
+    })
+    .unwrap()
+}
+
+#[test]
+fn change_feed_resumes_after_the_returned_lsn() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let (_log, feed, log_oid) = write_log().await;
+        let mut after = None;
+        let mut xids = Vec::new();
+        loop {
+            let response = feed
+                .read_async(&ChangeFeedRequest::new(log_oid, after, 1, 0))
+                .await
+                .unwrap();
+            if response.events().is_empty() {
+                assert_eq!(response.last_lsn(), after);
+                break;
+            }
+            // A transaction is never split across responses.
+            let xid = response.events()[0].xid();
+            assert!(response.events().iter().all(|e| e.xid() == xid));
+            xids.push((xid, response.events().len()));
+            after = response.last_lsn();
+        }
+        // Transaction 4 was prepared before the cursor of its resolve.
+        assert_eq!(xids, vec![(1, 2), (5, 2), (4, 1)]);
+    })
+    .unwrap()
+}
+
+#[test]
+fn change_feed_waits_then_returns_empty() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let (log, feed, log_oid) = write_log().await;
+        let end = Some(log.next_lsn().as_u64() - 1);
+        let response = feed
+            .read_async(&ChangeFeedRequest::new(log_oid, end, 10, 100))
+            .await
+            .unwrap();
+        assert!(response.events().is_empty());
+        assert_eq!(response.last_lsn(), end);
+
+        let err = feed
+            .read_async(&ChangeFeedRequest::new(gen_oid(), None, 10, 0))
+            .await
+            .unwrap_err();
+        assert_eq!(err.ec(), ErrorCode::EntityNotFound);
+    })
+    .unwrap()
+}
+
+/// Frames written but not yet fsynced are left for a later read, so the feed
+/// never reports a change a crash could still take back.
+#[test]
+fn change_feed_reads_only_fsynced_frames() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let (log, feed, log_oid) = write_log().await;
+        let end = Some(log.next_lsn().as_u64() - 1);
+        append_xl_batch_async(&log, &committed(8, vec![]))
+            .await
+            .unwrap();
+
+        let response = feed
+            .read_async(&ChangeFeedRequest::new(log_oid, end, 10, 0))
+            .await
+            .unwrap();
+        assert!(response.events().is_empty());
+        assert_eq!(response.last_lsn(), end);
+
+        log.flush_async().await.unwrap();
+        let response = feed
+            .read_async(&ChangeFeedRequest::new(log_oid, end, 10, 0))
+            .await
+            .unwrap();
+        assert_eq!(response.last_lsn(), Some(log.next_lsn().as_u64() - 1));
+    })
+    .unwrap()
+}
+
+/// Rows and deltas logged before an ALTER TABLE are decoded with the layout
+/// they were stamped with and reported in the current one.
+#[test]
+fn change_feed_upgrades_rows_logged_before_alter_table() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let meta = Arc::new(TestMetaMgr::new());
+        let schema = SchemaTable::new(
+            "stock".to_string(),
+            vec![column("id"), column("qty")],
+            vec![0],
+            vec![1],
+        );
+        meta.create_table(&schema).await.unwrap();
+        let desc = meta.get_table_by_id(schema.id()).await.unwrap();
+
+        let dir = test_dir();
+        let log_oid = gen_oid();
+        let layout = WorkerLogLayout::new(&dir, log_oid, CHUNK_SIZE).unwrap();
+        let log = ChunkedWorkerLogBackend::new(layout).await.unwrap();
+        let mut row = insert(&desc, 1, 10);
+        if let TxOp::Write(XLWrite::Insert(insert)) = &mut row {
+            insert.layout_version = Some(desc.layout_version());
+        }
+        let delta = encode_delta_assigns(&[DeltaAssign {
+            attr: 1,
+            op: DeltaOp::AddDeferred,
+            literal: send_binary(&DataValue::from_i32(5), desc.get_attr(1).type_desc()).unwrap(),
+        }])
+        .unwrap();
+        let update = TxOp::Write(XLWrite::Update(XLUpdate {
+            table_id: desc.id(),
+            partition_id: PARTITION,
+            tuple_id: 0,
+            key: tuple(desc.key_desc(), 1),
+            delta,
+            layout: Some(XLDeltaLayout {
+                version: desc.layout_version(),
+                column_oids: vec![desc.get_attr(1).id()],
+            }),
+        }));
+        append_xl_batch_async(&log, &committed(1, vec![row, update]))
+            .await
+            .unwrap();
+        log.flush_async().await.unwrap();
+
+        meta.alter_table(
+            desc.id(),
+            &[AlterTable::AlterColumnType {
+                name: "qty".to_string(),
+                type_id: TypeFamily::I64,
+                type_param: DataType::new_no_param(TypeFamily::I64).to_info(),
+            }],
+        )
+        .await
+        .unwrap();
+
+        let feed = ChangeFeed::new(dir, CHUNK_SIZE, None, vec![log_oid], meta);
+        let response = feed
+            .read_async(&ChangeFeedRequest::new(log_oid, None, 10, 0))
+            .await
+            .unwrap();
+        let events = response.events();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].row().values()[1].to_i64(), 10);
+        let delta = &events[1].deltas()[0];
+        assert_eq!(delta.column().name(), "qty");
+        assert_eq!(delta.op(), ChangeDeltaOp::Add);
+        assert_eq!(delta.operand().to_i64(), 5);
+    })
+    .unwrap()
+}
+
+/// A committing `Resolve` whose `Prepare` was reclaimed takes the prepared
+/// writes from the checkpoint, and fails the read when the checkpoint does
+/// not have them either, rather than dropping the commit.
+#[test]
+fn change_feed_reads_reclaimed_prepares_from_the_checkpoint() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let meta = Arc::new(TestMetaMgr::new());
+        let schema = SchemaTable::new(
+            "stock".to_string(),
+            vec![column("id"), column("qty")],
+            vec![0],
+            vec![1],
+        );
+        meta.create_table(&schema).await.unwrap();
+        let desc = meta.get_table_by_id(schema.id()).await.unwrap();
+
+        let dir = test_dir();
+        let log_oid = gen_oid();
+        let layout = WorkerLogLayout::new(&dir, log_oid, CHUNK_SIZE).unwrap();
+        let log = ChunkedWorkerLogBackend::new(layout.clone()).await.unwrap();
+        let resolve = XLBatch::new(vec![XLEntry {
+            xid: 4,
+            ops: vec![TxOp::Resolve {
+                coordinator_worker_id: 9,
+                commit: true,
+            }],
+        }]);
+        append_xl_batch_async(&log, &resolve).await.unwrap();
+        log.flush_async().await.unwrap();
+
+        let feed = ChangeFeed::new(dir, CHUNK_SIZE, None, vec![log_oid], meta);
+        let err = feed
+            .read_async(&ChangeFeedRequest::new(log_oid, None, 10, 0))
+            .await
+            .unwrap_err();
+        assert_eq!(err.ec(), ErrorCode::InvalidState);
+
+        let TxOp::Write(write) = insert(&desc, 4, 40) else {
+            unreachable!()
+        };
+        let payload = WorkerLogCheckpoint {
+            prepared_txs: vec![PreparedTxRecord {
+                coordinator_worker_id: 9,
+                tx_id: 4,
+                writes: vec![write],
+            }],
+            ..Default::default()
+        }
+        .encode()
+        .unwrap();
+        let fs = default_sys_io_context().provider_arc().fs_arc();
+        layout
+            .write_checkpoint_async(
+                fs.as_ref(),
+                &WalCheckpoint {
+                    lsn: LSN::new(0),
+                    payload,
+                },
+            )
+            .await
+            .unwrap();
+        let response = feed
+            .read_async(&ChangeFeedRequest::new(log_oid, None, 10, 0))
+            .await
+            .unwrap();
+        let events = response.events();
+        assert_eq!(events.len(), 1);
+        assert_eq!((events[0].xid(), events[0].kind()), (4, ChangeKind::Insert));
+        assert_eq!(
+            events[0].row().values().iter().map(int).collect::<Vec<_>>(),
+            [4, 40]
+        );
+    })
+    .unwrap()
+}
@@ -0,0 +1,552 @@
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::todo,
+    clippy::unimplemented
+)]
+//! End-to-end tests for column defaults and table constraints: a real
+//! single-worker [`WorkerRuntime`] on temporary directories runs SQL through
+//! `query`/`execute`, so the constraint hooks are exercised against the real
+//! catalog, secondary indexes and storage.
+//!
+//! Miri cannot execute the tree-sitter FFI behind SQL parsing, so the whole
+//! module is excluded under Miri (see `mod.rs`).
+
+use std::sync::Arc;
+
+use mudu::error::ErrorCode;
+use mudu_type::data_value::DataValue;
+use mudu_type::datum::DatumDyn;
+use mudu_utils::oid::gen_oid;
+
+use crate::command::update_key_value::UpdateKeyValue;
+use crate::contract::cmd_exec::CmdExec;
+use crate::server::e2e_test_util::{build_worker, exec, exec_err, query_rows, TestDirs};
+use crate::server::session_bound_worker_runtime::new_session_bound_worker_runtime;
+use crate::server::worker_local::WorkerLocal;
+use crate::x_engine::api::{DeltaAssign, DeltaOp, VecDatum};
+use crate::x_engine::tx_mgr::TxMgr;
+use crate::x_engine::x_param::PUpdateKeyValue;
+
+#[test]
+fn constraint_e2e_column_defaults() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let dirs = TestDirs::new("constraint_e2e_defaults");
+        let worker = build_worker(&dirs).await;
+        let session = worker.create_session(1).unwrap();
+        let local_arc = new_session_bound_worker_runtime(worker.clone(), session);
+        let local: &dyn WorkerLocal = local_arc.as_ref();
+
+        exec(
+            local,
+            session,
+            "CREATE TABLE orders (id UUID PRIMARY KEY DEFAULT gen_oid(), \
+             qty INTEGER DEFAULT 1, note TEXT DEFAULT 'new', created TIMESTAMP DEFAULT now())",
+            (),
+        )
+        .await;
+
+        // The key and every value column come from their defaults; the
+        // parameterized statement runs twice to go through the plan cache.
+        let insert_sql = "INSERT INTO orders (qty) VALUES (?)";
+        assert_eq!(exec(local, session, insert_sql, (5i32,)).await, 1);
+        assert_eq!(exec(local, session, insert_sql, (7i32,)).await, 1);
+        let rows = query_rows(
+            local,
+            session,
+            "SELECT id, qty, note, created FROM orders ORDER BY qty",
+            (),
+        )
+        .await;
+        assert_eq!(rows.len(), 2);
+        assert_ne!(
+            rows[0].values()[0].expect_uuid(),
+            rows[1].values()[0].expect_uuid()
+        );
+        for (row, qty) in rows.iter().zip([5, 7]) {
+            assert_eq!(row.values()[1].to_i32(), qty);
+            assert_eq!(row.values()[2].expect_string(), "new");
+            assert!(!row.values()[3].is_null());
+        }
+
+        // An explicit value, NULL included, overrides the default.
+        exec(
+            local,
+            session,
+            "INSERT INTO orders (qty, note) VALUES (9, NULL)",
+            (),
+        )
+        .await;
+        let rows = query_rows(local, session, "SELECT note FROM orders WHERE qty = 9", ()).await;
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].values()[0].is_null());
+
+        // now() needs a timestamp column.
+        let err = exec_err(
+            local,
+            session,
+            "CREATE TABLE bad (id INTEGER PRIMARY KEY, at INTEGER DEFAULT now())",
+            (),
+        )
+        .await;
+        assert_eq!(err.ec(), ErrorCode::InvalidType);
+
+        // A column added with a constant DEFAULT reads it on existing rows.
+        exec(
+            local,
+            session,
+            "ALTER TABLE orders ADD COLUMN prio INTEGER NOT NULL DEFAULT 3",
+            (),
+        )
+        .await;
+        let rows = query_rows(local, session, "SELECT prio FROM orders", ()).await;
+        assert_eq!(rows.len(), 3);
+        for row in &rows {
+            assert_eq!(row.values()[0].to_i32(), 3);
+        }
+
+        // Existing rows cannot be backfilled without a constant DEFAULT.
+        for sql in [
+            "ALTER TABLE orders ADD COLUMN flag INTEGER NOT NULL",
+            "ALTER TABLE orders ADD COLUMN seen TIMESTAMP DEFAULT now()",
+        ] {
+            let err = exec_err(local, session, sql, ()).await;
+            assert_eq!(err.ec(), ErrorCode::UnsupportedOperation);
+        }
+        exec(
+            local,
+            session,
+            "CREATE TABLE empty_orders (id INTEGER PRIMARY KEY)",
+            (),
+        )
+        .await;
+        exec(
+            local,
+            session,
+            "ALTER TABLE empty_orders ADD COLUMN flag INTEGER NOT NULL",
+            (),
+        )
+        .await;
+    });
+}
+
+#[test]
+fn constraint_e2e_unique() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let dirs = TestDirs::new("constraint_e2e_unique");
+        let worker = build_worker(&dirs).await;
+        let session = worker.create_session(1).unwrap();
+        let local_arc = new_session_bound_worker_runtime(worker.clone(), session);
+        let local: &dyn WorkerLocal = local_arc.as_ref();
+
+        exec(
+            local,
+            session,
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT, UNIQUE KEY (email))",
+            (),
+        )
+        .await;
+        let insert_sql = "INSERT INTO users VALUES (?, ?)";
+        exec(local, session, insert_sql, (1i32, "a@x".to_string())).await;
+        exec(local, session, insert_sql, (2i32, "b@x".to_string())).await;
+        let err = exec_err(local, session, insert_sql, (3i32, "a@x".to_string())).await;
+        assert_eq!(err.ec(), ErrorCode::ConstraintViolation);
+
+        // NULLs never collide.
+        exec(local, session, "INSERT INTO users (id) VALUES (4)", ()).await;
+        exec(local, session, "INSERT INTO users (id) VALUES (5)", ()).await;
+
+        // An update may keep its own value but not take another row's.
+        let update_sql = "UPDATE users SET email = ? WHERE id = ?";
+        assert_eq!(
+            exec(local, session, update_sql, ("a@x".to_string(), 1i32)).await,
+            1
+        );
+        let err = exec_err(local, session, update_sql, ("b@x".to_string(), 1i32)).await;
+        assert_eq!(err.ec(), ErrorCode::ConstraintViolation);
+        exec(local, session, update_sql, ("c@x".to_string(), 1i32)).await;
+        exec(local, session, insert_sql, (6i32, "a@x".to_string())).await;
+
+        // A relative assignment is checked on the value it produces.
+        exec(
+            local,
+            session,
+            "CREATE TABLE seats (id INTEGER PRIMARY KEY, seat INTEGER, UNIQUE KEY (seat))",
+            (),
+        )
+        .await;
+        exec(
+            local,
+            session,
+            "INSERT INTO seats VALUES (1, 1), (2, 2)",
+            (),
+        )
+        .await;
+        let increment_sql = "UPDATE seats SET seat = seat + ? WHERE id = ?";
+        let err = exec_err(local, session, increment_sql, (1i32, 1i32)).await;
+        assert_eq!(err.ec(), ErrorCode::ConstraintViolation);
+        assert_eq!(exec(local, session, increment_sql, (5i32, 2i32)).await, 1);
+        assert_eq!(exec(local, session, increment_sql, (1i32, 1i32)).await, 1);
+        let rows = query_rows(local, session, "SELECT seat FROM seats ORDER BY id", ()).await;
+        let seats: Vec<i32> = rows.iter().map(|row| row.values()[0].to_i32()).collect();
+        assert_eq!(seats, vec![2, 7]);
+
+        // The index backing the constraint cannot be dropped on its own.
+        let err = exec_err(local, session, "DROP INDEX users_email_key", ()).await;
+        assert_eq!(err.ec(), ErrorCode::UnsupportedOperation);
+
+        let rows = query_rows(local, session, "SELECT id FROM users", ()).await;
+        assert_eq!(rows.len(), 6);
+    });
+}
+
+#[test]
+fn constraint_e2e_concurrent_writers_wait_on_value_locks() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let dirs = TestDirs::new("constraint_e2e_concurrent");
+        let worker = build_worker(&dirs).await;
+        let session_a = worker.create_session(1).unwrap();
+        let session_b = worker.create_session(2).unwrap();
+        let local_a_arc = new_session_bound_worker_runtime(worker.clone(), session_a);
+        let local_b_arc = new_session_bound_worker_runtime(worker.clone(), session_b);
+        let local_a: &dyn WorkerLocal = local_a_arc.as_ref();
+        let local_b: &dyn WorkerLocal = local_b_arc.as_ref();
+
+        exec(
+            local_a,
+            session_a,
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT, UNIQUE KEY (email))",
+            (),
+        )
+        .await;
+        exec(
+            local_a,
+            session_a,
+            "CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER, \
+             FOREIGN KEY (user_id) REFERENCES users (id))",
+            (),
+        )
+        .await;
+
+        // A holds the lock on the unique value until it commits, so B's check
+        // runs after A's row is visible.
+        exec(local_a, session_a, "BEGIN", ()).await;
+        exec(
+            local_a,
+            session_a,
+            "INSERT INTO users VALUES (1, 'a@x')",
+            (),
+        )
+        .await;
+        let insert = local_b.execute(
+            session_b,
+            Box::new("INSERT INTO users VALUES (2, 'a@x')".to_string()),
+            Box::new(()),
+        );
+        let commit = async {
+            for _ in 0..16 {
+                crate::common::yield_now::cooperative_yield_now().await;
+            }
+            exec(local_a, session_a, "COMMIT", ()).await
+        };
+        let (inserted, _) = futures::join!(insert, commit);
+        assert_eq!(inserted.unwrap_err().ec(), ErrorCode::ConstraintViolation);
+
+        // The same holds for a child row racing the delete of its parent.
+        exec(local_a, session_a, "BEGIN", ()).await;
+        exec(local_a, session_a, "DELETE FROM users WHERE id = 1", ()).await;
+        let insert = local_b.execute(
+            session_b,
+            Box::new("INSERT INTO orders VALUES (10, 1)".to_string()),
+            Box::new(()),
+        );
+        let commit = async {
+            for _ in 0..16 {
+                crate::common::yield_now::cooperative_yield_now().await;
+            }
+            exec(local_a, session_a, "COMMIT", ()).await
+        };
+        let (inserted, _) = futures::join!(insert, commit);
+        assert_eq!(inserted.unwrap_err().ec(), ErrorCode::ConstraintViolation);
+        let rows = query_rows(local_b, session_b, "SELECT id FROM orders", ()).await;
+        assert!(rows.is_empty());
+    });
+}
+
+#[test]
+fn constraint_e2e_foreign_keys() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let dirs = TestDirs::new("constraint_e2e_foreign_keys");
+        let worker = build_worker(&dirs).await;
+        let session = worker.create_session(1).unwrap();
+        let local_arc = new_session_bound_worker_runtime(worker.clone(), session);
+        let local: &dyn WorkerLocal = local_arc.as_ref();
+
+        exec(
+            local,
+            session,
+            "CREATE TABLE customers (id INTEGER PRIMARY KEY, name TEXT)",
+            (),
+        )
+        .await;
+        exec(
+            local,
+            session,
+            "CREATE TABLE orders (id INTEGER PRIMARY KEY, customer_id INTEGER, \
+             FOREIGN KEY (customer_id) REFERENCES customers (id) ON DELETE CASCADE)",
+            (),
+        )
+        .await;
+        exec(
+            local,
+            session,
+            "CREATE TABLE invoices (id INTEGER PRIMARY KEY, customer_id INTEGER, \
+             FOREIGN KEY (customer_id) REFERENCES customers (id))",
+            (),
+        )
+        .await;
+        exec(
+            local,
+            session,
+            "INSERT INTO customers VALUES (1, 'ann'), (2, 'bob')",
+            (),
+        )
+        .await;
+
+        // A referencing row needs its parent; a NULL reference is not checked.
+        let insert_order = "INSERT INTO orders VALUES (?, ?)";
+        exec(local, session, insert_order, (10i32, 1i32)).await;
+        exec(local, session, insert_order, (11i32, 1i32)).await;
+        exec(local, session, insert_order, (12i32, 2i32)).await;
+        let err = exec_err(local, session, insert_order, (13i32, 3i32)).await;
+        assert_eq!(err.ec(), ErrorCode::ConstraintViolation);
+        exec(local, session, "INSERT INTO orders (id) VALUES (14)", ()).await;
+        let err = exec_err(
+            local,
+            session,
+            "UPDATE orders SET customer_id = 3 WHERE id = 10",
+            (),
+        )
+        .await;
+        assert_eq!(err.ec(), ErrorCode::ConstraintViolation);
+        // A relative assignment is checked on the value it produces.
+        let err = exec_err(
+            local,
+            session,
+            "UPDATE orders SET customer_id = customer_id + 1 WHERE id = 12",
+            (),
+        )
+        .await;
+        assert_eq!(err.ec(), ErrorCode::ConstraintViolation);
+        exec(
+            local,
+            session,
+            "UPDATE orders SET customer_id = customer_id + 1 WHERE id = 11",
+            (),
+        )
+        .await;
+
+        // ON DELETE CASCADE removes the referencing orders.
+        assert_eq!(
+            exec(local, session, "DELETE FROM customers WHERE id = 1", ()).await,
+            1
+        );
+        let rows = query_rows(local, session, "SELECT id FROM orders ORDER BY id", ()).await;
+        let ids: Vec<i32> = rows.iter().map(|row| row.values()[0].to_i32()).collect();
+        assert_eq!(ids, vec![11, 12, 14]);
+
+        // The default ON DELETE RESTRICT rejects the delete.
+        exec(local, session, "INSERT INTO invoices VALUES (20, 2)", ()).await;
+        let err = exec_err(local, session, "DELETE FROM customers WHERE id = 2", ()).await;
+        assert_eq!(err.ec(), ErrorCode::ConstraintViolation);
+        let rows = query_rows(local, session, "SELECT id FROM customers", ()).await;
+        assert_eq!(rows.len(), 1);
+
+        // A referenced table outlives its referencing tables.
+        let err = exec_err(local, session, "DROP TABLE customers", ()).await;
+        assert_eq!(err.ec(), ErrorCode::ConstraintViolation);
+        exec(local, session, "DROP TABLE invoices", ()).await;
+        exec(local, session, "DROP TABLE orders", ()).await;
+        exec(local, session, "DROP TABLE customers", ()).await;
+
+        // The referenced columns must be the primary key or a unique key.
+        exec(
+            local,
+            session,
+            "CREATE TABLE regions (id INTEGER PRIMARY KEY, code TEXT)",
+            (),
+        )
+        .await;
+        let err = exec_err(
+            local,
+            session,
+            "CREATE TABLE shops (id INTEGER PRIMARY KEY, region TEXT, \
+             FOREIGN KEY (region) REFERENCES regions (code))",
+            (),
+        )
+        .await;
+        assert_eq!(err.ec(), ErrorCode::InvalidArgument);
+    });
+}
+
+#[test]
+fn constraint_e2e_checks() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let dirs = TestDirs::new("constraint_e2e_checks");
+        let worker = build_worker(&dirs).await;
+        let session = worker.create_session(1).unwrap();
+        let local_arc = new_session_bound_worker_runtime(worker.clone(), session);
+        let local: &dyn WorkerLocal = local_arc.as_ref();
+
+        exec(
+            local,
+            session,
+            "CREATE TABLE stock (id INTEGER PRIMARY KEY, qty INTEGER CHECK (qty >= 0), \
+             lo INTEGER, hi INTEGER, note TEXT, CONSTRAINT lo_hi CHECK (lo <= hi))",
+            (),
+        )
+        .await;
+        let insert_sql = "INSERT INTO stock (id, qty, lo, hi) VALUES (?, ?, ?, ?)";
+        exec(local, session, insert_sql, (1i32, 5i32, 1i32, 2i32)).await;
+        let err = exec_err(local, session, insert_sql, (2i32, -1i32, 1i32, 2i32)).await;
+        assert_eq!(err.ec(), ErrorCode::ConstraintViolation);
+        let err = exec_err(local, session, insert_sql, (2i32, 1i32, 3i32, 2i32)).await;
+        assert_eq!(err.ec(), ErrorCode::ConstraintViolation);
+        // An UNKNOWN condition passes.
+        exec(
+            local,
+            session,
+            "INSERT INTO stock (id, lo) VALUES (3, 9)",
+            (),
+        )
+        .await;
+
+        // Updates are checked on the row they produce, a relative one included.
+        let err = exec_err(local, session, "UPDATE stock SET hi = 0 WHERE id = 1", ()).await;
+        assert_eq!(err.ec(), ErrorCode::ConstraintViolation);
+        let err = exec_err(
+            local,
+            session,
+            "UPDATE stock SET qty = qty - 6 WHERE id = 1",
+            (),
+        )
+        .await;
+        assert_eq!(err.ec(), ErrorCode::ConstraintViolation);
+        assert_eq!(
+            exec(
+                local,
+                session,
+                "UPDATE stock SET qty = qty - 5 WHERE id = 1",
+                ()
+            )
+            .await,
+            1
+        );
+        exec(
+            local,
+            session,
+            "UPDATE stock SET note = 'x' WHERE id = 1",
+            (),
+        )
+        .await;
+        let rows = query_rows(
+            local,
+            session,
+            "SELECT qty, lo, hi FROM stock WHERE id = 1",
+            (),
+        )
+        .await;
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].values()[0].to_i32(), 0);
+        assert_eq!(rows[0].values()[2].to_i32(), 2);
+
+        // The columns a check reads cannot change under it.
+        let err = exec_err(local, session, "ALTER TABLE stock DROP COLUMN lo", ()).await;
+        assert_eq!(err.ec(), ErrorCode::UnsupportedOperation);
+        let err = exec_err(
+            local,
+            session,
+            "ALTER TABLE stock RENAME COLUMN qty TO amount",
+            (),
+        )
+        .await;
+        assert_eq!(err.ec(), ErrorCode::UnsupportedOperation);
+        exec(local, session, "ALTER TABLE stock DROP COLUMN note", ()).await;
+        let err = exec_err(local, session, insert_sql, (4i32, -2i32, 1i32, 2i32)).await;
+        assert_eq!(err.ec(), ErrorCode::ConstraintViolation);
+
+        // A condition that does not bind rejects the table.
+        let err = exec_err(
+            local,
+            session,
+            "CREATE TABLE bad (id INTEGER PRIMARY KEY, v INTEGER CHECK (v + 1))",
+            (),
+        )
+        .await;
+        assert_eq!(err.ec(), ErrorCode::InvalidType);
+    });
+}
+
+#[test]
+fn constraint_e2e_check_on_delta_assignment() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let dirs = TestDirs::new("constraint_e2e_check_on_delta_assignment");
+        let worker = build_worker(&dirs).await;
+        let session = worker.create_session(1).unwrap();
+        let local_arc = new_session_bound_worker_runtime(worker.clone(), session);
+        let local: &dyn WorkerLocal = local_arc.as_ref();
+
+        exec(
+            local,
+            session,
+            "CREATE TABLE stock (id INTEGER PRIMARY KEY, qty INTEGER CHECK (qty >= 0))",
+            (),
+        )
+        .await;
+        exec(local, session, "INSERT INTO stock VALUES (1, 5)", ()).await;
+
+        // A delta assignment, which SQL only emits for unconstrained
+        // columns, is checked on the value it produces.
+        let meta_mgr = worker.meta_mgr();
+        let x_contract = worker.x_contract();
+        let desc = meta_mgr.get_table_by_name("stock").await.unwrap().unwrap();
+        let i32_datum = |value: i32| -> Vec<u8> {
+            DataValue::from_i32(value)
+                .to_binary(desc.get_attr(1).type_desc())
+                .unwrap()
+                .into()
+        };
+        let decrement = |tx_mgr: Arc<dyn TxMgr>, amount: i32| {
+            UpdateKeyValue::new(
+                PUpdateKeyValue {
+                    tx_mgr,
+                    table_id: desc.id(),
+                    key: VecDatum::new(vec![(0, i32_datum(1))]),
+                    value: VecDatum::new(Vec::new()),
+                    delta_assignments: vec![DeltaAssign {
+                        attr: 1,
+                        op: DeltaOp::Sub,
+                        literal: i32_datum(amount),
+                    }],
+                    expr_assignments: Vec::new(),
+                    returning: None,
+                },
+                x_contract.clone(),
+                meta_mgr.clone(),
+            )
+        };
+
+        let tx_mgr = x_contract.begin_tx().await.unwrap();
+        let err = decrement(tx_mgr.clone(), 6).run().await.unwrap_err();
+        assert_eq!(err.ec(), ErrorCode::ConstraintViolation);
+        let update = decrement(tx_mgr.clone(), 5);
+        update.run().await.unwrap();
+        assert_eq!(update.affected_rows().await.unwrap(), 1);
+        x_contract.commit_tx(tx_mgr).await.unwrap();
+
+        let rows = query_rows(local, session, "SELECT qty FROM stock WHERE id = 1", ()).await;
+        assert_eq!(rows[0].values()[0].to_i32(), 0);
+    })
+    .unwrap()
+}
@@ -0,0 +1,119 @@
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::todo,
+    clippy::unimplemented
+)]
+//! Helpers shared by the SQL end-to-end tests of this module: a real
+//! single-worker [`WorkerRuntime`] on temporary directories, and shortcuts
+//! that run SQL through a [`WorkerLocal`] and unwrap the outcome.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use mudu::common::id::OID;
+use mudu::error::MuduError;
+use mudu_contract::database::sql_params::SQLParams;
+use mudu_contract::tuple::tuple_value::TupleValue;
+use mudu_sys::env_var::temp_dir;
+use mudu_utils::oid::gen_oid;
+
+use crate::server::auth::ServerAuth;
+use crate::server::worker::{WorkerRuntime, WorkerRuntimeParams};
+use crate::server::worker_local::WorkerLocal;
+use crate::server::worker_registry::load_or_create_worker_registry;
+use crate::wal::worker_log::{WalSyncPolicy, WorkerLogBatching};
+
+/// Temporary directories of one test runtime, removed on drop.
+pub(crate) struct TestDirs {
+    base: PathBuf,
+    registry_dir: String,
+    log_dir: String,
+    data_dir: String,
+}
+
+impl TestDirs {
+    pub(crate) fn new(prefix: &str) -> Self {
+        let base = temp_dir().join(format!("{}_{}", prefix, gen_oid()));
+        Self {
+            registry_dir: base.join("registry").to_string_lossy().into_owned(),
+            log_dir: base.join("log").to_string_lossy().into_owned(),
+            data_dir: base.join("data").to_string_lossy().into_owned(),
+            base,
+        }
+    }
+}
+
+impl Drop for TestDirs {
+    fn drop(&mut self) {
+        let _ = mudu_sys::fs::sync::remove_dir_all(&self.base);
+    }
+}
+
+/// Start an initialized single-worker runtime on `dirs`.
+pub(crate) async fn build_worker(dirs: &TestDirs) -> WorkerRuntime {
+    let registry = load_or_create_worker_registry(&dirs.registry_dir, 1).unwrap();
+    let identity = registry.worker(0).cloned().unwrap();
+    let worker = WorkerRuntime::new(WorkerRuntimeParams {
+        identity,
+        worker_count: 1,
+        log_dir: dirs.log_dir.clone(),
+        data_dir: dirs.data_dir.clone(),
+        log_chunk_size: 4096,
+        log_batching: WorkerLogBatching::default(),
+        wal_sync_policy: WalSyncPolicy::Commit,
+        wal_archive_dir: None,
+        procedure_runtime: None,
+        registry,
+        async_runtime: None,
+        server_instance_id: 0,
+        auth: Arc::new(ServerAuth::disabled()),
+    })
+    .await
+    .unwrap();
+    worker.initialize().await.unwrap();
+    worker.bootstrap_storage_async().await.unwrap();
+    worker
+}
+
+pub(crate) async fn exec<P: SQLParams + 'static>(
+    local: &dyn WorkerLocal,
+    session: OID,
+    sql: &str,
+    params: P,
+) -> u64 {
+    local
+        .execute(session, Box::new(sql.to_string()), Box::new(params))
+        .await
+        .unwrap()
+}
+
+pub(crate) async fn exec_err<P: SQLParams + 'static>(
+    local: &dyn WorkerLocal,
+    session: OID,
+    sql: &str,
+    params: P,
+) -> MuduError {
+    local
+        .execute(session, Box::new(sql.to_string()), Box::new(params))
+        .await
+        .unwrap_err()
+}
+
+pub(crate) async fn query_rows<P: SQLParams + 'static>(
+    local: &dyn WorkerLocal,
+    session: OID,
+    sql: &str,
+    params: P,
+) -> Vec<TupleValue> {
+    let result = local
+        .query(session, Box::new(sql.to_string()), Box::new(params))
+        .await
+        .unwrap();
+    let mut rows = Vec::new();
+    while let Some(row) = result.next().await.unwrap() {
+        rows.push(row);
+    }
+    rows
+}
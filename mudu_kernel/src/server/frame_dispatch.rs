@@ -9,11 +9,44 @@ use mudu::mudu_error;
 use mudu_contract::protocol::{Frame, FrameHeader, MessageType, HEADER_LEN};
 use mudu_sys::scoped_task_trace;
 
+/// Largest request payload a connection accepts. Connection loops handle one
+/// frame at a time and write its response before reading the next, so a peer
+/// that does not drain responses stalls on its own socket; this bound keeps a
+/// single oversized frame from growing the read buffer without limit.
+pub(in crate::server) const MAX_REQUEST_PAYLOAD_LEN: usize = 64 * 1024 * 1024;
+
+/// Read buffer capacity a connection keeps between frames. A buffer grown
+/// for a large request is shrunk back to it once the request is served, so
+/// idle connections do not each hold on to their largest request.
+pub(in crate::server) const READ_BUF_CAPACITY: usize = 8192;
+
+/// Shrink `read_buf` back to [`READ_BUF_CAPACITY`] after a large request.
+pub(in crate::server) fn release_read_buf(read_buf: &mut Vec<u8>) {
+    if read_buf.capacity() > READ_BUF_CAPACITY {
+        read_buf.shrink_to(READ_BUF_CAPACITY);
+    }
+}
+
+/// Reject a frame header whose payload exceeds [`MAX_REQUEST_PAYLOAD_LEN`].
+pub(in crate::server) fn check_request_payload_len(header: &FrameHeader) -> RS<()> {
+    let payload_len = header.payload_len() as usize;
+    if payload_len > MAX_REQUEST_PAYLOAD_LEN {
+        return Err(mudu_error!(
+            ErrorCode::Parse,
+            format!(
+                "request payload of {payload_len} bytes exceeds the {MAX_REQUEST_PAYLOAD_LEN} byte limit"
+            )
+        ));
+    }
+    Ok(())
+}
+
 pub fn try_decode_next_frame(buf: &[u8]) -> RS<Option<(Frame, usize)>> {
     if buf.len() < HEADER_LEN {
         return Ok(None);
     }
     let header = FrameHeader::decode_header_bytes(&buf[..HEADER_LEN])?;
+    check_request_payload_len(&header)?;
     let payload_len = header.payload_len() as usize;
     let frame_len = HEADER_LEN + payload_len;
     if buf.len() < frame_len {
@@ -31,6 +64,7 @@ pub async fn dispatch_frame_async(
     scoped_task_trace!();
     let _stage =
         crate::server::stage_stats::StageGuard::new(crate::server::stage_stats::Stage::FrameHandle);
+    worker.check_frame_authorized(conn_id, frame.header().message_type())?;
     let ctx = RequestCtx::new(
         new_session_bound_worker_runtime(worker.clone(), 0),
         conn_id,
@@ -48,15 +82,17 @@ pub async fn dispatch_frame_async(
         | MessageType::Batch
         | MessageType::ProcedureInvoke
         | MessageType::SessionCreate
-        | MessageType::SessionClose => unreachable!(),
-        MessageType::Handshake | MessageType::Auth | MessageType::Response | MessageType::Error => {
-            Err(mudu_error!(
-                ErrorCode::Parse,
-                format!(
-                    "unsupported client message type {:?}",
-                    frame.header().message_type()
-                )
-            ))
-        }
+        | MessageType::SessionClose
+        | MessageType::ChangeFeed
+        | MessageType::Fetch
+        | MessageType::CloseCursor
+        | MessageType::Auth => unreachable!(),
+        MessageType::Handshake | MessageType::Response | MessageType::Error => Err(mudu_error!(
+            ErrorCode::Parse,
+            format!(
+                "unsupported client message type {:?}",
+                frame.header().message_type()
+            )
+        )),
     }
 }
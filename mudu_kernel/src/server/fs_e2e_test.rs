@@ -31,6 +31,7 @@
 //! module is excluded under Miri (see `mod.rs`).
 
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use mudu::common::id::OID;
 use mudu::error::ErrorCode;
@@ -40,6 +41,7 @@ use mudu_utils::oid::gen_oid;
 
 use crate::meta::fs_object::{FS_OBJECT_STATE_PENDING, FS_OBJECT_STATE_SEALED};
 use crate::meta::fs_type_catalog::fs_storage_root;
+use crate::server::auth::ServerAuth;
 use crate::server::session_bound_worker_runtime::new_session_bound_worker_runtime;
 use crate::server::worker::{WorkerRuntime, WorkerRuntimeParams};
 use crate::server::worker_local::{WorkerExecute, WorkerLocal};
@@ -92,10 +94,12 @@ async fn build_worker(dirs: &TestDirs) -> WorkerRuntime {
         log_chunk_size: 4096,
         log_batching: WorkerLogBatching::default(),
         wal_sync_policy: WalSyncPolicy::Commit,
+        wal_archive_dir: None,
         procedure_runtime: None,
         registry,
         async_runtime: None,
         server_instance_id: 0,
+        auth: Arc::new(ServerAuth::disabled()),
     })
     .await
     .unwrap();
@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+use mudu::common::result::RS;
+use mudu_contract::protocol::{decode_change_feed_request, Frame, MessageType};
+
+use crate::server::async_func_task::HandleResult;
+use crate::server::message_dispatcher::MessageHandler;
+use crate::server::request_ctx::RequestCtx;
+
+pub(in crate::server) struct ChangeFeedHandler;
+
+#[async_trait]
+impl MessageHandler for ChangeFeedHandler {
+    fn message_type(&self) -> MessageType {
+        MessageType::ChangeFeed
+    }
+
+    async fn handle(&self, ctx: &RequestCtx, frame: &Frame) -> RS<HandleResult> {
+        let request = decode_change_feed_request(frame)?;
+        ctx.change_feed(request).await
+    }
+}
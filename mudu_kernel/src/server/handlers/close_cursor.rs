@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+use mudu::common::result::RS;
+use mudu_contract::protocol::{decode_close_cursor_request, Frame, MessageType};
+
+use crate::server::async_func_task::HandleResult;
+use crate::server::message_dispatcher::MessageHandler;
+use crate::server::request_ctx::RequestCtx;
+
+pub(in crate::server) struct CloseCursorHandler;
+
+#[async_trait]
+impl MessageHandler for CloseCursorHandler {
+    fn message_type(&self) -> MessageType {
+        MessageType::CloseCursor
+    }
+
+    async fn handle(&self, ctx: &RequestCtx, frame: &Frame) -> RS<HandleResult> {
+        let request = decode_close_cursor_request(frame)?;
+        ctx.close_cursor(request).await
+    }
+}
@@ -4,6 +4,7 @@ use mudu::error::ErrorCode;
 use mudu::mudu_error;
 use mudu_contract::protocol::{
     decode_handshake_request, encode_handshake_response, Frame, HandshakeResponse, MessageType,
+    AUTH_SCRAM_SHA_256_CAPABILITY,
 };
 
 use crate::server::async_func_task::HandleResult;
@@ -29,12 +30,17 @@ impl MessageHandler for HandshakeHandler {
                 mudu_error!(ErrorCode::Parse, "no mutually supported protocol version")
             })?;
 
+        let mut capabilities = vec![
+            "protocol.handshake".to_string(),
+            "result.table.v1".to_string(),
+        ];
+        // Tells the client it has to authenticate before anything else.
+        if ctx.auth_required() {
+            capabilities.push(AUTH_SCRAM_SHA_256_CAPABILITY.to_string());
+        }
         let response = HandshakeResponse {
             selected_version: selected,
-            capabilities: vec![
-                "protocol.handshake".to_string(),
-                "result.table.v1".to_string(),
-            ],
+            capabilities,
         };
         Ok(HandleResult::Response(encode_handshake_response(
             ctx.request_id(),
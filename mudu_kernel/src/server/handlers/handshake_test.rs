@@ -56,7 +56,7 @@ impl XContract for NullXContract {
         &self,
         _tx_mgr: Arc<dyn TxMgr>,
         _oid: OID,
-        _alter_table: &AlterTable,
+        _alter_table: &[AlterTable],
     ) -> RS<()> {
         unimplemented!()
     }
@@ -1,5 +1,9 @@
+mod auth;
 mod batch;
+mod change_feed;
+mod close_cursor;
 mod execute;
+mod fetch;
 mod get;
 mod handshake;
 mod procedure_invoke;
@@ -12,8 +16,12 @@ mod session_create;
 #[cfg(test)]
 mod handshake_test;
 
+pub(in crate::server) use auth::AuthHandler;
 pub(in crate::server) use batch::BatchHandler;
+pub(in crate::server) use change_feed::ChangeFeedHandler;
+pub(in crate::server) use close_cursor::CloseCursorHandler;
 pub(in crate::server) use execute::ExecuteHandler;
+pub(in crate::server) use fetch::FetchHandler;
 pub(in crate::server) use get::GetHandler;
 pub(in crate::server) use handshake::HandshakeHandler;
 pub(in crate::server) use procedure_invoke::ProcedureInvokeHandler;
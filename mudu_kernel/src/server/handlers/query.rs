@@ -35,6 +35,7 @@ impl MessageHandler for QueryHandler {
             request.app_name(),
             request.sql(),
             request.params(),
+            request.fetch_size(),
             Some(digest),
         )
         .await
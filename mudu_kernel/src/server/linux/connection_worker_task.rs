@@ -1,5 +1,5 @@
 use crate::server::async_func_task::HandleResult;
-use crate::server::frame_dispatch::dispatch_frame_async;
+use crate::server::frame_dispatch::{dispatch_frame_async, release_read_buf, READ_BUF_CAPACITY};
 use crate::server::protocol_codec::{read_next_frame, write_response};
 use crate::server::worker::WorkerRuntime;
 use mudu::common::result::RS;
@@ -67,7 +67,7 @@ async fn _run_connection_worker_task(
     initial_response: Option<Vec<u8>>,
 ) -> RS<()> {
     mudu_utils::scoped_task_trace!();
-    let mut read_buf = Vec::with_capacity(8192);
+    let mut read_buf = Vec::with_capacity(READ_BUF_CAPACITY);
     trace!(
         conn_id,
         remote_addr = %remote_addr,
@@ -109,6 +109,9 @@ async fn _run_connection_worker_task(
                 watch_conn("conn.phase", "read_frame_error_close_socket");
                 trace!(conn_id, error = %err, "read protocol frame failed");
                 let _ = close(socket).await;
+                // A rejected frame ends the connection; drop its sessions and
+                // cursors as a peer close would.
+                let _ = worker.close_connection_sessions(conn_id);
                 return Err(err);
             }
         };
@@ -158,6 +161,7 @@ async fn _run_connection_worker_task(
         }
         watch_conn("conn.phase", "frame_done");
         read_buf = frame.into_payload();
+        release_read_buf(&mut read_buf);
     }
     trace!(conn_id, "io_uring connection worker stopped");
     Ok(())
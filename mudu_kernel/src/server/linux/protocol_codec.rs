@@ -4,6 +4,8 @@ use mudu::mudu_error;
 use mudu_contract::protocol::{Frame, FrameHeader, HEADER_LEN};
 use mudu_sys::io::socket::{recv_into, send_all, IoSocket};
 
+use crate::server::frame_dispatch::check_request_payload_len;
+
 pub(in crate::server) async fn read_next_frame(
     socket: &IoSocket,
     read_buf: &mut Vec<u8>,
@@ -15,6 +17,7 @@ pub(in crate::server) async fn read_next_frame(
         None => return Ok(None),
     }
     let header = FrameHeader::decode_header_bytes(&header_buf)?;
+    check_request_payload_len(&header)?;
     read_buf.clear();
     read_buf.resize(header.payload_len() as usize, 0);
     if !read_buf.is_empty() {
@@ -1,3 +1,4 @@
+use crate::server::replication::spawn_replication_source;
 use crate::server::server_launch::ServerLaunch;
 use crate::server::worker::{WorkerRuntime, WorkerRuntimeParams};
 use crate::server::worker_loop_stats::WorkerLoopStats;
@@ -44,6 +45,12 @@ pub(crate) fn sync_serve_iouring(
             "invalid io_uring worker count"
         ));
     }
+    if cfg.cfg().replicate_from().is_some() {
+        return Err(mudu_error!(
+            ErrorCode::NotImplemented,
+            "a standby server needs server_mode Tokio"
+        ));
+    }
     let sys = SysIoContext::iouring();
     let conn_id_alloc = Arc::new(AtomicU64::new(1));
     let mailboxes: Vec<_> = (0..cfg.cfg().worker_count())
@@ -55,6 +62,7 @@ pub(crate) fn sync_serve_iouring(
     let stop_flag = Arc::new(AtomicBool::new(false));
     let recovery_coordinator = Arc::new(RecoveryCoordinator::new(cfg.cfg().worker_count(), ready));
 
+    let replication_source = spawn_replication_source(&cfg, stop.clone())?;
     let stop_for_notifier = stop.clone();
     let shutdown_mailboxes = mailboxes.clone();
     let shutdown_mailbox_fds = mailbox_fds.clone();
@@ -114,6 +122,8 @@ pub(crate) fn sync_serve_iouring(
         let log_chunk_size = cfg.cfg().log_chunk_size();
         let log_batching = cfg.deps().log_batching();
         let wal_sync_policy = cfg.deps().wal_sync_policy();
+        let wal_archive_dir = cfg.deps().wal_archive_dir().map(str::to_string);
+        let auth = cfg.deps().auth();
         let worker_count = cfg.cfg().worker_count();
         let server_instance_id = cfg.cfg().server_instance_id();
         let listener = cfg.take_prebound_listener(worker_id);
@@ -183,10 +193,12 @@ pub(crate) fn sync_serve_iouring(
                             log_chunk_size,
                             log_batching,
                             wal_sync_policy,
+                            wal_archive_dir: wal_archive_dir.clone(),
                             procedure_runtime,
                             registry: worker_registry,
                             async_runtime,
                             server_instance_id,
+                            auth,
                         });
                         let worker = match drive_future_with_ring(
                             &mut ring,
@@ -250,6 +262,11 @@ pub(crate) fn sync_serve_iouring(
             .join()
             .map_err(|_| mudu_error!(ErrorCode::Thread, "join io_uring shutdown notifier error"))?;
         notify_result?;
+        if let Some(replication_source) = replication_source {
+            replication_source
+                .join()
+                .map_err(|_| mudu_error!(ErrorCode::Thread, "join replication source error"))??;
+        }
         log_worker_stats(&worker_stats);
     }
     for fd in mailbox_fds {
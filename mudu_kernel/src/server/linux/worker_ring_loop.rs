@@ -24,6 +24,7 @@ use crate::server::session_bound_worker_runtime::{
     as_worker_local_ref, new_session_bound_worker_runtime,
 };
 use crate::server::task;
+use crate::server::version_gc::VERSION_GC_INTERVAL;
 use crate::server::worker::WorkerRuntime;
 use crate::server::worker_local::{set_current_worker_local, unset_current_worker_local};
 use crate::server::worker_loop_stats::WorkerLoopStats;
@@ -95,6 +96,8 @@ pub(in crate::server) struct WorkerRingLoop {
     stats: WorkerLoopStats,
     fs_gc_next_due: mudu_sys::time::Instant,
     fs_gc_inflight: Arc<AtomicBool>,
+    version_gc_next_due: mudu_sys::time::Instant,
+    version_gc_inflight: Arc<AtomicBool>,
     page_flush_next_due: mudu_sys::time::Instant,
     page_flush_inflight: Arc<AtomicBool>,
 }
@@ -218,6 +221,8 @@ impl WorkerRingLoop {
             },
             fs_gc_next_due: mudu_sys::time::instant_now() + FS_GC_INTERVAL,
             fs_gc_inflight: Arc::new(AtomicBool::new(false)),
+            version_gc_next_due: mudu_sys::time::instant_now() + VERSION_GC_INTERVAL,
+            version_gc_inflight: Arc::new(AtomicBool::new(false)),
             page_flush_next_due: mudu_sys::time::instant_now() + DIRTY_PAGE_FLUSH_INTERVAL,
             page_flush_inflight: Arc::new(AtomicBool::new(false)),
         })
@@ -675,6 +680,31 @@ impl WorkerRingLoop {
         Ok(())
     }
 
+    /// Spawn one MVCC version GC round when the interval has elapsed; same
+    /// cadence and shutdown behavior as `submit_fs_gc_round_if_due`.
+    pub(in crate::server) fn submit_version_gc_round_if_due(&mut self) -> RS<()> {
+        if self.shutting_down || self.stop.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let now = mudu_sys::time::instant_now();
+        if *now < *self.version_gc_next_due || self.version_gc_inflight.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        self.version_gc_next_due = now + VERSION_GC_INTERVAL;
+        self.version_gc_inflight.store(true, Ordering::Relaxed);
+        let gc = self.worker.version_gc();
+        let inflight = self.version_gc_inflight.clone();
+        let worker_id = self.worker.worker_id();
+        self.spawn(None, async move {
+            if let Err(err) = gc.gc_round().await {
+                error!(worker_id, "version gc round failed, {}", err);
+            }
+            inflight.store(false, Ordering::Relaxed);
+            Ok(())
+        });
+        Ok(())
+    }
+
     /// Spawn one deferred data-page flush round when the interval has
     /// elapsed; same cadence mechanism as `submit_fs_gc_round_if_due`
     /// (io_uring worker tasks cannot sleep on the tokio timer). Unlike fs
@@ -698,6 +728,12 @@ impl WorkerRingLoop {
             if let Err(err) = flush_worker_dirty_pages(&worker).await {
                 error!(worker_id, "page flush round failed, {}", err);
             }
+            if let Err(err) = worker.resolve_cross_partition_transactions().await {
+                error!(
+                    worker_id,
+                    "cross-partition transaction resolution failed, {}", err
+                );
+            }
             inflight.store(false, Ordering::Relaxed);
             Ok(())
         });
@@ -909,6 +945,7 @@ mod tests {
     )]
 
     use super::*;
+    use crate::server::auth::ServerAuth;
     use crate::server::callback_registry::{CallbackDomain, CallbackEventKey, CallbackTrigger};
     use crate::server::worker::WorkerRuntimeParams;
     use crate::server::worker_registry::load_or_create_worker_registry;
@@ -998,10 +1035,12 @@ mod tests {
             log_chunk_size: 4096,
             log_batching: WorkerLogBatching::default(),
             wal_sync_policy: WalSyncPolicy::Commit,
+            wal_archive_dir: None,
             procedure_runtime: None,
             registry,
             async_runtime: None,
             server_instance_id: 0,
+            auth: Arc::new(ServerAuth::disabled()),
         })
         .await
         .unwrap();
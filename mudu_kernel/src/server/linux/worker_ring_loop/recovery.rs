@@ -1,7 +1,9 @@
 use super::*;
 use crate::wal::lsn::LSN;
 use crate::wal::typed_worker_log::WorkerLogRecoveryHandler;
-use crate::wal::worker_log::{ChunkedWorkerLogBackend, WorkerLogBackend, WorkerLogRecoverySource};
+use crate::wal::worker_log::{
+    ChunkedWorkerLogBackend, WalCheckpoint, WorkerLogBackend, WorkerLogRecoverySource,
+};
 use crate::wal::xl_batch::XLBatch;
 use async_trait::async_trait;
 use std::path::{Path, PathBuf};
@@ -16,6 +18,10 @@ impl WorkerLogRecoveryHandler<XLBatch> for WorkerRingLoopRecoveryHandler {
         self.worker.replay_log_batch(entry).await
     }
 
+    async fn restore_checkpoint(&self, checkpoint: &WalCheckpoint) -> RS<()> {
+        self.worker.restore_log_checkpoint(checkpoint)
+    }
+
     fn finish(&self) -> RS<()> {
         self.worker.finish_log_recovery()
     }
@@ -37,6 +43,13 @@ impl WorkerLogRecoverySource for WorkerRingLoopRecoverySource {
     async fn read_chunk(&self, path: &Path) -> RS<Vec<u8>> {
         Ok(self.backend.fs().read_all(path).await?)
     }
+
+    async fn checkpoint(&self) -> RS<Option<WalCheckpoint>> {
+        self.backend
+            .layout()?
+            .read_checkpoint_async(self.backend.fs().as_ref())
+            .await
+    }
 }
 
 impl WorkerRingLoop {
@@ -16,9 +16,11 @@ impl WorkerRingLoop {
     /// blocking and continues polling instead of letting one long poll round
     /// delay every pending completion (e.g. a WAL fsync CQE) behind it.
     pub(super) fn run_service_loop(&mut self) -> RS<WorkerLoopStats> {
+        let _export = loop_stats::export_worker(self.worker.worker_id())?;
         loop {
             crate::server::stage_stats::dump_if_due(self.worker.worker_id());
             loop_stats::dump_if_due(self.worker.worker_id());
+            loop_stats::publish_worker_stats(&self.stats);
             loop_stats::count(LoopCounter::Iterations);
             if self.stop.load(Ordering::Relaxed) || self.shutdown_triggered.load(Ordering::Relaxed)
             {
@@ -55,6 +57,7 @@ impl WorkerRingLoop {
             self.submit_mailbox_read_if_needed()?;
             self.submit_accept_if_needed()?;
             self.submit_fs_gc_round_if_due()?;
+            self.submit_version_gc_round_if_due()?;
             self.submit_page_flush_round_if_due()?;
             self.submit_user_ring_io_if_needed()?;
             self.stats.submit_calls += 1;
@@ -5,17 +5,29 @@
 //! polling, task polling, ring submit, CQE wait) are timed with an RAII
 //! [`LoopGuard`] and accumulated into thread-local counters, alongside a few
 //! counter-only events (iterations, budget-exhausted poll slices, wait
-//! timeouts, 1ms idle sleeps, processed CQEs). Each worker event loop
+//! timeouts, 1ms idle sleeps, processed CQEs) and two gauges for the worker
+//! checkpoint (age of the last checkpoint, WAL bytes left on disk after it;
+//! see [`record_wal_checkpoint`]). Each worker event loop
 //! periodically calls [`dump_if_due`], which logs the aggregated numbers and
 //! resets them.
 //!
 //! This mirrors the `MUDU_STAGE_STATS` mechanism in
 //! [`crate::server::stage_stats`]; when the environment variable is not set,
 //! the only per-span cost is one branch on a cached static flag.
+//!
+//! Independently of the environment variable, a worker loop registered with
+//! [`export_worker`] also adds its counters, timed phases, checkpoint WAL
+//! size and [`WorkerLoopStats`] into monotonic totals that
+//! [`crate::server::metrics`] exposes to scrapers.
 
+use crate::server::metrics::Exposition;
+use crate::server::worker_loop_stats::WorkerLoopStats;
+use mudu::common::result::RS;
+use mudu_sys::sync::SMutex;
 use mudu_sys::time::{instant_now, Instant};
-use std::cell::Cell;
-use std::sync::OnceLock;
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 
 /// Timed phases of one `run_service_loop` iteration.
@@ -76,6 +88,10 @@ const COUNTER_NAMES: [&str; COUNTER_COUNT] = [
 
 const DUMP_INTERVAL: Duration = Duration::from_secs(10);
 
+/// How often a worker loop copies its [`WorkerLoopStats`] into its exported
+/// totals.
+const PUBLISH_INTERVAL: Duration = Duration::from_secs(1);
+
 thread_local! {
     static PHASE_ACCUM: [Cell<u64>; PHASE_COUNT] = const {
         [const { Cell::new(0) }; PHASE_COUNT]
@@ -87,6 +103,165 @@ thread_local! {
         [const { Cell::new(0) }; COUNTER_COUNT]
     };
     static LAST_DUMP: Cell<Option<Instant>> = const { Cell::new(None) };
+    static LAST_CHECKPOINT: Cell<Option<Instant>> = const { Cell::new(None) };
+    static WAL_BYTES: Cell<u64> = const { Cell::new(0) };
+    static TOTALS: RefCell<Option<Arc<LoopTotals>>> = const { RefCell::new(None) };
+    static LAST_PUBLISH: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// Monotonic totals of one worker loop, readable from any thread.
+struct LoopTotals {
+    worker_id: u128,
+    counters: [AtomicU64; COUNTER_COUNT],
+    phase_calls: [AtomicU64; PHASE_COUNT],
+    phase_ns: [AtomicU64; PHASE_COUNT],
+    wal_bytes: AtomicU64,
+    io: SMutex<WorkerLoopStats>,
+}
+
+fn exported_loops() -> &'static SMutex<Vec<Arc<LoopTotals>>> {
+    static LOOPS: OnceLock<SMutex<Vec<Arc<LoopTotals>>>> = OnceLock::new();
+    LOOPS.get_or_init(|| SMutex::new(Vec::new()))
+}
+
+fn with_totals(f: impl FnOnce(&LoopTotals)) {
+    TOTALS.with(|totals| {
+        if let Ok(totals) = totals.try_borrow() {
+            if let Some(totals) = totals.as_ref() {
+                f(totals);
+            }
+        }
+    });
+}
+
+/// Keeps the calling thread's loop totals exported until dropped.
+pub(crate) struct LoopExport {
+    totals: Arc<LoopTotals>,
+}
+
+/// Exports the loop totals of the calling thread under `worker_id` until
+/// the returned guard is dropped.
+pub(crate) fn export_worker(worker_id: u128) -> RS<LoopExport> {
+    let totals = Arc::new(LoopTotals {
+        worker_id,
+        counters: [const { AtomicU64::new(0) }; COUNTER_COUNT],
+        phase_calls: [const { AtomicU64::new(0) }; PHASE_COUNT],
+        phase_ns: [const { AtomicU64::new(0) }; PHASE_COUNT],
+        wal_bytes: AtomicU64::new(0),
+        io: SMutex::new(WorkerLoopStats::default()),
+    });
+    exported_loops().lock()?.push(totals.clone());
+    TOTALS.with(|current| *current.borrow_mut() = Some(totals.clone()));
+    Ok(LoopExport { totals })
+}
+
+impl Drop for LoopExport {
+    fn drop(&mut self) {
+        TOTALS.with(|current| {
+            if let Ok(mut current) = current.try_borrow_mut() {
+                *current = None;
+            }
+        });
+        if let Ok(mut loops) = exported_loops().lock() {
+            loops.retain(|totals| !Arc::ptr_eq(totals, &self.totals));
+        }
+    }
+}
+
+/// Copies `stats` into the exported totals of the calling thread, at most
+/// once every [`PUBLISH_INTERVAL`].
+pub(in crate::server) fn publish_worker_stats(stats: &WorkerLoopStats) {
+    let due = LAST_PUBLISH.with(|last| match last.get() {
+        Some(previous) => previous.elapsed() >= PUBLISH_INTERVAL,
+        None => true,
+    });
+    if !due {
+        return;
+    }
+    LAST_PUBLISH.with(|last| last.set(Some(instant_now())));
+    with_totals(|totals| {
+        if let Ok(mut io) = totals.io.lock() {
+            *io = stats.clone();
+        }
+    });
+}
+
+/// Appends the totals of every exported worker loop to `out`.
+pub(crate) fn write_metrics(out: &mut Exposition) -> RS<()> {
+    let mut loops = exported_loops().lock()?.clone();
+    loops.sort_by_key(|totals| totals.worker_id);
+    let workers: Vec<String> = loops
+        .iter()
+        .map(|totals| totals.worker_id.to_string())
+        .collect();
+
+    out.family(
+        "mudu_worker_loop_events_total",
+        "counter",
+        "Worker event-loop events.",
+    );
+    for (totals, worker) in loops.iter().zip(&workers) {
+        for (index, name) in COUNTER_NAMES.iter().enumerate() {
+            out.sample(
+                "mudu_worker_loop_events_total",
+                &[("worker", worker.as_str()), ("event", *name)],
+                totals.counters[index].load(Ordering::Relaxed),
+            );
+        }
+    }
+    out.family(
+        "mudu_worker_loop_phase_calls_total",
+        "counter",
+        "Timed worker event-loop phases; recorded only with MUDU_LOOP_STATS=1.",
+    );
+    out.family(
+        "mudu_worker_loop_phase_seconds_total",
+        "counter",
+        "Time spent in worker event-loop phases; recorded only with MUDU_LOOP_STATS=1.",
+    );
+    for (totals, worker) in loops.iter().zip(&workers) {
+        for (index, name) in PHASE_NAMES.iter().enumerate() {
+            let labels = [("worker", worker.as_str()), ("phase", *name)];
+            out.sample(
+                "mudu_worker_loop_phase_calls_total",
+                &labels,
+                totals.phase_calls[index].load(Ordering::Relaxed),
+            );
+            out.sample(
+                "mudu_worker_loop_phase_seconds_total",
+                &labels,
+                totals.phase_ns[index].load(Ordering::Relaxed) as f64 / 1e9,
+            );
+        }
+    }
+    out.family(
+        "mudu_worker_io_events_total",
+        "counter",
+        "Worker io_uring submissions, completions and queue operations.",
+    );
+    for (totals, worker) in loops.iter().zip(&workers) {
+        let io = totals.io.lock()?.clone();
+        for (name, value) in io.counters() {
+            out.sample(
+                "mudu_worker_io_events_total",
+                &[("worker", worker.as_str()), ("event", name)],
+                value,
+            );
+        }
+    }
+    out.family(
+        "mudu_worker_wal_retained_bytes",
+        "gauge",
+        "WAL bytes left on disk after the last worker checkpoint.",
+    );
+    for (totals, worker) in loops.iter().zip(&workers) {
+        out.sample(
+            "mudu_worker_wal_retained_bytes",
+            &[("worker", worker.as_str())],
+            totals.wal_bytes.load(Ordering::Relaxed),
+        );
+    }
+    Ok(())
 }
 
 fn stats_enabled() -> bool {
@@ -124,27 +299,56 @@ impl Drop for LoopGuard {
         };
         let elapsed_ns = start.elapsed().as_nanos() as u64;
         let index = self.phase as usize;
+        with_totals(|totals| {
+            totals.phase_calls[index].fetch_add(1, Ordering::Relaxed);
+            totals.phase_ns[index].fetch_add(elapsed_ns, Ordering::Relaxed);
+        });
         PHASE_ACCUM.with(|accum| accum[index].set(accum[index].get() + 1));
         PHASE_TOTAL_NS.with(|totals| totals[index].set(totals[index].get() + elapsed_ns));
     }
 }
 
-/// Increments a counter-only loop event by one. One branch on the cached
-/// flag when stats are disabled.
+/// Increments a counter-only loop event by one.
 pub(crate) fn count(counter: LoopCounter) {
     count_by(counter, 1);
 }
 
-/// Increments a counter-only loop event by `n`. One branch on the cached
-/// flag when stats are disabled.
+/// Increments a counter-only loop event by `n`. Besides the exported totals,
+/// one branch on the cached flag when stats are disabled.
 pub(crate) fn count_by(counter: LoopCounter, n: u64) {
+    let index = counter as usize;
+    with_totals(|totals| {
+        totals.counters[index].fetch_add(n, Ordering::Relaxed);
+    });
     if !stats_enabled() {
         return;
     }
-    let index = counter as usize;
     COUNTERS.with(|counters| counters[index].set(counters[index].get() + n));
 }
 
+/// Records a finished worker checkpoint and the size of the WAL chunks it
+/// left on disk. Unlike the counters these gauges are not reset by
+/// [`dump_if_due`]; every dump reports the checkpoint age at dump time.
+pub(crate) fn record_wal_checkpoint(wal_bytes: u64) {
+    with_totals(|totals| totals.wal_bytes.store(wal_bytes, Ordering::Relaxed));
+    if !stats_enabled() {
+        return;
+    }
+    LAST_CHECKPOINT.with(|last| last.set(Some(instant_now())));
+    WAL_BYTES.with(|bytes| bytes.set(wal_bytes));
+}
+
+// ` checkpoint_age_ms=.. wal_bytes=..` once a checkpoint was recorded on
+// this thread.
+fn checkpoint_gauges() -> Option<String> {
+    let last = LAST_CHECKPOINT.with(Cell::get)?;
+    let wal_bytes = WAL_BYTES.with(Cell::get);
+    Some(format!(
+        " checkpoint_age_ms={} wal_bytes={wal_bytes}",
+        last.elapsed().as_millis()
+    ))
+}
+
 /// Logs the aggregated loop counters (one line per worker thread) and
 /// resets them, at most once every [`DUMP_INTERVAL`]. Intended to be called
 /// from each worker's event-loop iteration; it is a no-op when stats are
@@ -186,6 +390,9 @@ pub(crate) fn dump_if_due(worker_id: u128) {
             }
         });
     });
+    if let Some(gauges) = checkpoint_gauges() {
+        line.push_str(&gauges);
+    }
     if !line.is_empty() {
         tracing::info!(worker_id, "loop_stats:{line}");
     }
@@ -246,4 +453,20 @@ mod tests {
         let after = COUNTERS.with(|counters| counters[index].get());
         assert_eq!(after, before, "disabled stats must not record");
     }
+
+    #[test]
+    fn checkpoint_gauges_report_age_and_wal_bytes() {
+        record_wal_checkpoint(4096);
+        assert!(
+            LAST_CHECKPOINT.with(Cell::get).is_none(),
+            "disabled stats must not record"
+        );
+        assert!(checkpoint_gauges().is_none());
+
+        LAST_CHECKPOINT.with(|last| last.set(Some(instant_now())));
+        WAL_BYTES.with(|bytes| bytes.set(4096));
+        let gauges = checkpoint_gauges().unwrap();
+        assert!(gauges.starts_with(" checkpoint_age_ms="), "{gauges}");
+        assert!(gauges.ends_with(" wal_bytes=4096"), "{gauges}");
+    }
 }
@@ -2,8 +2,9 @@ use std::sync::OnceLock;
 
 use crate::server::async_func_task::HandleResult;
 use crate::server::handlers::{
-    BatchHandler, ExecuteHandler, GetHandler, HandshakeHandler, ProcedureInvokeHandler, PutHandler,
-    QueryHandler, RangeScanHandler, SessionCloseHandler, SessionCreateHandler,
+    AuthHandler, BatchHandler, ChangeFeedHandler, CloseCursorHandler, ExecuteHandler, FetchHandler,
+    GetHandler, HandshakeHandler, ProcedureInvokeHandler, PutHandler, QueryHandler,
+    RangeScanHandler, SessionCloseHandler, SessionCreateHandler,
 };
 use crate::server::request_ctx::RequestCtx;
 use async_trait::async_trait;
@@ -30,6 +31,7 @@ impl MessageDispatcher {
     fn new() -> Self {
         let mut handlers: Vec<(MessageType, Box<dyn MessageHandler>)> = Vec::new();
         register(&mut handlers, Box::new(HandshakeHandler));
+        register(&mut handlers, Box::new(AuthHandler));
         register(&mut handlers, Box::new(QueryHandler));
         register(&mut handlers, Box::new(ExecuteHandler));
         register(&mut handlers, Box::new(BatchHandler));
@@ -39,6 +41,9 @@ impl MessageDispatcher {
         register(&mut handlers, Box::new(ProcedureInvokeHandler));
         register(&mut handlers, Box::new(SessionCreateHandler));
         register(&mut handlers, Box::new(SessionCloseHandler));
+        register(&mut handlers, Box::new(ChangeFeedHandler));
+        register(&mut handlers, Box::new(FetchHandler));
+        register(&mut handlers, Box::new(CloseCursorHandler));
         Self { handlers }
     }
 
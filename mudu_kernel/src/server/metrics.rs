@@ -0,0 +1,390 @@
+//! Process-wide server metrics in the Prometheus text exposition format.
+//!
+//! Unlike [`crate::server::stage_stats`] and [`crate::server::loop_stats`],
+//! which log per-thread numbers and reset them, the values here are
+//! monotonic for the lifetime of the process and readable from any thread,
+//! so an HTTP scraper can sample them at its own pace. Recording is always
+//! on; every event costs a few relaxed atomic adds.
+//!
+//! [`render`] produces the whole exposition: commit and lock-wait latency
+//! histograms, WAL write and fsync counters, procedure invocations per
+//! `app/module/procedure`, session counts and the per-worker loop totals
+//! exported by [`crate::server::loop_stats`].
+
+use mudu::common::result::RS;
+use scc::HashMap as SccHashMap;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+/// Upper bounds (seconds) of the latency histogram buckets; observations
+/// above the last bound only land in the implicit `+Inf` bucket.
+const LATENCY_BUCKETS: [f64; 14] = [
+    0.0001, 0.00025, 0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 5.0,
+];
+
+const BUCKET_SLOTS: usize = LATENCY_BUCKETS.len() + 1;
+
+/// Procedure series kept at most; invocations of further distinct names are
+/// not recorded, so clients calling arbitrary names cannot grow the
+/// exposition without bound.
+const MAX_PROCEDURE_SERIES: usize = 4096;
+
+/// Latency histogram with fixed [`LATENCY_BUCKETS`].
+pub(crate) struct Histogram {
+    /// Per-bucket (non-cumulative) observation counts; the last slot is the
+    /// `+Inf` overflow bucket.
+    buckets: [AtomicU64; BUCKET_SLOTS],
+    sum_ns: AtomicU64,
+}
+
+impl Histogram {
+    pub(crate) const fn new() -> Self {
+        Self {
+            buckets: [const { AtomicU64::new(0) }; BUCKET_SLOTS],
+            sum_ns: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn observe(&self, elapsed: Duration) {
+        let secs = elapsed.as_secs_f64();
+        let index = LATENCY_BUCKETS
+            .iter()
+            .position(|bound| secs <= *bound)
+            .unwrap_or(LATENCY_BUCKETS.len());
+        self.buckets[index].fetch_add(1, Ordering::Relaxed);
+        self.sum_ns
+            .fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    fn write(&self, out: &mut Exposition, name: &str, labels: &[(&str, &str)]) {
+        let mut cumulative = 0u64;
+        for (index, bucket) in self.buckets.iter().enumerate() {
+            cumulative += bucket.load(Ordering::Relaxed);
+            let le = match LATENCY_BUCKETS.get(index) {
+                Some(bound) => bound.to_string(),
+                None => "+Inf".to_string(),
+            };
+            let mut bucket_labels = labels.to_vec();
+            bucket_labels.push(("le", &le));
+            out.sample(&format!("{name}_bucket"), &bucket_labels, cumulative);
+        }
+        let sum = self.sum_ns.load(Ordering::Relaxed) as f64 / 1e9;
+        out.sample(&format!("{name}_sum"), labels, sum);
+        out.sample(&format!("{name}_count"), labels, cumulative);
+    }
+}
+
+struct ServerMetrics {
+    commit_latency: Histogram,
+    commit_failures: AtomicU64,
+    wal_written_bytes: AtomicU64,
+    wal_write_rounds: AtomicU64,
+    wal_fsyncs: AtomicU64,
+    lock_wait_latency: Histogram,
+    lock_wait_timeouts: AtomicU64,
+    sessions_opened: AtomicU64,
+    sessions_active: AtomicI64,
+}
+
+static METRICS: ServerMetrics = ServerMetrics {
+    commit_latency: Histogram::new(),
+    commit_failures: AtomicU64::new(0),
+    wal_written_bytes: AtomicU64::new(0),
+    wal_write_rounds: AtomicU64::new(0),
+    wal_fsyncs: AtomicU64::new(0),
+    lock_wait_latency: Histogram::new(),
+    lock_wait_timeouts: AtomicU64::new(0),
+    sessions_opened: AtomicU64::new(0),
+    sessions_active: AtomicI64::new(0),
+};
+
+/// `(app, module, procedure)` of a procedure series.
+type ProcedureKey = (String, String, String);
+
+struct ProcedureMetrics {
+    errors: AtomicU64,
+    latency: Histogram,
+}
+
+fn procedures() -> &'static SccHashMap<ProcedureKey, Arc<ProcedureMetrics>> {
+    static PROCEDURES: OnceLock<SccHashMap<ProcedureKey, Arc<ProcedureMetrics>>> = OnceLock::new();
+    PROCEDURES.get_or_init(SccHashMap::new)
+}
+
+/// Records one finished transaction commit (including read-only commits
+/// and commits rejected by validation).
+pub(crate) fn record_commit(elapsed: Duration, committed: bool) {
+    METRICS.commit_latency.observe(elapsed);
+    if !committed {
+        METRICS.commit_failures.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Records one WAL write round that wrote `bytes` to the log chunks.
+pub(crate) fn record_wal_write(bytes: u64) {
+    METRICS.wal_write_rounds.fetch_add(1, Ordering::Relaxed);
+    METRICS
+        .wal_written_bytes
+        .fetch_add(bytes, Ordering::Relaxed);
+}
+
+/// Records `count` completed fsyncs of WAL chunk files.
+pub(crate) fn record_wal_fsyncs(count: u64) {
+    METRICS.wal_fsyncs.fetch_add(count, Ordering::Relaxed);
+}
+
+/// Records one lock acquisition that had to wait, whether it was granted or
+/// timed out.
+pub(crate) fn record_lock_wait(elapsed: Duration, acquired: bool) {
+    METRICS.lock_wait_latency.observe(elapsed);
+    if !acquired {
+        METRICS.lock_wait_timeouts.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+pub(crate) fn record_session_opened() {
+    METRICS.sessions_opened.fetch_add(1, Ordering::Relaxed);
+    METRICS.sessions_active.fetch_add(1, Ordering::Relaxed);
+}
+
+pub(crate) fn record_session_closed() {
+    METRICS.sessions_active.fetch_sub(1, Ordering::Relaxed);
+}
+
+/// Records one invocation of `procedure_name` (`app/module/procedure`).
+/// Names that do not have that shape are not recorded.
+pub(crate) fn record_procedure(procedure_name: &str, elapsed: Duration, ok: bool) {
+    let mut segments = procedure_name.split('/');
+    let (Some(app), Some(module), Some(procedure), None) = (
+        segments.next(),
+        segments.next(),
+        segments.next(),
+        segments.next(),
+    ) else {
+        return;
+    };
+    let key = (app.to_string(), module.to_string(), procedure.to_string());
+    let series = match procedures().get_sync(&key) {
+        Some(entry) => entry.get().clone(),
+        None => {
+            if procedures().len() >= MAX_PROCEDURE_SERIES {
+                return;
+            }
+            procedures()
+                .entry_sync(key)
+                .or_insert_with(|| {
+                    Arc::new(ProcedureMetrics {
+                        errors: AtomicU64::new(0),
+                        latency: Histogram::new(),
+                    })
+                })
+                .get()
+                .clone()
+        }
+    };
+    series.latency.observe(elapsed);
+    if !ok {
+        series.errors.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Renders every metric in the Prometheus text exposition format
+/// (version 0.0.4).
+pub fn render() -> RS<String> {
+    let mut out = Exposition::default();
+
+    out.family(
+        "mudu_commit_duration_seconds",
+        "histogram",
+        "Latency of transaction commits.",
+    );
+    METRICS
+        .commit_latency
+        .write(&mut out, "mudu_commit_duration_seconds", &[]);
+    out.counter(
+        "mudu_commit_failures_total",
+        "Commits that returned an error.",
+        METRICS.commit_failures.load(Ordering::Relaxed),
+    );
+
+    out.counter(
+        "mudu_wal_written_bytes_total",
+        "Bytes written to WAL chunk files.",
+        METRICS.wal_written_bytes.load(Ordering::Relaxed),
+    );
+    out.counter(
+        "mudu_wal_write_rounds_total",
+        "WAL group-commit write rounds.",
+        METRICS.wal_write_rounds.load(Ordering::Relaxed),
+    );
+    out.counter(
+        "mudu_wal_fsyncs_total",
+        "Completed fsyncs of WAL chunk files.",
+        METRICS.wal_fsyncs.load(Ordering::Relaxed),
+    );
+
+    out.family(
+        "mudu_lock_wait_duration_seconds",
+        "histogram",
+        "Time lock requests spent waiting for conflicting holders.",
+    );
+    METRICS
+        .lock_wait_latency
+        .write(&mut out, "mudu_lock_wait_duration_seconds", &[]);
+    out.counter(
+        "mudu_lock_wait_timeouts_total",
+        "Lock waits that timed out.",
+        METRICS.lock_wait_timeouts.load(Ordering::Relaxed),
+    );
+
+    out.family("mudu_sessions_active", "gauge", "Sessions currently open.");
+    out.sample(
+        "mudu_sessions_active",
+        &[],
+        METRICS.sessions_active.load(Ordering::Relaxed),
+    );
+    out.counter(
+        "mudu_sessions_opened_total",
+        "Sessions opened since start.",
+        METRICS.sessions_opened.load(Ordering::Relaxed),
+    );
+
+    write_procedures(&mut out);
+    crate::server::replication::write_metrics(&mut out)?;
+    crate::server::loop_stats::write_metrics(&mut out)?;
+    Ok(out.text)
+}
+
+fn write_procedures(out: &mut Exposition) {
+    let mut series = Vec::new();
+    procedures().iter_sync(|key, metrics| {
+        series.push((key.clone(), metrics.clone()));
+        true
+    });
+    series.sort_by(|(left, _), (right, _)| left.cmp(right));
+
+    out.family(
+        "mudu_procedure_duration_seconds",
+        "histogram",
+        "Latency of procedure invocations.",
+    );
+    for ((app, module, procedure), metrics) in &series {
+        let labels = [
+            ("app", app.as_str()),
+            ("module", module.as_str()),
+            ("procedure", procedure.as_str()),
+        ];
+        metrics
+            .latency
+            .write(out, "mudu_procedure_duration_seconds", &labels);
+    }
+    out.family(
+        "mudu_procedure_errors_total",
+        "counter",
+        "Procedure invocations that returned an error.",
+    );
+    for ((app, module, procedure), metrics) in &series {
+        let labels = [
+            ("app", app.as_str()),
+            ("module", module.as_str()),
+            ("procedure", procedure.as_str()),
+        ];
+        out.sample(
+            "mudu_procedure_errors_total",
+            &labels,
+            metrics.errors.load(Ordering::Relaxed),
+        );
+    }
+}
+
+/// Text exposition under construction.
+#[derive(Default)]
+pub(crate) struct Exposition {
+    text: String,
+}
+
+impl Exposition {
+    /// Starts a metric family with its `HELP` and `TYPE` lines.
+    pub(crate) fn family(&mut self, name: &str, kind: &str, help: &str) {
+        self.text
+            .push_str(&format!("# HELP {name} {help}\n# TYPE {name} {kind}\n"));
+    }
+
+    pub(crate) fn sample(
+        &mut self,
+        name: &str,
+        labels: &[(&str, &str)],
+        value: impl std::fmt::Display,
+    ) {
+        self.text.push_str(name);
+        if !labels.is_empty() {
+            let labels: Vec<String> = labels
+                .iter()
+                .map(|(label, value)| format!("{label}=\"{}\"", escape_label(value)))
+                .collect();
+            self.text.push_str(&format!("{{{}}}", labels.join(",")));
+        }
+        self.text.push_str(&format!(" {value}\n"));
+    }
+
+    /// A counter family with a single unlabelled sample.
+    fn counter(&mut self, name: &str, help: &str, value: u64) {
+        self.family(name, "counter", help);
+        self.sample(name, &[], value);
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+    use super::*;
+
+    #[test]
+    fn histogram_renders_cumulative_buckets() {
+        let histogram = Histogram::new();
+        histogram.observe(Duration::from_micros(50));
+        histogram.observe(Duration::from_millis(3));
+        histogram.observe(Duration::from_secs(9));
+        let mut out = Exposition::default();
+        histogram.write(&mut out, "latency_seconds", &[("op", "x")]);
+        let lines: Vec<&str> = out.text.lines().collect();
+        assert_eq!(lines[0], "latency_seconds_bucket{op=\"x\",le=\"0.0001\"} 1");
+        assert!(lines.contains(&"latency_seconds_bucket{op=\"x\",le=\"0.005\"} 2"));
+        assert!(lines.contains(&"latency_seconds_bucket{op=\"x\",le=\"5\"} 2"));
+        assert!(lines.contains(&"latency_seconds_bucket{op=\"x\",le=\"+Inf\"} 3"));
+        assert!(lines.contains(&"latency_seconds_count{op=\"x\"} 3"));
+        assert!(lines.contains(&"latency_seconds_sum{op=\"x\"} 9.00305"));
+    }
+
+    #[test]
+    fn label_values_are_escaped() {
+        let mut out = Exposition::default();
+        out.sample("m", &[("name", "a\"b\\c\nd")], 1);
+        assert_eq!(out.text, "m{name=\"a\\\"b\\\\c\\nd\"} 1\n");
+    }
+
+    #[test]
+    fn render_reports_procedures_by_app_module_and_name() {
+        record_procedure("metrics_app/orders/place", Duration::from_millis(2), true);
+        record_procedure("metrics_app/orders/place", Duration::from_millis(4), false);
+        record_procedure("not-a-procedure-path", Duration::from_millis(1), true);
+        let text = render().unwrap();
+        let labels = "app=\"metrics_app\",module=\"orders\",procedure=\"place\"";
+        assert!(text.contains(&format!(
+            "mudu_procedure_duration_seconds_count{{{labels}}} 2\n"
+        )));
+        assert!(text.contains(&format!("mudu_procedure_errors_total{{{labels}}} 1\n")));
+        assert!(!text.contains("not-a-procedure-path"));
+        assert!(text.contains("# TYPE mudu_commit_duration_seconds histogram\n"));
+        assert!(text.contains("# TYPE mudu_sessions_active gauge\n"));
+    }
+}
@@ -9,13 +9,30 @@
 #![allow(clippy::module_inception)]
 pub mod async_func_runtime;
 mod async_func_task;
+pub mod auth;
+#[cfg(test)]
+mod auth_test;
+pub mod backup;
+#[cfg(all(test, not(miri)))]
+mod backup_test;
 #[cfg(all(test, target_os = "linux"))]
 #[path = "linux/callback_registry.rs"]
 mod callback_registry;
+pub mod change_feed;
+#[cfg(all(test, not(miri)))]
+mod change_feed_test;
 pub mod connection_state;
 #[cfg(target_os = "linux")]
 #[path = "linux/connection_worker_task.rs"]
 mod connection_worker_task;
+#[cfg(all(test, not(miri)))]
+pub mod constraint_e2e_test;
+#[cfg(all(test, not(miri)))]
+pub(crate) mod e2e_test_util;
+#[cfg(all(test, not(miri)))]
+pub mod explain_e2e_test;
+#[cfg(all(test, not(miri)))]
+pub mod expression_e2e_test;
 mod frame_dispatch;
 #[cfg(all(test, not(miri)))]
 pub mod fs_e2e_test;
@@ -44,6 +61,9 @@ pub mod message_bus_api;
 mod message_bus_runtime;
 mod message_bus_state;
 mod message_dispatcher;
+pub mod metrics;
+#[cfg(all(test, not(miri)))]
+pub mod paging_e2e_test;
 pub mod partition_router;
 mod partition_rpc;
 #[cfg(all(test, target_os = "linux"))]
@@ -56,9 +76,18 @@ mod procedure_runtimes;
 #[cfg(target_os = "linux")]
 #[path = "linux/protocol_codec.rs"]
 mod protocol_codec;
+pub(crate) mod query_cursor;
+#[cfg(test)]
+mod query_cursor_test;
+pub mod replication;
+#[cfg(all(test, not(miri)))]
+mod replication_test;
 mod request_ctx;
 mod request_response_worker;
+mod route_fence;
 pub mod routing;
+#[cfg(all(test, not(miri)))]
+pub mod select_e2e_test;
 pub mod server;
 pub mod server_cfg;
 #[cfg(target_os = "linux")]
@@ -74,6 +103,11 @@ mod task;
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
 pub(crate) mod test_meta_mgr;
+#[cfg(all(test, not(miri)))]
+pub mod upsert_e2e_test;
+pub(crate) mod version_gc;
+#[cfg(all(test, not(miri)))]
+pub mod where_e2e_test;
 pub mod worker;
 pub mod worker_local;
 mod worker_loop_stats;
@@ -86,6 +120,7 @@ pub mod worker_registry;
 mod worker_ring_loop;
 mod worker_session_manager;
 pub mod worker_snapshot;
+mod worker_ssi;
 mod worker_storage;
 mod worker_tx_manager;
 pub mod x_contract;
@@ -0,0 +1,196 @@
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::todo,
+    clippy::unimplemented
+)]
+//! End-to-end tests for paged results: query cursors driven by `Query` with
+//! a fetch size, `Fetch` and `CloseCursor`, and range scans following their
+//! continuation, all as encoded frames through `dispatch_frame_async` on a
+//! real single-worker [`WorkerRuntime`].
+//!
+//! Miri cannot execute the tree-sitter FFI behind SQL parsing, so the whole
+//! module is excluded under Miri (see `mod.rs`).
+
+use mudu::common::id::OID;
+use mudu::common::result::RS;
+use mudu::error::ErrorCode;
+use mudu_contract::protocol::{
+    decode_close_cursor_response, decode_fetch_response, decode_range_scan_response,
+    encode_client_request, encode_close_cursor_request, encode_fetch_request,
+    encode_range_scan_request, ClientRequest, CloseCursorRequest, FetchRequest, FetchResponse,
+    Frame, RangeScanRequest,
+};
+use mudu_contract::tuple::tuple_value::TupleValue;
+
+use crate::server::async_func_task::HandleResult;
+use crate::server::e2e_test_util::{build_worker, TestDirs};
+use crate::server::frame_dispatch::dispatch_frame_async;
+use crate::server::session_bound_worker_runtime::new_session_bound_worker_runtime;
+use crate::server::worker::WorkerRuntime;
+use crate::server::worker_local::WorkerLocal;
+
+const CONN_ID: u64 = 1;
+
+/// Dispatch one encoded request frame and return the response frame.
+async fn dispatch(worker: &WorkerRuntime, encoded: RS<Vec<u8>>) -> RS<Frame> {
+    let frame = Frame::decode(&encoded.unwrap()).unwrap();
+    let HandleResult::Response(payload) = dispatch_frame_async(worker, CONN_ID, &frame).await?;
+    Ok(Frame::decode(&payload).unwrap())
+}
+
+async fn query_page(worker: &WorkerRuntime, session: OID, sql: &str) -> FetchResponse {
+    let request = ClientRequest::new_with_oid(session, "app", sql).with_fetch_size(2);
+    let frame = dispatch(worker, encode_client_request(1, &request))
+        .await
+        .unwrap();
+    decode_fetch_response(&frame).unwrap()
+}
+
+async fn fetch(worker: &WorkerRuntime, session: OID, cursor_id: u64) -> RS<FetchResponse> {
+    let request = FetchRequest::new(session, cursor_id, 0);
+    let frame = dispatch(worker, encode_fetch_request(2, &request)).await?;
+    Ok(decode_fetch_response(&frame).unwrap())
+}
+
+fn ids(rows: &[TupleValue]) -> Vec<i32> {
+    rows.iter().map(|row| row.values()[0].to_i32()).collect()
+}
+
+/// Follow the continuation of a paged scan of `[a, z)` to its end and return
+/// the keys of every page.
+async fn scan_pages(worker: &WorkerRuntime, session: OID) -> Vec<Vec<Vec<u8>>> {
+    let mut pages = Vec::new();
+    let mut continuation = None;
+    loop {
+        let request =
+            RangeScanRequest::new(session, b"a".to_vec(), b"z".to_vec()).with_page(2, continuation);
+        let frame = dispatch(worker, encode_range_scan_request(3, &request))
+            .await
+            .unwrap();
+        let (items, next) = decode_range_scan_response(&frame).unwrap().into_parts();
+        pages.push(items.into_iter().map(|item| item.key().to_vec()).collect());
+        match next {
+            Some(next) => continuation = Some(next),
+            None => return pages,
+        }
+    }
+}
+
+#[test]
+fn paging_e2e_query_cursor() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let dirs = TestDirs::new("paging_e2e_query_cursor");
+        let worker = build_worker(&dirs).await;
+        let session = worker.create_session(CONN_ID).unwrap();
+        let local_arc = new_session_bound_worker_runtime(worker.clone(), session);
+        let local: &dyn WorkerLocal = local_arc.as_ref();
+        local
+            .execute(
+                session,
+                Box::new("CREATE TABLE t (id INTEGER PRIMARY KEY)".to_string()),
+                Box::new(()),
+            )
+            .await
+            .unwrap();
+        local
+            .execute(
+                session,
+                Box::new("INSERT INTO t VALUES (1), (2), (3), (4), (5)".to_string()),
+                Box::new(()),
+            )
+            .await
+            .unwrap();
+
+        let sql = "SELECT id FROM t ORDER BY id";
+        let page = query_page(&worker, session, sql).await;
+        assert_eq!(page.row_desc().fields()[0].name(), "id");
+        assert_eq!(ids(page.rows()), vec![1, 2]);
+        let cursor_id = page.cursor_id().unwrap();
+        let page = fetch(&worker, session, cursor_id).await.unwrap();
+        assert_eq!(ids(page.rows()), vec![3, 4]);
+        assert_eq!(page.cursor_id(), Some(cursor_id));
+        let page = fetch(&worker, session, cursor_id).await.unwrap();
+        assert_eq!(ids(page.rows()), vec![5]);
+        assert_eq!(page.cursor_id(), None);
+        let err = fetch(&worker, session, cursor_id).await.unwrap_err();
+        assert_eq!(err.ec(), ErrorCode::EntityNotFound);
+
+        // A result that fits the first page opens no cursor.
+        let page = query_page(&worker, session, "SELECT id FROM t WHERE id = 3").await;
+        assert_eq!(ids(page.rows()), vec![3]);
+        assert_eq!(page.cursor_id(), None);
+
+        let cursor_id = query_page(&worker, session, sql).await.cursor_id().unwrap();
+        let request = CloseCursorRequest::new(session, cursor_id);
+        let frame = dispatch(&worker, encode_close_cursor_request(4, &request))
+            .await
+            .unwrap();
+        assert!(decode_close_cursor_response(&frame).unwrap().closed());
+        assert!(fetch(&worker, session, cursor_id).await.is_err());
+
+        // Closing the session drops the cursors opened in it.
+        let cursor_id = query_page(&worker, session, sql).await.cursor_id().unwrap();
+        assert!(worker.close_session(CONN_ID, session).unwrap());
+        assert!(fetch(&worker, session, cursor_id).await.is_err());
+
+        // A cursor opened in an explicit transaction ends with it, while one
+        // reading in its own transaction outlives it.
+        let session = worker.create_session(CONN_ID).unwrap();
+        let own_tx = query_page(&worker, session, sql).await.cursor_id().unwrap();
+        query_page(&worker, session, "BEGIN").await;
+        let in_tx = query_page(&worker, session, sql).await.cursor_id().unwrap();
+        query_page(&worker, session, "COMMIT").await;
+        assert!(fetch(&worker, session, in_tx).await.is_err());
+        let page = fetch(&worker, session, own_tx).await.unwrap();
+        assert_eq!(ids(page.rows()), vec![3, 4]);
+    });
+}
+
+#[test]
+fn paging_e2e_range_scan_continuation() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let dirs = TestDirs::new("paging_e2e_range_scan_continuation");
+        let worker = build_worker(&dirs).await;
+        let session = worker.create_session(CONN_ID).unwrap();
+        let local_arc = new_session_bound_worker_runtime(worker.clone(), session);
+        let local: &dyn WorkerLocal = local_arc.as_ref();
+        for key in [b"e", b"a", b"d", b"b", b"c"] {
+            local
+                .put_async(session, key.to_vec(), key.to_vec())
+                .await
+                .unwrap();
+        }
+
+        let pages = scan_pages(&worker, session).await;
+        assert_eq!(
+            pages,
+            vec![
+                vec![b"a".to_vec(), b"b".to_vec()],
+                vec![b"c".to_vec(), b"d".to_vec()],
+                vec![b"e".to_vec()],
+            ]
+        );
+
+        // Pages overlay the writes the session transaction staged.
+        local
+            .query(session, Box::new("BEGIN".to_string()), Box::new(()))
+            .await
+            .unwrap();
+        local.delete_async(session, b"b").await.unwrap();
+        local
+            .put_async(session, b"bb".to_vec(), b"bb".to_vec())
+            .await
+            .unwrap();
+        let pages = scan_pages(&worker, session).await;
+        assert_eq!(
+            pages,
+            vec![
+                vec![b"a".to_vec(), b"bb".to_vec()],
+                vec![b"c".to_vec(), b"d".to_vec()],
+                vec![b"e".to_vec()],
+            ]
+        );
+    });
+}
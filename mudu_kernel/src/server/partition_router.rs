@@ -2,6 +2,7 @@ use std::ops::Bound;
 use std::sync::Arc;
 
 use mudu::common::buf::Buf;
+use mudu::common::crc::crc64;
 use mudu::common::id::OID;
 use mudu::common::result::RS;
 use mudu::error::ErrorCode;
@@ -9,11 +10,13 @@ use mudu::mudu_error;
 use mudu_contract::tuple::build_tuple::build_tuple;
 use mudu_contract::tuple::comparator::tuple_compare;
 use mudu_contract::tuple::tuple_binary_desc::TupleBinaryDesc;
-use mudu_type::datum::DatumDyn;
+use mudu_type::datum::{Datum, DatumDyn};
 use mudu_type::type_family::TypeFamily;
 
 use crate::contract::meta_mgr::MetaMgr;
-use crate::contract::partition_rule::{PartitionBound, PartitionRuleDesc};
+use crate::contract::partition_rule::{
+    PartitionBound, PartitionRuleDesc, PartitionRuleKind, RangePartitionDef,
+};
 use crate::contract::table_desc::TableDesc;
 use crate::x_engine::api::VecDatum;
 
@@ -41,40 +44,49 @@ impl PartitionRouter {
             .meta_mgr
             .get_partition_rule_by_id(binding.rule_id)
             .await?;
-        let route_tuple = build_route_tuple(table_desc, &binding.ref_attr_indices, key)?;
-        let route_desc = build_route_tuple_desc(table_desc, &binding.ref_attr_indices)?;
+        self.route_rule_key_partition(&rule, table_desc, &binding.ref_attr_indices, key)
+            .map(Some)
+    }
+
+    /// Route a table key under `rule`, whose key columns are the table
+    /// attributes `ref_attr_indices`.
+    pub fn route_rule_key_partition(
+        &self,
+        rule: &PartitionRuleDesc,
+        table_desc: &TableDesc,
+        ref_attr_indices: &[usize],
+        key: &VecDatum,
+    ) -> RS<OID> {
+        if let PartitionRuleKind::Hash { .. } = rule.kind {
+            let key_texts = ref_attr_indices
+                .iter()
+                .map(|attr| {
+                    let data_type = table_desc.get_attr(*attr).type_desc();
+                    binary_to_key_text(data_type.type_family(), &route_attr_binary(key, *attr)?)
+                })
+                .collect::<RS<Vec<_>>>()?;
+            return hash_partition(rule, &key_texts);
+        }
+        let route_tuple = build_route_tuple(table_desc, ref_attr_indices, key)?;
+        let route_desc = build_route_tuple_desc(table_desc, ref_attr_indices)?;
         tracing::debug!(
             table = table_desc.name(),
-            table_id,
-            ref_attrs = ?binding.ref_attr_indices,
+            table_id = table_desc.id(),
+            ref_attrs = ?ref_attr_indices,
             route_tuple = ?route_tuple,
             rule = ?rule.partitions.iter().map(|p| p.partition_id).collect::<Vec<_>>(),
             "route_exact_partition evaluating"
         );
 
         for partition in &rule.partitions {
-            let after_start = match &partition.start {
-                PartitionBound::Unbounded => true,
-                PartitionBound::Value(values) => {
-                    let bound = build_partition_bound_tuple(&route_desc, values)?;
-                    !tuple_compare(&route_desc, &route_tuple, &bound)?.is_lt()
-                }
-            };
-            let before_end = match &partition.end {
-                PartitionBound::Unbounded => true,
-                PartitionBound::Value(values) => {
-                    let bound = build_partition_bound_tuple(&route_desc, values)?;
-                    tuple_compare(&route_desc, &route_tuple, &bound)?.is_lt()
-                }
-            };
-            if after_start && before_end {
-                return Ok(Some(partition.partition_id));
+            if partition_contains(rule, &route_desc, partition, &route_tuple)? {
+                return Ok(partition.partition_id);
             }
         }
 
         Err(mudu_error!(
             ErrorCode::EntityNotFound,
-            format!("no partition matched table {} key", table_id)
+            format!("no partition matched table {} key", table_desc.id())
         ))
     }
 
@@ -92,6 +104,9 @@ impl PartitionRouter {
             .meta_mgr
             .get_partition_rule_by_id(binding.rule_id)
             .await?;
+        if let PartitionRuleKind::Hash { .. } = rule.kind {
+            return Ok(Some(all_partitions(&rule)));
+        }
         let route_desc = build_route_tuple_desc(table_desc, &binding.ref_attr_indices)?;
         let start_tuple = build_route_bound_tuple(table_desc, &binding.ref_attr_indices, start)?;
         let end_tuple = build_route_bound_tuple(table_desc, &binding.ref_attr_indices, end)?;
@@ -115,24 +130,23 @@ impl PartitionRouter {
         rule: &PartitionRuleDesc,
         key_values: &[Vec<u8>],
     ) -> RS<OID> {
+        if let PartitionRuleKind::Hash { columns } = &rule.kind {
+            if columns.len() != key_values.len() {
+                return Err(mudu_error!(
+                    ErrorCode::InvalidTuple,
+                    "partition key width mismatch"
+                ));
+            }
+            let key_texts = key_values
+                .iter()
+                .map(|raw| textual_to_key_text(raw))
+                .collect::<RS<Vec<_>>>()?;
+            return hash_partition(rule, &key_texts);
+        }
         let route_desc = build_rule_tuple_desc(&rule.key_types)?;
         let route_tuple = build_partition_bound_tuple(&route_desc, key_values)?;
         for partition in &rule.partitions {
-            let after_start = match &partition.start {
-                PartitionBound::Unbounded => true,
-                PartitionBound::Value(values) => {
-                    let bound = build_partition_bound_tuple(&route_desc, values)?;
-                    !tuple_compare(&route_desc, &route_tuple, &bound)?.is_lt()
-                }
-            };
-            let before_end = match &partition.end {
-                PartitionBound::Unbounded => true,
-                PartitionBound::Value(values) => {
-                    let bound = build_partition_bound_tuple(&route_desc, values)?;
-                    tuple_compare(&route_desc, &route_tuple, &bound)?.is_lt()
-                }
-            };
-            if after_start && before_end {
+            if partition_contains(rule, &route_desc, partition, &route_tuple)? {
                 return Ok(partition.partition_id);
             }
         }
@@ -142,6 +156,73 @@ impl PartitionRouter {
         ))
     }
 
+    /// The next version of range rule `rule` with the partition containing
+    /// `at` split in two: the partition keeps `[start, at)` and a new
+    /// partition `new_name` takes `[at, end)`. Returns the new rule and the
+    /// ids of the split and the new partition.
+    pub fn split_rule_partition(
+        &self,
+        rule: &PartitionRuleDesc,
+        at: &[Vec<u8>],
+        new_name: String,
+    ) -> RS<(PartitionRuleDesc, OID, OID)> {
+        if rule.kind != PartitionRuleKind::Range {
+            return Err(mudu_error!(
+                ErrorCode::NotImplemented,
+                format!("partition rule {} is not a RANGE rule", rule.name)
+            ));
+        }
+        if rule
+            .partitions
+            .iter()
+            .any(|partition| partition.name == new_name)
+        {
+            return Err(mudu_error!(
+                ErrorCode::EntityAlreadyExists,
+                format!(
+                    "partition {} already exists in rule {}",
+                    new_name, rule.name
+                )
+            ));
+        }
+        let split_id = self.route_rule_exact_partition(rule, at)?;
+        let index = rule
+            .partitions
+            .iter()
+            .position(|partition| partition.partition_id == split_id)
+            .ok_or_else(|| {
+                mudu_error!(
+                    ErrorCode::EntityNotFound,
+                    format!("no such partition {}", split_id)
+                )
+            })?;
+        if let PartitionBound::Value(start) = &rule.partitions[index].start {
+            let route_desc = build_rule_tuple_desc(&rule.key_types)?;
+            let start = build_partition_bound_tuple(&route_desc, start)?;
+            let at = build_partition_bound_tuple(&route_desc, at)?;
+            if tuple_compare(&route_desc, &start, &at)?.is_eq() {
+                return Err(mudu_error!(
+                    ErrorCode::InvalidArgument,
+                    format!(
+                        "split point is already the start of partition {}",
+                        rule.partitions[index].name
+                    )
+                ));
+            }
+        }
+        let mut split = rule.clone();
+        let end = std::mem::replace(
+            &mut split.partitions[index].end,
+            PartitionBound::Value(at.to_vec()),
+        );
+        let new_partition =
+            RangePartitionDef::new(new_name, PartitionBound::Value(at.to_vec()), end);
+        let new_id = new_partition.partition_id;
+        split.partitions.insert(index + 1, new_partition);
+        split.version = rule.version + 1;
+        Ok((split, split_id, new_id))
+    }
+
     /// Whether every partition-key column (`ref_attr_indices`) is pinned by
     /// an equality item in `prefix` (e.g. `no_w_id = ?` for a rule on
     /// `(no_w_id)`).
@@ -165,6 +246,9 @@ impl PartitionRouter {
         start: &Bound<Vec<Vec<u8>>>,
         end: &Bound<Vec<Vec<u8>>>,
     ) -> RS<Vec<OID>> {
+        if let PartitionRuleKind::Hash { .. } = rule.kind {
+            return Ok(all_partitions(rule));
+        }
         let route_desc = build_rule_tuple_desc(&rule.key_types)?;
         let start_tuple = build_rule_bound_tuple(&route_desc, start)?;
         let end_tuple = build_rule_bound_tuple(&route_desc, end)?;
@@ -224,6 +308,29 @@ fn partition_overlaps(
                 format!("no such partition {}", partition_id)
             )
         })?;
+    match rule.kind {
+        PartitionRuleKind::Range => {}
+        PartitionRuleKind::Hash { .. } => return Ok(true),
+        PartitionRuleKind::List => {
+            for values in &partition.values {
+                let value = build_partition_bound_tuple(route_desc, values)?;
+                let after_start = match start {
+                    Bound::Unbounded => true,
+                    Bound::Included(start) => !tuple_compare(route_desc, &value, start)?.is_lt(),
+                    Bound::Excluded(start) => tuple_compare(route_desc, &value, start)?.is_gt(),
+                };
+                let before_end = match end {
+                    Bound::Unbounded => true,
+                    Bound::Included(end) => !tuple_compare(route_desc, &value, end)?.is_gt(),
+                    Bound::Excluded(end) => tuple_compare(route_desc, &value, end)?.is_lt(),
+                };
+                if after_start && before_end {
+                    return Ok(true);
+                }
+            }
+            return Ok(false);
+        }
+    }
 
     let start_ok = match (end, &partition.start) {
         (Bound::Unbounded, _) | (_, PartitionBound::Unbounded) => true,
@@ -244,6 +351,143 @@ fn partition_overlaps(
     Ok(start_ok && end_ok)
 }
 
+/// Whether `tuple` falls in the range of a range partition or equals one of
+/// the values of a list partition.
+fn partition_contains(
+    rule: &PartitionRuleDesc,
+    route_desc: &TupleBinaryDesc,
+    partition: &RangePartitionDef,
+    tuple: &[u8],
+) -> RS<bool> {
+    if let PartitionRuleKind::List = rule.kind {
+        for values in &partition.values {
+            let value = build_partition_bound_tuple(route_desc, values)?;
+            if tuple_compare(route_desc, tuple, &value)?.is_eq() {
+                return Ok(true);
+            }
+        }
+        return Ok(false);
+    }
+    let after_start = match &partition.start {
+        PartitionBound::Unbounded => true,
+        PartitionBound::Value(values) => {
+            let bound = build_partition_bound_tuple(route_desc, values)?;
+            !tuple_compare(route_desc, tuple, &bound)?.is_lt()
+        }
+    };
+    let before_end = match &partition.end {
+        PartitionBound::Unbounded => true,
+        PartitionBound::Value(values) => {
+            let bound = build_partition_bound_tuple(route_desc, values)?;
+            tuple_compare(route_desc, tuple, &bound)?.is_lt()
+        }
+    };
+    Ok(after_start && before_end)
+}
+
+fn all_partitions(rule: &PartitionRuleDesc) -> Vec<OID> {
+    rule.partitions
+        .iter()
+        .map(|partition| partition.partition_id)
+        .collect()
+}
+
+/// Pick the partition of a hash rule from the canonical texts of the key
+/// values, in `REFERENCES` order.
+///
+/// The texts rather than the binaries are hashed so that a table row and a
+/// textual route request for the same key agree, whatever the column types.
+fn hash_partition(rule: &PartitionRuleDesc, key_texts: &[String]) -> RS<OID> {
+    if rule.partitions.is_empty() {
+        return Err(mudu_error!(
+            ErrorCode::EntityNotFound,
+            format!("partition rule {} has no partitions", rule.name)
+        ));
+    }
+    let mut bytes = Vec::new();
+    for text in key_texts {
+        bytes.extend_from_slice(&(text.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(text.as_bytes());
+    }
+    let index = crc64(&bytes) % rule.partitions.len() as u64;
+    Ok(rule.partitions[index as usize].partition_id)
+}
+
+/// Canonical text of a key value: integers in decimal, floats in their
+/// shortest form and anything else verbatim.
+fn canonical_key_text(text: &str) -> String {
+    if let Ok(n) = text.parse::<i128>() {
+        n.to_string()
+    } else if let Ok(n) = text.parse::<u128>() {
+        n.to_string()
+    } else if let Ok(n) = text.parse::<f64>() {
+        n.to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+fn textual_to_key_text(raw: &[u8]) -> RS<String> {
+    let text = String::from_utf8(raw.to_vec())
+        .map_err(|e| mudu_error!(ErrorCode::Decode, "partition key text is not utf8", e))?;
+    Ok(canonical_key_text(&strip_text_literal_quotes(text.trim())))
+}
+
+/// Whether a column of type `data_type_id` can be a hash partition key.
+/// Its binary values must render as the text `canonical_key_text` makes of
+/// the same key given as a literal, so both route to one partition. BOOL
+/// and UUID literals have several spellings and are not supported.
+pub(crate) fn is_hash_key_type(data_type_id: TypeFamily) -> bool {
+    matches!(
+        data_type_id,
+        TypeFamily::I8
+            | TypeFamily::I16
+            | TypeFamily::I32
+            | TypeFamily::I64
+            | TypeFamily::I128
+            | TypeFamily::U128
+            | TypeFamily::F32
+            | TypeFamily::F64
+            | TypeFamily::String
+    )
+}
+
+fn binary_to_key_text(data_type_id: TypeFamily, binary: &[u8]) -> RS<String> {
+    let text = match data_type_id {
+        TypeFamily::I8 => <i8 as Datum>::from_binary(binary)?.to_string(),
+        TypeFamily::I16 => <i16 as Datum>::from_binary(binary)?.to_string(),
+        TypeFamily::I32 => <i32 as Datum>::from_binary(binary)?.to_string(),
+        TypeFamily::I64 => <i64 as Datum>::from_binary(binary)?.to_string(),
+        TypeFamily::I128 => <i128 as Datum>::from_binary(binary)?.to_string(),
+        TypeFamily::U128 => <u128 as Datum>::from_binary(binary)?.to_string(),
+        TypeFamily::F32 => <f32 as Datum>::from_binary(binary)?.to_string(),
+        TypeFamily::F64 => <f64 as Datum>::from_binary(binary)?.to_string(),
+        TypeFamily::String => <String as Datum>::from_binary(binary)?,
+        _ => {
+            return Err(mudu_error!(
+                ErrorCode::NotImplemented,
+                format!(
+                    "hash partition key type {:?} is not supported",
+                    data_type_id
+                )
+            ));
+        }
+    };
+    Ok(canonical_key_text(&text))
+}
+
+fn route_attr_binary(key: &VecDatum, attr: usize) -> RS<Buf> {
+    key.data()
+        .iter()
+        .find_map(|(current_attr, binary)| (*current_attr == attr).then(|| binary.clone()))
+        .ok_or_else(|| {
+            mudu_error!(
+                ErrorCode::EntityNotFound,
+                format!("missing partition route attribute {}", attr)
+            )
+        })
+}
+
 fn build_route_tuple_desc(table_desc: &TableDesc, ref_attrs: &[usize]) -> RS<TupleBinaryDesc> {
     let mut fields = ref_attrs
         .iter()
@@ -260,17 +504,7 @@ fn build_route_tuple_desc(table_desc: &TableDesc, ref_attrs: &[usize]) -> RS<Tup
 fn build_route_tuple(table_desc: &TableDesc, ref_attrs: &[usize], key: &VecDatum) -> RS<Vec<u8>> {
     let mut values = Vec::with_capacity(ref_attrs.len());
     for attr in ref_attrs {
-        let binary = key
-            .data()
-            .iter()
-            .find_map(|(current_attr, binary)| (*current_attr == *attr).then(|| binary.clone()))
-            .ok_or_else(|| {
-                mudu_error!(
-                    ErrorCode::EntityNotFound,
-                    format!("missing partition route attribute {}", attr)
-                )
-            })?;
-        values.push(binary);
+        values.push(route_attr_binary(key, *attr)?);
     }
     build_tuple(&values, &build_route_tuple_desc(table_desc, ref_attrs)?)
 }
@@ -764,6 +998,62 @@ mod tests {
         assert_eq!(all, ids);
     }
 
+    #[test]
+    fn split_rule_partition_splits_containing_range_and_bumps_version() {
+        let rule = single_col_rule();
+        let router = PartitionRouter::new(Arc::new(TestMetaMgr::default()));
+        let (split, split_id, new_id) = router
+            .split_rule_partition(&rule, &[v("15")], "p1b".to_string())
+            .unwrap();
+        assert_eq!(split_id, rule.partitions[0].partition_id);
+        assert_eq!(split.version, rule.version + 1);
+        assert_eq!(split.partitions.len(), 4);
+        assert_eq!(
+            split.partitions[0].end,
+            PartitionBound::Value(vec![v("15")])
+        );
+        assert_eq!(split.partitions[1].partition_id, new_id);
+        assert_eq!(split.partitions[1].name, "p1b");
+        assert_eq!(
+            split.partitions[1].start,
+            PartitionBound::Value(vec![v("15")])
+        );
+        assert_eq!(
+            split.partitions[1].end,
+            PartitionBound::Value(vec![v("20")])
+        );
+        assert_eq!(
+            router
+                .route_rule_exact_partition(&split, &[v("12")])
+                .unwrap(),
+            split_id
+        );
+        assert_eq!(
+            router
+                .route_rule_exact_partition(&split, &[v("17")])
+                .unwrap(),
+            new_id
+        );
+
+        let err = router
+            .split_rule_partition(&rule, &[v("20")], "px".to_string())
+            .unwrap_err();
+        assert_eq!(err.ec(), ErrorCode::InvalidArgument);
+        let err = router
+            .split_rule_partition(&rule, &[v("35")], "px".to_string())
+            .unwrap_err();
+        assert_eq!(err.ec(), ErrorCode::EntityNotFound);
+        let err = router
+            .split_rule_partition(&rule, &[v("15")], "p2".to_string())
+            .unwrap_err();
+        assert_eq!(err.ec(), ErrorCode::EntityAlreadyExists);
+        let hash = PartitionRuleDesc::new_hash("h".to_string(), vec!["id".to_string()], 2);
+        let err = router
+            .split_rule_partition(&hash, &[v("1")], "px".to_string())
+            .unwrap_err();
+        assert_eq!(err.ec(), ErrorCode::NotImplemented);
+    }
+
     #[test]
     fn route_rule_exact_partition_bound_count_mismatch_returns_invalid_tuple() {
         let rule = multi_col_rule();
@@ -774,6 +1064,146 @@ mod tests {
         assert_eq!(err.ec(), ErrorCode::InvalidTuple);
     }
 
+    #[test]
+    fn hash_rule_routes_rows_and_textual_keys_alike() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let table_desc = test_table_desc();
+            let rule = PartitionRuleDesc::new_hash("h".to_string(), vec!["id".to_string()], 4);
+            let ids: Vec<OID> = rule.partitions.iter().map(|p| p.partition_id).collect();
+            let router = PartitionRouter::new(Arc::new(partitioned_meta_mgr(
+                table_desc.clone(),
+                rule.clone(),
+                vec![0],
+            )));
+
+            let mut used = Vec::new();
+            for n in 0..64 {
+                let row = router
+                    .route_exact_partition(
+                        table_desc.id(),
+                        table_desc.as_ref(),
+                        &VecDatum::new(vec![(0, i32_value(n))]),
+                    )
+                    .await
+                    .unwrap()
+                    .unwrap();
+                let text = router
+                    .route_rule_exact_partition(&rule, &[v(&format!(" '{}' ", n))])
+                    .unwrap();
+                assert_eq!(row, text);
+                if !used.contains(&row) {
+                    used.push(row);
+                }
+            }
+            assert!(used.len() > 1, "sequential keys should spread");
+
+            let all = router
+                .route_range_partitions(
+                    table_desc.id(),
+                    table_desc.as_ref(),
+                    &Bound::Included(vec![(0, i32_value(1))]),
+                    &Bound::Excluded(vec![(0, i32_value(2))]),
+                )
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(all, ids);
+            let all = router
+                .route_rule_range_partitions(&rule, &Bound::Unbounded, &Bound::Unbounded)
+                .unwrap();
+            assert_eq!(all, ids);
+
+            let err = router
+                .route_rule_exact_partition(&rule, &[v("1"), v("2")])
+                .unwrap_err();
+            assert_eq!(err.ec(), ErrorCode::InvalidTuple);
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn hash_keys_of_small_integer_columns_match_their_literals() {
+        let cases = [
+            (TypeFamily::I8, (-7i8).to_be_bytes().to_vec(), " -7 "),
+            (TypeFamily::I16, 300i16.to_be_bytes().to_vec(), "'300'"),
+        ];
+        for (data_type_id, binary, literal) in cases {
+            assert!(is_hash_key_type(data_type_id));
+            assert_eq!(
+                binary_to_key_text(data_type_id, &binary).unwrap(),
+                textual_to_key_text(literal.as_bytes()).unwrap()
+            );
+        }
+        assert!(!is_hash_key_type(TypeFamily::Bool));
+        assert!(!is_hash_key_type(TypeFamily::Uuid));
+    }
+
+    #[test]
+    fn list_rule_routes_listed_values_and_prunes_ranges() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let table_desc = test_table_desc();
+            let rule = PartitionRuleDesc::new_list(
+                "l".to_string(),
+                vec![TypeFamily::I32],
+                vec![
+                    RangePartitionDef::new_list(
+                        "odd".to_string(),
+                        vec![vec![v("1")], vec![v("3")]],
+                    ),
+                    RangePartitionDef::new_list(
+                        "even".to_string(),
+                        vec![vec![v("2")], vec![v("4")]],
+                    ),
+                ],
+            );
+            let ids: Vec<OID> = rule.partitions.iter().map(|p| p.partition_id).collect();
+            let router = PartitionRouter::new(Arc::new(partitioned_meta_mgr(
+                table_desc.clone(),
+                rule.clone(),
+                vec![0],
+            )));
+
+            let point = router
+                .route_exact_partition(
+                    table_desc.id(),
+                    table_desc.as_ref(),
+                    &VecDatum::new(vec![(0, i32_value(3))]),
+                )
+                .await
+                .unwrap();
+            assert_eq!(point, Some(ids[0]));
+            assert_eq!(
+                router.route_rule_exact_partition(&rule, &[v("4")]).unwrap(),
+                ids[1]
+            );
+            let err = router
+                .route_rule_exact_partition(&rule, &[v("5")])
+                .unwrap_err();
+            assert_eq!(err.ec(), ErrorCode::EntityNotFound);
+
+            let pruned = router
+                .route_range_partitions(
+                    table_desc.id(),
+                    table_desc.as_ref(),
+                    &Bound::Included(vec![(0, i32_value(3))]),
+                    &Bound::Excluded(vec![(0, i32_value(4))]),
+                )
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(pruned, vec![ids[0]]);
+            let pruned = router
+                .route_rule_range_partitions(
+                    &rule,
+                    &Bound::Excluded(vec![v("1")]),
+                    &Bound::Excluded(vec![v("3")]),
+                )
+                .unwrap();
+            assert_eq!(pruned, vec![ids[1]]);
+        })
+        .unwrap()
+    }
+
     #[test]
     fn textual_to_binary_strips_quotes() {
         let rule = single_col_rule();
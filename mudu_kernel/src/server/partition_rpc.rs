@@ -60,6 +60,16 @@ pub enum PartitionRpcRequest {
         key: Vec<u8>,
         select: Vec<AttrIndex>,
     },
+    /// Take a statement-level lock on `key` under `lock_token` without
+    /// reading anything. Used for constraint value locks, whose relation is
+    /// not a stored relation. The lock is released like the one taken by
+    /// `LockKeyForUpdate`.
+    LockKey {
+        lock_token: OID,
+        table_id: OID,
+        partition_id: OID,
+        key: Vec<u8>,
+    },
     /// Release every statement-level lock held by `lock_token` on this
     /// worker (rollback path; orphan reclamation is the backstop).
     UnlockKeys { lock_token: OID },
@@ -78,6 +88,37 @@ pub enum PartitionRpcRequest {
         lock_token: Option<OID>,
         writes: Vec<XLWrite>,
     },
+    /// Read every committed row of one partition as raw `(key, value)`
+    /// tuples; used to copy rows when a partition rule is altered.
+    ScanPartition { table_id: OID, partition_id: OID },
+    /// Phase one of a cross-partition commit: take commit locks on the
+    /// participant's share `writes` of coordinator transaction `tx_id` and
+    /// make it durable as prepared. The writes stay invisible and locked
+    /// until `CommitPreparedTx` or `AbortPreparedTx` resolves them.
+    PrepareTx {
+        tx_id: u64,
+        coordinator_worker_id: OID,
+        writes: Vec<XLWrite>,
+    },
+    /// Phase two: apply a prepared transaction and release its locks. A
+    /// transaction that is no longer prepared has already been committed and
+    /// is acknowledged as is.
+    CommitPreparedTx {
+        tx_id: u64,
+        coordinator_worker_id: OID,
+    },
+    /// Drop a prepared transaction, or only release the statement locks the
+    /// coordinator holds here when it never got prepared.
+    AbortPreparedTx {
+        tx_id: u64,
+        coordinator_worker_id: OID,
+    },
+    /// Ask the coordinator for the outcome of transaction `tx_id` that
+    /// `participant_worker_id` still holds prepared.
+    QueryTxDecision {
+        tx_id: u64,
+        participant_worker_id: OID,
+    },
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
@@ -90,7 +131,17 @@ pub enum PartitionRpcResponse {
     ApplyCrossPartitionTx,
     /// Number of writes applied by a `CommitWriteSet` handoff commit.
     CommitWriteSet(usize),
+    /// Acknowledgement for `LockKey`.
+    LockKey,
     /// Acknowledgement for `UnlockKeys`.
     UnlockKeys,
     Err(String),
+    /// Raw `(key, value)` rows returned by `ScanPartition`.
+    ScanPartition(Vec<(Vec<u8>, Vec<u8>)>),
+    /// Acknowledgement for `PrepareTx`, `CommitPreparedTx` and
+    /// `AbortPreparedTx`.
+    PreparedTx,
+    /// Answer to `QueryTxDecision`: `Some(true)` to commit, `Some(false)` to
+    /// abort, `None` while the coordinator cannot tell yet.
+    TxDecision(Option<bool>),
 }
@@ -15,6 +15,7 @@
 //! module is excluded under Miri (see `mod.rs`).
 
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use mudu::common::id::OID;
 use mudu_contract::database::sql_params::SQLParams;
@@ -28,6 +29,7 @@ use mudu_type::data_value::DataValue;
 use mudu_utils::oid::gen_oid;
 
 use crate::server::async_func_task::HandleResult;
+use crate::server::auth::ServerAuth;
 use crate::server::handlers::{ExecuteHandler, QueryHandler};
 use crate::server::message_dispatcher::MessageHandler;
 use crate::server::request_ctx::RequestCtx;
@@ -76,10 +78,12 @@ async fn build_worker(dirs: &TestDirs) -> WorkerRuntime {
         log_chunk_size: 4096,
         log_batching: WorkerLogBatching::default(),
         wal_sync_policy: WalSyncPolicy::Commit,
+        wal_archive_dir: None,
         procedure_runtime: None,
         registry,
         async_runtime: None,
         server_instance_id: 0,
+        auth: Arc::new(ServerAuth::disabled()),
     })
     .await
     .unwrap();
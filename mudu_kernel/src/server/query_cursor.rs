@@ -0,0 +1,310 @@
+//! Server-side cursors over query results.
+//!
+//! A query sent with a fetch size answers with its first page of rows; when
+//! more rows remain the worker parks the running query here under a cursor
+//! id and the client pulls the rest with `Fetch` frames until the cursor is
+//! exhausted or it sends `CloseCursor`. One row is read ahead of every page,
+//! so a result that fits in the first page never opens a cursor, and the
+//! last page reports the cursor as finished without an extra round trip. A
+//! page ends at its row count or at [`MAX_PAGE_BYTES`] of encoded rows,
+//! whichever comes first.
+//!
+//! A cursor holds the live result set of its query, not its rows: each
+//! fetch pulls one page from the query plan, so a cursor costs the memory
+//! of the plan's state whatever the size of the result. The query keeps
+//! reading in its transaction. A cursor opened inside an explicit
+//! transaction is closed when that transaction ends; otherwise the cursor
+//! owns a transaction of its own (see [`CursorTx`]), committed once the
+//! cursor is drained and rolled back when it is closed early.
+//!
+//! Cursors belong to the connection and session that opened them. They are
+//! dropped when the session or the connection closes, and a connection may
+//! hold at most [`MAX_OPEN_CURSORS`] at a time.
+
+use async_trait::async_trait;
+use mudu::common::id::OID;
+use mudu::common::result::RS;
+use mudu::error::ErrorCode;
+use mudu::mudu_error;
+use mudu_contract::database::result_set::ResultSetAsync;
+use mudu_contract::protocol::FetchResponse;
+use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
+use mudu_contract::tuple::tuple_value::TupleValue;
+use mudu_sys::sync::async_::futures_mutex::FMutex;
+use mudu_sys::sync::{SMutex, SMutexGuard};
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Most rows one page carries, whatever fetch size or scan limit the client
+/// asks for.
+pub const MAX_PAGE_ROWS: u32 = 10_000;
+
+/// Encoded bytes of rows after which a page ends early, whatever its row
+/// count. Connections write one response before reading the next request,
+/// so this bounds the output a connection buffers for a page.
+pub const MAX_PAGE_BYTES: usize = 4 * 1024 * 1024;
+
+/// Most cursors one connection may keep open.
+pub const MAX_OPEN_CURSORS: usize = 64;
+
+/// Clamp a requested page size to `1..=MAX_PAGE_ROWS`.
+pub(crate) fn page_rows(requested: u32) -> usize {
+    requested.clamp(1, MAX_PAGE_ROWS) as usize
+}
+
+/// A transaction a cursor owns. Dropping it without [`CursorTx::commit`]
+/// rolls it back.
+#[async_trait]
+pub(crate) trait CursorTx: Send + Sync {
+    /// End the transaction once the query is drained.
+    async fn commit(self: Box<Self>) -> RS<()>;
+}
+
+/// The transaction the rows of a cursor are read in.
+pub(crate) enum ReadTx {
+    /// The explicit transaction of the cursor's session; the cursor is
+    /// closed when that transaction ends.
+    Session,
+    /// A transaction of the cursor's own.
+    Owned(Box<dyn CursorTx>),
+    /// None left: the rows were read before the cursor opened.
+    Done,
+}
+
+/// Read a page of `result_set`: up to `max_rows` rows, fewer once they
+/// encode to [`MAX_PAGE_BYTES`], plus the row after them, if any.
+pub(crate) async fn read_page(
+    result_set: &dyn ResultSetAsync,
+    max_rows: usize,
+) -> RS<(Vec<TupleValue>, Option<TupleValue>)> {
+    let columns = result_set.desc().fields().len();
+    let mut rows = Vec::with_capacity(max_rows.min(1024));
+    let mut bytes = 0;
+    while rows.len() < max_rows && bytes < MAX_PAGE_BYTES {
+        match next_row(result_set, columns).await? {
+            Some(row) => {
+                bytes += encoded_len(&row)?;
+                rows.push(row);
+            }
+            None => return Ok((rows, None)),
+        }
+    }
+    let next = next_row(result_set, columns).await?;
+    Ok((rows, next))
+}
+
+async fn next_row(result_set: &dyn ResultSetAsync, columns: usize) -> RS<Option<TupleValue>> {
+    let row = result_set.next().await?;
+    if let Some(row) = &row {
+        if row.values().len() != columns {
+            return Err(mudu_error!(
+                ErrorCode::FatalInternal,
+                "non consistent column number"
+            ));
+        }
+    }
+    Ok(row)
+}
+
+/// The bytes `row` takes in a response frame.
+fn encoded_len(row: &TupleValue) -> RS<usize> {
+    let mut counter = ByteCounter(0);
+    rmp_serde::encode::write(&mut counter, row)
+        .map_err(|e| mudu_error!(ErrorCode::Encode, "encode query row error", e))?;
+    Ok(counter.0)
+}
+
+struct ByteCounter(usize);
+
+impl Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+struct QueryCursor {
+    conn_id: u64,
+    session_id: OID,
+    fetch_size: usize,
+    desc: TupleFieldDesc,
+    in_session_tx: bool,
+    state: FMutex<CursorState>,
+}
+
+struct CursorState {
+    result_set: Arc<dyn ResultSetAsync>,
+    /// The row read ahead of the next page; a cursor is open while it has
+    /// one.
+    lookahead: Option<TupleValue>,
+    tx: Option<Box<dyn CursorTx>>,
+}
+
+/// The open cursors of one worker.
+pub(crate) struct QueryCursors {
+    next_id: AtomicU64,
+    cursors: SMutex<HashMap<u64, Arc<QueryCursor>>>,
+}
+
+impl QueryCursors {
+    pub(crate) fn new() -> Self {
+        Self {
+            next_id: AtomicU64::new(1),
+            cursors: SMutex::new(HashMap::new()),
+        }
+    }
+
+    /// Park the query behind `result_set`, whose first page was answered and
+    /// whose next row is `lookahead`, and return the id that fetches the
+    /// rest.
+    pub(crate) fn open(
+        &self,
+        conn_id: u64,
+        session_id: OID,
+        result_set: Arc<dyn ResultSetAsync>,
+        lookahead: TupleValue,
+        read_tx: ReadTx,
+        fetch_size: u32,
+    ) -> RS<u64> {
+        let mut cursors = self.lock()?;
+        let open = cursors
+            .values()
+            .filter(|cursor| cursor.conn_id == conn_id)
+            .count();
+        if open >= MAX_OPEN_CURSORS {
+            return Err(mudu_error!(
+                ErrorCode::QuotaExceeded,
+                format!("connection already has {MAX_OPEN_CURSORS} open cursors")
+            ));
+        }
+        let in_session_tx = matches!(read_tx, ReadTx::Session);
+        let tx = match read_tx {
+            ReadTx::Owned(tx) => Some(tx),
+            ReadTx::Session | ReadTx::Done => None,
+        };
+        let cursor_id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        cursors.insert(
+            cursor_id,
+            Arc::new(QueryCursor {
+                conn_id,
+                session_id,
+                fetch_size: page_rows(fetch_size),
+                desc: result_set.desc().clone(),
+                in_session_tx,
+                state: FMutex::new(CursorState {
+                    result_set,
+                    lookahead: Some(lookahead),
+                    tx,
+                }),
+            }),
+        );
+        Ok(cursor_id)
+    }
+
+    /// Read the next page of `cursor_id`; `max_rows` 0 keeps the fetch size
+    /// the cursor was opened with. The cursor is closed once drained, or
+    /// when reading the page fails.
+    pub(crate) async fn fetch(
+        &self,
+        conn_id: u64,
+        session_id: OID,
+        cursor_id: u64,
+        max_rows: u32,
+    ) -> RS<FetchResponse> {
+        let cursor = self
+            .lock()?
+            .get(&cursor_id)
+            .filter(|cursor| cursor.conn_id == conn_id && cursor.session_id == session_id)
+            .cloned()
+            .ok_or_else(|| no_such_cursor(cursor_id))?;
+        let max_rows = if max_rows == 0 {
+            cursor.fetch_size
+        } else {
+            page_rows(max_rows)
+        };
+        let result = Self::read_next_page(&cursor, cursor_id, max_rows).await;
+        if !matches!(result, Ok((_, true))) {
+            self.lock()?.remove(&cursor_id);
+        }
+        let (rows, open) = result?;
+        Ok(FetchResponse::new(
+            cursor.desc.clone(),
+            rows,
+            open.then_some(cursor_id),
+        ))
+    }
+
+    // The rows of the next page, and whether the cursor stays open. The
+    // owned transaction is committed with the last page.
+    async fn read_next_page(
+        cursor: &QueryCursor,
+        cursor_id: u64,
+        max_rows: usize,
+    ) -> RS<(Vec<TupleValue>, bool)> {
+        let mut state = cursor.state.lock().await;
+        // A concurrent fetch drained the cursor first.
+        let Some(first) = state.lookahead.take() else {
+            return Err(no_such_cursor(cursor_id));
+        };
+        let (mut rows, lookahead) = read_page(state.result_set.as_ref(), max_rows - 1).await?;
+        rows.insert(0, first);
+        state.lookahead = lookahead;
+        if state.lookahead.is_some() {
+            return Ok((rows, true));
+        }
+        if let Some(tx) = state.tx.take() {
+            tx.commit().await?;
+        }
+        Ok((rows, false))
+    }
+
+    /// Close `cursor_id`; returns whether it was open.
+    pub(crate) fn close(&self, conn_id: u64, session_id: OID, cursor_id: u64) -> RS<bool> {
+        let mut cursors = self.lock()?;
+        let owned = cursors
+            .get(&cursor_id)
+            .is_some_and(|cursor| cursor.conn_id == conn_id && cursor.session_id == session_id);
+        if owned {
+            cursors.remove(&cursor_id);
+        }
+        Ok(owned)
+    }
+
+    /// Close the cursors reading in the explicit transaction of
+    /// `session_id`, which just ended.
+    pub(crate) fn drop_session_tx(&self, session_id: OID) -> RS<()> {
+        self.lock()?
+            .retain(|_, cursor| !cursor.in_session_tx || cursor.session_id != session_id);
+        Ok(())
+    }
+
+    /// Close every cursor opened in `session_id`.
+    pub(crate) fn drop_session(&self, session_id: OID) -> RS<()> {
+        self.lock()?
+            .retain(|_, cursor| cursor.session_id != session_id);
+        Ok(())
+    }
+
+    /// Close every cursor opened on `conn_id`.
+    pub(crate) fn drop_connection(&self, conn_id: u64) -> RS<()> {
+        self.lock()?.retain(|_, cursor| cursor.conn_id != conn_id);
+        Ok(())
+    }
+
+    fn lock(&self) -> RS<SMutexGuard<'_, HashMap<u64, Arc<QueryCursor>>>> {
+        self.cursors.lock()
+    }
+}
+
+fn no_such_cursor(cursor_id: u64) -> mudu::error::MuduError {
+    mudu_error!(
+        ErrorCode::EntityNotFound,
+        format!("no such cursor: {cursor_id}")
+    )
+}
@@ -0,0 +1,276 @@
+#![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+//! Tests for [`super::query_cursor::QueryCursors`].
+
+use async_trait::async_trait;
+use futures::executor::block_on;
+use mudu::common::result::RS;
+use mudu::error::ErrorCode;
+use mudu_contract::database::result_set::ResultSetAsync;
+use mudu_contract::tuple::datum_desc::DatumDesc;
+use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
+use mudu_contract::tuple::tuple_value::TupleValue;
+use mudu_type::data_type::DataType;
+use mudu_type::data_value::DataValue;
+use mudu_type::type_family::TypeFamily;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::mudu_conn::mudu_result_set_async::MuduResultSetAsync;
+use crate::server::query_cursor::{
+    read_page, CursorTx, QueryCursors, ReadTx, MAX_OPEN_CURSORS, MAX_PAGE_BYTES,
+};
+
+/// A result set of `0..count` that counts the rows pulled from it.
+struct CountingResultSet {
+    rows: MuduResultSetAsync,
+    pulled: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl ResultSetAsync for CountingResultSet {
+    async fn next(&self) -> RS<Option<TupleValue>> {
+        let row = self.rows.next().await?;
+        if row.is_some() {
+            self.pulled.fetch_add(1, Ordering::SeqCst);
+        }
+        Ok(row)
+    }
+
+    fn desc(&self) -> &TupleFieldDesc {
+        self.rows.desc()
+    }
+}
+
+fn counting_result_set(count: i32) -> (Arc<dyn ResultSetAsync>, Arc<AtomicUsize>) {
+    let pulled = Arc::new(AtomicUsize::new(0));
+    let rows = MuduResultSetAsync::from_rows(
+        (0..count)
+            .map(|id| TupleValue::from(vec![DataValue::from_i32(id)]))
+            .collect(),
+        TupleFieldDesc::new(vec![DatumDesc::new(
+            "id".to_string(),
+            DataType::default_for(TypeFamily::I32),
+        )]),
+    );
+    let result_set = CountingResultSet {
+        rows,
+        pulled: pulled.clone(),
+    };
+    (Arc::new(result_set), pulled)
+}
+
+fn result_set(count: i32) -> Arc<dyn ResultSetAsync> {
+    counting_result_set(count).0
+}
+
+/// Records how the transaction a cursor owns ended.
+#[derive(Default)]
+struct TxEnd {
+    committed: AtomicUsize,
+    rolled_back: AtomicUsize,
+}
+
+struct TestTx {
+    end: Arc<TxEnd>,
+    committed: bool,
+}
+
+#[async_trait]
+impl CursorTx for TestTx {
+    async fn commit(mut self: Box<Self>) -> RS<()> {
+        self.committed = true;
+        self.end.committed.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+impl Drop for TestTx {
+    fn drop(&mut self) {
+        if !self.committed {
+            self.end.rolled_back.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+}
+
+fn ids(rows: &[TupleValue]) -> Vec<i32> {
+    rows.iter()
+        .map(|row| *row.values()[0].expect_i32())
+        .collect()
+}
+
+/// Run the first page of a query the way the request path does and park the
+/// rest on a cursor.
+fn open_with(
+    cursors: &QueryCursors,
+    conn_id: u64,
+    session_id: u128,
+    result_set: Arc<dyn ResultSetAsync>,
+    read_tx: ReadTx,
+) -> (Vec<i32>, u64) {
+    let (rows, next) = block_on(read_page(result_set.as_ref(), 2)).unwrap();
+    let cursor_id = cursors
+        .open(conn_id, session_id, result_set, next.unwrap(), read_tx, 2)
+        .unwrap();
+    (ids(&rows), cursor_id)
+}
+
+fn open(cursors: &QueryCursors, conn_id: u64, session_id: u128, count: i32) -> (Vec<i32>, u64) {
+    open_with(
+        cursors,
+        conn_id,
+        session_id,
+        result_set(count),
+        ReadTx::Session,
+    )
+}
+
+#[test]
+fn read_page_reads_one_row_ahead() {
+    let result_set = result_set(2);
+    let (rows, next) = block_on(read_page(result_set.as_ref(), 2)).unwrap();
+    assert_eq!(ids(&rows), vec![0, 1]);
+    assert!(next.is_none());
+
+    let longer = result_set(3);
+    let (rows, next) = block_on(read_page(longer.as_ref(), 2)).unwrap();
+    assert_eq!(ids(&rows), vec![0, 1]);
+    assert_eq!(ids(&[next.unwrap()]), vec![2]);
+}
+
+#[test]
+fn read_page_ends_at_the_byte_budget() {
+    // Four rows of a quarter of the budget each fill it.
+    let text = "x".repeat(MAX_PAGE_BYTES / 4);
+    let result_set = MuduResultSetAsync::from_rows(
+        (0..6)
+            .map(|_| TupleValue::from(vec![DataValue::from_string(text.clone())]))
+            .collect(),
+        TupleFieldDesc::new(vec![DatumDesc::new(
+            "text".to_string(),
+            DataType::default_for(TypeFamily::String),
+        )]),
+    );
+    let (rows, next) = block_on(read_page(&result_set, 100)).unwrap();
+    assert_eq!(rows.len(), 4);
+    assert!(next.is_some());
+}
+
+#[test]
+fn fetch_pages_until_the_cursor_is_drained() {
+    let cursors = QueryCursors::new();
+    let (first, cursor_id) = open(&cursors, 1, 7, 5);
+    assert_eq!(first, vec![0, 1]);
+
+    let page = block_on(cursors.fetch(1, 7, cursor_id, 0)).unwrap();
+    assert_eq!(ids(page.rows()), vec![2, 3]);
+    assert_eq!(page.cursor_id(), Some(cursor_id));
+
+    // The last page closes the cursor without another round trip.
+    let page = block_on(cursors.fetch(1, 7, cursor_id, 10)).unwrap();
+    assert_eq!(ids(page.rows()), vec![4]);
+    assert_eq!(page.cursor_id(), None);
+    let err = block_on(cursors.fetch(1, 7, cursor_id, 0)).unwrap_err();
+    assert_eq!(err.ec(), ErrorCode::EntityNotFound);
+}
+
+#[test]
+fn cursors_belong_to_their_connection_and_session() {
+    let cursors = QueryCursors::new();
+    let (_, cursor_id) = open(&cursors, 1, 7, 5);
+    for (conn_id, session_id) in [(2, 7), (1, 8)] {
+        let err = block_on(cursors.fetch(conn_id, session_id, cursor_id, 0)).unwrap_err();
+        assert_eq!(err.ec(), ErrorCode::EntityNotFound);
+        assert!(!cursors.close(conn_id, session_id, cursor_id).unwrap());
+    }
+    assert!(cursors.close(1, 7, cursor_id).unwrap());
+    assert!(!cursors.close(1, 7, cursor_id).unwrap());
+}
+
+#[test]
+fn closing_a_session_or_connection_drops_its_cursors() {
+    let cursors = QueryCursors::new();
+    let (_, in_session) = open(&cursors, 1, 7, 5);
+    let (_, other_session) = open(&cursors, 1, 8, 5);
+    let (_, other_conn) = open(&cursors, 2, 9, 5);
+
+    cursors.drop_session(7).unwrap();
+    assert!(block_on(cursors.fetch(1, 7, in_session, 0)).is_err());
+    assert!(block_on(cursors.fetch(1, 8, other_session, 0)).is_ok());
+
+    cursors.drop_connection(1).unwrap();
+    assert!(block_on(cursors.fetch(1, 8, other_session, 0)).is_err());
+    assert!(block_on(cursors.fetch(2, 9, other_conn, 0)).is_ok());
+}
+
+#[test]
+fn open_cursors_per_connection_are_capped() {
+    let cursors = QueryCursors::new();
+    for _ in 0..MAX_OPEN_CURSORS {
+        open(&cursors, 1, 7, 3);
+    }
+    let row = || TupleValue::from(vec![DataValue::from_i32(0)]);
+    let err = cursors
+        .open(1, 7, result_set(1), row(), ReadTx::Done, 2)
+        .unwrap_err();
+    assert_eq!(err.ec(), ErrorCode::QuotaExceeded);
+    // Other connections have their own budget.
+    assert!(cursors
+        .open(2, 7, result_set(1), row(), ReadTx::Done, 2)
+        .is_ok());
+}
+
+#[test]
+fn fetch_pulls_only_the_page_it_returns() {
+    let cursors = QueryCursors::new();
+    let (result_set, pulled) = counting_result_set(100_000);
+    let (first, cursor_id) = open_with(&cursors, 1, 7, result_set, ReadTx::Session);
+    assert_eq!(first, vec![0, 1]);
+    // The first page and the row read ahead of it.
+    assert_eq!(pulled.load(Ordering::SeqCst), 3);
+
+    let page = block_on(cursors.fetch(1, 7, cursor_id, 5)).unwrap();
+    assert_eq!(ids(page.rows()), vec![2, 3, 4, 5, 6]);
+    assert_eq!(pulled.load(Ordering::SeqCst), 8);
+}
+
+#[test]
+fn the_owned_tx_commits_when_drained_and_rolls_back_when_closed() {
+    let cursors = QueryCursors::new();
+    let drained = Arc::new(TxEnd::default());
+    let tx = TestTx {
+        end: drained.clone(),
+        committed: false,
+    };
+    let (_, cursor_id) = open_with(&cursors, 1, 7, result_set(4), ReadTx::Owned(Box::new(tx)));
+    let page = block_on(cursors.fetch(1, 7, cursor_id, 0)).unwrap();
+    assert_eq!(page.cursor_id(), None);
+    assert_eq!(drained.committed.load(Ordering::SeqCst), 1);
+    assert_eq!(drained.rolled_back.load(Ordering::SeqCst), 0);
+
+    let closed = Arc::new(TxEnd::default());
+    let tx = TestTx {
+        end: closed.clone(),
+        committed: false,
+    };
+    let (_, cursor_id) = open_with(&cursors, 1, 7, result_set(5), ReadTx::Owned(Box::new(tx)));
+    assert!(cursors.close(1, 7, cursor_id).unwrap());
+    assert_eq!(closed.committed.load(Ordering::SeqCst), 0);
+    assert_eq!(closed.rolled_back.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn ending_the_session_tx_drops_only_cursors_reading_in_it() {
+    let cursors = QueryCursors::new();
+    let (_, in_tx) = open(&cursors, 1, 7, 5);
+    let (_, done) = open_with(&cursors, 1, 7, result_set(5), ReadTx::Done);
+    let tx = TestTx {
+        end: Arc::new(TxEnd::default()),
+        committed: false,
+    };
+    let (_, owning) = open_with(&cursors, 1, 7, result_set(5), ReadTx::Owned(Box::new(tx)));
+
+    cursors.drop_session_tx(7).unwrap();
+    assert!(block_on(cursors.fetch(1, 7, in_tx, 0)).is_err());
+    assert!(block_on(cursors.fetch(1, 7, owning, 0)).is_ok());
+    assert!(block_on(cursors.fetch(1, 7, done, 0)).is_ok());
+}
@@ -0,0 +1,1028 @@
+//! Streaming replication to read-only standby servers.
+//!
+//! A primary started with a replication port serves its worker logs to
+//! standbys (see [`spawn_replication_source`]). A standby opens one stream
+//! per worker, asks for the frames after the newest LSN of its own copy of
+//! that worker's log, and applies what it receives with the replay path of
+//! crash recovery (`WorkerXContract::apply_replicated_frames_async`). Until
+//! it is promoted, a standby only runs read-only transactions, which read at
+//! the snapshot horizon the primary logs with every dirty-page flush round.
+//!
+//! A standby starts from a restored backup of its primary, so its worker
+//! registry and log LSNs continue the primary's. Only the worker logs are
+//! streamed: schema changes and fs objects are not, and a standby has to be
+//! reseeded after either. The primary only ships frames once they are
+//! fsynced, so a standby never holds a transaction a crash of the primary
+//! loses.
+//!
+//! Every message is the big-endian `u32` length of its msgpack header, the
+//! `u32` length of its payload, then both. Only [`PrimaryMessage::Frames`]
+//! has a payload: log frames back to back, as laid out in a chunk file.
+
+use crate::contract::user::{UserDesc, UserRole};
+use crate::server::auth::{AuthCfg, ServerAuth};
+use crate::server::metrics::Exposition;
+use crate::server::server::adopt_worker_listener;
+use crate::server::server_launch::ServerLaunch;
+use crate::server::worker::WorkerRuntime;
+use crate::wal::log_frame::frame_lsn;
+use crate::wal::worker_log::{scan_valid_frame_prefix, synced_lsn_of, WorkerLogLayout};
+use mudu::common::id::OID;
+use mudu::common::result::RS;
+use mudu::error::{ErrorCode, MuduError};
+use mudu::mudu_error;
+use mudu_contract::protocol::scram::{ScramClient, ScramCredential, DEFAULT_SCRAM_ITERATIONS};
+use mudu_contract::protocol::{AuthRequest, AuthResponse};
+use mudu_sys::contract::async_file::AsyncFile;
+use mudu_sys::contract::async_fs::AsyncFs;
+use mudu_sys::contract::file_options::FileOptions;
+use mudu_sys::default_sys_io_context;
+use mudu_sys::net::{AsyncTcpListener, AsyncTcpStream};
+use mudu_sys::sync::async_::stop_flag::StopRx;
+use mudu_sys::sync::async_::ANotify;
+use mudu_sys::sync::SMutex;
+use mudu_sys::task::sync::SJoinHandle;
+use mudu_sys::time::{instant_now, Instant};
+use mudu_sys::tokio;
+use mudu_sys::tokio::io::{AsyncReadExt, AsyncWriteExt};
+use mudu_utils::notifier::Waiter;
+use mudu_utils::task_async::{spawn_local_task, CurrentThreadTaskRuntime};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+/// Version of the replication protocol; both servers must speak the same.
+pub const REPLICATION_PROTOCOL_VERSION: u32 = 1;
+
+/// Largest message header or payload either side accepts.
+const MAX_MESSAGE_BYTES: usize = 64 * 1024 * 1024;
+
+/// Log bytes the primary reads and ships at once.
+const MAX_FRAMES_BYTES: usize = 4 * 1024 * 1024;
+
+/// How often the primary looks for new frames once a stream caught up.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// How often the primary reports its log end to an idle stream.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long a standby waits before reconnecting a dropped stream.
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum StandbyMessage {
+    /// A step of the SCRAM exchange; only when the primary requires
+    /// authentication.
+    Auth(AuthRequest),
+    /// Starts streaming the log of worker `log_oid` from the frame after
+    /// `after_lsn`, or from its first frame.
+    Start {
+        version: u32,
+        log_oid: String,
+        after_lsn: Option<u64>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum PrimaryMessage {
+    Auth(AuthResponse),
+    /// Log frames in the payload; `primary_lsn` is the LSN the primary's
+    /// next frame of this log will get.
+    Frames {
+        primary_lsn: u64,
+    },
+    Heartbeat {
+        primary_lsn: u64,
+    },
+    /// The stream cannot continue; the standby does not retry.
+    Error {
+        message: String,
+    },
+}
+
+async fn write_message<M: Serialize>(
+    stream: &mut AsyncTcpStream,
+    message: &M,
+    payload: &[u8],
+) -> RS<()> {
+    let header = rmp_serde::to_vec(message)
+        .map_err(|e| mudu_error!(ErrorCode::Encode, "encode replication message error", e))?;
+    let mut buf = Vec::with_capacity(8 + header.len() + payload.len());
+    buf.extend_from_slice(&(header.len() as u32).to_be_bytes());
+    buf.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    buf.extend_from_slice(&header);
+    buf.extend_from_slice(payload);
+    stream
+        .write_all(&buf)
+        .await
+        .map_err(|e| mudu_error!(ErrorCode::Network, "write replication message error", e))
+}
+
+async fn read_message<M: DeserializeOwned>(stream: &mut AsyncTcpStream) -> RS<(M, Vec<u8>)> {
+    let mut lens = [0u8; 8];
+    read_exact(stream, &mut lens).await?;
+    let header_len = u32::from_be_bytes([lens[0], lens[1], lens[2], lens[3]]) as usize;
+    let payload_len = u32::from_be_bytes([lens[4], lens[5], lens[6], lens[7]]) as usize;
+    if header_len > MAX_MESSAGE_BYTES || payload_len > MAX_MESSAGE_BYTES {
+        return Err(mudu_error!(
+            ErrorCode::Decode,
+            format!(
+                "replication message of {} + {} bytes exceeds the limit of {}",
+                header_len, payload_len, MAX_MESSAGE_BYTES
+            )
+        ));
+    }
+    let mut header = vec![0u8; header_len];
+    read_exact(stream, &mut header).await?;
+    let mut payload = vec![0u8; payload_len];
+    read_exact(stream, &mut payload).await?;
+    let message = rmp_serde::from_slice(&header)
+        .map_err(|e| mudu_error!(ErrorCode::Decode, "decode replication message error", e))?;
+    Ok((message, payload))
+}
+
+async fn read_exact(stream: &mut AsyncTcpStream, buf: &mut [u8]) -> RS<()> {
+    stream
+        .read_exact(buf)
+        .await
+        .map(|_| ())
+        .map_err(|e| mudu_error!(ErrorCode::Network, "read replication message error", e))
+}
+
+/// Starts serving the worker logs to standbys when the server has a
+/// replication port. The port is bound before this returns, so a conflict
+/// fails startup; the returned thread exits once `stop` fires.
+pub(crate) fn spawn_replication_source(
+    cfg: &ServerLaunch,
+    stop: Waiter,
+) -> RS<Option<SJoinHandle<RS<()>>>> {
+    let Some(port) = cfg.cfg().replication_listen_port() else {
+        return Ok(None);
+    };
+    let listen_addr: SocketAddr = format!("{}:{}", cfg.cfg().listen_ip(), port)
+        .parse()
+        .map_err(|e| {
+            mudu_error!(
+                ErrorCode::Parse,
+                format!("parse replication listen address error: {}", port),
+                e
+            )
+        })?;
+    let listener = mudu_sys::net::sync::bind_tcp(listen_addr)?;
+    let admin = match cfg.cfg().auth() {
+        Some(auth) => Some(UserDesc::new(
+            auth.admin_user().to_string(),
+            UserRole::Admin,
+            ScramCredential::generate(auth.admin_password(), DEFAULT_SCRAM_ITERATIONS)?,
+        )),
+        None => None,
+    };
+    let source = Arc::new(ReplicationSource {
+        log_dir: PathBuf::from(cfg.cfg().log_dir()),
+        chunk_size: cfg.cfg().log_chunk_size(),
+        archive_dir: cfg.deps().wal_archive_dir().map(PathBuf::from),
+        worker_ids: cfg
+            .deps()
+            .worker_registry()
+            .workers()
+            .iter()
+            .map(|worker| worker.worker_id)
+            .collect(),
+        auth: ServerAuth::new(cfg.cfg().auth().cloned()),
+        admin,
+        next_conn_id: AtomicU64::new(1),
+    });
+    info!(addr = %listen_addr, "serving worker logs to standbys");
+    let handle = mudu_sys::task::sync::spawn_thread_named("replication-source", move || {
+        let runtime = CurrentThreadTaskRuntime::new().map_err(|e| {
+            mudu_error!(
+                ErrorCode::Tokio,
+                "build replication source runtime error",
+                e
+            )
+        })?;
+        runtime.block_on(async move {
+            listener.set_nonblocking(true).map_err(|e| {
+                mudu_error!(
+                    ErrorCode::Network,
+                    "set replication listener nonblocking error",
+                    e
+                )
+            })?;
+            let listener = adopt_worker_listener(listener).await?;
+            serve_standbys(source, listener, stop).await
+        })
+    })?;
+    Ok(Some(handle))
+}
+
+struct ReplicationSource {
+    log_dir: PathBuf,
+    chunk_size: u64,
+    archive_dir: Option<PathBuf>,
+    worker_ids: Vec<OID>,
+    auth: ServerAuth,
+    /// Catalog entry of the configured admin, the only user a standby can
+    /// authenticate as.
+    admin: Option<UserDesc>,
+    next_conn_id: AtomicU64,
+}
+
+async fn serve_standbys(
+    source: Arc<ReplicationSource>,
+    listener: AsyncTcpListener,
+    stop: Waiter,
+) -> RS<()> {
+    loop {
+        let accepted = tokio::select! {
+            accepted = listener.accept() => accepted,
+            _ = stop.wait() => return Ok(()),
+        };
+        let (stream, remote_addr) = match accepted {
+            Ok(accepted) => accepted,
+            Err(err) => {
+                warn!("accept standby connection error, {}", err);
+                continue;
+            }
+        };
+        let conn_id = source.next_conn_id.fetch_add(1, Ordering::Relaxed);
+        let source = source.clone();
+        spawn_local_task(
+            stop.clone(),
+            &format!("replication_stream_{conn_id}"),
+            async move {
+                if let Err(err) = serve_standby(&source, stream, conn_id).await {
+                    warn!(remote = %remote_addr, "replication stream closed, {}", err);
+                }
+                source.auth.forget(conn_id);
+            },
+        )?;
+    }
+}
+
+/// Serves one standby stream until the connection drops.
+async fn serve_standby(
+    source: &ReplicationSource,
+    mut stream: AsyncTcpStream,
+    conn_id: u64,
+) -> RS<()> {
+    stream.set_nodelay(true).map_err(|e| {
+        mudu_error!(
+            ErrorCode::Network,
+            "set standby connection nodelay error",
+            e
+        )
+    })?;
+    let mut tail = loop {
+        let (message, _) = read_message::<StandbyMessage>(&mut stream).await?;
+        let outcome = match message {
+            StandbyMessage::Auth(request) => match source.authenticate(conn_id, request) {
+                Ok(response) => {
+                    write_message(&mut stream, &PrimaryMessage::Auth(response), &[]).await?;
+                    continue;
+                }
+                Err(err) => Err(err),
+            },
+            StandbyMessage::Start {
+                version,
+                log_oid,
+                after_lsn,
+            } => source.start(conn_id, version, &log_oid, after_lsn),
+        };
+        match outcome {
+            Ok(tail) => break tail,
+            Err(err) => {
+                let message = PrimaryMessage::Error {
+                    message: err.to_string(),
+                };
+                write_message(&mut stream, &message, &[]).await?;
+                return Err(err);
+            }
+        }
+    };
+    let mut last_sent = instant_now();
+    loop {
+        let frames = match tail.read_next().await {
+            Ok(frames) => frames,
+            Err(err) => {
+                let message = PrimaryMessage::Error {
+                    message: format!("{}; reseed the standby from a backup of the primary", err),
+                };
+                write_message(&mut stream, &message, &[]).await?;
+                return Err(err);
+            }
+        };
+        let primary_lsn = tail.expected();
+        if !frames.is_empty() {
+            write_message(
+                &mut stream,
+                &PrimaryMessage::Frames { primary_lsn },
+                &frames,
+            )
+            .await?;
+            last_sent = instant_now();
+            continue;
+        }
+        if last_sent.elapsed() >= HEARTBEAT_INTERVAL {
+            write_message(&mut stream, &PrimaryMessage::Heartbeat { primary_lsn }, &[]).await?;
+            last_sent = instant_now();
+        }
+        mudu_sys::task::async_::sleep(POLL_INTERVAL).await;
+    }
+}
+
+impl ReplicationSource {
+    fn authenticate(&self, conn_id: u64, request: AuthRequest) -> RS<AuthResponse> {
+        match request {
+            AuthRequest::ClientFirst { user, client_nonce } => {
+                let desc = self
+                    .admin
+                    .as_ref()
+                    .filter(|admin| admin.name() == user)
+                    .cloned();
+                self.auth.server_first(conn_id, user, client_nonce, desc)
+            }
+            AuthRequest::ClientFinal { nonce, proof } => {
+                self.auth.server_final(conn_id, &nonce, &proof)
+            }
+        }
+    }
+
+    fn start(
+        &self,
+        conn_id: u64,
+        version: u32,
+        log_oid: &str,
+        after_lsn: Option<u64>,
+    ) -> RS<LogTail> {
+        if version != REPLICATION_PROTOCOL_VERSION {
+            return Err(mudu_error!(
+                ErrorCode::IncompatibleProtocolVersion,
+                format!(
+                    "standby speaks replication protocol {}, the primary {}",
+                    version, REPLICATION_PROTOCOL_VERSION
+                )
+            ));
+        }
+        if !self.auth.is_admin(conn_id) {
+            return Err(mudu_error!(
+                ErrorCode::PermissionDenied,
+                "replication requires an authenticated admin"
+            ));
+        }
+        let log_oid = log_oid
+            .parse::<OID>()
+            .ok()
+            .filter(|log_oid| self.worker_ids.contains(log_oid))
+            .ok_or_else(|| {
+                mudu_error!(
+                    ErrorCode::EntityNotFound,
+                    format!(
+                        "unknown worker log {}; seed the standby from a backup of the primary",
+                        log_oid
+                    )
+                )
+            })?;
+        let layout = WorkerLogLayout::new(&self.log_dir, log_oid, self.chunk_size)?
+            .with_archive_dir(self.archive_dir.clone());
+        Ok(LogTail::new(
+            layout,
+            default_sys_io_context().provider_arc().fs_arc(),
+            after_lsn.map_or(0, |lsn| lsn + 1),
+        ))
+    }
+}
+
+/// Reads one worker log from a given LSN on, following the writer from
+/// chunk to chunk. Chunks a checkpoint reclaimed are read from the archive
+/// directory when there is one. Standby streams and the change feed
+/// (`crate::server::change_feed`) read through it.
+pub(crate) struct LogTail {
+    layout: WorkerLogLayout,
+    fs: Arc<dyn AsyncFs>,
+    /// LSN of the next frame to ship.
+    expected: u64,
+    /// Chunk sequence and byte offset to read from next; `None` until the
+    /// chunk holding `expected` is found.
+    position: Option<(u64, u64)>,
+    /// True until the frames before `expected` in the located chunk were
+    /// passed over.
+    skipping: bool,
+    /// The newest frame passed over while `skipping`.
+    skipped_lsn: Option<u64>,
+}
+
+impl LogTail {
+    pub(crate) fn new(layout: WorkerLogLayout, fs: Arc<dyn AsyncFs>, expected: u64) -> Self {
+        Self {
+            layout,
+            fs,
+            expected,
+            position: None,
+            skipping: true,
+            skipped_lsn: None,
+        }
+    }
+
+    /// LSN of the next frame [`Self::read_next`] returns.
+    pub(crate) fn expected(&self) -> u64 {
+        self.expected
+    }
+
+    /// LSN of the oldest frame still readable, in the log directory or the
+    /// archive; `None` while the log holds no frame.
+    pub(crate) async fn oldest_lsn(&self) -> RS<Option<u64>> {
+        for sequence in self.sequences().await? {
+            if let Some(first) = self.first_lsn(sequence).await? {
+                return Ok(Some(first));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the complete frames fsynced after the last call, back to
+    /// back; empty when there are none yet. Frames a crash of the writer can
+    /// still lose are left for a later call (see
+    /// `crate::wal::worker_log::synced_lsn_of`).
+    pub(crate) async fn read_next(&mut self) -> RS<Vec<u8>> {
+        let Some((sequence, offset)) = self.locate().await? else {
+            return Ok(Vec::new());
+        };
+        let synced = synced_lsn_of(&self.layout)?;
+        let file = self.open_chunk(sequence).await?.ok_or_else(|| {
+            mudu_error!(
+                ErrorCode::InvalidState,
+                format!(
+                    "worker log chunk {} was reclaimed before it was read; \
+                     set wal_archive_dir to keep reclaimed chunks",
+                    sequence
+                )
+            )
+        })?;
+        let (frames, consumed) = read_frames(file.as_ref(), offset).await?;
+        let mut out = Vec::new();
+        let mut read = 0;
+        for frame in frames {
+            let lsn = frame_lsn(&frame)?.as_u64();
+            // The chunk found by `locate` may start before `expected`.
+            if self.skipping && lsn < self.expected {
+                self.skipped_lsn = Some(lsn);
+                read += frame.len() as u64;
+                continue;
+            }
+            if synced.is_some_and(|synced| lsn >= synced) {
+                self.position = Some((sequence, offset + read));
+                return Ok(out);
+            }
+            self.skipping = false;
+            if lsn != self.expected {
+                return Err(self.gap(lsn));
+            }
+            out.extend_from_slice(&frame);
+            read += frame.len() as u64;
+            self.expected += 1;
+        }
+        self.position = Some((sequence, offset + consumed));
+        if consumed == 0 {
+            if self.skipping {
+                // The log ends before `expected`: a standby holds frames the
+                // primary lost, or a reader asked for an LSN the log never reached.
+                if self.skipped_lsn.is_some_and(|lsn| lsn + 1 < self.expected) {
+                    return Err(mudu_error!(
+                        ErrorCode::InvalidState,
+                        format!(
+                            "LSN {} is ahead of the end of worker log {}",
+                            self.expected,
+                            self.layout.log_oid()
+                        )
+                    ));
+                }
+                self.skipping = false;
+            }
+            // Nothing new in this chunk: follow the writer once it started
+            // the next one.
+            if let Some(first) = self.first_lsn(sequence + 1).await? {
+                if first != self.expected {
+                    return Err(self.gap(first));
+                }
+                self.position = Some((sequence + 1, 0));
+            }
+        }
+        Ok(out)
+    }
+
+    /// Finds the chunk holding `expected`: the last one whose first frame
+    /// does not come after it.
+    async fn locate(&mut self) -> RS<Option<(u64, u64)>> {
+        if self.position.is_some() {
+            return Ok(self.position);
+        }
+        let mut found = None;
+        for sequence in self.sequences().await? {
+            match self.first_lsn(sequence).await? {
+                Some(first) if first <= self.expected => found = Some(sequence),
+                Some(first) if found.is_none() => return Err(self.gap(first)),
+                _ => break,
+            }
+        }
+        self.position = found.map(|sequence| (sequence, 0));
+        Ok(self.position)
+    }
+
+    fn gap(&self, lsn: u64) -> MuduError {
+        mudu_error!(
+            ErrorCode::InvalidState,
+            format!(
+                "worker log {} continues at {} instead of {}",
+                self.layout.log_oid(),
+                lsn,
+                self.expected
+            )
+        )
+    }
+
+    /// Chunk sequences in the log directory and the archive, ascending.
+    async fn sequences(&self) -> RS<Vec<u64>> {
+        let mut sequences = self.layout.chunk_sequences_async(self.fs.as_ref()).await?;
+        if let Some(archive_dir) = self.layout.archive_dir() {
+            if self.fs.path_exists(archive_dir).await? {
+                for path in self.fs.read_dir(archive_dir).await? {
+                    if let Some(sequence) = self.layout.parse_chunk_sequence(&path) {
+                        sequences.push(sequence);
+                    }
+                }
+            }
+        }
+        sequences.sort_unstable();
+        sequences.dedup();
+        Ok(sequences)
+    }
+
+    /// Opens chunk `sequence`, from the archive once a checkpoint moved it
+    /// there.
+    async fn open_chunk(&self, sequence: u64) -> RS<Option<Arc<dyn AsyncFile>>> {
+        let path = self.layout.chunk_path(sequence);
+        if self.fs.path_exists(&path).await? {
+            if let Ok(file) = self.fs.open(&path, FileOptions::read_only()).await {
+                return Ok(Some(file));
+            }
+        }
+        let (Some(archive_dir), Some(file_name)) = (self.layout.archive_dir(), path.file_name())
+        else {
+            return Ok(None);
+        };
+        let archived = archive_dir.join(file_name);
+        if !self.fs.path_exists(&archived).await? {
+            return Ok(None);
+        }
+        self.fs
+            .open(&archived, FileOptions::read_only())
+            .await
+            .map(Some)
+    }
+
+    async fn first_lsn(&self, sequence: u64) -> RS<Option<u64>> {
+        let Some(file) = self.open_chunk(sequence).await? else {
+            return Ok(None);
+        };
+        let (frames, _) = read_frames(file.as_ref(), 0).await?;
+        match frames.first() {
+            Some(frame) => Ok(Some(frame_lsn(frame)?.as_u64())),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Reads the complete frames of `file` from `offset` on, about
+/// [`MAX_FRAMES_BYTES`] of them, and the bytes they cover. A frame still
+/// being written is left for the next read.
+async fn read_frames(file: &dyn AsyncFile, offset: u64) -> RS<(Vec<Vec<u8>>, u64)> {
+    let len = file.file_len().await?;
+    if offset >= len {
+        return Ok((Vec::new(), 0));
+    }
+    let remaining = (len - offset) as usize;
+    let bytes = file
+        .read_exact_at(offset, remaining.min(MAX_FRAMES_BYTES))
+        .await?;
+    let mut prefix = scan_valid_frame_prefix(&bytes);
+    if prefix.frames.is_empty() && remaining > MAX_FRAMES_BYTES {
+        // The next frame alone is larger than one read.
+        prefix = scan_valid_frame_prefix(&file.read_exact_at(offset, remaining).await?);
+    }
+    Ok((prefix.frames, prefix.valid_len as u64))
+}
+
+/// Replication state of a standby server, shared by its workers and the
+/// admin API.
+pub struct Standby {
+    primary_addr: String,
+    credential: Option<AuthCfg>,
+    worker_count: usize,
+    promote_requested: AtomicBool,
+    /// Signalled once when a promote is requested.
+    promote: ANotify,
+    /// Signalled once every worker reported its promote.
+    promoted: ANotify,
+    workers: SMutex<BTreeMap<OID, WorkerStream>>,
+}
+
+/// What the metrics and the promote request see of one worker's stream.
+#[derive(Default)]
+struct WorkerStream {
+    connected: bool,
+    applied_lsn: u64,
+    primary_lsn: u64,
+    /// Since when the worker has not been known to be caught up with the
+    /// primary; `None` while it is.
+    behind_since: Option<Instant>,
+    /// The worker's promote result, once it has one.
+    promoted: Option<RS<()>>,
+}
+
+impl Standby {
+    pub fn new(primary_addr: String, credential: Option<AuthCfg>, worker_count: usize) -> Self {
+        Self {
+            primary_addr,
+            credential,
+            worker_count,
+            promote_requested: AtomicBool::new(false),
+            promote: ANotify::new(),
+            promoted: ANotify::new(),
+            workers: SMutex::new(BTreeMap::new()),
+        }
+    }
+
+    fn update(&self, log_oid: OID, update: impl FnOnce(&mut WorkerStream)) -> RS<()> {
+        let mut workers = self.workers.lock()?;
+        update(workers.entry(log_oid).or_default());
+        Ok(())
+    }
+
+    fn report_promoted(&self, log_oid: OID, result: RS<()>) -> RS<()> {
+        let mut workers = self.workers.lock()?;
+        workers.entry(log_oid).or_default().promoted = Some(result);
+        let reported = workers
+            .values()
+            .filter(|stream| stream.promoted.is_some())
+            .count();
+        if reported >= self.worker_count {
+            self.promoted.notify_waiters();
+        }
+        Ok(())
+    }
+}
+
+fn standbys() -> &'static SMutex<HashMap<PathBuf, Arc<Standby>>> {
+    static STANDBYS: OnceLock<SMutex<HashMap<PathBuf, Arc<Standby>>>> = OnceLock::new();
+    STANDBYS.get_or_init(|| SMutex::new(HashMap::new()))
+}
+
+/// Makes `standby` the replication state of the server whose worker logs
+/// live in `log_dir`, for [`promote_standby_async`] and the metrics.
+pub(crate) fn register_standby(log_dir: &Path, standby: Arc<Standby>) -> RS<()> {
+    standbys().lock()?.insert(log_dir.to_path_buf(), standby);
+    Ok(())
+}
+
+pub(crate) fn unregister_standby(log_dir: &Path) -> RS<()> {
+    standbys().lock()?.remove(log_dir);
+    Ok(())
+}
+
+/// Promotes the standby server whose worker logs live in `log_dir`: every
+/// worker stops streaming and becomes writable. Returns once all of them
+/// did; promoting an already promoted standby does nothing.
+pub async fn promote_standby_async(log_dir: &Path) -> RS<()> {
+    let standby = standbys()
+        .lock()?
+        .get(log_dir)
+        .cloned()
+        .ok_or_else(|| mudu_error!(ErrorCode::InvalidState, "the server is not a standby"))?;
+    if !standby.promote_requested.swap(true, Ordering::SeqCst) {
+        info!(primary = %standby.primary_addr, "promoting standby");
+        standby.promote.notify_waiters();
+    }
+    loop {
+        let notified = standby.promoted.notified();
+        {
+            let workers = standby.workers.lock()?;
+            let results: Vec<_> = workers
+                .values()
+                .filter_map(|stream| stream.promoted.as_ref())
+                .collect();
+            if results.len() >= standby.worker_count {
+                return match results.into_iter().find_map(|result| result.as_ref().err()) {
+                    Some(err) => Err(mudu_error!(
+                        ErrorCode::Internal,
+                        "promoting the standby failed",
+                        err.clone()
+                    )),
+                    None => Ok(()),
+                };
+            }
+        }
+        notified.await;
+    }
+}
+
+/// How a stream from the primary ended.
+enum StreamEnd {
+    Stopped,
+    Promote,
+    /// The connection failed; the stream is retried.
+    Disconnected(MuduError),
+    /// The log cannot be continued from the primary; not retried.
+    Failed(MuduError),
+}
+
+/// Streams this worker's log from the primary until the server stops or
+/// the standby is promoted, reconnecting after connection failures. Applies
+/// run to completion: they are never interrupted by a stop or a promote.
+pub(crate) async fn run_standby_worker(
+    worker: WorkerRuntime,
+    standby: Arc<Standby>,
+    mut stop_rx: StopRx,
+) -> RS<()> {
+    let log_oid = worker.worker_id();
+    standby.update(log_oid, |stream| stream.behind_since = Some(instant_now()))?;
+    loop {
+        if stop_rx.is_stopped() {
+            return Ok(());
+        }
+        if standby.promote_requested.load(Ordering::SeqCst) {
+            break;
+        }
+        worker.reset_replication_stream()?;
+        let end = stream_from_primary(&worker, &standby, &mut stop_rx).await;
+        standby.update(log_oid, |stream| {
+            stream.connected = false;
+            stream.behind_since.get_or_insert_with(instant_now);
+        })?;
+        match end {
+            StreamEnd::Stopped => return Ok(()),
+            StreamEnd::Promote => break,
+            StreamEnd::Disconnected(err) => {
+                warn!(worker_id = log_oid, "replication stream dropped, {}", err);
+                let retry = until_interrupted(
+                    async {
+                        mudu_sys::task::async_::sleep(RECONNECT_INTERVAL).await;
+                        Ok(())
+                    },
+                    &standby,
+                    &mut stop_rx,
+                )
+                .await;
+                match retry {
+                    Err(StreamEnd::Stopped) => return Ok(()),
+                    Err(StreamEnd::Promote) => break,
+                    _ => {}
+                }
+            }
+            StreamEnd::Failed(err) => {
+                error!(worker_id = log_oid, "replication stopped, {}", err);
+                let wait =
+                    until_interrupted(std::future::pending::<RS<()>>(), &standby, &mut stop_rx)
+                        .await;
+                match wait {
+                    Err(StreamEnd::Promote) => break,
+                    _ => return Ok(()),
+                }
+            }
+        }
+    }
+    let result = worker.promote_standby_async().await;
+    if let Err(err) = &result {
+        error!(
+            worker_id = log_oid,
+            "promoting standby worker failed, {}", err
+        );
+    }
+    standby.report_promoted(log_oid, result)
+}
+
+async fn stream_from_primary(
+    worker: &WorkerRuntime,
+    standby: &Standby,
+    stop_rx: &mut StopRx,
+) -> StreamEnd {
+    let log_oid = worker.worker_id();
+    let next_lsn = match worker.replication_next_lsn() {
+        Ok(Some(next_lsn)) => next_lsn.as_u64(),
+        Ok(None) => {
+            return StreamEnd::Failed(mudu_error!(
+                ErrorCode::InvalidState,
+                "a standby worker needs a worker log"
+            ))
+        }
+        Err(err) => return StreamEnd::Failed(err),
+    };
+    let connect = connect_to_primary(standby, log_oid, next_lsn.checked_sub(1));
+    let mut stream = match until_interrupted(connect, standby, stop_rx).await {
+        Ok(stream) => stream,
+        Err(end) => return end,
+    };
+    info!(
+        worker_id = log_oid,
+        primary = %standby.primary_addr,
+        next_lsn,
+        "replication stream started"
+    );
+    let connected = standby.update(log_oid, |stream| {
+        stream.connected = true;
+        stream.applied_lsn = next_lsn;
+    });
+    if let Err(err) = connected {
+        return StreamEnd::Failed(err);
+    }
+    loop {
+        let read = read_message::<PrimaryMessage>(&mut stream);
+        let (message, payload) = match until_interrupted(read, standby, stop_rx).await {
+            Ok(message) => message,
+            Err(end) => return end,
+        };
+        let primary_lsn = match message {
+            PrimaryMessage::Frames { primary_lsn } => {
+                let frames = scan_valid_frame_prefix(&payload);
+                if let Some(reason) = frames.corrupt_reason {
+                    return StreamEnd::Disconnected(mudu_error!(
+                        ErrorCode::CorruptedData,
+                        format!("replicated log frames are corrupt, {}", reason)
+                    ));
+                }
+                if let Err(err) = worker.apply_replicated_frames_async(frames.frames).await {
+                    return StreamEnd::Failed(err);
+                }
+                primary_lsn
+            }
+            PrimaryMessage::Heartbeat { primary_lsn } => primary_lsn,
+            PrimaryMessage::Error { message } => {
+                return StreamEnd::Failed(mudu_error!(
+                    ErrorCode::InvalidState,
+                    format!("the primary ended the replication stream: {}", message)
+                ))
+            }
+            PrimaryMessage::Auth(_) => {
+                return StreamEnd::Disconnected(mudu_error!(
+                    ErrorCode::Decode,
+                    "unexpected authentication message from the primary"
+                ))
+            }
+        };
+        let applied_lsn = match worker.replication_next_lsn() {
+            Ok(next_lsn) => next_lsn.map_or(0, |lsn| lsn.as_u64()),
+            Err(err) => return StreamEnd::Failed(err),
+        };
+        let updated = standby.update(log_oid, |stream| {
+            stream.applied_lsn = applied_lsn;
+            stream.primary_lsn = primary_lsn;
+            if applied_lsn >= primary_lsn {
+                stream.behind_since = None;
+            } else if stream.behind_since.is_none() {
+                stream.behind_since = Some(instant_now());
+            }
+        });
+        if let Err(err) = updated {
+            return StreamEnd::Failed(err);
+        }
+    }
+}
+
+/// Connects to the primary, authenticates when the standby has
+/// credentials, and starts streaming worker log `log_oid` after
+/// `after_lsn`.
+async fn connect_to_primary(
+    standby: &Standby,
+    log_oid: OID,
+    after_lsn: Option<u64>,
+) -> RS<AsyncTcpStream> {
+    let mut stream = AsyncTcpStream::connect(standby.primary_addr.as_str()).await?;
+    stream.set_nodelay(true).map_err(|e| {
+        mudu_error!(
+            ErrorCode::Network,
+            "set primary connection nodelay error",
+            e
+        )
+    })?;
+    if let Some(credential) = &standby.credential {
+        let mut client = ScramClient::new(credential.admin_user(), credential.admin_password());
+        let first = StandbyMessage::Auth(client.client_first());
+        write_message(&mut stream, &first, &[]).await?;
+        let server_first = expect_auth(&mut stream).await?;
+        let last = StandbyMessage::Auth(client.client_final(server_first)?);
+        write_message(&mut stream, &last, &[]).await?;
+        client.verify_server_final(expect_auth(&mut stream).await?)?;
+    }
+    let start = StandbyMessage::Start {
+        version: REPLICATION_PROTOCOL_VERSION,
+        log_oid: log_oid.to_string(),
+        after_lsn,
+    };
+    write_message(&mut stream, &start, &[]).await?;
+    Ok(stream)
+}
+
+async fn expect_auth(stream: &mut AsyncTcpStream) -> RS<AuthResponse> {
+    match read_message::<PrimaryMessage>(stream).await?.0 {
+        PrimaryMessage::Auth(response) => Ok(response),
+        PrimaryMessage::Error { message } => Err(mudu_error!(
+            ErrorCode::PermissionDenied,
+            format!("the primary rejected the standby: {}", message)
+        )),
+        _ => Err(mudu_error!(
+            ErrorCode::Decode,
+            "unexpected message from the primary during authentication"
+        )),
+    }
+}
+
+/// Runs `fut` unless the server stops or a promote is requested first.
+/// An error of `fut` counts as a dropped connection.
+async fn until_interrupted<T>(
+    fut: impl Future<Output = RS<T>>,
+    standby: &Standby,
+    stop_rx: &mut StopRx,
+) -> Result<T, StreamEnd> {
+    let mut fut = std::pin::pin!(fut);
+    loop {
+        if stop_rx.is_stopped() {
+            return Err(StreamEnd::Stopped);
+        }
+        tokio::select! {
+            result = &mut fut => return result.map_err(StreamEnd::Disconnected),
+            _ = standby.promote.notified() => return Err(StreamEnd::Promote),
+            changed = stop_rx.changed() => {
+                if !changed {
+                    return Err(StreamEnd::Stopped);
+                }
+            }
+        }
+    }
+}
+
+/// Writes the replication lag of every standby worker stream.
+pub(crate) fn write_metrics(out: &mut Exposition) -> RS<()> {
+    let standbys: Vec<_> = standbys().lock()?.values().cloned().collect();
+    let mut series = Vec::new();
+    for standby in standbys {
+        if standby.promote_requested.load(Ordering::SeqCst) {
+            continue;
+        }
+        for (log_oid, stream) in standby.workers.lock()?.iter() {
+            let lag = stream
+                .behind_since
+                .map_or(0.0, |since| since.elapsed().as_secs_f64());
+            series.push((
+                log_oid.to_string(),
+                lag,
+                stream.applied_lsn,
+                stream.primary_lsn,
+            ));
+        }
+    }
+
+    out.family(
+        "mudu_replication_lag_seconds",
+        "gauge",
+        "Seconds since a standby worker was last caught up with its primary.",
+    );
+    for (worker, lag, _, _) in &series {
+        out.sample(
+            "mudu_replication_lag_seconds",
+            &[("worker", worker.as_str())],
+            lag,
+        );
+    }
+    out.family(
+        "mudu_replication_applied_lsn",
+        "gauge",
+        "Next LSN a standby worker expects from its primary.",
+    );
+    for (worker, _, applied_lsn, _) in &series {
+        out.sample(
+            "mudu_replication_applied_lsn",
+            &[("worker", worker.as_str())],
+            applied_lsn,
+        );
+    }
+    out.family(
+        "mudu_replication_primary_lsn",
+        "gauge",
+        "Next LSN of the primary's worker log, as last reported to the standby.",
+    );
+    for (worker, _, _, primary_lsn) in &series {
+        out.sample(
+            "mudu_replication_primary_lsn",
+            &[("worker", worker.as_str())],
+            primary_lsn,
+        );
+    }
+    Ok(())
+}
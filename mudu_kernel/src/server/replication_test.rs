@@ -0,0 +1,191 @@
+#![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+//! Tests for [`super::replication`]: reading a primary's worker log from a
+//! given LSN on and the promote request on a server that is no standby.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use mudu::error::ErrorCode;
+use mudu_sys::env_var::temp_dir;
+use mudu_sys::fs::sync as sync_fs;
+use mudu_utils::oid::gen_oid;
+
+use crate::server::replication::{promote_standby_async, LogTail};
+use crate::wal::log_frame::frame_lsn;
+use crate::wal::worker_log::{
+    decode_frames, scan_valid_frame_prefix, ChunkedWorkerLogBackend, WalSyncPolicy, WorkerLogLayout,
+};
+use crate::wal::xl_batch::{append_xl_batch_async, decode_xl_batches, XLBatch};
+use crate::wal::xl_entry::{TxOp, XLEntry};
+
+const CHUNK_SIZE: u64 = 4096;
+
+fn test_dir(name: &str) -> PathBuf {
+    temp_dir().join(format!("replication_test_{}_{}", name, gen_oid()))
+}
+
+fn committed_at(xid: u64, unix_micros: u64) -> XLBatch {
+    XLBatch::new(vec![XLEntry {
+        xid,
+        ops: vec![TxOp::Begin, TxOp::Commit, TxOp::CommitTime { unix_micros }],
+    }])
+}
+
+/// Writes `count` committed transactions to a fresh worker log with small
+/// chunks, so the log spans several of them.
+async fn write_log(count: u64) -> (ChunkedWorkerLogBackend, WorkerLogLayout) {
+    let layout = WorkerLogLayout::new(test_dir("log"), gen_oid(), CHUNK_SIZE).unwrap();
+    let log = ChunkedWorkerLogBackend::new(layout.clone()).await.unwrap();
+    for xid in 1..=count {
+        append_xl_batch_async(&log, &committed_at(xid, xid * 10))
+            .await
+            .unwrap();
+    }
+    log.flush_async().await.unwrap();
+    (log, layout)
+}
+
+/// Reads the tail until it has nothing new and returns the LSNs it shipped.
+async fn drain(tail: &mut LogTail) -> Vec<u64> {
+    let mut lsns = Vec::new();
+    loop {
+        let payload = tail.read_next().await.unwrap();
+        if payload.is_empty() {
+            return lsns;
+        }
+        for frame in decode_frames(&payload).unwrap() {
+            lsns.push(frame_lsn(&frame).unwrap().as_u64());
+        }
+    }
+}
+
+#[test]
+fn log_tail_follows_the_log_across_chunks() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let (log, layout) = write_log(400).await;
+        assert!(sync_fs::path_exists(layout.chunk_path(1)));
+        let end = log.next_lsn().as_u64();
+
+        let mut tail = LogTail::new(layout.clone(), log.fs(), 0);
+        assert_eq!(drain(&mut tail).await, (0..end).collect::<Vec<_>>());
+        assert_eq!(tail.expected(), end);
+
+        // Frames written later are picked up where the tail stopped.
+        append_xl_batch_async(&log, &committed_at(401, 4010))
+            .await
+            .unwrap();
+        log.flush_async().await.unwrap();
+        let more = drain(&mut tail).await;
+        assert_eq!(more.first(), Some(&end));
+        assert_eq!(tail.expected(), log.next_lsn().as_u64());
+
+        // A standby that already holds a prefix resumes in a later chunk.
+        let resume = end - 3;
+        let mut tail = LogTail::new(layout, log.fs(), resume);
+        assert_eq!(drain(&mut tail).await.first(), Some(&resume));
+    })
+    .unwrap()
+}
+
+#[test]
+fn log_tail_refuses_a_standby_ahead_of_the_primary() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let (log, layout) = write_log(3).await;
+        let ahead = log.next_lsn().as_u64() + 5;
+        let mut tail = LogTail::new(layout, log.fs(), ahead);
+        // The first read passes over the frames the standby already holds.
+        assert!(tail.read_next().await.unwrap().is_empty());
+        let err = tail.read_next().await.unwrap_err();
+        assert_eq!(err.ec(), ErrorCode::InvalidState);
+    })
+    .unwrap()
+}
+
+#[test]
+fn log_tail_reports_reclaimed_chunks() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let (log, layout) = write_log(400).await;
+        sync_fs::remove_file(layout.chunk_path(0)).unwrap();
+        let mut tail = LogTail::new(layout, log.fs(), 0);
+        let err = tail.read_next().await.unwrap_err();
+        assert_eq!(err.ec(), ErrorCode::InvalidState);
+    })
+    .unwrap()
+}
+
+/// A primary that crashes with written but not yet fsynced frames restarts
+/// without them and logs other transactions at their LSNs. None of the lost
+/// frames may have been shipped, or the standby would diverge.
+#[test]
+fn log_tail_ships_only_fsynced_frames_across_a_primary_restart() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let layout = WorkerLogLayout::new(test_dir("unsynced"), gen_oid(), CHUNK_SIZE)
+            .unwrap()
+            .with_sync_policy(WalSyncPolicy::Periodic {
+                interval: Duration::from_secs(3600),
+            });
+        let log = ChunkedWorkerLogBackend::new(layout.clone()).await.unwrap();
+        for xid in 1..=3 {
+            log.append_entry_group_commit(&committed_at(xid, xid * 10))
+                .await
+                .unwrap();
+        }
+        log.fsync_unsynced_paths().await.unwrap();
+        let synced = log.next_lsn().as_u64();
+        // Written and acknowledged, but the fsync interval has not passed.
+        for xid in 4..=6 {
+            log.append_entry_group_commit(&committed_at(xid, xid * 10))
+                .await
+                .unwrap();
+        }
+        assert!(log.next_lsn().as_u64() > synced);
+
+        let mut tail = LogTail::new(layout.clone(), log.fs(), 0);
+        assert_eq!(drain(&mut tail).await, (0..synced).collect::<Vec<_>>());
+        assert_eq!(tail.expected(), synced);
+
+        // Crash: the unsynced tail never reached the disk.
+        drop(log);
+        let chunk = layout.chunk_path(0);
+        let bytes = sync_fs::read(&chunk).unwrap();
+        let kept: Vec<u8> = scan_valid_frame_prefix(&bytes)
+            .frames
+            .into_iter()
+            .filter(|frame| frame_lsn(frame).unwrap().as_u64() < synced)
+            .flatten()
+            .collect();
+        sync_fs::write(&chunk, kept).unwrap();
+
+        let log = ChunkedWorkerLogBackend::new(layout.clone()).await.unwrap();
+        assert_eq!(log.next_lsn().as_u64(), synced);
+        for xid in 7..=8 {
+            log.append_entry_group_commit(&committed_at(xid, xid * 10))
+                .await
+                .unwrap();
+        }
+        log.fsync_unsynced_paths().await.unwrap();
+
+        // The standby continues with the transactions the primary kept.
+        let payload = tail.read_next().await.unwrap();
+        let xids: Vec<u64> = decode_xl_batches(&decode_frames(&payload).unwrap())
+            .unwrap()
+            .into_iter()
+            .flat_map(|batch| batch.entries)
+            .map(|entry| entry.xid)
+            .collect();
+        assert_eq!(xids, vec![7, 8]);
+        assert_eq!(tail.expected(), log.next_lsn().as_u64());
+    })
+    .unwrap()
+}
+
+#[test]
+fn promote_is_refused_by_a_primary() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let err = promote_standby_async(&test_dir("primary"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.ec(), ErrorCode::InvalidState);
+    })
+    .unwrap()
+}
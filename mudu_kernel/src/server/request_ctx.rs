@@ -5,11 +5,14 @@ use mudu::mudu_error;
 use mudu_contract::database::result_set::ResultSetAsync;
 use mudu_contract::database::sql_param_value::SQLParamValue;
 use mudu_contract::database::sql_params::SQLParams;
+use mudu_contract::protocol::change_feed::ChangeFeedRequest;
 use mudu_contract::protocol::{
-    encode_get_response, encode_procedure_invoke_response, encode_put_response,
-    encode_range_scan_response, encode_server_response, encode_session_close_response,
-    encode_session_create_response, GetResponse, KeyValue, ProcedureInvokeResponse, PutResponse,
-    RangeScanResponse, ServerPerfDigest, ServerResponse, SessionCloseResponse,
+    encode_auth_response, encode_change_feed_response, encode_close_cursor_response,
+    encode_fetch_response, encode_get_response, encode_procedure_invoke_response,
+    encode_put_response, encode_range_scan_response, encode_server_response,
+    encode_session_close_response, encode_session_create_response, AuthRequest, CloseCursorRequest,
+    CloseCursorResponse, FetchRequest, GetResponse, KeyValue, ProcedureInvokeResponse, PutResponse,
+    RangeScanPage, RangeScanResponse, ServerPerfDigest, ServerResponse, SessionCloseResponse,
     SessionCreateResponse,
 };
 use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
@@ -19,10 +22,12 @@ use mudu_type::data_value::DataValue;
 use std::sync::Arc;
 
 use crate::server::async_func_task::HandleResult;
+use crate::server::query_cursor::{page_rows, MAX_PAGE_BYTES};
 use crate::server::request_response_worker::WorkerRuntimeRef;
 use crate::server::routing::parse_session_open_config;
 use crate::server::routing::SessionOpenConfig;
 use crate::server::worker_registry::WorkerRegistry;
+use crate::server::worker_snapshot::KvItem;
 
 #[derive(Clone)]
 pub(in crate::server) struct RequestCtx {
@@ -56,6 +61,29 @@ impl RequestCtx {
         self.worker.registry()
     }
 
+    pub(in crate::server) fn auth_required(&self) -> bool {
+        self.worker.auth_required()
+    }
+
+    pub(in crate::server) async fn authenticate(&self, request: AuthRequest) -> RS<HandleResult> {
+        let response = self.worker.authenticate(self.conn_id, request).await?;
+        Ok(HandleResult::Response(encode_auth_response(
+            self.request_id,
+            &response,
+        )?))
+    }
+
+    pub(in crate::server) async fn change_feed(
+        &self,
+        request: ChangeFeedRequest,
+    ) -> RS<HandleResult> {
+        let response = self.worker.read_changes(self.conn_id, &request).await?;
+        Ok(HandleResult::Response(encode_change_feed_response(
+            self.request_id,
+            &response,
+        )?))
+    }
+
     pub(in crate::server) fn parse_session_open_config(
         &self,
         config_json: Option<&str>,
@@ -130,16 +158,42 @@ impl RequestCtx {
         )?))
     }
 
+    /// Scan `[start_key, end_key)`. A paged scan returns at most the page
+    /// limit and, when the page is full, the last key as the continuation the
+    /// next page resumes after.
     pub(in crate::server) async fn range_scan(
         &self,
         session_id: OID,
         start_key: &[u8],
         end_key: &[u8],
+        page: Option<&RangeScanPage>,
     ) -> RS<HandleResult> {
-        let items = self
-            .worker
-            .range_async(session_id, start_key, end_key)
-            .await?;
+        let (items, continuation) = match page {
+            None => (
+                self.worker
+                    .range_async(session_id, start_key, end_key)
+                    .await?,
+                None,
+            ),
+            Some(page) => {
+                let limit = page_rows(page.limit());
+                let start_key = match page.continuation() {
+                    Some(last_key) => resume_key(start_key, last_key),
+                    None => start_key.to_vec(),
+                };
+                let mut items = self
+                    .worker
+                    .range_page(self.conn_id, session_id, &start_key, end_key, limit)
+                    .await?;
+                let cut = cut_range_page(&mut items);
+                let continuation = if cut || items.len() == limit {
+                    items.last().map(|item| item.key.clone())
+                } else {
+                    None
+                };
+                (items, continuation)
+            }
+        };
         Ok(HandleResult::Response(encode_range_scan_response(
             self.request_id,
             &RangeScanResponse::new(
@@ -147,7 +201,8 @@ impl RequestCtx {
                     .into_iter()
                     .map(|item| KeyValue::new(item.key, item.value))
                     .collect(),
-            ),
+            )
+            .with_continuation(continuation),
         )?))
     }
 
@@ -157,8 +212,12 @@ impl RequestCtx {
         _app_name: &str,
         sql: &str,
         params: &[DataValue],
+        fetch_size: u32,
         perf_digest: Option<ServerPerfDigest>,
     ) -> RS<HandleResult> {
+        if fetch_size > 0 {
+            return self.query_page(oid, sql, params, fetch_size).await;
+        }
         let exec_start = instant_now();
         let response = self
             .worker
@@ -175,6 +234,50 @@ impl RequestCtx {
         self.encode_server_response(response)
     }
 
+    /// Run a query and answer with its first `fetch_size` rows, leaving the
+    /// rest on a cursor when more remain.
+    async fn query_page(
+        &self,
+        oid: OID,
+        sql: &str,
+        params: &[DataValue],
+        fetch_size: u32,
+    ) -> RS<HandleResult> {
+        let response = self
+            .worker
+            .query_page(
+                self.conn_id,
+                oid,
+                Box::new(sql.to_string()),
+                sql_params(params),
+                fetch_size,
+            )
+            .await?;
+        Ok(HandleResult::Response(encode_fetch_response(
+            self.request_id,
+            &response,
+        )?))
+    }
+
+    pub(in crate::server) async fn fetch(&self, request: FetchRequest) -> RS<HandleResult> {
+        let response = self.worker.fetch_cursor(self.conn_id, &request).await?;
+        Ok(HandleResult::Response(encode_fetch_response(
+            self.request_id,
+            &response,
+        )?))
+    }
+
+    pub(in crate::server) async fn close_cursor(
+        &self,
+        request: CloseCursorRequest,
+    ) -> RS<HandleResult> {
+        let closed = self.worker.close_cursor(self.conn_id, &request)?;
+        Ok(HandleResult::Response(encode_close_cursor_response(
+            self.request_id,
+            &CloseCursorResponse::new(closed),
+        )?))
+    }
+
     pub(in crate::server) async fn execute_sql(
         &self,
         oid: OID,
@@ -286,6 +389,36 @@ impl RequestCtx {
     }
 }
 
+/// The first key after `last_key`, the resume point of a paged scan, but
+/// never before `start_key`.
+fn resume_key(start_key: &[u8], last_key: &[u8]) -> Vec<u8> {
+    let mut key = Vec::with_capacity(last_key.len() + 1);
+    key.extend_from_slice(last_key);
+    key.push(0);
+    if key.as_slice() < start_key {
+        start_key.to_vec()
+    } else {
+        key
+    }
+}
+
+/// End a range page at [`MAX_PAGE_BYTES`] of keys and values; returns
+/// whether items were cut off.
+fn cut_range_page(items: &mut Vec<KvItem>) -> bool {
+    let mut bytes = 0;
+    let last = items.iter().position(|item| {
+        bytes += item.key.len() + item.value.len();
+        bytes >= MAX_PAGE_BYTES
+    });
+    match last {
+        Some(last) if last + 1 < items.len() => {
+            items.truncate(last + 1);
+            true
+        }
+        _ => false,
+    }
+}
+
 /// Builds the worker-side SQL parameter object from wire values. An empty
 /// parameter list keeps the previous unit-params behavior exactly.
 fn sql_params(params: &[DataValue]) -> Box<dyn SQLParams> {
@@ -3,9 +3,19 @@ use crate::server::worker_registry::WorkerRegistry;
 use async_trait::async_trait;
 use mudu::common::id::OID;
 use mudu::common::result::RS;
-use mudu_contract::protocol::{ProcedureInvokeRequest, ProcedureInvokeResponse};
+use mudu::error::ErrorCode;
+use mudu::mudu_error;
+use mudu_contract::database::sql_params::SQLParams;
+use mudu_contract::database::sql_stmt::SQLStmt;
+use mudu_contract::protocol::change_feed::{ChangeFeedRequest, ChangeFeedResponse};
+use mudu_contract::protocol::{
+    AuthRequest, AuthResponse, CloseCursorRequest, FetchRequest, FetchResponse,
+    ProcedureInvokeRequest, ProcedureInvokeResponse,
+};
 use std::sync::Arc;
 
+use crate::server::worker_snapshot::KvItem;
+
 use crate::server::routing::SessionOpenConfig;
 
 #[async_trait]
@@ -25,6 +35,74 @@ pub trait RequestResponseWorker: Send + Sync {
         conn_id: u64,
         request: &ProcedureInvokeRequest,
     ) -> RS<ProcedureInvokeResponse>;
+
+    fn auth_required(&self) -> bool {
+        false
+    }
+
+    async fn authenticate(&self, _conn_id: u64, _request: AuthRequest) -> RS<AuthResponse> {
+        Err(mudu_error!(
+            ErrorCode::NotImplemented,
+            "authentication is not supported by this worker"
+        ))
+    }
+
+    async fn read_changes(
+        &self,
+        _conn_id: u64,
+        _request: &ChangeFeedRequest,
+    ) -> RS<ChangeFeedResponse> {
+        Err(mudu_error!(
+            ErrorCode::NotImplemented,
+            "change data capture is not supported by this worker"
+        ))
+    }
+
+    /// Scan at most `limit` items of `[start_key, end_key)` in a session of
+    /// `conn_id`.
+    async fn range_page(
+        &self,
+        _conn_id: u64,
+        _session_id: OID,
+        _start_key: &[u8],
+        _end_key: &[u8],
+        _limit: usize,
+    ) -> RS<Vec<KvItem>> {
+        Err(mudu_error!(
+            ErrorCode::NotImplemented,
+            "paged range scans are not supported by this worker"
+        ))
+    }
+
+    /// Run a query in a session of `conn_id` and answer with its first
+    /// `fetch_size` rows, leaving the rest on a cursor when more remain.
+    async fn query_page(
+        &self,
+        _conn_id: u64,
+        _session_id: OID,
+        _sql: Box<dyn SQLStmt>,
+        _param: Box<dyn SQLParams>,
+        _fetch_size: u32,
+    ) -> RS<FetchResponse> {
+        Err(mudu_error!(
+            ErrorCode::NotImplemented,
+            "query cursors are not supported by this worker"
+        ))
+    }
+
+    async fn fetch_cursor(&self, _conn_id: u64, _request: &FetchRequest) -> RS<FetchResponse> {
+        Err(mudu_error!(
+            ErrorCode::NotImplemented,
+            "query cursors are not supported by this worker"
+        ))
+    }
+
+    fn close_cursor(&self, _conn_id: u64, _request: &CloseCursorRequest) -> RS<bool> {
+        Err(mudu_error!(
+            ErrorCode::NotImplemented,
+            "query cursors are not supported by this worker"
+        ))
+    }
 }
 
 pub trait WorkerRuntimeApi: RequestResponseWorker + WorkerLocal {}
@@ -0,0 +1,134 @@
+//! Fence for rows written under a partition rule that is being replaced.
+//!
+//! A partition rule switch (`WorkerXContract::_alter_partition_rule`) copies
+//! rows to their new partition, bumps the rule version and then moves what
+//! is left in a catch-up transaction. A statement routed the old way may
+//! stage a row on its old partition after the copy scanned it, or commit
+//! after the catch-up did, which would strand the row where no reader looks
+//! for it. The rule is therefore fenced before the copy starts and until the
+//! catch-up is done.
+//!
+//! While a rule is fenced, every commit checks that the rows it inserts or
+//! updates route, under the new version of the rule, to the partition they
+//! were staged on, and fails with `ErrorCode::Transaction` otherwise. Deletes
+//! are not checked: a stale delete of a moved row conflicts with the
+//! catch-up, which deletes that row on its old partition under a lock.
+//!
+//! A commit holds [`RouteFence::enter`] from its check until its rows are
+//! applied, and a prepared two-phase transaction that passed the check is
+//! pinned until it is resolved. [`RouteFence::drain`] waits for both, so a
+//! scan that starts after it sees every row routed the old way.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use mudu::common::id::OID;
+use mudu::common::result::RS;
+use mudu_sys::sync::async_::{ANotify, FRwLock, FRwLockReadGuard};
+use mudu_sys::sync::SMutex;
+
+use crate::contract::partition_rule::PartitionRuleDesc;
+
+pub(crate) struct RouteFence {
+    /// The new version of every fenced rule, by rule id.
+    rules: SMutex<BTreeMap<OID, PartitionRuleDesc>>,
+    lock: FRwLock,
+    /// Set while [`RouteFence::drain`] waits for the lock; keeps new commits
+    /// out so a steady stream of them cannot starve it.
+    draining: AtomicBool,
+    resumed: ANotify,
+    /// Prepared two-phase transactions, by `(coordinator_worker_id, tx_id)`.
+    pinned: SMutex<BTreeSet<(OID, u64)>>,
+    unpinned: ANotify,
+}
+
+impl RouteFence {
+    pub(crate) fn new() -> Self {
+        Self {
+            rules: SMutex::new(BTreeMap::new()),
+            lock: FRwLock::new(),
+            draining: AtomicBool::new(false),
+            resumed: ANotify::new(),
+            pinned: SMutex::new(BTreeSet::new()),
+            unpinned: ANotify::new(),
+        }
+    }
+
+    /// The new versions of the fenced rules; empty when no rule switch runs.
+    pub(crate) fn fenced_rules(&self) -> RS<BTreeMap<OID, PartitionRuleDesc>> {
+        Ok(self.rules.lock()?.clone())
+    }
+
+    pub(crate) fn raise(&self, rule: &PartitionRuleDesc) -> RS<()> {
+        self.rules.lock()?.insert(rule.oid, rule.clone());
+        Ok(())
+    }
+
+    pub(crate) fn lower(&self, rule_id: OID) -> RS<()> {
+        self.rules.lock()?.remove(&rule_id);
+        Ok(())
+    }
+
+    /// Held by a commit from its route check until its rows are applied.
+    pub(crate) async fn enter(&self) -> FRwLockReadGuard<'_> {
+        loop {
+            if !self.draining.load(Ordering::Acquire) {
+                return self.lock.read().await;
+            }
+            self.resumed.clear_signal();
+            if !self.draining.load(Ordering::Acquire) {
+                continue;
+            }
+            self.resumed.notified().await;
+        }
+    }
+
+    /// Keeps [`Self::drain`] waiting until the prepared transaction is
+    /// unpinned. Taken while holding [`Self::enter`].
+    pub(crate) fn pin(&self, coordinator_worker_id: OID, tx_id: u64) -> RS<()> {
+        self.pinned.lock()?.insert((coordinator_worker_id, tx_id));
+        Ok(())
+    }
+
+    /// Unpins a resolved transaction; unpinning one that is not pinned is a
+    /// no-op.
+    pub(crate) fn unpin(&self, coordinator_worker_id: OID, tx_id: u64) -> RS<()> {
+        if self.pinned.lock()?.remove(&(coordinator_worker_id, tx_id)) {
+            self.unpinned.notify_waiters();
+        }
+        Ok(())
+    }
+
+    /// Waits until the commits holding [`Self::enter`] have applied their
+    /// rows and the transactions pinned so far are resolved. Transactions
+    /// whose coordinator is gone are resolved by the two-phase resolution
+    /// round, so the wait can last up to `TWO_PHASE_RESOLVE_DELAY`.
+    pub(crate) async fn drain(&self) -> RS<()> {
+        let pinned = {
+            self.draining.store(true, Ordering::Release);
+            let _draining = Draining { fence: self };
+            let _write = self.lock.write().await;
+            self.pinned.lock()?.clone()
+        };
+        loop {
+            self.unpinned.clear_signal();
+            if self.pinned.lock()?.is_disjoint(&pinned) {
+                return Ok(());
+            }
+            self.unpinned.notified().await;
+        }
+    }
+}
+
+// Raised `draining` flag; lowering it on drop keeps a cancelled `drain` from
+// locking commits out for good.
+struct Draining<'a> {
+    fence: &'a RouteFence,
+}
+
+impl Drop for Draining<'_> {
+    fn drop(&mut self) {
+        self.fence.draining.store(false, Ordering::Release);
+        self.fence.resumed.notify_waiters();
+    }
+}
@@ -1,8 +1,11 @@
 use crate::server::async_func_runtime::AsyncFuncInvokerPtr;
 use crate::server::async_func_task::HandleResult;
+use crate::server::auth::ServerAuth;
 use mudu_sys::contract::async_io_provider::AsyncIoProvider;
 
-use crate::server::frame_dispatch::{dispatch_frame_async, try_decode_next_frame};
+use crate::server::frame_dispatch::{
+    dispatch_frame_async, release_read_buf, try_decode_next_frame, READ_BUF_CAPACITY,
+};
 use crate::server::fs_gc::FS_GC_INTERVAL;
 use crate::server::message_bus_api::{
     register_worker_message_bus, set_current_message_bus, unregister_worker_message_bus,
@@ -10,13 +13,18 @@ use crate::server::message_bus_api::{
     OnRecvCallback, OutgoingMessage, RecvFilter, ServerInstanceId, SubscriptionId,
 };
 use crate::server::message_bus_state::WorkerMessageBusState;
+use crate::server::replication::{
+    register_standby, run_standby_worker, spawn_replication_source, unregister_standby, Standby,
+};
 use crate::server::session_bound_worker_runtime::{
     as_worker_local_ref, new_session_bound_worker_runtime,
 };
+use crate::server::version_gc::VERSION_GC_INTERVAL;
 use crate::server::worker::{WorkerRuntime, WorkerRuntimeParams};
 use crate::server::worker_local::{set_current_worker_local, unset_current_worker_local};
 use crate::server::worker_registry::{WorkerIdentity, WorkerRegistry};
 use crate::server::worker_storage::DIRTY_PAGE_FLUSH_INTERVAL;
+use crate::wal::typed_worker_log::skip_checkpointed_frames;
 use crate::wal::worker_log::{scan_valid_frame_prefix, ChunkedWorkerLogBackend, WorkerLogBackend};
 use crate::wal::worker_log::{WalSyncPolicy, WorkerLogBatching};
 use crate::wal::xl_batch::decode_xl_batches_with_pending;
@@ -42,6 +50,7 @@ use mudu_utils::task_async::{
 use mudu_sys::net::sync::StdTcpListener;
 use mudu_sys::sync::SMutex;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::mpsc;
 use std::sync::{atomic::AtomicBool, Arc};
@@ -205,10 +214,12 @@ struct WorkerBuildConfig {
     log_chunk_size: u64,
     log_batching: WorkerLogBatching,
     wal_sync_policy: WalSyncPolicy,
+    wal_archive_dir: Option<String>,
     procedure_runtime: Option<AsyncFuncInvokerPtr>,
     worker_identity: WorkerIdentity,
     worker_registry: Arc<WorkerRegistry>,
     async_runtime: Option<Arc<dyn AsyncIoProvider>>,
+    auth: Arc<ServerAuth>,
 }
 
 impl WorkerBuildConfig {
@@ -233,10 +244,12 @@ impl WorkerBuildConfig {
             log_chunk_size: server_cfg.log_chunk_size(),
             log_batching: deps.log_batching(),
             wal_sync_policy: deps.wal_sync_policy(),
+            wal_archive_dir: deps.wal_archive_dir().map(str::to_string),
             procedure_runtime: deps.procedure_runtime_for_worker(worker_id),
             worker_identity,
             worker_registry: deps.worker_registry(),
             async_runtime: deps.async_runtime(),
+            auth: deps.auth(),
         })
     }
 
@@ -249,10 +262,12 @@ impl WorkerBuildConfig {
             log_chunk_size: self.log_chunk_size,
             log_batching: self.log_batching,
             wal_sync_policy: self.wal_sync_policy,
+            wal_archive_dir: self.wal_archive_dir,
             procedure_runtime: self.procedure_runtime,
             registry: self.worker_registry,
             async_runtime: self.async_runtime,
             server_instance_id: self.server_instance_id,
+            auth: self.auth,
         })
         .await
     }
@@ -342,6 +357,7 @@ impl TokioTcpBackend {
         let stop_flag = Arc::new(AtomicBool::new(false));
         let service_ready = Arc::new(AtomicBool::new(false));
         let (stop_tx, stop_rx) = stop_channel();
+        let replication_source = spawn_replication_source(&cfg, stop.clone())?;
         let notifier = spawn_stop_bridge(
             "tokio-stop-bridge",
             stop,
@@ -351,6 +367,9 @@ impl TokioTcpBackend {
         )?;
         let result = sync_serve_tokio(cfg, stop_flag, stop_rx, service_ready, ready);
         wait_stop_bridge("tokio-stop-bridge", notifier)?;
+        if let Some(replication_source) = replication_source {
+            wait_stop_bridge("replication-source", replication_source)?;
+        }
         result
     }
 }
@@ -405,6 +424,19 @@ fn sync_serve_tokio(
         .collect();
     let (started_tx, started_rx) = mpsc::channel::<RS<()>>();
     let (rpc_ready_tx, rpc_ready_rx) = mpsc::channel::<RS<()>>();
+    let log_dir = PathBuf::from(cfg.cfg().log_dir());
+    let standby = match cfg.cfg().replicate_from() {
+        Some(primary_addr) => {
+            let standby = Arc::new(Standby::new(
+                primary_addr.to_string(),
+                cfg.cfg().auth().cloned(),
+                cfg.cfg().worker_count(),
+            ));
+            register_standby(&log_dir, standby.clone())?;
+            Some(standby)
+        }
+        None => None,
+    };
 
     let mut handles = Vec::with_capacity(cfg.cfg().worker_count());
     for worker_id in 0..cfg.cfg().worker_count() {
@@ -419,6 +451,7 @@ fn sync_serve_tokio(
         let service_ready = service_ready.clone();
         let started_tx = started_tx.clone();
         let rpc_ready_tx = rpc_ready_tx.clone();
+        let standby = standby.clone();
         let listener = if let Some(prebound) = cfg.take_prebound_listener(worker_id) {
             prebound
         } else {
@@ -480,6 +513,40 @@ fn sync_serve_tokio(
                     worker.ensure_partition_rpc_handler()?;
                     recover_worker_log_tokio(&worker).await?;
                     worker.fs_gc_recover_scan().await?;
+                    // A standby streams the primary's log from where its
+                    // own log ends and leaves in-doubt transactions to the
+                    // primary until it is promoted.
+                    let standby_join = match standby {
+                        Some(standby) => {
+                            worker.begin_standby();
+                            let (_standby_task_notifier, standby_task_waiter) = notify_wait();
+                            Some(spawn_local_task(
+                                standby_task_waiter,
+                                &format!("standby_stream_{worker_id}"),
+                                run_standby_worker(worker.clone(), standby, stop_rx.clone()),
+                            )?)
+                        }
+                        None => {
+                            // In-doubt cross-partition transactions are
+                            // settled in the background: their coordinators
+                            // may still be recovering.
+                            let cross_tx_worker = worker.clone();
+                            spawn_local_detached(
+                                &format!("cross_tx_recovery_{worker_id}"),
+                                async move {
+                                    if let Err(err) =
+                                        cross_tx_worker.recover_cross_partition_transactions().await
+                                    {
+                                        error!(
+                                            worker_id = cross_tx_worker.worker_id(),
+                                            "cross-partition transaction recovery failed, {}", err
+                                        );
+                                    }
+                                },
+                            )?;
+                            None
+                        }
+                    };
                     let (_gc_task_notifier, gc_task_waiter) = notify_wait();
                     let fs_gc = worker.fs_gc();
                     let gc_stop_rx = stop_rx.clone();
@@ -488,6 +555,18 @@ fn sync_serve_tokio(
                         &format!("fs_gc_loop_{worker_id}"),
                         async move { fs_gc.gc_loop(FS_GC_INTERVAL, gc_stop_rx).await },
                     )?;
+                    let (_version_gc_task_notifier, version_gc_task_waiter) = notify_wait();
+                    let version_gc = worker.version_gc();
+                    let version_gc_stop_rx = stop_rx.clone();
+                    let version_gc_join = spawn_local_task(
+                        version_gc_task_waiter,
+                        &format!("version_gc_loop_{worker_id}"),
+                        async move {
+                            version_gc
+                                .gc_loop(VERSION_GC_INTERVAL, version_gc_stop_rx)
+                                .await
+                        },
+                    )?;
                     // WAL group-commit flush driver. It is stopped only after
                     // the worker loop has drained, so no commit can enqueue
                     // behind the final force-flush round.
@@ -597,8 +676,25 @@ fn sync_serve_tokio(
                         Some(result) => result,
                         None => Ok(()),
                     };
+                    let version_gc_result = match version_gc_join.await.map_err(|e| {
+                        mudu_error!(ErrorCode::Tokio, "join version gc loop task error", e)
+                    })? {
+                        Some(result) => result,
+                        None => Ok(()),
+                    };
+                    let standby_result = match standby_join {
+                        Some(standby_join) => match standby_join.await.map_err(|e| {
+                            mudu_error!(ErrorCode::Tokio, "join standby stream task error", e)
+                        })? {
+                            Some(result) => result,
+                            None => Ok(()),
+                        },
+                        None => Ok(()),
+                    };
                     loop_result
                         .and(gc_result)
+                        .and(version_gc_result)
+                        .and(standby_result)
                         .and(wal_flush_result)
                         .and(wal_fsync_result)
                         .and(page_flush_result)
@@ -645,6 +741,14 @@ fn sync_serve_tokio(
         ready.notify_all();
     }
 
+    let result = join_tokio_workers(handles);
+    if standby.is_some() {
+        unregister_standby(&log_dir)?;
+    }
+    result
+}
+
+fn join_tokio_workers(handles: Vec<SJoinHandle<RS<()>>>) -> RS<()> {
     for (worker_id, handle) in handles.into_iter().enumerate() {
         trace!(worker_id, "joining tokio worker");
         let result = handle
@@ -753,6 +857,13 @@ async fn recover_worker_log_tokio(worker: &WorkerRuntime) -> RS<()> {
         return Ok(());
     };
     let fs = log.fs();
+    // Chunks covered by the checkpoint may still be on disk (a crash before
+    // they were deleted); their frames are skipped after the restore.
+    let checkpoint = log.layout()?.read_checkpoint_async(fs.as_ref()).await?;
+    if let Some(checkpoint) = &checkpoint {
+        worker.restore_log_checkpoint(checkpoint)?;
+    }
+    let checkpoint_lsn = checkpoint.map(|checkpoint| checkpoint.lsn);
     let chunk_paths = log.chunk_paths_sorted().await?;
     // Multi-part batches can straddle chunk boundaries; keep the
     // not-yet-terminated frames across chunks and drop whatever is left
@@ -776,11 +887,9 @@ async fn recover_worker_log_tokio(worker: &WorkerRuntime) -> RS<()> {
                 "dropping un-persisted worker log chunk tail during recovery"
             );
         }
-        let batches = decode_xl_batches_with_pending(
-            &prefix.frames,
-            &mut pending_frames,
-            &mut pending_start_lsn,
-        )?;
+        let frames = skip_checkpointed_frames(prefix.frames, checkpoint_lsn)?;
+        let batches =
+            decode_xl_batches_with_pending(&frames, &mut pending_frames, &mut pending_start_lsn)?;
         for batch in batches {
             worker.replay_log_batch(batch).await?;
         }
@@ -853,18 +962,23 @@ async fn run_worker_wal_fsync_loop(log: ChunkedWorkerLogBackend, mut stop_rx: St
 }
 
 /// Writes back dirty time-series data pages of this worker's relations and
-/// meta catalogs. Shared by the tokio flush loop and the io_uring ring
+/// meta catalogs, logs the snapshot horizon for standbys, then takes a
+/// worker checkpoint once one is due and this worker's part of a pending
+/// online backup. Shared by the tokio flush loop and the io_uring ring
 /// loop's periodic flush round.
 pub(crate) async fn flush_worker_dirty_pages(worker: &WorkerRuntime) -> RS<()> {
     worker.storage().flush_dirty_pages_async().await?;
     worker.meta_mgr().flush_dirty_pages().await?;
+    worker.log_snapshot_horizon_if_advanced().await?;
+    worker.checkpoint_if_due().await?;
+    worker.backup_if_requested().await?;
     Ok(())
 }
 
 /// Tokio deferred data-page flush driver. Every `DIRTY_PAGE_FLUSH_INTERVAL`
-/// it writes back the worker's dirty time-series pages; on stop it flushes
-/// whatever remains so the data files are clean before the worker tears
-/// down. A failed round is logged and retried at the next interval because
+/// it writes back the worker's dirty time-series pages and settles in-doubt
+/// cross-partition transactions; on stop it flushes whatever remains so the
+/// data files are clean before the worker tears down. A failed round is logged and retried at the next interval because
 /// dirty marks survive a failed flush; the final flush result propagates.
 async fn run_worker_page_flush_loop(worker: WorkerRuntime, mut stop_rx: StopRx) -> RS<()> {
     loop {
@@ -877,6 +991,16 @@ async fn run_worker_page_flush_loop(worker: WorkerRuntime, mut stop_rx: StopRx)
                 "page flush round failed, {}", err
             );
         }
+        // A standby settles nothing: the primary's decisions arrive with
+        // its log.
+        if !worker.is_standby() {
+            if let Err(err) = worker.resolve_cross_partition_transactions().await {
+                error!(
+                    worker_id = worker.worker_id(),
+                    "cross-partition transaction resolution failed, {}", err
+                );
+            }
+        }
         tokio::select! {
             _ = mudu_sys::task::async_::sleep(DIRTY_PAGE_FLUSH_INTERVAL) => {}
             changed = stop_rx.changed() => {
@@ -902,8 +1026,37 @@ async fn handle_tokio_connection(
     stream
         .set_nodelay(true)
         .map_err(|e| mudu_error!(ErrorCode::Network, "set tokio connection nodelay error", e))?;
-    let mut read_buf: Vec<u8> = Vec::with_capacity(8192);
-    let mut chunk = vec![0u8; 8192];
+    // Cursors and sessions of the connection are dropped however serving it
+    // ends, including on a rejected frame.
+    let served = serve_tokio_connection(
+        &worker,
+        &mut stream,
+        conn_id,
+        &stop,
+        &mut stop_rx,
+        &service_ready,
+    )
+    .await;
+    worker.close_connection_sessions(conn_id)?;
+    trace!(worker_id = worker.worker_id(), conn_id, remote = %remote_addr, "tokio connection closed");
+    served
+}
+
+/// Serve frames until the peer closes or the worker stops. Each response is
+/// written before the next frame is read, so a peer that stops reading
+/// responses (or pages) stops the connection instead of piling up output;
+/// pages are bounded by `query_cursor::MAX_PAGE_BYTES`, so the one response
+/// in flight is too.
+async fn serve_tokio_connection(
+    worker: &WorkerRuntime,
+    stream: &mut AsyncTcpStream,
+    conn_id: u64,
+    stop: &AtomicBool,
+    stop_rx: &mut StopRx,
+    service_ready: &AtomicBool,
+) -> RS<()> {
+    let mut read_buf: Vec<u8> = Vec::with_capacity(READ_BUF_CAPACITY);
+    let mut chunk = vec![0u8; READ_BUF_CAPACITY];
     loop {
         crate::server::stage_stats::dump_if_due(worker.worker_id());
         if stop.load(Ordering::Relaxed) || stop_rx.is_stopped() {
@@ -934,7 +1087,7 @@ async fn handle_tokio_connection(
                 })?;
                 continue;
             }
-            match dispatch_frame_async(&worker, conn_id, &frame).await {
+            match dispatch_frame_async(worker, conn_id, &frame).await {
                 Ok(HandleResult::Response(payload)) => {
                     stream.write_all(&payload).await.map_err(|e| {
                         mudu_error!(ErrorCode::Network, "write tokio tcp response error", e)
@@ -948,9 +1101,8 @@ async fn handle_tokio_connection(
                 }
             }
         }
+        release_read_buf(&mut read_buf);
     }
-    worker.close_connection_sessions(conn_id)?;
-    trace!(worker_id = worker.worker_id(), conn_id, remote = %remote_addr, "tokio connection closed");
     Ok(())
 }
 
@@ -975,7 +1127,7 @@ fn create_listener(listen_addr: SocketAddr) -> RS<StdTcpListener> {
 ///
 /// Native backend: adopts the real OS socket via `into_inner()`.
 #[cfg(not(feature = "ds"))]
-async fn adopt_worker_listener(listener: StdTcpListener) -> RS<AsyncTcpListener> {
+pub(crate) async fn adopt_worker_listener(listener: StdTcpListener) -> RS<AsyncTcpListener> {
     AsyncTcpListener::from_std(listener.into_inner())
         .map_err(|e| mudu_error!(ErrorCode::Network, "convert tokio tcp listener error", e))
 }
@@ -988,7 +1140,7 @@ async fn adopt_worker_listener(listener: StdTcpListener) -> RS<AsyncTcpListener>
 /// and rebinds the same address on the simulated async listener that
 /// simulated async clients connect to.
 #[cfg(feature = "ds")]
-async fn adopt_worker_listener(listener: StdTcpListener) -> RS<AsyncTcpListener> {
+pub(crate) async fn adopt_worker_listener(listener: StdTcpListener) -> RS<AsyncTcpListener> {
     let addr = listener.local_addr().map_err(|e| {
         mudu_error!(
             ErrorCode::Network,
@@ -1019,6 +1171,7 @@ mod tests {
     )]
 
     use super::*;
+    use crate::server::frame_dispatch::MAX_REQUEST_PAYLOAD_LEN;
     use mudu_contract::protocol::encode_get_request;
     use mudu_contract::protocol::GetRequest;
     use mudu_contract::protocol::HEADER_LEN;
@@ -1036,4 +1189,14 @@ mod tests {
         assert_eq!(decoded.0.header().request_id(), 1);
         assert_eq!(decoded.1, encoded.len());
     }
+
+    #[test]
+    fn try_decode_next_frame_rejects_oversized_payload() {
+        let mut encoded = encode_get_request(1, &GetRequest::new(1, b"k".to_vec())).unwrap();
+        // The payload length is the last field of the header.
+        let oversized = (MAX_REQUEST_PAYLOAD_LEN + 1) as u32;
+        encoded[HEADER_LEN - 4..HEADER_LEN].copy_from_slice(&oversized.to_be_bytes());
+        let err = try_decode_next_frame(&encoded[..HEADER_LEN]).unwrap_err();
+        assert_eq!(err.ec(), ErrorCode::Parse);
+    }
 }
@@ -1,3 +1,4 @@
+use crate::server::auth::AuthCfg;
 use crate::server::message_bus_api::ServerInstanceId;
 use crate::server::routing::RoutingMode;
 use crate::storage::page::page_block_ref::DEFAULT_PAGE_SIZE;
@@ -34,6 +35,10 @@ pub struct ServerCfg {
     page_size: usize,
     log_batching_max_wait: Duration,
     wal_sync_policy: WalSyncPolicy,
+    wal_archive_dir: Option<String>,
+    auth: Option<AuthCfg>,
+    replicate_from: Option<String>,
+    replication_listen_port: Option<u16>,
 }
 
 impl ServerCfg {
@@ -65,6 +70,10 @@ impl ServerCfg {
             page_size: DEFAULT_PAGE_SIZE,
             log_batching_max_wait: DEFAULT_LOG_BATCHING_MAX_WAIT,
             wal_sync_policy: WalSyncPolicy::Commit,
+            wal_archive_dir: None,
+            auth: None,
+            replicate_from: None,
+            replication_listen_port: None,
         })
     }
 
@@ -90,6 +99,36 @@ impl ServerCfg {
         self
     }
 
+    /// Copies every worker log chunk a checkpoint reclaims into
+    /// `wal_archive_dir` before deleting it, so a restore can replay past
+    /// the last backup. Off by default.
+    pub fn with_wal_archive_dir(mut self, wal_archive_dir: Option<String>) -> Self {
+        self.wal_archive_dir = wal_archive_dir;
+        self
+    }
+
+    /// Requires every connection to authenticate when `auth` is set; see
+    /// [`crate::server::auth`]. Authentication is off by default.
+    pub fn with_auth(mut self, auth: Option<AuthCfg>) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Runs the server as a read-only standby that streams the worker logs
+    /// of the primary whose replication port is `replicate_from`
+    /// (`host:port`); see [`crate::server::replication`].
+    pub fn with_replicate_from(mut self, replicate_from: Option<String>) -> Self {
+        self.replicate_from = replicate_from;
+        self
+    }
+
+    /// Serves the worker logs to standby servers on this port. Off by
+    /// default.
+    pub fn with_replication_listen_port(mut self, port: Option<u16>) -> Self {
+        self.replication_listen_port = port;
+        self
+    }
+
     pub fn with_multi_port(mut self, multi_port: bool) -> Self {
         self.multi_port = multi_port;
         self
@@ -135,6 +174,27 @@ impl ServerCfg {
         self.wal_sync_policy
     }
 
+    /// The worker log archive directory, `None` when archiving is off.
+    pub fn wal_archive_dir(&self) -> Option<&str> {
+        self.wal_archive_dir.as_deref()
+    }
+
+    /// The authentication settings, `None` when authentication is off.
+    pub fn auth(&self) -> Option<&AuthCfg> {
+        self.auth.as_ref()
+    }
+
+    /// The replication address of the primary, `None` unless the server is
+    /// a standby.
+    pub fn replicate_from(&self) -> Option<&str> {
+        self.replicate_from.as_deref()
+    }
+
+    /// The port serving the worker logs to standbys, `None` when off.
+    pub fn replication_listen_port(&self) -> Option<u16> {
+        self.replication_listen_port
+    }
+
     pub fn worker_count(&self) -> usize {
         self.worker_count
     }
@@ -5,6 +5,7 @@ use mudu::error::ErrorCode;
 use mudu::mudu_error;
 
 use crate::server::async_func_runtime::AsyncFuncInvokerPtr;
+use crate::server::auth::ServerAuth;
 use crate::server::procedure_runtimes::ProcedureRuntimes;
 use crate::server::server_cfg::ServerCfg;
 use crate::server::worker_registry::{load_or_create_worker_registry, WorkerRegistry};
@@ -15,9 +16,11 @@ use mudu_sys::contract::async_io_provider::AsyncIoProvider;
 pub struct ServerRuntimeDeps {
     log_batching: WorkerLogBatching,
     wal_sync_policy: WalSyncPolicy,
+    wal_archive_dir: Option<String>,
     procedure_runtimes: ProcedureRuntimes,
     worker_registry: Arc<WorkerRegistry>,
     async_runtime: Option<Arc<dyn AsyncIoProvider>>,
+    auth: Arc<ServerAuth>,
 }
 
 impl ServerRuntimeDeps {
@@ -28,9 +31,11 @@ impl ServerRuntimeDeps {
         Ok(Self {
             log_batching,
             wal_sync_policy: cfg.wal_sync_policy(),
+            wal_archive_dir: cfg.wal_archive_dir().map(str::to_string),
             procedure_runtimes: ProcedureRuntimes::default(),
             worker_registry,
             async_runtime: None,
+            auth: Arc::new(ServerAuth::new(cfg.auth().cloned())),
         })
     }
 
@@ -82,6 +87,10 @@ impl ServerRuntimeDeps {
         self.wal_sync_policy
     }
 
+    pub fn wal_archive_dir(&self) -> Option<&str> {
+        self.wal_archive_dir.as_deref()
+    }
+
     pub fn procedure_runtime_for_worker(&self, worker_id: usize) -> Option<AsyncFuncInvokerPtr> {
         self.procedure_runtimes.for_worker(worker_id)
     }
@@ -93,4 +102,9 @@ impl ServerRuntimeDeps {
     pub fn async_runtime(&self) -> Option<Arc<dyn AsyncIoProvider>> {
         self.async_runtime.clone()
     }
+
+    /// Connection authentication state shared by all workers.
+    pub fn auth(&self) -> Arc<ServerAuth> {
+        self.auth.clone()
+    }
 }
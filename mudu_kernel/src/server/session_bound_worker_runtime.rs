@@ -15,7 +15,11 @@ use mudu::mudu_error;
 use mudu_contract::database::result_set::ResultSetAsync;
 use mudu_contract::database::sql_params::SQLParams;
 use mudu_contract::database::sql_stmt::SQLStmt;
-use mudu_contract::protocol::{ProcedureInvokeRequest, ProcedureInvokeResponse};
+use mudu_contract::protocol::change_feed::{ChangeFeedRequest, ChangeFeedResponse};
+use mudu_contract::protocol::{
+    AuthRequest, AuthResponse, CloseCursorRequest, FetchRequest, FetchResponse,
+    ProcedureInvokeRequest, ProcedureInvokeResponse,
+};
 use std::sync::Arc;
 
 use crate::x_engine::api::{DeltaOp, XContract};
@@ -116,7 +120,7 @@ impl WorkerLocal for SessionBoundWorkerRuntime {
         end_key: &[u8],
     ) -> RS<Vec<KvItem>> {
         self.worker
-            .range_in_session(session_id, start_key, end_key)
+            .range_in_session(session_id, start_key, end_key, None)
             .await
     }
 
@@ -204,4 +208,54 @@ impl RequestResponseWorker for SessionBoundWorkerRuntime {
     ) -> RS<ProcedureInvokeResponse> {
         self.worker.handle_procedure_request(conn_id, request).await
     }
+
+    fn auth_required(&self) -> bool {
+        self.worker.auth_required()
+    }
+
+    async fn authenticate(&self, conn_id: u64, request: AuthRequest) -> RS<AuthResponse> {
+        self.worker.authenticate(conn_id, request).await
+    }
+
+    async fn read_changes(
+        &self,
+        conn_id: u64,
+        request: &ChangeFeedRequest,
+    ) -> RS<ChangeFeedResponse> {
+        self.worker.read_changes(conn_id, request).await
+    }
+
+    async fn range_page(
+        &self,
+        conn_id: u64,
+        session_id: OID,
+        start_key: &[u8],
+        end_key: &[u8],
+        limit: usize,
+    ) -> RS<Vec<KvItem>> {
+        self.worker
+            .range_for_connection(conn_id, session_id, start_key, end_key, Some(limit))
+            .await
+    }
+
+    async fn query_page(
+        &self,
+        conn_id: u64,
+        session_id: OID,
+        sql: Box<dyn SQLStmt>,
+        param: Box<dyn SQLParams>,
+        fetch_size: u32,
+    ) -> RS<FetchResponse> {
+        self.worker
+            .query_page(conn_id, session_id, sql, param, fetch_size)
+            .await
+    }
+
+    async fn fetch_cursor(&self, conn_id: u64, request: &FetchRequest) -> RS<FetchResponse> {
+        self.worker.fetch_cursor(conn_id, request).await
+    }
+
+    fn close_cursor(&self, conn_id: u64, request: &CloseCursorRequest) -> RS<bool> {
+        self.worker.close_cursor(conn_id, request)
+    }
 }
@@ -9,13 +9,19 @@ use mudu::error::ErrorCode;
 use mudu::mudu_error;
 
 use crate::contract::meta_mgr::MetaMgr;
+use crate::contract::partition_rule::PartitionRuleDesc;
+use crate::contract::partition_rule_binding::{PartitionPlacement, TablePartitionBinding};
 use crate::contract::schema_table::SchemaTable;
 use crate::contract::table_desc::TableDesc;
 use crate::contract::table_info::TableInfo;
+use crate::x_engine::api::AlterTable;
 
 pub(crate) struct TestMetaMgr {
     schemas: SMutex<HashMap<OID, SchemaTable>>,
     tables: SMutex<HashMap<OID, Arc<TableDesc>>>,
+    rules: SMutex<HashMap<OID, PartitionRuleDesc>>,
+    bindings: SMutex<HashMap<OID, TablePartitionBinding>>,
+    placements: SMutex<HashMap<OID, OID>>,
 }
 
 impl TestMetaMgr {
@@ -23,6 +29,9 @@ impl TestMetaMgr {
         Self {
             schemas: SMutex::new(HashMap::new()),
             tables: SMutex::new(HashMap::new()),
+            rules: SMutex::new(HashMap::new()),
+            bindings: SMutex::new(HashMap::new()),
+            placements: SMutex::new(HashMap::new()),
         }
     }
 }
@@ -68,7 +77,116 @@ impl MetaMgr for TestMetaMgr {
         Ok(())
     }
 
+    async fn alter_table(&self, table_id: OID, alters: &[AlterTable]) -> RS<()> {
+        let mut schemas = self.schemas.lock().unwrap();
+        let schema = schemas.get(&table_id).ok_or_else(|| {
+            mudu_error!(
+                ErrorCode::EntityNotFound,
+                format!("no such table {}", table_id)
+            )
+        })?;
+        let altered = schema.altered_all(alters)?;
+        let table = TableInfo::new(altered.clone())?.table_desc()?;
+        schemas.insert(table_id, altered);
+        self.tables.lock().unwrap().insert(table_id, table);
+        Ok(())
+    }
+
     async fn list_schemas(&self) -> RS<Vec<SchemaTable>> {
         Ok(self.schemas.lock().unwrap().values().cloned().collect())
     }
+
+    async fn create_partition_rule(&self, rule: &PartitionRuleDesc) -> RS<()> {
+        self.rules.lock().unwrap().insert(rule.oid, rule.clone());
+        Ok(())
+    }
+
+    async fn update_partition_rule(&self, rule: &PartitionRuleDesc) -> RS<()> {
+        let mut rules = self.rules.lock().unwrap();
+        match rules.get(&rule.oid) {
+            Some(current) if current.version < rule.version => {
+                rules.insert(rule.oid, rule.clone());
+                Ok(())
+            }
+            Some(current) => Err(mudu_error!(
+                ErrorCode::InvalidState,
+                format!(
+                    "partition rule {} is already at version {}",
+                    rule.name, current.version
+                )
+            )),
+            None => Err(mudu_error!(
+                ErrorCode::EntityNotFound,
+                format!("no such partition rule {}", rule.oid)
+            )),
+        }
+    }
+
+    async fn get_partition_rule_by_id(&self, oid: OID) -> RS<PartitionRuleDesc> {
+        self.rules
+            .lock()
+            .unwrap()
+            .get(&oid)
+            .cloned()
+            .ok_or_else(|| {
+                mudu_error!(
+                    ErrorCode::EntityNotFound,
+                    format!("no such partition rule {}", oid)
+                )
+            })
+    }
+
+    async fn get_partition_rule_by_name(&self, name: &str) -> RS<Option<PartitionRuleDesc>> {
+        Ok(self
+            .rules
+            .lock()
+            .unwrap()
+            .values()
+            .find(|rule| rule.name == name)
+            .cloned())
+    }
+
+    async fn list_partition_rules(&self) -> RS<Vec<PartitionRuleDesc>> {
+        Ok(self.rules.lock().unwrap().values().cloned().collect())
+    }
+
+    async fn bind_table_partition(&self, binding: &TablePartitionBinding) -> RS<()> {
+        self.bindings
+            .lock()
+            .unwrap()
+            .insert(binding.table_id, binding.clone());
+        Ok(())
+    }
+
+    async fn get_table_partition_binding(
+        &self,
+        table_id: OID,
+    ) -> RS<Option<TablePartitionBinding>> {
+        Ok(self.bindings.lock().unwrap().get(&table_id).cloned())
+    }
+
+    async fn upsert_partition_placements(&self, placements: &[PartitionPlacement]) -> RS<()> {
+        let mut current = self.placements.lock().unwrap();
+        for placement in placements {
+            current.insert(placement.partition_id, placement.worker_id);
+        }
+        Ok(())
+    }
+
+    async fn get_partition_worker(&self, partition_id: OID) -> RS<Option<OID>> {
+        Ok(self.placements.lock().unwrap().get(&partition_id).copied())
+    }
+
+    async fn list_partition_placements(&self) -> RS<Vec<PartitionPlacement>> {
+        Ok(self
+            .placements
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(partition_id, worker_id)| PartitionPlacement {
+                partition_id: *partition_id,
+                worker_id: *worker_id,
+            })
+            .collect())
+    }
 }
@@ -0,0 +1,219 @@
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::todo,
+    clippy::unimplemented
+)]
+//! End-to-end tests for `INSERT ... ON CONFLICT` and `RETURNING`: a real
+//! single-worker [`WorkerRuntime`] on temporary directories runs SQL through
+//! `query`/`execute`, both through the plan cache and the uncached path.
+//!
+//! Miri cannot execute the tree-sitter FFI behind SQL parsing, so the whole
+//! module is excluded under Miri (see `mod.rs`).
+
+use mudu::error::ErrorCode;
+
+use crate::server::e2e_test_util::{build_worker, exec, query_rows, TestDirs};
+use crate::server::session_bound_worker_runtime::new_session_bound_worker_runtime;
+use crate::server::worker_local::WorkerLocal;
+
+#[test]
+fn upsert_e2e_on_conflict() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let dirs = TestDirs::new("upsert_e2e_on_conflict");
+        let worker = build_worker(&dirs).await;
+        let session = worker.create_session(1).unwrap();
+        let local_arc = new_session_bound_worker_runtime(worker.clone(), session);
+        let local: &dyn WorkerLocal = local_arc.as_ref();
+
+        exec(
+            local,
+            session,
+            "CREATE TABLE kv (k INTEGER PRIMARY KEY, v TEXT, n INTEGER)",
+            (),
+        )
+        .await;
+
+        // The first execution inserts, the next ones update the existing row;
+        // the statement runs through the plan cache after the first time.
+        let upsert = "INSERT INTO kv VALUES (?, ?, 1) \
+                      ON CONFLICT (k) DO UPDATE SET v = EXCLUDED.v, n = n + 1";
+        for v in ["a", "b", "c"] {
+            assert_eq!(exec(local, session, upsert, (1i32, v.to_string())).await, 1);
+        }
+        let rows = query_rows(local, session, "SELECT v, n FROM kv WHERE k = 1", ()).await;
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].values()[0].expect_string(), "c");
+        assert_eq!(rows[0].values()[1].to_i32(), 3);
+
+        // DO NOTHING skips the conflicting row and still inserts new ones.
+        let affected = exec(
+            local,
+            session,
+            "INSERT INTO kv VALUES (1, 'x', 0), (2, 'y', 0) ON CONFLICT DO NOTHING",
+            (),
+        )
+        .await;
+        assert_eq!(affected, 1);
+        let rows = query_rows(local, session, "SELECT k, v FROM kv ORDER BY k", ()).await;
+        let values: Vec<(i32, String)> = rows
+            .iter()
+            .map(|row| {
+                (
+                    row.values()[0].to_i32(),
+                    row.values()[1].expect_string().clone(),
+                )
+            })
+            .collect();
+        assert_eq!(values, vec![(1, "c".to_string()), (2, "y".to_string())]);
+
+        // DO UPDATE expressions read the existing row and the proposed one.
+        let affected = exec(
+            local,
+            session,
+            "INSERT INTO kv VALUES (1, 'd', 5) \
+             ON CONFLICT (k) DO UPDATE SET n = n + EXCLUDED.n, v = EXCLUDED.v || v",
+            (),
+        )
+        .await;
+        assert_eq!(affected, 1);
+        let rows = query_rows(local, session, "SELECT v, n FROM kv WHERE k = 1", ()).await;
+        assert_eq!(rows[0].values()[0].expect_string(), "dc");
+        assert_eq!(rows[0].values()[1].to_i32(), 8);
+
+        // The conflict target must be the primary key.
+        let err = local
+            .execute(
+                session,
+                Box::new(
+                    "INSERT INTO kv VALUES (3, 'z', 0) ON CONFLICT (v) DO NOTHING".to_string(),
+                ),
+                Box::new(()),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.ec(), ErrorCode::NotImplemented);
+    });
+}
+
+#[test]
+fn upsert_e2e_concurrent_insert_takes_conflict_branch() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let dirs = TestDirs::new("upsert_e2e_concurrent");
+        let worker = build_worker(&dirs).await;
+        let session_a = worker.create_session(1).unwrap();
+        let session_b = worker.create_session(2).unwrap();
+        let local_a_arc = new_session_bound_worker_runtime(worker.clone(), session_a);
+        let local_b_arc = new_session_bound_worker_runtime(worker.clone(), session_b);
+        let local_a: &dyn WorkerLocal = local_a_arc.as_ref();
+        let local_b: &dyn WorkerLocal = local_b_arc.as_ref();
+
+        exec(
+            local_a,
+            session_a,
+            "CREATE TABLE kv (k INTEGER PRIMARY KEY, v TEXT, n INTEGER)",
+            (),
+        )
+        .await;
+
+        // Session A inserts the key without committing, so B's probe misses
+        // it and B's insert waits on A's statement lock.
+        exec(local_a, session_a, "BEGIN", ()).await;
+        exec(local_a, session_a, "INSERT INTO kv VALUES (1, 'a', 1)", ()).await;
+        let upsert = exec(
+            local_b,
+            session_b,
+            "INSERT INTO kv VALUES (1, 'b', 1) \
+             ON CONFLICT (k) DO UPDATE SET v = EXCLUDED.v, n = n + 1",
+            (),
+        );
+        let commit = async {
+            for _ in 0..16 {
+                crate::common::yield_now::cooperative_yield_now().await;
+            }
+            exec(local_a, session_a, "COMMIT", ()).await
+        };
+        let (affected, _) = futures::join!(upsert, commit);
+        assert_eq!(affected, 1);
+
+        let rows = query_rows(local_b, session_b, "SELECT v, n FROM kv WHERE k = 1", ()).await;
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].values()[0].expect_string(), "b");
+        assert_eq!(rows[0].values()[1].to_i32(), 2);
+    });
+}
+
+#[test]
+fn upsert_e2e_returning() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let dirs = TestDirs::new("upsert_e2e_returning");
+        let worker = build_worker(&dirs).await;
+        let session = worker.create_session(1).unwrap();
+        let local_arc = new_session_bound_worker_runtime(worker.clone(), session);
+        let local: &dyn WorkerLocal = local_arc.as_ref();
+
+        exec(
+            local,
+            session,
+            "CREATE TABLE kv (k INTEGER PRIMARY KEY, v TEXT, n INTEGER DEFAULT 7)",
+            (),
+        )
+        .await;
+
+        // RETURNING sees column defaults.
+        let insert = "INSERT INTO kv (k, v) VALUES (?, ?) RETURNING k, n";
+        for k in [1i32, 2] {
+            let rows = query_rows(local, session, insert, (k, "a".to_string())).await;
+            assert_eq!(rows.len(), 1);
+            assert_eq!(rows[0].values()[0].to_i32(), k);
+            assert_eq!(rows[0].values()[1].to_i32(), 7);
+        }
+
+        // An upsert returns the row as updated.
+        let rows = query_rows(
+            local,
+            session,
+            "INSERT INTO kv VALUES (1, 'b', 0) ON CONFLICT DO UPDATE SET n = n + 10 RETURNING *",
+            (),
+        )
+        .await;
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].values().len(), 3);
+        assert_eq!(rows[0].values()[1].expect_string(), "a");
+        assert_eq!(rows[0].values()[2].to_i32(), 17);
+
+        let update = "UPDATE kv SET v = ? WHERE k = ? RETURNING v";
+        let rows = query_rows(local, session, update, ("c".to_string(), 2i32)).await;
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].values()[0].expect_string(), "c");
+        let rows = query_rows(local, session, update, ("c".to_string(), 9i32)).await;
+        assert!(rows.is_empty());
+
+        // DELETE returns the row it removed.
+        let rows = query_rows(
+            local,
+            session,
+            "DELETE FROM kv WHERE k = 2 RETURNING k, v",
+            (),
+        )
+        .await;
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].values()[0].to_i32(), 2);
+        assert_eq!(rows[0].values()[1].expect_string(), "c");
+        let rows = query_rows(local, session, "SELECT k FROM kv", ()).await;
+        assert_eq!(rows.len(), 1);
+
+        // A command without RETURNING is not a query.
+        let err = local
+            .query(
+                session,
+                Box::new("DELETE FROM kv WHERE k = 1".to_string()),
+                Box::new(()),
+            )
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(err.ec(), ErrorCode::InvalidType);
+    });
+}
@@ -0,0 +1,82 @@
+//! MVCC version garbage collection.
+//!
+//! Every committed write appends a version to its row's in-memory chain and
+//! a record to the relation's key/value time-series files, and nothing on
+//! the write path ever drops them. [`VersionGc`] does, per worker: it takes
+//! the worker's low-water mark (see `WorkerSnapshotMgr::gc_low_water`),
+//! drops every version older than the newest one committed below it, and
+//! empties the time-series pages whose records were all dropped. The file
+//! insert path reuses emptied pages, so a hot table stops growing once its
+//! old versions are collected.
+//!
+//! The periodic driver follows [`super::fs_gc`]: the tokio backend runs
+//! [`VersionGc::gc_loop`], and the io_uring worker loop re-spawns one-round
+//! [`VersionGc::gc_round`] tasks from its service loop; see
+//! `linux/worker_ring_loop.rs`.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use mudu::common::result::RS;
+use mudu_sys::sync::async_::stop_flag::StopRx;
+use mudu_sys::tokio;
+use tracing::{debug, error};
+
+use crate::server::x_contract::WorkerXContract;
+use crate::storage::relation::relation::VersionGcStats;
+
+/// Interval between two version GC rounds.
+pub(crate) const VERSION_GC_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Per-worker MVCC version garbage collector; see the module docs.
+pub(crate) struct VersionGc {
+    snapshot_source: Arc<WorkerXContract>,
+}
+
+impl VersionGc {
+    pub(crate) fn new(snapshot_source: Arc<WorkerXContract>) -> Self {
+        Self { snapshot_source }
+    }
+
+    /// Run one round: compute the low-water mark and prune every relation
+    /// and the KV store of this worker against it.
+    pub(crate) async fn gc_round(&self) -> RS<VersionGcStats> {
+        let low_water = self.snapshot_source.gc_low_water()?;
+        let stats = self
+            .snapshot_source
+            .storage()
+            .gc_versions_async(low_water)
+            .await?;
+        if stats != VersionGcStats::default() {
+            debug!(
+                low_water,
+                pruned_versions = stats.pruned_versions,
+                reclaimed_pages = stats.reclaimed_pages,
+                "version gc round done"
+            );
+        }
+        Ok(stats)
+    }
+
+    /// Periodic GC loop for executors with a working timer (the tokio
+    /// backend); same shape as `FsGc::gc_loop`.
+    pub(crate) async fn gc_loop(&self, interval: Duration, mut stop_rx: StopRx) -> RS<()> {
+        loop {
+            tokio::select! {
+                _ = mudu_sys::task::async_::sleep(interval) => {}
+                changed = stop_rx.changed() => {
+                    if !changed || stop_rx.is_stopped() {
+                        break;
+                    }
+                }
+            }
+            if stop_rx.is_stopped() {
+                break;
+            }
+            if let Err(err) = self.gc_round().await {
+                error!("version gc round failed, {}", err);
+            }
+        }
+        Ok(())
+    }
+}
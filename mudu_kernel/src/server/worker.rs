@@ -1,15 +1,19 @@
 use crate::contract::meta_mgr::MetaMgr;
-use crate::mudu_conn::mudu_conn_core::MuduConnCore;
+use crate::mudu_conn::mudu_conn_core::{MuduConnCore, QueryOutput};
 use crate::mudu_conn::mudu_result_set_async::MuduResultSetAsync;
 use crate::mudu_conn::plan_cache::{CachedPlan, PlanCache};
 use crate::server::async_func_runtime::AsyncFuncInvokerPtr;
+use crate::server::auth::ServerAuth;
+use crate::server::change_feed::ChangeFeed;
 use crate::server::fs_gc::FsGc;
 use crate::server::fs_service::FsService;
 use crate::server::message_bus_api::ServerInstanceId;
+use crate::server::query_cursor::{page_rows, read_page, CursorTx, QueryCursors, ReadTx};
 use crate::server::routing::SessionOpenConfig;
 use crate::server::session_bound_worker_runtime::{
     as_worker_local_ref, new_session_bound_worker_runtime,
 };
+use crate::server::version_gc::VersionGc;
 use crate::server::worker_local::{
     set_current_worker_local, try_current_worker_local, unset_current_worker_local, WorkerExecute,
     WorkerLocalRef,
@@ -19,15 +23,17 @@ use crate::server::worker_session_manager::{SessionContext, WorkerSessionManager
 use crate::server::worker_snapshot::KvItem;
 use crate::server::x_contract::{WorkerStorage, WorkerXContract, WorkerXContractWorkerLogParams};
 use crate::sql::binder::Binder;
+use crate::wal::lsn::LSN;
 use crate::wal::worker_log::{
-    ChunkedWorkerLogBackend, WalSyncPolicy, WorkerLogBatching, WorkerLogLayout,
+    ChunkedWorkerLogBackend, WalCheckpoint, WalSyncPolicy, WorkerLogBatching, WorkerLogLayout,
 };
 use crate::wal::xl_batch::XLBatch;
 use crate::x_engine::api::{
     DeltaAssign, DeltaOp, OptInsert, OptRead, OptUpdate, Predicate, VecDatum, VecSelTerm, XContract,
 };
-use crate::x_engine::tx_mgr::TxMgr;
+use crate::x_engine::tx_mgr::{IsolationLevel, TxMgr, KV_RELATION_ID};
 use crate::x_engine::DataBin;
+use async_trait::async_trait;
 use mudu::common::id::{AttrIndex, OID};
 use mudu::common::result::RS;
 use mudu::error::ErrorCode;
@@ -35,14 +41,20 @@ use mudu::mudu_error;
 use mudu_contract::database::result_set::ResultSetAsync;
 use mudu_contract::database::sql_params::SQLParams;
 use mudu_contract::database::sql_stmt::SQLStmt;
-use mudu_contract::protocol::{ProcedureInvokeRequest, ProcedureInvokeResponse};
+use mudu_contract::protocol::change_feed::{ChangeFeedRequest, ChangeFeedResponse};
+use mudu_contract::protocol::{
+    AuthRequest, AuthResponse, CloseCursorRequest, FetchRequest, FetchResponse, MessageType,
+    ProcedureInvokeRequest, ProcedureInvokeResponse,
+};
 use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
 use mudu_sys::contract::async_io_provider::AsyncIoProvider;
 use mudu_sys::default_sys_io_context;
 use mudu_utils::task_trace;
 use std::collections::BTreeMap;
+use std::path::PathBuf;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
+use tracing::warn;
 
 #[derive(Clone)]
 /// Per-worker execution context used by the `client` backend.
@@ -68,8 +80,11 @@ pub struct WorkerRuntime {
     session_manager: Arc<WorkerSessionManager>,
     fs_service: Arc<FsService>,
     fs_gc: Arc<FsGc>,
+    version_gc: Arc<VersionGc>,
     registry: Arc<WorkerRegistry>,
     plan_cache: Arc<PlanCache>,
+    auth: Arc<ServerAuth>,
+    query_cursors: Arc<QueryCursors>,
 }
 
 /// Backward-compatible name for callers that still refer to the historical
@@ -84,14 +99,26 @@ enum TxControlStmt {
     Begin,
     Commit,
     Rollback,
+    /// `SET TRANSACTION ISOLATION LEVEL ...`: the active transaction.
+    /// `SERIALIZABLE` is checked per worker (see `server::worker_ssi`): such
+    /// a transaction fails with `NotImplemented` on its first read of, or
+    /// commit to, a partition owned by another worker.
+    SetTxIsolation(IsolationLevel),
+    /// `SET SESSION CHARACTERISTICS AS TRANSACTION ISOLATION LEVEL ...`:
+    /// every transaction the session begins afterwards.
+    SetSessionIsolation(IsolationLevel),
 }
 
 /// Recognize `BEGIN [TRANSACTION|WORK]` / `START TRANSACTION`,
 /// `COMMIT [TRANSACTION|WORK]` and `ROLLBACK [TRANSACTION|WORK]`
 /// (case-insensitive, optional trailing semicolons). Returns `None` for any
 /// other statement, which then flows through the normal SQL pipeline.
+/// Isolation-level statements are recognized by `parse_isolation_stmt`.
 fn parse_tx_control_stmt(sql: &str) -> Option<TxControlStmt> {
     let normalized = sql.trim().trim_end_matches(';').trim();
+    if let Some(control) = parse_isolation_stmt(normalized) {
+        return Some(control);
+    }
     const VARIANTS: &[(&str, TxControlStmt)] = &[
         ("BEGIN", TxControlStmt::Begin),
         ("BEGIN TRANSACTION", TxControlStmt::Begin),
@@ -110,6 +137,39 @@ fn parse_tx_control_stmt(sql: &str) -> Option<TxControlStmt> {
         .map(|(_, control)| *control)
 }
 
+/// Recognize `SET TRANSACTION ISOLATION LEVEL <level>` and the session-wide
+/// `SET SESSION CHARACTERISTICS AS TRANSACTION ISOLATION LEVEL <level>` (or
+/// `SET SESSION TRANSACTION ISOLATION LEVEL <level>`). `<level>` is
+/// `SERIALIZABLE`, or `SNAPSHOT` / `REPEATABLE READ` for snapshot isolation.
+fn parse_isolation_stmt(normalized: &str) -> Option<TxControlStmt> {
+    let words = normalized
+        .split_ascii_whitespace()
+        .map(|word| word.to_ascii_uppercase())
+        .collect::<Vec<_>>()
+        .join(" ");
+    const SCOPES: &[(&str, bool)] = &[
+        ("SET TRANSACTION ISOLATION LEVEL ", false),
+        ("SET SESSION TRANSACTION ISOLATION LEVEL ", true),
+        (
+            "SET SESSION CHARACTERISTICS AS TRANSACTION ISOLATION LEVEL ",
+            true,
+        ),
+    ];
+    let (level, session) = SCOPES
+        .iter()
+        .find_map(|(prefix, session)| words.strip_prefix(prefix).map(|level| (level, *session)))?;
+    let level = match level {
+        "SERIALIZABLE" => IsolationLevel::Serializable,
+        "SNAPSHOT" | "REPEATABLE READ" => IsolationLevel::Snapshot,
+        _ => return None,
+    };
+    Some(if session {
+        TxControlStmt::SetSessionIsolation(level)
+    } else {
+        TxControlStmt::SetTxIsolation(level)
+    })
+}
+
 pub struct WorkerRuntimeParams {
     pub identity: WorkerIdentity,
     pub worker_count: usize,
@@ -118,15 +178,20 @@ pub struct WorkerRuntimeParams {
     pub log_chunk_size: u64,
     pub log_batching: WorkerLogBatching,
     pub wal_sync_policy: WalSyncPolicy,
+    pub wal_archive_dir: Option<String>,
     pub procedure_runtime: Option<AsyncFuncInvokerPtr>,
     pub registry: Arc<WorkerRegistry>,
     pub async_runtime: Option<Arc<dyn AsyncIoProvider>>,
     pub server_instance_id: ServerInstanceId,
+    pub auth: Arc<ServerAuth>,
 }
 
 impl WorkerRuntime {
     pub async fn initialize(&self) -> RS<()> {
         self.contract.initialize().await?;
+        self.auth
+            .seed_bootstrap_admin(self.contract.meta_mgr().as_ref())
+            .await?;
         Ok(())
     }
 
@@ -139,10 +204,12 @@ impl WorkerRuntime {
             log_chunk_size,
             log_batching,
             wal_sync_policy,
+            wal_archive_dir,
             procedure_runtime,
             registry,
             async_runtime,
             server_instance_id,
+            auth,
         } = config;
         let active_sessions = Arc::new(AtomicUsize::new(0));
         // The runtime currently activates only the first partition assigned to
@@ -163,7 +230,8 @@ impl WorkerRuntime {
             })?;
         let log_layout = WorkerLogLayout::new(log_dir, worker_id, log_chunk_size)?
             .with_batching(log_batching)
-            .with_sync_policy(wal_sync_policy);
+            .with_sync_policy(wal_sync_policy)
+            .with_archive_dir(wal_archive_dir.map(PathBuf::from));
 
         // The fs service derives its storage roots from the data dir; keep a
         // copy before it is moved into the contract params.
@@ -209,6 +277,7 @@ impl WorkerRuntime {
             fs_service.object_store().clone(),
             contract.clone(),
         ));
+        let version_gc = Arc::new(VersionGc::new(contract.clone()));
         Ok(Self {
             server_instance_id,
             worker_index: identity.worker_index,
@@ -221,8 +290,11 @@ impl WorkerRuntime {
             session_manager,
             fs_service,
             fs_gc,
+            version_gc,
             registry,
             plan_cache: Arc::new(PlanCache::new()),
+            auth,
+            query_cursors: Arc::new(QueryCursors::new()),
         })
     }
 
@@ -268,6 +340,7 @@ impl WorkerRuntime {
         let _stage = crate::server::stage_stats::StageGuard::new(
             crate::server::stage_stats::Stage::ProcInvoke,
         );
+        let started = mudu_sys::time::instant_now();
         let result = procedure_runtime
             .invoke(
                 session_id,
@@ -276,6 +349,7 @@ impl WorkerRuntime {
                 worker_local,
             )
             .await;
+        crate::server::metrics::record_procedure(procedure_name, started.elapsed(), result.is_ok());
         trace.watch(
             "procedure.kernel.worker_invoke.stage",
             if result.is_ok() {
@@ -305,11 +379,14 @@ impl WorkerRuntime {
         let closed = self.session_manager.close_session(conn_id, session_id)?;
         if closed {
             self.fs_service.drop_session(session_id);
+            self.query_cursors.drop_session(session_id)?;
         }
         Ok(closed)
     }
 
     pub fn close_connection_sessions(&self, conn_id: u64) -> RS<()> {
+        self.auth.forget(conn_id);
+        self.query_cursors.drop_connection(conn_id)?;
         let session_ids = self.session_manager.close_connection_sessions(conn_id)?;
         for session_id in session_ids {
             self.fs_service.drop_session(session_id);
@@ -317,6 +394,175 @@ impl WorkerRuntime {
         Ok(())
     }
 
+    /// Whether connections must authenticate; see [`crate::server::auth`].
+    pub fn auth_required(&self) -> bool {
+        self.auth.required()
+    }
+
+    /// Reject a frame of a connection that has not authenticated yet.
+    pub fn check_frame_authorized(&self, conn_id: u64, message_type: MessageType) -> RS<()> {
+        self.auth.check_authorized(conn_id, message_type)
+    }
+
+    /// Run one step of the SCRAM exchange of `conn_id`.
+    pub async fn authenticate(&self, conn_id: u64, request: AuthRequest) -> RS<AuthResponse> {
+        match request {
+            AuthRequest::ClientFirst { user, client_nonce } => {
+                let desc = self.contract.meta_mgr().get_user_by_name(&user).await?;
+                self.auth.server_first(conn_id, user, client_nonce, desc)
+            }
+            AuthRequest::ClientFinal { nonce, proof } => {
+                self.auth.server_final(conn_id, &nonce, &proof)
+            }
+        }
+    }
+
+    /// Read the committed row changes of a worker log; admin only.
+    pub async fn read_changes(
+        &self,
+        conn_id: u64,
+        request: &ChangeFeedRequest,
+    ) -> RS<ChangeFeedResponse> {
+        if !self.auth.is_admin(conn_id) {
+            return Err(mudu_error!(
+                ErrorCode::PermissionDenied,
+                "change data capture requires an admin"
+            ));
+        }
+        let feed = ChangeFeed::new(
+            self.log_layout.log_dir.clone(),
+            self.log_layout.chunk_size(),
+            self.log_layout.archive_dir().map(PathBuf::from),
+            self.registry
+                .workers()
+                .iter()
+                .map(|worker| worker.worker_id)
+                .collect(),
+            self.meta_mgr(),
+        );
+        feed.read_async(request).await
+    }
+
+    /// Run a query of `session_id` and answer with its first `fetch_size`
+    /// rows, parking the running query on a cursor when more remain; see
+    /// [`crate::server::query_cursor`]. Inside an explicit transaction the
+    /// query reads in it; otherwise it reads in a transaction of its own,
+    /// which the cursor keeps until it is drained or closed.
+    pub(crate) async fn query_page(
+        &self,
+        conn_id: u64,
+        session_id: OID,
+        sql: Box<dyn SQLStmt>,
+        param: Box<dyn SQLParams>,
+        fetch_size: u32,
+    ) -> RS<FetchResponse> {
+        if let Some(control) = parse_tx_control_stmt(&sql.to_sql_string()) {
+            self.execute_tx_control_stmt(session_id, control).await?;
+            return Ok(FetchResponse::new(
+                TupleFieldDesc::new(vec![]),
+                vec![],
+                None,
+            ));
+        }
+        let core = self.sql_core(session_id)?;
+        if session_id != 0 && self.session_manager.has_session_tx(session_id)? {
+            let tx_mgr = self.sql_tx_mgr(session_id)?.ok_or_else(|| {
+                mudu_error!(ErrorCode::Internal, "session transaction is missing")
+            })?;
+            let output = self
+                .open_sql_query_with_tx(core, sql, param, tx_mgr)
+                .await?;
+            let result_set = output.into_result_set().await?;
+            return self
+                .first_page(conn_id, session_id, result_set, ReadTx::Session, fetch_size)
+                .await;
+        }
+        let tx_mgr = self.begin_cursor_tx(session_id)?;
+        let tx = WorkerCursorTx::new(self.contract.clone(), tx_mgr.clone());
+        let output = match self.open_sql_query_with_tx(core, sql, param, tx_mgr).await {
+            Ok(output) => output,
+            Err(err) => {
+                tx.abort().await?;
+                return Err(err);
+            }
+        };
+        let read_tx = match &output {
+            // Every row was read already: end the transaction now.
+            QueryOutput::Rows(..) => {
+                Box::new(tx).commit().await?;
+                ReadTx::Done
+            }
+            QueryOutput::Exec(_) => ReadTx::Owned(Box::new(tx)),
+        };
+        let result_set = output.into_result_set().await?;
+        self.first_page(conn_id, session_id, result_set, read_tx, fetch_size)
+            .await
+    }
+
+    /// Begin the transaction a paged query outside an explicit transaction
+    /// reads in, at the isolation level of its session.
+    fn begin_cursor_tx(&self, session_id: OID) -> RS<Arc<dyn TxMgr>> {
+        let tx_manager = self.contract.worker_begin_tx()?;
+        if session_id == 0 {
+            return Ok(tx_manager);
+        }
+        let level = self
+            .session_manager
+            .session_context(session_id)
+            .and_then(|session| session.isolation_level())
+            .and_then(|level| {
+                self.contract
+                    .worker_set_tx_isolation_level(tx_manager.as_ref(), level)
+            });
+        if let Err(err) = level {
+            self.contract.worker_rollback_tx(tx_manager)?;
+            return Err(err);
+        }
+        Ok(tx_manager)
+    }
+
+    async fn first_page(
+        &self,
+        conn_id: u64,
+        session_id: OID,
+        result_set: Arc<dyn ResultSetAsync>,
+        read_tx: ReadTx,
+        fetch_size: u32,
+    ) -> RS<FetchResponse> {
+        let (rows, next) = read_page(result_set.as_ref(), page_rows(fetch_size)).await?;
+        let desc = result_set.desc().clone();
+        let cursor_id = match next {
+            Some(lookahead) => Some(self.query_cursors.open(
+                conn_id, session_id, result_set, lookahead, read_tx, fetch_size,
+            )?),
+            None => {
+                if let ReadTx::Owned(tx) = read_tx {
+                    tx.commit().await?;
+                }
+                None
+            }
+        };
+        Ok(FetchResponse::new(desc, rows, cursor_id))
+    }
+
+    /// Read the next page of a cursor opened on `conn_id`.
+    pub async fn fetch_cursor(&self, conn_id: u64, request: &FetchRequest) -> RS<FetchResponse> {
+        self.query_cursors
+            .fetch(
+                conn_id,
+                request.session_id() as OID,
+                request.cursor_id(),
+                request.max_rows(),
+            )
+            .await
+    }
+
+    /// Close a cursor opened on `conn_id`; returns whether it was open.
+    pub fn close_cursor(&self, conn_id: u64, request: &CloseCursorRequest) -> RS<bool> {
+        self.query_cursors
+            .close(conn_id, request.session_id() as OID, request.cursor_id())
+    }
+
     pub fn open_session(&self, session_id: OID) -> RS<OID> {
         self.session_manager.open_session(session_id)
     }
@@ -324,7 +570,7 @@ impl WorkerRuntime {
     pub fn close_session_by_id(&self, session_id: OID) -> RS<()> {
         self.session_manager.close_session_by_id(session_id)?;
         self.fs_service.drop_session(session_id);
-        Ok(())
+        self.query_cursors.drop_session(session_id)
     }
 
     fn session_context(&self, session_id: OID) -> RS<Arc<SessionContext>> {
@@ -362,9 +608,11 @@ impl WorkerRuntime {
         session_id: OID,
         start_key: &[u8],
         end_key: &[u8],
+        limit: Option<usize>,
     ) -> RS<Vec<KvItem>> {
         self.ensure_session_owned_by_connection(conn_id, session_id)?;
-        self.range_in_session(session_id, start_key, end_key).await
+        self.range_in_session(session_id, start_key, end_key, limit)
+            .await
     }
 
     pub(crate) async fn execute_tx_async(
@@ -373,17 +621,17 @@ impl WorkerRuntime {
         instruction: WorkerExecute,
     ) -> RS<()> {
         match instruction {
-            WorkerExecute::BeginTx => self
-                .session_manager
-                .begin_session_tx(session_id, self.contract.worker_begin_tx()?),
+            WorkerExecute::BeginTx => self.start_session_tx(session_id),
             WorkerExecute::CommitTx => {
                 let tx_manager = self.session_manager.take_session_tx(session_id)?;
+                self.query_cursors.drop_session_tx(session_id)?;
                 self.contract
                     .worker_commit_routed_tx_async(tx_manager)
                     .await
             }
             WorkerExecute::RollbackTx => {
                 let tx_manager = self.session_manager.take_session_tx(session_id)?;
+                self.query_cursors.drop_session_tx(session_id)?;
                 self.contract.worker_abort_tx_async(tx_manager).await?;
                 Ok(())
             }
@@ -400,10 +648,53 @@ impl WorkerRuntime {
             TxControlStmt::Begin => WorkerExecute::BeginTx,
             TxControlStmt::Commit => WorkerExecute::CommitTx,
             TxControlStmt::Rollback => WorkerExecute::RollbackTx,
+            TxControlStmt::SetTxIsolation(level) => {
+                let tx_manager = self
+                    .session_manager
+                    .with_session_tx(session_id, Ok)?
+                    .ok_or_else(|| {
+                        mudu_error!(
+                            ErrorCode::Transaction,
+                            "SET TRANSACTION ISOLATION LEVEL requires an active transaction"
+                        )
+                    })?;
+                return self
+                    .contract
+                    .worker_set_tx_isolation_level(tx_manager.as_ref(), level);
+            }
+            TxControlStmt::SetSessionIsolation(level) => {
+                return self
+                    .session_manager
+                    .session_context(session_id)?
+                    .set_isolation_level(level);
+            }
         };
         self.execute_tx_async(session_id, instruction).await
     }
 
+    /// Begin the transaction of `session_id` at the session's isolation
+    /// level. The transaction is released again if it cannot be attached,
+    /// so a failed BEGIN leaks neither its snapshot nor an SSI registration.
+    fn start_session_tx(&self, session_id: OID) -> RS<()> {
+        let tx_manager = self.contract.worker_begin_tx()?;
+        let attached = self
+            .session_manager
+            .session_context(session_id)
+            .and_then(|session| session.isolation_level())
+            .and_then(|level| {
+                self.contract
+                    .worker_set_tx_isolation_level(tx_manager.as_ref(), level)
+            })
+            .and_then(|_| {
+                self.session_manager
+                    .begin_session_tx(session_id, tx_manager.clone())
+            });
+        if attached.is_err() {
+            self.contract.worker_rollback_tx(tx_manager)?;
+        }
+        attached
+    }
+
     pub(crate) async fn put_in_session_async(
         &self,
         session_id: OID,
@@ -457,6 +748,8 @@ impl WorkerRuntime {
             Some(value) => Ok(value),
             None => match tx_manager {
                 Some(tx_manager) => {
+                    tx_manager.record_statement();
+                    tx_manager.record_read(KV_RELATION_ID, key);
                     self.contract
                         .worker_get_with_snapshot_async(&tx_manager.snapshot(), key)
                         .await
@@ -466,32 +759,42 @@ impl WorkerRuntime {
         }
     }
 
+    /// Scan `[start_key, end_key)` in the session, overlaying the writes its
+    /// transaction has staged. With a `limit`, returns at most that many items
+    /// from the start of the range.
     pub(crate) async fn range_in_session(
         &self,
         session_id: OID,
         start_key: &[u8],
         end_key: &[u8],
+        limit: Option<usize>,
     ) -> RS<Vec<KvItem>> {
         let tx_manager = self.session_manager.with_session_tx(session_id, Ok)?;
         let staged = tx_manager
             .as_ref()
             .map(|tx_manager| tx_manager.staged_items_in_range(start_key, end_key))
             .unwrap_or_default();
+        // Every staged delete can hide one base item, so over-read the base
+        // scan by the staged count to still fill the page after the merge.
+        let base_limit = limit.map(|limit| limit.saturating_add(staged.len()));
 
         let mut merged = BTreeMap::new();
         let base_items = match tx_manager {
             Some(tx_manager) => {
+                tx_manager.record_statement();
+                tx_manager.record_range_read(KV_RELATION_ID, start_key, end_key);
                 self.contract
                     .worker_range_scan_with_snapshot_async(
                         &tx_manager.snapshot(),
                         start_key,
                         end_key,
+                        base_limit,
                     )
                     .await?
             }
             None => {
                 self.contract
-                    .worker_range_scan_async(start_key, end_key)
+                    .worker_range_scan_async(start_key, end_key, base_limit)
                     .await?
             }
         };
@@ -504,6 +807,7 @@ impl WorkerRuntime {
         Ok(merged
             .into_iter()
             .filter_map(|(key, value)| value.map(|value| KvItem { key, value }))
+            .take(limit.unwrap_or(usize::MAX))
             .collect())
     }
 
@@ -774,6 +1078,10 @@ impl WorkerRuntime {
         self.fs_gc.clone()
     }
 
+    pub(crate) fn version_gc(&self) -> Arc<VersionGc> {
+        self.version_gc.clone()
+    }
+
     /// Run the fs GC startup recovery scan. Called after WAL replay so the
     /// scan observes every committed `_fs_object` row.
     pub(crate) async fn fs_gc_recover_scan(&self) -> RS<()> {
@@ -791,11 +1099,17 @@ impl WorkerRuntime {
         Ok(self.session_context(oid)?.mudu_conn_core())
     }
 
+    /// The transaction a statement of session `oid` runs in, marked as
+    /// having run one (see `worker_set_tx_isolation_level`).
     fn sql_tx_mgr(&self, oid: OID) -> RS<Option<Arc<dyn TxMgr>>> {
         if oid == 0 {
             return Ok(None);
         }
-        self.session_manager.with_session_tx(oid, Ok)
+        let tx_mgr = self.session_manager.with_session_tx(oid, Ok)?;
+        if let Some(tx_mgr) = &tx_mgr {
+            tx_mgr.record_statement();
+        }
+        Ok(tx_mgr)
     }
 
     async fn run_sql_query_with_tx(
@@ -805,6 +1119,22 @@ impl WorkerRuntime {
         param: Box<dyn SQLParams>,
         tx_mgr: Arc<dyn TxMgr>,
     ) -> RS<Arc<dyn ResultSetAsync>> {
+        let (rows, desc) = self
+            .open_sql_query_with_tx(core, stmt, param, tx_mgr)
+            .await?
+            .into_rows()
+            .await?;
+        Ok(Arc::new(MuduResultSetAsync::from_rows(rows, desc)))
+    }
+
+    /// Bind and plan a query in `tx_mgr`, leaving its rows unread.
+    async fn open_sql_query_with_tx(
+        &self,
+        core: Arc<MuduConnCore>,
+        stmt: Box<dyn SQLStmt>,
+        param: Box<dyn SQLParams>,
+        tx_mgr: Arc<dyn TxMgr>,
+    ) -> RS<QueryOutput> {
         let trace = task_trace!();
         trace.watch("sql.kind", "query");
         let text = stmt.to_sql_string();
@@ -815,7 +1145,7 @@ impl WorkerRuntime {
         if let Some(plan) = self.plan_cache.get(&text, catalog_version)? {
             trace.watch("sql.stage", "plan_cache_hit");
             let result = plan
-                .run_query(
+                .open_query(
                     param.as_ref(),
                     tx_mgr,
                     self.contract.clone(),
@@ -824,8 +1154,7 @@ impl WorkerRuntime {
                 )
                 .await;
             trace.watch("sql.stage", if result.is_ok() { "done" } else { "error" });
-            let (rows, desc) = result?;
-            return Ok(Arc::new(MuduResultSetAsync::from_rows(rows, desc)));
+            return result;
         }
         trace.watch("sql.stage", "parse");
         let stmt = {
@@ -847,7 +1176,7 @@ impl WorkerRuntime {
             // DDL/COPY statements are never templated; take the regular path.
             trace.watch("sql.stage", "query");
             let result = core
-                .query(&stmt, param, tx_mgr, self.contract.clone())
+                .open_query(&stmt, param, tx_mgr, self.contract.clone())
                 .await;
             trace.watch("sql.stage", if result.is_ok() { "done" } else { "error" });
             return result;
@@ -862,7 +1191,7 @@ impl WorkerRuntime {
         };
         trace.watch("sql.stage", "query");
         let result = plan
-            .run_query(
+            .open_query(
                 param.as_ref(),
                 tx_mgr,
                 self.contract.clone(),
@@ -871,8 +1200,7 @@ impl WorkerRuntime {
             )
             .await;
         trace.watch("sql.stage", if result.is_ok() { "done" } else { "error" });
-        let (rows, desc) = result?;
-        Ok(Arc::new(MuduResultSetAsync::from_rows(rows, desc)))
+        result
     }
 
     async fn run_sql_execute_with_tx(
@@ -998,8 +1326,7 @@ impl WorkerRuntime {
         let started_tx = if self.session_manager.has_session_tx(oid)? {
             false
         } else {
-            self.session_manager
-                .begin_session_tx(oid, self.contract.worker_begin_tx()?)?;
+            self.start_session_tx(oid)?;
             true
         };
         let tx_mgr = self
@@ -1055,8 +1382,7 @@ impl WorkerRuntime {
             false
         } else {
             trace.watch("procedure.worker_execute.stage", "session_begin_tx_start");
-            self.session_manager
-                .begin_session_tx(oid, self.contract.worker_begin_tx()?)?;
+            self.start_session_tx(oid)?;
             trace.watch("procedure.worker_execute.stage", "session_begin_tx_done");
             true
         };
@@ -1121,8 +1447,7 @@ impl WorkerRuntime {
         let started_tx = if self.session_manager.has_session_tx(oid)? {
             false
         } else {
-            self.session_manager
-                .begin_session_tx(oid, self.contract.worker_begin_tx()?)?;
+            self.start_session_tx(oid)?;
             true
         };
         let tx_mgr = self
@@ -1161,12 +1486,72 @@ impl WorkerRuntime {
         self.contract.finish_worker_log_recovery()
     }
 
+    /// Restores the worker state recorded by a worker log checkpoint; runs
+    /// before the entries after the checkpoint are replayed.
+    pub fn restore_log_checkpoint(&self, checkpoint: &WalCheckpoint) -> RS<()> {
+        self.contract.restore_worker_log_checkpoint(checkpoint)
+    }
+
+    /// Takes a worker checkpoint when one is due; see
+    /// `WorkerXContract::checkpoint_if_due`.
+    pub(crate) async fn checkpoint_if_due(&self) -> RS<()> {
+        self.contract.checkpoint_if_due().await
+    }
+
+    /// Takes this worker's part of a pending online backup; see
+    /// `WorkerXContract::backup_if_requested`.
+    pub(crate) async fn backup_if_requested(&self) -> RS<()> {
+        self.contract.backup_if_requested().await
+    }
+
+    /// Logs the snapshot horizon for standbys once it advanced; see
+    /// `WorkerXContract::log_snapshot_horizon_if_advanced`.
+    pub(crate) async fn log_snapshot_horizon_if_advanced(&self) -> RS<()> {
+        self.contract.log_snapshot_horizon_if_advanced().await
+    }
+
+    pub fn is_standby(&self) -> bool {
+        self.contract.is_standby()
+    }
+
+    pub fn begin_standby(&self) {
+        self.contract.begin_standby()
+    }
+
+    pub async fn promote_standby_async(&self) -> RS<()> {
+        self.contract.promote_standby_async().await
+    }
+
+    pub(crate) async fn apply_replicated_frames_async(
+        &self,
+        frames: Vec<Vec<u8>>,
+    ) -> RS<Option<u64>> {
+        self.contract.apply_replicated_frames_async(frames).await
+    }
+
+    pub(crate) fn reset_replication_stream(&self) -> RS<()> {
+        self.contract.reset_replication_stream()
+    }
+
+    pub(crate) fn replication_next_lsn(&self) -> RS<Option<LSN>> {
+        self.contract.replication_next_lsn()
+    }
+
     pub async fn recover_cross_partition_transactions(&self) -> RS<()> {
         self.contract
             .recover_pending_cross_partition_records_async()
             .await
     }
 
+    /// Settles cross-partition transactions left in doubt by a lost
+    /// prepare, decision or acknowledgement. Runs with the periodic flush
+    /// round.
+    pub(crate) async fn resolve_cross_partition_transactions(&self) -> RS<()> {
+        self.contract
+            .resolve_pending_cross_partition_records_async()
+            .await
+    }
+
     pub fn open_session_with_config(&self, conn_id: u64, config: SessionOpenConfig) -> RS<OID> {
         if config.target_worker_index() != self.worker_index()
             || config.worker_id() != self.worker_id()
@@ -1183,7 +1568,7 @@ impl WorkerRuntime {
             ));
         }
         if config.session_id() == 0 {
-            self.create_session_with_admin(conn_id, true)
+            self.create_session_with_admin(conn_id, self.auth.is_admin(conn_id))
         } else {
             self.ensure_session_owned_by_connection(conn_id, config.session_id())?;
             Ok(config.session_id())
@@ -1191,6 +1576,50 @@ impl WorkerRuntime {
     }
 }
 
+/// The transaction a paged query outside an explicit transaction reads in,
+/// handed to its cursor.
+struct WorkerCursorTx {
+    contract: Arc<WorkerXContract>,
+    tx_mgr: Option<Arc<dyn TxMgr>>,
+}
+
+impl WorkerCursorTx {
+    fn new(contract: Arc<WorkerXContract>, tx_mgr: Arc<dyn TxMgr>) -> Self {
+        Self {
+            contract,
+            tx_mgr: Some(tx_mgr),
+        }
+    }
+
+    async fn abort(mut self) -> RS<()> {
+        match self.tx_mgr.take() {
+            Some(tx_mgr) => self.contract.worker_abort_tx_async(tx_mgr).await,
+            None => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl CursorTx for WorkerCursorTx {
+    async fn commit(mut self: Box<Self>) -> RS<()> {
+        match self.tx_mgr.take() {
+            Some(tx_mgr) => self.contract.worker_commit_routed_tx_async(tx_mgr).await,
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for WorkerCursorTx {
+    // A cursor closed before it was drained.
+    fn drop(&mut self) {
+        if let Some(tx_mgr) = self.tx_mgr.take() {
+            if let Err(err) = self.contract.worker_rollback_tx(tx_mgr) {
+                warn!("rolling back the transaction of a closed cursor failed: {err}");
+            }
+        }
+    }
+}
+
 // Miri cannot execute FFI calls into the tree-sitter C parser, which is
 // initialized when creating a WorkerRuntime session. The individual tests in
 // this module are skipped under Miri; worker behavior is still exercised by
@@ -1273,10 +1702,12 @@ mod tests {
             log_chunk_size: 4096,
             log_batching: WorkerLogBatching::default(),
             wal_sync_policy: WalSyncPolicy::Commit,
+            wal_archive_dir: None,
             procedure_runtime,
             registry,
             async_runtime: None,
             server_instance_id: 0,
+            auth: Arc::new(ServerAuth::disabled()),
         })
         .await
         .unwrap()
@@ -1495,10 +1926,12 @@ mod tests {
             log_chunk_size: 4096,
             log_batching: WorkerLogBatching::default(),
             wal_sync_policy: WalSyncPolicy::Commit,
+            wal_archive_dir: None,
             procedure_runtime: None,
             registry,
             async_runtime: None,
             server_instance_id: 0,
+            auth: Arc::new(ServerAuth::disabled()),
         })
         .await
         .unwrap();
@@ -1771,6 +2204,108 @@ mod tests {
         assert_eq!(parse_tx_control_stmt("BEGIN; COMMIT;"), None);
     }
 
+    #[test]
+    fn isolation_stmt_recognition() {
+        assert_eq!(
+            parse_tx_control_stmt("set transaction isolation level  serializable;"),
+            Some(TxControlStmt::SetTxIsolation(IsolationLevel::Serializable))
+        );
+        assert_eq!(
+            parse_tx_control_stmt("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ"),
+            Some(TxControlStmt::SetTxIsolation(IsolationLevel::Snapshot))
+        );
+        assert_eq!(
+            parse_tx_control_stmt(
+                "SET SESSION CHARACTERISTICS AS TRANSACTION ISOLATION LEVEL SERIALIZABLE"
+            ),
+            Some(TxControlStmt::SetSessionIsolation(
+                IsolationLevel::Serializable
+            ))
+        );
+        assert_eq!(
+            parse_tx_control_stmt("set session transaction isolation level snapshot"),
+            Some(TxControlStmt::SetSessionIsolation(IsolationLevel::Snapshot))
+        );
+        assert_eq!(
+            parse_tx_control_stmt("SET TRANSACTION ISOLATION LEVEL CHAOS"),
+            None
+        );
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn worker_serializable_sessions_abort_write_skew() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let (log_dir, registry) = test_registry(1);
+            let worker = test_worker(0, 1, &log_dir, &log_dir, registry, None).await;
+
+            let session_a = worker.create_session(1).unwrap();
+            let session_b = worker.create_session(2).unwrap();
+            sql_execute(
+                &worker,
+                session_a,
+                "SET SESSION CHARACTERISTICS AS TRANSACTION ISOLATION LEVEL SERIALIZABLE",
+            )
+            .await
+            .unwrap();
+            sql_execute(&worker, session_a, "BEGIN").await.unwrap();
+            sql_execute(&worker, session_b, "BEGIN").await.unwrap();
+            sql_execute(
+                &worker,
+                session_b,
+                "SET TRANSACTION ISOLATION LEVEL SERIALIZABLE",
+            )
+            .await
+            .unwrap();
+            let local_a = new_session_bound_worker_runtime(worker.clone(), session_a);
+            let local_b = new_session_bound_worker_runtime(worker.clone(), session_b);
+            for (local, session) in [(&local_a, session_a), (&local_b, session_b)] {
+                assert_eq!(local.get_async(session, b"x").await.unwrap(), None);
+                assert_eq!(local.get_async(session, b"y").await.unwrap(), None);
+            }
+            local_a
+                .put_async(session_a, b"x".to_vec(), b"1".to_vec())
+                .await
+                .unwrap();
+            local_b
+                .put_async(session_b, b"y".to_vec(), b"1".to_vec())
+                .await
+                .unwrap();
+
+            sql_execute(&worker, session_a, "COMMIT").await.unwrap();
+            let err = sql_execute(&worker, session_b, "COMMIT").await.unwrap_err();
+
+            assert_eq!(err.ec(), ErrorCode::SerializationFailure);
+            assert_eq!(worker.get_async(b"x").await.unwrap(), Some(b"1".to_vec()));
+            assert_eq!(worker.get_async(b"y").await.unwrap(), None);
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn worker_set_transaction_isolation_after_first_statement_fails() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let (log_dir, registry) = test_registry(1);
+            let worker = test_worker(0, 1, &log_dir, &log_dir, registry, None).await;
+
+            let session = worker.create_session(1).unwrap();
+            sql_execute(&worker, session, "BEGIN").await.unwrap();
+            let local = new_session_bound_worker_runtime(worker.clone(), session);
+            assert_eq!(local.get_async(session, b"x").await.unwrap(), None);
+            let err = sql_execute(
+                &worker,
+                session,
+                "SET TRANSACTION ISOLATION LEVEL SERIALIZABLE",
+            )
+            .await
+            .unwrap_err();
+            assert_eq!(err.ec(), ErrorCode::Transaction);
+            sql_execute(&worker, session, "ROLLBACK").await.unwrap();
+        })
+        .unwrap()
+    }
+
     async fn sql_execute(worker: &WorkerRuntime, session_id: OID, sql: &str) -> RS<u64> {
         worker
             .execute(session_id, Box::new(sql.to_string()), Box::new(()))
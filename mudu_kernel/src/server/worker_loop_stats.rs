@@ -22,3 +22,30 @@ pub(in crate::server) struct WorkerLoopStats {
     pub mailbox_drained: u64,
     pub local_register: u64,
 }
+
+impl WorkerLoopStats {
+    /// Every counter with its name, in declaration order.
+    pub(in crate::server) fn counters(&self) -> [(&'static str, u64); 19] {
+        [
+            ("submit_calls", self.submit_calls),
+            ("wait_cqe_calls", self.wait_cqe_calls),
+            ("cqe_accept", self.cqe_accept),
+            ("cqe_mailbox", self.cqe_mailbox),
+            ("cqe_recv", self.cqe_recv),
+            ("cqe_send", self.cqe_send),
+            ("cqe_log_write", self.cqe_log_write),
+            ("cqe_close", self.cqe_close),
+            ("recv_queue_push", self.recv_queue_push),
+            ("recv_queue_pop", self.recv_queue_pop),
+            ("send_queue_push", self.send_queue_push),
+            ("send_queue_pop", self.send_queue_pop),
+            ("recv_submit", self.recv_submit),
+            ("send_submit", self.send_submit),
+            ("log_write_submit", self.log_write_submit),
+            ("accept_submit", self.accept_submit),
+            ("mailbox_submit", self.mailbox_submit),
+            ("mailbox_drained", self.mailbox_drained),
+            ("local_register", self.local_register),
+        ]
+    }
+}
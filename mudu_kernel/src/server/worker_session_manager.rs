@@ -1,6 +1,7 @@
 use crate::contract::meta_mgr::MetaMgr;
 use crate::mudu_conn::mudu_conn_core::MuduConnCore;
-use crate::x_engine::tx_mgr::TxMgr;
+use crate::server::metrics;
+use crate::x_engine::tx_mgr::{IsolationLevel, TxMgr};
 use mudu::common::id::OID;
 use mudu::common::result::RS;
 use mudu::error::ErrorCode;
@@ -23,6 +24,8 @@ pub(crate) struct WorkerSessionManager {
 
 pub(crate) struct SessionContext {
     tx_manager: SMutex<Option<Arc<dyn TxMgr>>>,
+    /// Isolation level of the transactions this session begins.
+    isolation_level: SMutex<IsolationLevel>,
     mudu_conn_core: Arc<MuduConnCore>,
     is_admin: bool,
 }
@@ -70,6 +73,7 @@ impl WorkerSessionManager {
                 .connection_sessions(conn_id)
                 .insert_sync(session_id, ());
             self.active_sessions.fetch_add(1, Ordering::Relaxed);
+            metrics::record_session_opened();
             return Ok(session_id);
         }
     }
@@ -89,6 +93,7 @@ impl WorkerSessionManager {
                 }
                 if removed_owner {
                     self.active_sessions.fetch_sub(1, Ordering::Relaxed);
+                    metrics::record_session_closed();
                 }
                 Ok(true)
             }
@@ -109,6 +114,7 @@ impl WorkerSessionManager {
             session_ids.iter_sync(|session_id, _| {
                 if self.session_owner.remove_sync(session_id).is_some() {
                     self.active_sessions.fetch_sub(1, Ordering::Relaxed);
+                    metrics::record_session_closed();
                 }
                 let _ = self.session_contexts.remove_sync(session_id);
                 closed.push(*session_id);
@@ -255,6 +261,7 @@ impl SessionContext {
     ) -> RS<Self> {
         Ok(Self {
             tx_manager: SMutex::new(None),
+            isolation_level: SMutex::new(IsolationLevel::default()),
             mudu_conn_core: Arc::new(MuduConnCore::new(meta_mgr, async_runtime, is_admin)?),
             is_admin,
         })
@@ -273,6 +280,15 @@ impl SessionContext {
         Ok(self.tx_manager.lock()?.take())
     }
 
+    pub(crate) fn isolation_level(&self) -> RS<IsolationLevel> {
+        Ok(*self.isolation_level.lock()?)
+    }
+
+    pub(crate) fn set_isolation_level(&self, level: IsolationLevel) -> RS<()> {
+        *self.isolation_level.lock()? = level;
+        Ok(())
+    }
+
     pub(crate) fn mudu_conn_core(&self) -> Arc<MuduConnCore> {
         self.mudu_conn_core.clone()
     }
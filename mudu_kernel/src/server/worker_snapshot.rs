@@ -3,7 +3,7 @@ use mudu::common::result::RS;
 use mudu::error::ErrorCode;
 use mudu::mudu_error;
 use mudu_sys::sync::SMutex;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct KvItem {
@@ -19,7 +19,24 @@ pub struct WorkerSnapshot {
 
 pub struct WorkerSnapshotMgr {
     next_ts: AtomicU64,
-    running: SMutex<Vec<u64>>,
+    running: SMutex<Vec<RunningTx>>,
+    /// Set while the worker applies a primary's log as a read-only standby.
+    replica: AtomicBool,
+    /// On a standby, every transaction of the primary below this xid has
+    /// ended and its log has been applied.
+    replica_horizon: AtomicU64,
+    /// Transactions with an xid up to this one began before the standby
+    /// was promoted and stay read-only.
+    writable_after: AtomicU64,
+}
+
+/// One running transaction, kept sorted by `xid`. `horizon` is the oldest
+/// xid its snapshot may still treat as invisible: every version committed
+/// below it is visible to that snapshot.
+#[derive(Debug, Clone, Copy)]
+struct RunningTx {
+    xid: u64,
+    horizon: u64,
 }
 
 impl WorkerSnapshot {
@@ -53,16 +70,38 @@ impl WorkerSnapshot {
 
 impl WorkerSnapshotMgr {
     pub fn begin_tx(&self) -> RS<WorkerSnapshot> {
+        if self.is_replica() {
+            return self.begin_replica_tx();
+        }
         let xid = self.next_ts.fetch_add(1, Ordering::Relaxed) + 1;
         let mut running = self.running.lock()?;
         let snapshot = WorkerSnapshot {
             xid,
-            running: running.clone(),
+            running: running.iter().map(|tx| tx.xid).collect(),
         };
-        insert_sorted_unique(&mut running, xid);
+        let horizon = running.first().map_or(xid, |oldest| oldest.xid.min(xid));
+        if let Err(index) = running.binary_search_by_key(&xid, |tx| tx.xid) {
+            running.insert(index, RunningTx { xid, horizon });
+        }
         Ok(snapshot)
     }
 
+    /// A standby transaction reads at the replication horizon: the
+    /// primary's transactions below it have all ended and been applied, the
+    /// ones above it may still be in flight. Several standby transactions
+    /// share that xid, so the running list may hold it more than once.
+    fn begin_replica_tx(&self) -> RS<WorkerSnapshot> {
+        let horizon = self.replication_horizon();
+        let xid = horizon.saturating_sub(1);
+        let mut running = self.running.lock()?;
+        let index = running.partition_point(|tx| tx.xid <= xid);
+        running.insert(index, RunningTx { xid, horizon });
+        Ok(WorkerSnapshot {
+            xid,
+            running: Vec::new(),
+        })
+    }
+
     pub fn alloc_committed_ts(&self) -> u64 {
         self.next_ts.fetch_add(1, Ordering::Relaxed) + 1
     }
@@ -73,7 +112,7 @@ impl WorkerSnapshotMgr {
 
     pub fn end_tx(&self, xid: u64) -> RS<()> {
         let mut running = self.running.lock()?;
-        match running.binary_search(&xid) {
+        match running.binary_search_by_key(&xid, |tx| tx.xid) {
             Ok(index) => {
                 running.remove(index);
                 Ok(())
@@ -87,7 +126,27 @@ impl WorkerSnapshotMgr {
 
     /// Return the oldest xid still running, if any.
     pub fn oldest_running_xid(&self) -> RS<Option<u64>> {
-        Ok(self.running.lock()?.first().copied())
+        Ok(self.running.lock()?.first().map(|tx| tx.xid))
+    }
+
+    /// Return the version GC low-water mark: every version committed below
+    /// it is visible to every running and future snapshot, so of the
+    /// versions of one row below the mark only the newest can still be read.
+    ///
+    /// The oldest running xid alone is not enough: a transaction that
+    /// already finished can still appear in the running list of an older
+    /// snapshot, which then must not see its versions. Each running
+    /// transaction therefore records the oldest xid its snapshot excluded
+    /// at begin, and the mark is the minimum of those.
+    pub fn gc_low_water(&self) -> RS<u64> {
+        let running = self.running.lock()?;
+        let low_water = running.iter().map(|tx| tx.horizon).min();
+        let low_water = low_water.unwrap_or_else(|| self.latest_xid() + 1);
+        if self.is_replica() {
+            Ok(low_water.min(self.replication_horizon()))
+        } else {
+            Ok(low_water)
+        }
     }
 
     /// Return the newest xid allocated so far (begin or commit timestamp);
@@ -95,6 +154,46 @@ impl WorkerSnapshotMgr {
     pub fn latest_xid(&self) -> u64 {
         self.next_ts.load(Ordering::Relaxed)
     }
+
+    /// Switch to standby mode: new transactions read at the replication
+    /// horizon and may not write. Without a horizon recovered from the log,
+    /// everything replayed so far is taken as ended.
+    pub fn begin_replica(&self) {
+        let _ = self.replica_horizon.compare_exchange(
+            0,
+            self.latest_xid() + 1,
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+        );
+        self.replica.store(true, Ordering::Release);
+    }
+
+    /// Record a snapshot horizon replayed from the log.
+    pub fn observe_snapshot_horizon(&self, horizon: u64) {
+        self.replica_horizon.fetch_max(horizon, Ordering::Release);
+    }
+
+    /// Leave standby mode. The standby transactions still running began at
+    /// or below the newest replayed xid and remain read-only.
+    pub fn promote(&self) {
+        self.writable_after
+            .store(self.latest_xid(), Ordering::Release);
+        self.replica.store(false, Ordering::Release);
+    }
+
+    pub fn is_replica(&self) -> bool {
+        self.replica.load(Ordering::Acquire)
+    }
+
+    /// Whether the transaction `xid` is not allowed to write: every
+    /// transaction of a standby, and those that began before its promotion.
+    pub fn is_read_only_xid(&self, xid: u64) -> bool {
+        self.is_replica() || xid <= self.writable_after.load(Ordering::Acquire)
+    }
+
+    pub fn replication_horizon(&self) -> u64 {
+        self.replica_horizon.load(Ordering::Acquire)
+    }
 }
 
 impl Default for WorkerSnapshotMgr {
@@ -102,6 +201,9 @@ impl Default for WorkerSnapshotMgr {
         Self {
             next_ts: AtomicU64::new(0),
             running: SMutex::new(Vec::new()),
+            replica: AtomicBool::new(false),
+            replica_horizon: AtomicU64::new(0),
+            writable_after: AtomicU64::new(0),
         }
     }
 }
@@ -113,9 +215,66 @@ fn is_visible_to_snapshot(version_xid: u64, snapshot: &WorkerSnapshot) -> bool {
     snapshot.running.binary_search(&version_xid).is_err()
 }
 
-fn insert_sorted_unique(values: &mut Vec<u64>, value: u64) {
-    match values.binary_search(&value) {
-        Ok(_) => {}
-        Err(index) => values.insert(index, value),
+#[cfg(test)]
+mod tests {
+    #![allow(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::todo,
+        clippy::unimplemented
+    )]
+
+    use super::*;
+
+    #[test]
+    fn gc_low_water_covers_snapshots_of_finished_transactions() {
+        let mgr = WorkerSnapshotMgr::default();
+        assert_eq!(mgr.gc_low_water().unwrap(), 1);
+
+        let t1 = mgr.begin_tx().unwrap();
+        let t2 = mgr.begin_tx().unwrap();
+        assert_eq!(mgr.gc_low_water().unwrap(), t1.xid());
+
+        // t1 finishes, but t2's snapshot still excludes it: versions
+        // committed by t1 must stay collectable only above t1.
+        mgr.end_tx(t1.xid()).unwrap();
+        assert!(!t2.is_visible(t1.xid()));
+        assert_eq!(mgr.oldest_running_xid().unwrap(), Some(t2.xid()));
+        assert_eq!(mgr.gc_low_water().unwrap(), t1.xid());
+
+        mgr.end_tx(t2.xid()).unwrap();
+        let committed = mgr.alloc_committed_ts();
+        assert_eq!(mgr.gc_low_water().unwrap(), committed + 1);
+    }
+
+    #[test]
+    fn replica_transactions_read_at_the_replication_horizon() {
+        let mgr = WorkerSnapshotMgr::default();
+        mgr.observe_committed_ts(10);
+        mgr.observe_snapshot_horizon(6);
+        mgr.begin_replica();
+        assert_eq!(mgr.replication_horizon(), 6);
+
+        let t1 = mgr.begin_tx().unwrap();
+        let t2 = mgr.begin_tx().unwrap();
+        assert_eq!(t1.xid(), 5);
+        assert_eq!(t2.xid(), 5);
+        assert!(t1.is_visible(5));
+        assert!(!t1.is_visible(6));
+        assert!(mgr.is_read_only_xid(t1.xid()));
+        assert_eq!(mgr.gc_low_water().unwrap(), 6);
+
+        mgr.observe_snapshot_horizon(9);
+        mgr.end_tx(t1.xid()).unwrap();
+        mgr.end_tx(t2.xid()).unwrap();
+        assert_eq!(mgr.gc_low_water().unwrap(), 9);
+
+        mgr.promote();
+        assert!(!mgr.is_replica());
+        assert!(mgr.is_read_only_xid(t1.xid()));
+        let t3 = mgr.begin_tx().unwrap();
+        assert_eq!(t3.xid(), 11);
+        assert!(!mgr.is_read_only_xid(t3.xid()));
     }
 }
@@ -0,0 +1,397 @@
+//! Serializable snapshot isolation (SSI) conflict tracking.
+//!
+//! Snapshot isolation lets two concurrent transactions each read what the
+//! other writes (write skew). SSI detects the read/write anti-dependencies
+//! behind such schedules: `R -> W` when `R` read a key that a concurrent
+//! `W` wrote. Every non-serializable schedule contains a transaction with
+//! both an incoming and an outgoing anti-dependency (a "pivot"), so a
+//! serializable transaction is aborted at commit with
+//! `ErrorCode::SerializationFailure` when it would become a pivot, or when
+//! it would complete a pivot among transactions that already committed.
+//!
+//! [`SsiTracker`] is per worker and checks a committing transaction against
+//! the transactions committed on the same worker. Two transactions are
+//! concurrent when their snapshots do not see each other; records every
+//! running snapshot sees (below `WorkerSnapshotMgr::gc_low_water`) are
+//! dropped. A committed serializable transaction is therefore kept until
+//! the last snapshot that overlaps it is gone, even after no serializable
+//! transaction runs any more, so snapshot writers committing later still
+//! mark its anti-dependencies. With neither running nor retained
+//! serializable transactions nothing is recorded, and snapshot-only
+//! workloads pay one uncontended lock per commit.
+//!
+//! The guarantee only covers transactions on one worker. Serializable
+//! transactions are refused access to partitions of other workers (see
+//! `serializable_remote_access_error`); the writes cross-worker snapshot
+//! transactions apply here are recorded like local commits.
+
+use crate::server::worker_snapshot::WorkerSnapshot;
+use crate::x_engine::tx_mgr::{IsolationLevel, PhysicalRelationId, ReadSet, TxMgr};
+use mudu::common::result::RS;
+use mudu::error::ErrorCode;
+use mudu::mudu_error;
+use mudu_sys::sync::SMutex;
+use std::collections::BTreeSet;
+
+pub(crate) struct SsiTracker {
+    state: SMutex<SsiState>,
+}
+
+#[derive(Default)]
+struct SsiState {
+    /// Number of running serializable transactions.
+    serializable_running: usize,
+    committed: Vec<CommittedTx>,
+}
+
+impl SsiState {
+    /// Drop the records every running snapshot sees. Returns whether
+    /// commits still have to be recorded: a serializable transaction is
+    /// running or a retained one may still gain anti-dependencies.
+    fn prune(&mut self, low_water: u64) -> bool {
+        self.committed
+            .retain(|committed| committed.xid >= low_water);
+        if self.serializable_running > 0
+            || self
+                .committed
+                .iter()
+                .any(|committed| committed.serializable)
+        {
+            return true;
+        }
+        self.committed.clear();
+        false
+    }
+}
+
+/// A transaction committed while serializable transactions were running.
+struct CommittedTx {
+    xid: u64,
+    writes: BTreeSet<(PhysicalRelationId, Vec<u8>)>,
+    /// Empty for snapshot transactions: their reads are not tracked.
+    reads: ReadSet,
+    serializable: bool,
+    /// A concurrent transaction read a key this one wrote.
+    in_conflict: bool,
+    /// This transaction read a key a concurrent one wrote.
+    out_conflict: bool,
+}
+
+impl SsiTracker {
+    pub(crate) fn new() -> Self {
+        Self {
+            state: SMutex::new(SsiState::default()),
+        }
+    }
+
+    /// Register a transaction that switched to serializable isolation.
+    pub(crate) fn begin_serializable(&self) -> RS<()> {
+        self.state.lock()?.serializable_running += 1;
+        Ok(())
+    }
+
+    /// Unregister a serializable transaction (committed or rolled back).
+    /// Its committed record stays until every overlapping snapshot ended.
+    pub(crate) fn end_serializable(&self) -> RS<()> {
+        let mut state = self.state.lock()?;
+        state.serializable_running = state.serializable_running.saturating_sub(1);
+        Ok(())
+    }
+
+    /// Check `tx` about to commit `writes` against the transactions already
+    /// committed and, if it may commit, record it. Snapshot transactions
+    /// are never aborted here; their writes are recorded so serializable
+    /// readers can see them. `low_water` is the worker's GC low-water mark.
+    pub(crate) fn commit_check(
+        &self,
+        tx: &dyn TxMgr,
+        writes: &[(PhysicalRelationId, Vec<u8>)],
+        low_water: u64,
+    ) -> RS<()> {
+        let mut state = self.state.lock()?;
+        if !state.prune(low_water) {
+            return Ok(());
+        }
+        let snapshot = tx.snapshot();
+        let writes: BTreeSet<_> = writes.iter().cloned().collect();
+        let serializable = tx.isolation_level() == IsolationLevel::Serializable;
+        let (reads, in_edges, out_edges) = if serializable {
+            let reads = tx.read_set();
+            let (in_edges, out_edges) =
+                check_serializable(&state.committed, &snapshot, &reads, &writes)?;
+            (reads, in_edges, out_edges)
+        } else {
+            let in_edges = read_by_concurrent(&state.committed, &snapshot, &writes);
+            (ReadSet::default(), in_edges, Vec::new())
+        };
+        for index in &in_edges {
+            state.committed[*index].out_conflict = true;
+        }
+        for index in &out_edges {
+            state.committed[*index].in_conflict = true;
+        }
+        if !writes.is_empty() || !reads.is_empty() {
+            state.committed.push(CommittedTx {
+                xid: snapshot.xid(),
+                writes,
+                reads,
+                serializable,
+                in_conflict: !in_edges.is_empty(),
+                out_conflict: !out_edges.is_empty(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Record `writes` applied outside a local commit (the prepared share of
+    /// a cross-worker transaction) as a snapshot transaction `xid`. `xid` is
+    /// allocated after the apply, so every snapshot taken before treats the
+    /// writes as concurrent. The writer's own snapshot lives on another
+    /// worker, so it is taken as concurrent with every retained reader.
+    pub(crate) fn record_applied_writes(
+        &self,
+        xid: u64,
+        writes: &[(PhysicalRelationId, Vec<u8>)],
+        low_water: u64,
+    ) -> RS<()> {
+        let mut state = self.state.lock()?;
+        if !state.prune(low_water) || writes.is_empty() {
+            return Ok(());
+        }
+        let writes: BTreeSet<_> = writes.iter().cloned().collect();
+        let in_edges: Vec<_> = state
+            .committed
+            .iter()
+            .enumerate()
+            .filter(|(_, other)| {
+                writes
+                    .iter()
+                    .any(|(relation, key)| other.reads.covers(relation, key))
+            })
+            .map(|(index, _)| index)
+            .collect();
+        for index in &in_edges {
+            state.committed[*index].out_conflict = true;
+        }
+        state.committed.push(CommittedTx {
+            xid,
+            writes,
+            reads: ReadSet::default(),
+            serializable: false,
+            in_conflict: !in_edges.is_empty(),
+            out_conflict: false,
+        });
+        Ok(())
+    }
+}
+
+/// Returns the indexes of the committed transactions concurrent with
+/// `snapshot` that read one of `writes` (`R -> T` for a committing snapshot
+/// transaction `T`, which is never aborted).
+fn read_by_concurrent(
+    committed: &[CommittedTx],
+    snapshot: &WorkerSnapshot,
+    writes: &BTreeSet<(PhysicalRelationId, Vec<u8>)>,
+) -> Vec<usize> {
+    committed
+        .iter()
+        .enumerate()
+        .filter(|(_, other)| !snapshot.is_visible(other.xid))
+        .filter(|(_, other)| {
+            writes
+                .iter()
+                .any(|(relation, key)| other.reads.covers(relation, key))
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Returns the indexes of the committed transactions with an
+/// anti-dependency into (`R -> T`) and out of (`T -> W`) the committing
+/// serializable transaction `T`, or a serialization failure.
+fn check_serializable(
+    committed: &[CommittedTx],
+    snapshot: &WorkerSnapshot,
+    reads: &ReadSet,
+    writes: &BTreeSet<(PhysicalRelationId, Vec<u8>)>,
+) -> RS<(Vec<usize>, Vec<usize>)> {
+    let mut in_edges = Vec::new();
+    let mut out_edges = Vec::new();
+    for (index, other) in committed.iter().enumerate() {
+        if snapshot.is_visible(other.xid) {
+            continue;
+        }
+        // First committer wins: statement locks serialize concurrent
+        // writers of a key, but the later one may have decided what to
+        // write from a snapshot that misses the earlier write.
+        if other.writes.iter().any(|write| writes.contains(write)) {
+            return Err(mudu_error!(
+                ErrorCode::SerializationFailure,
+                "could not serialize access due to concurrent update"
+            ));
+        }
+        if other
+            .writes
+            .iter()
+            .any(|(relation, key)| reads.covers(relation, key))
+        {
+            out_edges.push(index);
+        }
+        if writes
+            .iter()
+            .any(|(relation, key)| other.reads.covers(relation, key))
+        {
+            in_edges.push(index);
+        }
+    }
+    let pivot = !in_edges.is_empty() && !out_edges.is_empty();
+    let completes_pivot = out_edges.iter().any(|index| committed[*index].out_conflict)
+        || in_edges.iter().any(|index| committed[*index].in_conflict);
+    if pivot || completes_pivot {
+        return Err(mudu_error!(
+            ErrorCode::SerializationFailure,
+            "could not serialize access due to read/write dependencies among transactions"
+        ));
+    }
+    Ok((in_edges, out_edges))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::todo,
+        clippy::unimplemented
+    )]
+
+    use super::*;
+    use crate::server::worker_snapshot::WorkerSnapshotMgr;
+    use crate::server::worker_tx_manager::WorkerTxManager;
+
+    const REL: PhysicalRelationId = PhysicalRelationId {
+        table_id: 1,
+        partition_id: 0,
+    };
+
+    fn serializable_tx(mgr: &WorkerSnapshotMgr, ssi: &SsiTracker) -> WorkerTxManager {
+        let tx = WorkerTxManager::new(mgr.begin_tx().unwrap());
+        tx.set_isolation_level(IsolationLevel::Serializable)
+            .unwrap();
+        ssi.begin_serializable().unwrap();
+        tx
+    }
+
+    fn write(key: &[u8]) -> Vec<(PhysicalRelationId, Vec<u8>)> {
+        vec![(REL, key.to_vec())]
+    }
+
+    #[test]
+    fn write_skew_aborts_second_committer() {
+        let mgr = WorkerSnapshotMgr::default();
+        let ssi = SsiTracker::new();
+        let t1 = serializable_tx(&mgr, &ssi);
+        let t2 = serializable_tx(&mgr, &ssi);
+        for tx in [&t1, &t2] {
+            tx.record_read(REL, b"x");
+            tx.record_read(REL, b"y");
+        }
+        let low_water = mgr.gc_low_water().unwrap();
+        ssi.commit_check(&t1, &write(b"x"), low_water).unwrap();
+        let err = ssi.commit_check(&t2, &write(b"y"), low_water).unwrap_err();
+        assert_eq!(err.ec(), ErrorCode::SerializationFailure);
+    }
+
+    #[test]
+    fn disjoint_or_sequential_transactions_commit() {
+        let mgr = WorkerSnapshotMgr::default();
+        let ssi = SsiTracker::new();
+        let t1 = serializable_tx(&mgr, &ssi);
+        let t2 = serializable_tx(&mgr, &ssi);
+        t1.record_read(REL, b"x");
+        t2.record_range_read(REL, b"m", b"");
+        let low_water = mgr.gc_low_water().unwrap();
+        ssi.commit_check(&t1, &write(b"x"), low_water).unwrap();
+        // t2 -> t1 only: one anti-dependency is not a pivot.
+        t2.record_read(REL, b"x");
+        ssi.commit_check(&t2, &write(b"y"), low_water).unwrap();
+        mgr.end_tx(t1.xid()).unwrap();
+        mgr.end_tx(t2.xid()).unwrap();
+
+        // t3 starts after both committed, so it sees their writes.
+        let t3 = serializable_tx(&mgr, &ssi);
+        t3.record_read(REL, b"x");
+        t3.record_read(REL, b"y");
+        ssi.commit_check(&t3, &write(b"x"), mgr.gc_low_water().unwrap())
+            .unwrap();
+    }
+
+    #[test]
+    fn concurrent_update_of_same_key_aborts() {
+        let mgr = WorkerSnapshotMgr::default();
+        let ssi = SsiTracker::new();
+        let t1 = serializable_tx(&mgr, &ssi);
+        let t2 = serializable_tx(&mgr, &ssi);
+        let low_water = mgr.gc_low_water().unwrap();
+        ssi.commit_check(&t1, &write(b"x"), low_water).unwrap();
+        let err = ssi.commit_check(&t2, &write(b"x"), low_water).unwrap_err();
+        assert_eq!(err.ec(), ErrorCode::SerializationFailure);
+    }
+
+    #[test]
+    fn snapshot_writer_completes_pivot_of_committed_reader() {
+        let mgr = WorkerSnapshotMgr::default();
+        let ssi = SsiTracker::new();
+        let t1 = serializable_tx(&mgr, &ssi);
+        let t3 = serializable_tx(&mgr, &ssi);
+        let ts = WorkerTxManager::new(mgr.begin_tx().unwrap());
+        let low_water = mgr.gc_low_water().unwrap();
+        t1.record_read(REL, b"x");
+        ssi.commit_check(&t1, &write(b"y"), low_water).unwrap();
+        ssi.end_serializable().unwrap();
+        // t1 -> ts: t1 read the x the concurrent ts writes.
+        ssi.commit_check(&ts, &write(b"x"), low_water).unwrap();
+        // t3 -> t1 closes the dangerous structure around t1.
+        t3.record_read(REL, b"y");
+        let err = ssi.commit_check(&t3, &write(b"z"), low_water).unwrap_err();
+        assert_eq!(err.ec(), ErrorCode::SerializationFailure);
+    }
+
+    #[test]
+    fn history_outlives_serializable_transactions_until_overlapping_snapshots_end() {
+        let mgr = WorkerSnapshotMgr::default();
+        let ssi = SsiTracker::new();
+        let t1 = serializable_tx(&mgr, &ssi);
+        let ts = WorkerTxManager::new(mgr.begin_tx().unwrap());
+        t1.record_read(REL, b"x");
+        ssi.commit_check(&t1, &write(b"y"), mgr.gc_low_water().unwrap())
+            .unwrap();
+        ssi.end_serializable().unwrap();
+        mgr.end_tx(t1.xid()).unwrap();
+
+        ssi.commit_check(&ts, &write(b"x"), mgr.gc_low_water().unwrap())
+            .unwrap();
+        {
+            let state = ssi.state.lock().unwrap();
+            assert_eq!(state.committed.len(), 2);
+            assert!(state.committed[0].out_conflict);
+        }
+        mgr.end_tx(ts.xid()).unwrap();
+
+        let t2 = WorkerTxManager::new(mgr.begin_tx().unwrap());
+        ssi.commit_check(&t2, &write(b"x"), mgr.gc_low_water().unwrap())
+            .unwrap();
+        assert!(ssi.state.lock().unwrap().committed.is_empty());
+    }
+
+    #[test]
+    fn nothing_is_tracked_without_serializable_transactions() {
+        let mgr = WorkerSnapshotMgr::default();
+        let ssi = SsiTracker::new();
+        let t1 = WorkerTxManager::new(mgr.begin_tx().unwrap());
+        ssi.commit_check(&t1, &write(b"x"), mgr.gc_low_water().unwrap())
+            .unwrap();
+        assert!(ssi.state.lock().unwrap().committed.is_empty());
+    }
+}
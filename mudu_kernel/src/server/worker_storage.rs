@@ -1,6 +1,7 @@
-use mudu_sys::sync::SMutex;
+use mudu_sys::sync::{SMutex, SRwLock};
 use std::collections::{BTreeMap, BTreeSet, Bound};
 use std::ops::Bound::{Excluded, Included, Unbounded};
+use std::path::Path;
 use std::sync::{Arc, OnceLock, Weak};
 use std::time::Duration;
 
@@ -10,11 +11,13 @@ use mudu::error::ErrorCode;
 use mudu::mudu_error;
 use mudu_sys::contract::async_io_provider::AsyncIoProvider;
 use mudu_sys::sync::async_::mutex::AMutex;
+use mudu_sys::sync::async_::{FRwLock, FRwLockReadGuard, FRwLockWriteGuard};
 use mudu_utils::{scoped_task_trace, task_trace};
 use scc::HashMap as SccHashMap;
 
 use crate::contract::data_row::DataRow;
 use crate::contract::meta_mgr::MetaMgr;
+use crate::contract::partition_rule::PartitionRuleDesc;
 use crate::contract::partition_rule_binding::TablePartitionBinding;
 use crate::contract::schema_table::SchemaTable;
 use crate::contract::table_desc::TableDesc;
@@ -23,15 +26,18 @@ use crate::contract::version_tuple::VersionTuple;
 use crate::index::index_key::key_tuple::KeyTuple;
 use crate::meta::fs_object::{fs_object_desc, FS_OBJECT_TABLE_ID};
 use crate::server::partition_router::DEFAULT_UNPARTITIONED_TABLE_PARTITION_ID;
+use crate::server::route_fence::RouteFence;
 use crate::server::worker_snapshot::{KvItem, WorkerSnapshot};
 #[cfg(test)]
 use crate::server::worker_tx_manager::WorkerTxManager;
-use crate::storage::relation::relation::Relation;
+use crate::storage::relation::layout_upgrade::{retired_layout, upgrade_deltas, LayoutUpgrade};
+use crate::storage::relation::relation::{Relation, VersionGcStats};
+use crate::wal::worker_log::WalCheckpointStats;
 use crate::wal::xl_batch::XLBatch;
-use crate::wal::xl_data_op::{XLDelete, XLInsert, XLUpdate, XLWrite};
+use crate::wal::xl_data_op::{XLDelete, XLDeltaLayout, XLInsert, XLUpdate, XLWrite};
 use crate::wal::xl_entry::TxOp;
-use crate::x_engine::api::DeltaAssign;
-use crate::x_engine::tx_mgr::{PhysicalRelationId, TxMgr};
+use crate::x_engine::api::{AlterTable, DeltaAssign};
+use crate::x_engine::tx_mgr::{IsolationLevel, PhysicalRelationId, TxMgr};
 use tracing::{info, trace};
 
 type WorkerStorageRegistry = std::collections::HashMap<String, Vec<Weak<WorkerStorage>>>;
@@ -43,11 +49,31 @@ type WorkerStorageRegistry = std::collections::HashMap<String, Vec<Weak<WorkerSt
 /// governs write coalescing and shutdown latency.
 pub(crate) const DIRTY_PAGE_FLUSH_INTERVAL: Duration = Duration::from_millis(10);
 
+/// Keys a KV range scan lists from the key index per lock acquisition.
+const KV_RANGE_BATCH: usize = 256;
+
 fn storage_registry() -> &'static SMutex<WorkerStorageRegistry> {
     static REGISTRY: OnceLock<SMutex<WorkerStorageRegistry>> = OnceLock::new();
     REGISTRY.get_or_init(|| SMutex::new(std::collections::HashMap::new()))
 }
 
+// The DDL lock of each data directory, shared by every worker storage on it;
+// see `WorkerStorage::exclude_commits_async`.
+fn ddl_lock(relation_path: &str) -> Arc<FRwLock> {
+    static LOCKS: OnceLock<SMutex<std::collections::HashMap<String, Arc<FRwLock>>>> =
+        OnceLock::new();
+    let locks = LOCKS.get_or_init(|| SMutex::new(std::collections::HashMap::new()));
+    #[expect(
+        clippy::expect_used,
+        reason = "a poisoned DDL lock registry is a programming error"
+    )]
+    let mut locks = locks.lock().expect("ddl lock registry poisoned");
+    locks
+        .entry(relation_path.to_string())
+        .or_insert_with(|| Arc::new(FRwLock::new()))
+        .clone()
+}
+
 #[derive(Clone, Debug)]
 pub(crate) struct PreparedWorkerCommit {
     xid: u64,
@@ -67,6 +93,10 @@ pub struct WorkerStorage {
     async_runtime: Option<Arc<dyn AsyncIoProvider>>,
     relation_store: SccHashMap<PhysicalRelationId, Arc<Relation>>,
     kv_store: SccHashMap<Vec<u8>, DataRow>,
+    /// Every key of `kv_store` in key order, so a range scan seeks to its
+    /// start key instead of visiting the whole hash map. Keys are never
+    /// removed: a delete leaves a tombstone version in `kv_store`.
+    kv_keys: SRwLock<BTreeSet<Vec<u8>>>,
     /// Idempotency guard for cross-partition apply, keyed by
     /// `(tx_id, partition_id)`: the coordinator applies one call per
     /// participant partition, so replay/retries of the same partition's
@@ -79,6 +109,8 @@ pub struct WorkerStorage {
     // and the open-time chain validation observes a torn page chain
     // (Decode errors: broken page link / two tails / disconnected pages).
     relation_create_lock: AMutex<()>,
+    route_fence: RouteFence,
+    ddl_lock: Arc<FRwLock>,
 }
 
 impl WorkerStorage {
@@ -103,12 +135,15 @@ impl WorkerStorage {
         Self {
             mgr,
             default_partition_id: partition_id,
+            ddl_lock: ddl_lock(&relation_path),
             relation_path,
             async_runtime,
             relation_store: SccHashMap::new(),
             kv_store: SccHashMap::new(),
+            kv_keys: SRwLock::new(BTreeSet::new()),
             applied_cross_tx: SccHashMap::new(),
             relation_create_lock: AMutex::new(()),
+            route_fence: RouteFence::new(),
         }
     }
 
@@ -164,6 +199,97 @@ impl WorkerStorage {
         }
     }
 
+    /// Checkpoints the PL WAL of every relation this worker hosts (see
+    /// `Relation::checkpoint_async`) and returns the combined outcome. Like
+    /// the dirty-page sweep, a failing relation does not skip the rest and
+    /// the first error is returned at the end.
+    pub(crate) async fn checkpoint_relations_async(&self) -> RS<WalCheckpointStats> {
+        let mut relations = Vec::new();
+        self.relation_store.iter_sync(|_, relation| {
+            relations.push(relation.clone());
+            true
+        });
+        let mut stats = WalCheckpointStats::default();
+        let mut first_err = None;
+        for relation in relations {
+            match relation.checkpoint_async().await {
+                Ok(relation_stats) => stats += relation_stats,
+                Err(err) => {
+                    if first_err.is_none() {
+                        first_err = Some(err);
+                    }
+                }
+            }
+            crate::common::yield_now::cooperative_yield_now().await;
+        }
+        match first_err {
+            Some(err) => Err(err),
+            None => Ok(stats),
+        }
+    }
+
+    /// Copies the files and PL WALs of every relation this worker hosts into
+    /// `target`, at their paths relative to the data directory (see
+    /// `Relation::backup_async`). Unlike the sweeps above, the first failure
+    /// ends the backup: a backup missing a relation is of no use.
+    pub(crate) async fn backup_relations_async(&self, target: &Path) -> RS<()> {
+        let mut relations = Vec::new();
+        self.relation_store.iter_sync(|_, relation| {
+            relations.push(relation.clone());
+            true
+        });
+        let source = Path::new(&self.relation_path);
+        for relation in relations {
+            relation.backup_async(source, target).await?;
+            crate::common::yield_now::cooperative_yield_now().await;
+        }
+        Ok(())
+    }
+
+    /// Prunes the versions no snapshot at or above `low_water` can read from
+    /// every relation this worker hosts (see `Relation::gc_versions_async`)
+    /// and from the worker KV store. Failures follow the dirty-page sweep:
+    /// the rest still runs and the first error is returned at the end.
+    pub(crate) async fn gc_versions_async(&self, low_water: u64) -> RS<VersionGcStats> {
+        let mut relations = Vec::new();
+        self.relation_store.iter_sync(|_, relation| {
+            relations.push(relation.clone());
+            true
+        });
+        let mut stats = VersionGcStats::default();
+        let mut first_err = None;
+        for relation in relations {
+            match relation.gc_versions_async(low_water).await {
+                Ok(relation_stats) => stats += relation_stats,
+                Err(err) => {
+                    if first_err.is_none() {
+                        first_err = Some(err);
+                    }
+                }
+            }
+            crate::common::yield_now::cooperative_yield_now().await;
+        }
+        let mut rows = Vec::new();
+        self.kv_store.iter_sync(|_, row| {
+            rows.push(row.clone());
+            true
+        });
+        for row in rows {
+            match row.prune_versions_sync(low_water) {
+                Ok((pruned, _)) => stats.pruned_versions += pruned as u64,
+                Err(err) => {
+                    if first_err.is_none() {
+                        first_err = Some(err);
+                    }
+                }
+            }
+        }
+        match first_err {
+            Some(err) => Err(err),
+            None => Ok(stats),
+        }
+    }
+
     // Open one `_fs_object` relation per partition known to the local meta
     // (plus partition 0) so fs-object rows staged by the DML hooks can be
     // committed and replayed on any local partition.
@@ -201,6 +327,93 @@ impl WorkerStorage {
         self.broadcast_drop_table_async(oid).await
     }
 
+    /// Applies the actions of one ALTER TABLE statement. Existing rows are
+    /// not rewritten: the table's relations are closed and reopened lazily
+    /// under the new layout, which reads older rows through their retired
+    /// layout. Worker log entries stamp the layout they were written under,
+    /// so replaying them after the switch upgrades them.
+    ///
+    /// Must run under [`Self::exclude_commits_async`], so no commit applies
+    /// rows between the catalog change and the close of the relations.
+    pub async fn alter_table_async(&self, oid: OID, alters: &[AlterTable]) -> RS<()> {
+        self.mgr.alter_table(oid, alters).await?;
+        self.broadcast_alter_table_async(oid).await
+    }
+
+    /// Held by a commit from stamping its rows with their layout until they
+    /// are applied, and by the resolution of a prepared two-phase
+    /// transaction while it applies its rows.
+    pub(crate) async fn enter_commit(&self) -> FRwLockReadGuard<'_> {
+        self.ddl_lock.read().await
+    }
+
+    /// Keeps commits out of every worker storage on the data directory until
+    /// the guard is dropped, once the commits holding
+    /// [`Self::enter_commit`] are done. One lock serves the whole data
+    /// directory: a commit may hold it on its coordinator while a participant
+    /// takes it again, which a lock per worker would deadlock against a DDL
+    /// statement taking them one by one.
+    pub(crate) async fn exclude_commits_async(&self) -> FRwLockWriteGuard<'_> {
+        self.ddl_lock.write().await
+    }
+
+    /// Hands partition `partition_id` over to its next owner: every worker on
+    /// the data directory makes the partition's relation WAL and dirty pages
+    /// durable and closes its relations, so the next access reopens them from
+    /// the files, replaying the relation WAL, on whichever worker owns the
+    /// partition by then.
+    pub(crate) async fn handoff_partition_async(&self, partition_id: OID) -> RS<()> {
+        let peers = self.peer_instances()?;
+        if peers.is_empty() {
+            return self.apply_handoff_partition_local_async(partition_id).await;
+        }
+        for storage in peers {
+            storage
+                .apply_handoff_partition_local_async(partition_id)
+                .await?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn route_fence(&self) -> &RouteFence {
+        &self.route_fence
+    }
+
+    /// Fences the rows routed under the current version of `rule` on every
+    /// worker storage of this process; see [`RouteFence`].
+    pub(crate) fn raise_route_fence(&self, rule: &PartitionRuleDesc) -> RS<()> {
+        let peers = self.peer_instances()?;
+        if peers.is_empty() {
+            return self.route_fence.raise(rule);
+        }
+        for storage in peers {
+            storage.route_fence.raise(rule)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn lower_route_fence(&self, rule_id: OID) -> RS<()> {
+        let peers = self.peer_instances()?;
+        if peers.is_empty() {
+            return self.route_fence.lower(rule_id);
+        }
+        for storage in peers {
+            storage.route_fence.lower(rule_id)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn drain_route_fence(&self) -> RS<()> {
+        let peers = self.peer_instances()?;
+        if peers.is_empty() {
+            return self.route_fence.drain().await;
+        }
+        for storage in peers {
+            storage.route_fence.drain().await?;
+        }
+        Ok(())
+    }
+
     #[cfg(test)]
     pub async fn contains_key(&self, oid: OID, key: &KeyTuple, txm: &dyn TxMgr) -> RS<bool> {
         self.contains_key_on_partition(oid, None, key, txm).await
@@ -279,7 +492,14 @@ impl WorkerStorage {
         // transaction's begin snapshot. Staged overlays above preserve
         // read-your-writes; write-write conflict protection for staged keys
         // comes from the statement/commit locks, not from this snapshot.
-        let snapshot = WorkerSnapshot::latest_committed();
+        // Serializable transactions are the exception, see
+        // `relation_read_snapshot`.
+        let snapshot = if txm.has_statement_lock(&relation_id, key.as_slice()) {
+            WorkerSnapshot::latest_committed()
+        } else {
+            txm.record_read(relation_id, key.as_slice());
+            relation_read_snapshot(txm)
+        };
         self.read_visible_relation_value(oid, partition_id, &key, &snapshot)
             .await
     }
@@ -382,16 +602,15 @@ impl WorkerStorage {
         // READ COMMITTED (see `get_on_partition`): base items observe the
         // latest committed versions; the transaction's staged overlay is
         // merged on top for read-your-writes.
+        let relation_id = self.relation_id(oid, self.physical_partition_id(partition_id));
+        if txm.isolation_level() == IsolationLevel::Serializable {
+            let (read_start, read_end) = bounds_to_read_range(&bounds);
+            txm.record_range_read(relation_id, &read_start, &read_end);
+        }
         let base_items = self
-            .range_visible_relation(
-                oid,
-                partition_id,
-                bounds,
-                &WorkerSnapshot::latest_committed(),
-            )
+            .range_visible_relation(oid, partition_id, bounds, &relation_read_snapshot(txm))
             .await?;
         let (start_key, end_key) = bounds_to_scan(&bounds);
-        let relation_id = self.relation_id(oid, self.physical_partition_id(partition_id));
         let staged_items = txm.staged_relation_items_in_range(relation_id, &start_key, &end_key);
 
         let mut merged = BTreeMap::new();
@@ -408,6 +627,49 @@ impl WorkerStorage {
             .collect())
     }
 
+    /// Rows of relation `oid` whose leading columns of secondary index
+    /// `index_oid` equal `prefix`, with the transaction's staged writes merged
+    /// on top under the same READ COMMITTED rules as `range_on_partition`.
+    pub async fn index_lookup_on_partition(
+        &self,
+        oid: OID,
+        partition_id: Option<OID>,
+        index_oid: OID,
+        prefix: &[Vec<u8>],
+        txm: &dyn TxMgr,
+    ) -> RS<Vec<(Vec<u8>, Vec<u8>)>> {
+        self.ensure_relation_index(oid, partition_id).await?;
+        let relation = self.get_relation_async(oid, partition_id).await?;
+        let relation_id = self.relation_id(oid, self.physical_partition_id(partition_id));
+        // The rows an index probe may match are not a key range, so a
+        // serializable reader conservatively records the whole relation.
+        txm.record_range_read(relation_id, &[], &[]);
+        let base_items = relation
+            .visible_index_lookup(index_oid, prefix, &relation_read_snapshot(txm))
+            .await?;
+        let staged_items = txm.staged_relation_items_in_range(relation_id, &[], &[]);
+
+        let mut merged = BTreeMap::new();
+        for (key, value) in base_items {
+            merged.insert(key, value);
+        }
+        for (key, value) in staged_items {
+            match value {
+                Some(value)
+                    if relation.index_prefix_matches(index_oid, &key, &value, prefix)? =>
+                {
+                    merged.insert(key, value);
+                }
+                // A staged delete, or a staged write that moves the row out
+                // of the probed prefix.
+                _ => {
+                    merged.remove(&key);
+                }
+            }
+        }
+        Ok(merged.into_iter().collect())
+    }
+
     pub async fn kv_get(
         &self,
         key: &[u8],
@@ -432,42 +694,70 @@ impl WorkerStorage {
             .map(|version| version.tuple().clone()))
     }
 
+    /// Read the visible key/value pairs in `[start_key, end_key)` in key
+    /// order. With a `limit`, stops after that many visible items.
+    ///
+    /// The keys are listed from the ordered key index a batch at a time,
+    /// starting at `start_key`, so a page reads the keys it returns plus the
+    /// tombstones in between rather than every key of the store.
     pub async fn kv_range(
         &self,
         start_key: &[u8],
         end_key: &[u8],
         snapshot: Option<&WorkerSnapshot>,
+        limit: Option<usize>,
     ) -> RS<Vec<KvItem>> {
-        let mut rows = Vec::new();
-        self.kv_store.iter_sync(|key, row| {
-            let in_range = if end_key.is_empty() {
-                key.as_slice() >= start_key
-            } else {
-                key.as_slice() >= start_key && key.as_slice() < end_key
-            };
-            if in_range {
-                rows.push((key.clone(), row.clone()));
-            }
-            true
-        });
-
         let mut items = Vec::new();
-        for (key, row) in rows {
-            let visible = match snapshot {
-                Some(snapshot) => {
-                    let snapshot = snapshot.to_snapshot();
-                    row.read(&snapshot).await?
-                }
-                None => row.read_latest().await?,
+        if !end_key.is_empty() && start_key >= end_key {
+            return Ok(items);
+        }
+        let upper = if end_key.is_empty() {
+            Unbounded
+        } else {
+            Excluded(end_key.to_vec())
+        };
+        let mut lower = Included(start_key.to_vec());
+        loop {
+            let wanted = match limit {
+                Some(limit) if items.len() >= limit => break,
+                Some(limit) => (limit - items.len()).min(KV_RANGE_BATCH),
+                None => KV_RANGE_BATCH,
+            };
+            // The index lock is not held across the version reads below.
+            let keys: Vec<Vec<u8>> = self
+                .kv_keys
+                .read()?
+                .range((lower.clone(), upper.clone()))
+                .take(wanted)
+                .cloned()
+                .collect();
+            let Some(last) = keys.last() else {
+                break;
             };
-            if let Some(visible) = visible.filter(|version| !version.is_deleted()) {
-                items.push(KvItem {
-                    key,
-                    value: visible.tuple().clone(),
-                });
+            lower = Excluded(last.clone());
+            for key in keys {
+                let Some(row) = self
+                    .kv_store
+                    .get_sync(&key)
+                    .map(|entry| entry.get().clone())
+                else {
+                    continue;
+                };
+                let visible = match snapshot {
+                    Some(snapshot) => {
+                        let snapshot = snapshot.to_snapshot();
+                        row.read(&snapshot).await?
+                    }
+                    None => row.read_latest().await?,
+                };
+                if let Some(visible) = visible.filter(|version| !version.is_deleted()) {
+                    items.push(KvItem {
+                        key,
+                        value: visible.tuple().clone(),
+                    });
+                }
             }
         }
-        items.sort_by(|left, right| left.key.cmp(&right.key));
         Ok(items)
     }
 
@@ -479,6 +769,8 @@ impl WorkerStorage {
         Ok(())
     }
 
+    /// Checks a transaction's staged writes for conflicts and stamps them
+    /// with their layout. Called under [`Self::enter_commit`].
     pub(crate) async fn prepare_commit_async(&self, txm: &dyn TxMgr) -> RS<PreparedWorkerCommit> {
         self.prepare_commit_parts_async(
             &txm.snapshot(),
@@ -488,6 +780,7 @@ impl WorkerStorage {
             txm.staged_put_items().into_iter().collect(),
             txm.xl_batch(),
             &txm.statement_locked_keys().into_iter().collect(),
+            &txm.staged_layout_versions(),
         )
         .await
     }
@@ -507,6 +800,7 @@ impl WorkerStorage {
             items,
             batch,
             &BTreeSet::new(),
+            &BTreeMap::new(),
         )
         .await
     }
@@ -654,11 +948,30 @@ impl WorkerStorage {
     }
 
     pub(crate) fn worker_put_local(&self, key: Vec<u8>, value: Vec<u8>, xid: u64) -> RS<()> {
-        write_version_to_kv_store(&self.kv_store, key, Some(value), xid)
+        write_version_to_kv_store(&self.kv_store, &self.kv_keys, key, Some(value), xid)
     }
 
     pub(crate) fn worker_delete_local(&self, key: Vec<u8>, xid: u64) -> RS<()> {
-        write_version_to_kv_store(&self.kv_store, key, None, xid)
+        write_version_to_kv_store(&self.kv_store, &self.kv_keys, key, None, xid)
+    }
+
+    /// The newest committed version of every live worker KV row as
+    /// `(key, value, xid)`, for the worker log checkpoint. Deleted rows are
+    /// left out; restoring the rows with [`Self::worker_put_local`] rebuilds
+    /// the state the checkpointed log entries produced.
+    pub(crate) fn kv_checkpoint_rows(&self) -> Vec<(Vec<u8>, Vec<u8>, u64)> {
+        let mut rows = Vec::new();
+        self.kv_store.iter_sync(|key, row| {
+            if let Some(version) = latest_version(row).filter(|version| !version.is_deleted()) {
+                rows.push((
+                    key.clone(),
+                    version.tuple().clone(),
+                    version.timestamp().c_min(),
+                ));
+            }
+            true
+        });
+        rows
     }
 
     #[allow(
@@ -672,12 +985,23 @@ impl WorkerStorage {
         relation_rows: BTreeMap<PhysicalRelationId, BTreeMap<Vec<u8>, Option<Vec<u8>>>>,
         relation_deltas: BTreeMap<PhysicalRelationId, BTreeMap<Vec<u8>, Vec<DeltaAssign>>>,
         kv_rows: BTreeMap<Vec<u8>, Option<Vec<u8>>>,
-        batch: XLBatch,
+        mut batch: XLBatch,
         conflict_skip: &BTreeSet<(PhysicalRelationId, Vec<u8>)>,
+        layout_versions: &BTreeMap<OID, u32>,
     ) -> RS<PreparedWorkerCommit> {
         self.ensure_no_relation_conflicts_async(snapshot, xid, &relation_rows, conflict_skip)
             .await?;
         self.ensure_no_kv_conflicts(snapshot, xid, &kv_rows)?;
+        self.stamp_layout_versions(
+            batch.entries.iter_mut().flat_map(|entry| {
+                entry.ops.iter_mut().filter_map(|op| match op {
+                    TxOp::Write(write) => Some(write),
+                    _ => None,
+                })
+            }),
+            layout_versions,
+        )
+        .await?;
 
         Ok(PreparedWorkerCommit {
             xid,
@@ -688,6 +1012,51 @@ impl WorkerStorage {
         })
     }
 
+    /// Stamps relation writes with the layout of their table, so replay can
+    /// tell rows logged before a later ALTER TABLE from current ones. Worker
+    /// KV records are left unstamped.
+    ///
+    /// `layout_versions` holds the layout each table's rows were staged
+    /// under (see `TxMgr::record_layout_version`). A table altered since
+    /// fails the commit with `ErrorCode::Transaction`: its staged rows are
+    /// encoded for a layout that is no longer current.
+    pub(crate) async fn stamp_layout_versions<'a>(
+        &self,
+        writes: impl IntoIterator<Item = &'a mut XLWrite>,
+        layout_versions: &BTreeMap<OID, u32>,
+    ) -> RS<()> {
+        for write in writes {
+            match write {
+                XLWrite::Insert(insert) if insert.table_id != 0 || insert.partition_id != 0 => {
+                    let desc = self.mgr.get_table_by_id(insert.table_id).await?;
+                    ensure_staged_layout(&desc, layout_versions)?;
+                    insert.layout_version = Some(desc.layout_version());
+                }
+                XLWrite::Update(update) => {
+                    let desc = self.mgr.get_table_by_id(update.table_id).await?;
+                    ensure_staged_layout(&desc, layout_versions)?;
+                    let deltas =
+                        crate::server::x_contract::utils::decode_delta_assigns(&update.delta)?;
+                    let column_oids = deltas
+                        .iter()
+                        .map(|delta| {
+                            desc.fields()
+                                .get(delta.attr)
+                                .map(|field| field.id())
+                                .ok_or_else(|| mudu_error!(ErrorCode::IndexOutOfRange))
+                        })
+                        .collect::<RS<Vec<_>>>()?;
+                    update.layout = Some(XLDeltaLayout {
+                        version: desc.layout_version(),
+                        column_oids,
+                    });
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
     async fn ensure_no_relation_conflicts_async(
         &self,
         snapshot: &WorkerSnapshot,
@@ -775,6 +1144,7 @@ impl WorkerStorage {
         for (key, value) in &prepared.kv_rows {
             write_version_to_kv_store_async(
                 &self.kv_store,
+                &self.kv_keys,
                 key.clone(),
                 value.clone(),
                 prepared.xid,
@@ -784,13 +1154,28 @@ impl WorkerStorage {
         Ok(())
     }
 
+    /// Replays a logged row. A row logged under a layout that ALTER TABLE
+    /// has retired since is upgraded to the current layout first: the
+    /// relation only appends to the value file of the current layout.
     async fn apply_relation_replay_insert_async(&self, insert: XLInsert, xid: u64) -> RS<()> {
         self.ensure_relation_index(insert.table_id, Some(insert.partition_id))
             .await?;
         let relation = self
             .get_relation_by_id_async(&self.relation_id(insert.table_id, insert.partition_id))
             .await?;
-        relation.write_value(insert.key, insert.value, xid).await
+        let value = match insert.layout_version {
+            Some(version) => {
+                let desc = self.mgr.get_table_by_id(insert.table_id).await?;
+                match retired_layout(desc.as_ref(), version)? {
+                    Some(retired) => {
+                        LayoutUpgrade::new(retired, desc.as_ref())?.upgrade(&insert.value)?
+                    }
+                    None => insert.value,
+                }
+            }
+            None => insert.value,
+        };
+        relation.write_value(insert.key, value, xid).await
     }
 
     async fn apply_relation_replay_delete_async(&self, delete: XLDelete, xid: u64) -> RS<()> {
@@ -805,7 +1190,8 @@ impl WorkerStorage {
     /// Replays a deferred-delta WAL update: the assignments are re-evaluated
     /// against the row as replay finds it. Replay order equals commit (LSN)
     /// order, and deferred deltas commute by contract, so the result equals
-    /// the live apply.
+    /// the live apply. Assignments logged under a retired layout are
+    /// re-targeted at the current columns by column OID.
     async fn apply_relation_replay_update_async(&self, update: XLUpdate, xid: u64) -> RS<()> {
         self.ensure_relation_index(update.table_id, Some(update.partition_id))
             .await?;
@@ -813,7 +1199,12 @@ impl WorkerStorage {
             .get_relation_by_id_async(&self.relation_id(update.table_id, update.partition_id))
             .await?;
         let desc = self.mgr.get_table_by_id(update.table_id).await?;
-        let deltas = crate::server::x_contract::utils::decode_delta_assigns(&update.delta)?;
+        let mut deltas = crate::server::x_contract::utils::decode_delta_assigns(&update.delta)?;
+        if let Some(layout) = &update.layout {
+            if let Some(retired) = retired_layout(desc.as_ref(), layout.version)? {
+                deltas = upgrade_deltas(retired, desc.as_ref(), &layout.column_oids, deltas)?;
+            }
+        }
         relation
             .write_rows_delta(desc.as_ref(), &[(update.key, deltas)], xid)
             .await
@@ -1007,6 +1398,49 @@ impl WorkerStorage {
         );
     }
 
+    // `ensure_relation_index` reopens the table's relations with the altered
+    // table desc.
+    async fn apply_alter_table_local_async(&self, oid: OID) -> RS<()> {
+        self.close_relations_async(|relation_id| relation_id.table_id == oid)
+            .await
+    }
+
+    async fn apply_handoff_partition_local_async(&self, partition_id: OID) -> RS<()> {
+        self.close_relations_async(|relation_id| relation_id.partition_id == partition_id)
+            .await
+    }
+
+    // Closes every open relation matching `filter` after making its WAL and
+    // dirty pages durable.
+    async fn close_relations_async(&self, filter: impl Fn(&PhysicalRelationId) -> bool) -> RS<()> {
+        let _guard = self.relation_create_lock.lock().await;
+        let mut relation_ids = Vec::new();
+        self.relation_store.iter_sync(|relation_id, _| {
+            if filter(relation_id) {
+                relation_ids.push(*relation_id);
+            }
+            true
+        });
+        for relation_id in relation_ids {
+            if let Some((_, relation)) = self.relation_store.remove_async(&relation_id).await {
+                relation.flush_wal_async().await?;
+                relation.flush_dirty_pages().await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn broadcast_alter_table_async(&self, oid: OID) -> RS<()> {
+        let peers = self.peer_instances()?;
+        if peers.is_empty() {
+            return self.apply_alter_table_local_async(oid).await;
+        }
+        for storage in peers {
+            storage.apply_alter_table_local_async(oid).await?;
+        }
+        Ok(())
+    }
+
     async fn broadcast_create_table_async(&self, schema: &SchemaTable) -> RS<()> {
         trace!(table = %schema.table_name(), oid = schema.id(), "worker_storage broadcast_create_table_async enter");
         let peers = self.peer_instances()?;
@@ -1075,7 +1509,39 @@ impl WorkerStorage {
     }
 }
 
+// Fails when the rows of `desc` were staged under another layout than its
+// current one.
+fn ensure_staged_layout(desc: &TableDesc, layout_versions: &BTreeMap<OID, u32>) -> RS<()> {
+    match layout_versions.get(&desc.id()) {
+        Some(staged) if *staged != desc.layout_version() => Err(mudu_error!(
+            ErrorCode::Transaction,
+            format!(
+                "table {} was altered after this transaction wrote to it",
+                desc.name()
+            )
+        )),
+        _ => Ok(()),
+    }
+}
+
 impl PreparedWorkerCommit {
+    /// The relation rows this commit inserts or updates, deferred deltas
+    /// included.
+    pub(crate) fn relation_puts(&self) -> Vec<(PhysicalRelationId, &[u8])> {
+        let mut puts = Vec::new();
+        for (relation_id, rows) in &self.relation_rows {
+            for (key, value) in rows {
+                if value.is_some() {
+                    puts.push((*relation_id, key.as_slice()));
+                }
+            }
+        }
+        for (relation_id, rows) in &self.relation_deltas {
+            puts.extend(rows.keys().map(|key| (*relation_id, key.as_slice())));
+        }
+        puts
+    }
+
     pub(crate) fn batch(&self) -> &XLBatch {
         &self.batch
     }
@@ -1087,43 +1553,55 @@ fn new_value_version(xid: u64, value: Vec<u8>) -> VersionTuple {
 
 fn write_version_to_kv_store(
     kv_store: &SccHashMap<Vec<u8>, DataRow>,
+    kv_keys: &SRwLock<BTreeSet<Vec<u8>>>,
     key: Vec<u8>,
     value: Option<Vec<u8>>,
     xid: u64,
 ) -> RS<()> {
-    let row = kv_store
-        .get_sync(&key)
-        .map(|entry| entry.get().clone())
-        .unwrap_or_else(|| DataRow::new(0));
+    let existing = kv_store.get_sync(&key).map(|entry| entry.get().clone());
+    let is_new = existing.is_none();
+    let row = existing.unwrap_or_else(|| DataRow::new(0));
     let version = match value {
         Some(value) => new_value_version(xid, value),
         None => VersionTuple::new_delete(Timestamp::new(xid, u64::MAX)),
     };
     row.write_sync(version, None)?;
+    index_new_kv_key(kv_keys, &key, is_new)?;
     let _ = kv_store.insert_sync(key, row);
     Ok(())
 }
 
 async fn write_version_to_kv_store_async(
     kv_store: &SccHashMap<Vec<u8>, DataRow>,
+    kv_keys: &SRwLock<BTreeSet<Vec<u8>>>,
     key: Vec<u8>,
     value: Option<Vec<u8>>,
     xid: u64,
 ) -> RS<()> {
     scoped_task_trace!();
-    let row = kv_store
-        .get_sync(&key)
-        .map(|entry| entry.get().clone())
-        .unwrap_or_else(|| DataRow::new(0));
+    let existing = kv_store.get_sync(&key).map(|entry| entry.get().clone());
+    let is_new = existing.is_none();
+    let row = existing.unwrap_or_else(|| DataRow::new(0));
     let version = match value {
         Some(value) => new_value_version(xid, value),
         None => VersionTuple::new_delete(Timestamp::new(xid, u64::MAX)),
     };
     row.write(version, None).await?;
+    index_new_kv_key(kv_keys, &key, is_new)?;
     let _ = kv_store.insert_sync(key, row);
     Ok(())
 }
 
+// Adds a key written for the first time to the ordered key index. A range
+// scan that lists the key before the row is in `kv_store` skips it, as it
+// would a key written after the scan.
+fn index_new_kv_key(kv_keys: &SRwLock<BTreeSet<Vec<u8>>>, key: &[u8], is_new: bool) -> RS<()> {
+    if is_new {
+        let _ = kv_keys.write()?.insert(key.to_vec());
+    }
+    Ok(())
+}
+
 fn latest_version(row: &DataRow) -> Option<VersionTuple> {
     row.read_latest_sync().ok().flatten()
 }
@@ -1155,6 +1633,27 @@ async fn collect_bound_partition_ids(
     Ok(())
 }
 
+/// The snapshot an unlocked relation read of `txm` observes: the latest
+/// committed version (READ COMMITTED) unless the transaction is
+/// serializable, which reads its begin snapshot so that every version it
+/// misses belongs to a concurrent writer the SSI commit check can see.
+fn relation_read_snapshot(txm: &dyn TxMgr) -> WorkerSnapshot {
+    match txm.isolation_level() {
+        IsolationLevel::Serializable => txm.snapshot(),
+        IsolationLevel::Snapshot => WorkerSnapshot::latest_committed(),
+    }
+}
+
+/// `[start, end)` covering every key within `bounds`; an empty `end` is
+/// unbounded. Unlike `bounds_to_scan`, an included end key stays inside.
+fn bounds_to_read_range(bounds: &(Bound<&[u8]>, Bound<&[u8]>)) -> (Vec<u8>, Vec<u8>) {
+    let (start, mut end) = bounds_to_scan(bounds);
+    if let Included(_) = bounds.1 {
+        end.push(0);
+    }
+    (start, end)
+}
+
 fn bounds_to_scan(bounds: &(Bound<&[u8]>, Bound<&[u8]>)) -> (Vec<u8>, Vec<u8>) {
     let start = match bounds.0 {
         Included(key) | Excluded(key) => key.to_vec(),
@@ -1531,7 +2030,7 @@ mod tests {
         let snapshot = WorkerSnapshot::new(2, vec![]);
         storage.worker_put_local(b"b".to_vec(), b"2".to_vec(), 3)?;
 
-        let rows = storage.kv_range(b"a", b"z", Some(&snapshot)).await?;
+        let rows = storage.kv_range(b"a", b"z", Some(&snapshot), None).await?;
         assert_eq!(
             rows,
             vec![KvItem {
@@ -1542,6 +2041,68 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn worker_storage_kv_range_limit_skips_deleted_keys() {
+        block_on(async move {
+            let r = _worker_storage_kv_range_limit_skips_deleted_keys().await;
+            assert!(r.is_ok())
+        })
+    }
+
+    async fn _worker_storage_kv_range_limit_skips_deleted_keys() -> RS<()> {
+        let (storage, _oid) = test_storage().await?;
+        for key in [b"d", b"a", b"c", b"b"] {
+            storage.worker_put_local(key.to_vec(), key.to_vec(), 1)?;
+        }
+        storage.worker_delete_local(b"b".to_vec(), 2)?;
+
+        let rows = storage.kv_range(b"a", b"z", None, Some(2)).await?;
+        let keys: Vec<_> = rows.into_iter().map(|item| item.key).collect();
+        assert_eq!(keys, vec![b"a".to_vec(), b"c".to_vec()]);
+        Ok(())
+    }
+
+    #[test]
+    fn worker_storage_kv_range_pages_across_index_batches() {
+        block_on(async move {
+            let r = _worker_storage_kv_range_pages_across_index_batches().await;
+            assert!(r.is_ok())
+        })
+    }
+
+    async fn _worker_storage_kv_range_pages_across_index_batches() -> RS<()> {
+        let (storage, _oid) = test_storage().await?;
+        let key = |i: usize| format!("k{:04}", i).into_bytes();
+        let count = KV_RANGE_BATCH * 2 + 7;
+        for i in 0..count {
+            storage.worker_put_local(key(i), key(i), 1)?;
+        }
+        // A run of tombstones longer than one index batch.
+        for i in 10..10 + KV_RANGE_BATCH + 3 {
+            storage.worker_delete_local(key(i), 2)?;
+        }
+
+        let mut seen = Vec::new();
+        let mut start = Vec::new();
+        loop {
+            let page = storage.kv_range(&start, b"", None, Some(100)).await?;
+            let Some(last) = page.last() else {
+                break;
+            };
+            // The next page starts right after the last returned key.
+            start = last.key.clone();
+            start.push(0);
+            seen.extend(page.into_iter().map(|item| item.key));
+        }
+        let expected: Vec<_> = (0..count)
+            .filter(|i| !(10..10 + KV_RANGE_BATCH + 3).contains(i))
+            .map(key)
+            .collect();
+        assert_eq!(seen, expected);
+        assert!(storage.kv_range(b"k9", b"k1", None, None).await?.is_empty());
+        Ok(())
+    }
+
     #[test]
     fn worker_storage_kv_allows_concurrent_commits_on_different_keys() {
         block_on(async move {
@@ -1600,6 +2161,7 @@ mod tests {
                     tuple_id: 0,
                     key: b"k".to_vec(),
                     value: b"v".to_vec(),
+                    layout_version: None,
                 })),
                 TxOp::Write(XLWrite::Insert(XLInsert {
                     table_id: oid,
@@ -1607,6 +2169,7 @@ mod tests {
                     tuple_id: 0,
                     key: i32_bytes(7),
                     value: i32_bytes(70),
+                    layout_version: None,
                 })),
                 TxOp::Commit,
             ],
@@ -1713,6 +2276,7 @@ mod tests {
                     tuple_id: 0,
                     key: i32_bytes(7),
                     delta: crate::server::x_contract::utils::encode_delta_assigns(&deltas)?,
+                    layout: None,
                 })),
                 TxOp::Commit,
             ],
@@ -1727,6 +2291,110 @@ mod tests {
         Ok(())
     }
 
+    /// Rows logged before an ALTER TABLE are replayed after it: the insert is
+    /// upgraded from the layout stamped at commit and the deferred delta is
+    /// re-targeted at the altered column, operand converted to its new type.
+    #[test]
+    fn worker_storage_replay_upgrades_rows_logged_before_alter_table() {
+        block_on(async move {
+            let r = _worker_storage_replay_upgrades_rows_logged_before_alter_table().await;
+            r.unwrap()
+        })
+    }
+
+    async fn _worker_storage_replay_upgrades_rows_logged_before_alter_table() -> RS<()> {
+        let mgr = Arc::new(TestMetaMgr::new());
+        let root = mudu_sys::env_var::temp_dir()
+            .join(format!(
+                "worker_storage_alter_replay_test_{}",
+                mudu_utils::oid::gen_oid()
+            ))
+            .to_string_lossy()
+            .to_string();
+        let storage = WorkerStorage::new(mgr.clone(), 0, root.clone());
+        let schema = test_schema();
+        let oid = schema.id();
+        storage.create_table_async(&schema).await?;
+        let old_desc = mgr.get_table_by_id(oid).await?;
+
+        let deltas = vec![crate::x_engine::api::DeltaAssign {
+            attr: 1,
+            op: crate::x_engine::api::DeltaOp::AddDeferred,
+            literal: i32_bytes(2),
+        }];
+        let mut writes = vec![
+            XLWrite::Insert(XLInsert {
+                table_id: oid,
+                partition_id: 0,
+                tuple_id: 0,
+                key: i32_bytes(7),
+                value: crate::server::x_contract::utils::build_value_tuple(
+                    &crate::x_engine::api::VecDatum::new(vec![(1, i32_bytes(10))]),
+                    &old_desc,
+                )?,
+                layout_version: None,
+            }),
+            XLWrite::Update(XLUpdate {
+                table_id: oid,
+                partition_id: 0,
+                tuple_id: 0,
+                key: i32_bytes(7),
+                delta: crate::server::x_contract::utils::encode_delta_assigns(&deltas)?,
+                layout: None,
+            }),
+        ];
+        storage
+            .stamp_layout_versions(&mut writes, &BTreeMap::new())
+            .await?;
+        match &writes[1] {
+            XLWrite::Update(update) => assert_eq!(
+                update.layout,
+                Some(XLDeltaLayout {
+                    version: old_desc.layout_version(),
+                    column_oids: vec![old_desc.get_attr(1).id()],
+                })
+            ),
+            _ => unreachable!(),
+        }
+
+        storage
+            .alter_table_async(
+                oid,
+                &[AlterTable::AlterColumnType {
+                    name: "v".to_string(),
+                    type_id: TypeFamily::I64,
+                    type_param: mudu_type::data_type::DataType::new_no_param(TypeFamily::I64)
+                        .to_info(),
+                }],
+            )
+            .await?;
+        drop(storage);
+
+        // Restart: a fresh storage over the same files replays the log.
+        let storage = WorkerStorage::new(mgr.clone(), 0, root);
+        let mut ops = vec![TxOp::Begin];
+        ops.extend(writes.into_iter().map(TxOp::Write));
+        ops.push(TxOp::Commit);
+        storage
+            .replay_batch(XLBatch::new(vec![crate::wal::xl_entry::XLEntry {
+                xid: 2,
+                ops,
+            }]))
+            .await?;
+
+        let new_desc = mgr.get_table_by_id(oid).await?;
+        assert_ne!(new_desc.layout_version(), old_desc.layout_version());
+        let tx = begin_tx(3, vec![]);
+        assert_eq!(
+            storage.get(oid, &i32_bytes(7), &tx).await?,
+            Some(crate::server::x_contract::utils::build_value_tuple(
+                &crate::x_engine::api::VecDatum::new(vec![(1, 12i64.to_be_bytes().to_vec())]),
+                &new_desc,
+            )?)
+        );
+        Ok(())
+    }
+
     /// Regression test: one cross-partition transaction whose write set
     /// spans two partitions hosted on this worker must persist BOTH rows.
     /// The coordinator applies one call per participant partition, so a
@@ -1740,6 +2408,7 @@ mod tests {
             tuple_id: 0,
             key: i32_bytes(1),
             value: i32_bytes(10),
+            layout_version: None,
         });
         let write_p2 = XLWrite::Insert(XLInsert {
             table_id: oid,
@@ -1747,6 +2416,7 @@ mod tests {
             tuple_id: 0,
             key: i32_bytes(2),
             value: i32_bytes(20),
+            layout_version: None,
         });
 
         storage
@@ -1780,6 +2450,7 @@ mod tests {
             tuple_id: 0,
             key: i32_bytes(9),
             value: i32_bytes(90),
+            layout_version: None,
         });
 
         storage
@@ -1832,6 +2503,7 @@ mod tests {
                     tuple_id: 0,
                     key: i32_bytes(5),
                     value: i32_bytes(50),
+                    layout_version: None,
                 })),
                 TxOp::Commit,
             ],
@@ -4,8 +4,10 @@ use crate::wal::xl_batch::XLBatch;
 use crate::wal::xl_data_op::{XLDelete, XLInsert, XLUpdate, XLWrite};
 use crate::wal::xl_entry::{TxOp, XLEntry};
 use crate::x_engine::api::DeltaAssign;
-use crate::x_engine::tx_mgr::{PhysicalRelationId, TxMgr};
+use crate::x_engine::tx_mgr::{IsolationLevel, PhysicalRelationId, ReadSet, TxMgr, KV_RELATION_ID};
 use mudu::common::id::OID;
+use mudu::error::ErrorCode;
+use mudu::mudu_error;
 use mudu_utils::task_trace;
 use std::cell::RefCell;
 use std::collections::{BTreeMap, BTreeSet};
@@ -30,6 +32,14 @@ struct WorkerTxState {
     // keys locked on the local worker and remote owners that granted locks.
     statement_lock_keys: BTreeSet<(PhysicalRelationId, Vec<u8>)>,
     remote_lock_owners: BTreeSet<OID>,
+    // Value layout of the staged rows of each table, recorded when they are
+    // staged; see `WorkerStorage::stamp_layout_versions`.
+    layout_versions: BTreeMap<OID, u32>,
+    isolation_level: IsolationLevel,
+    // Committed-storage reads, kept only while `isolation_level` is
+    // `Serializable`; checked against concurrent writers at commit.
+    read_set: ReadSet,
+    ran_statement: bool,
 }
 
 pub struct WorkerTxManager {
@@ -51,6 +61,10 @@ impl WorkerTxManager {
                 relation_log_index: BTreeMap::new(),
                 statement_lock_keys: BTreeSet::new(),
                 remote_lock_owners: BTreeSet::new(),
+                layout_versions: BTreeMap::new(),
+                isolation_level: IsolationLevel::Snapshot,
+                read_set: ReadSet::default(),
+                ran_statement: false,
             }),
         }
     }
@@ -103,6 +117,7 @@ impl TxMgr for WorkerTxManager {
                 tuple_id: 0,
                 key: key.clone(),
                 value: value.clone(),
+                layout_version: None,
             }));
             state.state_kv_write(key, Some(value), op);
         });
@@ -146,6 +161,9 @@ impl TxMgr for WorkerTxManager {
                 tuple_id: 0,
                 key: key.clone(),
                 value: value.clone(),
+                // Stamped at commit with the layout recorded when the row was
+                // staged, see WorkerStorage::stamp_layout_versions.
+                layout_version: None,
             }));
             let key = (relation_id, key);
             state.stage_rel_write(key, Some(value), op);
@@ -239,6 +257,7 @@ impl TxMgr for WorkerTxManager {
                 tuple_id: 0,
                 key: key.clone(),
                 delta: encoded,
+                layout: None,
             })));
             state
                 .staged_relation_deferred_deltas
@@ -314,13 +333,7 @@ impl TxMgr for WorkerTxManager {
             state.write_ops.clear();
             let mut write_ops = Vec::new();
             for key in state.stage_kv_write.keys() {
-                write_ops.push((
-                    PhysicalRelationId {
-                        table_id: 0,
-                        partition_id: 0,
-                    },
-                    key.clone(),
-                ));
+                write_ops.push((KV_RELATION_ID, key.clone()));
             }
             for (relation_id, ops) in &state.staged_relation_ops {
                 for key in ops.keys() {
@@ -364,13 +377,78 @@ impl TxMgr for WorkerTxManager {
         self.with_state_mut(|state| state.remote_lock_owners.clear());
     }
 
+    fn record_layout_version(&self, table_id: OID, version: u32) -> mudu::common::result::RS<()> {
+        let staged =
+            self.with_state_mut(|state| *state.layout_versions.entry(table_id).or_insert(version));
+        if staged != version {
+            return Err(mudu_error!(
+                ErrorCode::Transaction,
+                format!(
+                    "table {} was altered after this transaction wrote to it",
+                    table_id
+                )
+            ));
+        }
+        Ok(())
+    }
+
+    fn staged_layout_versions(&self) -> BTreeMap<OID, u32> {
+        self.with_state(|state| state.layout_versions.clone())
+    }
+
+    fn record_statement(&self) {
+        self.with_state_mut(|state| state.ran_statement = true);
+    }
+
+    fn has_run_statement(&self) -> bool {
+        self.with_state(|state| state.ran_statement)
+    }
+
+    fn isolation_level(&self) -> IsolationLevel {
+        self.with_state(|state| state.isolation_level)
+    }
+
+    fn set_isolation_level(&self, level: IsolationLevel) -> mudu::common::result::RS<()> {
+        self.with_state_mut(|state| {
+            state.isolation_level = level;
+            if level != IsolationLevel::Serializable {
+                state.read_set = ReadSet::default();
+            }
+        });
+        Ok(())
+    }
+
+    fn record_read(&self, relation: PhysicalRelationId, key: &[u8]) {
+        self.with_state_mut(|state| {
+            if state.isolation_level == IsolationLevel::Serializable {
+                state.read_set.keys.insert((relation, key.to_vec()));
+            }
+        });
+    }
+
+    fn record_range_read(&self, relation: PhysicalRelationId, start: &[u8], end: &[u8]) {
+        self.with_state_mut(|state| {
+            if state.isolation_level == IsolationLevel::Serializable {
+                state
+                    .read_set
+                    .ranges
+                    .push((relation, start.to_vec(), end.to_vec()));
+            }
+        });
+    }
+
+    fn read_set(&self) -> ReadSet {
+        self.with_state(|state| state.read_set.clone())
+    }
+
     fn xl_batch(&self) -> XLBatch {
         self.with_state(|state| {
             let xid = self.snapshot.xid();
-            let mut ops = Vec::with_capacity(state.log_buffer.len() + 2);
+            let mut ops = Vec::with_capacity(state.log_buffer.len() + 3);
             ops.push(TxOp::Begin);
             ops.extend(state.log_buffer.clone());
             ops.push(TxOp::Commit);
+            ops.push(TxOp::commit_time_now());
             XLBatch::new(vec![XLEntry { xid, ops }])
         })
     }
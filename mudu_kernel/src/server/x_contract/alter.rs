@@ -0,0 +1,113 @@
+use super::*;
+use crate::contract::schema_column::{ColumnDefault, SchemaColumn};
+use crate::contract::table_info::TableInfo;
+use crate::storage::relation::layout_upgrade::{retired_layout, LayoutUpgrade};
+
+impl WorkerXContract {
+    /// Applies the actions of one ALTER TABLE statement to table `oid`.
+    ///
+    /// Commits are kept out of every worker on the data directory while the
+    /// catalog is switched and the table's relations are closed (see
+    /// `WorkerStorage::exclude_commits_async`). A transaction that staged
+    /// rows of the table before the switch fails at commit, as its rows are
+    /// encoded for the old layout. Existing rows are upgraded when they are
+    /// read, so a column type change first checks that every row converts,
+    /// and a column existing rows cannot be backfilled for is only added to
+    /// an empty table.
+    pub(crate) async fn _alter_table(&self, oid: OID, alters: &[AlterTable]) -> RS<()> {
+        // Rows logged under the old layout are made durable in the relation
+        // files before the switch, so recovery does not have to upgrade them
+        // from the log (replay still can: logged rows carry their layout).
+        self.checkpoint_async().await?;
+        let _excluded = self.storage.exclude_commits_async().await;
+        if alters
+            .iter()
+            .any(|alter| matches!(alter, AlterTable::AlterColumnType { .. }))
+        {
+            self.ensure_rows_convert(oid, alters).await?;
+        }
+        if let Some(column) = alters.iter().find_map(|alter| match alter {
+            AlterTable::AddColumn(column) if !backfills(column) => Some(column),
+            _ => None,
+        }) {
+            self.ensure_table_empty(oid, column).await?;
+        }
+        self.storage.alter_table_async(oid, alters).await
+    }
+
+    // Fails when table `oid` has a row, which the added `column` would have
+    // no value for.
+    async fn ensure_table_empty(&self, oid: OID, column: &SchemaColumn) -> RS<()> {
+        let desc = self.meta_mgr.get_table_by_id(oid).await?;
+        for partition_id in self.table_partitions(oid, desc.as_ref()).await? {
+            if !self.scan_partition(oid, partition_id).await?.is_empty() {
+                let reason = if column.default_value().is_some() {
+                    "a non-constant DEFAULT"
+                } else {
+                    "NOT NULL without a DEFAULT"
+                };
+                return Err(mudu_error!(
+                    ErrorCode::UnsupportedOperation,
+                    format!(
+                        "cannot add column {} with {} to non-empty table {}",
+                        column.get_name(),
+                        reason,
+                        desc.name()
+                    )
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    async fn table_partitions(&self, oid: OID, desc: &TableDesc) -> RS<Vec<OID>> {
+        Ok(self
+            .partition_router
+            .route_range_partitions(oid, desc, &Bound::Unbounded, &Bound::Unbounded)
+            .await?
+            .unwrap_or_else(|| vec![self.storage.physical_partition_id(None)]))
+    }
+
+    // Upgrades every row of table `oid` to the layout `alters` produce and
+    // fails on the first one that does not convert.
+    async fn ensure_rows_convert(&self, oid: OID, alters: &[AlterTable]) -> RS<()> {
+        let desc = self.meta_mgr.get_table_by_id(oid).await?;
+        let schema = self
+            .meta_mgr
+            .list_schemas()
+            .await?
+            .into_iter()
+            .find(|schema| schema.id() == oid)
+            .ok_or_else(|| {
+                mudu_error!(ErrorCode::EntityNotFound, format!("no such table {}", oid))
+            })?;
+        let altered = TableInfo::new(schema.altered_all(alters)?)?.table_desc()?;
+        let Some(retired) = retired_layout(altered.as_ref(), desc.layout_version())? else {
+            return Ok(());
+        };
+        let upgrade = LayoutUpgrade::new(retired, altered.as_ref())?;
+        for partition_id in self.table_partitions(oid, desc.as_ref()).await? {
+            for (_, value) in self.scan_partition(oid, partition_id).await? {
+                upgrade.upgrade(&value).map_err(|err| {
+                    mudu_error!(
+                        ErrorCode::TypeConversionFailed,
+                        format!("cannot alter the column types of table {}", desc.name()),
+                        err
+                    )
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Whether rows stored before `column` is added can read a value for it: NULL,
+// or a constant DEFAULT. `NOW()` and `GEN_OID()` defaults differ per row and
+// per read, so they are not backfilled.
+fn backfills(column: &SchemaColumn) -> bool {
+    match column.default_value() {
+        Some(ColumnDefault::Value(_)) => true,
+        Some(ColumnDefault::Now | ColumnDefault::GenOid) => false,
+        None => column.nullable(),
+    }
+}
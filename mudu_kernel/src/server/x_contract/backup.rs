@@ -0,0 +1,37 @@
+use super::*;
+use crate::server::backup::claim_backup;
+use crate::wal::worker_log::WorkerLogBackup;
+use std::path::Path;
+
+impl WorkerXContract {
+    /// Takes this worker's part of a pending online backup, if there is
+    /// one; see `crate::server::backup`. Called from the periodic dirty-page
+    /// flush round, which also runs the checkpoints, so a backup never
+    /// overlaps a checkpoint of the same worker. A failed backup is
+    /// reported to the job and does not fail the flush round.
+    pub(crate) async fn backup_if_requested(&self) -> RS<()> {
+        let Some(job) = claim_backup(&self.log_layout.log_dir, self.worker_id)? else {
+            return Ok(());
+        };
+        let result = self.backup_async(job.target(), job.log_target()).await;
+        debug!(
+            worker_id = self.worker_id,
+            ok = result.is_ok(),
+            "worker backup done"
+        );
+        job.report(result)
+    }
+
+    /// Checkpoints, then copies the hosted relations into `target` and the
+    /// worker log into `log_target`. The log is copied last: every write in
+    /// the copied relation files was logged before it, so the copied log
+    /// replays onto them. Returns `None` for a worker without a log.
+    async fn backup_async(&self, target: &Path, log_target: &Path) -> RS<Option<WorkerLogBackup>> {
+        self.checkpoint_async().await?;
+        self.storage.backup_relations_async(target).await?;
+        match self.log_cloned()? {
+            Some(log) => Ok(Some(log.backup_async(log_target).await?)),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use super::*;
+use crate::wal::worker_log::{WalCheckpoint, WalCheckpointStats};
+use mudu_sys::sync::async_::{ANotify, FRwLock, FRwLockReadGuard, FRwLockWriteGuard};
+use mudu_sys::time::instant_now;
+use serde::{Deserialize, Serialize};
+
+/// Interval between two worker log checkpoints. Each checkpoint writes back
+/// the dirty relation pages, records the worker KV rows and deletes the WAL
+/// chunks it covers, so this bounds both WAL growth and restart replay.
+pub(crate) const WAL_CHECKPOINT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Worker state recorded by a worker log checkpoint. Relation rows are not
+/// part of it: the relations checkpoint their own PL WAL first, so their
+/// data files already hold every write the checkpointed entries made.
+///
+/// Unresolved two-phase commit state is recorded too: the prepare and
+/// decision entries that produced it are deleted with the sealed chunks.
+#[derive(Default, Serialize, Deserialize)]
+pub(crate) struct WorkerLogCheckpoint {
+    pub(crate) max_xid: u64,
+    pub(crate) kv_rows: Vec<(Vec<u8>, Vec<u8>, u64)>,
+    #[serde(default)]
+    pub(crate) prepared_txs: Vec<two_phase::PreparedTxRecord>,
+    #[serde(default)]
+    pub(crate) commit_decisions: Vec<two_phase::CommitDecisionRecord>,
+}
+
+impl WorkerLogCheckpoint {
+    pub(crate) fn decode(checkpoint: &WalCheckpoint) -> RS<Self> {
+        rmp_serde::from_slice(&checkpoint.payload)
+            .map_err(|e| mudu_error!(ErrorCode::Decode, "decode worker log checkpoint error", e))
+    }
+
+    pub(crate) fn encode(&self) -> RS<Vec<u8>> {
+        rmp_serde::to_vec(self)
+            .map_err(|e| mudu_error!(ErrorCode::Encode, "encode worker log checkpoint error", e))
+    }
+}
+
+/// Keeps worker log appends out of the window in which a checkpoint seals
+/// the log. Every path that allocates worker log LSNs holds [`Self::enter`]
+/// from the allocation until its writes are applied to storage, so once the
+/// checkpoint holds [`Self::exclusive`] the storage state matches the log
+/// exactly.
+///
+/// The underlying `FRwLock` admits readers while a writer merely waits;
+/// `pending` keeps new commits out once a checkpoint asks for the gate, so
+/// a steady stream of overlapping commits cannot starve it.
+pub(crate) struct CheckpointGate {
+    lock: FRwLock,
+    pending: AtomicBool,
+    resumed: ANotify,
+}
+
+/// Exclusive hold of a [`CheckpointGate`]. The write guard is released
+/// before `pending` is cleared and the waiting commits are woken.
+pub(crate) struct CheckpointGateGuard<'a> {
+    _write: FRwLockWriteGuard<'a>,
+    _pending: PendingCheckpoint<'a>,
+}
+
+// Raised `pending` flag; lowering it on drop keeps a cancelled `exclusive`
+// from locking commits out for good.
+struct PendingCheckpoint<'a> {
+    gate: &'a CheckpointGate,
+}
+
+impl CheckpointGate {
+    pub(crate) fn new() -> Self {
+        Self {
+            lock: FRwLock::new(),
+            pending: AtomicBool::new(false),
+            resumed: ANotify::new(),
+        }
+    }
+
+    pub(crate) async fn enter(&self) -> FRwLockReadGuard<'_> {
+        loop {
+            if !self.pending.load(Ordering::Acquire) {
+                return self.lock.read().await;
+            }
+            self.resumed.clear_signal();
+            if !self.pending.load(Ordering::Acquire) {
+                continue;
+            }
+            self.resumed.notified().await;
+        }
+    }
+
+    pub(crate) async fn exclusive(&self) -> CheckpointGateGuard<'_> {
+        self.pending.store(true, Ordering::Release);
+        let pending = PendingCheckpoint { gate: self };
+        CheckpointGateGuard {
+            _write: self.lock.write().await,
+            _pending: pending,
+        }
+    }
+}
+
+impl Drop for PendingCheckpoint<'_> {
+    fn drop(&mut self) {
+        self.gate.pending.store(false, Ordering::Release);
+        self.gate.resumed.notify_waiters();
+    }
+}
+
+impl WorkerXContract {
+    /// Takes a fuzzy checkpoint of this worker:
+    ///
+    /// 1. under the checkpoint gate, the worker log is sealed at its last
+    ///    allocated LSN and the worker KV rows are recorded; commits resume
+    ///    as soon as the gate is released;
+    /// 2. every hosted relation checkpoints its PL WAL, writing back the
+    ///    pages the sealed entries changed;
+    /// 3. the KV rows are written as the worker log checkpoint and the
+    ///    sealed chunks are deleted.
+    ///
+    /// Recovery then restores the KV rows and replays only the worker log
+    /// entries after the checkpoint.
+    pub(crate) async fn checkpoint_async(&self) -> RS<WalCheckpointStats> {
+        let log = self.log_cloned()?;
+        let sealed = match log.as_ref() {
+            Some(log) => {
+                let _gate = self.checkpoint_gate.exclusive().await;
+                match log.begin_checkpoint_async().await? {
+                    Some(seal) => Some((seal, self.encode_worker_log_checkpoint()?)),
+                    None => None,
+                }
+            }
+            None => None,
+        };
+        let mut stats = self.storage.checkpoint_relations_async().await?;
+        if let Some(log) = log.as_ref() {
+            stats += match sealed {
+                Some((seal, payload)) => log.finish_checkpoint_async(seal, payload).await?,
+                None => WalCheckpointStats {
+                    wal_bytes: log.wal_size_bytes_async().await?,
+                    ..Default::default()
+                },
+            };
+        }
+        *self.last_checkpoint.lock()? = instant_now();
+        Ok(stats)
+    }
+
+    /// Takes a checkpoint once [`WAL_CHECKPOINT_INTERVAL`] has passed since
+    /// the previous one. Called from the periodic dirty-page flush round.
+    pub(crate) async fn checkpoint_if_due(&self) -> RS<()> {
+        let last = *self.last_checkpoint.lock()?;
+        if last.elapsed() < WAL_CHECKPOINT_INTERVAL {
+            return Ok(());
+        }
+        let stats = self.checkpoint_async().await?;
+        crate::server::loop_stats::record_wal_checkpoint(stats.wal_bytes);
+        debug!(
+            worker_id = self.worker_id,
+            reclaimed_chunks = stats.reclaimed_chunks,
+            reclaimed_bytes = stats.reclaimed_bytes,
+            wal_bytes = stats.wal_bytes,
+            "worker checkpoint done"
+        );
+        Ok(())
+    }
+
+    /// Restores the worker KV rows and two-phase commit state recorded by a
+    /// worker log checkpoint, before the entries after it are replayed.
+    pub fn restore_worker_log_checkpoint(&self, checkpoint: &WalCheckpoint) -> RS<()> {
+        let state = WorkerLogCheckpoint::decode(checkpoint)?;
+        for (key, value, xid) in state.kv_rows {
+            self.storage.worker_put_local(key, value, xid)?;
+        }
+        self.restore_two_phase_records(state.prepared_txs, state.commit_decisions)?;
+        self.snapshot_mgr.observe_committed_ts(state.max_xid);
+        Ok(())
+    }
+
+    fn encode_worker_log_checkpoint(&self) -> RS<Vec<u8>> {
+        WorkerLogCheckpoint {
+            max_xid: self.snapshot_mgr.latest_xid(),
+            kv_rows: self.storage.kv_checkpoint_rows(),
+            prepared_txs: self.two_phase.prepared_records()?,
+            commit_decisions: self.two_phase.decision_records()?,
+        }
+        .encode()
+    }
+}
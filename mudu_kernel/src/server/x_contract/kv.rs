@@ -27,31 +27,87 @@ impl WorkerXContract {
         // Drop every statement-level lock this transaction took locally (a
         // no-op for transactions that never locked).
         self.tx_lock.release_all(tx_mgr.xid() as OID)?;
+        if tx_mgr.isolation_level() == IsolationLevel::Serializable {
+            self.ssi.end_serializable()?;
+        }
         self.snapshot_mgr.end_tx(tx_mgr.xid())
     }
 
+    /// Switch `tx` to isolation `level`, registering serializable
+    /// transactions with the SSI tracker. Reads made before the switch are
+    /// not tracked, so it is rejected once the transaction ran a statement.
+    /// A serializable transaction is confined to the partitions of this
+    /// worker; see `serializable_remote_access_error`.
+    pub fn worker_set_tx_isolation_level(&self, tx: &dyn TxMgr, level: IsolationLevel) -> RS<()> {
+        if tx.has_run_statement() || !tx.is_empty() {
+            return Err(mudu_error!(
+                ErrorCode::Transaction,
+                "the isolation level must be set before the first statement of the transaction"
+            ));
+        }
+        if tx.isolation_level() == level {
+            return Ok(());
+        }
+        tx.set_isolation_level(level)?;
+        match level {
+            IsolationLevel::Serializable => self.ssi.begin_serializable(),
+            IsolationLevel::Snapshot => self.ssi.end_serializable(),
+        }
+    }
+
+    /// Run the SSI commit check for `tx` committing `write_ops` (see
+    /// `server::worker_ssi`).
+    pub(crate) fn ssi_commit_check(
+        &self,
+        tx: &dyn TxMgr,
+        write_ops: &[(PhysicalRelationId, Vec<u8>)],
+    ) -> RS<()> {
+        self.ssi
+            .commit_check(tx, write_ops, self.snapshot_mgr.gc_low_water()?)
+    }
+
+    /// Record `writes` applied here by a cross-worker transaction with the
+    /// SSI tracker, under a local xid allocated after the apply.
+    pub(crate) fn ssi_record_applied_writes(
+        &self,
+        writes: &[(PhysicalRelationId, Vec<u8>)],
+    ) -> RS<()> {
+        let snapshot = self.snapshot_mgr.begin_tx()?;
+        let xid = snapshot.xid();
+        self.snapshot_mgr.end_tx(xid)?;
+        self.ssi
+            .record_applied_writes(xid, writes, self.snapshot_mgr.gc_low_water()?)
+    }
+
     /// Roll back `tx`, additionally releasing any statement-level locks it
     /// holds on remote owner workers (best-effort; orphan reclamation on the
     /// owner is the backstop).
     pub async fn worker_abort_tx_async(&self, tx: Arc<dyn TxMgr>) -> RS<()> {
-        let owners = tx.remote_lock_owners();
-        if !owners.is_empty() {
-            let token = statement_lock_token(self.worker_id, tx.xid());
-            for owner in owners {
-                if let Err(err) = self.remote_unlock_keys(owner, token).await {
-                    debug!(
-                        worker_id = self.worker_id,
-                        owner, "remote unlock keys on abort failed: {err}"
-                    );
-                }
+        self.release_remote_locks(tx.xid(), tx.remote_lock_owners())
+            .await;
+        self.worker_rollback_tx(tx)
+    }
+
+    /// Release the statement-level locks transaction `xid` holds on the
+    /// remote workers `owners` (best-effort; orphan reclamation on the owner
+    /// is the backstop).
+    pub(crate) async fn release_remote_locks(&self, xid: u64, owners: Vec<OID>) {
+        let token = statement_lock_token(self.worker_id, xid);
+        for owner in owners {
+            if let Err(err) = self.remote_unlock_keys(owner, token).await {
+                debug!(
+                    worker_id = self.worker_id,
+                    owner, "remote unlock keys failed: {err}"
+                );
             }
         }
-        self.worker_rollback_tx(tx)
     }
 
     pub async fn worker_put_async(&self, key: Vec<u8>, value: Vec<u8>) -> RS<()> {
         let trace = task_trace!();
         trace.watch("put.stage", "contract_worker_put_start");
+        self.ensure_writable(None)?;
+        let _gate = self.checkpoint_gate.enter().await;
         let (storage, log, prepared) = {
             let xid = self.snapshot_mgr.alloc_committed_ts();
             trace.watch("put.xid", &xid.to_string());
@@ -76,7 +132,9 @@ impl WorkerXContract {
     }
 
     pub async fn worker_delete_async(&self, key: &[u8]) -> RS<()> {
+        self.ensure_writable(None)?;
         let key = key.to_vec();
+        let _gate = self.checkpoint_gate.enter().await;
         let (storage, log, prepared) = {
             let xid = self.snapshot_mgr.alloc_committed_ts();
             (
@@ -112,8 +170,9 @@ impl WorkerXContract {
         &self,
         start_key: &[u8],
         end_key: &[u8],
+        limit: Option<usize>,
     ) -> RS<Vec<KvItem>> {
-        self.storage.kv_range(start_key, end_key, None).await
+        self.storage.kv_range(start_key, end_key, None, limit).await
     }
 
     pub async fn worker_range_scan_with_snapshot_async(
@@ -121,9 +180,10 @@ impl WorkerXContract {
         snapshot: &WorkerSnapshot,
         start_key: &[u8],
         end_key: &[u8],
+        limit: Option<usize>,
     ) -> RS<Vec<KvItem>> {
         self.storage
-            .kv_range(start_key, end_key, Some(snapshot))
+            .kv_range(start_key, end_key, Some(snapshot), limit)
             .await
     }
 
@@ -141,6 +201,10 @@ impl WorkerXContract {
         if items.is_empty() {
             return self.snapshot_mgr.end_tx(xid);
         }
+        if let Err(err) = self.ensure_writable(Some(xid)) {
+            self.snapshot_mgr.end_tx(xid)?;
+            return Err(err);
+        }
         let (storage, log, prepared) = {
             let prepared = self
                 .storage
@@ -148,13 +212,16 @@ impl WorkerXContract {
                 .await?;
             (self.storage.clone(), self.log_cloned()?, prepared)
         };
-        if let Some(log) = log {
-            new_xl_batch_writer(log.clone())
-                .append(prepared.batch())
-                .await?;
-            log.flush_async().await?;
+        {
+            let _gate = self.checkpoint_gate.enter().await;
+            if let Some(log) = log {
+                new_xl_batch_writer(log.clone())
+                    .append(prepared.batch())
+                    .await?;
+                log.flush_async().await?;
+            }
+            storage.apply_prepared_commit_async(prepared).await?;
         }
-        storage.apply_prepared_commit_async(prepared).await?;
         self.snapshot_mgr.end_tx(xid)
     }
 
@@ -175,6 +242,13 @@ impl WorkerXContract {
         tx: Arc<dyn TxMgr>,
         lock_owner: OID,
     ) -> RS<()> {
+        let started = mudu_sys::time::instant_now();
+        let result = self.commit_tx_with_lock_owner(tx, lock_owner).await;
+        crate::server::metrics::record_commit(started.elapsed(), result.is_ok());
+        result
+    }
+
+    async fn commit_tx_with_lock_owner(&self, tx: Arc<dyn TxMgr>, lock_owner: OID) -> RS<()> {
         let _t = task_trace!();
         let _stage_total = crate::server::stage_stats::StageGuard::new(
             crate::server::stage_stats::Stage::CommitTotal,
@@ -188,12 +262,21 @@ impl WorkerXContract {
         _t.watch("procedure.worker_commit.stage", "is_empty_check");
         if tx.is_empty() {
             _t.watch("procedure.worker_commit.stage", "rollback_empty_tx");
+            // A read-only serializable transaction can still close a
+            // dangerous structure among committed ones.
+            let checked = self.ssi_commit_check(tx.as_ref(), &[]);
             self.tx_lock.release_all(lock_owner)?;
-            return self.worker_rollback_tx(tx);
+            self.worker_rollback_tx(tx)?;
+            return checked;
+        }
+        if let Err(err) = self.ensure_writable(Some(xid)) {
+            self.tx_lock.release_all(lock_owner)?;
+            self.worker_rollback_tx(tx)?;
+            return Err(err);
         }
         _t.watch("procedure.worker_commit.stage", "build_write_ops");
         tx.build_write_ops();
-        let (storage, log, prepared) = {
+        let (storage, log, prepared, commit, route) = {
             let write_ops = tx.write_ops();
             _t.watch("procedure.worker_commit.stage", "tx_lock_try_lock");
             {
@@ -203,6 +286,9 @@ impl WorkerXContract {
                 acquire_commit_locks(&self.tx_lock, lock_owner, &write_ops).await?;
             }
             _t.watch("procedure.worker_commit.stage", "prepare_commit_start");
+            // Held until the rows are applied, so the layout they are
+            // stamped with stays current.
+            let commit = self.storage.enter_commit().await;
             let prepared = {
                 let _stage = crate::server::stage_stats::StageGuard::new(
                     crate::server::stage_stats::Stage::CommitPrepare,
@@ -210,7 +296,25 @@ impl WorkerXContract {
                 self.storage.prepare_commit_async(tx.as_ref()).await?
             };
             _t.watch("procedure.worker_commit.stage", "prepare_commit_done");
-            (self.storage.clone(), self.log_cloned()?, prepared)
+            if let Err(err) = self.ssi_commit_check(tx.as_ref(), &write_ops) {
+                self.tx_lock.release_all(lock_owner)?;
+                self.worker_rollback_tx(tx)?;
+                return Err(err);
+            }
+            // Held until the rows are applied; see `check_fenced_routes`.
+            let route = self.storage.route_fence().enter().await;
+            if let Err(err) = self.check_fenced_routes(&prepared.relation_puts()).await {
+                self.tx_lock.release_all(lock_owner)?;
+                self.worker_rollback_tx(tx)?;
+                return Err(err);
+            }
+            (
+                self.storage.clone(),
+                self.log_cloned()?,
+                prepared,
+                commit,
+                route,
+            )
         };
         trace!("log flush {}", xid);
         // Critical section order: commit locks -> prepare -> enqueue (LSN
@@ -219,8 +323,10 @@ impl WorkerXContract {
         // AFTER the locks are released, so the batch/fsync latency is not
         // serialized behind per-key commit locks. LSNs are still allocated
         // inside the critical section, which keeps WAL order equal to apply
-        // order.
+        // order, and under the checkpoint gate, so a checkpoint never seals
+        // the log between a commit's enqueue and its apply.
         let result: RS<Option<crate::wal::lsn::LSN>> = async {
+            let _gate = self.checkpoint_gate.enter().await;
             let mut last_lsn = None;
             if let Some(log) = log.as_ref() {
                 _t.watch("procedure.worker_execute.stage", "wal_enqueue_start");
@@ -270,6 +376,8 @@ impl WorkerXContract {
             Ok(last_lsn)
         }
         .await;
+        drop(route);
+        drop(commit);
         trace!("log flush done {}", xid);
         _t.watch("procedure.worker_commit.stage", "tx_lock_release");
         self.tx_lock.release_all(lock_owner)?;
@@ -307,15 +415,41 @@ impl WorkerXContract {
         if let Some(max_xid) = max_xid {
             self.snapshot_mgr.observe_committed_ts(max_xid);
         }
-        self.storage.replay_batch(batch).await
+        let horizon = batch
+            .entries
+            .iter()
+            .flat_map(|entry| entry.ops.iter())
+            .filter_map(|op| match op {
+                TxOp::SnapshotHorizon { xid } => Some(*xid),
+                _ => None,
+            })
+            .max();
+        let batch = self.replay_two_phase_entries(batch)?;
+        self.storage.replay_batch(batch).await?;
+        // Only once the writes logged before it are applied can a standby
+        // read at the horizon.
+        if let Some(horizon) = horizon {
+            self.snapshot_mgr.observe_snapshot_horizon(horizon);
+        }
+        Ok(())
     }
 
     pub fn finish_worker_log_recovery(&self) -> RS<()> {
         Ok(())
     }
 
+    /// Settles the cross-partition transactions recovery left in doubt:
+    /// redelivers logged commit decisions and asks the coordinators of
+    /// prepared transactions for their outcome.
     pub async fn recover_pending_cross_partition_records_async(&self) -> RS<()> {
-        Ok(())
+        self.recover_two_phase_txs_async().await
+    }
+
+    /// Periodic two-phase resolution round: only transactions left
+    /// unresolved for a while are taken over from the live commit path.
+    pub async fn resolve_pending_cross_partition_records_async(&self) -> RS<()> {
+        self.resolve_two_phase_txs_async(two_phase::TWO_PHASE_RESOLVE_DELAY)
+            .await
     }
 
     pub fn ensure_partition_rpc_handler(self: &Arc<Self>) -> RS<()> {
@@ -116,7 +116,12 @@ impl WorkerXContract {
             active_sessions,
             async_runtime,
             snapshot_mgr: WorkerSnapshotMgr::default(),
+            ssi: SsiTracker::new(),
             tx_lock: XLockMgr::new(),
+            checkpoint_gate: checkpoint::CheckpointGate::new(),
+            last_checkpoint: SMutex::new(mudu_sys::time::instant_now()),
+            two_phase: two_phase::TwoPhaseState::new(),
+            replication: replication::ReplicationState::new(),
         })
     }
 
@@ -247,6 +252,12 @@ impl WorkerXContract {
         self.snapshot_mgr.oldest_running_xid()
     }
 
+    /// Return the version GC low-water mark of this worker; see
+    /// `WorkerSnapshotMgr::gc_low_water`.
+    pub(crate) fn gc_low_water(&self) -> RS<u64> {
+        self.snapshot_mgr.gc_low_water()
+    }
+
     /// Return the newest xid allocated by the snapshot manager (begin or
     /// commit timestamp); `0` when nothing was allocated yet.
     pub(crate) fn latest_xid(&self) -> u64 {
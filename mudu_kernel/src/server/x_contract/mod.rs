@@ -32,6 +32,7 @@ pub(crate) use crate::server::partition_rpc::{
     PartitionRpcRequest, PartitionRpcResponse, RpcBound,
 };
 pub(crate) use crate::server::worker_snapshot::{KvItem, WorkerSnapshot, WorkerSnapshotMgr};
+pub(crate) use crate::server::worker_ssi::SsiTracker;
 pub(crate) use crate::server::worker_storage::WorkerStorage;
 pub(crate) use crate::server::worker_tx_manager::WorkerTxManager;
 pub(crate) use crate::server::x_lock_mgr::XLockMgr;
@@ -45,7 +46,7 @@ pub(crate) use crate::x_engine::api::{
     AlterTable, DeltaAssign, DeltaOp, Filter, OptDelete, OptInsert, OptRead, OptUpdate, Predicate,
     RSCursor, RangeData, TupleRow, VecDatum, VecSelTerm, XContract,
 };
-pub(crate) use crate::x_engine::tx_mgr::{PhysicalRelationId, TxMgr};
+pub(crate) use crate::x_engine::tx_mgr::{IsolationLevel, PhysicalRelationId, TxMgr};
 pub(crate) use mudu_sys::contract::async_io_provider::AsyncIoProvider;
 
 pub(crate) type DataBin = Buf;
@@ -69,7 +70,12 @@ pub struct WorkerXContract {
     /// provider but the backend performs steady-state I/O via this provider.
     async_runtime: Option<Arc<dyn AsyncIoProvider>>,
     snapshot_mgr: WorkerSnapshotMgr,
+    ssi: SsiTracker,
     tx_lock: XLockMgr,
+    checkpoint_gate: checkpoint::CheckpointGate,
+    last_checkpoint: SMutex<mudu_sys::time::Instant>,
+    two_phase: two_phase::TwoPhaseState,
+    replication: replication::ReplicationState,
     // commit_gate: AsyncMutex<()>,
 }
 
@@ -86,15 +92,24 @@ pub struct VecCursorInner {
 /// io_uring-only contract.
 pub type IoUringXContract = WorkerXContract;
 
+pub(crate) mod alter;
+pub(crate) mod backup;
+pub(crate) mod checkpoint;
 pub(crate) mod cursor;
 pub(crate) mod kv;
 pub(crate) mod lifecycle;
 pub(crate) mod ops;
 pub(crate) mod params;
+pub(crate) mod repartition;
+pub(crate) mod replication;
 pub(crate) mod rpc;
 #[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
 pub(crate) mod tests;
 pub(crate) mod trait_impl;
+pub(crate) mod two_phase;
+#[cfg(all(test, feature = "ds"))]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod two_phase_sim_tests;
 pub(crate) mod utils;
 
 pub use params::{WorkerXContractParams, WorkerXContractWorkerLogParams};
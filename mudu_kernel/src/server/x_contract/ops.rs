@@ -1,3 +1,4 @@
+use super::repartition::{route_key_datum, ReadRoute};
 use super::utils::*;
 use super::*;
 
@@ -21,7 +22,7 @@ impl WorkerXContract {
     /// `STATEMENT_LOCK_TIMEOUT`) and record it on the transaction for
     /// commit/rollback release. Re-entrant for keys the transaction already
     /// holds.
-    async fn acquire_statement_lock(
+    pub(super) async fn acquire_statement_lock(
         &self,
         tx_mgr: &dyn TxMgr,
         relation_id: PhysicalRelationId,
@@ -54,7 +55,7 @@ impl WorkerXContract {
     /// `LockKeyForUpdate` (lock + read in one round trip) and record the
     /// owner for rollback release. Returns the currently committed value,
     /// projected like `remote_read_key`.
-    async fn lock_remote_key_for_update(
+    pub(super) async fn lock_remote_key_for_update(
         &self,
         tx_mgr: &dyn TxMgr,
         worker_id: OID,
@@ -71,6 +72,38 @@ impl WorkerXContract {
         Ok(value)
     }
 
+    /// Lock the constraint value `value` of `oid`, a unique index or a table
+    /// whose key a foreign key references, until `tx_mgr` ends. The lock is
+    /// taken on the worker owning the default partition, so transactions
+    /// coordinated by any worker contend for it.
+    pub(super) async fn lock_constraint_value_async(
+        &self,
+        tx_mgr: &dyn TxMgr,
+        oid: OID,
+        value: Vec<u8>,
+    ) -> RS<()> {
+        let relation_id = PhysicalRelationId {
+            table_id: oid,
+            partition_id: CONSTRAINT_LOCK_PARTITION_ID,
+        };
+        match self
+            .resolve_partition_worker(DEFAULT_UNPARTITIONED_TABLE_PARTITION_ID)
+            .await?
+        {
+            Some(worker_id) if worker_id != self.worker_id => {
+                let lock_token = statement_lock_token(self.worker_id, tx_mgr.xid());
+                self.remote_lock_key(worker_id, lock_token, relation_id, value)
+                    .await?;
+                tx_mgr.record_remote_lock_owner(worker_id);
+                Ok(())
+            }
+            _ => {
+                self.acquire_statement_lock(tx_mgr, relation_id, value)
+                    .await
+            }
+        }
+    }
+
     pub(crate) fn _begin_tx(&self) -> RS<Arc<dyn TxMgr>> {
         Ok(Arc::new(WorkerTxManager::new(
             self.snapshot_mgr.begin_tx()?,
@@ -95,6 +128,7 @@ impl WorkerXContract {
         );
         let key = build_key_tuple(keys, &desc)?;
         let value = build_value_tuple(values, &desc)?;
+        tx_mgr.record_layout_version(table_id, desc.layout_version())?;
         let target_partition = self
             .partition_router
             .route_exact_partition(table_id, desc.as_ref(), keys)
@@ -256,6 +290,7 @@ impl WorkerXContract {
                                 .map(|value| project_selected_fields(&desc, &key, &value, select))
                                 .transpose()?,
                             None => {
+                                ensure_local_serializable_read(tx_mgr.as_ref())?;
                                 self.remote_read_key(
                                     worker_id,
                                     table_id,
@@ -339,8 +374,49 @@ impl WorkerXContract {
         select: &VecSelTerm,
     ) -> RS<Arc<dyn RSCursor>> {
         ensure_supported_predicate(pred_non_key)?;
-        let start = build_bound_key(pred_key.start(), &desc)?;
-        let end = build_bound_key(pred_key.end(), &desc)?;
+        // A read racing a partition rule switch is retried; see `read_route`.
+        let projected = loop {
+            let route = self.read_route(table_id).await?;
+            let rows = self
+                .read_range_rows(
+                    &desc,
+                    &tx_mgr,
+                    table_id,
+                    pred_key,
+                    pred_non_key,
+                    select,
+                    route.as_ref(),
+                )
+                .await?;
+            match route {
+                Some(route) if self.read_route_changed(&route).await? => continue,
+                _ => break rows,
+            }
+        };
+        Ok(Arc::new(VecCursor {
+            inner: SMutex::new(VecCursorInner {
+                rows: projected,
+                index: 0,
+            }),
+        }))
+    }
+
+    // The rows of a range read, with the rows a fenced `route` does not
+    // route to the partition they were read from left out.
+    #[allow(clippy::too_many_arguments)]
+    async fn read_range_rows(
+        &self,
+        desc: &Arc<TableDesc>,
+        tx_mgr: &Arc<dyn TxMgr>,
+        table_id: OID,
+        pred_key: &RangeData,
+        pred_non_key: &Predicate,
+        select: &VecSelTerm,
+        route: Option<&ReadRoute>,
+    ) -> RS<Vec<TupleRow>> {
+        let route = route.filter(|route| route.fenced);
+        let start = build_bound_key(pred_key.start(), desc)?;
+        let end = build_bound_key(pred_key.end(), desc)?;
         let target_partitions = match pred_non_key {
             Predicate::KeyPrefixEq(prefix) => {
                 match self
@@ -377,6 +453,7 @@ impl WorkerXContract {
                 for partition_id in partitions {
                     match self.resolve_partition_worker(partition_id).await? {
                         Some(worker_id) if worker_id != self.worker_id => {
+                            ensure_local_serializable_read(tx_mgr.as_ref())?;
                             let key_prefix = match pred_non_key {
                                 Predicate::KeyPrefixEq(prefix) => Some(prefix.as_slice()),
                                 _ => None,
@@ -391,14 +468,24 @@ impl WorkerXContract {
                                 &start,
                                 &end,
                             );
-                            let rows = if overlay.is_empty() && key_prefix.is_none() {
+                            let (mut remote_select, index_filter) =
+                                remote_index_filter(select, pred_non_key);
+                            // A fenced read also fetches the partition key.
+                            let route_at = route.map(|route| {
+                                let at = remote_select.vec().len();
+                                let mut attrs = remote_select.vec().clone();
+                                attrs.extend_from_slice(&route.binding.ref_attr_indices);
+                                remote_select = VecSelTerm::new(attrs);
+                                at
+                            });
+                            let mut rows = if overlay.is_empty() && key_prefix.is_none() {
                                 self.remote_read_range(
                                     worker_id,
                                     table_id,
                                     partition_id,
-                                    rpc_bound_from_key_bound(pred_key.start(), &desc)?,
-                                    rpc_bound_from_key_bound(pred_key.end(), &desc)?,
-                                    select.vec().to_vec(),
+                                    rpc_bound_from_key_bound(pred_key.start(), desc)?,
+                                    rpc_bound_from_key_bound(pred_key.end(), desc)?,
+                                    remote_select.vec().to_vec(),
                                 )
                                 .await?
                             } else {
@@ -406,34 +493,87 @@ impl WorkerXContract {
                                     worker_id,
                                     table_id,
                                     partition_id,
-                                    desc: &desc,
+                                    desc,
                                     pred_key,
-                                    select,
+                                    select: &remote_select,
                                     overlay: &overlay,
                                     key_prefix,
                                 })
                                 .await?
                             };
+                            if let (Some(route), Some(at)) = (route, route_at) {
+                                let mut routed = Vec::with_capacity(rows.len());
+                                for row in rows {
+                                    let route_key = VecDatum::new(
+                                        route
+                                            .binding
+                                            .ref_attr_indices
+                                            .iter()
+                                            .zip(&row[at..])
+                                            .filter_map(|(attr, value)| {
+                                                value.clone().map(|value| (*attr, value))
+                                            })
+                                            .collect(),
+                                    );
+                                    if self.read_routes_row(
+                                        route,
+                                        desc,
+                                        partition_id,
+                                        &route_key,
+                                    )? {
+                                        routed.push(row);
+                                    }
+                                }
+                                rows = routed;
+                            }
+                            if let Some(index_filter) = &index_filter {
+                                rows.retain(|row| {
+                                    index_filter.iter().all(|(position, expected)| {
+                                        row.get(*position)
+                                            .is_some_and(|actual| actual.as_ref() == Some(expected))
+                                    })
+                                });
+                            }
+                            if index_filter.is_some() || route_at.is_some() {
+                                for row in rows.iter_mut() {
+                                    row.truncate(select.vec().len());
+                                }
+                            }
                             for row in rows {
                                 projected.push(TupleRow::new_nullable(row));
                             }
                         }
                         _ => {
                             let rows = self
-                                .storage
-                                .range_on_partition(
+                                .scan_local(
                                     table_id,
                                     Some(partition_id),
-                                    (bound_key_as_ref(&start), bound_key_as_ref(&end)),
+                                    (&start, &end),
+                                    pred_non_key,
                                     tx_mgr.as_ref(),
                                 )
                                 .await?;
                             for (key, value) in rows {
-                                if !matches_predicate(&desc, &key, &value, pred_non_key)? {
+                                if !matches_predicate(desc, &key, &value, pred_non_key)? {
                                     continue;
                                 }
+                                if let Some(route) = route {
+                                    let route_key = route_key_datum(
+                                        desc,
+                                        &route.binding.ref_attr_indices,
+                                        &key,
+                                    )?;
+                                    if !self.read_routes_row(
+                                        route,
+                                        desc,
+                                        partition_id,
+                                        &route_key,
+                                    )? {
+                                        continue;
+                                    }
+                                }
                                 projected.push(TupleRow::new_nullable(project_selected_fields(
-                                    &desc, &key, &value, select,
+                                    desc, &key, &value, select,
                                 )?));
                             }
                         }
@@ -442,29 +582,55 @@ impl WorkerXContract {
             }
             None => {
                 let rows = self
-                    .storage
-                    .range(
+                    .scan_local(
                         table_id,
-                        (bound_key_as_ref(&start), bound_key_as_ref(&end)),
+                        None,
+                        (&start, &end),
+                        pred_non_key,
                         tx_mgr.as_ref(),
                     )
                     .await?;
                 for (key, value) in rows {
-                    if !matches_predicate(&desc, &key, &value, pred_non_key)? {
+                    if !matches_predicate(desc, &key, &value, pred_non_key)? {
                         continue;
                     }
                     projected.push(TupleRow::new_nullable(project_selected_fields(
-                        &desc, &key, &value, select,
+                        desc, &key, &value, select,
                     )?));
                 }
             }
         }
-        Ok(Arc::new(VecCursor {
-            inner: SMutex::new(VecCursorInner {
-                rows: projected,
-                index: 0,
-            }),
-        }))
+        Ok(projected)
+    }
+
+    /// Reads the local rows of a range read: a `Predicate::IndexEq` is
+    /// answered from the secondary index, anything else scans the key range.
+    async fn scan_local(
+        &self,
+        table_id: OID,
+        partition_id: Option<OID>,
+        (start, end): (&Bound<Vec<u8>>, &Bound<Vec<u8>>),
+        pred_non_key: &Predicate,
+        tx_mgr: &dyn TxMgr,
+    ) -> RS<Vec<(Vec<u8>, Vec<u8>)>> {
+        match pred_non_key {
+            Predicate::IndexEq { index_oid, prefix } => {
+                let prefix: Vec<Vec<u8>> = prefix.iter().map(|(_, value)| value.clone()).collect();
+                self.storage
+                    .index_lookup_on_partition(table_id, partition_id, *index_oid, &prefix, tx_mgr)
+                    .await
+            }
+            _ => {
+                self.storage
+                    .range_on_partition(
+                        table_id,
+                        partition_id,
+                        (bound_key_as_ref(start), bound_key_as_ref(end)),
+                        tx_mgr,
+                    )
+                    .await
+            }
+        }
     }
 
     pub(crate) async fn _delete(
@@ -546,6 +712,7 @@ impl WorkerXContract {
     ) -> RS<usize> {
         ensure_supported_predicate(pred_non_key)?;
         let key = build_key_tuple(pred_key, &desc)?;
+        tx_mgr.record_layout_version(table_id, desc.layout_version())?;
         let target_partition = self
             .partition_router
             .route_exact_partition(table_id, desc.as_ref(), pred_key)
@@ -789,6 +956,33 @@ impl WorkerXContract {
     }
 }
 
+/// Remote partitions have no index access over RPC, so a
+/// `Predicate::IndexEq` read fetches the indexed columns along with `select`.
+/// Returns the projection to request and, for an index read, the
+/// (position, expected value) pairs each fetched row must match before the
+/// extra columns are truncated away.
+fn remote_index_filter(
+    select: &VecSelTerm,
+    predicate: &Predicate,
+) -> (VecSelTerm, Option<Vec<(usize, DataBin)>>) {
+    let Predicate::IndexEq { prefix, .. } = predicate else {
+        return (select.clone(), None);
+    };
+    let mut attrs = select.vec().clone();
+    let mut filter = Vec::with_capacity(prefix.len());
+    for (attr, expected) in prefix {
+        let position = match attrs.iter().position(|candidate| candidate == attr) {
+            Some(position) => position,
+            None => {
+                attrs.push(*attr);
+                attrs.len() - 1
+            }
+        };
+        filter.push((position, expected.clone()));
+    }
+    (VecSelTerm::new(attrs), Some(filter))
+}
+
 /// Equality test for a `Predicate::KeyPrefixEq` prefix against a row's
 /// decoded key datums (the same byte encoding `matches_predicate` compares
 /// on raw keys).
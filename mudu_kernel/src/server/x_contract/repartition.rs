@@ -0,0 +1,544 @@
+use super::utils::*;
+use super::*;
+use crate::contract::partition_rule::PartitionRuleDesc;
+use crate::contract::partition_rule_binding::{PartitionPlacement, TablePartitionBinding};
+
+// Rows moving to another partition, keyed by `(table id, key)`, with their
+// source partition, target partition and value.
+pub(super) type MovedRows = BTreeMap<(OID, Vec<u8>), (OID, OID, Vec<u8>)>;
+
+/// How a range read routes a partitioned table: the partition rule read
+/// from the catalog before the read, and whether a switch of the rule was
+/// fenced then. See `WorkerXContract::read_route`.
+pub(super) struct ReadRoute {
+    pub(super) rule: PartitionRuleDesc,
+    pub(super) binding: TablePartitionBinding,
+    pub(super) fenced: bool,
+}
+
+impl WorkerXContract {
+    /// Switches partition rule `rule.oid` to `rule` while traffic keeps
+    /// flowing:
+    ///
+    /// 1. every partition whose owner changes is handed off (its relations
+    ///    are flushed and closed on all workers), the placements are
+    ///    recorded, and the partition is handed off again so writes that
+    ///    reached the old owner in between are flushed as well; the new owner
+    ///    reopens the relations and replays their relation WAL;
+    /// 2. the rule is fenced (see [`RouteFence`]): from now until the end of
+    ///    4, commits inserting or updating a row on a partition it no longer
+    ///    routes to under `rule` fail, and the commits that passed the check
+    ///    before are drained;
+    /// 3. copy phase: rows of every partition whose range changes that route
+    ///    elsewhere under `rule` are copied to their new partition in one
+    ///    transaction, so they reach the owner's relation WAL; then the rule
+    ///    is replaced in the catalog (version bump), which switches the
+    ///    partition router of every worker at once;
+    /// 4. catch-up phase: the same partitions are scanned again and every
+    ///    moved row is deleted from its old partition. Its new partition
+    ///    takes the old partition's row only while it still holds what the
+    ///    copy phase left there, so a write made there after the switch is
+    ///    kept. Both sides are locked and read before they are written, and
+    ///    a round that loses a lock race is retried.
+    ///
+    /// From 3 to 4 a moved row is on both partitions. Range reads keep it
+    /// only from the partition the rule they routed with assigns it to, and
+    /// are retried when the rule changes under them; see
+    /// [`Self::read_route`].
+    ///
+    /// [`RouteFence`]: crate::server::route_fence::RouteFence
+    pub(crate) async fn _alter_partition_rule(
+        &self,
+        rule: &PartitionRuleDesc,
+        placements: &[PartitionPlacement],
+    ) -> RS<()> {
+        let current = self.meta_mgr.get_partition_rule_by_id(rule.oid).await?;
+        if rule.version <= current.version {
+            return Err(mudu_error!(
+                ErrorCode::InvalidState,
+                format!(
+                    "partition rule {} is already at version {}",
+                    rule.name, current.version
+                )
+            ));
+        }
+        let mut moved = Vec::new();
+        for placement in placements {
+            let existing = current
+                .partitions
+                .iter()
+                .any(|partition| partition.partition_id == placement.partition_id);
+            let owner = self
+                .resolve_partition_worker(placement.partition_id)
+                .await?;
+            if existing && owner != Some(placement.worker_id) {
+                moved.push(placement.partition_id);
+            }
+        }
+        let narrowed = current
+            .partitions
+            .iter()
+            .filter(|partition| !rule.partitions.contains(partition))
+            .map(|partition| partition.partition_id)
+            .collect::<Vec<_>>();
+        let tables = self.tables_bound_to_rule(rule.oid).await?;
+
+        for partition_id in &moved {
+            self.storage.handoff_partition_async(*partition_id).await?;
+        }
+        if !placements.is_empty() {
+            self.meta_mgr
+                .upsert_partition_placements(placements)
+                .await?;
+        }
+        for partition_id in &moved {
+            self.storage.handoff_partition_async(*partition_id).await?;
+        }
+        self.storage.raise_route_fence(rule)?;
+        let switched = self.switch_partition_rule(&tables, rule, &narrowed).await;
+        let lowered = self.storage.lower_route_fence(rule.oid);
+        switched?;
+        lowered
+    }
+
+    // Steps 2 to 4 of `_alter_partition_rule`, with the rule fenced.
+    async fn switch_partition_rule(
+        &self,
+        tables: &[(Arc<TableDesc>, TablePartitionBinding)],
+        rule: &PartitionRuleDesc,
+        sources: &[OID],
+    ) -> RS<()> {
+        self.storage.drain_route_fence().await?;
+        let copied = self.copy_moved_rows(tables, rule, sources).await?;
+        self.meta_mgr.update_partition_rule(rule).await?;
+        self.catch_up_moved_rows(tables, rule, sources, copied)
+            .await
+    }
+
+    /// The route of a range read of `table_id`, or `None` when the table is
+    /// not partitioned.
+    ///
+    /// While a switch of the rule is fenced, a moved row may be on its old
+    /// and on its new partition, so a fenced read keeps a row only from the
+    /// partition its route assigns it to (see [`Self::read_routes_row`]). A
+    /// read is retried when the rule version changed or the rule got fenced
+    /// while it ran (see [`Self::read_route_changed`]), so it never mixes
+    /// the rows of two routes.
+    pub(super) async fn read_route(&self, table_id: OID) -> RS<Option<ReadRoute>> {
+        let Some(binding) = self.meta_mgr.get_table_partition_binding(table_id).await? else {
+            return Ok(None);
+        };
+        let fenced = self
+            .storage
+            .route_fence()
+            .fenced_rules()?
+            .contains_key(&binding.rule_id);
+        let rule = self
+            .meta_mgr
+            .get_partition_rule_by_id(binding.rule_id)
+            .await?;
+        Ok(Some(ReadRoute {
+            rule,
+            binding,
+            fenced,
+        }))
+    }
+
+    /// Whether a read routed by `route` has to be retried.
+    pub(super) async fn read_route_changed(&self, route: &ReadRoute) -> RS<bool> {
+        let fenced = self
+            .storage
+            .route_fence()
+            .fenced_rules()?
+            .contains_key(&route.rule.oid);
+        if fenced && !route.fenced {
+            return Ok(true);
+        }
+        let rule = self
+            .meta_mgr
+            .get_partition_rule_by_id(route.rule.oid)
+            .await?;
+        Ok(rule.version != route.rule.version)
+    }
+
+    /// Whether a read routed by `route` keeps a row read from
+    /// `partition_id`, given the row's partition-key columns `route_key`.
+    pub(super) fn read_routes_row(
+        &self,
+        route: &ReadRoute,
+        desc: &TableDesc,
+        partition_id: OID,
+        route_key: &VecDatum,
+    ) -> RS<bool> {
+        if !route.fenced {
+            return Ok(true);
+        }
+        let routed = self.partition_router.route_rule_key_partition(
+            &route.rule,
+            desc,
+            &route.binding.ref_attr_indices,
+            route_key,
+        )?;
+        Ok(routed == partition_id)
+    }
+
+    /// Fails a commit that inserts or updates one of `puts` on a partition
+    /// it no longer routes to under a fenced partition rule; see
+    /// [`RouteFence`](crate::server::route_fence::RouteFence). The caller
+    /// holds the fence from this check until the rows are applied.
+    pub(crate) async fn check_fenced_routes(&self, puts: &[(PhysicalRelationId, &[u8])]) -> RS<()> {
+        let rules = self.storage.route_fence().fenced_rules()?;
+        if rules.is_empty() {
+            return Ok(());
+        }
+        let mut tables = BTreeMap::new();
+        for (relation_id, key) in puts {
+            if !tables.contains_key(&relation_id.table_id) {
+                let fenced = match self
+                    .meta_mgr
+                    .get_table_partition_binding(relation_id.table_id)
+                    .await?
+                {
+                    Some(binding) if rules.contains_key(&binding.rule_id) => {
+                        let desc = self.meta_mgr.get_table_by_id(relation_id.table_id).await?;
+                        Some((desc, binding))
+                    }
+                    _ => None,
+                };
+                tables.insert(relation_id.table_id, fenced);
+            }
+            let Some(Some((desc, binding))) = tables.get(&relation_id.table_id) else {
+                continue;
+            };
+            let Some(rule) = rules.get(&binding.rule_id) else {
+                continue;
+            };
+            let route_key = route_key_datum(desc, &binding.ref_attr_indices, key)?;
+            let routed = self.partition_router.route_rule_key_partition(
+                rule,
+                desc,
+                &binding.ref_attr_indices,
+                &route_key,
+            )?;
+            if routed != relation_id.partition_id {
+                return Err(mudu_error!(
+                    ErrorCode::Transaction,
+                    format!(
+                        "a row of table {} written to partition {} moves to partition {} while partition rule {} is switched",
+                        desc.name(),
+                        relation_id.partition_id,
+                        routed,
+                        rule.name
+                    )
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Every committed row of one partition as raw `(key, value)` tuples,
+    /// read on the worker owning the partition.
+    pub(crate) async fn scan_partition(
+        &self,
+        table_id: OID,
+        partition_id: OID,
+    ) -> RS<Vec<(Vec<u8>, Vec<u8>)>> {
+        let owner = self
+            .resolve_partition_worker(partition_id)
+            .await?
+            .unwrap_or(self.worker_id);
+        if owner == self.worker_id {
+            self.scan_partition_local(table_id, partition_id).await
+        } else {
+            self.remote_scan_partition(owner, table_id, partition_id)
+                .await
+        }
+    }
+
+    pub(crate) async fn scan_partition_local(
+        &self,
+        table_id: OID,
+        partition_id: OID,
+    ) -> RS<Vec<(Vec<u8>, Vec<u8>)>> {
+        let tx_mgr = self.worker_begin_tx()?;
+        let rows = self
+            .storage
+            .range_on_partition(
+                table_id,
+                Some(partition_id),
+                (Bound::Unbounded, Bound::Unbounded),
+                tx_mgr.as_ref(),
+            )
+            .await?;
+        self.worker_rollback_tx(tx_mgr)?;
+        Ok(rows)
+    }
+
+    pub(super) async fn tables_bound_to_rule(
+        &self,
+        rule_id: OID,
+    ) -> RS<Vec<(Arc<TableDesc>, TablePartitionBinding)>> {
+        let mut tables = Vec::new();
+        for schema in self.meta_mgr.list_schemas().await? {
+            let Some(binding) = self
+                .meta_mgr
+                .get_table_partition_binding(schema.id())
+                .await?
+            else {
+                continue;
+            };
+            if binding.rule_id == rule_id {
+                let desc = self.meta_mgr.get_table_by_id(schema.id()).await?;
+                tables.push((desc, binding));
+            }
+        }
+        Ok(tables)
+    }
+
+    // Copy phase: stages every moved row into its new partition and commits
+    // the copies in one transaction. Returns the copied rows.
+    pub(super) async fn copy_moved_rows(
+        &self,
+        tables: &[(Arc<TableDesc>, TablePartitionBinding)],
+        rule: &PartitionRuleDesc,
+        sources: &[OID],
+    ) -> RS<MovedRows> {
+        let moved = self.moved_rows(tables, rule, sources).await?;
+        if moved.is_empty() {
+            return Ok(moved);
+        }
+        let tx_mgr = self._begin_tx()?;
+        for (desc, _) in tables {
+            tx_mgr.record_layout_version(desc.id(), desc.layout_version())?;
+        }
+        for ((table_id, key), (_, target, value)) in &moved {
+            tx_mgr.put_relation(
+                PhysicalRelationId {
+                    table_id: *table_id,
+                    partition_id: *target,
+                },
+                key.clone(),
+                value.clone(),
+            );
+        }
+        self.worker_commit_routed_tx_async(tx_mgr).await?;
+        Ok(moved)
+    }
+
+    // Catch-up phase: see step 4 of `_alter_partition_rule`. Every round
+    // runs in one transaction; a round failing on a lock or write conflict
+    // is retried.
+    pub(super) async fn catch_up_moved_rows(
+        &self,
+        tables: &[(Arc<TableDesc>, TablePartitionBinding)],
+        rule: &PartitionRuleDesc,
+        sources: &[OID],
+        copied: MovedRows,
+    ) -> RS<()> {
+        const MAX_CATCH_UP_ATTEMPTS: u32 = 3;
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            match self
+                .catch_up_moved_rows_once(tables, rule, sources, &copied)
+                .await
+            {
+                Err(err)
+                    if err.ec() == ErrorCode::Transaction && attempt < MAX_CATCH_UP_ATTEMPTS =>
+                {
+                    debug!(
+                        worker_id = self.worker_id,
+                        rule = rule.name.as_str(),
+                        attempt,
+                        "partition rule catch-up retrying: {err}"
+                    );
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn catch_up_moved_rows_once(
+        &self,
+        tables: &[(Arc<TableDesc>, TablePartitionBinding)],
+        rule: &PartitionRuleDesc,
+        sources: &[OID],
+        copied: &MovedRows,
+    ) -> RS<()> {
+        let mut moved = self.moved_rows(tables, rule, sources).await?;
+        for (row, (source, target, _)) in copied {
+            // Deleted from the old partition since the copy phase.
+            moved
+                .entry(row.clone())
+                .or_insert_with(|| (*source, *target, Vec::new()));
+        }
+        if moved.is_empty() {
+            return Ok(());
+        }
+        let descs = tables
+            .iter()
+            .map(|(desc, _)| (desc.id(), desc.clone()))
+            .collect::<BTreeMap<_, _>>();
+        let tx_mgr = self._begin_tx()?;
+        let staged: RS<()> = async {
+            for ((table_id, key), (source, target, value)) in moved {
+                let Some(desc) = descs.get(&table_id) else {
+                    continue;
+                };
+                let source_row = self
+                    .lock_moved_row(tx_mgr.as_ref(), desc, source, &key)
+                    .await?;
+                let target_row = self
+                    .lock_moved_row(tx_mgr.as_ref(), desc, target, &key)
+                    .await?;
+                let copied_row = copied
+                    .get(&(table_id, key.clone()))
+                    .map(|(_, _, value)| project_value(desc, &key, value))
+                    .transpose()?;
+                let scanned_row = source_row
+                    .as_ref()
+                    .map(|_| project_value(desc, &key, &value))
+                    .transpose()?;
+                if source_row != scanned_row {
+                    // Written after the scan; the next round picks it up.
+                    return Err(mudu_error!(
+                        ErrorCode::Transaction,
+                        format!("moved row of table {} changed during catch-up", desc.name())
+                    ));
+                }
+                if source_row.is_some() {
+                    tx_mgr.delete_relation(
+                        PhysicalRelationId {
+                            table_id,
+                            partition_id: source,
+                        },
+                        key.clone(),
+                    );
+                }
+                // The new partition was written after the switch.
+                if target_row != copied_row || target_row == source_row {
+                    continue;
+                }
+                let relation_id = PhysicalRelationId {
+                    table_id,
+                    partition_id: target,
+                };
+                if source_row.is_some() {
+                    tx_mgr.record_layout_version(table_id, desc.layout_version())?;
+                    tx_mgr.put_relation(relation_id, key, value);
+                } else {
+                    tx_mgr.delete_relation(relation_id, key);
+                }
+            }
+            Ok(())
+        }
+        .await;
+        match staged {
+            Ok(()) => self.worker_commit_routed_tx_async(tx_mgr).await,
+            Err(err) => {
+                self.worker_abort_tx_async(tx_mgr).await?;
+                Err(err)
+            }
+        }
+    }
+
+    // Locks `key` on `partition_id` for `tx_mgr` and reads the value
+    // committed there, projected by `project_value`.
+    async fn lock_moved_row(
+        &self,
+        tx_mgr: &dyn TxMgr,
+        desc: &TableDesc,
+        partition_id: OID,
+        key: &[u8],
+    ) -> RS<Option<Vec<Option<DataBin>>>> {
+        match self.resolve_partition_worker(partition_id).await? {
+            Some(worker_id) if worker_id != self.worker_id => {
+                self.lock_remote_key_for_update(
+                    tx_mgr,
+                    worker_id,
+                    desc.id(),
+                    partition_id,
+                    key.to_vec(),
+                    desc.value_indices().clone(),
+                )
+                .await
+            }
+            _ => {
+                let relation_id = PhysicalRelationId {
+                    table_id: desc.id(),
+                    partition_id,
+                };
+                self.acquire_statement_lock(tx_mgr, relation_id, key.to_vec())
+                    .await?;
+                let read_tx = self.worker_begin_tx()?;
+                let value = self
+                    .storage
+                    .get_on_partition(desc.id(), Some(partition_id), key, read_tx.as_ref())
+                    .await;
+                self.worker_rollback_tx(read_tx)?;
+                value?
+                    .map(|value| project_value(desc, key, &value))
+                    .transpose()
+            }
+        }
+    }
+
+    // The rows of `sources` that route to another partition under `rule`.
+    async fn moved_rows(
+        &self,
+        tables: &[(Arc<TableDesc>, TablePartitionBinding)],
+        rule: &PartitionRuleDesc,
+        sources: &[OID],
+    ) -> RS<MovedRows> {
+        let mut moved = BTreeMap::new();
+        for (desc, binding) in tables {
+            for source in sources {
+                for (key, value) in self.scan_partition(desc.id(), *source).await? {
+                    let route_key = route_key_datum(desc, &binding.ref_attr_indices, &key)?;
+                    let target = self.partition_router.route_rule_key_partition(
+                        rule,
+                        desc,
+                        &binding.ref_attr_indices,
+                        &route_key,
+                    )?;
+                    if target != *source {
+                        moved.insert((desc.id(), key), (*source, target, value));
+                    }
+                }
+            }
+        }
+        Ok(moved)
+    }
+}
+
+// The value columns of a raw row, as `LockKeyForUpdate` reads them.
+fn project_value(desc: &TableDesc, key: &[u8], value: &[u8]) -> RS<Vec<Option<DataBin>>> {
+    project_selected_fields(
+        desc,
+        key,
+        value,
+        &VecSelTerm::new(desc.value_indices().clone()),
+    )
+}
+
+// The partition-key columns of a raw key tuple, as routed by the partition
+// router.
+pub(super) fn route_key_datum(
+    desc: &TableDesc,
+    ref_attr_indices: &[AttrIndex],
+    key: &[u8],
+) -> RS<VecDatum> {
+    let mut data = Vec::with_capacity(ref_attr_indices.len());
+    for attr in ref_attr_indices {
+        let field = desc.get_attr(*attr);
+        if field.primary_index().is_none() {
+            return Err(mudu_error!(
+                ErrorCode::NotImplemented,
+                format!("table {} is partitioned by a non-key column", desc.name())
+            ));
+        }
+        let field_desc = desc.key_desc().get_field_desc(field.datum_index());
+        data.push((*attr, field_desc.get(key)?.to_vec()));
+    }
+    Ok(VecDatum::new(data))
+}
@@ -0,0 +1,164 @@
+use super::*;
+use crate::wal::lsn::LSN;
+use crate::wal::xl_batch::decode_xl_batches_with_pending;
+use std::sync::atomic::AtomicU64;
+
+/// Per-worker replication state. A primary remembers the last snapshot
+/// horizon it logged; a standby holds the frames of a log entry it has only
+/// received in part, since only whole entries are appended and replayed.
+pub(crate) struct ReplicationState {
+    logged_horizon: AtomicU64,
+    incomplete: SMutex<IncompleteEntry>,
+}
+
+#[derive(Default)]
+struct IncompleteEntry {
+    frames: Vec<Vec<u8>>,
+    decoder_pending: Vec<Vec<u8>>,
+    decoder_start_lsn: Option<LSN>,
+}
+
+impl ReplicationState {
+    pub(crate) fn new() -> Self {
+        Self {
+            logged_horizon: AtomicU64::new(0),
+            incomplete: SMutex::new(IncompleteEntry::default()),
+        }
+    }
+}
+
+impl WorkerXContract {
+    /// Logs a [`TxOp::SnapshotHorizon`] entry once the oldest running
+    /// transaction moved past the last logged horizon, so a standby
+    /// replaying this log knows which xids it can read at. Called from the
+    /// periodic dirty-page flush round; a standby logs nothing itself.
+    pub(crate) async fn log_snapshot_horizon_if_advanced(&self) -> RS<()> {
+        if self.snapshot_mgr.is_replica() {
+            return Ok(());
+        }
+        let Some(log) = self.log_cloned()? else {
+            return Ok(());
+        };
+        let _gate = self.checkpoint_gate.enter().await;
+        // Commits allocate their LSN before their transaction ends, and no
+        // await separates reading the horizon from allocating this entry's
+        // LSN: every transaction below the horizon is logged before it.
+        let horizon = match self.snapshot_mgr.oldest_running_xid()? {
+            Some(xid) => xid,
+            None => self.snapshot_mgr.latest_xid() + 1,
+        };
+        if horizon <= self.replication.logged_horizon.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let batch = XLBatch::new(vec![XLEntry {
+            xid: 0,
+            ops: vec![TxOp::SnapshotHorizon { xid: horizon }],
+        }]);
+        new_xl_batch_writer(log).append(&batch).await?;
+        self.replication
+            .logged_horizon
+            .fetch_max(horizon, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Applies log frames streamed from the primary on a standby: the
+    /// frames of every completed entry are appended to the local log and
+    /// replayed like recovery does; a trailing partial entry is held until
+    /// the rest of it arrives. Returns the commit time of the newest
+    /// replayed transaction, if any.
+    pub(crate) async fn apply_replicated_frames_async(
+        &self,
+        frames: Vec<Vec<u8>>,
+    ) -> RS<Option<u64>> {
+        let log = self.log_cloned()?.ok_or_else(|| {
+            mudu_error!(
+                ErrorCode::Internal,
+                "a standby worker needs a worker log to replicate into"
+            )
+        })?;
+        let (complete, batches) = {
+            let mut incomplete = self.replication.incomplete.lock()?;
+            let IncompleteEntry {
+                frames: held,
+                decoder_pending,
+                decoder_start_lsn,
+            } = &mut *incomplete;
+            let mut complete = Vec::new();
+            let mut batches = Vec::new();
+            for frame in frames {
+                let decoded = decode_xl_batches_with_pending(
+                    std::slice::from_ref(&frame),
+                    decoder_pending,
+                    decoder_start_lsn,
+                )?;
+                held.push(frame);
+                if !decoded.is_empty() {
+                    complete.append(held);
+                    batches.extend(decoded);
+                }
+            }
+            (complete, batches)
+        };
+        if complete.is_empty() {
+            return Ok(None);
+        }
+        let commit_time = batches
+            .iter()
+            .flat_map(|batch| batch.entries.iter().filter_map(XLEntry::commit_time))
+            .max();
+        let _gate = self.checkpoint_gate.enter().await;
+        log.append_replicated_frames_async(complete).await?;
+        for batch in batches {
+            self.replay_worker_log_batch(batch).await?;
+        }
+        Ok(commit_time)
+    }
+
+    /// Drops a partially received entry; its frames are sent again when
+    /// the standby reconnects.
+    pub(crate) fn reset_replication_stream(&self) -> RS<()> {
+        *self.replication.incomplete.lock()? = IncompleteEntry::default();
+        Ok(())
+    }
+
+    /// The LSN the next replicated frame must carry.
+    pub(crate) fn replication_next_lsn(&self) -> RS<Option<LSN>> {
+        Ok(self.log_cloned()?.map(|log| log.next_lsn()))
+    }
+
+    pub fn is_standby(&self) -> bool {
+        self.snapshot_mgr.is_replica()
+    }
+
+    /// Switches this worker to a read-only standby; see
+    /// [`WorkerSnapshotMgr::begin_replica`].
+    pub fn begin_standby(&self) {
+        self.snapshot_mgr.begin_replica()
+    }
+
+    /// Makes a standby writable. Transactions that began as standby
+    /// transactions stay read-only.
+    pub async fn promote_standby_async(&self) -> RS<()> {
+        {
+            let _gate = self.checkpoint_gate.enter().await;
+            self.snapshot_mgr.promote();
+        }
+        self.recover_two_phase_txs_async().await
+    }
+
+    /// Rejects a write on a standby, or by a transaction that began before
+    /// the standby was promoted.
+    pub(crate) fn ensure_writable(&self, xid: Option<u64>) -> RS<()> {
+        let read_only = match xid {
+            Some(xid) => self.snapshot_mgr.is_read_only_xid(xid),
+            None => self.snapshot_mgr.is_replica(),
+        };
+        if read_only {
+            return Err(mudu_error!(
+                ErrorCode::ReadOnlyTransaction,
+                "the server is a read-only standby"
+            ));
+        }
+        Ok(())
+    }
+}
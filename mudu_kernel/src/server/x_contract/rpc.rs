@@ -9,10 +9,11 @@ pub(crate) struct CrossPartitionParticipant {
 }
 
 fn cross_partition_wal_ops(write_set: &[XLWrite]) -> Vec<TxOp> {
-    let mut ops = Vec::with_capacity(write_set.len() + 2);
+    let mut ops = Vec::with_capacity(write_set.len() + 3);
     ops.push(TxOp::Begin);
     ops.extend(write_set.iter().cloned().map(TxOp::Write));
     ops.push(TxOp::Commit);
+    ops.push(TxOp::commit_time_now());
     ops
 }
 
@@ -39,6 +40,7 @@ fn staged_write_set(
                     tuple_id: 0,
                     key: key.clone(),
                     value: value.clone(),
+                    layout_version: None,
                 })),
                 None => write_set.push(XLWrite::Delete(XLDelete {
                     table_id: relation_id.table_id,
@@ -353,6 +355,28 @@ impl WorkerXContract {
                     .transpose()?;
                 Ok(PartitionRpcResponse::ReadKey(projected))
             }
+            PartitionRpcRequest::LockKey {
+                lock_token,
+                table_id,
+                partition_id,
+                key,
+            } => {
+                let relation_id = PhysicalRelationId {
+                    table_id,
+                    partition_id,
+                };
+                let acquired = self
+                    .tx_lock
+                    .lock_some(lock_token, &[(relation_id, key)], STATEMENT_LOCK_TIMEOUT)
+                    .await?;
+                if !acquired {
+                    return Err(mudu_error!(
+                        ErrorCode::Transaction,
+                        "failed to acquire statement locks"
+                    ));
+                }
+                Ok(PartitionRpcResponse::LockKey)
+            }
             PartitionRpcRequest::UnlockKeys { lock_token } => {
                 debug!(
                     worker_id = self.worker_id,
@@ -361,6 +385,70 @@ impl WorkerXContract {
                 self.tx_lock.release_all(lock_token)?;
                 Ok(PartitionRpcResponse::UnlockKeys)
             }
+            PartitionRpcRequest::ScanPartition {
+                table_id,
+                partition_id,
+            } => {
+                debug!(
+                    worker_id = self.worker_id,
+                    table_id, partition_id, "execute partition rpc scan_partition"
+                );
+                Ok(PartitionRpcResponse::ScanPartition(
+                    self.scan_partition_local(table_id, partition_id).await?,
+                ))
+            }
+            PartitionRpcRequest::PrepareTx {
+                tx_id,
+                coordinator_worker_id,
+                writes,
+            } => {
+                debug!(
+                    worker_id = self.worker_id,
+                    tx_id,
+                    coordinator_worker_id,
+                    writes = writes.len(),
+                    "execute partition rpc prepare_tx"
+                );
+                self.prepare_tx_async(coordinator_worker_id, tx_id, writes)
+                    .await?;
+                Ok(PartitionRpcResponse::PreparedTx)
+            }
+            PartitionRpcRequest::CommitPreparedTx {
+                tx_id,
+                coordinator_worker_id,
+            } => {
+                debug!(
+                    worker_id = self.worker_id,
+                    tx_id, coordinator_worker_id, "execute partition rpc commit_prepared_tx"
+                );
+                self.resolve_prepared_tx_async(coordinator_worker_id, tx_id, true)
+                    .await?;
+                Ok(PartitionRpcResponse::PreparedTx)
+            }
+            PartitionRpcRequest::AbortPreparedTx {
+                tx_id,
+                coordinator_worker_id,
+            } => {
+                debug!(
+                    worker_id = self.worker_id,
+                    tx_id, coordinator_worker_id, "execute partition rpc abort_prepared_tx"
+                );
+                self.resolve_prepared_tx_async(coordinator_worker_id, tx_id, false)
+                    .await?;
+                Ok(PartitionRpcResponse::PreparedTx)
+            }
+            PartitionRpcRequest::QueryTxDecision {
+                tx_id,
+                participant_worker_id,
+            } => {
+                debug!(
+                    worker_id = self.worker_id,
+                    tx_id, participant_worker_id, "execute partition rpc query_tx_decision"
+                );
+                Ok(PartitionRpcResponse::TxDecision(
+                    self.query_tx_decision(tx_id, participant_worker_id)?,
+                ))
+            }
         }
     }
 
@@ -373,14 +461,24 @@ impl WorkerXContract {
         let lock_owner = lock_token.unwrap_or(tx_mgr.xid() as OID);
         for write in writes {
             match write {
-                XLWrite::Insert(insert) => tx_mgr.put_relation(
-                    PhysicalRelationId {
-                        table_id: insert.table_id,
-                        partition_id: insert.partition_id,
-                    },
-                    insert.key.clone(),
-                    insert.value.clone(),
-                ),
+                XLWrite::Insert(insert) => {
+                    // The row stays encoded under the layout the
+                    // coordinator staged it with.
+                    if let Some(version) = insert.layout_version {
+                        if let Err(err) = tx_mgr.record_layout_version(insert.table_id, version) {
+                            self.worker_rollback_tx(tx_mgr)?;
+                            return Err(err);
+                        }
+                    }
+                    tx_mgr.put_relation(
+                        PhysicalRelationId {
+                            table_id: insert.table_id,
+                            partition_id: insert.partition_id,
+                        },
+                        insert.key.clone(),
+                        insert.value.clone(),
+                    )
+                }
                 XLWrite::Delete(delete) => tx_mgr.delete_relation(
                     PhysicalRelationId {
                         table_id: delete.table_id,
@@ -520,6 +618,31 @@ impl WorkerXContract {
         }
     }
 
+    pub(crate) async fn remote_scan_partition(
+        &self,
+        target_worker_id: OID,
+        table_id: OID,
+        partition_id: OID,
+    ) -> RS<Vec<(Vec<u8>, Vec<u8>)>> {
+        match self
+            .send_partition_rpc(
+                target_worker_id,
+                PartitionRpcRequest::ScanPartition {
+                    table_id,
+                    partition_id,
+                },
+            )
+            .await?
+        {
+            PartitionRpcResponse::ScanPartition(rows) => Ok(rows),
+            PartitionRpcResponse::Err(err) => Err(mudu_error!(ErrorCode::Internal, err)),
+            _ => Err(mudu_error!(
+                ErrorCode::Internal,
+                "unexpected scan_partition rpc response"
+            )),
+        }
+    }
+
     /// Take a statement-level write lock on `key` at the owning worker under
     /// `lock_token` and return the currently committed value (projected),
     /// like `remote_read_key`.
@@ -554,6 +677,36 @@ impl WorkerXContract {
         }
     }
 
+    /// Take a statement-level lock on `key` of `relation_id` under
+    /// `lock_token` on the given worker.
+    pub(crate) async fn remote_lock_key(
+        &self,
+        target_worker_id: OID,
+        lock_token: OID,
+        relation_id: PhysicalRelationId,
+        key: Vec<u8>,
+    ) -> RS<()> {
+        match self
+            .send_partition_rpc(
+                target_worker_id,
+                PartitionRpcRequest::LockKey {
+                    lock_token,
+                    table_id: relation_id.table_id,
+                    partition_id: relation_id.partition_id,
+                    key,
+                },
+            )
+            .await?
+        {
+            PartitionRpcResponse::LockKey => Ok(()),
+            PartitionRpcResponse::Err(err) => Err(mudu_error!(ErrorCode::Internal, err)),
+            _ => Err(mudu_error!(
+                ErrorCode::Internal,
+                "unexpected lock_key rpc response"
+            )),
+        }
+    }
+
     /// Release all statement-level locks held by `lock_token` on the given
     /// owner worker (rollback path).
     pub(crate) async fn remote_unlock_keys(
@@ -607,32 +760,85 @@ impl WorkerXContract {
         }
     }
 
-    async fn remote_apply_cross_partition_tx(
+    /// Send phase one of a cross-partition commit to a participant.
+    pub(crate) async fn remote_prepare_tx(
         &self,
         target_worker_id: OID,
-        tx_id: OID,
-        partition_id: OID,
-        visibility_epoch: u64,
-        partition_write_set: Vec<XLWrite>,
+        tx_id: u64,
+        writes: Vec<XLWrite>,
     ) -> RS<()> {
         match self
             .send_partition_rpc(
                 target_worker_id,
-                PartitionRpcRequest::ApplyCrossPartitionTx {
+                PartitionRpcRequest::PrepareTx {
                     tx_id,
                     coordinator_worker_id: self.worker_id,
-                    partition_id,
-                    visibility_epoch,
-                    partition_write_set,
+                    writes,
                 },
             )
             .await?
         {
-            PartitionRpcResponse::ApplyCrossPartitionTx => Ok(()),
+            PartitionRpcResponse::PreparedTx => Ok(()),
+            PartitionRpcResponse::Err(err) => Err(mudu_error!(ErrorCode::Transaction, err)),
+            _ => Err(mudu_error!(
+                ErrorCode::Internal,
+                "unexpected prepare_tx rpc response"
+            )),
+        }
+    }
+
+    /// Send the outcome of a transaction this worker coordinates to a
+    /// participant.
+    pub(crate) async fn remote_resolve_prepared_tx(
+        &self,
+        target_worker_id: OID,
+        tx_id: u64,
+        commit: bool,
+    ) -> RS<()> {
+        let coordinator_worker_id = self.worker_id;
+        let request = if commit {
+            PartitionRpcRequest::CommitPreparedTx {
+                tx_id,
+                coordinator_worker_id,
+            }
+        } else {
+            PartitionRpcRequest::AbortPreparedTx {
+                tx_id,
+                coordinator_worker_id,
+            }
+        };
+        match self.send_partition_rpc(target_worker_id, request).await? {
+            PartitionRpcResponse::PreparedTx => Ok(()),
             PartitionRpcResponse::Err(err) => Err(mudu_error!(ErrorCode::Internal, err)),
             _ => Err(mudu_error!(
                 ErrorCode::Internal,
-                "unexpected apply_cross_partition_tx rpc response"
+                "unexpected resolve_prepared_tx rpc response"
+            )),
+        }
+    }
+
+    /// Ask the coordinator of a transaction this worker holds prepared for
+    /// its outcome.
+    pub(crate) async fn remote_query_tx_decision(
+        &self,
+        coordinator_worker_id: OID,
+        tx_id: u64,
+    ) -> RS<Option<bool>> {
+        match self
+            .send_partition_rpc(
+                coordinator_worker_id,
+                PartitionRpcRequest::QueryTxDecision {
+                    tx_id,
+                    participant_worker_id: self.worker_id,
+                },
+            )
+            .await?
+        {
+            PartitionRpcResponse::TxDecision(decision) => Ok(decision),
+            PartitionRpcResponse::Err(err) => Err(mudu_error!(ErrorCode::Internal, err)),
+            _ => Err(mudu_error!(
+                ErrorCode::Internal,
+                "unexpected query_tx_decision rpc response"
             )),
         }
     }
@@ -653,7 +859,11 @@ impl WorkerXContract {
     /// writes to the wrong worker's storage.
     pub(crate) async fn worker_commit_routed_tx_async(&self, tx: Arc<dyn TxMgr>) -> RS<()> {
         if tx.is_empty() {
-            return self.worker_commit_tx_async(tx).await;
+            return self.commit_local_tx_async(tx).await;
+        }
+        if let Err(err) = self.ensure_writable(Some(tx.xid())) {
+            self.worker_abort_tx_async(tx).await?;
+            return Err(err);
         }
         if !tx.staged_put_items().is_empty() {
             let staged = tx.staged_relation_ops();
@@ -669,7 +879,7 @@ impl WorkerXContract {
                     ));
                 }
             }
-            return self.worker_commit_tx_async(tx).await;
+            return self.commit_local_tx_async(tx).await;
         }
         let staged = tx.staged_relation_ops();
         let mut partition_owners = BTreeMap::new();
@@ -681,7 +891,11 @@ impl WorkerXContract {
             partition_owners.insert(relation_id.partition_id, owner);
         }
         if !is_cross_partition_tx(tx.as_ref(), self.worker_id, &partition_owners) {
-            return self.worker_commit_tx_async(tx).await;
+            return self.commit_local_tx_async(tx).await;
+        }
+        if tx.isolation_level() == IsolationLevel::Serializable {
+            self.worker_abort_tx_async(tx).await?;
+            return Err(serializable_remote_access_error());
         }
         let mut has_local_writes = false;
         let mut remote_owners = BTreeSet::new();
@@ -697,26 +911,34 @@ impl WorkerXContract {
                 return self.handoff_commit_tx_async(tx, owner, &staged).await;
             }
         }
-        // Mixed local/remote or multiple owners: the cross-partition apply
-        // path does not take the owner's XLockMgr, so statement locks held on
-        // remote owners would neither protect this commit nor be released by
-        // it. Release them up front (this route keeps the pre-pessimistic
-        // semantics); local statement locks stay and are released by the
-        // commit/rollback cleanup.
+        // Mixed local/remote or multiple owners: the two-phase commit keeps
+        // the statement locks held on participants (their prepare takes the
+        // commit locks under the same token, and resolving it releases
+        // them). Locks on remote owners that end up without writes are
+        // released once the commit is applied; local statement locks are
+        // released by the commit/rollback cleanup.
+        let xid = tx.xid();
+        let idle_owners = tx
+            .remote_lock_owners()
+            .into_iter()
+            .filter(|owner| !remote_owners.contains(owner))
+            .collect();
+        let result = self.worker_commit_cross_partition_tx_async(tx).await;
+        self.release_remote_locks(xid, idle_owners).await;
+        result
+    }
+
+    /// Commit `tx` on this worker, then release the statement-level locks it
+    /// holds on remote workers, such as constraint value locks (see
+    /// `lock_constraint_value_async`). They are released only once the
+    /// commit is applied, so a transaction waiting on one of them observes
+    /// the committed writes.
+    async fn commit_local_tx_async(&self, tx: Arc<dyn TxMgr>) -> RS<()> {
+        let xid = tx.xid();
         let owners = tx.remote_lock_owners();
-        if !owners.is_empty() {
-            let token = statement_lock_token(self.worker_id, tx.xid());
-            for owner in owners {
-                if let Err(err) = self.remote_unlock_keys(owner, token).await {
-                    debug!(
-                        worker_id = self.worker_id,
-                        owner, "remote unlock before cross-partition commit failed: {err}"
-                    );
-                }
-            }
-            tx.clear_remote_lock_owners();
-        }
-        self.worker_commit_cross_partition_tx_async(tx).await
+        let result = self.worker_commit_tx_async(tx).await;
+        self.release_remote_locks(xid, owners).await;
+        result
     }
 
     /// Hand the staged write set of `tx` over to `owner_worker_id`, which
@@ -730,7 +952,10 @@ impl WorkerXContract {
         owner_worker_id: OID,
         staged: &BTreeMap<PhysicalRelationId, BTreeMap<Vec<u8>, Option<Vec<u8>>>>,
     ) -> RS<()> {
-        let writes = staged_write_set(staged);
+        let mut writes = staged_write_set(staged);
+        self.storage
+            .stamp_layout_versions(&mut writes, &tx.staged_layout_versions())
+            .await?;
         let lock_token = statement_lock_token(self.worker_id, tx.xid());
         debug!(
             worker_id = self.worker_id,
@@ -744,6 +969,14 @@ impl WorkerXContract {
             .await;
         match result {
             Ok(_) => {
+                // The owner released its locks with the commit; release the
+                // ones held on other workers now that the writes are applied.
+                let idle_owners = tx
+                    .remote_lock_owners()
+                    .into_iter()
+                    .filter(|owner| *owner != owner_worker_id)
+                    .collect();
+                self.release_remote_locks(tx.xid(), idle_owners).await;
                 tx.clear_remote_lock_owners();
                 self.worker_rollback_tx(tx)
             }
@@ -757,6 +990,21 @@ impl WorkerXContract {
         }
     }
 
+    /// Commit a transaction whose writes span several owner workers with a
+    /// two-phase commit coordinated by this worker:
+    ///
+    /// 1. every remote participant prepares its share of the write set
+    ///    (commit locks plus a durable prepare record); any failure aborts
+    ///    the participants and the transaction;
+    /// 2. the commit decision is logged together with the local share of the
+    ///    writes, which is applied under the local commit locks;
+    /// 3. once the decision is durable it is delivered to the participants.
+    ///    Undelivered decisions are retried by the resolution round, and
+    ///    participants that lose track of the coordinator ask it for the
+    ///    outcome.
+    ///
+    /// Transactions whose partitions all live on this worker skip the
+    /// protocol and log a plain committed entry.
     pub(crate) async fn worker_commit_cross_partition_tx_async(
         &self,
         tx: Arc<dyn TxMgr>,
@@ -766,30 +1014,73 @@ impl WorkerXContract {
         let write_ops = tx.write_ops();
         acquire_commit_locks(&self.tx_lock, xid as OID, &write_ops).await?;
 
+        self.begin_two_phase_tx(xid)?;
         let result = async {
-            let _prepared = self.storage.prepare_commit_async(tx.as_ref()).await?;
+            // Held until the local share is applied, so the layout the rows
+            // are stamped with stays current.
+            let _commit = self.storage.enter_commit().await;
+            let prepared = self.storage.prepare_commit_async(tx.as_ref()).await?;
+            // Held until the local share is applied; the remote shares are
+            // pinned by their participants' prepare.
+            let _route = self.storage.route_fence().enter().await;
+            self.check_fenced_routes(&prepared.relation_puts()).await?;
             let (participants, write_set) = self.build_cross_partition_tx_ops(tx.as_ref()).await?;
+            let mut local_writes = Vec::new();
+            let mut remote_writes: BTreeMap<OID, Vec<XLWrite>> = BTreeMap::new();
+            for participant in &participants {
+                let writes = partition_write_set(&write_set, participant.partition_id);
+                if participant.worker_id == self.worker_id {
+                    local_writes.extend(writes);
+                } else {
+                    remote_writes
+                        .entry(participant.worker_id)
+                        .or_default()
+                        .extend(writes);
+                }
+            }
+            let remote_participants: Vec<OID> = remote_writes.keys().copied().collect();
+            for (participant, writes) in remote_writes {
+                if let Err(err) = self.remote_prepare_tx(participant, xid, writes).await {
+                    self.abort_two_phase_participants(xid, &remote_participants)
+                        .await;
+                    return Err(err);
+                }
+            }
+            let _gate = self.checkpoint_gate.enter().await;
             // Enqueue (allocating LSNs) inside the commit-lock critical
             // section so WAL order matches apply order, but defer the flush
             // drive and the durability wait until after the locks are
             // released.
-            let mut last_lsn = None;
-            if let Some(log) = self.log_cloned()? {
-                let batch = XLBatch::new(vec![XLEntry {
-                    xid,
-                    ops: cross_partition_wal_ops(&write_set),
-                }]);
-                let frames = log.serialize_entry(&batch)?;
-                let lsns = frame_lsns(&frames)?;
-                // Non-force enqueue: share fsyncs through the group-commit
-                // window instead of forcing one per commit.
-                last_lsn = Some(log.enqueue_group_commit(frames, lsns, false).await?);
-            }
-            self.apply_cross_partition_ops(xid as OID, participants, write_set)
-                .await
-                .map(|()| last_lsn)
+            let last_lsn = if remote_participants.is_empty() {
+                match self.log_cloned()? {
+                    Some(log) => {
+                        let batch = XLBatch::new(vec![XLEntry {
+                            xid,
+                            ops: cross_partition_wal_ops(&local_writes),
+                        }]);
+                        let frames = log.serialize_entry(&batch)?;
+                        let lsns = frame_lsns(&frames)?;
+                        // Non-force enqueue: share fsyncs through the
+                        // group-commit window instead of forcing one per
+                        // commit.
+                        Some(log.enqueue_group_commit(frames, lsns, false).await?)
+                    }
+                    None => None,
+                }
+            } else {
+                self.log_commit_decision(xid, &local_writes, remote_participants.clone())
+                    .await?
+            };
+            self.storage
+                .apply_cross_partition_tx_async(xid as OID, &local_writes)
+                .await?;
+            // A snapshot transaction: recorded for serializable readers
+            // here, never aborted by the check.
+            self.ssi_commit_check(tx.as_ref(), &write_ops)?;
+            Ok((last_lsn, remote_participants))
         }
         .await;
+        self.end_two_phase_tx(xid)?;
 
         self.tx_lock.release(xid as OID, &write_ops)?;
         // Drive the group-commit flush round outside the commit-lock
@@ -799,11 +1090,26 @@ impl WorkerXContract {
         if let Some(log) = self.log_cloned()? {
             log.drive_group_commit_flush().await?;
         }
-        self.worker_rollback_tx(tx)?;
-        let last_lsn = result?;
+        let (last_lsn, remote_participants) = match result {
+            Ok(committed) => {
+                tx.clear_remote_lock_owners();
+                self.worker_rollback_tx(tx)?;
+                committed
+            }
+            Err(err) => {
+                // Releases the statement locks still held on participants
+                // that never got to prepare.
+                self.worker_abort_tx_async(tx).await?;
+                return Err(err);
+            }
+        };
         if let (Some(log), Some(last_lsn)) = (self.log_cloned()?, last_lsn) {
             log.wait_group_commit_advanced(last_lsn).await?;
         }
+        if !remote_participants.is_empty() {
+            self.deliver_commit_decision(xid, remote_participants)
+                .await?;
+        }
         Ok(())
     }
 
@@ -820,6 +1126,10 @@ impl WorkerXContract {
                 .unwrap_or(self.worker_id);
             participants.insert(relation_id.partition_id, worker_id);
         }
+        let mut writes = staged_write_set(&staged);
+        self.storage
+            .stamp_layout_versions(&mut writes, &tx.staged_layout_versions())
+            .await?;
         Ok((
             participants
                 .into_iter()
@@ -828,35 +1138,9 @@ impl WorkerXContract {
                     worker_id,
                 })
                 .collect(),
-            staged_write_set(&staged),
+            writes,
         ))
     }
-
-    async fn apply_cross_partition_ops(
-        &self,
-        tx_id: OID,
-        participants: Vec<CrossPartitionParticipant>,
-        write_set: Vec<XLWrite>,
-    ) -> RS<()> {
-        for participant in &participants {
-            let writes = partition_write_set(&write_set, participant.partition_id);
-            if participant.worker_id != self.worker_id {
-                self.remote_apply_cross_partition_tx(
-                    participant.worker_id,
-                    tx_id,
-                    participant.partition_id,
-                    tx_id as u64,
-                    writes,
-                )
-                .await?;
-            } else {
-                self.storage
-                    .apply_cross_partition_tx_async(tx_id, &writes)
-                    .await?;
-            }
-        }
-        Ok(())
-    }
 }
 
 #[cfg(test)]
@@ -962,9 +1246,10 @@ mod tests {
     #[test]
     fn cross_partition_wal_ops_empty_is_begin_commit() {
         let ops = cross_partition_wal_ops(&[]);
-        assert_eq!(ops.len(), 2);
+        assert_eq!(ops.len(), 3);
         assert!(matches!(ops[0], TxOp::Begin));
         assert!(matches!(ops[1], TxOp::Commit));
+        assert!(matches!(ops[2], TxOp::CommitTime { .. }));
     }
 
     #[test]
@@ -976,6 +1261,7 @@ mod tests {
                 tuple_id: 0,
                 key: b"a".to_vec(),
                 value: b"1".to_vec(),
+                layout_version: None,
             }),
             XLWrite::Delete(XLDelete {
                 table_id: 2,
@@ -985,11 +1271,12 @@ mod tests {
             }),
         ];
         let ops = cross_partition_wal_ops(&writes);
-        assert_eq!(ops.len(), 4);
+        assert_eq!(ops.len(), 5);
         assert!(matches!(ops[0], TxOp::Begin));
         assert!(matches!(ops[1], TxOp::Write(_)));
         assert!(matches!(ops[2], TxOp::Write(_)));
         assert!(matches!(ops[3], TxOp::Commit));
+        assert!(matches!(ops[4], TxOp::CommitTime { .. }));
     }
 
     #[test]
@@ -1001,6 +1288,7 @@ mod tests {
                 tuple_id: 0,
                 key: b"a".to_vec(),
                 value: b"1".to_vec(),
+                layout_version: None,
             }),
             XLWrite::Insert(XLInsert {
                 table_id: 2,
@@ -1008,6 +1296,7 @@ mod tests {
                 tuple_id: 0,
                 key: b"b".to_vec(),
                 value: b"2".to_vec(),
+                layout_version: None,
             }),
             XLWrite::Delete(XLDelete {
                 table_id: 3,
@@ -1221,6 +1510,7 @@ mod tests {
                 tuple_id: 0,
                 key,
                 value,
+                layout_version: None,
             })],
         };
         assert_eq!(
@@ -1521,98 +1811,381 @@ mod tests {
         assert_eq!(participant_by_partition.get(&1).copied(), Some(7));
     }
 
+    fn prepare_request(
+        coordinator_worker_id: OID,
+        tx_id: u64,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) -> PartitionRpcRequest {
+        PartitionRpcRequest::PrepareTx {
+            tx_id,
+            coordinator_worker_id,
+            writes: vec![XLWrite::Insert(XLInsert {
+                table_id: table_id(),
+                partition_id: 0,
+                tuple_id: 0,
+                key,
+                value,
+                layout_version: None,
+            })],
+        }
+    }
+
+    fn read_value_request(key: Vec<u8>) -> PartitionRpcRequest {
+        PartitionRpcRequest::ReadKey {
+            table_id: table_id(),
+            partition_id: 0,
+            key,
+            select: vec![1],
+        }
+    }
+
+    fn relation_lock_key(key: Vec<u8>) -> (PhysicalRelationId, Vec<u8>) {
+        (
+            PhysicalRelationId {
+                table_id: table_id(),
+                partition_id: 0,
+            },
+            key,
+        )
+    }
+
     #[tokio::test(flavor = "current_thread")]
-    async fn apply_cross_partition_ops_applies_local_writes() {
-        let contract = make_contract().await;
+    async fn prepared_tx_stays_invisible_and_locked_until_committed() {
+        let participant = make_contract_with_worker(20, 20).await;
         let desc = meta_table(&test_schema()).unwrap();
         let key = build_key_tuple(&key_row(1), &desc).unwrap();
         let value = build_value_tuple(&value_row(10), &desc).unwrap();
 
-        let participants = vec![CrossPartitionParticipant {
-            partition_id: 0,
-            worker_id: contract.worker_id(),
-        }];
-        let write_set = vec![XLWrite::Insert(XLInsert {
-            table_id: table_id(),
-            partition_id: 0,
-            tuple_id: 0,
-            key: key.clone(),
-            value,
-        })];
+        let response = participant
+            .execute_partition_rpc(prepare_request(10, 7, key.clone(), value))
+            .await
+            .unwrap();
+        assert_eq!(response, PartitionRpcResponse::PreparedTx);
+        let response = participant
+            .execute_partition_rpc(read_value_request(key.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response, PartitionRpcResponse::ReadKey(None));
+        // A local commit touching the prepared key cannot take its lock.
+        assert!(!participant
+            .tx_lock
+            .try_lock_some(99, &[relation_lock_key(key.clone())])
+            .unwrap());
 
-        contract
-            .apply_cross_partition_ops(1, participants, write_set)
+        let commit = PartitionRpcRequest::CommitPreparedTx {
+            tx_id: 7,
+            coordinator_worker_id: 10,
+        };
+        for _ in 0..2 {
+            // Redelivered decisions are acknowledged without effect.
+            let response = participant
+                .execute_partition_rpc(commit.clone())
+                .await
+                .unwrap();
+            assert_eq!(response, PartitionRpcResponse::PreparedTx);
+        }
+        let response = participant
+            .execute_partition_rpc(read_value_request(key.clone()))
             .await
             .unwrap();
+        assert_eq!(
+            response,
+            PartitionRpcResponse::ReadKey(Some(vec![Some(datum(10))]))
+        );
+        assert!(participant
+            .tx_lock
+            .try_lock_some(99, &[relation_lock_key(key)])
+            .unwrap());
+    }
 
-        let read = PartitionRpcRequest::ReadKey {
-            table_id: table_id(),
-            partition_id: 0,
-            key,
-            select: vec![1],
+    #[tokio::test(flavor = "current_thread")]
+    async fn aborted_prepared_tx_drops_writes_and_releases_locks() {
+        let participant = make_contract_with_worker(20, 20).await;
+        let desc = meta_table(&test_schema()).unwrap();
+        let key = build_key_tuple(&key_row(1), &desc).unwrap();
+        let value = build_value_tuple(&value_row(10), &desc).unwrap();
+
+        participant
+            .execute_partition_rpc(prepare_request(10, 7, key.clone(), value.clone()))
+            .await
+            .unwrap();
+        // The same transaction of another coordinator is a different one.
+        participant
+            .execute_partition_rpc(prepare_request(11, 7, b"other".to_vec(), value.clone()))
+            .await
+            .unwrap();
+        let result = participant
+            .execute_partition_rpc(prepare_request(10, 7, b"again".to_vec(), value))
+            .await;
+        assert_eq!(result.unwrap_err().ec(), ErrorCode::Transaction);
+
+        let response = participant
+            .execute_partition_rpc(PartitionRpcRequest::AbortPreparedTx {
+                tx_id: 7,
+                coordinator_worker_id: 10,
+            })
+            .await
+            .unwrap();
+        assert_eq!(response, PartitionRpcResponse::PreparedTx);
+        let response = participant
+            .execute_partition_rpc(read_value_request(key.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response, PartitionRpcResponse::ReadKey(None));
+        assert!(participant
+            .tx_lock
+            .try_lock_some(99, &[relation_lock_key(key)])
+            .unwrap());
+        assert_eq!(participant.two_phase.prepared_records().unwrap().len(), 1);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn query_tx_decision_waits_for_recovery_and_presumes_abort() {
+        let coordinator = make_contract_with_worker(10, 10).await;
+        let query = |participant_worker_id| PartitionRpcRequest::QueryTxDecision {
+            tx_id: 7,
+            participant_worker_id,
         };
-        let response = contract.execute_partition_rpc(read).await.unwrap();
+
+        let response = coordinator.execute_partition_rpc(query(20)).await.unwrap();
+        assert_eq!(response, PartitionRpcResponse::TxDecision(None));
+        coordinator.recover_two_phase_txs_async().await.unwrap();
+        let response = coordinator.execute_partition_rpc(query(20)).await.unwrap();
+        assert_eq!(response, PartitionRpcResponse::TxDecision(Some(false)));
+
+        coordinator.begin_two_phase_tx(7).unwrap();
+        let response = coordinator.execute_partition_rpc(query(20)).await.unwrap();
+        assert_eq!(response, PartitionRpcResponse::TxDecision(None));
+        let _gate = coordinator.checkpoint_gate.enter().await;
+        coordinator
+            .log_commit_decision(7, &[], vec![20])
+            .await
+            .unwrap();
+        coordinator.end_two_phase_tx(7).unwrap();
+        let response = coordinator.execute_partition_rpc(query(20)).await.unwrap();
+        assert_eq!(response, PartitionRpcResponse::TxDecision(Some(true)));
+        let response = coordinator.execute_partition_rpc(query(30)).await.unwrap();
+        assert_eq!(response, PartitionRpcResponse::TxDecision(Some(false)));
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn cross_partition_commit_prepares_then_commits_remote_participant() {
+        let bus = Arc::new(MockMessageBus::new(10));
+        set_current_message_bus(bus.clone());
+        let coordinator = make_contract_with_worker(10, 20).await;
+        let participant = make_contract_with_worker(20, 20).await;
+        let desc = meta_table(&test_schema()).unwrap();
+        let key = build_key_tuple(&key_row(1), &desc).unwrap();
+        let value = build_value_tuple(&value_row(10), &desc).unwrap();
+
+        let tx = coordinator.worker_begin_tx().unwrap();
+        let xid = tx.xid();
+        tx.put_relation(
+            PhysicalRelationId {
+                table_id: table_id(),
+                partition_id: 0,
+            },
+            key.clone(),
+            value,
+        );
+        for _ in 0..2 {
+            bus.push_response(response_envelope(
+                1,
+                20,
+                10,
+                PartitionRpcResponse::PreparedTx,
+            ));
+        }
+        coordinator
+            .worker_commit_cross_partition_tx_async(tx)
+            .await
+            .unwrap();
+
+        // Phase one prepares the participant's share, phase two commits it;
+        // replaying both on the participant applies the write.
+        let sent = bus.take_sent();
+        assert_eq!(sent.len(), 2);
+        for (dst, message) in sent {
+            assert_eq!(dst, 20);
+            let request = rmp_serde::from_slice::<PartitionRpcRequest>(message.payload()).unwrap();
+            participant.execute_partition_rpc(request).await.unwrap();
+        }
+        let response = participant
+            .execute_partition_rpc(read_value_request(key))
+            .await
+            .unwrap();
         assert_eq!(
             response,
             PartitionRpcResponse::ReadKey(Some(vec![Some(datum(10))]))
         );
+        // Every participant acknowledged: the decision is forgotten.
+        assert!(coordinator.two_phase.decision_records().unwrap().is_empty());
+        coordinator.recover_two_phase_txs_async().await.unwrap();
+        assert_eq!(coordinator.query_tx_decision(xid, 20).unwrap(), Some(false));
+        unset_current_message_bus();
     }
 
     #[tokio::test(flavor = "current_thread")]
-    async fn apply_cross_partition_ops_sends_remote_request() {
+    async fn cross_partition_commit_aborts_participants_when_prepare_fails() {
         let bus = Arc::new(MockMessageBus::new(10));
         set_current_message_bus(bus.clone());
-        let data_dir = temp_dir()
-            .join(format!("rpc_remote_apply_{}", gen_oid()))
-            .to_string_lossy()
-            .to_string();
-        let contract = WorkerXContract::with_log_and_data_dir(WorkerXContractParams {
-            meta_mgr: Arc::new(TestMetaMgr::new()),
-            log: None,
-            log_layout: Default::default(),
-            active_sessions: Default::default(),
-            worker_id: 10,
-            default_unpartitioned_worker_id: 20,
-            partition_id: 0,
-            data_dir,
-            async_runtime: None,
-            server_instance_id: 0,
-        })
-        .unwrap();
+        let coordinator = make_contract_with_worker(10, 20).await;
 
+        let tx = coordinator.worker_begin_tx().unwrap();
+        tx.put_relation(
+            PhysicalRelationId {
+                table_id: table_id(),
+                partition_id: 0,
+            },
+            b"k".to_vec(),
+            b"v".to_vec(),
+        );
         bus.push_response(response_envelope(
             1,
             20,
             10,
-            PartitionRpcResponse::ApplyCrossPartitionTx,
+            PartitionRpcResponse::Err("lock timeout".to_string()),
         ));
+        bus.push_response(response_envelope(
+            1,
+            20,
+            10,
+            PartitionRpcResponse::PreparedTx,
+        ));
+        let result = coordinator.worker_commit_cross_partition_tx_async(tx).await;
+        assert_eq!(result.unwrap_err().ec(), ErrorCode::Transaction);
 
-        let participants = vec![CrossPartitionParticipant {
-            partition_id: 0,
-            worker_id: 20,
-        }];
-        let write_set = vec![XLWrite::Insert(XLInsert {
-            table_id: 1,
+        let kinds: Vec<_> = bus
+            .take_sent()
+            .into_iter()
+            .map(|(_, message)| {
+                rmp_serde::from_slice::<PartitionRpcRequest>(message.payload()).unwrap()
+            })
+            .collect();
+        assert!(matches!(kinds[0], PartitionRpcRequest::PrepareTx { .. }));
+        assert!(matches!(
+            kinds[1],
+            PartitionRpcRequest::AbortPreparedTx { .. }
+        ));
+        assert!(coordinator.two_phase.decision_records().unwrap().is_empty());
+        unset_current_message_bus();
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn replay_holds_prepared_writes_until_resolved() {
+        let participant = make_contract_with_worker(20, 20).await;
+        let desc = meta_table(&test_schema()).unwrap();
+        let key = build_key_tuple(&key_row(1), &desc).unwrap();
+        let value = build_value_tuple(&value_row(10), &desc).unwrap();
+        let write = XLWrite::Insert(XLInsert {
+            table_id: table_id(),
             partition_id: 0,
             tuple_id: 0,
-            key: b"rk".to_vec(),
-            value: b"rv".to_vec(),
-        })];
+            key: key.clone(),
+            value,
+            layout_version: None,
+        });
 
-        contract
-            .apply_cross_partition_ops(1, participants, write_set.clone())
+        participant
+            .replay_worker_log_batch(XLBatch::new(vec![XLEntry {
+                xid: 7,
+                ops: vec![
+                    TxOp::Begin,
+                    TxOp::Write(write),
+                    TxOp::Prepare {
+                        coordinator_worker_id: 10,
+                    },
+                ],
+            }]))
+            .await
+            .unwrap();
+        let response = participant
+            .execute_partition_rpc(read_value_request(key.clone()))
             .await
             .unwrap();
+        assert_eq!(response, PartitionRpcResponse::ReadKey(None));
+        assert!(!participant
+            .tx_lock
+            .try_lock_some(99, &[relation_lock_key(key.clone())])
+            .unwrap());
 
-        let sent = bus.take_sent();
-        assert_eq!(sent.len(), 1);
-        assert_eq!(sent[0].0, 20);
-        let decoded = rmp_serde::from_slice::<PartitionRpcRequest>(sent[0].1.payload()).unwrap();
-        assert!(matches!(
-            decoded,
-            PartitionRpcRequest::ApplyCrossPartitionTx { .. }
-        ));
-        unset_current_message_bus();
+        participant
+            .replay_worker_log_batch(XLBatch::new(vec![XLEntry {
+                xid: 7,
+                ops: vec![TxOp::Resolve {
+                    coordinator_worker_id: 10,
+                    commit: true,
+                }],
+            }]))
+            .await
+            .unwrap();
+        let response = participant
+            .execute_partition_rpc(read_value_request(key.clone()))
+            .await
+            .unwrap();
+        assert_eq!(
+            response,
+            PartitionRpcResponse::ReadKey(Some(vec![Some(datum(10))]))
+        );
+        assert!(participant
+            .tx_lock
+            .try_lock_some(99, &[relation_lock_key(key)])
+            .unwrap());
+        assert!(participant.two_phase.prepared_records().unwrap().is_empty());
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn replay_applies_decision_writes_and_tracks_delivery() {
+        let coordinator = make_contract_with_worker(10, 10).await;
+        let desc = meta_table(&test_schema()).unwrap();
+        let key = build_key_tuple(&key_row(1), &desc).unwrap();
+        let value = build_value_tuple(&value_row(10), &desc).unwrap();
+
+        coordinator
+            .replay_worker_log_batch(XLBatch::new(vec![XLEntry {
+                xid: 7,
+                ops: vec![
+                    TxOp::Begin,
+                    TxOp::Write(XLWrite::Insert(XLInsert {
+                        table_id: table_id(),
+                        partition_id: 0,
+                        tuple_id: 0,
+                        key: key.clone(),
+                        value,
+                        layout_version: None,
+                    })),
+                    TxOp::CommitDecision {
+                        participants: vec![20],
+                    },
+                ],
+            }]))
+            .await
+            .unwrap();
+        let response = coordinator
+            .execute_partition_rpc(read_value_request(key))
+            .await
+            .unwrap();
+        assert_eq!(
+            response,
+            PartitionRpcResponse::ReadKey(Some(vec![Some(datum(10))]))
+        );
+        assert_eq!(
+            coordinator.two_phase.decision_records().unwrap(),
+            vec![two_phase::CommitDecisionRecord {
+                tx_id: 7,
+                participants: vec![20],
+            }]
+        );
+
+        coordinator
+            .replay_worker_log_batch(XLBatch::new(vec![XLEntry {
+                xid: 7,
+                ops: vec![TxOp::DecisionDelivered],
+            }]))
+            .await
+            .unwrap();
+        assert!(coordinator.two_phase.decision_records().unwrap().is_empty());
     }
 
     #[tokio::test(flavor = "current_thread")]
@@ -1811,6 +2384,7 @@ mod tests {
                     tuple_id: 0,
                     key: key.clone(),
                     value: value.clone(),
+                    layout_version: None,
                 })],
             })
             .await;
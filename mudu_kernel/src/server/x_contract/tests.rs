@@ -10,6 +10,8 @@ mod tests {
 
     use super::super::utils::{build_key_tuple, build_value_tuple};
     use super::super::*;
+    use crate::contract::partition_rule::{PartitionBound, PartitionRuleDesc, RangePartitionDef};
+    use crate::contract::partition_rule_binding::{PartitionPlacement, TablePartitionBinding};
     use crate::contract::schema_column::SchemaColumn;
     use crate::contract::table_info::TableInfo;
     use crate::server::test_meta_mgr::TestMetaMgr;
@@ -238,6 +240,7 @@ mod tests {
                     tuple_id: 0,
                     key: b"wk".to_vec(),
                     value: b"wv".to_vec(),
+                    layout_version: None,
                 })),
                 TxOp::Write(XLWrite::Insert(XLInsert {
                     table_id,
@@ -246,6 +249,7 @@ mod tests {
                     key: build_key_tuple(&key_row(3), &meta_table(&schema).unwrap()).unwrap(),
                     value: build_value_tuple(&value_row(30), &meta_table(&schema).unwrap())
                         .unwrap(),
+                    layout_version: None,
                 })),
                 TxOp::Commit,
             ],
@@ -316,6 +320,7 @@ mod tests {
                         .unwrap(),
                     value: build_value_tuple(&value_row(80), &meta_table(&test_schema()).unwrap())
                         .unwrap(),
+                    layout_version: None,
                 })),
                 TxOp::Commit,
             ],
@@ -331,6 +336,75 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn serializable_tx_rejects_partitions_of_other_workers() {
+        block_on(async move {
+            let r = _serializable_tx_rejects_partitions_of_other_workers().await;
+            assert!(r.is_ok(), "{:?}", r.err())
+        })
+    }
+
+    /// The SSI tracker only sees its own worker, so a serializable
+    /// transaction fails on its first read of, or commit to, a partition
+    /// owned by another worker.
+    async fn _serializable_tx_rejects_partitions_of_other_workers() -> RS<()> {
+        let worker_id = gen_oid();
+        let schema = test_schema();
+        let table_id = schema.id();
+        let contract = WorkerXContract::with_log_and_data_dir(WorkerXContractParams {
+            meta_mgr: Arc::new(TestMetaMgr::new()),
+            log: None,
+            log_layout: Default::default(),
+            active_sessions: Default::default(),
+            worker_id,
+            // Partition 0 of the unpartitioned table lives on another worker.
+            default_unpartitioned_worker_id: worker_id + 1,
+            partition_id: 0,
+            data_dir: temp_dir()
+                .join(format!("serializable_remote_{}", gen_oid()))
+                .to_string_lossy()
+                .to_string(),
+            async_runtime: None,
+            server_instance_id: 0,
+        })?;
+        let ddl_tx = contract.begin_tx().await?;
+        contract.create_table(ddl_tx.clone(), &schema).await?;
+        contract.commit_tx(ddl_tx).await?;
+        let desc = meta_table(&schema)?;
+
+        let tx = contract.worker_begin_tx()?;
+        contract.worker_set_tx_isolation_level(tx.as_ref(), IsolationLevel::Serializable)?;
+        let err = contract
+            .read_key(
+                tx.clone(),
+                table_id,
+                &key_row(1),
+                &VecSelTerm::new(vec![1]),
+                &OptRead::default(),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.ec(), ErrorCode::NotImplemented);
+        contract.worker_abort_tx_async(tx).await?;
+
+        let tx = contract.worker_begin_tx()?;
+        contract.worker_set_tx_isolation_level(tx.as_ref(), IsolationLevel::Serializable)?;
+        tx.put_relation(
+            PhysicalRelationId {
+                table_id,
+                partition_id: DEFAULT_UNPARTITIONED_TABLE_PARTITION_ID,
+            },
+            build_key_tuple(&key_row(1), &desc)?,
+            build_value_tuple(&value_row(1), &desc)?,
+        );
+        let err = contract
+            .worker_commit_routed_tx_async(tx)
+            .await
+            .unwrap_err();
+        assert_eq!(err.ec(), ErrorCode::NotImplemented);
+        Ok(())
+    }
+
     async fn read_i32_value(
         contract: &WorkerXContract,
         table_id: OID,
@@ -451,6 +525,74 @@ mod tests {
         assert_eq!(contract.worker_get_async(b"wk").await.unwrap(), None);
     }
 
+    #[test]
+    fn worker_log_checkpoint_restores_worker_kv_with_later_entries() {
+        block_on(async move {
+            let r = _worker_log_checkpoint_restores_worker_kv_with_later_entries().await;
+            if let Err(e) = r {
+                panic!("worker log checkpoint recovery failed: {}", e);
+            }
+        })
+    }
+
+    async fn _worker_log_checkpoint_restores_worker_kv_with_later_entries() -> RS<()> {
+        let dir = temp_dir().join(format!("worker_log_checkpoint_{}", gen_oid()));
+        let layout = WorkerLogLayout::new(dir, gen_oid(), 4096)?;
+        let log = ChunkedWorkerLogBackend::new(layout.clone()).await?;
+        let contract = WorkerXContract::with_worker_log(log.clone()).await?;
+        contract
+            .worker_put_async(b"k1".to_vec(), b"v1".to_vec())
+            .await?;
+        contract
+            .worker_put_async(b"k2".to_vec(), b"v2".to_vec())
+            .await?;
+        contract.worker_delete_async(b"k2").await?;
+        let stats = contract.checkpoint_async().await?;
+        assert_eq!(stats.reclaimed_chunks, 1);
+        contract
+            .worker_put_async(b"k3".to_vec(), b"v3".to_vec())
+            .await?;
+        let max_xid = contract.latest_xid();
+
+        // Simulate crash recovery: restore the checkpoint into a fresh
+        // contract, then replay only the entries after it.
+        let fs = log.fs();
+        let checkpoint = layout
+            .read_checkpoint_async(fs.as_ref())
+            .await?
+            .expect("checkpoint must be written");
+        let recovered = WorkerXContract::with_worker_log(
+            ChunkedWorkerLogBackend::new(WorkerLogLayout::new(
+                temp_dir().join(format!("worker_log_checkpoint_recovered_{}", gen_oid())),
+                gen_oid(),
+                4096,
+            )?)
+            .await?,
+        )
+        .await?;
+        recovered.restore_worker_log_checkpoint(&checkpoint)?;
+        for path in log.chunk_paths_sorted().await? {
+            let frames = crate::wal::typed_worker_log::skip_checkpointed_frames(
+                decode_frames(&fs.read_all(&path).await?)?,
+                Some(checkpoint.lsn),
+            )?;
+            for batch in crate::wal::xl_batch::decode_xl_batches(&frames)? {
+                recovered.replay_worker_log_batch(batch).await?;
+            }
+        }
+        assert_eq!(
+            recovered.worker_get_async(b"k1").await?,
+            Some(b"v1".to_vec())
+        );
+        assert_eq!(recovered.worker_get_async(b"k2").await?, None);
+        assert_eq!(
+            recovered.worker_get_async(b"k3").await?,
+            Some(b"v3".to_vec())
+        );
+        assert!(recovered.latest_xid() >= max_xid);
+        Ok(())
+    }
+
     #[test]
     fn iouring_xcontract_update_maps_table_attr_to_value_tuple_index() {
         block_on(async move {
@@ -719,6 +861,227 @@ mod tests {
         Ok((Arc::new(contract), table_id))
     }
 
+    #[test]
+    fn xcontract_split_and_move_partition_keep_rows_reachable() {
+        block_on(async move {
+            let meta_mgr = Arc::new(TestMetaMgr::new());
+            let contract = WorkerXContract::with_log(meta_mgr.clone(), None)?;
+            let schema = test_schema();
+            let table_id = schema.id();
+            let ddl_tx = contract.begin_tx().await?;
+            contract.create_table(ddl_tx.clone(), &schema).await?;
+            contract.commit_tx(ddl_tx).await?;
+            let rule = PartitionRuleDesc::new_range(
+                "r".to_string(),
+                vec![TypeFamily::I32],
+                vec![RangePartitionDef::new(
+                    "p0".to_string(),
+                    PartitionBound::Unbounded,
+                    PartitionBound::Unbounded,
+                )],
+            );
+            meta_mgr.create_partition_rule(&rule).await?;
+            meta_mgr
+                .bind_table_partition(&TablePartitionBinding {
+                    table_id,
+                    rule_id: rule.oid,
+                    ref_attr_indices: vec![0],
+                })
+                .await?;
+
+            let insert_tx = contract.begin_tx().await?;
+            for k in 1..=6 {
+                contract
+                    .insert(
+                        insert_tx.clone(),
+                        table_id,
+                        &key_row(k),
+                        &value_row(k * 10),
+                        &OptInsert::default(),
+                    )
+                    .await?;
+            }
+            contract.commit_tx(insert_tx).await?;
+
+            let (split, lower, upper) = PartitionRouter::new(meta_mgr.clone())
+                .split_rule_partition(&rule, &[b"4".to_vec()], "p1".to_string())?;
+            let tx = contract.begin_tx().await?;
+            contract
+                .alter_partition_rule(tx.clone(), &split, &[])
+                .await?;
+            contract.abort_tx(tx).await?;
+
+            let current = meta_mgr.get_partition_rule_by_id(rule.oid).await?;
+            assert_eq!(current.version, rule.version + 1);
+            assert_eq!(contract.scan_partition(table_id, lower).await?.len(), 3);
+            assert_eq!(contract.scan_partition(table_id, upper).await?.len(), 3);
+            for k in 1..=6 {
+                let row = read_i32_value(&contract, table_id, k).await?;
+                assert_eq!(row, Some(datum(k * 10)));
+            }
+
+            let mut moved = current.clone();
+            moved.version += 1;
+            let placement = PartitionPlacement {
+                partition_id: upper,
+                worker_id: contract.worker_id,
+            };
+            let tx = contract.begin_tx().await?;
+            contract
+                .alter_partition_rule(tx.clone(), &moved, &[placement])
+                .await?;
+            contract.abort_tx(tx).await?;
+
+            assert_eq!(
+                meta_mgr.get_partition_worker(upper).await?,
+                Some(contract.worker_id)
+            );
+            for k in 1..=6 {
+                let row = read_i32_value(&contract, table_id, k).await?;
+                assert_eq!(row, Some(datum(k * 10)));
+            }
+            let stale = contract.begin_tx().await?;
+            let err = contract
+                .alter_partition_rule(stale.clone(), &moved, &[])
+                .await
+                .unwrap_err();
+            assert_eq!(err.ec(), ErrorCode::InvalidState);
+            contract.abort_tx(stale).await?;
+            Ok::<(), mudu::error::MuduError>(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn xcontract_repartition_catch_up_keeps_writes_made_after_the_switch() {
+        block_on(async move {
+            let meta_mgr = Arc::new(TestMetaMgr::new());
+            let contract = WorkerXContract::with_log(meta_mgr.clone(), None)?;
+            let schema = test_schema();
+            let table_id = schema.id();
+            let ddl_tx = contract.begin_tx().await?;
+            contract.create_table(ddl_tx.clone(), &schema).await?;
+            contract.commit_tx(ddl_tx).await?;
+            let rule = PartitionRuleDesc::new_range(
+                "r".to_string(),
+                vec![TypeFamily::I32],
+                vec![RangePartitionDef::new(
+                    "p0".to_string(),
+                    PartitionBound::Unbounded,
+                    PartitionBound::Unbounded,
+                )],
+            );
+            meta_mgr.create_partition_rule(&rule).await?;
+            meta_mgr
+                .bind_table_partition(&TablePartitionBinding {
+                    table_id,
+                    rule_id: rule.oid,
+                    ref_attr_indices: vec![0],
+                })
+                .await?;
+            let insert_tx = contract.begin_tx().await?;
+            for k in 1..=6 {
+                contract
+                    .insert(
+                        insert_tx.clone(),
+                        table_id,
+                        &key_row(k),
+                        &value_row(k * 10),
+                        &OptInsert::default(),
+                    )
+                    .await?;
+            }
+            contract.commit_tx(insert_tx).await?;
+            let (split, lower, upper) = PartitionRouter::new(meta_mgr.clone())
+                .split_rule_partition(&rule, &[b"4".to_vec()], "p1".to_string())?;
+
+            // Routed to the old partition before the switch, committed after.
+            let stale_update = contract.begin_tx().await?;
+            contract
+                .update(
+                    stale_update.clone(),
+                    table_id,
+                    &key_row(5),
+                    &Predicate::CNF(vec![]),
+                    &value_row(500),
+                    &OptUpdate::default(),
+                )
+                .await?;
+            let stale_insert = contract.begin_tx().await?;
+            contract
+                .insert(
+                    stale_insert.clone(),
+                    table_id,
+                    &key_row(7),
+                    &value_row(70),
+                    &OptInsert::default(),
+                )
+                .await?;
+
+            // `_alter_partition_rule` up to the switch.
+            let tables = contract.tables_bound_to_rule(rule.oid).await?;
+            contract.storage.raise_route_fence(&split)?;
+            contract.storage.drain_route_fence().await?;
+            let copied = contract.copy_moved_rows(&tables, &split, &[lower]).await?;
+            meta_mgr.update_partition_rule(&split).await?;
+
+            let err = contract.commit_tx(stale_update).await.unwrap_err();
+            assert_eq!(err.ec(), ErrorCode::Transaction);
+            let err = contract.commit_tx(stale_insert).await.unwrap_err();
+            assert_eq!(err.ec(), ErrorCode::Transaction);
+
+            // Routed to the new partition, committed before the catch-up.
+            let update_tx = contract.begin_tx().await?;
+            contract
+                .update(
+                    update_tx.clone(),
+                    table_id,
+                    &key_row(4),
+                    &Predicate::CNF(vec![]),
+                    &value_row(400),
+                    &OptUpdate::default(),
+                )
+                .await?;
+            contract.commit_tx(update_tx).await?;
+            let delete_tx = contract.begin_tx().await?;
+            let deleted = contract
+                .delete(
+                    delete_tx.clone(),
+                    table_id,
+                    &key_row(6),
+                    &Predicate::CNF(Vec::new()),
+                    &OptDelete::default(),
+                )
+                .await?;
+            assert_eq!(deleted, 1);
+            contract.commit_tx(delete_tx).await?;
+
+            contract
+                .catch_up_moved_rows(&tables, &split, &[lower], copied)
+                .await?;
+            contract.storage.lower_route_fence(rule.oid)?;
+
+            assert_eq!(contract.scan_partition(table_id, lower).await?.len(), 3);
+            assert_eq!(contract.scan_partition(table_id, upper).await?.len(), 2);
+            for k in 1..=3 {
+                let row = read_i32_value(&contract, table_id, k).await?;
+                assert_eq!(row, Some(datum(k * 10)));
+            }
+            assert_eq!(
+                read_i32_value(&contract, table_id, 4).await?,
+                Some(datum(400))
+            );
+            assert_eq!(
+                read_i32_value(&contract, table_id, 5).await?,
+                Some(datum(50))
+            );
+            assert_eq!(read_i32_value(&contract, table_id, 6).await?, None);
+            assert_eq!(read_i32_value(&contract, table_id, 7).await?, None);
+            Ok::<(), mudu::error::MuduError>(())
+        })
+        .unwrap();
+    }
+
     #[test]
     fn xcontract_delete_removes_existing_row() {
         block_on(async move {
@@ -789,6 +1152,122 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn xcontract_read_range_during_repartition_returns_each_row_once() {
+        block_on(async move {
+            let meta_mgr = Arc::new(TestMetaMgr::new());
+            let contract = WorkerXContract::with_log(meta_mgr.clone(), None)?;
+            let schema = test_schema();
+            let table_id = schema.id();
+            let ddl_tx = contract.begin_tx().await?;
+            contract.create_table(ddl_tx.clone(), &schema).await?;
+            contract.commit_tx(ddl_tx).await?;
+            let rule = PartitionRuleDesc::new_range(
+                "r".to_string(),
+                vec![TypeFamily::I32],
+                vec![RangePartitionDef::new(
+                    "p0".to_string(),
+                    PartitionBound::Unbounded,
+                    PartitionBound::Unbounded,
+                )],
+            );
+            meta_mgr.create_partition_rule(&rule).await?;
+            meta_mgr
+                .bind_table_partition(&TablePartitionBinding {
+                    table_id,
+                    rule_id: rule.oid,
+                    ref_attr_indices: vec![0],
+                })
+                .await?;
+            let insert_tx = contract.begin_tx().await?;
+            for k in 1..=6 {
+                contract
+                    .insert(
+                        insert_tx.clone(),
+                        table_id,
+                        &key_row(k),
+                        &value_row(k * 10),
+                        &OptInsert::default(),
+                    )
+                    .await?;
+            }
+            contract.commit_tx(insert_tx).await?;
+            let (split, lower, upper) = PartitionRouter::new(meta_mgr.clone())
+                .split_rule_partition(&rule, &[b"4".to_vec()], "p1".to_string())?;
+
+            async fn scan(contract: &WorkerXContract, table_id: OID) -> RS<Vec<TupleRow>> {
+                let read_tx = contract.begin_tx().await?;
+                let cursor = contract
+                    .read_range(
+                        read_tx.clone(),
+                        table_id,
+                        &RangeData::new(std::ops::Bound::Unbounded, std::ops::Bound::Unbounded),
+                        &Predicate::CNF(Vec::new()),
+                        &VecSelTerm::new(vec![0, 1]),
+                        &OptRead::default(),
+                    )
+                    .await?;
+                let mut rows = Vec::new();
+                while let Some(row) = cursor.next().await? {
+                    rows.push(row);
+                }
+                contract.abort_tx(read_tx).await?;
+                Ok(rows)
+            }
+
+            // Each step of `_alter_partition_rule`, with a scan in between.
+            let tables = contract.tables_bound_to_rule(rule.oid).await?;
+            let before = contract.read_route(table_id).await?.unwrap();
+            contract.storage.raise_route_fence(&split)?;
+            assert!(contract.read_route_changed(&before).await?);
+            contract.storage.drain_route_fence().await?;
+            let copied = contract.copy_moved_rows(&tables, &split, &[lower]).await?;
+            assert_eq!(contract.scan_partition(table_id, lower).await?.len(), 6);
+            assert_eq!(contract.scan_partition(table_id, upper).await?.len(), 3);
+            assert_eq!(scan(&contract, table_id).await?.len(), 6);
+
+            let fenced = contract.read_route(table_id).await?.unwrap();
+            meta_mgr.update_partition_rule(&split).await?;
+            assert!(contract.read_route_changed(&fenced).await?);
+            assert_eq!(scan(&contract, table_id).await?.len(), 6);
+
+            contract
+                .catch_up_moved_rows(&tables, &split, &[lower], copied)
+                .await?;
+            assert_eq!(scan(&contract, table_id).await?.len(), 6);
+            contract.storage.lower_route_fence(rule.oid)?;
+            assert_eq!(scan(&contract, table_id).await?.len(), 6);
+
+            // A write to a moving row is rejected from the fence on.
+            let (resplit, _, _) = PartitionRouter::new(meta_mgr.clone()).split_rule_partition(
+                &split,
+                &[b"2".to_vec()],
+                "p2".to_string(),
+            )?;
+            contract.storage.raise_route_fence(&resplit)?;
+            let update_tx = contract.begin_tx().await?;
+            contract
+                .update(
+                    update_tx.clone(),
+                    table_id,
+                    &key_row(3),
+                    &Predicate::CNF(vec![]),
+                    &value_row(300),
+                    &OptUpdate::default(),
+                )
+                .await?;
+            let err = contract.commit_tx(update_tx).await.unwrap_err();
+            assert_eq!(err.ec(), ErrorCode::Transaction);
+            contract.storage.lower_route_fence(rule.oid)?;
+            assert_eq!(
+                read_i32_value(&contract, table_id, 3).await?,
+                Some(datum(30))
+            );
+            Ok::<(), mudu::error::MuduError>(())
+        })
+        .unwrap();
+    }
+
     #[test]
     fn xcontract_read_range_returns_matching_rows() {
         block_on(async move {
@@ -1,25 +1,47 @@
 use super::*;
+use crate::contract::partition_rule::PartitionRuleDesc;
+use crate::contract::partition_rule_binding::PartitionPlacement;
 
 #[async_trait]
 impl XContract for WorkerXContract {
-    async fn create_table(&self, _tx_mgr: Arc<dyn TxMgr>, schema: &SchemaTable) -> RS<()> {
+    async fn create_table(&self, tx_mgr: Arc<dyn TxMgr>, schema: &SchemaTable) -> RS<()> {
+        self.ensure_writable(Some(tx_mgr.xid()))?;
         self.storage.create_table_async(schema).await
     }
 
-    async fn drop_table(&self, _tx_mgr: Arc<dyn TxMgr>, oid: OID) -> RS<()> {
+    async fn drop_table(&self, tx_mgr: Arc<dyn TxMgr>, oid: OID) -> RS<()> {
+        self.ensure_writable(Some(tx_mgr.xid()))?;
         self.storage.drop_table_async(oid).await
     }
 
     async fn alter_table(
         &self,
-        _tx_mgr: Arc<dyn TxMgr>,
-        _oid: OID,
-        _alter_table: &AlterTable,
+        tx_mgr: Arc<dyn TxMgr>,
+        oid: OID,
+        alter_table: &[AlterTable],
+    ) -> RS<()> {
+        self.ensure_writable(Some(tx_mgr.xid()))?;
+        self._alter_table(oid, alter_table).await
+    }
+
+    async fn alter_partition_rule(
+        &self,
+        tx_mgr: Arc<dyn TxMgr>,
+        rule: &PartitionRuleDesc,
+        placements: &[PartitionPlacement],
     ) -> RS<()> {
-        Err(mudu_error!(
-            ErrorCode::NotImplemented,
-            "alter table is not implemented"
-        ))
+        self.ensure_writable(Some(tx_mgr.xid()))?;
+        self._alter_partition_rule(rule, placements).await
+    }
+
+    async fn lock_constraint_value(
+        &self,
+        tx_mgr: Arc<dyn TxMgr>,
+        oid: OID,
+        value: Vec<u8>,
+    ) -> RS<()> {
+        self.lock_constraint_value_async(tx_mgr.as_ref(), oid, value)
+            .await
     }
 
     async fn begin_tx(&self) -> RS<Arc<dyn TxMgr>> {
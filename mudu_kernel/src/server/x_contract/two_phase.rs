@@ -0,0 +1,542 @@
+use super::utils::*;
+use super::*;
+use crate::wal::log_frame::frame_lsns;
+use crate::wal::lsn::LSN;
+use mudu_sys::time::{instant_now, Instant};
+use serde::{Deserialize, Serialize};
+
+/// Age after which a prepared transaction or an undelivered commit decision
+/// is taken over by the periodic resolution round. It matches the partition
+/// RPC timeout, so the round never races a live prepare or delivery that is
+/// still waiting for its answer.
+pub(crate) const TWO_PHASE_RESOLVE_DELAY: Duration = Duration::from_secs(10);
+
+/// A participant's prepared share of a cross-partition transaction, as
+/// recorded by a worker log checkpoint.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub(crate) struct PreparedTxRecord {
+    pub(crate) coordinator_worker_id: OID,
+    pub(crate) tx_id: u64,
+    pub(crate) writes: Vec<XLWrite>,
+}
+
+/// A coordinator commit decision that some participants have not
+/// acknowledged yet, as recorded by a worker log checkpoint.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub(crate) struct CommitDecisionRecord {
+    pub(crate) tx_id: u64,
+    pub(crate) participants: Vec<OID>,
+}
+
+/// Two-phase commit state of one worker, in both roles.
+///
+/// Participants key prepared transactions by `(coordinator_worker_id,
+/// tx_id)`: transaction ids are only unique per worker. Prepared and decision
+/// entries change only under the checkpoint gate, together with the WAL
+/// record that makes the change durable, so a checkpoint records exactly the
+/// state its sealed log produced.
+pub(crate) struct TwoPhaseState {
+    prepared: SMutex<BTreeMap<(OID, u64), (Vec<XLWrite>, Instant)>>,
+    decisions: SMutex<BTreeMap<u64, (Vec<OID>, Instant)>>,
+    /// Coordinator transactions between their first prepare and their
+    /// decision; a participant asking about one of them has to wait.
+    in_flight: SMutex<BTreeSet<u64>>,
+    /// Set once worker log recovery is done. Before that, a transaction
+    /// without a decision may still have one further down the log.
+    recovered: AtomicBool,
+}
+
+impl TwoPhaseState {
+    pub(crate) fn new() -> Self {
+        Self {
+            prepared: SMutex::new(BTreeMap::new()),
+            decisions: SMutex::new(BTreeMap::new()),
+            in_flight: SMutex::new(BTreeSet::new()),
+            recovered: AtomicBool::new(false),
+        }
+    }
+
+    /// The coordinator's answer to a participant holding `tx_id` prepared;
+    /// see `PartitionRpcRequest::QueryTxDecision`. Without a pending decision
+    /// naming the participant the transaction is presumed aborted.
+    fn decision(&self, tx_id: u64, participant_worker_id: OID) -> RS<Option<bool>> {
+        if !self.recovered.load(Ordering::Acquire) || self.in_flight.lock()?.contains(&tx_id) {
+            return Ok(None);
+        }
+        Ok(Some(self.decisions.lock()?.get(&tx_id).is_some_and(
+            |(participants, _)| participants.contains(&participant_worker_id),
+        )))
+    }
+
+    /// Drop `delivered` from the participants still owed the decision of
+    /// `tx_id`; returns true when that was the last one.
+    fn decision_delivered(&self, tx_id: u64, delivered: &[OID]) -> RS<bool> {
+        let mut decisions = self.decisions.lock()?;
+        let Some((participants, _)) = decisions.get_mut(&tx_id) else {
+            return Ok(false);
+        };
+        participants.retain(|participant| !delivered.contains(participant));
+        if participants.is_empty() {
+            decisions.remove(&tx_id);
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    pub(crate) fn prepared_records(&self) -> RS<Vec<PreparedTxRecord>> {
+        Ok(self
+            .prepared
+            .lock()?
+            .iter()
+            .map(
+                |((coordinator_worker_id, tx_id), (writes, _))| PreparedTxRecord {
+                    coordinator_worker_id: *coordinator_worker_id,
+                    tx_id: *tx_id,
+                    writes: writes.clone(),
+                },
+            )
+            .collect())
+    }
+
+    pub(crate) fn decision_records(&self) -> RS<Vec<CommitDecisionRecord>> {
+        Ok(self
+            .decisions
+            .lock()?
+            .iter()
+            .map(|(tx_id, (participants, _))| CommitDecisionRecord {
+                tx_id: *tx_id,
+                participants: participants.clone(),
+            })
+            .collect())
+    }
+}
+
+/// Commit-lock keys of a write set.
+fn write_lock_keys(writes: &[XLWrite]) -> Vec<(PhysicalRelationId, Vec<u8>)> {
+    writes
+        .iter()
+        .map(|write| {
+            (
+                PhysicalRelationId {
+                    table_id: write.table_id(),
+                    partition_id: write.partition_id(),
+                },
+                write.key().to_vec(),
+            )
+        })
+        .collect()
+}
+
+/// The rows a write set inserts or updates.
+fn write_puts(writes: &[XLWrite]) -> Vec<(PhysicalRelationId, &[u8])> {
+    writes
+        .iter()
+        .filter(|write| !matches!(write, XLWrite::Delete(_)))
+        .map(|write| {
+            (
+                PhysicalRelationId {
+                    table_id: write.table_id(),
+                    partition_id: write.partition_id(),
+                },
+                write.key(),
+            )
+        })
+        .collect()
+}
+
+/// A replayable entry applying `writes` as committed by `xid`.
+fn committed_writes_entry(xid: u64, writes: Vec<XLWrite>) -> XLEntry {
+    let mut ops = Vec::with_capacity(writes.len() + 2);
+    ops.push(TxOp::Begin);
+    ops.extend(writes.into_iter().map(TxOp::Write));
+    ops.push(TxOp::Commit);
+    XLEntry { xid, ops }
+}
+
+impl WorkerXContract {
+    /// Enqueues `entry` on the worker log without waiting for it to be
+    /// durable. Callers hold the checkpoint gate until the state change the
+    /// entry records is applied.
+    async fn enqueue_two_phase_entry(&self, entry: XLEntry) -> RS<Option<LSN>> {
+        let Some(log) = self.log_cloned()? else {
+            return Ok(None);
+        };
+        let frames = log.serialize_entry(&XLBatch::new(vec![entry]))?;
+        let lsns = frame_lsns(&frames)?;
+        Ok(Some(log.enqueue_group_commit(frames, lsns, false).await?))
+    }
+
+    async fn wait_two_phase_entry_durable(&self, lsn: Option<LSN>) -> RS<()> {
+        if let (Some(log), Some(lsn)) = (self.log_cloned()?, lsn) {
+            log.drive_group_commit_flush().await?;
+            log.wait_group_commit_advanced(lsn).await?;
+        }
+        Ok(())
+    }
+
+    /// Participant side of `PrepareTx`: lock the write set under the
+    /// coordinator's statement-lock token (re-entrant with the statement
+    /// locks it already holds here) and log it as prepared. The vote is yes
+    /// once the prepare record is durable.
+    pub(crate) async fn prepare_tx_async(
+        &self,
+        coordinator_worker_id: OID,
+        tx_id: u64,
+        writes: Vec<XLWrite>,
+    ) -> RS<()> {
+        let key = (coordinator_worker_id, tx_id);
+        // Checked before locking: the locks of the prepared transaction are
+        // held under the same token and must survive the rejection.
+        if self.two_phase.prepared.lock()?.contains_key(&key) {
+            return Err(mudu_error!(
+                ErrorCode::Transaction,
+                format!(
+                    "transaction {} of coordinator {} is already prepared",
+                    tx_id, coordinator_worker_id
+                )
+            ));
+        }
+        let lock_token = statement_lock_token(coordinator_worker_id, tx_id);
+        acquire_commit_locks(&self.tx_lock, lock_token, &write_lock_keys(&writes)).await?;
+        let result = async {
+            // Pinned below until the transaction is resolved.
+            let _route = self.storage.route_fence().enter().await;
+            self.check_fenced_routes(&write_puts(&writes)).await?;
+            let _gate = self.checkpoint_gate.enter().await;
+            let mut ops = Vec::with_capacity(writes.len() + 2);
+            ops.push(TxOp::Begin);
+            ops.extend(writes.iter().cloned().map(TxOp::Write));
+            ops.push(TxOp::Prepare {
+                coordinator_worker_id,
+            });
+            let lsn = self
+                .enqueue_two_phase_entry(XLEntry { xid: tx_id, ops })
+                .await?;
+            self.two_phase
+                .prepared
+                .lock()?
+                .insert(key, (writes, instant_now()));
+            self.storage
+                .route_fence()
+                .pin(coordinator_worker_id, tx_id)?;
+            Ok(lsn)
+        }
+        .await;
+        match result {
+            Ok(lsn) => self.wait_two_phase_entry_durable(lsn).await,
+            Err(err) => {
+                self.tx_lock.release_all(lock_token)?;
+                Err(err)
+            }
+        }
+    }
+
+    /// Participant side of `CommitPreparedTx` and `AbortPreparedTx`: log the
+    /// outcome, apply the prepared writes on commit, and release every lock
+    /// the coordinator's token holds here. Resolving a transaction that is
+    /// not prepared only releases those locks, which makes both messages
+    /// idempotent.
+    pub(crate) async fn resolve_prepared_tx_async(
+        &self,
+        coordinator_worker_id: OID,
+        tx_id: u64,
+        commit: bool,
+    ) -> RS<()> {
+        let result = async {
+            let _gate = self.checkpoint_gate.enter().await;
+            let Some((writes, _)) = self
+                .two_phase
+                .prepared
+                .lock()?
+                .remove(&(coordinator_worker_id, tx_id))
+            else {
+                return Ok(None);
+            };
+            let lsn = self
+                .enqueue_two_phase_entry(XLEntry {
+                    xid: tx_id,
+                    ops: vec![TxOp::Resolve {
+                        coordinator_worker_id,
+                        commit,
+                    }],
+                })
+                .await?;
+            if commit {
+                let keys = write_lock_keys(&writes);
+                // Replay upgrades rows prepared under a layout an ALTER TABLE
+                // retired since; the catalog and the open relations must not
+                // switch while it runs.
+                let _commit = self.storage.enter_commit().await;
+                self.storage
+                    .replay_batch(XLBatch::new(vec![committed_writes_entry(tx_id, writes)]))
+                    .await?;
+                self.ssi_record_applied_writes(&keys)?;
+            }
+            Ok(lsn)
+        }
+        .await;
+        self.storage
+            .route_fence()
+            .unpin(coordinator_worker_id, tx_id)?;
+        self.tx_lock
+            .release_all(statement_lock_token(coordinator_worker_id, tx_id))?;
+        // A commit is acknowledged only once its record is durable: after
+        // the acknowledgement the coordinator forgets the decision, and a
+        // prepare replayed without its outcome would be presumed aborted.
+        self.wait_two_phase_entry_durable(result?).await
+    }
+
+    /// Coordinator side of `QueryTxDecision`.
+    pub(crate) fn query_tx_decision(
+        &self,
+        tx_id: u64,
+        participant_worker_id: OID,
+    ) -> RS<Option<bool>> {
+        self.two_phase.decision(tx_id, participant_worker_id)
+    }
+
+    /// Marks coordinator transaction `tx_id` as deciding: participants asking
+    /// about it are told to wait until [`Self::end_two_phase_tx`].
+    pub(crate) fn begin_two_phase_tx(&self, tx_id: u64) -> RS<()> {
+        self.two_phase.in_flight.lock()?.insert(tx_id);
+        Ok(())
+    }
+
+    pub(crate) fn end_two_phase_tx(&self, tx_id: u64) -> RS<()> {
+        self.two_phase.in_flight.lock()?.remove(&tx_id);
+        Ok(())
+    }
+
+    /// Logs the commit decision of `tx_id` together with the coordinator's
+    /// own share `local_writes`. Called under the checkpoint gate; the local
+    /// writes are applied by the caller before the gate is released.
+    pub(crate) async fn log_commit_decision(
+        &self,
+        tx_id: u64,
+        local_writes: &[XLWrite],
+        participants: Vec<OID>,
+    ) -> RS<Option<LSN>> {
+        let mut ops = Vec::with_capacity(local_writes.len() + 2);
+        ops.push(TxOp::Begin);
+        ops.extend(local_writes.iter().cloned().map(TxOp::Write));
+        ops.push(TxOp::CommitDecision {
+            participants: participants.clone(),
+        });
+        let lsn = self
+            .enqueue_two_phase_entry(XLEntry { xid: tx_id, ops })
+            .await?;
+        self.two_phase
+            .decisions
+            .lock()?
+            .insert(tx_id, (participants, instant_now()));
+        Ok(lsn)
+    }
+
+    /// Sends the durable commit decision of `tx_id` to `participants`.
+    /// Participants that cannot be reached keep the decision pending for the
+    /// resolution round; once every participant acknowledged it, that is
+    /// logged so recovery stops redelivering it.
+    pub(crate) async fn deliver_commit_decision(
+        &self,
+        tx_id: u64,
+        participants: Vec<OID>,
+    ) -> RS<()> {
+        let mut delivered = Vec::with_capacity(participants.len());
+        for participant in participants {
+            match self
+                .remote_resolve_prepared_tx(participant, tx_id, true)
+                .await
+            {
+                Ok(()) => delivered.push(participant),
+                Err(err) => debug!(
+                    worker_id = self.worker_id,
+                    participant, tx_id, "commit decision delivery failed: {err}"
+                ),
+            }
+        }
+        let _gate = self.checkpoint_gate.enter().await;
+        if self.two_phase.decision_delivered(tx_id, &delivered)? {
+            self.enqueue_two_phase_entry(XLEntry {
+                xid: tx_id,
+                ops: vec![TxOp::DecisionDelivered],
+            })
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Best-effort abort of `tx_id` on `participants`, prepared or not: it
+    /// also releases the statement locks the transaction holds there. A
+    /// participant that misses it asks for the decision later.
+    pub(crate) async fn abort_two_phase_participants(&self, tx_id: u64, participants: &[OID]) {
+        for participant in participants {
+            if let Err(err) = self
+                .remote_resolve_prepared_tx(*participant, tx_id, false)
+                .await
+            {
+                debug!(
+                    worker_id = self.worker_id,
+                    participant, tx_id, "abort of prepared transaction failed: {err}"
+                );
+            }
+        }
+    }
+
+    /// One resolution round: redeliver commit decisions and settle prepared
+    /// transactions that are at least `min_age` old. Unreachable peers and
+    /// undecided transactions are left for the next round.
+    pub(crate) async fn resolve_two_phase_txs_async(&self, min_age: Duration) -> RS<()> {
+        let decisions: Vec<(u64, Vec<OID>)> = self
+            .two_phase
+            .decisions
+            .lock()?
+            .iter()
+            .filter(|(_, (_, since))| since.elapsed() >= min_age)
+            .map(|(tx_id, (participants, _))| (*tx_id, participants.clone()))
+            .collect();
+        for (tx_id, participants) in decisions {
+            self.deliver_commit_decision(tx_id, participants).await?;
+        }
+        let in_doubt: Vec<(OID, u64)> = self
+            .two_phase
+            .prepared
+            .lock()?
+            .iter()
+            .filter(|(_, (_, since))| since.elapsed() >= min_age)
+            .map(|(key, _)| *key)
+            .collect();
+        for (coordinator_worker_id, tx_id) in in_doubt {
+            match self
+                .remote_query_tx_decision(coordinator_worker_id, tx_id)
+                .await
+            {
+                Ok(Some(commit)) => {
+                    debug!(
+                        worker_id = self.worker_id,
+                        coordinator_worker_id, tx_id, commit, "resolving in-doubt transaction"
+                    );
+                    self.resolve_prepared_tx_async(coordinator_worker_id, tx_id, commit)
+                        .await?;
+                }
+                Ok(None) => {}
+                Err(err) => debug!(
+                    worker_id = self.worker_id,
+                    coordinator_worker_id, tx_id, "query of transaction decision failed: {err}"
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    /// Marks worker log recovery as done, so undecided transactions can be
+    /// presumed aborted, then settles whatever recovery left in doubt.
+    pub(crate) async fn recover_two_phase_txs_async(&self) -> RS<()> {
+        self.two_phase.recovered.store(true, Ordering::Release);
+        self.resolve_two_phase_txs_async(Duration::ZERO).await
+    }
+
+    /// Re-installs a prepared transaction found by recovery and takes its
+    /// commit locks again.
+    fn restore_prepared_tx(
+        &self,
+        coordinator_worker_id: OID,
+        tx_id: u64,
+        writes: Vec<XLWrite>,
+    ) -> RS<()> {
+        let lock_token = statement_lock_token(coordinator_worker_id, tx_id);
+        if !self
+            .tx_lock
+            .try_lock_some(lock_token, &write_lock_keys(&writes))?
+        {
+            return Err(mudu_error!(
+                ErrorCode::Internal,
+                format!(
+                    "recovered prepared transaction {} of coordinator {} conflicts with another lock",
+                    tx_id, coordinator_worker_id
+                )
+            ));
+        }
+        self.two_phase
+            .prepared
+            .lock()?
+            .insert((coordinator_worker_id, tx_id), (writes, instant_now()));
+        self.storage.route_fence().pin(coordinator_worker_id, tx_id)
+    }
+
+    pub(crate) fn restore_two_phase_records(
+        &self,
+        prepared: Vec<PreparedTxRecord>,
+        decisions: Vec<CommitDecisionRecord>,
+    ) -> RS<()> {
+        for record in prepared {
+            self.restore_prepared_tx(record.coordinator_worker_id, record.tx_id, record.writes)?;
+        }
+        let mut pending = self.two_phase.decisions.lock()?;
+        for record in decisions {
+            pending.insert(record.tx_id, (record.participants, instant_now()));
+        }
+        Ok(())
+    }
+
+    /// Rebuilds the two-phase state from a replayed batch and returns the
+    /// entries storage should apply: prepared writes are held back until
+    /// their commit outcome is replayed, the coordinator's decision entries
+    /// replay as committed.
+    pub(crate) fn replay_two_phase_entries(&self, batch: XLBatch) -> RS<XLBatch> {
+        let mut entries = Vec::with_capacity(batch.entries.len());
+        for entry in batch.entries {
+            let marker = entry.ops.iter().find_map(|op| match op {
+                TxOp::Prepare { .. }
+                | TxOp::Resolve { .. }
+                | TxOp::CommitDecision { .. }
+                | TxOp::DecisionDelivered => Some(op.clone()),
+                _ => None,
+            });
+            match marker {
+                Some(TxOp::Prepare {
+                    coordinator_worker_id,
+                }) => {
+                    let writes = entry
+                        .ops
+                        .into_iter()
+                        .filter_map(|op| match op {
+                            TxOp::Write(write) => Some(write),
+                            _ => None,
+                        })
+                        .collect();
+                    self.restore_prepared_tx(coordinator_worker_id, entry.xid, writes)?;
+                }
+                Some(TxOp::Resolve {
+                    coordinator_worker_id,
+                    commit,
+                }) => {
+                    let prepared = self
+                        .two_phase
+                        .prepared
+                        .lock()?
+                        .remove(&(coordinator_worker_id, entry.xid));
+                    self.tx_lock
+                        .release_all(statement_lock_token(coordinator_worker_id, entry.xid))?;
+                    self.storage
+                        .route_fence()
+                        .unpin(coordinator_worker_id, entry.xid)?;
+                    if let (Some((writes, _)), true) = (prepared, commit) {
+                        entries.push(committed_writes_entry(entry.xid, writes));
+                    }
+                }
+                Some(TxOp::CommitDecision { participants }) => {
+                    self.two_phase
+                        .decisions
+                        .lock()?
+                        .insert(entry.xid, (participants, instant_now()));
+                    entries.push(entry);
+                }
+                Some(TxOp::DecisionDelivered) => {
+                    self.two_phase.decisions.lock()?.remove(&entry.xid);
+                }
+                _ => entries.push(entry),
+            }
+        }
+        Ok(XLBatch::new(entries))
+    }
+}
@@ -1,4 +1,5 @@
 use super::*;
+use mudu_contract::tuple::nullable_tuple::is_null;
 use mudu_type::data_type::DataType;
 use mudu_type::data_type_function::{recv_binary, send_binary};
 use mudu_type::data_value::DataValue;
@@ -7,7 +8,7 @@ use mudu_type::type_family::TypeFamily;
 pub(crate) fn ensure_supported_predicate(predicate: &Predicate) -> RS<()> {
     match predicate {
         Predicate::CNF(items) | Predicate::DNF(items) if items.is_empty() => Ok(()),
-        Predicate::KeyPrefixEq(_) => Ok(()),
+        Predicate::KeyPrefixEq(_) | Predicate::IndexEq { .. } => Ok(()),
         Predicate::CNF(items) | Predicate::DNF(items) => {
             let _ = items
                 .iter()
@@ -25,7 +26,7 @@ pub(crate) fn ensure_supported_predicate(predicate: &Predicate) -> RS<()> {
 pub(crate) fn matches_predicate(
     desc: &TableDesc,
     key: &[u8],
-    _value: &[u8],
+    value: &[u8],
     predicate: &Predicate,
 ) -> RS<bool> {
     match predicate {
@@ -44,6 +45,23 @@ pub(crate) fn matches_predicate(
             }
             Ok(true)
         }
+        Predicate::IndexEq { prefix, .. } => {
+            for (attr, expected) in prefix {
+                let field = desc.get_attr(*attr);
+                let (tuple_desc, tuple) = match field.primary_index() {
+                    Some(_) => (desc.key_desc(), key),
+                    None => (desc.value_desc(), value),
+                };
+                if is_null(tuple, tuple_desc, field.datum_index())? {
+                    return Ok(false);
+                }
+                let actual = tuple_desc.get_field_desc(field.datum_index()).get(tuple)?;
+                if actual != expected.as_slice() {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        }
         Predicate::CNF(_) | Predicate::DNF(_) => Err(mudu_error!(
             ErrorCode::NotImplemented,
             "non-key predicates are not implemented in io_uring xcontract"
@@ -292,25 +310,39 @@ fn eval_delta_assignment(
 ) -> RS<DataBin> {
     let field = desc.get_attr(assign.attr);
     let index = field.datum_index();
-    let data_type = field.type_desc();
     let current_binary = match mudu_contract::tuple::nullable_tuple::read_value(
         &current.to_vec(),
         desc.value_desc(),
         index,
     )? {
-        NullableValue::Null => {
-            return Err(mudu_error!(
-                ErrorCode::InvalidTuple,
-                format!("arithmetic update on NULL column {}", field.name())
-            ));
-        }
-        NullableValue::Value(_) => desc
-            .value_desc()
-            .get_field_desc(index)
-            .get(current)?
-            .to_vec(),
+        NullableValue::Null => None,
+        NullableValue::Value(_) => Some(
+            desc.value_desc()
+                .get_field_desc(index)
+                .get(current)?
+                .to_vec(),
+        ),
     };
-    let current_value = recv_binary(&current_binary, data_type).map_err(|e| e.to_m_err())?;
+    eval_delta_value(current_binary.as_deref(), assign, desc)
+}
+
+/// Evaluate `col = col <+|-> operand` on `current`, the column value in the
+/// column's binary format (`None` for NULL), returning the new value in the
+/// same format.
+pub(crate) fn eval_delta_value(
+    current: Option<&[u8]>,
+    assign: &DeltaAssign,
+    desc: &TableDesc,
+) -> RS<DataBin> {
+    let field = desc.get_attr(assign.attr);
+    let data_type = field.type_desc();
+    let Some(current_binary) = current else {
+        return Err(mudu_error!(
+            ErrorCode::InvalidTuple,
+            format!("arithmetic update on NULL column {}", field.name())
+        ));
+    };
+    let current_value = recv_binary(current_binary, data_type).map_err(|e| e.to_m_err())?;
     let literal_value = recv_binary(&assign.literal, data_type).map_err(|e| e.to_m_err())?;
 
     // The conditional-restock op packs its own parameters
@@ -501,6 +533,7 @@ pub(crate) fn single_put_batch(xid: u64, key: Vec<u8>, value: Vec<u8>) -> XLBatc
                 tuple_id: 0,
                 key,
                 value,
+                layout_version: None,
             })),
             crate::wal::xl_entry::TxOp::Commit,
         ],
@@ -567,6 +600,11 @@ pub(crate) fn staged_overlay_in_bounds(
 /// fail genuine deadlocks instead of piling up latency.
 pub(crate) const STATEMENT_LOCK_TIMEOUT: Duration = Duration::from_millis(5000);
 
+/// Partition id of the lock relations of constraint values (see
+/// `WorkerXContract::lock_constraint_value_async`). It names no stored
+/// partition, so these locks never collide with row locks.
+pub(crate) const CONSTRAINT_LOCK_PARTITION_ID: OID = OID::MAX;
+
 /// Coordinator-scoped token identifying a transaction's statement-level
 /// locks on remote owner workers. Coordinator worker ids are random OIDs, so
 /// the xor with the (small) transaction xid stays unique in practice and
@@ -614,3 +652,24 @@ pub(crate) fn is_cross_partition_tx(
             != local_worker_id
     })
 }
+
+/// Serializable transactions are checked by the SSI tracker of the worker
+/// they run on, which only sees that worker's reads and commits; reading or
+/// writing a partition owned by another worker would silently weaken the
+/// guarantee, so it is rejected instead.
+pub(crate) fn serializable_remote_access_error() -> mudu::error::MuduError {
+    mudu_error!(
+        ErrorCode::NotImplemented,
+        "serializable transactions cannot access partitions owned by other workers"
+    )
+}
+
+/// Fails when serializable `tx` is about to read a partition owned by a
+/// remote worker (see `serializable_remote_access_error`).
+pub(crate) fn ensure_local_serializable_read(tx: &dyn TxMgr) -> RS<()> {
+    if tx.isolation_level() == IsolationLevel::Serializable {
+        Err(serializable_remote_access_error())
+    } else {
+        Ok(())
+    }
+}
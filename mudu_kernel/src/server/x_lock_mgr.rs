@@ -1,3 +1,4 @@
+use crate::server::metrics;
 use crate::x_engine::tx_mgr::PhysicalRelationId;
 use mudu::common::id::OID;
 use mudu::common::result::RS;
@@ -123,7 +124,8 @@ impl XLockMgr {
                 notify: ANotify::new(),
             })
         };
-        let deadline = instant_now() + timeout;
+        let started = instant_now();
+        let deadline = started + timeout;
         loop {
             let mut wakes = Vec::new();
             {
@@ -131,6 +133,7 @@ impl XLockMgr {
                 if try_acquire_locked(&mut state, oid, table_keys, Some(&waiter), &mut wakes) {
                     drop(state);
                     self.send_wakes(wakes);
+                    metrics::record_lock_wait(started.elapsed(), true);
                     return Ok(true);
                 }
                 enqueue_locked(&mut state, oid, table_keys, &waiter);
@@ -151,6 +154,7 @@ impl XLockMgr {
                 let wakes = dequeue_locked(&mut state, table_keys, waiter.seq);
                 drop(state);
                 self.send_wakes(wakes);
+                metrics::record_lock_wait(started.elapsed(), false);
                 return Ok(false);
             }
             let _ = mudu_sys::task::async_::timeout(deadline - now, waiter.notify.notified()).await;
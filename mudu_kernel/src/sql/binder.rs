@@ -1,45 +1,73 @@
+use crate::command::constraint::{constrained_attrs, has_constraints, is_referenced};
 use crate::command::fs_hook::has_fs_bound_columns;
 use crate::contract::fs_type::{FsColumnBinding, FsTypeKind};
 use crate::contract::meta_mgr::MetaMgr;
-use crate::contract::partition_rule::{PartitionBound, PartitionRuleDesc, RangePartitionDef};
+use crate::contract::partition_rule::{
+    PartitionBound, PartitionRuleDesc, PartitionRuleKind, RangePartitionDef,
+};
 use crate::contract::partition_rule_binding::{PartitionPlacement, TablePartitionBinding};
-use crate::contract::schema_column::SchemaColumn;
+use crate::contract::schema_check::SchemaCheck;
+use crate::contract::schema_column::{ColumnDefault, SchemaColumn};
+use crate::contract::schema_foreign_key::{ReferentialAction, SchemaForeignKey};
+use crate::contract::schema_index::SchemaIndex;
 use crate::contract::schema_table::SchemaTable;
 use crate::contract::table_desc::TableDesc;
+use crate::contract::table_info::TableInfo;
+use crate::server::partition_router::{is_hash_key_type, PartitionRouter};
 use crate::sql::bound_stmt::{
-    BoundCommand, BoundCopyFrom, BoundCopyTo, BoundCreateFsType, BoundCreatePartitionPlacement,
-    BoundCreatePartitionRule, BoundCreateTable, BoundDropTable, BoundDropType, BoundStmt,
+    BoundAlterPartitionRule, BoundAlterTable, BoundCommand, BoundCopyFrom, BoundCopyTo,
+    BoundCreateFsType, BoundCreatePartitionPlacement, BoundCreatePartitionRule, BoundCreateTable,
+    BoundDropTable, BoundDropType, BoundExplain, BoundQuery, BoundRowOrder, BoundSelectColumn,
+    BoundSelectItem, BoundStmt,
 };
 use crate::sql::bound_template::{
-    template_from_expr, BoundTemplate, DeleteTemplate, InsertRowTemplate, InsertTemplate,
-    ParamSlot, PredicateTemplate, ResidualTemplate, SelectTemplate, SetValueTemplate, SlotRecorder,
-    StmtTemplate, TemplateDatum, UpdateTemplate,
+    template_from_expr, BoundTemplate, ConditionTemplate, ConflictValueTemplate, DeleteTemplate,
+    HavingTemplate, InsertRowTemplate, InsertTemplate, JoinConditionTemplate, JoinSelectTemplate,
+    JoinTemplate, OnConflictTemplate, ParamSlot, PredicateTemplate, ResidualTemplate,
+    SelectTemplate, SetValueTemplate, SlotRecorder, StmtTemplate, TemplateDatum, UpdateTemplate,
 };
 use crate::sql::copy_layout::CopyLayout;
+use crate::sql::expr_binding::{bind_check, ExprBinder};
+use crate::sql::join_scope::JoinScope;
+use crate::sql::select_projection::{attr_type, bind_returning, item_datum_desc, JsonColumns};
 use crate::sql::value_codec::ValueCodec;
-use crate::x_engine::api::DeltaOp;
+use crate::x_engine::api::{AlterTable, DeltaOp};
+use mudu::common::id::{AttrIndex, OID};
 use mudu::common::result::RS;
 use mudu::error::ErrorCode as ER;
 use mudu::mudu_error;
 use mudu_contract::database::sql_params::SQLParams;
+use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
 use mudu_type::data_type::DataType;
 use mudu_type::data_type_info::DataTypeInfo;
 use mudu_type::type_family::TypeFamily;
+use sql_parser::ast::column_def::ColumnDefault as AstColumnDefault;
 use sql_parser::ast::expr_compare::ExprCompare;
 use sql_parser::ast::expr_item::{ExprItem, ExprValue};
 use sql_parser::ast::expr_literal::ExprLiteral;
-use sql_parser::ast::expr_operator::{Arithmetic, ValueCompare};
+use sql_parser::ast::expr_name::ExprName;
+use sql_parser::ast::expr_operator::{Arithmetic, LogicalConnective, ValueCompare};
 use sql_parser::ast::expression::ExprType;
+use sql_parser::ast::stmt_alter_partition_rule::{
+    StmtAlterPartitionRule, StmtAlterPartitionRuleAction,
+};
+use sql_parser::ast::stmt_alter_table::{AlterTableAction, StmtAlterTable};
 use sql_parser::ast::stmt_create_fs_type::{FsTypeKind as AstFsTypeKind, StmtCreateFsType};
+use sql_parser::ast::stmt_create_index::StmtCreateIndex;
 use sql_parser::ast::stmt_create_partition_placement::StmtCreatePartitionPlacement;
-use sql_parser::ast::stmt_create_partition_rule::{StmtCreatePartitionRule, StmtPartitionBound};
+use sql_parser::ast::stmt_create_partition_rule::{
+    StmtCreatePartitionRule, StmtPartitionBound, StmtPartitionScheme,
+};
 use sql_parser::ast::stmt_create_table::StmtCreateTable;
 use sql_parser::ast::stmt_delete::StmtDelete;
+use sql_parser::ast::stmt_drop_index::StmtDropIndex;
 use sql_parser::ast::stmt_drop_table::StmtDropTable;
 use sql_parser::ast::stmt_drop_type::StmtDropType;
-use sql_parser::ast::stmt_insert::StmtInsert;
+use sql_parser::ast::stmt_insert::{OnConflict, OnConflictAction, StmtInsert};
+use sql_parser::ast::stmt_select::StmtSelect;
 use sql_parser::ast::stmt_type::{StmtCommand, StmtType};
-use sql_parser::ast::stmt_update::{AssignedValue, StmtUpdate};
+use sql_parser::ast::stmt_update::{AssignedValue, Assignment, StmtUpdate};
+use sql_parser::ast::table_constraint::ReferentialAction as AstReferentialAction;
 use std::ops::Bound;
 use std::sync::Arc;
 
@@ -68,13 +96,30 @@ impl Binder {
         match stmt {
             StmtType::Select(stmt) => {
                 let mut recorder = SlotRecorder::default();
-                let template = self.bind_select_template(stmt, &mut recorder).await?;
-                BoundTemplate::new(StmtTemplate::Select(template), recorder.into_slots())
-                    .fill(params)
+                let template = self.bind_query_template(stmt, &mut recorder).await?;
+                BoundTemplate::new(template, recorder.into_slots()).fill(params)
             }
             StmtType::Command(command) => Ok(BoundStmt::Command(
                 self.bind_command_ref(command, params).await?,
             )),
+            StmtType::Explain(explain) => {
+                let mut recorder = SlotRecorder::default();
+                let template = self
+                    .bind_query_template(explain.select(), &mut recorder)
+                    .await?;
+                let BoundStmt::Query(query) =
+                    BoundTemplate::new(template, recorder.into_slots()).fill(params)?
+                else {
+                    return Err(mudu_error!(
+                        ER::InvalidState,
+                        "EXPLAIN bound to a non-query statement"
+                    ));
+                };
+                Ok(BoundStmt::Query(BoundQuery::Explain(BoundExplain {
+                    analyze: explain.analyze(),
+                    query: Box::new(query),
+                })))
+            }
         }
     }
 
@@ -88,9 +133,7 @@ impl Binder {
     pub async fn bind_template(&self, stmt: &StmtType) -> RS<Option<BoundTemplate>> {
         let mut recorder = SlotRecorder::default();
         let stmt = match stmt {
-            StmtType::Select(stmt) => {
-                StmtTemplate::Select(self.bind_select_template(stmt, &mut recorder).await?)
-            }
+            StmtType::Select(stmt) => self.bind_query_template(stmt, &mut recorder).await?,
             StmtType::Command(StmtCommand::Insert(stmt)) => {
                 StmtTemplate::Insert(self.bind_insert_template(stmt, &mut recorder).await?)
             }
@@ -186,12 +229,24 @@ impl Binder {
             StmtCommand::CreatePartitionRule(stmt) => Ok(BoundCommand::CreatePartitionRule(
                 self.bind_create_partition_rule(stmt)?,
             )),
+            StmtCommand::AlterPartitionRule(stmt) => Ok(BoundCommand::AlterPartitionRule(
+                self.bind_alter_partition_rule(stmt).await?,
+            )),
             StmtCommand::CreateTable(stmt) => Ok(BoundCommand::CreateTable(
                 self.bind_create_table(stmt).await?,
             )),
             StmtCommand::DropTable(stmt) => {
                 Ok(BoundCommand::DropTable(self.bind_drop_table(stmt).await?))
             }
+            StmtCommand::AlterTable(stmt) => {
+                Ok(BoundCommand::AlterTable(self.bind_alter_table(stmt).await?))
+            }
+            StmtCommand::CreateIndex(stmt) => Ok(BoundCommand::AlterTable(
+                self.bind_create_index(stmt).await?,
+            )),
+            StmtCommand::DropIndex(stmt) => {
+                Ok(BoundCommand::AlterTable(self.bind_drop_index(stmt).await?))
+            }
             StmtCommand::CreateFsType(stmt) => {
                 Ok(BoundCommand::CreateFsType(Self::bind_create_fs_type(&stmt)))
             }
@@ -206,24 +261,268 @@ impl Binder {
         }
     }
 
+    async fn bind_query_template(
+        &self,
+        stmt: &StmtSelect,
+        recorder: &mut SlotRecorder,
+    ) -> RS<StmtTemplate> {
+        if stmt.get_joins().is_empty() {
+            Ok(StmtTemplate::Select(
+                self.bind_select_template(stmt, recorder).await?,
+            ))
+        } else {
+            Ok(StmtTemplate::Join(
+                self.bind_join_select_template(stmt, recorder).await?,
+            ))
+        }
+    }
+
+    /// Binds a `SELECT` over joined tables. Placeholders are recorded in the
+    /// `ON` conditions in join order, then in `WHERE`.
+    async fn bind_join_select_template(
+        &self,
+        stmt: &StmtSelect,
+        recorder: &mut SlotRecorder,
+    ) -> RS<JoinSelectTemplate> {
+        if !stmt.get_group_by().is_empty() || !stmt.get_having().is_empty() {
+            return Err(mudu_error!(
+                ER::NotImplemented,
+                "GROUP BY and HAVING over joined tables are not implemented"
+            ));
+        }
+        if !stmt.get_where_condition().is_empty() {
+            return Err(mudu_error!(
+                ER::NotImplemented,
+                "WHERE over joined tables supports only comparisons connected by AND"
+            ));
+        }
+        let scope = JoinScope::from_select(self.meta_mgr.as_ref(), stmt).await?;
+        let (projection, tuple_desc) = scope.bind_projection(stmt.get_select_term_list())?;
+        let (hidden, order_by) =
+            scope.bind_order_by(stmt.get_order_by(), &projection, &tuple_desc)?;
+        let mut joins = Vec::with_capacity(stmt.get_joins().len());
+        for (index, join) in stmt.get_joins().iter().enumerate() {
+            let table_desc = self.get_table_by_name(join.table_name()).await?;
+            // ON may only reference this table and the tables before it.
+            let visible = scope.offset(index + 2);
+            joins.push(JoinTemplate {
+                kind: join.kind(),
+                table_id: table_desc.id(),
+                on: Self::bind_join_conditions(&scope, join.on_predicate(), visible, recorder)?,
+            });
+        }
+        let filter = Self::bind_join_conditions(
+            &scope,
+            stmt.get_where_predicate(),
+            scope.width(),
+            recorder,
+        )?;
+        let table_desc = self.get_table_by_name(stmt.get_table_reference()).await?;
+        let (predicate, residual, filter) = self.split_driving_conditions(&table_desc, filter)?;
+        Ok(JoinSelectTemplate {
+            table_id: table_desc.id(),
+            predicate,
+            residual,
+            joins,
+            projection,
+            tuple_desc,
+            filter,
+            hidden,
+            order: BoundRowOrder {
+                order_by,
+                offset: stmt.get_offset().unwrap_or(0),
+                limit: stmt.get_limit(),
+            },
+        })
+    }
+
+    fn bind_join_conditions(
+        scope: &JoinScope,
+        predicates: &[ExprCompare],
+        visible: usize,
+        recorder: &mut SlotRecorder,
+    ) -> RS<Vec<JoinConditionTemplate>> {
+        let resolve = |name: &ExprName| -> RS<usize> {
+            let pos = scope.resolve(name)?;
+            if pos >= visible {
+                return Err(mudu_error!(
+                    ER::Parse,
+                    format!(
+                        "column {} is not visible in this join condition",
+                        name.name()
+                    )
+                ));
+            }
+            Ok(pos)
+        };
+        let mut conditions = Vec::with_capacity(predicates.len());
+        for predicate in predicates {
+            let op = *predicate.op();
+            let condition = match (predicate.left(), predicate.right()) {
+                (ExprItem::ItemName(left), ExprItem::ItemName(right)) => {
+                    let left = resolve(left)?;
+                    let right = resolve(right)?;
+                    let (_, left_type, _) = scope.column(left)?;
+                    let (_, right_type, _) = scope.column(right)?;
+                    if left_type.type_family() != right_type.type_family() {
+                        return Err(mudu_error!(
+                            ER::NotImplemented,
+                            "comparing columns of different types is not implemented"
+                        ));
+                    }
+                    JoinConditionTemplate::Column { left, op, right }
+                }
+                (ExprItem::ItemName(name), ExprItem::ItemValue(value)) => {
+                    Self::bind_join_literal(scope, resolve(name)?, op, value, recorder)?
+                }
+                (ExprItem::ItemValue(value), ExprItem::ItemName(name)) => Self::bind_join_literal(
+                    scope,
+                    resolve(name)?,
+                    Self::reverse_compare(op),
+                    value,
+                    recorder,
+                )?,
+                _ => {
+                    return Err(mudu_error!(
+                        ER::NotImplemented,
+                        "only column/literal and column/column join predicates are supported"
+                    ))
+                }
+            };
+            conditions.push(condition);
+        }
+        Ok(conditions)
+    }
+
+    /// Moves the `WHERE` comparisons of a first-table column with a literal
+    /// out of the join filter: key columns drive the access to the first
+    /// table the way they do for a single-table `SELECT`, and the others
+    /// filter its rows before they are joined. The first table's columns
+    /// lead the joined row, so their positions are their attribute indexes.
+    ///
+    /// A comparison the key access cannot take (`<>`, NULL, a second bound
+    /// on the same side, keys that are not a left prefix) stays in the
+    /// filter of the joined rows.
+    fn split_driving_conditions(
+        &self,
+        table_desc: &TableDesc,
+        filter: Vec<JoinConditionTemplate>,
+    ) -> RS<(
+        PredicateTemplate,
+        Vec<ResidualTemplate>,
+        Vec<JoinConditionTemplate>,
+    )> {
+        let width = table_desc.fields().len();
+        let mut conjuncts = CompareConjuncts::new();
+        let mut key_conditions = Vec::new();
+        let mut rest = Vec::with_capacity(filter.len());
+        for condition in filter {
+            let JoinConditionTemplate::Literal { pos, op, literal } = &condition else {
+                rest.push(condition);
+                continue;
+            };
+            if *pos >= width {
+                rest.push(condition);
+                continue;
+            }
+            let usable = match table_desc.get_attr(*pos).primary_index() {
+                None => true,
+                Some(_) => {
+                    let taken = match op {
+                        ValueCompare::EQ => conjuncts.eq_items.iter().any(|(a, _)| a == pos),
+                        ValueCompare::GE | ValueCompare::GT => {
+                            !matches!(conjuncts.start, Bound::Unbounded)
+                        }
+                        ValueCompare::LE | ValueCompare::LT => {
+                            !matches!(conjuncts.end, Bound::Unbounded)
+                        }
+                        ValueCompare::NE => true,
+                    };
+                    !taken && !matches!(literal, TemplateDatum::Const(None))
+                }
+            };
+            if !usable {
+                rest.push(condition);
+                continue;
+            }
+            Self::push_compare_conjunct(table_desc, *pos, *op, literal.clone(), &mut conjuncts)?;
+            if table_desc.get_attr(*pos).primary_index().is_some() {
+                key_conditions.push(condition);
+            }
+        }
+        if key_conditions.is_empty() {
+            return Ok((PredicateTemplate::True, conjuncts.residual, rest));
+        }
+        match self.combine_key_predicate_template(
+            table_desc,
+            conjuncts.eq_items,
+            conjuncts.start,
+            conjuncts.end,
+        ) {
+            Ok(predicate) => Ok((predicate, conjuncts.residual, rest)),
+            Err(_) => {
+                // The key comparisons cannot form one access path; the
+                // first table is scanned and they filter the joined rows.
+                rest.extend(key_conditions);
+                Ok((PredicateTemplate::True, conjuncts.residual, rest))
+            }
+        }
+    }
+
+    fn bind_join_literal(
+        scope: &JoinScope,
+        pos: usize,
+        op: ValueCompare,
+        value: &ExprValue,
+        recorder: &mut SlotRecorder,
+    ) -> RS<JoinConditionTemplate> {
+        let (_, data_type, _) = scope.column(pos)?;
+        Ok(JoinConditionTemplate::Literal {
+            pos,
+            op,
+            literal: template_from_expr(value, data_type, recorder)?,
+        })
+    }
+
     async fn bind_select_template(
         &self,
-        stmt: &sql_parser::ast::stmt_select::StmtSelect,
+        stmt: &StmtSelect,
         recorder: &mut SlotRecorder,
     ) -> RS<SelectTemplate> {
         let table_desc = self.get_table_by_name(stmt.get_table_reference()).await?;
-        let (select_items, tuple_desc) = crate::sql::select_projection::bind_select_items(
+        let mut list = crate::sql::select_projection::bind_select_list(&table_desc, stmt)?;
+        let (predicate, residual, conditions) = self.bind_where_template(
             &table_desc,
-            stmt.get_select_term_list(),
+            stmt.get_where_predicate(),
+            stmt.get_where_condition(),
+            &mut list.json_columns,
+            recorder,
         )?;
-        let (predicate, residual) =
-            self.bind_predicate_template(&table_desc, stmt.get_where_predicate(), recorder)?;
+        // HAVING placeholders follow the WHERE placeholders.
+        let mut having = Vec::with_capacity(list.having.len());
+        for (pos, op, value, data_type) in &list.having {
+            having.push(HavingTemplate {
+                pos: *pos,
+                op: *op,
+                literal: template_from_expr(value, data_type, recorder)?,
+            });
+        }
         Ok(SelectTemplate {
             table_id: table_desc.id(),
-            select_items,
-            tuple_desc,
+            select_items: list.items,
+            tuple_desc: list.tuple_desc,
             predicate,
             residual,
+            conditions,
+            group_by: list.group_by,
+            hidden_items: list.hidden_items,
+            having,
+            order: BoundRowOrder {
+                order_by: list.order_by,
+                offset: stmt.get_offset().unwrap_or(0),
+                limit: stmt.get_limit(),
+            },
+            json_columns: list.json_columns.into_columns(),
             has_fs_columns: has_fs_bound_columns(&table_desc),
         })
     }
@@ -244,12 +543,13 @@ impl Binder {
             .map(|index| index + value_offset)
             .collect();
         columns.append(&mut value_columns);
-        let schema = SchemaTable::new(
+        let mut schema = SchemaTable::new(
             stmt.table_name().clone(),
             columns,
             key_indices,
             value_indices,
         );
+        self.bind_table_constraints(&stmt, &mut schema).await?;
         let partition_binding = if let Some(partition) = stmt.partition() {
             let rule = self
                 .meta_mgr
@@ -283,6 +583,32 @@ impl Binder {
                     format!("partition rule {} has no partitions", partition.rule_name())
                 ));
             }
+            if let PartitionRuleKind::Hash { columns } = &rule.kind {
+                if columns.len() != ref_attr_indices.len() {
+                    return Err(mudu_error!(
+                        ER::Parse,
+                        format!(
+                            "hash partition rule {} expects {} reference columns",
+                            partition.rule_name(),
+                            columns.len()
+                        )
+                    ));
+                }
+                if let Some(column) = ref_attr_indices
+                    .iter()
+                    .map(|attr| &schema.columns()[*attr])
+                    .find(|column| !is_hash_key_type(column.type_id()))
+                {
+                    return Err(mudu_error!(
+                        ER::NotImplemented,
+                        format!(
+                            "hash partition key column {} of type {:?} is not supported",
+                            column.get_name(),
+                            column.type_id()
+                        )
+                    ));
+                }
+            }
             Some(TablePartitionBinding {
                 table_id: schema.id(),
                 rule_id: rule.oid,
@@ -297,58 +623,213 @@ impl Binder {
         })
     }
 
+    // UNIQUE constraints become unique indexes. A FOREIGN KEY gets an index
+    // over its referencing columns, which finds the referencing rows when a
+    // parent row is deleted. A CHECK constraint is stored as written.
+    async fn bind_table_constraints(
+        &self,
+        stmt: &StmtCreateTable,
+        schema: &mut SchemaTable,
+    ) -> RS<()> {
+        for unique in stmt.unique_constraints() {
+            let column_oids = constraint_column_oids(schema, unique.columns())?;
+            let name = match unique.name() {
+                Some(name) => name.to_string(),
+                None => constraint_index_name(schema.table_name(), unique.columns(), "key"),
+            };
+            self.check_constraint_index_name(schema, &name).await?;
+            let mut index = SchemaIndex::new(name, column_oids);
+            index.set_unique(true);
+            schema.add_index(index);
+        }
+        for foreign_key in stmt.foreign_keys() {
+            let column_oids = constraint_column_oids(schema, foreign_key.columns())?;
+            let (ref_table_oid, ref_columns) = if foreign_key.ref_table() == schema.table_name() {
+                (
+                    schema.id(),
+                    referenced_columns_of_schema(schema, foreign_key.ref_columns())?,
+                )
+            } else {
+                let parent = self.get_table_by_name(foreign_key.ref_table()).await?;
+                (
+                    parent.id(),
+                    referenced_columns_of_table(&parent, foreign_key.ref_columns())?,
+                )
+            };
+            for (oid, (_, ref_type)) in column_oids.iter().zip(ref_columns.iter()) {
+                let column = schema
+                    .columns()
+                    .iter()
+                    .find(|column| column.get_oid() == *oid)
+                    .ok_or_else(|| mudu_error!(ER::EntityNotFound))?;
+                if column.type_id() != *ref_type {
+                    return Err(mudu_error!(
+                        ER::InvalidType,
+                        format!(
+                            "foreign key column {} does not match the type of the referenced column",
+                            column.get_name()
+                        )
+                    ));
+                }
+            }
+            let name = match foreign_key.name() {
+                Some(name) => name.to_string(),
+                None => constraint_index_name(schema.table_name(), foreign_key.columns(), "fkey"),
+            };
+            self.check_constraint_index_name(schema, &name).await?;
+            let index = SchemaIndex::new(name.clone(), column_oids.clone());
+            let on_delete = match foreign_key.on_delete() {
+                AstReferentialAction::Restrict => ReferentialAction::Restrict,
+                AstReferentialAction::Cascade => ReferentialAction::Cascade,
+            };
+            schema.add_foreign_key(SchemaForeignKey::new(
+                name,
+                column_oids,
+                index.id(),
+                ref_table_oid,
+                ref_columns.into_iter().map(|(oid, _)| oid).collect(),
+                on_delete,
+            ));
+            schema.add_index(index);
+        }
+        if stmt.check_constraints().is_empty() {
+            return Ok(());
+        }
+        // Binding the conditions now rejects a bad one before the table
+        // exists; the catalog keeps their text.
+        let desc = TableInfo::new(schema.clone())?.table_desc()?;
+        for check in stmt.check_constraints() {
+            let (_, attrs) = bind_check(&desc, check.condition())?;
+            let taken = |name: &str| schema.checks().iter().any(|check| check.name() == name);
+            let name = match check.name() {
+                Some(name) if taken(name) => {
+                    return Err(mudu_error!(
+                        ER::EntityAlreadyExists,
+                        format!("check constraint {} already exists", name)
+                    ));
+                }
+                Some(name) => name.to_string(),
+                None => {
+                    let columns: Vec<String> = attrs
+                        .iter()
+                        .map(|attr| desc.get_attr(*attr).name().clone())
+                        .collect();
+                    let base = constraint_index_name(schema.table_name(), &columns, "check");
+                    let mut name = base.clone();
+                    let mut ordinal = 1;
+                    while taken(&name) {
+                        ordinal += 1;
+                        name = format!("{}{}", base, ordinal);
+                    }
+                    name
+                }
+            };
+            let column_oids = attrs.iter().map(|attr| desc.get_attr(*attr).id()).collect();
+            schema.add_check(SchemaCheck::new(
+                name,
+                check.text().to_string(),
+                column_oids,
+            ));
+        }
+        Ok(())
+    }
+
+    // Index names are unique across tables.
+    async fn check_constraint_index_name(&self, schema: &SchemaTable, name: &str) -> RS<()> {
+        if schema.index_by_name(name).is_some() || self.find_index_owner(name).await?.is_some() {
+            return Err(mudu_error!(
+                ER::EntityAlreadyExists,
+                format!("index {} already exists", name)
+            ));
+        }
+        Ok(())
+    }
+
     fn bind_create_partition_rule(
         &self,
         stmt: StmtCreatePartitionRule,
     ) -> RS<BoundCreatePartitionRule> {
-        let partitions = stmt
-            .partitions()
-            .iter()
-            .map(|partition| {
-                Ok(RangePartitionDef::new(
-                    partition.name().to_string(),
-                    Self::bind_partition_bound(partition.start()),
-                    Self::bind_partition_bound(partition.end()),
-                ))
-            })
-            .collect::<RS<Vec<_>>>()?;
-        Ok(BoundCreatePartitionRule {
-            rule: PartitionRuleDesc::new_range(
-                stmt.rule_name().to_string(),
-                Self::infer_partition_rule_key_types(stmt.partitions())?,
-                partitions,
-            ),
-        })
+        let rule_name = stmt.rule_name().to_string();
+        let rule = match stmt.scheme() {
+            StmtPartitionScheme::Range(partitions) => {
+                let bounds = partitions
+                    .iter()
+                    .flat_map(|partition| [partition.start(), partition.end()])
+                    .filter_map(|bound| match bound {
+                        StmtPartitionBound::Unbounded => None,
+                        StmtPartitionBound::Value(values) => Some(values.as_slice()),
+                    });
+                let key_types = Self::infer_partition_rule_key_types(bounds, "bound")?;
+                let partitions = partitions
+                    .iter()
+                    .map(|partition| {
+                        RangePartitionDef::new(
+                            partition.name().to_string(),
+                            Self::bind_partition_bound(partition.start()),
+                            Self::bind_partition_bound(partition.end()),
+                        )
+                    })
+                    .collect();
+                PartitionRuleDesc::new_range(rule_name, key_types, partitions)
+            }
+            StmtPartitionScheme::Hash {
+                columns,
+                partition_count,
+            } => {
+                if *partition_count == 0 {
+                    return Err(mudu_error!(
+                        ER::Parse,
+                        "HASH partition rule must have at least one partition"
+                    ));
+                }
+                PartitionRuleDesc::new_hash(rule_name, columns.clone(), *partition_count as usize)
+            }
+            StmtPartitionScheme::List(partitions) => {
+                let values = partitions
+                    .iter()
+                    .flat_map(|partition| partition.values())
+                    .map(|tuple| tuple.as_slice());
+                let key_types = Self::infer_partition_rule_key_types(values, "value")?;
+                let partitions = partitions
+                    .iter()
+                    .map(|partition| {
+                        RangePartitionDef::new_list(
+                            partition.name().to_string(),
+                            partition.values().to_vec(),
+                        )
+                    })
+                    .collect();
+                PartitionRuleDesc::new_list(rule_name, key_types, partitions)
+            }
+        };
+        Ok(BoundCreatePartitionRule { rule })
     }
 
-    fn infer_partition_rule_key_types(
-        partitions: &[sql_parser::ast::stmt_create_partition_rule::StmtRangePartition],
+    // Infer the key column types of a rule from the textual key tuples of its
+    // range bounds or list values; `what` names those tuples in errors.
+    fn infer_partition_rule_key_types<'a>(
+        tuples: impl Iterator<Item = &'a [Vec<u8>]>,
+        what: &str,
     ) -> RS<Vec<TypeFamily>> {
         let mut width = None;
         let mut type_slots: Vec<InferredKeyType> = Vec::new();
 
-        for partition in partitions {
-            for bound in [partition.start(), partition.end()] {
-                let values = match bound {
-                    StmtPartitionBound::Unbounded => continue,
-                    StmtPartitionBound::Value(values) => values,
-                };
-                if let Some(expected) = width {
-                    if expected != values.len() {
-                        return Err(mudu_error!(
-                            ER::Parse,
-                            "partition bound width mismatch in CREATE PARTITION RULE"
-                        ));
-                    }
-                } else {
-                    width = Some(values.len());
-                    type_slots = vec![InferredKeyType::I64; values.len()];
+        for values in tuples {
+            if let Some(expected) = width {
+                if expected != values.len() {
+                    return Err(mudu_error!(
+                        ER::Parse,
+                        format!("partition {} width mismatch in CREATE PARTITION RULE", what)
+                    ));
                 }
+            } else {
+                width = Some(values.len());
+                type_slots = vec![InferredKeyType::I64; values.len()];
+            }
 
-                for (index, raw) in values.iter().enumerate() {
-                    let next = infer_textual_value_type(raw)?;
-                    type_slots[index] = type_slots[index].merge(next);
-                }
+            for (index, raw) in values.iter().enumerate() {
+                let next = infer_textual_value_type(raw)?;
+                type_slots[index] = type_slots[index].merge(next);
             }
         }
 
@@ -394,21 +875,83 @@ impl Binder {
                         )
                     )
                 })?;
-            let worker_id = placement.worker_id().parse::<u128>().map_err(|e| {
-                mudu_error!(
-                    ER::Parse,
-                    format!("invalid worker id {}", placement.worker_id()),
-                    e
-                )
-            })?;
             placements.push(PartitionPlacement {
                 partition_id: partition.partition_id,
-                worker_id,
+                worker_id: Self::bind_worker_id(placement.worker_id())?,
             });
         }
         Ok(BoundCreatePartitionPlacement { placements })
     }
 
+    fn bind_worker_id(worker_id: &str) -> RS<OID> {
+        worker_id
+            .parse::<u128>()
+            .map_err(|e| mudu_error!(ER::Parse, format!("invalid worker id {}", worker_id), e))
+    }
+
+    // Both actions produce the next version of the rule; a split places the
+    // new partition on the worker of the partition it was split from.
+    async fn bind_alter_partition_rule(
+        &self,
+        stmt: StmtAlterPartitionRule,
+    ) -> RS<BoundAlterPartitionRule> {
+        let rule = self
+            .meta_mgr
+            .get_partition_rule_by_name(stmt.rule_name())
+            .await?
+            .ok_or_else(|| {
+                mudu_error!(
+                    ER::EntityNotFound,
+                    format!("no such partition rule {}", stmt.rule_name())
+                )
+            })?;
+        match stmt.action() {
+            StmtAlterPartitionRuleAction::Split { at, new_partition } => {
+                let new_name = match new_partition {
+                    Some(name) => name.clone(),
+                    None => (rule.partitions.len()..)
+                        .map(|index| format!("p{}", index))
+                        .find(|name| rule.partitions.iter().all(|p| &p.name != name))
+                        .unwrap_or_default(),
+                };
+                let (rule, split_id, new_id) = PartitionRouter::new(self.meta_mgr.clone())
+                    .split_rule_partition(&rule, at, new_name)?;
+                let placements = match self.meta_mgr.get_partition_worker(split_id).await? {
+                    Some(worker_id) => vec![PartitionPlacement {
+                        partition_id: new_id,
+                        worker_id,
+                    }],
+                    None => Vec::new(),
+                };
+                Ok(BoundAlterPartitionRule { rule, placements })
+            }
+            StmtAlterPartitionRuleAction::Move { partition, worker } => {
+                let partition_id = rule
+                    .partitions
+                    .iter()
+                    .find(|def| &def.name == partition)
+                    .map(|def| def.partition_id)
+                    .ok_or_else(|| {
+                        mudu_error!(
+                            ER::EntityNotFound,
+                            format!(
+                                "no such partition {} in rule {}",
+                                partition,
+                                stmt.rule_name()
+                            )
+                        )
+                    })?;
+                let placements = vec![PartitionPlacement {
+                    partition_id,
+                    worker_id: Self::bind_worker_id(worker)?,
+                }];
+                let mut rule = rule;
+                rule.version += 1;
+                Ok(BoundAlterPartitionRule { rule, placements })
+            }
+        }
+    }
+
     fn bind_partition_bound(bound: &StmtPartitionBound) -> PartitionBound {
         match bound {
             StmtPartitionBound::Unbounded => PartitionBound::Unbounded,
@@ -429,6 +972,153 @@ impl Binder {
         }
     }
 
+    async fn bind_alter_table(&self, stmt: StmtAlterTable) -> RS<BoundAlterTable> {
+        let table_desc = match self.meta_mgr.get_table_by_name(stmt.table_name()).await? {
+            Some(table_desc) => table_desc,
+            None if stmt.if_exists() => {
+                return Ok(BoundAlterTable {
+                    oid: None,
+                    actions: Vec::new(),
+                })
+            }
+            None => {
+                return Err(mudu_error!(
+                    ER::EntityNotFound,
+                    format!("cannot find table {}", stmt.table_name())
+                ))
+            }
+        };
+        let mut actions = Vec::with_capacity(stmt.actions().len());
+        for action in stmt.actions() {
+            let action = match action {
+                AlterTableAction::AddColumn(column) => {
+                    if column.is_primary_key() {
+                        return Err(mudu_error!(
+                            ER::UnsupportedOperation,
+                            format!("cannot add primary key column {}", column.column_name())
+                        ));
+                    }
+                    AlterTable::AddColumn(self.schema_column_from_ast(column).await?)
+                }
+                AlterTableAction::DropColumn {
+                    column_name,
+                    if_exists,
+                } => {
+                    if *if_exists && !table_desc.name2oid().contains_key(column_name) {
+                        continue;
+                    }
+                    AlterTable::DropColumn(column_name.clone())
+                }
+                AlterTableAction::RenameColumn { old_name, new_name } => AlterTable::RenameColumn {
+                    old_name: old_name.clone(),
+                    new_name: new_name.clone(),
+                },
+                AlterTableAction::AlterColumnType {
+                    column_name,
+                    data_type,
+                    data_type_param,
+                } => {
+                    let ty = data_type
+                        .clone()
+                        .uni_to_with_params(data_type_param.clone())?;
+                    AlterTable::AlterColumnType {
+                        name: column_name.clone(),
+                        type_id: ty.type_family(),
+                        type_param: DataTypeInfo::from_opt_object(&ty),
+                    }
+                }
+            };
+            actions.push(action);
+        }
+        Ok(BoundAlterTable {
+            oid: Some(table_desc.id()),
+            actions,
+        })
+    }
+
+    // CREATE INDEX and DROP INDEX bind to an ALTER TABLE of the indexed
+    // table, so the index change reaches every worker through the same
+    // catalog path as a column change.
+    async fn bind_create_index(&self, stmt: StmtCreateIndex) -> RS<BoundAlterTable> {
+        let table_desc = self
+            .meta_mgr
+            .get_table_by_name(stmt.table_name())
+            .await?
+            .ok_or_else(|| {
+                mudu_error!(
+                    ER::EntityNotFound,
+                    format!("cannot find table {}", stmt.table_name())
+                )
+            })?;
+        if self.find_index_owner(stmt.index_name()).await?.is_some() {
+            if stmt.if_not_exists() {
+                return Ok(BoundAlterTable {
+                    oid: Some(table_desc.id()),
+                    actions: Vec::new(),
+                });
+            }
+            return Err(mudu_error!(
+                ER::EntityAlreadyExists,
+                format!("index {} already exists", stmt.index_name())
+            ));
+        }
+        let mut column_oids = Vec::with_capacity(stmt.columns().len());
+        for column in stmt.columns() {
+            let oid = *table_desc.name2oid().get(column).ok_or_else(|| {
+                mudu_error!(
+                    ER::EntityNotFound,
+                    format!(
+                        "cannot find column {} in table {}",
+                        column,
+                        stmt.table_name()
+                    )
+                )
+            })?;
+            if column_oids.contains(&oid) {
+                return Err(mudu_error!(
+                    ER::UnsupportedOperation,
+                    format!("column {} is listed twice in index", column)
+                ));
+            }
+            column_oids.push(oid);
+        }
+        Ok(BoundAlterTable {
+            oid: Some(table_desc.id()),
+            actions: vec![AlterTable::AddIndex(SchemaIndex::new(
+                stmt.index_name().to_string(),
+                column_oids,
+            ))],
+        })
+    }
+
+    async fn bind_drop_index(&self, stmt: StmtDropIndex) -> RS<BoundAlterTable> {
+        match self.find_index_owner(stmt.index_name()).await? {
+            Some(oid) => Ok(BoundAlterTable {
+                oid: Some(oid),
+                actions: vec![AlterTable::DropIndex(stmt.index_name().to_string())],
+            }),
+            None if stmt.if_exists() => Ok(BoundAlterTable {
+                oid: None,
+                actions: Vec::new(),
+            }),
+            None => Err(mudu_error!(
+                ER::EntityNotFound,
+                format!("cannot find index {}", stmt.index_name())
+            )),
+        }
+    }
+
+    // Index names are unique across tables, so DROP INDEX needs no table.
+    async fn find_index_owner(&self, index_name: &str) -> RS<Option<OID>> {
+        Ok(self
+            .meta_mgr
+            .list_schemas()
+            .await?
+            .iter()
+            .find(|schema| schema.index_by_name(index_name).is_some())
+            .map(|schema| schema.id()))
+    }
+
     fn bind_create_fs_type(stmt: &StmtCreateFsType) -> BoundCreateFsType {
         let kind = match stmt.kind() {
             AstFsTypeKind::File => FsTypeKind::File,
@@ -500,10 +1190,32 @@ impl Binder {
             rows.push(InsertRowTemplate { key, value });
         }
 
+        let default_attrs = (0..table_desc.fields().len())
+            .filter(|attr| {
+                let field = table_desc.get_attr(*attr);
+                field.default_value().is_some() && !columns.contains(field.name())
+            })
+            .collect();
+        let on_conflict = match stmt.on_conflict() {
+            Some(on_conflict) => {
+                let constrained = constrained_attrs(self.meta_mgr.as_ref(), &table_desc).await?;
+                Some(self.bind_on_conflict_template(
+                    &table_desc,
+                    on_conflict,
+                    &constrained,
+                    recorder,
+                )?)
+            }
+            None => None,
+        };
         Ok(InsertTemplate {
             table_id: table_desc.id(),
             rows,
+            default_attrs,
             has_fs_columns: has_fs_bound_columns(&table_desc),
+            has_constraints: has_constraints(&table_desc),
+            on_conflict,
+            returning: bind_returning(&table_desc, stmt.returning())?,
         })
     }
 
@@ -541,71 +1253,181 @@ impl Binder {
         recorder: &mut SlotRecorder,
     ) -> RS<UpdateTemplate> {
         let table_desc = self.get_table_by_name(stmt.get_table_reference()).await?;
+        let constrained = constrained_attrs(self.meta_mgr.as_ref(), &table_desc).await?;
         let mut value = Vec::with_capacity(stmt.get_set_values().len());
-
         for assignment in stmt.get_set_values() {
-            let attr = self.attr_index_by_name(&table_desc, assignment.get_column_reference())?;
-            let field = table_desc.get_attr(attr);
-            if field.primary_index().is_some() {
-                return Err(mudu_error!(
-                    ER::NotImplemented,
-                    "updating primary key columns is not implemented"
-                ));
-            }
-            match assignment.get_set_value() {
-                AssignedValue::Value(expr) => {
-                    let datum = template_from_expr(expr, field.type_desc(), recorder)?;
-                    if matches!(datum, TemplateDatum::Const(None)) && !field.nullable() {
-                        return Err(mudu_error!(
-                            ER::InvalidTuple,
-                            format!("cannot update NOT NULL column {} to NULL", field.name())
-                        ));
-                    }
-                    match datum {
-                        // Assigning NULL to an FS-bound column marks it for rebinding:
-                        // the DML hook assigns a fresh system object id (an empty
-                        // datum is the "touched, system-assigned" sentinel).
-                        TemplateDatum::Const(None) if field.fs_binding().is_some() => value.push((
-                            attr,
-                            SetValueTemplate::Absolute(TemplateDatum::Const(Some(Vec::new()))),
-                        )),
-                        TemplateDatum::Const(None) => {}
-                        datum => value.push((attr, SetValueTemplate::Absolute(datum))),
-                    }
-                }
-                AssignedValue::Expression(expr) => {
-                    let set_value = Self::bind_delta_assignment_template(
-                        assignment.get_column_reference(),
-                        expr,
-                        field.type_desc(),
-                        recorder,
-                    )?;
-                    value.push((attr, set_value));
-                }
+            let (attr, set_value) = self.bind_assignment_template(
+                &table_desc,
+                assignment,
+                &constrained,
+                false,
+                recorder,
+            )?;
+            if let Some(set_value) = set_value {
+                value.push((attr, set_value));
             }
         }
-        let key =
-            self.bind_exact_key_template(&table_desc, stmt.get_where_predicate(), recorder)?;
+        let (key, scan) = self.bind_written_rows_template(
+            &table_desc,
+            stmt.get_where_predicate(),
+            stmt.get_where_condition(),
+            recorder,
+        )?;
 
         Ok(UpdateTemplate {
             table_id: table_desc.id(),
             key,
             value,
             has_fs_columns: has_fs_bound_columns(&table_desc),
+            has_constraints: has_constraints(&table_desc)
+                || is_referenced(self.meta_mgr.as_ref(), table_desc.id()).await?,
+            returning: bind_returning(&table_desc, stmt.get_returning())?,
+            scan,
         })
     }
 
-    /// Bind the restricted expression assignment `SET col = col <+|->
-    /// <integer>` where the right operand is an integer literal or a parameter
-    /// placeholder (`?`).
+    /// Binds one `SET` assignment of an `UPDATE` or `ON CONFLICT DO UPDATE`.
+    /// The value is `None` when the assignment leaves the column unchanged
+    /// (NULL into a nullable column that is not fs-bound). `constrained`
+    /// lists the columns an update must check (see `constrained_attrs`).
+    fn bind_assignment_template(
+        &self,
+        table_desc: &TableDesc,
+        assignment: &Assignment,
+        constrained: &[AttrIndex],
+        excluded: bool,
+        recorder: &mut SlotRecorder,
+    ) -> RS<(AttrIndex, Option<SetValueTemplate>)> {
+        let attr = self.attr_index_by_name(table_desc, assignment.get_column_reference())?;
+        let field = table_desc.get_attr(attr);
+        if field.primary_index().is_some() {
+            return Err(mudu_error!(
+                ER::NotImplemented,
+                "updating primary key columns is not implemented"
+            ));
+        }
+        let set_value = match assignment.get_set_value() {
+            AssignedValue::Value(expr) => {
+                let datum = template_from_expr(expr, field.type_desc(), recorder)?;
+                if matches!(datum, TemplateDatum::Const(None)) && !field.nullable() {
+                    return Err(mudu_error!(
+                        ER::InvalidTuple,
+                        format!("cannot update NOT NULL column {} to NULL", field.name())
+                    ));
+                }
+                match datum {
+                    // Assigning NULL to an FS-bound column marks it for rebinding:
+                    // the DML hook assigns a fresh system object id (an empty
+                    // datum is the "touched, system-assigned" sentinel).
+                    TemplateDatum::Const(None) if field.fs_binding().is_some() => Some(
+                        SetValueTemplate::Absolute(TemplateDatum::Const(Some(Vec::new()))),
+                    ),
+                    TemplateDatum::Const(None) => None,
+                    datum => Some(SetValueTemplate::Absolute(datum)),
+                }
+            }
+            AssignedValue::Expression(expr) => {
+                // Unique indexes, foreign keys and check constraints are
+                // checked on the new value of the columns they cover; as an
+                // expression it is evaluated once, over the row the check
+                // reads, instead of again by the storage layer.
+                let delta = if constrained.contains(&attr) {
+                    None
+                } else {
+                    Self::bind_delta_assignment_template(
+                        assignment.get_column_reference(),
+                        expr,
+                        field.type_desc(),
+                        recorder,
+                    )?
+                };
+                match delta {
+                    Some(delta) => Some(delta),
+                    None => Some(Self::bind_expr_assignment_template(
+                        table_desc, attr, expr, excluded, recorder,
+                    )?),
+                }
+            }
+        };
+        Ok((attr, set_value))
+    }
+
+    /// Binds `ON CONFLICT`. A conflict target must name the primary key: it
+    /// is the only conflict detected before the row is written.
+    fn bind_on_conflict_template(
+        &self,
+        table_desc: &TableDesc,
+        on_conflict: &OnConflict,
+        constrained: &[AttrIndex],
+        recorder: &mut SlotRecorder,
+    ) -> RS<OnConflictTemplate> {
+        if !on_conflict.columns().is_empty() {
+            let mut target = on_conflict
+                .columns()
+                .iter()
+                .map(|name| self.attr_index_by_name(table_desc, name))
+                .collect::<RS<Vec<_>>>()?;
+            target.sort_unstable();
+            target.dedup();
+            let key: Vec<AttrIndex> = (0..table_desc.fields().len())
+                .filter(|attr| table_desc.get_attr(*attr).primary_index().is_some())
+                .collect();
+            if target != key {
+                return Err(mudu_error!(
+                    ER::NotImplemented,
+                    "ON CONFLICT targets other than the primary key are not implemented"
+                ));
+            }
+        }
+        let assignments = match on_conflict.action() {
+            OnConflictAction::DoNothing => return Ok(OnConflictTemplate::DoNothing),
+            OnConflictAction::DoUpdate(assignments) => assignments,
+        };
+        if has_fs_bound_columns(table_desc) {
+            return Err(mudu_error!(
+                ER::NotImplemented,
+                "ON CONFLICT DO UPDATE on tables with fs-bound columns is not implemented"
+            ));
+        }
+        let mut value = Vec::with_capacity(assignments.len());
+        for assignment in assignments {
+            let attr = self.attr_index_by_name(table_desc, assignment.get_column_reference())?;
+            let field = table_desc.get_attr(attr);
+            // `SET col = EXCLUDED.col` copies the proposed value as is,
+            // without reading the existing row.
+            if excluded_column(assignment.get_set_value())
+                .is_some_and(|source| source.name() == field.name())
+            {
+                if field.primary_index().is_some() {
+                    return Err(mudu_error!(
+                        ER::NotImplemented,
+                        "updating primary key columns is not implemented"
+                    ));
+                }
+                value.push((attr, ConflictValueTemplate::Excluded(attr)));
+                continue;
+            }
+            let (attr, set_value) =
+                self.bind_assignment_template(table_desc, assignment, constrained, true, recorder)?;
+            if let Some(set_value) = set_value {
+                value.push((attr, ConflictValueTemplate::Set(set_value)));
+            }
+        }
+        Ok(OnConflictTemplate::DoUpdate(value))
+    }
+
+    /// Bind the expression assignment `SET col = col <+|-> <integer>` where
+    /// the right operand is an integer literal or a parameter placeholder
+    /// (`?`) as a delta, which the storage layer applies without reading the
+    /// row first.
     ///
     /// The left operand must reference the assigned column itself and the
     /// right operand must be a signed/unsigned integer literal or a parameter
     /// of an integer type family; the assigned column must be an integer or
-    /// numeric column. Any other expression form is rejected as
-    /// `NotImplemented`. The operand is stored in the column's binary format
-    /// (coerced for numeric columns) so the executor can decode it with the
-    /// column type. A placeholder is recorded as a delta-operand slot; its
+    /// numeric column. Any other expression form returns `None` and is bound
+    /// as a general expression. The operand is stored in the column's binary
+    /// format (coerced for numeric columns) so the executor can decode it
+    /// with the column type. A placeholder is recorded as a delta-operand slot; its
     /// type-family check and encoding run at fill time through the same path
     /// as a plain `SET col = ?` assignment (identical to immediate binding,
     /// where both run at bind time).
@@ -614,30 +1436,23 @@ impl Binder {
         expr: &ExprType,
         column_type: &DataType,
         recorder: &mut SlotRecorder,
-    ) -> RS<SetValueTemplate> {
-        let not_implemented = || {
-            mudu_error!(
-                ER::NotImplemented,
-                "expression updates are not implemented \
-                 (only `SET col = col +|- <integer literal or ?>` is supported)"
-            )
-        };
+    ) -> RS<Option<SetValueTemplate>> {
         let ExprType::Arithmetic(arithmetic) = expr else {
-            return Err(not_implemented());
+            return Ok(None);
         };
         let op = match arithmetic.op() {
             Arithmetic::PLUS => DeltaOp::Add,
             Arithmetic::MINUS => DeltaOp::Sub,
-            _ => return Err(not_implemented()),
+            _ => return Ok(None),
         };
         let ExprType::Value(left) = arithmetic.left() else {
-            return Err(not_implemented());
+            return Ok(None);
         };
         let ExprItem::ItemName(name) = left.as_ref() else {
-            return Err(not_implemented());
+            return Ok(None);
         };
-        if name.name() != column_reference {
-            return Err(not_implemented());
+        if name.name() != column_reference || is_excluded(name) {
+            return Ok(None);
         }
         match column_type.type_family() {
             // Numeric columns (e.g. TPC-C money columns) accept an integer
@@ -648,13 +1463,13 @@ impl Binder {
             | TypeFamily::I128
             | TypeFamily::U128
             | TypeFamily::Numeric => {}
-            _ => return Err(not_implemented()),
+            _ => return Ok(None),
         }
         let ExprType::Value(right) = arithmetic.right() else {
-            return Err(not_implemented());
+            return Ok(None);
         };
         let ExprItem::ItemValue(value) = right.as_ref() else {
-            return Err(not_implemented());
+            return Ok(None);
         };
         let operand = match value {
             ExprValue::ValueLiteral(ExprLiteral::DatumLiteral(literal)) => {
@@ -664,7 +1479,7 @@ impl Binder {
                     // delta operands as quoted string literals; ValueCodec
                     // coerces them to the column's numeric layout.
                     TypeFamily::String if column_type.type_family() == TypeFamily::Numeric => {}
-                    _ => return Err(not_implemented()),
+                    _ => return Ok(None),
                 }
                 let Some(binary) = ValueCodec::binary_from_literal(
                     &ExprLiteral::DatumLiteral(literal.clone()),
@@ -672,7 +1487,7 @@ impl Binder {
                 )?
                 else {
                     // A datum literal never binds to NULL.
-                    return Err(not_implemented());
+                    return Ok(None);
                 };
                 TemplateDatum::Const(Some(binary))
             }
@@ -684,9 +1499,39 @@ impl Binder {
                 // plain `SET col = ?` assignment.
                 TemplateDatum::Slot(recorder.push(column_type.clone(), true))
             }
-            _ => return Err(not_implemented()),
+            _ => return Ok(None),
         };
-        Ok(SetValueTemplate::Delta { op, operand })
+        Ok(Some(SetValueTemplate::Delta { op, operand }))
+    }
+
+    /// Bind `SET col = <expr>` over the row's current column values, and
+    /// with `excluded` over the proposed row's `EXCLUDED` columns too; the
+    /// result is converted to the column type.
+    fn bind_expr_assignment_template(
+        table_desc: &TableDesc,
+        attr: AttrIndex,
+        expr: &ExprType,
+        excluded: bool,
+        recorder: &mut SlotRecorder,
+    ) -> RS<SetValueTemplate> {
+        let field = table_desc.get_attr(attr);
+        if field.fs_binding().is_some() {
+            return Err(mudu_error!(
+                ER::NotImplemented,
+                "expression updates of fs-bound columns are not implemented"
+            ));
+        }
+        let mut json_columns = JsonColumns::default();
+        let expr = ExprBinder::new(table_desc, &mut json_columns, Some(recorder))
+            .with_excluded(excluded)
+            .bind_assigned(expr, field.type_desc())?;
+        if !json_columns.columns().is_empty() {
+            return Err(mudu_error!(
+                ER::NotImplemented,
+                "JSON paths in SET expressions are not implemented"
+            ));
+        }
+        Ok(SetValueTemplate::Expr(expr))
     }
 
     async fn bind_delete_template(
@@ -695,14 +1540,84 @@ impl Binder {
         recorder: &mut SlotRecorder,
     ) -> RS<DeleteTemplate> {
         let table_desc = self.get_table_by_name(stmt.get_table_reference()).await?;
-        let key =
-            self.bind_exact_key_template(&table_desc, stmt.get_where_predicate(), recorder)?;
+        let (key, scan) = self.bind_written_rows_template(
+            &table_desc,
+            stmt.get_where_predicate(),
+            stmt.get_where_condition(),
+            recorder,
+        )?;
         Ok(DeleteTemplate {
             table_id: table_desc.id(),
             key,
+            returning: bind_returning(&table_desc, stmt.get_returning())?,
+            scan,
         })
     }
 
+    /// Binds the `WHERE` clause of an `UPDATE` or `DELETE`. Comparisons
+    /// connected by AND must name the whole primary key, and the statement
+    /// writes that row; a clause with `OR`, `IN`, `BETWEEN`, `LIKE`, `IS
+    /// NULL` or `NOT` is bound like a `SELECT` of the primary key columns,
+    /// whose rows are the keys written.
+    fn bind_written_rows_template(
+        &self,
+        table_desc: &TableDesc,
+        predicates: &[ExprCompare],
+        where_condition: &[ExprType],
+        recorder: &mut SlotRecorder,
+    ) -> RS<(Vec<(usize, TemplateDatum)>, Option<SelectTemplate>)> {
+        let mut json_columns = JsonColumns::default();
+        let (predicate, residual, conditions) = if where_condition.is_empty() {
+            let (predicate, residual) =
+                self.bind_predicate_template(table_desc, predicates, recorder)?;
+            if !matches!(predicate, PredicateTemplate::KeyIn { .. }) {
+                let key = Self::exact_key_template(table_desc, predicate, residual)?;
+                return Ok((key, None));
+            }
+            // A key list is written through the key scan, one row per key.
+            (predicate, residual, Vec::new())
+        } else {
+            self.bind_where_template(
+                table_desc,
+                predicates,
+                where_condition,
+                &mut json_columns,
+                recorder,
+            )?
+        };
+        let select_items: Vec<_> = table_desc
+            .key_indices()
+            .iter()
+            .map(|attr| {
+                BoundSelectItem::Column(BoundSelectColumn {
+                    attr: *attr,
+                    output_name: table_desc.get_attr(*attr).name().clone(),
+                })
+            })
+            .collect();
+        let tuple_desc = TupleFieldDesc::new(
+            select_items
+                .iter()
+                .map(|item| item_datum_desc(table_desc, json_columns.columns(), item))
+                .collect(),
+        );
+        let scan = SelectTemplate {
+            table_id: table_desc.id(),
+            select_items,
+            tuple_desc,
+            predicate,
+            residual,
+            conditions,
+            group_by: Vec::new(),
+            hidden_items: Vec::new(),
+            having: Vec::new(),
+            order: BoundRowOrder::default(),
+            json_columns: json_columns.into_columns(),
+            has_fs_columns: has_fs_bound_columns(table_desc),
+        };
+        Ok((Vec::new(), Some(scan)))
+    }
+
     fn bind_predicate_template(
         &self,
         table_desc: &TableDesc,
@@ -712,56 +1627,373 @@ impl Binder {
         if predicates.is_empty() {
             return Ok((PredicateTemplate::True, Vec::new()));
         }
-
-        let mut eq_items = vec![];
-        let mut start: Bound<Vec<(usize, TemplateDatum)>> = Bound::Unbounded;
-        let mut end: Bound<Vec<(usize, TemplateDatum)>> = Bound::Unbounded;
-        let mut residual = Vec::new();
-
+        let mut conjuncts = CompareConjuncts::new();
         for predicate in predicates {
-            let (field_name, expr_value, op) =
-                self.field_literal_compare(predicate).ok_or_else(|| {
-                    mudu_error!(
-                        ER::NotImplemented,
-                        "only column/literal predicates are supported"
-                    )
-                })?;
-            let attr = self.attr_index_by_name(table_desc, field_name)?;
-            let field = table_desc.get_attr(attr);
-            let datum = template_from_expr(&expr_value, field.type_desc(), recorder)?;
-            if field.primary_index().is_none() {
-                // Non-key predicate: evaluate it row-by-row in the executor
-                // layer as a residual filter after the key access.
-                residual.push(ResidualTemplate {
-                    attr,
-                    op,
-                    literal: datum,
+            self.bind_compare_conjunct(table_desc, predicate, recorder, &mut conjuncts)?;
+        }
+        let predicate = self.combine_key_predicate_template(
+            table_desc,
+            conjuncts.eq_items,
+            conjuncts.start,
+            conjuncts.end,
+        )?;
+        Ok((predicate, conjuncts.residual))
+    }
+
+    /// Binds the `WHERE` clause of a single-table `SELECT`, or of an `UPDATE`
+    /// or `DELETE` scanning its rows. Conjuncts are
+    /// bound in statement order so that placeholders keep their positions;
+    /// column/literal comparisons drive the key access, an `IN` list or `OR`
+    /// of equalities naming whole primary keys becomes a multi-point lookup,
+    /// and any other conjunct is evaluated per row.
+    fn bind_where_template(
+        &self,
+        table_desc: &TableDesc,
+        predicates: &[ExprCompare],
+        where_condition: &[ExprType],
+        json_columns: &mut JsonColumns,
+        recorder: &mut SlotRecorder,
+    ) -> RS<(
+        PredicateTemplate,
+        Vec<ResidualTemplate>,
+        Vec<ConditionTemplate>,
+    )> {
+        let compared_predicates;
+        let where_condition = if where_condition.is_empty() {
+            if predicates
+                .iter()
+                .all(|predicate| self.field_literal_compare(predicate).is_some())
+            {
+                let (predicate, residual) =
+                    self.bind_predicate_template(table_desc, predicates, recorder)?;
+                return Ok((predicate, residual, Vec::new()));
+            }
+            // Some comparison is between two columns.
+            compared_predicates = predicates
+                .iter()
+                .map(|predicate| ExprType::Compare(Arc::new(predicate.clone())))
+                .collect::<Vec<_>>();
+            &compared_predicates
+        } else {
+            where_condition
+        };
+        let mut conjuncts = CompareConjuncts::new();
+        let mut conditions = Vec::new();
+        for expr in where_condition {
+            match expr {
+                // A comparison on a JSON path cannot drive the key access.
+                ExprType::Compare(predicate)
+                    if !Self::is_json_path_compare(predicate)
+                        && self.field_literal_compare(predicate).is_some() =>
+                {
+                    self.bind_compare_conjunct(table_desc, predicate, recorder, &mut conjuncts)?
+                }
+                expr => conditions.push(self.bind_condition_template(
+                    table_desc,
+                    expr,
+                    json_columns,
+                    recorder,
+                )?),
+            }
+        }
+        let mut predicate = if conjuncts.eq_items.is_empty()
+            && matches!(conjuncts.start, Bound::Unbounded)
+            && matches!(conjuncts.end, Bound::Unbounded)
+        {
+            PredicateTemplate::True
+        } else {
+            self.combine_key_predicate_template(
+                table_desc,
+                conjuncts.eq_items,
+                conjuncts.start,
+                conjuncts.end,
+            )?
+        };
+        let prefix = match &predicate {
+            PredicateTemplate::True => Some(Vec::new()),
+            PredicateTemplate::KeyPrefixEq { prefix } => Some(prefix.clone()),
+            _ => None,
+        };
+        if let Some(prefix) = prefix {
+            let point_lookup = conditions
+                .iter()
+                .enumerate()
+                .find_map(|(index, condition)| {
+                    Self::key_points(table_desc, &prefix, condition).map(|keys| (index, keys))
                 });
-                continue;
+            if let Some((index, keys)) = point_lookup {
+                conditions.remove(index);
+                predicate = PredicateTemplate::KeyIn { keys };
             }
-            if matches!(datum, TemplateDatum::Const(None)) {
+        }
+        Ok((predicate, conjuncts.residual, conditions))
+    }
+
+    fn bind_compare_conjunct(
+        &self,
+        table_desc: &TableDesc,
+        predicate: &ExprCompare,
+        recorder: &mut SlotRecorder,
+        conjuncts: &mut CompareConjuncts,
+    ) -> RS<()> {
+        let (name, expr_value, op) = self.field_literal_compare(predicate).ok_or_else(|| {
+            mudu_error!(
+                ER::NotImplemented,
+                "only column/literal predicates are supported"
+            )
+        })?;
+        let attr = self.attr_index_by_name(table_desc, name.name())?;
+        let field = table_desc.get_attr(attr);
+        let datum = template_from_expr(&expr_value, field.type_desc(), recorder)?;
+        Self::push_compare_conjunct(table_desc, attr, op, datum, conjuncts)
+    }
+
+    /// Adds `attr op datum` to the key access, or to the residual filters
+    /// when `attr` is not a key column.
+    fn push_compare_conjunct(
+        table_desc: &TableDesc,
+        attr: AttrIndex,
+        op: ValueCompare,
+        datum: TemplateDatum,
+        conjuncts: &mut CompareConjuncts,
+    ) -> RS<()> {
+        if table_desc.get_attr(attr).primary_index().is_none() {
+            // Non-key predicate: evaluate it row-by-row in the executor
+            // layer as a residual filter after the key access.
+            conjuncts.residual.push(ResidualTemplate {
+                attr,
+                op,
+                literal: datum,
+            });
+            return Ok(());
+        }
+        if matches!(datum, TemplateDatum::Const(None)) {
+            return Err(mudu_error!(
+                ER::NotImplemented,
+                "NULL key predicates are not implemented; use IS NULL"
+            ));
+        }
+        match op {
+            ValueCompare::EQ => conjuncts.eq_items.push((attr, datum)),
+            ValueCompare::GE => conjuncts.start = Bound::Included(vec![(attr, datum)]),
+            ValueCompare::GT => conjuncts.start = Bound::Excluded(vec![(attr, datum)]),
+            ValueCompare::LE => conjuncts.end = Bound::Included(vec![(attr, datum)]),
+            ValueCompare::LT => conjuncts.end = Bound::Excluded(vec![(attr, datum)]),
+            ValueCompare::NE => {
                 return Err(mudu_error!(
                     ER::NotImplemented,
-                    "NULL key predicates are not implemented; use IS NULL"
-                ));
+                    "not-equal predicates are not implemented"
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    /// Binds a `WHERE` conjunct that cannot drive the key access. A leaf
+    /// testing a column or a JSON path against literals or placeholders
+    /// keeps its own form; any other leaf is bound as a boolean expression.
+    fn bind_condition_template(
+        &self,
+        table_desc: &TableDesc,
+        expr: &ExprType,
+        json_columns: &mut JsonColumns,
+        recorder: &mut SlotRecorder,
+    ) -> RS<ConditionTemplate> {
+        let not_implemented = || {
+            mudu_error!(
+                ER::NotImplemented,
+                "only boolean expressions are supported in WHERE"
+            )
+        };
+        let column = |item: &ExprItem, json_columns: &mut JsonColumns| -> RS<usize> {
+            match item {
+                ExprItem::ItemName(name) => json_columns.attr(table_desc, name),
+                ExprItem::ItemValue(_) => Err(not_implemented()),
+            }
+        };
+        let literal = |item: &ExprItem,
+                       attr: usize,
+                       json_columns: &JsonColumns,
+                       recorder: &mut SlotRecorder| match item {
+            ExprItem::ItemValue(value) => template_from_expr(
+                value,
+                &attr_type(table_desc, json_columns.columns(), attr),
+                recorder,
+            ),
+            ExprItem::ItemName(_) => Err(not_implemented()),
+        };
+        let is_column_test = |item: &ExprItem, values: &[&ExprItem]| {
+            matches!(item, ExprItem::ItemName(_))
+                && values
+                    .iter()
+                    .all(|value| matches!(value, ExprItem::ItemValue(_)))
+        };
+        let condition = match expr {
+            ExprType::Logical(logical) => {
+                let is_and = matches!(logical.op(), LogicalConnective::AND);
+                let mut operands = Vec::new();
+                for side in [logical.left(), logical.right()] {
+                    // Flatten chains of the same connective.
+                    match self.bind_condition_template(table_desc, side, json_columns, recorder)? {
+                        ConditionTemplate::And(inner) if is_and => operands.extend(inner),
+                        ConditionTemplate::Or(inner) if !is_and => operands.extend(inner),
+                        operand => operands.push(operand),
+                    }
+                }
+                if is_and {
+                    ConditionTemplate::And(operands)
+                } else {
+                    ConditionTemplate::Or(operands)
+                }
+            }
+            ExprType::Not(operand) => ConditionTemplate::Not(Box::new(
+                self.bind_condition_template(table_desc, operand, json_columns, recorder)?,
+            )),
+            ExprType::Compare(predicate) if self.field_literal_compare(predicate).is_some() => {
+                let (name, expr_value, op) = self
+                    .field_literal_compare(predicate)
+                    .ok_or_else(not_implemented)?;
+                let attr = json_columns.attr(table_desc, name)?;
+                let data_type = attr_type(table_desc, json_columns.columns(), attr);
+                ConditionTemplate::Compare {
+                    attr,
+                    op,
+                    literal: template_from_expr(&expr_value, &data_type, recorder)?,
+                }
+            }
+            ExprType::InList(in_list)
+                if is_column_test(in_list.item(), &in_list.list().iter().collect::<Vec<_>>()) =>
+            {
+                let attr = column(in_list.item(), json_columns)?;
+                let list = in_list
+                    .list()
+                    .iter()
+                    .map(|item| literal(item, attr, json_columns, recorder))
+                    .collect::<RS<Vec<_>>>()?;
+                ConditionTemplate::InList {
+                    attr,
+                    list,
+                    negated: in_list.negated(),
+                }
+            }
+            ExprType::Between(between)
+                if is_column_test(between.item(), &[between.low(), between.high()]) =>
+            {
+                let attr = column(between.item(), json_columns)?;
+                ConditionTemplate::Between {
+                    attr,
+                    low: literal(between.low(), attr, json_columns, recorder)?,
+                    high: literal(between.high(), attr, json_columns, recorder)?,
+                    negated: between.negated(),
+                }
             }
-            match op {
-                ValueCompare::EQ => eq_items.push((attr, datum)),
-                ValueCompare::GE => start = Bound::Included(vec![(attr, datum)]),
-                ValueCompare::GT => start = Bound::Excluded(vec![(attr, datum)]),
-                ValueCompare::LE => end = Bound::Included(vec![(attr, datum)]),
-                ValueCompare::LT => end = Bound::Excluded(vec![(attr, datum)]),
-                ValueCompare::NE => {
+            ExprType::Like(like) if is_column_test(like.item(), &[like.pattern()]) => {
+                let attr = column(like.item(), json_columns)?;
+                if attr_type(table_desc, json_columns.columns(), attr).type_family()
+                    != TypeFamily::String
+                {
                     return Err(mudu_error!(
                         ER::NotImplemented,
-                        "not-equal predicates are not implemented"
-                    ))
+                        "LIKE is only implemented for string columns"
+                    ));
+                }
+                ConditionTemplate::Like {
+                    attr,
+                    pattern: literal(like.pattern(), attr, json_columns, recorder)?,
+                    case_insensitive: like.case_insensitive(),
+                    negated: like.negated(),
                 }
             }
-        }
+            ExprType::IsNull(is_null) if is_column_test(is_null.item(), &[]) => {
+                ConditionTemplate::IsNull {
+                    attr: column(is_null.item(), json_columns)?,
+                    negated: is_null.negated(),
+                }
+            }
+            expr => ConditionTemplate::Expr(
+                ExprBinder::new(table_desc, json_columns, Some(recorder)).bind_condition(expr)?,
+            ),
+        };
+        Ok(condition)
+    }
 
-        let predicate = self.combine_key_predicate_template(table_desc, eq_items, start, end)?;
-        Ok((predicate, residual))
+    /// The complete primary keys selected by `condition` together with the
+    /// equality `prefix`, when the condition is a non-negated `IN` list or an
+    /// `OR` of equalities on key columns; keys compared with NULL are dropped
+    /// since they match no row.
+    fn key_points(
+        table_desc: &TableDesc,
+        prefix: &[(usize, TemplateDatum)],
+        condition: &ConditionTemplate,
+    ) -> Option<Vec<Vec<(usize, TemplateDatum)>>> {
+        fn points(condition: &ConditionTemplate) -> Option<Vec<Vec<(usize, TemplateDatum)>>> {
+            match condition {
+                ConditionTemplate::Compare {
+                    attr,
+                    op: ValueCompare::EQ,
+                    literal,
+                } => Some(vec![vec![(*attr, literal.clone())]]),
+                ConditionTemplate::InList {
+                    attr,
+                    list,
+                    negated: false,
+                } => Some(
+                    list.iter()
+                        .map(|literal| vec![(*attr, literal.clone())])
+                        .collect(),
+                ),
+                ConditionTemplate::And(operands) => {
+                    let mut point = Vec::new();
+                    for operand in operands {
+                        let ConditionTemplate::Compare {
+                            attr,
+                            op: ValueCompare::EQ,
+                            literal,
+                        } = operand
+                        else {
+                            return None;
+                        };
+                        point.push((*attr, literal.clone()));
+                    }
+                    Some(vec![point])
+                }
+                ConditionTemplate::Or(operands) => {
+                    let mut all = Vec::new();
+                    for operand in operands {
+                        all.extend(points(operand)?);
+                    }
+                    Some(all)
+                }
+                _ => None,
+            }
+        }
+        let mut keys = Vec::new();
+        for point in points(condition)? {
+            let mut key = prefix.to_vec();
+            key.extend(point);
+            // JSON path columns are never key columns.
+            if key
+                .iter()
+                .any(|(attr, _)| *attr >= table_desc.fields().len())
+            {
+                return None;
+            }
+            key.sort_by_key(|(attr, _)| table_desc.get_attr(*attr).primary_index());
+            let complete = key.len() == table_desc.key_indices().len()
+                && key.iter().enumerate().all(|(index, (attr, _))| {
+                    table_desc.get_attr(*attr).primary_index() == Some(index)
+                });
+            if !complete {
+                return None;
+            }
+            if key
+                .iter()
+                .all(|(_, datum)| !matches!(datum, TemplateDatum::Const(None)))
+            {
+                keys.push(key);
+            }
+        }
+        Some(keys)
     }
 
     fn combine_key_predicate_template(
@@ -805,14 +2037,13 @@ impl Binder {
         Ok(PredicateTemplate::KeyRange { start, end })
     }
 
-    fn bind_exact_key_template(
-        &self,
+    /// The complete primary key an `UPDATE` or `DELETE` without a key scan
+    /// writes, ordered by key position.
+    fn exact_key_template(
         table_desc: &TableDesc,
-        predicates: &[ExprCompare],
-        recorder: &mut SlotRecorder,
+        predicate: PredicateTemplate,
+        residual: Vec<ResidualTemplate>,
     ) -> RS<Vec<(usize, TemplateDatum)>> {
-        let (predicate, residual) =
-            self.bind_predicate_template(table_desc, predicates, recorder)?;
         if !residual.is_empty() {
             return Err(mudu_error!(
                 ER::NotImplemented,
@@ -855,26 +2086,35 @@ impl Binder {
                 ER::NotImplemented,
                 "range update/delete is not implemented"
             )),
+            // Written through the key scan by `bind_written_rows_template`.
+            PredicateTemplate::KeyIn { .. } => Err(mudu_error!(
+                ER::Internal,
+                "a key list has no single exact key"
+            )),
         }
     }
 
     fn field_literal_compare<'a>(
         &self,
         predicate: &'a ExprCompare,
-    ) -> Option<(&'a String, ExprValue, ValueCompare)> {
+    ) -> Option<(&'a ExprName, ExprValue, ValueCompare)> {
         match (predicate.left(), predicate.right()) {
             (ExprItem::ItemName(name), ExprItem::ItemValue(value)) => {
-                Some((name.name(), value.clone(), *predicate.op()))
+                Some((name, value.clone(), *predicate.op()))
+            }
+            (ExprItem::ItemValue(value), ExprItem::ItemName(name)) => {
+                Some((name, value.clone(), Self::reverse_compare(*predicate.op())))
             }
-            (ExprItem::ItemValue(value), ExprItem::ItemName(name)) => Some((
-                name.name(),
-                value.clone(),
-                Self::reverse_compare(*predicate.op()),
-            )),
             _ => None,
         }
     }
 
+    fn is_json_path_compare(predicate: &ExprCompare) -> bool {
+        [predicate.left(), predicate.right()]
+            .into_iter()
+            .any(|item| matches!(item, ExprItem::ItemName(name) if name.json_path().is_some()))
+    }
+
     fn reverse_compare(op: ValueCompare) -> ValueCompare {
         ValueCompare::revert_cmp_op(op)
     }
@@ -900,6 +2140,10 @@ impl Binder {
         schema_column.set_primary_index(column.primary_key_index());
         schema_column.set_nullable(column.nullable());
         schema_column.set_index(column.column_index());
+        if let Some(default) = column.default_value() {
+            let default = column_default_from_ast(default, &schema_column)?;
+            schema_column.set_default_value(Some(default));
+        }
         Ok(schema_column)
     }
 
@@ -942,6 +2186,185 @@ impl Binder {
     }
 }
 
+// The column of an `EXCLUDED.col` assignment value.
+fn excluded_column(value: &AssignedValue) -> Option<&ExprName> {
+    let AssignedValue::Expression(ExprType::Value(item)) = value else {
+        return None;
+    };
+    match item.as_ref() {
+        ExprItem::ItemName(name) if is_excluded(name) => Some(name),
+        _ => None,
+    }
+}
+
+fn is_excluded(name: &ExprName) -> bool {
+    name.qualifier()
+        .is_some_and(|qualifier| qualifier.eq_ignore_ascii_case("excluded"))
+}
+
+// Resolves a column DEFAULT against the column type: a literal is encoded
+// once here, `now()` and `gen_oid()` are evaluated for every inserted row.
+fn column_default_from_ast(default: &AstColumnDefault, column: &SchemaColumn) -> RS<ColumnDefault> {
+    if column.fs_binding().is_some() {
+        return Err(mudu_error!(
+            ER::UnsupportedOperation,
+            format!(
+                "fs-bound column {} cannot have a DEFAULT",
+                column.get_name()
+            )
+        ));
+    }
+    let data_type = column.type_param().to_data_type()?;
+    let type_error = |expected: &str| {
+        mudu_error!(
+            ER::InvalidType,
+            format!(
+                "DEFAULT of column {} requires {}",
+                column.get_name(),
+                expected
+            )
+        )
+    };
+    match default {
+        AstColumnDefault::Literal(literal) => ValueCodec::binary_from_literal(literal, &data_type)?
+            .map(ColumnDefault::Value)
+            .ok_or_else(|| type_error("a non-NULL value")),
+        AstColumnDefault::Now => match data_type.type_family() {
+            TypeFamily::Timestamp | TypeFamily::TimestampTz => Ok(ColumnDefault::Now),
+            _ => Err(type_error("a timestamp column for now()")),
+        },
+        AstColumnDefault::GenOid => match data_type.type_family() {
+            TypeFamily::U128 | TypeFamily::Uuid => Ok(ColumnDefault::GenOid),
+            _ => Err(type_error("a U128 or UUID column for gen_oid()")),
+        },
+    }
+}
+
+fn constraint_column_oids(schema: &SchemaTable, names: &[String]) -> RS<Vec<OID>> {
+    let mut oids = Vec::with_capacity(names.len());
+    for name in names {
+        let oid = schema
+            .columns()
+            .iter()
+            .find(|column| column.get_name() == name)
+            .map(|column| column.get_oid())
+            .ok_or_else(|| {
+                mudu_error!(ER::EntityNotFound, format!("cannot find column {}", name))
+            })?;
+        if oids.contains(&oid) {
+            return Err(mudu_error!(
+                ER::UnsupportedOperation,
+                format!("column {} is listed twice in constraint", name)
+            ));
+        }
+        oids.push(oid);
+    }
+    Ok(oids)
+}
+
+fn constraint_index_name(table_name: &str, columns: &[String], suffix: &str) -> String {
+    format!("{}_{}_{}", table_name, columns.join("_"), suffix)
+}
+
+// The referenced columns of a foreign key shall be the primary key or the
+// columns of a unique index of the parent table, in any order.
+fn check_referenced_key(
+    ref_oids: &[OID],
+    key_oids: &[OID],
+    unique_indexes: &mut dyn Iterator<Item = Vec<OID>>,
+) -> RS<()> {
+    let same_columns = |oids: &[OID]| {
+        oids.len() == ref_oids.len() && oids.iter().all(|oid| ref_oids.contains(oid))
+    };
+    if same_columns(key_oids) || unique_indexes.any(|oids| same_columns(&oids)) {
+        Ok(())
+    } else {
+        Err(mudu_error!(
+            ER::InvalidArgument,
+            "foreign key must reference the primary key or a unique constraint"
+        ))
+    }
+}
+
+fn referenced_columns_of_schema(
+    schema: &SchemaTable,
+    names: &[String],
+) -> RS<Vec<(OID, TypeFamily)>> {
+    let oids = constraint_column_oids(schema, names)?;
+    let key_oids: Vec<OID> = schema.key_columns().iter().map(|c| c.get_oid()).collect();
+    check_referenced_key(
+        &oids,
+        &key_oids,
+        &mut schema
+            .indexes()
+            .iter()
+            .filter(|index| index.is_unique())
+            .map(|index| index.column_oids().clone()),
+    )?;
+    Ok(oids
+        .into_iter()
+        .filter_map(|oid| {
+            schema
+                .columns()
+                .iter()
+                .find(|column| column.get_oid() == oid)
+                .map(|column| (oid, column.type_id()))
+        })
+        .collect())
+}
+
+fn referenced_columns_of_table(
+    table_desc: &TableDesc,
+    names: &[String],
+) -> RS<Vec<(OID, TypeFamily)>> {
+    let mut columns = Vec::with_capacity(names.len());
+    for name in names {
+        let oid = *table_desc.name2oid().get(name).ok_or_else(|| {
+            mudu_error!(
+                ER::EntityNotFound,
+                format!("cannot find column {} in table {}", name, table_desc.name())
+            )
+        })?;
+        let type_id = table_desc
+            .oid2col()
+            .get(&oid)
+            .map(|field| field.type_desc().type_family())
+            .ok_or_else(|| mudu_error!(ER::EntityNotFound, format!("no column {}", oid)))?;
+        columns.push((oid, type_id));
+    }
+    let oids: Vec<OID> = columns.iter().map(|(oid, _)| *oid).collect();
+    check_referenced_key(
+        &oids,
+        table_desc.key_field_oid(),
+        &mut table_desc
+            .indexes()
+            .iter()
+            .filter(|index| index.unique)
+            .map(|index| index.fields.iter().map(|field| field.id()).collect()),
+    )?;
+    Ok(columns)
+}
+
+/// Key access and residual filters collected from the comparison conjuncts
+/// of a `WHERE` clause.
+struct CompareConjuncts {
+    eq_items: Vec<(usize, TemplateDatum)>,
+    start: Bound<Vec<(usize, TemplateDatum)>>,
+    end: Bound<Vec<(usize, TemplateDatum)>>,
+    residual: Vec<ResidualTemplate>,
+}
+
+impl CompareConjuncts {
+    fn new() -> Self {
+        Self {
+            eq_items: Vec::new(),
+            start: Bound::Unbounded,
+            end: Bound::Unbounded,
+            residual: Vec::new(),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum InferredKeyType {
     I64,
@@ -13,7 +13,9 @@ mod tests {
 
     use crate::contract::fs_type::{FsColumnBinding, FsTypeDesc, FsTypeKind};
     use crate::contract::meta_mgr::MetaMgr;
-    use crate::contract::partition_rule::{PartitionBound, PartitionRuleDesc, RangePartitionDef};
+    use crate::contract::partition_rule::{
+        PartitionBound, PartitionRuleDesc, PartitionRuleKind, RangePartitionDef,
+    };
     use crate::contract::partition_rule_binding::PartitionPlacement;
     use crate::contract::schema_column::SchemaColumn;
     use crate::contract::schema_table::SchemaTable;
@@ -21,10 +23,10 @@ mod tests {
     use crate::contract::table_info::TableInfo;
     use crate::sql::binder::Binder;
     use crate::sql::bound_stmt::{
-        AggregateFunc, BoundCommand, BoundPredicate, BoundQuery, BoundSelectItem, BoundSetValue,
-        BoundStmt,
+        AggregateFunc, BoundCommand, BoundCondition, BoundJoinCondition, BoundOrderBy,
+        BoundPredicate, BoundQuery, BoundSelectItem, BoundSetValue, BoundStmt,
     };
-    use crate::x_engine::api::DeltaOp;
+    use crate::x_engine::api::{AlterTable, DeltaOp};
     use async_trait::async_trait;
     use mudu::common::id::OID;
     use mudu::common::result::RS;
@@ -40,6 +42,7 @@ mod tests {
     use mudu_type::type_family::TypeFamily;
     use sql_parser::ast::expr_operator::ValueCompare;
     use sql_parser::ast::parser::SQLParser;
+    use sql_parser::ast::select_join::JoinKind;
     use sql_parser::ast::stmt_type::StmtType;
     use std::collections::HashMap;
     use std::sync::Arc;
@@ -240,6 +243,25 @@ mod tests {
         ))))
     }
 
+    fn json_binder() -> Binder {
+        let column = |name: &str, family: TypeFamily| {
+            SchemaColumn::new(
+                name.to_string(),
+                family,
+                DataTypeInfo::from_opt_object(&DataType::default_for(family)),
+            )
+        };
+        Binder::new(Arc::new(TestMetaMgr::new(SchemaTable::new(
+            "docs".to_string(),
+            vec![
+                column("id", TypeFamily::I32),
+                column("doc", TypeFamily::Json),
+            ],
+            vec![0],
+            vec![1],
+        ))))
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)]
     fn bind_select_builds_key_eq_predicate() {
@@ -644,19 +666,27 @@ mod tests {
 
     #[test]
     #[cfg_attr(miri, ignore)]
-    fn bind_update_rejects_expression_updates() {
+    fn bind_update_binds_expression_updates() {
         mudu_sys::task::async_::block_on_tokio_current_thread(async move {
-            let err = binder()
+            let bound = binder()
                 .bind(
-                    parse_stmt("update users set name = id + 1 where id = 1;"),
-                    &(),
+                    parse_stmt("update users set name = upper(name) || ? where id = 1;"),
+                    &("!".to_string(),),
                 )
                 .await
-                .unwrap_err();
+                .unwrap();
 
-            assert!(err
-                .to_string()
-                .contains("expression updates are not implemented"));
+            let BoundStmt::Command(BoundCommand::Update(update)) = bound else {
+                panic!("expected bound update");
+            };
+            assert_eq!(update.value.len(), 1);
+            let BoundSetValue::Expr(expr) = &update.value[0].1 else {
+                panic!("expected expression assignment");
+            };
+            assert_eq!(expr.data_type().type_family(), TypeFamily::String);
+            let mut attrs = Vec::new();
+            expr.collect_attrs(&mut attrs);
+            assert_eq!(attrs, vec![1]);
         })
         .unwrap()
     }
@@ -859,46 +889,66 @@ mod tests {
 
             assert!(err
                 .to_string()
-                .contains("expression updates are not implemented"));
+                .contains("non-integer delta parameters are not implemented"));
         })
         .unwrap()
     }
 
     #[test]
     #[cfg_attr(miri, ignore)]
-    fn bind_update_rejects_unsupported_expression_forms() {
+    fn bind_update_binds_other_expression_forms_as_expressions() {
         mudu_sys::task::async_::block_on_tokio_current_thread(async move {
-            // Left operand must be the assigned column itself.
+            // Only `col = col +|- <integer>` is a delta.
             for sql in [
                 // Different column on the left.
                 "update counters set count = id + 1 where id = 1;",
                 // Literal on the left.
                 "update counters set count = 1 + count where id = 1;",
-                // Only + and - are supported.
+                // Only + and - are deltas.
                 "update counters set count = count * 2 where id = 1;",
                 // Placeholder on the left is not the assigned column.
                 "update counters set count = ? + count where id = 1;",
-                // Non-integer assigned column.
-                "update users set name = name + 1 where id = 1;",
-                // Non-integer literal.
-                "update counters set count = count + 'x' where id = 1;",
             ] {
-                let b = if sql.contains("users") {
-                    binder()
-                } else {
-                    counter_binder()
+                let bound = counter_binder()
+                    .bind(parse_stmt(sql), &(2i32,))
+                    .await
+                    .unwrap();
+                let BoundStmt::Command(BoundCommand::Update(update)) = bound else {
+                    panic!("expected bound update");
                 };
-                let err = b.bind(parse_stmt(sql), &()).await.unwrap_err();
                 assert!(
-                    err.to_string()
-                        .contains("expression updates are not implemented"),
-                    "expected NotImplemented for {sql}, got {err}"
+                    matches!(update.value[0].1, BoundSetValue::Expr(_)),
+                    "expected an expression assignment for {sql}"
                 );
             }
         })
         .unwrap()
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn bind_update_rejects_ill_typed_expressions() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let err = binder()
+                .bind(
+                    parse_stmt("update users set name = name + 1 where id = 1;"),
+                    &(),
+                )
+                .await
+                .unwrap_err();
+            assert_eq!(err.ec(), ErrorCode::InvalidType);
+            let err = counter_binder()
+                .bind(
+                    parse_stmt("update counters set count = count + 'x' where id = 1;"),
+                    &(),
+                )
+                .await
+                .unwrap_err();
+            assert_eq!(err.ec(), ErrorCode::TypeConversionFailed);
+        })
+        .unwrap()
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)]
     fn bind_delete_rejects_non_key_predicates() {
@@ -969,6 +1019,10 @@ mod tests {
     }
 
     fn partitioned_binder() -> Binder {
+        partitioned_binder_with_rule(rule_with_bounds("r_orders"))
+    }
+
+    fn partitioned_binder_with_rule(rule: PartitionRuleDesc) -> Binder {
         let schema = SchemaTable::new(
             "orders".to_string(),
             vec![
@@ -991,10 +1045,7 @@ mod tests {
             vec![0, 1],
             vec![2],
         );
-        Binder::new(Arc::new(TestMetaMgr::with_rule(
-            schema,
-            rule_with_bounds("r_orders"),
-        )))
+        Binder::new(Arc::new(TestMetaMgr::with_rule(schema, rule)))
     }
 
     #[test]
@@ -1023,6 +1074,120 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn bind_create_hash_and_list_partition_rules() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let bound = binder()
+                .bind(
+                    parse_stmt("CREATE PARTITION RULE r_hash HASH (user_id) PARTITIONS 3;"),
+                    &(),
+                )
+                .await
+                .unwrap();
+            let BoundStmt::Command(BoundCommand::CreatePartitionRule(rule)) = bound else {
+                panic!("expected create partition rule");
+            };
+            assert_eq!(
+                rule.rule.kind,
+                PartitionRuleKind::Hash {
+                    columns: vec!["user_id".to_string()]
+                }
+            );
+            assert_eq!(rule.rule.partitions.len(), 3);
+
+            let bound = binder()
+                .bind(
+                    parse_stmt(
+                        "CREATE PARTITION RULE r_list LIST (
+                            PARTITION east VALUES IN (('ny', 1), ('ma', 2)),
+                            PARTITION west VALUES IN (('ca', 3))
+                        );",
+                    ),
+                    &(),
+                )
+                .await
+                .unwrap();
+            let BoundStmt::Command(BoundCommand::CreatePartitionRule(rule)) = bound else {
+                panic!("expected create partition rule");
+            };
+            assert_eq!(rule.rule.kind, PartitionRuleKind::List);
+            assert_eq!(
+                rule.rule.key_types,
+                vec![TypeFamily::String, TypeFamily::I64]
+            );
+            assert_eq!(rule.rule.partitions[0].values.len(), 2);
+
+            for sql in [
+                "CREATE PARTITION RULE r_hash HASH (user_id) PARTITIONS 0;",
+                "CREATE PARTITION RULE r_list LIST (
+                    PARTITION east VALUES IN (('ny', 1)),
+                    PARTITION west VALUES IN ('ca')
+                );",
+            ] {
+                let err = binder().bind(parse_stmt(sql), &()).await.unwrap_err();
+                assert_eq!(err.ec(), ErrorCode::Parse, "{}", sql);
+            }
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn bind_create_table_checks_hash_rule_reference_count() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let rule = PartitionRuleDesc::new_hash(
+                "r_orders".to_string(),
+                vec!["region_id".to_string()],
+                4,
+            );
+            let sql = |refs: &str| {
+                format!(
+                    "CREATE TABLE orders (
+                        region_id INT,
+                        order_id INT,
+                        amount INT,
+                        PRIMARY KEY (region_id, order_id)
+                    ) PARTITION BY GLOBAL RULE r_orders REFERENCES ({});",
+                    refs
+                )
+            };
+            let bound = partitioned_binder_with_rule(rule.clone())
+                .bind(parse_stmt(&sql("region_id")), &())
+                .await
+                .unwrap();
+            let BoundStmt::Command(BoundCommand::CreateTable(create)) = bound else {
+                panic!("expected create table");
+            };
+            assert_eq!(
+                create.partition_binding.as_ref().unwrap().ref_attr_indices,
+                vec![0]
+            );
+
+            let err = partitioned_binder_with_rule(rule.clone())
+                .bind(parse_stmt(&sql("region_id, order_id")), &())
+                .await
+                .unwrap_err();
+            assert_eq!(err.ec(), ErrorCode::Parse);
+
+            // UUID keys have no canonical hash key text.
+            let err = partitioned_binder_with_rule(rule)
+                .bind(
+                    parse_stmt(
+                        "CREATE TABLE devices (
+                            region_id UUID PRIMARY KEY,
+                            name TEXT
+                        ) PARTITION BY GLOBAL RULE r_orders REFERENCES (region_id);",
+                    ),
+                    &(),
+                )
+                .await
+                .unwrap_err();
+            assert_eq!(err.ec(), ErrorCode::NotImplemented);
+        })
+        .unwrap()
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)]
     fn bind_create_partition_placement_resolves_partition_and_worker() {
@@ -1051,6 +1216,67 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn bind_alter_partition_rule_split_and_move() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let rule = rule_with_bounds("r_orders");
+            let bound = partitioned_binder_with_rule(rule.clone())
+                .bind(
+                    parse_stmt("ALTER PARTITION RULE r_orders SPLIT AT (50);"),
+                    &(),
+                )
+                .await
+                .unwrap();
+            let BoundStmt::Command(BoundCommand::AlterPartitionRule(alter)) = bound else {
+                panic!("expected alter partition rule");
+            };
+            assert_eq!(alter.rule.version, rule.version + 1);
+            let names = alter
+                .rule
+                .partitions
+                .iter()
+                .map(|def| def.name.as_str())
+                .collect::<Vec<_>>();
+            assert_eq!(names, vec!["p0", "p2", "p1"]);
+            assert_eq!(
+                alter.rule.partitions[0].end,
+                PartitionBound::Value(vec![b"50".to_vec()])
+            );
+            assert!(alter.placements.is_empty());
+
+            let bound = partitioned_binder_with_rule(rule.clone())
+                .bind(
+                    parse_stmt("ALTER PARTITION RULE r_orders MOVE PARTITION p1 TO WORKER 12;"),
+                    &(),
+                )
+                .await
+                .unwrap();
+            let BoundStmt::Command(BoundCommand::AlterPartitionRule(alter)) = bound else {
+                panic!("expected alter partition rule");
+            };
+            assert_eq!(alter.rule.version, rule.version + 1);
+            assert_eq!(alter.placements.len(), 1);
+            assert_eq!(
+                alter.placements[0].partition_id,
+                rule.partitions[1].partition_id
+            );
+            assert_eq!(alter.placements[0].worker_id, 12);
+
+            for sql in [
+                "ALTER PARTITION RULE r_orders SPLIT AT (100);",
+                "ALTER PARTITION RULE r_orders SPLIT AT (50) INTO p1;",
+                "ALTER PARTITION RULE r_orders MOVE PARTITION p9 TO WORKER 12;",
+            ] {
+                let result = partitioned_binder_with_rule(rule.clone())
+                    .bind(parse_stmt(sql), &())
+                    .await;
+                assert!(result.is_err(), "{}", sql);
+            }
+        })
+        .unwrap()
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)]
     fn bind_create_table_with_partition_binding_resolves_rule() {
@@ -1306,6 +1532,112 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn bind_alter_table_resolves_actions() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let bound = binder()
+                .bind(
+                    parse_stmt(
+                        "alter table users add column age bigint not null, \
+                         drop column if exists missing, rename column name to full_name, \
+                         alter column age type int;",
+                    ),
+                    &(),
+                )
+                .await
+                .unwrap();
+
+            let BoundStmt::Command(BoundCommand::AlterTable(alter)) = bound else {
+                panic!("expected bound alter table");
+            };
+            assert!(alter.oid.is_some());
+            // DROP COLUMN IF EXISTS on a missing column binds to no action.
+            assert_eq!(alter.actions.len(), 3);
+            let AlterTable::AddColumn(column) = &alter.actions[0] else {
+                panic!("expected add column");
+            };
+            assert_eq!(column.get_name(), "age");
+            assert_eq!(column.type_id(), TypeFamily::I64);
+            assert!(!column.nullable());
+            assert!(matches!(
+                &alter.actions[1],
+                AlterTable::RenameColumn { old_name, new_name }
+                    if old_name == "name" && new_name == "full_name"
+            ));
+            assert!(matches!(
+                &alter.actions[2],
+                AlterTable::AlterColumnType { name, type_id, .. }
+                    if name == "age" && *type_id == TypeFamily::I32
+            ));
+
+            let bound = binder()
+                .bind(
+                    parse_stmt("alter table if exists missing drop column x;"),
+                    &(),
+                )
+                .await
+                .unwrap();
+            let BoundStmt::Command(BoundCommand::AlterTable(alter)) = bound else {
+                panic!("expected bound alter table");
+            };
+            assert!(alter.oid.is_none());
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn bind_create_and_drop_index() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let bound = binder()
+                .bind(parse_stmt("create index users_name on users (name);"), &())
+                .await
+                .unwrap();
+            let BoundStmt::Command(BoundCommand::AlterTable(alter)) = bound else {
+                panic!("expected bound alter table");
+            };
+            assert!(alter.oid.is_some());
+            let [AlterTable::AddIndex(index)] = alter.actions.as_slice() else {
+                panic!("expected add index");
+            };
+            assert_eq!(index.name(), "users_name");
+            assert_eq!(index.column_oids().len(), 1);
+
+            let err = binder()
+                .bind(parse_stmt("create index users_x on users (missing);"), &())
+                .await
+                .unwrap_err();
+            assert_eq!(err.ec(), ErrorCode::EntityNotFound);
+
+            let err = binder()
+                .bind(
+                    parse_stmt("create index users_x on users (name, name);"),
+                    &(),
+                )
+                .await
+                .unwrap_err();
+            assert_eq!(err.ec(), ErrorCode::UnsupportedOperation);
+
+            let err = binder()
+                .bind(parse_stmt("drop index users_name;"), &())
+                .await
+                .unwrap_err();
+            assert_eq!(err.ec(), ErrorCode::EntityNotFound);
+
+            let bound = binder()
+                .bind(parse_stmt("drop index if exists users_name;"), &())
+                .await
+                .unwrap();
+            let BoundStmt::Command(BoundCommand::AlterTable(alter)) = bound else {
+                panic!("expected bound alter table");
+            };
+            assert!(alter.oid.is_none());
+            assert!(alter.actions.is_empty());
+        })
+        .unwrap()
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)]
     fn bind_drop_table_fails_when_missing_without_if_exists() {
@@ -1533,7 +1865,7 @@ mod tests {
             .iter()
             .map(|item| match item {
                 BoundSelectItem::Aggregate(aggregate) => aggregate.result_type.type_family(),
-                BoundSelectItem::Column(_) => panic!("expected aggregate"),
+                _ => panic!("expected aggregate"),
             })
             .collect();
         assert_eq!(
@@ -1556,6 +1888,52 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn bind_select_expression_items() {
+        let select = bind_select("select id * 2 as twice, upper(name), id from users;");
+        let names: Vec<&str> = select
+            .tuple_desc
+            .fields()
+            .iter()
+            .map(|field| field.name())
+            .collect();
+        assert_eq!(names, vec!["twice", "upper", "id"]);
+        let families: Vec<TypeFamily> = select
+            .select_items
+            .iter()
+            .map(|item| match item {
+                BoundSelectItem::Expression(expr) => expr.expr.data_type().type_family(),
+                BoundSelectItem::Column(_) => TypeFamily::I32,
+                BoundSelectItem::Aggregate(_) => panic!("unexpected aggregate"),
+            })
+            .collect();
+        assert_eq!(
+            families,
+            vec![TypeFamily::I64, TypeFamily::String, TypeFamily::I32]
+        );
+        assert_eq!(
+            bind_select_err("select count(*), id * 2 from users;"),
+            ErrorCode::NotImplemented
+        );
+        assert_eq!(
+            bind_select_err("select id from users order by id * 2;"),
+            ErrorCode::NotImplemented
+        );
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn bind_where_expression_becomes_condition() {
+        let select = bind_select("select id from users where id + 1 > 2 and name = 'x';");
+        assert_eq!(select.residual.len(), 1);
+        assert!(matches!(select.conditions[..], [BoundCondition::Expr(_)]));
+        assert_eq!(
+            bind_select_err("select id from users where name = id;"),
+            ErrorCode::InvalidType
+        );
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)]
     fn bind_select_unsupported_function_rejected() {
@@ -1607,6 +1985,182 @@ mod tests {
         ));
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn bind_select_key_in_list_reads_distinct_keys() {
+        let select = bind_select("select id from users where id in (3, 1, 3, null);");
+        let BoundPredicate::KeyIn { keys } = &select.predicate else {
+            panic!("expected key list predicate, got {:?}", select.predicate);
+        };
+        // The duplicate is read once and NULL matches no key.
+        assert_eq!(keys.len(), 2);
+        assert!(keys.iter().all(|key| key.len() == 1 && key[0].0 == 0));
+        assert!(select.conditions.is_empty());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn bind_select_key_prefix_with_or_of_keys() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let bound = composite_binder()
+                .bind(
+                    parse_stmt(
+                        "select name from accounts \
+                         where tenant_id = 1 and (user_id = 2 or user_id = 3) and name like 'a%';",
+                    ),
+                    &(),
+                )
+                .await
+                .unwrap();
+            let BoundStmt::Query(BoundQuery::Select(select)) = bound else {
+                panic!("expected bound select");
+            };
+            let BoundPredicate::KeyIn { keys } = &select.predicate else {
+                panic!("expected key list predicate, got {:?}", select.predicate);
+            };
+            let attrs: Vec<Vec<usize>> = keys
+                .iter()
+                .map(|key| key.iter().map(|(attr, _)| *attr).collect())
+                .collect();
+            assert_eq!(attrs, vec![vec![0, 1], vec![0, 1]]);
+            assert!(matches!(
+                select.conditions.as_slice(),
+                [BoundCondition::Like {
+                    attr: 2,
+                    case_insensitive: false,
+                    negated: false,
+                    ..
+                }]
+            ));
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn bind_select_or_on_non_key_column_becomes_condition() {
+        let select = bind_select(
+            "select id from users where name = 'a' or name is null or id between 1 and 5;",
+        );
+        assert!(matches!(select.predicate, BoundPredicate::True));
+        assert!(select.residual.is_empty());
+        let [BoundCondition::Or(operands)] = select.conditions.as_slice() else {
+            panic!("expected one OR condition, got {:?}", select.conditions);
+        };
+        assert!(matches!(
+            operands.as_slice(),
+            [
+                BoundCondition::Compare {
+                    attr: 1,
+                    op: ValueCompare::EQ,
+                    literal: Some(_),
+                },
+                BoundCondition::IsNull {
+                    attr: 1,
+                    negated: false,
+                },
+                BoundCondition::Between { attr: 0, .. },
+            ]
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn bind_where_conditions_rejected_where_unsupported() {
+        assert_eq!(
+            bind_select_err("select id from users where id like '1%';"),
+            ErrorCode::NotImplemented
+        );
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn bind_update_delete_with_where_conditions_scan_the_keys() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let bound = binder()
+                .bind(
+                    parse_stmt("update users set name = 'x' where id = 1 or name is null;"),
+                    &(),
+                )
+                .await
+                .unwrap();
+            let BoundStmt::Command(BoundCommand::Update(update)) = bound else {
+                panic!("expected bound update");
+            };
+            assert!(update.key.is_empty());
+            let scan = update.scan.expect("expected a key scan");
+            assert!(matches!(
+                scan.select_items.as_slice(),
+                [BoundSelectItem::Column(column)] if column.attr == 0
+            ));
+            assert!(matches!(
+                scan.conditions.as_slice(),
+                [BoundCondition::Or(_)]
+            ));
+
+            let bound = binder()
+                .bind(parse_stmt("delete from users where id in (1, 2);"), &())
+                .await
+                .unwrap();
+            let BoundStmt::Command(BoundCommand::Delete(delete)) = bound else {
+                panic!("expected bound delete");
+            };
+            assert!(delete.key.is_empty());
+            let scan = delete.scan.expect("expected a key scan");
+            assert!(matches!(scan.predicate, BoundPredicate::KeyIn { .. }));
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn bind_select_group_by_having_order_by_limit() {
+        let select = bind_select(
+            "select name, count(*) as c from users group by name having count(*) > 1 \
+            order by c desc, name limit 5 offset 2;",
+        );
+        assert_eq!(select.group_by, vec![1]);
+        assert!(select.hidden_items.is_empty());
+        assert_eq!(select.having.len(), 1);
+        assert_eq!(select.having[0].pos, 1);
+        assert_eq!(select.having[0].op, ValueCompare::GT);
+        assert_eq!(
+            select.order.order_by,
+            vec![
+                BoundOrderBy {
+                    pos: 1,
+                    descending: true,
+                    nulls_first: true,
+                },
+                BoundOrderBy {
+                    pos: 0,
+                    descending: false,
+                    nulls_first: false,
+                },
+            ]
+        );
+        assert_eq!(select.order.offset, 2);
+        assert_eq!(select.order.limit, Some(5));
+
+        // A sort column outside the select list is carried as a hidden item.
+        let select = bind_select("select id from users order by name;");
+        assert_eq!(select.hidden_items.len(), 1);
+        assert_eq!(select.order.order_by[0].pos, 1);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn bind_select_ungrouped_column_rejected() {
+        assert_eq!(
+            bind_select_err("select id, count(*) from users group by name;"),
+            ErrorCode::Parse
+        );
+        assert_eq!(
+            bind_select_err("select id from users order by 2;"),
+            ErrorCode::Parse
+        );
+    }
+
     /// Timing probe for the statement-path cost discussion: how expensive is
     /// `Binder::bind` for TPC-C-shaped statements (literals, no params).
     /// Not a correctness test; prints per-bind cost.
@@ -1695,4 +2249,130 @@ mod tests {
         })
         .unwrap()
     }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn bind_join_select_resolves_qualified_columns() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let meta_mgr = Arc::new(TestMetaMgr::new(schema()));
+            meta_mgr.create_table(&composite_schema()).await.unwrap();
+            let binder = Binder::new(meta_mgr);
+            let params = mudu_contract::database::sql_param_value::SQLParamValue::from_vec(vec![
+                DataValue::from_i32(7),
+            ]);
+            let bound = binder
+                .bind(
+                    parse_stmt(
+                        "select u.name, a.tenant_id from users u left join accounts a \
+                         on a.user_id = u.id and a.tenant_id = ? where u.id > 1;",
+                    ),
+                    &params,
+                )
+                .await
+                .unwrap();
+
+            let BoundStmt::Query(BoundQuery::Join(join)) = bound else {
+                panic!("expected bound join select");
+            };
+            // users(id, name) is followed by accounts(tenant_id, user_id, name).
+            assert_eq!(join.projection, vec![1, 2]);
+            assert!(join.tuple_desc.fields()[1].nullable());
+            assert_eq!(join.joins.len(), 1);
+            assert_eq!(join.joins[0].kind, JoinKind::LeftOuter);
+            match &join.joins[0].on[..] {
+                [BoundJoinCondition::Column {
+                    left: 3,
+                    op: ValueCompare::EQ,
+                    right: 0,
+                }, BoundJoinCondition::Literal {
+                    pos: 2,
+                    op: ValueCompare::EQ,
+                    literal: Some(literal),
+                }] => {
+                    let expected = DataValue::from_i32(7)
+                        .to_binary(&DataType::default_for(TypeFamily::I32))
+                        .unwrap();
+                    assert_eq!(literal.as_slice(), expected.as_ref());
+                }
+                other => panic!("unexpected ON conditions {other:?}"),
+            }
+            assert!(matches!(
+                join.filter[..],
+                [BoundJoinCondition::Literal {
+                    pos: 0,
+                    op: ValueCompare::GT,
+                    ..
+                }]
+            ));
+
+            let err = binder
+                .bind(
+                    parse_stmt("select name from users join accounts on user_id = id;"),
+                    &(),
+                )
+                .await
+                .unwrap_err();
+            assert_eq!(err.ec(), ErrorCode::Parse);
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn bind_select_json_paths_as_virtual_columns() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let bound = json_binder()
+                .bind(
+                    parse_stmt(
+                        "select id, doc->>'name' from docs where doc->>'kind' = 'a' and id > 1;",
+                    ),
+                    &(),
+                )
+                .await
+                .unwrap();
+            let BoundStmt::Query(BoundQuery::Select(select)) = bound else {
+                panic!("expected bound select");
+            };
+            // Both paths follow the two table columns as attributes 2 and 3.
+            assert_eq!(select.json_columns.len(), 2);
+            assert!(select.json_columns.iter().all(|column| column.attr == 1));
+            assert!(matches!(
+                &select.select_items[1],
+                BoundSelectItem::Column(column) if column.attr == 2
+            ));
+            let field = &select.tuple_desc.fields()[1];
+            assert_eq!(field.name(), "doc->>'name'");
+            assert_eq!(field.type_family(), TypeFamily::String);
+            // The key comparison still drives the scan; the path comparison
+            // is evaluated row by row.
+            assert!(matches!(select.predicate, BoundPredicate::KeyRange { .. }));
+            assert!(matches!(
+                select.conditions[..],
+                [BoundCondition::Compare { attr: 3, .. }]
+            ));
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn bind_select_json_path_rejects_non_json_columns_and_grouping() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let binder = json_binder();
+            let err = binder
+                .bind(parse_stmt("select id->'a' from docs;"), &())
+                .await
+                .unwrap_err();
+            assert_eq!(err.ec(), ErrorCode::InvalidArgument);
+            let err = binder
+                .bind(
+                    parse_stmt("select doc->'a', count(*) from docs group by id;"),
+                    &(),
+                )
+                .await
+                .unwrap_err();
+            assert_eq!(err.ec(), ErrorCode::NotImplemented);
+        })
+        .unwrap()
+    }
 }
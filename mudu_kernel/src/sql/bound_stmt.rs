@@ -2,11 +2,15 @@ use crate::contract::fs_type::FsTypeKind;
 use crate::contract::partition_rule::PartitionRuleDesc;
 use crate::contract::partition_rule_binding::{PartitionPlacement, TablePartitionBinding};
 use crate::contract::schema_table::SchemaTable;
-use crate::x_engine::api::DeltaOp;
+use crate::x_engine::api::{AlterTable, DeltaOp};
 use mudu::common::id::{AttrIndex, OID};
+use mudu::common::result::RS;
 use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
 use mudu_type::data_type_fn_param::DataType;
-use sql_parser::ast::expr_operator::ValueCompare;
+use mudu_type::type_family::TypeFamily;
+use sql_parser::ast::expr_json_path::JsonPath;
+use sql_parser::ast::expr_operator::{Arithmetic, ValueCompare};
+use sql_parser::ast::select_join::JoinKind;
 use std::ops::Bound;
 
 #[derive(Clone, Debug)]
@@ -18,14 +22,26 @@ pub enum BoundStmt {
 #[derive(Clone, Debug)]
 pub enum BoundQuery {
     Select(BoundSelect),
+    Join(BoundJoinSelect),
+    Explain(BoundExplain),
+}
+
+/// `EXPLAIN [ANALYZE]`: reports the plan of `query` instead of its rows;
+/// with `analyze` the query also runs.
+#[derive(Clone, Debug)]
+pub struct BoundExplain {
+    pub analyze: bool,
+    pub query: Box<BoundQuery>,
 }
 
 #[derive(Clone, Debug)]
 pub enum BoundCommand {
     CreatePartitionPlacement(BoundCreatePartitionPlacement),
     CreatePartitionRule(BoundCreatePartitionRule),
+    AlterPartitionRule(BoundAlterPartitionRule),
     CreateTable(BoundCreateTable),
     DropTable(BoundDropTable),
+    AlterTable(BoundAlterTable),
     CreateFsType(BoundCreateFsType),
     DropType(BoundDropType),
     Insert(BoundInsert),
@@ -35,6 +51,18 @@ pub enum BoundCommand {
     CopyTo(BoundCopyTo),
 }
 
+impl BoundCommand {
+    /// Whether the command has a `RETURNING` clause, i.e. produces rows.
+    pub fn has_returning(&self) -> bool {
+        match self {
+            BoundCommand::Insert(insert) => insert.returning.is_some(),
+            BoundCommand::Update(update) => update.returning.is_some(),
+            BoundCommand::Delete(delete) => delete.returning.is_some(),
+            _ => false,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct BoundSelect {
     pub table_id: OID,
@@ -44,14 +72,38 @@ pub struct BoundSelect {
     /// Residual (non-key) predicates evaluated row-by-row in the executor
     /// layer after the key access.
     pub residual: Vec<BoundResidual>,
+    /// `WHERE` conjuncts other than comparisons, evaluated row-by-row in the
+    /// executor layer together with the residual predicates.
+    pub conditions: Vec<BoundCondition>,
+    /// `GROUP BY` attributes; empty without grouping.
+    pub group_by: Vec<AttrIndex>,
+    /// Items computed after the select list only for `HAVING` and
+    /// `ORDER BY`; they are dropped from the output.
+    pub hidden_items: Vec<BoundSelectItem>,
+    /// `HAVING` comparisons over the select list and hidden items.
+    pub having: Vec<BoundHaving>,
+    /// `ORDER BY` over the select list and hidden items, and `LIMIT`.
+    pub order: BoundRowOrder,
+    /// JSON path columns; the `i`-th one is addressed as attribute
+    /// `table columns + i` by select items and conditions.
+    pub json_columns: Vec<BoundJsonColumn>,
 }
 
-/// A bound select-list item: either a plain column projection or an
-/// aggregate over the whole (filtered) input.
+/// A `->`/`->>` JSON path over a JSON column, used as a virtual column.
+#[derive(Clone, Debug)]
+pub struct BoundJsonColumn {
+    /// The JSON column the path is applied to.
+    pub attr: AttrIndex,
+    pub path: JsonPath,
+}
+
+/// A bound select-list item: a plain column projection, an aggregate over
+/// the whole (filtered) input, or a scalar expression computed per row.
 #[derive(Clone, Debug)]
 pub enum BoundSelectItem {
     Column(BoundSelectColumn),
     Aggregate(BoundAggregate),
+    Expression(BoundSelectExpr),
 }
 
 /// A plain column projection in a select list.
@@ -61,7 +113,14 @@ pub struct BoundSelectColumn {
     pub output_name: String,
 }
 
-/// Supported aggregate functions (without `GROUP BY`).
+/// A scalar expression in a select list, e.g. `price * qty AS total`.
+#[derive(Clone, Debug)]
+pub struct BoundSelectExpr {
+    pub expr: BoundExpr,
+    pub output_name: String,
+}
+
+/// Supported aggregate functions.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum AggregateFunc {
     Count,
@@ -94,11 +153,457 @@ pub struct BoundResidual {
     pub literal: Option<Vec<u8>>,
 }
 
+/// A `WHERE` condition over the columns of one table, evaluated with SQL
+/// three-valued logic. Every literal is encoded in its column's binary format
+/// and `None` is NULL.
+#[derive(Clone, Debug)]
+pub enum BoundCondition {
+    And(Vec<BoundCondition>),
+    Or(Vec<BoundCondition>),
+    Not(Box<BoundCondition>),
+    Compare {
+        attr: AttrIndex,
+        op: ValueCompare,
+        literal: Option<Vec<u8>>,
+    },
+    InList {
+        attr: AttrIndex,
+        list: Vec<Option<Vec<u8>>>,
+        negated: bool,
+    },
+    Between {
+        attr: AttrIndex,
+        low: Option<Vec<u8>>,
+        high: Option<Vec<u8>>,
+        negated: bool,
+    },
+    /// `LIKE` on a string column; `%` matches any run of characters and `_`
+    /// one character.
+    Like {
+        attr: AttrIndex,
+        pattern: Option<Vec<u8>>,
+        case_insensitive: bool,
+        negated: bool,
+    },
+    IsNull {
+        attr: AttrIndex,
+        negated: bool,
+    },
+    /// Any other boolean scalar expression, e.g. `lower(name) = 'bob'`.
+    Expr(BoundExpr),
+}
+
+/// A scalar expression over the columns of one table. Every node carries
+/// its result type, and the operands of an operator or comparison already
+/// share one type family (the binder inserts `Cast` nodes where needed).
+///
+/// A constant is encoded in its own type's binary format, `None` being
+/// NULL; parameter templates use `TemplateDatum` constants instead.
+#[derive(Clone, Debug)]
+pub enum BoundExpr<D = Option<Vec<u8>>> {
+    Column {
+        attr: AttrIndex,
+        data_type: DataType,
+    },
+    Const {
+        value: D,
+        data_type: DataType,
+    },
+    /// `+`, `-`, `*`, `/` and `%` over numbers, or `||` over strings.
+    Arithmetic {
+        op: Arithmetic,
+        left: Box<BoundExpr<D>>,
+        right: Box<BoundExpr<D>>,
+        data_type: DataType,
+    },
+    Compare {
+        op: ValueCompare,
+        left: Box<BoundExpr<D>>,
+        right: Box<BoundExpr<D>>,
+    },
+    And(Vec<BoundExpr<D>>),
+    Or(Vec<BoundExpr<D>>),
+    Not(Box<BoundExpr<D>>),
+    IsNull {
+        operand: Box<BoundExpr<D>>,
+        negated: bool,
+    },
+    InList {
+        operand: Box<BoundExpr<D>>,
+        list: Vec<BoundExpr<D>>,
+        negated: bool,
+    },
+    Between {
+        operand: Box<BoundExpr<D>>,
+        low: Box<BoundExpr<D>>,
+        high: Box<BoundExpr<D>>,
+        negated: bool,
+    },
+    Like {
+        operand: Box<BoundExpr<D>>,
+        pattern: Box<BoundExpr<D>>,
+        case_insensitive: bool,
+        negated: bool,
+    },
+    /// `CASE WHEN .. THEN ..`; the first branch whose condition is TRUE
+    /// wins. A simple `CASE x WHEN v ..` is bound as `x = v` conditions.
+    Case {
+        branches: Vec<(BoundExpr<D>, BoundExpr<D>)>,
+        else_result: Option<Box<BoundExpr<D>>>,
+        data_type: DataType,
+    },
+    Cast {
+        operand: Box<BoundExpr<D>>,
+        data_type: DataType,
+    },
+    Function {
+        func: ScalarFunc,
+        args: Vec<BoundExpr<D>>,
+        data_type: DataType,
+    },
+}
+
+impl<D> BoundExpr<D> {
+    /// Result type of the expression; predicates are boolean.
+    pub fn data_type(&self) -> DataType {
+        match self {
+            BoundExpr::Column { data_type, .. }
+            | BoundExpr::Const { data_type, .. }
+            | BoundExpr::Arithmetic { data_type, .. }
+            | BoundExpr::Case { data_type, .. }
+            | BoundExpr::Cast { data_type, .. }
+            | BoundExpr::Function { data_type, .. } => data_type.clone(),
+            _ => DataType::default_for(TypeFamily::Bool),
+        }
+    }
+
+    /// The direct operands of the expression.
+    pub fn children(&self) -> Vec<&BoundExpr<D>> {
+        match self {
+            BoundExpr::Column { .. } | BoundExpr::Const { .. } => Vec::new(),
+            BoundExpr::Arithmetic { left, right, .. } | BoundExpr::Compare { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            BoundExpr::And(operands) | BoundExpr::Or(operands) => operands.iter().collect(),
+            BoundExpr::Not(operand)
+            | BoundExpr::IsNull { operand, .. }
+            | BoundExpr::Cast { operand, .. } => vec![operand.as_ref()],
+            BoundExpr::InList { operand, list, .. } => {
+                let mut children = vec![operand.as_ref()];
+                children.extend(list);
+                children
+            }
+            BoundExpr::Between {
+                operand, low, high, ..
+            } => vec![operand.as_ref(), low.as_ref(), high.as_ref()],
+            BoundExpr::Like {
+                operand, pattern, ..
+            } => vec![operand.as_ref(), pattern.as_ref()],
+            BoundExpr::Case {
+                branches,
+                else_result,
+                ..
+            } => {
+                let mut children = Vec::with_capacity(branches.len() * 2 + 1);
+                for (condition, result) in branches {
+                    children.push(condition);
+                    children.push(result);
+                }
+                children.extend(else_result.as_deref());
+                children
+            }
+            BoundExpr::Function { args, .. } => args.iter().collect(),
+        }
+    }
+
+    /// Append the columns the expression reads to `attrs`, in first-use
+    /// order.
+    pub fn collect_attrs(&self, attrs: &mut Vec<AttrIndex>) {
+        if let BoundExpr::Column { attr, .. } = self {
+            if !attrs.contains(attr) {
+                attrs.push(*attr);
+            }
+        }
+        for child in self.children() {
+            child.collect_attrs(attrs);
+        }
+    }
+
+    /// The same expression with every constant mapped through `f`.
+    pub fn try_map_const<E>(&self, f: &mut dyn FnMut(&D) -> RS<E>) -> RS<BoundExpr<E>> {
+        Ok(match self {
+            BoundExpr::Column { attr, data_type } => BoundExpr::Column {
+                attr: *attr,
+                data_type: data_type.clone(),
+            },
+            BoundExpr::Const { value, data_type } => BoundExpr::Const {
+                value: f(value)?,
+                data_type: data_type.clone(),
+            },
+            BoundExpr::Arithmetic {
+                op,
+                left,
+                right,
+                data_type,
+            } => BoundExpr::Arithmetic {
+                op: *op,
+                left: map_boxed(left, f)?,
+                right: map_boxed(right, f)?,
+                data_type: data_type.clone(),
+            },
+            BoundExpr::Compare { op, left, right } => BoundExpr::Compare {
+                op: *op,
+                left: map_boxed(left, f)?,
+                right: map_boxed(right, f)?,
+            },
+            BoundExpr::And(operands) => BoundExpr::And(map_all(operands, f)?),
+            BoundExpr::Or(operands) => BoundExpr::Or(map_all(operands, f)?),
+            BoundExpr::Not(operand) => BoundExpr::Not(map_boxed(operand, f)?),
+            BoundExpr::IsNull { operand, negated } => BoundExpr::IsNull {
+                operand: map_boxed(operand, f)?,
+                negated: *negated,
+            },
+            BoundExpr::InList {
+                operand,
+                list,
+                negated,
+            } => BoundExpr::InList {
+                operand: map_boxed(operand, f)?,
+                list: map_all(list, f)?,
+                negated: *negated,
+            },
+            BoundExpr::Between {
+                operand,
+                low,
+                high,
+                negated,
+            } => BoundExpr::Between {
+                operand: map_boxed(operand, f)?,
+                low: map_boxed(low, f)?,
+                high: map_boxed(high, f)?,
+                negated: *negated,
+            },
+            BoundExpr::Like {
+                operand,
+                pattern,
+                case_insensitive,
+                negated,
+            } => BoundExpr::Like {
+                operand: map_boxed(operand, f)?,
+                pattern: map_boxed(pattern, f)?,
+                case_insensitive: *case_insensitive,
+                negated: *negated,
+            },
+            BoundExpr::Case {
+                branches,
+                else_result,
+                data_type,
+            } => {
+                let mut mapped = Vec::with_capacity(branches.len());
+                for (condition, result) in branches {
+                    mapped.push((condition.try_map_const(f)?, result.try_map_const(f)?));
+                }
+                BoundExpr::Case {
+                    branches: mapped,
+                    else_result: match else_result {
+                        Some(else_result) => Some(map_boxed(else_result, f)?),
+                        None => None,
+                    },
+                    data_type: data_type.clone(),
+                }
+            }
+            BoundExpr::Cast { operand, data_type } => BoundExpr::Cast {
+                operand: map_boxed(operand, f)?,
+                data_type: data_type.clone(),
+            },
+            BoundExpr::Function {
+                func,
+                args,
+                data_type,
+            } => BoundExpr::Function {
+                func: *func,
+                args: map_all(args, f)?,
+                data_type: data_type.clone(),
+            },
+        })
+    }
+}
+
+fn map_boxed<D, E>(expr: &BoundExpr<D>, f: &mut dyn FnMut(&D) -> RS<E>) -> RS<Box<BoundExpr<E>>> {
+    expr.try_map_const(f).map(Box::new)
+}
+
+fn map_all<D, E>(exprs: &[BoundExpr<D>], f: &mut dyn FnMut(&D) -> RS<E>) -> RS<Vec<BoundExpr<E>>> {
+    exprs
+        .iter()
+        .map(|expr| expr.try_map_const(&mut *f))
+        .collect()
+}
+
+/// Built-in scalar functions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScalarFunc {
+    Lower,
+    Upper,
+    /// `substr(text, start[, length])`, counting characters from 1.
+    Substr,
+    Abs,
+    /// `round(x[, digits])`; `digits` must be a constant.
+    Round,
+    Coalesce,
+    /// `date_trunc('field', ts)`; the field is fixed at bind time.
+    DateTrunc(TimeField),
+    /// `extract(field FROM ts)` (NUMERIC result) and `date_part('field',
+    /// ts)` (double result).
+    Extract(TimeField),
+}
+
+/// A date/time field named by `date_trunc` and `extract`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeField {
+    Microsecond,
+    Millisecond,
+    Second,
+    Minute,
+    Hour,
+    Day,
+    /// Day of the week, Sunday = 0 (`extract` only).
+    DayOfWeek,
+    /// Day of the year, from 1 (`extract` only).
+    DayOfYear,
+    /// ISO 8601 week; `date_trunc` moves to its Monday.
+    Week,
+    Month,
+    Quarter,
+    Year,
+    /// Seconds since 1970-01-01 00:00:00 UTC (`extract` only).
+    Epoch,
+}
+
+impl TimeField {
+    /// The field named `name` (case-insensitive, singular or plural).
+    pub fn from_name(name: &str) -> Option<TimeField> {
+        let name = name.to_lowercase();
+        let name = name.strip_suffix('s').unwrap_or(&name);
+        Some(match name {
+            "microsecond" => TimeField::Microsecond,
+            "millisecond" => TimeField::Millisecond,
+            "second" => TimeField::Second,
+            "minute" => TimeField::Minute,
+            "hour" => TimeField::Hour,
+            "day" => TimeField::Day,
+            "dow" => TimeField::DayOfWeek,
+            "doy" => TimeField::DayOfYear,
+            "week" => TimeField::Week,
+            "month" => TimeField::Month,
+            "quarter" => TimeField::Quarter,
+            "year" => TimeField::Year,
+            "epoch" => TimeField::Epoch,
+            _ => return None,
+        })
+    }
+}
+
+/// A `HAVING` comparison; `pos` indexes the select list followed by the
+/// hidden items.
+#[derive(Clone, Debug)]
+pub struct BoundHaving {
+    pub pos: usize,
+    pub op: ValueCompare,
+    /// Literal encoded in the item's binary format; `None` is NULL.
+    pub literal: Option<Vec<u8>>,
+}
+
+/// One `ORDER BY` key; `pos` indexes the output row followed by the hidden
+/// columns.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BoundOrderBy {
+    pub pos: usize,
+    pub descending: bool,
+    pub nulls_first: bool,
+}
+
+/// `ORDER BY`, `OFFSET` and `LIMIT` of a query, applied in that order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BoundRowOrder {
+    pub order_by: Vec<BoundOrderBy>,
+    pub offset: u64,
+    pub limit: Option<u64>,
+}
+
+impl BoundRowOrder {
+    /// Whether rows pass through in input order, all of them.
+    pub fn is_empty(&self) -> bool {
+        self.order_by.is_empty() && self.offset == 0 && self.limit.is_none()
+    }
+}
+
+/// A `SELECT` over joined tables.
+///
+/// A joined row concatenates every column of every table in `FROM` order;
+/// column positions below index that row.
+#[derive(Clone, Debug)]
+pub struct BoundJoinSelect {
+    /// First table of the `FROM` clause.
+    pub table_id: OID,
+    /// Key access of the first table, from the `WHERE` comparisons of its
+    /// columns with literals, as for a single-table `SELECT`.
+    pub predicate: BoundPredicate,
+    /// Other `WHERE` comparisons of first-table columns with literals,
+    /// evaluated on its rows before they are joined.
+    pub residual: Vec<BoundResidual>,
+    pub joins: Vec<BoundJoin>,
+    /// Output columns as positions in the joined row.
+    pub projection: Vec<usize>,
+    pub tuple_desc: TupleFieldDesc,
+    /// The remaining `WHERE` conditions, evaluated on joined rows.
+    pub filter: Vec<BoundJoinCondition>,
+    /// Joined-row positions only needed by `ORDER BY`, projected after the
+    /// output columns and dropped after sorting.
+    pub hidden: Vec<usize>,
+    pub order: BoundRowOrder,
+}
+
+/// One joined table and its `ON` condition.
+#[derive(Clone, Debug)]
+pub struct BoundJoin {
+    pub kind: JoinKind,
+    pub table_id: OID,
+    /// `ON` conditions; they reference this table and the tables before it.
+    pub on: Vec<BoundJoinCondition>,
+}
+
+/// A comparison over the columns of a joined row.
+#[derive(Clone, Debug)]
+pub enum BoundJoinCondition {
+    /// `column OP literal`; the literal is encoded in the column's binary
+    /// format and `None` is NULL.
+    Literal {
+        pos: usize,
+        op: ValueCompare,
+        literal: Option<Vec<u8>>,
+    },
+    /// `column OP column` between columns of the same type family.
+    Column {
+        left: usize,
+        op: ValueCompare,
+        right: usize,
+    },
+}
+
 #[derive(Clone, Debug)]
 pub struct BoundCreatePartitionRule {
     pub rule: PartitionRuleDesc,
 }
 
+/// `ALTER PARTITION RULE`: the next version of the rule and the placements
+/// to record with it.
+#[derive(Clone, Debug)]
+pub struct BoundAlterPartitionRule {
+    pub rule: PartitionRuleDesc,
+    pub placements: Vec<PartitionPlacement>,
+}
+
 #[derive(Clone, Debug)]
 pub struct BoundCreatePartitionPlacement {
     pub placements: Vec<PartitionPlacement>,
@@ -115,6 +620,12 @@ pub struct BoundDropTable {
     pub oid: Option<OID>,
 }
 
+#[derive(Clone, Debug)]
+pub struct BoundAlterTable {
+    pub oid: Option<OID>,
+    pub actions: Vec<AlterTable>,
+}
+
 #[derive(Clone, Debug)]
 pub struct BoundCreateFsType {
     pub name: String,
@@ -130,6 +641,34 @@ pub struct BoundDropType {
 pub struct BoundInsert {
     pub table_id: OID,
     pub rows: Vec<BoundInsertRow>,
+    /// Columns the statement omits that have a `DEFAULT`.
+    pub default_attrs: Vec<AttrIndex>,
+    pub on_conflict: Option<BoundOnConflict>,
+    pub returning: Option<BoundReturning>,
+}
+
+/// `ON CONFLICT` action of an `INSERT`, taken when a row's primary key
+/// already exists.
+#[derive(Clone, Debug)]
+pub enum BoundOnConflict {
+    DoNothing,
+    /// Update the existing row; assignments are in statement order.
+    DoUpdate(Vec<(AttrIndex, BoundConflictValue)>),
+}
+
+/// Value assigned by one `ON CONFLICT DO UPDATE SET` item.
+#[derive(Clone, Debug)]
+pub enum BoundConflictValue {
+    Set(BoundSetValue),
+    /// `EXCLUDED.col`: the value the conflicting row proposed for `col`.
+    Excluded(AttrIndex),
+}
+
+/// `RETURNING` list of an `INSERT`, `UPDATE` or `DELETE`.
+#[derive(Clone, Debug)]
+pub struct BoundReturning {
+    pub attrs: Vec<AttrIndex>,
+    pub tuple_desc: TupleFieldDesc,
 }
 
 #[derive(Clone, Debug)]
@@ -143,6 +682,10 @@ pub struct BoundUpdate {
     pub table_id: OID,
     pub key: Vec<(AttrIndex, Vec<u8>)>,
     pub value: Vec<(AttrIndex, BoundSetValue)>,
+    pub returning: Option<BoundReturning>,
+    /// Scan of the primary key columns selecting the rows to update, for a
+    /// `WHERE` clause other than a primary key equality; `key` is empty then.
+    pub scan: Option<BoundSelect>,
 }
 
 /// Value assigned to a column by one `UPDATE ... SET` item.
@@ -160,12 +703,18 @@ pub enum BoundSetValue {
         /// Operand encoded in the column's binary format.
         literal: Vec<u8>,
     },
+    /// Expression over the row's current column values, e.g. `SET total =
+    /// price * qty`; the result is converted to the column's type.
+    Expr(BoundExpr),
 }
 
 #[derive(Clone, Debug)]
 pub struct BoundDelete {
     pub table_id: OID,
     pub key: Vec<(AttrIndex, Vec<u8>)>,
+    pub returning: Option<BoundReturning>,
+    /// Scan selecting the rows to delete, like [`BoundUpdate::scan`].
+    pub scan: Option<BoundSelect>,
 }
 
 #[derive(Clone, Debug)]
@@ -197,4 +746,9 @@ pub enum BoundPredicate {
         start: Bound<Vec<(AttrIndex, Vec<u8>)>>,
         end: Bound<Vec<(AttrIndex, Vec<u8>)>>,
     },
+    /// Complete primary keys from an `IN` list or an `OR` of equalities, read
+    /// one point at a time; distinct, in statement order.
+    KeyIn {
+        keys: Vec<Vec<(AttrIndex, Vec<u8>)>>,
+    },
 }
@@ -12,16 +12,21 @@
 //! Invariants:
 //! - Slots are recorded in parameter order; `slots[i].param_index` counts
 //!   placeholders in the same order immediate binding would consume them
-//!   (SELECT: WHERE only; UPDATE: SET items then WHERE key; INSERT: rows x
-//!   columns; delta assignment placeholders count once).
+//!   (SELECT: WHERE in statement order then HAVING, or the ON conditions in
+//!   join order and then
+//!   WHERE for joins; UPDATE: SET items then WHERE key; INSERT: rows x
+//!   columns then `ON CONFLICT DO UPDATE` SET items; delta assignment
+//!   placeholders count once).
 //! - A `Slot` datum always fills to a non-NULL binary: immediate binding
 //!   never maps a placeholder to SQL NULL, so nullability handling for slot
 //!   positions is identical (NULL literals are `Const(None)` and are fully
 //!   resolved at template-bind time).
 
 use crate::sql::bound_stmt::{
-    BoundCommand, BoundDelete, BoundInsert, BoundInsertRow, BoundPredicate, BoundQuery,
-    BoundResidual, BoundSelect, BoundSelectItem, BoundSetValue, BoundStmt, BoundUpdate,
+    BoundCommand, BoundCondition, BoundConflictValue, BoundDelete, BoundExpr, BoundHaving,
+    BoundInsert, BoundInsertRow, BoundJoin, BoundJoinCondition, BoundJoinSelect, BoundJsonColumn,
+    BoundOnConflict, BoundPredicate, BoundQuery, BoundResidual, BoundReturning, BoundRowOrder,
+    BoundSelect, BoundSelectItem, BoundSetValue, BoundStmt, BoundUpdate,
 };
 use crate::sql::value_codec::ValueCodec;
 use crate::x_engine::api::DeltaOp;
@@ -36,6 +41,7 @@ use mudu_type::data_type_fn_param::DataType;
 use mudu_type::type_family::TypeFamily;
 use sql_parser::ast::expr_item::ExprValue;
 use sql_parser::ast::expr_operator::ValueCompare;
+use sql_parser::ast::select_join::JoinKind;
 use std::ops::Bound;
 
 /// One placeholder occurrence in a template, in parameter order.
@@ -83,6 +89,14 @@ impl SlotRecorder {
         index
     }
 
+    /// Changes the type slot `index` binds against, once expression binding
+    /// has inferred it from the placeholder's siblings.
+    pub(crate) fn retype(&mut self, index: u32, data_type: DataType) {
+        if let Some(slot) = self.slots.get_mut(index as usize) {
+            slot.data_type = data_type;
+        }
+    }
+
     pub(crate) fn into_slots(self) -> Vec<ParamSlot> {
         self.slots
     }
@@ -105,6 +119,9 @@ pub(crate) fn template_from_expr(
     }
 }
 
+/// A scalar expression whose constants are template datums.
+pub type ExprTemplate = BoundExpr<TemplateDatum>;
+
 /// A bound statement with parameter placeholders kept as slots.
 #[derive(Clone, Debug)]
 pub struct BoundTemplate {
@@ -117,6 +134,7 @@ pub struct BoundTemplate {
 #[derive(Clone, Debug)]
 pub enum StmtTemplate {
     Select(SelectTemplate),
+    Join(JoinSelectTemplate),
     Insert(InsertTemplate),
     Update(UpdateTemplate),
     Delete(DeleteTemplate),
@@ -130,11 +148,54 @@ pub struct SelectTemplate {
     pub tuple_desc: TupleFieldDesc,
     pub predicate: PredicateTemplate,
     pub residual: Vec<ResidualTemplate>,
+    pub conditions: Vec<ConditionTemplate>,
+    pub group_by: Vec<AttrIndex>,
+    pub hidden_items: Vec<BoundSelectItem>,
+    pub having: Vec<HavingTemplate>,
+    pub order: BoundRowOrder,
+    pub json_columns: Vec<BoundJsonColumn>,
     /// Whether the table has fs-bound columns (recorded for symmetry with the
     /// write templates; reads have no fs hook).
     pub has_fs_columns: bool,
 }
 
+/// Template form of [`BoundJoinSelect`].
+#[derive(Clone, Debug)]
+pub struct JoinSelectTemplate {
+    pub table_id: OID,
+    pub predicate: PredicateTemplate,
+    pub residual: Vec<ResidualTemplate>,
+    pub joins: Vec<JoinTemplate>,
+    pub projection: Vec<usize>,
+    pub tuple_desc: TupleFieldDesc,
+    pub filter: Vec<JoinConditionTemplate>,
+    pub hidden: Vec<usize>,
+    pub order: BoundRowOrder,
+}
+
+/// Template form of [`BoundJoin`].
+#[derive(Clone, Debug)]
+pub struct JoinTemplate {
+    pub kind: JoinKind,
+    pub table_id: OID,
+    pub on: Vec<JoinConditionTemplate>,
+}
+
+/// Template form of [`BoundJoinCondition`].
+#[derive(Clone, Debug)]
+pub enum JoinConditionTemplate {
+    Literal {
+        pos: usize,
+        op: ValueCompare,
+        literal: TemplateDatum,
+    },
+    Column {
+        left: usize,
+        op: ValueCompare,
+        right: usize,
+    },
+}
+
 /// Template form of [`BoundPredicate`].
 #[derive(Clone, Debug)]
 pub enum PredicateTemplate {
@@ -149,6 +210,11 @@ pub enum PredicateTemplate {
         start: Bound<Vec<(AttrIndex, TemplateDatum)>>,
         end: Bound<Vec<(AttrIndex, TemplateDatum)>>,
     },
+    /// Complete keys; duplicates are dropped at fill time, when every slot
+    /// value is known.
+    KeyIn {
+        keys: Vec<Vec<(AttrIndex, TemplateDatum)>>,
+    },
 }
 
 /// Template form of [`BoundResidual`].
@@ -159,14 +225,78 @@ pub struct ResidualTemplate {
     pub literal: TemplateDatum,
 }
 
+/// Template form of [`BoundCondition`].
+#[derive(Clone, Debug)]
+pub enum ConditionTemplate {
+    And(Vec<ConditionTemplate>),
+    Or(Vec<ConditionTemplate>),
+    Not(Box<ConditionTemplate>),
+    Compare {
+        attr: AttrIndex,
+        op: ValueCompare,
+        literal: TemplateDatum,
+    },
+    InList {
+        attr: AttrIndex,
+        list: Vec<TemplateDatum>,
+        negated: bool,
+    },
+    Between {
+        attr: AttrIndex,
+        low: TemplateDatum,
+        high: TemplateDatum,
+        negated: bool,
+    },
+    Like {
+        attr: AttrIndex,
+        pattern: TemplateDatum,
+        case_insensitive: bool,
+        negated: bool,
+    },
+    IsNull {
+        attr: AttrIndex,
+        negated: bool,
+    },
+    Expr(ExprTemplate),
+}
+
+/// Template form of [`BoundHaving`].
+#[derive(Clone, Debug)]
+pub struct HavingTemplate {
+    pub pos: usize,
+    pub op: ValueCompare,
+    pub literal: TemplateDatum,
+}
+
 /// Template form of [`BoundInsert`].
 #[derive(Clone, Debug)]
 pub struct InsertTemplate {
     pub table_id: OID,
     pub rows: Vec<InsertRowTemplate>,
+    /// Columns the statement omits that have a `DEFAULT`.
+    pub default_attrs: Vec<AttrIndex>,
     /// Tables with fs-bound columns keep the fs DML hook, so they are
     /// classified `Other` and executed through the regular planner path.
     pub has_fs_columns: bool,
+    /// Tables with column defaults or table constraints keep the constraint
+    /// hook, and are classified `Other` for the same reason.
+    pub has_constraints: bool,
+    pub on_conflict: Option<OnConflictTemplate>,
+    pub returning: Option<BoundReturning>,
+}
+
+/// Template form of [`BoundOnConflict`].
+#[derive(Clone, Debug)]
+pub enum OnConflictTemplate {
+    DoNothing,
+    DoUpdate(Vec<(AttrIndex, ConflictValueTemplate)>),
+}
+
+/// Template form of [`BoundConflictValue`].
+#[derive(Clone, Debug)]
+pub enum ConflictValueTemplate {
+    Set(SetValueTemplate),
+    Excluded(AttrIndex),
 }
 
 /// Template form of [`BoundInsertRow`]; every datum is non-NULL by
@@ -182,12 +312,19 @@ pub struct InsertRowTemplate {
 pub struct UpdateTemplate {
     pub table_id: OID,
     /// Complete primary key (enforced by template binding, same as immediate
-    /// binding).
+    /// binding), or empty when `scan` selects the rows.
     pub key: Vec<(AttrIndex, TemplateDatum)>,
     pub value: Vec<(AttrIndex, SetValueTemplate)>,
     /// Tables with fs-bound columns keep the fs DML hook, so they are
     /// classified `Other` and executed through the regular planner path.
     pub has_fs_columns: bool,
+    /// Tables with table constraints, or referenced by a foreign key, keep
+    /// the constraint hook, and are classified `Other` for the same reason.
+    pub has_constraints: bool,
+    pub returning: Option<BoundReturning>,
+    /// Template form of [`BoundUpdate::scan`]; updates through a scan are
+    /// classified `Other`.
+    pub scan: Option<SelectTemplate>,
 }
 
 /// Template form of [`BoundSetValue`].
@@ -198,6 +335,8 @@ pub enum SetValueTemplate {
     Absolute(TemplateDatum),
     /// Restricted expression assignment `SET col = col <+|-> <literal or ?>`.
     Delta { op: DeltaOp, operand: TemplateDatum },
+    /// Expression over the row's current column values.
+    Expr(ExprTemplate),
 }
 
 /// Template form of [`BoundDelete`].
@@ -205,20 +344,26 @@ pub enum SetValueTemplate {
 pub struct DeleteTemplate {
     pub table_id: OID,
     pub key: Vec<(AttrIndex, TemplateDatum)>,
+    pub returning: Option<BoundReturning>,
+    pub scan: Option<SelectTemplate>,
 }
 
 /// Execution classification of a cached template.
 #[derive(Clone, Debug)]
 pub enum PlanClass {
     /// Point read: full primary-key equality, no residual filter, no
-    /// aggregate, plain column projection with unique attributes. Executed as
-    /// one `XContract::read_key` plus result materialization.
+    /// aggregate, grouping, ordering or limit, plain column projection with
+    /// unique attributes. Executed as one `XContract::read_key` plus result
+    /// materialization.
     PointRead { select: Vec<AttrIndex> },
-    /// Point update on a table without fs-bound columns: executed as one
-    /// `XContract::update` (absolute and delta assignments split like the
-    /// planner does).
+    /// Point update on a table without fs-bound columns, constraints or a
+    /// `RETURNING` clause, and without expression assignments or a scan
+    /// selecting the rows: executed as
+    /// one `XContract::update` (absolute and delta assignments split like
+    /// the planner does).
     PointUpdate,
-    /// Point insert on a table without fs-bound columns: executed as one
+    /// Point insert on a table without fs-bound columns or constraints, and
+    /// without `ON CONFLICT` or `RETURNING`: executed as one
     /// `XContract::insert` per row.
     PointInsert,
     /// Everything else: fill the template and feed the resulting
@@ -237,6 +382,12 @@ impl BoundTemplate {
             StmtTemplate::Select(select) => {
                 if !matches!(select.predicate, PredicateTemplate::KeyEq { .. })
                     || !select.residual.is_empty()
+                    || !select.conditions.is_empty()
+                    || !select.group_by.is_empty()
+                    || !select.hidden_items.is_empty()
+                    || !select.having.is_empty()
+                    || !select.order.is_empty()
+                    || !select.json_columns.is_empty()
                 {
                     return PlanClass::Other;
                 }
@@ -253,13 +404,33 @@ impl BoundTemplate {
                             }
                             attrs.push(column.attr);
                         }
-                        BoundSelectItem::Aggregate(_) => return PlanClass::Other,
+                        BoundSelectItem::Aggregate(_) | BoundSelectItem::Expression(_) => {
+                            return PlanClass::Other
+                        }
                     }
                 }
                 PlanClass::PointRead { select: attrs }
             }
-            StmtTemplate::Update(update) if !update.has_fs_columns => PlanClass::PointUpdate,
-            StmtTemplate::Insert(insert) if !insert.has_fs_columns => PlanClass::PointInsert,
+            StmtTemplate::Update(update)
+                if !update.has_fs_columns
+                    && !update.has_constraints
+                    && update.returning.is_none()
+                    && update.scan.is_none()
+                    && !update
+                        .value
+                        .iter()
+                        .any(|(_, value)| matches!(value, SetValueTemplate::Expr(_))) =>
+            {
+                PlanClass::PointUpdate
+            }
+            StmtTemplate::Insert(insert)
+                if !insert.has_fs_columns
+                    && !insert.has_constraints
+                    && insert.on_conflict.is_none()
+                    && insert.returning.is_none() =>
+            {
+                PlanClass::PointInsert
+            }
             _ => PlanClass::Other,
         }
     }
@@ -271,6 +442,9 @@ impl BoundTemplate {
             StmtTemplate::Select(select) => Ok(BoundStmt::Query(BoundQuery::Select(
                 select.fill(&self.slots, params)?,
             ))),
+            StmtTemplate::Join(join) => Ok(BoundStmt::Query(BoundQuery::Join(
+                join.fill(&self.slots, params)?,
+            ))),
             StmtTemplate::Insert(insert) => Ok(BoundStmt::Command(BoundCommand::Insert(
                 insert.fill(&self.slots, params)?,
             ))),
@@ -303,8 +477,8 @@ fn fill_slot(slot: &ParamSlot, params: &dyn SQLParams) -> RS<Buf> {
             _ => {
                 return Err(mudu_error!(
                     ER::NotImplemented,
-                    "expression updates are not implemented \
-                     (only `SET col = col +|- <integer literal or ?>` is supported)"
+                    "non-integer delta parameters are not implemented \
+                     (`SET col = col +|- ?` takes an integer)"
                 ))
             }
         }
@@ -346,6 +520,23 @@ pub(crate) fn fill_pairs(
         .collect()
 }
 
+fn fill_residuals(
+    residual: &[ResidualTemplate],
+    slots: &[ParamSlot],
+    params: &dyn SQLParams,
+) -> RS<Vec<BoundResidual>> {
+    residual
+        .iter()
+        .map(|residual| {
+            Ok(BoundResidual {
+                attr: residual.attr,
+                op: residual.op,
+                literal: residual.literal.fill(slots, params)?,
+            })
+        })
+        .collect()
+}
+
 impl SelectTemplate {
     fn fill(&self, slots: &[ParamSlot], params: &dyn SQLParams) -> RS<BoundSelect> {
         Ok(BoundSelect {
@@ -353,21 +544,83 @@ impl SelectTemplate {
             select_items: self.select_items.clone(),
             tuple_desc: self.tuple_desc.clone(),
             predicate: self.predicate.fill(slots, params)?,
-            residual: self
-                .residual
+            residual: fill_residuals(&self.residual, slots, params)?,
+            conditions: self
+                .conditions
+                .iter()
+                .map(|condition| condition.fill(slots, params))
+                .collect::<RS<Vec<_>>>()?,
+            group_by: self.group_by.clone(),
+            hidden_items: self.hidden_items.clone(),
+            having: self
+                .having
                 .iter()
-                .map(|residual| {
-                    Ok(BoundResidual {
-                        attr: residual.attr,
-                        op: residual.op,
-                        literal: residual.literal.fill(slots, params)?,
+                .map(|having| {
+                    Ok(BoundHaving {
+                        pos: having.pos,
+                        op: having.op,
+                        literal: having.literal.fill(slots, params)?,
                     })
                 })
                 .collect::<RS<Vec<_>>>()?,
+            order: self.order.clone(),
+            json_columns: self.json_columns.clone(),
         })
     }
 }
 
+impl JoinSelectTemplate {
+    fn fill(&self, slots: &[ParamSlot], params: &dyn SQLParams) -> RS<BoundJoinSelect> {
+        Ok(BoundJoinSelect {
+            table_id: self.table_id,
+            predicate: self.predicate.fill(slots, params)?,
+            residual: fill_residuals(&self.residual, slots, params)?,
+            joins: self
+                .joins
+                .iter()
+                .map(|join| {
+                    Ok(BoundJoin {
+                        kind: join.kind,
+                        table_id: join.table_id,
+                        on: fill_join_conditions(&join.on, slots, params)?,
+                    })
+                })
+                .collect::<RS<Vec<_>>>()?,
+            projection: self.projection.clone(),
+            tuple_desc: self.tuple_desc.clone(),
+            filter: fill_join_conditions(&self.filter, slots, params)?,
+            hidden: self.hidden.clone(),
+            order: self.order.clone(),
+        })
+    }
+}
+
+fn fill_join_conditions(
+    conditions: &[JoinConditionTemplate],
+    slots: &[ParamSlot],
+    params: &dyn SQLParams,
+) -> RS<Vec<BoundJoinCondition>> {
+    conditions
+        .iter()
+        .map(|condition| {
+            Ok(match condition {
+                JoinConditionTemplate::Literal { pos, op, literal } => {
+                    BoundJoinCondition::Literal {
+                        pos: *pos,
+                        op: *op,
+                        literal: literal.fill(slots, params)?,
+                    }
+                }
+                JoinConditionTemplate::Column { left, op, right } => BoundJoinCondition::Column {
+                    left: *left,
+                    op: *op,
+                    right: *right,
+                },
+            })
+        })
+        .collect()
+}
+
 impl PredicateTemplate {
     fn fill(&self, slots: &[ParamSlot], params: &dyn SQLParams) -> RS<BoundPredicate> {
         match self {
@@ -382,10 +635,82 @@ impl PredicateTemplate {
                 start: fill_bound(start, slots, params)?,
                 end: fill_bound(end, slots, params)?,
             }),
+            PredicateTemplate::KeyIn { keys } => {
+                let mut filled: Vec<Vec<(AttrIndex, Buf)>> = Vec::with_capacity(keys.len());
+                for key in keys {
+                    let key = fill_pairs(key, slots, params)?;
+                    if !filled.contains(&key) {
+                        filled.push(key);
+                    }
+                }
+                Ok(BoundPredicate::KeyIn { keys: filled })
+            }
         }
     }
 }
 
+impl ConditionTemplate {
+    fn fill(&self, slots: &[ParamSlot], params: &dyn SQLParams) -> RS<BoundCondition> {
+        let fill_all = |conditions: &[ConditionTemplate]| {
+            conditions
+                .iter()
+                .map(|condition| condition.fill(slots, params))
+                .collect::<RS<Vec<_>>>()
+        };
+        Ok(match self {
+            ConditionTemplate::And(conditions) => BoundCondition::And(fill_all(conditions)?),
+            ConditionTemplate::Or(conditions) => BoundCondition::Or(fill_all(conditions)?),
+            ConditionTemplate::Not(condition) => {
+                BoundCondition::Not(Box::new(condition.fill(slots, params)?))
+            }
+            ConditionTemplate::Compare { attr, op, literal } => BoundCondition::Compare {
+                attr: *attr,
+                op: *op,
+                literal: literal.fill(slots, params)?,
+            },
+            ConditionTemplate::InList {
+                attr,
+                list,
+                negated,
+            } => BoundCondition::InList {
+                attr: *attr,
+                list: list
+                    .iter()
+                    .map(|datum| datum.fill(slots, params))
+                    .collect::<RS<Vec<_>>>()?,
+                negated: *negated,
+            },
+            ConditionTemplate::Between {
+                attr,
+                low,
+                high,
+                negated,
+            } => BoundCondition::Between {
+                attr: *attr,
+                low: low.fill(slots, params)?,
+                high: high.fill(slots, params)?,
+                negated: *negated,
+            },
+            ConditionTemplate::Like {
+                attr,
+                pattern,
+                case_insensitive,
+                negated,
+            } => BoundCondition::Like {
+                attr: *attr,
+                pattern: pattern.fill(slots, params)?,
+                case_insensitive: *case_insensitive,
+                negated: *negated,
+            },
+            ConditionTemplate::IsNull { attr, negated } => BoundCondition::IsNull {
+                attr: *attr,
+                negated: *negated,
+            },
+            ConditionTemplate::Expr(expr) => BoundCondition::Expr(fill_expr(expr, slots, params)?),
+        })
+    }
+}
+
 fn fill_bound(
     bound: &Bound<Vec<(AttrIndex, TemplateDatum)>>,
     slots: &[ParamSlot],
@@ -412,6 +737,28 @@ impl InsertTemplate {
                     })
                 })
                 .collect::<RS<Vec<_>>>()?,
+            default_attrs: self.default_attrs.clone(),
+            on_conflict: match &self.on_conflict {
+                None => None,
+                Some(OnConflictTemplate::DoNothing) => Some(BoundOnConflict::DoNothing),
+                Some(OnConflictTemplate::DoUpdate(assignments)) => Some(BoundOnConflict::DoUpdate(
+                    assignments
+                        .iter()
+                        .map(|(attr, value)| {
+                            let value = match value {
+                                ConflictValueTemplate::Set(set_value) => {
+                                    BoundConflictValue::Set(set_value.fill(slots, params)?)
+                                }
+                                ConflictValueTemplate::Excluded(source) => {
+                                    BoundConflictValue::Excluded(*source)
+                                }
+                            };
+                            Ok((*attr, value))
+                        })
+                        .collect::<RS<Vec<_>>>()?,
+                )),
+            },
+            returning: self.returning.clone(),
         })
     }
 }
@@ -424,28 +771,48 @@ impl UpdateTemplate {
             value: self
                 .value
                 .iter()
-                .map(|(attr, set_value)| {
-                    let set_value = match set_value {
-                        SetValueTemplate::Absolute(datum) => {
-                            BoundSetValue::Absolute(datum.fill_some(slots, params)?)
-                        }
-                        SetValueTemplate::Delta { op, operand } => BoundSetValue::Delta {
-                            op: *op,
-                            literal: operand.fill_some(slots, params)?,
-                        },
-                    };
-                    Ok((*attr, set_value))
-                })
+                .map(|(attr, set_value)| Ok((*attr, set_value.fill(slots, params)?)))
                 .collect::<RS<Vec<_>>>()?,
+            returning: self.returning.clone(),
+            scan: self
+                .scan
+                .as_ref()
+                .map(|scan| scan.fill(slots, params))
+                .transpose()?,
         })
     }
 }
 
+impl SetValueTemplate {
+    fn fill(&self, slots: &[ParamSlot], params: &dyn SQLParams) -> RS<BoundSetValue> {
+        Ok(match self {
+            SetValueTemplate::Absolute(datum) => {
+                BoundSetValue::Absolute(datum.fill_some(slots, params)?)
+            }
+            SetValueTemplate::Delta { op, operand } => BoundSetValue::Delta {
+                op: *op,
+                literal: operand.fill_some(slots, params)?,
+            },
+            SetValueTemplate::Expr(expr) => BoundSetValue::Expr(fill_expr(expr, slots, params)?),
+        })
+    }
+}
+
+fn fill_expr(expr: &ExprTemplate, slots: &[ParamSlot], params: &dyn SQLParams) -> RS<BoundExpr> {
+    expr.try_map_const(&mut |datum| datum.fill(slots, params))
+}
+
 impl DeleteTemplate {
     fn fill(&self, slots: &[ParamSlot], params: &dyn SQLParams) -> RS<BoundDelete> {
         Ok(BoundDelete {
             table_id: self.table_id,
             key: fill_pairs(&self.key, slots, params)?,
+            returning: self.returning.clone(),
+            scan: self
+                .scan
+                .as_ref()
+                .map(|scan| scan.fill(slots, params))
+                .transpose()?,
         })
     }
 }
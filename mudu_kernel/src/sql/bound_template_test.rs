@@ -295,6 +295,34 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn upsert_and_returning_are_templated_as_other() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let binder = Binder::new(meta_mgr().await);
+            // Conflict assignment placeholders follow the row values.
+            let sql = "insert into counters values (?, ?, ?) \
+                       on conflict (id) do update set count = count + ?, note = excluded.note \
+                       returning id, count";
+            let template = bind_template(&binder, sql).await;
+            assert!(matches!(template.classify(), PlanClass::Other));
+            assert_eq!(template.slots.len(), 4);
+            assert!(template.slots[3].delta_operand);
+            assert_fill_matches_bind(&binder, sql, &(1i32, 2i32, "a".to_string(), 3i32)).await;
+
+            let sql = "update counters set count = ? where id = ? returning count";
+            let template = bind_template(&binder, sql).await;
+            assert!(matches!(template.classify(), PlanClass::Other));
+            assert_fill_matches_bind(&binder, sql, &(1i32, 2i32)).await;
+
+            let stmt =
+                parse_stmt("insert into counters values (1, 2, 'a') on conflict (note) do nothing");
+            let err = binder.bind_template(&stmt).await.unwrap_err();
+            assert_eq!(err.ec(), mudu::error::ErrorCode::NotImplemented);
+        })
+        .unwrap()
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)]
     fn numeric_string_params_fill_like_immediate_bind() {
@@ -384,7 +412,7 @@ mod tests {
             let err = template.fill(&(1.5f64, 1i32)).unwrap_err();
             assert!(err
                 .to_string()
-                .contains("expression updates are not implemented"));
+                .contains("non-integer delta parameters are not implemented"));
         })
         .unwrap()
     }
@@ -1,10 +1,13 @@
 use crate::contract::meta_mgr::MetaMgr;
 use crate::contract::table_desc::TableDesc;
+use crate::executor::explain::explain_tuple_desc;
+use crate::sql::join_scope::JoinScope;
+use crate::sql::select_projection::bind_returning;
 use mudu::common::result::RS;
 use mudu::error::ErrorCode as ER;
 use mudu::mudu_error;
 use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
-use sql_parser::ast::stmt_type::StmtType;
+use sql_parser::ast::stmt_type::{StmtCommand, StmtType};
 use std::sync::Arc;
 
 pub struct Describer {}
@@ -23,7 +26,8 @@ impl Describer {
     pub async fn describe(meta_mgr: &dyn MetaMgr, stmt: &StmtType) -> RS<TupleFieldDesc> {
         match stmt {
             StmtType::Select(stmt) => Self::describe_select(meta_mgr, stmt).await,
-            StmtType::Command(_) => Ok(TupleFieldDesc::new(Vec::new())),
+            StmtType::Command(stmt) => Self::describe_command(meta_mgr, stmt).await,
+            StmtType::Explain(_) => Ok(explain_tuple_desc()),
         }
     }
 
@@ -31,12 +35,31 @@ impl Describer {
         meta_mgr: &dyn MetaMgr,
         stmt: &sql_parser::ast::stmt_select::StmtSelect,
     ) -> RS<TupleFieldDesc> {
+        if !stmt.get_joins().is_empty() {
+            let scope = JoinScope::from_select(meta_mgr, stmt).await?;
+            let (_projection, tuple_desc) = scope.bind_projection(stmt.get_select_term_list())?;
+            return Ok(tuple_desc);
+        }
         let table_desc = Self::get_table_by_name(meta_mgr, stmt.get_table_reference()).await?;
-        let (_items, tuple_desc) = crate::sql::select_projection::bind_select_items(
-            &table_desc,
-            stmt.get_select_term_list(),
-        )?;
-        Ok(tuple_desc)
+        let list = crate::sql::select_projection::bind_select_list(&table_desc, stmt)?;
+        Ok(list.tuple_desc)
+    }
+
+    // A command describes the rows of its `RETURNING` clause, if any.
+    async fn describe_command(meta_mgr: &dyn MetaMgr, stmt: &StmtCommand) -> RS<TupleFieldDesc> {
+        let (table, returning) = match stmt {
+            StmtCommand::Insert(insert) => (insert.table_name(), insert.returning()),
+            StmtCommand::Update(update) => (update.get_table_reference(), update.get_returning()),
+            StmtCommand::Delete(delete) => (delete.get_table_reference(), delete.get_returning()),
+            _ => return Ok(TupleFieldDesc::new(Vec::new())),
+        };
+        if returning.is_empty() {
+            return Ok(TupleFieldDesc::new(Vec::new()));
+        }
+        let table_desc = Self::get_table_by_name(meta_mgr, table).await?;
+        Ok(bind_returning(&table_desc, returning)?
+            .map(|returning| returning.tuple_desc)
+            .unwrap_or_else(|| TupleFieldDesc::new(Vec::new())))
     }
 
     async fn get_table_by_name(meta_mgr: &dyn MetaMgr, name: &str) -> RS<Arc<TableDesc>> {
@@ -28,6 +28,7 @@ mod tests {
     use mudu_type::type_family::TypeFamily;
     use sql_parser::ast::expr_name::ExprName;
     use sql_parser::ast::select_term::{SelectField, SelectTerm};
+    use sql_parser::ast::stmt_explain::StmtExplain;
     use sql_parser::ast::stmt_insert::StmtInsert;
     use sql_parser::ast::stmt_select::StmtSelect;
     use sql_parser::ast::stmt_type::StmtCommand;
@@ -213,4 +214,23 @@ mod tests {
         })
         .unwrap();
     }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn describe_explain_returns_single_plan_column() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let StmtType::Select(query) = select("users", vec!["id"]) else {
+                panic!("expected a SELECT statement");
+            };
+            let stmt = StmtType::Explain(StmtExplain::new(true, query));
+            let desc = Describer::describe(meta_mgr().as_ref(), &stmt)
+                .await
+                .unwrap();
+            let fields = desc.fields();
+            assert_eq!(fields.len(), 1);
+            assert_eq!(fields[0].name(), "plan");
+            assert_eq!(fields[0].type_family(), TypeFamily::String);
+        })
+        .unwrap();
+    }
 }
@@ -0,0 +1,863 @@
+//! Binding of general scalar expressions: arithmetic, `||`, comparisons,
+//! boolean connectives, `CASE`, `CAST` and the built-in scalar functions.
+//!
+//! Used for computed select-list items, `WHERE` conjuncts that are not a
+//! column compared with a value, `UPDATE ... SET col = <expr>` and the
+//! assignments of `ON CONFLICT DO UPDATE`.
+//!
+//! Operands are bound in statement order so placeholders keep their
+//! parameter positions. Literals and placeholders have no column to take a
+//! type from, so they are typed after their sibling operands: a quoted
+//! literal or a placeholder next to a typed operand takes (a widened form
+//! of) that operand's type, e.g. `created_at < '2024-01-01'` compares
+//! timestamps. A placeholder with no typed sibling cannot be bound.
+
+use crate::contract::table_desc::TableDesc;
+use crate::executor::scalar_expr::{encode, integer_of, ScalarExpr};
+use crate::sql::bound_stmt::{BoundExpr, ScalarFunc, TimeField};
+use crate::sql::bound_template::{ExprTemplate, SlotRecorder, TemplateDatum};
+use crate::sql::select_projection::{attr_index_by_name, attr_type, JsonColumns};
+use mudu::common::id::AttrIndex;
+use mudu::common::result::RS;
+use mudu::error::ErrorCode as ER;
+use mudu::mudu_error;
+use mudu_type::data_type_fn_param::DataType;
+use mudu_type::data_type_param_numeric::DataTypeParamNumeric;
+use mudu_type::data_typed::DataTyped;
+use mudu_type::data_value::DataValue;
+use mudu_type::type_family::TypeFamily;
+use sql_parser::ast::expr_function::{ExprFunction, FunctionArg};
+use sql_parser::ast::expr_item::{ExprItem, ExprValue};
+use sql_parser::ast::expr_literal::ExprLiteral;
+use sql_parser::ast::expr_operator::{Arithmetic, LogicalConnective, ValueCompare};
+use sql_parser::ast::expression::ExprType;
+
+/// Largest scale arithmetic gives a NUMERIC result unless an operand
+/// already has more.
+const NUMERIC_RESULT_SCALE: u8 = 18;
+/// Fractional digits of a NUMERIC quotient at least.
+const NUMERIC_DIVISION_SCALE: u8 = 6;
+/// Scale of a NUMERIC placeholder, whose value is only known at execution.
+const NUMERIC_PARAM_SCALE: u8 = 18;
+
+/// Binds expressions of one statement against one table.
+pub(crate) struct ExprBinder<'a> {
+    table_desc: &'a TableDesc,
+    json_columns: &'a mut JsonColumns,
+    /// `None` where placeholders are not accepted (the select list).
+    recorder: Option<&'a mut SlotRecorder>,
+    /// Whether `EXCLUDED.col` names a column of the proposed row.
+    excluded: bool,
+}
+
+// A bound operand whose type may still depend on its siblings.
+#[derive(Clone)]
+enum Operand {
+    Expr(ExprTemplate),
+    Literal(ExprLiteral),
+    /// Slot recorded with a provisional type.
+    Placeholder(u32),
+}
+
+impl<'a> ExprBinder<'a> {
+    pub(crate) fn new(
+        table_desc: &'a TableDesc,
+        json_columns: &'a mut JsonColumns,
+        recorder: Option<&'a mut SlotRecorder>,
+    ) -> Self {
+        Self {
+            table_desc,
+            json_columns,
+            recorder,
+            excluded: false,
+        }
+    }
+
+    /// Accept `EXCLUDED.col` for the value `ON CONFLICT DO UPDATE` proposed
+    /// for `col`. Proposed columns follow the table's own columns in the
+    /// row the expression is evaluated over, so JSON paths are refused.
+    pub(crate) fn with_excluded(mut self, excluded: bool) -> Self {
+        self.excluded = excluded;
+        self
+    }
+
+    /// Bind a scalar expression.
+    pub(crate) fn bind(&mut self, expr: &ExprType) -> RS<ExprTemplate> {
+        let operand = self.operand(expr)?;
+        self.resolve(operand, None)
+    }
+
+    /// Bind a boolean expression, e.g. a `WHERE` conjunct.
+    pub(crate) fn bind_condition(&mut self, expr: &ExprType) -> RS<ExprTemplate> {
+        let bound = match expr {
+            ExprType::Compare(compare) => {
+                let left = self.item_operand(compare.left())?;
+                let right = self.item_operand(compare.right())?;
+                self.compare(*compare.op(), left, right)?
+            }
+            expr => self.bind(expr)?,
+        };
+        if bound.data_type().type_family() != TypeFamily::Bool {
+            return Err(mudu_error!(
+                ER::InvalidType,
+                format!(
+                    "condition must be boolean, not {}",
+                    bound.data_type().name()
+                )
+            ));
+        }
+        Ok(bound)
+    }
+
+    /// Bind the value assigned to a column of type `data_type`, converted
+    /// to that type; a bare literal or placeholder takes the column type.
+    pub(crate) fn bind_assigned(
+        &mut self,
+        expr: &ExprType,
+        data_type: &DataType,
+    ) -> RS<ExprTemplate> {
+        let operand = self.operand(expr)?;
+        let expr = self.resolve(operand, Some(data_type))?;
+        Ok(cast_to(expr, data_type))
+    }
+
+    fn operand(&mut self, expr: &ExprType) -> RS<Operand> {
+        match expr {
+            ExprType::Value(item) => self.item_operand(item),
+            expr => Ok(Operand::Expr(self.bind_expr(expr)?)),
+        }
+    }
+
+    fn item_operand(&mut self, item: &ExprItem) -> RS<Operand> {
+        match item {
+            ExprItem::ItemName(name)
+                if self.excluded
+                    && name
+                        .qualifier()
+                        .is_some_and(|qualifier| qualifier.eq_ignore_ascii_case("excluded")) =>
+            {
+                if name.json_path().is_some() {
+                    return Err(mudu_error!(
+                        ER::NotImplemented,
+                        "JSON paths of EXCLUDED columns are not implemented"
+                    ));
+                }
+                let attr = attr_index_by_name(self.table_desc, name.name())?;
+                Ok(Operand::Expr(BoundExpr::Column {
+                    attr: self.table_desc.fields().len() + attr,
+                    data_type: self.table_desc.get_attr(attr).type_desc().clone(),
+                }))
+            }
+            ExprItem::ItemName(name) => {
+                let attr = self.json_columns.attr(self.table_desc, name)?;
+                Ok(Operand::Expr(BoundExpr::Column {
+                    attr,
+                    data_type: attr_type(self.table_desc, self.json_columns.columns(), attr),
+                }))
+            }
+            ExprItem::ItemValue(ExprValue::ValueLiteral(literal)) => {
+                Ok(Operand::Literal(literal.clone()))
+            }
+            ExprItem::ItemValue(ExprValue::ValuePlaceholder) => {
+                let recorder = self.recorder.as_deref_mut().ok_or_else(|| {
+                    mudu_error!(
+                        ER::NotImplemented,
+                        "parameters in the select list are not implemented"
+                    )
+                })?;
+                Ok(Operand::Placeholder(
+                    recorder.push(DataType::default_for(TypeFamily::String), false),
+                ))
+            }
+        }
+    }
+
+    fn arg_operand(&mut self, function: &ExprFunction, arg: &FunctionArg) -> RS<Operand> {
+        match arg {
+            FunctionArg::Star => Err(mudu_error!(
+                ER::InvalidArgument,
+                format!("function {} does not accept `*`", function.name())
+            )),
+            FunctionArg::Column(name) => self.item_operand(&ExprItem::ItemName(name.clone())),
+            FunctionArg::Expression(expr) => self.operand(expr),
+        }
+    }
+
+    // Type `operand`, taking `hint` for a quoted literal or a placeholder.
+    fn resolve(&mut self, operand: Operand, hint: Option<&DataType>) -> RS<ExprTemplate> {
+        match operand {
+            Operand::Expr(expr) => Ok(expr),
+            Operand::Literal(literal) => literal_expr(&literal, hint),
+            Operand::Placeholder(index) => {
+                let data_type = hint.map(param_type).ok_or_else(|| {
+                    mudu_error!(
+                        ER::NotImplemented,
+                        "cannot infer the type of a parameter in this expression"
+                    )
+                })?;
+                if let Some(recorder) = self.recorder.as_deref_mut() {
+                    recorder.retype(index, data_type.clone());
+                }
+                Ok(BoundExpr::Const {
+                    value: TemplateDatum::Slot(index),
+                    data_type,
+                })
+            }
+        }
+    }
+
+    // Type sibling operands, hinting untyped ones with the first typed one.
+    fn resolve_all(&mut self, operands: Vec<Operand>) -> RS<Vec<ExprTemplate>> {
+        let hint = operands.iter().find_map(|operand| match operand {
+            Operand::Expr(expr) => Some(expr.data_type()),
+            Operand::Literal(ExprLiteral::DatumLiteral(typed))
+                if typed.data_type().type_family() != TypeFamily::String =>
+            {
+                Some(literal_type(typed.data_type(), typed))
+            }
+            _ => None,
+        });
+        operands
+            .into_iter()
+            .map(|operand| self.resolve(operand, hint.as_ref()))
+            .collect()
+    }
+
+    fn bind_expr(&mut self, expr: &ExprType) -> RS<ExprTemplate> {
+        match expr {
+            ExprType::Value(item) => {
+                let operand = self.item_operand(item)?;
+                self.resolve(operand, None)
+            }
+            ExprType::Arithmetic(arithmetic) => {
+                let left = self.operand(arithmetic.left())?;
+                let right = self.operand(arithmetic.right())?;
+                self.arithmetic(*arithmetic.op(), left, right)
+            }
+            ExprType::Compare(_) => self.bind_condition(expr),
+            ExprType::ScalarCompare(compare) => {
+                let left = self.operand(compare.left())?;
+                let right = self.operand(compare.right())?;
+                self.compare(*compare.op(), left, right)
+            }
+            ExprType::Logical(logical) => {
+                let is_and = matches!(logical.op(), LogicalConnective::AND);
+                let mut operands = Vec::new();
+                for side in [logical.left(), logical.right()] {
+                    // Flatten chains of the same connective.
+                    match self.bind_condition(side)? {
+                        BoundExpr::And(inner) if is_and => operands.extend(inner),
+                        BoundExpr::Or(inner) if !is_and => operands.extend(inner),
+                        operand => operands.push(operand),
+                    }
+                }
+                Ok(if is_and {
+                    BoundExpr::And(operands)
+                } else {
+                    BoundExpr::Or(operands)
+                })
+            }
+            ExprType::Not(operand) => Ok(BoundExpr::Not(Box::new(self.bind_condition(operand)?))),
+            ExprType::InList(in_list) => {
+                let mut operands = vec![self.item_operand(in_list.item())?];
+                for item in in_list.list() {
+                    operands.push(self.item_operand(item)?);
+                }
+                let (mut exprs, _) = unify(self.resolve_all(operands)?, "IN")?;
+                let operand = exprs.remove(0);
+                Ok(BoundExpr::InList {
+                    operand: Box::new(operand),
+                    list: exprs,
+                    negated: in_list.negated(),
+                })
+            }
+            ExprType::Between(between) => {
+                let operands = vec![
+                    self.item_operand(between.item())?,
+                    self.item_operand(between.low())?,
+                    self.item_operand(between.high())?,
+                ];
+                let (exprs, _) = unify(self.resolve_all(operands)?, "BETWEEN")?;
+                let [operand, low, high] = boxed_array(exprs)?;
+                Ok(BoundExpr::Between {
+                    operand,
+                    low,
+                    high,
+                    negated: between.negated(),
+                })
+            }
+            ExprType::Like(like) => {
+                let operands = vec![
+                    self.item_operand(like.item())?,
+                    self.item_operand(like.pattern())?,
+                ];
+                let exprs = self.resolve_all(operands)?;
+                if exprs
+                    .iter()
+                    .any(|expr| expr.data_type().type_family() != TypeFamily::String)
+                {
+                    return Err(mudu_error!(
+                        ER::NotImplemented,
+                        "LIKE is only implemented for strings"
+                    ));
+                }
+                let [operand, pattern] = boxed_array(exprs)?;
+                Ok(BoundExpr::Like {
+                    operand,
+                    pattern,
+                    case_insensitive: like.case_insensitive(),
+                    negated: like.negated(),
+                })
+            }
+            ExprType::IsNull(is_null) => {
+                let operand = self.item_operand(is_null.item())?;
+                Ok(BoundExpr::IsNull {
+                    operand: Box::new(self.resolve(operand, None)?),
+                    negated: is_null.negated(),
+                })
+            }
+            ExprType::Function(function) => self.function(function),
+            ExprType::Case(case) => {
+                // A simple CASE compares its operand with every WHEN value.
+                let case_operand = match case.operand() {
+                    Some(operand) => Some(self.operand(operand)?),
+                    None => None,
+                };
+                let mut conditions = Vec::with_capacity(case.branches().len());
+                let mut results = Vec::with_capacity(case.branches().len() + 1);
+                for (when, then) in case.branches() {
+                    let condition = match &case_operand {
+                        Some(case_operand) => {
+                            let value = self.operand(when)?;
+                            self.compare(ValueCompare::EQ, case_operand.clone(), value)?
+                        }
+                        None => self.bind_condition(when)?,
+                    };
+                    conditions.push(condition);
+                    results.push(self.operand(then)?);
+                }
+                let has_else = case.else_result().is_some();
+                if let Some(else_result) = case.else_result() {
+                    results.push(self.operand(else_result)?);
+                }
+                let (mut results, data_type) = unify(self.resolve_all(results)?, "CASE")?;
+                let else_result = if has_else {
+                    results.pop().map(Box::new)
+                } else {
+                    None
+                };
+                Ok(BoundExpr::Case {
+                    branches: conditions.into_iter().zip(results).collect(),
+                    else_result,
+                    data_type,
+                })
+            }
+            ExprType::Cast(cast) => {
+                let target = cast.target().param().clone();
+                let operand = self.operand(cast.expr())?;
+                let operand = self.resolve(operand, Some(&target))?;
+                Ok(cast_to(operand, &target))
+            }
+        }
+    }
+
+    fn arithmetic(&mut self, op: Arithmetic, left: Operand, right: Operand) -> RS<ExprTemplate> {
+        let [left, right] = array(self.resolve_all(vec![left, right])?)?;
+        let data_type = arithmetic_type(op, &left.data_type(), &right.data_type())?;
+        let (left, right) = if matches!(op, Arithmetic::CONCAT) {
+            (cast_to(left, &data_type), cast_to(right, &data_type))
+        } else {
+            (left, right)
+        };
+        Ok(BoundExpr::Arithmetic {
+            op,
+            left: Box::new(left),
+            right: Box::new(right),
+            data_type,
+        })
+    }
+
+    fn compare(&mut self, op: ValueCompare, left: Operand, right: Operand) -> RS<ExprTemplate> {
+        let (exprs, _) = unify(self.resolve_all(vec![left, right])?, "comparison")?;
+        let [left, right] = boxed_array(exprs)?;
+        Ok(BoundExpr::Compare { op, left, right })
+    }
+
+    fn function(&mut self, function: &ExprFunction) -> RS<ExprTemplate> {
+        let name = function.name().to_lowercase();
+        let args = function.args();
+        let arity = |min: usize, max: usize| -> RS<()> {
+            if args.len() < min || args.len() > max {
+                return Err(mudu_error!(
+                    ER::InvalidArgument,
+                    format!("wrong number of arguments to function {}", name)
+                ));
+            }
+            Ok(())
+        };
+        let text_type = DataType::default_for(TypeFamily::String);
+        let integer_type = DataType::default_for(TypeFamily::I64);
+        let (func, args, data_type) = match name.as_str() {
+            "lower" | "upper" => {
+                arity(1, 1)?;
+                let arg = self.arg_operand(function, &args[0])?;
+                let arg = self.resolve(arg, Some(&text_type))?;
+                expect_family(&name, &arg, |family| family == TypeFamily::String)?;
+                let func = if name == "lower" {
+                    ScalarFunc::Lower
+                } else {
+                    ScalarFunc::Upper
+                };
+                (func, vec![arg], text_type)
+            }
+            "substr" | "substring" => {
+                arity(2, 3)?;
+                let mut bound = Vec::with_capacity(args.len());
+                for (index, arg) in args.iter().enumerate() {
+                    let operand = self.arg_operand(function, arg)?;
+                    let hint = if index == 0 {
+                        &text_type
+                    } else {
+                        &integer_type
+                    };
+                    let arg = self.resolve(operand, Some(hint))?;
+                    if index == 0 {
+                        expect_family(&name, &arg, |family| family == TypeFamily::String)?;
+                    } else {
+                        expect_family(&name, &arg, is_integer)?;
+                    }
+                    bound.push(arg);
+                }
+                (ScalarFunc::Substr, bound, text_type)
+            }
+            "abs" => {
+                arity(1, 1)?;
+                let arg = self.arg_operand(function, &args[0])?;
+                let arg = self.resolve(arg, None)?;
+                expect_family(&name, &arg, is_number)?;
+                let data_type = match arg.data_type().type_family() {
+                    family if is_integer(family) => integer_type,
+                    TypeFamily::Numeric => arg.data_type(),
+                    _ => DataType::default_for(TypeFamily::F64),
+                };
+                (ScalarFunc::Abs, vec![arg], data_type)
+            }
+            "round" => {
+                arity(1, 2)?;
+                let arg = self.arg_operand(function, &args[0])?;
+                let arg = self.resolve(arg, None)?;
+                expect_family(&name, &arg, is_number)?;
+                // The result scale depends on the digits, so they must be
+                // known at bind time.
+                let digits = match args.get(1) {
+                    Some(digits) => match self.arg_operand(function, digits)? {
+                        Operand::Literal(ExprLiteral::DatumLiteral(typed)) => {
+                            integer_of(typed.data_internal()).ok_or_else(|| {
+                                mudu_error!(
+                                    ER::NotImplemented,
+                                    "the digits of round must be an integer literal"
+                                )
+                            })?
+                        }
+                        _ => {
+                            return Err(mudu_error!(
+                                ER::NotImplemented,
+                                "the digits of round must be an integer literal"
+                            ))
+                        }
+                    },
+                    None => 0,
+                };
+                let data_type = match arg.data_type().type_family() {
+                    TypeFamily::F32 | TypeFamily::F64 => DataType::default_for(TypeFamily::F64),
+                    _ => numeric_type(digits.clamp(0, NUMERIC_RESULT_SCALE as i64) as u8),
+                };
+                let digits = BoundExpr::Const {
+                    value: TemplateDatum::Const(encode_i64(digits)?),
+                    data_type: integer_type,
+                };
+                (ScalarFunc::Round, vec![arg, digits], data_type)
+            }
+            "coalesce" => {
+                let mut operands = Vec::with_capacity(args.len());
+                for arg in args {
+                    operands.push(self.arg_operand(function, arg)?);
+                }
+                let (bound, data_type) = unify(self.resolve_all(operands)?, "COALESCE")?;
+                (ScalarFunc::Coalesce, bound, data_type)
+            }
+            "date_trunc" => {
+                arity(2, 2)?;
+                let field = self.time_field(function, &args[0])?;
+                if matches!(
+                    field,
+                    TimeField::DayOfWeek | TimeField::DayOfYear | TimeField::Epoch
+                ) {
+                    return Err(mudu_error!(
+                        ER::InvalidArgument,
+                        format!("date_trunc does not support {:?}", field)
+                    ));
+                }
+                let source = self.arg_operand(function, &args[1])?;
+                let source =
+                    self.resolve(source, Some(&DataType::default_for(TypeFamily::Timestamp)))?;
+                expect_family(&name, &source, |family| {
+                    matches!(
+                        family,
+                        TypeFamily::Date | TypeFamily::Timestamp | TypeFamily::TimestampTz
+                    )
+                })?;
+                let data_type = match source.data_type().type_family() {
+                    TypeFamily::TimestampTz => DataType::default_for(TypeFamily::TimestampTz),
+                    _ => DataType::default_for(TypeFamily::Timestamp),
+                };
+                (ScalarFunc::DateTrunc(field), vec![source], data_type)
+            }
+            "extract" | "date_part" => {
+                arity(2, 2)?;
+                let field = self.time_field(function, &args[0])?;
+                let source = self.arg_operand(function, &args[1])?;
+                let source =
+                    self.resolve(source, Some(&DataType::default_for(TypeFamily::Timestamp)))?;
+                expect_family(&name, &source, |family| {
+                    matches!(
+                        family,
+                        TypeFamily::Date
+                            | TypeFamily::Time
+                            | TypeFamily::Timestamp
+                            | TypeFamily::TimestampTz
+                    )
+                })?;
+                let data_type = if name == "date_part" {
+                    DataType::default_for(TypeFamily::F64)
+                } else {
+                    numeric_type(match field {
+                        TimeField::Second | TimeField::Epoch => 6,
+                        TimeField::Millisecond => 3,
+                        _ => 0,
+                    })
+                };
+                (ScalarFunc::Extract(field), vec![source], data_type)
+            }
+            "count" | "sum" | "avg" | "min" | "max" => {
+                return Err(mudu_error!(
+                    ER::NotImplemented,
+                    format!("aggregate {} is not allowed in this expression", name)
+                ))
+            }
+            _ => {
+                return Err(mudu_error!(
+                    ER::NotImplemented,
+                    format!("unsupported function {}", function.name())
+                ))
+            }
+        };
+        Ok(BoundExpr::Function {
+            func,
+            args,
+            data_type,
+        })
+    }
+
+    // The field named by the string literal `arg`.
+    fn time_field(&mut self, function: &ExprFunction, arg: &FunctionArg) -> RS<TimeField> {
+        let invalid = || {
+            mudu_error!(
+                ER::InvalidArgument,
+                format!(
+                    "the first argument of {} must name a date/time field",
+                    function.name()
+                )
+            )
+        };
+        let Operand::Literal(ExprLiteral::DatumLiteral(typed)) = self.arg_operand(function, arg)?
+        else {
+            return Err(invalid());
+        };
+        let name = typed.data_internal().as_string().ok_or_else(invalid)?;
+        TimeField::from_name(name).ok_or_else(|| {
+            mudu_error!(
+                ER::InvalidArgument,
+                format!("unknown date/time field {:?}", name)
+            )
+        })
+    }
+}
+
+/// Bind the condition of a `CHECK` constraint of `table_desc`. Returns the
+/// condition resolved over a full row indexed by attribute, and the columns
+/// it reads.
+pub(crate) fn bind_check(
+    table_desc: &TableDesc,
+    condition: &ExprType,
+) -> RS<(ScalarExpr, Vec<AttrIndex>)> {
+    let mut json_columns = JsonColumns::default();
+    let template =
+        ExprBinder::new(table_desc, &mut json_columns, None).bind_condition(condition)?;
+    let bound = without_params(&template)?;
+    let width = table_desc.fields().len();
+    let expr = ScalarExpr::resolve(&bound, &|attr| {
+        if attr < width {
+            Ok(attr)
+        } else {
+            Err(mudu_error!(
+                ER::NotImplemented,
+                "JSON paths in a check constraint are not implemented"
+            ))
+        }
+    })?;
+    let mut attrs = Vec::new();
+    bound.collect_attrs(&mut attrs);
+    Ok((expr, attrs))
+}
+
+/// Convert a bound expression without placeholders into a plain bound
+/// expression.
+pub(crate) fn without_params(expr: &ExprTemplate) -> RS<BoundExpr> {
+    expr.try_map_const(&mut |datum| match datum {
+        TemplateDatum::Const(value) => Ok(value.clone()),
+        TemplateDatum::Slot(_) => Err(mudu_error!(
+            ER::Internal,
+            "unexpected parameter in a constant expression"
+        )),
+    })
+}
+
+// Own type of a literal: integers widen to BIGINT and a NUMERIC keeps the
+// scale it was written with.
+fn literal_type(data_type: &DataType, typed: &DataTyped) -> DataType {
+    match data_type.type_family() {
+        family if is_integer(family) => DataType::default_for(TypeFamily::I64),
+        TypeFamily::F32 | TypeFamily::F64 => DataType::default_for(TypeFamily::F64),
+        TypeFamily::Numeric => {
+            let scale = typed
+                .data_internal()
+                .as_numeric()
+                .map(|numeric| numeric.as_bigdecimal().as_bigint_and_exponent().1)
+                .unwrap_or(0);
+            numeric_type(scale.clamp(0, 38) as u8)
+        }
+        TypeFamily::String => DataType::default_for(TypeFamily::String),
+        _ => data_type.clone(),
+    }
+}
+
+fn literal_expr(literal: &ExprLiteral, hint: Option<&DataType>) -> RS<ExprTemplate> {
+    let typed = match literal {
+        ExprLiteral::Null => {
+            return Ok(BoundExpr::Const {
+                value: TemplateDatum::Const(None),
+                data_type: hint
+                    .cloned()
+                    .unwrap_or_else(|| DataType::default_for(TypeFamily::String)),
+            })
+        }
+        ExprLiteral::DatumLiteral(typed) => typed,
+    };
+    let own = literal_type(typed.data_type(), typed);
+    let data_type = match hint {
+        // A quoted literal is read as the type it is used with, e.g. a
+        // timestamp in `created_at < '2024-01-01'`.
+        Some(hint)
+            if own.type_family() == TypeFamily::String
+                && hint.type_family() != TypeFamily::String =>
+        {
+            param_type(hint)
+        }
+        _ => own,
+    };
+    let value = encode(typed.data_internal().clone(), typed.data_type(), &data_type)?;
+    Ok(BoundExpr::Const {
+        value: TemplateDatum::Const(value),
+        data_type,
+    })
+}
+
+fn encode_i64(value: i64) -> RS<Option<Vec<u8>>> {
+    let data_type = DataType::default_for(TypeFamily::I64);
+    encode(DataValue::from_i64(value), &data_type, &data_type)
+}
+
+// Type a placeholder or quoted literal takes from its sibling `hint`:
+// the widest type of the same kind, so the value is not truncated.
+fn param_type(hint: &DataType) -> DataType {
+    match hint.type_family() {
+        family if is_integer(family) => DataType::default_for(TypeFamily::I64),
+        TypeFamily::F32 => DataType::default_for(TypeFamily::F64),
+        TypeFamily::Numeric => numeric_type(NUMERIC_PARAM_SCALE.max(numeric_scale(hint))),
+        family => DataType::default_for(family),
+    }
+}
+
+fn numeric_type(scale: u8) -> DataType {
+    DataType::from_numeric(DataTypeParamNumeric::new(38, scale))
+}
+
+fn numeric_scale(data_type: &DataType) -> u8 {
+    data_type
+        .as_numeric_param()
+        .map(|param| param.scale())
+        .unwrap_or(0)
+}
+
+fn is_integer(family: TypeFamily) -> bool {
+    matches!(
+        family,
+        TypeFamily::I8 | TypeFamily::I16 | TypeFamily::I32 | TypeFamily::I64
+    )
+}
+
+fn is_number(family: TypeFamily) -> bool {
+    is_integer(family)
+        || matches!(
+            family,
+            TypeFamily::Numeric | TypeFamily::F32 | TypeFamily::F64
+        )
+}
+
+fn expect_family(name: &str, arg: &ExprTemplate, accepts: impl Fn(TypeFamily) -> bool) -> RS<()> {
+    if accepts(arg.data_type().type_family()) {
+        return Ok(());
+    }
+    Err(mudu_error!(
+        ER::InvalidType,
+        format!(
+            "function {} does not accept {} arguments",
+            name,
+            arg.data_type().name()
+        )
+    ))
+}
+
+fn arithmetic_type(op: Arithmetic, left: &DataType, right: &DataType) -> RS<DataType> {
+    let (l, r) = (left.type_family(), right.type_family());
+    if matches!(op, Arithmetic::CONCAT) {
+        return Ok(DataType::default_for(TypeFamily::String));
+    }
+    if !is_number(l) || !is_number(r) {
+        return Err(mudu_error!(
+            ER::InvalidType,
+            format!(
+                "operator {:?} does not accept {} and {}",
+                op,
+                left.name(),
+                right.name()
+            )
+        ));
+    }
+    if is_integer(l) && is_integer(r) {
+        return Ok(DataType::default_for(TypeFamily::I64));
+    }
+    if matches!(l, TypeFamily::F32 | TypeFamily::F64)
+        || matches!(r, TypeFamily::F32 | TypeFamily::F64)
+    {
+        if matches!(op, Arithmetic::MODULO) {
+            return Err(mudu_error!(
+                ER::InvalidType,
+                "operator % is not defined for double precision"
+            ));
+        }
+        return Ok(DataType::default_for(TypeFamily::F64));
+    }
+    let (ls, rs) = (numeric_scale(left), numeric_scale(right));
+    let scale = match op {
+        Arithmetic::MULTIPLE => ls.saturating_add(rs),
+        Arithmetic::DIVIDE => ls.max(rs).max(NUMERIC_DIVISION_SCALE),
+        _ => ls.max(rs),
+    };
+    Ok(numeric_type(
+        scale.min(NUMERIC_RESULT_SCALE.max(ls).max(rs)),
+    ))
+}
+
+// Type both sides of a comparison (or the values of IN, CASE, COALESCE)
+// can be converted to.
+fn common_type(left: &DataType, right: &DataType) -> Option<DataType> {
+    let (l, r) = (left.type_family(), right.type_family());
+    if l == r {
+        return Some(match l {
+            TypeFamily::Numeric => numeric_type(numeric_scale(left).max(numeric_scale(right))),
+            TypeFamily::String => DataType::default_for(TypeFamily::String),
+            _ => left.clone(),
+        });
+    }
+    if is_integer(l) && is_integer(r) {
+        return Some(DataType::default_for(TypeFamily::I64));
+    }
+    if is_number(l) && is_number(r) {
+        let exact = |family: TypeFamily| is_integer(family) || family == TypeFamily::Numeric;
+        return Some(if exact(l) && exact(r) {
+            numeric_type(numeric_scale(left).max(numeric_scale(right)))
+        } else {
+            DataType::default_for(TypeFamily::F64)
+        });
+    }
+    let temporal = |family: TypeFamily| match family {
+        TypeFamily::Date => Some(0),
+        TypeFamily::Timestamp => Some(1),
+        TypeFamily::TimestampTz => Some(2),
+        _ => None,
+    };
+    let family = if temporal(l)?.max(temporal(r)?) == 2 {
+        TypeFamily::TimestampTz
+    } else {
+        TypeFamily::Timestamp
+    };
+    Some(DataType::default_for(family))
+}
+
+// Convert every expression to one common type.
+fn unify(exprs: Vec<ExprTemplate>, context: &str) -> RS<(Vec<ExprTemplate>, DataType)> {
+    let mut types = exprs.iter().map(|expr| expr.data_type());
+    let first = types
+        .next()
+        .ok_or_else(|| mudu_error!(ER::InvalidArgument, format!("empty {}", context)))?;
+    let mut common = first;
+    for data_type in types {
+        common = common_type(&common, &data_type).ok_or_else(|| {
+            mudu_error!(
+                ER::InvalidType,
+                format!(
+                    "{} types {} and {} cannot be matched",
+                    context,
+                    common.name(),
+                    data_type.name()
+                )
+            )
+        })?;
+    }
+    let exprs = exprs
+        .into_iter()
+        .map(|expr| cast_to(expr, &common))
+        .collect();
+    Ok((exprs, common))
+}
+
+// `expr` converted to `target`; integers of any width and values of the
+// same type are used as they are.
+fn cast_to(expr: ExprTemplate, target: &DataType) -> ExprTemplate {
+    let source = expr.data_type();
+    let (from, to) = (source.type_family(), target.type_family());
+    let same = if from == TypeFamily::Numeric && to == TypeFamily::Numeric {
+        numeric_scale(&source) == numeric_scale(target)
+    } else {
+        from == to || (is_integer(from) && to == TypeFamily::I64)
+    };
+    if same {
+        return expr;
+    }
+    BoundExpr::Cast {
+        operand: Box::new(expr),
+        data_type: target.clone(),
+    }
+}
+
+fn array<const N: usize>(exprs: Vec<ExprTemplate>) -> RS<[ExprTemplate; N]> {
+    exprs
+        .try_into()
+        .map_err(|_| mudu_error!(ER::Internal, "unexpected operand count"))
+}
+
+fn boxed_array<const N: usize>(exprs: Vec<ExprTemplate>) -> RS<[Box<ExprTemplate>; N]> {
+    Ok(array::<N>(exprs)?.map(Box::new))
+}
@@ -0,0 +1,287 @@
+//! Column resolution for `SELECT` statements over joined tables.
+//!
+//! Used by both the query binder and the statement describer so that joined
+//! column positions and the output tuple description agree on both paths.
+
+use crate::contract::meta_mgr::MetaMgr;
+use crate::contract::table_desc::TableDesc;
+use crate::sql::bound_stmt::BoundOrderBy;
+use mudu::common::result::RS;
+use mudu::error::ErrorCode as ER;
+use mudu::mudu_error;
+use mudu_contract::tuple::datum_desc::DatumDesc;
+use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
+use mudu_type::data_type::DataType;
+use sql_parser::ast::expr_name::ExprName;
+use sql_parser::ast::select_clause::{OrderByItem, OrderTarget};
+use sql_parser::ast::select_join::JoinKind;
+use sql_parser::ast::select_term::{SelectField, SelectTerm};
+use sql_parser::ast::stmt_select::StmtSelect;
+use std::sync::Arc;
+
+struct ScopeTable {
+    name: String,
+    alias: Option<String>,
+    desc: Arc<TableDesc>,
+    // Position of the table's first column in the joined row.
+    offset: usize,
+    // Whether a LEFT JOIN may NULL-extend the table's columns.
+    null_extended: bool,
+}
+
+impl ScopeTable {
+    fn reference_name(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// The tables of a `FROM` clause in join order.
+///
+/// A joined row concatenates every column of every table, so a column is
+/// addressed by its table's offset plus its attribute index.
+pub(crate) struct JoinScope {
+    tables: Vec<ScopeTable>,
+}
+
+impl JoinScope {
+    /// Resolves the `FROM` table and every joined table of `stmt`.
+    pub(crate) async fn from_select(meta_mgr: &dyn MetaMgr, stmt: &StmtSelect) -> RS<Self> {
+        let mut scope = Self { tables: Vec::new() };
+        let desc = get_table_by_name(meta_mgr, stmt.get_table_reference()).await?;
+        scope.push(
+            stmt.get_table_reference(),
+            stmt.get_table_alias(),
+            desc,
+            false,
+        )?;
+        for join in stmt.get_joins() {
+            let desc = get_table_by_name(meta_mgr, join.table_name()).await?;
+            scope.push(
+                join.table_name(),
+                join.alias(),
+                desc,
+                join.kind() == JoinKind::LeftOuter,
+            )?;
+        }
+        Ok(scope)
+    }
+
+    fn push(
+        &mut self,
+        name: &str,
+        alias: Option<&str>,
+        desc: Arc<TableDesc>,
+        null_extended: bool,
+    ) -> RS<()> {
+        let table = ScopeTable {
+            name: name.to_string(),
+            alias: alias.map(str::to_string),
+            desc,
+            offset: self.width(),
+            null_extended,
+        };
+        if self
+            .tables
+            .iter()
+            .any(|other| other.reference_name() == table.reference_name())
+        {
+            return Err(mudu_error!(
+                ER::Parse,
+                format!(
+                    "table name {} specified more than once",
+                    table.reference_name()
+                )
+            ));
+        }
+        self.tables.push(table);
+        Ok(())
+    }
+
+    /// Number of columns of a joined row.
+    pub(crate) fn width(&self) -> usize {
+        self.tables
+            .last()
+            .map(|table| table.offset + table.desc.fields().len())
+            .unwrap_or(0)
+    }
+
+    /// Position of the first column of the `index`-th table; the width of
+    /// the joined row when there is no such table.
+    pub(crate) fn offset(&self, index: usize) -> usize {
+        self.tables
+            .get(index)
+            .map(|table| table.offset)
+            .unwrap_or_else(|| self.width())
+    }
+
+    /// Resolves a column reference to its position in the joined row.
+    ///
+    /// A qualified name matches the table alias, or the table name when the
+    /// table has no alias; an unqualified name must be unique across tables.
+    pub(crate) fn resolve(&self, name: &ExprName) -> RS<usize> {
+        if name.json_path().is_some() {
+            return Err(mudu_error!(
+                ER::NotImplemented,
+                "JSON paths in join queries are not implemented"
+            ));
+        }
+        let mut found = None;
+        for table in &self.tables {
+            if let Some(qualifier) = name.qualifier() {
+                if qualifier != table.reference_name() {
+                    continue;
+                }
+            }
+            let Some(attr) = (0..table.desc.fields().len())
+                .find(|attr| table.desc.get_attr(*attr).name() == name.name())
+            else {
+                continue;
+            };
+            if found.is_some() {
+                return Err(mudu_error!(
+                    ER::Parse,
+                    format!("column reference {} is ambiguous", name.name())
+                ));
+            }
+            found = Some(table.offset + attr);
+        }
+        found.ok_or_else(|| {
+            mudu_error!(
+                ER::EntityNotFound,
+                format!("cannot find column {}", display_name(name))
+            )
+        })
+    }
+
+    /// Name and type of the column at `pos` in the joined row, and whether
+    /// it can be NULL.
+    pub(crate) fn column(&self, pos: usize) -> RS<(&String, &DataType, bool)> {
+        let table = self
+            .tables
+            .iter()
+            .rev()
+            .find(|table| table.offset <= pos)
+            .filter(|table| pos - table.offset < table.desc.fields().len())
+            .ok_or_else(|| mudu_error!(ER::IndexOutOfRange))?;
+        let field = table.desc.get_attr(pos - table.offset);
+        Ok((
+            field.name(),
+            field.type_desc(),
+            field.nullable() || table.null_extended,
+        ))
+    }
+
+    /// Binds the select list to joined-row positions and the output tuple
+    /// description. Only plain columns are supported over joins.
+    pub(crate) fn bind_projection(&self, terms: &[SelectTerm]) -> RS<(Vec<usize>, TupleFieldDesc)> {
+        let mut projection = Vec::with_capacity(terms.len());
+        let mut desc_fields = Vec::with_capacity(terms.len());
+        for term in terms {
+            let SelectField::Column(name) = term.field() else {
+                return Err(mudu_error!(
+                    ER::NotImplemented,
+                    "aggregates and expressions over joined tables are not implemented"
+                ));
+            };
+            let pos = self.resolve(name)?;
+            let (name, data_type, nullable) = self.column(pos)?;
+            let output_name = if term.alias().is_empty() {
+                name.clone()
+            } else {
+                term.alias().clone()
+            };
+            desc_fields.push(DatumDesc::new_nullable(
+                output_name,
+                data_type.clone(),
+                nullable,
+            ));
+            projection.push(pos);
+        }
+        Ok((projection, TupleFieldDesc::new(desc_fields)))
+    }
+
+    /// Binds `ORDER BY` to positions in the projected row. A column that is
+    /// not projected is added after the output columns; the returned list
+    /// holds the joined-row positions of those hidden columns.
+    pub(crate) fn bind_order_by(
+        &self,
+        items: &[OrderByItem],
+        projection: &[usize],
+        tuple_desc: &TupleFieldDesc,
+    ) -> RS<(Vec<usize>, Vec<BoundOrderBy>)> {
+        let mut hidden: Vec<usize> = Vec::new();
+        let mut order_by = Vec::with_capacity(items.len());
+        for item in items {
+            let pos = match item.target() {
+                OrderTarget::Position(position) => {
+                    if *position > projection.len() {
+                        return Err(mudu_error!(
+                            ER::Parse,
+                            format!("ORDER BY position {} is not in select list", position)
+                        ));
+                    }
+                    position - 1
+                }
+                OrderTarget::Field(SelectField::Function(_) | SelectField::Expression(_)) => {
+                    return Err(mudu_error!(
+                        ER::NotImplemented,
+                        "expressions over joined tables are not implemented"
+                    ))
+                }
+                OrderTarget::Field(SelectField::Column(name)) => {
+                    // An output column name takes precedence over an input
+                    // column.
+                    let output = name
+                        .qualifier()
+                        .is_none()
+                        .then(|| {
+                            tuple_desc
+                                .fields()
+                                .iter()
+                                .position(|field| field.name() == name.name())
+                        })
+                        .flatten();
+                    match output {
+                        Some(pos) => pos,
+                        None => {
+                            let column = self.resolve(name)?;
+                            match projection.iter().position(|pos| *pos == column) {
+                                Some(pos) => pos,
+                                None => {
+                                    let index = match hidden.iter().position(|pos| *pos == column) {
+                                        Some(index) => index,
+                                        None => {
+                                            hidden.push(column);
+                                            hidden.len() - 1
+                                        }
+                                    };
+                                    projection.len() + index
+                                }
+                            }
+                        }
+                    }
+                }
+            };
+            order_by.push(BoundOrderBy {
+                pos,
+                descending: item.descending(),
+                nulls_first: item.nulls_first(),
+            });
+        }
+        Ok((hidden, order_by))
+    }
+}
+
+fn display_name(name: &ExprName) -> String {
+    match name.qualifier() {
+        Some(qualifier) => format!("{}.{}", qualifier, name.name()),
+        None => name.name().clone(),
+    }
+}
+
+async fn get_table_by_name(meta_mgr: &dyn MetaMgr, name: &str) -> RS<Arc<TableDesc>> {
+    meta_mgr
+        .get_table_by_name(name)
+        .await?
+        .ok_or_else(|| mudu_error!(ER::EntityNotFound, format!("no such table {}", name)))
+}
@@ -23,6 +23,8 @@ pub mod describer;
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
 mod describer_test;
+pub(crate) mod expr_binding;
+mod join_scope;
 pub mod plan_ctx;
 pub mod planner;
 pub mod proj_list;
@@ -1,3 +1,5 @@
+use crate::command::alter_partition_rule::AlterPartitionRule;
+use crate::command::alter_table::AlterTable;
 use crate::command::create_fs_type::CreateFsType;
 use crate::command::create_partition_placement::CreatePartitionPlacement;
 use crate::command::create_partition_rule::CreatePartitionRule;
@@ -8,25 +10,50 @@ use crate::command::drop_table::DropTable;
 use crate::command::insert_key_value::InsertKeyValue;
 use crate::command::load_from_file::{LoadFromFile, LoadFromFileParams};
 use crate::command::save_to_file::{SaveToFile, SaveToFileParams};
+use crate::command::scanned_write::{ScannedWrite, WriteRow};
 use crate::command::update_key_value::UpdateKeyValue;
 use crate::contract::cmd_exec::CmdExec;
 use crate::contract::query_exec::QueryExec;
+use crate::contract::table_desc::TableDesc;
+use crate::executor::aggregate::{AggregateExec, AggregateOutput, AggregateSpec};
+use crate::executor::explain::{AnalyzeExec, ExplainExec};
+use crate::executor::filter::{ColumnFilter, ConditionFilter, FilterExec, ResidualFilter};
+use crate::executor::hash_join::{HashJoinExec, HashJoinKey};
+use crate::executor::index_access_range::IndexAccessRange;
+use crate::executor::index_nested_loop_join::{IndexLookup, IndexNestedLoopJoinExec};
+use crate::executor::join::JoinCondition;
+use crate::executor::json_path::JsonPathColumn;
+use crate::executor::limit::LimitExec;
+use crate::executor::nested_loop_join::NestedLoopJoinExec;
+use crate::executor::scalar_expr::ScalarExpr;
+use crate::executor::sort::{SortExec, SortKey};
 use crate::sql::bound_stmt::{
-    BoundCommand, BoundCopyFrom, BoundCopyTo, BoundCreateFsType, BoundCreatePartitionPlacement,
+    BoundAlterPartitionRule, BoundAlterTable, BoundCommand, BoundCondition, BoundConflictValue,
+    BoundCopyFrom, BoundCopyTo, BoundCreateFsType, BoundCreatePartitionPlacement,
     BoundCreatePartitionRule, BoundCreateTable, BoundDelete, BoundDropTable, BoundDropType,
-    BoundInsert, BoundPredicate, BoundQuery, BoundSelect, BoundSelectItem, BoundSetValue,
-    BoundUpdate,
+    BoundInsert, BoundJoinCondition, BoundJoinSelect, BoundJsonColumn, BoundOnConflict,
+    BoundPredicate, BoundQuery, BoundResidual, BoundReturning, BoundRowOrder, BoundSelect,
+    BoundSelectItem, BoundSetValue, BoundUpdate,
 };
 use crate::sql::plan_ctx::PlanCtx;
+use crate::sql::select_projection::{attr_type, item_datum_desc};
 use crate::x_engine::api::{DeltaAssign, OptRead, Predicate, RangeData, VecDatum, VecSelTerm};
+use crate::x_engine::data_bin::DataBin;
 use crate::x_engine::x_param::{
-    PAccessKey, PAccessRange, PCreateFsType, PCreatePartitionPlacement, PCreatePartitionRule,
-    PCreateTable, PDeleteKeyValue, PDropTable, PDropType, PInsertKeyValue, PUpdateKeyValue,
+    PAccessKey, PAccessRange, PAlterPartitionRule, PAlterTable, PConflictAction, PCreateFsType,
+    PCreatePartitionPlacement, PCreatePartitionRule, PCreateTable, PDeleteKeyValue, PDropTable,
+    PDropType, PInsertKeyValue, PReturning, PUpdateKeyValue,
 };
-use mudu::common::id::AttrIndex;
+use mudu::common::id::{AttrIndex, OID};
 use mudu::common::result::RS;
 use mudu::error::ErrorCode as ER;
 use mudu::mudu_error;
+use mudu_contract::tuple::datum_desc::DatumDesc;
+use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
+use mudu_type::data_type_fn_param::DataType;
+use mudu_type::type_family::TypeFamily;
+use sql_parser::ast::expr_operator::ValueCompare;
+use sql_parser::ast::select_join::JoinKind;
 use std::sync::Arc;
 
 /// Append `attr` to `attrs` unless already present.
@@ -36,18 +63,362 @@ fn push_unique(attrs: &mut Vec<AttrIndex>, attr: AttrIndex) {
     }
 }
 
+/// Pick the secondary index whose leading columns are fixed by the most
+/// equality residuals, as an `IndexEq` scan predicate.
+///
+/// The residuals stay in place: the index only narrows the rows read, and
+/// the filter still evaluates every condition.
+fn choose_index(residual: &[BoundResidual], table_desc: &TableDesc) -> Option<Predicate> {
+    let mut best: Option<(OID, Vec<(AttrIndex, DataBin)>)> = None;
+    for index in table_desc.indexes() {
+        let mut prefix = Vec::new();
+        for field in &index.fields {
+            let attr = field.column_index();
+            let literal = residual
+                .iter()
+                .find_map(|residual| match &residual.literal {
+                    Some(literal) if residual.attr == attr && residual.op == ValueCompare::EQ => {
+                        Some(literal.clone())
+                    }
+                    _ => None,
+                });
+            match literal {
+                Some(literal) => prefix.push((attr, literal)),
+                None => break,
+            }
+        }
+        let longer = best
+            .as_ref()
+            .is_none_or(|(_, best_prefix)| prefix.len() > best_prefix.len());
+        if !prefix.is_empty() && longer {
+            best = Some((index.oid, prefix));
+        }
+    }
+    best.map(|(index_oid, prefix)| Predicate::IndexEq { index_oid, prefix })
+}
+
+/// Physical operator for one join step.
+#[derive(Debug, PartialEq, Eq)]
+enum JoinMethod {
+    /// Inner primary key attributes and the outer positions supplying them.
+    IndexNestedLoop(Vec<(AttrIndex, usize)>),
+    /// Equi-join column pairs as (outer position, inner attribute).
+    Hash(Vec<(usize, AttrIndex)>),
+    NestedLoop,
+}
+
+/// Choose how to join the table `inner` to an outer row of `outer_width`
+/// columns.
+///
+/// `types` holds the column types of the whole joined row. Equalities
+/// between one outer and one inner column of the same type are usable keys;
+/// floating-point columns are not, because equal values such as `0.0` and
+/// `-0.0` have different encodings. When the keys fix the whole primary key
+/// of `inner` it is read by key per outer row, otherwise any key allows a
+/// hash join.
+fn choose_join_method(
+    on: &[BoundJoinCondition],
+    outer_width: usize,
+    inner: &TableDesc,
+    types: &[DataType],
+) -> JoinMethod {
+    let inner_range = outer_width..outer_width + inner.fields().len();
+    let mut pairs = Vec::new();
+    for condition in on {
+        let BoundJoinCondition::Column {
+            left,
+            op: ValueCompare::EQ,
+            right,
+        } = condition
+        else {
+            continue;
+        };
+        let (outer_pos, inner_pos) = if *left < outer_width && inner_range.contains(right) {
+            (*left, *right)
+        } else if *right < outer_width && inner_range.contains(left) {
+            (*right, *left)
+        } else {
+            continue;
+        };
+        let (Some(outer_type), Some(inner_type)) = (types.get(outer_pos), types.get(inner_pos))
+        else {
+            continue;
+        };
+        if outer_type.to_info() != inner_type.to_info()
+            || matches!(outer_type.type_family(), TypeFamily::F32 | TypeFamily::F64)
+        {
+            continue;
+        }
+        pairs.push((outer_pos, inner_pos - outer_width));
+    }
+    let key = inner
+        .key_indices()
+        .iter()
+        .map(|attr| {
+            pairs
+                .iter()
+                .find(|(_, inner_attr)| inner_attr == attr)
+                .map(|(outer_pos, _)| (*attr, *outer_pos))
+        })
+        .collect::<Option<Vec<_>>>();
+    match key {
+        Some(key) if !key.is_empty() => JoinMethod::IndexNestedLoop(key),
+        _ if !pairs.is_empty() => JoinMethod::Hash(pairs),
+        _ => JoinMethod::NestedLoop,
+    }
+}
+
+/// Resolve join conditions against the joined row column `types`.
+fn join_condition(conditions: &[BoundJoinCondition], types: &[DataType]) -> RS<JoinCondition> {
+    let type_at = |pos: usize| -> RS<DataType> {
+        types
+            .get(pos)
+            .cloned()
+            .ok_or_else(|| mudu_error!(ER::InvalidState, "join column out of row bounds"))
+    };
+    let mut condition = JoinCondition::default();
+    for bound in conditions {
+        match bound {
+            BoundJoinCondition::Literal { pos, op, literal } => {
+                condition.filters.push(ResidualFilter {
+                    input_pos: *pos,
+                    data_type: type_at(*pos)?,
+                    op: *op,
+                    literal: literal.clone(),
+                })
+            }
+            BoundJoinCondition::Column { left, op, right } => {
+                condition.column_filters.push(ColumnFilter {
+                    left_pos: *left,
+                    left_type: type_at(*left)?,
+                    op: *op,
+                    right_pos: *right,
+                    right_type: type_at(*right)?,
+                })
+            }
+        }
+    }
+    Ok(condition)
+}
+
+/// Append the columns `condition` reads to `attrs`, in first-use order.
+fn condition_attrs(condition: &BoundCondition, attrs: &mut Vec<AttrIndex>) {
+    match condition {
+        BoundCondition::And(conditions) | BoundCondition::Or(conditions) => {
+            for condition in conditions {
+                condition_attrs(condition, attrs);
+            }
+        }
+        BoundCondition::Not(condition) => condition_attrs(condition, attrs),
+        BoundCondition::Compare { attr, .. }
+        | BoundCondition::InList { attr, .. }
+        | BoundCondition::Between { attr, .. }
+        | BoundCondition::Like { attr, .. }
+        | BoundCondition::IsNull { attr, .. } => push_unique(attrs, *attr),
+        BoundCondition::Expr(expr) => expr.collect_attrs(attrs),
+    }
+}
+
+/// Resolve `condition` against a scan row whose columns are placed by
+/// `attr_pos`.
+fn condition_filter(
+    condition: &BoundCondition,
+    table_desc: &TableDesc,
+    json_columns: &[BoundJsonColumn],
+    attr_pos: &dyn Fn(AttrIndex) -> RS<usize>,
+) -> RS<ConditionFilter> {
+    let resolve_all = |conditions: &[BoundCondition]| {
+        conditions
+            .iter()
+            .map(|condition| condition_filter(condition, table_desc, json_columns, attr_pos))
+            .collect::<RS<Vec<_>>>()
+    };
+    let data_type = |attr: AttrIndex| attr_type(table_desc, json_columns, attr);
+    Ok(match condition {
+        BoundCondition::And(conditions) => ConditionFilter::And(resolve_all(conditions)?),
+        BoundCondition::Or(conditions) => ConditionFilter::Or(resolve_all(conditions)?),
+        BoundCondition::Not(condition) => ConditionFilter::Not(Box::new(condition_filter(
+            condition,
+            table_desc,
+            json_columns,
+            attr_pos,
+        )?)),
+        BoundCondition::Compare { attr, op, literal } => ConditionFilter::Compare(ResidualFilter {
+            input_pos: attr_pos(*attr)?,
+            data_type: data_type(*attr),
+            op: *op,
+            literal: literal.clone(),
+        }),
+        BoundCondition::InList {
+            attr,
+            list,
+            negated,
+        } => ConditionFilter::InList {
+            input_pos: attr_pos(*attr)?,
+            data_type: data_type(*attr),
+            list: list.clone(),
+            negated: *negated,
+        },
+        BoundCondition::Between {
+            attr,
+            low,
+            high,
+            negated,
+        } => ConditionFilter::Between {
+            input_pos: attr_pos(*attr)?,
+            data_type: data_type(*attr),
+            low: low.clone(),
+            high: high.clone(),
+            negated: *negated,
+        },
+        BoundCondition::Like {
+            attr,
+            pattern,
+            case_insensitive,
+            negated,
+        } => ConditionFilter::Like {
+            input_pos: attr_pos(*attr)?,
+            data_type: data_type(*attr),
+            pattern: pattern.clone(),
+            case_insensitive: *case_insensitive,
+            negated: *negated,
+        },
+        BoundCondition::IsNull { attr, negated } => ConditionFilter::IsNull {
+            input_pos: attr_pos(*attr)?,
+            negated: *negated,
+        },
+        BoundCondition::Expr(expr) => ConditionFilter::Expr(ScalarExpr::resolve(expr, attr_pos)?),
+    })
+}
+
+// Split `SET` values into the absolute values, the delta assignments and
+// the expression assignments of an update. Expressions are resolved against
+// the full row.
+fn split_set_values(
+    set_values: Vec<(AttrIndex, BoundSetValue)>,
+) -> RS<(VecDatum, Vec<DeltaAssign>, Vec<(AttrIndex, ScalarExpr)>)> {
+    let mut absolute = Vec::new();
+    let mut delta_assignments = Vec::new();
+    let mut expr_assignments = Vec::new();
+    for (attr, set_value) in set_values {
+        match set_value {
+            BoundSetValue::Absolute(binary) => absolute.push((attr, binary)),
+            BoundSetValue::Delta { op, literal } => {
+                delta_assignments.push(DeltaAssign { attr, op, literal })
+            }
+            BoundSetValue::Expr(expr) => {
+                expr_assignments.push((attr, ScalarExpr::resolve(&expr, &|attr| Ok(attr))?))
+            }
+        }
+    }
+    Ok((VecDatum::new(absolute), delta_assignments, expr_assignments))
+}
+
+fn plan_returning(returning: BoundReturning) -> PReturning {
+    PReturning {
+        select: VecSelTerm::new(returning.attrs),
+        tuple_desc: returning.tuple_desc,
+    }
+}
+
+/// Every column of `table_desc` in attribute order.
+fn all_attrs(table_desc: &TableDesc) -> VecSelTerm {
+    VecSelTerm::new((0..table_desc.fields().len()).collect())
+}
+
+/// Whether the scan planned for `stmt` returns rows already in its
+/// `ORDER BY` order.
+///
+/// A range scan of one partition returns rows in primary key order, so the
+/// order matches when the sort keys are ascending key columns in key order.
+/// Key columns fixed by the predicate may appear anywhere or be left out.
+/// A secondary index scan returns rows in index key order instead.
+fn scan_matches_order(stmt: &BoundSelect, table_desc: &TableDesc) -> bool {
+    let full_scan = match &stmt.predicate {
+        BoundPredicate::True => true,
+        BoundPredicate::KeyRange { start, end } => matches!(
+            (start, end),
+            (std::ops::Bound::Unbounded, std::ops::Bound::Unbounded)
+        ),
+        _ => false,
+    };
+    if full_scan && choose_index(&stmt.residual, table_desc).is_some() {
+        return false;
+    }
+    let fixed: Vec<AttrIndex> = match &stmt.predicate {
+        // At most one row.
+        BoundPredicate::KeyEq { .. } => return true,
+        // Keys are read in statement order.
+        BoundPredicate::KeyIn { .. } => return false,
+        BoundPredicate::KeyPrefixEq { prefix } => prefix.iter().map(|(attr, _)| *attr).collect(),
+        _ => Vec::new(),
+    };
+    let mut free_key = table_desc
+        .key_indices()
+        .iter()
+        .filter(|attr| !fixed.contains(attr));
+    for key in &stmt.order.order_by {
+        let item = stmt
+            .select_items
+            .iter()
+            .chain(stmt.hidden_items.iter())
+            .nth(key.pos);
+        let Some(BoundSelectItem::Column(column)) = item else {
+            return false;
+        };
+        if fixed.contains(&column.attr) {
+            continue;
+        }
+        if key.descending || free_key.next() != Some(&column.attr) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Type of the `pos`-th column of `desc`.
+fn field_type(desc: &TupleFieldDesc, pos: usize) -> RS<DataType> {
+    desc.fields()
+        .get(pos)
+        .map(|field| field.data_type().clone())
+        .ok_or_else(|| mudu_error!(ER::InvalidState, "column out of row bounds"))
+}
+
 pub struct Planner {
     ctx: PlanCtx,
+    /// Wrap every query operator in an [`AnalyzeExec`] (`EXPLAIN ANALYZE`).
+    analyze: bool,
 }
 
 impl Planner {
     pub fn new(ctx: PlanCtx) -> Self {
-        Self { ctx }
+        Self {
+            ctx,
+            analyze: false,
+        }
     }
 
     pub async fn plan_query(&self, query: BoundQuery) -> RS<Arc<dyn QueryExec>> {
         match query {
             BoundQuery::Select(select) => self.plan_select(select).await,
+            BoundQuery::Join(join) => self.plan_join(join).await,
+            BoundQuery::Explain(explain) => {
+                let planner = Planner {
+                    ctx: self.ctx.clone(),
+                    analyze: explain.analyze,
+                };
+                let exec = match *explain.query {
+                    BoundQuery::Select(select) => planner.plan_select(select).await?,
+                    BoundQuery::Join(join) => planner.plan_join(join).await?,
+                    BoundQuery::Explain(_) => {
+                        return Err(mudu_error!(
+                            ER::NotImplemented,
+                            "EXPLAIN of an EXPLAIN statement"
+                        ));
+                    }
+                };
+                Ok(Arc::new(ExplainExec::new(exec, explain.analyze)))
+            }
         }
     }
 
@@ -59,31 +430,101 @@ impl Planner {
             BoundCommand::CreatePartitionRule(stmt) => {
                 Ok(Arc::new(self.plan_create_partition_rule(stmt)))
             }
+            BoundCommand::AlterPartitionRule(stmt) => {
+                Ok(Arc::new(self.plan_alter_partition_rule(stmt)))
+            }
             BoundCommand::CreateTable(stmt) => Ok(Arc::new(self.plan_create_table(stmt))),
             BoundCommand::DropTable(stmt) => Ok(Arc::new(self.plan_drop_table(stmt))),
+            BoundCommand::AlterTable(stmt) => Ok(Arc::new(self.plan_alter_table(stmt))),
             BoundCommand::CreateFsType(stmt) => Ok(Arc::new(self.plan_create_fs_type(stmt))),
             BoundCommand::DropType(stmt) => Ok(Arc::new(self.plan_drop_fs_type(stmt))),
-            BoundCommand::Insert(stmt) => Ok(Arc::new(self.plan_insert(stmt))),
-            BoundCommand::Update(stmt) => Ok(Arc::new(self.plan_update(stmt))),
-            BoundCommand::Delete(stmt) => Ok(Arc::new(self.plan_delete(stmt))),
+            BoundCommand::Insert(stmt) => Ok(Arc::new(self.plan_insert(stmt)?)),
+            BoundCommand::Update(stmt) => self.plan_update(stmt).await,
+            BoundCommand::Delete(stmt) => self.plan_delete(stmt).await,
             BoundCommand::CopyFrom(stmt) => Ok(Arc::new(self.plan_copy_from(stmt))),
             BoundCommand::CopyTo(stmt) => Ok(Arc::new(self.plan_copy_to(stmt))),
         }
     }
 
+    /// `exec`, wrapped to collect `EXPLAIN ANALYZE` counters when analyzing.
+    fn instrument(&self, exec: Arc<dyn QueryExec>) -> Arc<dyn QueryExec> {
+        if self.analyze {
+            Arc::new(AnalyzeExec::new(exec))
+        } else {
+            exec
+        }
+    }
+
+    /// Apply `ORDER BY`, `OFFSET` and `LIMIT` to the rows of `exec`, laid out as
+    /// `row_desc`: the output columns of `tuple_desc` followed by columns only
+    /// the sort needs, which are dropped last. `sorted` and `limited` mark work
+    /// the scan already did.
+    fn plan_row_order(
+        &self,
+        exec: Arc<dyn QueryExec>,
+        row_desc: TupleFieldDesc,
+        order: &BoundRowOrder,
+        tuple_desc: TupleFieldDesc,
+        sorted: bool,
+        limited: bool,
+    ) -> RS<Arc<dyn QueryExec>> {
+        let mut exec = exec;
+        if !sorted && !order.order_by.is_empty() {
+            let keys = order
+                .order_by
+                .iter()
+                .map(|key| {
+                    Ok(SortKey {
+                        pos: key.pos,
+                        data_type: field_type(&row_desc, key.pos)?,
+                        descending: key.descending,
+                        nulls_first: key.nulls_first,
+                    })
+                })
+                .collect::<RS<Vec<_>>>()?;
+            exec = self.instrument(Arc::new(SortExec::new(row_desc.clone(), exec, keys)));
+        }
+        if !limited && (order.offset > 0 || order.limit.is_some()) {
+            exec = self.instrument(Arc::new(LimitExec::new(
+                row_desc.clone(),
+                exec,
+                order.offset,
+                order.limit,
+            )));
+        }
+        let width = tuple_desc.fields().len();
+        if row_desc.fields().len() > width {
+            exec = self.instrument(Arc::new(FilterExec::new(
+                tuple_desc,
+                exec,
+                Vec::new(),
+                (0..width).collect(),
+            )));
+        }
+        Ok(exec)
+    }
+
     async fn plan_select(&self, stmt: BoundSelect) -> RS<Arc<dyn QueryExec>> {
         let table_desc = self.ctx.meta_mgr.get_table_by_id(stmt.table_id).await?;
 
-        let has_aggregate = stmt
+        // The select list followed by the items only `HAVING` and
+        // `ORDER BY` need.
+        let items: Vec<&BoundSelectItem> = stmt
             .select_items
             .iter()
-            .any(|item| matches!(item, BoundSelectItem::Aggregate(_)));
+            .chain(stmt.hidden_items.iter())
+            .collect();
+        let grouped = !stmt.group_by.is_empty()
+            || !stmt.having.is_empty()
+            || items
+                .iter()
+                .any(|item| matches!(item, BoundSelectItem::Aggregate(_)));
 
         // Columns the storage scan must produce, deduplicated in first-use
-        // order: output columns, aggregate arguments and residual filter
-        // columns.
+        // order: output columns, aggregate arguments, expression operands,
+        // grouping columns, residual filter columns and condition columns.
         let mut scan_attrs: Vec<AttrIndex> = Vec::new();
-        for item in &stmt.select_items {
+        for item in &items {
             match item {
                 BoundSelectItem::Column(column) => push_unique(&mut scan_attrs, column.attr),
                 BoundSelectItem::Aggregate(aggregate) => {
@@ -91,11 +532,25 @@ impl Planner {
                         push_unique(&mut scan_attrs, attr);
                     }
                 }
+                BoundSelectItem::Expression(expr) => expr.expr.collect_attrs(&mut scan_attrs),
             }
         }
+        for attr in &stmt.group_by {
+            push_unique(&mut scan_attrs, *attr);
+        }
         for residual in &stmt.residual {
             push_unique(&mut scan_attrs, residual.attr);
         }
+        for condition in &stmt.conditions {
+            condition_attrs(condition, &mut scan_attrs);
+        }
+        // JSON path columns are computed from their source columns after the
+        // scan and follow the scanned columns in the row.
+        let width = table_desc.fields().len();
+        scan_attrs.retain(|attr| *attr < width);
+        for column in &stmt.json_columns {
+            push_unique(&mut scan_attrs, column.attr);
+        }
         if scan_attrs.is_empty() {
             // Pure `COUNT(*)`: the scan still needs one column to drive the
             // row count; the first key column is the cheapest.
@@ -106,80 +561,185 @@ impl Planner {
             scan_attrs.push(attr);
         }
         let attr_pos = |attr: AttrIndex| -> RS<usize> {
+            if let Some(index) = attr.checked_sub(width) {
+                return Ok(scan_attrs.len() + index);
+            }
             scan_attrs
                 .iter()
                 .position(|a| *a == attr)
                 .ok_or_else(|| mudu_error!(ER::InvalidState, "attribute missing from scan list"))
         };
+        let json_columns = stmt
+            .json_columns
+            .iter()
+            .map(|column| {
+                Ok(JsonPathColumn {
+                    input_pos: attr_pos(column.attr)?,
+                    path: column.path.clone(),
+                })
+            })
+            .collect::<RS<Vec<_>>>()?;
 
-        // Resolve residual filters against the scan row layout before any
-        // executor is built.
+        // Resolve residual filters and conditions against the scan row
+        // layout before any executor is built.
         let mut filters = Vec::with_capacity(stmt.residual.len());
         for residual in &stmt.residual {
-            filters.push(crate::executor::filter::ResidualFilter {
+            filters.push(ResidualFilter {
                 input_pos: attr_pos(residual.attr)?,
                 data_type: table_desc.get_attr(residual.attr).type_desc().clone(),
                 op: residual.op,
                 literal: residual.literal.clone(),
             });
         }
+        let conditions = stmt
+            .conditions
+            .iter()
+            .map(|condition| {
+                condition_filter(condition, &table_desc, &stmt.json_columns, &attr_pos)
+            })
+            .collect::<RS<Vec<_>>>()?;
+        let mut row_fields = stmt.tuple_desc.fields().to_vec();
+        for item in &stmt.hidden_items {
+            row_fields.push(item_datum_desc(&table_desc, &stmt.json_columns, item));
+        }
+        let row_desc = TupleFieldDesc::new(row_fields);
+
+        // Rows of one partition come out of a range scan in primary key
+        // order, so a matching `ORDER BY` needs no sort, and without
+        // filters `OFFSET`/`LIMIT` can stop the scan itself.
+        let sorted = !grouped
+            && scan_matches_order(&stmt, &table_desc)
+            && self
+                .ctx
+                .meta_mgr
+                .get_table_partition_binding(stmt.table_id)
+                .await?
+                .is_none();
+        let limited = sorted
+            && filters.is_empty()
+            && conditions.is_empty()
+            && !matches!(stmt.predicate, BoundPredicate::KeyEq { .. })
+            && (stmt.order.offset > 0 || stmt.order.limit.is_some());
+        let scan_limit = limited.then_some((stmt.order.offset, stmt.order.limit));
+
         let scan_desc =
             crate::executor::project_tuple_desc(&table_desc, &VecSelTerm::new(scan_attrs.clone()));
-        let scan = self
-            .plan_scan(&stmt, VecSelTerm::new(scan_attrs.clone()))
-            .await?;
+        let scan = self.instrument(
+            self.plan_scan(
+                &stmt.predicate,
+                &stmt.residual,
+                &table_desc,
+                VecSelTerm::new(scan_attrs.clone()),
+                scan_limit,
+            )
+            .await?,
+        );
 
-        if has_aggregate {
+        if grouped {
             // With aggregates the filter only passes rows through; the
             // aggregate executor performs the final projection.
-            let child = if filters.is_empty() {
+            let child = if filters.is_empty() && conditions.is_empty() {
                 scan
             } else {
-                Arc::new(crate::executor::filter::FilterExec::new(
-                    scan_desc,
-                    scan,
-                    filters,
-                    (0..scan_attrs.len()).collect(),
+                self.instrument(Arc::new(
+                    FilterExec::new(scan_desc, scan, filters, (0..scan_attrs.len()).collect())
+                        .with_conditions(conditions)
+                        .with_json_columns(json_columns),
                 ))
             };
-            let specs = stmt
-                .select_items
-                .iter()
-                .map(|item| match item {
+            let mut specs = Vec::new();
+            let mut outputs = Vec::with_capacity(items.len());
+            for item in &items {
+                match item {
                     BoundSelectItem::Aggregate(aggregate) => {
-                        Ok(crate::executor::aggregate::AggregateSpec {
+                        specs.push(AggregateSpec {
                             func: aggregate.func,
                             arg_pos: aggregate.arg.map(attr_pos).transpose()?,
                             arg_type: aggregate
                                 .arg
                                 .map(|attr| table_desc.get_attr(attr).type_desc().clone()),
                             result_type: aggregate.result_type.clone(),
-                        })
+                        });
+                        outputs.push(AggregateOutput::Aggregate(specs.len() - 1));
                     }
-                    BoundSelectItem::Column(_) => Err(mudu_error!(
-                        ER::InvalidState,
-                        "plain column in an aggregate select list"
-                    )),
-                })
+                    BoundSelectItem::Column(column) => {
+                        let index = stmt
+                            .group_by
+                            .iter()
+                            .position(|attr| *attr == column.attr)
+                            .ok_or_else(|| {
+                                mudu_error!(
+                                    ER::InvalidState,
+                                    "ungrouped column in an aggregate select list"
+                                )
+                            })?;
+                        outputs.push(AggregateOutput::Group(index));
+                    }
+                    BoundSelectItem::Expression(_) => {
+                        return Err(mudu_error!(
+                            ER::InvalidState,
+                            "expression in an aggregate select list"
+                        ))
+                    }
+                }
+            }
+            let group_by = stmt
+                .group_by
+                .iter()
+                .map(|attr| attr_pos(*attr))
                 .collect::<RS<Vec<_>>>()?;
-            return Ok(Arc::new(crate::executor::aggregate::AggregateExec::new(
-                stmt.tuple_desc.clone(),
-                child,
-                specs,
-            )));
+            let mut exec = self.instrument(Arc::new(
+                AggregateExec::new(row_desc.clone(), child, specs).with_groups(group_by, outputs),
+            ));
+            if !stmt.having.is_empty() {
+                let mut having = Vec::with_capacity(stmt.having.len());
+                for predicate in &stmt.having {
+                    having.push(ResidualFilter {
+                        input_pos: predicate.pos,
+                        data_type: field_type(&row_desc, predicate.pos)?,
+                        op: predicate.op,
+                        literal: predicate.literal.clone(),
+                    });
+                }
+                exec = self.instrument(Arc::new(FilterExec::new(
+                    row_desc.clone(),
+                    exec,
+                    having,
+                    (0..items.len()).collect(),
+                )));
+            }
+            return self.plan_row_order(exec, row_desc, &stmt.order, stmt.tuple_desc, false, false);
         }
 
-        // Plain column projection: use the scan directly when its row layout
+        // Plain projection: use the scan directly when its row layout
         // already matches the output exactly. Aggregates were handled above,
-        // so every item here is a column.
-        let mut output_attrs: Vec<AttrIndex> = Vec::with_capacity(stmt.select_items.len());
-        for item in &stmt.select_items {
-            let BoundSelectItem::Column(column) = item else {
-                continue;
-            };
-            output_attrs.push(column.attr);
+        // so every item here is a column or an expression; expressions are
+        // computed after the scan and JSON path columns of the filter row.
+        let mut output_attrs: Vec<AttrIndex> = Vec::with_capacity(items.len());
+        let mut projection = Vec::with_capacity(items.len());
+        let mut expressions = Vec::new();
+        for item in &items {
+            match item {
+                BoundSelectItem::Column(column) => {
+                    output_attrs.push(column.attr);
+                    projection.push(attr_pos(column.attr)?);
+                }
+                BoundSelectItem::Expression(expr) => {
+                    projection.push(scan_attrs.len() + json_columns.len() + expressions.len());
+                    expressions.push((
+                        ScalarExpr::resolve(&expr.expr, &attr_pos)?,
+                        expr.expr.data_type(),
+                    ));
+                }
+                BoundSelectItem::Aggregate(_) => {}
+            }
         }
-        let mut direct = filters.is_empty() && output_attrs == scan_attrs;
+        let mut direct = filters.is_empty()
+            && conditions.is_empty()
+            && json_columns.is_empty()
+            && expressions.is_empty()
+            && stmt.hidden_items.is_empty()
+            && output_attrs == scan_attrs;
         if direct {
             for item in &stmt.select_items {
                 let BoundSelectItem::Column(column) = item else {
@@ -191,33 +751,188 @@ impl Planner {
                 }
             }
         }
-        if direct {
-            return Ok(scan);
-        }
-        let projection = output_attrs
+        let exec: Arc<dyn QueryExec> = if direct {
+            scan
+        } else {
+            self.instrument(Arc::new(
+                FilterExec::new(row_desc.clone(), scan, filters, projection)
+                    .with_conditions(conditions)
+                    .with_json_columns(json_columns)
+                    .with_expressions(expressions),
+            ))
+        };
+        self.plan_row_order(
+            exec,
+            row_desc,
+            &stmt.order,
+            stmt.tuple_desc,
+            sorted,
+            limited,
+        )
+    }
+
+    /// Plans a left-deep join: the `FROM` table is the outermost input and
+    /// every joined table is added in statement order. The `FROM` table is
+    /// read like a single-table `SELECT` through its own `WHERE`
+    /// comparisons; the rest of `WHERE` and the projection are applied to
+    /// the final joined row.
+    async fn plan_join(&self, stmt: BoundJoinSelect) -> RS<Arc<dyn QueryExec>> {
+        let first = self.ctx.meta_mgr.get_table_by_id(stmt.table_id).await?;
+        let first_desc = crate::executor::project_tuple_desc(&first, &all_attrs(&first));
+        let mut desc_fields = first_desc.fields().to_vec();
+        let mut types: Vec<DataType> = first
+            .fields()
             .iter()
-            .map(|attr| attr_pos(*attr))
-            .collect::<RS<Vec<_>>>()?;
-        Ok(Arc::new(crate::executor::filter::FilterExec::new(
-            stmt.tuple_desc.clone(),
-            scan,
-            filters,
-            projection,
-        )))
+            .map(|field| field.type_desc().clone())
+            .collect();
+        let mut exec = self.instrument(
+            self.plan_scan(
+                &stmt.predicate,
+                &stmt.residual,
+                &first,
+                all_attrs(&first),
+                None,
+            )
+            .await?,
+        );
+        if !stmt.residual.is_empty() {
+            let filters = stmt
+                .residual
+                .iter()
+                .map(|residual| ResidualFilter {
+                    input_pos: residual.attr,
+                    data_type: first.get_attr(residual.attr).type_desc().clone(),
+                    op: residual.op,
+                    literal: residual.literal.clone(),
+                })
+                .collect();
+            exec = self.instrument(Arc::new(FilterExec::new(
+                first_desc,
+                exec,
+                filters,
+                (0..types.len()).collect(),
+            )));
+        }
+        for join in &stmt.joins {
+            let table_desc = self.ctx.meta_mgr.get_table_by_id(join.table_id).await?;
+            let outer_width = types.len();
+            let inner_width = table_desc.fields().len();
+            for attr in 0..inner_width {
+                let field = table_desc.get_attr(attr);
+                types.push(field.type_desc().clone());
+                desc_fields.push(DatumDesc::new_nullable(
+                    field.name().clone(),
+                    field.type_desc().clone(),
+                    field.nullable() || join.kind == JoinKind::LeftOuter,
+                ));
+            }
+            let tuple_desc = TupleFieldDesc::new(desc_fields.clone());
+            let condition = join_condition(&join.on, &types)?;
+            let joined: Arc<dyn QueryExec> =
+                match choose_join_method(&join.on, outer_width, &table_desc, &types) {
+                    JoinMethod::IndexNestedLoop(key) => Arc::new(IndexNestedLoopJoinExec::new(
+                        tuple_desc,
+                        join.kind,
+                        exec,
+                        IndexLookup {
+                            tx_mgr: self.ctx.tx_mgr.clone(),
+                            table_id: join.table_id,
+                            key,
+                            select: all_attrs(&table_desc),
+                        },
+                        self.ctx.x_contract.clone(),
+                        condition,
+                    )),
+                    JoinMethod::Hash(pairs) => Arc::new(HashJoinExec::new(
+                        tuple_desc,
+                        join.kind,
+                        exec,
+                        self.plan_full_scan(&table_desc).await?,
+                        pairs
+                            .into_iter()
+                            .map(|(outer_pos, inner_pos)| HashJoinKey {
+                                outer_pos,
+                                inner_pos,
+                            })
+                            .collect(),
+                        inner_width,
+                        condition,
+                    )),
+                    JoinMethod::NestedLoop => Arc::new(NestedLoopJoinExec::new(
+                        tuple_desc,
+                        join.kind,
+                        exec,
+                        self.plan_full_scan(&table_desc).await?,
+                        inner_width,
+                        condition,
+                    )),
+                };
+            exec = self.instrument(joined);
+        }
+        let filter = join_condition(&stmt.filter, &types)?;
+        let mut row_fields = stmt.tuple_desc.fields().to_vec();
+        for pos in &stmt.hidden {
+            let field = desc_fields
+                .get(*pos)
+                .ok_or_else(|| mudu_error!(ER::InvalidState, "join column out of row bounds"))?;
+            row_fields.push(field.clone());
+        }
+        let row_desc = TupleFieldDesc::new(row_fields);
+        let mut projection = stmt.projection;
+        projection.extend(stmt.hidden.iter().copied());
+        let exec = self.instrument(Arc::new(
+            FilterExec::new(row_desc.clone(), exec, filter.filters, projection)
+                .with_column_filters(filter.column_filters),
+        ));
+        self.plan_row_order(exec, row_desc, &stmt.order, stmt.tuple_desc, false, false)
+    }
+
+    async fn plan_full_scan(&self, table_desc: &TableDesc) -> RS<Arc<dyn QueryExec>> {
+        let exec = IndexAccessRange::new(
+            PAccessRange {
+                tx_mgr: self.ctx.tx_mgr.clone(),
+                table_id: table_desc.id(),
+                pred_key: RangeData::new(std::ops::Bound::Unbounded, std::ops::Bound::Unbounded),
+                pred_non_key: Predicate::CNF(Vec::new()),
+                select: all_attrs(table_desc),
+                opt_read: OptRead::default(),
+            },
+            self.ctx.x_contract.clone(),
+            self.ctx.meta_mgr.clone(),
+        )
+        .await?;
+        Ok(self.instrument(Arc::new(exec)))
     }
 
-    async fn plan_scan(&self, stmt: &BoundSelect, select: VecSelTerm) -> RS<Arc<dyn QueryExec>> {
-        match &stmt.predicate {
+    /// Plans the access to `table_desc` for a key predicate; `residual`
+    /// only picks a secondary index, the caller still filters by it.
+    async fn plan_scan(
+        &self,
+        predicate: &BoundPredicate,
+        residual: &[BoundResidual],
+        table_desc: &TableDesc,
+        select: VecSelTerm,
+        limit: Option<(u64, Option<u64>)>,
+    ) -> RS<Arc<dyn QueryExec>> {
+        // `OFFSET`/`LIMIT` pushed into a range scan.
+        let limit_range = |exec: IndexAccessRange| match limit {
+            Some((offset, limit)) => exec.with_limit(offset, limit),
+            None => exec,
+        };
+        let table_id = table_desc.id();
+        match predicate {
             BoundPredicate::True => {
-                let exec = crate::executor::index_access_range::IndexAccessRange::new(
+                let pred_non_key = choose_index(residual, table_desc)
+                    .unwrap_or_else(|| Predicate::CNF(Vec::new()));
+                let exec = IndexAccessRange::new(
                     PAccessRange {
                         tx_mgr: self.ctx.tx_mgr.clone(),
-                        table_id: stmt.table_id,
+                        table_id,
                         pred_key: RangeData::new(
                             std::ops::Bound::Unbounded,
                             std::ops::Bound::Unbounded,
                         ),
-                        pred_non_key: Predicate::CNF(Vec::new()),
+                        pred_non_key,
                         select,
                         opt_read: OptRead::default(),
                     },
@@ -225,13 +940,13 @@ impl Planner {
                     self.ctx.meta_mgr.clone(),
                 )
                 .await?;
-                Ok(Arc::new(exec))
+                Ok(Arc::new(limit_range(exec)))
             }
             BoundPredicate::KeyEq { key } => {
                 let exec = crate::executor::index_access_key::IndexAccessKey::new(
                     PAccessKey {
                         tx_mgr: self.ctx.tx_mgr.clone(),
-                        table_id: stmt.table_id,
+                        table_id,
                         pred_key: VecDatum::new(key.clone()),
                         select,
                         opt_read: OptRead::default(),
@@ -242,11 +957,30 @@ impl Planner {
                 .await?;
                 Ok(Arc::new(exec))
             }
+            BoundPredicate::KeyIn { keys } => {
+                // The access parameters need one key even when every key was
+                // dropped; `with_keys` replaces it.
+                let pred_key = keys.first().cloned().unwrap_or_default();
+                let exec = crate::executor::index_access_key::IndexAccessKey::new(
+                    PAccessKey {
+                        tx_mgr: self.ctx.tx_mgr.clone(),
+                        table_id,
+                        pred_key: VecDatum::new(pred_key),
+                        select,
+                        opt_read: OptRead::default(),
+                    },
+                    self.ctx.x_contract.clone(),
+                    self.ctx.meta_mgr.clone(),
+                )
+                .await?
+                .with_keys(keys.iter().cloned().map(VecDatum::new).collect());
+                Ok(Arc::new(exec))
+            }
             BoundPredicate::KeyPrefixEq { prefix } => {
-                let exec = crate::executor::index_access_range::IndexAccessRange::new(
+                let exec = IndexAccessRange::new(
                     PAccessRange {
                         tx_mgr: self.ctx.tx_mgr.clone(),
-                        table_id: stmt.table_id,
+                        table_id,
                         pred_key: RangeData::new(
                             std::ops::Bound::Unbounded,
                             std::ops::Bound::Unbounded,
@@ -259,15 +993,25 @@ impl Planner {
                     self.ctx.meta_mgr.clone(),
                 )
                 .await?;
-                Ok(Arc::new(exec))
+                Ok(Arc::new(limit_range(exec)))
             }
             BoundPredicate::KeyRange { start, end } => {
-                let exec = crate::executor::index_access_range::IndexAccessRange::new(
+                // An index lookup ignores key bounds, so it only replaces a
+                // full scan.
+                let unbounded = matches!(
+                    (start, end),
+                    (std::ops::Bound::Unbounded, std::ops::Bound::Unbounded)
+                );
+                let pred_non_key = unbounded
+                    .then(|| choose_index(residual, table_desc))
+                    .flatten()
+                    .unwrap_or_else(|| Predicate::CNF(Vec::new()));
+                let exec = IndexAccessRange::new(
                     PAccessRange {
                         tx_mgr: self.ctx.tx_mgr.clone(),
-                        table_id: stmt.table_id,
+                        table_id,
                         pred_key: RangeData::new(start.clone(), end.clone()),
-                        pred_non_key: Predicate::CNF(Vec::new()),
+                        pred_non_key,
                         select,
                         opt_read: OptRead::default(),
                     },
@@ -275,7 +1019,7 @@ impl Planner {
                     self.ctx.meta_mgr.clone(),
                 )
                 .await?;
-                Ok(Arc::new(exec))
+                Ok(Arc::new(limit_range(exec)))
             }
         }
     }
@@ -326,6 +1070,30 @@ impl Planner {
         )
     }
 
+    fn plan_alter_partition_rule(&self, stmt: BoundAlterPartitionRule) -> AlterPartitionRule {
+        AlterPartitionRule::new(
+            PAlterPartitionRule {
+                tx_mgr: self.ctx.tx_mgr.clone(),
+                rule: stmt.rule,
+                placements: stmt.placements,
+            },
+            self.ctx.x_contract.clone(),
+            self.ctx.meta_mgr.clone(),
+        )
+    }
+
+    fn plan_alter_table(&self, stmt: BoundAlterTable) -> AlterTable {
+        AlterTable::new(
+            PAlterTable {
+                tx_mgr: self.ctx.tx_mgr.clone(),
+                oid: stmt.oid,
+                actions: stmt.actions,
+            },
+            self.ctx.x_contract.clone(),
+            self.ctx.meta_mgr.clone(),
+        )
+    }
+
     fn plan_create_fs_type(&self, stmt: BoundCreateFsType) -> CreateFsType {
         CreateFsType::new(
             PCreateFsType {
@@ -340,8 +1108,29 @@ impl Planner {
         DropFsType::new(PDropType { name: stmt.name }, self.ctx.meta_mgr.clone())
     }
 
-    fn plan_insert(&self, stmt: BoundInsert) -> InsertKeyValue {
-        InsertKeyValue::new(
+    fn plan_insert(&self, stmt: BoundInsert) -> RS<InsertKeyValue> {
+        let on_conflict = match stmt.on_conflict {
+            None => None,
+            Some(BoundOnConflict::DoNothing) => Some(PConflictAction::DoNothing),
+            Some(BoundOnConflict::DoUpdate(assignments)) => {
+                let mut set_values = Vec::new();
+                let mut excluded = Vec::new();
+                for (attr, value) in assignments {
+                    match value {
+                        BoundConflictValue::Set(set_value) => set_values.push((attr, set_value)),
+                        BoundConflictValue::Excluded(source) => excluded.push((attr, source)),
+                    }
+                }
+                let (value, delta_assignments, expr_assignments) = split_set_values(set_values)?;
+                Some(PConflictAction::DoUpdate {
+                    value,
+                    delta_assignments,
+                    expr_assignments,
+                    excluded,
+                })
+            }
+        };
+        Ok(InsertKeyValue::new(
             PInsertKeyValue {
                 tx_mgr: self.ctx.tx_mgr.clone(),
                 table_id: stmt.table_id,
@@ -350,46 +1139,98 @@ impl Planner {
                     .into_iter()
                     .map(|row| (VecDatum::new(row.key), VecDatum::new(row.value)))
                     .collect(),
+                default_attrs: stmt.default_attrs,
+                on_conflict,
+                returning: stmt.returning.map(plan_returning),
             },
             self.ctx.x_contract.clone(),
             self.ctx.meta_mgr.clone(),
-        )
+        ))
     }
 
-    fn plan_update(&self, stmt: BoundUpdate) -> UpdateKeyValue {
-        let mut absolute = Vec::new();
-        let mut delta_assignments = Vec::new();
-        for (attr, set_value) in stmt.value {
-            match set_value {
-                BoundSetValue::Absolute(binary) => absolute.push((attr, binary)),
-                BoundSetValue::Delta { op, literal } => {
-                    delta_assignments.push(DeltaAssign { attr, op, literal })
-                }
-            }
-        }
-        UpdateKeyValue::new(
-            PUpdateKeyValue {
-                tx_mgr: self.ctx.tx_mgr.clone(),
-                table_id: stmt.table_id,
-                key: VecDatum::new(stmt.key),
-                value: VecDatum::new(absolute),
-                delta_assignments,
-            },
-            self.ctx.x_contract.clone(),
-            self.ctx.meta_mgr.clone(),
-        )
+    async fn plan_update(&self, stmt: BoundUpdate) -> RS<Arc<dyn CmdExec>> {
+        let (value, delta_assignments, expr_assignments) = split_set_values(stmt.value)?;
+        let param = PUpdateKeyValue {
+            tx_mgr: self.ctx.tx_mgr.clone(),
+            table_id: stmt.table_id,
+            key: VecDatum::new(stmt.key),
+            value,
+            delta_assignments,
+            expr_assignments,
+            returning: stmt.returning.map(plan_returning),
+        };
+        let x_contract = self.ctx.x_contract.clone();
+        let meta_mgr = self.ctx.meta_mgr.clone();
+        let Some(scan) = stmt.scan else {
+            return Ok(Arc::new(UpdateKeyValue::new(param, x_contract, meta_mgr)));
+        };
+        let returning = param
+            .returning
+            .as_ref()
+            .map(|returning| returning.tuple_desc.clone());
+        let write_row: WriteRow = Box::new(move |key| -> Arc<dyn CmdExec> {
+            let mut param = param.clone();
+            param.key = key;
+            Arc::new(UpdateKeyValue::new(
+                param,
+                x_contract.clone(),
+                meta_mgr.clone(),
+            ))
+        });
+        self.plan_scanned_write(scan, returning, write_row).await
     }
 
-    fn plan_delete(&self, stmt: BoundDelete) -> DeleteKeyValue {
-        DeleteKeyValue::new(
-            PDeleteKeyValue {
-                tx_mgr: self.ctx.tx_mgr.clone(),
-                table_id: stmt.table_id,
-                key: VecDatum::new(stmt.key),
-            },
-            self.ctx.x_contract.clone(),
-            self.ctx.meta_mgr.clone(),
-        )
+    async fn plan_delete(&self, stmt: BoundDelete) -> RS<Arc<dyn CmdExec>> {
+        let param = PDeleteKeyValue {
+            tx_mgr: self.ctx.tx_mgr.clone(),
+            table_id: stmt.table_id,
+            key: VecDatum::new(stmt.key),
+            returning: stmt.returning.map(plan_returning),
+        };
+        let x_contract = self.ctx.x_contract.clone();
+        let meta_mgr = self.ctx.meta_mgr.clone();
+        let Some(scan) = stmt.scan else {
+            return Ok(Arc::new(DeleteKeyValue::new(param, x_contract, meta_mgr)));
+        };
+        let returning = param
+            .returning
+            .as_ref()
+            .map(|returning| returning.tuple_desc.clone());
+        let write_row: WriteRow = Box::new(move |key| -> Arc<dyn CmdExec> {
+            let mut param = param.clone();
+            param.key = key;
+            Arc::new(DeleteKeyValue::new(
+                param,
+                x_contract.clone(),
+                meta_mgr.clone(),
+            ))
+        });
+        self.plan_scanned_write(scan, returning, write_row).await
+    }
+
+    /// An `UPDATE` or `DELETE` writing, through `write_row`, every row whose
+    /// primary key `scan` returns.
+    async fn plan_scanned_write(
+        &self,
+        scan: BoundSelect,
+        returning: Option<TupleFieldDesc>,
+        write_row: WriteRow,
+    ) -> RS<Arc<dyn CmdExec>> {
+        let key_attrs = scan
+            .select_items
+            .iter()
+            .map(|item| match item {
+                BoundSelectItem::Column(column) => Ok(column.attr),
+                _ => Err(mudu_error!(
+                    ER::InvalidState,
+                    "update/delete scan selects a non-column item"
+                )),
+            })
+            .collect::<RS<Vec<_>>>()?;
+        let scan = self.plan_select(scan).await?;
+        Ok(Arc::new(ScannedWrite::new(
+            scan, key_attrs, returning, write_row,
+        )))
     }
 
     fn plan_copy_from(&self, stmt: BoundCopyFrom) -> LoadFromFile {
@@ -429,16 +1270,19 @@ mod tests {
         clippy::unimplemented
     )]
 
-    use super::Planner;
+    use super::{choose_join_method, scan_matches_order, JoinMethod, Planner};
     use crate::contract::meta_mgr::MetaMgr;
+    use crate::contract::query_exec::QueryExec;
     use crate::contract::schema_column::SchemaColumn;
+    use crate::contract::schema_index::SchemaIndex;
     use crate::contract::schema_table::SchemaTable;
     use crate::contract::table_desc::TableDesc;
     use crate::contract::table_info::TableInfo;
     use crate::server::worker_snapshot::WorkerSnapshot;
     use crate::sql::bound_stmt::{
-        AggregateFunc, BoundAggregate, BoundPredicate, BoundQuery, BoundResidual, BoundSelect,
-        BoundSelectColumn, BoundSelectItem,
+        AggregateFunc, BoundAggregate, BoundExplain, BoundHaving, BoundJoin, BoundJoinCondition,
+        BoundJoinSelect, BoundOrderBy, BoundPredicate, BoundQuery, BoundResidual, BoundRowOrder,
+        BoundSelect, BoundSelectColumn, BoundSelectItem,
     };
     use crate::sql::plan_ctx::PlanCtx;
     use crate::x_engine::api::{
@@ -449,6 +1293,7 @@ mod tests {
     use async_trait::async_trait;
     use mudu::common::id::OID;
     use mudu::common::result::RS;
+    use mudu_contract::tuple::datum_desc::DatumDesc;
     use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
     use mudu_contract::tuple::typed_bin::TypedBin;
     use mudu_sys::sync::SMutex;
@@ -456,7 +1301,7 @@ mod tests {
     use mudu_type::data_type_info::DataTypeInfo;
     use mudu_type::datum::DatumDyn;
     use mudu_type::type_family::TypeFamily;
-    use std::collections::{BTreeMap, HashMap};
+    use std::collections::{BTreeMap, HashMap, VecDeque};
     use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::Arc;
 
@@ -578,18 +1423,23 @@ mod tests {
         }
     }
 
-    struct TestCursor;
+    struct TestCursor {
+        rows: SMutex<VecDeque<TupleRow>>,
+    }
 
     #[async_trait]
     impl RSCursor for TestCursor {
         async fn next(&self) -> RS<Option<TupleRow>> {
-            Ok(None)
+            Ok(self.rows.lock().unwrap().pop_front())
         }
     }
 
     struct TestXContract {
         read_key_calls: AtomicUsize,
         read_range_calls: AtomicUsize,
+        range_predicates: SMutex<Vec<Predicate>>,
+        // Rows returned by every range read.
+        range_rows: SMutex<Vec<TupleRow>>,
     }
 
     impl TestXContract {
@@ -597,6 +1447,8 @@ mod tests {
             Self {
                 read_key_calls: AtomicUsize::new(0),
                 read_range_calls: AtomicUsize::new(0),
+                range_predicates: SMutex::new(Vec::new()),
+                range_rows: SMutex::new(Vec::new()),
             }
         }
     }
@@ -613,7 +1465,7 @@ mod tests {
             &self,
             _tx_mgr: Arc<dyn TxMgr>,
             _oid: OID,
-            _alter_table: &AlterTable,
+            _alter_table: &[AlterTable],
         ) -> RS<()> {
             unimplemented!()
         }
@@ -653,12 +1505,18 @@ mod tests {
             _tx_mgr: Arc<dyn TxMgr>,
             _table_id: OID,
             _pred_key: &RangeData,
-            _pred_non_key: &Predicate,
+            pred_non_key: &Predicate,
             _select: &VecSelTerm,
             _opt_read: &OptRead,
         ) -> RS<Arc<dyn RSCursor>> {
             self.read_range_calls.fetch_add(1, Ordering::Relaxed);
-            Ok(Arc::new(TestCursor))
+            self.range_predicates
+                .lock()
+                .unwrap()
+                .push(pred_non_key.clone());
+            Ok(Arc::new(TestCursor {
+                rows: SMutex::new(self.range_rows.lock().unwrap().iter().cloned().collect()),
+            }))
         }
         async fn delete(
             &self,
@@ -731,6 +1589,12 @@ mod tests {
                         key: vec![(0, vec![1]), (1, vec![2])],
                     },
                     residual: Vec::new(),
+                    conditions: Vec::new(),
+                    group_by: Vec::new(),
+                    hidden_items: Vec::new(),
+                    having: Vec::new(),
+                    order: BoundRowOrder::default(),
+                    json_columns: Vec::new(),
                 }))
                 .await
                 .unwrap();
@@ -743,6 +1607,61 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    fn planner_reads_each_key_of_a_key_list() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let meta_mgr = Arc::new(TestMetaMgr::new(composite_schema()));
+            let x_contract = Arc::new(TestXContract::new());
+            let planner = Planner::new(PlanCtx {
+                tx_mgr: Arc::new(TestTxMgr),
+                meta_mgr: meta_mgr.clone(),
+                x_contract: x_contract.clone(),
+                async_runtime: None,
+            });
+
+            for keys in [
+                vec![
+                    vec![(0, vec![1]), (1, vec![2])],
+                    vec![(0, vec![1]), (1, vec![3])],
+                    vec![(0, vec![4]), (1, vec![5])],
+                ],
+                Vec::new(),
+            ] {
+                let expected = keys.len();
+                let before = x_contract.read_key_calls.load(Ordering::Relaxed);
+                let exec = planner
+                    .plan_query(BoundQuery::Select(BoundSelect {
+                        table_id: meta_mgr.table_id(),
+                        select_items: vec![BoundSelectItem::Column(BoundSelectColumn {
+                            attr: 0,
+                            output_name: "tenant_id".to_string(),
+                        })],
+                        tuple_desc: TupleFieldDesc::new(Vec::new()),
+                        predicate: BoundPredicate::KeyIn { keys },
+                        residual: Vec::new(),
+                        conditions: Vec::new(),
+                        group_by: Vec::new(),
+                        hidden_items: Vec::new(),
+                        having: Vec::new(),
+                        order: BoundRowOrder::default(),
+                        json_columns: Vec::new(),
+                    }))
+                    .await
+                    .unwrap();
+
+                exec.open().await.unwrap();
+                // No key has a row, so every key is read.
+                assert!(exec.next().await.unwrap().is_none());
+                assert_eq!(
+                    x_contract.read_key_calls.load(Ordering::Relaxed) - before,
+                    expected
+                );
+            }
+            assert_eq!(x_contract.read_range_calls.load(Ordering::Relaxed), 0);
+        })
+        .unwrap()
+    }
+
     #[test]
     fn planner_uses_read_range_for_primary_key_prefix_equality() {
         mudu_sys::task::async_::block_on_tokio_current_thread(async move {
@@ -767,6 +1686,12 @@ mod tests {
                         prefix: vec![(0, vec![1])],
                     },
                     residual: Vec::new(),
+                    conditions: Vec::new(),
+                    group_by: Vec::new(),
+                    hidden_items: Vec::new(),
+                    having: Vec::new(),
+                    order: BoundRowOrder::default(),
+                    json_columns: Vec::new(),
                 }))
                 .await
                 .unwrap();
@@ -803,6 +1728,12 @@ mod tests {
                     tuple_desc: TupleFieldDesc::new(Vec::new()),
                     predicate: BoundPredicate::True,
                     residual: Vec::new(),
+                    conditions: Vec::new(),
+                    group_by: Vec::new(),
+                    hidden_items: Vec::new(),
+                    having: Vec::new(),
+                    order: BoundRowOrder::default(),
+                    json_columns: Vec::new(),
                 }))
                 .await
                 .unwrap();
@@ -852,6 +1783,12 @@ mod tests {
                         op: sql_parser::ast::expr_operator::ValueCompare::LT,
                         literal: Some(literal),
                     }],
+                    conditions: Vec::new(),
+                    group_by: Vec::new(),
+                    hidden_items: Vec::new(),
+                    having: Vec::new(),
+                    order: BoundRowOrder::default(),
+                    json_columns: Vec::new(),
                 }))
                 .await
                 .unwrap();
@@ -863,4 +1800,591 @@ mod tests {
         })
         .unwrap()
     }
+
+    #[test]
+    fn planner_uses_secondary_index_for_equality_residual() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let schema = composite_schema();
+            let name_oid = schema.columns()[2].get_oid();
+            let schema = schema
+                .altered(&AlterTable::AddIndex(SchemaIndex::new(
+                    "accounts_name".to_string(),
+                    vec![name_oid],
+                )))
+                .unwrap();
+            let index_oid = schema.indexes()[0].id();
+            let meta_mgr = Arc::new(TestMetaMgr::new(schema));
+            let x_contract = Arc::new(TestXContract::new());
+            let planner = Planner::new(PlanCtx {
+                tx_mgr: Arc::new(TestTxMgr),
+                meta_mgr: meta_mgr.clone(),
+                x_contract: x_contract.clone(),
+                async_runtime: None,
+            });
+
+            let literal: Vec<u8> = mudu_type::data_value::DataValue::from_string("m".to_string())
+                .to_binary(&DataType::default_for(TypeFamily::String))
+                .unwrap()
+                .into();
+            for (op, uses_index) in [
+                (sql_parser::ast::expr_operator::ValueCompare::EQ, true),
+                (sql_parser::ast::expr_operator::ValueCompare::LT, false),
+            ] {
+                let exec = planner
+                    .plan_query(BoundQuery::Select(BoundSelect {
+                        table_id: meta_mgr.table_id(),
+                        select_items: vec![BoundSelectItem::Column(BoundSelectColumn {
+                            attr: 0,
+                            output_name: "tenant_id".to_string(),
+                        })],
+                        tuple_desc: TupleFieldDesc::new(Vec::new()),
+                        predicate: BoundPredicate::KeyRange {
+                            start: std::ops::Bound::Unbounded,
+                            end: std::ops::Bound::Unbounded,
+                        },
+                        residual: vec![BoundResidual {
+                            attr: 2,
+                            op,
+                            literal: Some(literal.clone()),
+                        }],
+                        conditions: Vec::new(),
+                        group_by: Vec::new(),
+                        hidden_items: Vec::new(),
+                        having: Vec::new(),
+                        order: BoundRowOrder::default(),
+                        json_columns: Vec::new(),
+                    }))
+                    .await
+                    .unwrap();
+                exec.open().await.unwrap();
+                assert!(exec.next().await.unwrap().is_none());
+
+                let predicate = x_contract.range_predicates.lock().unwrap().pop().unwrap();
+                match predicate {
+                    Predicate::IndexEq {
+                        index_oid: oid,
+                        prefix,
+                    } => {
+                        assert!(uses_index);
+                        assert_eq!(oid, index_oid);
+                        assert_eq!(prefix, vec![(2, literal.clone())]);
+                    }
+                    _ => assert!(!uses_index),
+                }
+            }
+        })
+        .unwrap()
+    }
+
+    fn column_item(attr: usize, name: &str) -> BoundSelectItem {
+        BoundSelectItem::Column(BoundSelectColumn {
+            attr,
+            output_name: name.to_string(),
+        })
+    }
+
+    fn order_key(pos: usize, descending: bool) -> BoundOrderBy {
+        BoundOrderBy {
+            pos,
+            descending,
+            nulls_first: descending,
+        }
+    }
+
+    fn key_order_select(predicate: BoundPredicate, order_by: Vec<BoundOrderBy>) -> BoundSelect {
+        BoundSelect {
+            table_id: 0,
+            select_items: vec![column_item(0, "tenant_id"), column_item(1, "user_id")],
+            tuple_desc: TupleFieldDesc::new(Vec::new()),
+            predicate,
+            residual: Vec::new(),
+            conditions: Vec::new(),
+            group_by: Vec::new(),
+            hidden_items: Vec::new(),
+            having: Vec::new(),
+            order: BoundRowOrder {
+                order_by,
+                offset: 0,
+                limit: None,
+            },
+            json_columns: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn planner_matches_order_by_to_primary_key_order() {
+        let table_desc = TableInfo::new(composite_schema())
+            .unwrap()
+            .table_desc()
+            .unwrap();
+        let cases = [
+            (BoundPredicate::True, vec![order_key(0, false)], true),
+            (
+                BoundPredicate::True,
+                vec![order_key(0, false), order_key(1, false)],
+                true,
+            ),
+            (BoundPredicate::True, vec![order_key(1, false)], false),
+            (BoundPredicate::True, vec![order_key(0, true)], false),
+            (
+                BoundPredicate::KeyPrefixEq {
+                    prefix: vec![(0, vec![1])],
+                },
+                vec![order_key(1, false), order_key(0, true)],
+                true,
+            ),
+            (
+                BoundPredicate::KeyIn {
+                    keys: vec![vec![(0, vec![1]), (1, vec![2])]],
+                },
+                vec![order_key(0, false)],
+                false,
+            ),
+        ];
+        for (predicate, order_by, expected) in cases {
+            let stmt = key_order_select(predicate, order_by);
+            assert_eq!(scan_matches_order(&stmt, &table_desc), expected);
+        }
+    }
+
+    fn i32_column(name: &str) -> SchemaColumn {
+        SchemaColumn::new(
+            name.to_string(),
+            TypeFamily::I32,
+            DataTypeInfo::from_opt_object(&DataType::default_for(TypeFamily::I32)),
+        )
+    }
+
+    fn orders_schema() -> SchemaTable {
+        SchemaTable::new(
+            "orders".to_string(),
+            vec![
+                i32_column("order_id"),
+                i32_column("tenant_id"),
+                i32_column("user_id"),
+            ],
+            vec![0],
+            vec![1, 2],
+        )
+    }
+
+    // `orders` (3 columns) joined with `accounts` (3 columns): positions
+    // 0..3 are orders columns and 3..6 accounts columns.
+    fn join_types() -> Vec<DataType> {
+        let mut types = vec![DataType::default_for(TypeFamily::I32); 5];
+        types.push(DataType::default_for(TypeFamily::String));
+        types
+    }
+
+    fn column_eq(left: usize, right: usize) -> BoundJoinCondition {
+        BoundJoinCondition::Column {
+            left,
+            op: sql_parser::ast::expr_operator::ValueCompare::EQ,
+            right,
+        }
+    }
+
+    #[test]
+    fn planner_chooses_join_method_from_equalities() {
+        let accounts = TableInfo::new(composite_schema())
+            .unwrap()
+            .table_desc()
+            .unwrap();
+        let types = join_types();
+
+        let full_key = [column_eq(3, 1), column_eq(2, 4)];
+        assert_eq!(
+            choose_join_method(&full_key, 3, &accounts, &types),
+            JoinMethod::IndexNestedLoop(vec![(0, 1), (1, 2)])
+        );
+
+        let partial_key = [column_eq(1, 3)];
+        assert_eq!(
+            choose_join_method(&partial_key, 3, &accounts, &types),
+            JoinMethod::Hash(vec![(1, 0)])
+        );
+
+        // Mismatched types and inequalities are not join keys.
+        let no_key = [
+            column_eq(1, 5),
+            BoundJoinCondition::Column {
+                left: 1,
+                op: sql_parser::ast::expr_operator::ValueCompare::LT,
+                right: 3,
+            },
+        ];
+        assert_eq!(
+            choose_join_method(&no_key, 3, &accounts, &types),
+            JoinMethod::NestedLoop
+        );
+    }
+
+    #[test]
+    fn planner_joins_by_primary_key_lookups() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let meta_mgr = Arc::new(TestMetaMgr::new(composite_schema()));
+            meta_mgr.create_table(&orders_schema()).await.unwrap();
+            let orders = meta_mgr.get_table_by_name("orders").await.unwrap().unwrap();
+            let accounts = meta_mgr
+                .get_table_by_name("accounts")
+                .await
+                .unwrap()
+                .unwrap();
+            let x_contract = Arc::new(TestXContract::new());
+            let i32_bin = |value: i32| -> Option<Vec<u8>> {
+                Some(
+                    mudu_type::data_value::DataValue::from_i32(value)
+                        .to_binary(&DataType::default_for(TypeFamily::I32))
+                        .unwrap()
+                        .into(),
+                )
+            };
+            *x_contract.range_rows.lock().unwrap() = vec![
+                TupleRow::new_nullable(vec![i32_bin(1), i32_bin(7), i32_bin(8)]),
+                TupleRow::new_nullable(vec![i32_bin(2), i32_bin(7), None]),
+            ];
+            let planner = Planner::new(PlanCtx {
+                tx_mgr: Arc::new(TestTxMgr),
+                meta_mgr: meta_mgr.clone(),
+                x_contract: x_contract.clone(),
+                async_runtime: None,
+            });
+
+            let exec = planner
+                .plan_query(BoundQuery::Join(BoundJoinSelect {
+                    table_id: orders.id(),
+                    joins: vec![BoundJoin {
+                        kind: sql_parser::ast::select_join::JoinKind::LeftOuter,
+                        table_id: accounts.id(),
+                        on: vec![column_eq(3, 1), column_eq(4, 2)],
+                    }],
+                    projection: vec![0, 5],
+                    tuple_desc: TupleFieldDesc::new(Vec::new()),
+                    predicate: BoundPredicate::True,
+                    residual: Vec::new(),
+                    filter: Vec::new(),
+                    hidden: Vec::new(),
+                    order: BoundRowOrder::default(),
+                }))
+                .await
+                .unwrap();
+
+            exec.open().await.unwrap();
+            // No account is visible, so both orders are NULL-extended.
+            for order_id in [1, 2] {
+                let row = exec.next().await.unwrap().unwrap();
+                assert_eq!(row.fields(), &vec![i32_bin(order_id), None]);
+            }
+            assert!(exec.next().await.unwrap().is_none());
+            assert_eq!(x_contract.read_range_calls.load(Ordering::Relaxed), 1);
+            // The order with a NULL user_id is not looked up.
+            assert_eq!(x_contract.read_key_calls.load(Ordering::Relaxed), 1);
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn planner_drives_join_by_key_lookup() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let meta_mgr = Arc::new(TestMetaMgr::new(composite_schema()));
+            meta_mgr.create_table(&orders_schema()).await.unwrap();
+            let orders = meta_mgr.get_table_by_name("orders").await.unwrap().unwrap();
+            let accounts = meta_mgr
+                .get_table_by_name("accounts")
+                .await
+                .unwrap()
+                .unwrap();
+            let x_contract = Arc::new(TestXContract::new());
+            let planner = Planner::new(PlanCtx {
+                tx_mgr: Arc::new(TestTxMgr),
+                meta_mgr: meta_mgr.clone(),
+                x_contract: x_contract.clone(),
+                async_runtime: None,
+            });
+
+            let exec = planner
+                .plan_query(BoundQuery::Join(BoundJoinSelect {
+                    table_id: orders.id(),
+                    joins: vec![BoundJoin {
+                        kind: sql_parser::ast::select_join::JoinKind::Inner,
+                        table_id: accounts.id(),
+                        on: vec![column_eq(3, 1), column_eq(4, 2)],
+                    }],
+                    projection: vec![0, 5],
+                    tuple_desc: TupleFieldDesc::new(Vec::new()),
+                    predicate: BoundPredicate::KeyEq {
+                        key: vec![(0, i32_bin(1).unwrap())],
+                    },
+                    residual: Vec::new(),
+                    filter: Vec::new(),
+                    hidden: Vec::new(),
+                    order: BoundRowOrder::default(),
+                }))
+                .await
+                .unwrap();
+
+            exec.open().await.unwrap();
+            assert!(exec.next().await.unwrap().is_none());
+            // `orders` is read by its key; no range scan runs.
+            assert_eq!(x_contract.read_key_calls.load(Ordering::Relaxed), 1);
+            assert_eq!(x_contract.read_range_calls.load(Ordering::Relaxed), 0);
+        })
+        .unwrap()
+    }
+
+    fn i32_bin(value: i32) -> Option<Vec<u8>> {
+        Some(
+            mudu_type::data_value::DataValue::from_i32(value)
+                .to_binary(&DataType::default_for(TypeFamily::I32))
+                .unwrap()
+                .into(),
+        )
+    }
+
+    fn i32_desc(names: &[&str]) -> TupleFieldDesc {
+        TupleFieldDesc::new(
+            names
+                .iter()
+                .map(|name| {
+                    DatumDesc::new(name.to_string(), DataType::default_for(TypeFamily::I32))
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn planner_sorts_and_limits_rows() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let meta_mgr = Arc::new(TestMetaMgr::new(composite_schema()));
+            let x_contract = Arc::new(TestXContract::new());
+            *x_contract.range_rows.lock().unwrap() = [1, 3, 2]
+                .into_iter()
+                .map(|user_id| TupleRow::new_nullable(vec![i32_bin(1), i32_bin(user_id)]))
+                .collect();
+            let planner = Planner::new(PlanCtx {
+                tx_mgr: Arc::new(TestTxMgr),
+                meta_mgr: meta_mgr.clone(),
+                x_contract: x_contract.clone(),
+                async_runtime: None,
+            });
+
+            // SELECT tenant_id FROM accounts ORDER BY user_id DESC LIMIT 2
+            let exec = planner
+                .plan_query(BoundQuery::Select(BoundSelect {
+                    table_id: meta_mgr.table_id(),
+                    select_items: vec![column_item(0, "tenant_id")],
+                    tuple_desc: i32_desc(&["tenant_id"]),
+                    predicate: BoundPredicate::True,
+                    residual: Vec::new(),
+                    conditions: Vec::new(),
+                    group_by: Vec::new(),
+                    hidden_items: vec![column_item(1, "user_id")],
+                    having: Vec::new(),
+                    order: BoundRowOrder {
+                        order_by: vec![order_key(1, true)],
+                        offset: 0,
+                        limit: Some(2),
+                    },
+                    json_columns: Vec::new(),
+                }))
+                .await
+                .unwrap();
+
+            exec.open().await.unwrap();
+            // The hidden sort column is dropped from the output.
+            for _ in 0..2 {
+                let row = exec.next().await.unwrap().unwrap();
+                assert_eq!(row.fields(), &vec![i32_bin(1)]);
+            }
+            assert!(exec.next().await.unwrap().is_none());
+        })
+        .unwrap()
+    }
+
+    fn sort_limit_select(table_id: OID) -> BoundSelect {
+        // SELECT tenant_id FROM accounts ORDER BY user_id DESC LIMIT 2
+        BoundSelect {
+            table_id,
+            select_items: vec![column_item(0, "tenant_id")],
+            tuple_desc: i32_desc(&["tenant_id"]),
+            predicate: BoundPredicate::True,
+            residual: Vec::new(),
+            conditions: Vec::new(),
+            group_by: Vec::new(),
+            hidden_items: vec![column_item(1, "user_id")],
+            having: Vec::new(),
+            order: BoundRowOrder {
+                order_by: vec![order_key(1, true)],
+                offset: 0,
+                limit: Some(2),
+            },
+            json_columns: Vec::new(),
+        }
+    }
+
+    async fn explain_lines(exec: Arc<dyn QueryExec>) -> Vec<String> {
+        let string_type = DataType::default_for(TypeFamily::String);
+        exec.open().await.unwrap();
+        let mut lines = Vec::new();
+        while let Some(row) = exec.next().await.unwrap() {
+            let value = TypedBin::new(TypeFamily::String, row.fields()[0].clone().unwrap())
+                .to_value(&string_type)
+                .unwrap();
+            lines.push(value.expect_string().clone());
+        }
+        lines
+    }
+
+    #[test]
+    fn planner_explains_key_lookup_without_reading() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let meta_mgr = Arc::new(TestMetaMgr::new(composite_schema()));
+            let x_contract = Arc::new(TestXContract::new());
+            let planner = Planner::new(PlanCtx {
+                tx_mgr: Arc::new(TestTxMgr),
+                meta_mgr: meta_mgr.clone(),
+                x_contract: x_contract.clone(),
+                async_runtime: None,
+            });
+
+            let mut select = key_order_select(
+                BoundPredicate::KeyEq {
+                    key: vec![(0, vec![1]), (1, vec![2])],
+                },
+                Vec::new(),
+            );
+            select.table_id = meta_mgr.table_id();
+            let exec = planner
+                .plan_query(BoundQuery::Explain(BoundExplain {
+                    analyze: false,
+                    query: Box::new(BoundQuery::Select(select)),
+                }))
+                .await
+                .unwrap();
+
+            assert_eq!(
+                explain_lines(exec).await,
+                vec!["IndexAccessKey (table=accounts keys=1)".to_string()]
+            );
+            assert_eq!(x_contract.read_key_calls.load(Ordering::Relaxed), 0);
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn planner_explain_analyze_counts_rows_per_operator() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let meta_mgr = Arc::new(TestMetaMgr::new(composite_schema()));
+            let x_contract = Arc::new(TestXContract::new());
+            *x_contract.range_rows.lock().unwrap() = [1, 3, 2]
+                .into_iter()
+                .map(|user_id| TupleRow::new_nullable(vec![i32_bin(1), i32_bin(user_id)]))
+                .collect();
+            let planner = Planner::new(PlanCtx {
+                tx_mgr: Arc::new(TestTxMgr),
+                meta_mgr: meta_mgr.clone(),
+                x_contract: x_contract.clone(),
+                async_runtime: None,
+            });
+
+            let exec = planner
+                .plan_query(BoundQuery::Explain(BoundExplain {
+                    analyze: true,
+                    query: Box::new(BoundQuery::Select(sort_limit_select(meta_mgr.table_id()))),
+                }))
+                .await
+                .unwrap();
+
+            let lines = explain_lines(exec).await;
+            let expected = [
+                "Filter (columns=1) [rows=2 ",
+                "  -> Limit (offset=0 limit=2) [rows=2 ",
+                "    -> Sort (keys=user_id desc) [rows=2 ",
+                "      -> Filter (columns=2) [rows=3 ",
+                "        -> IndexAccessRange (table=accounts full scan) [rows=3 ",
+            ];
+            assert_eq!(lines.len(), expected.len(), "{lines:?}");
+            for (line, prefix) in lines.iter().zip(expected) {
+                assert!(line.starts_with(prefix), "{line}");
+            }
+            assert_eq!(x_contract.read_range_calls.load(Ordering::Relaxed), 1);
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn planner_groups_and_filters_with_having() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let meta_mgr = Arc::new(TestMetaMgr::new(composite_schema()));
+            let x_contract = Arc::new(TestXContract::new());
+            *x_contract.range_rows.lock().unwrap() = [3, 1, 2, 3, 1, 3]
+                .into_iter()
+                .map(|tenant_id| TupleRow::new_nullable(vec![i32_bin(tenant_id)]))
+                .collect();
+            let planner = Planner::new(PlanCtx {
+                tx_mgr: Arc::new(TestTxMgr),
+                meta_mgr: meta_mgr.clone(),
+                x_contract: x_contract.clone(),
+                async_runtime: None,
+            });
+            let i64_type = DataType::default_for(TypeFamily::I64);
+            let one: Vec<u8> = mudu_type::data_value::DataValue::from_i64(1)
+                .to_binary(&i64_type)
+                .unwrap()
+                .into();
+
+            // SELECT tenant_id, COUNT(*) FROM accounts GROUP BY tenant_id
+            // HAVING COUNT(*) > 1 ORDER BY tenant_id
+            let exec = planner
+                .plan_query(BoundQuery::Select(BoundSelect {
+                    table_id: meta_mgr.table_id(),
+                    select_items: vec![
+                        column_item(0, "tenant_id"),
+                        BoundSelectItem::Aggregate(BoundAggregate {
+                            func: AggregateFunc::Count,
+                            arg: None,
+                            result_type: i64_type.clone(),
+                            output_name: "count".to_string(),
+                            nullable: false,
+                        }),
+                    ],
+                    tuple_desc: TupleFieldDesc::new(vec![
+                        DatumDesc::new(
+                            "tenant_id".to_string(),
+                            DataType::default_for(TypeFamily::I32),
+                        ),
+                        DatumDesc::new("count".to_string(), i64_type.clone()),
+                    ]),
+                    predicate: BoundPredicate::True,
+                    residual: Vec::new(),
+                    group_by: vec![0],
+                    hidden_items: Vec::new(),
+                    having: vec![BoundHaving {
+                        pos: 1,
+                        op: sql_parser::ast::expr_operator::ValueCompare::GT,
+                        literal: Some(one),
+                    }],
+                    order: BoundRowOrder {
+                        order_by: vec![order_key(0, false)],
+                        offset: 0,
+                        limit: None,
+                    },
+                    json_columns: Vec::new(),
+                }))
+                .await
+                .unwrap();
+
+            exec.open().await.unwrap();
+            for (tenant_id, count) in [(1, 2), (3, 3)] {
+                let row = exec.next().await.unwrap().unwrap();
+                assert_eq!(row.fields()[0], i32_bin(tenant_id));
+                let value = TypedBin::new(TypeFamily::I64, row.fields()[1].clone().unwrap())
+                    .to_value(&i64_type)
+                    .unwrap();
+                assert_eq!(value.to_i64(), count);
+            }
+            assert!(exec.next().await.unwrap().is_none());
+        })
+        .unwrap()
+    }
 }
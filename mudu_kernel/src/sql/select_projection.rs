@@ -1,10 +1,17 @@
-//! Shared binding logic for select-list items (columns and aggregates).
+//! Shared binding logic for select-list items (columns, aggregates and
+//! scalar expressions) and the `GROUP BY`, `HAVING` and `ORDER BY` clauses
+//! that refer to them.
 //!
 //! Used by both the query binder and the statement describer so that the
 //! output tuple description is computed identically on both paths.
 
 use crate::contract::table_desc::TableDesc;
-use crate::sql::bound_stmt::{AggregateFunc, BoundAggregate, BoundSelectColumn, BoundSelectItem};
+use crate::executor::json_path::json_path_type;
+use crate::sql::bound_stmt::{
+    AggregateFunc, BoundAggregate, BoundJsonColumn, BoundOrderBy, BoundReturning,
+    BoundSelectColumn, BoundSelectExpr, BoundSelectItem,
+};
+use crate::sql::expr_binding::{without_params, ExprBinder};
 use mudu::common::id::AttrIndex;
 use mudu::common::result::RS;
 use mudu::error::ErrorCode as ER;
@@ -14,73 +21,414 @@ use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
 use mudu_type::data_type_fn_param::DataType;
 use mudu_type::data_type_param_numeric::DataTypeParamNumeric;
 use mudu_type::type_family::TypeFamily;
-use sql_parser::ast::expr_function::FunctionArg;
+use sql_parser::ast::expr_function::{ExprFunction, FunctionArg};
+use sql_parser::ast::expr_item::ExprValue;
+use sql_parser::ast::expr_name::ExprName;
+use sql_parser::ast::expr_operator::ValueCompare;
+use sql_parser::ast::expression::ExprType;
+use sql_parser::ast::select_clause::{OrderByItem, OrderTarget};
 use sql_parser::ast::select_term::{SelectField, SelectTerm};
+use sql_parser::ast::stmt_select::StmtSelect;
 
 /// Fractional digits added to AVG results over exact numeric inputs.
 const AVG_RESULT_SCALE: u8 = 6;
 
-/// Bind the select-list terms of a query against a table, producing the
-/// projection items and the output tuple description.
+/// A select list bound together with its grouping and ordering clauses.
+pub(crate) struct BoundSelectList {
+    pub items: Vec<BoundSelectItem>,
+    pub tuple_desc: TupleFieldDesc,
+    pub group_by: Vec<AttrIndex>,
+    /// Items only referenced by `HAVING` or `ORDER BY`, after `items`.
+    pub hidden_items: Vec<BoundSelectItem>,
+    /// `HAVING` comparisons as (item position, operator, value, item type);
+    /// the caller binds the values so placeholders follow `WHERE`.
+    pub having: Vec<(usize, ValueCompare, ExprValue, DataType)>,
+    pub order_by: Vec<BoundOrderBy>,
+    /// JSON path columns referenced so far; `WHERE` may add more.
+    pub json_columns: JsonColumns,
+}
+
+/// The JSON path columns of a query, registered on first use. The `i`-th
+/// one is addressed as attribute `table columns + i`.
+#[derive(Default)]
+pub(crate) struct JsonColumns {
+    columns: Vec<BoundJsonColumn>,
+}
+
+impl JsonColumns {
+    /// Attribute of `name`: a table column, or a JSON path column when the
+    /// name carries a `->`/`->>` path.
+    pub fn attr(&mut self, table_desc: &TableDesc, name: &ExprName) -> RS<AttrIndex> {
+        let attr = attr_index_by_name(table_desc, name.name())?;
+        let Some(path) = name.json_path() else {
+            return Ok(attr);
+        };
+        if table_desc.get_attr(attr).type_desc().type_family() != TypeFamily::Json {
+            return Err(mudu_error!(
+                ER::InvalidArgument,
+                format!("column {} is not a JSON column", name.name())
+            ));
+        }
+        let width = table_desc.fields().len();
+        if let Some(index) = self
+            .columns
+            .iter()
+            .position(|column| column.attr == attr && &column.path == path)
+        {
+            return Ok(width + index);
+        }
+        self.columns.push(BoundJsonColumn {
+            attr,
+            path: path.clone(),
+        });
+        Ok(width + self.columns.len() - 1)
+    }
+
+    pub fn columns(&self) -> &[BoundJsonColumn] {
+        &self.columns
+    }
+
+    pub fn into_columns(self) -> Vec<BoundJsonColumn> {
+        self.columns
+    }
+}
+
+/// Type of `attr`, which may be a JSON path column: JSON for `->`, text
+/// for `->>`.
+pub(crate) fn attr_type(
+    table_desc: &TableDesc,
+    json_columns: &[BoundJsonColumn],
+    attr: AttrIndex,
+) -> DataType {
+    match attr.checked_sub(table_desc.fields().len()) {
+        Some(index) => json_path_type(&json_columns[index].path),
+        None => table_desc.get_attr(attr).type_desc().clone(),
+    }
+}
+
+// Name and nullability of `attr`; a JSON path column is named after its
+// expression and is NULL wherever the path is missing.
+fn attr_name(
+    table_desc: &TableDesc,
+    json_columns: &[BoundJsonColumn],
+    attr: AttrIndex,
+) -> (String, bool) {
+    match attr.checked_sub(table_desc.fields().len()) {
+        Some(index) => {
+            let column = &json_columns[index];
+            let name = table_desc.get_attr(column.attr).name();
+            (format!("{}{}", name, column.path), true)
+        }
+        None => {
+            let field = table_desc.get_attr(attr);
+            (field.name().clone(), field.nullable())
+        }
+    }
+}
+
+/// Bind the select list, `GROUP BY`, `HAVING` and `ORDER BY` of a query
+/// against a table.
 ///
+/// A query with `GROUP BY`, `HAVING` or an aggregate is grouped: its plain
+/// columns must be `GROUP BY` columns and it has no scalar expressions.
 /// Without `GROUP BY` a select list is either all plain columns or all
-/// aggregates; mixing the two is rejected.
-pub(crate) fn bind_select_items(
-    table_desc: &TableDesc,
-    terms: &[SelectTerm],
-) -> RS<(Vec<BoundSelectItem>, TupleFieldDesc)> {
-    let has_aggregate = terms
-        .iter()
-        .any(|term| matches!(term.field(), SelectField::Function(_)));
+/// aggregates. `HAVING` and `ORDER BY` refer
+/// to select-list items where they match one (`ORDER BY` also by output name
+/// or position) and otherwise add hidden items.
+pub(crate) fn bind_select_list(table_desc: &TableDesc, stmt: &StmtSelect) -> RS<BoundSelectList> {
+    let mut group_by = Vec::with_capacity(stmt.get_group_by().len());
+    for name in stmt.get_group_by() {
+        if name.json_path().is_some() {
+            return Err(mudu_error!(
+                ER::NotImplemented,
+                "JSON paths in GROUP BY are not implemented"
+            ));
+        }
+        let attr = attr_index_by_name(table_desc, name.name())?;
+        if !group_by.contains(&attr) {
+            group_by.push(attr);
+        }
+    }
+    let terms = stmt.get_select_term_list();
+    let has_aggregate = terms.iter().any(
+        |term| matches!(term.field(), SelectField::Function(function) if is_aggregate(function)),
+    );
     let has_column = terms
         .iter()
         .any(|term| matches!(term.field(), SelectField::Column(_)));
-    if has_aggregate && has_column {
+    if group_by.is_empty() && has_aggregate && has_column {
         return Err(mudu_error!(
             ER::NotImplemented,
             "mixing plain columns and aggregates without GROUP BY is not implemented"
         ));
     }
+    let grouped = !group_by.is_empty() || has_aggregate || !stmt.get_having().is_empty();
+    let mut json_columns = JsonColumns::default();
+    let (items, tuple_desc) = bind_select_items(table_desc, terms, &mut json_columns)?;
+    let mut list = BoundSelectList {
+        items,
+        tuple_desc,
+        group_by,
+        hidden_items: Vec::new(),
+        having: Vec::new(),
+        order_by: Vec::new(),
+        json_columns,
+    };
+    if grouped {
+        for item in &list.items {
+            match item {
+                BoundSelectItem::Column(column) => list.check_grouped(table_desc, column.attr)?,
+                BoundSelectItem::Aggregate(_) => {}
+                BoundSelectItem::Expression(_) => {
+                    return Err(mudu_error!(
+                        ER::NotImplemented,
+                        "expressions in aggregate queries are not implemented"
+                    ))
+                }
+            }
+        }
+    }
+    for predicate in stmt.get_having() {
+        let pos = list.resolve_field(table_desc, predicate.field(), grouped)?;
+        let data_type = list.item_type(table_desc, pos)?;
+        list.having
+            .push((pos, predicate.op(), predicate.value().clone(), data_type));
+    }
+    for item in stmt.get_order_by() {
+        let pos = list.resolve_order_target(table_desc, item, grouped)?;
+        list.order_by.push(BoundOrderBy {
+            pos,
+            descending: item.descending(),
+            nulls_first: item.nulls_first(),
+        });
+    }
+    Ok(list)
+}
+
+impl BoundSelectList {
+    fn check_grouped(&self, table_desc: &TableDesc, attr: AttrIndex) -> RS<()> {
+        if self.group_by.contains(&attr) {
+            return Ok(());
+        }
+        if attr >= table_desc.fields().len() {
+            return Err(mudu_error!(
+                ER::NotImplemented,
+                "JSON paths in aggregate queries are not implemented"
+            ));
+        }
+        Err(mudu_error!(
+            ER::Parse,
+            format!(
+                "column {} must appear in the GROUP BY clause or be used in an aggregate function",
+                table_desc.get_attr(attr).name()
+            )
+        ))
+    }
+
+    fn all_items(&self) -> impl Iterator<Item = &BoundSelectItem> {
+        self.items.iter().chain(self.hidden_items.iter())
+    }
 
+    fn item_type(&self, table_desc: &TableDesc, pos: usize) -> RS<DataType> {
+        match self.all_items().nth(pos) {
+            Some(BoundSelectItem::Column(column)) => Ok(attr_type(
+                table_desc,
+                self.json_columns.columns(),
+                column.attr,
+            )),
+            Some(BoundSelectItem::Aggregate(aggregate)) => Ok(aggregate.result_type.clone()),
+            Some(BoundSelectItem::Expression(expr)) => Ok(expr.expr.data_type()),
+            None => Err(mudu_error!(ER::IndexOutOfRange)),
+        }
+    }
+
+    // Position of the item `field` computes, adding a hidden item when no
+    // item does yet.
+    fn resolve_field(
+        &mut self,
+        table_desc: &TableDesc,
+        field: &SelectField,
+        grouped: bool,
+    ) -> RS<usize> {
+        let item = match field {
+            SelectField::Column(name) => {
+                let attr = self.json_columns.attr(table_desc, name)?;
+                if grouped {
+                    self.check_grouped(table_desc, attr)?;
+                }
+                if let Some(pos) = self.all_items().position(
+                    |item| matches!(item, BoundSelectItem::Column(column) if column.attr == attr),
+                ) {
+                    return Ok(pos);
+                }
+                BoundSelectItem::Column(BoundSelectColumn {
+                    attr,
+                    output_name: attr_name(table_desc, self.json_columns.columns(), attr).0,
+                })
+            }
+            SelectField::Function(function) if is_aggregate(function) => {
+                if !grouped {
+                    return Err(mudu_error!(
+                        ER::NotImplemented,
+                        format!(
+                            "aggregate {} outside an aggregate query is not implemented",
+                            function.name()
+                        )
+                    ));
+                }
+                let aggregate = bind_aggregate(table_desc, function, "")?;
+                if let Some(pos) = self.all_items().position(|item| {
+                    matches!(item, BoundSelectItem::Aggregate(other)
+                        if other.func == aggregate.func && other.arg == aggregate.arg)
+                }) {
+                    return Ok(pos);
+                }
+                BoundSelectItem::Aggregate(aggregate)
+            }
+            SelectField::Function(_) | SelectField::Expression(_) => {
+                return Err(mudu_error!(
+                    ER::NotImplemented,
+                    "expressions in HAVING and ORDER BY are not implemented"
+                ))
+            }
+        };
+        self.hidden_items.push(item);
+        Ok(self.items.len() + self.hidden_items.len() - 1)
+    }
+
+    fn resolve_order_target(
+        &mut self,
+        table_desc: &TableDesc,
+        item: &OrderByItem,
+        grouped: bool,
+    ) -> RS<usize> {
+        match item.target() {
+            OrderTarget::Position(position) => {
+                if *position > self.items.len() {
+                    return Err(mudu_error!(
+                        ER::Parse,
+                        format!("ORDER BY position {} is not in select list", position)
+                    ));
+                }
+                Ok(position - 1)
+            }
+            OrderTarget::Field(SelectField::Column(name))
+                if name.qualifier().is_none() && name.json_path().is_none() =>
+            {
+                // An output column name takes precedence over an input column.
+                match self
+                    .tuple_desc
+                    .fields()
+                    .iter()
+                    .position(|field| field.name() == name.name())
+                {
+                    Some(pos) => Ok(pos),
+                    None => {
+                        self.resolve_field(table_desc, &SelectField::Column(name.clone()), grouped)
+                    }
+                }
+            }
+            OrderTarget::Field(field) => self.resolve_field(table_desc, field, grouped),
+        }
+    }
+}
+
+/// Bind the select-list terms of a query against a table, producing the
+/// projection items and the output tuple description.
+fn bind_select_items(
+    table_desc: &TableDesc,
+    terms: &[SelectTerm],
+    json_columns: &mut JsonColumns,
+) -> RS<(Vec<BoundSelectItem>, TupleFieldDesc)> {
     let mut items = Vec::with_capacity(terms.len());
     let mut desc_fields = Vec::with_capacity(terms.len());
     for term in terms {
-        match term.field() {
+        let item = match term.field() {
             SelectField::Column(name) => {
-                let attr = attr_index_by_name(table_desc, name.name())?;
-                let field = table_desc.get_attr(attr);
+                let attr = json_columns.attr(table_desc, name)?;
                 let output_name = if term.alias().is_empty() {
-                    field.name().clone()
+                    attr_name(table_desc, json_columns.columns(), attr).0
                 } else {
                     term.alias().clone()
                 };
-                desc_fields.push(DatumDesc::new_nullable(
-                    output_name.clone(),
-                    field.type_desc().clone(),
-                    field.nullable(),
-                ));
-                items.push(BoundSelectItem::Column(BoundSelectColumn {
-                    attr,
-                    output_name,
-                }));
+                BoundSelectItem::Column(BoundSelectColumn { attr, output_name })
+            }
+            SelectField::Function(function) if is_aggregate(function) => {
+                BoundSelectItem::Aggregate(bind_aggregate(table_desc, function, term.alias())?)
             }
             SelectField::Function(function) => {
-                let aggregate = bind_aggregate(table_desc, function, term.alias())?;
-                desc_fields.push(DatumDesc::new_nullable(
-                    aggregate.output_name.clone(),
-                    aggregate.result_type.clone(),
-                    aggregate.nullable,
-                ));
-                items.push(BoundSelectItem::Aggregate(aggregate));
+                let expr = ExprType::Function(function.clone());
+                bind_select_expr(
+                    table_desc,
+                    json_columns,
+                    &expr,
+                    term.alias(),
+                    function.name(),
+                )?
             }
-        }
+            SelectField::Expression(expr) => {
+                bind_select_expr(table_desc, json_columns, expr, term.alias(), "?column?")?
+            }
+        };
+        desc_fields.push(item_datum_desc(table_desc, json_columns.columns(), &item));
+        items.push(item);
     }
     Ok((items, TupleFieldDesc::new(desc_fields)))
 }
 
+/// Output description of a select-list or hidden item.
+pub(crate) fn item_datum_desc(
+    table_desc: &TableDesc,
+    json_columns: &[BoundJsonColumn],
+    item: &BoundSelectItem,
+) -> DatumDesc {
+    match item {
+        BoundSelectItem::Column(column) => DatumDesc::new_nullable(
+            column.output_name.clone(),
+            attr_type(table_desc, json_columns, column.attr),
+            attr_name(table_desc, json_columns, column.attr).1,
+        ),
+        BoundSelectItem::Aggregate(aggregate) => DatumDesc::new_nullable(
+            aggregate.output_name.clone(),
+            aggregate.result_type.clone(),
+            aggregate.nullable,
+        ),
+        BoundSelectItem::Expression(expr) => {
+            DatumDesc::new_nullable(expr.output_name.clone(), expr.expr.data_type(), true)
+        }
+    }
+}
+
+// A computed select-list item; unaliased, it is named `default_name`.
+fn bind_select_expr(
+    table_desc: &TableDesc,
+    json_columns: &mut JsonColumns,
+    expr: &ExprType,
+    alias: &str,
+    default_name: &str,
+) -> RS<BoundSelectItem> {
+    let expr = ExprBinder::new(table_desc, json_columns, None).bind(expr)?;
+    let output_name = if alias.is_empty() {
+        default_name.to_string()
+    } else {
+        alias.to_string()
+    };
+    Ok(BoundSelectItem::Expression(BoundSelectExpr {
+        expr: without_params(&expr)?,
+        output_name,
+    }))
+}
+
+fn is_aggregate(function: &ExprFunction) -> bool {
+    matches!(
+        function.name().to_lowercase().as_str(),
+        "count" | "sum" | "avg" | "min" | "max"
+    )
+}
+
 fn bind_aggregate(
     table_desc: &TableDesc,
-    function: &sql_parser::ast::expr_function::ExprFunction,
+    function: &ExprFunction,
     alias: &str,
 ) -> RS<BoundAggregate> {
     let name = function.name().to_lowercase();
@@ -98,6 +446,12 @@ fn bind_aggregate(
         }
     };
 
+    if function.args().len() > 1 {
+        return Err(mudu_error!(
+            ER::InvalidArgument,
+            format!("function {} takes a single argument", name)
+        ));
+    }
     let arg: Option<AttrIndex> = match function.arg() {
         FunctionArg::Star => {
             if func != AggregateFunc::Count {
@@ -109,6 +463,12 @@ fn bind_aggregate(
             None
         }
         FunctionArg::Column(column) => Some(attr_index_by_name(table_desc, column.name())?),
+        FunctionArg::Expression(_) => {
+            return Err(mudu_error!(
+                ER::NotImplemented,
+                format!("function {} over an expression is not implemented", name)
+            ))
+        }
     };
     // Only COUNT accepts `*`; every other function was validated above to
     // have a column argument.
@@ -194,6 +554,57 @@ fn bind_aggregate(
     })
 }
 
+/// Bind the `RETURNING` list of an `INSERT`, `UPDATE` or `DELETE`; `None`
+/// without the clause. Only plain columns are supported, and `*` stands for
+/// every column of the table.
+pub(crate) fn bind_returning(
+    table_desc: &TableDesc,
+    terms: &[SelectTerm],
+) -> RS<Option<BoundReturning>> {
+    if terms.is_empty() {
+        return Ok(None);
+    }
+    let mut attrs = Vec::with_capacity(terms.len());
+    let mut desc_fields = Vec::with_capacity(terms.len());
+    for term in terms {
+        let SelectField::Column(name) = term.field() else {
+            return Err(mudu_error!(
+                ER::NotImplemented,
+                "aggregates in RETURNING are not implemented"
+            ));
+        };
+        if name.json_path().is_some() {
+            return Err(mudu_error!(
+                ER::NotImplemented,
+                "JSON paths in RETURNING are not implemented"
+            ));
+        }
+        let names: Vec<AttrIndex> = if name.name().is_empty() {
+            (0..table_desc.fields().len()).collect()
+        } else {
+            vec![attr_index_by_name(table_desc, name.name())?]
+        };
+        for attr in names {
+            let field = table_desc.get_attr(attr);
+            let output_name = if term.alias().is_empty() {
+                field.name().clone()
+            } else {
+                term.alias().clone()
+            };
+            desc_fields.push(DatumDesc::new_nullable(
+                output_name,
+                field.type_desc().clone(),
+                field.nullable(),
+            ));
+            attrs.push(attr);
+        }
+    }
+    Ok(Some(BoundReturning {
+        attrs,
+        tuple_desc: TupleFieldDesc::new(desc_fields),
+    }))
+}
+
 pub(crate) fn attr_index_by_name(table_desc: &TableDesc, name: &str) -> RS<AttrIndex> {
     let total = table_desc.fields().len();
     (0..total)
@@ -1,8 +1,10 @@
 use mudu::common::buf::Buf;
 use mudu::common::result::RS;
 use mudu::data_type::numeric::Numeric;
+use mudu::data_type::uuid::parse_uuid;
 use mudu::error::ErrorCode as ER;
 use mudu::mudu_error;
+use mudu::utils::json::{from_json_str, JsonValue};
 use mudu_type::data_type_fn_param::DataType;
 use mudu_type::data_typed::DataTyped;
 use mudu_type::datum::DatumDyn;
@@ -69,6 +71,67 @@ impl ValueCodec {
             (TypeFamily::I64, TypeFamily::U128) => {
                 DataTyped::from_oid(literal.data_internal().to_i64() as u128)
             }
+            (TypeFamily::I64 | TypeFamily::I32, TypeFamily::I8) => {
+                let value = Self::integer_of(literal);
+                DataTyped::from_i8(i8::try_from(value).map_err(|e| {
+                    mudu_error!(
+                        ER::TypeConversionFailed,
+                        format!("integer {value} out of range for tinyint"),
+                        e
+                    )
+                })?)
+            }
+            (TypeFamily::I64 | TypeFamily::I32, TypeFamily::I16) => {
+                let value = Self::integer_of(literal);
+                DataTyped::from_i16(i16::try_from(value).map_err(|e| {
+                    mudu_error!(
+                        ER::TypeConversionFailed,
+                        format!("integer {value} out of range for smallint"),
+                        e
+                    )
+                })?)
+            }
+            (TypeFamily::I8 | TypeFamily::I16, TypeFamily::I32) => {
+                DataTyped::from_i32(Self::integer_of(literal) as i32)
+            }
+            (TypeFamily::I8 | TypeFamily::I16, TypeFamily::I64) => {
+                DataTyped::from_i64(Self::integer_of(literal))
+            }
+            (TypeFamily::U128, TypeFamily::Uuid) => {
+                DataTyped::from_uuid(*literal.data_internal().expect_u128())
+            }
+            (TypeFamily::String, TypeFamily::Uuid) => {
+                let text = literal.data_internal().expect_string();
+                DataTyped::from_uuid(parse_uuid(text.trim()).map_err(|e| {
+                    mudu_error!(
+                        ER::TypeConversionFailed,
+                        format!("string to uuid literal cast: {e}")
+                    )
+                })?)
+            }
+            // A string is the text of a JSON document (`'{"a":1}'`, `'"x"'`);
+            // other scalars become the JSON value they spell.
+            (TypeFamily::String, TypeFamily::Json) => {
+                let text = literal.data_internal().expect_string();
+                let json = from_json_str::<JsonValue>(text).map_err(|e| {
+                    mudu_error!(
+                        ER::TypeConversionFailed,
+                        format!("string to json literal cast: {text:?}"),
+                        e
+                    )
+                })?;
+                DataTyped::from_json(json.to_string())
+            }
+            (TypeFamily::Bool, TypeFamily::Json) => {
+                DataTyped::from_json(literal.data_internal().expect_bool().to_string())
+            }
+            (
+                TypeFamily::I8 | TypeFamily::I16 | TypeFamily::I32 | TypeFamily::I64,
+                TypeFamily::Json,
+            ) => DataTyped::from_json(Self::integer_of(literal).to_string()),
+            (TypeFamily::Numeric, TypeFamily::Json) => {
+                DataTyped::from_json(literal.data_internal().expect_numeric().to_plain_string())
+            }
             (TypeFamily::F64, TypeFamily::F32) => {
                 DataTyped::from_f32(literal.data_internal().to_f64() as f32)
             }
@@ -120,4 +183,18 @@ impl ValueCodec {
         };
         Ok(coerced)
     }
+
+    // Value of an integer literal of any width.
+    fn integer_of(literal: &DataTyped) -> i64 {
+        let value = literal.data_internal();
+        if let Some(v) = value.as_i8() {
+            *v as i64
+        } else if let Some(v) = value.as_i16() {
+            *v as i64
+        } else if let Some(v) = value.as_i32() {
+            *v as i64
+        } else {
+            value.to_i64()
+        }
+    }
 }
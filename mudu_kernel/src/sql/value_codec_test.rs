@@ -147,4 +147,86 @@ mod tests {
                 .as_ref()
         );
     }
+
+    #[test]
+    fn integer_literal_is_range_checked_for_small_integer_columns() {
+        let ty = DataType::default_for(TypeFamily::I16);
+        let binary = ValueCodec::binary_from_literal(
+            &ExprLiteral::DatumLiteral(DataTyped::from_i64(-300)),
+            &ty,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            binary.as_slice(),
+            (-300i16).to_binary(&ty).unwrap().as_ref()
+        );
+
+        assert!(ValueCodec::binary_from_literal(
+            &ExprLiteral::DatumLiteral(DataTyped::from_i64(300)),
+            &DataType::default_for(TypeFamily::I8),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn string_literal_is_parsed_into_uuid_column_encoding() {
+        let ty = DataType::default_for(TypeFamily::Uuid);
+        let binary = ValueCodec::binary_from_literal(
+            &ExprLiteral::DatumLiteral(DataTyped::from_string(
+                "123e4567-e89b-12d3-a456-426614174000".to_string(),
+            )),
+            &ty,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            binary.as_slice(),
+            DataTyped::from_uuid(0x123e4567_e89b_12d3_a456_426614174000)
+                .data_internal()
+                .to_binary(&ty)
+                .unwrap()
+                .as_ref()
+        );
+
+        assert!(ValueCodec::binary_from_literal(
+            &ExprLiteral::DatumLiteral(DataTyped::from_string("not-a-uuid".to_string())),
+            &ty,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn literals_are_converted_into_json_documents() {
+        let ty = DataType::default_for(TypeFamily::Json);
+        let json = |literal: DataTyped| {
+            ValueCodec::binary_from_literal(&ExprLiteral::DatumLiteral(literal), &ty)
+                .unwrap()
+                .unwrap()
+        };
+        let expected = |text: &str| {
+            DataTyped::from_json(text.to_string())
+                .data_internal()
+                .to_binary(&ty)
+                .unwrap()
+        };
+
+        assert_eq!(
+            json(DataTyped::from_string("{ \"a\" : [1, 2] }".to_string())).as_slice(),
+            expected("{\"a\":[1,2]}").as_ref()
+        );
+        assert_eq!(
+            json(DataTyped::from_i64(5)).as_slice(),
+            expected("5").as_ref()
+        );
+        assert_eq!(
+            json(DataTyped::from_bool(true)).as_slice(),
+            expected("true").as_ref()
+        );
+        assert!(ValueCodec::binary_from_literal(
+            &ExprLiteral::DatumLiteral(DataTyped::from_string("{".to_string())),
+            &ty,
+        )
+        .is_err());
+    }
 }
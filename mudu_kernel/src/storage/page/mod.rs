@@ -6,6 +6,7 @@ pub mod page_block_ref_mut;
 mod page_block_ref_test;
 pub mod page_header;
 mod page_id;
+pub(crate) mod page_reads;
 pub mod page_tailer;
 pub mod record_slot;
 pub mod record_slot_ref;
@@ -0,0 +1,21 @@
+//! Per-thread count of page reads, sampled by `EXPLAIN ANALYZE`.
+//!
+//! A worker runs many tasks on one thread, so a reader attributes reads to
+//! its own work by sampling the counter around each synchronous poll.
+
+use std::cell::Cell;
+
+thread_local! {
+    static PAGE_READS: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Counts one page read by the calling thread, page cache hits included.
+pub(crate) fn record_page_read() {
+    PAGE_READS.with(|count| count.set(count.get().wrapping_add(1)));
+}
+
+/// Pages read by the calling thread so far; only differences between two
+/// samples are meaningful.
+pub(crate) fn page_reads() -> u64 {
+    PAGE_READS.with(Cell::get)
+}
@@ -0,0 +1,218 @@
+use mudu::common::id::OID;
+use mudu::common::result::RS;
+use mudu::error::ErrorCode;
+use mudu::mudu_error;
+use mudu_contract::tuple::nullable_tuple::{is_null, NullableValue, TupleBuilder};
+use mudu_contract::tuple::tuple_binary_desc::TupleBinaryDesc as TupleDesc;
+use mudu_type::data_type::DataType;
+use mudu_type::data_type_function::{output_textual, recv_binary, send_binary};
+use mudu_type::data_value::DataValue;
+
+use crate::contract::schema_column::ColumnDefault;
+use crate::contract::table_desc::{RetiredValueLayout, TableDesc};
+use crate::x_engine::api::{DeltaAssign, DeltaOp};
+
+// Where the value of one current-layout field comes from.
+enum FieldSource {
+    // Same type in the retired layout: decode as is.
+    Copy(usize),
+    // Type changed by ALTER COLUMN TYPE: convert through the textual form.
+    Convert(usize, DataType),
+    // Column added after the retired layout, with its constant DEFAULT.
+    Missing(Option<DataValue>),
+}
+
+struct UpgradeField {
+    source: FieldSource,
+    type_desc: DataType,
+    nullable: bool,
+}
+
+/// Rewrites value payloads stored under a retired layout into the current
+/// value tuple layout. Fields are matched by column OID, so renamed columns
+/// keep their values, dropped columns are discarded and added columns read as
+/// their constant DEFAULT, or NULL without one. ALTER TABLE only adds a NOT
+/// NULL column without a DEFAULT to an empty table; should an older row lack
+/// such a column anyway, it reads as the type default.
+pub(crate) struct LayoutUpgrade {
+    from_desc: TupleDesc,
+    to_desc: TupleDesc,
+    // Indexed by datum index in `to_desc`.
+    fields: Vec<UpgradeField>,
+}
+
+impl LayoutUpgrade {
+    pub(crate) fn new(retired: &RetiredValueLayout, current: &TableDesc) -> RS<Self> {
+        let to_desc = current.value_desc().clone();
+        let mut fields: Vec<Option<UpgradeField>> =
+            (0..to_desc.field_count()).map(|_| None).collect();
+        for target in current.value_info() {
+            let source = match retired
+                .value_fields
+                .iter()
+                .find(|field| field.id() == target.id())
+            {
+                Some(field) if field.type_desc().to_info() == target.type_desc().to_info() => {
+                    FieldSource::Copy(field.datum_index())
+                }
+                Some(field) => FieldSource::Convert(field.datum_index(), field.type_desc().clone()),
+                None => match target.default_value() {
+                    Some(ColumnDefault::Value(datum)) => FieldSource::Missing(Some(
+                        recv_binary(datum, target.type_desc())
+                            .map_err(|e| e.to_m_err())?
+                            .as_ref()
+                            .clone(),
+                    )),
+                    _ => FieldSource::Missing(None),
+                },
+            };
+            let slot = fields
+                .get_mut(target.datum_index())
+                .ok_or_else(|| mudu_error!(ErrorCode::IndexOutOfRange))?;
+            *slot = Some(UpgradeField {
+                source,
+                type_desc: target.type_desc().clone(),
+                nullable: target.nullable(),
+            });
+        }
+        let fields = fields
+            .into_iter()
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| mudu_error!(ErrorCode::InvalidTuple))?;
+        Ok(Self {
+            from_desc: retired.value_desc.clone(),
+            to_desc,
+            fields,
+        })
+    }
+
+    pub(crate) fn upgrade(&self, payload: &[u8]) -> RS<Vec<u8>> {
+        let mut values = Vec::with_capacity(self.fields.len());
+        for field in &self.fields {
+            let value = match &field.source {
+                FieldSource::Copy(index) | FieldSource::Convert(index, _)
+                    if is_null(payload, &self.from_desc, *index)? =>
+                {
+                    None
+                }
+                FieldSource::Copy(index) => {
+                    let bytes = self.from_desc.get_field_desc(*index).get(payload)?;
+                    let (value, _) =
+                        field.type_desc.type_family().fn_recv()(bytes, &field.type_desc)
+                            .map_err(|e| e.to_m_err())?;
+                    Some(value)
+                }
+                FieldSource::Convert(index, from_type) => {
+                    let bytes = self.from_desc.get_field_desc(*index).get(payload)?;
+                    let (value, _) = from_type.type_family().fn_recv()(bytes, from_type)
+                        .map_err(|e| e.to_m_err())?;
+                    let text = output_textual(&value, from_type).map_err(|e| e.to_m_err())?;
+                    let value = field.type_desc.type_family().fn_input()(&text, &field.type_desc)
+                        .map_err(|e| {
+                        mudu_error!(
+                            ErrorCode::TypeConversionFailed,
+                            format!("cannot convert stored value {} to the new type", text),
+                            e.to_m_err()
+                        )
+                    })?;
+                    Some(value)
+                }
+                FieldSource::Missing(default) => default.clone(),
+            };
+            let value = match value {
+                Some(value) => NullableValue::Value(value),
+                None if field.nullable => NullableValue::Null,
+                None => NullableValue::Value(
+                    field.type_desc.type_family().fn_default()(&field.type_desc)
+                        .map_err(|e| e.to_m_err())?,
+                ),
+            };
+            values.push(value);
+        }
+        TupleBuilder::new(&self.to_desc).build(&values)
+    }
+}
+
+/// The retired layout `version` of `desc`, or `None` when `version` is the
+/// current layout.
+pub(crate) fn retired_layout(desc: &TableDesc, version: u32) -> RS<Option<&RetiredValueLayout>> {
+    if version == desc.layout_version() {
+        return Ok(None);
+    }
+    desc.retired_layouts()
+        .iter()
+        .find(|layout| layout.version == version)
+        .map(Some)
+        .ok_or_else(|| {
+            mudu_error!(
+                ErrorCode::CorruptedData,
+                format!(
+                    "unknown layout version {} of table {}, current is {}",
+                    version,
+                    desc.id(),
+                    desc.layout_version()
+                )
+            )
+        })
+}
+
+/// Re-targets delta assignments encoded under a retired layout at the
+/// current layout. `column_oids[i]` is the column OID of `deltas[i]`.
+/// Assignments to dropped columns are discarded and operands in the binary
+/// format of a column whose type changed are converted through the textual
+/// form.
+pub(crate) fn upgrade_deltas(
+    retired: &RetiredValueLayout,
+    current: &TableDesc,
+    column_oids: &[OID],
+    deltas: Vec<DeltaAssign>,
+) -> RS<Vec<DeltaAssign>> {
+    if column_oids.len() != deltas.len() {
+        return Err(mudu_error!(
+            ErrorCode::CorruptedData,
+            "delta column OIDs do not match the assignments"
+        ));
+    }
+    let mut upgraded = Vec::with_capacity(deltas.len());
+    for (oid, mut delta) in column_oids.iter().zip(deltas) {
+        let Some((attr, target)) = current
+            .fields()
+            .iter()
+            .enumerate()
+            .find(|(_, field)| field.id() == *oid)
+        else {
+            continue;
+        };
+        let source = retired
+            .value_fields
+            .iter()
+            .find(|field| field.id() == *oid)
+            .ok_or_else(|| {
+                mudu_error!(
+                    ErrorCode::CorruptedData,
+                    format!("delta column {} is not in layout {}", oid, retired.version)
+                )
+            })?;
+        // The SubWrapDeferred operand packs fixed i64s, independent of the
+        // column type.
+        if delta.op != DeltaOp::SubWrapDeferred
+            && source.type_desc().to_info() != target.type_desc().to_info()
+        {
+            let value =
+                recv_binary(&delta.literal, source.type_desc()).map_err(|e| e.to_m_err())?;
+            let text = output_textual(&value, source.type_desc()).map_err(|e| e.to_m_err())?;
+            let value = target.type_desc().type_family().fn_input()(&text, target.type_desc())
+                .map_err(|e| {
+                    mudu_error!(
+                        ErrorCode::TypeConversionFailed,
+                        format!("cannot convert logged operand {} to the new type", text),
+                        e.to_m_err()
+                    )
+                })?;
+            delta.literal = send_binary(&value, target.type_desc()).map_err(|e| e.to_m_err())?;
+        }
+        delta.attr = attr;
+        upgraded.push(delta);
+    }
+    Ok(upgraded)
+}
@@ -1,2 +1,4 @@
 #![allow(clippy::module_inception)]
+pub(crate) mod layout_upgrade;
 pub mod relation;
+pub(crate) mod secondary_index;
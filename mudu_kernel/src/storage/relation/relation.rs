@@ -1,6 +1,7 @@
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use std::ops::Bound;
+use std::path::Path;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
@@ -23,7 +24,10 @@ use crate::index::btree::btree_index::BTreeIndex;
 use crate::index::index_key::compare_context::CompareContext;
 use crate::index::index_key::key_tuple::KeyTuple;
 use crate::server::worker_snapshot::WorkerSnapshot;
+use crate::storage::relation::layout_upgrade::LayoutUpgrade;
+use crate::storage::relation::secondary_index::SecondaryIndex;
 use crate::storage::time_series::time_series_file::{TimeSeriesFile, TimeSeriesFileIdentity};
+use crate::wal::worker_log::WalCheckpointStats;
 use crate::x_engine::api::{DeltaAssign, VecDatum};
 use mudu_utils::scoped_task_trace;
 use tracing::trace;
@@ -33,6 +37,13 @@ use tracing::trace;
 const KEY_FILE_INDEX: u32 = 0;
 const VALUE_FILE_INDEX: u32 = 1;
 
+// Each value tuple layout of a table lives in its own value file, so a
+// layout change never rewrites existing pages (their schema hash pins the
+// layout they were written with).
+fn value_file_index(layout_version: u32) -> u32 {
+    VALUE_FILE_INDEX + layout_version
+}
+
 // Same-key writes are serialized through a fixed set of stripe latches
 // instead of one relation-wide lock, so unrelated keys proceed in parallel.
 // The count is sized so a large multi-row batch (e.g. a 32-line order) covers
@@ -42,6 +53,26 @@ const VALUE_FILE_INDEX: u32 = 1;
 // stays trivial.
 const WRITE_STRIPE_COUNT: usize = 512;
 
+// Rows pruned between two cooperative yields of a version GC pass, so one
+// large relation cannot monopolize the worker event loop.
+const VERSION_GC_YIELD_ROWS: usize = 1024;
+
+/// Outcome of one version GC pass; see [`Relation::gc_versions_async`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VersionGcStats {
+    /// Versions dropped from the in-memory version chains.
+    pub pruned_versions: u64,
+    /// Time-series pages emptied because every record on them was pruned.
+    pub reclaimed_pages: u64,
+}
+
+impl std::ops::AddAssign for VersionGcStats {
+    fn add_assign(&mut self, other: Self) {
+        self.pruned_versions += other.pruned_versions;
+        self.reclaimed_pages += other.reclaimed_pages;
+    }
+}
+
 pub struct Relation {
     inner: RelationInner,
 }
@@ -52,6 +83,13 @@ struct RelationInner {
     index: BTreeIndex<DataRow>,
     key_file: TimeSeriesFile,
     value_file: TimeSeriesFile,
+    // Value files of layouts retired by ALTER TABLE, newest first. They are
+    // only read: payloads found there are upgraded to the current layout.
+    retired_value_files: Vec<RetiredValueFile>,
+    // Secondary indexes of the table. They are not persisted: opening the
+    // relation rebuilds them from the latest version of every row, and
+    // version GC prunes the entries of the versions it drops.
+    secondary_indexes: Vec<SecondaryIndex>,
     next_tuple_id: AtomicU64,
     // Per-key write serialization. The normal commit path already
     // serializes same-key check-then-act through the XLockMgr commit locks,
@@ -67,6 +105,11 @@ struct RelationInner {
     write_stripes: Box<[AMutex<()>]>,
 }
 
+struct RetiredValueFile {
+    file: TimeSeriesFile,
+    upgrade: LayoutUpgrade,
+}
+
 // Safety: every piece of shared mutable state inside RelationInner is
 // internally synchronized, and no `&self` method hands out a mutable alias:
 // - `BTreeIndex` guards its map with SRwLock; its RefCell compare context is
@@ -74,6 +117,7 @@ struct RelationInner {
 //   the comparator error context is thread-local.
 // - `TimeSeriesFile` serializes its writers with an async latch and keeps
 //   chain metadata in atomics; readers are latch-free by design.
+// - `SecondaryIndex` guards its entries with SRwLock.
 // - `next_tuple_id` is an atomic counter.
 // - same-key write check-then-act is serialized by `write_stripes`.
 unsafe impl Send for RelationInner {}
@@ -160,6 +204,34 @@ impl Relation {
         self.inner.has_write_conflict(key, snapshot).await
     }
 
+    /// Rows visible to `snapshot` whose leading columns of secondary index
+    /// `index_oid` equal `prefix` (one binary-encoded value per column), in
+    /// primary-key byte order.
+    pub async fn visible_index_lookup(
+        &self,
+        index_oid: OID,
+        prefix: &[Vec<u8>],
+        snapshot: &WorkerSnapshot,
+    ) -> RS<Vec<(Vec<u8>, Vec<u8>)>> {
+        self.inner
+            .visible_index_lookup(index_oid, prefix, snapshot)
+            .await
+    }
+
+    /// Whether the row (`key`, `value`) carries `prefix` in the leading
+    /// columns of secondary index `index_oid`.
+    pub fn index_prefix_matches(
+        &self,
+        index_oid: OID,
+        key: &[u8],
+        value: &[u8],
+        prefix: &[Vec<u8>],
+    ) -> RS<bool> {
+        self.inner
+            .secondary_index(index_oid)?
+            .matches(key, value, prefix)
+    }
+
     pub async fn write_value(&self, key: Vec<u8>, value: Vec<u8>, xid: u64) -> RS<()> {
         self.inner.write_rows(&[(key, Some(value))], xid).await
     }
@@ -220,6 +292,44 @@ impl Relation {
         self.inner.value_file.flush_wal_async().await?;
         Ok(())
     }
+
+    /// Checkpoints the PL WAL of both files and reclaims the chunks the
+    /// checkpoints cover; see [`TimeSeriesFile::checkpoint_async`].
+    pub(crate) async fn checkpoint_async(&self) -> RS<WalCheckpointStats> {
+        let mut stats = self.inner.key_file.checkpoint_async().await?;
+        stats += self.inner.value_file.checkpoint_async().await?;
+        Ok(stats)
+    }
+
+    /// Copies every file of the relation, retired value files included, and
+    /// their PL WALs into a backup; see [`TimeSeriesFile::backup_async`].
+    pub(crate) async fn backup_async(&self, source_root: &Path, target_root: &Path) -> RS<()> {
+        let inner = &self.inner;
+        inner
+            .key_file
+            .backup_async(source_root, target_root)
+            .await?;
+        inner
+            .value_file
+            .backup_async(source_root, target_root)
+            .await?;
+        for retired in &inner.retired_value_files {
+            retired.file.backup_async(source_root, target_root).await?;
+        }
+        Ok(())
+    }
+
+    /// Drops every version no snapshot at or above `low_water` can read
+    /// (see `DataRow::prune_versions_sync`) and empties the key and value
+    /// file pages that held only such versions.
+    ///
+    /// Both files are compacted with the same rule, so a reopen never
+    /// rebuilds a pruned version: its key record is gone along with its
+    /// value record, instead of coming back as a tombstone. Rows whose only
+    /// remaining version is a delete keep that tombstone.
+    pub(crate) async fn gc_versions_async(&self, low_water: u64) -> RS<VersionGcStats> {
+        self.inner.gc_versions_async(low_water).await
+    }
 }
 
 #[cfg(test)]
@@ -249,7 +359,7 @@ impl RelationInner {
         let value_identity = TimeSeriesFileIdentity {
             partition_id,
             table_id,
-            file_index: VALUE_FILE_INDEX,
+            file_index: value_file_index(table_desc.layout_version()),
         };
         let key_schema_hash = tuple_schema_hash(b'K', table_desc.key_desc());
         let value_schema_hash = tuple_schema_hash(b'V', table_desc.value_desc());
@@ -276,6 +386,16 @@ impl RelationInner {
                 true,
             )
             .await?,
+            retired_value_files: open_retired_value_files(
+                None,
+                None,
+                &path,
+                table_id,
+                partition_id,
+                table_desc,
+            )
+            .await?,
+            secondary_indexes: open_secondary_indexes(table_desc),
             next_tuple_id: AtomicU64::new(1),
             write_stripes: (0..WRITE_STRIPE_COUNT).map(|_| AMutex::new(())).collect(),
         };
@@ -331,7 +451,7 @@ impl RelationInner {
         let value_identity = TimeSeriesFileIdentity {
             partition_id,
             table_id,
-            file_index: VALUE_FILE_INDEX,
+            file_index: value_file_index(table_desc.layout_version()),
         };
         let key_schema_hash = tuple_schema_hash(b'K', table_desc.key_desc());
         let value_schema_hash = tuple_schema_hash(b'V', table_desc.value_desc());
@@ -404,6 +524,16 @@ impl RelationInner {
                     }
                 }
             },
+            retired_value_files: open_retired_value_files(
+                Some(&fs),
+                provider.as_ref(),
+                &path,
+                table_id,
+                partition_id,
+                table_desc,
+            )
+            .await?,
+            secondary_indexes: open_secondary_indexes(table_desc),
             next_tuple_id: AtomicU64::new(1),
             write_stripes: (0..WRITE_STRIPE_COUNT).map(|_| AMutex::new(())).collect(),
         };
@@ -451,13 +581,13 @@ impl RelationInner {
             };
 
             let timestamp = Timestamp::new(key_row.timestamp, u64::MAX);
-            let version = match self
-                .value_file
-                .get(key_row.timestamp, key_row.tuple_id)
+            let version = if self
+                .has_value_record(key_row.timestamp, key_row.tuple_id)
                 .await?
             {
-                Some(_) => VersionTuple::new(timestamp, Vec::new()),
-                None => VersionTuple::new_delete(timestamp),
+                VersionTuple::new(timestamp, Vec::new())
+            } else {
+                VersionTuple::new_delete(timestamp)
             };
             // Rebuilt versions are metadata-only: their payloads stay in the
             // value file, and visibility reads fall back to the file until
@@ -468,9 +598,74 @@ impl RelationInner {
 
         self.next_tuple_id
             .store(max_tuple_id.saturating_add(1).max(1), Ordering::Release);
+        self.rebuild_secondary_indexes_async().await
+    }
+
+    // Index the latest version of every live row. Older versions are not
+    // indexed: the relation is only reopened when no transaction is reading
+    // an older snapshot of it.
+    async fn rebuild_secondary_indexes_async(&self) -> RS<()> {
+        if self.secondary_indexes.is_empty() {
+            return Ok(());
+        }
+        for (key, row) in self.index.range((Bound::Unbounded, Bound::Unbounded))? {
+            let Some(version) = latest_version_async(&row).await else {
+                continue;
+            };
+            if version.is_deleted() {
+                continue;
+            }
+            let tuple_id = row
+                .tuple_id()
+                .await?
+                .ok_or_else(|| mudu_error!(ErrorCode::Internal, "missing tuple id"))?;
+            let value = self
+                .read_value_payload(version.timestamp().c_min(), tuple_id)
+                .await?;
+            for secondary in &self.secondary_indexes {
+                secondary.insert(key.as_slice(), &value, version.timestamp().c_min())?;
+            }
+        }
         Ok(())
     }
 
+    async fn visible_index_lookup(
+        &self,
+        index_oid: OID,
+        prefix: &[Vec<u8>],
+        snapshot: &WorkerSnapshot,
+    ) -> RS<Vec<(Vec<u8>, Vec<u8>)>> {
+        let secondary = self.secondary_index(index_oid)?;
+        let snapshot = snapshot.to_snapshot();
+        let mut items = Vec::new();
+        for key in secondary.lookup(prefix)? {
+            let Some(row) = self.index.get(&KeyTuple::from(key.clone()))? else {
+                continue;
+            };
+            let Some((key, value)) = visible_payloads_async(self, key, &row, &snapshot).await?
+            else {
+                continue;
+            };
+            // The entry may come from a version the snapshot no longer sees.
+            if secondary.matches(&key, &value, prefix)? {
+                items.push((key, value));
+            }
+        }
+        Ok(items)
+    }
+
+    fn secondary_index(&self, index_oid: OID) -> RS<&SecondaryIndex> {
+        self.secondary_indexes
+            .iter()
+            .find(|secondary| secondary.oid() == index_oid)
+            .ok_or_else(|| {
+                mudu_error!(
+                    ErrorCode::EntityNotFound,
+                    format!("no such index {}", index_oid)
+                )
+            })
+    }
+
     async fn visible_meta(
         &self,
         key: &KeyTuple,
@@ -548,8 +743,7 @@ impl RelationInner {
             // The index key already holds the key bytes; no need to walk the
             // key file's version chain again for every row.
             if let Some(pair) =
-                visible_payloads_async(&self.value_file, key.as_slice().to_vec(), &row, &snapshot)
-                    .await?
+                visible_payloads_async(self, key.as_slice().to_vec(), &row, &snapshot).await?
             {
                 items.push(pair);
             }
@@ -660,6 +854,15 @@ impl RelationInner {
             let _stage = crate::server::stage_stats::StageGuard::new(
                 crate::server::stage_stats::Stage::WrRowIndex,
             );
+            // Index entries go in before the versions are published, so a
+            // reader that sees the new version also finds it by index.
+            for (key, value) in rows {
+                if let Some(value) = value {
+                    for secondary in &self.secondary_indexes {
+                        secondary.insert(key, value, timestamp.c_min())?;
+                    }
+                }
+            }
             for ((_, value), (_, row, _)) in rows.iter().zip(resolved) {
                 // Keep the committed payload in the in-memory version so
                 // visibility reads can skip the value file's page chain; the
@@ -747,6 +950,9 @@ impl RelationInner {
                     desc,
                 )
             })?;
+            for secondary in &self.secondary_indexes {
+                secondary.insert(key, &computed, timestamp.c_min())?;
+            }
             computed_rows.push((key.clone(), computed));
         }
 
@@ -773,6 +979,49 @@ impl RelationInner {
         Ok(())
     }
 
+    async fn gc_versions_async(&self, low_water: u64) -> RS<VersionGcStats> {
+        let mut stats = VersionGcStats::default();
+        // Commit timestamp of the oldest retained version per tuple id: the
+        // file records of a tuple below it belong to pruned versions.
+        let mut oldest_retained = std::collections::HashMap::new();
+        let rows = self.index.range((Bound::Unbounded, Bound::Unbounded))?;
+        for (index, (key, row)) in rows.into_iter().enumerate() {
+            let (pruned, oldest) = row.prune_versions_sync(low_water)?;
+            stats.pruned_versions += pruned as u64;
+            if let Some(oldest) = oldest {
+                // Index entries of the pruned versions lead nowhere now.
+                if pruned > 0 {
+                    for secondary in &self.secondary_indexes {
+                        secondary.prune(key.as_slice(), oldest)?;
+                    }
+                }
+                let tuple_id = row
+                    .tuple_id_sync()?
+                    .ok_or_else(|| mudu_error!(ErrorCode::Internal, "missing tuple id"))?;
+                oldest_retained.insert(tuple_id as u64, oldest);
+            }
+            if (index + 1) % VERSION_GC_YIELD_ROWS == 0 {
+                crate::common::yield_now::cooperative_yield_now().await;
+            }
+        }
+        // A page can only become fully dead when one of its versions was
+        // pruned, so a pass that pruned nothing has no page to reclaim.
+        if stats.pruned_versions == 0 {
+            return Ok(stats);
+        }
+        let is_dead = |timestamp: u64, tuple_id: u64| {
+            oldest_retained
+                .get(&tuple_id)
+                .is_some_and(|oldest| timestamp < *oldest)
+        };
+        let (key_pages, value_pages) = futures::join!(
+            self.key_file.compact_dead_pages(is_dead),
+            self.value_file.compact_dead_pages(is_dead)
+        );
+        stats.reclaimed_pages += (key_pages? + value_pages?) as u64;
+        Ok(stats)
+    }
+
     fn stripe_index(key: &[u8]) -> usize {
         let mut hasher = DefaultHasher::new();
         key.hash(&mut hasher);
@@ -784,16 +1033,43 @@ impl RelationInner {
     }
 
     async fn read_value_payload(&self, timestamp: u64, tuple_id: OID) -> RS<Vec<u8>> {
-        let record = self.value_file.get(timestamp, tuple_id as u64).await?;
-        record.map(|record| record.payload).ok_or_else(|| {
-            mudu_error!(
-                ErrorCode::EntityNotFound,
-                format!("missing value payload ts={timestamp} tuple_id={tuple_id}")
-            )
-        })
+        if let Some(record) = self.value_file.get(timestamp, tuple_id as u64).await? {
+            return Ok(record.payload);
+        }
+        // Written before an ALTER TABLE: the record sits in the value file of
+        // the layout current at write time.
+        for retired in &self.retired_value_files {
+            if let Some(record) = retired.file.get(timestamp, tuple_id as u64).await? {
+                return retired.upgrade.upgrade(&record.payload);
+            }
+        }
+        Err(mudu_error!(
+            ErrorCode::EntityNotFound,
+            format!("missing value payload ts={timestamp} tuple_id={tuple_id}")
+        ))
+    }
+
+    async fn has_value_record(&self, timestamp: u64, tuple_id: u64) -> RS<bool> {
+        if self.value_file.get(timestamp, tuple_id).await?.is_some() {
+            return Ok(true);
+        }
+        for retired in &self.retired_value_files {
+            if retired.file.get(timestamp, tuple_id).await?.is_some() {
+                return Ok(true);
+            }
+        }
+        Ok(false)
     }
 }
 
+fn open_secondary_indexes(table_desc: &TableDesc) -> Vec<SecondaryIndex> {
+    table_desc
+        .indexes()
+        .iter()
+        .map(|index| SecondaryIndex::new(index, table_desc))
+        .collect()
+}
+
 fn tuple_schema_hash(
     role: u8,
     desc: &mudu_contract::tuple::tuple_binary_desc::TupleBinaryDesc,
@@ -828,8 +1104,57 @@ fn tuple_schema_hash(
     h
 }
 
+async fn open_retired_value_files(
+    fs: Option<&Arc<dyn AsyncFs>>,
+    provider: Option<&Arc<dyn AsyncIoProvider>>,
+    path: &str,
+    table_id: OID,
+    partition_id: OID,
+    table_desc: &TableDesc,
+) -> RS<Vec<RetiredValueFile>> {
+    let mut files = Vec::with_capacity(table_desc.retired_layouts().len());
+    for layout in table_desc.retired_layouts().iter().rev() {
+        let identity = TimeSeriesFileIdentity {
+            partition_id,
+            table_id,
+            file_index: value_file_index(layout.version),
+        };
+        let schema_hash = tuple_schema_hash(b'V', &layout.value_desc);
+        let file = match (provider, fs) {
+            (Some(provider), _) => {
+                TimeSeriesFile::open_relation_file_with_sys_io_context(
+                    SysIoContext::new(provider.clone()),
+                    path,
+                    identity,
+                    schema_hash,
+                    true,
+                )
+                .await?
+            }
+            (None, Some(fs)) => {
+                TimeSeriesFile::open_relation_file_with_fs(
+                    fs.clone(),
+                    path,
+                    identity,
+                    schema_hash,
+                    true,
+                )
+                .await?
+            }
+            (None, None) => {
+                TimeSeriesFile::open_relation_file(path, identity, schema_hash, true).await?
+            }
+        };
+        files.push(RetiredValueFile {
+            file,
+            upgrade: LayoutUpgrade::new(layout, table_desc)?,
+        });
+    }
+    Ok(files)
+}
+
 async fn visible_payloads_async(
-    value_file: &TimeSeriesFile,
+    relation: &RelationInner,
     key: Vec<u8>,
     row: &DataRow,
     snapshot: &Snapshot,
@@ -849,17 +1174,9 @@ async fn visible_payloads_async(
     if payload_authoritative {
         return Ok(Some((key, version.tuple_into())));
     }
-    let ts = version.timestamp().c_min();
-    let value = value_file
-        .get(ts, tuple_id as u64)
-        .await?
-        .map(|record| record.payload)
-        .ok_or_else(|| {
-            mudu_error!(
-                ErrorCode::EntityNotFound,
-                format!("missing value payload ts={ts} tuple_id={tuple_id}")
-            )
-        })?;
+    let value = relation
+        .read_value_payload(version.timestamp().c_min(), tuple_id)
+        .await?;
     Ok(Some((key, value)))
 }
 
@@ -1184,6 +1501,84 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    fn gc_versions_prunes_chains_and_reclaims_file_pages() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let schema = test_schema();
+            let table_desc = TableInfo::new(schema.clone())
+                .unwrap()
+                .table_desc()
+                .unwrap();
+            let table_id = schema.id();
+            let partition_id = 10;
+            let path = relation_path();
+
+            let relation = Relation::new(table_id, partition_id, path.clone(), table_desc.as_ref())
+                .await
+                .unwrap();
+            let value = |xid: i32, key: i32| i32_bytes(xid * 1000 + key);
+            for xid in 1..=30 {
+                let rows: Vec<(Vec<u8>, Option<Vec<u8>>)> = (0..40)
+                    .map(|key| (i32_bytes(key), Some(value(xid, key))))
+                    .collect();
+                relation.write_rows(&rows, xid as u64).await.unwrap();
+            }
+
+            // Version 24 is the newest one below the mark; 1..=23 go.
+            let stats = relation.gc_versions_async(25).await.unwrap();
+            assert_eq!(stats.pruned_versions, 40 * 23);
+            assert!(stats.reclaimed_pages > 0);
+            let snapshot = |xid: u64| WorkerSnapshot::new(xid, vec![]);
+            for key in 0..40 {
+                let key_tuple = KeyTuple::from(i32_bytes(key));
+                assert_eq!(
+                    relation
+                        .visible_value(&key_tuple, &snapshot(24))
+                        .await
+                        .unwrap(),
+                    Some(value(24, key))
+                );
+                assert_eq!(
+                    relation
+                        .visible_value(&key_tuple, &snapshot(30))
+                        .await
+                        .unwrap(),
+                    Some(value(30, key))
+                );
+            }
+            // Nothing is left below the mark: a second pass is a no-op.
+            assert_eq!(
+                relation.gc_versions_async(25).await.unwrap(),
+                VersionGcStats::default()
+            );
+            relation.write_delete(i32_bytes(0), 31).await.unwrap();
+            relation.flush_wal_async().await.unwrap();
+            drop(relation);
+
+            // Pruned versions do not come back on reopen, neither as values
+            // nor as tombstones shadowing the retained versions.
+            let reopened = Relation::new(table_id, partition_id, path.clone(), table_desc.as_ref())
+                .await
+                .unwrap();
+            for key in 0..40 {
+                let key_tuple = KeyTuple::from(i32_bytes(key));
+                assert_eq!(
+                    reopened
+                        .visible_value(&key_tuple, &snapshot(24))
+                        .await
+                        .unwrap(),
+                    Some(value(24, key))
+                );
+                let latest = reopened
+                    .visible_value(&key_tuple, &snapshot(31))
+                    .await
+                    .unwrap();
+                assert_eq!(latest, (key != 0).then(|| value(30, key)));
+            }
+        })
+        .unwrap()
+    }
+
     #[test]
     fn visible_value_serves_in_memory_payload_and_file_fallback() {
         mudu_sys::task::async_::block_on_tokio_current_thread(async move {
@@ -1290,6 +1685,320 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    fn reads_rows_written_before_alter_table_in_the_new_layout() {
+        use crate::contract::schema_column::ColumnDefault;
+        use crate::x_engine::api::AlterTable;
+        use mudu_contract::tuple::nullable_tuple::{NullableValue, TupleBuilder};
+        use mudu_type::data_type::DataType;
+        use mudu_type::data_type_function::send_binary;
+        use mudu_type::data_value::DataValue;
+
+        // Orders (column name, value) pairs by datum index of `desc`.
+        fn value_tuple(desc: &TableDesc, values: Vec<(&str, NullableValue)>) -> Vec<u8> {
+            let mut slots: Vec<Option<NullableValue>> =
+                (0..desc.value_desc().field_count()).map(|_| None).collect();
+            for (name, value) in values {
+                let oid = desc.name2oid()[name];
+                slots[desc.oid2col()[&oid].datum_index()] = Some(value);
+            }
+            let values: Vec<NullableValue> = slots.into_iter().map(|v| v.unwrap()).collect();
+            TupleBuilder::new(desc.value_desc()).build(&values).unwrap()
+        }
+
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let schema = test_schema();
+            let old_desc = TableInfo::new(schema.clone())
+                .unwrap()
+                .table_desc()
+                .unwrap();
+            let table_id = schema.id();
+            let path = relation_path();
+            let key = || KeyTuple::from(i32_bytes(1));
+            let snapshot = |xid: u64| WorkerSnapshot::new(xid, vec![]);
+
+            let relation = Relation::new(table_id, 17, path.clone(), old_desc.as_ref())
+                .await
+                .unwrap();
+            relation
+                .write_value(
+                    i32_bytes(1),
+                    value_tuple(
+                        &old_desc,
+                        vec![("v", NullableValue::Value(DataValue::from_i32(11)))],
+                    ),
+                    1,
+                )
+                .await
+                .unwrap();
+            relation.flush_wal_async().await.unwrap();
+            drop(relation);
+
+            let mut added = SchemaColumn::new(
+                "w".to_string(),
+                TypeFamily::I32,
+                DataTypeInfo::from_text(TypeFamily::I32, String::new()),
+            );
+            added.set_nullable(false);
+            let default = send_binary(
+                &DataValue::from_i32(7),
+                &DataType::new_no_param(TypeFamily::I32),
+            )
+            .unwrap();
+            added.set_default_value(Some(ColumnDefault::Value(default)));
+            let altered = schema
+                .altered(&AlterTable::AlterColumnType {
+                    name: "v".to_string(),
+                    type_id: TypeFamily::I64,
+                    type_param: DataType::new_no_param(TypeFamily::I64).to_info(),
+                })
+                .unwrap()
+                .altered(&AlterTable::AddColumn(added))
+                .unwrap()
+                .altered(&AlterTable::AddColumn(SchemaColumn::new(
+                    "note".to_string(),
+                    TypeFamily::I32,
+                    DataTypeInfo::from_text(TypeFamily::I32, String::new()),
+                )))
+                .unwrap();
+            let new_desc = TableInfo::new(altered).unwrap().table_desc().unwrap();
+            assert_eq!(new_desc.layout_version(), 3);
+
+            let reopened = Relation::new(table_id, 17, path.clone(), new_desc.as_ref())
+                .await
+                .unwrap();
+            // The old row is converted on read: `v` widened to I64, the NOT
+            // NULL column `w` takes its DEFAULT, `note` reads as NULL.
+            let upgraded = value_tuple(
+                &new_desc,
+                vec![
+                    ("v", NullableValue::Value(DataValue::from_i64(11))),
+                    ("w", NullableValue::Value(DataValue::from_i32(7))),
+                    ("note", NullableValue::Null),
+                ],
+            );
+            assert_eq!(
+                reopened.visible_value(&key(), &snapshot(1)).await.unwrap(),
+                Some(upgraded.clone())
+            );
+
+            let rewritten = value_tuple(
+                &new_desc,
+                vec![
+                    ("v", NullableValue::Value(DataValue::from_i64(12))),
+                    ("w", NullableValue::Value(DataValue::from_i32(2))),
+                    ("note", NullableValue::Value(DataValue::from_i32(3))),
+                ],
+            );
+            reopened
+                .write_value(i32_bytes(1), rewritten.clone(), 2)
+                .await
+                .unwrap();
+            reopened.flush_wal_async().await.unwrap();
+            drop(reopened);
+
+            // Rows of both layouts survive another reopen.
+            let reopened = Relation::new(table_id, 17, path.clone(), new_desc.as_ref())
+                .await
+                .unwrap();
+            assert_eq!(
+                reopened.visible_value(&key(), &snapshot(1)).await.unwrap(),
+                Some(upgraded)
+            );
+            let key_bytes = i32_bytes(1);
+            let latest = reopened
+                .visible_range(
+                    (Bound::Included(key_bytes.as_slice()), Bound::Unbounded),
+                    &snapshot(2),
+                )
+                .await
+                .unwrap();
+            assert_eq!(latest, vec![(i32_bytes(1), rewritten)]);
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn secondary_index_lookup_rechecks_values_and_rebuilds_on_reopen() {
+        use crate::contract::schema_index::SchemaIndex;
+        use crate::x_engine::api::AlterTable;
+        use mudu_contract::tuple::nullable_tuple::{NullableValue, TupleBuilder};
+        use mudu_type::data_value::DataValue;
+
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let schema = test_schema();
+            let v_oid = schema.columns()[1].get_oid();
+            let schema = schema
+                .altered(&AlterTable::AddIndex(SchemaIndex::new(
+                    "t_v".to_string(),
+                    vec![v_oid],
+                )))
+                .unwrap();
+            let desc = TableInfo::new(schema.clone())
+                .unwrap()
+                .table_desc()
+                .unwrap();
+            let index_oid = desc.indexes()[0].oid;
+            let value = |v: i32| {
+                TupleBuilder::new(desc.value_desc())
+                    .build(&[NullableValue::Value(DataValue::from_i32(v))])
+                    .unwrap()
+            };
+            let probe = |v: i32| {
+                vec![desc
+                    .value_desc()
+                    .get_field_desc(0)
+                    .get(&value(v))
+                    .unwrap()
+                    .to_vec()]
+            };
+            let path = relation_path();
+
+            let relation = Relation::new(schema.id(), 27, path.clone(), desc.as_ref())
+                .await
+                .unwrap();
+            relation
+                .write_value(i32_bytes(1), value(5), 1)
+                .await
+                .unwrap();
+            relation
+                .write_value(i32_bytes(2), value(5), 2)
+                .await
+                .unwrap();
+            relation
+                .write_value(i32_bytes(3), value(6), 3)
+                .await
+                .unwrap();
+            // Row 2 moves away from 5 and row 3 is deleted.
+            relation
+                .write_value(i32_bytes(2), value(7), 4)
+                .await
+                .unwrap();
+            relation.write_delete(i32_bytes(3), 5).await.unwrap();
+
+            let snapshot = WorkerSnapshot::new(5, vec![]);
+            let found = relation
+                .visible_index_lookup(index_oid, &probe(5), &snapshot)
+                .await
+                .unwrap();
+            assert_eq!(found, vec![(i32_bytes(1), value(5))]);
+            // An older snapshot still reaches row 2 through its old value.
+            let found = relation
+                .visible_index_lookup(index_oid, &probe(5), &WorkerSnapshot::new(3, vec![]))
+                .await
+                .unwrap();
+            assert_eq!(found.len(), 2);
+            assert!(relation
+                .visible_index_lookup(index_oid, &probe(6), &snapshot)
+                .await
+                .unwrap()
+                .is_empty());
+            relation.flush_wal_async().await.unwrap();
+            drop(relation);
+
+            let reopened = Relation::new(schema.id(), 27, path.clone(), desc.as_ref())
+                .await
+                .unwrap();
+            let found = reopened
+                .visible_index_lookup(index_oid, &probe(7), &snapshot)
+                .await
+                .unwrap();
+            assert_eq!(found, vec![(i32_bytes(2), value(7))]);
+            assert!(reopened
+                .visible_index_lookup(index_oid + 1, &probe(7), &snapshot)
+                .await
+                .is_err());
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn secondary_index_gc_prunes_entries_of_dropped_versions() {
+        use crate::contract::schema_index::SchemaIndex;
+        use crate::x_engine::api::AlterTable;
+        use mudu_contract::tuple::nullable_tuple::{NullableValue, TupleBuilder};
+        use mudu_type::data_value::DataValue;
+
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let schema = test_schema();
+            let v_oid = schema.columns()[1].get_oid();
+            let schema = schema
+                .altered(&AlterTable::AddIndex(SchemaIndex::new(
+                    "t_v".to_string(),
+                    vec![v_oid],
+                )))
+                .unwrap();
+            let desc = TableInfo::new(schema.clone())
+                .unwrap()
+                .table_desc()
+                .unwrap();
+            let index_oid = desc.indexes()[0].oid;
+            let value = |v: i32| {
+                TupleBuilder::new(desc.value_desc())
+                    .build(&[NullableValue::Value(DataValue::from_i32(v))])
+                    .unwrap()
+            };
+            let probe = |v: i32| {
+                vec![desc
+                    .value_desc()
+                    .get_field_desc(0)
+                    .get(&value(v))
+                    .unwrap()
+                    .to_vec()]
+            };
+
+            let relation = Relation::new(schema.id(), 28, relation_path(), desc.as_ref())
+                .await
+                .unwrap();
+            relation
+                .write_value(i32_bytes(1), value(5), 1)
+                .await
+                .unwrap();
+            relation
+                .write_value(i32_bytes(2), value(5), 2)
+                .await
+                .unwrap();
+            relation
+                .write_value(i32_bytes(3), value(6), 3)
+                .await
+                .unwrap();
+            // Row 1 keeps 5 across an update, row 2 moves to 7, row 3 goes.
+            relation
+                .write_value(i32_bytes(1), value(5), 4)
+                .await
+                .unwrap();
+            relation
+                .write_value(i32_bytes(2), value(7), 5)
+                .await
+                .unwrap();
+            relation.write_delete(i32_bytes(3), 6).await.unwrap();
+            let entries = |v: i32| {
+                relation
+                    .inner
+                    .secondary_index(index_oid)
+                    .unwrap()
+                    .lookup(&probe(v))
+                    .unwrap()
+            };
+
+            // Snapshot 3 still needs the old versions: nothing is pruned.
+            relation.gc_versions_async(3).await.unwrap();
+            assert_eq!(entries(5), vec![i32_bytes(1), i32_bytes(2)]);
+            assert_eq!(entries(6), vec![i32_bytes(3)]);
+
+            relation.gc_versions_async(7).await.unwrap();
+            assert_eq!(entries(5), vec![i32_bytes(1)]);
+            assert!(entries(6).is_empty());
+            assert_eq!(entries(7), vec![i32_bytes(2)]);
+            let found = relation
+                .visible_index_lookup(index_oid, &probe(5), &WorkerSnapshot::new(7, vec![]))
+                .await
+                .unwrap();
+            assert_eq!(found, vec![(i32_bytes(1), value(5))]);
+        })
+        .unwrap()
+    }
+
     #[test]
     #[ignore = "micro-benchmark; run on demand with --ignored --nocapture"]
     fn write_path_breakdown() {
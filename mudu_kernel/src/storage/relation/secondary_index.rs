@@ -0,0 +1,169 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::ops::Bound;
+
+use mudu::common::id::OID;
+use mudu::common::result::RS;
+use mudu_contract::tuple::nullable_tuple::is_null;
+use mudu_contract::tuple::tuple_binary_desc::TupleBinaryDesc as TupleDesc;
+use mudu_sys::sync::SRwLock;
+
+use crate::contract::table_desc::{IndexDesc, TableDesc};
+
+// Binary encoding of one indexed column value; `None` is NULL.
+type IndexDatum = Option<Vec<u8>>;
+
+struct IndexColumn {
+    in_key: bool,
+    datum_index: usize,
+}
+
+/// In-memory secondary index of one relation.
+///
+/// Maps the binary encoding of the indexed columns to the primary keys of
+/// the rows that carried those values. The index only answers equality
+/// lookups, so entries are ordered by their encoded bytes rather than by
+/// value, and a lookup on a left prefix of the indexed columns is one
+/// contiguous range.
+///
+/// Entries are added for every committed version and are not removed when a
+/// row is updated or deleted, because older snapshots may still reach the
+/// row through them. Lookups re-check the visible row against the probed
+/// values. Version GC calls [`SecondaryIndex::prune`] once it drops the
+/// versions of a row, which removes the entries no retained version carries.
+pub(crate) struct SecondaryIndex {
+    oid: OID,
+    columns: Vec<IndexColumn>,
+    key_desc: TupleDesc,
+    value_desc: TupleDesc,
+    entries: SRwLock<Entries>,
+}
+
+#[derive(Default)]
+struct Entries {
+    by_value: BTreeMap<Vec<IndexDatum>, BTreeSet<Vec<u8>>>,
+    // Per primary key, the index key carried from each commit timestamp on.
+    // A run of versions carrying the same index key is kept as one item
+    // stamped with its newest commit.
+    by_key: HashMap<Vec<u8>, BTreeMap<u64, Vec<IndexDatum>>>,
+}
+
+impl SecondaryIndex {
+    pub(crate) fn new(index: &IndexDesc, table_desc: &TableDesc) -> Self {
+        Self {
+            oid: index.oid,
+            columns: index
+                .fields
+                .iter()
+                .map(|field| IndexColumn {
+                    in_key: field.is_primary(),
+                    datum_index: field.datum_index(),
+                })
+                .collect(),
+            key_desc: table_desc.key_desc().clone(),
+            value_desc: table_desc.value_desc().clone(),
+            entries: SRwLock::new(Entries::default()),
+        }
+    }
+
+    pub(crate) fn oid(&self) -> OID {
+        self.oid
+    }
+
+    /// Records that the version of row `key` committed at `timestamp`
+    /// carries `value`.
+    pub(crate) fn insert(&self, key: &[u8], value: &[u8], timestamp: u64) -> RS<()> {
+        let index_key = self.index_key(key, value)?;
+        let mut entries = self.entries.write()?;
+        let versions = entries.by_key.entry(key.to_vec()).or_default();
+        let previous = versions
+            .range(..timestamp)
+            .next_back()
+            .filter(|(_, previous)| **previous == index_key)
+            .map(|(previous, _)| *previous);
+        if let Some(previous) = previous {
+            let _ = versions.remove(&previous);
+        }
+        let _ = versions.insert(timestamp, index_key.clone());
+        let _ = entries
+            .by_value
+            .entry(index_key)
+            .or_default()
+            .insert(key.to_vec());
+        Ok(())
+    }
+
+    /// Drops the entries of row `key` that only versions committed before
+    /// `oldest_retained` carried. Called after version GC pruned those
+    /// versions, so no snapshot can reach the row through them any more.
+    pub(crate) fn prune(&self, key: &[u8], oldest_retained: u64) -> RS<()> {
+        let mut entries = self.entries.write()?;
+        let Some(versions) = entries.by_key.get_mut(key) else {
+            return Ok(());
+        };
+        let retained = versions.split_off(&oldest_retained);
+        let pruned = std::mem::replace(versions, retained);
+        let stale: Vec<Vec<IndexDatum>> = pruned
+            .into_values()
+            .filter(|index_key| !versions.values().any(|kept| kept == index_key))
+            .collect();
+        if versions.is_empty() {
+            let _ = entries.by_key.remove(key);
+        }
+        for index_key in stale {
+            if let Some(primary_keys) = entries.by_value.get_mut(&index_key) {
+                let _ = primary_keys.remove(key);
+                if primary_keys.is_empty() {
+                    let _ = entries.by_value.remove(&index_key);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Primary keys of every entry whose leading indexed columns equal
+    /// `prefix`, in key byte order. The rows may no longer carry the values.
+    pub(crate) fn lookup(&self, prefix: &[Vec<u8>]) -> RS<Vec<Vec<u8>>> {
+        let start: Vec<IndexDatum> = prefix.iter().cloned().map(Some).collect();
+        let entries = self.entries.read()?;
+        let mut keys = BTreeSet::new();
+        for (_, primary_keys) in entries
+            .by_value
+            .range::<Vec<IndexDatum>, _>((Bound::Included(&start), Bound::Unbounded))
+            .take_while(|(index_key, _)| index_key.starts_with(&start))
+        {
+            keys.extend(primary_keys.iter().cloned());
+        }
+        Ok(keys.into_iter().collect())
+    }
+
+    /// Whether the row (`key`, `value`) carries `prefix` in its leading
+    /// indexed columns.
+    pub(crate) fn matches(&self, key: &[u8], value: &[u8], prefix: &[Vec<u8>]) -> RS<bool> {
+        for (column, expected) in self.columns.iter().zip(prefix) {
+            if self.datum(column, key, value)?.as_ref() != Some(expected) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn index_key(&self, key: &[u8], value: &[u8]) -> RS<Vec<IndexDatum>> {
+        self.columns
+            .iter()
+            .map(|column| self.datum(column, key, value))
+            .collect()
+    }
+
+    fn datum(&self, column: &IndexColumn, key: &[u8], value: &[u8]) -> RS<IndexDatum> {
+        let (desc, tuple) = if column.in_key {
+            (&self.key_desc, key)
+        } else {
+            (&self.value_desc, value)
+        };
+        if is_null(tuple, desc, column.datum_index)? {
+            return Ok(None);
+        }
+        let bytes = desc.get_field_desc(column.datum_index).get(tuple)?;
+        Ok(Some(bytes.to_vec()))
+    }
+}
@@ -2,12 +2,15 @@ use crate::storage::page::page_header::NONE_PAGE_ID;
 use crate::storage::page::PageId;
 use crate::wal::pl_batch::{new_pl_batch_writer, PLBatch};
 use crate::wal::pl_entry::{PLEntry, PLFileId, PLOp};
-use crate::wal::worker_log::ChunkedWorkerLogBackend;
+use crate::wal::worker_log::{
+    backup_target_path, copy_file_async, ChunkedWorkerLogBackend, WalCheckpointStats,
+};
 use mudu::common::id::OID;
 use mudu::common::result::RS;
 use mudu::error::ErrorCode;
 use mudu::mudu_error;
 use mudu_sys::contract::async_fs::AsyncFs;
+use mudu_sys::default_sys_io_context;
 use mudu_sys::fs::SysFile;
 use mudu_sys::sync::async_::AMutex;
 use mudu_sys::sync::SMutex;
@@ -25,8 +28,9 @@ const RELATION_WAL_CHUNK_SIZE: u64 = 256 * 1024;
 /// while holding the write latch, so deferred page writes (WAL-first dirty
 /// pages) stay memory-bounded. This is a performance watermark, not a
 /// correctness mechanism: every dirty page is already covered by the PL
-/// WAL, which currently has no GC (WAL chunk reclamation is future work),
-/// so an unflushed dirty page is always replayed on open.
+/// WAL, and a checkpoint (see `TimeSeriesFile::checkpoint_async`) flushes
+/// the dirty pages before it reclaims the chunks covering them, so an
+/// unflushed dirty page is always replayed on open.
 const DIRTY_PAGE_FLUSH_THRESHOLD: usize = 256;
 
 /// Logical identity for one physical time-series file.
@@ -85,6 +89,12 @@ pub struct TimeSeriesFile {
     // `NONE_PAGE_ID` (u64::MAX) encodes `None`; valid page ids never reach it.
     head_page_id: AtomicU64,
     tail_page_id: AtomicU64,
+    // Empty pages still linked into the chain (emptied by compaction or by
+    // deletes). The chain must stay connected on disk, so empty pages are
+    // not unlinked eagerly; the insert path takes one from here instead of
+    // growing the file when it needs a fresh head page. Only touched while
+    // holding `write_latch` (or before the file is shared, on open).
+    free_pages: SMutex<Vec<PageId>>,
     tuple_format_version: u32,
     tuple_schema_hash: u64,
     tuple_flags: u64,
@@ -203,6 +213,53 @@ impl TimeSeriesFile {
         Ok(())
     }
 
+    /// Checkpoints the PL WAL of this file: the WAL is sealed under
+    /// `write_latch`, every page changed up to the seal is written to the
+    /// data file and fsynced, and then the checkpoint is recorded and the
+    /// WAL chunks it covers are deleted, so the next open replays only what
+    /// was logged after it. Files without a WAL have nothing to reclaim.
+    pub(crate) async fn checkpoint_async(&self) -> RS<WalCheckpointStats> {
+        let Some(backend) = &self.wal_backend else {
+            return Ok(WalCheckpointStats::default());
+        };
+        let seal = {
+            let _write_guard = self.write_latch.lock().await;
+            backend.begin_checkpoint_async().await?
+        };
+        let Some(seal) = seal else {
+            return Ok(WalCheckpointStats {
+                wal_bytes: backend.wal_size_bytes_async().await?,
+                ..Default::default()
+            });
+        };
+        self.flush().await?;
+        backend.finish_checkpoint_async(seal, Vec::new()).await
+    }
+
+    /// Copies this file and its PL WAL into `target_root` for an online
+    /// backup, at their paths relative to `source_root`. The data file is
+    /// copied under `dirty_flush_latch`, so no page write lands mid-copy and
+    /// every copied page is a whole image; the WAL is copied afterwards and
+    /// so covers every change those pages hold, which the copy replays on
+    /// open like after a crash.
+    pub(crate) async fn backup_async(&self, source_root: &Path, target_root: &Path) -> RS<()> {
+        let fs = self
+            .fs
+            .clone()
+            .unwrap_or_else(|| default_sys_io_context().provider_arc().fs_arc());
+        {
+            let _guard = self.dirty_flush_latch.lock().await;
+            let target = backup_target_path(source_root, target_root, &self.path)?;
+            copy_file_async(fs.as_ref(), &self.path, &target).await?;
+        }
+        if let Some(backend) = &self.wal_backend {
+            let layout = backend.layout()?;
+            let target = backup_target_path(source_root, target_root, &layout.log_dir)?;
+            backend.backup_async(&target).await?;
+        }
+        Ok(())
+    }
+
     /// Writes every dirty page image from `page_cache` to the data file and
     /// clears its dirty mark. A page whose write fails keeps its mark so
     /// the next round retries it; a page re-dirtied while its image was in
@@ -370,6 +427,7 @@ impl TimeSeriesFile {
     }
 }
 
+mod compact;
 mod io;
 mod open;
 mod page;
@@ -0,0 +1,97 @@
+use super::page::build_entries_page_image;
+use super::plan::{PlannedPageWrite, TimeSeriesFileMutationPlan};
+use super::TimeSeriesFile;
+use crate::storage::page::page_block_ref::PageBlockRef;
+use crate::storage::page::PageId;
+use mudu::common::result::RS;
+use mudu_utils::scoped_task_trace;
+
+impl TimeSeriesFile {
+    /// Empties every page whose records are all dead according to
+    /// `is_dead(timestamp, tuple_id)` and returns the number of pages
+    /// emptied.
+    ///
+    /// Emptied pages keep their chain links (open requires a connected
+    /// chain) and go to the free list, where the insert path picks them up
+    /// again instead of growing the file. Pages holding any live record are
+    /// left untouched: records are never moved between pages here, so the
+    /// chain order readers rely on is preserved.
+    ///
+    /// Candidates are found by a latch-free walk and re-checked under the
+    /// write latch, so concurrent writers only block for the rewrite itself.
+    /// The rewrite goes through the PL WAL like any other mutation.
+    pub async fn compact_dead_pages<F>(&self, is_dead: F) -> RS<usize>
+    where
+        F: Fn(u64, u64) -> bool,
+    {
+        scoped_task_trace!();
+        let mut candidates = Vec::new();
+        let mut current = self.head_page_id();
+        while let Some(page_id) = current {
+            let page_buf = self.read_page(page_id).await?;
+            let page = PageBlockRef::try_new(&page_buf)?;
+            if page_records_dead(&page, &is_dead)? {
+                candidates.push(page_id);
+            }
+            current = page.active_next_page()?;
+        }
+        if candidates.is_empty() {
+            return Ok(0);
+        }
+
+        let emptied = {
+            let _write_guard = self.write_latch.lock().await;
+            let mut plan = TimeSeriesFileMutationPlan::default();
+            for page_id in candidates {
+                let page_buf = self.read_page(page_id).await?;
+                let page = PageBlockRef::try_new(&page_buf)?;
+                // A writer may have added a live record since the walk.
+                if !page_records_dead(&page, &is_dead)? {
+                    continue;
+                }
+                let header = page.header()?;
+                let image = build_entries_page_image(
+                    page_id,
+                    header.prev_page(),
+                    header.next_page(),
+                    &[],
+                    self.tuple_format_version,
+                    self.tuple_schema_hash,
+                    self.tuple_flags,
+                )?;
+                plan.page_writes.push(PlannedPageWrite { page_id, image });
+            }
+            let emptied: Vec<PageId> = plan.page_writes.iter().map(|write| write.page_id).collect();
+            if !emptied.is_empty() {
+                self.persist_plan(plan).await?;
+                self.free_pages.lock()?.extend(emptied.iter().copied());
+            }
+            emptied.len()
+        };
+        self.flush_dirty_pages_if_over_threshold().await?;
+        Ok(emptied)
+    }
+
+    /// Number of emptied pages waiting on the free list for reuse.
+    pub fn free_page_count(&self) -> RS<usize> {
+        Ok(self.free_pages.lock()?.len())
+    }
+}
+
+/// Whether `page` holds at least one record and every record is dead.
+fn page_records_dead<F>(page: &PageBlockRef<'_>, is_dead: &F) -> RS<bool>
+where
+    F: Fn(u64, u64) -> bool,
+{
+    let count = page.slot_count()?;
+    if count == 0 {
+        return Ok(false);
+    }
+    for slot_index in 0..count {
+        let slot = page.slot_ref(slot_index)?;
+        if !is_dead(slot.timestamp(), slot.tuple_id()) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
@@ -242,7 +242,7 @@ impl TimeSeriesFile {
         }
 
         let page_count = PageId::from(len / PAGE_SIZE as u64);
-        let (head_page_id, tail_page_id) =
+        let (head_page_id, tail_page_id, free_pages) =
             load_chain_metadata(&file, page_count, tuple_schema_hash).await?;
         Ok(Self {
             fs: Some(fs),
@@ -257,6 +257,7 @@ impl TimeSeriesFile {
             page_count: AtomicU64::new(page_count.as_u64()),
             head_page_id: AtomicU64::new(raw_page_id(head_page_id)),
             tail_page_id: AtomicU64::new(raw_page_id(tail_page_id)),
+            free_pages: SMutex::new(free_pages),
             tuple_format_version: if tuple_schema_hash != 0 { 1 } else { 0 },
             tuple_schema_hash,
             tuple_flags: 0,
@@ -293,7 +294,7 @@ impl TimeSeriesFile {
         }
 
         let page_count = PageId::from(len / PAGE_SIZE as u64);
-        let (head_page_id, tail_page_id) =
+        let (head_page_id, tail_page_id, free_pages) =
             load_chain_metadata(&file, page_count, tuple_schema_hash).await?;
         Ok(Self {
             fs: None,
@@ -308,6 +309,7 @@ impl TimeSeriesFile {
             page_count: AtomicU64::new(page_count.as_u64()),
             head_page_id: AtomicU64::new(raw_page_id(head_page_id)),
             tail_page_id: AtomicU64::new(raw_page_id(tail_page_id)),
+            free_pages: SMutex::new(free_pages),
             tuple_format_version: if tuple_schema_hash != 0 { 1 } else { 0 },
             tuple_schema_hash,
             tuple_flags: 0,
@@ -319,9 +321,9 @@ async fn load_chain_metadata(
     file: &SysFile,
     page_count: PageId,
     expected_schema_hash: u64,
-) -> RS<(Option<PageId>, Option<PageId>)> {
+) -> RS<(Option<PageId>, Option<PageId>, Vec<PageId>)> {
     if page_count == 0 {
-        return Ok((None, None));
+        return Ok((None, None, Vec::new()));
     }
 
     let mut headers = Vec::with_capacity(page_count.as_usize());
@@ -378,6 +380,7 @@ async fn load_chain_metadata(
     let mut current = head;
     let mut visited = vec![false; page_count.as_usize()];
     let mut prev_non_empty_min = None;
+    let mut free_pages = Vec::new();
     loop {
         if visited[current.as_usize()] {
             return Err(mudu_error!(
@@ -412,6 +415,8 @@ async fn load_chain_metadata(
                 }
             }
             prev_non_empty_min = Some(min_ts);
+        } else {
+            free_pages.push(current);
         }
 
         match (header.next_page() != NONE_PAGE_ID).then_some(header.next_page()) {
@@ -427,5 +432,5 @@ async fn load_chain_metadata(
         ));
     }
 
-    Ok((Some(head), Some(tail)))
+    Ok((Some(head), Some(tail), free_pages))
 }
@@ -1,6 +1,7 @@
 use super::io::{page_offset, read_file_exact};
 use super::{TimeSeriesFile, TimeSeriesRecord};
 use crate::storage::page::page_block_ref::{PageBlockRef, PAGE_SIZE};
+use crate::storage::page::page_reads::record_page_read;
 use crate::storage::page::PageId;
 use mudu::common::result::RS;
 use mudu::error::ErrorCode;
@@ -77,11 +78,17 @@ impl TimeSeriesFile {
                 .cmp(&right.timestamp)
                 .then_with(|| left.tuple_id.cmp(&right.tuple_id))
         });
+        // A writer that reuses a free page relinks it at the head while this
+        // scan may be walking past it, so the walk can revisit pages.
+        rows.dedup_by(|right, left| {
+            left.timestamp == right.timestamp && left.tuple_id == right.tuple_id
+        });
         Ok(rows)
     }
 
     pub(super) async fn read_page(&self, page_id: PageId) -> RS<Arc<Vec<u8>>> {
         scoped_task_trace!();
+        record_page_read();
         // Cache first: writers publish every new or updated page image into
         // the cache before the chain metadata atomics, so a cache hit is
         // always authoritative — including a freshly allocated page whose
@@ -1033,6 +1033,43 @@ fn wal_recovers_unflushed_mixed_batches_and_continued_writes() {
     .unwrap()
 }
 
+#[test]
+fn checkpoint_reclaims_wal_and_recovery_replays_later_writes() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let base = temp_relation_base("checkpoint");
+        let identity = TimeSeriesFileIdentity {
+            partition_id: 81,
+            table_id: 83,
+            file_index: 0,
+        };
+        let mut expected = ExpectedRows::new();
+
+        let file = TimeSeriesFile::open_relation_file(&base, identity.clone(), 0xd1a7_0003, true)
+            .await
+            .unwrap();
+        write_mixed_batches(&file, &mut expected, 0x50, 0).await;
+        let stats = file.checkpoint_async().await.unwrap();
+        assert_eq!(file.dirty_page_count(), 0);
+        assert!(stats.reclaimed_chunks > 0);
+        assert!(stats.reclaimed_bytes > 0);
+        assert_eq!(stats.wal_bytes, 0);
+
+        // Writes after the checkpoint live only in the WAL; recovery must
+        // replay them on top of the checkpointed data file.
+        write_mixed_batches(&file, &mut expected, 0x60, 1000).await;
+        file.flush_wal_async().await.unwrap();
+        drop(file);
+
+        let file = TimeSeriesFile::open_relation_file(&base, identity, 0xd1a7_0003, false)
+            .await
+            .unwrap();
+        verify_file_contents(&file, &expected).await;
+        file.close().await.unwrap();
+        mudu_sys::fs::sync::remove_dir_all(base).unwrap();
+    })
+    .unwrap()
+}
+
 #[test]
 fn wal_replay_after_full_flush_leaves_data_file_byte_stable() {
     mudu_sys::task::async_::block_on_tokio_current_thread(async move {
@@ -1136,3 +1173,158 @@ fn open_rejects_page_with_corrupted_checksum_on_disk() {
     })
     .unwrap()
 }
+
+#[test]
+fn compaction_empties_dead_pages_and_inserts_reuse_them() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let base = temp_relation_base("compact");
+        let identity = TimeSeriesFileIdentity {
+            partition_id: 85,
+            table_id: 87,
+            file_index: 0,
+        };
+        let file = TimeSeriesFile::open_relation_file(&base, identity.clone(), 0xd1a7_0005, true)
+            .await
+            .unwrap();
+        const VERSIONS: u64 = 64;
+        const CUTOFF: u64 = 48;
+        for ts in 1..=VERSIONS {
+            file.insert(ts, 1, &payload(ts as u8, 512)).await.unwrap();
+        }
+        let page_count = file.page_count();
+
+        let emptied = file
+            .compact_dead_pages(|ts, _tuple_id| ts < CUTOFF)
+            .await
+            .unwrap();
+        assert!(emptied > 0);
+        assert_eq!(file.free_page_count().unwrap(), emptied);
+        assert_eq!(file.page_count(), page_count);
+        for ts in CUTOFF..=VERSIONS {
+            assert!(file.get(ts, 1).await.unwrap().is_some());
+        }
+        assert!(file.get(1, 1).await.unwrap().is_none());
+        // Pages holding a live record are never touched.
+        assert_eq!(
+            file.compact_dead_pages(|ts, _tuple_id| ts < CUTOFF)
+                .await
+                .unwrap(),
+            0
+        );
+
+        // New head pages come off the free list until it runs dry.
+        let mut ts = VERSIONS;
+        while file.free_page_count().unwrap() > 0 && ts < VERSIONS * 4 {
+            ts += 1;
+            file.insert(ts, 1, &payload(ts as u8, 512)).await.unwrap();
+            assert_eq!(file.page_count(), page_count);
+        }
+        assert_eq!(file.free_page_count().unwrap(), 0);
+        let expected = file.scan_range(0, u64::MAX).await.unwrap();
+        assert!(expected.len() < ts as usize);
+        file.flush_wal_async().await.unwrap();
+        drop(file);
+
+        // WAL replay restores the emptied and relinked pages, and the chain
+        // still validates on open.
+        let file = TimeSeriesFile::open_relation_file(&base, identity, 0xd1a7_0005, false)
+            .await
+            .unwrap();
+        let recovered = file.scan_range(0, u64::MAX).await.unwrap();
+        let key =
+            |row: &super::TimeSeriesRecord| (row.timestamp, row.tuple_id, row.payload.clone());
+        assert_eq!(
+            recovered.iter().map(key).collect::<Vec<_>>(),
+            expected.iter().map(key).collect::<Vec<_>>()
+        );
+        file.close().await.unwrap();
+        mudu_sys::fs::sync::remove_dir_all(base).unwrap();
+    })
+    .unwrap()
+}
+
+#[cfg(feature = "ds")]
+#[test]
+fn sim_crash_keeps_acknowledged_rows() {
+    use mudu_sys::sim::{sim_seeds, SimFsFaults, SimRng, Simulation};
+    use mudu_sys::SysIoContext;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    for seed in sim_seeds() {
+        let sim = Simulation::new(seed);
+        let fs = sim.new_fs(SimFsFaults {
+            torn_writes: true,
+            ..SimFsFaults::default()
+        });
+        let sys = SysIoContext::new(sim.node(IpAddr::V4(Ipv4Addr::LOCALHOST), fs.clone()));
+        let mut rng = SimRng::new(seed).fork(1);
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let base = std::path::PathBuf::from("/sim/ts");
+            let identity = TimeSeriesFileIdentity {
+                partition_id: 3,
+                table_id: 5,
+                file_index: 0,
+            };
+            let file = TimeSeriesFile::open_relation_file_with_sys_io_context(
+                sys.clone(),
+                &base,
+                identity.clone(),
+                0x5eed,
+                true,
+            )
+            .await
+            .unwrap();
+            file.flush_wal_async().await.unwrap();
+            file.flush().await.unwrap();
+
+            // PL frames are only written by the WAL flush, so the power cut
+            // lands in (or tears) a WAL write; acknowledged rows must survive.
+            fs.crash_after(rng.range_inclusive(1, 48));
+            let mut inserted = Vec::new();
+            let mut acked = 0;
+            for tuple_id in 1..=32u64 {
+                let timestamp = rng.range_inclusive(1, 1000);
+                let payload = format!("row-{tuple_id}").into_bytes();
+                if file.insert(timestamp, tuple_id, &payload).await.is_err() {
+                    break;
+                }
+                inserted.push((timestamp, tuple_id, payload));
+                if rng.chance(0.3) {
+                    if file.flush_wal_async().await.is_err() {
+                        break;
+                    }
+                    acked = inserted.len();
+                }
+            }
+            drop(file);
+            fs.restart();
+
+            let reopened = TimeSeriesFile::open_relation_file_with_sys_io_context(
+                sys, &base, identity, 0x5eed, false,
+            )
+            .await
+            .unwrap_or_else(|e| panic!("seed {seed}: recovery failed: {e}"));
+            for (timestamp, tuple_id, payload) in &inserted[..acked] {
+                let row = reopened.get(*timestamp, *tuple_id).await.unwrap();
+                assert_eq!(
+                    row.map(|row| row.payload).as_ref(),
+                    Some(payload),
+                    "seed {seed}: acknowledged row {tuple_id} lost"
+                );
+            }
+            for row in reopened.scan_range(0, u64::MAX).await.unwrap() {
+                assert!(
+                    inserted.iter().any(|(timestamp, tuple_id, payload)| (
+                        *timestamp, *tuple_id, payload
+                    ) == (
+                        row.timestamp,
+                        row.tuple_id,
+                        &row.payload
+                    )),
+                    "seed {seed}: recovered a row that was never inserted"
+                );
+            }
+        })
+        .unwrap();
+    }
+}
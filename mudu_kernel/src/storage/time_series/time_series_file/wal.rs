@@ -10,7 +10,9 @@ use crate::wal::pl_batch::{
 use crate::wal::pl_entry::{PLEntry, PLFileId, PLOp, PageDelta};
 use crate::wal::typed_worker_log::AsyncWorkerLogRecoveryHandler;
 use crate::wal::worker_log::AsyncWorkerLogRecoverySource;
-use crate::wal::worker_log::{ChunkedWorkerLogBackend, WorkerLogBackend, WorkerLogLayout};
+use crate::wal::worker_log::{
+    ChunkedWorkerLogBackend, WalCheckpoint, WorkerLogBackend, WorkerLogLayout,
+};
 use async_trait::async_trait;
 use mudu::common::id::OID;
 use mudu::common::result::RS;
@@ -268,6 +270,15 @@ impl AsyncWorkerLogRecoverySource for RelationWalRecoverySource {
     async fn read_chunk(&mut self, path: &Path) -> RS<Vec<u8>> {
         self.fs.as_ref().read_all(path).await
     }
+
+    // The checkpoint carries no payload: it only records that every page
+    // change at or below its LSN is in the data file.
+    async fn checkpoint(&mut self) -> RS<Option<WalCheckpoint>> {
+        self.backend
+            .layout()?
+            .read_checkpoint_async(self.fs.as_ref())
+            .await
+    }
 }
 
 struct RelationWalRecoveryHandler {
@@ -56,6 +56,61 @@ impl<'a> WriteOverlay<'a> {
         page_id
     }
 
+    /// Takes an empty page off the file's free list and unlinks it from the
+    /// batch's chain so the caller can link it in again as a fresh page.
+    /// `anchor` is the page the caller links the fresh page next to; it must
+    /// stay in place. Stale free-list entries (the anchor itself, or a page
+    /// that holds records again) are dropped. Returns `None` when no free
+    /// page is left.
+    async fn reuse_free_page(&mut self, anchor: PageId) -> RS<Option<PageId>> {
+        loop {
+            let Some(page_id) = self.file.free_pages.lock()?.pop() else {
+                return Ok(None);
+            };
+            if page_id == anchor {
+                continue;
+            }
+            let page_buf = self.read_page(page_id).await?;
+            let page = PageBlockRef::try_new(&page_buf)?;
+            if page.slot_count()? != 0 {
+                continue;
+            }
+            let prev_page_id = page.active_prev_page()?;
+            let next_page_id = page.active_next_page()?;
+            match prev_page_id {
+                Some(prev_page_id) => self.set_next_link(prev_page_id, next_page_id).await?,
+                None => self.head_page_id = next_page_id,
+            }
+            match next_page_id {
+                Some(next_page_id) => self.set_prev_link(next_page_id, prev_page_id).await?,
+                None => self.tail_page_id = prev_page_id,
+            }
+            return Ok(Some(page_id));
+        }
+    }
+
+    async fn set_prev_link(&mut self, page_id: PageId, prev_page_id: Option<PageId>) -> RS<()> {
+        let mut page_buf = self.read_page(page_id).await?.as_ref().clone();
+        let header = PageBlockRef::try_new(&page_buf)?.header()?;
+        {
+            let mut page = PageBlockRefMut::new(&mut page_buf);
+            page.set_page_links(prev_page_id.unwrap_or(NONE_PAGE_ID), header.next_page())?;
+        }
+        self.write_page_image(page_id, page_buf);
+        Ok(())
+    }
+
+    async fn set_next_link(&mut self, page_id: PageId, next_page_id: Option<PageId>) -> RS<()> {
+        let mut page_buf = self.read_page(page_id).await?.as_ref().clone();
+        let header = PageBlockRef::try_new(&page_buf)?.header()?;
+        {
+            let mut page = PageBlockRefMut::new(&mut page_buf);
+            page.set_page_links(header.prev_page(), next_page_id.unwrap_or(NONE_PAGE_ID))?;
+        }
+        self.write_page_image(page_id, page_buf);
+        Ok(())
+    }
+
     /// Returns the newest image of `page_id`: the pending batch image when
     /// the batch already rewrote the page, otherwise the file's published
     /// image (page cache or data file).
@@ -383,9 +438,11 @@ impl TimeSeriesFile {
         Ok(entries)
     }
 
-    /// Plans a record insertion into a freshly allocated page linked before
+    /// Plans a record insertion into a fresh page linked before
     /// `next_page_id` (updating the head pointer when there is no previous
-    /// page).
+    /// page). The fresh page is an emptied page taken off the free list when
+    /// one is available (see [`WriteOverlay::reuse_free_page`]), so a file
+    /// whose old versions are compacted away stops growing.
     async fn plan_insert_before_page(
         &self,
         overlay: &mut WriteOverlay<'_>,
@@ -394,7 +451,10 @@ impl TimeSeriesFile {
         tuple_id: u64,
         payload: &[u8],
     ) -> RS<()> {
-        let page_id = overlay.alloc_page_id();
+        let page_id = match overlay.reuse_free_page(next_page_id).await? {
+            Some(page_id) => page_id,
+            None => overlay.alloc_page_id(),
+        };
         let next_page_buf = overlay.read_page(next_page_id).await?;
         let next_page = PageBlockRef::try_new(&next_page_buf)?;
         let prev_page_id = next_page.active_prev_page()?;
@@ -1,7 +1,7 @@
-use crate::wal::log_frame::decode_entries_with_pending;
+use crate::wal::log_frame::{decode_entries_with_pending, frame_lsn};
 use crate::wal::lsn::LSN;
 use crate::wal::worker_log::{
-    scan_valid_frame_prefix, AsyncWorkerLogRecoverySource, WorkerLogBackend,
+    scan_valid_frame_prefix, AsyncWorkerLogRecoverySource, WalCheckpoint, WorkerLogBackend,
     WorkerLogRecoverySource,
 };
 use async_trait::async_trait;
@@ -21,6 +21,12 @@ where
 {
     async fn handle_entry(&self, entry: L, start_lsn: LSN) -> RS<()>;
 
+    /// Restores the state recorded by `checkpoint` before the entries after
+    /// it are replayed.
+    async fn restore_checkpoint(&self, _checkpoint: &WalCheckpoint) -> RS<()> {
+        Ok(())
+    }
+
     fn finish(&self) -> RS<()> {
         Ok(())
     }
@@ -33,6 +39,12 @@ where
 {
     async fn handle_entry(&self, entry: L, start_lsn: LSN) -> RS<()>;
 
+    /// Restores the state recorded by `checkpoint` before the entries after
+    /// it are replayed.
+    async fn restore_checkpoint(&self, _checkpoint: &WalCheckpoint) -> RS<()> {
+        Ok(())
+    }
+
     async fn finish(&self) -> RS<()> {
         Ok(())
     }
@@ -64,6 +76,25 @@ fn decode_chunk_frames_for_recovery(path: &Path, bytes: &[u8]) -> Vec<Vec<u8>> {
     prefix.frames
 }
 
+/// Drops the frames covered by the checkpoint at `checkpoint_lsn`. A
+/// checkpoint is taken between entries, so no multi-part entry straddles
+/// its LSN.
+pub(crate) fn skip_checkpointed_frames(
+    frames: Vec<Vec<u8>>,
+    checkpoint_lsn: Option<LSN>,
+) -> RS<Vec<Vec<u8>>> {
+    let Some(checkpoint_lsn) = checkpoint_lsn else {
+        return Ok(frames);
+    };
+    let mut kept = Vec::with_capacity(frames.len());
+    for frame in frames {
+        if frame_lsn(&frame)? > checkpoint_lsn {
+            kept.push(frame);
+        }
+    }
+    Ok(kept)
+}
+
 /// A multi-part entry whose remaining frames never made it to disk (the
 /// writer crashed mid-entry) is dropped at end-of-log with a warning
 /// instead of failing recovery.
@@ -134,6 +165,11 @@ where
     where
         S: WorkerLogRecoverySource,
     {
+        let checkpoint = source.checkpoint().await?;
+        if let Some(checkpoint) = &checkpoint {
+            self.handler.restore_checkpoint(checkpoint).await?;
+        }
+        let checkpoint_lsn = checkpoint.map(|checkpoint| checkpoint.lsn);
         let chunk_paths = source.chunk_paths_sorted().await?;
         let mut pending_frames = Vec::new();
         let mut pending_start_lsn = None;
@@ -142,7 +178,10 @@ where
             if bytes.is_empty() {
                 continue;
             }
-            let frames = decode_chunk_frames_for_recovery(path.as_path(), &bytes);
+            let frames = skip_checkpointed_frames(
+                decode_chunk_frames_for_recovery(path.as_path(), &bytes),
+                checkpoint_lsn,
+            )?;
             let entries = decode_entries_with_pending::<L>(
                 &frames,
                 &mut pending_frames,
@@ -162,6 +201,11 @@ where
     where
         S: AsyncWorkerLogRecoverySource,
     {
+        let checkpoint = source.checkpoint().await?;
+        if let Some(checkpoint) = &checkpoint {
+            self.handler.restore_checkpoint(checkpoint).await?;
+        }
+        let checkpoint_lsn = checkpoint.map(|checkpoint| checkpoint.lsn);
         let chunk_paths = source.chunk_paths_sorted().await?;
         let mut pending_frames = Vec::new();
         let mut pending_start_lsn = None;
@@ -170,7 +214,10 @@ where
             if bytes.is_empty() {
                 continue;
             }
-            let frames = decode_chunk_frames_for_recovery(path.as_path(), &bytes);
+            let frames = skip_checkpointed_frames(
+                decode_chunk_frames_for_recovery(path.as_path(), &bytes),
+                checkpoint_lsn,
+            )?;
             let entries = decode_entries_with_pending::<L>(
                 &frames,
                 &mut pending_frames,
@@ -192,6 +239,11 @@ where
         AH: AsyncWorkerLogRecoveryHandler<L>,
     {
         scoped_task_trace!();
+        let checkpoint = source.checkpoint().await?;
+        if let Some(checkpoint) = &checkpoint {
+            handler.restore_checkpoint(checkpoint).await?;
+        }
+        let checkpoint_lsn = checkpoint.map(|checkpoint| checkpoint.lsn);
         let chunk_paths = source.chunk_paths_sorted().await?;
         let mut pending_frames = Vec::new();
         let mut pending_start_lsn = None;
@@ -200,7 +252,10 @@ where
             if bytes.is_empty() {
                 continue;
             }
-            let frames = decode_chunk_frames_for_recovery(path.as_path(), &bytes);
+            let frames = skip_checkpointed_frames(
+                decode_chunk_frames_for_recovery(path.as_path(), &bytes),
+                checkpoint_lsn,
+            )?;
             let entries = decode_entries_with_pending::<L>(
                 &frames,
                 &mut pending_frames,
@@ -249,6 +304,7 @@ mod tests {
     #[derive(Default)]
     struct CollectingHandler {
         entries: SMutex<Vec<(LSN, TestEntry)>>,
+        checkpoints: SMutex<Vec<WalCheckpoint>>,
     }
 
     #[async_trait]
@@ -257,6 +313,11 @@ mod tests {
             self.entries.lock().unwrap().push((start_lsn, entry));
             Ok(())
         }
+
+        async fn restore_checkpoint(&self, checkpoint: &WalCheckpoint) -> RS<()> {
+            self.checkpoints.lock().unwrap().push(checkpoint.clone());
+            Ok(())
+        }
     }
 
     #[async_trait]
@@ -271,6 +332,13 @@ mod tests {
         paths: Vec<PathBuf>,
     }
 
+    // Serves the chunks as-is with a given checkpoint, as if the chunks it
+    // covers had not been deleted yet.
+    struct CheckpointedRecoverySource {
+        paths: Vec<PathBuf>,
+        checkpoint: WalCheckpoint,
+    }
+
     struct NoopHandler;
 
     #[async_trait]
@@ -290,6 +358,21 @@ mod tests {
         }
     }
 
+    #[async_trait]
+    impl WorkerLogRecoverySource for CheckpointedRecoverySource {
+        async fn chunk_paths_sorted(&self) -> RS<Vec<PathBuf>> {
+            Ok(self.paths.clone())
+        }
+
+        async fn read_chunk(&self, path: &Path) -> RS<Vec<u8>> {
+            mudu_sys::fs::sync::read(path)
+        }
+
+        async fn checkpoint(&self) -> RS<Option<WalCheckpoint>> {
+            Ok(Some(self.checkpoint.clone()))
+        }
+    }
+
     #[async_trait]
     impl AsyncWorkerLogRecoverySource for FileRecoverySource {
         async fn chunk_paths_sorted(&mut self) -> RS<Vec<PathBuf>> {
@@ -335,6 +418,44 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    fn typed_worker_log_recovery_restores_checkpoint_and_skips_covered_entries() {
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let dir = temp_dir().join(format!("typed_worker_log_checkpoint_{}", gen_oid()));
+            let raw =
+                ChunkedWorkerLogBackend::new(WorkerLogLayout::new(dir, gen_oid(), 256).unwrap())
+                    .await
+                    .unwrap();
+            let handler = Arc::new(CollectingHandler::default());
+            let log = TypedWorkerLog::new(raw.clone(), handler.clone());
+
+            let entries = (1..=3)
+                .map(|id| TestEntry {
+                    id,
+                    payload: vec![id as u8; 32],
+                })
+                .collect::<Vec<_>>();
+            for entry in &entries {
+                log.append(entry).await.unwrap();
+            }
+            raw.flush_async().await.unwrap();
+            let checkpoint = WalCheckpoint {
+                lsn: LSN::new(1),
+                payload: b"state".to_vec(),
+            };
+            let mut source = CheckpointedRecoverySource {
+                paths: raw.chunk_paths_sorted().await.unwrap(),
+                checkpoint: checkpoint.clone(),
+            };
+            log.recover(&mut source).await.unwrap();
+
+            assert_eq!(*handler.checkpoints.lock().unwrap(), vec![checkpoint]);
+            let recovered = handler.entries.lock().unwrap().clone();
+            assert_eq!(recovered, vec![(LSN::new(2), entries[2].clone())]);
+        })
+        .unwrap()
+    }
+
     #[test]
     fn typed_worker_log_appends_and_recovers_generic_entries_async() {
         mudu_sys::task::async_::block_on_tokio_current_thread(async move {
@@ -1,8 +1,11 @@
 use crate::wal::log_frame::{frame_len, split_frame};
 use crate::wal::lsn::LSN;
+pub(crate) use crate::wal::worker_wal_backend::{
+    backup_target_path, copy_file_async, synced_lsn_of,
+};
 pub use crate::wal::worker_wal_backend::{
-    WalSyncPolicy, WorkerLogBatching, WorkerLogLayout, WorkerLogTail,
-    WorkerWALBackend as ChunkedWorkerLogBackend,
+    WalCheckpoint, WalCheckpointStats, WalSyncPolicy, WorkerLogBackup, WorkerLogBatching,
+    WorkerLogLayout, WorkerLogTail, WorkerWALBackend as ChunkedWorkerLogBackend,
 };
 use async_trait::async_trait;
 use mudu::common::result::RS;
@@ -22,15 +25,25 @@ pub trait WorkerLogBackend: Clone + Send + Sync {
 }
 
 #[async_trait]
-pub trait WorkerLogRecoverySource {
+pub trait WorkerLogRecoverySource: Sync {
     async fn chunk_paths_sorted(&self) -> RS<Vec<PathBuf>>;
     async fn read_chunk(&self, path: &Path) -> RS<Vec<u8>>;
+
+    /// The checkpoint recovery starts from; `None` replays every chunk.
+    async fn checkpoint(&self) -> RS<Option<WalCheckpoint>> {
+        Ok(None)
+    }
 }
 
 #[async_trait]
 pub trait AsyncWorkerLogRecoverySource: Send {
     async fn chunk_paths_sorted(&mut self) -> RS<Vec<PathBuf>>;
     async fn read_chunk(&mut self, path: &Path) -> RS<Vec<u8>>;
+
+    /// The checkpoint recovery starts from; `None` replays every chunk.
+    async fn checkpoint(&mut self) -> RS<Option<WalCheckpoint>> {
+        Ok(None)
+    }
 }
 
 pub fn decode_frames(payload: &[u8]) -> RS<Vec<Vec<u8>>> {
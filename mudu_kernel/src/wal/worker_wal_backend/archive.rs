@@ -0,0 +1,245 @@
+use crate::wal::log_frame::{deserialize_entry, frame_lsn, split_frame};
+use crate::wal::lsn::LSN;
+use crate::wal::worker_log::scan_valid_frame_prefix;
+use mudu::common::id::OID;
+use mudu::common::result::RS;
+use mudu::error::ErrorCode;
+use mudu::mudu_error;
+use mudu_sys::contract::async_fs::AsyncFs;
+use mudu_sys::contract::file_options::FileOptions;
+use mudu_sys::fs::sync as sync_fs;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use super::backend::WorkerWALBackend;
+use super::layout::WorkerLogLayout;
+
+/// Bytes read and written per step when a file is copied.
+const COPY_BLOCK_SIZE: u64 = 1024 * 1024;
+
+/// The part of an online backup that belongs to one worker log, recorded in
+/// the backup manifest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkerLogBackup {
+    #[serde(with = "oid_as_string")]
+    pub log_oid: OID,
+    /// LSN of the checkpoint copied with the log, if the log had one.
+    pub checkpoint_lsn: Option<u64>,
+    /// Every entry up to this LSN was durable before the log was copied.
+    /// A restore never cuts the log below it: the relation files of the
+    /// backup were copied earlier and may already reflect those entries.
+    pub end_lsn: u64,
+}
+
+/// The manifest is JSON, whose numbers cannot hold a 128-bit OID; it keeps
+/// them as decimal strings.
+mod oid_as_string {
+    use mudu::common::id::OID;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(oid: &OID, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(oid)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<OID, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Copies `from` to `to` in [`COPY_BLOCK_SIZE`] steps and fsyncs the copy.
+/// An existing `to` is replaced. The length of `from` is taken once at the
+/// start, so bytes appended to it during the copy are left out. Returns the
+/// number of bytes copied.
+pub(crate) async fn copy_file_async(fs: &dyn AsyncFs, from: &Path, to: &Path) -> RS<u64> {
+    if let Some(parent) = to.parent() {
+        fs.create_dir_all(parent).await?;
+    }
+    let source = fs.open(from, FileOptions::read_only()).await?;
+    let len = source.file_len().await?;
+    fs.remove_file_if_exists(to).await?;
+    let target = fs.open(to, FileOptions::read_write_create()).await?;
+    let mut offset = 0u64;
+    while offset < len {
+        let step = (len - offset).min(COPY_BLOCK_SIZE);
+        let block = source.read_exact_at(offset, step as usize).await?;
+        target.write_all_at(offset, &block).await?;
+        offset += step;
+    }
+    target.fsync().await?;
+    target.close().await?;
+    source.close().await?;
+    Ok(len)
+}
+
+/// Where `path`, a file under `source_root`, goes in a backup rooted at
+/// `target_root`.
+pub(crate) fn backup_target_path(
+    source_root: &Path,
+    target_root: &Path,
+    path: &Path,
+) -> RS<PathBuf> {
+    let relative = path.strip_prefix(source_root).map_err(|e| {
+        mudu_error!(
+            ErrorCode::InvalidArgument,
+            format!("{} is outside of {}", path.display(), source_root.display()),
+            e
+        )
+    })?;
+    Ok(target_root.join(relative))
+}
+
+impl WorkerLogLayout {
+    /// Copies chunk `sequence` to the archive directory under its own file
+    /// name. Does nothing when the log has no archive directory.
+    pub(crate) async fn archive_chunk_async(&self, fs: &dyn AsyncFs, sequence: u64) -> RS<()> {
+        let Some(archive_dir) = self.archive_dir() else {
+            return Ok(());
+        };
+        let path = self.chunk_path(sequence);
+        let Some(file_name) = path.file_name() else {
+            return Ok(());
+        };
+        let bytes = copy_file_async(fs, &path, &archive_dir.join(file_name)).await?;
+        debug!(
+            log_oid = self.log_oid(),
+            sequence,
+            bytes,
+            archive_dir = %archive_dir.display(),
+            "worker log chunk archived"
+        );
+        Ok(())
+    }
+
+    /// True when `path` names a chunk or a checkpoint of this log.
+    pub fn is_log_file(&self, path: &Path) -> bool {
+        self.parse_chunk_sequence(path).is_some() || self.parse_checkpoint_lsn(path).is_some()
+    }
+
+    /// This log's chunks in `dir`, ascending by sequence. Used offline by
+    /// restore, on directories no server has open.
+    pub fn chunk_paths_in_sync(&self, dir: &Path) -> RS<Vec<(u64, PathBuf)>> {
+        if !sync_fs::path_exists(dir) {
+            return Ok(Vec::new());
+        }
+        let mut entries = sync_fs::read_dir(dir)?
+            .into_iter()
+            .filter_map(|path| Some((self.parse_chunk_sequence(&path)?, path)))
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|(sequence, _)| *sequence);
+        Ok(entries)
+    }
+
+    /// Copies the chunks of this log found in `archive_dir` into the log
+    /// directory, replacing chunks of the same sequence: an archived chunk
+    /// was sealed after any copy of it a backup holds, so it is a superset.
+    /// Chunks below `from_sequence` are skipped. Returns the number of
+    /// chunks copied.
+    pub fn restore_archived_chunks_sync(
+        &self,
+        archive_dir: &Path,
+        from_sequence: u64,
+    ) -> RS<usize> {
+        sync_fs::create_dir_all(&self.log_dir)?;
+        let mut restored = 0;
+        for (sequence, path) in self.chunk_paths_in_sync(archive_dir)? {
+            if sequence < from_sequence {
+                continue;
+            }
+            sync_fs::copy(&path, self.chunk_path(sequence))?;
+            restored += 1;
+        }
+        Ok(restored)
+    }
+
+    /// Cuts the log before the first entry after `keep_through` for which
+    /// `stop` returns true: the chunk holding the entry's first frame is
+    /// truncated there and every later chunk is deleted, so the next open
+    /// recovers exactly the entries before it. An unterminated or torn tail
+    /// is left alone; recovery drops it. Returns the start LSN of the first
+    /// entry removed, or `None` when no entry matched.
+    pub fn truncate_log_sync<L, F>(&self, keep_through: u64, mut stop: F) -> RS<Option<LSN>>
+    where
+        L: DeserializeOwned,
+        F: FnMut(LSN, &L) -> bool,
+    {
+        let chunks = self.chunk_paths_in_sync(&self.log_dir)?;
+        let mut pending = Vec::new();
+        let mut pending_start = (0usize, 0u64);
+        for (index, (_, path)) in chunks.iter().enumerate() {
+            let bytes = sync_fs::read(path)?;
+            let mut offset = 0u64;
+            for frame in scan_valid_frame_prefix(&bytes).frames {
+                let (header, _, _) = split_frame(&frame)?;
+                if pending.is_empty() {
+                    pending_start = (index, offset);
+                }
+                offset += frame.len() as u64;
+                pending.push(frame);
+                if header.n_part() != 0 {
+                    continue;
+                }
+                let start_lsn = frame_lsn(&pending[0])?;
+                let entry = deserialize_entry::<L>(&pending)?;
+                pending.clear();
+                if start_lsn.as_u64() > keep_through && stop(start_lsn, &entry) {
+                    let (cut_index, cut_offset) = pending_start;
+                    self.cut_chunks_sync(&chunks, cut_index, cut_offset)?;
+                    return Ok(Some(start_lsn));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn cut_chunks_sync(
+        &self,
+        chunks: &[(u64, PathBuf)],
+        cut_index: usize,
+        cut_offset: u64,
+    ) -> RS<()> {
+        let (_, cut_path) = chunks.get(cut_index).ok_or_else(|| {
+            mudu_error!(ErrorCode::Internal, "worker log cut point is out of range")
+        })?;
+        let file = sync_fs::SOpenOptions::new().write(true).open(cut_path)?;
+        file.set_len(cut_offset)?;
+        file.sync_all()?;
+        for (_, path) in &chunks[cut_index + 1..] {
+            sync_fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+impl WorkerWALBackend {
+    /// Copies this log into `target` for an online backup: every entry
+    /// allocated so far is made durable first, then the newest checkpoint
+    /// and every chunk are copied. Entries appended while the chunks are
+    /// copied may or may not be part of the copy, possibly as a torn tail
+    /// that recovery drops.
+    pub(crate) async fn backup_async(&self, target: &Path) -> RS<WorkerLogBackup> {
+        let end_lsn = self.last_allocated_lsn();
+        self.force_flush_log_async().await?;
+        self.fsync_unsynced_paths().await?;
+        let layout = self.layout()?;
+        let fs = self.fs();
+        let checkpoint_lsn = self.checkpoint_lsn()?;
+        let mut paths = layout.chunk_paths_sorted_async(fs.as_ref()).await?;
+        if let Some(lsn) = checkpoint_lsn {
+            paths.insert(0, layout.checkpoint_path(lsn));
+        }
+        for path in paths {
+            if let Some(file_name) = path.file_name() {
+                copy_file_async(fs.as_ref(), &path, &target.join(file_name)).await?;
+            }
+        }
+        Ok(WorkerLogBackup {
+            log_oid: layout.log_oid(),
+            checkpoint_lsn: checkpoint_lsn.map(|lsn| lsn.as_u64()),
+            end_lsn: end_lsn.as_u64(),
+        })
+    }
+}
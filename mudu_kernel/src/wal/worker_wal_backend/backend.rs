@@ -2,20 +2,22 @@ use crate::wal::log_frame::{frame_lsns, serialize_entry};
 use crate::wal::lsn::LSN;
 use crate::wal::worker_log::WorkerLogBackend;
 use async_trait::async_trait;
+use mudu::common::id::OID;
 use mudu::common::result::RS;
 use mudu::error::ErrorCode;
 use mudu::mudu_error;
 use mudu_sys::contract::async_fs::AsyncFs;
 use mudu_sys::contract::async_io_provider::AsyncIoProvider;
 use mudu_sys::scoped_task_trace;
+use mudu_sys::sync::async_::mutex::AMutex;
 use mudu_sys::sync::async_::ANotify;
 use mudu_sys::sync::SMutex;
 use mudu_sys::{default_sys_io_context, SysIoContext};
 use serde::Serialize;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock, Weak};
 use std::time::Duration;
 
 use super::batching::WorkerLogBatching;
@@ -38,6 +40,34 @@ fn new_flush_slots() -> Vec<SMutex<FlushTask>> {
     (0..FLUSH_SLOT_COUNT).map(|_| SMutex::new(None)).collect()
 }
 
+type SyncedLsnRegistry = HashMap<(PathBuf, OID), Weak<AtomicU64>>;
+
+fn synced_lsn_registry() -> &'static SMutex<SyncedLsnRegistry> {
+    static REGISTRY: OnceLock<SMutex<SyncedLsnRegistry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| SMutex::new(HashMap::new()))
+}
+
+fn register_synced_lsn(layout: &WorkerLogLayout, synced_lsn: &Arc<AtomicU64>) -> RS<()> {
+    let mut registry = synced_lsn_registry().lock()?;
+    registry.retain(|_, synced_lsn| synced_lsn.strong_count() > 0);
+    registry.insert(
+        (layout.log_dir.clone(), layout.log_oid),
+        Arc::downgrade(synced_lsn),
+    );
+    Ok(())
+}
+
+/// The LSN below which every frame of the log at `layout` is fsynced, while
+/// a backend of this process writes the log; `None` when none does, and the
+/// frames on disk are all there is.
+pub(crate) fn synced_lsn_of(layout: &WorkerLogLayout) -> RS<Option<u64>> {
+    Ok(synced_lsn_registry()
+        .lock()?
+        .get(&(layout.log_dir.clone(), layout.log_oid))
+        .and_then(Weak::upgrade)
+        .map(|synced_lsn| synced_lsn.load(Ordering::Acquire)))
+}
+
 #[derive(Clone)]
 pub struct WorkerWALBackend {
     pub(crate) inner: Arc<WorkerLogInner>,
@@ -63,6 +93,13 @@ pub(crate) struct WorkerLogInner {
 
     pub(crate) flush_waiter: WaitLsn,
 
+    /// Every frame below this LSN is fsynced. Unlike the `flush_waiter`
+    /// watermark, which in `Periodic` mode only means written, this never
+    /// covers a frame a crash can still lose; log readers that ship or
+    /// publish frames (standby streams, the change feed) stop here. Shared
+    /// with readers on other threads through [`synced_lsn_of`].
+    pub(crate) synced_lsn: Arc<AtomicU64>,
+
     /// Wakes the tokio flush driver when an enqueued batch satisfies the
     /// batching watermarks. Notify-only; the driver re-checks the queue.
     pub(crate) flush_trigger: ANotify,
@@ -87,10 +124,19 @@ pub(crate) struct WorkerLogInner {
     /// time so a fresh log does not fsync immediately.
     pub(crate) last_fsync: SMutex<std::time::Instant>,
 
-    /// Chunk paths written by write-only flush rounds but not yet fsynced.
-    /// Always empty in `Commit` mode.
+    /// Chunk paths written by write-only flush rounds or direct appends but
+    /// not yet fsynced. Commit-mode flush rounds never add to it.
     pub(crate) unsynced_paths: SMutex<HashSet<PathBuf>>,
 
+    /// Serializes [`WorkerWALBackend::fsync_unsynced_paths`] calls, so one
+    /// that finishes first never advances `synced_lsn` over paths an earlier
+    /// one took and is still fsyncing.
+    pub(crate) fsync_lock: AMutex<()>,
+
+    /// LSN of the newest checkpoint of this log; entries at or below it are
+    /// covered by the checkpoint.
+    pub(crate) checkpoint_lsn: SMutex<Option<LSN>>,
+
     pub(crate) state: SMutex<ChunkedWorkerLog>,
 }
 
@@ -280,6 +326,9 @@ impl WorkerWALBackend {
         active_sessions: Arc<AtomicUsize>,
     ) -> RS<Self> {
         let sync_policy = layout.sync_policy();
+        // Frames found on disk at open are what recovery replays.
+        let synced_lsn = Arc::new(AtomicU64::new(tail.next_lsn.into()));
+        register_synced_lsn(&layout, &synced_lsn)?;
         Ok(Self {
             inner: Arc::new(WorkerLogInner {
                 io,
@@ -288,12 +337,15 @@ impl WorkerWALBackend {
                 active_sessions,
                 next_lsn: AtomicU64::new(tail.next_lsn.into()),
                 flush_waiter: WaitLsn::new(tail.next_lsn, vec![], Some(layout.log_oid)),
+                synced_lsn,
                 flush_trigger: ANotify::new(),
                 flush_drivers: std::sync::atomic::AtomicUsize::new(0),
                 flush_rounds: AtomicU64::new(0),
                 sync_policy,
                 last_fsync: SMutex::new(*mudu_sys::time::instant_now()),
                 unsynced_paths: SMutex::new(HashSet::new()),
+                fsync_lock: AMutex::new(()),
+                checkpoint_lsn: SMutex::new(tail.checkpoint_lsn),
                 state: SMutex::new(ChunkedWorkerLog::new(layout, tail)?),
             }),
             flush_tasks: Arc::new(new_flush_slots()),
@@ -302,8 +354,13 @@ impl WorkerWALBackend {
     }
 
     pub(crate) async fn append_raw(&self, payload: &[u8]) -> RS<()> {
+        self.append_raw_to(payload).await.map(|_| ())
+    }
+
+    /// Appends `payload` and returns the chunk it went to.
+    async fn append_raw_to(&self, payload: &[u8]) -> RS<Option<PathBuf>> {
         if payload.is_empty() {
-            return Ok(());
+            return Ok(None);
         }
         let reservation = {
             let mut guard = self
@@ -313,7 +370,9 @@ impl WorkerWALBackend {
                 .map_err(|_| mudu_error!(ErrorCode::Internal, "worker kv log lock poisoned"))?;
             guard.reserve_append(payload.len() as u64)?
         };
-        self.append_reserved_sync(reservation, payload).await
+        let path = reservation.path.clone();
+        self.append_reserved_sync(reservation, payload).await?;
+        Ok(Some(path))
     }
 
     /// Last LSN allocated from this log's sequence so far. A commit that
@@ -329,6 +388,55 @@ impl WorkerWALBackend {
         )
     }
 
+    /// Every frame below the returned LSN is fsynced.
+    pub(crate) fn synced_lsn(&self) -> u64 {
+        self.inner.synced_lsn.load(Ordering::Acquire)
+    }
+
+    /// Records that every frame below `lsn` is fsynced.
+    pub(crate) fn advance_synced_lsn(&self, lsn: u64) {
+        self.inner.synced_lsn.fetch_max(lsn, Ordering::AcqRel);
+    }
+
+    /// The LSN the next appended frame gets.
+    pub(crate) fn next_lsn(&self) -> LSN {
+        LSN::new(self.inner.next_lsn.load(Ordering::Relaxed))
+    }
+
+    /// Appends frames streamed from a primary's log, keeping their LSNs, and
+    /// makes them durable. The frames must continue this log's sequence
+    /// without a gap: a standby only follows the log it was seeded from.
+    pub(crate) async fn append_replicated_frames_async(&self, frames: Vec<Vec<u8>>) -> RS<()> {
+        let lsns = frame_lsns(&frames)?;
+        let Some(last) = lsns.last() else {
+            return Ok(());
+        };
+        let next = self.next_lsn();
+        let contiguous = lsns
+            .iter()
+            .enumerate()
+            .all(|(i, lsn)| lsn.as_u64() == next.as_u64() + i as u64);
+        if !contiguous {
+            return Err(mudu_error!(
+                ErrorCode::InvalidState,
+                format!(
+                    "replicated log frames {}..={} do not continue the local log at {}; \
+                     reseed the standby from a backup of the primary",
+                    lsns[0].as_u64(),
+                    last.as_u64(),
+                    next.as_u64()
+                )
+            ));
+        }
+        self.inner
+            .next_lsn
+            .fetch_max(last.as_u64() + 1, Ordering::Relaxed);
+        self.append_frames_async(frames).await?;
+        self.flush_async().await?;
+        self.advance_synced_lsn(last.as_u64() + 1);
+        Ok(())
+    }
+
     pub fn flush(&self) -> RS<()> {
         let path = self.current_chunk_path()?;
         if let Some(path) = path {
@@ -339,6 +447,7 @@ impl WorkerWALBackend {
 
     pub async fn flush_async(&self) -> RS<()> {
         mudu_utils::scoped_task_trace!();
+        self.fsync_unsynced_paths().await?;
         let path = self.current_chunk_path()?;
         let Some(path) = path else {
             return Ok(());
@@ -420,15 +529,22 @@ impl WorkerLogBackend for WorkerWALBackend {
         // is unchanged: as before, these writes only become durable when the
         // caller invokes `flush`/`flush_async` (or a later fsync covers the
         // same chunk); reporting early never satisfies a group-commit waiter
-        // for its own queued LSNs, which are only reported after fsync.
+        // for its own queued LSNs, which are only reported after fsync. Their
+        // chunks are tracked as unsynced before the report, so the synced
+        // LSN only passes them once `fsync_unsynced_paths` covered them.
         let lsns = frame_lsns(&frames)?;
         let mut write_result = Ok(());
+        let mut written = HashSet::new();
         for frame in &frames {
-            if let Err(e) = self.append_raw(frame).await {
-                write_result = Err(e);
-                break;
+            match self.append_raw_to(frame).await {
+                Ok(path) => written.extend(path),
+                Err(e) => {
+                    write_result = Err(e);
+                    break;
+                }
             }
         }
+        self.inner.unsynced_paths.lock()?.extend(written);
         // Report even on write error: the LSNs are consumed and will never be
         // rewritten, and not reporting would stall every later group-commit
         // waiter behind the gap.
@@ -0,0 +1,251 @@
+use crate::wal::log_frame::{deserialize_entry, frame_lsn, serialize_entry};
+use crate::wal::lsn::LSN;
+use crate::wal::worker_log::scan_valid_frame_prefix;
+use mudu::common::result::RS;
+use mudu::error::ErrorCode;
+use mudu::mudu_error;
+use mudu_sys::contract::async_fs::AsyncFs;
+use mudu_sys::contract::file_options::FileOptions;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::{debug, warn};
+
+use super::backend::WorkerWALBackend;
+use super::layout::WorkerLogLayout;
+
+/// A persisted checkpoint of one worker log.
+///
+/// Every entry at or below `lsn` is reflected in `payload` or in state the
+/// log owner made durable before writing the checkpoint, so recovery
+/// restores `payload` and replays only the entries after `lsn`, and the
+/// chunks holding older entries can be deleted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WalCheckpoint {
+    pub lsn: LSN,
+    pub payload: Vec<u8>,
+}
+
+/// Outcome of one checkpoint, for logging and loop stats.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WalCheckpointStats {
+    /// Chunks deleted because the checkpoint covers all of their entries.
+    pub reclaimed_chunks: u64,
+    pub reclaimed_bytes: u64,
+    /// Bytes of the chunks still on disk after reclamation.
+    pub wal_bytes: u64,
+}
+
+impl std::ops::AddAssign for WalCheckpointStats {
+    fn add_assign(&mut self, other: Self) {
+        self.reclaimed_chunks += other.reclaimed_chunks;
+        self.reclaimed_bytes += other.reclaimed_bytes;
+        self.wal_bytes += other.wal_bytes;
+    }
+}
+
+/// A log sealed by [`WorkerWALBackend::begin_checkpoint_async`]: entries up
+/// to `lsn` are durable, and the chunks below `next_sequence` hold no entry
+/// above it and receive no further writes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WalCheckpointSeal {
+    pub lsn: LSN,
+    next_sequence: u64,
+}
+
+impl WorkerLogLayout {
+    pub fn checkpoint_path(&self, lsn: LSN) -> PathBuf {
+        self.log_dir
+            .join(format!("{}.{}.ckpt", self.short_oid, lsn.as_u64()))
+    }
+
+    pub(super) fn parse_checkpoint_lsn(&self, path: &Path) -> Option<u64> {
+        let file_name = path.file_name()?.to_str()?;
+        let prefix = format!("{}.", self.short_oid);
+        let suffix = ".ckpt";
+        if !file_name.starts_with(&prefix) || !file_name.ends_with(suffix) {
+            return None;
+        }
+        file_name[prefix.len()..file_name.len() - suffix.len()]
+            .parse::<u64>()
+            .ok()
+    }
+
+    /// The checkpoint files among `paths`, ascending by LSN.
+    pub(crate) fn checkpoint_paths(&self, paths: Vec<PathBuf>) -> Vec<(u64, PathBuf)> {
+        let mut entries = paths
+            .into_iter()
+            .filter_map(|path| Some((self.parse_checkpoint_lsn(&path)?, path)))
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|(lsn, _)| *lsn);
+        entries
+    }
+
+    /// Decodes one checkpoint file. A file that does not decode completely
+    /// (a crash while it was written) is skipped with a warning: the older
+    /// checkpoint it was meant to replace is only removed after it is
+    /// durable.
+    pub(crate) fn decode_checkpoint_file(
+        &self,
+        path: &Path,
+        bytes: &[u8],
+    ) -> Option<WalCheckpoint> {
+        let prefix = scan_valid_frame_prefix(bytes);
+        let decoded = match (&prefix.corrupt_reason, prefix.frames.first()) {
+            (None, Some(first)) => frame_lsn(first).and_then(|lsn| {
+                Ok(WalCheckpoint {
+                    lsn,
+                    payload: deserialize_entry::<Vec<u8>>(&prefix.frames)?,
+                })
+            }),
+            (Some(reason), _) => Err(mudu_error!(ErrorCode::Decode, reason.clone())),
+            (None, None) => Err(mudu_error!(ErrorCode::Decode, "checkpoint file is empty")),
+        };
+        match decoded {
+            Ok(checkpoint) => Some(checkpoint),
+            Err(err) => {
+                warn!(
+                    path = %path.display(),
+                    error = %err,
+                    "skipping undecodable worker log checkpoint"
+                );
+                None
+            }
+        }
+    }
+
+    /// The newest decodable checkpoint of this log, if any.
+    pub async fn read_checkpoint_async(&self, fs: &dyn AsyncFs) -> RS<Option<WalCheckpoint>> {
+        fs.create_dir_all(&self.log_dir).await?;
+        let mut paths = self.checkpoint_paths(fs.read_dir(&self.log_dir).await?);
+        while let Some((_, path)) = paths.pop() {
+            let bytes = fs.read_all(&path).await?;
+            if let Some(checkpoint) = self.decode_checkpoint_file(&path, &bytes) {
+                return Ok(Some(checkpoint));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Writes and fsyncs `checkpoint`, then removes every older checkpoint
+    /// file. The checkpoint is framed like log entries (CRC-checked, split
+    /// at the frame size limit) with frame LSNs starting at its own LSN.
+    pub(crate) async fn write_checkpoint_async(
+        &self,
+        fs: &dyn AsyncFs,
+        checkpoint: &WalCheckpoint,
+    ) -> RS<()> {
+        let frames = serialize_entry(
+            &checkpoint.payload,
+            self.frame_size_limit(),
+            &AtomicU64::new(checkpoint.lsn.as_u64()),
+        )?;
+        let path = self.checkpoint_path(checkpoint.lsn);
+        fs.remove_file_if_exists(&path).await?;
+        let file = fs.open(&path, FileOptions::read_write_create()).await?;
+        file.write_all_at(0, &frames.concat()).await?;
+        file.fsync().await?;
+        drop(file);
+        for (lsn, older) in self.checkpoint_paths(fs.read_dir(&self.log_dir).await?) {
+            if lsn < checkpoint.lsn.as_u64() {
+                fs.remove_file_if_exists(&older).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl WorkerWALBackend {
+    /// LSN of the last checkpoint written or found on open.
+    pub(crate) fn checkpoint_lsn(&self) -> RS<Option<LSN>> {
+        Ok(*self.inner.checkpoint_lsn.lock()?)
+    }
+
+    /// First half of a checkpoint: makes every allocated entry durable and
+    /// seals the current chunk, so every existing chunk holds only entries
+    /// the checkpoint covers and is never written again. Returns `None` when
+    /// nothing was logged since the last checkpoint.
+    ///
+    /// The caller must keep new entries from being logged until this
+    /// returns (the worker checkpoint gate, a relation file's write latch);
+    /// otherwise a later entry could still be written into a sealed chunk.
+    /// The expensive part of a checkpoint (page writes, the checkpoint file,
+    /// chunk deletion) happens in [`Self::finish_checkpoint_async`] without
+    /// that restriction.
+    pub(crate) async fn begin_checkpoint_async(&self) -> RS<Option<WalCheckpointSeal>> {
+        if self.inner.next_lsn.load(Ordering::SeqCst) == 0 {
+            return Ok(None);
+        }
+        let lsn = self.last_allocated_lsn();
+        if self
+            .checkpoint_lsn()?
+            .is_some_and(|previous| previous >= lsn)
+        {
+            return Ok(None);
+        }
+        // Quiescence means every allocated entry was written or is still
+        // queued; drain the queue, then make the written chunks durable.
+        self.force_flush_log_async().await?;
+        self.fsync_unsynced_paths().await?;
+        self.flush_async().await?;
+        let (next_sequence, cached) = self.inner.state.lock()?.seal_current();
+        if let Some(file) = cached {
+            file.close().await?;
+        }
+        Ok(Some(WalCheckpointSeal { lsn, next_sequence }))
+    }
+
+    /// Second half of a checkpoint: persists `payload` as the checkpoint at
+    /// `seal.lsn` and deletes the chunks sealed by
+    /// [`Self::begin_checkpoint_async`], archiving each one first when the
+    /// layout has an archive directory.
+    pub(crate) async fn finish_checkpoint_async(
+        &self,
+        seal: WalCheckpointSeal,
+        payload: Vec<u8>,
+    ) -> RS<WalCheckpointStats> {
+        let layout = self.layout()?;
+        let fs = self.fs();
+        layout
+            .write_checkpoint_async(
+                fs.as_ref(),
+                &WalCheckpoint {
+                    lsn: seal.lsn,
+                    payload,
+                },
+            )
+            .await?;
+        *self.inner.checkpoint_lsn.lock()? = Some(seal.lsn);
+        let mut stats = WalCheckpointStats::default();
+        for sequence in layout.chunk_sequences_async(fs.as_ref()).await? {
+            let path = layout.chunk_path(sequence);
+            let len = fs.metadata_len(&path).await?;
+            if sequence < seal.next_sequence {
+                layout.archive_chunk_async(fs.as_ref(), sequence).await?;
+                fs.remove_file_if_exists(&path).await?;
+                stats.reclaimed_chunks += 1;
+                stats.reclaimed_bytes += len;
+            } else {
+                stats.wal_bytes += len;
+            }
+        }
+        debug!(
+            log_oid = layout.log_oid(),
+            lsn = seal.lsn.as_u64(),
+            reclaimed_chunks = stats.reclaimed_chunks,
+            reclaimed_bytes = stats.reclaimed_bytes,
+            wal_bytes = stats.wal_bytes,
+            "worker log checkpoint written"
+        );
+        Ok(stats)
+    }
+
+    /// Total size of this log's chunks on disk.
+    pub(crate) async fn wal_size_bytes_async(&self) -> RS<u64> {
+        let fs = self.fs();
+        let mut total = 0u64;
+        for path in self.layout()?.chunk_paths_sorted_async(fs.as_ref()).await? {
+            total += fs.metadata_len(&path).await?;
+        }
+        Ok(total)
+    }
+}
@@ -130,6 +130,11 @@ impl WaitLsn {
         Ok(())
     }
 
+    /// Every LSN below the returned one was reported via [`WaitLsn::ready`].
+    pub(crate) fn watermark(&self) -> u64 {
+        self.next_wait_lsn.load(Ordering::Acquire)
+    }
+
     /// Waits until every LSN up to and including `target` has been reported
     /// durable via [`WaitLsn::ready`].
     ///
@@ -377,14 +382,21 @@ impl WorkerWALBackend {
     }
 
     /// Fsyncs every dirty chunk path regardless of the periodic interval.
-    /// Used by the periodic driver when the interval elapses and by shutdown
-    /// paths so a clean stop never leaves acknowledged commits un-fsynced.
+    /// Used by the periodic driver when the interval elapses, by commit-mode
+    /// flush rounds for direct appends, and by shutdown paths so a clean
+    /// stop never leaves acknowledged commits un-fsynced. Advances the
+    /// synced LSN over every LSN reported before the call.
     pub(crate) async fn fsync_unsynced_paths(&self) -> RS<()> {
+        let _fsync = self.inner.fsync_lock.lock().await;
+        // Sampled before the paths are taken: the paths of every LSN below
+        // it are in the taken set or were fsynced by an earlier call.
+        let written = self.inner.flush_waiter.watermark();
         let paths: Vec<PathBuf> = {
             let mut unsynced = self.inner.unsynced_paths.lock()?;
             std::mem::take(&mut *unsynced).into_iter().collect()
         };
         if paths.is_empty() {
+            self.advance_synced_lsn(written);
             return Ok(());
         }
         debug!(
@@ -421,6 +433,7 @@ impl WorkerWALBackend {
             ),
         }
         result?;
+        self.advance_synced_lsn(written);
         *self.inner.last_fsync.lock()? = *mudu_sys::time::instant_now();
         Ok(())
     }
@@ -442,6 +455,7 @@ impl WorkerWALBackend {
         let release_result = self.release_async_file(path, file).await;
         fsync_result?;
         release_result?;
+        crate::server::metrics::record_wal_fsyncs(1);
         Ok(())
     }
 
@@ -951,6 +965,11 @@ impl WorkerWALBackend {
             flush_paths = prepared.flush_paths.len(),
             "worker_wal execute_flush_batch start"
         );
+        let written_bytes: u64 = prepared
+            .writes
+            .iter()
+            .map(|write| write.payload.len() as u64)
+            .sum();
 
         if wal_pwrite_experiment() {
             // EXPERIMENT (MUDU_WAL_PWRITE=1): issue the WAL write as a direct
@@ -1018,17 +1037,23 @@ impl WorkerWALBackend {
             }
         }
 
+        crate::server::metrics::record_wal_write(written_bytes);
+
         if self.inner.sync_policy != WalSyncPolicy::Commit {
             // Periodic mode: every flush round is write-only. The watermark
             // now means "written to page cache"; the dirty chunk paths are
             // tracked so the periodic fsync task (running in its own task
             // slot, or the tokio fsync loop) fsyncs them once the interval
-            // elapses. Write rounds never wait for an fsync.
-            self.complete_persisted_lsns(prepared.ready_lsns)?;
-            let mut unsynced = self.inner.unsynced_paths.lock()?;
-            for path in prepared.flush_paths {
-                unsynced.insert(path);
+            // elapses. Write rounds never wait for an fsync. The paths are
+            // tracked before the LSNs are reported: an fsync that sees the
+            // LSNs in the watermark also finds their paths.
+            {
+                let mut unsynced = self.inner.unsynced_paths.lock()?;
+                for path in prepared.flush_paths {
+                    unsynced.insert(path);
+                }
             }
+            self.complete_persisted_lsns(prepared.ready_lsns)?;
             return Ok(());
         }
 
@@ -1041,6 +1066,7 @@ impl WorkerWALBackend {
         } = prepared;
 
         let last_index = flush_paths.len().saturating_sub(1);
+        let fsyncs = flush_paths.len() as u64;
         if worker_ring::has_current_worker_ring() {
             let mut flush_handles = Vec::with_capacity(flush_paths.len());
             for (index, path) in flush_paths.into_iter().enumerate() {
@@ -1106,6 +1132,12 @@ impl WorkerWALBackend {
                 }
             }
         }
+        // Every reported LSN is now fsynced by this round or by a concurrent
+        // one that reported after its own fsync, except direct appends (the
+        // snapshot horizon records): their chunks are fsynced here, which
+        // also advances the synced LSN over the reported ones.
+        self.fsync_unsynced_paths().await?;
+        crate::server::metrics::record_wal_fsyncs(fsyncs);
         Ok(())
     }
 
@@ -1258,6 +1290,7 @@ mod tests {
     use crate::wal::worker_wal_backend::state::{AppendReservation, ChunkedWorkerLog};
     use mudu_sys::default_sys_io_context;
     use mudu_sys::env_var::temp_dir;
+    use mudu_sys::sync::async_::mutex::AMutex;
     use mudu_sys::time::instant_now;
     use mudu_utils::oid::gen_oid;
     use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
@@ -1300,6 +1333,7 @@ mod tests {
             current_size: 0,
             next_sequence: 0,
             next_lsn: LSN::new(0),
+            checkpoint_lsn: None,
         };
         WorkerWALBackend {
             inner: Arc::new(WorkerLogInner {
@@ -1309,12 +1343,15 @@ mod tests {
                 active_sessions: Arc::new(AtomicUsize::new(0)),
                 next_lsn: AtomicU64::new(0),
                 flush_waiter: WaitLsn::new(LSN::new(0), vec![], Some(layout.log_oid)),
+                synced_lsn: Arc::new(AtomicU64::new(0)),
                 flush_trigger: ANotify::new(),
                 flush_drivers: std::sync::atomic::AtomicUsize::new(0),
                 flush_rounds: AtomicU64::new(0),
                 sync_policy: layout.sync_policy(),
                 last_fsync: SMutex::new(instant_now().into_std()),
                 unsynced_paths: SMutex::new(HashSet::new()),
+                fsync_lock: AMutex::new(()),
+                checkpoint_lsn: SMutex::new(None),
                 state: SMutex::new(ChunkedWorkerLog::new(layout.clone(), tail).unwrap()),
             }),
             flush_tasks: Arc::new((0..FLUSH_SLOT_COUNT).map(|_| SMutex::new(None)).collect()),
@@ -20,16 +20,19 @@ pub struct WorkerLogTail {
     pub current_size: u64,
     pub next_sequence: u64,
     pub next_lsn: LSN,
+    /// LSN of the newest decodable checkpoint, if the log has one.
+    pub checkpoint_lsn: Option<LSN>,
 }
 
 #[derive(Clone, Debug)]
 pub struct WorkerLogLayout {
-    log_dir: PathBuf,
+    pub(crate) log_dir: PathBuf,
     pub(crate) log_oid: OID,
     chunk_size: u64,
     pub(crate) short_oid: String,
     batching: WorkerLogBatching,
     sync_policy: WalSyncPolicy,
+    archive_dir: Option<PathBuf>,
 }
 
 impl Default for WorkerLogLayout {
@@ -47,6 +50,7 @@ impl WorkerLogLayout {
             short_oid: ShortUuid::from_uuid(&Uuid::from_u128(log_oid)).to_string(),
             batching: WorkerLogBatching::default(),
             sync_policy: WalSyncPolicy::default(),
+            archive_dir: None,
         }
     }
 
@@ -76,6 +80,14 @@ impl WorkerLogLayout {
         self
     }
 
+    /// Copies every chunk a checkpoint reclaims to `archive_dir` before it
+    /// is deleted (see `WorkerWALBackend::finish_checkpoint_async`). `None`,
+    /// the default, deletes reclaimed chunks without archiving them.
+    pub fn with_archive_dir(mut self, archive_dir: Option<PathBuf>) -> Self {
+        self.archive_dir = archive_dir;
+        self
+    }
+
     pub fn log_oid(&self) -> OID {
         self.log_oid
     }
@@ -101,6 +113,10 @@ impl WorkerLogLayout {
         self.sync_policy
     }
 
+    pub fn archive_dir(&self) -> Option<&Path> {
+        self.archive_dir.as_deref()
+    }
+
     pub async fn scan_tail(&self) -> RS<WorkerLogTail> {
         fs::create_dir_all(&self.log_dir).await?;
         let mut max_sequence: Option<u64> = None;
@@ -109,23 +125,26 @@ impl WorkerLogLayout {
                 max_sequence = Some(max_sequence.map_or(sequence, |current| current.max(sequence)));
             }
         }
+        let checkpoint_lsn = self.scan_checkpoint_lsn().await?;
         let Some(sequence) = max_sequence else {
             return Ok(WorkerLogTail {
                 current_sequence: None,
                 current_size: 0,
                 next_sequence: 0,
-                next_lsn: LSN::new(0),
+                next_lsn: next_lsn_after(None, checkpoint_lsn),
+                checkpoint_lsn,
             });
         };
         let path = self.chunk_path(sequence);
         let size = fs::metadata_len(&path).await?;
-        let next_lsn = self.scan_next_lsn().await?;
+        let next_lsn = next_lsn_after(self.scan_max_lsn().await?, checkpoint_lsn);
         if size < self.chunk_size {
             Ok(WorkerLogTail {
                 current_sequence: Some(sequence),
                 current_size: size,
                 next_sequence: sequence + 1,
                 next_lsn,
+                checkpoint_lsn,
             })
         } else {
             Ok(WorkerLogTail {
@@ -133,6 +152,7 @@ impl WorkerLogLayout {
                 current_size: 0,
                 next_sequence: sequence + 1,
                 next_lsn,
+                checkpoint_lsn,
             })
         }
     }
@@ -158,7 +178,7 @@ impl WorkerLogLayout {
         Ok(entries.into_iter().map(|(_, path)| path).collect())
     }
 
-    fn parse_chunk_sequence(&self, path: &Path) -> Option<u64> {
+    pub(crate) fn parse_chunk_sequence(&self, path: &Path) -> Option<u64> {
         let file_name = path.file_name()?.to_str()?;
         let prefix = format!("{}.", self.short_oid);
         let suffix = ".xl";
@@ -169,7 +189,18 @@ impl WorkerLogLayout {
         sequence.parse::<u64>().ok()
     }
 
-    async fn scan_next_lsn(&self) -> RS<LSN> {
+    async fn scan_checkpoint_lsn(&self) -> RS<Option<LSN>> {
+        let mut paths = self.checkpoint_paths(fs::read_dir(&self.log_dir).await?);
+        while let Some((_, path)) = paths.pop() {
+            let bytes = fs::read_all(&path).await?;
+            if let Some(checkpoint) = self.decode_checkpoint_file(&path, &bytes) {
+                return Ok(Some(checkpoint.lsn));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn scan_max_lsn(&self) -> RS<Option<LSN>> {
         let mut max_lsn: Option<LSN> = None;
         for path in self.chunk_paths_sorted().await? {
             let bytes = fs::read_all(&path).await?;
@@ -186,20 +217,25 @@ impl WorkerLogLayout {
                 max_lsn = Some(max_lsn.map_or(lsn, |current| current.max(lsn)));
             }
         }
-        Ok(max_lsn.map_or(LSN::new(0), |lsn| lsn.saturating_add(1)))
+        Ok(max_lsn)
     }
 
     pub async fn scan_tail_async(&self, fs: &dyn AsyncFs) -> RS<WorkerLogTail> {
         mudu_utils::scoped_task_trace!();
         fs.create_dir_all(&self.log_dir).await?;
         let sequences = self.chunk_sequences_async(fs).await?;
+        let checkpoint_lsn = self
+            .read_checkpoint_async(fs)
+            .await?
+            .map(|checkpoint| checkpoint.lsn);
         let max_sequence = sequences.last().copied();
         let Some(sequence) = max_sequence else {
             return Ok(WorkerLogTail {
                 current_sequence: None,
                 current_size: 0,
                 next_sequence: 0,
-                next_lsn: LSN::new(0),
+                next_lsn: next_lsn_after(None, checkpoint_lsn),
+                checkpoint_lsn,
             });
         };
         // Walk every chunk once: derive the next LSN from the longest valid
@@ -223,7 +259,10 @@ impl WorkerLogLayout {
                     reason = %reason,
                     "truncating un-persisted worker log chunk tail"
                 );
-                truncate_file_to(&path, prefix.valid_len as u64)?;
+                // Truncate through the provider's file system and persist
+                // the size change, so a dropped un-persisted tail cannot
+                // reappear after another crash.
+                fs.truncate(&path, prefix.valid_len as u64).await?;
                 size = prefix.valid_len as u64;
             }
             if let Some(lsn) = prefix.max_lsn {
@@ -233,13 +272,14 @@ impl WorkerLogLayout {
                 tail_size = size;
             }
         }
-        let next_lsn = max_lsn.map_or(LSN::new(0), |lsn| lsn.saturating_add(1));
+        let next_lsn = next_lsn_after(max_lsn, checkpoint_lsn);
         if tail_size < self.chunk_size {
             Ok(WorkerLogTail {
                 current_sequence: Some(sequence),
                 current_size: tail_size,
                 next_sequence: sequence + 1,
                 next_lsn,
+                checkpoint_lsn,
             })
         } else {
             Ok(WorkerLogTail {
@@ -247,6 +287,7 @@ impl WorkerLogLayout {
                 current_size: 0,
                 next_sequence: sequence + 1,
                 next_lsn,
+                checkpoint_lsn,
             })
         }
     }
@@ -263,33 +304,29 @@ impl WorkerLogLayout {
         Ok(entries.into_iter().map(|(_, path)| path).collect())
     }
 
-    async fn chunk_sequences_async(&self, fs: &dyn AsyncFs) -> RS<Vec<u64>> {
+    /// Chunk sequences present in the log directory, ascending. Listed from
+    /// the directory rather than probed from zero: a checkpoint reclaims the
+    /// oldest chunks, so the surviving sequences need not start at zero.
+    pub(crate) async fn chunk_sequences_async(&self, fs: &dyn AsyncFs) -> RS<Vec<u64>> {
         let trace = mudu_utils::task_trace!();
         trace.watch("wal.layout.stage", "chunk_sequences_start");
         let mut sequences = Vec::new();
-        let mut sequence = 0u64;
-        loop {
-            trace.watch("wal.layout.sequence_probe", &sequence.to_string());
-            let path = self.chunk_path(sequence);
-            if !fs.path_exists(&path).await? {
-                trace.watch("wal.layout.stage", "chunk_sequences_done");
-                break;
+        for path in fs.read_dir(&self.log_dir).await? {
+            if let Some(sequence) = self.parse_chunk_sequence(path.as_path()) {
+                sequences.push(sequence);
             }
-            sequences.push(sequence);
-            sequence = sequence.saturating_add(1);
         }
+        sequences.sort_unstable();
         trace.watch("wal.layout.sequences", &sequences.len().to_string());
         Ok(sequences)
     }
 }
 
-/// Truncates `path` to `len` bytes and persists the size change, so a
-/// dropped un-persisted WAL tail cannot reappear after another crash.
-fn truncate_file_to(path: &Path, len: u64) -> RS<()> {
-    let mut options = mudu_sys::fs::sync::SOpenOptions::new();
-    options.read(true).write(true);
-    let file = options.open(path)?;
-    file.set_len(len)?;
-    file.sync_data()?;
-    Ok(())
+/// The LSN the next appended frame gets: one past the newest frame on disk,
+/// and never at or below the checkpoint LSN, even when every chunk holding
+/// the frames it covers was reclaimed.
+fn next_lsn_after(max_lsn: Option<LSN>, checkpoint_lsn: Option<LSN>) -> LSN {
+    let after_frames = max_lsn.map_or(LSN::new(0), |lsn| lsn.saturating_add(1));
+    let after_checkpoint = checkpoint_lsn.map_or(LSN::new(0), |lsn| lsn.saturating_add(1));
+    after_frames.max(after_checkpoint)
 }
@@ -1,16 +1,25 @@
+mod archive;
 mod backend;
 mod batching;
+mod checkpoint;
 mod file_cache;
 mod flush;
 mod layout;
 mod state;
 mod sync_policy;
 
+pub use archive::WorkerLogBackup;
+pub(crate) use archive::{backup_target_path, copy_file_async};
+pub(crate) use backend::synced_lsn_of;
 pub use backend::WorkerWALBackend;
 pub use batching::WorkerLogBatching;
+pub use checkpoint::{WalCheckpoint, WalCheckpointSeal, WalCheckpointStats};
 pub use layout::{WorkerLogLayout, WorkerLogTail};
 pub use sync_policy::WalSyncPolicy;
 
+#[cfg(all(test, feature = "ds"))]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod sim_tests;
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
 mod tests;
@@ -0,0 +1,336 @@
+//! Seeded crash-recovery tests of the worker WAL on the simulated disk.
+//!
+//! Each seed arms a power cut at a random point of a group-commit workload,
+//! restarts the disk with a seeded subset of the unsynced writes, reopens
+//! the log and checks what recovery kept. Replay a failure with
+//! `MUDU_DS_SEED=<seed>`.
+
+use super::*;
+
+use crate::wal::log_frame::frame_lsns;
+use crate::wal::worker_log::{decode_frames_allow_trailing, WorkerLogBackend};
+use crate::wal::xl_batch::{decode_xl_batches_with_pending, XLBatch};
+use crate::wal::xl_data_op::{XLInsert, XLWrite};
+use crate::wal::xl_entry::{TxOp, XLEntry};
+use mudu::common::result::RS;
+use mudu_sys::contract::async_fs::AsyncFs;
+use mudu_sys::contract::async_io_provider::AsyncIoProvider;
+use mudu_sys::sim::{sim_seeds, SimFs, SimFsFaults, SimRng, Simulation};
+use std::net::{IpAddr, Ipv4Addr};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const CHUNK_SIZE: u64 = 512;
+const WORKLOAD_STREAM: u64 = 0x5157;
+
+struct SimNode {
+    fs: Arc<SimFs>,
+    provider: Arc<dyn AsyncIoProvider>,
+}
+
+fn sim_node(seed: u64, faults: SimFsFaults) -> SimNode {
+    let sim = Simulation::new(seed);
+    let fs = sim.new_fs(faults);
+    let provider = sim.node(IpAddr::V4(Ipv4Addr::LOCALHOST), fs.clone());
+    SimNode { fs, provider }
+}
+
+fn wal_layout(name: &str, log_oid: u128) -> WorkerLogLayout {
+    WorkerLogLayout::new(PathBuf::from("/sim").join(name), log_oid, CHUNK_SIZE).unwrap()
+}
+
+fn insert_batch(xid: u64) -> XLBatch {
+    XLBatch::new(vec![XLEntry {
+        xid,
+        ops: vec![
+            TxOp::Begin,
+            TxOp::Write(XLWrite::Insert(XLInsert {
+                table_id: 1,
+                partition_id: 0,
+                tuple_id: xid,
+                key: format!("k{xid}").into_bytes(),
+                value: vec![xid as u8; 24],
+                layout_version: None,
+            })),
+            TxOp::Commit,
+        ],
+    }])
+}
+
+/// Queues `batch` for group commit without waiting for durability.
+async fn enqueue(log: &WorkerWALBackend, batch: &XLBatch) -> RS<()> {
+    let frames = log.serialize_entry(batch)?;
+    let lsns = frame_lsns(&frames)?;
+    log.enqueue_group_commit(frames, lsns, false).await?;
+    Ok(())
+}
+
+/// Makes `batch` durable before returning, like a committing session.
+async fn commit(log: &WorkerWALBackend, batch: &XLBatch) -> RS<()> {
+    enqueue(log, batch).await?;
+    log.force_flush_log_async().await
+}
+
+async fn recovered_batches(layout: &WorkerLogLayout, fs: &dyn AsyncFs) -> Vec<XLBatch> {
+    let mut frames = Vec::new();
+    for path in layout.chunk_paths_sorted_async(fs).await.unwrap() {
+        frames.extend(decode_frames_allow_trailing(&fs.read_all(&path).await.unwrap()).unwrap());
+    }
+    let mut pending = Vec::new();
+    let mut pending_start_lsn = None;
+    decode_xl_batches_with_pending(&frames, &mut pending, &mut pending_start_lsn).unwrap()
+}
+
+/// Runs an insert workload until the armed power cut, returning every batch
+/// handed to the log and how many of them were acknowledged durable.
+async fn run_until_crash(log: &WorkerWALBackend, rng: &mut SimRng) -> (Vec<XLBatch>, usize) {
+    let mut written = Vec::new();
+    let mut acked = 0;
+    for xid in 1..=64 {
+        let batch = insert_batch(xid);
+        if enqueue(log, &batch).await.is_err() {
+            break;
+        }
+        written.push(batch);
+        if rng.chance(0.4) {
+            if log.force_flush_log_async().await.is_err() {
+                break;
+            }
+            acked = written.len();
+        }
+    }
+    (written, acked)
+}
+
+#[test]
+fn wal_recovers_acknowledged_prefix_after_seeded_crash() {
+    for seed in sim_seeds() {
+        let faults = SimFsFaults {
+            torn_writes: true,
+            ..SimFsFaults::default()
+        };
+        let node = sim_node(seed, faults);
+        let mut rng = SimRng::new(seed).fork(WORKLOAD_STREAM);
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let layout = wal_layout("wal", 7);
+            let log = WorkerWALBackend::new_with_provider(layout.clone(), node.provider.clone())
+                .await
+                .unwrap();
+            node.fs.crash_after(rng.range_inclusive(1, 96));
+            let (written, acked) = run_until_crash(&log, &mut rng).await;
+            drop(log);
+            node.fs.restart();
+
+            let log = WorkerWALBackend::new_with_provider(layout.clone(), node.provider.clone())
+                .await
+                .unwrap_or_else(|e| panic!("seed {seed}: recovery failed: {e}"));
+            let recovered = recovered_batches(&layout, node.provider.fs()).await;
+            assert!(
+                written.starts_with(&recovered),
+                "seed {seed}: recovered batches are not a prefix of the written ones"
+            );
+            assert!(
+                recovered.len() >= acked,
+                "seed {seed}: lost acknowledged batches ({} recovered, {acked} acked)",
+                recovered.len()
+            );
+
+            // The recovered log keeps accepting commits after the torn tail.
+            let next = insert_batch(1000);
+            commit(&log, &next).await.unwrap();
+            let after = recovered_batches(&layout, node.provider.fs()).await;
+            assert_eq!(after.len(), recovered.len() + 1, "seed {seed}");
+            assert_eq!(after.last(), Some(&next), "seed {seed}");
+        })
+        .unwrap();
+    }
+}
+
+#[test]
+fn wal_recovers_cleanly_when_fsyncs_are_lost() {
+    for seed in sim_seeds() {
+        let faults = SimFsFaults {
+            lost_fsync_probability: 0.3,
+            torn_writes: true,
+            capacity: None,
+        };
+        let node = sim_node(seed, faults);
+        let mut rng = SimRng::new(seed).fork(WORKLOAD_STREAM);
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let layout = wal_layout("wal", 8);
+            let log = WorkerWALBackend::new_with_provider(layout.clone(), node.provider.clone())
+                .await
+                .unwrap();
+            node.fs.crash_after(rng.range_inclusive(1, 96));
+            let (written, _) = run_until_crash(&log, &mut rng).await;
+            drop(log);
+            node.fs.restart();
+
+            // A lying disk may drop acknowledged batches, but what survives
+            // is still an ordered prefix and the log stays usable.
+            WorkerWALBackend::new_with_provider(layout.clone(), node.provider.clone())
+                .await
+                .unwrap_or_else(|e| panic!("seed {seed}: recovery failed: {e}"));
+            let recovered = recovered_batches(&layout, node.provider.fs()).await;
+            assert!(written.starts_with(&recovered), "seed {seed}");
+        })
+        .unwrap();
+    }
+}
+
+#[test]
+fn wal_reports_disk_full_without_corrupting_the_log() {
+    let faults = SimFsFaults {
+        capacity: Some(CHUNK_SIZE * 2),
+        ..SimFsFaults::default()
+    };
+    let node = sim_node(1, faults);
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let layout = wal_layout("wal", 9);
+        let log = WorkerWALBackend::new_with_provider(layout.clone(), node.provider.clone())
+            .await
+            .unwrap();
+        let mut acked = Vec::new();
+        let err = loop {
+            let batch = insert_batch(acked.len() as u64 + 1);
+            match commit(&log, &batch).await {
+                Ok(()) => acked.push(batch),
+                Err(err) => break err,
+            }
+        };
+        assert_eq!(err.ec(), mudu::error::ErrorCode::StorageFull);
+        drop(log);
+        node.fs.restart();
+
+        WorkerWALBackend::new_with_provider(layout.clone(), node.provider.clone())
+            .await
+            .unwrap();
+        let recovered = recovered_batches(&layout, node.provider.fs()).await;
+        assert!(recovered.starts_with(&acked));
+    })
+    .unwrap();
+}
+
+const COORDINATOR: u128 = 1;
+const PARTICIPANT: u128 = 2;
+
+fn two_phase_record(xid: u64, op: TxOp) -> XLBatch {
+    XLBatch::new(vec![XLEntry { xid, ops: vec![op] }])
+}
+
+fn has_record(batches: &[XLBatch], xid: u64, matches: impl Fn(&TxOp) -> bool) -> bool {
+    batches
+        .iter()
+        .flat_map(|batch| &batch.entries)
+        .any(|entry| entry.xid == xid && entry.ops.iter().any(&matches))
+}
+
+/// Drives the cross-partition commit log protocol of `xid`: the
+/// participant prepares, the coordinator logs its decision, the participant
+/// resolves and the coordinator records delivery. Each step waits for the
+/// previous one to be durable, as the commit path does.
+async fn two_phase_commit(
+    coordinator: &WorkerWALBackend,
+    participant: &WorkerWALBackend,
+    xid: u64,
+) -> RS<()> {
+    let prepare = TxOp::Prepare {
+        coordinator_worker_id: COORDINATOR,
+    };
+    commit(participant, &two_phase_record(xid, prepare)).await?;
+    let decision = TxOp::CommitDecision {
+        participants: vec![PARTICIPANT],
+    };
+    commit(coordinator, &two_phase_record(xid, decision)).await?;
+    let resolve = TxOp::Resolve {
+        coordinator_worker_id: COORDINATOR,
+        commit: true,
+    };
+    commit(participant, &two_phase_record(xid, resolve)).await?;
+    commit(coordinator, &two_phase_record(xid, TxOp::DecisionDelivered)).await
+}
+
+#[test]
+fn two_phase_commit_log_order_survives_seeded_crash() {
+    for seed in sim_seeds() {
+        let faults = SimFsFaults {
+            torn_writes: true,
+            ..SimFsFaults::default()
+        };
+        let node = sim_node(seed, faults);
+        let mut rng = SimRng::new(seed).fork(WORKLOAD_STREAM);
+        mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+            let coordinator_layout = wal_layout("coordinator", COORDINATOR);
+            let participant_layout = wal_layout("participant", PARTICIPANT);
+            let coordinator = WorkerWALBackend::new_with_provider(
+                coordinator_layout.clone(),
+                node.provider.clone(),
+            )
+            .await
+            .unwrap();
+            let participant = WorkerWALBackend::new_with_provider(
+                participant_layout.clone(),
+                node.provider.clone(),
+            )
+            .await
+            .unwrap();
+            node.fs.crash_after(rng.range_inclusive(1, 120));
+            let mut started = 0;
+            for xid in 1..=16 {
+                started = xid;
+                if two_phase_commit(&coordinator, &participant, xid)
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+            drop(coordinator);
+            drop(participant);
+            node.fs.restart();
+
+            for layout in [&coordinator_layout, &participant_layout] {
+                WorkerWALBackend::new_with_provider(layout.clone(), node.provider.clone())
+                    .await
+                    .unwrap_or_else(|e| panic!("seed {seed}: recovery failed: {e}"));
+            }
+            let fs = node.provider.fs();
+            let coordinator_log = recovered_batches(&coordinator_layout, fs).await;
+            let participant_log = recovered_batches(&participant_layout, fs).await;
+            for xid in 1..=started {
+                let prepared = has_record(&participant_log, xid, |op| {
+                    matches!(op, TxOp::Prepare { .. })
+                });
+                let decided = has_record(&coordinator_log, xid, |op| {
+                    matches!(op, TxOp::CommitDecision { .. })
+                });
+                let resolved = has_record(&participant_log, xid, |op| {
+                    matches!(op, TxOp::Resolve { commit: true, .. })
+                });
+                let delivered = has_record(&coordinator_log, xid, |op| {
+                    matches!(op, TxOp::DecisionDelivered)
+                });
+                assert!(
+                    !decided || prepared,
+                    "seed {seed}: xid {xid} decided unprepared"
+                );
+                assert!(
+                    !resolved || decided,
+                    "seed {seed}: xid {xid} resolved undecided"
+                );
+                assert!(
+                    !delivered || resolved,
+                    "seed {seed}: xid {xid} delivered unresolved"
+                );
+                if xid < started {
+                    // Every step of a finished transaction was acknowledged.
+                    assert!(
+                        delivered,
+                        "seed {seed}: xid {xid} lost acknowledged records"
+                    );
+                }
+            }
+        })
+        .unwrap();
+    }
+}
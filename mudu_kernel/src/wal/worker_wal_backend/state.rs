@@ -76,6 +76,16 @@ impl ChunkedWorkerLog {
         })
     }
 
+    /// Closes the current chunk to further appends: the next reservation
+    /// starts a new chunk. Returns the first sequence that is still open to
+    /// appends and the cached handle of the sealed chunk, if any.
+    pub(crate) fn seal_current(&mut self) -> (u64, Option<SysFile>) {
+        self.current_sequence = None;
+        self.current_size = 0;
+        let cached = self.current_file.take().map(|(_, file)| file);
+        (self.next_sequence, cached)
+    }
+
     pub(crate) fn current_path(&self) -> Option<PathBuf> {
         self.current_sequence
             .map(|sequence| self.layout.chunk_path(sequence))
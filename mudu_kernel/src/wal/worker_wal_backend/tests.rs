@@ -46,6 +46,7 @@ fn sample_batch() -> XLBatch {
                 tuple_id: 0,
                 key: b"k1".to_vec(),
                 value: b"v1".to_vec(),
+                layout_version: None,
             })),
             TxOp::Commit,
         ],
@@ -100,6 +101,7 @@ fn worker_log_decodes_multiple_frames_from_single_chunk_payload() {
                 tuple_id: 0,
                 key: b"k2".to_vec(),
                 value: b"v2".to_vec(),
+                layout_version: None,
             })),
             TxOp::Commit,
         ],
@@ -182,6 +184,7 @@ fn sample_xl_batch_1() -> XLBatch {
                 tuple_id: 0,
                 key: b"k".to_vec(),
                 value: vec![9u8; 512],
+                layout_version: None,
             })),
             TxOp::Commit,
         ],
@@ -404,3 +407,198 @@ fn worker_log_reopen_truncates_checksum_corrupted_tail() {
         .unwrap()
     })
 }
+
+#[test]
+fn worker_log_checkpoint_reclaims_sealed_chunks() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let dir = temp_dir().join(format!("worker_log_checkpoint_{}", gen_oid()));
+        let layout = WorkerLogLayout::new(dir, gen_oid(), 4096).unwrap();
+        let provider = create_io_provider(ProviderType::Tokio);
+        let log = WorkerWALBackend::new(layout.clone()).await.unwrap();
+        append_xl_batch_async(&log, &sample_batch()).await.unwrap();
+        append_xl_batch_async(&log, &sample_batch()).await.unwrap();
+
+        let seal = log.begin_checkpoint_async().await.unwrap().unwrap();
+        assert_eq!(seal.lsn, LSN::new(1));
+        // Entries appended after the seal go to a new chunk.
+        append_xl_batch_async(&log, &sample_batch()).await.unwrap();
+        log.flush_async().await.unwrap();
+        let stats = log
+            .finish_checkpoint_async(seal, b"state".to_vec())
+            .await
+            .unwrap();
+        assert_eq!(stats.reclaimed_chunks, 1);
+        assert!(stats.reclaimed_bytes > 0);
+        assert!(stats.wal_bytes > 0);
+        assert!(!mudu_sys::fs::sync::sync_path_exists(layout.chunk_path(0)));
+        assert!(mudu_sys::fs::sync::sync_path_exists(layout.chunk_path(1)));
+
+        let checkpoint = layout
+            .read_checkpoint_async(provider.fs())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            checkpoint,
+            WalCheckpoint {
+                lsn: LSN::new(1),
+                payload: b"state".to_vec(),
+            }
+        );
+        let frames =
+            decode_frames(&mudu_sys::fs::sync::read(layout.chunk_path(1)).unwrap()).unwrap();
+        assert_eq!(decode_xl_batches(&frames).unwrap(), vec![sample_batch()]);
+    })
+    .unwrap()
+}
+
+#[test]
+fn worker_log_checkpoint_keeps_lsns_after_full_reclaim() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let dir = temp_dir().join(format!("worker_log_checkpoint_reopen_{}", gen_oid()));
+        let layout = WorkerLogLayout::new(dir, gen_oid(), 4096).unwrap();
+        let log = WorkerWALBackend::new(layout.clone()).await.unwrap();
+        append_xl_batch_async(&log, &sample_batch()).await.unwrap();
+        append_xl_batch_async(&log, &sample_batch()).await.unwrap();
+        let seal = log.begin_checkpoint_async().await.unwrap().unwrap();
+        let stats = log.finish_checkpoint_async(seal, Vec::new()).await.unwrap();
+        assert_eq!(stats.wal_bytes, 0);
+        // Nothing was logged since, so there is nothing to checkpoint.
+        assert!(log.begin_checkpoint_async().await.unwrap().is_none());
+        drop(log);
+
+        // Every chunk is gone; the checkpoint alone keeps new entries above
+        // the LSNs it covers.
+        let reopened = WorkerWALBackend::new(layout).await.unwrap();
+        assert_eq!(reopened.checkpoint_lsn().unwrap(), Some(LSN::new(1)));
+        append_xl_batch_async(&reopened, &sample_batch())
+            .await
+            .unwrap();
+        assert_eq!(reopened.last_allocated_lsn(), LSN::new(2));
+    })
+    .unwrap()
+}
+
+fn batch_with_xid(xid: u64) -> XLBatch {
+    let mut batch = sample_batch();
+    batch.entries[0].xid = xid;
+    batch
+}
+
+#[test]
+fn worker_log_checkpoint_archives_reclaimed_chunks() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let dir = temp_dir().join(format!("worker_log_archive_{}", gen_oid()));
+        let archive_dir = dir.join("archive");
+        let layout = WorkerLogLayout::new(dir.join("log"), gen_oid(), 4096)
+            .unwrap()
+            .with_archive_dir(Some(archive_dir.clone()));
+        let log = WorkerWALBackend::new(layout.clone()).await.unwrap();
+        append_xl_batch_async(&log, &sample_batch()).await.unwrap();
+        append_xl_batch_async(&log, &sample_batch()).await.unwrap();
+        let sealed_bytes = {
+            log.flush_async().await.unwrap();
+            mudu_sys::fs::sync::read(layout.chunk_path(0)).unwrap()
+        };
+        let seal = log.begin_checkpoint_async().await.unwrap().unwrap();
+        let stats = log.finish_checkpoint_async(seal, Vec::new()).await.unwrap();
+        assert_eq!(stats.reclaimed_chunks, 1);
+        assert!(!mudu_sys::fs::sync::sync_path_exists(layout.chunk_path(0)));
+        let archived = layout.chunk_paths_in_sync(&archive_dir).unwrap();
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].0, 0);
+        assert_eq!(
+            mudu_sys::fs::sync::read(&archived[0].1).unwrap(),
+            sealed_bytes
+        );
+
+        // A restore brings the archived chunk back into the log directory.
+        let restored = WorkerLogLayout::new(dir.join("restored"), layout.log_oid(), 4096).unwrap();
+        assert_eq!(
+            restored
+                .restore_archived_chunks_sync(&archive_dir, 0)
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            mudu_sys::fs::sync::read(restored.chunk_path(0)).unwrap(),
+            sealed_bytes
+        );
+        assert_eq!(
+            restored
+                .restore_archived_chunks_sync(&archive_dir, 1)
+                .unwrap(),
+            0
+        );
+    })
+    .unwrap()
+}
+
+#[test]
+fn worker_log_truncate_cuts_before_first_matching_entry() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let dir = temp_dir().join(format!("worker_log_truncate_{}", gen_oid()));
+        let layout = WorkerLogLayout::new(dir, gen_oid(), 4096).unwrap();
+        let log = WorkerWALBackend::new(layout.clone()).await.unwrap();
+        for xid in 1..=3 {
+            append_xl_batch_async(&log, &batch_with_xid(xid))
+                .await
+                .unwrap();
+        }
+        log.flush_async().await.unwrap();
+        drop(log);
+
+        let third = |_: LSN, batch: &XLBatch| batch.entries[0].xid >= 3;
+        // The entry is kept when it is not past `keep_through`.
+        assert_eq!(layout.truncate_log_sync(2, third).unwrap(), None);
+        assert_eq!(
+            layout.truncate_log_sync(0, third).unwrap(),
+            Some(LSN::new(2))
+        );
+        let frames =
+            decode_frames(&mudu_sys::fs::sync::read(layout.chunk_path(0)).unwrap()).unwrap();
+        assert_eq!(
+            decode_xl_batches(&frames).unwrap(),
+            vec![batch_with_xid(1), batch_with_xid(2)]
+        );
+
+        // The cut log reopens and keeps appending after the kept entries.
+        let reopened = WorkerWALBackend::new(layout).await.unwrap();
+        assert_eq!(reopened.last_allocated_lsn(), LSN::new(1));
+    })
+    .unwrap()
+}
+
+#[test]
+fn worker_log_backup_copies_checkpoint_and_chunks() {
+    mudu_sys::task::async_::block_on_tokio_current_thread(async move {
+        let dir = temp_dir().join(format!("worker_log_backup_{}", gen_oid()));
+        let layout = WorkerLogLayout::new(dir.join("log"), gen_oid(), 4096).unwrap();
+        let log = WorkerWALBackend::new(layout.clone()).await.unwrap();
+        append_xl_batch_async(&log, &sample_batch()).await.unwrap();
+        let seal = log.begin_checkpoint_async().await.unwrap().unwrap();
+        log.finish_checkpoint_async(seal, b"state".to_vec())
+            .await
+            .unwrap();
+        append_xl_batch_async(&log, &sample_batch()).await.unwrap();
+
+        let target = dir.join("backup");
+        let backup = log.backup_async(&target).await.unwrap();
+        assert_eq!(
+            backup,
+            WorkerLogBackup {
+                log_oid: layout.log_oid(),
+                checkpoint_lsn: Some(0),
+                end_lsn: 1,
+            }
+        );
+        let copied = WorkerLogLayout::new(&target, layout.log_oid(), 4096).unwrap();
+        let frames =
+            decode_frames(&mudu_sys::fs::sync::read(copied.chunk_path(1)).unwrap()).unwrap();
+        assert_eq!(decode_xl_batches(&frames).unwrap(), vec![sample_batch()]);
+        assert!(mudu_sys::fs::sync::sync_path_exists(
+            copied.checkpoint_path(LSN::new(0))
+        ));
+    })
+    .unwrap()
+}
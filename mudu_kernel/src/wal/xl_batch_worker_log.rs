@@ -142,6 +142,7 @@ mod tests {
                         tuple_id: xid as u64 + 10,
                         key: format!("key-{xid}").into_bytes(),
                         value: vec![xid as u8; payload_size],
+                        layout_version: None,
                     })),
                     TxOp::Commit,
                 ],
@@ -237,6 +238,7 @@ mod tests {
                     tuple_id: 0,
                     key: b"k1".to_vec(),
                     value: b"v1".to_vec(),
+                    layout_version: None,
                 })),
                 TxOp::Write(XLWrite::Insert(XLInsert {
                     table_id: 9,
@@ -244,6 +246,7 @@ mod tests {
                     tuple_id: 0,
                     key: b"k2".to_vec(),
                     value: b"v2".to_vec(),
+                    layout_version: None,
                 })),
                 TxOp::Commit,
             ],
@@ -27,6 +27,12 @@ pub struct XLInsert {
     ///
     /// Unlike updates, inserts persist the complete row payload here.
     pub value: Vec<u8>,
+    /// Value layout version of the table `value` was encoded with, stamped
+    /// at commit. Replay upgrades payloads of a layout retired by ALTER
+    /// TABLE since. `None` in worker KV records and in entries logged before
+    /// the version was recorded; replay then writes the value as is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout_version: Option<u32>,
 }
 
 /// Logical WAL payload for deleting one tuple from a table.
@@ -63,6 +69,21 @@ pub struct XLUpdate {
     /// This is not necessarily the full row image. It stores the change set
     /// needed to transform the previous value into the new value.
     pub delta: Vec<u8>,
+    /// Table layout the delta was encoded against, stamped at commit; see
+    /// [`XLInsert::layout_version`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout: Option<XLDeltaLayout>,
+}
+
+/// Table layout of an [`XLUpdate`] delta. Column indexes shift when ALTER
+/// TABLE adds or drops columns, so the OIDs of the assigned columns are
+/// recorded to re-target the delta at replay.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct XLDeltaLayout {
+    /// Value layout version of the table.
+    pub version: u32,
+    /// Column OID of each delta assignment, in assignment order.
+    pub column_oids: Vec<OID>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
@@ -88,6 +109,14 @@ impl XLWrite {
             Self::Delete(write) => write.partition_id,
         }
     }
+
+    pub fn key(&self) -> &[u8] {
+        match self {
+            Self::Insert(write) => &write.key,
+            Self::Update(write) => &write.key,
+            Self::Delete(write) => &write.key,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -109,6 +138,7 @@ mod tests {
             tuple_id: 3,
             key: vec![4],
             value: vec![5, 6],
+            layout_version: Some(7),
         }
     }
 
@@ -119,6 +149,10 @@ mod tests {
             tuple_id: 13,
             key: vec![14],
             delta: vec![15, 16],
+            layout: Some(XLDeltaLayout {
+                version: 3,
+                column_oids: vec![17],
+            }),
         }
     }
 
@@ -147,6 +181,19 @@ mod tests {
         assert_eq!(orig, decoded);
     }
 
+    #[test]
+    fn xl_insert_without_layout_version_keeps_the_legacy_encoding() {
+        let orig = XLInsert {
+            layout_version: None,
+            ..sample_insert()
+        };
+        let encoded = rmp_serde::to_vec(&orig).unwrap();
+        let legacy = rmp_serde::to_vec(&(1u128, 2u128, 3u64, vec![4u8], vec![5u8, 6])).unwrap();
+        assert_eq!(encoded, legacy);
+        let decoded: XLInsert = rmp_serde::from_slice(&legacy).unwrap();
+        assert_eq!(decoded, orig);
+    }
+
     #[test]
     fn xl_delete_serializes_and_deserializes() {
         let orig = sample_delete();
@@ -168,4 +215,11 @@ mod tests {
         assert_eq!(XLWrite::Update(sample_update()).partition_id(), 12);
         assert_eq!(XLWrite::Delete(sample_delete()).partition_id(), 22);
     }
+
+    #[test]
+    fn xl_write_key_returns_embedded_key() {
+        assert_eq!(XLWrite::Insert(sample_insert()).key(), &[4]);
+        assert_eq!(XLWrite::Update(sample_update()).key(), &[14]);
+        assert_eq!(XLWrite::Delete(sample_delete()).key(), &[24]);
+    }
 }
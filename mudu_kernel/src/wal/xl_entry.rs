@@ -1,6 +1,8 @@
 use crate::wal::xl_data_op::XLWrite;
 use mudu::common::id::OID;
+use mudu_sys::time::system_time_now;
 use serde::{Deserialize, Serialize};
+use std::time::UNIX_EPOCH;
 
 /// A transaction-log entry for a single transaction.
 ///
@@ -39,6 +41,32 @@ pub enum TxOp {
     Abort,
     /// Apply one tuple write to a table.
     Write(XLWrite),
+    /// Marks a participant's share of a two-phase cross-partition commit as
+    /// prepared: the writes logged before it are durable but are applied only
+    /// once a [`TxOp::Resolve`] for the same `(coordinator_worker_id, xid)`
+    /// commits them.
+    Prepare { coordinator_worker_id: OID },
+    /// Participant outcome of a prepared transaction: `commit` applies the
+    /// prepared writes, otherwise they are dropped.
+    Resolve {
+        coordinator_worker_id: OID,
+        commit: bool,
+    },
+    /// Coordinator commit decision of a two-phase commit. The coordinator's
+    /// own writes logged before it are committed; `participants` are the
+    /// workers that still have to learn the decision.
+    CommitDecision { participants: Vec<OID> },
+    /// Every participant of the transaction's [`TxOp::CommitDecision`] has
+    /// acknowledged it.
+    DecisionDelivered,
+    /// Wall-clock time the commit was logged, in microseconds since the Unix
+    /// epoch. Replay ignores it; point-in-time restore stops before the
+    /// first entry committed after its target time.
+    CommitTime { unix_micros: u64 },
+    /// Every transaction with a smaller xid had ended when this entry's LSN
+    /// was allocated, so a standby that replayed up to here can read at
+    /// `xid` without seeing a partial transaction. Carries no writes.
+    SnapshotHorizon { xid: u64 },
 }
 
 impl TxOp {
@@ -55,6 +83,26 @@ impl TxOp {
             _ => None,
         }
     }
+
+    /// A [`TxOp::CommitTime`] carrying the current wall-clock time.
+    pub fn commit_time_now() -> Self {
+        let unix_micros = system_time_now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_micros()
+            .min(u64::MAX as u128) as u64;
+        Self::CommitTime { unix_micros }
+    }
+}
+
+impl XLEntry {
+    /// The commit time recorded by a [`TxOp::CommitTime`] of this entry.
+    pub fn commit_time(&self) -> Option<u64> {
+        self.ops.iter().find_map(|op| match op {
+            TxOp::CommitTime { unix_micros } => Some(*unix_micros),
+            _ => None,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -77,6 +125,7 @@ mod tests {
             tuple_id: 1,
             key: vec![1],
             value: vec![2],
+            layout_version: None,
         })
     }
 
@@ -95,12 +144,53 @@ mod tests {
         assert_eq!(orig, decoded);
     }
 
+    #[test]
+    fn xl_entry_two_phase_ops_round_trip() {
+        let prepare = XLEntry {
+            xid: 7,
+            ops: vec![
+                TxOp::Begin,
+                TxOp::Write(sample_insert_write()),
+                TxOp::Prepare {
+                    coordinator_worker_id: 3,
+                },
+            ],
+        };
+        let resolve = XLEntry {
+            xid: 7,
+            ops: vec![TxOp::Resolve {
+                coordinator_worker_id: 3,
+                commit: true,
+            }],
+        };
+        let decision = XLEntry {
+            xid: 8,
+            ops: vec![
+                TxOp::Begin,
+                TxOp::Write(sample_insert_write()),
+                TxOp::CommitDecision {
+                    participants: vec![1, 2],
+                },
+            ],
+        };
+        let delivered = XLEntry {
+            xid: 8,
+            ops: vec![TxOp::DecisionDelivered],
+        };
+        for orig in [prepare, resolve, decision, delivered] {
+            let encoded = rmp_serde::to_vec(&orig).unwrap();
+            let decoded: XLEntry = rmp_serde::from_slice(&encoded).unwrap();
+            assert_eq!(orig, decoded);
+        }
+    }
+
     #[test]
     fn tx_op_table_id_returns_write_table_id_or_none() {
         assert_eq!(TxOp::Write(sample_insert_write()).table_id(), Some(100));
         assert_eq!(TxOp::Begin.table_id(), None);
         assert_eq!(TxOp::Commit.table_id(), None);
         assert_eq!(TxOp::Abort.table_id(), None);
+        assert_eq!(TxOp::DecisionDelivered.table_id(), None);
     }
 
     #[test]
@@ -119,6 +209,7 @@ mod tests {
             tuple_id: 2,
             key: vec![3],
             delta: vec![4],
+            layout: None,
         });
         let delete = XLWrite::Delete(XLDelete {
             table_id: 500,
@@ -2,6 +2,10 @@ use async_trait::async_trait;
 use std::ops::Bound;
 use std::sync::Arc;
 
+use crate::contract::partition_rule::PartitionRuleDesc;
+use crate::contract::partition_rule_binding::PartitionPlacement;
+use crate::contract::schema_column::SchemaColumn;
+use crate::contract::schema_index::SchemaIndex;
 use crate::contract::schema_table::SchemaTable;
 use crate::x_engine::data_bin::DataBin;
 use crate::x_engine::operator::Operator;
@@ -11,6 +15,8 @@ use mudu::common::result::RS;
 use mudu::error::ErrorCode;
 use mudu::mudu_error;
 use mudu_contract::tuple::tuple_field::TupleField;
+use mudu_type::data_type_info::DataTypeInfo;
+use mudu_type::type_family::TypeFamily;
 
 pub type TupleRow = TupleField;
 
@@ -57,10 +63,41 @@ pub enum Predicate {
     DNF(Vec<Vec<(AttrIndex, Filter)>>),
     /// equality over a left prefix of the primary key, evaluated during range reads
     KeyPrefixEq(Vec<(AttrIndex, DataBin)>),
+    /// equality over a left prefix of the columns of secondary index
+    /// `index_oid`, listed in index column order; range reads answer it from
+    /// the index instead of scanning the key range
+    IndexEq {
+        index_oid: OID,
+        prefix: Vec<(AttrIndex, DataBin)>,
+    },
 }
 
 /// alter table parameter
-pub enum AlterTable {}
+///
+/// Columns are addressed by name. Primary key columns cannot be dropped or
+/// retyped: the key tuple layout of a relation never changes.
+#[derive(Clone, Debug)]
+pub enum AlterTable {
+    /// Appends a non-key column. Rows written before the change read it as
+    /// NULL, or as the type's default value when the column is NOT NULL.
+    AddColumn(SchemaColumn),
+    /// Drops a non-key column.
+    DropColumn(String),
+    /// Renames a column; the stored tuple layout is unchanged.
+    RenameColumn { old_name: String, new_name: String },
+    /// Changes the type of a non-key column. Values stored under the old
+    /// type are converted through their textual form when read.
+    AlterColumnType {
+        name: String,
+        type_id: TypeFamily,
+        type_param: DataTypeInfo,
+    },
+    /// Adds a secondary index. Index names are unique within a table; the
+    /// index is built from the existing rows when the relations reopen.
+    AddIndex(SchemaIndex),
+    /// Drops a secondary index by name.
+    DropIndex(String),
+}
 
 /// Sign of a restricted `SET col = col <+|-> <literal>` update assignment.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -191,14 +228,30 @@ pub trait XContract: Send + Sync {
     /// Drops the table identified by `oid`.
     async fn drop_table(&self, tx_mgr: Arc<dyn TxMgr>, oid: OID) -> RS<()>;
 
-    /// Applies an alter-table operation to the target table.
+    /// Applies the actions of one ALTER TABLE statement to the target table,
+    /// in order, as a single catalog change.
     async fn alter_table(
         &self,
         tx_mgr: Arc<dyn TxMgr>,
         oid: OID,
-        alter_table: &AlterTable,
+        alter_table: &[AlterTable],
     ) -> RS<()>;
 
+    /// Switches partition rule `rule.oid` to `rule`, a newer version of it,
+    /// recording `placements` and moving the rows of every table bound to the
+    /// rule whose partition or owning worker changes.
+    async fn alter_partition_rule(
+        &self,
+        _tx_mgr: Arc<dyn TxMgr>,
+        _rule: &PartitionRuleDesc,
+        _placements: &[PartitionPlacement],
+    ) -> RS<()> {
+        Err(mudu_error!(
+            ErrorCode::NotImplemented,
+            "alter partition rule is not implemented"
+        ))
+    }
+
     /// Starts a new transaction and returns its transaction manager.
     async fn begin_tx(&self) -> RS<Arc<dyn TxMgr>>;
 
@@ -269,6 +322,23 @@ pub trait XContract: Send + Sync {
         opt_insert: &OptInsert,
     ) -> RS<()>;
 
+    /// Locks the constraint value `value` of `oid`, a unique index or a
+    /// table whose key a foreign key references, until the transaction
+    /// commits or aborts.
+    ///
+    /// The constraint hooks take this lock before they look a value up, so
+    /// a concurrent transaction checking the same value waits for this one
+    /// to finish and then reads its committed rows. Implementations without
+    /// concurrent transactions may keep the default, which locks nothing.
+    async fn lock_constraint_value(
+        &self,
+        _tx_mgr: Arc<dyn TxMgr>,
+        _oid: OID,
+        _value: Vec<u8>,
+    ) -> RS<()> {
+        Ok(())
+    }
+
     /// Returns the id of the worker executing this contract, or 0 when the
     /// implementation does not know (for example in tests).
     ///
@@ -4,7 +4,7 @@ use crate::x_engine::api::DeltaAssign;
 use mudu::common::id::OID;
 use mudu::common::result::RS;
 use mudu::mudu_error;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct PhysicalRelationId {
@@ -12,6 +12,51 @@ pub struct PhysicalRelationId {
     pub partition_id: OID,
 }
 
+/// The relation id KV (non-relational) writes and reads are tracked under.
+pub const KV_RELATION_ID: PhysicalRelationId = PhysicalRelationId {
+    table_id: 0,
+    partition_id: 0,
+};
+
+/// Transaction isolation level.
+///
+/// `Snapshot` is the default: concurrent writes of the same key conflict,
+/// but a transaction never conflicts with writers of keys it only read, so
+/// write skew is possible. `Serializable` additionally tracks the keys the
+/// transaction read and aborts it at commit with
+/// `ErrorCode::SerializationFailure` when it could be part of a
+/// non-serializable schedule (see `server::worker_ssi`).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum IsolationLevel {
+    #[default]
+    Snapshot,
+    Serializable,
+}
+
+/// Keys and key ranges a serializable transaction read from committed
+/// storage. Ranges are `[start, end)`; an empty `end` is unbounded.
+#[derive(Clone, Debug, Default)]
+pub struct ReadSet {
+    pub keys: BTreeSet<(PhysicalRelationId, Vec<u8>)>,
+    pub ranges: Vec<(PhysicalRelationId, Vec<u8>, Vec<u8>)>,
+}
+
+impl ReadSet {
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty() && self.ranges.is_empty()
+    }
+
+    /// Whether a write of `key` in `relation` overlaps this read set.
+    pub fn covers(&self, relation: &PhysicalRelationId, key: &[u8]) -> bool {
+        self.keys.contains(&(*relation, key.to_vec()))
+            || self.ranges.iter().any(|(range_relation, start, end)| {
+                range_relation == relation
+                    && key >= start.as_slice()
+                    && (end.is_empty() || key < end.as_slice())
+            })
+    }
+}
+
 pub trait TxMgr: Send + Sync {
     fn xid(&self) -> u64;
 
@@ -122,4 +167,60 @@ pub trait TxMgr: Send + Sync {
     /// Forget the tracked remote lock owners (after a successful handoff
     /// commit, which releases them on the owner).
     fn clear_remote_lock_owners(&self) {}
+
+    /// Record that a statement of the session ran in this transaction.
+    /// Default is a no-op.
+    fn record_statement(&self) {}
+
+    /// Whether a statement ran in this transaction; its isolation level is
+    /// fixed from then on.
+    fn has_run_statement(&self) -> bool {
+        false
+    }
+
+    /// Isolation level of this transaction. Default is `Snapshot`.
+    fn isolation_level(&self) -> IsolationLevel {
+        IsolationLevel::Snapshot
+    }
+
+    /// Change the isolation level of this transaction. The default only
+    /// accepts `Snapshot`; transaction managers without read tracking cannot
+    /// run serializable transactions.
+    fn set_isolation_level(&self, level: IsolationLevel) -> RS<()> {
+        match level {
+            IsolationLevel::Snapshot => Ok(()),
+            IsolationLevel::Serializable => Err(mudu_error!(
+                mudu::error::ErrorCode::NotImplemented,
+                "serializable isolation is not supported by this transaction manager"
+            )),
+        }
+    }
+
+    /// Record that this transaction read `key` of `relation` from committed
+    /// storage. Only serializable transactions keep reads; the default is a
+    /// no-op.
+    fn record_read(&self, _relation: PhysicalRelationId, _key: &[u8]) {}
+
+    /// Record that this transaction scanned `[start, end)` of `relation`
+    /// (an empty `end` is unbounded). Same rules as `record_read`.
+    fn record_range_read(&self, _relation: PhysicalRelationId, _start: &[u8], _end: &[u8]) {}
+
+    /// The reads recorded so far.
+    fn read_set(&self) -> ReadSet {
+        ReadSet::default()
+    }
+
+    /// Record that a statement staged rows of `table_id` encoded under value
+    /// layout `version`. Fails with `ErrorCode::Transaction` when rows of the
+    /// table were staged under another layout before, i.e. an ALTER TABLE
+    /// ran in between. Default is a no-op.
+    fn record_layout_version(&self, _table_id: OID, _version: u32) -> RS<()> {
+        Ok(())
+    }
+
+    /// The value layout the staged rows of each table were encoded under, as
+    /// recorded by `record_layout_version`.
+    fn staged_layout_versions(&self) -> BTreeMap<OID, u32> {
+        BTreeMap::new()
+    }
 }
@@ -2,9 +2,13 @@ use crate::contract::fs_type::FsTypeKind;
 use crate::contract::partition_rule::PartitionRuleDesc;
 use crate::contract::partition_rule_binding::{PartitionPlacement, TablePartitionBinding};
 use crate::contract::schema_table::SchemaTable;
-use crate::x_engine::api::{DeltaAssign, OptRead, Predicate, RangeData, VecDatum, VecSelTerm};
+use crate::executor::scalar_expr::ScalarExpr;
+use crate::x_engine::api::{
+    AlterTable, DeltaAssign, OptRead, Predicate, RangeData, VecDatum, VecSelTerm,
+};
 use crate::x_engine::tx_mgr::TxMgr;
-use mudu::common::id::OID;
+use mudu::common::id::{AttrIndex, OID};
+use mudu_contract::tuple::tuple_field_desc::TupleFieldDesc;
 use std::sync::Arc;
 
 #[derive(Clone)]
@@ -50,6 +54,20 @@ pub struct PDropTable {
     pub oid: Option<OID>,
 }
 
+#[derive(Clone)]
+pub struct PAlterPartitionRule {
+    pub tx_mgr: Arc<dyn TxMgr>,
+    pub rule: PartitionRuleDesc,
+    pub placements: Vec<PartitionPlacement>,
+}
+
+#[derive(Clone)]
+pub struct PAlterTable {
+    pub tx_mgr: Arc<dyn TxMgr>,
+    pub oid: Option<OID>,
+    pub actions: Vec<AlterTable>,
+}
+
 #[derive(Clone)]
 pub struct PCreateFsType {
     pub name: String,
@@ -66,6 +84,36 @@ pub struct PInsertKeyValue {
     pub tx_mgr: Arc<dyn TxMgr>,
     pub table_id: OID,
     pub rows: Vec<(VecDatum, VecDatum)>,
+    /// Columns every row omits that are filled from their `DEFAULT`.
+    pub default_attrs: Vec<AttrIndex>,
+    /// Action taken instead of inserting a row whose key already exists.
+    pub on_conflict: Option<PConflictAction>,
+    pub returning: Option<PReturning>,
+}
+
+/// `ON CONFLICT` action of [`PInsertKeyValue`].
+#[derive(Clone)]
+pub enum PConflictAction {
+    DoNothing,
+    /// Update the existing row, split like [`PUpdateKeyValue`].
+    DoUpdate {
+        value: VecDatum,
+        delta_assignments: Vec<DeltaAssign>,
+        /// Evaluated over the existing row followed by the proposed row, so
+        /// `EXCLUDED.col` is at the table width plus `col`.
+        expr_assignments: Vec<(AttrIndex, ScalarExpr)>,
+        /// `SET col = EXCLUDED.src` assignments as `(col, src)`, filled from
+        /// the proposed row.
+        excluded: Vec<(AttrIndex, AttrIndex)>,
+    },
+}
+
+/// `RETURNING` list of a DML command: the columns read back from each
+/// affected row.
+#[derive(Clone)]
+pub struct PReturning {
+    pub select: VecSelTerm,
+    pub tuple_desc: TupleFieldDesc,
 }
 
 #[derive(Clone)]
@@ -77,6 +125,10 @@ pub struct PUpdateKeyValue {
     /// Restricted `SET col = col <+|-> <integer literal or ?>` assignments,
     /// evaluated against the latest committed row under the statement lock.
     pub delta_assignments: Vec<DeltaAssign>,
+    /// `SET col = <expr>` assignments, evaluated over the row's current
+    /// values (column `i` at position `i`).
+    pub expr_assignments: Vec<(AttrIndex, ScalarExpr)>,
+    pub returning: Option<PReturning>,
 }
 
 #[derive(Clone)]
@@ -84,4 +136,5 @@ pub struct PDeleteKeyValue {
     pub tx_mgr: Arc<dyn TxMgr>,
     pub table_id: OID,
     pub key: VecDatum,
+    pub returning: Option<PReturning>,
 }
@@ -26,6 +26,27 @@ pub trait AppMgr: Send + Sync {
     /// If no such application exists, an error should be returned.
     async fn uninstall(&self, app_name: Vec<u8>) -> RS<()>;
 
+    /// Upgrade one installed application from the `.mpk` binary payload of
+    /// a newer version.
+    ///
+    /// The package must name an installed application and carry a higher
+    /// `version` than the installed one. Invocations already in flight
+    /// finish on the old version while new invocations route to the new one,
+    /// so the application stays available throughout. The package's optional
+    /// `migrate.sql` runs once against the application database, after every
+    /// runtime serves the new version. The replaced package is kept so that
+    /// [`AppMgr::rollback`] can restore it; if the upgrade or its migration
+    /// fails, the old version is restored everywhere.
+    async fn upgrade(&self, mpk_binary: Vec<u8>) -> RS<()>;
+
+    /// Restore the version an application had before its last upgrade.
+    ///
+    /// The input is a UTF-8 encoded application name. Migration SQL cannot
+    /// be reverted, so rolling back an upgrade whose migration was applied
+    /// is refused. If the application has no previous version, an error
+    /// should be returned.
+    async fn rollback(&self, app_name: Vec<u8>) -> RS<()>;
+
     /// Return application metadata according to the supplied filter options.
     ///
     /// This method should expose the same package-derived information that the
@@ -1,19 +1,27 @@
 #![allow(missing_docs)]
 
+use std::time::Duration;
+
 #[derive(Clone, Copy, Debug)]
 pub struct HttpApiCapabilities {
     pub enable_invoke: bool,
     pub enable_uninstall: bool,
+    /// Lifetime of bearer tokens; `None` leaves the API unauthenticated.
+    /// When set, install/uninstall require an admin token and invoke requires
+    /// any valid token.
+    pub auth_token_ttl: Option<Duration>,
 }
 
 impl HttpApiCapabilities {
     pub const LEGACY: Self = Self {
         enable_invoke: true,
         enable_uninstall: false,
+        auth_token_ttl: None,
     };
 
     pub const IOURING: Self = Self {
         enable_invoke: true,
         enable_uninstall: true,
+        auth_token_ttl: None,
     };
 }
@@ -1,9 +1,12 @@
 use super::HttpApi;
+use super::http_token_store::HttpTokenStore;
 use std::sync::Arc;
 
 #[derive(Clone)]
 pub(crate) struct HttpApiContext {
     pub api: Arc<dyn HttpApi>,
+    /// `None` when the API is unauthenticated.
+    pub tokens: Option<Arc<HttpTokenStore>>,
 }
 
 unsafe impl Send for HttpApiContext {}
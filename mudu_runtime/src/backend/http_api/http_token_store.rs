@@ -0,0 +1,83 @@
+use mudu::common::result::RS;
+use mudu::error::ErrorCode;
+use mudu::mudu_error;
+use mudu_contract::protocol::scram::new_nonce;
+use mudu_kernel::contract::user::UserRole;
+use mudu_sys::sync::SMutex;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Bearer tokens issued by `POST /mudu/auth/token`, kept in memory: a
+/// restart logs every HTTP client out.
+pub(crate) struct HttpTokenStore {
+    ttl: Duration,
+    tokens: SMutex<HashMap<String, HttpToken>>,
+}
+
+struct HttpToken {
+    role: UserRole,
+    expires_at: Instant,
+}
+
+impl HttpTokenStore {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            tokens: SMutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Issue a token for an authenticated user, dropping expired ones.
+    pub(crate) fn issue(&self, role: UserRole) -> RS<String> {
+        let now = mudu_sys::time::instant_now();
+        let token = new_nonce();
+        let mut tokens = self.tokens.lock()?;
+        tokens.retain(|_, token| token.expires_at > now);
+        tokens.insert(
+            token.clone(),
+            HttpToken {
+                role,
+                expires_at: now + self.ttl,
+            },
+        );
+        Ok(token)
+    }
+
+    /// Check the `Authorization` header of a request; `admin` additionally
+    /// requires the token to belong to an admin.
+    pub(crate) fn authorize(&self, authorization: Option<&str>, admin: bool) -> RS<()> {
+        let token = authorization
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim)
+            .ok_or_else(|| {
+                mudu_error!(
+                    ErrorCode::PermissionDenied,
+                    "missing bearer token, request one from /mudu/auth/token"
+                )
+            })?;
+        let now = mudu_sys::time::instant_now();
+        let role = self
+            .tokens
+            .lock()?
+            .get(token)
+            .filter(|token| token.expires_at > now)
+            .map(|token| token.role)
+            .ok_or_else(|| {
+                mudu_error!(
+                    ErrorCode::PermissionDenied,
+                    "invalid or expired bearer token"
+                )
+            })?;
+        if admin && !role.is_admin() {
+            return Err(mudu_error!(
+                ErrorCode::PermissionDenied,
+                "this operation requires an admin user"
+            ));
+        }
+        Ok(())
+    }
+}
@@ -1,9 +1,9 @@
 #![allow(missing_docs)]
 
 use super::{
-    AsyncKernelInvokeClientFactory, HttpApi, KernelInvokeClientFactory, PartitionRouteEntry,
-    PartitionRouteRequest, PartitionRouteResponse, ServerTopology, WorkerTopology, find_app,
-    parse_json_object_body, to_param,
+    AsyncKernelInvokeClientFactory, ChangeFeedQuery, HttpApi, KernelInvokeClientFactory,
+    PartitionRouteEntry, PartitionRouteRequest, PartitionRouteResponse, ServerTopology,
+    WorkerTopology, find_app, parse_json_object_body, to_param,
 };
 use crate::backend::app_mgr::AppMgr;
 use crate::backend::mudud_cfg::MuduDBCfg;
@@ -14,17 +14,24 @@ use mudu::mudu_error;
 use mudu::utils::json::JsonValue;
 use mudu_binding::procedure::procedure_invoke;
 use mudu_contract::procedure::proc_desc::ProcDesc;
+use mudu_contract::protocol::change_feed::ChangeFeedRequest;
 use mudu_kernel::contract::meta_mgr::MetaMgr;
+use mudu_kernel::contract::partition_rule::PartitionRuleKind;
+use mudu_kernel::contract::user::UserRole;
 use mudu_kernel::meta::meta_mgr_factory::MetaMgrFactory;
 use mudu_kernel::mudu_conn::mudu_conn_async::{
     set_default_remote_addr, set_default_remote_worker_id,
 };
+use mudu_kernel::server::backup::backup_async;
+use mudu_kernel::server::change_feed::ChangeFeed;
 use mudu_kernel::server::partition_router::{
     DEFAULT_UNPARTITIONED_TABLE_PARTITION_ID, PartitionRouter,
 };
+use mudu_kernel::server::replication::promote_standby_async;
 use mudu_kernel::server::worker_registry::WorkerRegistry;
 use serde_json::Value;
 use std::ops::Bound;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 pub struct KernelHttpApi {
@@ -36,6 +43,11 @@ pub struct KernelHttpApi {
     meta_mgr: Arc<dyn MetaMgr>,
     partition_router: PartitionRouter,
     client_factory: Arc<dyn AsyncKernelInvokeClientFactory>,
+    /// Database directory of the kernel serving this API; backups are only
+    /// possible when it is known.
+    db_path: Option<PathBuf>,
+    log_chunk_size: u64,
+    wal_archive_dir: Option<PathBuf>,
 }
 
 impl KernelHttpApi {
@@ -55,6 +67,11 @@ impl KernelHttpApi {
             worker_registry,
             meta_mgr,
             Arc::new(KernelInvokeClientFactory),
+        )
+        .with_db_path(&cfg.db_path)
+        .with_worker_log(
+            cfg.log_chunk_size,
+            cfg.wal_archive_dir.as_ref().map(PathBuf::from),
         ))
     }
 
@@ -78,9 +95,27 @@ impl KernelHttpApi {
             partition_router: PartitionRouter::new(meta_mgr.clone()),
             meta_mgr,
             client_factory,
+            db_path: None,
+            log_chunk_size: 0,
+            wal_archive_dir: None,
         }
     }
 
+    /// Enables online backups of the kernel storing its data and worker
+    /// logs in `db_path`.
+    pub fn with_db_path(mut self, db_path: impl Into<PathBuf>) -> Self {
+        self.db_path = Some(db_path.into());
+        self
+    }
+
+    /// Layout of the worker logs in the database directory, which the
+    /// change feed reads.
+    pub fn with_worker_log(mut self, chunk_size: u64, archive_dir: Option<PathBuf>) -> Self {
+        self.log_chunk_size = chunk_size;
+        self.wal_archive_dir = archive_dir;
+        self
+    }
+
     async fn resolve_partition_worker(
         &self,
         partition_id: mudu::common::id::OID,
@@ -190,6 +225,87 @@ impl HttpApi for KernelHttpApi {
         self.app_mgr.uninstall(app_name.as_bytes().to_vec()).await
     }
 
+    async fn upgrade_mpk(&self, mpk_binary: Vec<u8>) -> RS<()> {
+        self.app_mgr.upgrade(mpk_binary).await
+    }
+
+    async fn rollback_app(&self, app_name: &str) -> RS<()> {
+        self.app_mgr.rollback(app_name.as_bytes().to_vec()).await
+    }
+
+    async fn backup(&self, target_dir: &str) -> RS<Value> {
+        let db_path = self.db_path.as_ref().ok_or_else(|| {
+            mudu_error!(
+                ErrorCode::NotImplemented,
+                "backup needs the database directory of the server"
+            )
+        })?;
+        let worker_ids = self
+            .worker_registry
+            .workers()
+            .iter()
+            .map(|worker| worker.worker_id)
+            .collect();
+        let manifest = backup_async(db_path, db_path, worker_ids, Path::new(target_dir)).await?;
+        serde_json::to_value(manifest)
+            .map_err(|e| mudu_error!(ErrorCode::Encode, "encode backup manifest error", e))
+    }
+
+    async fn promote(&self) -> RS<()> {
+        let db_path = self.db_path.as_ref().ok_or_else(|| {
+            mudu_error!(
+                ErrorCode::NotImplemented,
+                "promote needs the database directory of the server"
+            )
+        })?;
+        promote_standby_async(db_path).await
+    }
+
+    async fn read_changes(&self, worker_index: usize, query: ChangeFeedQuery) -> RS<Value> {
+        let db_path = self.db_path.as_ref().ok_or_else(|| {
+            mudu_error!(
+                ErrorCode::NotImplemented,
+                "change data capture needs the database directory of the server"
+            )
+        })?;
+        let worker_ids: Vec<_> = self
+            .worker_registry
+            .workers()
+            .iter()
+            .map(|worker| worker.worker_id)
+            .collect();
+        let worker_id = *worker_ids.get(worker_index).ok_or_else(|| {
+            mudu_error!(
+                ErrorCode::EntityNotFound,
+                format!(
+                    "worker index {} out of {} workers",
+                    worker_index,
+                    worker_ids.len()
+                )
+            )
+        })?;
+        let feed = ChangeFeed::new(
+            db_path.clone(),
+            self.log_chunk_size,
+            self.wal_archive_dir.clone(),
+            worker_ids,
+            self.meta_mgr.clone(),
+        );
+        let request =
+            ChangeFeedRequest::new(worker_id, query.after_lsn, query.max_events, query.wait_ms);
+        feed.read_async(&request).await?.to_json()
+    }
+
+    async fn authenticate_user(&self, user: &str, password: &str) -> RS<UserRole> {
+        match self.meta_mgr.get_user_by_name(user).await? {
+            Some(desc) if desc.credential().verify_password(password)? => Ok(desc.role()),
+            _ => Err(mudu_error!(
+                ErrorCode::PermissionDenied,
+                "invalid user name or password"
+            )),
+        }
+    }
+
     async fn route_partition(&self, request: PartitionRouteRequest) -> RS<PartitionRouteResponse> {
         let rule = self
             .meta_mgr
@@ -248,7 +364,15 @@ impl HttpApi for KernelHttpApi {
                 worker_id,
             });
         }
-        Ok(PartitionRouteResponse { routes })
+        let kind = match rule.kind {
+            PartitionRuleKind::Range => "range",
+            PartitionRuleKind::Hash { .. } => "hash",
+            PartitionRuleKind::List => "list",
+        };
+        Ok(PartitionRouteResponse {
+            kind: kind.to_string(),
+            routes,
+        })
     }
 
     async fn invoke_json(
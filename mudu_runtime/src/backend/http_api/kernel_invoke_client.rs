@@ -5,16 +5,21 @@ use mudu_cli::client::async_client::{
     AsyncClient as KernelAsyncTcpClient, AsyncClientImpl as KernelTcpClient,
 };
 use mudu_contract::protocol::{ProcedureInvokeRequest, SessionCloseRequest, SessionCreateRequest};
+use mudu_kernel::mudu_conn::mudu_conn_async::default_remote_credential;
 
 pub(super) struct KernelInvokeClient {
     inner: KernelTcpClient,
 }
 
 impl KernelInvokeClient {
+    /// Connect to the local server, logging in as the bootstrap admin when
+    /// the server requires authentication.
     pub(super) async fn connect(addr: &str) -> RS<Self> {
-        Ok(Self {
-            inner: KernelTcpClient::connect(addr).await?,
-        })
+        let mut inner = KernelTcpClient::connect(addr).await?;
+        if let Some((user, password)) = default_remote_credential() {
+            inner.login(&user, &password).await?;
+        }
+        Ok(Self { inner })
     }
 }
 
@@ -68,20 +68,13 @@ impl HttpApi for LegacyHttpApi {
     }
 
     async fn install_mpk(&self, mpk_binary: Vec<u8>) -> RS<()> {
-        let service = self.service.clone();
-        let file_path = actix_web::web::block(move || {
-            let temp_mpk_file = mudu_sys::env_var::temp_dir().join(format!("{:x}.mpk", gen_oid()));
-            mudu_sys::fs::sync::write(&temp_mpk_file, &mpk_binary)?;
-            let file_path = temp_mpk_file
-                .as_path()
-                .to_str()
-                .ok_or_else(|| mudu_error!(ErrorCode::InvalidUtf8, "cannot get string of PathBuf"))?
-                .to_string();
-            RS::Ok(file_path)
-        })
-        .await
-        .map_err(|e| mudu_error!(ErrorCode::Thread, "blocking install task failed", e))??;
-        service.install(file_path).await
+        let file_path = write_temp_package(mpk_binary).await?;
+        self.service.install(file_path).await
+    }
+
+    async fn upgrade_mpk(&self, mpk_binary: Vec<u8>) -> RS<()> {
+        let file_path = write_temp_package(mpk_binary).await?;
+        self.service.upgrade(file_path).await
     }
 
     async fn invoke_json(
@@ -102,3 +95,18 @@ impl HttpApi for LegacyHttpApi {
         Ok(result)
     }
 }
+
+async fn write_temp_package(mpk_binary: Vec<u8>) -> RS<String> {
+    actix_web::web::block(move || {
+        let temp_mpk_file = mudu_sys::env_var::temp_dir().join(format!("{:x}.mpk", gen_oid()));
+        mudu_sys::fs::sync::write(&temp_mpk_file, &mpk_binary)?;
+        let file_path = temp_mpk_file
+            .as_path()
+            .to_str()
+            .ok_or_else(|| mudu_error!(ErrorCode::InvalidUtf8, "cannot get string of PathBuf"))?
+            .to_string();
+        RS::Ok(file_path)
+    })
+    .await
+    .map_err(|e| mudu_error!(ErrorCode::Thread, "blocking install task failed", e))?
+}
@@ -9,6 +9,9 @@ use procedure_list::ProcedureList;
 mod http_api_context;
 pub(crate) use http_api_context::HttpApiContext;
 
+mod http_token_store;
+pub(crate) use http_token_store::HttpTokenStore;
+
 mod legacy_http_api;
 pub use legacy_http_api::LegacyHttpApi;
 
@@ -23,7 +26,7 @@ use crate::service::app_inst::AppInst;
 use crate::service::runtime::Runtime;
 use actix_cors::Cors;
 use actix_web::http::StatusCode;
-use actix_web::{App, HttpResponse, HttpServer, Responder, delete, get, post, web};
+use actix_web::{App, HttpRequest, HttpResponse, HttpServer, Responder, delete, get, post, web};
 use async_trait::async_trait;
 use base64::Engine;
 use mudu::common::id::OID;
@@ -37,6 +40,7 @@ use mudu_binding::universal::uni_oid::UniOid;
 use mudu_contract::procedure::proc_desc::ProcDesc;
 use mudu_contract::procedure::procedure_param::ProcedureParam;
 use mudu_contract::tuple::datum_desc::DatumDesc;
+use mudu_kernel::contract::user::UserRole;
 use mudu_sys::net::sync::StdTcpListener;
 use mudu_utils::notifier::Waiter;
 use mudu_utils::scoped_task_trace;
@@ -103,6 +107,31 @@ pub struct ServerTopology {
     pub workers: Vec<WorkerTopology>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BackupRequest {
+    /// Directory on the server host the backup is written to; it must be
+    /// missing or empty.
+    pub target_dir: String,
+}
+
+/// Query string of `GET /mudu/cdc/{worker_index}`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChangeFeedQuery {
+    /// `last_lsn` of the previous response; the oldest retained change when
+    /// missing.
+    #[serde(default)]
+    pub after_lsn: Option<u64>,
+    #[serde(default = "default_change_feed_max_events")]
+    pub max_events: u32,
+    /// How long to hold the request open when there is no change yet.
+    #[serde(default)]
+    pub wait_ms: u64,
+}
+
+fn default_change_feed_max_events() -> u32 {
+    1000
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct PartitionRouteRequest {
     pub rule_name: String,
@@ -130,6 +159,9 @@ pub struct PartitionRouteEntry {
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct PartitionRouteResponse {
+    /// Rule kind: `range`, `hash` or `list`.
+    #[serde(default)]
+    pub kind: String,
     pub routes: Vec<PartitionRouteEntry>,
 }
 
@@ -176,6 +208,59 @@ pub trait HttpApi: Send + Sync {
             format!("uninstall is not supported for {}", app_name)
         ))
     }
+
+    /// Upgrade an installed app to the newer version in `mpk_binary`.
+    async fn upgrade_mpk(&self, _mpk_binary: Vec<u8>) -> RS<()> {
+        Err(mudu_error!(
+            ErrorCode::NotImplemented,
+            "upgrade is not supported"
+        ))
+    }
+
+    /// Restore the version an app had before its last upgrade.
+    async fn rollback_app(&self, app_name: &str) -> RS<()> {
+        Err(mudu_error!(
+            ErrorCode::NotImplemented,
+            format!("rollback is not supported for {}", app_name)
+        ))
+    }
+
+    /// Take an online backup of the database into `target_dir` and return
+    /// its manifest.
+    async fn backup(&self, target_dir: &str) -> RS<Value> {
+        Err(mudu_error!(
+            ErrorCode::NotImplemented,
+            format!("backup to {} is not supported", target_dir)
+        ))
+    }
+
+    /// Promote the standby server to a writable primary.
+    async fn promote(&self) -> RS<()> {
+        Err(mudu_error!(
+            ErrorCode::NotImplemented,
+            "promote is not supported"
+        ))
+    }
+
+    /// Read the committed row changes of the log of worker `worker_index`
+    /// as JSON events and the cursor to resume from.
+    async fn read_changes(&self, worker_index: usize, _query: ChangeFeedQuery) -> RS<Value> {
+        Err(mudu_error!(
+            ErrorCode::NotImplemented,
+            format!(
+                "change data capture of worker {} is not supported",
+                worker_index
+            )
+        ))
+    }
+
+    /// Check a user's password and return the user's role.
+    async fn authenticate_user(&self, _user: &str, _password: &str) -> RS<UserRole> {
+        Err(mudu_error!(
+            ErrorCode::NotImplemented,
+            "user authentication is not supported"
+        ))
+    }
 }
 
 #[async_trait(?Send)]
@@ -226,7 +311,12 @@ pub async fn serve_http_api_on_listener_with_stop(
 ) -> std::io::Result<()> {
     scoped_task_trace!();
     let payload_limit = 500 * 1024 * 1024;
-    let data = web::Data::new(HttpApiContext { api });
+    let data = web::Data::new(HttpApiContext {
+        api,
+        tokens: capabilities
+            .auth_token_ttl
+            .map(|ttl| Arc::new(HttpTokenStore::new(ttl))),
+    });
 
     let server = HttpServer::new(move || {
         let cors = Cors::permissive();
@@ -266,18 +356,40 @@ pub async fn serve_http_api_on_listener_with_stop(
 }
 
 pub(crate) fn configure_routes(cfg: &mut web::ServiceConfig, capabilities: HttpApiCapabilities) {
-    cfg.service(app_list)
+    cfg.service(metrics)
+        .service(app_list)
         .service(app_proc_list)
         .service(app_proc_detail)
         .service(server_topology)
         .service(partition_route)
-        .service(install);
+        .service(install)
+        .service(upgrade)
+        .service(rollback)
+        .service(backup)
+        .service(promote)
+        .service(change_feed);
     if capabilities.enable_invoke {
         cfg.service(invoke);
     }
     if capabilities.enable_uninstall {
         cfg.service(uninstall);
     }
+    if capabilities.auth_token_ttl.is_some() {
+        cfg.service(auth_token);
+    }
+}
+
+/// Check the bearer token of `req` when the API is authenticated.
+fn authorize(req: &HttpRequest, context: &HttpApiContext, admin: bool) -> RS<()> {
+    match &context.tokens {
+        Some(tokens) => tokens.authorize(
+            req.headers()
+                .get(actix_web::http::header::AUTHORIZATION)
+                .and_then(|value| value.to_str().ok()),
+            admin,
+        ),
+        None => Ok(()),
+    }
 }
 
 fn http_ok(data: Value) -> HttpResponse {
@@ -312,6 +424,19 @@ fn http_err(user_message: impl Into<String>, err: &MuduError) -> HttpResponse {
     }))
 }
 
+/// Prometheus scrape endpoint: the kernel metrics of this process in the
+/// text exposition format.
+#[get("/metrics")]
+async fn metrics() -> impl Responder {
+    match mudu_kernel::server::metrics::render() {
+        Ok(text) => HttpResponse::Ok()
+            .content_type("text/plain; version=0.0.4; charset=utf-8")
+            .body(text),
+        Err(e) => HttpResponse::InternalServerError()
+            .body(format!("fail to render metrics: {}", e.message())),
+    }
+}
+
 #[post("/mudu/partition/route")]
 async fn partition_route(body: String, context: web::Data<HttpApiContext>) -> impl Responder {
     let request = match serde_json::from_str::<PartitionRouteRequest>(&body) {
@@ -396,8 +521,51 @@ async fn app_proc_detail(
     }
 }
 
+#[derive(Deserialize)]
+struct AuthTokenRequest {
+    user: String,
+    password: String,
+}
+
+#[post("/mudu/auth/token")]
+async fn auth_token(body: String, context: web::Data<HttpApiContext>) -> impl Responder {
+    let request = match serde_json::from_str::<AuthTokenRequest>(&body) {
+        Ok(request) => request,
+        Err(e) => {
+            let err = mudu_error!(ErrorCode::Decode, "fail to parse auth token request", e);
+            return http_err("fail to parse auth token request", &err);
+        }
+    };
+    let Some(tokens) = &context.tokens else {
+        let err = mudu_error!(ErrorCode::NotImplemented, "authentication is not enabled");
+        return http_err("fail to issue auth token", &err);
+    };
+    let issued = match context
+        .api
+        .authenticate_user(&request.user, &request.password)
+        .await
+    {
+        Ok(role) => tokens.issue(role),
+        Err(e) => Err(e),
+    };
+    match issued {
+        Ok(token) => http_ok(serde_json::json!({
+            "token": token,
+            "expires_in_secs": tokens.ttl().as_secs(),
+        })),
+        Err(e) => http_err(format!("fail to authenticate user {}", request.user), &e),
+    }
+}
+
 #[post("/mudu/app/install")]
-async fn install(body: web::Bytes, context: web::Data<HttpApiContext>) -> impl Responder {
+async fn install(
+    req: HttpRequest,
+    body: web::Bytes,
+    context: web::Data<HttpApiContext>,
+) -> impl Responder {
+    if let Err(e) = authorize(&req, &context, true) {
+        return http_err("fail to install package", &e);
+    }
     let body_str = String::from_utf8_lossy(&body).to_string();
     match decode_install_request(&body_str) {
         Ok(binary) => {
@@ -411,9 +579,114 @@ async fn install(body: web::Bytes, context: web::Data<HttpApiContext>) -> impl R
     }
 }
 
+#[post("/mudu/app/upgrade")]
+async fn upgrade(
+    req: HttpRequest,
+    body: web::Bytes,
+    context: web::Data<HttpApiContext>,
+) -> impl Responder {
+    if let Err(e) = authorize(&req, &context, true) {
+        return http_err("fail to upgrade package", &e);
+    }
+    let body_str = String::from_utf8_lossy(&body).to_string();
+    match decode_install_request(&body_str) {
+        Ok(binary) => {
+            let package_name = mpk_package_name(&binary).unwrap_or_else(|| "<unknown>".to_string());
+            match context.api.upgrade_mpk(binary).await {
+                Ok(()) => http_ok(JsonValue::Null),
+                Err(e) => http_err(format!("fail to upgrade package {}", package_name), &e),
+            }
+        }
+        Err(e) => http_err("fail to upgrade package <invalid request>", &e),
+    }
+}
+
+#[post("/mudu/app/rollback/{app_name}")]
+async fn rollback(
+    req: HttpRequest,
+    path: web::Path<String>,
+    context: web::Data<HttpApiContext>,
+) -> impl Responder {
+    let app_name = path.into_inner();
+    if let Err(e) = authorize(&req, &context, true) {
+        return http_err(format!("fail to roll back app {}", app_name), &e);
+    }
+    match context.api.rollback_app(&app_name).await {
+        Ok(()) => http_ok(JsonValue::Null),
+        Err(e) => http_err(format!("fail to roll back app {}", app_name), &e),
+    }
+}
+
+#[post("/mudu/admin/backup")]
+async fn backup(
+    req: HttpRequest,
+    body: String,
+    context: web::Data<HttpApiContext>,
+) -> impl Responder {
+    if let Err(e) = authorize(&req, &context, true) {
+        return http_err("fail to back up", &e);
+    }
+    let request = match serde_json::from_str::<BackupRequest>(&body) {
+        Ok(request) => request,
+        Err(e) => {
+            let err = mudu_error!(ErrorCode::Decode, "fail to parse backup request", e);
+            return http_err("fail to parse backup request", &err);
+        }
+    };
+    match context.api.backup(&request.target_dir).await {
+        Ok(manifest) => http_ok(manifest),
+        Err(e) => http_err(format!("fail to back up to {}", request.target_dir), &e),
+    }
+}
+
+#[post("/mudu/admin/promote")]
+async fn promote(req: HttpRequest, context: web::Data<HttpApiContext>) -> impl Responder {
+    if let Err(e) = authorize(&req, &context, true) {
+        return http_err("fail to promote", &e);
+    }
+    match context.api.promote().await {
+        Ok(()) => http_ok(JsonValue::Null),
+        Err(e) => http_err("fail to promote the standby", &e),
+    }
+}
+
+/// Change data capture long-poll: the row changes committed to the log of
+/// one worker after `after_lsn`. Pass the returned `last_lsn` back to read
+/// on.
+#[get("/mudu/cdc/{worker_index}")]
+async fn change_feed(
+    req: HttpRequest,
+    path: web::Path<usize>,
+    query: web::Query<ChangeFeedQuery>,
+    context: web::Data<HttpApiContext>,
+) -> impl Responder {
+    let worker_index = path.into_inner();
+    if let Err(e) = authorize(&req, &context, true) {
+        return http_err("fail to read changes", &e);
+    }
+    match context
+        .api
+        .read_changes(worker_index, query.into_inner())
+        .await
+    {
+        Ok(changes) => http_ok(changes),
+        Err(e) => http_err(
+            format!("fail to read changes of worker {}", worker_index),
+            &e,
+        ),
+    }
+}
+
 #[delete("/mudu/app/uninstall/{app_name}")]
-async fn uninstall(path: web::Path<String>, context: web::Data<HttpApiContext>) -> impl Responder {
+async fn uninstall(
+    req: HttpRequest,
+    path: web::Path<String>,
+    context: web::Data<HttpApiContext>,
+) -> impl Responder {
     let app_name = path.into_inner();
+    if let Err(e) = authorize(&req, &context, true) {
+        return http_err(format!("fail to uninstall app {}", app_name), &e);
+    }
     match context.api.uninstall_app(&app_name).await {
         Ok(()) => http_ok(JsonValue::Null),
         Err(e) => http_err(format!("fail to uninstall app {}", app_name), &e),
@@ -422,6 +695,7 @@ async fn uninstall(path: web::Path<String>, context: web::Data<HttpApiContext>)
 
 #[post("/mudu/app/invoke/{app_name}/{mod_name}/{proc_name}")]
 async fn invoke(
+    req: HttpRequest,
     path: web::Path<(String, String, String)>,
     body: web::Bytes,
     context: web::Data<HttpApiContext>,
@@ -429,6 +703,9 @@ async fn invoke(
     let (app_name, mod_name, proc_name) = path.into_inner();
     let body_str = String::from_utf8_lossy(&body).to_string();
     let proc = format!("{}/{}/{}", app_name, mod_name, proc_name);
+    if let Err(e) = authorize(&req, &context, false) {
+        return http_err(format!("fail to invoke procedure {}", proc), &e);
+    }
     match context
         .api
         .invoke_json(&app_name, &mod_name, &proc_name, body_str)
@@ -656,6 +933,7 @@ mod test {
             App::new()
                 .app_data(web::Data::new(HttpApiContext {
                     api: Arc::new(MockHttpApi),
+                    tokens: None,
                 }))
                 .configure(|cfg| configure_routes(cfg, HttpApiCapabilities::LEGACY)),
         )
@@ -739,6 +1017,14 @@ mod test {
             Ok(())
         }
 
+        async fn upgrade(&self, _mpk_binary: Vec<u8>) -> RS<()> {
+            Ok(())
+        }
+
+        async fn rollback(&self, _app_name: Vec<u8>) -> RS<()> {
+            Ok(())
+        }
+
         async fn list(&self, _option: &ListOption) -> RS<crate::service::app_list::AppList> {
             let desc = ProcDesc::new(
                 "mod1".to_string(),
@@ -756,6 +1042,7 @@ mod test {
                         lang: "rust".to_string(),
                         version: "0.1.0".to_string(),
                         use_async: false,
+                        limits: Default::default(),
                     },
                     ddl: String::new(),
                     mod_proc_desc: mod_desc,
@@ -905,6 +1192,82 @@ mod test {
         assert_eq!(range.routes[1].worker_id, w1);
     }
 
+    #[actix_web::test]
+    async fn kernel_http_api_routes_hash_rule_by_name() {
+        if cfg!(miri) {
+            return;
+        }
+        let log_dir =
+            mudu_sys::env_var::temp_dir().join(format!("http_api_hash_route_test_{}", gen_oid()));
+        let registry =
+            mudu_kernel::server::worker_registry::load_or_create_worker_registry(&log_dir, 4)
+                .unwrap();
+        let meta_dir =
+            mudu_sys::env_var::temp_dir().join(format!("http_api_hash_route_meta_{}", gen_oid()));
+        let meta_mgr = MetaMgrFactory::create(meta_dir.to_string_lossy().to_string())
+            .await
+            .unwrap();
+        meta_mgr.initialize().await.unwrap();
+
+        let rule = PartitionRuleDesc::new_hash("hash_rule".to_string(), vec!["id".to_string()], 3);
+        let workers = registry.workers();
+        let placements = rule
+            .partitions
+            .iter()
+            .zip(workers.iter())
+            .map(|(partition, worker)| PartitionPlacement {
+                partition_id: partition.partition_id,
+                worker_id: worker.worker_id,
+            })
+            .collect::<Vec<_>>();
+        meta_mgr.create_partition_rule(&rule).await.unwrap();
+        meta_mgr
+            .upsert_partition_placements(&placements)
+            .await
+            .unwrap();
+
+        let api = KernelHttpApi::with_client_factory(
+            Arc::new(MockAppMgr),
+            "127.0.0.1:9527".to_string(),
+            false,
+            9527,
+            registry,
+            meta_mgr,
+            Arc::new(MockClientFactory {
+                requests: Arc::new(SMutex::new(Vec::new())),
+                fail_close: false,
+            }),
+        );
+
+        let point = api
+            .route_partition(PartitionRouteRequest {
+                rule_name: "hash_rule".to_string(),
+                key: Some(vec!["42".to_string()]),
+                start: None,
+                end: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(point.kind, "hash");
+        assert_eq!(point.routes.len(), 1);
+        let placement = placements
+            .iter()
+            .find(|placement| placement.partition_id == point.routes[0].partition_id)
+            .unwrap();
+        assert_eq!(point.routes[0].worker_id, placement.worker_id);
+
+        let range = api
+            .route_partition(PartitionRouteRequest {
+                rule_name: "hash_rule".to_string(),
+                key: None,
+                start: Some(vec!["0".to_string()]),
+                end: Some(vec!["10".to_string()]),
+            })
+            .await
+            .unwrap();
+        assert_eq!(range.routes.len(), 3);
+    }
+
     #[actix_web::test]
     async fn kernel_http_api_lists_metadata_and_topology() {
         if cfg!(miri) {
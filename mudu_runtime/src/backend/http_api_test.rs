@@ -11,6 +11,7 @@ mod tests {
     use mudu_contract::procedure::procedure_param::ProcedureParam;
     use mudu_contract::procedure::procedure_result::ProcedureResult;
     use mudu_contract::tuple::tuple_datum::TupleDatum;
+    use mudu_kernel::contract::user::UserRole;
     use mudu_sys::contract::async_io_provider::AsyncIoProvider;
     use serde_json::{Map, Value};
     use std::io::{Cursor, Write};
@@ -106,6 +107,10 @@ mod tests {
             Ok(())
         }
 
+        async fn upgrade(&self, _pkg_path: String) -> RS<()> {
+            Ok(())
+        }
+
         fn async_runtime(&self) -> Option<Arc<dyn AsyncIoProvider>> {
             None
         }
@@ -119,6 +124,7 @@ mod tests {
                     lang: "rust".to_string(),
                     version: "0.1.0".to_string(),
                     use_async,
+                    limits: Default::default(),
                 },
                 desc: sample_desc(),
             }),
@@ -337,6 +343,7 @@ mod tests {
             App::new()
                 .app_data(web::Data::new(HttpApiContext {
                     api: legacy_http_api(),
+                    tokens: None,
                 }))
                 .configure(|cfg| configure_routes(cfg, HttpApiCapabilities::IOURING)),
         )
@@ -350,6 +357,37 @@ mod tests {
         assert_eq!(resp["data"], serde_json::json!(["app1"]));
     }
 
+    #[actix_web::test]
+    async fn http_metrics_serves_text_exposition() {
+        if cfg!(miri) {
+            return;
+        }
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(HttpApiContext {
+                    api: legacy_http_api(),
+                    tokens: None,
+                }))
+                .configure(|cfg| configure_routes(cfg, HttpApiCapabilities::IOURING)),
+        )
+        .await;
+
+        let req = actix_test::TestRequest::get().uri("/metrics").to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(
+            resp.headers()
+                .get(actix_web::http::header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .is_some_and(|value| value.starts_with("text/plain; version=0.0.4"))
+        );
+        let body = actix_test::read_body(resp).await;
+        let text = String::from_utf8(body.to_vec()).unwrap();
+        assert!(text.contains("# TYPE mudu_commit_duration_seconds histogram\n"));
+        assert!(text.contains("# TYPE mudu_wal_fsyncs_total counter\n"));
+        assert!(text.contains("# TYPE mudu_sessions_active gauge\n"));
+    }
+
     #[actix_web::test]
     async fn http_app_proc_list_returns_procedures() {
         if cfg!(miri) {
@@ -359,6 +397,7 @@ mod tests {
             App::new()
                 .app_data(web::Data::new(HttpApiContext {
                     api: legacy_http_api(),
+                    tokens: None,
                 }))
                 .configure(|cfg| configure_routes(cfg, HttpApiCapabilities::IOURING)),
         )
@@ -381,6 +420,7 @@ mod tests {
             App::new()
                 .app_data(web::Data::new(HttpApiContext {
                     api: legacy_http_api(),
+                    tokens: None,
                 }))
                 .configure(|cfg| configure_routes(cfg, HttpApiCapabilities::IOURING)),
         )
@@ -403,6 +443,7 @@ mod tests {
             App::new()
                 .app_data(web::Data::new(HttpApiContext {
                     api: legacy_http_api(),
+                    tokens: None,
                 }))
                 .configure(|cfg| configure_routes(cfg, HttpApiCapabilities::IOURING)),
         )
@@ -427,6 +468,7 @@ mod tests {
             App::new()
                 .app_data(web::Data::new(HttpApiContext {
                     api: legacy_http_api(),
+                    tokens: None,
                 }))
                 .configure(|cfg| configure_routes(cfg, HttpApiCapabilities::IOURING)),
         )
@@ -449,6 +491,7 @@ mod tests {
             App::new()
                 .app_data(web::Data::new(HttpApiContext {
                     api: legacy_http_api(),
+                    tokens: None,
                 }))
                 .configure(|cfg| configure_routes(cfg, HttpApiCapabilities::IOURING)),
         )
@@ -471,6 +514,7 @@ mod tests {
             App::new()
                 .app_data(web::Data::new(HttpApiContext {
                     api: legacy_http_api(),
+                    tokens: None,
                 }))
                 .configure(|cfg| configure_routes(cfg, HttpApiCapabilities::IOURING)),
         )
@@ -483,6 +527,125 @@ mod tests {
         assert!(!resp["ok"].as_bool().unwrap());
     }
 
+    #[actix_web::test]
+    async fn http_upgrade_accepts_base64_payload() {
+        if cfg!(miri) {
+            return;
+        }
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(HttpApiContext {
+                    api: legacy_http_api(),
+                    tokens: None,
+                }))
+                .configure(|cfg| configure_routes(cfg, HttpApiCapabilities::IOURING)),
+        )
+        .await;
+
+        let payload = base64::engine::general_purpose::STANDARD.encode(b"mpk");
+        let body = serde_json::json!({"mpk_base64": payload}).to_string();
+        let req = actix_test::TestRequest::post()
+            .uri("/mudu/app/upgrade")
+            .set_payload(body)
+            .to_request();
+        let resp: Value = actix_test::call_and_read_body_json(&app, req).await;
+        assert!(resp["ok"].as_bool().unwrap());
+    }
+
+    #[actix_web::test]
+    async fn http_rollback_returns_not_implemented_for_legacy() {
+        if cfg!(miri) {
+            return;
+        }
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(HttpApiContext {
+                    api: legacy_http_api(),
+                    tokens: None,
+                }))
+                .configure(|cfg| configure_routes(cfg, HttpApiCapabilities::IOURING)),
+        )
+        .await;
+
+        let req = actix_test::TestRequest::post()
+            .uri("/mudu/app/rollback/app1")
+            .to_request();
+        let resp: Value = actix_test::call_and_read_body_json(&app, req).await;
+        assert!(!resp["ok"].as_bool().unwrap());
+        assert_eq!(resp["status"], ErrorCode::NotImplemented.to_u32());
+    }
+
+    #[actix_web::test]
+    async fn http_backup_returns_not_implemented_for_legacy() {
+        if cfg!(miri) {
+            return;
+        }
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(HttpApiContext {
+                    api: legacy_http_api(),
+                    tokens: None,
+                }))
+                .configure(|cfg| configure_routes(cfg, HttpApiCapabilities::IOURING)),
+        )
+        .await;
+
+        let req = actix_test::TestRequest::post()
+            .uri("/mudu/admin/backup")
+            .set_payload(serde_json::json!({"target_dir": "/tmp/backup"}).to_string())
+            .to_request();
+        let resp: Value = actix_test::call_and_read_body_json(&app, req).await;
+        assert!(!resp["ok"].as_bool().unwrap());
+        assert_eq!(resp["status"], ErrorCode::NotImplemented.to_u32());
+    }
+
+    #[actix_web::test]
+    async fn http_promote_returns_not_implemented_for_legacy() {
+        if cfg!(miri) {
+            return;
+        }
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(HttpApiContext {
+                    api: legacy_http_api(),
+                    tokens: None,
+                }))
+                .configure(|cfg| configure_routes(cfg, HttpApiCapabilities::IOURING)),
+        )
+        .await;
+
+        let req = actix_test::TestRequest::post()
+            .uri("/mudu/admin/promote")
+            .to_request();
+        let resp: Value = actix_test::call_and_read_body_json(&app, req).await;
+        assert!(!resp["ok"].as_bool().unwrap());
+        assert_eq!(resp["status"], ErrorCode::NotImplemented.to_u32());
+    }
+
+    #[actix_web::test]
+    async fn http_backup_rejects_invalid_json() {
+        if cfg!(miri) {
+            return;
+        }
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(HttpApiContext {
+                    api: legacy_http_api(),
+                    tokens: None,
+                }))
+                .configure(|cfg| configure_routes(cfg, HttpApiCapabilities::IOURING)),
+        )
+        .await;
+
+        let req = actix_test::TestRequest::post()
+            .uri("/mudu/admin/backup")
+            .set_payload("not-json")
+            .to_request();
+        let resp: Value = actix_test::call_and_read_body_json(&app, req).await;
+        assert!(!resp["ok"].as_bool().unwrap());
+        assert_eq!(resp["status"], ErrorCode::Decode.to_u32());
+    }
+
     #[actix_web::test]
     async fn http_partition_route_returns_error_on_invalid_json() {
         if cfg!(miri) {
@@ -492,6 +655,7 @@ mod tests {
             App::new()
                 .app_data(web::Data::new(HttpApiContext {
                     api: legacy_http_api(),
+                    tokens: None,
                 }))
                 .configure(|cfg| configure_routes(cfg, HttpApiCapabilities::IOURING)),
         )
@@ -515,6 +679,7 @@ mod tests {
             App::new()
                 .app_data(web::Data::new(HttpApiContext {
                     api: legacy_http_api(),
+                    tokens: None,
                 }))
                 .configure(|cfg| configure_routes(cfg, HttpApiCapabilities::IOURING)),
         )
@@ -535,11 +700,13 @@ mod tests {
         let disabled = HttpApiCapabilities {
             enable_invoke: false,
             enable_uninstall: false,
+            auth_token_ttl: None,
         };
         let app = actix_test::init_service(
             App::new()
                 .app_data(web::Data::new(HttpApiContext {
                     api: legacy_http_api(),
+                    tokens: None,
                 }))
                 .configure(|cfg| configure_routes(cfg, disabled)),
         )
@@ -552,4 +719,101 @@ mod tests {
         let resp = actix_test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::NOT_FOUND);
     }
+
+    fn token_auth() -> HttpApiCapabilities {
+        HttpApiCapabilities {
+            auth_token_ttl: Some(std::time::Duration::from_secs(60)),
+            ..HttpApiCapabilities::IOURING
+        }
+    }
+
+    #[actix_web::test]
+    async fn http_token_auth_gates_install_and_invoke() {
+        if cfg!(miri) {
+            return;
+        }
+        let tokens = Arc::new(HttpTokenStore::new(std::time::Duration::from_secs(60)));
+        let admin = tokens.issue(UserRole::Admin).unwrap();
+        let user = tokens.issue(UserRole::User).unwrap();
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(HttpApiContext {
+                    api: legacy_http_api(),
+                    tokens: Some(tokens),
+                }))
+                .configure(|cfg| configure_routes(cfg, token_auth())),
+        )
+        .await;
+        let install_body = serde_json::json!({
+            "mpk_base64": base64::engine::general_purpose::STANDARD.encode(b"mpk"),
+        })
+        .to_string();
+
+        let install = |token: Option<&str>| {
+            let mut req = actix_test::TestRequest::post()
+                .uri("/mudu/app/install")
+                .set_payload(install_body.clone());
+            if let Some(token) = token {
+                req = req.insert_header(("Authorization", format!("Bearer {}", token)));
+            }
+            req.to_request()
+        };
+        let resp: Value = actix_test::call_and_read_body_json(&app, install(None)).await;
+        assert!(!resp["ok"].as_bool().unwrap());
+        let resp: Value = actix_test::call_and_read_body_json(&app, install(Some("bogus"))).await;
+        assert!(!resp["ok"].as_bool().unwrap());
+        let resp: Value = actix_test::call_and_read_body_json(&app, install(Some(&user))).await;
+        assert!(!resp["ok"].as_bool().unwrap());
+        let resp: Value = actix_test::call_and_read_body_json(&app, install(Some(&admin))).await;
+        assert!(resp["ok"].as_bool().unwrap());
+
+        let req = actix_test::TestRequest::post()
+            .uri("/mudu/app/invoke/app1/mod1/proc1")
+            .set_payload(r#"{"value":1}"#)
+            .to_request();
+        let resp: Value = actix_test::call_and_read_body_json(&app, req).await;
+        assert!(!resp["ok"].as_bool().unwrap());
+        let req = actix_test::TestRequest::post()
+            .uri("/mudu/app/invoke/app1/mod1/proc1")
+            .insert_header(("Authorization", format!("Bearer {}", user)))
+            .set_payload(r#"{"value":1}"#)
+            .to_request();
+        let resp: Value = actix_test::call_and_read_body_json(&app, req).await;
+        assert!(resp["ok"].as_bool().unwrap());
+
+        // Read-only routes stay open.
+        let req = actix_test::TestRequest::get()
+            .uri("/mudu/app/list")
+            .to_request();
+        let resp: Value = actix_test::call_and_read_body_json(&app, req).await;
+        assert!(resp["ok"].as_bool().unwrap());
+    }
+
+    #[actix_web::test]
+    async fn http_auth_token_rejects_api_without_users() {
+        if cfg!(miri) {
+            return;
+        }
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(HttpApiContext {
+                    api: legacy_http_api(),
+                    tokens: Some(Arc::new(HttpTokenStore::new(
+                        std::time::Duration::from_secs(60),
+                    ))),
+                }))
+                .configure(|cfg| configure_routes(cfg, token_auth())),
+        )
+        .await;
+        let req = actix_test::TestRequest::post()
+            .uri("/mudu/auth/token")
+            .set_payload(r#"{"user":"admin","password":"secret"}"#)
+            .to_request();
+        let resp: Value = actix_test::call_and_read_body_json(&app, req).await;
+        assert!(!resp["ok"].as_bool().unwrap());
+        assert_eq!(
+            resp["status"].as_u64().unwrap(),
+            ErrorCode::NotImplemented.to_u32() as u64
+        );
+    }
 }
@@ -12,6 +12,7 @@ use mudu_utils::notifier::Waiter;
 use mudu_utils::task_async::build_current_thread_runtime;
 use std::sync::Arc;
 use std::sync::mpsc;
+use std::time::Duration;
 use tracing::{error, info};
 
 pub fn spawn_management_thread(
@@ -78,7 +79,12 @@ pub fn spawn_management_thread(
             if let Err(e) = serve_http_api_on_listener_with_stop(
                 api,
                 listener,
-                HttpApiCapabilities::IOURING,
+                HttpApiCapabilities {
+                    auth_token_ttl: cfg
+                        .auth_enabled
+                        .then(|| Duration::from_secs(cfg.http_token_ttl_secs)),
+                    ..HttpApiCapabilities::IOURING
+                },
                 cfg.http_worker_threads,
                 Some(stop),
             )
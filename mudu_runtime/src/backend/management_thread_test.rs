@@ -27,6 +27,14 @@ impl AppMgr for MockAppMgr {
         Ok(())
     }
 
+    async fn upgrade(&self, _mpk_binary: Vec<u8>) -> RS<()> {
+        Ok(())
+    }
+
+    async fn rollback(&self, _app_name: Vec<u8>) -> RS<()> {
+        Ok(())
+    }
+
     async fn list(&self, _option: &ListOption) -> RS<AppList> {
         Ok(AppList { apps: vec![] })
     }
@@ -1,5 +1,6 @@
 use crate::backend::app_mgr::AppMgr;
 use crate::backend::mudud_cfg::MuduDBCfg;
+use crate::service::app_inst_impl::{is_migrated, is_migration_pending};
 use crate::service::app_list::{AppList, AppListItem};
 use crate::service::app_package::AppPackage;
 use crate::service::runtime::Runtime;
@@ -10,6 +11,7 @@ use mudu::common::id::OID;
 use mudu::common::result::RS;
 use mudu::common::xid::INVALID_OID;
 use mudu::error::ErrorCode;
+use mudu::error::others::io_error_with_message;
 use mudu::mudu_error;
 use mudu_binding::procedure::procedure_invoke;
 use mudu_kernel::server::async_func_runtime::AsyncFuncInvoker;
@@ -18,10 +20,17 @@ use mudu_sys::contract::async_io_provider::AsyncIoProvider;
 use mudu_sys::sync::SMutex;
 use mudu_sys::sync::SRwLock;
 use std::collections::HashSet;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Weak};
 
 const MPK_EXTENSION: &str = "mpk";
+/// Suffix of the package an upgrade replaced, kept next to the installed
+/// `{app}.mpk` for rollback. Its extension is not `mpk`, so the runtime
+/// never loads it as an installed package.
+const PREVIOUS_PACKAGE_SUFFIX: &str = "prev";
+/// Suffix of the file a package is written to before it is renamed in place.
+const STAGING_PACKAGE_SUFFIX: &str = "staged";
 
 struct MuduProcInvoker {
     cfg: MuduDBCfg,
@@ -50,6 +59,16 @@ impl MuduProcInvoker {
         runtime.install(pkg_path).await
     }
 
+    async fn replace(&self, pkg_path: String) -> RS<()> {
+        let runtime = self.runtime.read()?.clone();
+        runtime.replace(pkg_path).await
+    }
+
+    async fn migrate(&self, pkg_path: String) -> RS<()> {
+        let runtime = self.runtime.read()?.clone();
+        runtime.migrate(pkg_path).await
+    }
+
     async fn reload(&self) -> RS<()> {
         let runtime = create_runtime_from_cfg(&self.cfg, self.async_runtime.clone()).await?;
         *self.runtime.write()? = runtime;
@@ -199,12 +218,95 @@ impl AppMgr for MuduAppMgr {
                 )
             })?;
         mudu_sys::fs::sync::remove_file(&package_path)?;
+        let previous_path = previous_package_path(&package_path);
+        if mudu_sys::fs::sync::path_exists(&previous_path) {
+            mudu_sys::fs::sync::remove_file(&previous_path)?;
+        }
         for invoker in self.live_invokers()? {
             invoker.reload().await?;
         }
         Ok(())
     }
 
+    async fn upgrade(&self, mpk_binary: Vec<u8>) -> RS<()> {
+        let mpk_path = self.cfg.mpk_path.clone();
+        // Same as install: package parsing and file I/O run on the blocking
+        // thread pool.
+        let package_path =
+            actix_web::web::block(move || stage_upgrade_package(&mpk_path, &mpk_binary))
+                .await
+                .map_err(|e| mudu_error!(ErrorCode::Thread, "blocking upgrade task failed", e))??;
+        let upgrade_path = path_to_string(&package_path)?;
+
+        let invokers = self.live_invokers()?;
+        let mut upgraded = 0;
+        let mut result = Ok(());
+        for invoker in &invokers {
+            result = invoker.replace(upgrade_path.clone()).await;
+            if result.is_err() {
+                break;
+            }
+            upgraded += 1;
+        }
+        // The migration runs once, after every invoker serves the new
+        // version, so no invoker runs the old version against the migrated
+        // schema.
+        if result.is_ok()
+            && let Some(invoker) = invokers.first()
+        {
+            result = invoker.migrate(upgrade_path.clone()).await;
+        }
+        if let Err(e) = result {
+            // A migration that failed partway may have changed the schema,
+            // which the previous version cannot serve.
+            let upgraded = AppPackage::load(&package_path)?.package_cfg;
+            if is_migration_pending(&self.cfg.db_path, &upgraded.name, &upgraded.version) {
+                return Err(mudu_error!(
+                    ErrorCode::InvalidState,
+                    format!(
+                        "the migration of app {} to version {} failed partway, so the \
+                         previous version is not restored",
+                        upgraded.name, upgraded.version
+                    ),
+                    e
+                ));
+            }
+            // Put the replaced package back, on disk and on the invokers
+            // that already switched, so every invoker serves one version.
+            restore_previous_package(&package_path)?;
+            for invoker in &invokers[..upgraded] {
+                invoker.replace(upgrade_path.clone()).await?;
+            }
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    async fn rollback(&self, app_name: Vec<u8>) -> RS<()> {
+        let app_name = String::from_utf8(app_name)
+            .map_err(|e| mudu_error!(ErrorCode::Decode, "decode app name error", e))?;
+        let package_path = find_package_path_by_app_name(&self.cfg.mpk_path, &app_name)?
+            .ok_or_else(|| {
+                mudu_error!(
+                    ErrorCode::EntityNotFound,
+                    format!("no such app {}", app_name)
+                )
+            })?;
+        if !mudu_sys::fs::sync::path_exists(previous_package_path(&package_path)) {
+            return Err(mudu_error!(
+                ErrorCode::EntityNotFound,
+                format!("app {} has no previous version to roll back to", app_name)
+            ));
+        }
+        check_rollback_allowed(&self.cfg.db_path, &package_path)?;
+        restore_previous_package(&package_path)?;
+        let rollback_path = path_to_string(&package_path)?;
+        for invoker in self.live_invokers()? {
+            invoker.replace(rollback_path.clone()).await?;
+        }
+        Ok(())
+    }
+
     async fn list(&self, option: &ListOption) -> RS<AppList> {
         let names = option.names.iter().cloned().collect::<HashSet<String>>();
         let mut apps = load_packages(&self.cfg.mpk_path)?
@@ -273,6 +375,100 @@ fn write_package_to_disk(mpk_path: &str, mpk_binary: &[u8]) -> RS<PathBuf> {
     Ok(final_path)
 }
 
+/// Validate an upgrade package and move it in place of the installed one,
+/// keeping the installed package as the previous version. Returns the path of
+/// the installed package.
+fn stage_upgrade_package(mpk_path: &str, mpk_binary: &[u8]) -> RS<PathBuf> {
+    let temp_path = temp_package_path(&mudu_sys::env_var::temp_dir().to_string_lossy());
+    mudu_sys::fs::sync::write(&temp_path, mpk_binary)?;
+    let package = AppPackage::load(&temp_path);
+    let _ = mudu_sys::fs::sync::remove_file(&temp_path);
+    let package = package?;
+    let app_name = &package.package_cfg.name;
+    let package_path = find_package_path_by_app_name(mpk_path, app_name)?.ok_or_else(|| {
+        mudu_error!(
+            ErrorCode::EntityNotFound,
+            format!("app {} is not installed, install it first", app_name)
+        )
+    })?;
+    let installed = AppPackage::load(&package_path)?;
+    package.check_upgrade_of(&installed.package_cfg)?;
+    let installed_binary = mudu_sys::fs::sync::read(&package_path)?;
+    write_atomically(&previous_package_path(&package_path), &installed_binary)?;
+    write_atomically(&package_path, mpk_binary)?;
+    Ok(package_path)
+}
+
+/// Write `data` to `path` so that a crash leaves either the old or the new
+/// file there, never a partial one: the data is written and synced to a
+/// staging file in the same directory, which is then renamed over `path`.
+/// The staging file does not end in `.mpk`, so it is never loaded as a
+/// package.
+fn write_atomically(path: &Path, data: &[u8]) -> RS<()> {
+    let mut staging_path = path.as_os_str().to_owned();
+    staging_path.push(".");
+    staging_path.push(STAGING_PACKAGE_SUFFIX);
+    let staging_path = PathBuf::from(staging_path);
+    let mut file = mudu_sys::fs::sync::SFile::create(&staging_path)?;
+    file.write_all(data)
+        .map_err(|e| io_error_with_message(e, "write staged package error"))?;
+    file.sync_all()?;
+    drop(file);
+    mudu_sys::fs::sync::rename(&staging_path, path)?;
+    sync_parent_dir(path)
+}
+
+fn sync_parent_dir(path: &Path) -> RS<()> {
+    match path.parent() {
+        Some(dir) => mudu_sys::fs::sync::SFile::open(dir)?.sync_all(),
+        None => Ok(()),
+    }
+}
+
+/// Refuse to roll back past an applied or partly applied migration: the
+/// previous version would run against a schema it does not know, and the
+/// migration cannot be reverted.
+fn check_rollback_allowed(db_path: &str, package_path: &Path) -> RS<()> {
+    let installed = AppPackage::load(package_path)?;
+    let info = &installed.package_cfg;
+    if installed.migrate_sql.trim().is_empty() {
+        return Ok(());
+    }
+    let applied = if is_migrated(db_path, &info.name, &info.version) {
+        "applied"
+    } else if is_migration_pending(db_path, &info.name, &info.version) {
+        "partly applied"
+    } else {
+        return Ok(());
+    };
+    Err(mudu_error!(
+        ErrorCode::InvalidArgument,
+        format!(
+            "app {} version {} has {} its migration, which rollback cannot revert",
+            info.name, info.version, applied
+        )
+    ))
+}
+
+/// Move the previous version of the package at `package_path` back in place.
+fn restore_previous_package(package_path: &Path) -> RS<()> {
+    mudu_sys::fs::sync::rename(previous_package_path(package_path), package_path)?;
+    sync_parent_dir(package_path)
+}
+
+fn previous_package_path(package_path: &Path) -> PathBuf {
+    let mut path = package_path.as_os_str().to_owned();
+    path.push(".");
+    path.push(PREVIOUS_PACKAGE_SUFFIX);
+    PathBuf::from(path)
+}
+
+fn path_to_string(path: &Path) -> RS<String> {
+    path.to_str()
+        .map(str::to_string)
+        .ok_or_else(|| mudu_error!(ErrorCode::InvalidUtf8, "package path is not valid utf-8"))
+}
+
 fn load_packages<P: AsRef<Path>>(mpk_path: P) -> RS<Vec<AppPackage>> {
     let mut packages = Vec::new();
     let path = mpk_path.as_ref();
@@ -350,3 +546,7 @@ fn parse_procedure_name(procedure_name: &str) -> RS<(String, String, String)> {
         proc_name.to_string(),
     ))
 }
+
+#[cfg(test)]
+#[path = "mudu_app_mgr_test.rs"]
+mod mudu_app_mgr_test;
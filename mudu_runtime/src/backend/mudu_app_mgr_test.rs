@@ -0,0 +1,132 @@
+use super::{
+    check_rollback_allowed, previous_package_path, restore_previous_package, stage_upgrade_package,
+};
+use crate::service::app_package::AppPackage;
+use crate::service::file_name;
+use mudu::error::ErrorCode;
+use mudu_contract::procedure::mod_proc_desc::ModProcDesc;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+fn package_binary(name: &str, version: &str) -> Vec<u8> {
+    package_binary_with_migration(name, version, "")
+}
+
+fn package_binary_with_migration(name: &str, version: &str, migrate_sql: &str) -> Vec<u8> {
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default();
+    let entries = [
+        (
+            file_name::PACKAGE_CFG,
+            format!(
+                r#"{{"name":"{}","lang":"rust","version":"{}","use_async":true}}"#,
+                name, version
+            ),
+        ),
+        (
+            file_name::PROCEDURE_DESC,
+            serde_json::to_string(&ModProcDesc::new(HashMap::new())).unwrap(),
+        ),
+        (
+            file_name::DDL_SQL,
+            "create table t(id integer);\n".to_string(),
+        ),
+        (file_name::INIT_DB_SQL, String::new()),
+        (file_name::MIGRATE_SQL, migrate_sql.to_string()),
+    ];
+    for (entry, text) in entries {
+        zip.start_file(entry, options).unwrap();
+        zip.write_all(text.as_bytes()).unwrap();
+    }
+    zip.finish().unwrap().into_inner()
+}
+
+fn mpk_dir() -> PathBuf {
+    let dir = mudu_sys::env_var::temp_dir().join(format!(
+        "mudu_app_mgr_test_{:x}",
+        mudu_utils::oid::gen_oid()
+    ));
+    mudu_sys::fs::sync::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn installed_version(dir: &Path) -> String {
+    AppPackage::load(dir.join("wallet.mpk"))
+        .unwrap()
+        .package_cfg
+        .version
+}
+
+// Zip archives go through flate2/zlib-rs, which Miri flags in the dependency.
+#[test]
+#[cfg_attr(miri, ignore)]
+fn upgrade_keeps_previous_package_for_rollback() {
+    let dir = mpk_dir();
+    let mpk_path = dir.to_string_lossy().to_string();
+    mudu_sys::fs::sync::write(dir.join("wallet.mpk"), package_binary("wallet", "0.1.0")).unwrap();
+
+    let package_path =
+        stage_upgrade_package(&mpk_path, &package_binary("wallet", "0.2.0")).unwrap();
+    assert_eq!(package_path, dir.join("wallet.mpk"));
+    assert_eq!(installed_version(&dir), "0.2.0");
+    assert!(mudu_sys::fs::sync::path_exists(previous_package_path(
+        &package_path
+    )));
+    assert!(!mudu_sys::fs::sync::path_exists(
+        dir.join("wallet.mpk.staged")
+    ));
+
+    restore_previous_package(&package_path).unwrap();
+    assert_eq!(installed_version(&dir), "0.1.0");
+    assert!(!mudu_sys::fs::sync::path_exists(previous_package_path(
+        &package_path
+    )));
+
+    mudu_sys::fs::sync::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn upgrade_rejects_older_version_and_unknown_app() {
+    let dir = mpk_dir();
+    let mpk_path = dir.to_string_lossy().to_string();
+    mudu_sys::fs::sync::write(dir.join("wallet.mpk"), package_binary("wallet", "0.2.0")).unwrap();
+
+    let err = stage_upgrade_package(&mpk_path, &package_binary("wallet", "0.2")).unwrap_err();
+    assert_eq!(err.ec(), ErrorCode::InvalidArgument);
+    assert_eq!(installed_version(&dir), "0.2.0");
+
+    let err = stage_upgrade_package(&mpk_path, &package_binary("ledger", "1.0.0")).unwrap_err();
+    assert_eq!(err.ec(), ErrorCode::EntityNotFound);
+
+    mudu_sys::fs::sync::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn rollback_refuses_applied_migration() {
+    let dir = mpk_dir();
+    let mpk_path = dir.to_string_lossy().to_string();
+    let db_path = mpk_path.clone();
+    mudu_sys::fs::sync::write(dir.join("wallet.mpk"), package_binary("wallet", "0.1.0")).unwrap();
+    let package_path = stage_upgrade_package(
+        &mpk_path,
+        &package_binary_with_migration("wallet", "0.2.0", "alter table t add column v integer;"),
+    )
+    .unwrap();
+    check_rollback_allowed(&db_path, &package_path).unwrap();
+
+    // A migration that failed partway may have changed the schema too.
+    let pending = dir.join("wallet.0.2.0.migrate.pending");
+    mudu_sys::fs::sync::write(&pending, b"").unwrap();
+    let err = check_rollback_allowed(&db_path, &package_path).unwrap_err();
+    assert_eq!(err.ec(), ErrorCode::InvalidArgument);
+    mudu_sys::fs::sync::remove_file(&pending).unwrap();
+
+    mudu_sys::fs::sync::write(dir.join("wallet.0.2.0.migrate.lock"), b"").unwrap();
+    let err = check_rollback_allowed(&db_path, &package_path).unwrap_err();
+    assert_eq!(err.ec(), ErrorCode::InvalidArgument);
+
+    mudu_sys::fs::sync::remove_dir_all(&dir).unwrap();
+}
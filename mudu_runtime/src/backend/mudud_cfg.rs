@@ -3,6 +3,7 @@ use crate::service::runtime_opt::ComponentTarget;
 use mudu::common::result::RS;
 use mudu::error::ErrorCode;
 use mudu::mudu_error;
+use mudu_kernel::server::auth::AuthCfg;
 use mudu_kernel::wal::worker_log::WalSyncPolicy;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt::Display;
@@ -164,6 +165,33 @@ pub struct MuduDBCfg {
     /// A power loss may lose acknowledged commits from the last interval.
     #[serde(default = "default_wal_sync_interval_ms")]
     pub wal_sync_interval_ms: u64,
+    /// Directory the worker log chunks reclaimed by checkpoints are copied
+    /// to before they are deleted. Together with a backup it allows a
+    /// point-in-time restore. Archiving is off when omitted.
+    #[serde(default)]
+    pub wal_archive_dir: Option<String>,
+    /// Port serving the worker logs to standby servers. Replication is off
+    /// when omitted.
+    #[serde(default)]
+    pub replication_listen_port: Option<u16>,
+    /// `host:port` of the primary's replication port. When set, the server
+    /// runs as a read-only standby of that primary until it is promoted.
+    #[serde(default)]
+    pub replicate_from: Option<String>,
+    /// Require clients of the TCP protocol to authenticate, and an admin
+    /// bearer token for the HTTP app management routes.
+    #[serde(default)]
+    pub auth_enabled: bool,
+    /// Bootstrap admin created on first start when `auth_enabled` is set.
+    #[serde(default = "default_auth_admin_user")]
+    pub auth_admin_user: String,
+    /// Password of the bootstrap admin. Required when `auth_enabled` is set;
+    /// only used to create the admin, later changes have no effect.
+    #[serde(default)]
+    pub auth_admin_password: Option<String>,
+    /// Lifetime in seconds of the bearer tokens issued by the HTTP API.
+    #[serde(default = "default_http_token_ttl_secs")]
+    pub http_token_ttl_secs: u64,
 }
 
 impl Display for MuduDBCfg {
@@ -217,6 +245,25 @@ impl Display for MuduDBCfg {
             "  -> WAL sync interval ms: {}",
             self.wal_sync_interval_ms
         )?;
+        writeln!(
+            f,
+            "  -> WAL archive dir: {}",
+            self.wal_archive_dir.as_deref().unwrap_or("(off)")
+        )?;
+        writeln!(
+            f,
+            "  -> Replication listen port: {}",
+            self.replication_listen_port
+                .map_or_else(|| "(off)".to_string(), |port| port.to_string())
+        )?;
+        writeln!(
+            f,
+            "  -> Replicate from: {}",
+            self.replicate_from.as_deref().unwrap_or("(primary)")
+        )?;
+        writeln!(f, "  -> Auth enabled: {}", self.auth_enabled)?;
+        writeln!(f, "  -> Auth admin user: {}", self.auth_admin_user)?;
+        writeln!(f, "  -> HTTP token TTL secs: {}", self.http_token_ttl_secs)?;
         writeln!(f, "-------------------")?;
         Ok(())
     }
@@ -248,6 +295,13 @@ impl Default for MuduDBCfg {
             wal_flush_max_wait_us: default_wal_flush_max_wait_us(),
             wal_sync_mode: default_wal_sync_mode(),
             wal_sync_interval_ms: default_wal_sync_interval_ms(),
+            wal_archive_dir: None,
+            replication_listen_port: None,
+            replicate_from: None,
+            auth_enabled: false,
+            auth_admin_user: default_auth_admin_user(),
+            auth_admin_password: None,
+            http_token_ttl_secs: default_http_token_ttl_secs(),
         }
     }
 }
@@ -323,6 +377,31 @@ wal_sync_mode = "commit"
 # WAL fsync interval in milliseconds for wal_sync_mode = "periodic".
 # periodic 模式下的 WAL fsync 间隔（毫秒）。
 wal_sync_interval_ms = 10
+
+# Copy the WAL chunks reclaimed by checkpoints here before deleting them, for
+# point-in-time restore (see `mudud restore`). Off when unset.
+# 检查点回收的 WAL 分块在删除前复制到此目录，用于按时间点恢复。
+# wal_archive_dir = "./wal_archive"
+
+# Serve the worker logs to standby servers on this port. Off when unset.
+# 在此端口向备库提供 worker 日志。未设置时关闭。
+# replication_listen_port = 9600
+
+# Run as a read-only standby of the primary whose replication port is given
+# here. Seed db_path from a backup of the primary first (`mudud restore`).
+# 作为该主库的只读备库运行。启动前需先用主库的备份恢复 db_path。
+# replicate_from = "127.0.0.1:9600"
+
+# Require clients to authenticate (SCRAM-SHA-256 on the TCP protocol, bearer
+# tokens for the HTTP app management routes).
+auth_enabled = false
+
+# Bootstrap admin created on first start when auth_enabled = true.
+auth_admin_user = "admin"
+# auth_admin_password = "change-me"
+
+# Lifetime in seconds of HTTP API bearer tokens.
+http_token_ttl_secs = 3600
 "#;
 
 impl MuduDBCfg {
@@ -383,6 +462,32 @@ impl MuduDBCfg {
             )),
         }
     }
+
+    /// Maps the `auth_*` settings to the kernel's authentication config,
+    /// `None` when authentication is off. Enabling it without an admin
+    /// password is rejected with a startup error.
+    pub fn auth_cfg(&self) -> RS<Option<AuthCfg>> {
+        if !self.auth_enabled {
+            return Ok(None);
+        }
+        match self.auth_admin_password.as_deref() {
+            Some(password) if !password.is_empty() && !self.auth_admin_user.is_empty() => Ok(Some(
+                AuthCfg::new(self.auth_admin_user.clone(), password.to_string()),
+            )),
+            _ => Err(mudu_error!(
+                ErrorCode::InvalidArgument,
+                "auth_enabled requires non-empty auth_admin_user and auth_admin_password"
+            )),
+        }
+    }
+}
+
+fn default_auth_admin_user() -> String {
+    "admin".to_string()
+}
+
+fn default_http_token_ttl_secs() -> u64 {
+    3600
 }
 
 fn default_true() -> bool {
@@ -236,3 +236,17 @@ fn init_cfg_writes_to_current_directory() {
 
     let _ = mudu_sys::fs::sync::remove_file(&current_dir_cfg);
 }
+
+#[test]
+fn auth_cfg_requires_admin_password_when_enabled() {
+    let mut cfg = MuduDBCfg::default();
+    assert!(cfg.auth_cfg().unwrap().is_none());
+
+    cfg.auth_enabled = true;
+    assert!(cfg.auth_cfg().is_err());
+
+    cfg.auth_admin_password = Some("secret".to_string());
+    let auth = cfg.auth_cfg().unwrap().unwrap();
+    assert_eq!(auth.admin_user(), "admin");
+    assert_eq!(auth.admin_password(), "secret");
+}
@@ -9,7 +9,7 @@ use crate::service::runtime_opt::RuntimeOpt;
 use mudu::common::result::RS;
 use mudu_kernel::mudu_conn::mudu_conn_async::{
     clear_default_remote_if_current, set_default_remote_addr, set_default_remote_async_runtime,
-    set_default_remote_worker_id,
+    set_default_remote_credential, set_default_remote_worker_id,
 };
 use mudu_kernel::server::routing::RoutingMode;
 use mudu_kernel::server::server::TokioTcpBackend as KernelTokioTcpBackend;
@@ -75,7 +75,11 @@ impl TokioBackend {
         .with_multi_port(cfg.tcp_multi_port)
         .with_page_size(cfg.page_size)?
         .with_log_batching_max_wait(std::time::Duration::from_micros(cfg.wal_flush_max_wait_us))
-        .with_wal_sync_policy(cfg.wal_sync_policy()?);
+        .with_wal_sync_policy(cfg.wal_sync_policy()?)
+        .with_wal_archive_dir(cfg.wal_archive_dir.clone())
+        .with_replication_listen_port(cfg.replication_listen_port)
+        .with_replicate_from(cfg.replicate_from.clone())
+        .with_auth(cfg.auth_cfg()?);
         let mut server_deps = ServerRuntimeDeps::from_cfg(&base_server_cfg)?
             .with_async_runtime(async_runtime.clone());
         let default_remote_addr = format!("{}:{}", cfg.listen_ip, cfg.tcp_listen_port);
@@ -84,6 +88,12 @@ impl TokioBackend {
         set_default_remote_async_runtime(server_deps.async_runtime());
         set_default_remote_addr(Some(default_remote_addr.clone()));
         set_default_remote_worker_id(default_remote_worker_id);
+        set_default_remote_credential(cfg.auth_cfg()?.map(|auth| {
+            (
+                auth.admin_user().to_string(),
+                auth.admin_password().to_string(),
+            )
+        }));
         let procedure_cfg = cfg.clone();
         let procedure_app_mgr = app_mgr.clone();
         let procedure_runtimes = task_async::block_on_tokio_current_thread(async move {
@@ -6,6 +6,7 @@ use lazy_static::lazy_static;
 use libsql::{Builder, Connection, Database, Statement, Transaction, params_from_iter};
 use mudu::common::result::RS;
 use mudu::common::xid::OID;
+use mudu::data_type::uuid::format_uuid;
 use mudu::error::ErrorCode;
 use mudu::error::MuduError;
 use mudu::mudu_error;
@@ -259,6 +260,9 @@ fn to_libsql_params(sql_param: &dyn SQLParams) -> RS<LibSQLParam> {
 fn _to_libsql_value(datum: &DataValue, ty: &DataType) -> RS<libsql::Value> {
     let id = ty.type_family();
     let v = match id {
+        TypeFamily::Bool => libsql::Value::Integer(*datum.expect_bool() as _),
+        TypeFamily::I8 => libsql::Value::Integer(*datum.expect_i8() as _),
+        TypeFamily::I16 => libsql::Value::Integer(*datum.expect_i16() as _),
         TypeFamily::I32 => libsql::Value::Integer(*datum.expect_i32() as _),
         TypeFamily::I64 => libsql::Value::Integer(*datum.expect_i64() as _),
         TypeFamily::U128 => libsql::Value::Text(datum.expect_u128().to_string()),
@@ -281,6 +285,8 @@ fn _to_libsql_value(datum: &DataValue, ty: &DataType) -> RS<libsql::Value> {
                 .format(6)
                 .map_err(|e| mudu_error!(ErrorCode::TypeConversionFailed, e))?,
         ),
+        TypeFamily::Uuid => libsql::Value::Text(format_uuid(*datum.expect_uuid())),
+        TypeFamily::Json => libsql::Value::Text(datum.expect_json().clone()),
         TypeFamily::Array => libsql::Value::Blob(datum.to_binary(ty)?.into()),
         TypeFamily::Record => libsql::Value::Blob(datum.to_binary(ty)?.into()),
         TypeFamily::Binary => libsql::Value::Blob(datum.to_binary(ty)?.into()),
@@ -24,7 +24,8 @@ fn _mgr_get(app_name: &String) -> Option<SchemaMgr> {
 }
 
 fn _mgr_add(app_name: String, schema_mgr: SchemaMgr) {
-    let _ = _MGR.insert_sync(app_name, schema_mgr);
+    // Upsert: an app upgrade re-registers the schema under the same name.
+    let _ = _MGR.upsert_sync(app_name, schema_mgr);
 }
 
 fn _mgr_remove(app_name: &String) {
@@ -46,7 +47,8 @@ impl SchemaMgr {
         _mgr_get(app_name)
     }
 
-    /// Registers a schema manager for the given application.
+    /// Registers a schema manager for the given application, replacing any
+    /// previously registered one.
     pub fn add_mgr(app_name: String, schema_mgr: SchemaMgr) {
         _mgr_add(app_name, schema_mgr);
     }
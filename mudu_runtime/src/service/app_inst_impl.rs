@@ -369,6 +369,72 @@ async fn initdb(
     Ok(())
 }
 
+/// Run `sql`, the migration of `app_name` to `version`. The migration runs
+/// once: like `initdb`, a lock file in `db_path` records that it has been
+/// applied, so the runtimes of the other workers skip it.
+///
+/// The statements of a migration are not applied atomically: DDL is not
+/// transactional on every backend. A pending marker is written before the
+/// first statement and removed once the migration is applied, so a failed
+/// migration is known to have possibly changed the schema. Such a migration
+/// is not run again until an operator repairs the database and removes the
+/// marker.
+pub(crate) async fn migratedb(
+    db_path: &str,
+    app_name: &str,
+    version: &str,
+    sql: &str,
+    enable_async: bool,
+    server_mode: ServerMode,
+    async_runtime: Option<Arc<dyn AsyncIoProvider>>,
+) -> RS<()> {
+    if sql.trim().is_empty() {
+        return Ok(());
+    }
+    let migrate_lock = migrate_lock_path(db_path, app_name, version);
+    if fs::sync::sync_path_exists(&migrate_lock) {
+        return Ok(());
+    }
+    let pending = migrate_pending_path(db_path, app_name, version);
+    if fs::sync::sync_path_exists(&pending) {
+        return Err(mudu_error!(
+            ErrorCode::InvalidState,
+            format!(
+                "the migration of app {} to version {} failed partway; repair the database \
+                 and remove {} to run it again",
+                app_name,
+                version,
+                pending.display()
+            )
+        ));
+    }
+    let conn = new_conn(db_path, app_name, enable_async, server_mode, async_runtime).await?;
+    fs::sync::SFile::create(&pending)?;
+    conn.execute_silent(sql.to_owned()).await?;
+    fs::sync::SFile::create(&migrate_lock)?;
+    fs::sync::remove_file(&pending)?;
+    Ok(())
+}
+
+/// Whether the migration of `app_name` to `version` has been applied.
+pub(crate) fn is_migrated(db_path: &str, app_name: &str, version: &str) -> bool {
+    fs::sync::sync_path_exists(migrate_lock_path(db_path, app_name, version))
+}
+
+/// Whether the migration of `app_name` to `version` started and did not
+/// finish, leaving the schema possibly changed.
+pub(crate) fn is_migration_pending(db_path: &str, app_name: &str, version: &str) -> bool {
+    fs::sync::sync_path_exists(migrate_pending_path(db_path, app_name, version))
+}
+
+fn migrate_lock_path(db_path: &str, app_name: &str, version: &str) -> PathBuf {
+    PathBuf::from(db_path).join(format!("{}.{}.migrate.lock", app_name, version))
+}
+
+fn migrate_pending_path(db_path: &str, app_name: &str, version: &str) -> PathBuf {
+    PathBuf::from(db_path).join(format!("{}.{}.migrate.pending", app_name, version))
+}
+
 async fn is_schema_initialized(
     db_path: &str,
     app_name: &str,
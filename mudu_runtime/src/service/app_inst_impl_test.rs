@@ -1,6 +1,6 @@
 #![allow(clippy::unwrap_used)]
 
-use super::AppInstImpl;
+use super::{AppInstImpl, is_migrated, is_migration_pending, migratedb};
 use crate::backend::mudud_cfg::ServerMode;
 use crate::service::app_inst::AppInst;
 use crate::service::app_package::AppPackage;
@@ -30,10 +30,12 @@ fn test_package(desc: ModProcDesc) -> AppPackage {
             lang: "rust".to_string(),
             version: "0.1.0".to_string(),
             use_async: false,
+            limits: Default::default(),
         },
         ddl_sql: "CREATE TABLE t(id INTEGER PRIMARY KEY);".to_string(),
         package_desc: desc,
         initdb_sql: String::new(),
+        migrate_sql: String::new(),
         modules: HashMap::new(),
     }
 }
@@ -168,3 +170,73 @@ async fn invoke_async_rejected_when_async_disabled() {
     assert_eq!(err.ec(), mudu::error::ErrorCode::Database);
     assert!(err.to_string().contains("enable async mode"));
 }
+
+// libsql performs real SQLite file IO outside `mudu_sys`; the
+// deterministic-simulation backend keeps fs writes in memory,
+// so the database file cannot be created. Native backend only.
+#[cfg(not(feature = "ds"))]
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn failed_migration_stays_pending_until_repaired() {
+    let db_path = temp_db_path("migrate");
+    let package = test_package(ModProcDesc::new(HashMap::new()));
+    AppInstImpl::build(
+        &db_path,
+        &package,
+        vec![],
+        ComponentTarget::P2,
+        false,
+        ServerMode::Legacy,
+        None,
+    )
+    .await
+    .unwrap();
+
+    // The second statement fails after the first changed the schema.
+    let sql = "ALTER TABLE t ADD COLUMN v INTEGER; ALTER TABLE missing ADD COLUMN w INTEGER;";
+    migratedb(
+        &db_path,
+        "app",
+        "0.2.0",
+        sql,
+        false,
+        ServerMode::Legacy,
+        None,
+    )
+    .await
+    .unwrap_err();
+    assert!(is_migration_pending(&db_path, "app", "0.2.0"));
+    assert!(!is_migrated(&db_path, "app", "0.2.0"));
+
+    let err = migratedb(
+        &db_path,
+        "app",
+        "0.2.0",
+        sql,
+        false,
+        ServerMode::Legacy,
+        None,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.ec(), mudu::error::ErrorCode::InvalidState);
+
+    // Once repaired, the migration runs again.
+    mudu_sys::fs::sync::remove_file(
+        std::path::PathBuf::from(&db_path).join("app.0.2.0.migrate.pending"),
+    )
+    .unwrap();
+    migratedb(
+        &db_path,
+        "app",
+        "0.2.0",
+        "ALTER TABLE t ADD COLUMN w INTEGER;",
+        false,
+        ServerMode::Legacy,
+        None,
+    )
+    .await
+    .unwrap();
+    assert!(is_migrated(&db_path, "app", "0.2.0"));
+    assert!(!is_migration_pending(&db_path, "app", "0.2.0"));
+}
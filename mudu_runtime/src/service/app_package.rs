@@ -25,6 +25,9 @@ pub struct AppPackage {
     pub ddl_sql: String,
     pub package_desc: ModProcDesc,
     pub initdb_sql: String,
+    /// SQL run once when this package upgrades an older version of the app;
+    /// empty if the package has none.
+    pub migrate_sql: String,
     pub modules: HashMap<String, Vec<u8>>,
 }
 
@@ -34,6 +37,7 @@ impl AppPackage {
     ///     1 `package.desc.json`
     ///     1 `ddl.sql`
     ///     1 `initdb.sql`
+    ///     0 or 1 `migrate.sql`
     ///     1 or more `*.wasm`
     ///
     /// Load package
@@ -51,6 +55,23 @@ impl AppPackage {
     pub fn name(&self) -> &String {
         &self.package_cfg.name
     }
+
+    /// Check that this package can upgrade the `installed` version of its
+    /// app: it must carry a strictly newer version.
+    pub fn check_upgrade_of(&self, installed: &AppInfo) -> RS<()> {
+        let installed_version = installed.app_version()?;
+        let version = self.package_cfg.app_version()?;
+        if version <= installed_version {
+            return Err(mudu_error!(
+                ErrorCode::InvalidArgument,
+                format!(
+                    "app {} version {} is not newer than installed version {}",
+                    self.package_cfg.name, version, installed_version
+                )
+            ));
+        }
+        Ok(())
+    }
 }
 
 fn load_and_extract_package<P: AsRef<Path>>(package_path: P) -> RS<AppPackage> {
@@ -62,6 +83,7 @@ fn load_and_extract_package<P: AsRef<Path>>(package_path: P) -> RS<AppPackage> {
         .map_err(|e| mudu_error!(ErrorCode::Decode, "read achieve file failed", e))?;
     let mut ddl_sql = String::new();
     let mut initdb_sql = String::new();
+    let mut migrate_sql = String::new();
     let mut app_cfg_text = String::new();
     let mut app_proc_desc_text = String::new();
     let mut manifest_text = String::new();
@@ -82,6 +104,8 @@ fn load_and_extract_package<P: AsRef<Path>>(package_path: P) -> RS<AppPackage> {
             file.read_to_string(&mut ddl_sql).map_err(io_error)?;
         } else if file_name == file_name::INIT_DB_SQL {
             file.read_to_string(&mut initdb_sql).map_err(io_error)?;
+        } else if file_name == file_name::MIGRATE_SQL {
+            file.read_to_string(&mut migrate_sql).map_err(io_error)?;
         } else if file_name == file_name::PROCEDURE_DESC {
             file.read_to_string(&mut app_proc_desc_text)
                 .map_err(io_error)?;
@@ -160,6 +184,7 @@ fn load_and_extract_package<P: AsRef<Path>>(package_path: P) -> RS<AppPackage> {
         ddl_sql,
         package_desc: app_proc_desc,
         initdb_sql,
+        migrate_sql,
         modules,
     })
 }
@@ -80,6 +80,29 @@ mod tests {
         mudu_sys::fs::sync::remove_file(package_file).unwrap();
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn loads_optional_migrate_sql() {
+        let package_file = package_file("app_json_migrate");
+        write_package(
+            &package_file,
+            Some(standard_cfg()),
+            Some(&standard_desc()),
+            Some(b"create table t(id integer);\n"),
+            Some(b""),
+            &[
+                ("module.wasm", b"\0asm\x01\0\0\0"),
+                (file_name::MIGRATE_SQL, b"alter table t add column v integer;\n"),
+            ],
+        );
+
+        let package = AppPackage::load(&package_file).unwrap();
+        assert_eq!(package.migrate_sql, "alter table t add column v integer;\n");
+        assert!(!package.modules.contains_key("migrate"));
+
+        mudu_sys::fs::sync::remove_file(package_file).unwrap();
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)]
     fn single_module_package_aligns_desc_module_name() {
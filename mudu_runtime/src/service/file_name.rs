@@ -3,6 +3,7 @@ pub const PROCEDURE_DESC: &str = "package.desc.json";
 pub const PACKAGE_MANIFEST: &str = "package.manifest.json";
 pub const DDL_SQL: &str = "ddl.sql";
 pub const INIT_DB_SQL: &str = "initdb.sql";
+pub const MIGRATE_SQL: &str = "migrate.sql";
 pub const BYTE_CODE_MOD_SUFFIX: &str = ".wasm";
 
 pub const APP_PACKAGE_EXTENSION: &str = "mpk";
@@ -25,6 +25,10 @@ mod test_wasm_mod_path;
 pub mod procedure_instance_pool;
 /// Component responsible for invoking procedures.
 pub mod procedure_invoke_component;
+/// Resource limits enforced on procedure instances.
+pub mod procedure_limits;
+#[cfg(test)]
+mod procedure_limits_test;
 #[cfg(test)]
 mod runtime_simple_test;
 /// Service task registry and execution.
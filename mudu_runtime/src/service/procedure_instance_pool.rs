@@ -12,10 +12,17 @@
 //! discarded instead of being returned to the pool, so a poisoned store is
 //! never reused. A clean return carrying a domain error (an abort, encoded in
 //! the result bytes) leaves the store healthy and the instance is reused.
+//!
+//! Every store enforces the app's resource limits (see `procedure_limits`);
+//! the fuel and wall-clock budgets are reset before each invocation.
 
 #![allow(missing_docs)]
 
+use crate::service::procedure_limits::{
+    ProcedureLimiter, invoke_deadline_ticks, invoke_fuel, limit_exceeded_error,
+};
 use crate::service::wasi_context_component::{WasiContextComponent, build_wasi_component_context};
+use mudu::common::app_info::AppLimits;
 use mudu::common::result::RS;
 use mudu::error::ErrorCode;
 use mudu::mudu_error;
@@ -42,18 +49,20 @@ struct PooledInstance {
 
 pub struct ProcedureInstancePool {
     idle: SMutex<HashMap<String, Vec<PooledInstance>>>,
+    limits: AppLimits,
 }
 
 impl Default for ProcedureInstancePool {
     fn default() -> Self {
-        Self::new()
+        Self::new(AppLimits::default())
     }
 }
 
 impl ProcedureInstancePool {
-    pub fn new() -> Self {
+    pub fn new(limits: AppLimits) -> Self {
         Self {
             idle: SMutex::new(HashMap::new()),
+            limits,
         }
     }
 
@@ -73,7 +82,7 @@ impl ProcedureInstancePool {
         };
         let pooled = match pooled {
             Some(pooled) => pooled,
-            None => Self::instantiate(instance_pre, func_name).await?,
+            None => self.instantiate(instance_pre, func_name).await?,
         };
         Ok(LeasedInstance {
             pool: self.clone(),
@@ -83,14 +92,24 @@ impl ProcedureInstancePool {
     }
 
     async fn instantiate(
+        &self,
         instance_pre: &InstancePre<WasiContextComponent>,
         func_name: &str,
     ) -> RS<PooledInstance> {
-        let mut store = Store::new(instance_pre.engine(), build_wasi_component_context(None));
+        let mut context = build_wasi_component_context(None);
+        context.set_limiter(ProcedureLimiter::new(&self.limits));
+        let mut store = Store::new(instance_pre.engine(), context);
+        store.limiter(|context| context.limiter_mut());
+        // Instantiation may run guest start code: budget it like a call.
+        reset_invoke_budget(&mut store, &self.limits)?;
         let instance = instance_pre
             .instantiate_async(&mut store)
             .await
-            .map_err(|e| mudu_error!(ErrorCode::Internal, "component instantiate error", e))?;
+            .map_err(|e| {
+                limit_exceeded_error(e).unwrap_or_else(|e| {
+                    mudu_error!(ErrorCode::Internal, "component instantiate error", e)
+                })
+            })?;
         let function = instance.get_func(&mut store, func_name).ok_or_else(|| {
             mudu_error!(
                 ErrorCode::Internal,
@@ -113,6 +132,11 @@ impl ProcedureInstancePool {
         }
     }
 
+    #[cfg(test)]
+    pub fn limits(&self) -> &AppLimits {
+        &self.limits
+    }
+
     #[cfg(test)]
     pub fn idle_len(&self, func_name: &str) -> usize {
         self.idle
@@ -122,6 +146,17 @@ impl ProcedureInstancePool {
     }
 }
 
+/// Refill the fuel and restart the wall-clock deadline of `store`.
+fn reset_invoke_budget(store: &mut Store<WasiContextComponent>, limits: &AppLimits) -> RS<()> {
+    if let Some(fuel) = invoke_fuel(limits) {
+        store
+            .set_fuel(fuel)
+            .map_err(|e| mudu_error!(ErrorCode::Internal, "set procedure fuel error", e))?;
+    }
+    store.set_epoch_deadline(invoke_deadline_ticks(limits));
+    Ok(())
+}
+
 impl Drop for LeasedInstance {
     fn drop(&mut self) {
         // A lease that was never invoked (e.g. parameter serialization
@@ -155,6 +190,7 @@ impl LeasedInstance {
             .inner
             .take()
             .ok_or_else(|| mudu_error!(ErrorCode::Internal, "leased instance already consumed"))?;
+        reset_invoke_budget(&mut pooled.store, &self.pool.limits)?;
         let call_result = pooled
             .typed_func
             .call_async(&mut pooled.store, (param_p2,))
@@ -171,12 +207,11 @@ impl LeasedInstance {
                 result
             }
             Err(e) => {
-                // The guest trapped: the store may be poisoned, discard it.
-                Err(mudu_error!(
-                    ErrorCode::DomainViolation,
-                    "invoke call async error",
-                    e
-                ))
+                // The guest trapped (possibly on a resource limit): the
+                // store may be poisoned, discard it.
+                Err(limit_exceeded_error(e).unwrap_or_else(|e| {
+                    mudu_error!(ErrorCode::DomainViolation, "invoke call async error", e)
+                }))
             }
         }
     }
@@ -190,13 +225,21 @@ mod tests {
     use crate::service::runtime_opt::{ComponentTarget, RuntimeOpt};
     use crate::service::test_wasm_mod_path::wasm_mod_path;
     use crate::service::wt_runtime_component::WTRuntimeComponent;
+    use mudu::common::app_info::AppLimits;
+    use mudu::error::ErrorCode;
     use mudu::utils::case_convert::to_kebab_case;
     use mudu_contract::procedure::procedure_param::ProcedureParam;
     use mudu_type::data_value::DataValue;
     use std::path::PathBuf;
 
     fn get_procedure(proc_name: &str) -> Procedure {
-        let package = AppPackage::load(PathBuf::from(wasm_mod_path()).join("app1.mpk")).unwrap();
+        get_procedure_with_limits(proc_name, AppLimits::default())
+    }
+
+    fn get_procedure_with_limits(proc_name: &str, limits: AppLimits) -> Procedure {
+        let mut package =
+            AppPackage::load(PathBuf::from(wasm_mod_path()).join("app1.mpk")).unwrap();
+        package.package_cfg.limits = limits;
         let mut runtime = WTRuntimeComponent::build(&RuntimeOpt {
             component_target: ComponentTarget::P2,
             enable_async: true,
@@ -252,4 +295,33 @@ mod tests {
         }
         assert_eq!(proc.instance().pool_idle_len(&func_name), 1);
     }
+
+    /// A procedure that exhausts its fuel budget fails with the dedicated
+    /// limit error, and its trapped instance is not returned to the pool.
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)]
+    async fn fuel_exhaustion_is_reported_as_limit_exceeded() {
+        let limits = AppLimits {
+            max_fuel: Some(1),
+            ..AppLimits::default()
+        };
+        let proc = get_procedure_with_limits("proc2_mtp", limits.clone());
+        assert_eq!(proc.instance().pool_limits(), &limits);
+        let func_name = to_kebab_case(&format!(
+            "{}{}",
+            mudu_contract::procedure::proc::MUDU_PROC_P2_PREFIX,
+            proc.proc_name()
+        ));
+        let err = ProcedureInvokeComponent::call_async(
+            &proc,
+            ComponentTarget::P2,
+            ProcOpt::default(),
+            sample_param(),
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.ec(), ErrorCode::ProcedureLimitExceeded);
+        assert_eq!(proc.instance().pool_idle_len(&func_name), 0);
+    }
 }
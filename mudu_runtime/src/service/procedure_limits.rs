@@ -0,0 +1,172 @@
+//! Resource limits for WASM procedure instances.
+//!
+//! Every pooled [`Store`](wasmtime::Store) enforces its app's [`AppLimits`]:
+//!
+//! * wall-clock time, through epoch interruption. One ticker thread per
+//!   engine bumps the epoch every [`EPOCH_TICK`]; each invocation gets a
+//!   deadline of `max_wall_clock_millis` worth of ticks. The deadline is not
+//!   CPU time: an invocation blocked in a host call keeps using it up, and
+//!   the trap only fires once the guest runs again.
+//! * fuel, refilled to `max_fuel` before each invocation. Metering costs
+//!   guest throughput, so only apps that set `max_fuel` run on a fuel
+//!   consuming engine (see [`needs_fuel`]).
+//! * linear memory and table growth, through [`ProcedureLimiter`].
+//!
+//! A procedure that hits any of them traps; [`limit_exceeded_error`] turns
+//! the trap into `ErrorCode::ProcedureLimitExceeded` so callers can tell a
+//! runaway procedure apart from a crashed one.
+
+use mudu::common::app_info::AppLimits;
+use mudu::common::result::RS;
+use mudu::error::ErrorCode;
+use mudu::error::MuduError;
+use mudu::mudu_error;
+use mudu_sys::task::sync::{sleep_blocking, spawn_thread_named};
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+use wasmtime::{Engine, ResourceLimiter, Trap};
+
+/// Interval between two epoch increments; the granularity of the wall-clock
+/// time limit.
+pub const EPOCH_TICK: Duration = Duration::from_millis(10);
+
+/// Epoch deadline used when the wall-clock time is unbounded. Far enough to
+/// never be reached, small enough not to overflow when added to the epoch.
+const UNBOUNDED_EPOCH_TICKS: u64 = u64::MAX / 2;
+
+/// Spawn the thread that advances the epoch of `engine` every
+/// [`EPOCH_TICK`]. The thread exits once the engine is dropped.
+pub fn spawn_epoch_ticker(engine: &Engine) -> RS<()> {
+    let engine = engine.weak();
+    spawn_thread_named("wasm-epoch-ticker", move || {
+        loop {
+            sleep_blocking(EPOCH_TICK);
+            match engine.upgrade() {
+                Some(engine) => engine.increment_epoch(),
+                None => break,
+            }
+        }
+    })?;
+    Ok(())
+}
+
+/// Epoch ticks of wall-clock time one invocation may take under `limits`.
+pub fn invoke_deadline_ticks(limits: &AppLimits) -> u64 {
+    match limits.max_wall_clock_millis {
+        Some(millis) => millis.div_ceil(EPOCH_TICK.as_millis() as u64).max(1),
+        None => UNBOUNDED_EPOCH_TICKS,
+    }
+}
+
+/// Whether stores of an app with `limits` need an engine that consumes
+/// fuel.
+pub fn needs_fuel(limits: &AppLimits) -> bool {
+    limits.max_fuel.is_some()
+}
+
+/// Fuel one invocation may burn under `limits`; `None` when the app runs
+/// without fuel metering.
+pub fn invoke_fuel(limits: &AppLimits) -> Option<u64> {
+    limits.max_fuel
+}
+
+/// Memory and table growth limiter installed on every procedure store.
+#[derive(Debug, Clone, Default)]
+pub struct ProcedureLimiter {
+    max_memory_bytes: Option<usize>,
+    max_table_elements: Option<usize>,
+}
+
+impl ProcedureLimiter {
+    pub fn new(limits: &AppLimits) -> Self {
+        Self {
+            max_memory_bytes: limits.max_memory_bytes.map(saturating_usize),
+            max_table_elements: limits.max_table_elements.map(saturating_usize),
+        }
+    }
+}
+
+impl ResourceLimiter for ProcedureLimiter {
+    fn memory_growing(
+        &mut self,
+        _current: usize,
+        desired: usize,
+        _maximum: Option<usize>,
+    ) -> wasmtime::Result<bool> {
+        check_growth(LimitKind::Memory, desired, self.max_memory_bytes)
+    }
+
+    fn table_growing(
+        &mut self,
+        _current: usize,
+        desired: usize,
+        _maximum: Option<usize>,
+    ) -> wasmtime::Result<bool> {
+        check_growth(LimitKind::TableElements, desired, self.max_table_elements)
+    }
+}
+
+fn check_growth(kind: LimitKind, desired: usize, limit: Option<usize>) -> wasmtime::Result<bool> {
+    match limit {
+        Some(limit) if desired > limit => Err(wasmtime::Error::new(GrowthLimitExceeded {
+            kind,
+            desired,
+            limit,
+        })),
+        _ => Ok(true),
+    }
+}
+
+fn saturating_usize(value: u64) -> usize {
+    usize::try_from(value).unwrap_or(usize::MAX)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LimitKind {
+    Memory,
+    TableElements,
+}
+
+/// Trap payload raised by [`ProcedureLimiter`]; growing past the limit
+/// traps instead of failing the `grow` instruction, so a procedure cannot
+/// keep running with a half-built data structure.
+#[derive(Debug)]
+struct GrowthLimitExceeded {
+    kind: LimitKind,
+    desired: usize,
+    limit: usize,
+}
+
+impl Display for GrowthLimitExceeded {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            LimitKind::Memory => write!(
+                f,
+                "procedure memory would grow to {} bytes, limit is {} bytes",
+                self.desired, self.limit
+            ),
+            LimitKind::TableElements => write!(
+                f,
+                "procedure table would grow to {} elements, limit is {} elements",
+                self.desired, self.limit
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GrowthLimitExceeded {}
+
+/// `Ok` with an `ErrorCode::ProcedureLimitExceeded` error when the guest
+/// trapped on one of its resource limits; any other error is handed back.
+pub fn limit_exceeded_error(e: wasmtime::Error) -> Result<MuduError, wasmtime::Error> {
+    let message = if let Some(exceeded) = e.downcast_ref::<GrowthLimitExceeded>() {
+        exceeded.to_string()
+    } else {
+        match e.downcast_ref::<Trap>() {
+            Some(Trap::Interrupt) => "procedure exceeded its wall-clock time limit".to_string(),
+            Some(Trap::OutOfFuel) => "procedure ran out of fuel".to_string(),
+            _ => return Err(e),
+        }
+    };
+    Ok(mudu_error!(ErrorCode::ProcedureLimitExceeded, message, e))
+}
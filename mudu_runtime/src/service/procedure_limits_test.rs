@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+    use crate::service::procedure_limits::{
+        EPOCH_TICK, ProcedureLimiter, invoke_deadline_ticks, invoke_fuel, limit_exceeded_error,
+        needs_fuel,
+    };
+    use mudu::common::app_info::AppLimits;
+    use mudu::error::ErrorCode;
+    use wasmtime::{ResourceLimiter, Trap};
+
+    fn limits(max_memory_bytes: Option<u64>, max_table_elements: Option<u64>) -> AppLimits {
+        AppLimits {
+            max_memory_bytes,
+            max_table_elements,
+            ..AppLimits::unbounded()
+        }
+    }
+
+    #[test]
+    fn deadline_rounds_up_to_whole_ticks() {
+        let tick = EPOCH_TICK.as_millis() as u64;
+        let mut limits = AppLimits::unbounded();
+        limits.max_wall_clock_millis = Some(tick * 2 + 1);
+        assert_eq!(invoke_deadline_ticks(&limits), 3);
+        limits.max_wall_clock_millis = Some(0);
+        assert_eq!(invoke_deadline_ticks(&limits), 1);
+        limits.max_wall_clock_millis = None;
+        assert!(invoke_deadline_ticks(&limits) > u64::MAX / 4);
+    }
+
+    #[test]
+    fn fuel_is_metered_only_when_limited() {
+        let mut limits = AppLimits::default();
+        assert!(!needs_fuel(&limits));
+        assert_eq!(invoke_fuel(&limits), None);
+        limits.max_fuel = Some(42);
+        assert!(needs_fuel(&limits));
+        assert_eq!(invoke_fuel(&limits), Some(42));
+    }
+
+    #[test]
+    fn limiter_rejects_growth_past_the_limit() {
+        let mut limiter = ProcedureLimiter::new(&limits(Some(1 << 16), Some(10)));
+        assert!(limiter.memory_growing(0, 1 << 16, None).unwrap());
+        assert!(limiter.table_growing(0, 10, None).unwrap());
+
+        let err = limiter.memory_growing(1 << 16, 2 << 16, None).unwrap_err();
+        let err = limit_exceeded_error(err).unwrap();
+        assert_eq!(err.ec(), ErrorCode::ProcedureLimitExceeded);
+        assert!(err.message().contains("memory"));
+
+        let err = limiter.table_growing(10, 11, None).unwrap_err();
+        let err = limit_exceeded_error(err).unwrap();
+        assert_eq!(err.ec(), ErrorCode::ProcedureLimitExceeded);
+        assert!(err.message().contains("table"));
+    }
+
+    #[test]
+    fn unbounded_limiter_allows_any_growth() {
+        let mut limiter = ProcedureLimiter::new(&AppLimits::unbounded());
+        assert!(limiter.memory_growing(0, usize::MAX, None).unwrap());
+        assert!(limiter.table_growing(0, usize::MAX, None).unwrap());
+    }
+
+    #[test]
+    fn only_limit_traps_map_to_limit_exceeded() {
+        for trap in [Trap::OutOfFuel, Trap::Interrupt] {
+            let err = limit_exceeded_error(wasmtime::Error::new(trap)).unwrap();
+            assert_eq!(err.ec(), ErrorCode::ProcedureLimitExceeded);
+        }
+        assert!(limit_exceeded_error(wasmtime::Error::new(Trap::UnreachableCodeReached)).is_err());
+    }
+}
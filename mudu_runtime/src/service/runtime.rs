@@ -1,6 +1,8 @@
 use crate::service::app_inst::AppInst;
 use async_trait::async_trait;
 use mudu::common::result::RS;
+use mudu::error::ErrorCode;
+use mudu::mudu_error;
 use mudu_sys::contract::async_io_provider::AsyncIoProvider;
 use std::sync::Arc;
 
@@ -16,6 +18,37 @@ pub trait Runtime: Send + Sync {
     /// Installs an application from the given package path.
     async fn install(&self, pkg_path: String) -> RS<()>;
 
+    /// Upgrades an installed application to the newer version in the given
+    /// package and then runs the package's migration SQL. For callers that
+    /// drive a single runtime; with several runtimes, switch all of them with
+    /// [`Runtime::replace`] before running [`Runtime::migrate`] once.
+    async fn upgrade(&self, pkg_path: String) -> RS<()> {
+        Err(mudu_error!(
+            ErrorCode::NotImplemented,
+            format!("upgrade is not supported for {}", pkg_path)
+        ))
+    }
+
+    /// Replaces an installed application with the package at the given
+    /// path, whatever its version, without running any migration.
+    /// Invocations that already started keep running on the replaced
+    /// version.
+    async fn replace(&self, pkg_path: String) -> RS<()> {
+        Err(mudu_error!(
+            ErrorCode::NotImplemented,
+            format!("replace is not supported for {}", pkg_path)
+        ))
+    }
+
+    /// Runs the migration SQL of the package at the given path against the
+    /// application database, once per application version.
+    async fn migrate(&self, pkg_path: String) -> RS<()> {
+        Err(mudu_error!(
+            ErrorCode::NotImplemented,
+            format!("migrate is not supported for {}", pkg_path)
+        ))
+    }
+
     /// Returns the configured async I/O provider, if any.
     fn async_runtime(&self) -> Option<Arc<dyn AsyncIoProvider>>;
 }
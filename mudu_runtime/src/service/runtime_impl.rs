@@ -55,6 +55,18 @@ impl Runtime for RuntimeImpl {
         self.inner.install(pkg_path).await
     }
 
+    async fn upgrade(&self, pkg_path: String) -> RS<()> {
+        self.inner.upgrade(pkg_path).await
+    }
+
+    async fn replace(&self, pkg_path: String) -> RS<()> {
+        self.inner.replace(pkg_path).await
+    }
+
+    async fn migrate(&self, pkg_path: String) -> RS<()> {
+        self.inner.migrate(pkg_path).await
+    }
+
     fn async_runtime(&self) -> Option<Arc<dyn AsyncIoProvider>> {
         self.inner.async_runtime()
     }
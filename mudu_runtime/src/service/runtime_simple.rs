@@ -1,5 +1,5 @@
 use crate::service::app_inst::AppInst;
-use crate::service::app_inst_impl::AppInstImpl;
+use crate::service::app_inst_impl::{AppInstImpl, is_migration_pending, migratedb};
 use crate::service::app_package::AppPackage;
 use crate::service::file_name;
 use crate::service::runtime_opt::RuntimeOpt;
@@ -148,6 +148,62 @@ impl RuntimeSimple {
 
     async fn install_pkg<P: AsRef<Path>>(&self, path: P) -> RS<()> {
         let mpk_name = self.init_mpk(path.as_ref().to_path_buf()).await?;
+        self.store_package(path, &mpk_name)
+    }
+
+    /// Replace the installed app with the one in `app_package` and return
+    /// the replaced instance.
+    ///
+    /// The new instance is swapped into `apps` only once it is fully built,
+    /// so every invocation resolves either the old or the new version.
+    /// Invocations that already hold the old instance keep running on it and
+    /// on its procedure instance pools, which are dropped after the last of
+    /// them finishes.
+    ///
+    /// The replacement accepts any version and runs no migration.
+    async fn swap_pkg(&self, app_package: &AppPackage) -> RS<AppInstImpl> {
+        let mpk_name = app_package.name().clone();
+        let installed = self
+            .apps
+            .get_sync(&mpk_name)
+            .map(|e| e.get().clone())
+            .ok_or_else(|| {
+                mudu_error!(
+                    ErrorCode::EntityNotFound,
+                    format!("app {} is not installed", mpk_name)
+                )
+            })?;
+        let modules = self.wt_runtime.compile_modules(app_package)?;
+        let app_instance = AppInstImpl::build(
+            &self.db_path,
+            app_package,
+            modules,
+            self.rt_opt.component_target(),
+            self.rt_opt.enable_async,
+            self.rt_opt.sever_mode,
+            self.rt_opt.async_runtime(),
+        )
+        .await?;
+        let _ = self.apps.upsert_sync(mpk_name, app_instance);
+        Ok(installed)
+    }
+
+    async fn migrate_pkg(&self, app_package: &AppPackage) -> RS<()> {
+        migratedb(
+            &self.db_path,
+            app_package.name(),
+            &app_package.package_cfg.version,
+            &app_package.migrate_sql,
+            self.rt_opt.enable_async,
+            self.rt_opt.sever_mode,
+            self.rt_opt.async_runtime(),
+        )
+        .await
+    }
+
+    /// Copy the package at `path` into the package directory, unless it
+    /// already lives there.
+    fn store_package<P: AsRef<Path>>(&self, path: P, mpk_name: &str) -> RS<()> {
         let pkg_path = PathBuf::from(self.package_path.clone());
         let parent = path.as_ref().parent().ok_or_else(|| {
             mudu_error!(
@@ -183,6 +239,52 @@ impl RuntimeSimple {
         Ok(())
     }
 
+    /// Upgrade the installed app to the newer version in the package at
+    /// `pkg_path`. The migration runs once the new version serves
+    /// invocations; if it fails before changing the database, the old
+    /// version is put back. A migration that failed partway may have changed
+    /// the schema, so the new version stays installed.
+    pub async fn upgrade(&self, pkg_path: String) -> RS<()> {
+        let app_package = load_package_from_file(&pkg_path)?;
+        let installed = self
+            .apps
+            .get_sync(app_package.name())
+            .map(|e| e.get().cfg().clone())
+            .ok_or_else(|| {
+                mudu_error!(
+                    ErrorCode::EntityNotFound,
+                    format!("app {} is not installed", app_package.name())
+                )
+            })?;
+        app_package.check_upgrade_of(&installed)?;
+        let previous = self.swap_pkg(&app_package).await?;
+        if let Err(e) = self.migrate_pkg(&app_package).await {
+            if !is_migration_pending(
+                &self.db_path,
+                app_package.name(),
+                &app_package.package_cfg.version,
+            ) {
+                let _ = self.apps.upsert_sync(app_package.name().clone(), previous);
+            }
+            return Err(e);
+        }
+        self.store_package(pkg_path, app_package.name())
+    }
+
+    /// Replace the installed app with the package at `pkg_path`, whatever
+    /// its version, without running its migration.
+    pub async fn replace(&self, pkg_path: String) -> RS<()> {
+        let app_package = load_package_from_file(&pkg_path)?;
+        self.swap_pkg(&app_package).await?;
+        self.store_package(pkg_path, app_package.name())
+    }
+
+    /// Run the migration SQL of the package at `pkg_path`, once per version.
+    pub async fn migrate(&self, pkg_path: String) -> RS<()> {
+        let app_package = load_package_from_file(&pkg_path)?;
+        self.migrate_pkg(&app_package).await
+    }
+
     pub fn async_runtime(&self) -> Option<Arc<dyn AsyncIoProvider>> {
         self.rt_opt.async_runtime()
     }
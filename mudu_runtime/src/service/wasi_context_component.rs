@@ -1,3 +1,4 @@
+use crate::service::procedure_limits::ProcedureLimiter;
 use mudu_kernel::server::worker_local::WorkerLocalRef;
 use wasmtime::component::ResourceTable;
 use wasmtime_wasi::{WasiCtx, WasiCtxBuilder, WasiCtxView, WasiView};
@@ -7,6 +8,7 @@ pub struct WasiContextComponent {
     ctx: WasiCtx,
     table: ResourceTable,
     worker_local: Option<WorkerLocalRef>,
+    limiter: ProcedureLimiter,
 }
 
 impl WasiView for WasiContextComponent {
@@ -24,6 +26,7 @@ impl WasiContextComponent {
             ctx,
             table: Default::default(),
             worker_local,
+            limiter: ProcedureLimiter::default(),
         }
     }
 
@@ -36,6 +39,15 @@ impl WasiContextComponent {
     pub fn set_worker_local(&mut self, worker_local: Option<WorkerLocalRef>) {
         self.worker_local = worker_local;
     }
+
+    /// Memory/table growth limiter of the store; see `procedure_limits`.
+    pub fn limiter_mut(&mut self) -> &mut ProcedureLimiter {
+        &mut self.limiter
+    }
+
+    pub fn set_limiter(&mut self, limiter: ProcedureLimiter) {
+        self.limiter = limiter;
+    }
 }
 
 pub fn build_wasi_component_context(worker_local: Option<WorkerLocalRef>) -> WasiContextComponent {
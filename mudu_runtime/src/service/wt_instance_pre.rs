@@ -2,6 +2,7 @@
 
 use crate::service::procedure_instance_pool::{LeasedInstance, ProcedureInstancePool};
 use crate::service::wasi_context_component::WasiContextComponent;
+use mudu::common::app_info::AppLimits;
 use mudu::common::result::RS;
 use std::sync::Arc;
 
@@ -12,12 +13,14 @@ pub struct WTInstancePre {
 }
 
 impl WTInstancePre {
+    /// Wrap `instance_pre`; every instance leased from it enforces `limits`.
     pub fn from_component(
         instance_pre: wasmtime::component::InstancePre<WasiContextComponent>,
+        limits: AppLimits,
     ) -> Self {
         Self {
             inner: Arc::new(instance_pre),
-            pool: Arc::new(ProcedureInstancePool::new()),
+            pool: Arc::new(ProcedureInstancePool::new(limits)),
         }
     }
 
@@ -33,6 +36,11 @@ impl WTInstancePre {
         self.pool.lease(self.inner.as_ref(), func_name).await
     }
 
+    #[cfg(test)]
+    pub fn pool_limits(&self) -> &AppLimits {
+        self.pool.limits()
+    }
+
     #[cfg(test)]
     pub fn pool_idle_len(&self, func_name: &str) -> usize {
         self.pool.idle_len(func_name)
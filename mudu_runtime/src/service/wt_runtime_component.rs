@@ -1,9 +1,11 @@
 use crate::service::app_package::AppPackage;
 use crate::service::package_module::PackageModule;
+use crate::service::procedure_limits::{needs_fuel, spawn_epoch_ticker};
 use crate::service::runtime_opt::{ComponentTarget, RuntimeOpt};
 use crate::service::wasi_context_component;
 use crate::service::wasi_context_component::WasiContextComponent;
 use crate::service::wt_instance_pre::WTInstancePre;
+use mudu::common::app_info::AppLimits;
 use mudu::common::result::RS;
 use mudu::error::ErrorCode;
 use mudu::mudu_error;
@@ -15,15 +17,26 @@ use wasmtime_wasi::p2::add_to_linker_sync;
 
 pub struct WTRuntimeComponent {
     runtime_opt: RuntimeOpt,
+    engine: WTEngine,
+    // Engine for apps that set a fuel limit. Fuel metering is an engine-wide
+    // setting that slows down all guest code, so apps without the limit run
+    // on `engine`, which does not consume fuel.
+    fuel_engine: WTEngine,
+}
+
+struct WTEngine {
     engine: Engine,
     linker: Linker<WasiContextComponent>,
 }
 
-impl WTRuntimeComponent {
-    pub fn build(runtime_opt: &RuntimeOpt) -> RS<Self> {
-        let runtime_opt = runtime_opt.clone();
+impl WTEngine {
+    fn build(runtime_opt: &RuntimeOpt, consume_fuel: bool) -> RS<Self> {
         let mut cfg = Config::new();
         cfg.wasm_component_model(true);
+        // Procedure resource limits (see `procedure_limits`): every store
+        // gets a wall-clock deadline per invocation, and a fuel budget when
+        // the engine consumes fuel.
+        cfg.consume_fuel(consume_fuel).epoch_interruption(true);
         if runtime_opt.enable_async {
             cfg.wasm_component_model_async(true)
                 .wasm_component_model_more_async_builtins(true);
@@ -35,17 +48,13 @@ impl WTRuntimeComponent {
                 e
             )
         })?;
+        spawn_epoch_ticker(&engine)?;
         // Configure linker with host functions
         let linker = Linker::new(&engine);
-        Ok(Self {
-            runtime_opt,
-            engine,
-            linker,
-        })
+        Ok(Self { engine, linker })
     }
 
-    pub fn instantiate(&mut self) -> RS<()> {
-        let component_target = self.runtime_opt.component_target();
+    fn instantiate(&mut self, component_target: ComponentTarget) -> RS<()> {
         wasi_context_component::async_host::mududb::async_api::system::add_to_linker::<_, HasSelf<_>>(
             &mut self.linker,
             |c| c,
@@ -79,9 +88,33 @@ impl WTRuntimeComponent {
         }
         Ok(())
     }
+}
+
+impl WTRuntimeComponent {
+    pub fn build(runtime_opt: &RuntimeOpt) -> RS<Self> {
+        let runtime_opt = runtime_opt.clone();
+        let engine = WTEngine::build(&runtime_opt, false)?;
+        let fuel_engine = WTEngine::build(&runtime_opt, true)?;
+        Ok(Self {
+            runtime_opt,
+            engine,
+            fuel_engine,
+        })
+    }
+
+    pub fn instantiate(&mut self) -> RS<()> {
+        let component_target = self.runtime_opt.component_target();
+        self.engine.instantiate(component_target)?;
+        self.fuel_engine.instantiate(component_target)
+    }
 
     pub fn compile_modules(&self, package: &AppPackage) -> RS<Vec<(String, PackageModule)>> {
-        let modules = instantiate_component_modules(&self.engine, &self.linker, package)?;
+        let engine = if needs_fuel(&package.package_cfg.limits) {
+            &self.fuel_engine
+        } else {
+            &self.engine
+        };
+        let modules = instantiate_component_modules(&engine.engine, &engine.linker, package)?;
         Ok(modules)
     }
 }
@@ -92,6 +125,7 @@ fn instantiate_component(
     name: String,
     byte_code: &[u8],
     desc_vec: &[ProcDesc],
+    limits: &AppLimits,
 ) -> RS<PackageModule> {
     let component = match Component::from_binary(engine, byte_code) {
         Ok(component) => component,
@@ -123,7 +157,7 @@ fn instantiate_component(
     })?;
 
     PackageModule::new(
-        WTInstancePre::from_component(instance_pre),
+        WTInstancePre::from_component(instance_pre, limits.clone()),
         desc_vec.to_owned(),
     )
 }
@@ -143,7 +177,14 @@ pub fn instantiate_component_modules(
                 format!("no such module named {}", mod_name)
             )
         })?;
-        let module = instantiate_component(engine, linker, mod_name.clone(), byte_code, vec_desc)?;
+        let module = instantiate_component(
+            engine,
+            linker,
+            mod_name.clone(),
+            byte_code,
+            vec_desc,
+            &package.package_cfg.limits,
+        )?;
         modules.push((mod_name.clone(), module));
     }
     Ok(modules)
@@ -26,10 +26,12 @@ mod tests {
                 lang: "rust".to_string(),
                 version: "0.1.0".to_string(),
                 use_async: false,
+                limits: Default::default(),
             },
             ddl_sql: "create table t(id int primary key);".to_string(),
             package_desc: desc,
             initdb_sql: String::new(),
+            migrate_sql: String::new(),
             modules,
         }
     }
@@ -1,8 +1,7 @@
 // mudu_sys is a target-selecting facade crate.
 // On native targets the `native` feature (default) re-exports mudu_sys_impl.
-// The empty `ds` feature is an internal-only hook: a closed-source
-// deterministic simulation overlay adds the backend dependency and the
-// corresponding re-export; in this repository enabling `ds` is a no-op.
+// The `ds` feature builds the deterministic simulation provider of
+// mudu_sys_impl (`sim`) and the simulation tests that use it.
 #[cfg(all(not(target_arch = "wasm32"), feature = "native"))]
 pub use mudu_sys_impl::*;
 
@@ -5,4 +5,7 @@ pub enum ProviderType {
     Tokio,
     /// io_uring-based provider.
     IoUring,
+    /// In-memory deterministic simulation provider.
+    #[cfg(feature = "ds")]
+    Sim,
 }
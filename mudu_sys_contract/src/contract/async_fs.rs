@@ -44,6 +44,14 @@ pub trait AsyncFs: Send + Sync {
         ))
     }
 
+    /// Truncate the file at `path` to `len` bytes and persist the new size.
+    async fn truncate(&self, _path: &Path, _len: u64) -> RS<()> {
+        Err(mudu::mudu_error!(
+            mudu::error::ErrorCode::NotImplemented,
+            "truncate is not implemented"
+        ))
+    }
+
     /// Read the entire contents of the file at `path` as a UTF-8 string.
     async fn read_to_string(&self, path: &Path) -> RS<String> {
         let bytes = self.read_all(path).await?;
@@ -142,6 +150,12 @@ mod tests {
         assert_eq!(err.ec(), ErrorCode::NotImplemented);
     }
 
+    #[test]
+    fn truncate_default_returns_not_implemented() {
+        let err = block_on(MockFs.truncate(Path::new("/tmp/x"), 0)).unwrap_err();
+        assert_eq!(err.ec(), ErrorCode::NotImplemented);
+    }
+
     #[test]
     fn read_to_string_default_uses_read_all() {
         let result = block_on(MockFs.read_to_string(Path::new("/tmp/x")));
@@ -5,4 +5,7 @@ pub enum AsyncMode {
     Tokio,
     /// Use io_uring.
     IoUring,
+    /// Use the in-memory deterministic simulation.
+    #[cfg(feature = "ds")]
+    Sim,
 }
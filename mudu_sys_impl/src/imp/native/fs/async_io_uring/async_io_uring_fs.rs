@@ -68,4 +68,29 @@ impl AsyncFs for AsyncIoUringFs {
             )
         })
     }
+
+    async fn truncate(&self, path: &Path, len: u64) -> RS<()> {
+        // Like `remove_dir_all`, a one-off recovery operation: run it
+        // synchronously instead of adding an ftruncate op to the ring.
+        let file = std::fs::OpenOptions::new()
+            .write(true)
+            .open(path)
+            .map_err(|e| {
+                mudu::mudu_error!(
+                    mudu::error::ErrorCode::from(&e),
+                    "open file for truncate error",
+                    e
+                )
+            })?;
+        file.set_len(len).map_err(|e| {
+            mudu::mudu_error!(mudu::error::ErrorCode::from(&e), "truncate file error", e)
+        })?;
+        file.sync_data().map_err(|e| {
+            mudu::mudu_error!(
+                mudu::error::ErrorCode::from(&e),
+                "sync truncated file error",
+                e
+            )
+        })
+    }
 }
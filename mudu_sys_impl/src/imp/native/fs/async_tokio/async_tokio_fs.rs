@@ -45,4 +45,8 @@ impl AsyncFs for AsyncTokioFs {
     async fn remove_dir_all(&self, path: &Path) -> RS<()> {
         async_tokio::remove_dir_all(path).await
     }
+
+    async fn truncate(&self, path: &Path, len: u64) -> RS<()> {
+        async_tokio::truncate(path, len).await
+    }
 }
@@ -56,4 +56,25 @@ mod tests {
         fs.remove_dir_all(&root).await.unwrap();
         assert!(!async_::path_exists(&root).await.unwrap());
     }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn tokio_fs_truncate_shrinks_file() {
+        use crate::contract::async_fs::AsyncFs;
+        use crate::imp::native::fs::async_tokio::async_tokio_fs::AsyncTokioFs;
+
+        let path = temp_path("truncate.dat");
+        if let Some(parent) = path.parent() {
+            async_::create_dir_all(parent).await.unwrap();
+        }
+        let file = async_::TokioFile::open(&path, FileOptions::read_write_create())
+            .await
+            .unwrap();
+        file.write_all_at(0, b"payload").await.unwrap();
+        drop(file);
+
+        let fs = AsyncTokioFs::new();
+        fs.truncate(&path, 3).await.unwrap();
+        assert_eq!(fs.read_all(&path).await.unwrap(), b"pay".to_vec());
+        async_::remove_file_if_exists(&path).await.unwrap();
+    }
 }
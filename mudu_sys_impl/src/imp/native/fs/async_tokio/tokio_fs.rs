@@ -49,6 +49,20 @@ pub(crate) async fn remove_dir_all(path: impl AsRef<Path>) -> RS<()> {
     Ok(())
 }
 
+pub(crate) async fn truncate(path: impl AsRef<Path>, len: u64) -> RS<()> {
+    let file = fs::OpenOptions::new()
+        .write(true)
+        .open(path.as_ref())
+        .await
+        .map_err(|e| io_error_with_message(e, "open tokio file for truncate error"))?;
+    file.set_len(len)
+        .await
+        .map_err(|e| io_error_with_message(e, "truncate tokio file error"))?;
+    file.sync_data()
+        .await
+        .map_err(|e| io_error_with_message(e, "sync truncated tokio file error"))
+}
+
 pub(crate) async fn read_dir(path: impl AsRef<Path>) -> RS<Vec<PathBuf>> {
     let mut paths = Vec::new();
     let mut entries = fs::read_dir(path)
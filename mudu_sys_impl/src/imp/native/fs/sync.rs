@@ -235,6 +235,12 @@ impl FsSync {
         std::fs::copy(from, to).map_err(|e| io_error_with_message(e, "copy file error"))
     }
 
+    /// Rename `from` to `to`, replacing `to` if it exists. Within one
+    /// filesystem the replacement is atomic.
+    pub fn rename(&self, from: &Path, to: &Path) -> RS<()> {
+        std::fs::rename(from, to).map_err(|e| io_error_with_message(e, "rename file error"))
+    }
+
     pub fn metadata(&self, path: &Path) -> RS<SMetadata> {
         std::fs::metadata(path)
             .map(SMetadata::from_inner)
@@ -664,6 +670,16 @@ pub fn copy(from: impl AsRef<Path>, to: impl AsRef<Path>) -> RS<u64> {
     sync_copy(from, to)
 }
 
+pub fn sync_rename(from: impl AsRef<Path>, to: impl AsRef<Path>) -> RS<()> {
+    crate::default_sys_io_context()
+        .fs_sync()
+        .rename(from.as_ref(), to.as_ref())
+}
+
+pub fn rename(from: impl AsRef<Path>, to: impl AsRef<Path>) -> RS<()> {
+    sync_rename(from, to)
+}
+
 pub fn sync_metadata(path: impl AsRef<Path>) -> RS<SMetadata> {
     crate::default_sys_io_context()
         .fs_sync()
@@ -801,6 +817,20 @@ mod tests {
         cleanup(&dir);
     }
 
+    #[test]
+    fn fs_sync_rename_replaces_target() {
+        let fs = FsSync::new();
+        let dir = tmp_dir();
+        let src = dir.join("src.txt");
+        let dst = dir.join("dst.txt");
+        fs.write(&src, b"new").unwrap();
+        fs.write(&dst, b"old").unwrap();
+        fs.rename(&src, &dst).unwrap();
+        assert!(!fs.path_exists(&src));
+        assert_eq!(fs.read_all(&dst).unwrap(), b"new");
+        cleanup(&dir);
+    }
+
     #[test]
     fn fs_sync_open_create_and_options() {
         let fs = FsSync::new();
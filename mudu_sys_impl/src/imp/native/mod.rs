@@ -10,6 +10,8 @@ pub mod os;
 pub mod process;
 pub mod random;
 pub mod runtime;
+#[cfg(feature = "ds")]
+pub mod sim;
 pub mod sync;
 pub mod task;
 pub mod thread;
@@ -35,6 +35,8 @@ impl Runtime {
                 Arc::new(TokioNet::new()),
                 Arc::new(AsyncTokioFs::new()),
             )),
+            #[cfg(feature = "ds")]
+            ProviderType::Sim => crate::imp::native::sim::standalone_provider(),
         }
     }
 
@@ -90,6 +92,13 @@ mod tests {
         assert_eq!(provider.mode(), AsyncMode::Tokio);
     }
 
+    #[cfg(feature = "ds")]
+    #[test]
+    fn create_async_runtime_sim_has_sim_mode() {
+        let provider = create_async_runtime(ProviderType::Sim);
+        assert_eq!(provider.mode(), AsyncMode::Sim);
+    }
+
     #[cfg_attr(miri, ignore)]
     #[test]
     fn runtime_new_mode_matches_io_uring_availability() {
@@ -0,0 +1,189 @@
+use crate::contract::task_async::SysTaskAsync;
+use async_trait::async_trait;
+use mudu::common::result::RS;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+/// Locks a simulation mutex. The simulation runs on one thread, so a
+/// poisoned lock only follows a panic that already failed the run; keep
+/// going with the inner state instead of panicking again.
+pub(crate) fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Virtual time of a simulation.
+///
+/// Time only moves when the executor runs out of ready tasks and jumps to
+/// the earliest pending timer (or when a test calls [`SimClock::advance`]),
+/// so delays cost no wall-clock time and timer order is reproducible.
+#[derive(Clone, Default)]
+pub struct SimClock {
+    inner: Arc<Mutex<ClockState>>,
+}
+
+#[derive(Default)]
+struct ClockState {
+    now: Duration,
+    next_timer_id: u64,
+    timers: BTreeMap<(Duration, u64), Waker>,
+}
+
+impl SimClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Virtual time elapsed since the simulation started.
+    pub fn now(&self) -> Duration {
+        lock(&self.inner).now
+    }
+
+    /// A future that completes once virtual time has advanced by `duration`.
+    pub fn sleep(&self, duration: Duration) -> SimSleep {
+        let deadline = self.now().saturating_add(duration);
+        self.sleep_until(deadline)
+    }
+
+    /// A future that completes once virtual time reaches `deadline`.
+    pub fn sleep_until(&self, deadline: Duration) -> SimSleep {
+        SimSleep {
+            clock: self.clone(),
+            deadline,
+            timer: None,
+        }
+    }
+
+    /// Wakes `waker` once virtual time reaches `deadline`.
+    pub(crate) fn register_timer(&self, deadline: Duration, waker: Waker) -> u64 {
+        let mut state = lock(&self.inner);
+        let id = state.next_timer_id;
+        state.next_timer_id += 1;
+        state.timers.insert((deadline, id), waker);
+        id
+    }
+
+    pub(crate) fn cancel_timer(&self, deadline: Duration, id: u64) {
+        lock(&self.inner).timers.remove(&(deadline, id));
+    }
+
+    /// Deadline of the earliest pending timer.
+    pub fn next_deadline(&self) -> Option<Duration> {
+        lock(&self.inner)
+            .timers
+            .keys()
+            .next()
+            .map(|(deadline, _)| *deadline)
+    }
+
+    /// Jumps to the earliest pending timer and fires every timer due by
+    /// then. Returns `false` when no timer is pending.
+    pub fn advance_to_next_timer(&self) -> bool {
+        match self.next_deadline() {
+            Some(deadline) => {
+                self.advance_to(deadline);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves virtual time forward by `duration`, firing every timer due.
+    pub fn advance(&self, duration: Duration) {
+        let target = self.now().saturating_add(duration);
+        self.advance_to(target);
+    }
+
+    fn advance_to(&self, target: Duration) {
+        let due = {
+            let mut state = lock(&self.inner);
+            if target > state.now {
+                state.now = target;
+            }
+            let pending = state.timers.split_off(&(state.now, u64::MAX));
+            std::mem::replace(&mut state.timers, pending)
+        };
+        // Wake outside the lock: a woken task may register a new timer.
+        for waker in due.into_values() {
+            waker.wake();
+        }
+    }
+}
+
+#[async_trait]
+impl SysTaskAsync for SimClock {
+    async fn sleep(&self, dur: Duration) -> RS<()> {
+        SimClock::sleep(self, dur).await;
+        Ok(())
+    }
+}
+
+/// Future returned by [`SimClock::sleep`].
+pub struct SimSleep {
+    clock: SimClock,
+    deadline: Duration,
+    timer: Option<u64>,
+}
+
+impl Future for SimSleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if let Some(id) = this.timer.take() {
+            this.clock.cancel_timer(this.deadline, id);
+        }
+        if this.clock.now() >= this.deadline {
+            return Poll::Ready(());
+        }
+        this.timer = Some(this.clock.register_timer(this.deadline, cx.waker().clone()));
+        Poll::Pending
+    }
+}
+
+impl Drop for SimSleep {
+    fn drop(&mut self) {
+        if let Some(id) = self.timer.take() {
+            self.clock.cancel_timer(self.deadline, id);
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advance_fires_due_timers_only() {
+        let clock = SimClock::new();
+        let mut early = Box::pin(clock.sleep(Duration::from_millis(5)));
+        let mut late = Box::pin(clock.sleep(Duration::from_millis(50)));
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(early.as_mut().poll(&mut cx).is_pending());
+        assert!(late.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(clock.next_deadline(), Some(Duration::from_millis(5)));
+
+        assert!(clock.advance_to_next_timer());
+        assert_eq!(clock.now(), Duration::from_millis(5));
+        assert!(early.as_mut().poll(&mut cx).is_ready());
+        assert!(late.as_mut().poll(&mut cx).is_pending());
+
+        clock.advance(Duration::from_millis(45));
+        assert!(late.as_mut().poll(&mut cx).is_ready());
+        assert!(!clock.advance_to_next_timer());
+    }
+
+    #[test]
+    fn dropped_sleep_cancels_its_timer() {
+        let clock = SimClock::new();
+        let mut sleep = Box::pin(clock.sleep(Duration::from_secs(1)));
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(sleep.as_mut().poll(&mut cx).is_pending());
+        drop(sleep);
+        assert_eq!(clock.next_deadline(), None);
+    }
+}
@@ -0,0 +1,281 @@
+use crate::imp::native::sim::clock::{lock, SimClock};
+use crate::imp::native::sim::rng::SimRng;
+use mudu::common::result::RS;
+use mudu::error::ErrorCode;
+use mudu::mudu_error;
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+
+type LocalTask = Pin<Box<dyn Future<Output = ()>>>;
+
+/// Single-threaded executor with seeded scheduling.
+///
+/// Every step polls one task picked by the seeded generator among the ready
+/// ones, so interleavings vary with the seed yet replay exactly for a given
+/// seed. When no task is ready the virtual clock jumps to the next timer.
+#[derive(Clone)]
+pub struct SimExecutor {
+    inner: Rc<ExecutorInner>,
+}
+
+struct ExecutorInner {
+    clock: SimClock,
+    rng: RefCell<SimRng>,
+    tasks: RefCell<Vec<Option<LocalTask>>>,
+    ready: Arc<Mutex<BTreeSet<usize>>>,
+    steps: RefCell<u64>,
+}
+
+struct TaskWaker {
+    id: usize,
+    ready: Arc<Mutex<BTreeSet<usize>>>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        lock(&self.ready).insert(self.id);
+    }
+}
+
+impl SimExecutor {
+    pub fn new(clock: SimClock, rng: SimRng) -> Self {
+        Self {
+            inner: Rc::new(ExecutorInner {
+                clock,
+                rng: RefCell::new(rng),
+                tasks: RefCell::new(Vec::new()),
+                ready: Arc::new(Mutex::new(BTreeSet::new())),
+                steps: RefCell::new(0),
+            }),
+        }
+    }
+
+    pub fn clock(&self) -> &SimClock {
+        &self.inner.clock
+    }
+
+    /// Number of task polls performed so far.
+    pub fn steps(&self) -> u64 {
+        *self.inner.steps.borrow()
+    }
+
+    /// Spawns `future` as a task; it first runs on a later scheduling step.
+    pub fn spawn<F>(&self, future: F) -> SimJoinHandle<F::Output>
+    where
+        F: Future + 'static,
+        F::Output: 'static,
+    {
+        let slot = Rc::new(RefCell::new(JoinSlot {
+            output: None,
+            waker: None,
+        }));
+        let task_slot = slot.clone();
+        let task: LocalTask = Box::pin(async move {
+            let output = future.await;
+            let waker = {
+                let mut slot = task_slot.borrow_mut();
+                slot.output = Some(output);
+                slot.waker.take()
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        });
+        let id = {
+            let mut tasks = self.inner.tasks.borrow_mut();
+            tasks.push(Some(task));
+            tasks.len() - 1
+        };
+        lock(&self.inner.ready).insert(id);
+        SimJoinHandle { slot }
+    }
+
+    /// Runs the simulation until `future` completes.
+    ///
+    /// Fails when every task is blocked and no timer is pending: nothing
+    /// could ever make progress again.
+    pub fn block_on<F>(&self, future: F) -> RS<F::Output>
+    where
+        F: Future + 'static,
+        F::Output: 'static,
+    {
+        let handle = self.spawn(future);
+        loop {
+            if let Some(output) = handle.try_take() {
+                return Ok(output);
+            }
+            if self.run_one()? {
+                continue;
+            }
+            if !self.inner.clock.advance_to_next_timer() {
+                return Err(mudu_error!(
+                    ErrorCode::Internal,
+                    "simulation stalled: no runnable task and no pending timer"
+                ));
+            }
+        }
+    }
+
+    /// Polls one ready task picked by the seeded generator. Returns `false`
+    /// when no task is ready.
+    fn run_one(&self) -> RS<bool> {
+        let id = {
+            let mut ready = lock(&self.inner.ready);
+            if ready.is_empty() {
+                return Ok(false);
+            }
+            let index = self.inner.rng.borrow_mut().below(ready.len() as u64) as usize;
+            let id =
+                ready.iter().nth(index).copied().ok_or_else(|| {
+                    mudu_error!(ErrorCode::Internal, "simulation ready set changed")
+                })?;
+            ready.remove(&id);
+            id
+        };
+        // Take the task out of its slot while polling, so the task itself
+        // can spawn more tasks.
+        let task = self
+            .inner
+            .tasks
+            .borrow_mut()
+            .get_mut(id)
+            .and_then(Option::take);
+        let Some(mut task) = task else {
+            // Woken after it completed.
+            return Ok(true);
+        };
+        *self.inner.steps.borrow_mut() += 1;
+        let waker = Waker::from(Arc::new(TaskWaker {
+            id,
+            ready: self.inner.ready.clone(),
+        }));
+        let mut cx = Context::from_waker(&waker);
+        if task.as_mut().poll(&mut cx).is_pending() {
+            if let Some(slot) = self.inner.tasks.borrow_mut().get_mut(id) {
+                *slot = Some(task);
+            }
+        }
+        Ok(true)
+    }
+}
+
+struct JoinSlot<T> {
+    output: Option<T>,
+    waker: Option<Waker>,
+}
+
+/// Completion handle of a task spawned on a [`SimExecutor`].
+pub struct SimJoinHandle<T> {
+    slot: Rc<RefCell<JoinSlot<T>>>,
+}
+
+impl<T> SimJoinHandle<T> {
+    /// The task output, if the task has finished.
+    pub fn try_take(&self) -> Option<T> {
+        self.slot.borrow_mut().output.take()
+    }
+}
+
+impl<T> Future for SimJoinHandle<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = self.slot.borrow_mut();
+        match slot.output.take() {
+            Some(output) => Poll::Ready(output),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn interleaving(seed: u64) -> Vec<u32> {
+        let executor = SimExecutor::new(SimClock::new(), SimRng::new(seed));
+        let trace = Rc::new(RefCell::new(Vec::new()));
+        let spawner = executor.clone();
+        let task_trace = trace.clone();
+        executor
+            .block_on(async move {
+                let mut handles = Vec::new();
+                for task in 0..4u32 {
+                    let trace = task_trace.clone();
+                    handles.push(spawner.spawn(async move {
+                        for _ in 0..4 {
+                            trace.borrow_mut().push(task);
+                            YieldOnce(false).await;
+                        }
+                    }));
+                }
+                for handle in handles {
+                    handle.await;
+                }
+            })
+            .unwrap();
+        trace.take()
+    }
+
+    struct YieldOnce(bool);
+
+    impl Future for YieldOnce {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                return Poll::Ready(());
+            }
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    #[test]
+    fn same_seed_replays_same_interleaving() {
+        assert_eq!(interleaving(11), interleaving(11));
+        assert_eq!(interleaving(11).len(), 16);
+    }
+
+    #[test]
+    fn different_seeds_explore_different_interleavings() {
+        let first = interleaving(0);
+        assert!((1..32).any(|seed| interleaving(seed) != first));
+    }
+
+    #[test]
+    fn block_on_advances_virtual_time_through_sleeps() {
+        let clock = SimClock::new();
+        let executor = SimExecutor::new(clock.clone(), SimRng::new(1));
+        let sleeper = clock.clone();
+        let woke_at = executor
+            .block_on(async move {
+                sleeper.sleep(Duration::from_secs(30)).await;
+                sleeper.now()
+            })
+            .unwrap();
+        assert_eq!(woke_at, Duration::from_secs(30));
+    }
+
+    #[test]
+    fn block_on_reports_a_stalled_simulation() {
+        let executor = SimExecutor::new(SimClock::new(), SimRng::new(1));
+        let err = executor.block_on(std::future::pending::<()>()).unwrap_err();
+        assert_eq!(err.ec(), ErrorCode::Internal);
+    }
+}
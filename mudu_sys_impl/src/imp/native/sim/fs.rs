@@ -0,0 +1,611 @@
+use crate::contract::async_file::AsyncFile;
+use crate::contract::async_fs::AsyncFs;
+use crate::contract::file_options::FileOptions;
+use crate::imp::native::sim::clock::lock;
+use crate::imp::native::sim::rng::SimRng;
+use async_trait::async_trait;
+use mudu::common::result::RS;
+use mudu::error::ErrorCode;
+use mudu::mudu_error;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Storage faults a [`SimFs`] injects.
+#[derive(Clone, Debug, Default)]
+pub struct SimFsFaults {
+    /// Probability that an fsync reports success without persisting
+    /// anything (a lying disk cache).
+    pub lost_fsync_probability: f64,
+    /// Whether a crash may persist a prefix of the first write it drops.
+    pub torn_writes: bool,
+    /// Bytes the disk holds; a write growing past it fails with
+    /// `ErrorCode::StorageFull`.
+    pub capacity: Option<u64>,
+}
+
+/// In-memory file system with crash semantics.
+///
+/// Every file keeps its durable contents apart from the contents the
+/// running process sees. Writes and size changes are journaled in issue
+/// order until an fsync of their file makes them durable. A crash keeps a
+/// seeded prefix of the journal (and, with torn writes, part of the first
+/// dropped write), so unsynced data is lost in an order-preserving way, like
+/// an ordered-mode journaling file system. Namespace changes (create,
+/// remove, mkdir) are durable immediately.
+pub struct SimFs {
+    state: Arc<Mutex<FsState>>,
+}
+
+struct FsState {
+    rng: SimRng,
+    faults: SimFsFaults,
+    /// Bumped by every restart; handles from an earlier epoch are stale.
+    epoch: u64,
+    dirs: BTreeSet<PathBuf>,
+    files: BTreeMap<PathBuf, u64>,
+    inodes: BTreeMap<u64, Inode>,
+    next_ino: u64,
+    journal: Vec<JournalOp>,
+    crash_after: Option<u64>,
+    powered_off: bool,
+}
+
+#[derive(Default)]
+struct Inode {
+    data: Vec<u8>,
+    durable: Vec<u8>,
+}
+
+struct JournalOp {
+    ino: u64,
+    kind: JournalKind,
+}
+
+enum JournalKind {
+    Write { offset: u64, bytes: Vec<u8> },
+    SetLen(u64),
+}
+
+fn apply_write(data: &mut Vec<u8>, offset: u64, bytes: &[u8]) {
+    let offset = offset as usize;
+    let end = offset + bytes.len();
+    if data.len() < end {
+        data.resize(end, 0);
+    }
+    data[offset..end].copy_from_slice(bytes);
+}
+
+fn not_found(path: &Path) -> mudu::error::err::MuduError {
+    mudu_error!(
+        ErrorCode::NotFound,
+        format!("simulated path {} not found", path.display())
+    )
+}
+
+impl FsState {
+    /// Fails once the disk lost power; otherwise counts one mutating
+    /// operation towards an armed crash point.
+    fn begin_mutation(&mut self) -> RS<()> {
+        self.check_powered()?;
+        if let Some(remaining) = self.crash_after {
+            if remaining == 0 {
+                self.crash_after = None;
+                self.powered_off = true;
+                return Err(mudu_error!(ErrorCode::Io, "simulated disk lost power"));
+            }
+            self.crash_after = Some(remaining - 1);
+        }
+        Ok(())
+    }
+
+    fn check_powered(&self) -> RS<()> {
+        if self.powered_off {
+            return Err(mudu_error!(ErrorCode::Io, "simulated disk is powered off"));
+        }
+        Ok(())
+    }
+
+    fn check_handle(&self, epoch: u64) -> RS<()> {
+        self.check_powered()?;
+        if epoch != self.epoch {
+            return Err(mudu_error!(
+                ErrorCode::Io,
+                "file handle was invalidated by a simulated crash"
+            ));
+        }
+        Ok(())
+    }
+
+    fn inode(&self, ino: u64) -> RS<&Inode> {
+        self.inodes
+            .get(&ino)
+            .ok_or_else(|| mudu_error!(ErrorCode::Internal, "simulated inode missing"))
+    }
+
+    fn inode_mut(&mut self, ino: u64) -> RS<&mut Inode> {
+        self.inodes
+            .get_mut(&ino)
+            .ok_or_else(|| mudu_error!(ErrorCode::Internal, "simulated inode missing"))
+    }
+
+    fn used_bytes(&self) -> u64 {
+        self.inodes
+            .values()
+            .map(|inode| inode.data.len() as u64)
+            .sum()
+    }
+
+    fn parent_exists(&self, path: &Path) -> bool {
+        match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => self.dirs.contains(parent),
+            _ => true,
+        }
+    }
+
+    fn write(&mut self, ino: u64, offset: u64, bytes: &[u8]) -> RS<()> {
+        self.begin_mutation()?;
+        if let Some(capacity) = self.faults.capacity {
+            let len = self.inode(ino)?.data.len() as u64;
+            let growth = (offset + bytes.len() as u64).saturating_sub(len);
+            if self.used_bytes() + growth > capacity {
+                return Err(mudu_error!(
+                    ErrorCode::StorageFull,
+                    "simulated disk is full"
+                ));
+            }
+        }
+        apply_write(&mut self.inode_mut(ino)?.data, offset, bytes);
+        self.journal.push(JournalOp {
+            ino,
+            kind: JournalKind::Write {
+                offset,
+                bytes: bytes.to_vec(),
+            },
+        });
+        Ok(())
+    }
+
+    fn set_len(&mut self, ino: u64, len: u64) -> RS<()> {
+        self.inode_mut(ino)?.data.resize(len as usize, 0);
+        self.journal.push(JournalOp {
+            ino,
+            kind: JournalKind::SetLen(len),
+        });
+        Ok(())
+    }
+
+    fn fsync(&mut self, ino: u64) -> RS<()> {
+        self.begin_mutation()?;
+        if self.rng.chance(self.faults.lost_fsync_probability) {
+            return Ok(());
+        }
+        let inode = self.inode_mut(ino)?;
+        inode.durable = inode.data.clone();
+        self.journal.retain(|op| op.ino != ino);
+        Ok(())
+    }
+
+    fn restart(&mut self) {
+        let journal = std::mem::take(&mut self.journal);
+        let cut = self.rng.range_inclusive(0, journal.len() as u64) as usize;
+        let torn_len = match journal.get(cut) {
+            Some(JournalOp {
+                kind: JournalKind::Write { bytes, .. },
+                ..
+            }) if self.faults.torn_writes => self.rng.below(bytes.len() as u64) as usize,
+            _ => 0,
+        };
+        for (index, op) in journal.iter().enumerate().take(cut + 1) {
+            let Some(inode) = self.inodes.get_mut(&op.ino) else {
+                continue;
+            };
+            match &op.kind {
+                JournalKind::Write { offset, bytes } if index < cut => {
+                    apply_write(&mut inode.durable, *offset, bytes)
+                }
+                JournalKind::Write { offset, bytes } if torn_len > 0 => {
+                    apply_write(&mut inode.durable, *offset, &bytes[..torn_len])
+                }
+                JournalKind::Write { .. } => {}
+                JournalKind::SetLen(len) if index < cut => inode.durable.resize(*len as usize, 0),
+                JournalKind::SetLen(_) => {}
+            }
+        }
+        let linked: BTreeSet<u64> = self.files.values().copied().collect();
+        self.inodes.retain(|ino, _| linked.contains(ino));
+        for inode in self.inodes.values_mut() {
+            inode.data = inode.durable.clone();
+        }
+        self.epoch += 1;
+        self.crash_after = None;
+        self.powered_off = false;
+    }
+}
+
+impl SimFs {
+    pub fn new(rng: SimRng, faults: SimFsFaults) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(FsState {
+                rng,
+                faults,
+                epoch: 0,
+                dirs: BTreeSet::new(),
+                files: BTreeMap::new(),
+                inodes: BTreeMap::new(),
+                next_ino: 0,
+                journal: Vec::new(),
+                crash_after: None,
+                powered_off: false,
+            })),
+        }
+    }
+
+    /// Replaces the injected faults, e.g. to fill the disk mid-run.
+    pub fn set_faults(&self, faults: SimFsFaults) {
+        lock(&self.state).faults = faults;
+    }
+
+    /// Cuts power after `ops` more mutating operations (writes, fsyncs,
+    /// truncations, creations and removals). The operation that hits the
+    /// crash point and every later one fail until [`SimFs::restart`].
+    pub fn crash_after(&self, ops: u64) {
+        lock(&self.state).crash_after = Some(ops);
+    }
+
+    /// Cuts power now.
+    pub fn crash(&self) {
+        lock(&self.state).powered_off = true;
+    }
+
+    pub fn is_crashed(&self) -> bool {
+        lock(&self.state).powered_off
+    }
+
+    /// Brings the disk back after a crash with only what survived it: the
+    /// durable contents plus a seeded prefix of the unsynced writes.
+    /// Handles opened before the restart are stale afterwards. Restarting a
+    /// running disk models a crash at this instant.
+    pub fn restart(&self) {
+        lock(&self.state).restart();
+    }
+
+    /// Unsynced operations a crash would currently put at risk.
+    pub fn unsynced_ops(&self) -> usize {
+        lock(&self.state).journal.len()
+    }
+}
+
+#[async_trait]
+impl AsyncFs for SimFs {
+    async fn open(&self, path: &Path, options: FileOptions) -> RS<Arc<dyn AsyncFile>> {
+        let mut state = lock(&self.state);
+        state.check_powered()?;
+        if state.dirs.contains(path) {
+            return Err(mudu_error!(
+                ErrorCode::InvalidInput,
+                format!("simulated path {} is a directory", path.display())
+            ));
+        }
+        let ino = match state.files.get(path).copied() {
+            Some(_) if options.create_new => {
+                return Err(mudu_error!(
+                    ErrorCode::AlreadyExists,
+                    format!("simulated file {} already exists", path.display())
+                ));
+            }
+            Some(ino) => {
+                if options.truncate && options.write {
+                    state.begin_mutation()?;
+                    state.set_len(ino, 0)?;
+                }
+                ino
+            }
+            None if options.create || options.create_new => {
+                if !state.parent_exists(path) {
+                    return Err(not_found(path));
+                }
+                state.begin_mutation()?;
+                let ino = state.next_ino;
+                state.next_ino += 1;
+                state.inodes.insert(ino, Inode::default());
+                state.files.insert(path.to_path_buf(), ino);
+                ino
+            }
+            None => return Err(not_found(path)),
+        };
+        Ok(Arc::new(SimFile {
+            state: self.state.clone(),
+            ino,
+            epoch: state.epoch,
+            writable: options.write || options.append,
+            append: options.append,
+        }))
+    }
+
+    async fn create_dir_all(&self, path: &Path) -> RS<()> {
+        let mut state = lock(&self.state);
+        state.check_powered()?;
+        if state.files.contains_key(path) {
+            return Err(mudu_error!(
+                ErrorCode::AlreadyExists,
+                format!("simulated path {} is a file", path.display())
+            ));
+        }
+        for ancestor in path.ancestors() {
+            if !ancestor.as_os_str().is_empty() {
+                state.dirs.insert(ancestor.to_path_buf());
+            }
+        }
+        Ok(())
+    }
+
+    async fn metadata_len(&self, path: &Path) -> RS<u64> {
+        let state = lock(&self.state);
+        state.check_powered()?;
+        match state.files.get(path) {
+            Some(ino) => Ok(state.inode(*ino)?.data.len() as u64),
+            None if state.dirs.contains(path) => Ok(0),
+            None => Err(not_found(path)),
+        }
+    }
+
+    async fn path_exists(&self, path: &Path) -> RS<bool> {
+        let state = lock(&self.state);
+        state.check_powered()?;
+        Ok(state.files.contains_key(path) || state.dirs.contains(path))
+    }
+
+    async fn remove_file_if_exists(&self, path: &Path) -> RS<()> {
+        let mut state = lock(&self.state);
+        if !state.files.contains_key(path) {
+            return state.check_powered();
+        }
+        state.begin_mutation()?;
+        state.files.remove(path);
+        Ok(())
+    }
+
+    async fn read_dir(&self, path: &Path) -> RS<Vec<PathBuf>> {
+        let state = lock(&self.state);
+        state.check_powered()?;
+        if !state.dirs.contains(path) {
+            return Err(not_found(path));
+        }
+        let dirs = state.dirs.iter();
+        let files = state.files.keys();
+        Ok(dirs
+            .chain(files)
+            .filter(|entry| entry.parent() == Some(path))
+            .cloned()
+            .collect())
+    }
+
+    async fn remove_dir_all(&self, path: &Path) -> RS<()> {
+        let mut state = lock(&self.state);
+        if !state.dirs.contains(path) {
+            state.check_powered()?;
+            return Err(not_found(path));
+        }
+        state.begin_mutation()?;
+        state.dirs.retain(|dir| !dir.starts_with(path));
+        state.files.retain(|file, _| !file.starts_with(path));
+        Ok(())
+    }
+
+    async fn write_all(&self, path: &Path, data: &[u8]) -> RS<()> {
+        let mut options = FileOptions::read_write_create();
+        options.truncate = true;
+        let file = self.open(path, options).await?;
+        file.write_all_at(0, data).await
+    }
+
+    async fn truncate(&self, path: &Path, len: u64) -> RS<()> {
+        let mut state = lock(&self.state);
+        state.check_powered()?;
+        let ino = *state.files.get(path).ok_or_else(|| not_found(path))?;
+        state.begin_mutation()?;
+        state.set_len(ino, len)?;
+        state.fsync(ino)
+    }
+}
+
+/// Handle to a [`SimFs`] file.
+pub struct SimFile {
+    state: Arc<Mutex<FsState>>,
+    ino: u64,
+    epoch: u64,
+    writable: bool,
+    append: bool,
+}
+
+#[async_trait]
+impl AsyncFile for SimFile {
+    async fn read_exact_at(&self, offset: u64, len: usize) -> RS<Vec<u8>> {
+        let state = lock(&self.state);
+        state.check_handle(self.epoch)?;
+        let data = &state.inode(self.ino)?.data;
+        let start = offset as usize;
+        match start.checked_add(len) {
+            Some(end) if end <= data.len() => Ok(data[start..end].to_vec()),
+            _ => Err(mudu_error!(
+                ErrorCode::UnexpectedEof,
+                "read past the end of a simulated file"
+            )),
+        }
+    }
+
+    async fn write_all_at(&self, offset: u64, payload: &[u8]) -> RS<()> {
+        let mut state = lock(&self.state);
+        state.check_handle(self.epoch)?;
+        if !self.writable {
+            return Err(mudu_error!(
+                ErrorCode::PermissionDenied,
+                "simulated file is not open for writing"
+            ));
+        }
+        let offset = if self.append {
+            state.inode(self.ino)?.data.len() as u64
+        } else {
+            offset
+        };
+        state.write(self.ino, offset, payload)
+    }
+
+    async fn fsync(&self) -> RS<()> {
+        let mut state = lock(&self.state);
+        state.check_handle(self.epoch)?;
+        state.fsync(self.ino)
+    }
+
+    async fn file_len(&self) -> RS<u64> {
+        let state = lock(&self.state);
+        state.check_handle(self.epoch)?;
+        Ok(state.inode(self.ino)?.data.len() as u64)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+
+    fn open_rw(fs: &SimFs, path: &str) -> Arc<dyn AsyncFile> {
+        block_on(fs.open(Path::new(path), FileOptions::read_write_create())).unwrap()
+    }
+
+    fn sim_fs(seed: u64, faults: SimFsFaults) -> SimFs {
+        let fs = SimFs::new(SimRng::new(seed), faults);
+        block_on(fs.create_dir_all(Path::new("/d"))).unwrap();
+        fs
+    }
+
+    #[test]
+    fn fsynced_data_survives_a_crash() {
+        let fs = sim_fs(1, SimFsFaults::default());
+        let file = open_rw(&fs, "/d/f");
+        block_on(file.write_all_at(0, b"durable")).unwrap();
+        block_on(file.fsync()).unwrap();
+        fs.crash();
+        assert_eq!(block_on(file.file_len()).unwrap_err().ec(), ErrorCode::Io);
+        fs.restart();
+        assert_eq!(
+            block_on(fs.read_all(Path::new("/d/f"))).unwrap(),
+            b"durable"
+        );
+        // Handles from before the crash are stale.
+        assert!(block_on(file.file_len()).is_err());
+    }
+
+    #[test]
+    fn unsynced_writes_survive_as_an_ordered_prefix() {
+        for seed in 0..64 {
+            let fs = sim_fs(seed, SimFsFaults::default());
+            let file = open_rw(&fs, "/d/f");
+            for (index, byte) in b"abcdef".iter().enumerate() {
+                block_on(file.write_all_at(index as u64, &[*byte])).unwrap();
+            }
+            assert_eq!(fs.unsynced_ops(), 6);
+            fs.restart();
+            let survived = block_on(fs.read_all(Path::new("/d/f"))).unwrap();
+            assert!(b"abcdef".starts_with(&survived), "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn torn_writes_keep_part_of_the_first_dropped_write() {
+        let faults = SimFsFaults {
+            torn_writes: true,
+            ..SimFsFaults::default()
+        };
+        let torn = (0..64).any(|seed| {
+            let fs = sim_fs(seed, faults.clone());
+            let file = open_rw(&fs, "/d/f");
+            block_on(file.write_all_at(0, b"0123456789")).unwrap();
+            fs.restart();
+            let len = block_on(fs.metadata_len(Path::new("/d/f"))).unwrap();
+            len > 0 && len < 10
+        });
+        assert!(torn);
+    }
+
+    #[test]
+    fn lost_fsync_reports_success_but_persists_nothing() {
+        let faults = SimFsFaults {
+            lost_fsync_probability: 1.0,
+            ..SimFsFaults::default()
+        };
+        let fs = sim_fs(5, faults);
+        let file = open_rw(&fs, "/d/f");
+        block_on(file.write_all_at(0, b"lost")).unwrap();
+        block_on(file.fsync()).unwrap();
+        assert_eq!(fs.unsynced_ops(), 1);
+    }
+
+    #[test]
+    fn capacity_limit_fails_growing_writes() {
+        let faults = SimFsFaults {
+            capacity: Some(8),
+            ..SimFsFaults::default()
+        };
+        let fs = sim_fs(1, faults);
+        let file = open_rw(&fs, "/d/f");
+        block_on(file.write_all_at(0, b"12345678")).unwrap();
+        // Overwrites in place need no space.
+        block_on(file.write_all_at(0, b"abcd")).unwrap();
+        let err = block_on(file.write_all_at(8, b"x")).unwrap_err();
+        assert_eq!(err.ec(), ErrorCode::StorageFull);
+    }
+
+    #[test]
+    fn crash_point_fails_the_operation_that_reaches_it() {
+        let fs = sim_fs(1, SimFsFaults::default());
+        let file = open_rw(&fs, "/d/f");
+        fs.crash_after(1);
+        block_on(file.write_all_at(0, b"a")).unwrap();
+        assert!(block_on(file.write_all_at(1, b"b")).is_err());
+        assert!(fs.is_crashed());
+        fs.restart();
+        assert!(!fs.is_crashed());
+        let len = block_on(fs.metadata_len(Path::new("/d/f"))).unwrap();
+        assert!(len <= 1);
+    }
+
+    #[test]
+    fn namespace_operations_follow_posix_rules() {
+        let fs = sim_fs(1, SimFsFaults::default());
+        let missing_parent =
+            block_on(fs.open(Path::new("/nope/f"), FileOptions::read_write_create()));
+        assert_eq!(missing_parent.err().unwrap().ec(), ErrorCode::NotFound);
+        let missing = block_on(fs.open(Path::new("/d/f"), FileOptions::read_only()));
+        assert_eq!(missing.err().unwrap().ec(), ErrorCode::NotFound);
+
+        open_rw(&fs, "/d/b");
+        open_rw(&fs, "/d/a");
+        block_on(fs.create_dir_all(Path::new("/d/sub/x"))).unwrap();
+        assert_eq!(
+            block_on(fs.read_dir(Path::new("/d"))).unwrap(),
+            vec![
+                PathBuf::from("/d/sub"),
+                PathBuf::from("/d/a"),
+                PathBuf::from("/d/b")
+            ]
+        );
+        block_on(fs.remove_file_if_exists(Path::new("/d/a"))).unwrap();
+        assert!(!block_on(fs.path_exists(Path::new("/d/a"))).unwrap());
+        block_on(fs.remove_dir_all(Path::new("/d"))).unwrap();
+        assert!(!block_on(fs.path_exists(Path::new("/d/b"))).unwrap());
+    }
+
+    #[test]
+    fn truncate_persists_the_new_size() {
+        let fs = sim_fs(1, SimFsFaults::default());
+        let file = open_rw(&fs, "/d/f");
+        block_on(file.write_all_at(0, b"keep-drop")).unwrap();
+        block_on(file.fsync()).unwrap();
+        block_on(fs.truncate(Path::new("/d/f"), 4)).unwrap();
+        fs.restart();
+        assert_eq!(block_on(fs.read_all(Path::new("/d/f"))).unwrap(), b"keep");
+    }
+}
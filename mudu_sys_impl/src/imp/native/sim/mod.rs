@@ -0,0 +1,217 @@
+//! Deterministic simulation of time, scheduling, storage and network.
+//!
+//! A [`Simulation`] is driven by one seed: its executor picks which ready
+//! task runs next, its file systems decide which unsynced writes survive a
+//! crash, and its network decides how long each segment is in flight. A
+//! failing seed therefore replays the same run, bug included.
+pub mod clock;
+pub mod executor;
+pub mod fs;
+pub mod net;
+pub mod rng;
+
+use crate::contract::async_io_provider::AsyncIoProvider;
+use crate::contract::async_mode::AsyncMode;
+use crate::contract::io_provider_base::IoProviderBase;
+use crate::imp::native::sim::clock::SimClock;
+use crate::imp::native::sim::executor::{SimExecutor, SimJoinHandle};
+use crate::imp::native::sim::fs::{SimFs, SimFsFaults};
+use crate::imp::native::sim::net::SimNetwork;
+use crate::imp::native::sim::rng::SimRng;
+use mudu::common::result::RS;
+use std::cell::Cell;
+use std::future::Future;
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::Arc;
+
+/// Environment variable pinning the seed simulation tests run with.
+pub const SIM_SEED_ENV: &str = "MUDU_DS_SEED";
+/// Environment variable setting how many seeds simulation tests sweep.
+pub const SIM_SEED_COUNT_ENV: &str = "MUDU_DS_SEEDS";
+
+const DEFAULT_SEED_COUNT: u64 = 8;
+
+const EXECUTOR_STREAM: u64 = 1;
+const NETWORK_STREAM: u64 = 2;
+const FIRST_FS_STREAM: u64 = 16;
+
+/// One seeded simulation run.
+pub struct Simulation {
+    seed: u64,
+    rng: SimRng,
+    clock: SimClock,
+    executor: SimExecutor,
+    network: SimNetwork,
+    next_fs_stream: Cell<u64>,
+}
+
+impl Simulation {
+    pub fn new(seed: u64) -> Self {
+        let rng = SimRng::new(seed);
+        let clock = SimClock::new();
+        Self {
+            seed,
+            executor: SimExecutor::new(clock.clone(), rng.fork(EXECUTOR_STREAM)),
+            network: SimNetwork::new(clock.clone(), rng.fork(NETWORK_STREAM)),
+            rng,
+            clock,
+            next_fs_stream: Cell::new(FIRST_FS_STREAM),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn clock(&self) -> &SimClock {
+        &self.clock
+    }
+
+    pub fn executor(&self) -> &SimExecutor {
+        &self.executor
+    }
+
+    pub fn network(&self) -> &SimNetwork {
+        &self.network
+    }
+
+    /// A new disk with its own fault stream.
+    pub fn new_fs(&self, faults: SimFsFaults) -> Arc<SimFs> {
+        let stream = self.next_fs_stream.get();
+        self.next_fs_stream.set(stream + 1);
+        Arc::new(SimFs::new(self.rng.fork(stream), faults))
+    }
+
+    /// IO provider of a node at `ip` storing its files on `fs`.
+    pub fn node(&self, ip: IpAddr, fs: Arc<SimFs>) -> Arc<dyn AsyncIoProvider> {
+        Arc::new(IoProviderBase::new_with(
+            AsyncMode::Sim,
+            Arc::new(self.network.host(ip)),
+            fs,
+        ))
+    }
+
+    pub fn spawn<F>(&self, future: F) -> SimJoinHandle<F::Output>
+    where
+        F: Future + 'static,
+        F::Output: 'static,
+    {
+        self.executor.spawn(future)
+    }
+
+    /// Runs the simulation until `future` completes.
+    pub fn block_on<F>(&self, future: F) -> RS<F::Output>
+    where
+        F: Future + 'static,
+        F::Output: 'static,
+    {
+        self.executor.block_on(future)
+    }
+}
+
+/// Seeds a simulation test should run.
+///
+/// `MUDU_DS_SEED` (decimal or `0x` hex) replays a single seed. Otherwise a
+/// small sweep runs; `MUDU_DS_SEEDS` widens it for long soak runs.
+pub fn sim_seeds() -> Vec<u64> {
+    if let Some(value) = crate::env_var::var(SIM_SEED_ENV) {
+        match parse_seed(&value) {
+            Some(seed) => return vec![seed],
+            None => tracing::warn!("ignoring unparsable {SIM_SEED_ENV}={value}"),
+        }
+    }
+    let count = match crate::env_var::var(SIM_SEED_COUNT_ENV) {
+        Some(value) => value.trim().parse().unwrap_or_else(|_| {
+            tracing::warn!("ignoring unparsable {SIM_SEED_COUNT_ENV}={value}");
+            DEFAULT_SEED_COUNT
+        }),
+        None => DEFAULT_SEED_COUNT,
+    };
+    (0..count).collect()
+}
+
+fn parse_seed(value: &str) -> Option<u64> {
+    let value = value.trim();
+    match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => value.parse().ok(),
+    }
+}
+
+/// Provider of a fault-free simulated node at `127.0.0.1`.
+///
+/// Its file system works under any executor; its network only makes
+/// progress under [`Simulation::block_on`], which drives the virtual clock.
+pub fn standalone_provider() -> Arc<dyn AsyncIoProvider> {
+    let seed = sim_seeds().first().copied().unwrap_or_default();
+    let sim = Simulation::new(seed);
+    let fs = sim.new_fs(SimFsFaults::default());
+    sim.node(IpAddr::V4(Ipv4Addr::LOCALHOST), fs)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::contract::async_fs::AsyncFs;
+    use crate::contract::file_options::FileOptions;
+    use std::path::Path;
+
+    #[test]
+    fn parse_seed_accepts_decimal_and_hex() {
+        assert_eq!(parse_seed("42"), Some(42));
+        assert_eq!(parse_seed(" 0x2a "), Some(42));
+        assert_eq!(parse_seed("seed"), None);
+    }
+
+    #[test]
+    fn node_provider_uses_the_simulated_fs() {
+        let sim = Simulation::new(1);
+        let fs = sim.new_fs(SimFsFaults::default());
+        let provider = sim.node(IpAddr::V4(Ipv4Addr::LOCALHOST), fs.clone());
+        assert_eq!(provider.mode(), AsyncMode::Sim);
+        let run = async move {
+            provider
+                .fs()
+                .create_dir_all(Path::new("/data"))
+                .await
+                .unwrap();
+            let file = provider
+                .fs()
+                .open(Path::new("/data/f"), FileOptions::read_write_create())
+                .await
+                .unwrap();
+            file.write_all_at(0, b"x").await.unwrap();
+        };
+        sim.block_on(run).unwrap();
+        assert_eq!(fs.unsynced_ops(), 1);
+    }
+
+    #[test]
+    fn same_seed_gives_same_fs_faults() {
+        let survivors = |seed| {
+            let sim = Simulation::new(seed);
+            let fs = sim.new_fs(SimFsFaults::default());
+            let run = {
+                let fs = fs.clone();
+                async move {
+                    fs.create_dir_all(Path::new("/d")).await.unwrap();
+                    let file = fs
+                        .open(Path::new("/d/f"), FileOptions::read_write_create())
+                        .await
+                        .unwrap();
+                    for index in 0..32u8 {
+                        file.write_all_at(index as u64, &[index]).await.unwrap();
+                    }
+                }
+            };
+            sim.block_on(run).unwrap();
+            fs.restart();
+            futures::executor::block_on(fs.read_all(Path::new("/d/f"))).unwrap()
+        };
+        assert_eq!(survivors(5), survivors(5));
+    }
+}
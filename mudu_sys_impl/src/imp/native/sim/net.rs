@@ -0,0 +1,516 @@
+use crate::contract::async_listener::AsyncListener;
+use crate::contract::async_net::AsyncNet;
+use crate::contract::async_stream::AsyncStream;
+use crate::imp::native::sim::clock::{lock, SimClock};
+use crate::imp::native::sim::rng::SimRng;
+use async_trait::async_trait;
+use mudu::common::result::RS;
+use mudu::error::ErrorCode;
+use mudu::mudu_error;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::future::poll_fn;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex, Weak};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+const FIRST_EPHEMERAL_PORT: u16 = 49152;
+
+/// Simulated network shared by every host of a simulation.
+///
+/// Segments are delayed by a seeded amount within the configured range but
+/// stay in order per direction, like TCP. Partitioning two hosts refuses new
+/// connections between them and resets the established ones.
+#[derive(Clone)]
+pub struct SimNetwork {
+    clock: SimClock,
+    state: Arc<Mutex<NetState>>,
+}
+
+struct NetState {
+    rng: SimRng,
+    min_delay: Duration,
+    max_delay: Duration,
+    next_port: u16,
+    listeners: BTreeMap<SocketAddr, Arc<Mutex<AcceptQueue>>>,
+    partitions: BTreeSet<(IpAddr, IpAddr)>,
+    connections: Vec<Connection>,
+}
+
+struct Connection {
+    hosts: (IpAddr, IpAddr),
+    pipes: [Weak<Mutex<Pipe>>; 2],
+}
+
+#[derive(Default)]
+struct AcceptQueue {
+    pending: VecDeque<(SimStream, SocketAddr)>,
+    wakers: Vec<Waker>,
+}
+
+/// One direction of a connection.
+#[derive(Default)]
+struct Pipe {
+    segments: VecDeque<(Duration, Vec<u8>)>,
+    closed: bool,
+    reset: bool,
+    reader: Option<Waker>,
+    timer: Option<(Duration, u64)>,
+}
+
+impl Pipe {
+    fn wake_reader(&mut self) {
+        if let Some(waker) = self.reader.take() {
+            waker.wake();
+        }
+    }
+}
+
+fn host_pair(a: IpAddr, b: IpAddr) -> (IpAddr, IpAddr) {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+impl SimNetwork {
+    pub fn new(clock: SimClock, rng: SimRng) -> Self {
+        Self {
+            clock,
+            state: Arc::new(Mutex::new(NetState {
+                rng,
+                min_delay: Duration::from_micros(50),
+                max_delay: Duration::from_millis(2),
+                next_port: FIRST_EPHEMERAL_PORT,
+                listeners: BTreeMap::new(),
+                partitions: BTreeSet::new(),
+                connections: Vec::new(),
+            })),
+        }
+    }
+
+    /// The network as seen from host `ip`.
+    pub fn host(&self, ip: IpAddr) -> SimNet {
+        SimNet {
+            network: self.clone(),
+            ip,
+        }
+    }
+
+    /// Sets the range segment delivery delays are drawn from.
+    pub fn set_delay(&self, min: Duration, max: Duration) {
+        let mut state = lock(&self.state);
+        state.min_delay = min;
+        state.max_delay = max.max(min);
+    }
+
+    /// Cuts `a` off from `b`: new connections fail with
+    /// `ErrorCode::NetworkUnreachable`, established ones are reset.
+    pub fn partition(&self, a: IpAddr, b: IpAddr) {
+        let pair = host_pair(a, b);
+        let mut state = lock(&self.state);
+        state.partitions.insert(pair);
+        state.connections.retain(|connection| {
+            if connection.hosts != pair {
+                return connection.pipes.iter().any(|pipe| pipe.strong_count() > 0);
+            }
+            for pipe in connection.pipes.iter().filter_map(Weak::upgrade) {
+                let mut pipe = lock(&pipe);
+                pipe.reset = true;
+                pipe.wake_reader();
+            }
+            false
+        });
+    }
+
+    pub fn heal(&self, a: IpAddr, b: IpAddr) {
+        lock(&self.state).partitions.remove(&host_pair(a, b));
+    }
+
+    pub fn heal_all(&self) {
+        lock(&self.state).partitions.clear();
+    }
+
+    fn delay(&self) -> Duration {
+        let mut state = lock(&self.state);
+        let (min, max) = (state.min_delay, state.max_delay);
+        let spread = (max - min).as_nanos() as u64;
+        min + Duration::from_nanos(state.rng.range_inclusive(0, spread))
+    }
+}
+
+/// [`AsyncNet`] of one simulated host.
+pub struct SimNet {
+    network: SimNetwork,
+    ip: IpAddr,
+}
+
+impl SimNet {
+    pub fn ip(&self) -> IpAddr {
+        self.ip
+    }
+}
+
+#[async_trait]
+impl AsyncNet for SimNet {
+    async fn bind_tcp(&self, addr: SocketAddr) -> RS<Arc<dyn AsyncListener>> {
+        let mut state = lock(&self.network.state);
+        let ip = if addr.ip().is_unspecified() {
+            self.ip
+        } else {
+            addr.ip()
+        };
+        let port = match addr.port() {
+            0 => {
+                let port = state.next_port;
+                state.next_port = state
+                    .next_port
+                    .checked_add(1)
+                    .unwrap_or(FIRST_EPHEMERAL_PORT);
+                port
+            }
+            port => port,
+        };
+        let local = SocketAddr::new(ip, port);
+        if state.listeners.contains_key(&local) {
+            return Err(mudu_error!(
+                ErrorCode::AddrInUse,
+                format!("simulated address {local} is in use")
+            ));
+        }
+        let queue = Arc::new(Mutex::new(AcceptQueue::default()));
+        state.listeners.insert(local, queue.clone());
+        Ok(Arc::new(SimListener {
+            inner: Arc::new(ListenerInner {
+                network: self.network.clone(),
+                addr: local,
+                queue,
+            }),
+        }))
+    }
+
+    async fn connect_tcp(&self, addr: SocketAddr) -> RS<Box<dyn AsyncStream>> {
+        // The handshake costs one delivery delay.
+        self.network.clock.sleep(self.network.delay()).await;
+        let mut state = lock(&self.network.state);
+        if state.partitions.contains(&host_pair(self.ip, addr.ip())) {
+            return Err(mudu_error!(
+                ErrorCode::NetworkUnreachable,
+                format!(
+                    "simulated host {} is partitioned from {}",
+                    self.ip,
+                    addr.ip()
+                )
+            ));
+        }
+        let queue = state.listeners.get(&addr).cloned().ok_or_else(|| {
+            mudu_error!(
+                ErrorCode::ConnectionRefused,
+                format!("no simulated listener at {addr}")
+            )
+        })?;
+        let local = SocketAddr::new(self.ip, state.next_port);
+        state.next_port = state
+            .next_port
+            .checked_add(1)
+            .unwrap_or(FIRST_EPHEMERAL_PORT);
+        let upstream = Arc::new(Mutex::new(Pipe::default()));
+        let downstream = Arc::new(Mutex::new(Pipe::default()));
+        state.connections.push(Connection {
+            hosts: host_pair(self.ip, addr.ip()),
+            pipes: [Arc::downgrade(&upstream), Arc::downgrade(&downstream)],
+        });
+        drop(state);
+        let client = SimStream {
+            network: self.network.clone(),
+            tx: upstream.clone(),
+            rx: downstream.clone(),
+        };
+        let server = SimStream {
+            network: self.network.clone(),
+            tx: downstream,
+            rx: upstream,
+        };
+        let wakers = {
+            let mut queue = lock(&queue);
+            queue.pending.push_back((server, local));
+            std::mem::take(&mut queue.wakers)
+        };
+        for waker in wakers {
+            waker.wake();
+        }
+        Ok(Box::new(client))
+    }
+}
+
+struct ListenerInner {
+    network: SimNetwork,
+    addr: SocketAddr,
+    queue: Arc<Mutex<AcceptQueue>>,
+}
+
+impl Drop for ListenerInner {
+    fn drop(&mut self) {
+        lock(&self.network.state).listeners.remove(&self.addr);
+    }
+}
+
+/// Listener bound on a [`SimNet`]; the address is released once the
+/// listener and all its clones are dropped.
+pub struct SimListener {
+    inner: Arc<ListenerInner>,
+}
+
+#[async_trait]
+impl AsyncListener for SimListener {
+    fn local_addr(&self) -> RS<SocketAddr> {
+        Ok(self.inner.addr)
+    }
+
+    async fn accept(&self) -> RS<(Box<dyn AsyncStream>, SocketAddr)> {
+        poll_fn(|cx| {
+            let mut queue = lock(&self.inner.queue);
+            match queue.pending.pop_front() {
+                Some((stream, peer)) => {
+                    Poll::Ready(Ok((Box::new(stream) as Box<dyn AsyncStream>, peer)))
+                }
+                None => {
+                    queue.wakers.push(cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        })
+        .await
+    }
+
+    fn as_raw_fd(&self) -> Option<std::os::fd::RawFd> {
+        None
+    }
+
+    fn try_clone_listener(&self) -> RS<Arc<dyn AsyncListener>> {
+        Ok(Arc::new(SimListener {
+            inner: self.inner.clone(),
+        }))
+    }
+}
+
+/// One end of a simulated TCP connection.
+pub struct SimStream {
+    network: SimNetwork,
+    tx: Arc<Mutex<Pipe>>,
+    rx: Arc<Mutex<Pipe>>,
+}
+
+fn connection_reset() -> mudu::error::err::MuduError {
+    mudu_error!(ErrorCode::ConnectionReset, "simulated connection was reset")
+}
+
+impl SimStream {
+    fn poll_read(&self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<RS<usize>> {
+        let clock = &self.network.clock;
+        let mut guard = lock(&self.rx);
+        let pipe = &mut *guard;
+        if let Some((deadline, id)) = pipe.timer.take() {
+            clock.cancel_timer(deadline, id);
+        }
+        if pipe.reset {
+            return Poll::Ready(Err(connection_reset()));
+        }
+        let now = clock.now();
+        let Some((deliver_at, segment)) = pipe.segments.front_mut() else {
+            if pipe.closed {
+                return Poll::Ready(Ok(0));
+            }
+            pipe.reader = Some(cx.waker().clone());
+            return Poll::Pending;
+        };
+        if *deliver_at > now {
+            let deadline = *deliver_at;
+            pipe.timer = Some((deadline, clock.register_timer(deadline, cx.waker().clone())));
+            return Poll::Pending;
+        }
+        let len = buf.len().min(segment.len());
+        buf[..len].copy_from_slice(&segment[..len]);
+        segment.drain(..len);
+        if segment.is_empty() {
+            pipe.segments.pop_front();
+        }
+        Poll::Ready(Ok(len))
+    }
+}
+
+#[async_trait]
+impl AsyncStream for SimStream {
+    async fn read(&mut self, buf: &mut [u8]) -> RS<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        poll_fn(|cx| self.poll_read(cx, buf)).await
+    }
+
+    async fn write_all(&mut self, buf: &[u8]) -> RS<()> {
+        let delay = self.network.delay();
+        let mut pipe = lock(&self.tx);
+        if pipe.reset {
+            return Err(connection_reset());
+        }
+        if pipe.closed {
+            return Err(mudu_error!(
+                ErrorCode::BrokenPipe,
+                "simulated stream was shut down for writing"
+            ));
+        }
+        if buf.is_empty() {
+            return Ok(());
+        }
+        let earliest = self.network.clock.now() + delay;
+        let deliver_at = match pipe.segments.back() {
+            Some((last, _)) => earliest.max(*last),
+            None => earliest,
+        };
+        pipe.segments.push_back((deliver_at, buf.to_vec()));
+        pipe.wake_reader();
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) -> RS<()> {
+        let mut pipe = lock(&self.tx);
+        pipe.closed = true;
+        pipe.wake_reader();
+        Ok(())
+    }
+
+    fn as_raw_fd(&self) -> Option<std::os::fd::RawFd> {
+        None
+    }
+
+    fn set_nodelay(&self) -> RS<()> {
+        Ok(())
+    }
+}
+
+impl Drop for SimStream {
+    fn drop(&mut self) {
+        // The peer reads what is in flight, then end of stream; its writes
+        // are reset.
+        let mut tx = lock(&self.tx);
+        tx.closed = true;
+        tx.wake_reader();
+        drop(tx);
+        let mut rx = lock(&self.rx);
+        rx.reset = true;
+        if let Some((deadline, id)) = rx.timer.take() {
+            self.network.clock.cancel_timer(deadline, id);
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::imp::native::sim::Simulation;
+    use std::net::Ipv4Addr;
+
+    fn ip(last: u8) -> IpAddr {
+        IpAddr::V4(Ipv4Addr::new(10, 0, 0, last))
+    }
+
+    async fn read_exact(stream: &mut Box<dyn AsyncStream>, len: usize) -> RS<Vec<u8>> {
+        let mut out = vec![0u8; len];
+        let mut filled = 0;
+        while filled < len {
+            let n = stream.read(&mut out[filled..]).await?;
+            if n == 0 {
+                break;
+            }
+            filled += n;
+        }
+        out.truncate(filled);
+        Ok(out)
+    }
+
+    #[test]
+    fn streams_deliver_bytes_in_order_after_a_delay() {
+        let sim = Simulation::new(3);
+        let server = sim.network().host(ip(1));
+        let client = sim.network().host(ip(2));
+        let clock = sim.clock().clone();
+        let received = sim
+            .block_on(async move {
+                let listener = server.bind_tcp(SocketAddr::new(ip(1), 7000)).await.unwrap();
+                let mut stream = client
+                    .connect_tcp(SocketAddr::new(ip(1), 7000))
+                    .await
+                    .unwrap();
+                let (mut accepted, peer) = listener.accept().await.unwrap();
+                assert_eq!(peer.ip(), ip(2));
+                stream.write_all(b"hello ").await.unwrap();
+                stream.write_all(b"world").await.unwrap();
+                stream.shutdown().await.unwrap();
+                let sent_at = clock.now();
+                let received = read_exact(&mut accepted, 64).await.unwrap();
+                assert!(clock.now() > sent_at);
+                received
+            })
+            .unwrap();
+        assert_eq!(received, b"hello world");
+    }
+
+    #[test]
+    fn binding_a_used_address_fails() {
+        let sim = Simulation::new(1);
+        let host = sim.network().host(ip(1));
+        sim.block_on(async move {
+            let addr = SocketAddr::new(ip(1), 7000);
+            let _listener = host.bind_tcp(addr).await.unwrap();
+            let err = host.bind_tcp(addr).await.err().unwrap();
+            assert_eq!(err.ec(), ErrorCode::AddrInUse);
+            let ephemeral = host.bind_tcp(SocketAddr::new(ip(1), 0)).await.unwrap();
+            assert_ne!(ephemeral.local_addr().unwrap().port(), 0);
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn connecting_without_a_listener_is_refused() {
+        let sim = Simulation::new(1);
+        let host = sim.network().host(ip(2));
+        let err = sim
+            .block_on(async move { host.connect_tcp(SocketAddr::new(ip(1), 9)).await.err() })
+            .unwrap()
+            .unwrap();
+        assert_eq!(err.ec(), ErrorCode::ConnectionRefused);
+    }
+
+    #[test]
+    fn partition_resets_connections_until_healed() {
+        let sim = Simulation::new(9);
+        let network = sim.network().clone();
+        let server = network.host(ip(1));
+        let client = network.host(ip(2));
+        sim.block_on(async move {
+            let addr = SocketAddr::new(ip(1), 7000);
+            let listener = server.bind_tcp(addr).await.unwrap();
+            let mut stream = client.connect_tcp(addr).await.unwrap();
+            let (mut accepted, _) = listener.accept().await.unwrap();
+
+            network.partition(ip(1), ip(2));
+            let mut buf = [0u8; 8];
+            let err = accepted.read(&mut buf).await.unwrap_err();
+            assert_eq!(err.ec(), ErrorCode::ConnectionReset);
+            let err = stream.write_all(b"x").await.unwrap_err();
+            assert_eq!(err.ec(), ErrorCode::ConnectionReset);
+            let err = client.connect_tcp(addr).await.err().unwrap();
+            assert_eq!(err.ec(), ErrorCode::NetworkUnreachable);
+
+            network.heal(ip(1), ip(2));
+            let mut stream = client.connect_tcp(addr).await.unwrap();
+            let (mut accepted, _) = listener.accept().await.unwrap();
+            stream.write_all(b"again").await.unwrap();
+            assert_eq!(read_exact(&mut accepted, 5).await.unwrap(), b"again");
+        })
+        .unwrap();
+    }
+}
@@ -37,6 +37,9 @@ pub mod process;
 pub mod provider;
 /// Random value and UUID generation.
 pub mod random;
+/// Deterministic simulation providers for crash and fault testing.
+#[cfg(feature = "ds")]
+pub mod sim;
 /// Public synchronization primitives and blocking IO helpers.
 pub mod sync;
 /// System-wide IO context and provider selection.
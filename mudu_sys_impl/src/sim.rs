@@ -0,0 +1,16 @@
+//! Deterministic simulation of time, scheduling, storage and network.
+//!
+//! Tests build a [`Simulation`] per seed, hand its providers to the code
+//! under test and inject crashes, torn writes, lost fsyncs, full disks,
+//! delays and partitions. Runs replay exactly for a given seed; set
+//! `MUDU_DS_SEED` to replay the seed a failing run reported and
+//! `MUDU_DS_SEEDS` to sweep more seeds. Built with the `ds` feature.
+#![allow(missing_docs)]
+pub use crate::imp::native::sim::clock::{SimClock, SimSleep};
+pub use crate::imp::native::sim::executor::{SimExecutor, SimJoinHandle};
+pub use crate::imp::native::sim::fs::{SimFile, SimFs, SimFsFaults};
+pub use crate::imp::native::sim::net::{SimListener, SimNet, SimNetwork, SimStream};
+pub use crate::imp::native::sim::rng::SimRng;
+pub use crate::imp::native::sim::{
+    sim_seeds, standalone_provider, Simulation, SIM_SEED_COUNT_ENV, SIM_SEED_ENV,
+};
@@ -225,6 +225,11 @@ mod tests {
         assert_eq!(DataType::new_no_param(TypeFamily::Date).name(), "date");
         assert_eq!(DataType::new_no_param(TypeFamily::U128).name(), "oid");
         assert_eq!(DataType::new_no_param(TypeFamily::I128).name(), "i128");
+        assert_eq!(DataType::new_no_param(TypeFamily::Bool).name(), "boolean");
+        assert_eq!(DataType::new_no_param(TypeFamily::I8).name(), "tinyint");
+        assert_eq!(DataType::new_no_param(TypeFamily::I16).name(), "smallint");
+        assert_eq!(DataType::new_no_param(TypeFamily::Uuid).name(), "uuid");
+        assert_eq!(DataType::new_no_param(TypeFamily::Json).name(), "json");
         assert_eq!(
             DataType::from_numeric(DataTypeParamNumeric::new(10, 2)).name(),
             "numeric"
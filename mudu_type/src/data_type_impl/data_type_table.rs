@@ -163,6 +163,61 @@ lazy_static! {
             fixed_length: Some(size_of::<i64>() as u32),
             opt_fn_param: Some(data_type_impl::fn_timestamptz_param::FN_TIMESTAMPTZ_PARAM),
         },
+        DatTypeDef {
+            id: TypeFamily::Bool,
+            kind: TypeKind::Scalar,
+            type_name: "boolean".to_string(),
+            fn_base: data_type_impl::fn_bool::FN_BOOL_CONVERT,
+            opt_fn_compare: Some(data_type_impl::fn_bool::FN_BOOL_COMPARE),
+            #[cfg(any(test, feature = "test"))]
+            fn_arbitrary: data_type_impl::fn_bool_arb::FN_BOOL_ARBITRARY,
+            fixed_length: Some(size_of::<u8>() as u32),
+            opt_fn_param: None,
+        },
+        DatTypeDef {
+            id: TypeFamily::I8,
+            kind: TypeKind::Scalar,
+            type_name: "tinyint".to_string(),
+            fn_base: data_type_impl::fn_i8::FN_I8_CONVERT,
+            opt_fn_compare: Some(data_type_impl::fn_i8::FN_I8_COMPARE),
+            #[cfg(any(test, feature = "test"))]
+            fn_arbitrary: data_type_impl::fn_i8_arb::FN_I8_ARBITRARY,
+            fixed_length: Some(size_of::<i8>() as u32),
+            opt_fn_param: None,
+        },
+        DatTypeDef {
+            id: TypeFamily::I16,
+            kind: TypeKind::Scalar,
+            type_name: "smallint".to_string(),
+            fn_base: data_type_impl::fn_i16::FN_I16_CONVERT,
+            opt_fn_compare: Some(data_type_impl::fn_i16::FN_I16_COMPARE),
+            #[cfg(any(test, feature = "test"))]
+            fn_arbitrary: data_type_impl::fn_i16_arb::FN_I16_ARBITRARY,
+            fixed_length: Some(size_of::<i16>() as u32),
+            opt_fn_param: None,
+        },
+        DatTypeDef {
+            id: TypeFamily::Uuid,
+            kind: TypeKind::Scalar,
+            type_name: "uuid".to_string(),
+            fn_base: data_type_impl::fn_uuid::FN_UUID_CONVERT,
+            opt_fn_compare: Some(data_type_impl::fn_uuid::FN_UUID_COMPARE),
+            #[cfg(any(test, feature = "test"))]
+            fn_arbitrary: data_type_impl::fn_uuid_arb::FN_UUID_ARBITRARY,
+            fixed_length: Some(size_of::<u128>() as u32),
+            opt_fn_param: None,
+        },
+        DatTypeDef {
+            id: TypeFamily::Json,
+            kind: TypeKind::Scalar,
+            type_name: "json".to_string(),
+            fn_base: data_type_impl::fn_json::FN_JSON_CONVERT,
+            opt_fn_compare: Some(data_type_impl::fn_json::FN_JSON_COMPARE),
+            #[cfg(any(test, feature = "test"))]
+            fn_arbitrary: data_type_impl::fn_json_arb::FN_JSON_ARBITRARY,
+            fixed_length: None,
+            opt_fn_param: None,
+        },
         DatTypeDef {
             id: TypeFamily::Array,
             kind: TypeKind::Array,
@@ -0,0 +1,138 @@
+use crate::data_type::DataType;
+use crate::data_type_fn_compare::{ErrCompare, FnCompare};
+use crate::data_type_fn_convert::FnBase;
+
+use crate::data_binary::DataBinary;
+use crate::data_json::DataJson;
+use crate::data_textual::DataTextual;
+use crate::data_value::DataValue;
+use crate::type_error::{TyEC, TyErr};
+use mudu::json_value;
+use mudu::utils::json::{JsonValue, from_json_str};
+use mudu::utils::msg_pack::MsgPackValue;
+use std::cmp::Ordering;
+use std::hash::Hasher;
+
+pub fn fn_bool_in_textual(v: &str, _dt: &DataType) -> Result<DataValue, TyErr> {
+    let json = from_json_str::<JsonValue>(v)
+        .map_err(|e| TyErr::new(TyEC::TypeConvertFailed, e.to_string()))?;
+    fn_bool_in_json(&DataJson::from(json), _dt)
+}
+
+pub fn fn_bool_out_textual(v: &DataValue, _dt: &DataType) -> Result<DataTextual, TyErr> {
+    let json = fn_bool_out_json(v, _dt)?;
+    Ok(DataTextual::from(json.to_string()))
+}
+
+pub fn fn_bool_in_json(v: &JsonValue, _: &DataType) -> Result<DataValue, TyErr> {
+    match v.as_bool() {
+        Some(b) => Ok(DataValue::from_bool(b)),
+        None => Err(TyErr::new(
+            TyEC::TypeConvertFailed,
+            format!("cannot convert json {} to bool", v),
+        )),
+    }
+}
+
+pub fn fn_bool_out_json(v: &DataValue, _: &DataType) -> Result<DataJson, TyErr> {
+    let b = *v.expect_bool();
+    let json = json_value!(b);
+    Ok(DataJson::from(json))
+}
+
+pub fn fn_bool_in_msgpack(msg_pack: &MsgPackValue, _: &DataType) -> Result<DataValue, TyErr> {
+    match msg_pack.as_bool() {
+        Some(b) => Ok(DataValue::from_bool(b)),
+        None => Err(TyErr::new(
+            TyEC::TypeConvertFailed,
+            "cannot convert msg pack to dat value".to_string(),
+        )),
+    }
+}
+
+pub fn fn_bool_out_msgpack(v: &DataValue, _: &DataType) -> Result<MsgPackValue, TyErr> {
+    Ok(MsgPackValue::Boolean(*v.expect_bool()))
+}
+
+pub fn fn_bool_len(_: &DataType) -> Result<Option<u32>, TyErr> {
+    Ok(Some(size_of::<u8>() as u32))
+}
+
+pub fn fn_bool_dat_output_len(_: &DataValue, _ty: &DataType) -> Result<u32, TyErr> {
+    Ok(fn_bool_len(_ty)?.unwrap())
+}
+
+pub fn fn_bool_send(v: &DataValue, _: &DataType) -> Result<DataBinary, TyErr> {
+    Ok(DataBinary::from(vec![*v.expect_bool() as u8]))
+}
+
+pub fn fn_bool_send_to(v: &DataValue, _: &DataType, buf: &mut [u8]) -> Result<u32, TyErr> {
+    if buf.is_empty() {
+        return Err(TyErr::new(
+            TyEC::InsufficientSpace,
+            "insufficient space".to_string(),
+        ));
+    }
+    buf[0] = *v.expect_bool() as u8;
+    Ok(size_of::<u8>() as u32)
+}
+
+pub fn fn_bool_recv(buf: &[u8], _: &DataType) -> Result<(DataValue, u32), TyErr> {
+    if buf.is_empty() {
+        return Err(TyErr::new(
+            TyEC::InsufficientSpace,
+            "insufficient space".to_string(),
+        ));
+    };
+    let b = match buf[0] {
+        0 => false,
+        1 => true,
+        n => {
+            return Err(TyErr::new(
+                TyEC::TypeConvertFailed,
+                format!("invalid bool byte {}", n),
+            ));
+        }
+    };
+    Ok((DataValue::from_bool(b), size_of::<u8>() as u32))
+}
+
+pub fn fn_bool_default(_: &DataType) -> Result<DataValue, TyErr> {
+    Ok(DataValue::from_bool(bool::default()))
+}
+
+/// `FnOrder` returns ordering result of a comparison between two object values.
+pub fn fn_bool_order(v1: &DataValue, v2: &DataValue) -> Result<Ordering, ErrCompare> {
+    Ok(v1.expect_bool().cmp(v2.expect_bool()))
+}
+
+/// `FnEqual` return equal result of a comparison between two object values.
+pub fn fn_bool_equal(v1: &DataValue, v2: &DataValue) -> Result<bool, ErrCompare> {
+    Ok(v1.expect_bool().eq(v2.expect_bool()))
+}
+
+pub fn fn_bool_hash(v: &DataValue, hasher: &mut dyn Hasher) -> Result<(), ErrCompare> {
+    hasher.write_u8(*v.expect_bool() as u8);
+    Ok(())
+}
+
+pub const FN_BOOL_COMPARE: FnCompare = FnCompare {
+    order: fn_bool_order,
+    equal: fn_bool_equal,
+    hash: fn_bool_hash,
+};
+
+pub const FN_BOOL_CONVERT: FnBase = FnBase {
+    input_textual: fn_bool_in_textual,
+    output_textual: fn_bool_out_textual,
+    input_json: fn_bool_in_json,
+    output_json: fn_bool_out_json,
+    input_msg_pack: fn_bool_in_msgpack,
+    output_msg_pack: fn_bool_out_msgpack,
+    type_len: fn_bool_len,
+    data_len: fn_bool_dat_output_len,
+    receive: fn_bool_recv,
+    send: fn_bool_send,
+    send_to: fn_bool_send_to,
+    default: fn_bool_default,
+};
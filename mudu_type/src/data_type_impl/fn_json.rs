@@ -0,0 +1,249 @@
+//! JSON documents. A value holds the compact text of a parsed document, so
+//! every stored value is well formed; the binary form is that text with a
+//! size prefix, like a string.
+//!
+//! Documents compare structurally: by kind (null < bool < number < string <
+//! array < object) and then by content, with object members compared by key
+//! regardless of their order in the text.
+
+use crate::data_binary::DataBinary;
+use crate::data_json::DataJson;
+use crate::data_textual::DataTextual;
+use crate::data_type::DataType;
+use crate::data_type_fn_compare::{ErrCompare, FnCompare};
+use crate::data_type_fn_convert::FnBase;
+use crate::data_value::DataValue;
+use crate::type_error::{TyEC, TyErr};
+use mudu::utils::bin_size::BinSize;
+use mudu::utils::buf::{read_sized_buf, write_sized_buf};
+use mudu::utils::json::{JsonNumber, JsonValue, from_json_str};
+use mudu::utils::msg_pack::{MsgPackUtf8String, MsgPackValue};
+use std::cmp::Ordering;
+use std::hash::Hasher;
+
+/// Parses `text` as a JSON document and returns its compact form.
+pub fn canonical_json(text: &str) -> Result<String, TyErr> {
+    let json = from_json_str::<JsonValue>(text)
+        .map_err(|e| TyErr::new(TyEC::TypeConvertFailed, e.to_string()))?;
+    Ok(json.to_string())
+}
+
+fn parse_stored(v: &DataValue) -> Result<JsonValue, ErrCompare> {
+    from_json_str::<JsonValue>(v.expect_json()).map_err(|e| ErrCompare::ErrInternal(e.to_string()))
+}
+
+fn kind_rank(v: &JsonValue) -> u8 {
+    match v {
+        JsonValue::Null => 0,
+        JsonValue::Bool(_) => 1,
+        JsonValue::Number(_) => 2,
+        JsonValue::String(_) => 3,
+        JsonValue::Array(_) => 4,
+        JsonValue::Object(_) => 5,
+    }
+}
+
+fn number_cmp(n1: &JsonNumber, n2: &JsonNumber) -> Ordering {
+    if let (Some(i1), Some(i2)) = (n1.as_i64(), n2.as_i64()) {
+        return i1.cmp(&i2);
+    }
+    if let (Some(u1), Some(u2)) = (n1.as_u64(), n2.as_u64()) {
+        return u1.cmp(&u2);
+    }
+    let f1 = n1.as_f64().unwrap_or(f64::NAN);
+    let f2 = n2.as_f64().unwrap_or(f64::NAN);
+    f1.total_cmp(&f2)
+}
+
+/// Total order over JSON documents used by the comparison functions.
+pub fn json_cmp(v1: &JsonValue, v2: &JsonValue) -> Ordering {
+    match (v1, v2) {
+        (JsonValue::Null, JsonValue::Null) => Ordering::Equal,
+        (JsonValue::Bool(b1), JsonValue::Bool(b2)) => b1.cmp(b2),
+        (JsonValue::Number(n1), JsonValue::Number(n2)) => number_cmp(n1, n2),
+        (JsonValue::String(s1), JsonValue::String(s2)) => s1.cmp(s2),
+        (JsonValue::Array(a1), JsonValue::Array(a2)) => {
+            for (e1, e2) in a1.iter().zip(a2.iter()) {
+                let ord = json_cmp(e1, e2);
+                if ord != Ordering::Equal {
+                    return ord;
+                }
+            }
+            a1.len().cmp(&a2.len())
+        }
+        (JsonValue::Object(o1), JsonValue::Object(o2)) => {
+            let mut m1: Vec<_> = o1.iter().collect();
+            let mut m2: Vec<_> = o2.iter().collect();
+            m1.sort_by(|a, b| a.0.cmp(b.0));
+            m2.sort_by(|a, b| a.0.cmp(b.0));
+            for ((k1, e1), (k2, e2)) in m1.iter().zip(m2.iter()) {
+                let ord = k1.cmp(k2).then_with(|| json_cmp(e1, e2));
+                if ord != Ordering::Equal {
+                    return ord;
+                }
+            }
+            m1.len().cmp(&m2.len())
+        }
+        _ => kind_rank(v1).cmp(&kind_rank(v2)),
+    }
+}
+
+fn json_hash(v: &JsonValue, hasher: &mut dyn Hasher) {
+    hasher.write_u8(kind_rank(v));
+    match v {
+        JsonValue::Null => {}
+        JsonValue::Bool(b) => hasher.write_u8(*b as u8),
+        JsonValue::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                hasher.write_i64(i);
+            } else if let Some(u) = n.as_u64() {
+                hasher.write_u64(u);
+            } else {
+                let f = n.as_f64().unwrap_or(f64::NAN);
+                // Integral floats hash like the integer they equal.
+                if f.fract() == 0.0 && f >= i64::MIN as f64 && f < i64::MAX as f64 {
+                    hasher.write_i64(f as i64);
+                } else {
+                    hasher.write_u64(f.to_bits());
+                }
+            }
+        }
+        JsonValue::String(s) => hasher.write(s.as_bytes()),
+        JsonValue::Array(a) => {
+            hasher.write_usize(a.len());
+            for e in a {
+                json_hash(e, hasher);
+            }
+        }
+        JsonValue::Object(o) => {
+            let mut members: Vec<_> = o.iter().collect();
+            members.sort_by(|a, b| a.0.cmp(b.0));
+            hasher.write_usize(members.len());
+            for (k, e) in members {
+                hasher.write(k.as_bytes());
+                json_hash(e, hasher);
+            }
+        }
+    }
+}
+
+fn fn_json_in_textual(v: &str, _: &DataType) -> Result<DataValue, TyErr> {
+    Ok(DataValue::from_json(canonical_json(v)?))
+}
+
+fn fn_json_out_textual(v: &DataValue, _: &DataType) -> Result<DataTextual, TyErr> {
+    Ok(DataTextual::from(v.expect_json().clone()))
+}
+
+fn fn_json_in_json(v: &JsonValue, _: &DataType) -> Result<DataValue, TyErr> {
+    Ok(DataValue::from_json(v.to_string()))
+}
+
+fn fn_json_out_json(v: &DataValue, _: &DataType) -> Result<DataJson, TyErr> {
+    let json = from_json_str::<JsonValue>(v.expect_json())
+        .map_err(|e| TyErr::new(TyEC::TypeConvertFailed, e.to_string()))?;
+    Ok(DataJson::from(json))
+}
+
+fn fn_json_in_msgpack(msg_pack: &MsgPackValue, _: &DataType) -> Result<DataValue, TyErr> {
+    match msg_pack.as_str() {
+        Some(s) => Ok(DataValue::from_json(canonical_json(s)?)),
+        None => Err(TyErr::new(
+            TyEC::TypeConvertFailed,
+            "cannot convert msg pack to json".to_string(),
+        )),
+    }
+}
+
+fn fn_json_out_msgpack(v: &DataValue, _: &DataType) -> Result<MsgPackValue, TyErr> {
+    Ok(MsgPackValue::String(MsgPackUtf8String::from(
+        v.expect_json().clone(),
+    )))
+}
+
+fn fn_json_len(_: &DataType) -> Result<Option<u32>, TyErr> {
+    Ok(None)
+}
+
+fn fn_json_dat_output_len(v: &DataValue, _: &DataType) -> Result<u32, TyErr> {
+    Ok((v.expect_json().len() + BinSize::size_of()) as u32)
+}
+
+fn fn_json_send(v: &DataValue, _: &DataType) -> Result<DataBinary, TyErr> {
+    let s = v.expect_json();
+    let mut vec = vec![0u8; s.len() + BinSize::size_of()];
+    if write_sized_buf(&mut vec, s.as_bytes()) == 0 {
+        return Err(TyErr::new(
+            TyEC::TypeConvertFailed,
+            format!("cannot convert json {} to binary", s),
+        ));
+    }
+    Ok(DataBinary::from(vec))
+}
+
+fn fn_json_send_to(v: &DataValue, _: &DataType, buf: &mut [u8]) -> Result<u32, TyErr> {
+    let s = v.expect_json();
+    if write_sized_buf(buf, s.as_bytes()) == 0 {
+        return Err(TyErr::new(
+            TyEC::InsufficientSpace,
+            "insufficient space".to_string(),
+        ));
+    }
+    Ok((s.len() + BinSize::size_of()) as u32)
+}
+
+fn fn_json_recv(buf: &[u8], _: &DataType) -> Result<(DataValue, u32), TyErr> {
+    match read_sized_buf(buf) {
+        Ok((read_n, b)) => {
+            let s = String::from_utf8(b.to_vec())
+                .map_err(|e| TyErr::new(TyEC::TypeConvertFailed, e.to_string()))?;
+            Ok((DataValue::from_json(s), read_n))
+        }
+        Err(n) => Err(TyErr::new(
+            TyEC::TypeConvertFailed,
+            format!("buffer size error, expected size {:?}", n),
+        )),
+    }
+}
+
+fn fn_json_default(_: &DataType) -> Result<DataValue, TyErr> {
+    Ok(DataValue::from_json(JsonValue::Null.to_string()))
+}
+
+fn fn_json_order(v1: &DataValue, v2: &DataValue) -> Result<Ordering, ErrCompare> {
+    Ok(json_cmp(&parse_stored(v1)?, &parse_stored(v2)?))
+}
+
+fn fn_json_equal(v1: &DataValue, v2: &DataValue) -> Result<bool, ErrCompare> {
+    Ok(fn_json_order(v1, v2)? == Ordering::Equal)
+}
+
+fn fn_json_hash(v: &DataValue, hasher: &mut dyn Hasher) -> Result<(), ErrCompare> {
+    json_hash(&parse_stored(v)?, hasher);
+    Ok(())
+}
+
+pub const FN_JSON_COMPARE: FnCompare = FnCompare {
+    order: fn_json_order,
+    equal: fn_json_equal,
+    hash: fn_json_hash,
+};
+
+pub const FN_JSON_CONVERT: FnBase = FnBase {
+    input_textual: fn_json_in_textual,
+    output_textual: fn_json_out_textual,
+    input_json: fn_json_in_json,
+    output_json: fn_json_out_json,
+    input_msg_pack: fn_json_in_msgpack,
+    output_msg_pack: fn_json_out_msgpack,
+    type_len: fn_json_len,
+    data_len: fn_json_dat_output_len,
+    receive: fn_json_recv,
+    send: fn_json_send,
+    send_to: fn_json_send_to,
+    default: fn_json_default,
+};
+
+#[cfg(test)]
+#[path = "fn_json_test.rs"]
+mod fn_json_test;
@@ -0,0 +1,57 @@
+use crate::data_type::DataType;
+use crate::data_type_fn_arbitrary::FnArbitrary;
+use crate::data_value::DataValue;
+use crate::type_family::TypeFamily;
+use arbitrary::{Arbitrary, Unstructured};
+use mudu::utils::json::{JsonMap, JsonValue};
+use test_utils::_arb_limit::_ARB_MAX_STRING_LEN;
+use test_utils::_arb_string::_arbitrary_string;
+
+const ARB_MAX_JSON_DEPTH: u32 = 3;
+const ARB_MAX_JSON_WIDTH: usize = 4;
+
+fn arb_json(u: &mut Unstructured, depth: u32) -> arbitrary::Result<JsonValue> {
+    let kinds = if depth >= ARB_MAX_JSON_DEPTH { 4 } else { 6 };
+    let json = match u.int_in_range(0..=kinds - 1)? {
+        0 => JsonValue::Null,
+        1 => JsonValue::Bool(bool::arbitrary(u)?),
+        2 => JsonValue::from(i64::arbitrary(u)?),
+        3 => JsonValue::String(_arbitrary_string(u, _ARB_MAX_STRING_LEN)?),
+        4 => {
+            let len = u.int_in_range(0..=ARB_MAX_JSON_WIDTH)?;
+            let mut array = Vec::with_capacity(len);
+            for _ in 0..len {
+                array.push(arb_json(u, depth + 1)?);
+            }
+            JsonValue::Array(array)
+        }
+        _ => {
+            let len = u.int_in_range(0..=ARB_MAX_JSON_WIDTH)?;
+            let mut object = JsonMap::new();
+            for _ in 0..len {
+                let key = _arbitrary_string(u, _ARB_MAX_STRING_LEN)?;
+                object.insert(key, arb_json(u, depth + 1)?);
+            }
+            JsonValue::Object(object)
+        }
+    };
+    Ok(json)
+}
+
+pub fn fn_json_arb_val(u: &mut Unstructured, _: &DataType) -> arbitrary::Result<DataValue> {
+    Ok(DataValue::from_json(arb_json(u, 0)?.to_string()))
+}
+
+pub fn fn_json_arb_printable(u: &mut Unstructured, _: &DataType) -> arbitrary::Result<String> {
+    Ok(arb_json(u, 0)?.to_string())
+}
+
+pub fn fn_json_arb_dt_param(_u: &mut Unstructured) -> arbitrary::Result<DataType> {
+    Ok(DataType::new_no_param(TypeFamily::Json))
+}
+
+pub const FN_JSON_ARBITRARY: FnArbitrary = FnArbitrary {
+    param: fn_json_arb_dt_param,
+    value_object: fn_json_arb_val,
+    value_print: fn_json_arb_printable,
+};
@@ -0,0 +1,56 @@
+#![allow(clippy::unwrap_used)]
+
+use super::{fn_json_equal, fn_json_hash, fn_json_in_textual, fn_json_order, fn_json_out_textual};
+use crate::data_type::DataType;
+use crate::data_value::DataValue;
+use crate::type_family::TypeFamily;
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hasher;
+
+fn json_type() -> DataType {
+    DataType::new_no_param(TypeFamily::Json)
+}
+
+fn parse(text: &str) -> DataValue {
+    fn_json_in_textual(text, &json_type()).unwrap()
+}
+
+fn hash(v: &DataValue) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    fn_json_hash(v, &mut hasher).unwrap();
+    hasher.finish()
+}
+
+#[test]
+fn json_textual_is_stored_compact() {
+    let v = parse("{ \"a\" : [1, 2],\n \"b\": null }");
+    assert_eq!(v.expect_json(), "{\"a\":[1,2],\"b\":null}");
+    let out = fn_json_out_textual(&v, &json_type()).unwrap();
+    assert_eq!(out.as_str(), "{\"a\":[1,2],\"b\":null}");
+    assert!(fn_json_in_textual("{\"a\":", &json_type()).is_err());
+}
+
+#[test]
+fn json_objects_compare_regardless_of_member_order() {
+    let v1 = parse("{\"a\":1,\"b\":{\"c\":true}}");
+    let v2 = parse("{\"b\":{\"c\":true},\"a\":1.0}");
+    assert!(fn_json_equal(&v1, &v2).unwrap());
+    assert_eq!(hash(&v1), hash(&v2));
+}
+
+#[test]
+fn json_orders_by_kind_then_value() {
+    let ordered = [
+        "null", "false", "true", "-3", "2.5", "10", "\"a\"", "[1]", "[1,0]", "{}",
+    ];
+    for pair in ordered.windows(2) {
+        assert_eq!(
+            fn_json_order(&parse(pair[0]), &parse(pair[1])).unwrap(),
+            Ordering::Less,
+            "{} < {}",
+            pair[0],
+            pair[1]
+        );
+    }
+}
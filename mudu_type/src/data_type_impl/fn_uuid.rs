@@ -0,0 +1,141 @@
+use crate::data_binary::DataBinary;
+use crate::data_json::DataJson;
+use crate::data_textual::DataTextual;
+use crate::data_type::DataType;
+use crate::data_type_fn_compare::{ErrCompare, FnCompare};
+use crate::data_type_fn_convert::FnBase;
+use crate::data_value::DataValue;
+use crate::type_error::{TyEC, TyErr};
+use mudu::common::endian;
+use mudu::data_type::uuid::{format_uuid, parse_uuid};
+use mudu::utils::json::{JsonValue, from_json_str};
+use mudu::utils::msg_pack::{MsgPackUtf8String, MsgPackValue};
+use std::cmp::Ordering;
+use std::hash::Hasher;
+
+fn fn_uuid_in_textual(v: &str, dt: &DataType) -> Result<DataValue, TyErr> {
+    let json = from_json_str::<JsonValue>(v)
+        .map_err(|e| TyErr::new(TyEC::TypeConvertFailed, e.to_string()))?;
+    fn_uuid_in_json(&json, dt)
+}
+
+fn fn_uuid_out_textual(v: &DataValue, dt: &DataType) -> Result<DataTextual, TyErr> {
+    let json = fn_uuid_out_json(v, dt)?;
+    Ok(DataTextual::from(json.to_string()))
+}
+
+fn fn_uuid_in_json(v: &JsonValue, _: &DataType) -> Result<DataValue, TyErr> {
+    match v.as_str() {
+        Some(s) => parse_uuid(s)
+            .map(DataValue::from_uuid)
+            .map_err(|e| TyErr::new(TyEC::TypeConvertFailed, e)),
+        None => Err(TyErr::new(
+            TyEC::TypeConvertFailed,
+            format!("cannot convert json {} to uuid", v),
+        )),
+    }
+}
+
+fn fn_uuid_out_json(v: &DataValue, _: &DataType) -> Result<DataJson, TyErr> {
+    Ok(DataJson::from(JsonValue::String(format_uuid(
+        *v.expect_uuid(),
+    ))))
+}
+
+fn fn_uuid_in_msgpack(msg_pack: &MsgPackValue, _: &DataType) -> Result<DataValue, TyErr> {
+    match msg_pack.as_str() {
+        Some(s) => parse_uuid(s)
+            .map(DataValue::from_uuid)
+            .map_err(|e| TyErr::new(TyEC::TypeConvertFailed, e)),
+        None => Err(TyErr::new(
+            TyEC::TypeConvertFailed,
+            "cannot convert msg pack to uuid".to_string(),
+        )),
+    }
+}
+
+fn fn_uuid_out_msgpack(v: &DataValue, _: &DataType) -> Result<MsgPackValue, TyErr> {
+    Ok(MsgPackValue::String(MsgPackUtf8String::from(format_uuid(
+        *v.expect_uuid(),
+    ))))
+}
+
+fn fn_uuid_len(_: &DataType) -> Result<Option<u32>, TyErr> {
+    Ok(Some(size_of::<u128>() as u32))
+}
+
+fn fn_uuid_dat_output_len(_: &DataValue, ty: &DataType) -> Result<u32, TyErr> {
+    Ok(fn_uuid_len(ty)?.unwrap())
+}
+
+fn fn_uuid_send(v: &DataValue, _: &DataType) -> Result<DataBinary, TyErr> {
+    let mut buf = vec![0; size_of::<u128>()];
+    endian::write_u128(&mut buf, *v.expect_uuid());
+    Ok(DataBinary::from(buf))
+}
+
+fn fn_uuid_send_to(v: &DataValue, _: &DataType, buf: &mut [u8]) -> Result<u32, TyErr> {
+    if buf.len() < size_of::<u128>() {
+        return Err(TyErr::new(
+            TyEC::InsufficientSpace,
+            "insufficient space".to_string(),
+        ));
+    }
+    endian::write_u128(buf, *v.expect_uuid());
+    Ok(size_of::<u128>() as u32)
+}
+
+fn fn_uuid_recv(buf: &[u8], _: &DataType) -> Result<(DataValue, u32), TyErr> {
+    if buf.len() < size_of::<u128>() {
+        return Err(TyErr::new(
+            TyEC::InsufficientSpace,
+            "insufficient space".to_string(),
+        ));
+    }
+    Ok((
+        DataValue::from_uuid(endian::read_u128(buf)),
+        size_of::<u128>() as u32,
+    ))
+}
+
+fn fn_uuid_default(_: &DataType) -> Result<DataValue, TyErr> {
+    Ok(DataValue::from_uuid(u128::default()))
+}
+
+fn fn_uuid_order(v1: &DataValue, v2: &DataValue) -> Result<Ordering, ErrCompare> {
+    Ok(v1.expect_uuid().cmp(v2.expect_uuid()))
+}
+
+fn fn_uuid_equal(v1: &DataValue, v2: &DataValue) -> Result<bool, ErrCompare> {
+    Ok(v1.expect_uuid() == v2.expect_uuid())
+}
+
+fn fn_uuid_hash(v: &DataValue, hasher: &mut dyn Hasher) -> Result<(), ErrCompare> {
+    hasher.write_u128(*v.expect_uuid());
+    Ok(())
+}
+
+pub const FN_UUID_COMPARE: FnCompare = FnCompare {
+    order: fn_uuid_order,
+    equal: fn_uuid_equal,
+    hash: fn_uuid_hash,
+};
+
+pub const FN_UUID_CONVERT: FnBase = FnBase {
+    input_textual: fn_uuid_in_textual,
+    output_textual: fn_uuid_out_textual,
+    input_json: fn_uuid_in_json,
+    output_json: fn_uuid_out_json,
+    input_msg_pack: fn_uuid_in_msgpack,
+    output_msg_pack: fn_uuid_out_msgpack,
+    type_len: fn_uuid_len,
+    data_len: fn_uuid_dat_output_len,
+    receive: fn_uuid_recv,
+    send: fn_uuid_send,
+    send_to: fn_uuid_send_to,
+    default: fn_uuid_default,
+};
+
+#[cfg(test)]
+#[path = "fn_uuid_test.rs"]
+mod fn_uuid_test;
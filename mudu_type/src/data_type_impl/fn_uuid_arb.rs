@@ -0,0 +1,24 @@
+use crate::data_type::DataType;
+use crate::data_type_fn_arbitrary::FnArbitrary;
+use crate::data_value::DataValue;
+use crate::type_family::TypeFamily;
+use arbitrary::{Arbitrary, Unstructured};
+use mudu::data_type::uuid::format_uuid;
+
+pub fn fn_uuid_arb_val(u: &mut Unstructured, _: &DataType) -> arbitrary::Result<DataValue> {
+    Ok(DataValue::from_uuid(u128::arbitrary(u)?))
+}
+
+pub fn fn_uuid_arb_printable(u: &mut Unstructured, _: &DataType) -> arbitrary::Result<String> {
+    Ok(format!("\"{}\"", format_uuid(u128::arbitrary(u)?)))
+}
+
+pub fn fn_uuid_arb_dt_param(_u: &mut Unstructured) -> arbitrary::Result<DataType> {
+    Ok(DataType::new_no_param(TypeFamily::Uuid))
+}
+
+pub const FN_UUID_ARBITRARY: FnArbitrary = FnArbitrary {
+    param: fn_uuid_arb_dt_param,
+    value_object: fn_uuid_arb_val,
+    value_print: fn_uuid_arb_printable,
+};
@@ -0,0 +1,21 @@
+#![allow(clippy::unwrap_used)]
+
+use super::{fn_uuid_in_textual, fn_uuid_out_textual};
+use crate::data_type::DataType;
+use crate::type_family::TypeFamily;
+
+#[test]
+fn uuid_textual_is_a_json_string() {
+    let ty = DataType::new_no_param(TypeFamily::Uuid);
+    let v = fn_uuid_in_textual("\"00000000-0000-0000-0000-00000000002a\"", &ty).unwrap();
+    assert_eq!(*v.expect_uuid(), 42);
+    let out = fn_uuid_out_textual(&v, &ty).unwrap();
+    assert_eq!(out.as_str(), "\"00000000-0000-0000-0000-00000000002a\"");
+}
+
+#[test]
+fn uuid_textual_rejects_malformed_text() {
+    let ty = DataType::new_no_param(TypeFamily::Uuid);
+    assert!(fn_uuid_in_textual("\"123e4567-e89b-12d3-a456\"", &ty).is_err());
+    assert!(fn_uuid_in_textual("42", &ty).is_err());
+}
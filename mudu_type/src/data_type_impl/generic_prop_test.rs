@@ -15,6 +15,11 @@ fn supported_scalar_type_ids() -> &'static [TypeFamily] {
         TypeFamily::String,
         TypeFamily::U128,
         TypeFamily::I128,
+        TypeFamily::Bool,
+        TypeFamily::I8,
+        TypeFamily::I16,
+        TypeFamily::Uuid,
+        TypeFamily::Json,
         TypeFamily::Binary,
     ]
 }
@@ -7,6 +7,9 @@ use std::collections::HashMap;
 
 lazy_static! {
     static ref _id_lang_type_name: Vec<(TypeFamily, &'static str)> = vec![
+        (TypeFamily::Bool, "bool"),
+        (TypeFamily::I8, "i8"),
+        (TypeFamily::I16, "i16"),
         (TypeFamily::I32, "i32"),
         (TypeFamily::I64, "i64"),
         (TypeFamily::I128, "i128"),
@@ -5,6 +5,9 @@ mod tests {
 
     #[test]
     fn dt_lang_name_to_id_known_types() {
+        assert_eq!(dt_lang_name_to_id("bool").unwrap().0, TypeFamily::Bool);
+        assert_eq!(dt_lang_name_to_id("i8").unwrap().0, TypeFamily::I8);
+        assert_eq!(dt_lang_name_to_id("i16").unwrap().0, TypeFamily::I16);
         assert_eq!(dt_lang_name_to_id("i32").unwrap().0, TypeFamily::I32);
         assert_eq!(dt_lang_name_to_id("i64").unwrap().0, TypeFamily::I64);
         assert_eq!(dt_lang_name_to_id("i128").unwrap().0, TypeFamily::I128);
@@ -21,6 +24,9 @@ mod tests {
 
     #[test]
     fn dt_id_to_lang_name_known_types() {
+        assert_eq!(dt_id_to_lang_name(TypeFamily::Bool).unwrap(), "bool");
+        assert_eq!(dt_id_to_lang_name(TypeFamily::I8).unwrap(), "i8");
+        assert_eq!(dt_id_to_lang_name(TypeFamily::I16).unwrap(), "i16");
         assert_eq!(dt_id_to_lang_name(TypeFamily::I32).unwrap(), "i32");
         assert_eq!(dt_id_to_lang_name(TypeFamily::I64).unwrap(), "i64");
         assert_eq!(dt_id_to_lang_name(TypeFamily::I128).unwrap(), "i128");
@@ -2,12 +2,16 @@ pub mod data_type_create;
 pub mod data_type_table;
 pub mod lang;
 
+mod fn_bool;
 mod fn_date;
 mod fn_f32;
 mod fn_f64;
 mod fn_i128;
+mod fn_i16;
 mod fn_i32;
 mod fn_i64;
+mod fn_i8;
+mod fn_json;
 mod fn_numeric;
 mod fn_numeric_param;
 mod fn_string;
@@ -19,6 +23,7 @@ mod fn_timestamp_param;
 mod fn_timestamptz;
 mod fn_timestamptz_param;
 mod fn_u128;
+mod fn_uuid;
 
 mod fn_array;
 #[cfg(any(test, feature = "test"))]
@@ -28,6 +33,8 @@ mod fn_binary;
 #[cfg(any(test, feature = "test"))]
 mod fn_binary_arb;
 #[cfg(any(test, feature = "test"))]
+mod fn_bool_arb;
+#[cfg(any(test, feature = "test"))]
 mod fn_date_arb;
 #[cfg(test)]
 mod fn_date_arb_test;
@@ -38,10 +45,16 @@ mod fn_f64_arb;
 #[cfg(any(test, feature = "test"))]
 mod fn_i128_arb;
 #[cfg(any(test, feature = "test"))]
+mod fn_i16_arb;
+#[cfg(any(test, feature = "test"))]
 mod fn_i32_arb;
 #[cfg(any(test, feature = "test"))]
 mod fn_i64_arb;
 #[cfg(any(test, feature = "test"))]
+mod fn_i8_arb;
+#[cfg(any(test, feature = "test"))]
+mod fn_json_arb;
+#[cfg(any(test, feature = "test"))]
 mod fn_numeric_arb;
 #[cfg(test)]
 mod fn_numeric_arb_test;
@@ -65,6 +78,8 @@ mod fn_timestamptz_arb;
 mod fn_timestamptz_arb_test;
 #[cfg(any(test, feature = "test"))]
 mod fn_u128_arb;
+#[cfg(any(test, feature = "test"))]
+mod fn_uuid_arb;
 
 // Property tests with many arbitrary inputs are too slow under Miri.
 #[cfg(all(test, not(miri)))]
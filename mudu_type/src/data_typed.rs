@@ -14,6 +14,27 @@ pub struct DataTyped {
 }
 
 impl DataTyped {
+    pub fn from_bool(val: bool) -> Self {
+        Self::new(
+            DataType::default_for(TypeFamily::Bool),
+            DataValue::from_bool(val),
+        )
+    }
+
+    pub fn from_i8(val: i8) -> Self {
+        Self::new(
+            DataType::default_for(TypeFamily::I8),
+            DataValue::from_i8(val),
+        )
+    }
+
+    pub fn from_i16(val: i16) -> Self {
+        Self::new(
+            DataType::default_for(TypeFamily::I16),
+            DataValue::from_i16(val),
+        )
+    }
+
     pub fn from_i32(val: i32) -> Self {
         Self::new(
             DataType::default_for(TypeFamily::I32),
@@ -42,6 +63,21 @@ impl DataTyped {
         )
     }
 
+    pub fn from_uuid(val: u128) -> Self {
+        Self::new(
+            DataType::default_for(TypeFamily::Uuid),
+            DataValue::from_uuid(val),
+        )
+    }
+
+    /// `val` must be the compact text of a JSON document.
+    pub fn from_json(val: String) -> Self {
+        Self::new(
+            DataType::default_for(TypeFamily::Json),
+            DataValue::from_json(val),
+        )
+    }
+
     pub fn from_f32(val: f32) -> Self {
         Self::new(
             DataType::default_for(TypeFamily::F32),
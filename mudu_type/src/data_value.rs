@@ -52,6 +52,12 @@ enum ValueKind {
     Record(Vec<DataValue>),
     Array(Vec<DataValue>),
     Binary(Vec<u8>),
+    Bool(bool),
+    I8(i8),
+    I16(i16),
+    Uuid(u128),
+    /// Compact text of a JSON document.
+    Json(String),
 }
 
 macro_rules! impl_data_value_methods {
@@ -227,6 +233,11 @@ impl_data_value_methods! {
     (Vec<DataValue>, Array, array),
     (Vec<DataValue>, Record, record),
     (Vec<u8>, Binary, binary),
+    (bool, Bool, bool),
+    (i8, I8, i8),
+    (i16, I16, i16),
+    (u128, Uuid, uuid),
+    (String, Json, json),
 }
 
 impl DatumDyn for DataValue {
@@ -262,6 +262,9 @@ macro_rules! impl_datum_trait {
 }
 
 impl_datum_trait!(
+    (Bool, bool, bool),
+    (I8, i8, i8),
+    (I16, i16, i16),
     (I32, i32, i32),
     (I64, i64, i64),
     (I128, i128, i128),
@@ -59,6 +59,11 @@ pub enum TypeFamily {
     Time = 9,
     Timestamp = 10,
     TimestampTz = 11,
+    Bool = 12,
+    I8 = 13,
+    I16 = 14,
+    Uuid = 15,
+    Json = 16,
 
     Array = 1001,
     Record = 1002,
@@ -192,13 +197,18 @@ impl TypeFamily {
     pub fn has_param(&self) -> bool {
         !matches!(
             self,
-            TypeFamily::I32
+            TypeFamily::Bool
+                | TypeFamily::I8
+                | TypeFamily::I16
+                | TypeFamily::I32
                 | TypeFamily::I64
                 | TypeFamily::I128
                 | TypeFamily::F32
                 | TypeFamily::F64
                 | TypeFamily::U128
                 | TypeFamily::Date
+                | TypeFamily::Uuid
+                | TypeFamily::Json
         )
     }
 
@@ -244,6 +254,11 @@ mod tests {
             TypeFamily::Time,
             TypeFamily::Timestamp,
             TypeFamily::TimestampTz,
+            TypeFamily::Bool,
+            TypeFamily::I8,
+            TypeFamily::I16,
+            TypeFamily::Uuid,
+            TypeFamily::Json,
             TypeFamily::Array,
             TypeFamily::Record,
             TypeFamily::Binary,
@@ -276,6 +291,11 @@ mod tests {
         assert!(TypeFamily::Time.is_scalar_type());
         assert!(TypeFamily::Timestamp.is_scalar_type());
         assert!(TypeFamily::TimestampTz.is_scalar_type());
+        assert!(TypeFamily::Bool.is_scalar_type());
+        assert!(TypeFamily::I8.is_scalar_type());
+        assert!(TypeFamily::I16.is_scalar_type());
+        assert!(TypeFamily::Uuid.is_scalar_type());
+        assert!(TypeFamily::Json.is_scalar_type());
         assert!(!TypeFamily::Array.is_scalar_type());
         assert!(!TypeFamily::Record.is_scalar_type());
         assert!(!TypeFamily::Binary.is_scalar_type());
@@ -298,6 +318,11 @@ mod tests {
             TypeFamily::F64,
             TypeFamily::U128,
             TypeFamily::Date,
+            TypeFamily::Bool,
+            TypeFamily::I8,
+            TypeFamily::I16,
+            TypeFamily::Uuid,
+            TypeFamily::Json,
         ];
         for v in has_param {
             assert!(v.has_param(), "{v:?} should have parameter");
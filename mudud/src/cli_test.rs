@@ -1,8 +1,9 @@
 //! Tests for the `mudud` CLI argument parser.
 #![allow(missing_docs)]
 
-use crate::{Args, Command, ServeArgs};
+use crate::{Args, Command, ServeArgs, parse_restore_target};
 use clap::Parser;
+use mudu_kernel::server::backup::RestoreTarget;
 
 #[test]
 fn args_parse_serve_with_cfg() -> Result<(), Box<dyn std::error::Error>> {
@@ -48,6 +49,52 @@ fn args_parse_init_cfg() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn args_parse_restore() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::try_parse_from([
+        "mudud",
+        "restore",
+        "-c",
+        "/tmp/mududb.toml",
+        "--from",
+        "/backup/full",
+        "--archive",
+        "/backup/wal",
+        "--until",
+        "1200",
+    ])?;
+    match args.command {
+        Some(Command::Restore(restore_args)) => {
+            assert_eq!(restore_args.cfg_path, Some("/tmp/mududb.toml".to_string()));
+            assert_eq!(restore_args.from, "/backup/full");
+            assert_eq!(restore_args.archive, Some("/backup/wal".to_string()));
+            assert_eq!(restore_args.until, Some("1200".to_string()));
+        }
+        other => assert!(
+            matches!(other, Some(Command::Restore(_))),
+            "expected restore subcommand"
+        ),
+    }
+    Ok(())
+}
+
+#[test]
+fn args_parse_restore_requires_from() {
+    let result = Args::try_parse_from(["mudud", "restore"]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn parse_restore_target_reads_lsn_and_timestamp() -> Result<(), Box<dyn std::error::Error>> {
+    assert_eq!(parse_restore_target("42")?, RestoreTarget::Lsn(42));
+    assert_eq!(
+        parse_restore_target("1970-01-01 00:00:01+00:00")?,
+        RestoreTarget::UnixMicros(1_000_000)
+    );
+    assert!(parse_restore_target("yesterday").is_err());
+    Ok(())
+}
+
 #[test]
 fn args_parse_rejects_unknown_flag() {
     let result = Args::try_parse_from(["mudud", "--unknown"]);
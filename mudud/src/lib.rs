@@ -14,11 +14,16 @@
 
 use clap::Parser;
 use mudu::common::result::RS;
+use mudu::data_type::timestamptz::TimestampTzValue;
+use mudu::error::ErrorCode;
+use mudu::mudu_error;
+use mudu_kernel::server::backup::{RestoreTarget, restore_backup_sync};
 use mudu_runtime::backend::backend::Backend;
 use mudu_runtime::backend::mudud_cfg::{MuduDBCfg, init_mudud_cfg, load_mudud_cfg};
 use mudu_sys::task::async_::wait_for_shutdown_signal;
 use mudu_sys::task::sync::{SJoinHandle, spawn_thread_named};
 use mudu_utils::notifier::{Notifier, Waiter, notify_wait};
+use std::path::Path;
 use tracing::info;
 
 /// Command-line arguments for `mudud`.
@@ -37,6 +42,8 @@ pub enum Command {
     Serve(ServeArgs),
     /// Write a default configuration file to the current directory.
     InitCfg,
+    /// Restore an online backup into the configured data directory.
+    Restore(RestoreArgs),
 }
 
 /// Arguments for the `serve` subcommand.
@@ -47,6 +54,25 @@ pub struct ServeArgs {
     pub cfg_path: Option<String>,
 }
 
+/// Arguments for the `restore` subcommand.
+#[derive(Debug, Parser, Default)]
+pub struct RestoreArgs {
+    /// Path to mududb configuration TOML file.
+    #[arg(short = 'c', long = "cfg", value_name = "FILE")]
+    pub cfg_path: Option<String>,
+    /// Backup directory written by `mcli backup`.
+    #[arg(long = "from", value_name = "DIR")]
+    pub from: String,
+    /// WAL archive to replay past the backup. Defaults to `wal_archive_dir`
+    /// of the configuration.
+    #[arg(long = "archive", value_name = "DIR")]
+    pub archive: Option<String>,
+    /// Stop the replay at this WAL LSN, or at this commit time
+    /// (e.g. "2026-10-18 12:00:00+00:00").
+    #[arg(long = "until", value_name = "LSN|TIMESTAMP")]
+    pub until: Option<String>,
+}
+
 /// Load configuration and run the backend until shutdown.
 pub fn serve(args: ServeArgs) -> RS<()> {
     let (stop_notifier, stop_waiter) = notify_wait();
@@ -93,6 +119,54 @@ where
     runner(cfg, stop_waiter)
 }
 
+/// Restore a backup into the configured `db_path`, which must be missing or
+/// empty. The server is not running during a restore; the next `serve`
+/// recovers the restored directory.
+pub fn restore(args: RestoreArgs) -> RS<()> {
+    let cfg = load_mudud_cfg(args.cfg_path)?;
+    let until = args
+        .until
+        .as_deref()
+        .map(parse_restore_target)
+        .transpose()?;
+    let archive = args.archive.or(cfg.wal_archive_dir);
+    let report = restore_backup_sync(
+        Path::new(&args.from),
+        Path::new(&cfg.db_path),
+        archive.as_deref().map(Path::new),
+        until,
+    )?;
+    info!(
+        from = %args.from,
+        data_path = %cfg.db_path,
+        archived_chunks = report.archived_chunks,
+        truncated_logs = report.truncated_logs,
+        "mudud restore finished"
+    );
+    Ok(())
+}
+
+/// Parse the `--until` argument of `restore`: an integer is a WAL LSN,
+/// anything else a timestamp with time zone.
+pub fn parse_restore_target(until: &str) -> RS<RestoreTarget> {
+    if let Ok(lsn) = until.parse::<u64>() {
+        return Ok(RestoreTarget::Lsn(lsn));
+    }
+    let timestamp = TimestampTzValue::parse(until).map_err(|e| {
+        mudu_error!(
+            ErrorCode::InvalidArgument,
+            format!("invalid restore target \"{}\": {}", until, e)
+        )
+    })?;
+    let micros = u64::try_from(timestamp.epoch_micros_utc()).map_err(|_| {
+        mudu_error!(
+            ErrorCode::InvalidArgument,
+            format!("restore target \"{}\" is before the Unix epoch", until)
+        )
+    })?;
+    Ok(RestoreTarget::UnixMicros(micros))
+}
+
 /// Write a default configuration file to the current directory.
 pub fn init_config() -> RS<()> {
     init_mudud_cfg()
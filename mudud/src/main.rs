@@ -14,7 +14,7 @@
 
 use clap::Parser;
 use mudu_utils::log::log_setup_ex;
-use mudud::{Args, Command, ServeArgs, init_config, serve};
+use mudud::{Args, Command, ServeArgs, init_config, restore, serve};
 use tracing::error;
 
 fn main() {
@@ -34,6 +34,7 @@ fn main() {
     let r = match args.command {
         Some(Command::InitCfg) => init_config(),
         Some(Command::Serve(serve_args)) => serve(serve_args),
+        Some(Command::Restore(restore_args)) => restore(restore_args),
         None => serve(ServeArgs::default()),
     };
     match r {
@@ -1,9 +1,21 @@
 //! Column definition AST node.
 
+use crate::ast::expr_literal::ExprLiteral;
 use mudu::common::id::AttrIndex;
 use mudu_binding::universal::uni_data_type::UniDataType;
 use mudu_binding::universal::uni_data_value::UniDataValue;
 
+/// `DEFAULT` clause of a column definition.
+#[derive(Clone, Debug)]
+pub enum ColumnDefault {
+    /// A literal value, e.g. `DEFAULT 0`.
+    Literal(ExprLiteral),
+    /// The statement time: `DEFAULT now()` or `DEFAULT CURRENT_TIMESTAMP`.
+    Now,
+    /// A freshly generated object id per row: `DEFAULT gen_oid()`.
+    GenOid,
+}
+
 /// Column definition inside a `CREATE TABLE` statement.
 #[derive(Clone, Debug)]
 pub struct ColumnDef {
@@ -12,6 +24,7 @@ pub struct ColumnDef {
     data_type_param: Option<Vec<UniDataValue>>,
     opt_primary_key_index: Option<AttrIndex>,
     nullable: bool,
+    default: Option<ColumnDefault>,
     index: AttrIndex,
 }
 
@@ -28,6 +41,7 @@ impl ColumnDef {
             data_type_param,
             opt_primary_key_index: None,
             nullable: true,
+            default: None,
             index: AttrIndex::MAX,
         }
     }
@@ -80,6 +94,16 @@ impl ColumnDef {
         self.nullable = nullable;
     }
 
+    /// Return the `DEFAULT` clause, if any.
+    pub fn default_value(&self) -> Option<&ColumnDefault> {
+        self.default.as_ref()
+    }
+
+    /// Set the `DEFAULT` clause.
+    pub fn set_default_value(&mut self, default: Option<ColumnDefault>) {
+        self.default = default;
+    }
+
     /// Set the table-level column index.
     pub fn set_index(&mut self, index: AttrIndex) {
         self.index = index;
@@ -0,0 +1,47 @@
+//! `CASE` expression AST node.
+
+use crate::ast::ast_node::ASTNode;
+use crate::ast::expression::ExprType;
+
+/// `CASE [operand] WHEN .. THEN .. [ELSE ..] END` expression.
+///
+/// With an operand (`CASE x WHEN 1 THEN ..`) every `WHEN` holds a value
+/// compared with the operand; without one every `WHEN` holds a condition.
+#[derive(Clone, Debug)]
+pub struct ExprCase {
+    operand: Option<ExprType>,
+    branches: Vec<(ExprType, ExprType)>,
+    else_result: Option<ExprType>,
+}
+
+impl ExprCase {
+    /// Create a new `CASE` expression from its `WHEN .. THEN ..` branches.
+    pub fn new(
+        operand: Option<ExprType>,
+        branches: Vec<(ExprType, ExprType)>,
+        else_result: Option<ExprType>,
+    ) -> Self {
+        Self {
+            operand,
+            branches,
+            else_result,
+        }
+    }
+
+    /// Return the operand of a simple `CASE x WHEN ..`, if any.
+    pub fn operand(&self) -> Option<&ExprType> {
+        self.operand.as_ref()
+    }
+
+    /// Return the `(WHEN, THEN)` pairs in statement order.
+    pub fn branches(&self) -> &Vec<(ExprType, ExprType)> {
+        &self.branches
+    }
+
+    /// Return the `ELSE` result, if any.
+    pub fn else_result(&self) -> Option<&ExprType> {
+        self.else_result.as_ref()
+    }
+}
+
+impl ASTNode for ExprCase {}
@@ -0,0 +1,31 @@
+//! `CAST` expression AST node.
+
+use crate::ast::ast_node::ASTNode;
+use crate::ast::expression::ExprType;
+use crate::ast::type_declare::TypeDeclare;
+
+/// `CAST(expr AS type)` expression.
+#[derive(Clone, Debug)]
+pub struct ExprCast {
+    expr: ExprType,
+    target: TypeDeclare,
+}
+
+impl ExprCast {
+    /// Create a new cast of `expr` to the `target` type.
+    pub fn new(expr: ExprType, target: TypeDeclare) -> Self {
+        Self { expr, target }
+    }
+
+    /// Return the converted expression.
+    pub fn expr(&self) -> &ExprType {
+        &self.expr
+    }
+
+    /// Return the target type.
+    pub fn target(&self) -> &TypeDeclare {
+        &self.target
+    }
+}
+
+impl ASTNode for ExprCast {}
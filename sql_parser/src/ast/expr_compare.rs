@@ -8,6 +8,7 @@ use crate::ast::expr_literal::ExprLiteral;
 use crate::ast::expr_name::ExprName;
 
 use crate::ast::expr_operator::ValueCompare;
+use crate::ast::expression::ExprType;
 
 // currently, we only support a ExprField compare with ExprLiteral
 /// Comparison expression (`=`, `<`, `>`, etc.) with left and right operands.
@@ -75,6 +76,39 @@ impl Debug for ExprCompare {
 
 impl ASTNode for ExprCompare {}
 
+/// Comparison whose operands are arbitrary scalar expressions, e.g.
+/// `price * qty > 100` or `lower(name) = 'bob'`.
+#[derive(Clone, Debug)]
+pub struct ExprScalarCompare {
+    op: ValueCompare,
+    left: ExprType,
+    right: ExprType,
+}
+
+impl ExprScalarCompare {
+    /// Create a new comparison between two scalar expressions.
+    pub fn new(op: ValueCompare, left: ExprType, right: ExprType) -> Self {
+        Self { op, left, right }
+    }
+
+    /// Return the comparison operator.
+    pub fn op(&self) -> &ValueCompare {
+        &self.op
+    }
+
+    /// Return the left operand.
+    pub fn left(&self) -> &ExprType {
+        &self.left
+    }
+
+    /// Return the right operand.
+    pub fn right(&self) -> &ExprType {
+        &self.right
+    }
+}
+
+impl ASTNode for ExprScalarCompare {}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::unwrap_used)]
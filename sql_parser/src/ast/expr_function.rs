@@ -1,26 +1,46 @@
 use crate::ast::ast_node::ASTNode;
 use crate::ast::expr_name::ExprName;
+use crate::ast::expression::ExprType;
+use mudu::common::result::RS;
+use mudu::error::ErrorCode;
+use mudu::mudu_error;
 
-/// Argument of a function call in a select list.
+/// Argument of a function call.
 #[derive(Clone, Debug)]
 pub enum FunctionArg {
     /// The `*` argument, e.g. `COUNT(*)`.
     Star,
     /// A single column reference argument, e.g. `SUM(col)`.
     Column(ExprName),
+    /// Any other scalar expression argument, e.g. `round(price * 1.1, 2)`.
+    Expression(ExprType),
 }
 
-/// Function call expression in a select list, e.g. `COUNT(*)` or `SUM(col)`.
+/// Function call expression, e.g. `COUNT(*)`, `SUM(col)` or `substr(name, 1, 3)`.
 #[derive(Clone, Debug)]
 pub struct ExprFunction {
     name: String,
-    arg: FunctionArg,
+    args: Vec<FunctionArg>,
 }
 
 impl ExprFunction {
     /// Create a function call expression with the given name and argument.
     pub fn new(name: String, arg: FunctionArg) -> Self {
-        Self { name, arg }
+        Self {
+            name,
+            args: vec![arg],
+        }
+    }
+
+    /// Create a function call expression with one or more arguments.
+    pub fn with_args(name: String, args: Vec<FunctionArg>) -> RS<Self> {
+        if args.is_empty() {
+            return Err(mudu_error!(
+                ErrorCode::Parse,
+                format!("function {} requires at least one argument", name)
+            ));
+        }
+        Ok(Self { name, args })
     }
 
     /// Return the function name as written in the SQL text.
@@ -28,9 +48,14 @@ impl ExprFunction {
         &self.name
     }
 
-    /// Return the function argument.
+    /// Return the first function argument.
     pub fn arg(&self) -> &FunctionArg {
-        &self.arg
+        &self.args[0]
+    }
+
+    /// Return all function arguments in call order.
+    pub fn args(&self) -> &Vec<FunctionArg> {
+        &self.args
     }
 }
 
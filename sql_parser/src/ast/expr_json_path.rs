@@ -0,0 +1,63 @@
+//! JSON path applied to a column with the `->` and `->>` operators.
+
+use std::fmt::{Display, Formatter};
+
+/// One step of a [`JsonPath`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum JsonPathStep {
+    /// Object member (`-> 'name'`).
+    Key(String),
+    /// Array element (`-> 0`); a negative index counts from the end.
+    Index(i64),
+}
+
+/// Chain of `->`/`->>` steps on a JSON column.
+///
+/// `->` selects a JSON value; `->>` selects it as text and can only be the
+/// last step.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct JsonPath {
+    steps: Vec<JsonPathStep>,
+    as_text: bool,
+}
+
+impl JsonPath {
+    /// Create an empty path.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a step; `as_text` marks a `->>` step.
+    pub fn push(&mut self, step: JsonPathStep, as_text: bool) {
+        self.steps.push(step);
+        self.as_text = as_text;
+    }
+
+    /// Return the path steps in application order.
+    pub fn steps(&self) -> &[JsonPathStep] {
+        &self.steps
+    }
+
+    /// Return `true` if the last step is `->>`.
+    pub fn as_text(&self) -> bool {
+        self.as_text
+    }
+}
+
+impl Display for JsonPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let last = self.steps.len().saturating_sub(1);
+        for (i, step) in self.steps.iter().enumerate() {
+            let op = if i == last && self.as_text {
+                "->>"
+            } else {
+                "->"
+            };
+            match step {
+                JsonPathStep::Key(key) => write!(f, "{}'{}'", op, key.replace('\'', "''"))?,
+                JsonPathStep::Index(index) => write!(f, "{}{}", op, index)?,
+            }
+        }
+        Ok(())
+    }
+}
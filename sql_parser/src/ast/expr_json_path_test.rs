@@ -0,0 +1,29 @@
+//! Unit tests for `JsonPath`.
+
+#![allow(missing_docs)]
+
+use crate::ast::expr_json_path::{JsonPath, JsonPathStep};
+
+#[test]
+fn push_records_steps_and_text_flag() {
+    let mut path = JsonPath::new();
+    assert!(path.steps().is_empty());
+    path.push(JsonPathStep::Key("tags".to_string()), false);
+    path.push(JsonPathStep::Index(0), true);
+    assert_eq!(
+        path.steps(),
+        &[
+            JsonPathStep::Key("tags".to_string()),
+            JsonPathStep::Index(0)
+        ]
+    );
+    assert!(path.as_text());
+}
+
+#[test]
+fn display_renders_operators() {
+    let mut path = JsonPath::new();
+    path.push(JsonPathStep::Key("a'b".to_string()), false);
+    path.push(JsonPathStep::Index(-1), true);
+    assert_eq!(path.to_string(), "->'a''b'->>-1");
+}
@@ -4,7 +4,7 @@ use crate::ast::ast_node::ASTNode;
 use crate::ast::expr_operator::LogicalConnective;
 use crate::ast::expression::ExprType;
 
-/// Logical connective expression (`AND`) with left and right operands.
+/// Logical connective expression (`AND`, `OR`) with left and right operands.
 #[derive(Clone, Debug)]
 pub struct ExprLogical {
     op: LogicalConnective,
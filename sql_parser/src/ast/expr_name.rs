@@ -1,9 +1,12 @@
 use crate::ast::ast_node::ASTNode;
+use crate::ast::expr_json_path::JsonPath;
 
 /// Named identifier expression (table, column, or alias name).
 #[derive(Clone, Debug)]
 pub struct ExprName {
     name: String,
+    qualifier: Option<String>,
+    json_path: Option<JsonPath>,
 }
 
 impl ExprName {
@@ -11,6 +14,8 @@ impl ExprName {
     pub fn new() -> Self {
         Self {
             name: "".to_string(),
+            qualifier: None,
+            json_path: None,
         }
     }
 
@@ -23,6 +28,26 @@ impl ExprName {
     pub fn name(&self) -> &String {
         &self.name
     }
+
+    /// Set the table name or alias qualifying the identifier (`t` in `t.c`).
+    pub fn set_qualifier(&mut self, qualifier: String) {
+        self.qualifier = Some(qualifier)
+    }
+
+    /// Return the qualifying table name or alias, if any.
+    pub fn qualifier(&self) -> Option<&str> {
+        self.qualifier.as_deref()
+    }
+
+    /// Set the JSON path applied to the column (`c -> 'k'`).
+    pub fn set_json_path(&mut self, json_path: JsonPath) {
+        self.json_path = Some(json_path)
+    }
+
+    /// Return the JSON path applied to the column, if any.
+    pub fn json_path(&self) -> Option<&JsonPath> {
+        self.json_path.as_ref()
+    }
 }
 
 impl Default for ExprName {
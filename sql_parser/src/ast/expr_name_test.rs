@@ -25,3 +25,13 @@ fn set_name_updates_identifier() {
     name.set_name("user_id".to_string());
     assert_eq!(name.name(), "user_id");
 }
+
+#[test]
+fn set_qualifier_records_table_reference() {
+    let mut name = ExprName::new();
+    assert!(name.qualifier().is_none());
+    name.set_name("user_id".to_string());
+    name.set_qualifier("u".to_string());
+    assert_eq!(name.qualifier(), Some("u"));
+    assert_eq!(name.name(), "user_id");
+}
@@ -8,9 +8,9 @@ use std::collections::HashMap;
 pub enum Operator {
     /// Value comparison operator (`=`, `<`, `>`, etc.).
     OValueCompare(ValueCompare),
-    /// Logical connective operator (`AND`).
+    /// Logical connective operator (`AND`, `OR`).
     OLogicalConnective(LogicalConnective),
-    /// Arithmetic operator (`+`, `-`, `*`, `/`).
+    /// Arithmetic operator (`+`, `-`, `*`, `/`, `%`, `||`).
     OArithmetic(Arithmetic),
 }
 
@@ -25,6 +25,10 @@ pub enum Arithmetic {
     MULTIPLE,
     /// Division (`/`).
     DIVIDE,
+    /// Remainder (`%`).
+    MODULO,
+    /// String concatenation (`||`).
+    CONCAT,
 }
 
 /// Value comparison operators.
@@ -49,6 +53,8 @@ pub enum ValueCompare {
 pub enum LogicalConnective {
     /// Logical AND.
     AND,
+    /// Logical OR.
+    OR,
 }
 
 fn name2op(name: String) -> RS<Operator> {
@@ -60,10 +66,13 @@ fn name2op(name: String) -> RS<Operator> {
         (">=", Operator::OValueCompare(ValueCompare::GE)),
         ("!=", Operator::OValueCompare(ValueCompare::NE)),
         ("AND", Operator::OLogicalConnective(LogicalConnective::AND)),
+        ("OR", Operator::OLogicalConnective(LogicalConnective::OR)),
         ("-", Operator::OArithmetic(Arithmetic::MINUS)),
         ("+", Operator::OArithmetic(Arithmetic::PLUS)),
         ("*", Operator::OArithmetic(Arithmetic::MULTIPLE)),
         ("/", Operator::OArithmetic(Arithmetic::DIVIDE)),
+        ("%", Operator::OArithmetic(Arithmetic::MODULO)),
+        ("||", Operator::OArithmetic(Arithmetic::CONCAT)),
     ];
     let map = HashMap::from(array);
     // SQL keywords and operators are case-insensitive (`and` == `AND`).
@@ -100,6 +109,7 @@ impl Operator {
             None => false,
             Some(c) => match c {
                 LogicalConnective::AND => true,
+                LogicalConnective::OR => false,
             },
         }
     }
@@ -42,6 +42,10 @@ fn from_name_parses_logical_and_arithmetic_operators() {
         Operator::from_name("AND".to_string()).unwrap(),
         Operator::OLogicalConnective(LogicalConnective::AND)
     ));
+    assert!(matches!(
+        Operator::from_name("or".to_string()).unwrap(),
+        Operator::OLogicalConnective(LogicalConnective::OR)
+    ));
     assert!(matches!(
         Operator::from_name("+".to_string()).unwrap(),
         Operator::OArithmetic(Arithmetic::PLUS)
@@ -58,15 +62,23 @@ fn from_name_parses_logical_and_arithmetic_operators() {
         Operator::from_name("/".to_string()).unwrap(),
         Operator::OArithmetic(Arithmetic::DIVIDE)
     ));
+    assert!(matches!(
+        Operator::from_name("%".to_string()).unwrap(),
+        Operator::OArithmetic(Arithmetic::MODULO)
+    ));
+    assert!(matches!(
+        Operator::from_name("||".to_string()).unwrap(),
+        Operator::OArithmetic(Arithmetic::CONCAT)
+    ));
 }
 
 #[test]
 fn from_name_rejects_unknown_operator() {
-    let result = Operator::from_name("OR".to_string());
+    let result = Operator::from_name("XOR".to_string());
     match result {
         Err(err) => {
             assert_eq!(err.ec(), ErrorCode::Parse);
-            assert!(err.to_string().contains("OR"));
+            assert!(err.to_string().contains("XOR"));
         }
         Ok(_) => panic!("expected an error for unknown operator"),
     }
@@ -92,6 +104,9 @@ fn is_logical_and_identifies_and_only() {
     assert!(Operator::from_name("AND".to_string())
         .unwrap()
         .is_logical_and());
+    assert!(!Operator::from_name("OR".to_string())
+        .unwrap()
+        .is_logical_and());
     assert!(!Operator::from_name("=".to_string())
         .unwrap()
         .is_logical_and());
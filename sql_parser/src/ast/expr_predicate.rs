@@ -0,0 +1,153 @@
+//! Predicate expression AST nodes (`IN`, `BETWEEN`, `LIKE`, `IS NULL`).
+
+use crate::ast::ast_node::ASTNode;
+use crate::ast::expr_item::ExprItem;
+
+/// `item [NOT] IN (value, ...)` predicate.
+#[derive(Clone, Debug)]
+pub struct ExprInList {
+    item: ExprItem,
+    list: Vec<ExprItem>,
+    negated: bool,
+}
+
+impl ExprInList {
+    /// Create a new `IN` list predicate.
+    pub fn new(item: ExprItem, list: Vec<ExprItem>, negated: bool) -> Self {
+        Self {
+            item,
+            list,
+            negated,
+        }
+    }
+
+    /// Return the tested operand.
+    pub fn item(&self) -> &ExprItem {
+        &self.item
+    }
+
+    /// Return the list values in statement order.
+    pub fn list(&self) -> &Vec<ExprItem> {
+        &self.list
+    }
+
+    /// Return `true` for `NOT IN`.
+    pub fn negated(&self) -> bool {
+        self.negated
+    }
+}
+
+impl ASTNode for ExprInList {}
+
+/// `item [NOT] BETWEEN low AND high` predicate.
+#[derive(Clone, Debug)]
+pub struct ExprBetween {
+    item: ExprItem,
+    low: ExprItem,
+    high: ExprItem,
+    negated: bool,
+}
+
+impl ExprBetween {
+    /// Create a new `BETWEEN` predicate.
+    pub fn new(item: ExprItem, low: ExprItem, high: ExprItem, negated: bool) -> Self {
+        Self {
+            item,
+            low,
+            high,
+            negated,
+        }
+    }
+
+    /// Return the tested operand.
+    pub fn item(&self) -> &ExprItem {
+        &self.item
+    }
+
+    /// Return the inclusive lower bound.
+    pub fn low(&self) -> &ExprItem {
+        &self.low
+    }
+
+    /// Return the inclusive upper bound.
+    pub fn high(&self) -> &ExprItem {
+        &self.high
+    }
+
+    /// Return `true` for `NOT BETWEEN`.
+    pub fn negated(&self) -> bool {
+        self.negated
+    }
+}
+
+impl ASTNode for ExprBetween {}
+
+/// `item [NOT] LIKE pattern` or `item [NOT] ILIKE pattern` predicate.
+#[derive(Clone, Debug)]
+pub struct ExprLike {
+    item: ExprItem,
+    pattern: ExprItem,
+    case_insensitive: bool,
+    negated: bool,
+}
+
+impl ExprLike {
+    /// Create a new `LIKE` predicate.
+    pub fn new(item: ExprItem, pattern: ExprItem, case_insensitive: bool, negated: bool) -> Self {
+        Self {
+            item,
+            pattern,
+            case_insensitive,
+            negated,
+        }
+    }
+
+    /// Return the tested operand.
+    pub fn item(&self) -> &ExprItem {
+        &self.item
+    }
+
+    /// Return the pattern, where `%` matches any run of characters and `_`
+    /// matches one character.
+    pub fn pattern(&self) -> &ExprItem {
+        &self.pattern
+    }
+
+    /// Return `true` for `ILIKE`.
+    pub fn case_insensitive(&self) -> bool {
+        self.case_insensitive
+    }
+
+    /// Return `true` for `NOT LIKE` and `NOT ILIKE`.
+    pub fn negated(&self) -> bool {
+        self.negated
+    }
+}
+
+impl ASTNode for ExprLike {}
+
+/// `item IS [NOT] NULL` predicate.
+#[derive(Clone, Debug)]
+pub struct ExprIsNull {
+    item: ExprItem,
+    negated: bool,
+}
+
+impl ExprIsNull {
+    /// Create a new `IS NULL` predicate.
+    pub fn new(item: ExprItem, negated: bool) -> Self {
+        Self { item, negated }
+    }
+
+    /// Return the tested operand.
+    pub fn item(&self) -> &ExprItem {
+        &self.item
+    }
+
+    /// Return `true` for `IS NOT NULL`.
+    pub fn negated(&self) -> bool {
+        self.negated
+    }
+}
+
+impl ASTNode for ExprIsNull {}
@@ -1,6 +1,8 @@
-//! Visitor helpers for extracting comparison expressions.
+//! Visitor helpers for extracting comparison expressions and `WHERE` conjuncts.
 
 use crate::ast::expr_compare::ExprCompare;
+use crate::ast::expr_item::ExprItem;
+use crate::ast::expr_operator::LogicalConnective;
 use crate::ast::expression::ExprType;
 use mudu::common::result::RS;
 use mudu::error::ErrorCode;
@@ -13,10 +15,13 @@ impl ExprVisitor {
     /// Recursively extract [`ExprCompare`] nodes from a logical or comparison
     /// expression tree.
     ///
-    /// Returns an error if the expression contains an unsupported node type.
+    /// Returns an error if the expression contains an unsupported node type,
+    /// including an `OR`.
     pub fn extract_expr_compare_list(expr: ExprType, vec: &mut Vec<ExprCompare>) -> RS<()> {
         match expr {
-            ExprType::Logical(expr_logical) => {
+            ExprType::Logical(expr_logical)
+                if matches!(expr_logical.op(), LogicalConnective::AND) =>
+            {
                 let left = expr_logical.left().clone();
                 let right = expr_logical.right().clone();
                 Self::extract_expr_compare_list(left, vec)?;
@@ -34,4 +39,55 @@ impl ExprVisitor {
         }
         Ok(())
     }
+
+    /// Split a `WHERE` expression into its top-level `AND` conjuncts.
+    ///
+    /// When every conjunct is a plain column comparison they go to
+    /// `compare_list`; a comparison on a JSON path is not plain.
+    /// Otherwise every conjunct, comparisons included, goes to
+    /// `condition_list` in statement order, so that placeholders keep their
+    /// order.
+    pub fn split_where_conjuncts(
+        expr: ExprType,
+        compare_list: &mut Vec<ExprCompare>,
+        condition_list: &mut Vec<ExprType>,
+    ) -> RS<()> {
+        let mut conjuncts = Vec::new();
+        Self::collect_conjuncts(expr, &mut conjuncts)?;
+        let plain_compare = |conjunct: &ExprType| match conjunct {
+            ExprType::Compare(compare) => [compare.left(), compare.right()].iter().all(
+                |item| !matches!(item, ExprItem::ItemName(name) if name.json_path().is_some()),
+            ),
+            _ => false,
+        };
+        if conjuncts.iter().all(plain_compare) {
+            for conjunct in conjuncts {
+                if let ExprType::Compare(expr) = conjunct {
+                    compare_list.push((*expr).clone());
+                }
+            }
+        } else {
+            condition_list.extend(conjuncts);
+        }
+        Ok(())
+    }
+
+    fn collect_conjuncts(expr: ExprType, conjuncts: &mut Vec<ExprType>) -> RS<()> {
+        match expr {
+            ExprType::Logical(expr_logical)
+                if matches!(expr_logical.op(), LogicalConnective::AND) =>
+            {
+                Self::collect_conjuncts(expr_logical.left().clone(), conjuncts)?;
+                Self::collect_conjuncts(expr_logical.right().clone(), conjuncts)?;
+            }
+            ExprType::Value(_) | ExprType::Arithmetic(_) => {
+                return Err(mudu_error!(
+                    ErrorCode::Parse,
+                    "expected a boolean expression in the where clause"
+                ));
+            }
+            _ => conjuncts.push(expr),
+        }
+        Ok(())
+    }
 }
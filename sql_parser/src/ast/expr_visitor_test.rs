@@ -56,3 +56,47 @@ fn extract_rejects_unsupported_expression() {
     let err = ExprVisitor::extract_expr_compare_list(unsupported, &mut list).unwrap_err();
     assert_eq!(err.ec(), ErrorCode::Parse);
 }
+
+#[test]
+fn extract_rejects_or() {
+    let or = ExprType::Logical(Arc::new(ExprLogical::new(
+        LogicalConnective::OR,
+        compare_expr(),
+        compare_expr(),
+    )));
+    let mut list = Vec::new();
+    let err = ExprVisitor::extract_expr_compare_list(or, &mut list).unwrap_err();
+    assert_eq!(err.ec(), ErrorCode::Parse);
+}
+
+#[test]
+fn split_keeps_every_conjunct_in_order_beside_an_or() {
+    let or = ExprType::Logical(Arc::new(ExprLogical::new(
+        LogicalConnective::OR,
+        compare_expr(),
+        compare_expr(),
+    )));
+    let and = ExprType::Logical(Arc::new(ExprLogical::new(
+        LogicalConnective::AND,
+        compare_expr(),
+        or,
+    )));
+    let mut compare_list = Vec::new();
+    let mut condition_list = Vec::new();
+    ExprVisitor::split_where_conjuncts(and, &mut compare_list, &mut condition_list).unwrap();
+    assert!(compare_list.is_empty());
+    assert_eq!(condition_list.len(), 2);
+    assert!(matches!(condition_list[0], ExprType::Compare(_)));
+    assert!(matches!(condition_list[1], ExprType::Logical(_)));
+
+    let and = ExprType::Logical(Arc::new(ExprLogical::new(
+        LogicalConnective::AND,
+        compare_expr(),
+        compare_expr(),
+    )));
+    let mut compare_list = Vec::new();
+    let mut condition_list = Vec::new();
+    ExprVisitor::split_where_conjuncts(and, &mut compare_list, &mut condition_list).unwrap();
+    assert_eq!(compare_list.len(), 2);
+    assert!(condition_list.is_empty());
+}
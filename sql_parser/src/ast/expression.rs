@@ -1,18 +1,41 @@
 use crate::ast::expr_arithmetic::ExprArithmetic;
-use crate::ast::expr_compare::ExprCompare;
+use crate::ast::expr_case::ExprCase;
+use crate::ast::expr_cast::ExprCast;
+use crate::ast::expr_compare::{ExprCompare, ExprScalarCompare};
+use crate::ast::expr_function::ExprFunction;
 use crate::ast::expr_item::ExprItem;
 use crate::ast::expr_logical::ExprLogical;
+use crate::ast::expr_predicate::{ExprBetween, ExprInList, ExprIsNull, ExprLike};
 use std::sync::Arc;
 
 /// Top-level expression enum.
 #[derive(Clone, Debug)]
 pub enum ExprType {
-    /// Logical connective expression (`AND`).
+    /// Logical connective expression (`AND`, `OR`).
     Logical(Arc<ExprLogical>),
+    /// Logical negation (`NOT`).
+    Not(Arc<ExprType>),
     /// Comparison expression (`=`, `<`, `>`, etc.).
     Compare(Arc<ExprCompare>),
+    /// `[NOT] IN` list predicate.
+    InList(Arc<ExprInList>),
+    /// `[NOT] BETWEEN` predicate.
+    Between(Arc<ExprBetween>),
+    /// `[NOT] LIKE` and `[NOT] ILIKE` predicate.
+    Like(Arc<ExprLike>),
+    /// `IS [NOT] NULL` predicate.
+    IsNull(Arc<ExprIsNull>),
     /// Atomic value expression (name, literal, or placeholder).
     Value(Arc<ExprItem>),
-    /// Arithmetic expression (`+`, `-`, `*`, `/`).
+    /// Arithmetic expression (`+`, `-`, `*`, `/`, `%`) or string
+    /// concatenation (`||`).
     Arithmetic(Arc<ExprArithmetic>),
+    /// Comparison between scalar expressions other than plain items.
+    ScalarCompare(Arc<ExprScalarCompare>),
+    /// Scalar function call, e.g. `lower(name)`.
+    Function(Arc<ExprFunction>),
+    /// `CASE .. END` expression.
+    Case(Arc<ExprCase>),
+    /// `CAST(expr AS type)` expression.
+    Cast(Arc<ExprCast>),
 }
@@ -6,19 +6,27 @@
 
 /// Common trait implemented by all AST node types.
 pub mod ast_node;
+/// `CASE` expression AST node.
+pub mod expr_case;
+/// `CAST` expression AST node.
+pub mod expr_cast;
 /// Comparison expression AST node (`=`, `<`, `>`, etc.).
 pub mod expr_compare;
 #[cfg(test)]
 mod expr_compare_test;
-/// Function call expression AST node (`COUNT(*)`, `SUM(col)`, etc.).
+/// Function call expression AST node (`COUNT(*)`, `lower(col)`, etc.).
 pub mod expr_function;
 /// Atomic expression items such as column names, literals, and placeholders.
 pub mod expr_item;
+/// JSON path of the `->` and `->>` operators.
+pub mod expr_json_path;
+#[cfg(test)]
+mod expr_json_path_test;
 /// Literal expression AST node (`NULL`, typed datum literals).
 pub mod expr_literal;
 #[cfg(test)]
 mod expr_literal_test;
-/// Logical connective expression AST node (`AND`).
+/// Logical connective expression AST node (`AND`, `OR`).
 pub mod expr_logical;
 #[cfg(test)]
 mod expr_logical_test;
@@ -30,6 +38,8 @@ mod expr_name_test;
 pub mod expr_operator;
 #[cfg(test)]
 mod expr_operator_test;
+/// Predicate expression AST nodes (`IN`, `BETWEEN`, `LIKE`, `IS NULL`).
+pub mod expr_predicate;
 mod expr_visitor;
 #[cfg(test)]
 mod expr_visitor_test;
@@ -38,6 +48,10 @@ pub mod expression;
 
 /// SQL parser entry point and statement dispatch.
 pub mod parser;
+/// `ORDER BY` items and `HAVING` comparisons of a `SELECT` statement.
+pub mod select_clause;
+/// `JOIN` clause of a `SELECT` statement.
+pub mod select_join;
 /// Select list term with optional alias.
 pub mod select_term;
 
@@ -53,15 +67,22 @@ mod stmt_delete_test;
 /// Column definition AST node.
 pub mod column_def;
 
-mod expr_arithmetic;
+/// Arithmetic expression AST node (`+`, `-`, `*`, `/`, `%`, `||`).
+pub mod expr_arithmetic;
 #[cfg(test)]
 mod parser_test;
+/// `ALTER PARTITION RULE` statement AST node.
+pub mod stmt_alter_partition_rule;
+/// `ALTER TABLE` statement AST node.
+pub mod stmt_alter_table;
 /// `COPY ... FROM` statement AST node.
 pub mod stmt_copy_from;
 /// `COPY ... TO` statement AST node.
 pub mod stmt_copy_to;
 /// `CREATE TYPE FILESYSTEM` statement AST node.
 pub mod stmt_create_fs_type;
+/// `CREATE INDEX` statement AST node.
+pub mod stmt_create_index;
 /// `CREATE PARTITION PLACEMENT` statement AST node.
 pub mod stmt_create_partition_placement;
 /// `CREATE PARTITION RULE` statement AST node.
@@ -70,10 +91,14 @@ pub mod stmt_create_partition_rule;
 mod stmt_create_partition_rule_test;
 /// `DROP` statement enum.
 pub mod stmt_drop;
+/// `DROP INDEX` statement AST node.
+pub mod stmt_drop_index;
 /// `DROP TABLE` statement AST node.
 pub mod stmt_drop_table;
 /// `DROP TYPE` statement AST node.
 pub mod stmt_drop_type;
+/// `EXPLAIN` statement AST node.
+pub mod stmt_explain;
 /// `INSERT` statement AST node.
 pub mod stmt_insert;
 /// List of parsed SQL statements.
@@ -92,5 +117,7 @@ pub mod stmt_type;
 pub mod stmt_update;
 #[cfg(test)]
 mod stmt_update_test;
+/// Table-level `UNIQUE` and `FOREIGN KEY` constraints of `CREATE TABLE`.
+pub mod table_constraint;
 /// SQL data type declaration AST node.
 pub mod type_declare;
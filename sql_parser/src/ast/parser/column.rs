@@ -1,7 +1,9 @@
 use super::context::ParseContext;
+use super::ddl::find_child_of_kind;
 use super::error::{node_or_descendant_has_kind, ts_node_context_string};
 use super::SQLParser;
-use crate::ast::column_def::ColumnDef;
+use crate::ast::column_def::{ColumnDef, ColumnDefault};
+use crate::ast::expr_literal::ExprLiteral;
 use crate::ast::stmt_create_table::StmtCreateTable;
 use crate::ts_const::{ts_field_name, ts_kind_id, ts_kind_name};
 use mudu::common::id::AttrIndex;
@@ -24,6 +26,15 @@ impl SQLParser {
         node: Node,
         stmt: &mut StmtCreateTable,
     ) -> RS<()> {
+        let column_def = self.visit_column_def(context, node)?;
+        stmt.add_column_def(column_def);
+        for n in column_check_constraints(node) {
+            stmt.add_check_constraint(self.visit_check_constraint(context, n)?);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn visit_column_def(&self, context: &ParseContext, node: Node) -> RS<ColumnDef> {
         let opt_n = node.child_by_field_name(ts_field_name::COLUMN_NAME);
         let n_column_name = rs_option(opt_n, "")?;
         let column_name = self.visit_identifier(context, n_column_name)?;
@@ -36,20 +47,25 @@ impl SQLParser {
         let iter = node.children_by_field_name(ts_field_name::COLUMN_CONSTRAINT, &mut cursor);
         let mut index_map = HashMap::new();
         for n in iter {
-            self.visit_column_constraint(n, &mut column_def, &mut index_map)?;
+            self.visit_column_constraint(context, n, &mut column_def, &mut index_map)?;
         }
-
-        stmt.add_column_def(column_def);
-
-        Ok(())
+        Ok(column_def)
     }
 
     pub(crate) fn visit_column_constraint(
         &self,
+        context: &ParseContext,
         node: Node,
         column_def: &mut ColumnDef,
         index_map: &mut HashMap<String, AttrIndex>,
     ) -> RS<()> {
+        if node
+            .child_by_field_name(ts_field_name::CHECK_CONSTRAINT)
+            .is_some()
+        {
+            // Added to the table by `visit_column_definition`.
+            return Ok(());
+        }
         if node
             .child_by_field_name(ts_field_name::PRIMARY_KEY)
             .is_some()
@@ -66,9 +82,57 @@ impl SQLParser {
         {
             column_def.set_nullable(false);
         }
+        if find_child_of_kind(node, ts_kind_id::KEYWORD_DEFAULT).is_some() {
+            column_def.set_default_value(self.visit_column_default(context, node)?);
+        }
         Ok(())
     }
 
+    /// Parse the expression of a `DEFAULT` column constraint. Literals,
+    /// `now()`/`CURRENT_TIMESTAMP` and `gen_oid()` are supported; `DEFAULT
+    /// NULL` is the same as no default.
+    fn visit_column_default(
+        &self,
+        context: &ParseContext,
+        node: Node,
+    ) -> RS<Option<ColumnDefault>> {
+        if let Some(n) = find_child_of_kind(node, ts_kind_id::LITERAL) {
+            return match self.visit_literal(context, n)? {
+                ExprLiteral::Null => Ok(None),
+                literal => Ok(Some(ColumnDefault::Literal(literal))),
+            };
+        }
+        if find_child_of_kind(node, ts_kind_id::KEYWORD_CURRENT_TIMESTAMP).is_some() {
+            return Ok(Some(ColumnDefault::Now));
+        }
+        if let Some(n) = find_child_of_kind(node, ts_kind_id::INVOCATION) {
+            let mut cursor = n.walk();
+            let has_parameter = n
+                .children_by_field_name(ts_field_name::PARAMETER, &mut cursor)
+                .next()
+                .is_some();
+            let name = find_child_of_kind(n, ts_kind_id::OBJECT_REFERENCE)
+                .map(|name| self.visit_object_reference(context, name))
+                .transpose()?
+                .unwrap_or_default();
+            if !has_parameter {
+                if name.eq_ignore_ascii_case("now") {
+                    return Ok(Some(ColumnDefault::Now));
+                }
+                if name.eq_ignore_ascii_case("gen_oid") {
+                    return Ok(Some(ColumnDefault::GenOid));
+                }
+            }
+        }
+        Err(mudu_error!(
+            ErrorCode::NotImplemented,
+            format!(
+                "unsupported DEFAULT expression in {}",
+                ts_node_context_string(context.parse_str(), &node)?
+            )
+        ))
+    }
+
     pub(crate) fn visit_data_type(
         &self,
         context: &ParseContext,
@@ -90,6 +154,9 @@ impl SQLParser {
         };
         let kind = child.kind_id();
         let ret = match kind {
+            ts_kind_id::KEYWORD_BOOLEAN => (UniDataType::Scalar(UniScalar::Bool), None),
+            ts_kind_id::TINYINT => (UniDataType::Scalar(UniScalar::I8), None),
+            ts_kind_id::SMALLINT => (UniDataType::Scalar(UniScalar::I16), None),
             ts_kind_id::INT => (UniDataType::Scalar(UniScalar::I32), None),
             ts_kind_id::BIGINT => (UniDataType::Scalar(UniScalar::I64), None),
             ts_kind_id::HUGEINT => (UniDataType::Scalar(UniScalar::I128), None),
@@ -133,6 +200,10 @@ impl SQLParser {
                 UniDataType::Scalar(UniScalar::TimestampTz),
                 self.visit_optional_precision_param(context, node)?,
             ),
+            ts_kind_id::KEYWORD_UUID => (UniDataType::Scalar(UniScalar::Uuid), None),
+            ts_kind_id::KEYWORD_JSON | ts_kind_id::KEYWORD_JSONB => {
+                (UniDataType::Scalar(UniScalar::Json), None)
+            }
             _ => {
                 return Err(mudu_error!(
                     ErrorCode::NotImplemented,
@@ -216,3 +287,12 @@ impl SQLParser {
         }
     }
 }
+
+/// The `CHECK` constraints among the column constraints of a column
+/// definition.
+pub(super) fn column_check_constraints(node: Node) -> Vec<Node> {
+    let mut cursor = node.walk();
+    node.children_by_field_name(ts_field_name::COLUMN_CONSTRAINT, &mut cursor)
+        .filter_map(|n| n.child_by_field_name(ts_field_name::CHECK_CONSTRAINT))
+        .collect()
+}
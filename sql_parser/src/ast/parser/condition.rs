@@ -0,0 +1,49 @@
+//! Standalone boolean conditions.
+//!
+//! A condition that is not part of a statement is parsed by the grammar as
+//! the `WHERE` clause of a select.
+
+use super::SQLParser;
+use crate::ast::expr_logical::ExprLogical;
+use crate::ast::expr_operator::LogicalConnective;
+use crate::ast::expression::ExprType;
+use crate::ast::stmt_type::StmtType;
+use mudu::common::result::RS;
+use mudu::error::ErrorCode;
+use mudu::mudu_error;
+use std::sync::Arc;
+
+// Join `operands` left to right with `op`.
+fn connect(op: LogicalConnective, operands: Vec<ExprType>) -> RS<ExprType> {
+    let mut iter = operands.into_iter();
+    let first = iter
+        .next()
+        .ok_or_else(|| mudu_error!(ErrorCode::Parse, "empty where condition"))?;
+    Ok(iter.fold(first, |left, right| {
+        ExprType::Logical(Arc::new(ExprLogical::new(op, left, right)))
+    }))
+}
+
+impl SQLParser {
+    // Parse a boolean condition as the `WHERE` clause of a select.
+    pub(super) fn parse_condition(&self, condition: &str) -> RS<ExprType> {
+        let sql = format!("select * from t where {}", condition);
+        let stmt_list = self.parse_standard(&sql)?;
+        let select = match stmt_list.stmts().first() {
+            Some(StmtType::Select(select)) => select,
+            _ => {
+                return Err(mudu_error!(
+                    ErrorCode::Parse,
+                    format!("invalid where condition {}", condition)
+                ))
+            }
+        };
+        let mut operands: Vec<ExprType> = select
+            .get_where_predicate()
+            .iter()
+            .map(|compare| ExprType::Compare(Arc::new(compare.clone())))
+            .collect();
+        operands.extend(select.get_where_condition().iter().cloned());
+        connect(LogicalConnective::AND, operands)
+    }
+}
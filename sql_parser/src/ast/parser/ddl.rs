@@ -1,35 +1,124 @@
-//! DDL (CREATE/DROP TABLE) statement parser.
+//! DDL (CREATE/ALTER/DROP TABLE, CREATE/DROP INDEX) statement parser.
 
+use super::column::column_check_constraints;
 use super::context::ParseContext;
+use super::error::ts_node_context_string;
 use super::SQLParser;
+use crate::ast::stmt_alter_table::{AlterTableAction, StmtAlterTable};
+use crate::ast::stmt_create_index::StmtCreateIndex;
 use crate::ast::stmt_create_table::StmtCreateTable;
+use crate::ast::stmt_drop_index::StmtDropIndex;
 use crate::ast::stmt_drop_table::StmtDropTable;
-use crate::ts_const::{ts_field_name, ts_kind_id};
+use crate::ast::stmt_type::{StmtCommand, StmtType};
+use crate::ast::table_constraint::{
+    CheckConstraint, ForeignKeyConstraint, ReferentialAction, UniqueConstraint,
+};
+use crate::ts_const::{ts_field_name, ts_kind_id, ts_kind_name};
 use mudu::common::result::RS;
-use mudu::common::result_of::rs_option;
+use mudu::common::result_of::{rs_of_opt, rs_option};
 use mudu::error::ErrorCode;
 use mudu::mudu_error;
 use std::collections::HashMap;
 use tree_sitter::Node;
 
 impl SQLParser {
-    pub(crate) fn visit_drop_statement(
-        &self,
-        context: &ParseContext,
-        node: Node,
-    ) -> RS<StmtDropTable> {
+    pub(crate) fn visit_drop_statement(&self, context: &ParseContext, node: Node) -> RS<StmtType> {
         let opt_child = node.child(0);
         let child = rs_option(opt_child, "")?;
         let kind = child.kind_id();
         match kind {
             ts_kind_id::DROP_TABLE => {
                 let s = self.visit_drop_table_statement(context, child)?;
-                Ok(s)
+                Ok(StmtType::Command(StmtCommand::DropTable(s)))
+            }
+            ts_kind_id::DROP_INDEX => {
+                let s = self.visit_drop_index(context, child)?;
+                Ok(StmtType::Command(StmtCommand::DropIndex(s)))
             }
             _ => Err(mudu_error!(ErrorCode::NotImplemented)),
         }
     }
 
+    /// Parse `DROP INDEX [IF EXISTS] <name>`.
+    pub(crate) fn visit_drop_index(&self, context: &ParseContext, node: Node) -> RS<StmtDropIndex> {
+        for (field, clause) in [
+            (ts_field_name::CONCURRENTLY, "CONCURRENTLY"),
+            (ts_field_name::DROP_BEHAVIOR, "CASCADE or RESTRICT"),
+            (ts_field_name::OBJECT_REFERENCE, "ON <table>"),
+        ] {
+            if node.child_by_field_name(field).is_some() {
+                return Err(mudu_error!(
+                    ErrorCode::NotImplemented,
+                    format!("DROP INDEX with {} is not implemented", clause)
+                ));
+            }
+        }
+        let if_exists = node.child_by_field_name(ts_field_name::IF_EXIST).is_some();
+        let opt = node.child_by_field_name(ts_field_name::IDENTIFIER_NAME);
+        let n_name = rs_option(opt, "no index name in drop index statement")?;
+        let index_name = self.visit_identifier(context, n_name)?;
+        Ok(StmtDropIndex::new(index_name, if_exists))
+    }
+
+    /// Parse `CREATE INDEX [IF NOT EXISTS] <name> ON <table> (<columns>)`.
+    pub(crate) fn visit_create_index(
+        &self,
+        context: &ParseContext,
+        node: Node,
+    ) -> RS<StmtCreateIndex> {
+        for (field, clause) in [
+            (ts_field_name::UNIQUE, "UNIQUE"),
+            (ts_field_name::CONCURRENTLY, "CONCURRENTLY"),
+            (ts_field_name::ONLY, "ONLY"),
+            (ts_field_name::USING, "USING"),
+            (ts_field_name::WHERE, "WHERE"),
+        ] {
+            if node.child_by_field_name(field).is_some() {
+                return Err(mudu_error!(
+                    ErrorCode::NotImplemented,
+                    format!("CREATE INDEX with {} is not implemented", clause)
+                ));
+            }
+        }
+        let opt = node.child_by_field_name(ts_field_name::INDEX_NAME);
+        let n_name = rs_of_opt(opt, || {
+            mudu_error!(ErrorCode::Parse, "create index must name the index")
+        })?;
+        let index_name = self.visit_identifier(context, n_name)?;
+        let if_not_exists = node
+            .child_by_field_name(ts_field_name::IF_NOT_EXISTS)
+            .is_some();
+        let opt = node.child_by_field_name(ts_field_name::OBJECT_REFERENCE);
+        let n_table = rs_option(opt, "no table in create index statement")?;
+        let table_name = self.visit_object_reference(context, n_table)?;
+
+        let opt = node.child_by_field_name(ts_field_name::ORDERED_COLUMNS);
+        let n_columns = rs_option(opt, "no column list in create index statement")?;
+        let mut columns = Vec::new();
+        let mut cursor = n_columns.walk();
+        for n_column in n_columns.named_children(&mut cursor) {
+            let mut column_cursor = n_column.walk();
+            if n_column
+                .named_children(&mut column_cursor)
+                .any(|n| n.kind_id() == ts_kind_id::DIRECTION)
+            {
+                return Err(mudu_error!(
+                    ErrorCode::NotImplemented,
+                    "ASC and DESC index columns are not implemented"
+                ));
+            }
+            let opt = n_column.child_by_field_name(ts_field_name::NAME);
+            let n_name = rs_option(opt, "no column name in create index column list")?;
+            columns.push(self.visit_column(context, n_name)?);
+        }
+        Ok(StmtCreateIndex::new(
+            index_name,
+            table_name,
+            columns,
+            if_not_exists,
+        ))
+    }
+
     pub(crate) fn visit_drop_table_statement(
         &self,
         context: &ParseContext,
@@ -48,6 +137,103 @@ impl SQLParser {
         Ok(StmtDropTable::new(object, if_exist))
     }
 
+    pub(crate) fn visit_alter_table(
+        &self,
+        context: &ParseContext,
+        node: Node,
+    ) -> RS<StmtAlterTable> {
+        let mut table_name = None;
+        let mut if_exists = false;
+        let mut actions = Vec::new();
+        for i in 0..node.child_count() {
+            let Some(c) = node.child(i as _) else {
+                continue;
+            };
+            match c.kind_id() {
+                ts_kind_id::KEYWORD_EXISTS => if_exists = true,
+                ts_kind_id::OBJECT_REFERENCE => {
+                    table_name = Some(self.visit_object_reference(context, c)?);
+                }
+                ts_kind_id::ADD_COLUMN => {
+                    let opt_n = find_child_of_kind(c, ts_kind_id::COLUMN_DEFINITION);
+                    let n = rs_option(opt_n, "no column definition in add column")?;
+                    if !column_check_constraints(n).is_empty() {
+                        return Err(mudu_error!(
+                            ErrorCode::NotImplemented,
+                            "CHECK constraints on an added column are not supported"
+                        ));
+                    }
+                    let column_def = self.visit_column_def(context, n)?;
+                    actions.push(AlterTableAction::AddColumn(column_def));
+                }
+                ts_kind_id::DROP_COLUMN => {
+                    let opt_n = c.child_by_field_name(ts_field_name::NAME);
+                    let n = rs_option(opt_n, "no column name in drop column")?;
+                    actions.push(AlterTableAction::DropColumn {
+                        column_name: self.visit_identifier(context, n)?,
+                        if_exists: find_child_of_kind(c, ts_kind_id::KEYWORD_EXISTS).is_some(),
+                    });
+                }
+                ts_kind_id::RENAME_COLUMN => {
+                    let opt_n = c.child_by_field_name(ts_field_name::OLD_NAME);
+                    let n_old = rs_option(opt_n, "no old column name in rename column")?;
+                    let opt_n = c.child_by_field_name(ts_field_name::NEW_NAME);
+                    let n_new = rs_option(opt_n, "no new column name in rename column")?;
+                    actions.push(AlterTableAction::RenameColumn {
+                        old_name: self.visit_identifier(context, n_old)?,
+                        new_name: self.visit_identifier(context, n_new)?,
+                    });
+                }
+                ts_kind_id::ALTER_COLUMN => {
+                    actions.push(self.visit_alter_column(context, c)?);
+                }
+                ts_kind_id::ADD_CONSTRAINT
+                | ts_kind_id::MODIFY_COLUMN
+                | ts_kind_id::CHANGE_COLUMN
+                | ts_kind_id::RENAME_OBJECT
+                | ts_kind_id::SET_SCHEMA
+                | ts_kind_id::CHANGE_OWNERSHIP => {
+                    return Err(mudu_error!(
+                        ErrorCode::NotImplemented,
+                        format!("alter table action {} is not supported", c.kind())
+                    ));
+                }
+                _ => {}
+            }
+        }
+        let table_name = rs_option(table_name, "no table name in alter table statement")?;
+        if actions.is_empty() {
+            return Err(mudu_error!(
+                ErrorCode::Parse,
+                "alter table statement has no action"
+            ));
+        }
+        Ok(StmtAlterTable::new(table_name, if_exists, actions))
+    }
+
+    fn visit_alter_column(&self, context: &ParseContext, node: Node) -> RS<AlterTableAction> {
+        let opt_n = node.child_by_field_name(ts_field_name::NAME);
+        let n_name = rs_option(opt_n, "no column name in alter column")?;
+        let column_name = self.visit_identifier(context, n_name)?;
+        match node.child_by_field_name(ts_field_name::TYPE) {
+            Some(n_type) => {
+                let (data_type, data_type_param) = self.visit_data_type(context, n_type)?;
+                Ok(AlterTableAction::AlterColumnType {
+                    column_name,
+                    data_type,
+                    data_type_param,
+                })
+            }
+            None => Err(mudu_error!(
+                ErrorCode::NotImplemented,
+                format!(
+                    "alter column {} only supports changing the type",
+                    column_name
+                )
+            )),
+        }
+    }
+
     pub(crate) fn visit_create_table_statement(
         &self,
         context: &ParseContext,
@@ -109,11 +295,109 @@ impl SQLParser {
     ) -> RS<()> {
         if let Some(n) = node.child_by_field_name(ts_field_name::PRIMARY_KEY_CONSTRAINT) {
             self.visit_primary_key_constraint(context, n, stmt)?;
+        } else if find_child_of_kind(node, ts_kind_id::ORDERED_COLUMNS).is_some() {
+            self.visit_key_constraint(context, node, stmt)?;
+        } else if let Some(n) = node.child_by_field_name(ts_field_name::CHECK_CONSTRAINT) {
+            stmt.add_check_constraint(self.visit_check_constraint(context, n)?);
         }
 
         Ok(())
     }
 
+    /// Parse `[CONSTRAINT name] CHECK (<condition>)`, keeping the condition
+    /// text as written.
+    pub(crate) fn visit_check_constraint(
+        &self,
+        context: &ParseContext,
+        node: Node,
+    ) -> RS<CheckConstraint> {
+        let name = node
+            .child_by_field_name(ts_field_name::NAME)
+            .map(|n| self.visit_identifier(context, n))
+            .transpose()?;
+        let opt_n = node.child_by_field_name(ts_field_name::PREDICATE);
+        let n_predicate = rs_option(opt_n, "no condition in check constraint")?;
+        let text = ts_node_context_string(context.parse_str(), &n_predicate)?;
+        let condition = self.visit_expression(context, n_predicate)?;
+        Ok(CheckConstraint::new(name, text, condition))
+    }
+
+    /// Parse `[UNIQUE | FOREIGN] KEY [name] (<column>, ...) [REFERENCES
+    /// <table> (<column>, ...) [ON DELETE CASCADE]]`.
+    pub(crate) fn visit_key_constraint(
+        &self,
+        context: &ParseContext,
+        node: Node,
+        stmt: &mut StmtCreateTable,
+    ) -> RS<()> {
+        let name = node
+            .child_by_field_name(ts_field_name::NAME)
+            .map(|n| self.visit_identifier(context, n))
+            .transpose()?;
+        let mut cursor = node.walk();
+        let column_lists = node
+            .children(&mut cursor)
+            .filter(|c| c.kind_id() == ts_kind_id::ORDERED_COLUMNS)
+            .map(|c| self.visit_ordered_columns(context, c))
+            .collect::<RS<Vec<_>>>()?;
+        let mut column_lists = column_lists.into_iter();
+        let columns = column_lists.next().unwrap_or_default();
+        if find_child_of_kind(node, ts_kind_id::KEYWORD_FOREIGN).is_some() {
+            let opt_n_table = find_child_of_kind(node, ts_kind_id::OBJECT_REFERENCE);
+            let n_table = rs_option(
+                opt_n_table,
+                "no REFERENCES clause in foreign key constraint",
+            )?;
+            let ref_table = self.visit_object_reference(context, n_table)?;
+            let ref_columns = column_lists.next().unwrap_or_default();
+            if ref_columns.len() != columns.len() {
+                return Err(mudu_error!(
+                    ErrorCode::Parse,
+                    format!(
+                        "foreign key references {} columns of {} but has {} columns",
+                        ref_columns.len(),
+                        ref_table,
+                        columns.len()
+                    )
+                ));
+            }
+            let on_delete = if find_child_of_kind(node, ts_kind_id::KEYWORD_CASCADE).is_some() {
+                ReferentialAction::Cascade
+            } else {
+                ReferentialAction::Restrict
+            };
+            stmt.add_foreign_key(ForeignKeyConstraint::new(
+                name,
+                columns,
+                ref_table,
+                ref_columns,
+                on_delete,
+            ));
+        } else if find_child_of_kind(node, ts_kind_id::KEYWORD_UNIQUE).is_some() {
+            stmt.add_unique_constraint(UniqueConstraint::new(name, columns));
+        } else {
+            return Err(mudu_error!(
+                ErrorCode::NotImplemented,
+                "KEY and INDEX table constraints are not supported, use CREATE INDEX"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Parse the column names of an `ordered_columns` list.
+    fn visit_ordered_columns(&self, context: &ParseContext, node: Node) -> RS<Vec<String>> {
+        let mut cursor = node.walk();
+        let columns = node
+            .named_children(&mut cursor)
+            .filter(|c| c.kind() == ts_kind_name::S_COLUMN)
+            .map(|c| {
+                let name = c.child_by_field_name(ts_field_name::NAME).unwrap_or(c);
+                self.visit_column(context, name)
+            })
+            .collect::<RS<Vec<_>>>()?;
+        Ok(columns)
+    }
+
     pub(crate) fn visit_primary_key_constraint(
         &self,
         context: &ParseContext,
@@ -141,3 +425,9 @@ impl SQLParser {
         Ok(())
     }
 }
+
+pub(super) fn find_child_of_kind(node: Node, kind: u16) -> Option<Node> {
+    (0..node.child_count())
+        .filter_map(|i| node.child(i as _))
+        .find(|c| c.kind_id() == kind)
+}
@@ -47,10 +47,15 @@ impl SQLParser {
                 let stmt = self.visit_create_table_statement(context, child)?;
                 Ok(StmtType::Command(StmtCommand::CreateTable(stmt)))
             }
-            ts_kind_id::DROP_STATEMENT => {
-                let stmt = self.visit_drop_statement(context, child)?;
-                Ok(StmtType::Command(StmtCommand::DropTable(stmt)))
+            ts_kind_id::ALTER_TABLE => {
+                let stmt = self.visit_alter_table(context, child)?;
+                Ok(StmtType::Command(StmtCommand::AlterTable(stmt)))
             }
+            ts_kind_id::CREATE_INDEX => {
+                let stmt = self.visit_create_index(context, child)?;
+                Ok(StmtType::Command(StmtCommand::CreateIndex(stmt)))
+            }
+            ts_kind_id::DROP_STATEMENT => self.visit_drop_statement(context, child),
             _ => Err(mudu_error!(ErrorCode::NotImplemented)),
         }
     }
@@ -0,0 +1,67 @@
+//! `ON CONFLICT` and `RETURNING` clauses of `INSERT`, `UPDATE` and `DELETE`
+//! statements.
+
+use super::context::ParseContext;
+use super::SQLParser;
+use crate::ast::select_term::SelectTerm;
+use crate::ast::stmt_insert::{OnConflict, OnConflictAction};
+use crate::ts_const::ts_field_name;
+use mudu::common::result::RS;
+use mudu::common::result_of::rs_option;
+use tree_sitter::Node;
+
+impl SQLParser {
+    /// Parse an `ON CONFLICT [(cols)] DO NOTHING | DO UPDATE SET ...` node.
+    pub(crate) fn visit_on_conflict(&self, context: &ParseContext, node: Node) -> RS<OnConflict> {
+        let mut columns = Vec::new();
+        if let Some(n) = node.child_by_field_name(ts_field_name::COLUMN_LIST) {
+            self.visit_column_list(context, n, &mut |name| {
+                columns.push(name);
+                Ok(())
+            })?;
+        }
+        let action = if node
+            .child_by_field_name(ts_field_name::DO_NOTHING)
+            .is_some()
+        {
+            OnConflictAction::DoNothing
+        } else {
+            let opt = node.child_by_field_name(ts_field_name::SET_VALUES);
+            let n_set_values = rs_option(opt, "no set values in ON CONFLICT DO UPDATE clause")?;
+            OnConflictAction::DoUpdate(self.visit_set_values(context, n_set_values)?)
+        };
+        Ok(OnConflict::new(columns, action))
+    }
+
+    /// Parse a `RETURNING <select list>` node.
+    pub(crate) fn visit_returning(
+        &self,
+        context: &ParseContext,
+        node: Node,
+    ) -> RS<Vec<SelectTerm>> {
+        let opt = node.child_by_field_name(ts_field_name::SELECT_EXPRESSION);
+        let n_select_expression = rs_option(opt, "no select list in returning clause")?;
+        let mut terms = Vec::new();
+        for i in 0..n_select_expression.child_count() {
+            let Some(n) = n_select_expression.child(i as _) else {
+                continue;
+            };
+            if n.kind().eq("term") {
+                terms.push(self.visit_term(context, n)?);
+            }
+        }
+        Ok(terms)
+    }
+
+    /// Parse the optional `RETURNING` field of a write statement node.
+    pub(crate) fn visit_opt_returning(
+        &self,
+        context: &ParseContext,
+        node: Node,
+    ) -> RS<Vec<SelectTerm>> {
+        match node.child_by_field_name(ts_field_name::RETURNING) {
+            Some(n) => self.visit_returning(context, n),
+            None => Ok(Vec::new()),
+        }
+    }
+}
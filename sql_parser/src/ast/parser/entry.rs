@@ -2,18 +2,22 @@
 
 use super::context::ParseContext;
 use super::partition::{
-    parse_partition_placement_item, parse_range_partition_def, parse_table_partition_suffix,
+    parse_list_partition_def, parse_partition_placement_item, parse_range_partition_def,
+    parse_table_partition_suffix,
 };
 use super::utils::{
     contains_ignore_ascii_case, find_keyword_position, find_matching_paren, split_top_level_csv,
     starts_with_ignore_ascii_case,
 };
 use super::SQLParser;
+use crate::ast::expression::ExprType;
+use crate::ast::stmt_alter_partition_rule::{StmtAlterPartitionRule, StmtAlterPartitionRuleAction};
 use crate::ast::stmt_create_fs_type::{FsTypeKind, StmtCreateFsType};
 use crate::ast::stmt_create_partition_placement::StmtCreatePartitionPlacement;
 use crate::ast::stmt_create_partition_rule::StmtCreatePartitionRule;
 use crate::ast::stmt_create_table::StmtCreateTable;
 use crate::ast::stmt_drop_type::StmtDropType;
+use crate::ast::stmt_explain::StmtExplain;
 use crate::ast::stmt_list::StmtList;
 use crate::ast::stmt_type::{StmtCommand, StmtType};
 use crate::ts_const::{ts_field_name, ts_kind_id};
@@ -55,6 +59,13 @@ impl SQLParser {
             )])));
         }
 
+        if starts_with_ignore_ascii_case(normalized, "alter partition rule ") {
+            let stmt = self.parse_alter_partition_rule_custom(normalized)?;
+            return Ok(Some(StmtList::new(vec![StmtType::Command(
+                StmtCommand::AlterPartitionRule(stmt),
+            )])));
+        }
+
         if starts_with_ignore_ascii_case(normalized, "create partition placement ") {
             let stmt = self.parse_create_partition_placement_custom(normalized)?;
             return Ok(Some(StmtList::new(vec![StmtType::Command(
@@ -138,34 +149,98 @@ impl SQLParser {
         Ok(stmt)
     }
 
-    /// Parse a `CREATE PARTITION RULE ...` statement.
+    /// Parse the condition of a `CHECK` constraint, as returned by
+    /// [`CheckConstraint::text`](crate::ast::table_constraint::CheckConstraint::text).
+    pub fn parse_check_condition(&self, text: &str) -> RS<ExprType> {
+        self.parse_condition(text)
+    }
+
+    /// Parse a `CREATE PARTITION RULE name {RANGE | HASH | LIST} ...`
+    /// statement.
     pub(crate) fn parse_create_partition_rule_custom(
         &self,
         sql: &str,
     ) -> RS<StmtCreatePartitionRule> {
         let prefix = "create partition rule ";
         let rest = sql[prefix.len()..].trim();
-        let range_pos = find_keyword_position(rest, "range").ok_or_else(|| {
-            mudu_error!(ErrorCode::Parse, "create partition rule must contain RANGE")
-        })?;
-        let rule_name = rest[..range_pos].trim();
-        if rule_name.is_empty() {
+        let name_end = rest
+            .find(|c: char| c.is_whitespace() || c == '(')
+            .unwrap_or(rest.len());
+        let rule_name = rest[..name_end].trim();
+        let rest = rest[name_end..].trim();
+        let scheme_end = rest
+            .find(|c: char| c.is_whitespace() || c == '(')
+            .unwrap_or(rest.len());
+        let scheme = &rest[..scheme_end];
+        let body = rest[scheme_end..].trim();
+        if rule_name.is_empty()
+            || ["range", "hash", "list"]
+                .iter()
+                .any(|keyword| rule_name.eq_ignore_ascii_case(keyword))
+        {
             return Err(mudu_error!(
                 ErrorCode::Parse,
                 "partition rule name is empty"
             ));
         }
-
-        let range_body = rest[range_pos + "range".len()..].trim();
-        if !range_body.starts_with('(') {
+        if !body.starts_with('(') {
+            return Err(mudu_error!(
+                ErrorCode::Parse,
+                format!(
+                    "partition rule {} clause must be wrapped in parentheses",
+                    scheme.to_ascii_uppercase()
+                )
+            ));
+        }
+        let close_index = find_matching_paren(body, 0)?;
+        let inner = body[1..close_index].trim();
+        let trailing = body[close_index + 1..].trim();
+        if scheme.eq_ignore_ascii_case("hash") {
+            let columns = split_top_level_csv(inner)
+                .into_iter()
+                .map(|column| column.trim().to_string())
+                .filter(|column| !column.is_empty())
+                .collect::<Vec<_>>();
+            let partitions_keyword = "partitions";
+            if columns.is_empty() || !starts_with_ignore_ascii_case(trailing, partitions_keyword) {
+                return Err(mudu_error!(
+                    ErrorCode::Parse,
+                    "partition rule HASH clause must be HASH (columns) PARTITIONS n"
+                ));
+            }
+            let partition_count = trailing[partitions_keyword.len()..]
+                .trim()
+                .parse::<u64>()
+                .map_err(|e| mudu_error!(ErrorCode::Parse, "invalid HASH partition count", e))?;
+            return Ok(StmtCreatePartitionRule::new_hash(
+                rule_name.to_string(),
+                columns,
+                partition_count,
+            ));
+        }
+        if !trailing.is_empty() {
             return Err(mudu_error!(
                 ErrorCode::Parse,
-                "partition rule RANGE clause must be wrapped in parentheses"
+                format!("unexpected text after partition rule: {}", trailing)
             ));
         }
-        let close_index = find_matching_paren(range_body, 0)?;
-        let inner = range_body[1..close_index].trim();
         let defs = split_top_level_csv(inner);
+        if scheme.eq_ignore_ascii_case("list") {
+            let partitions = defs
+                .into_iter()
+                .map(parse_list_partition_def)
+                .collect::<RS<Vec<_>>>()?;
+            return Ok(StmtCreatePartitionRule::new_list(
+                rule_name.to_string(),
+                partitions,
+            ));
+        }
+        if !scheme.eq_ignore_ascii_case("range") {
+            return Err(mudu_error!(
+                ErrorCode::Parse,
+                "create partition rule must contain RANGE, HASH or LIST"
+            ));
+        }
         let mut partitions = Vec::with_capacity(defs.len());
         for def in defs {
             partitions.push(parse_range_partition_def(def)?);
@@ -216,6 +291,80 @@ impl SQLParser {
         ))
     }
 
+    /// Parse an `ALTER PARTITION RULE <rule> SPLIT AT (<value>, ...) [INTO <name>]`
+    /// or `ALTER PARTITION RULE <rule> MOVE PARTITION <p> TO WORKER <w>`
+    /// statement.
+    pub(crate) fn parse_alter_partition_rule_custom(
+        &self,
+        sql: &str,
+    ) -> RS<StmtAlterPartitionRule> {
+        let prefix = "alter partition rule ";
+        let rest = sql[prefix.len()..].trim();
+        let (rule_name, rest) = match rest.find(char::is_whitespace) {
+            Some(index) => (&rest[..index], rest[index..].trim()),
+            None => (rest, ""),
+        };
+        validate_identifier(rule_name, "rule name")?;
+        let action = if starts_with_ignore_ascii_case(rest, "split at ") {
+            let rest = rest["split at ".len()..].trim();
+            if !rest.starts_with('(') {
+                return Err(mudu_error!(
+                    ErrorCode::Parse,
+                    "SPLIT AT value list must be wrapped in parentheses"
+                ));
+            }
+            let close_index = find_matching_paren(rest, 0)?;
+            let at = split_top_level_csv(&rest[1..close_index])
+                .into_iter()
+                .map(|value| value.trim().as_bytes().to_vec())
+                .collect::<Vec<_>>();
+            if at.is_empty() || at.iter().any(|value| value.is_empty()) {
+                return Err(mudu_error!(
+                    ErrorCode::Parse,
+                    "SPLIT AT must list the split point values"
+                ));
+            }
+            let tail = rest[close_index + 1..].trim();
+            let new_partition = if tail.is_empty() {
+                None
+            } else if starts_with_ignore_ascii_case(tail, "into ") {
+                let name = tail["into ".len()..].trim();
+                validate_identifier(name, "partition name")?;
+                Some(name.to_string())
+            } else {
+                return Err(mudu_error!(
+                    ErrorCode::Parse,
+                    format!("unexpected text after SPLIT AT: {}", tail)
+                ));
+            };
+            StmtAlterPartitionRuleAction::Split { at, new_partition }
+        } else if starts_with_ignore_ascii_case(rest, "move partition ") {
+            let rest = rest["move partition ".len()..].trim();
+            let to_worker = find_keyword_position(rest, "to worker").ok_or_else(|| {
+                mudu_error!(ErrorCode::Parse, "MOVE PARTITION must contain TO WORKER")
+            })?;
+            let partition = rest[..to_worker].trim();
+            let worker = rest[to_worker + "to worker".len()..].trim();
+            validate_identifier(partition, "partition name")?;
+            if worker.is_empty() || worker.contains(char::is_whitespace) {
+                return Err(mudu_error!(
+                    ErrorCode::Parse,
+                    format!("invalid worker id {}", worker)
+                ));
+            }
+            StmtAlterPartitionRuleAction::Move {
+                partition: partition.to_string(),
+                worker: worker.to_string(),
+            }
+        } else {
+            return Err(mudu_error!(
+                ErrorCode::Parse,
+                "alter partition rule must use SPLIT AT or MOVE PARTITION"
+            ));
+        };
+        Ok(StmtAlterPartitionRule::new(rule_name.to_string(), action))
+    }
+
     /// Parse a `CREATE TYPE FILESYSTEM FILE|DIRECTORY <name>` statement.
     pub(crate) fn parse_create_fs_type_custom(&self, sql: &str) -> RS<StmtCreateFsType> {
         let prefix = "create type filesystem ";
@@ -319,7 +468,8 @@ impl SQLParser {
         }
     }
 
-    /// Visit a single statement node.
+    /// Visit a single statement node; an `EXPLAIN [ANALYZE]` prefix wraps
+    /// the statement, which must be a `SELECT`.
     pub(crate) fn visit_statement(&self, context: &ParseContext, node: Node) -> RS<StmtType> {
         let opt_stmt = node.child_by_field_name(ts_field_name::STMT_GUT);
         let d_stmt = match opt_stmt {
@@ -329,13 +479,36 @@ impl SQLParser {
             }
         };
         let stmt = self.visit_statement_gut(context, d_stmt)?;
-        Ok(stmt)
+        if node.child_by_field_name(ts_field_name::EXPLAIN).is_none() {
+            return Ok(stmt);
+        }
+        if node.child_by_field_name(ts_field_name::VERBOSE).is_some() {
+            return Err(mudu_error!(
+                ErrorCode::NotImplemented,
+                "EXPLAIN VERBOSE is not implemented"
+            ));
+        }
+        let analyze = node.child_by_field_name(ts_field_name::ANALYZE).is_some();
+        match stmt {
+            StmtType::Select(select) => Ok(StmtType::Explain(StmtExplain::new(analyze, select))),
+            _ => Err(mudu_error!(
+                ErrorCode::NotImplemented,
+                "EXPLAIN supports only SELECT statements"
+            )),
+        }
     }
 }
 
 /// Validate that a type name is a non-empty identifier of alphanumeric
 /// characters or underscores that does not start with a digit.
 pub(crate) fn validate_type_name(name: &str) -> RS<()> {
+    validate_identifier(name, "type name")
+}
+
+/// Validate that `name` is a non-empty identifier of alphanumeric characters
+/// or underscores that does not start with a digit; `what` names it in the
+/// error message.
+fn validate_identifier(name: &str, what: &str) -> RS<()> {
     let valid = !name.is_empty()
         && !name.as_bytes()[0].is_ascii_digit()
         && name
@@ -344,7 +517,7 @@ pub(crate) fn validate_type_name(name: &str) -> RS<()> {
     if !valid {
         return Err(mudu_error!(
             ErrorCode::Parse,
-            format!("invalid type name {}", name)
+            format!("invalid {} {}", what, name)
         ));
     }
     Ok(())
@@ -354,11 +527,14 @@ pub(crate) fn validate_type_name(name: &str) -> RS<()> {
 #[path = "entry_test.rs"]
 mod entry_test;
 
-/// True when the SQL text contains syntax only the custom parser handles
-/// (partition DDL, filesystem types, or partitioned `CREATE TABLE`).
+/// True when the SQL text contains syntax only the custom parser handles:
+/// `CREATE PARTITION RULE`, `ALTER PARTITION RULE`,
+/// `CREATE PARTITION PLACEMENT`, `PARTITION BY GLOBAL RULE` on
+/// `CREATE TABLE`, or `CREATE TYPE FILESYSTEM`.
 pub(crate) fn contains_custom_statement_syntax(sql: &str) -> bool {
     let lowered = sql.to_lowercase();
     lowered.contains("create partition rule ")
+        || lowered.contains("alter partition rule ")
         || lowered.contains("create partition placement ")
         || lowered.contains("partition by global rule ")
         || lowered.contains("create type filesystem ")
@@ -1,8 +1,16 @@
 #![allow(clippy::unwrap_used)]
 #![allow(clippy::panic)]
 
+use crate::ast::expr_item::{ExprItem, ExprValue};
+use crate::ast::expr_operator::ValueCompare;
 use crate::ast::parser::SQLParser;
+use crate::ast::select_clause::OrderTarget;
+use crate::ast::select_join::JoinKind;
+use crate::ast::select_term::SelectField;
+use crate::ast::stmt_alter_partition_rule::StmtAlterPartitionRuleAction;
 use crate::ast::stmt_create_fs_type::FsTypeKind;
+use crate::ast::stmt_create_partition_rule::StmtPartitionScheme;
+use crate::ast::stmt_insert::OnConflictAction;
 use crate::ast::stmt_type::{StmtCommand, StmtType};
 use mudu::error::ErrorCode;
 
@@ -48,6 +56,131 @@ fn create_partition_rule_success_and_errors() {
     assert_eq!(bad.unwrap_err().ec(), ErrorCode::Parse);
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn create_hash_and_list_partition_rules() {
+    let stmt = parse("CREATE PARTITION RULE by_user HASH (user_id, region) PARTITIONS 4;")
+        .stmts()
+        .first()
+        .unwrap()
+        .clone();
+    let StmtType::Command(StmtCommand::CreatePartitionRule(rule)) = stmt else {
+        panic!("expected create partition rule");
+    };
+    assert_eq!(rule.rule_name(), "by_user");
+    assert!(rule.partitions().is_empty());
+    assert_eq!(
+        rule.scheme(),
+        &StmtPartitionScheme::Hash {
+            columns: vec!["user_id".to_string(), "region".to_string()],
+            partition_count: 4,
+        }
+    );
+
+    let stmt = parse(
+        "create partition rule by_region list (\
+        partition east values in ('ny', 'ma'), \
+        partition west values in (('ca', 1), ('wa', 2)));",
+    )
+    .stmts()
+    .first()
+    .unwrap()
+    .clone();
+    let StmtType::Command(StmtCommand::CreatePartitionRule(rule)) = stmt else {
+        panic!("expected create partition rule");
+    };
+    let StmtPartitionScheme::List(partitions) = rule.scheme() else {
+        panic!("expected list partition rule");
+    };
+    assert_eq!(partitions.len(), 2);
+    assert_eq!(partitions[0].name(), "east");
+    assert_eq!(
+        partitions[0].values(),
+        &[vec![b"'ny'".to_vec()], vec![b"'ma'".to_vec()]]
+    );
+    assert_eq!(
+        partitions[1].values()[1],
+        vec![b"'wa'".to_vec(), b"2".to_vec()]
+    );
+
+    for bad in [
+        "create partition rule h hash (id);",
+        "create partition rule h hash (id) partitions many;",
+        "create partition rule h hash () partitions 2;",
+        "create partition rule l list (partition p values in ());",
+        "create partition rule l list (partition p values from (1) to (2));",
+        "create partition rule l mod (partition p values in (1));",
+    ] {
+        let err = SQLParser::new().unwrap().parse(bad).unwrap_err();
+        assert_eq!(err.ec(), ErrorCode::Parse, "{}", bad);
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn alter_partition_rule_split_and_move() {
+    let stmt = parse("ALTER PARTITION RULE r1 SPLIT AT (100, 'b') INTO p_hi;")
+        .stmts()
+        .first()
+        .unwrap()
+        .clone();
+    let StmtType::Command(StmtCommand::AlterPartitionRule(alter)) = stmt else {
+        panic!("expected alter partition rule");
+    };
+    assert_eq!(alter.rule_name(), "r1");
+    assert_eq!(
+        alter.action(),
+        &StmtAlterPartitionRuleAction::Split {
+            at: vec![b"100".to_vec(), b"'b'".to_vec()],
+            new_partition: Some("p_hi".to_string()),
+        }
+    );
+
+    let stmt = parse("alter partition rule r1 split at (7)")
+        .stmts()
+        .first()
+        .unwrap()
+        .clone();
+    let StmtType::Command(StmtCommand::AlterPartitionRule(alter)) = stmt else {
+        panic!("expected alter partition rule");
+    };
+    assert_eq!(
+        alter.action(),
+        &StmtAlterPartitionRuleAction::Split {
+            at: vec![b"7".to_vec()],
+            new_partition: None,
+        }
+    );
+
+    let stmt = parse("alter partition rule r1 move partition p0 to worker 2;")
+        .stmts()
+        .first()
+        .unwrap()
+        .clone();
+    let StmtType::Command(StmtCommand::AlterPartitionRule(alter)) = stmt else {
+        panic!("expected alter partition rule");
+    };
+    assert_eq!(
+        alter.action(),
+        &StmtAlterPartitionRuleAction::Move {
+            partition: "p0".to_string(),
+            worker: "2".to_string(),
+        }
+    );
+
+    for bad in [
+        "alter partition rule r1 split at 5;",
+        "alter partition rule r1 split at ();",
+        "alter partition rule r1 split at (5) as p;",
+        "alter partition rule r1 move partition p0;",
+        "alter partition rule r1 move partition p0 to worker ;",
+        "alter partition rule r1 drop partition p0;",
+    ] {
+        let err = SQLParser::new().unwrap().parse(bad).unwrap_err();
+        assert_eq!(err.ec(), ErrorCode::Parse, "{}", bad);
+    }
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn create_partition_placement_success_and_errors() {
@@ -169,6 +302,216 @@ fn drop_type_success_and_errors() {
     assert_eq!(bad.unwrap_err().ec(), ErrorCode::Parse);
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn create_index_success_and_errors() {
+    let stmt = parse("create index orders_by_customer on orders (o_w_id, o_c_id);")
+        .stmts()
+        .first()
+        .unwrap()
+        .clone();
+    let StmtType::Command(StmtCommand::CreateIndex(create)) = stmt else {
+        panic!("expected create index");
+    };
+    assert_eq!(create.index_name(), "orders_by_customer");
+    assert_eq!(create.table_name(), "orders");
+    assert_eq!(
+        create.columns(),
+        &vec!["o_w_id".to_string(), "o_c_id".to_string()]
+    );
+    assert!(!create.if_not_exists());
+
+    let stmt = parse("CREATE INDEX IF NOT EXISTS c_last ON customer(c_last)")
+        .stmts()
+        .first()
+        .unwrap()
+        .clone();
+    let StmtType::Command(StmtCommand::CreateIndex(create)) = stmt else {
+        panic!("expected create index");
+    };
+    assert_eq!(create.index_name(), "c_last");
+    assert_eq!(create.table_name(), "customer");
+    assert!(create.if_not_exists());
+
+    let stmt = parse("CREATE  INDEX\n  IF NOT  EXISTS \"c last\"\tON customer ( c_last , c_id )")
+        .stmts()
+        .first()
+        .unwrap()
+        .clone();
+    let StmtType::Command(StmtCommand::CreateIndex(create)) = stmt else {
+        panic!("expected create index");
+    };
+    assert_eq!(create.index_name(), "\"c last\"");
+    assert_eq!(
+        create.columns(),
+        &vec!["c_last".to_string(), "c_id".to_string()]
+    );
+    assert!(create.if_not_exists());
+
+    let parser = SQLParser::new().unwrap();
+    for sql in [
+        // Missing ON clause.
+        "create index i1 orders (a);",
+        // Empty column list.
+        "create index i1 on orders ();",
+        // Trailing garbage after the column list.
+        "create index i1 on orders (a) using hash;",
+    ] {
+        assert_eq!(parser.parse(sql).unwrap_err().ec(), ErrorCode::MlParse);
+    }
+    for sql in [
+        "create unique index i1 on orders (a);",
+        "create index i1 on orders using hash (a);",
+        "create index i1 on orders (a desc);",
+        "create index on orders (a);",
+    ] {
+        assert!(parser.parse(sql).is_err(), "{}", sql);
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn drop_index_success_and_errors() {
+    let stmt = parse("drop index c_last;").stmts().first().unwrap().clone();
+    let StmtType::Command(StmtCommand::DropIndex(drop)) = stmt else {
+        panic!("expected drop index");
+    };
+    assert_eq!(drop.index_name(), "c_last");
+    assert!(!drop.if_exists());
+
+    let stmt = parse("DROP INDEX IF EXISTS c_last")
+        .stmts()
+        .first()
+        .unwrap()
+        .clone();
+    let StmtType::Command(StmtCommand::DropIndex(drop)) = stmt else {
+        panic!("expected drop index");
+    };
+    assert!(drop.if_exists());
+
+    let stmt = parse("drop  index  if\nexists \"c last\" ;")
+        .stmts()
+        .first()
+        .unwrap()
+        .clone();
+    let StmtType::Command(StmtCommand::DropIndex(drop)) = stmt else {
+        panic!("expected drop index");
+    };
+    assert_eq!(drop.index_name(), "\"c last\"");
+    assert!(drop.if_exists());
+
+    // Missing index name.
+    let bad = SQLParser::new().unwrap().parse("drop index;");
+    assert_eq!(bad.unwrap_err().ec(), ErrorCode::MlParse);
+    let bad = SQLParser::new()
+        .unwrap()
+        .parse("drop index c_last cascade;");
+    assert_eq!(bad.unwrap_err().ec(), ErrorCode::NotImplemented);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn insert_on_conflict_and_returning() {
+    let stmt = parse("insert into kv values (1, 'a') on conflict (k) do nothing;")
+        .stmts()
+        .first()
+        .unwrap()
+        .clone();
+    let StmtType::Command(StmtCommand::Insert(insert)) = stmt else {
+        panic!("expected insert");
+    };
+    let on_conflict = insert.on_conflict().unwrap();
+    assert_eq!(on_conflict.columns(), &vec!["k".to_string()]);
+    assert!(matches!(on_conflict.action(), OnConflictAction::DoNothing));
+    assert!(insert.returning().is_empty());
+
+    let stmt = parse(
+        "INSERT INTO kv (k, v, n) VALUES (?, ?, 1) \
+         ON CONFLICT DO UPDATE SET v = excluded.v, n = n + 1 RETURNING k, n",
+    )
+    .stmts()
+    .first()
+    .unwrap()
+    .clone();
+    let StmtType::Command(StmtCommand::Insert(insert)) = stmt else {
+        panic!("expected insert");
+    };
+    let on_conflict = insert.on_conflict().unwrap();
+    assert!(on_conflict.columns().is_empty());
+    let OnConflictAction::DoUpdate(assignments) = on_conflict.action() else {
+        panic!("expected DO UPDATE");
+    };
+    assert_eq!(assignments.len(), 2);
+    assert_eq!(assignments[0].get_column_reference(), "v");
+    assert_eq!(insert.returning().len(), 2);
+
+    let stmt = parse("insert into kv values (1, 'a') returning *")
+        .stmts()
+        .first()
+        .unwrap()
+        .clone();
+    let StmtType::Command(StmtCommand::Insert(insert)) = stmt else {
+        panic!("expected insert");
+    };
+    assert!(insert.on_conflict().is_none());
+    assert_eq!(insert.returning().len(), 1);
+
+    // A value that merely spells a keyword is not a clause.
+    let stmt = parse("insert into kv values (1, 'on conflict returning')")
+        .stmts()
+        .first()
+        .unwrap()
+        .clone();
+    let StmtType::Command(StmtCommand::Insert(insert)) = stmt else {
+        panic!("expected insert");
+    };
+    assert!(insert.on_conflict().is_none());
+
+    let bad = SQLParser::new()
+        .unwrap()
+        .parse("insert into kv values (1, 'a') on conflict do");
+    assert_eq!(bad.unwrap_err().ec(), ErrorCode::MlParse);
+    let bad = SQLParser::new()
+        .unwrap()
+        .parse("insert into kv values (1, 'a') returning");
+    assert_eq!(bad.unwrap_err().ec(), ErrorCode::MlParse);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn update_and_delete_returning() {
+    let stmt = parse("update kv set v = 'b' where k = 1 returning v")
+        .stmts()
+        .first()
+        .unwrap()
+        .clone();
+    let StmtType::Command(StmtCommand::Update(update)) = stmt else {
+        panic!("expected update");
+    };
+    assert_eq!(update.get_set_values().len(), 1);
+    assert_eq!(update.get_where_predicate().len(), 1);
+    let SelectField::Column(name) = update.get_returning()[0].field() else {
+        panic!("expected a column");
+    };
+    assert_eq!(name.name(), "v");
+
+    let stmt = parse("DELETE FROM kv WHERE NOT k = 1 RETURNING k, v")
+        .stmts()
+        .first()
+        .unwrap()
+        .clone();
+    let StmtType::Command(StmtCommand::Delete(delete)) = stmt else {
+        panic!("expected delete");
+    };
+    assert_eq!(delete.get_where_condition().len(), 1);
+    assert_eq!(delete.get_returning().len(), 2);
+
+    let bad = SQLParser::new()
+        .unwrap()
+        .parse("update kv set v = 'b' where k = 1 on conflict do nothing");
+    assert_eq!(bad.unwrap_err().ec(), ErrorCode::MlParse);
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn invalid_standard_sql_returns_parse_error() {
@@ -183,6 +526,7 @@ fn invalid_standard_sql_returns_parse_error() {
 #[cfg_attr(miri, ignore)]
 fn select_aggregate_functions_parse() {
     use crate::ast::expr_function::FunctionArg;
+    use crate::ast::expression::ExprType;
     use crate::ast::select_term::SelectField;
 
     let stmt = parse("select count(*) as c, sum(amount) from stock;")
@@ -200,7 +544,7 @@ fn select_aggregate_functions_parse() {
             assert_eq!(f.name(), "count");
             assert!(matches!(f.arg(), FunctionArg::Star));
         }
-        SelectField::Column(_) => panic!("expected function field"),
+        _ => panic!("expected function field"),
     }
     assert_eq!(terms[0].alias(), "c");
     match terms[1].field() {
@@ -208,10 +552,10 @@ fn select_aggregate_functions_parse() {
             assert_eq!(f.name(), "sum");
             match f.arg() {
                 FunctionArg::Column(name) => assert_eq!(name.name(), "amount"),
-                FunctionArg::Star => panic!("expected column argument"),
+                _ => panic!("expected column argument"),
             }
         }
-        SelectField::Column(_) => panic!("expected function field"),
+        _ => panic!("expected function field"),
     }
 
     // DISTINCT inside an invocation is rejected as not implemented.
@@ -221,12 +565,22 @@ fn select_aggregate_functions_parse() {
         .unwrap_err();
     assert_eq!(err.ec(), ErrorCode::NotImplemented);
 
-    // An arbitrary expression argument is rejected as not implemented.
-    let err = SQLParser::new()
+    // Any other argument is kept as an expression.
+    let stmt = parse("select sum(a + 1) from stock;")
+        .stmts()
+        .first()
         .unwrap()
-        .parse("select sum(a + 1) from stock;")
-        .unwrap_err();
-    assert_eq!(err.ec(), ErrorCode::NotImplemented);
+        .clone();
+    let StmtType::Select(select) = stmt else {
+        panic!("expected select");
+    };
+    match select.get_select_term_list()[0].field() {
+        SelectField::Function(f) => assert!(matches!(
+            f.arg(),
+            FunctionArg::Expression(ExprType::Arithmetic(_))
+        )),
+        _ => panic!("expected function field"),
+    }
 }
 
 #[test]
@@ -264,3 +618,367 @@ fn mixed_script_respects_semicolons_in_strings() {
     let list = parse(sql);
     assert_eq!(list.stmts().len(), 2);
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn select_with_joins_success_and_errors() {
+    let sql = "select o.o_id, c.c_name, i.i_price from orders as o \
+        join customer c on o.o_c_id = c.c_id and c.c_w_id = 1 \
+        left outer join item i on i.i_id = o.o_i_id \
+        where o.o_w_id = 1;";
+    let stmt = parse(sql).stmts().first().unwrap().clone();
+    let StmtType::Select(select) = stmt else {
+        panic!("expected select");
+    };
+    assert_eq!(select.get_table_reference(), "orders");
+    assert_eq!(select.get_table_alias(), Some("o"));
+    assert_eq!(select.get_select_term_list().len(), 3);
+    let SelectField::Column(column) = select.get_select_term_list()[1].field() else {
+        panic!("expected column");
+    };
+    assert_eq!(column.qualifier(), Some("c"));
+    assert_eq!(column.name(), "c_name");
+    assert_eq!(select.get_where_predicate().len(), 1);
+
+    let joins = select.get_joins();
+    assert_eq!(joins.len(), 2);
+    assert_eq!(joins[0].kind(), JoinKind::Inner);
+    assert_eq!(joins[0].table_name(), "customer");
+    assert_eq!(joins[0].alias(), Some("c"));
+    assert_eq!(joins[0].on_predicate().len(), 2);
+    let ExprItem::ItemName(left) = joins[0].on_predicate()[0].left() else {
+        panic!("expected column reference");
+    };
+    assert_eq!(left.qualifier(), Some("o"));
+    assert!(matches!(
+        joins[0].on_predicate()[0].right(),
+        ExprItem::ItemName(_)
+    ));
+    assert_eq!(joins[1].kind(), JoinKind::LeftOuter);
+    assert_eq!(joins[1].table_name(), "item");
+    assert_eq!(joins[1].alias(), Some("i"));
+    assert_eq!(joins[1].on_predicate().len(), 1);
+
+    // A join script mixed with plain statements.
+    let stmts = parse("select a from t inner join u on t.id = u.id; select a from t;");
+    assert_eq!(stmts.stmts().len(), 2);
+
+    // A table alias without a join.
+    let stmt = parse("select x.a from t x where x.a = 1;").into_stmts();
+    let StmtType::Select(select) = &stmt[0] else {
+        panic!("expected select");
+    };
+    assert_eq!(select.get_table_alias(), Some("x"));
+    assert!(select.get_joins().is_empty());
+
+    let bad = SQLParser::new().unwrap().parse("select a from t join u;");
+    assert_eq!(bad.unwrap_err().ec(), ErrorCode::Parse);
+
+    let bad = SQLParser::new()
+        .unwrap()
+        .parse("select a from t join u using (id);");
+    assert_eq!(bad.unwrap_err().ec(), ErrorCode::NotImplemented);
+
+    let bad = SQLParser::new()
+        .unwrap()
+        .parse("select a from t right join u on t.id = u.id;");
+    assert_eq!(bad.unwrap_err().ec(), ErrorCode::NotImplemented);
+
+    let bad = SQLParser::new()
+        .unwrap()
+        .parse("select a from t cross join u;");
+    assert_eq!(bad.unwrap_err().ec(), ErrorCode::NotImplemented);
+
+    let bad = SQLParser::new()
+        .unwrap()
+        .parse("select a from t join u on t.id = u.id or t.id = 1;");
+    assert_eq!(bad.unwrap_err().ec(), ErrorCode::NotImplemented);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn select_with_trailing_clauses_success_and_errors() {
+    let sql = "select o_c_id, count(*) as cnt from orders where o_w_id = 1 \
+        group by o_c_id having count(*) > ? and sum(o_amount) >= 10 \
+        order by cnt desc, 1 nulls first limit 10 offset 5;";
+    let stmt = parse(sql).stmts().first().unwrap().clone();
+    let StmtType::Select(select) = stmt else {
+        panic!("expected select");
+    };
+    assert_eq!(select.get_table_reference(), "orders");
+    assert_eq!(select.get_where_predicate().len(), 1);
+    assert_eq!(select.get_group_by().len(), 1);
+    assert_eq!(select.get_group_by()[0].name(), "o_c_id");
+
+    let having = select.get_having();
+    assert_eq!(having.len(), 2);
+    assert!(matches!(having[0].field(), SelectField::Function(_)));
+    assert_eq!(having[0].op(), ValueCompare::GT);
+    assert!(matches!(having[0].value(), ExprValue::ValuePlaceholder));
+    assert_eq!(having[1].op(), ValueCompare::GE);
+    assert!(matches!(having[1].value(), ExprValue::ValueLiteral(_)));
+
+    let order_by = select.get_order_by();
+    assert_eq!(order_by.len(), 2);
+    let OrderTarget::Field(SelectField::Column(name)) = order_by[0].target() else {
+        panic!("expected column target");
+    };
+    assert_eq!(name.name(), "cnt");
+    assert!(order_by[0].descending());
+    assert!(order_by[0].nulls_first());
+    assert!(matches!(order_by[1].target(), OrderTarget::Position(1)));
+    assert!(!order_by[1].descending());
+    assert!(order_by[1].nulls_first());
+    assert_eq!(select.get_limit(), Some(10));
+    assert_eq!(select.get_offset(), Some(5));
+
+    // Trailing clauses after a join, with OFFSET before LIMIT.
+    let stmt = parse("select t.a from t join u on t.id = u.id order by t.a offset 2 limit 3")
+        .stmts()
+        .first()
+        .unwrap()
+        .clone();
+    let StmtType::Select(select) = stmt else {
+        panic!("expected select");
+    };
+    assert_eq!(select.get_joins().len(), 1);
+    assert!(!select.get_order_by()[0].nulls_first());
+    assert_eq!(select.get_limit(), Some(3));
+    assert_eq!(select.get_offset(), Some(2));
+
+    let parser = SQLParser::new().unwrap();
+    let bad = parser.parse("select a from t limit ?;");
+    assert_eq!(bad.unwrap_err().ec(), ErrorCode::NotImplemented);
+    let bad = parser.parse("select a from t limit -1;");
+    assert_eq!(bad.unwrap_err().ec(), ErrorCode::Parse);
+    let bad = parser.parse("select a from t order by 0;");
+    assert_eq!(bad.unwrap_err().ec(), ErrorCode::Parse);
+    let bad = parser.parse("select a from t limit 1 order by a;");
+    assert_eq!(bad.unwrap_err().ec(), ErrorCode::MlParse);
+    let bad = parser.parse("select a from t group by a having a > 1 or a < 0;");
+    assert_eq!(bad.unwrap_err().ec(), ErrorCode::NotImplemented);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn explain_success_and_errors() {
+    let stmt = parse("explain select a from t where id = 1;")
+        .stmts()
+        .first()
+        .unwrap()
+        .clone();
+    let StmtType::Explain(explain) = stmt else {
+        panic!("expected explain");
+    };
+    assert!(!explain.analyze());
+    assert_eq!(explain.select().get_table_reference(), "t");
+    assert_eq!(explain.select().get_where_predicate().len(), 1);
+
+    let stmt = parse("EXPLAIN\n  ANALYZE select a from t order by a limit 3")
+        .stmts()
+        .first()
+        .unwrap()
+        .clone();
+    let StmtType::Explain(explain) = stmt else {
+        panic!("expected explain");
+    };
+    assert!(explain.analyze());
+    assert_eq!(explain.select().get_limit(), Some(3));
+
+    let stmts = parse("explain select a from t; select a from t;").into_stmts();
+    assert!(matches!(stmts[0], StmtType::Explain(_)));
+    assert!(matches!(stmts[1], StmtType::Select(_)));
+
+    let parser = SQLParser::new().unwrap();
+    for sql in [
+        "explain delete from t where id = 1;",
+        "EXPLAIN ANALYZE update t set a = 1 where id = 1",
+        "explain insert into t values (1, 2)",
+        "explain verbose select a from t",
+    ] {
+        assert_eq!(
+            parser.parse(sql).unwrap_err().ec(),
+            ErrorCode::NotImplemented,
+            "{}",
+            sql
+        );
+    }
+    let bad = parser.parse("explain analyze ;");
+    assert!(bad.is_err());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn select_scalar_expressions_parse() {
+    use crate::ast::expr_function::FunctionArg;
+    use crate::ast::expr_operator::Arithmetic;
+    use crate::ast::expression::ExprType;
+
+    let stmt = parse(
+        "select price * qty as total, name || '!', substr(name, 1, 3), \
+         case when qty > 10 then 'bulk' when qty > 0 then 'some' else 'none' end as size, \
+         cast(price as numeric(10, 2)), extract(year from created) \
+         from items where lower(name) = 'bob';",
+    )
+    .stmts()
+    .first()
+    .unwrap()
+    .clone();
+    let StmtType::Select(select) = stmt else {
+        panic!("expected select");
+    };
+    let terms = select.get_select_term_list();
+    assert_eq!(terms.len(), 6);
+    match terms[0].field() {
+        SelectField::Expression(ExprType::Arithmetic(arithmetic)) => {
+            assert!(matches!(arithmetic.op(), Arithmetic::MULTIPLE))
+        }
+        _ => panic!("expected arithmetic expression"),
+    }
+    assert_eq!(terms[0].alias(), "total");
+    match terms[1].field() {
+        SelectField::Expression(ExprType::Arithmetic(arithmetic)) => {
+            assert!(matches!(arithmetic.op(), Arithmetic::CONCAT))
+        }
+        _ => panic!("expected concatenation"),
+    }
+    match terms[2].field() {
+        SelectField::Function(f) => {
+            assert_eq!(f.name(), "substr");
+            assert_eq!(f.args().len(), 3);
+            assert!(matches!(f.args()[0], FunctionArg::Column(_)));
+            assert!(matches!(
+                f.args()[1],
+                FunctionArg::Expression(ExprType::Value(_))
+            ));
+        }
+        _ => panic!("expected function"),
+    }
+    match terms[3].field() {
+        SelectField::Expression(ExprType::Case(case)) => {
+            assert!(case.operand().is_none());
+            assert_eq!(case.branches().len(), 2);
+            assert!(matches!(case.branches()[0].0, ExprType::Compare(_)));
+            assert!(case.else_result().is_some());
+        }
+        _ => panic!("expected case"),
+    }
+    assert_eq!(terms[3].alias(), "size");
+    match terms[4].field() {
+        SelectField::Expression(ExprType::Cast(cast)) => {
+            assert!(matches!(cast.expr(), ExprType::Value(_)));
+            let param = cast.target().param().as_numeric_param().unwrap();
+            assert_eq!((param.precision(), param.scale()), (10, 2));
+        }
+        _ => panic!("expected cast"),
+    }
+    match terms[5].field() {
+        SelectField::Expression(ExprType::Function(f)) => {
+            assert_eq!(f.name(), "extract");
+            assert_eq!(f.args().len(), 2);
+        }
+        _ => panic!("expected extract"),
+    }
+    assert!(select.get_where_predicate().is_empty());
+    assert!(matches!(
+        select.get_where_condition()[..],
+        [ExprType::ScalarCompare(_)]
+    ));
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn nested_case_and_update_set_expression_parse() {
+    use crate::ast::expression::ExprType;
+    use crate::ast::stmt_update::AssignedValue;
+
+    let stmt = parse(
+        "update items set price = case kind when 1 then price * 2 \
+         else case when price is null then 0 else price end end where id = 1;",
+    )
+    .stmts()
+    .first()
+    .unwrap()
+    .clone();
+    let StmtType::Command(StmtCommand::Update(update)) = stmt else {
+        panic!("expected update");
+    };
+    let AssignedValue::Expression(ExprType::Case(case)) =
+        update.get_set_values()[0].get_set_value()
+    else {
+        panic!("expected case assignment");
+    };
+    assert!(case.operand().is_some());
+    assert_eq!(case.branches().len(), 1);
+    assert!(matches!(case.else_result(), Some(ExprType::Case(_))));
+
+    let parser = SQLParser::new().unwrap();
+    let bad = parser.parse("select case when a > 1 then 1 from t;");
+    assert_eq!(bad.unwrap_err().ec(), ErrorCode::MlParse);
+    let bad = parser.parse("select case end from t;");
+    assert_eq!(bad.unwrap_err().ec(), ErrorCode::MlParse);
+    let bad = parser.parse("select cast(a) from t;");
+    assert_eq!(bad.unwrap_err().ec(), ErrorCode::MlParse);
+    let bad = parser.parse("select extract(year, a) from t;");
+    assert_eq!(bad.unwrap_err().ec(), ErrorCode::MlParse);
+    let bad = parser.parse("select extract(y2k from a) from t;");
+    assert_eq!(bad.unwrap_err().ec(), ErrorCode::Parse);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn create_table_check_constraints() {
+    use crate::ast::expression::ExprType;
+
+    let stmt = parse(
+        "create table items (id int primary key, qty int not null check (qty >= 0), \
+         price int, kind int, constraint price_kind check (case when kind = 1 \
+         then price > 10 else price > 0 end), check(qty < 1000));",
+    )
+    .stmts()
+    .first()
+    .unwrap()
+    .clone();
+    let StmtType::Command(StmtCommand::CreateTable(table)) = stmt else {
+        panic!("expected create table");
+    };
+    assert_eq!(table.column_def().len(), 4);
+    assert!(!table.column_def()[1].nullable());
+    let checks = table.check_constraints();
+    assert_eq!(checks.len(), 3);
+    assert_eq!(checks[0].name(), None);
+    assert_eq!(checks[0].text(), "qty >= 0");
+    assert!(matches!(checks[0].condition(), ExprType::Compare(_)));
+    assert_eq!(checks[1].name(), Some("price_kind"));
+    assert!(checks[1].text().starts_with("case when kind = 1"));
+    assert!(matches!(checks[1].condition(), ExprType::Case(_)));
+    assert_eq!(checks[2].text(), "qty < 1000");
+
+    // A CHECK in a script with other statements, and with a partition suffix.
+    let stmts = parse(
+        "create table t (id int primary key, v int check (v <> 0)) \
+         partition by global rule sales references (id); select * from t;",
+    );
+    assert_eq!(stmts.stmts().len(), 2);
+    let StmtType::Command(StmtCommand::CreateTable(table)) = &stmts.stmts()[0] else {
+        panic!("expected create table");
+    };
+    assert!(table.partition().is_some());
+    assert_eq!(table.check_constraints().len(), 1);
+
+    // A column CHECK does not make its column NOT NULL.
+    let stmt = parse("create table t (id int primary key, v int check (v is not null));")
+        .stmts()
+        .first()
+        .unwrap()
+        .clone();
+    let StmtType::Command(StmtCommand::CreateTable(table)) = stmt else {
+        panic!("expected create table");
+    };
+    assert!(table.column_def()[1].nullable());
+    assert_eq!(table.check_constraints()[0].text(), "v is not null");
+
+    let parser = SQLParser::new().unwrap();
+    let bad = parser.parse("create table t (id int primary key, v int check (v >));");
+    assert!(bad.is_err());
+}
@@ -4,12 +4,14 @@ use super::context::ParseContext;
 use super::error::ts_node_context_string;
 use super::SQLParser;
 use crate::ast::expr_arithmetic::ExprArithmetic;
-use crate::ast::expr_compare::ExprCompare;
+use crate::ast::expr_compare::{ExprCompare, ExprScalarCompare};
 use crate::ast::expr_item::{ExprItem, ExprValue};
+use crate::ast::expr_json_path::JsonPathStep;
 use crate::ast::expr_literal::ExprLiteral;
 use crate::ast::expr_logical::ExprLogical;
 use crate::ast::expr_name::ExprName;
 use crate::ast::expr_operator::Operator;
+use crate::ast::expr_predicate::{ExprBetween, ExprInList, ExprIsNull, ExprLike};
 use crate::ast::expression::ExprType;
 use crate::ts_const::{ts_field_name, ts_kind_name};
 use mudu::common::result::RS;
@@ -30,6 +32,31 @@ impl SQLParser {
             return self.visit_binary_expression(context, n);
         }
 
+        let opt_unary_expression = node.child_by_field_name(ts_field_name::UNARY_EXPRESSION);
+        if let Some(n) = opt_unary_expression {
+            return self.visit_unary_expression(context, n);
+        }
+
+        let opt_between_expression = node.child_by_field_name(ts_field_name::BETWEEN_EXPRESSION);
+        if let Some(n) = opt_between_expression {
+            return self.visit_between_expression(context, n);
+        }
+
+        let opt_case_expression = node.child_by_field_name(ts_field_name::CASE_EXPRESSION);
+        if let Some(n) = opt_case_expression {
+            return self.visit_case_expression(context, n);
+        }
+
+        let opt_cast_expression = node.child_by_field_name(ts_field_name::CAST_EXPRESSION);
+        if let Some(n) = opt_cast_expression {
+            return self.visit_cast_expression(context, n);
+        }
+
+        let opt_extract_expression = node.child_by_field_name(ts_field_name::EXTRACT_EXPRESSION);
+        if let Some(n) = opt_extract_expression {
+            return self.visit_extract_expression(context, n);
+        }
+
         let opt_literal = node.child_by_field_name(ts_field_name::LITERAL);
         if let Some(n) = opt_literal {
             let literal = self.visit_literal(context, n)?;
@@ -44,6 +71,12 @@ impl SQLParser {
             return Ok(ExprType::Value(Arc::new(ExprItem::ItemName(field))));
         }
 
+        let opt_invocation = node.child_by_field_name(ts_field_name::INVOCATION);
+        if let Some(n) = opt_invocation {
+            let function = self.visit_invocation(context, n)?;
+            return Ok(ExprType::Function(Arc::new(function)));
+        }
+
         let opt_expression = node.child_by_field_name(ts_field_name::EXPRESSION_IN_PARENTHESIS);
         if let Some(n) = opt_expression {
             return self.visit_expression(context, n);
@@ -88,15 +121,9 @@ impl SQLParser {
             let s = self.visit_string(context, n)?;
             DataTyped::from_string(s)
         } else if let Some(_n) = node.child_by_field_name("keyword_true") {
-            return Err(mudu_error!(
-                ErrorCode::NotImplemented,
-                "boolean literal true"
-            ));
+            DataTyped::from_bool(true)
         } else if let Some(_n) = node.child_by_field_name("keyword_false") {
-            return Err(mudu_error!(
-                ErrorCode::NotImplemented,
-                "boolean literal false"
-            ));
+            DataTyped::from_bool(false)
         } else {
             return Err(mudu_error!(
                 ErrorCode::Parse,
@@ -116,6 +143,15 @@ impl SQLParser {
         let name = self.visit_identifier(context, n)?;
         let mut field = ExprName::new();
         field.set_name(name);
+        for i in 0..node.child_count() {
+            let Some(child) = node.child(i as _) else {
+                continue;
+            };
+            if child.kind().eq("object_reference") {
+                field.set_qualifier(self.visit_object_reference(context, child)?);
+                break;
+            }
+        }
         Ok(field)
     }
 
@@ -127,21 +163,76 @@ impl SQLParser {
     ) -> RS<ExprType> {
         let opt_n_operator = node.child_by_field_name(ts_field_name::OPERATOR);
         let n_operation = rs_option(opt_n_operator, "no operator in binary expression")?;
-        let op = self.visit_operator(context, n_operation)?;
         let opt_left = node.child_by_field_name(ts_field_name::LEFT);
         let left = rs_option(opt_left, "no left in binary expression")?;
         let opt_right = node.child_by_field_name(ts_field_name::RIGHT);
         let right = rs_option(opt_right, "no right in binary expression")?;
         let expr_left = self.visit_expression(context, left)?;
+        let operator_kind = n_operation.kind();
+        if operator_kind == ts_kind_name::S_KEYWORD_IN || operator_kind == ts_kind_name::S_NOT_IN {
+            if right.kind() != ts_kind_name::S_LIST {
+                return Err(mudu_error!(
+                    ErrorCode::NotImplemented,
+                    "IN with a subquery is not implemented"
+                ));
+            }
+            let mut cursor = right.walk();
+            let list = right
+                .children_by_field_name(ts_field_name::EXPRESSION, &mut cursor)
+                .map(|n| expr_item(self.visit_expression(context, n)?))
+                .collect::<RS<Vec<_>>>()?;
+            let negated = operator_kind == ts_kind_name::S_NOT_IN;
+            return Ok(ExprType::InList(Arc::new(ExprInList::new(
+                expr_item(expr_left)?,
+                list,
+                negated,
+            ))));
+        }
         let expr_right = self.visit_expression(context, right)?;
-        let expr: ExprType = match op {
-            Operator::OValueCompare(c) => {
-                let (l, r) = match (expr_left, expr_right) {
-                    (ExprType::Value(l), ExprType::Value(r)) => ((*l).clone(), (*r).clone()),
-                    _ => return Err(mudu_error!(ErrorCode::NotImplemented)),
-                };
-                ExprType::Compare(Arc::new(ExprCompare::new(c, l, r)))
+        if operator_kind == "->" || operator_kind == "->>" {
+            return json_path_step(expr_left, expr_right, operator_kind == "->>");
+        }
+        if operator_kind == ts_kind_name::S_KEYWORD_IS || operator_kind == ts_kind_name::S_IS_NOT {
+            let is_null = match &expr_right {
+                ExprType::Value(item) => matches!(
+                    &**item,
+                    ExprItem::ItemValue(ExprValue::ValueLiteral(ExprLiteral::Null))
+                ),
+                _ => false,
+            };
+            if !is_null {
+                return Err(mudu_error!(
+                    ErrorCode::NotImplemented,
+                    "only IS NULL and IS NOT NULL are implemented"
+                ));
             }
+            let negated = operator_kind == ts_kind_name::S_IS_NOT;
+            return Ok(ExprType::IsNull(Arc::new(ExprIsNull::new(
+                expr_item(expr_left)?,
+                negated,
+            ))));
+        }
+        if operator_kind == ts_kind_name::S_KEYWORD_LIKE
+            || operator_kind == ts_kind_name::S_NOT_LIKE
+        {
+            let op_string = ts_node_context_string(context.parse_str(), &n_operation)?;
+            let case_insensitive = op_string.to_ascii_lowercase().ends_with("ilike");
+            let negated = operator_kind == ts_kind_name::S_NOT_LIKE;
+            return Ok(ExprType::Like(Arc::new(ExprLike::new(
+                expr_item(expr_left)?,
+                expr_item(expr_right)?,
+                case_insensitive,
+                negated,
+            ))));
+        }
+        let op = self.visit_operator(context, n_operation)?;
+        let expr: ExprType = match op {
+            Operator::OValueCompare(c) => match (expr_left, expr_right) {
+                (ExprType::Value(l), ExprType::Value(r)) => {
+                    ExprType::Compare(Arc::new(ExprCompare::new(c, (*l).clone(), (*r).clone())))
+                }
+                (l, r) => ExprType::ScalarCompare(Arc::new(ExprScalarCompare::new(c, l, r))),
+            },
             Operator::OLogicalConnective(c) => {
                 ExprType::Logical(Arc::new(ExprLogical::new(c, expr_left, expr_right)))
             }
@@ -153,9 +244,110 @@ impl SQLParser {
         Ok(expr)
     }
 
+    /// Parse a prefix `NOT` expression into an [`ExprType`].
+    ///
+    /// `!`, `ANY`, `SOME` and `ALL` share the grammar rule but are not
+    /// implemented.
+    pub(crate) fn visit_unary_expression(
+        &self,
+        context: &ParseContext,
+        node: Node,
+    ) -> RS<ExprType> {
+        let opt_n_operator = node.child_by_field_name(ts_field_name::OPERATOR);
+        let n_operator = rs_option(opt_n_operator, "no operator in unary expression")?;
+        if n_operator.kind() != ts_kind_name::S_KEYWORD_NOT {
+            return Err(mudu_error!(
+                ErrorCode::NotImplemented,
+                format!(
+                    "unary operator {} is not implemented",
+                    ts_node_context_string(context.parse_str(), &n_operator)?
+                )
+            ));
+        }
+        let opt_operand = node.child_by_field_name(ts_field_name::OPERAND);
+        let operand = rs_option(opt_operand, "no operand in unary expression")?;
+        let expr = self.visit_expression(context, operand)?;
+        Ok(ExprType::Not(Arc::new(expr)))
+    }
+
+    /// Parse a `[NOT] BETWEEN` expression into an [`ExprType`].
+    pub(crate) fn visit_between_expression(
+        &self,
+        context: &ParseContext,
+        node: Node,
+    ) -> RS<ExprType> {
+        let opt_n_operator = node.child_by_field_name(ts_field_name::OPERATOR);
+        let n_operator = rs_option(opt_n_operator, "no operator in between expression")?;
+        // `NOT BETWEEN` tags both keywords as the operator; the first is `NOT`
+        let negated = n_operator.kind() == ts_kind_name::S_KEYWORD_NOT;
+        let opt_left = node.child_by_field_name(ts_field_name::LEFT);
+        let left = rs_option(opt_left, "no left in between expression")?;
+        let opt_low = node.child_by_field_name(ts_field_name::LOW);
+        let low = rs_option(opt_low, "no low bound in between expression")?;
+        let opt_high = node.child_by_field_name(ts_field_name::HIGH);
+        let high = rs_option(opt_high, "no high bound in between expression")?;
+        Ok(ExprType::Between(Arc::new(ExprBetween::new(
+            expr_item(self.visit_expression(context, left)?)?,
+            expr_item(self.visit_expression(context, low)?)?,
+            expr_item(self.visit_expression(context, high)?)?,
+            negated,
+        ))))
+    }
+
     /// Parse an operator node into an [`Operator`].
     pub(crate) fn visit_operator(&self, context: &ParseContext, node: Node) -> RS<Operator> {
         let op_string = ts_node_context_string(context.parse_str(), &node)?;
         Operator::from_name(op_string)
     }
 }
+
+// Appends one `->`/`->>` step to the JSON path of a column reference; the
+// step is a string literal (object member) or an integer literal (array
+// element).
+fn json_path_step(left: ExprType, right: ExprType, as_text: bool) -> RS<ExprType> {
+    let ExprItem::ItemName(mut name) = expr_item(left)? else {
+        return Err(mudu_error!(
+            ErrorCode::NotImplemented,
+            "JSON path operators are only implemented on columns"
+        ));
+    };
+    let mut path = name.json_path().cloned().unwrap_or_default();
+    if path.as_text() {
+        return Err(mudu_error!(
+            ErrorCode::Parse,
+            "->> must be the last step of a JSON path"
+        ));
+    }
+    let step = match expr_item(right)? {
+        ExprItem::ItemValue(ExprValue::ValueLiteral(ExprLiteral::DatumLiteral(typed))) => {
+            let value = typed.data_internal();
+            if let Some(key) = value.as_string() {
+                JsonPathStep::Key(key.clone())
+            } else if let Some(index) = value.as_i64() {
+                JsonPathStep::Index(*index)
+            } else {
+                return Err(mudu_error!(
+                    ErrorCode::Parse,
+                    "a JSON path step must be a string or integer literal"
+                ));
+            }
+        }
+        _ => {
+            return Err(mudu_error!(
+                ErrorCode::NotImplemented,
+                "a JSON path step must be a string or integer literal"
+            ))
+        }
+    };
+    path.push(step, as_text);
+    name.set_json_path(path);
+    Ok(ExprType::Value(Arc::new(ExprItem::ItemName(name))))
+}
+
+// Operand of a predicate; only names, literals and placeholders are supported.
+fn expr_item(expr: ExprType) -> RS<ExprItem> {
+    match expr {
+        ExprType::Value(item) => Ok((*item).clone()),
+        _ => Err(mudu_error!(ErrorCode::NotImplemented)),
+    }
+}
@@ -28,7 +28,12 @@ impl SQLParser {
         let opt = node.child_by_field_name(ts_field_name::INSERT_VALUES);
         let c = rs_option(opt, "no insert values clause in insert statement")?;
         let (columns, values) = self.visit_insert_values(context, c)?;
-        let stmt = StmtInsert::new(table_name, columns, values);
+        let mut stmt = StmtInsert::new(table_name, columns, values);
+
+        if let Some(c) = node.child_by_field_name(ts_field_name::ON_CONFLICT) {
+            stmt.set_on_conflict(Some(self.visit_on_conflict(context, c)?));
+        }
+        stmt.set_returning(self.visit_opt_returning(context, node)?);
         Ok(stmt)
     }
 
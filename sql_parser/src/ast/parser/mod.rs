@@ -50,14 +50,17 @@ impl SQLParser {
 }
 
 mod column;
+mod condition;
 mod context;
 mod ddl;
 mod dispatch;
+mod dml_suffix;
 mod entry;
 mod error;
 mod expression;
 mod insert;
 mod partition;
+mod scalar;
 mod select;
 mod update_delete;
 mod utils;
@@ -2,7 +2,9 @@ use super::utils::{
     find_keyword_position, find_matching_paren, split_top_level_csv, starts_with_ignore_ascii_case,
 };
 use crate::ast::stmt_create_partition_placement::StmtPartitionPlacementItem;
-use crate::ast::stmt_create_partition_rule::{StmtPartitionBound, StmtRangePartition};
+use crate::ast::stmt_create_partition_rule::{
+    StmtListPartition, StmtPartitionBound, StmtRangePartition,
+};
 use crate::ast::stmt_table_partition::StmtTablePartition;
 use mudu::common::result::RS;
 use mudu::error::ErrorCode;
@@ -77,6 +79,68 @@ pub(crate) fn parse_range_partition_def(input: &str) -> RS<StmtRangePartition> {
     Ok(StmtRangePartition::new(name.to_string(), start, end))
 }
 
+pub(crate) fn parse_list_partition_def(input: &str) -> RS<StmtListPartition> {
+    let prefix = "partition ";
+    if !starts_with_ignore_ascii_case(input, prefix) {
+        return Err(mudu_error!(
+            ErrorCode::Parse,
+            format!("invalid partition definition {}", input)
+        ));
+    }
+    let rest = input[prefix.len()..].trim();
+    let values_pos = find_keyword_position(rest, "values")
+        .ok_or_else(|| mudu_error!(ErrorCode::Parse, "partition definition must contain VALUES"))?;
+    let name = rest[..values_pos].trim();
+    let after_values = rest[values_pos + "values".len()..].trim();
+    if !starts_with_ignore_ascii_case(after_values, "in") {
+        return Err(mudu_error!(
+            ErrorCode::Parse,
+            "list partition definition must contain IN"
+        ));
+    }
+    let after_in = after_values["in".len()..].trim();
+    if name.is_empty() || !after_in.starts_with('(') {
+        return Err(mudu_error!(
+            ErrorCode::Parse,
+            format!("invalid partition definition {}", input)
+        ));
+    }
+    let close_index = find_matching_paren(after_in, 0)?;
+    if !after_in[close_index + 1..].trim().is_empty() {
+        return Err(mudu_error!(
+            ErrorCode::Parse,
+            format!("invalid partition definition {}", input)
+        ));
+    }
+    let mut values = Vec::new();
+    for item in split_top_level_csv(&after_in[1..close_index]) {
+        let item = item.trim();
+        let tuple = if item.starts_with('(') {
+            let item_close = find_matching_paren(item, 0)?;
+            split_top_level_csv(&item[1..item_close])
+                .into_iter()
+                .map(|value| value.trim().as_bytes().to_vec())
+                .collect::<Vec<_>>()
+        } else {
+            vec![item.as_bytes().to_vec()]
+        };
+        if tuple.iter().any(|value| value.is_empty()) {
+            return Err(mudu_error!(
+                ErrorCode::Parse,
+                format!("empty value in partition definition {}", input)
+            ));
+        }
+        values.push(tuple);
+    }
+    if values.is_empty() {
+        return Err(mudu_error!(
+            ErrorCode::Parse,
+            format!("list partition {} has no values", name)
+        ));
+    }
+    Ok(StmtListPartition::new(name.to_string(), values))
+}
+
 pub(crate) fn parse_partition_bound(input: &str) -> RS<StmtPartitionBound> {
     let trimmed = input.trim();
     if !trimmed.starts_with('(') || !trimmed.ends_with(')') {
@@ -0,0 +1,97 @@
+//! `CASE`, `CAST` and `EXTRACT` expression parser.
+
+use super::context::ParseContext;
+use super::error::ts_node_context_string;
+use super::SQLParser;
+use crate::ast::expr_case::ExprCase;
+use crate::ast::expr_cast::ExprCast;
+use crate::ast::expr_function::{ExprFunction, FunctionArg};
+use crate::ast::expr_item::{ExprItem, ExprValue};
+use crate::ast::expr_literal::ExprLiteral;
+use crate::ast::expression::ExprType;
+use crate::ast::type_declare::TypeDeclare;
+use crate::ts_const::ts_field_name;
+use mudu::common::result::RS;
+use mudu::common::result_of::rs_option;
+use mudu::error::ErrorCode;
+use mudu::mudu_error;
+use mudu_type::data_typed::DataTyped;
+use std::sync::Arc;
+use tree_sitter::Node;
+
+impl SQLParser {
+    /// Parse `CASE [operand] WHEN .. THEN .. [ELSE ..] END`.
+    pub(crate) fn visit_case_expression(&self, context: &ParseContext, node: Node) -> RS<ExprType> {
+        let operand = match node.child_by_field_name(ts_field_name::OPERAND) {
+            Some(n) => Some(self.visit_expression(context, n)?),
+            None => None,
+        };
+        let mut cursor = node.walk();
+        let n_branches: Vec<Node> = node
+            .children_by_field_name(ts_field_name::BRANCH, &mut cursor)
+            .collect();
+        let mut branches = Vec::with_capacity(n_branches.len());
+        for n_branch in n_branches {
+            let opt_n_condition = n_branch.child_by_field_name(ts_field_name::CONDITION);
+            let n_condition = rs_option(opt_n_condition, "no condition in when branch")?;
+            let opt_n_result = n_branch.child_by_field_name(ts_field_name::RESULT);
+            let n_result = rs_option(opt_n_result, "no result in when branch")?;
+            branches.push((
+                self.visit_expression(context, n_condition)?,
+                self.visit_expression(context, n_result)?,
+            ));
+        }
+        let else_result = match node.child_by_field_name(ts_field_name::ELSE) {
+            Some(n) => Some(self.visit_expression(context, n)?),
+            None => None,
+        };
+        Ok(ExprType::Case(Arc::new(ExprCase::new(
+            operand,
+            branches,
+            else_result,
+        ))))
+    }
+
+    /// Parse `CAST(expr AS type)`.
+    pub(crate) fn visit_cast_expression(&self, context: &ParseContext, node: Node) -> RS<ExprType> {
+        let opt_n_expression = node.child_by_field_name(ts_field_name::EXPRESSION);
+        let n_expression = rs_option(opt_n_expression, "no expression in cast")?;
+        let opt_n_type = node.child_by_field_name(ts_field_name::TYPE);
+        let n_type = rs_option(opt_n_type, "no type in cast")?;
+        let expr = self.visit_expression(context, n_expression)?;
+        let (data_type, data_type_param) = self.visit_data_type(context, n_type)?;
+        let target = TypeDeclare::new(data_type.uni_to_with_params(data_type_param)?);
+        Ok(ExprType::Cast(Arc::new(ExprCast::new(expr, target))))
+    }
+
+    /// Parse `EXTRACT(field FROM expr)` into the call `extract('field', expr)`.
+    pub(crate) fn visit_extract_expression(
+        &self,
+        context: &ParseContext,
+        node: Node,
+    ) -> RS<ExprType> {
+        let opt_n_part = node.child_by_field_name(ts_field_name::PART);
+        let n_part = rs_option(opt_n_part, "no field in extract")?;
+        let opt_n_source = node.child_by_field_name(ts_field_name::SOURCE);
+        let n_source = rs_option(opt_n_source, "no source in extract")?;
+        let part = ts_node_context_string(context.parse_str(), &n_part)?;
+        if !part.chars().all(|ch| ch.is_ascii_alphabetic()) {
+            return Err(mudu_error!(
+                ErrorCode::Parse,
+                format!("invalid EXTRACT field {}", part)
+            ));
+        }
+        let part = ExprType::Value(Arc::new(ExprItem::ItemValue(ExprValue::ValueLiteral(
+            ExprLiteral::DatumLiteral(DataTyped::from_string(part.to_ascii_lowercase())),
+        ))));
+        let source = self.visit_expression(context, n_source)?;
+        let function = ExprFunction::with_args(
+            "extract".to_string(),
+            vec![
+                FunctionArg::Expression(part),
+                FunctionArg::Expression(source),
+            ],
+        )?;
+        Ok(ExprType::Function(Arc::new(function)))
+    }
+}
@@ -5,10 +5,15 @@ use super::error::ts_node_context_string;
 use super::SQLParser;
 use crate::ast::expr_compare::ExprCompare;
 use crate::ast::expr_function::{ExprFunction, FunctionArg};
+use crate::ast::expr_item::ExprItem;
+use crate::ast::expr_operator::Operator;
 use crate::ast::expr_visitor::ExprVisitor;
+use crate::ast::expression::ExprType;
+use crate::ast::select_clause::{HavingPredicate, OrderByItem, OrderTarget};
+use crate::ast::select_join::{JoinKind, SelectJoin};
 use crate::ast::select_term::{SelectField, SelectTerm};
 use crate::ast::stmt_select::StmtSelect;
-use crate::ts_const::ts_field_name;
+use crate::ts_const::{ts_field_name, ts_kind_name};
 use mudu::common::result::RS;
 use mudu::common::result_of::rs_option;
 use mudu::error::ErrorCode;
@@ -70,33 +75,203 @@ impl SQLParser {
         let opt_n_relation = node.child_by_field_name(ts_field_name::RELATION);
         let n_relation = rs_option(opt_n_relation, "")?;
         self.visit_relation(context, n_relation, stmt)?;
+        let mut cursor = node.walk();
+        let n_joins: Vec<Node> = node
+            .children_by_field_name(ts_field_name::JOIN, &mut cursor)
+            .collect();
+        for n_join in n_joins {
+            let join = self.visit_join_clause(context, n_join)?;
+            stmt.add_join(join);
+        }
         let opt_n_where = node.child_by_field_name(ts_field_name::WHERE);
         if let Some(n_where) = opt_n_where {
-            let where_predicate_list = self.visit_where(context, n_where)?;
+            let (where_predicate_list, where_condition_list) =
+                self.visit_where(context, n_where)?;
             for p in where_predicate_list {
                 stmt.add_where_predicate(p);
             }
+            for c in where_condition_list {
+                stmt.add_where_condition(c);
+            }
+        }
+        if let Some(n_group_by) = node.child_by_field_name(ts_field_name::GROUP_BY) {
+            self.visit_group_by(context, n_group_by, stmt)?;
+        }
+        if let Some(n_having) = node.child_by_field_name(ts_field_name::HAVING) {
+            let opt_n_predicate = n_having.child_by_field_name(ts_field_name::PREDICATE);
+            let n_predicate = rs_option(opt_n_predicate, "no predicate in having")?;
+            let mut predicates = Vec::new();
+            self.visit_having_predicate(context, n_predicate, &mut predicates)?;
+            for predicate in predicates {
+                stmt.add_having(predicate);
+            }
+        }
+        if let Some(n_order_by) = node.child_by_field_name(ts_field_name::ORDER_BY) {
+            for i in 0..n_order_by.child_count() {
+                let Some(n) = n_order_by.child(i as _) else {
+                    continue;
+                };
+                if n.kind().eq(ts_kind_name::S_ORDER_TARGET) {
+                    let item = self.visit_order_target(context, n)?;
+                    stmt.add_order_by(item);
+                }
+            }
+        }
+        if let Some(n_limit) = node.child_by_field_name(ts_field_name::LIMIT) {
+            stmt.set_limit(visit_row_count(context, n_limit, "LIMIT")?);
+        }
+        if let Some(n_offset) = node.child_by_field_name(ts_field_name::OFFSET) {
+            stmt.set_offset(visit_row_count(context, n_offset, "OFFSET")?);
         }
 
         Ok(())
     }
 
-    pub(crate) fn visit_where(&self, context: &ParseContext, node: Node) -> RS<Vec<ExprCompare>> {
+    // Parse `GROUP BY <column>, ...`; only column references are supported.
+    fn visit_group_by(&self, context: &ParseContext, node: Node, stmt: &mut StmtSelect) -> RS<()> {
+        let mut cursor = node.walk();
+        let n_expressions: Vec<Node> = node
+            .children_by_field_name(ts_field_name::EXPRESSION, &mut cursor)
+            .collect();
+        for n_expression in n_expressions {
+            match n_expression.child_by_field_name(ts_field_name::QUALIFIED_FIELD) {
+                Some(n) => stmt.add_group_by(self.visit_qualified_field(context, n)?),
+                None => {
+                    return Err(mudu_error!(
+                        ErrorCode::NotImplemented,
+                        format!(
+                            "GROUP BY {}: only column references are supported",
+                            ts_node_context_string(context.parse_str(), &n_expression)?
+                        )
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Parse `HAVING <field> <op> <value> [AND ...]` into `predicates`.
+    fn visit_having_predicate(
+        &self,
+        context: &ParseContext,
+        node: Node,
+        predicates: &mut Vec<HavingPredicate>,
+    ) -> RS<()> {
+        if let Some(n) = node.child_by_field_name(ts_field_name::EXPRESSION_IN_PARENTHESIS) {
+            return self.visit_having_predicate(context, n, predicates);
+        }
+        let n_binary = match node.child_by_field_name(ts_field_name::BINARY_EXPRESSION) {
+            Some(n) => n,
+            None => {
+                return Err(mudu_error!(
+                    ErrorCode::Parse,
+                    format!(
+                        "expected a comparison in HAVING, found {}",
+                        ts_node_context_string(context.parse_str(), &node)?
+                    )
+                ))
+            }
+        };
+        let opt_n_operator = n_binary.child_by_field_name(ts_field_name::OPERATOR);
+        let n_operator = rs_option(opt_n_operator, "no operator in binary expression")?;
+        let opt_left = n_binary.child_by_field_name(ts_field_name::LEFT);
+        let left = rs_option(opt_left, "no left in binary expression")?;
+        let opt_right = n_binary.child_by_field_name(ts_field_name::RIGHT);
+        let right = rs_option(opt_right, "no right in binary expression")?;
+        if n_operator.kind() == ts_kind_name::S_KEYWORD_AND {
+            self.visit_having_predicate(context, left, predicates)?;
+            return self.visit_having_predicate(context, right, predicates);
+        }
+        let op = match self.visit_operator(context, n_operator) {
+            Ok(Operator::OValueCompare(op)) => op,
+            _ => {
+                return Err(mudu_error!(
+                    ErrorCode::NotImplemented,
+                    "HAVING supports only comparisons connected by AND"
+                ))
+            }
+        };
+        let mut term = SelectTerm::new();
+        self.visit_projection_expression(context, left, &mut term)?;
+        let value = match self.visit_expression(context, right)? {
+            ExprType::Value(item) => match &*item {
+                ExprItem::ItemValue(value) => value.clone(),
+                _ => {
+                    return Err(mudu_error!(
+                        ErrorCode::NotImplemented,
+                        "HAVING compares only against a literal or a placeholder"
+                    ))
+                }
+            },
+            _ => {
+                return Err(mudu_error!(
+                    ErrorCode::NotImplemented,
+                    "HAVING compares only against a literal or a placeholder"
+                ))
+            }
+        };
+        predicates.push(HavingPredicate::new(term.field().clone(), op, value));
+        Ok(())
+    }
+
+    // Parse `<target> [ASC|DESC] [NULLS FIRST|LAST]` of an ORDER BY clause.
+    fn visit_order_target(&self, context: &ParseContext, node: Node) -> RS<OrderByItem> {
+        let opt_n_expression = node.child_by_field_name(ts_field_name::EXPRESSION);
+        let n_expression = rs_option(opt_n_expression, "no expression in order by")?;
+        let position = match n_expression.child_by_field_name(ts_field_name::LITERAL) {
+            Some(n) => ts_node_context_string(context.parse_str(), &n)?
+                .parse::<usize>()
+                .ok(),
+            None => None,
+        };
+        let target = match position {
+            Some(0) => {
+                return Err(mudu_error!(
+                    ErrorCode::Parse,
+                    "ORDER BY position starts at 1"
+                ))
+            }
+            Some(position) => OrderTarget::Position(position),
+            None => {
+                let mut term = SelectTerm::new();
+                self.visit_projection_expression(context, n_expression, &mut term)?;
+                OrderTarget::Field(term.field().clone())
+            }
+        };
+        let descending = match node.child_by_field_name(ts_field_name::DIRECTION) {
+            Some(n) => n
+                .child(0)
+                .is_some_and(|n| n.kind() == ts_kind_name::S_KEYWORD_DESC),
+            None => false,
+        };
+        let nulls_first = node
+            .child_by_field_name(ts_field_name::NULLS)
+            .map(|n| n.kind() == ts_kind_name::S_KEYWORD_FIRST);
+        Ok(OrderByItem::new(target, descending, nulls_first))
+    }
+
+    /// Parse a `WHERE` clause into its top-level comparisons and its other
+    /// top-level conjuncts.
+    pub(crate) fn visit_where(
+        &self,
+        context: &ParseContext,
+        node: Node,
+    ) -> RS<(Vec<ExprCompare>, Vec<ExprType>)> {
         let opt = node.child_by_field_name(ts_field_name::PREDICATE);
         let n_predicate = rs_option(opt, "")?;
-        let where_predicate_list = self.visit_where_predicate_expression(context, n_predicate)?;
-        Ok(where_predicate_list)
+        self.visit_where_predicate_expression(context, n_predicate)
     }
 
     pub(crate) fn visit_where_predicate_expression(
         &self,
         context: &ParseContext,
         node: Node,
-    ) -> RS<Vec<ExprCompare>> {
+    ) -> RS<(Vec<ExprCompare>, Vec<ExprType>)> {
         let expr = self.visit_expression(context, node)?;
         let mut cmp_list = vec![];
-        ExprVisitor::extract_expr_compare_list(expr, &mut cmp_list)?;
-        Ok(cmp_list)
+        let mut condition_list = vec![];
+        ExprVisitor::split_where_conjuncts(expr, &mut cmp_list, &mut condition_list)?;
+        Ok((cmp_list, condition_list))
     }
 
     pub(crate) fn visit_relation(
@@ -105,12 +280,67 @@ impl SQLParser {
         node: Node,
         stmt: &mut StmtSelect,
     ) -> RS<()> {
+        let (name, alias) = self.visit_relation_name(context, node)?;
+        stmt.set_table_reference(name);
+        if let Some(alias) = alias {
+            stmt.set_table_alias(alias);
+        }
+        Ok(())
+    }
+
+    // Parse `<table> [[AS] <alias>]`.
+    fn visit_relation_name(
+        &self,
+        context: &ParseContext,
+        node: Node,
+    ) -> RS<(String, Option<String>)> {
         let opt_n_object_reference = node.child_by_field_name(ts_field_name::OBJECT_REFERENCE);
         let n_object_reference =
             rs_option(opt_n_object_reference, "no object reference in relation")?;
         let name = self.visit_object_reference(context, n_object_reference)?;
-        stmt.set_table_reference(name);
-        Ok(())
+        let alias = match node.child_by_field_name(ts_field_name::ALIAS) {
+            Some(n_alias) => Some(self.visit_alias_name(context, n_alias)?),
+            None => None,
+        };
+        Ok((name, alias))
+    }
+
+    /// Parse `[<join type>] JOIN <relation> ON <predicate>`.
+    ///
+    /// Only inner and left outer joins are implemented, and the `ON`
+    /// condition must be comparisons connected by `AND`.
+    pub(crate) fn visit_join_clause(&self, context: &ParseContext, node: Node) -> RS<SelectJoin> {
+        let kind = match node.child_by_field_name(ts_field_name::JOIN_TYPE) {
+            Some(n_join_type) => visit_join_type(context, n_join_type)?,
+            None => JoinKind::Inner,
+        };
+        let opt_n_relation = node.child_by_field_name(ts_field_name::RELATION);
+        let n_relation = rs_option(opt_n_relation, "no relation in join")?;
+        let (table_name, alias) = self.visit_relation_name(context, n_relation)?;
+        if node.child_by_field_name(ts_field_name::USING).is_some() {
+            return Err(mudu_error!(
+                ErrorCode::NotImplemented,
+                "JOIN ... USING is not implemented; use ON"
+            ));
+        }
+        let n_predicate = match node.child_by_field_name(ts_field_name::PREDICATE) {
+            Some(n) => n,
+            None => {
+                return Err(mudu_error!(
+                    ErrorCode::Parse,
+                    format!("join of {} has no ON condition", table_name)
+                ))
+            }
+        };
+        let (on_predicate, on_condition) =
+            self.visit_where_predicate_expression(context, n_predicate)?;
+        if !on_condition.is_empty() {
+            return Err(mudu_error!(
+                ErrorCode::NotImplemented,
+                "JOIN ... ON supports only comparisons connected by AND"
+            ));
+        }
+        Ok(SelectJoin::new(kind, table_name, alias, on_predicate))
     }
 
     pub(crate) fn visit_object_reference(&self, context: &ParseContext, node: Node) -> RS<String> {
@@ -177,21 +407,31 @@ impl SQLParser {
             return Ok(());
         }
         let opt_invocation = node.child_by_field_name(ts_field_name::INVOCATION);
-        match opt_invocation {
-            Some(n) => {
-                let function = self.visit_invocation(context, n)?;
-                term.set_field(SelectField::Function(function));
+        if let Some(n) = opt_invocation {
+            let function = self.visit_invocation(context, n)?;
+            term.set_field(SelectField::Function(function));
+            return Ok(());
+        }
+        // A JSON path (`c -> 'k'`) is a column-like expression; anything
+        // else is computed.
+        let expr = self.visit_expression(context, node)?;
+        if let ExprType::Value(item) = &expr {
+            if let ExprItem::ItemName(field) = &**item {
+                if field.json_path().is_some() {
+                    term.set_field(SelectField::Column(field.clone()));
+                    return Ok(());
+                }
             }
-            None => return Err(mudu_error!(ErrorCode::NotImplemented)),
-        };
+        }
+        term.set_field(SelectField::Expression(expr));
         Ok(())
     }
 
-    /// Parse a function invocation (`name(arg)`) in a select list.
+    /// Parse a function invocation (`name(arg, ...)`).
     ///
-    /// Only a single parameter is supported: either `*` (all fields) or a
-    /// plain column reference. `DISTINCT`, multiple parameters, and in-call
-    /// `ORDER BY` are rejected as not implemented.
+    /// Every parameter is `*` (only as the sole argument), a plain column
+    /// reference or any other scalar expression. `DISTINCT`, in-call
+    /// `ORDER BY` and `FILTER` are rejected as not implemented.
     pub(crate) fn visit_invocation(&self, context: &ParseContext, node: Node) -> RS<ExprFunction> {
         let mut name = None;
         for i in 0..node.child_count() {
@@ -220,22 +460,25 @@ impl SQLParser {
         let params: Vec<Node> = node
             .children_by_field_name(ts_field_name::PARAMETER, &mut cursor)
             .collect();
-        if params.len() != 1 {
+        if params.is_empty() {
             return Err(mudu_error!(
                 ErrorCode::NotImplemented,
-                format!("function {} requires exactly one argument", name)
+                format!("function {} requires at least one argument", name)
             ));
         }
         // Reject the extended invocation forms (DISTINCT, in-call ORDER BY,
         // FILTER, separator literals, LIMIT) instead of silently dropping
-        // them: only `name(arg)` with a single plain parameter is supported.
+        // them: only `name(arg, ...)` with plain parameters is supported.
         for i in 0..node.child_count() {
             let Some(child) = node.child(i as _) else {
                 continue;
             };
             let kind = child.kind();
-            let allowed =
-                kind.eq("object_reference") || kind.eq("(") || kind.eq(")") || kind.eq("term");
+            let allowed = kind.eq("object_reference")
+                || kind.eq("(")
+                || kind.eq(")")
+                || kind.eq(",")
+                || kind.eq("term");
             if !allowed {
                 return Err(mudu_error!(
                     ErrorCode::NotImplemented,
@@ -243,30 +486,35 @@ impl SQLParser {
                 ));
             }
         }
-        let param = params[0];
-        let arg = if param
-            .child_by_field_name(ts_field_name::ALL_FIELDS)
-            .is_some()
-        {
-            FunctionArg::Star
-        } else {
+        let mut args = Vec::with_capacity(params.len());
+        for param in params {
+            if param
+                .child_by_field_name(ts_field_name::ALL_FIELDS)
+                .is_some()
+            {
+                args.push(FunctionArg::Star);
+                continue;
+            }
             let opt_expression = param.child_by_field_name(ts_field_name::EXPRESSION);
             let expression = rs_option(opt_expression, "no expression in function argument")?;
-            let opt_field = expression.child_by_field_name(ts_field_name::QUALIFIED_FIELD);
-            match opt_field {
-                Some(n) => FunctionArg::Column(self.visit_qualified_field(context, n)?),
-                None => {
-                    return Err(mudu_error!(
-                        ErrorCode::NotImplemented,
-                        format!(
-                            "unsupported argument to function {}: only `*` or a column reference is supported",
-                            name
-                        )
-                    ))
-                }
-            }
-        };
-        Ok(ExprFunction::new(name, arg))
+            let arg = match self.visit_expression(context, expression)? {
+                ExprType::Value(item) => match &*item {
+                    ExprItem::ItemName(field) if field.json_path().is_none() => {
+                        FunctionArg::Column(field.clone())
+                    }
+                    _ => FunctionArg::Expression(ExprType::Value(item)),
+                },
+                expr => FunctionArg::Expression(expr),
+            };
+            args.push(arg);
+        }
+        if args.len() > 1 && args.iter().any(|arg| matches!(arg, FunctionArg::Star)) {
+            return Err(mudu_error!(
+                ErrorCode::Parse,
+                format!("* must be the only argument of function {}", name)
+            ));
+        }
+        ExprFunction::with_args(name, args)
     }
 
     pub(crate) fn visit_alias_name(&self, context: &ParseContext, node: Node) -> RS<String> {
@@ -286,3 +534,41 @@ impl SQLParser {
         }
     }
 }
+
+// Parse the row count of a LIMIT or OFFSET clause.
+fn visit_row_count(context: &ParseContext, node: Node, clause: &str) -> RS<u64> {
+    let opt_n_row_count = node.child_by_field_name(ts_field_name::ROW_COUNT);
+    let n_row_count = rs_option(opt_n_row_count, "no row count")?;
+    let text = ts_node_context_string(context.parse_str(), &n_row_count)?;
+    parse_row_count(clause, &text)
+}
+
+fn parse_row_count(clause: &str, text: &str) -> RS<u64> {
+    if text == "?" {
+        return Err(mudu_error!(
+            ErrorCode::NotImplemented,
+            format!("{} takes an integer literal, not a placeholder", clause)
+        ));
+    }
+    text.parse::<u64>().map_err(|_| {
+        mudu_error!(
+            ErrorCode::Parse,
+            format!("{} expects a non-negative integer, found {}", clause, text)
+        )
+    })
+}
+
+// Map the join type keywords of a join clause to a [`JoinKind`].
+fn visit_join_type(context: &ParseContext, node: Node) -> RS<JoinKind> {
+    match node.child(0).map(|n| n.kind()) {
+        Some(ts_kind_name::S_KEYWORD_INNER) => Ok(JoinKind::Inner),
+        Some(ts_kind_name::S_KEYWORD_LEFT) => Ok(JoinKind::LeftOuter),
+        _ => Err(mudu_error!(
+            ErrorCode::NotImplemented,
+            format!(
+                "{} join is not implemented",
+                ts_node_context_string(context.parse_str(), &node)?.to_uppercase()
+            )
+        )),
+    }
+}
@@ -29,9 +29,11 @@ impl SQLParser {
 
         let opt = node.child_by_field_name(ts_field_name::WHERE);
         let n_where = rs_option(opt, "no where clause in update statement")?;
-        let expr_list = self.visit_where(context, n_where)?;
+        let (expr_list, condition_list) = self.visit_where(context, n_where)?;
         stmt.set_where_predicate(expr_list);
+        stmt.set_where_condition(condition_list);
 
+        stmt.set_returning(self.visit_opt_returning(context, node)?);
         Ok(stmt)
     }
 
@@ -47,8 +49,10 @@ impl SQLParser {
         stmt.set_table_reference(table_reference);
         let opt = node.child_by_field_name(ts_field_name::WHERE);
         let n_where = rs_option(opt, "no where clause in delete statement")?;
-        let expr_list = self.visit_where(context, n_where)?;
+        let (expr_list, condition_list) = self.visit_where(context, n_where)?;
         stmt.set_where_predicate(expr_list);
+        stmt.set_where_condition(condition_list);
+        stmt.set_returning(self.visit_opt_returning(context, node)?);
         Ok(stmt)
     }
 
@@ -10,13 +10,19 @@
 
 #[cfg(test)]
 mod tests {
+    use crate::ast::column_def::ColumnDefault;
     use crate::ast::expr_item::{ExprItem, ExprValue};
-    use crate::ast::expr_operator::{Arithmetic, ValueCompare};
+    use crate::ast::expr_json_path::JsonPathStep;
+    use crate::ast::expr_literal::ExprLiteral;
+    use crate::ast::expr_operator::{Arithmetic, LogicalConnective, ValueCompare};
     use crate::ast::expression::ExprType;
     use crate::ast::parser::SQLParser;
+    use crate::ast::select_term::SelectField;
+    use crate::ast::stmt_alter_table::AlterTableAction;
     use crate::ast::stmt_create_table::StmtCreateTable;
     use crate::ast::stmt_type::{StmtCommand, StmtType};
     use crate::ast::stmt_update::AssignedValue;
+    use crate::ast::table_constraint::ReferentialAction;
     use mudu::common::result::RS;
     use mudu_binding::universal::uni_data_type::UniDataType;
     use mudu_binding::universal::uni_data_value::UniDataValue;
@@ -135,6 +141,116 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn parse_select_where_keeps_conjuncts_beyond_comparisons() {
+        let stmts = parse_sql(
+            "select id from users where id >= 1 and (name = 'a' or name = 'b') \
+             and id in (1, 2, ?) and age not between 1 and 9 and name ilike 'A%' \
+             and email is not null;",
+        )
+        .unwrap();
+
+        let StmtType::Select(stmt) = &stmts[0] else {
+            panic!("expected select");
+        };
+        // Comparisons stay among the conjuncts so placeholders keep their
+        // order.
+        assert!(stmt.get_where_predicate().is_empty());
+        let conditions = &stmt.get_where_condition()[1..];
+        assert_eq!(conditions.len(), 5);
+        assert!(matches!(
+            &stmt.get_where_condition()[0],
+            ExprType::Compare(compare) if matches!(compare.op(), ValueCompare::GE)
+        ));
+        let ExprType::Logical(or) = &conditions[0] else {
+            panic!("expected OR, got {:?}", conditions[0]);
+        };
+        assert!(matches!(or.op(), LogicalConnective::OR));
+        let ExprType::InList(in_list) = &conditions[1] else {
+            panic!("expected IN, got {:?}", conditions[1]);
+        };
+        assert_eq!(in_list.list().len(), 3);
+        assert!(!in_list.negated());
+        assert!(matches!(
+            in_list.list()[2],
+            ExprItem::ItemValue(ExprValue::ValuePlaceholder)
+        ));
+        let ExprType::Between(between) = &conditions[2] else {
+            panic!("expected BETWEEN, got {:?}", conditions[2]);
+        };
+        assert!(between.negated());
+        let ExprType::Like(like) = &conditions[3] else {
+            panic!("expected LIKE, got {:?}", conditions[3]);
+        };
+        assert!(like.case_insensitive());
+        assert!(!like.negated());
+        let ExprType::IsNull(is_null) = &conditions[4] else {
+            panic!("expected IS NULL, got {:?}", conditions[4]);
+        };
+        assert!(is_null.negated());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn parse_where_prefix_not_in_select_update_and_delete() {
+        let stmts = parse_sql(
+            "select id from users where not (id = 1 or id = 2) and name not like 'x%' \
+             order by id limit 3;",
+        )
+        .unwrap();
+        let StmtType::Select(stmt) = &stmts[0] else {
+            panic!("expected select");
+        };
+        assert!(stmt.get_where_predicate().is_empty());
+        assert_eq!(stmt.get_order_by().len(), 1);
+        assert_eq!(stmt.get_limit(), Some(3));
+        let conditions = stmt.get_where_condition();
+        assert_eq!(conditions.len(), 2);
+        let ExprType::Not(not) = &conditions[0] else {
+            panic!("expected NOT, got {:?}", conditions[0]);
+        };
+        assert!(
+            matches!(&**not, ExprType::Logical(or) if matches!(or.op(), LogicalConnective::OR))
+        );
+        assert!(matches!(&conditions[1], ExprType::Like(like) if like.negated()));
+
+        let stmts =
+            parse_sql("update users set name = 'x' where id = 1 and not email is null;").unwrap();
+        let StmtType::Command(StmtCommand::Update(stmt)) = &stmts[0] else {
+            panic!("expected update");
+        };
+        assert!(stmt.get_where_predicate().is_empty());
+        assert_eq!(stmt.get_set_values().len(), 1);
+        assert_eq!(stmt.get_where_condition().len(), 2);
+        let ExprType::Not(not) = &stmt.get_where_condition()[1] else {
+            panic!("expected NOT");
+        };
+        assert!(matches!(&**not, ExprType::IsNull(is_null) if !is_null.negated()));
+
+        // A prefix NOT binds looser than the comparison after it and tighter
+        // than AND.
+        let stmts = parse_sql("select id from users where not id = 1 and id < 5;").unwrap();
+        let StmtType::Select(stmt) = &stmts[0] else {
+            panic!("expected select");
+        };
+        assert_eq!(stmt.get_where_predicate().len(), 1);
+        let ExprType::Not(not) = &stmt.get_where_condition()[0] else {
+            panic!("expected NOT");
+        };
+        assert!(matches!(&**not, ExprType::Compare(_)));
+
+        let stmts = parse_sql("delete from users where id in (1, 2);").unwrap();
+        let StmtType::Command(StmtCommand::Delete(stmt)) = &stmts[0] else {
+            panic!("expected delete");
+        };
+        assert!(stmt.get_where_predicate().is_empty());
+        assert!(matches!(
+            &stmt.get_where_condition()[0],
+            ExprType::InList(in_list) if in_list.list().len() == 2
+        ));
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)]
     fn parse_decimal_literal_preserves_trailing_fractional_zeros() {
@@ -286,6 +402,51 @@ mod tests {
         assert!(stmt.drop_if_exists());
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn parse_alter_table_column_actions() {
+        let stmts = parse_sql(
+            "alter table users add column age int not null, drop column if exists note, \
+             rename column name to full_name, alter column score type bigint;",
+        )
+        .unwrap();
+
+        let StmtType::Command(StmtCommand::AlterTable(stmt)) = &stmts[0] else {
+            panic!("expected alter table");
+        };
+        assert_eq!(stmt.table_name(), "users");
+        assert!(!stmt.if_exists());
+        assert_eq!(stmt.actions().len(), 4);
+        let AlterTableAction::AddColumn(column) = &stmt.actions()[0] else {
+            panic!("expected add column");
+        };
+        assert_eq!(column.column_name(), "age");
+        assert!(!column.nullable());
+        assert!(matches!(
+            &stmt.actions()[1],
+            AlterTableAction::DropColumn { column_name, if_exists: true } if column_name == "note"
+        ));
+        assert!(matches!(
+            &stmt.actions()[2],
+            AlterTableAction::RenameColumn { old_name, new_name }
+                if old_name == "name" && new_name == "full_name"
+        ));
+        assert!(matches!(
+            &stmt.actions()[3],
+            AlterTableAction::AlterColumnType {
+                column_name,
+                data_type: UniDataType::Scalar(UniScalar::I64),
+                ..
+            } if column_name == "score"
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn parse_alter_column_set_default_returns_error() {
+        assert!(parse_sql("alter table users alter column age set default 1;").is_err());
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)]
     fn parse_copy_from_statement() {
@@ -565,6 +726,191 @@ mod tests {
         ));
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn parse_create_table_boolean_uuid_json_and_small_integer_types() {
+        let stmt = parse_create_table(
+            "
+            CREATE TABLE flags (
+                id UUID PRIMARY KEY,
+                enabled BOOLEAN,
+                level TINYINT,
+                port SMALLINT,
+                doc JSON,
+                meta JSONB
+            );
+            ",
+        )
+        .unwrap();
+
+        let expected = [
+            UniScalar::Bool,
+            UniScalar::I8,
+            UniScalar::I16,
+            UniScalar::Json,
+            UniScalar::Json,
+        ];
+        let columns = stmt.non_primary_columns();
+        assert_eq!(columns.len(), expected.len());
+        for (column, scalar) in columns.iter().zip(expected) {
+            assert!(matches!(column.data_type(), UniDataType::Scalar(s) if *s == scalar));
+            assert!(column.data_type_param().is_none());
+        }
+        assert!(matches!(
+            stmt.primary_columns()[0].data_type(),
+            UniDataType::Scalar(UniScalar::Uuid)
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn parse_create_table_column_defaults() {
+        let stmt = parse_create_table(
+            "
+            CREATE TABLE orders (
+                id UUID PRIMARY KEY DEFAULT gen_oid(),
+                status CHAR(16) NOT NULL DEFAULT 'new',
+                qty INT DEFAULT 1,
+                created TIMESTAMP DEFAULT now(),
+                updated TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                note TEXT DEFAULT NULL
+            );
+            ",
+        )
+        .unwrap();
+
+        let columns = stmt.column_def();
+        assert!(matches!(
+            columns[0].default_value(),
+            Some(ColumnDefault::GenOid)
+        ));
+        assert!(!columns[1].nullable());
+        assert!(matches!(
+            columns[1].default_value(),
+            Some(ColumnDefault::Literal(ExprLiteral::DatumLiteral(_)))
+        ));
+        assert!(matches!(
+            columns[2].default_value(),
+            Some(ColumnDefault::Literal(ExprLiteral::DatumLiteral(_)))
+        ));
+        assert!(matches!(
+            columns[3].default_value(),
+            Some(ColumnDefault::Now)
+        ));
+        assert!(matches!(
+            columns[4].default_value(),
+            Some(ColumnDefault::Now)
+        ));
+        assert!(columns[5].default_value().is_none());
+
+        let err =
+            parse_sql("CREATE TABLE t (id INT PRIMARY KEY, v INT DEFAULT random());").unwrap_err();
+        assert_eq!(err.ec(), mudu::error::ErrorCode::NotImplemented);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn parse_create_table_unique_and_foreign_key_constraints() {
+        let stmt = parse_create_table(
+            "
+            CREATE TABLE orders (
+                id INT PRIMARY KEY,
+                customer_id INT,
+                code CHAR(16),
+                region INT,
+                UNIQUE KEY orders_code (code, region),
+                FOREIGN KEY (customer_id) REFERENCES customers (id) ON DELETE CASCADE,
+                FOREIGN KEY orders_region (region) REFERENCES regions (id)
+            );
+            ",
+        )
+        .unwrap();
+
+        let unique = stmt.unique_constraints();
+        assert_eq!(unique.len(), 1);
+        assert_eq!(unique[0].name(), Some("orders_code"));
+        assert_eq!(
+            unique[0].columns(),
+            &vec!["code".to_string(), "region".to_string()]
+        );
+
+        let foreign_keys = stmt.foreign_keys();
+        assert_eq!(foreign_keys.len(), 2);
+        assert_eq!(foreign_keys[0].name(), None);
+        assert_eq!(foreign_keys[0].columns(), &vec!["customer_id".to_string()]);
+        assert_eq!(foreign_keys[0].ref_table(), "customers");
+        assert_eq!(foreign_keys[0].ref_columns(), &vec!["id".to_string()]);
+        assert_eq!(foreign_keys[0].on_delete(), ReferentialAction::Cascade);
+        assert_eq!(foreign_keys[1].name(), Some("orders_region"));
+        assert_eq!(foreign_keys[1].on_delete(), ReferentialAction::Restrict);
+
+        let err = parse_sql(
+            "CREATE TABLE t (id INT PRIMARY KEY, a INT, b INT, \
+             FOREIGN KEY (a, b) REFERENCES p (id));",
+        )
+        .unwrap_err();
+        assert_eq!(err.ec(), mudu::error::ErrorCode::Parse);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn parse_select_boolean_literal() {
+        let stmts = parse_sql("select id from flags where enabled = true;").unwrap();
+
+        let StmtType::Select(stmt) = &stmts[0] else {
+            panic!("expected select");
+        };
+        let predicate = &stmt.get_where_predicate()[0];
+        let ExprItem::ItemValue(ExprValue::ValueLiteral(ExprLiteral::DatumLiteral(typed))) =
+            predicate.right()
+        else {
+            panic!("expected boolean literal");
+        };
+        assert_eq!(typed.data_internal().as_bool(), Some(&true));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn parse_select_json_path_in_projection_and_where() {
+        let stmts = parse_sql(
+            "select doc -> 'tags' -> 0, doc ->> 'name' from docs where doc ->> 'kind' = 'a';",
+        )
+        .unwrap();
+
+        let StmtType::Select(stmt) = &stmts[0] else {
+            panic!("expected select");
+        };
+        let paths: Vec<String> = stmt
+            .get_select_term_list()
+            .iter()
+            .map(|term| match term.field() {
+                SelectField::Column(name) => {
+                    format!("{}{}", name.name(), name.json_path().unwrap())
+                }
+                SelectField::Function(_) => panic!("expected column"),
+            })
+            .collect();
+        assert_eq!(paths, vec!["doc->'tags'->0", "doc->>'name'"]);
+
+        // A comparison on a JSON path is kept as a condition.
+        assert!(stmt.get_where_predicate().is_empty());
+        let ExprType::Compare(compare) = &stmt.get_where_condition()[0] else {
+            panic!("expected comparison");
+        };
+        let ExprItem::ItemName(name) = compare.left() else {
+            panic!("expected column");
+        };
+        let path = name.json_path().unwrap();
+        assert_eq!(path.steps(), &[JsonPathStep::Key("kind".to_string())]);
+        assert!(path.as_text());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn parse_select_json_path_rejects_step_after_text() {
+        assert!(parse_sql("select doc ->> 'a' -> 'b' from docs;").is_err());
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)]
     fn parse_create_table_temporal_types_without_precision_keep_no_param_payload() {
@@ -0,0 +1,85 @@
+use crate::ast::ast_node::ASTNode;
+use crate::ast::expr_item::ExprValue;
+use crate::ast::expr_operator::ValueCompare;
+use crate::ast::select_term::SelectField;
+use std::fmt::Debug;
+
+/// What an `ORDER BY` item sorts on.
+#[derive(Clone, Debug)]
+pub enum OrderTarget {
+    /// A column, an output alias or an aggregate call.
+    Field(SelectField),
+    /// A 1-based position in the select list, e.g. `ORDER BY 2`.
+    Position(usize),
+}
+
+/// One item of an `ORDER BY` clause.
+#[derive(Clone, Debug)]
+pub struct OrderByItem {
+    target: OrderTarget,
+    descending: bool,
+    nulls_first: Option<bool>,
+}
+
+impl OrderByItem {
+    /// Create a new `ORDER BY` item; `nulls_first` is `None` when
+    /// `NULLS FIRST|LAST` was not specified.
+    pub fn new(target: OrderTarget, descending: bool, nulls_first: Option<bool>) -> Self {
+        Self {
+            target,
+            descending,
+            nulls_first,
+        }
+    }
+
+    /// Return the sort target.
+    pub fn target(&self) -> &OrderTarget {
+        &self.target
+    }
+
+    /// Return whether the item sorts in descending order.
+    pub fn descending(&self) -> bool {
+        self.descending
+    }
+
+    /// Return whether NULLs sort before non-NULL values. Unless specified,
+    /// NULLs sort as if larger than every value: last when ascending and
+    /// first when descending.
+    pub fn nulls_first(&self) -> bool {
+        self.nulls_first.unwrap_or(self.descending)
+    }
+}
+
+impl ASTNode for OrderByItem {}
+
+/// One comparison of a `HAVING` clause: `<field> <op> <value>`.
+#[derive(Clone, Debug)]
+pub struct HavingPredicate {
+    field: SelectField,
+    op: ValueCompare,
+    value: ExprValue,
+}
+
+impl HavingPredicate {
+    /// Create a new `HAVING` comparison.
+    pub fn new(field: SelectField, op: ValueCompare, value: ExprValue) -> Self {
+        Self { field, op, value }
+    }
+
+    /// Return the compared column or aggregate call.
+    pub fn field(&self) -> &SelectField {
+        &self.field
+    }
+
+    /// Return the comparison operator.
+    pub fn op(&self) -> ValueCompare {
+        self.op
+    }
+
+    /// Return the compared value.
+    pub fn value(&self) -> &ExprValue {
+        &self.value
+    }
+}
+
+impl ASTNode for HavingPredicate {}
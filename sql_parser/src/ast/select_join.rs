@@ -0,0 +1,61 @@
+use crate::ast::ast_node::ASTNode;
+use crate::ast::expr_compare::ExprCompare;
+use std::fmt::Debug;
+
+/// Kind of a join in a `FROM` clause.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JoinKind {
+    /// `[INNER] JOIN`.
+    Inner,
+    /// `LEFT [OUTER] JOIN`.
+    LeftOuter,
+}
+
+/// One `JOIN <table> [[AS] alias] ON <condition>` of a `FROM` clause.
+#[derive(Clone, Debug)]
+pub struct SelectJoin {
+    kind: JoinKind,
+    table_name: String,
+    alias: Option<String>,
+    // AND-ed comparisons of the ON condition
+    on_predicate: Vec<ExprCompare>,
+}
+
+impl SelectJoin {
+    /// Create a new join of `table_name`.
+    pub fn new(
+        kind: JoinKind,
+        table_name: String,
+        alias: Option<String>,
+        on_predicate: Vec<ExprCompare>,
+    ) -> Self {
+        Self {
+            kind,
+            table_name,
+            alias,
+            on_predicate,
+        }
+    }
+
+    /// Return the join kind.
+    pub fn kind(&self) -> JoinKind {
+        self.kind
+    }
+
+    /// Return the joined table name.
+    pub fn table_name(&self) -> &str {
+        &self.table_name
+    }
+
+    /// Return the table alias, if any.
+    pub fn alias(&self) -> Option<&str> {
+        self.alias.as_deref()
+    }
+
+    /// Return the comparisons of the `ON` condition.
+    pub fn on_predicate(&self) -> &Vec<ExprCompare> {
+        &self.on_predicate
+    }
+}
+
+impl ASTNode for SelectJoin {}
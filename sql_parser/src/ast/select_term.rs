@@ -1,13 +1,17 @@
 use crate::ast::expr_function::ExprFunction;
 use crate::ast::expr_name::ExprName;
+use crate::ast::expression::ExprType;
 
-/// What a single `SELECT` list term selects: a plain column or a function call.
+/// What a single `SELECT` list term selects: a plain column, a function call
+/// or a computed expression.
 #[derive(Clone, Debug)]
 pub enum SelectField {
     /// A plain column reference (an empty name represents `*` / all fields).
     Column(ExprName),
     /// A function call, e.g. `COUNT(*)` or `SUM(col)`.
     Function(ExprFunction),
+    /// Any other scalar expression, e.g. `price * qty` or `CASE .. END`.
+    Expression(ExprType),
 }
 
 /// A single term in a `SELECT` list, optionally with an alias.
@@ -71,7 +75,7 @@ mod tests {
     fn column_name(field: &SelectField) -> &str {
         match field {
             SelectField::Column(name) => name.name(),
-            SelectField::Function(_) => panic!("expected column field"),
+            _ => panic!("expected column field"),
         }
     }
 
@@ -108,7 +112,7 @@ mod tests {
                 assert_eq!(f.name(), "count");
                 assert!(matches!(f.arg(), FunctionArg::Star));
             }
-            SelectField::Column(_) => panic!("expected function field"),
+            _ => panic!("expected function field"),
         }
     }
 
@@ -0,0 +1,47 @@
+use crate::ast::ast_node::ASTNode;
+
+/// Action applied by an `ALTER PARTITION RULE` statement.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StmtAlterPartitionRuleAction {
+    /// `SPLIT AT (v, ...) [INTO name]`: split the range partition containing
+    /// the split point into two at that point.
+    Split {
+        /// Split point values, one per rule key column.
+        at: Vec<Vec<u8>>,
+        /// Optional name for the new upper partition.
+        new_partition: Option<String>,
+    },
+    /// `MOVE PARTITION p TO WORKER w`: reassign a partition to another worker.
+    Move {
+        /// Partition name.
+        partition: String,
+        /// Target worker identifier.
+        worker: String,
+    },
+}
+
+/// `ALTER PARTITION RULE` statement AST node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StmtAlterPartitionRule {
+    rule_name: String,
+    action: StmtAlterPartitionRuleAction,
+}
+
+impl StmtAlterPartitionRule {
+    /// Create a new `ALTER PARTITION RULE` statement.
+    pub fn new(rule_name: String, action: StmtAlterPartitionRuleAction) -> Self {
+        Self { rule_name, action }
+    }
+
+    /// Return the partition rule name.
+    pub fn rule_name(&self) -> &str {
+        &self.rule_name
+    }
+
+    /// Return the alter action.
+    pub fn action(&self) -> &StmtAlterPartitionRuleAction {
+        &self.action
+    }
+}
+
+impl ASTNode for StmtAlterPartitionRule {}
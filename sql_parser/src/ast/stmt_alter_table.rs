@@ -0,0 +1,72 @@
+use std::fmt::Debug;
+
+use crate::ast::ast_node::ASTNode;
+use crate::ast::column_def::ColumnDef;
+use mudu_binding::universal::uni_data_type::UniDataType;
+use mudu_binding::universal::uni_data_value::UniDataValue;
+
+/// One action of an `ALTER TABLE` statement.
+#[derive(Debug, Clone)]
+pub enum AlterTableAction {
+    /// `ADD [COLUMN] <column definition>`.
+    AddColumn(ColumnDef),
+    /// `DROP [COLUMN] [IF EXISTS] <name>`.
+    DropColumn {
+        /// Dropped column name.
+        column_name: String,
+        /// Whether `IF EXISTS` was specified.
+        if_exists: bool,
+    },
+    /// `RENAME [COLUMN] <old_name> TO <new_name>`.
+    RenameColumn {
+        /// Current column name.
+        old_name: String,
+        /// New column name.
+        new_name: String,
+    },
+    /// `ALTER [COLUMN] <name> [SET DATA] TYPE <data type>`.
+    AlterColumnType {
+        /// Altered column name.
+        column_name: String,
+        /// New column data type.
+        data_type: UniDataType,
+        /// Optional data type parameters of the new type.
+        data_type_param: Option<Vec<UniDataValue>>,
+    },
+}
+
+/// `ALTER TABLE` statement AST node.
+#[derive(Debug, Clone)]
+pub struct StmtAlterTable {
+    table_name: String,
+    if_exists: bool,
+    actions: Vec<AlterTableAction>,
+}
+
+impl StmtAlterTable {
+    /// Create a new `ALTER TABLE` statement.
+    pub fn new(table_name: String, if_exists: bool, actions: Vec<AlterTableAction>) -> Self {
+        Self {
+            table_name,
+            if_exists,
+            actions,
+        }
+    }
+
+    /// Return the table name.
+    pub fn table_name(&self) -> &str {
+        &self.table_name
+    }
+
+    /// Return whether `IF EXISTS` was specified.
+    pub fn if_exists(&self) -> bool {
+        self.if_exists
+    }
+
+    /// Return the actions in statement order.
+    pub fn actions(&self) -> &Vec<AlterTableAction> {
+        &self.actions
+    }
+}
+
+impl ASTNode for StmtAlterTable {}
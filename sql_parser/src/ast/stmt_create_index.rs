@@ -0,0 +1,50 @@
+use crate::ast::ast_node::ASTNode;
+
+/// `CREATE INDEX [IF NOT EXISTS] <name> ON <table> (<column>, ...)` statement
+/// AST node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StmtCreateIndex {
+    index_name: String,
+    table_name: String,
+    columns: Vec<String>,
+    if_not_exists: bool,
+}
+
+impl StmtCreateIndex {
+    /// Create a new `CREATE INDEX` statement.
+    pub fn new(
+        index_name: String,
+        table_name: String,
+        columns: Vec<String>,
+        if_not_exists: bool,
+    ) -> Self {
+        Self {
+            index_name,
+            table_name,
+            columns,
+            if_not_exists,
+        }
+    }
+
+    /// Return the index name.
+    pub fn index_name(&self) -> &str {
+        &self.index_name
+    }
+
+    /// Return the indexed table name.
+    pub fn table_name(&self) -> &str {
+        &self.table_name
+    }
+
+    /// Return the indexed column names in index order.
+    pub fn columns(&self) -> &Vec<String> {
+        &self.columns
+    }
+
+    /// Return whether `IF NOT EXISTS` was specified.
+    pub fn if_not_exists(&self) -> bool {
+        self.if_not_exists
+    }
+}
+
+impl ASTNode for StmtCreateIndex {}
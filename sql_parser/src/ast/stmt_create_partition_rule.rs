@@ -17,11 +17,34 @@ pub struct StmtRangePartition {
     end: StmtPartitionBound,
 }
 
+/// A single list partition inside a partition rule.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StmtListPartition {
+    name: String,
+    values: Vec<Vec<Vec<u8>>>,
+}
+
+/// How a partition rule maps keys to partitions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StmtPartitionScheme {
+    /// `RANGE (PARTITION p VALUES FROM (..) TO (..), ...)`.
+    Range(Vec<StmtRangePartition>),
+    /// `HASH (col, ...) PARTITIONS n`.
+    Hash {
+        /// Key column names.
+        columns: Vec<String>,
+        /// Number of partitions.
+        partition_count: u64,
+    },
+    /// `LIST (PARTITION p VALUES IN (..), ...)`.
+    List(Vec<StmtListPartition>),
+}
+
 /// `CREATE PARTITION RULE` statement AST node.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct StmtCreatePartitionRule {
     rule_name: String,
-    partitions: Vec<StmtRangePartition>,
+    scheme: StmtPartitionScheme,
 }
 
 impl StmtRangePartition {
@@ -46,12 +69,48 @@ impl StmtRangePartition {
     }
 }
 
+impl StmtListPartition {
+    /// Create a new list partition.
+    pub fn new(name: String, values: Vec<Vec<Vec<u8>>>) -> Self {
+        Self { name, values }
+    }
+
+    /// Return the partition name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Return the listed key tuples.
+    pub fn values(&self) -> &[Vec<Vec<u8>>] {
+        &self.values
+    }
+}
+
 impl StmtCreatePartitionRule {
-    /// Create a new `CREATE PARTITION RULE` statement.
+    /// Create a new range `CREATE PARTITION RULE` statement.
     pub fn new(rule_name: String, partitions: Vec<StmtRangePartition>) -> Self {
         Self {
             rule_name,
-            partitions,
+            scheme: StmtPartitionScheme::Range(partitions),
+        }
+    }
+
+    /// Create a new hash `CREATE PARTITION RULE` statement.
+    pub fn new_hash(rule_name: String, columns: Vec<String>, partition_count: u64) -> Self {
+        Self {
+            rule_name,
+            scheme: StmtPartitionScheme::Hash {
+                columns,
+                partition_count,
+            },
+        }
+    }
+
+    /// Create a new list `CREATE PARTITION RULE` statement.
+    pub fn new_list(rule_name: String, partitions: Vec<StmtListPartition>) -> Self {
+        Self {
+            rule_name,
+            scheme: StmtPartitionScheme::List(partitions),
         }
     }
 
@@ -60,9 +119,17 @@ impl StmtCreatePartitionRule {
         &self.rule_name
     }
 
-    /// Return the range partitions.
+    /// Return the range partitions; empty for hash and list rules.
     pub fn partitions(&self) -> &[StmtRangePartition] {
-        &self.partitions
+        match &self.scheme {
+            StmtPartitionScheme::Range(partitions) => partitions,
+            _ => &[],
+        }
+    }
+
+    /// Return the partitioning scheme.
+    pub fn scheme(&self) -> &StmtPartitionScheme {
+        &self.scheme
     }
 }
 
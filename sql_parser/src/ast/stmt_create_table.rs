@@ -1,6 +1,7 @@
 use crate::ast::ast_node::ASTNode;
 use crate::ast::column_def::ColumnDef;
 use crate::ast::stmt_table_partition::StmtTablePartition;
+use crate::ast::table_constraint::{CheckConstraint, ForeignKeyConstraint, UniqueConstraint};
 use mudu::common::id::AttrIndex;
 use std::fmt::Debug;
 
@@ -12,6 +13,9 @@ pub struct StmtCreateTable {
     primary_key_column_def: Vec<AttrIndex>,
     non_primary_key_column_def: Vec<AttrIndex>,
     partition: Option<StmtTablePartition>,
+    unique_constraints: Vec<UniqueConstraint>,
+    foreign_keys: Vec<ForeignKeyConstraint>,
+    check_constraints: Vec<CheckConstraint>,
 }
 
 impl StmtCreateTable {
@@ -23,6 +27,9 @@ impl StmtCreateTable {
             primary_key_column_def: vec![],
             non_primary_key_column_def: vec![],
             partition: None,
+            unique_constraints: vec![],
+            foreign_keys: vec![],
+            check_constraints: vec![],
         }
     }
 
@@ -89,6 +96,36 @@ impl StmtCreateTable {
         self.partition = Some(partition);
     }
 
+    /// Return the table-level `UNIQUE` constraints.
+    pub fn unique_constraints(&self) -> &Vec<UniqueConstraint> {
+        &self.unique_constraints
+    }
+
+    /// Add a table-level `UNIQUE` constraint.
+    pub fn add_unique_constraint(&mut self, constraint: UniqueConstraint) {
+        self.unique_constraints.push(constraint);
+    }
+
+    /// Return the `FOREIGN KEY` constraints.
+    pub fn foreign_keys(&self) -> &Vec<ForeignKeyConstraint> {
+        &self.foreign_keys
+    }
+
+    /// Add a `FOREIGN KEY` constraint.
+    pub fn add_foreign_key(&mut self, constraint: ForeignKeyConstraint) {
+        self.foreign_keys.push(constraint);
+    }
+
+    /// Return the `CHECK` constraints, of both the table and its columns.
+    pub fn check_constraints(&self) -> &Vec<CheckConstraint> {
+        &self.check_constraints
+    }
+
+    /// Add a `CHECK` constraint.
+    pub fn add_check_constraint(&mut self, constraint: CheckConstraint) {
+        self.check_constraints.push(constraint);
+    }
+
     /// Recalculate primary and non-primary column indices from constraints.
     pub fn assign_index_for_columns(&mut self) {
         self.primary_key_column_def.clear();
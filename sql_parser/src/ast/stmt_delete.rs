@@ -1,11 +1,15 @@
 use crate::ast::ast_node::ASTNode;
 use crate::ast::expr_compare::ExprCompare;
+use crate::ast::expression::ExprType;
+use crate::ast::select_term::SelectTerm;
 
 /// `DELETE` statement AST node.
 #[derive(Clone, Debug)]
 pub struct StmtDelete {
     table_reference: String,
     where_predicate: Vec<ExprCompare>,
+    where_condition: Vec<ExprType>,
+    returning: Vec<SelectTerm>,
 }
 
 impl Default for StmtDelete {
@@ -20,6 +24,8 @@ impl StmtDelete {
         Self {
             table_reference: "".to_string(),
             where_predicate: vec![],
+            where_condition: vec![],
+            returning: vec![],
         }
     }
 
@@ -47,6 +53,34 @@ impl StmtDelete {
     pub fn set_where_predicate(&mut self, where_predicate: Vec<ExprCompare>) {
         self.where_predicate = where_predicate;
     }
+
+    /// Add a top-level `WHERE` conjunct. Used instead of the `WHERE`
+    /// predicates when some conjunct is not a plain comparison, such as an
+    /// `OR`, `NOT`, `IN`, `BETWEEN`, `LIKE` or `IS NULL` predicate.
+    pub fn add_where_condition(&mut self, condition: ExprType) {
+        self.where_condition.push(condition);
+    }
+
+    /// Return the top-level `WHERE` conjuncts in statement order; empty when
+    /// the `WHERE` clause only AND-s comparisons.
+    pub fn get_where_condition(&self) -> &Vec<ExprType> {
+        &self.where_condition
+    }
+
+    /// Replace the top-level `WHERE` conjuncts.
+    pub fn set_where_condition(&mut self, where_condition: Vec<ExprType>) {
+        self.where_condition = where_condition;
+    }
+
+    /// Return the `RETURNING` list; empty when the clause is absent.
+    pub fn get_returning(&self) -> &Vec<SelectTerm> {
+        &self.returning
+    }
+
+    /// Replace the `RETURNING` list.
+    pub fn set_returning(&mut self, returning: Vec<SelectTerm>) {
+        self.returning = returning
+    }
 }
 
 impl ASTNode for StmtDelete {}
@@ -0,0 +1,30 @@
+use crate::ast::ast_node::ASTNode;
+
+/// `DROP INDEX [IF EXISTS] <name>` statement AST node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StmtDropIndex {
+    index_name: String,
+    if_exists: bool,
+}
+
+impl StmtDropIndex {
+    /// Create a new `DROP INDEX` statement.
+    pub fn new(index_name: String, if_exists: bool) -> Self {
+        Self {
+            index_name,
+            if_exists,
+        }
+    }
+
+    /// Return the index name.
+    pub fn index_name(&self) -> &str {
+        &self.index_name
+    }
+
+    /// Return whether `IF EXISTS` was specified.
+    pub fn if_exists(&self) -> bool {
+        self.if_exists
+    }
+}
+
+impl ASTNode for StmtDropIndex {}
@@ -0,0 +1,28 @@
+use crate::ast::ast_node::ASTNode;
+use crate::ast::stmt_select::StmtSelect;
+
+/// `EXPLAIN [ANALYZE] <select>` statement AST node.
+#[derive(Clone, Debug)]
+pub struct StmtExplain {
+    analyze: bool,
+    select: StmtSelect,
+}
+
+impl StmtExplain {
+    /// Create a new `EXPLAIN` statement; `analyze` also runs the query.
+    pub fn new(analyze: bool, select: StmtSelect) -> Self {
+        Self { analyze, select }
+    }
+
+    /// Return whether this is `EXPLAIN ANALYZE`.
+    pub fn analyze(&self) -> bool {
+        self.analyze
+    }
+
+    /// Return the explained query.
+    pub fn select(&self) -> &StmtSelect {
+        &self.select
+    }
+}
+
+impl ASTNode for StmtExplain {}
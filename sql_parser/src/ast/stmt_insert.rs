@@ -1,5 +1,41 @@
 use crate::ast::ast_node::ASTNode;
 use crate::ast::expr_item::ExprValue;
+use crate::ast::select_term::SelectTerm;
+use crate::ast::stmt_update::Assignment;
+
+/// Action of an `ON CONFLICT` clause.
+#[derive(Debug, Clone)]
+pub enum OnConflictAction {
+    /// `DO NOTHING`: skip the conflicting row.
+    DoNothing,
+    /// `DO UPDATE SET ...`: update the existing row instead. An assignment
+    /// may reference the proposed row as `EXCLUDED.col`.
+    DoUpdate(Vec<Assignment>),
+}
+
+/// `ON CONFLICT [(cols)] DO ...` clause of an `INSERT` statement.
+#[derive(Debug, Clone)]
+pub struct OnConflict {
+    columns: Vec<String>,
+    action: OnConflictAction,
+}
+
+impl OnConflict {
+    /// Create a new `ON CONFLICT` clause.
+    pub fn new(columns: Vec<String>, action: OnConflictAction) -> Self {
+        Self { columns, action }
+    }
+
+    /// Return the conflict target columns; empty when omitted.
+    pub fn columns(&self) -> &Vec<String> {
+        &self.columns
+    }
+
+    /// Return the conflict action.
+    pub fn action(&self) -> &OnConflictAction {
+        &self.action
+    }
+}
 
 /// `INSERT` statement AST node.
 #[derive(Debug, Clone)]
@@ -7,6 +43,8 @@ pub struct StmtInsert {
     table_reference: String,
     columns: Vec<String>,
     values_list: Vec<Vec<ExprValue>>,
+    on_conflict: Option<OnConflict>,
+    returning: Vec<SelectTerm>,
 }
 
 impl StmtInsert {
@@ -20,6 +58,8 @@ impl StmtInsert {
             table_reference,
             columns,
             values_list,
+            on_conflict: None,
+            returning: vec![],
         }
     }
 
@@ -37,6 +77,26 @@ impl StmtInsert {
     pub fn values_list(&self) -> &Vec<Vec<ExprValue>> {
         &self.values_list
     }
+
+    /// Return the `ON CONFLICT` clause, if any.
+    pub fn on_conflict(&self) -> Option<&OnConflict> {
+        self.on_conflict.as_ref()
+    }
+
+    /// Set the `ON CONFLICT` clause.
+    pub fn set_on_conflict(&mut self, on_conflict: Option<OnConflict>) {
+        self.on_conflict = on_conflict
+    }
+
+    /// Return the `RETURNING` list; empty when the clause is absent.
+    pub fn returning(&self) -> &Vec<SelectTerm> {
+        &self.returning
+    }
+
+    /// Replace the `RETURNING` list.
+    pub fn set_returning(&mut self, returning: Vec<SelectTerm>) {
+        self.returning = returning
+    }
 }
 
 impl ASTNode for StmtInsert {}
@@ -1,5 +1,9 @@
 use crate::ast::ast_node::ASTNode;
 use crate::ast::expr_compare::ExprCompare;
+use crate::ast::expr_name::ExprName;
+use crate::ast::expression::ExprType;
+use crate::ast::select_clause::{HavingPredicate, OrderByItem};
+use crate::ast::select_join::SelectJoin;
 use crate::ast::select_term::SelectTerm;
 use std::fmt::Debug;
 
@@ -8,8 +12,18 @@ use std::fmt::Debug;
 pub struct StmtSelect {
     select_term_list: Vec<SelectTerm>,
     table_reference: String,
-    // currently, we only support and logical connective expression
+    table_alias: Option<String>,
+    joins: Vec<SelectJoin>,
+    // a `WHERE` clause that only AND-s comparisons
     where_predicate: Vec<ExprCompare>,
+    // the top-level conjuncts of any other `WHERE` clause
+    where_condition: Vec<ExprType>,
+    group_by: Vec<ExprName>,
+    // conjunction of comparisons, like the `WHERE` clause
+    having: Vec<HavingPredicate>,
+    order_by: Vec<OrderByItem>,
+    limit: Option<u64>,
+    offset: Option<u64>,
 }
 
 impl Default for StmtSelect {
@@ -24,7 +38,15 @@ impl StmtSelect {
         Self {
             select_term_list: vec![],
             table_reference: "".to_string(),
+            table_alias: None,
+            joins: vec![],
             where_predicate: vec![],
+            where_condition: vec![],
+            group_by: vec![],
+            having: vec![],
+            order_by: vec![],
+            limit: None,
+            offset: None,
         }
     }
 
@@ -43,6 +65,29 @@ impl StmtSelect {
         &self.where_predicate
     }
 
+    /// Replace all `WHERE` predicates.
+    pub fn set_where_predicate(&mut self, where_predicate: Vec<ExprCompare>) {
+        self.where_predicate = where_predicate;
+    }
+
+    /// Add a top-level `WHERE` conjunct. Used instead of the `WHERE`
+    /// predicates when some conjunct is not a plain comparison, such as an
+    /// `OR`, `NOT`, `IN`, `BETWEEN`, `LIKE` or `IS NULL` predicate.
+    pub fn add_where_condition(&mut self, condition: ExprType) {
+        self.where_condition.push(condition);
+    }
+
+    /// Return the top-level `WHERE` conjuncts in statement order; empty when
+    /// the `WHERE` clause only AND-s comparisons.
+    pub fn get_where_condition(&self) -> &Vec<ExprType> {
+        &self.where_condition
+    }
+
+    /// Replace the top-level `WHERE` conjuncts.
+    pub fn set_where_condition(&mut self, where_condition: Vec<ExprType>) {
+        self.where_condition = where_condition;
+    }
+
     /// Return the `SELECT` list terms.
     pub fn get_select_term_list(&self) -> &Vec<SelectTerm> {
         &self.select_term_list
@@ -57,6 +102,76 @@ impl StmtSelect {
     pub fn get_table_reference(&self) -> &String {
         &self.table_reference
     }
+
+    /// Set the alias of the table reference.
+    pub fn set_table_alias(&mut self, alias: String) {
+        self.table_alias = Some(alias);
+    }
+
+    /// Return the alias of the table reference, if any.
+    pub fn get_table_alias(&self) -> Option<&str> {
+        self.table_alias.as_deref()
+    }
+
+    /// Add a join to the `FROM` clause.
+    pub fn add_join(&mut self, join: SelectJoin) {
+        self.joins.push(join);
+    }
+
+    /// Return the joins of the `FROM` clause in statement order.
+    pub fn get_joins(&self) -> &Vec<SelectJoin> {
+        &self.joins
+    }
+
+    /// Add a column to the `GROUP BY` clause.
+    pub fn add_group_by(&mut self, column: ExprName) {
+        self.group_by.push(column);
+    }
+
+    /// Return the `GROUP BY` columns.
+    pub fn get_group_by(&self) -> &Vec<ExprName> {
+        &self.group_by
+    }
+
+    /// Add a comparison to the `HAVING` clause.
+    pub fn add_having(&mut self, predicate: HavingPredicate) {
+        self.having.push(predicate);
+    }
+
+    /// Return all `HAVING` comparisons.
+    pub fn get_having(&self) -> &Vec<HavingPredicate> {
+        &self.having
+    }
+
+    /// Add an item to the `ORDER BY` clause.
+    pub fn add_order_by(&mut self, item: OrderByItem) {
+        self.order_by.push(item);
+    }
+
+    /// Return the `ORDER BY` items in statement order.
+    pub fn get_order_by(&self) -> &Vec<OrderByItem> {
+        &self.order_by
+    }
+
+    /// Set the `LIMIT` row count.
+    pub fn set_limit(&mut self, limit: u64) {
+        self.limit = Some(limit);
+    }
+
+    /// Return the `LIMIT` row count, if any.
+    pub fn get_limit(&self) -> Option<u64> {
+        self.limit
+    }
+
+    /// Set the `OFFSET` row count.
+    pub fn set_offset(&mut self, offset: u64) {
+        self.offset = Some(offset);
+    }
+
+    /// Return the `OFFSET` row count, if any.
+    pub fn get_offset(&self) -> Option<u64> {
+        self.offset
+    }
 }
 
 impl ASTNode for StmtSelect {}
@@ -10,6 +10,8 @@ use crate::ast::expr_item::{ExprItem, ExprValue};
 use crate::ast::expr_literal::ExprLiteral;
 use crate::ast::expr_name::ExprName;
 use crate::ast::expr_operator::ValueCompare;
+use crate::ast::select_clause::{OrderByItem, OrderTarget};
+use crate::ast::select_join::{JoinKind, SelectJoin};
 use crate::ast::select_term::{SelectField, SelectTerm};
 use crate::ast::stmt_select::StmtSelect;
 use mudu_type::data_typed::DataTyped;
@@ -58,3 +60,56 @@ fn set_table_reference_updates_from_clause() {
     stmt.set_table_reference("users".to_string());
     assert_eq!(stmt.get_table_reference(), "users");
 }
+
+#[test]
+fn add_join_keeps_statement_order() {
+    let mut stmt = StmtSelect::new();
+    stmt.set_table_reference("orders".to_string());
+    stmt.set_table_alias("o".to_string());
+    stmt.add_join(SelectJoin::new(
+        JoinKind::Inner,
+        "users".to_string(),
+        None,
+        vec![sample_predicate()],
+    ));
+    stmt.add_join(SelectJoin::new(
+        JoinKind::LeftOuter,
+        "items".to_string(),
+        Some("i".to_string()),
+        vec![],
+    ));
+    assert_eq!(stmt.get_table_alias(), Some("o"));
+    let joins = stmt.get_joins();
+    assert_eq!(joins.len(), 2);
+    assert_eq!(joins[0].kind(), JoinKind::Inner);
+    assert_eq!(joins[0].table_name(), "users");
+    assert!(joins[0].alias().is_none());
+    assert_eq!(joins[0].on_predicate().len(), 1);
+    assert_eq!(joins[1].kind(), JoinKind::LeftOuter);
+    assert_eq!(joins[1].alias(), Some("i"));
+}
+
+#[test]
+fn trailing_clauses_are_recorded() {
+    let mut stmt = StmtSelect::new();
+    assert!(stmt.get_group_by().is_empty());
+    assert!(stmt.get_order_by().is_empty());
+    assert_eq!(stmt.get_limit(), None);
+    assert_eq!(stmt.get_offset(), None);
+
+    let mut name = ExprName::new();
+    name.set_name("id".to_string());
+    stmt.add_group_by(name);
+    stmt.add_order_by(OrderByItem::new(
+        OrderTarget::Position(1),
+        true,
+        Some(false),
+    ));
+    stmt.set_limit(10);
+    stmt.set_offset(20);
+    assert_eq!(stmt.get_group_by()[0].name(), "id");
+    assert!(stmt.get_order_by()[0].descending());
+    assert!(!stmt.get_order_by()[0].nulls_first());
+    assert_eq!(stmt.get_limit(), Some(10));
+    assert_eq!(stmt.get_offset(), Some(20));
+}
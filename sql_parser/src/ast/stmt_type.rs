@@ -1,12 +1,17 @@
+use crate::ast::stmt_alter_partition_rule::StmtAlterPartitionRule;
+use crate::ast::stmt_alter_table::StmtAlterTable;
 use crate::ast::stmt_copy_from::StmtCopyFrom;
 use crate::ast::stmt_copy_to::StmtCopyTo;
 use crate::ast::stmt_create_fs_type::StmtCreateFsType;
+use crate::ast::stmt_create_index::StmtCreateIndex;
 use crate::ast::stmt_create_partition_placement::StmtCreatePartitionPlacement;
 use crate::ast::stmt_create_partition_rule::StmtCreatePartitionRule;
 use crate::ast::stmt_create_table::StmtCreateTable;
 use crate::ast::stmt_delete::StmtDelete;
+use crate::ast::stmt_drop_index::StmtDropIndex;
 use crate::ast::stmt_drop_table::StmtDropTable;
 use crate::ast::stmt_drop_type::StmtDropType;
+use crate::ast::stmt_explain::StmtExplain;
 use crate::ast::stmt_insert::StmtInsert;
 use crate::ast::stmt_select::StmtSelect;
 use crate::ast::stmt_update::StmtUpdate;
@@ -18,6 +23,8 @@ pub enum StmtType {
     Select(StmtSelect),
     /// Command statement (DML or DDL).
     Command(StmtCommand),
+    /// `EXPLAIN [ANALYZE]` of a `SELECT` statement.
+    Explain(StmtExplain),
 }
 
 /// SQL command statement variants.
@@ -33,10 +40,18 @@ pub enum StmtCommand {
     Insert(StmtInsert),
     /// `CREATE PARTITION RULE` statement.
     CreatePartitionRule(StmtCreatePartitionRule),
+    /// `ALTER PARTITION RULE` statement.
+    AlterPartitionRule(StmtAlterPartitionRule),
     /// `CREATE TABLE` statement.
     CreateTable(StmtCreateTable),
     /// `DROP TABLE` statement.
     DropTable(StmtDropTable),
+    /// `ALTER TABLE` statement.
+    AlterTable(StmtAlterTable),
+    /// `CREATE INDEX` statement.
+    CreateIndex(StmtCreateIndex),
+    /// `DROP INDEX` statement.
+    DropIndex(StmtDropIndex),
     /// `CREATE TYPE FILESYSTEM FILE|DIRECTORY` statement.
     CreateFsType(StmtCreateFsType),
     /// `DROP TYPE` statement.
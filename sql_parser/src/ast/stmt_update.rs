@@ -2,6 +2,7 @@ use crate::ast::ast_node::ASTNode;
 use crate::ast::expr_compare::ExprCompare;
 use crate::ast::expr_item::ExprValue;
 use crate::ast::expression::ExprType;
+use crate::ast::select_term::SelectTerm;
 
 /// Value assigned in an `UPDATE` `SET` clause.
 #[derive(Clone, Debug)]
@@ -25,6 +26,8 @@ pub struct StmtUpdate {
     table_reference: String,
     set_values: Vec<Assignment>,
     where_predicate: Vec<ExprCompare>,
+    where_condition: Vec<ExprType>,
+    returning: Vec<SelectTerm>,
 }
 
 impl Assignment {
@@ -70,6 +73,8 @@ impl StmtUpdate {
             table_reference: Default::default(),
             set_values: vec![],
             where_predicate: vec![],
+            where_condition: vec![],
+            returning: vec![],
         }
     }
 
@@ -93,6 +98,24 @@ impl StmtUpdate {
         self.where_predicate = pred_list
     }
 
+    /// Add a top-level `WHERE` conjunct. Used instead of the `WHERE`
+    /// predicates when some conjunct is not a plain comparison, such as an
+    /// `OR`, `NOT`, `IN`, `BETWEEN`, `LIKE` or `IS NULL` predicate.
+    pub fn add_where_condition(&mut self, condition: ExprType) {
+        self.where_condition.push(condition);
+    }
+
+    /// Return the top-level `WHERE` conjuncts in statement order; empty when
+    /// the `WHERE` clause only AND-s comparisons.
+    pub fn get_where_condition(&self) -> &Vec<ExprType> {
+        &self.where_condition
+    }
+
+    /// Replace the top-level `WHERE` conjuncts.
+    pub fn set_where_condition(&mut self, where_condition: Vec<ExprType>) {
+        self.where_condition = where_condition;
+    }
+
     /// Return all `SET` assignments.
     pub fn get_set_values(&self) -> &Vec<Assignment> {
         &self.set_values
@@ -102,6 +125,16 @@ impl StmtUpdate {
     pub fn set_set_values(&mut self, set_values: Vec<Assignment>) {
         self.set_values = set_values
     }
+
+    /// Return the `RETURNING` list; empty when the clause is absent.
+    pub fn get_returning(&self) -> &Vec<SelectTerm> {
+        &self.returning
+    }
+
+    /// Replace the `RETURNING` list.
+    pub fn set_returning(&mut self, returning: Vec<SelectTerm>) {
+        self.returning = returning
+    }
 }
 
 impl ASTNode for StmtUpdate {}
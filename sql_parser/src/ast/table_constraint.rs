@@ -0,0 +1,132 @@
+//! Table-level constraints of a `CREATE TABLE` statement.
+
+use crate::ast::ast_node::ASTNode;
+use crate::ast::expression::ExprType;
+
+/// `UNIQUE KEY [name] (<column>, ...)` table constraint.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UniqueConstraint {
+    name: Option<String>,
+    columns: Vec<String>,
+}
+
+impl UniqueConstraint {
+    /// Create a new unique constraint over `columns`.
+    pub fn new(name: Option<String>, columns: Vec<String>) -> Self {
+        Self { name, columns }
+    }
+
+    /// Return the constraint name, if one was given.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Return the constrained column names in key order.
+    pub fn columns(&self) -> &Vec<String> {
+        &self.columns
+    }
+}
+
+impl ASTNode for UniqueConstraint {}
+
+/// Action taken on referencing rows when a referenced row is deleted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReferentialAction {
+    /// Reject the delete while referencing rows exist (the default).
+    Restrict,
+    /// Delete the referencing rows as well (`ON DELETE CASCADE`).
+    Cascade,
+}
+
+/// `FOREIGN KEY [name] (<column>, ...) REFERENCES <table> (<column>, ...)
+/// [ON DELETE CASCADE]` table constraint.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForeignKeyConstraint {
+    name: Option<String>,
+    columns: Vec<String>,
+    ref_table: String,
+    ref_columns: Vec<String>,
+    on_delete: ReferentialAction,
+}
+
+impl ForeignKeyConstraint {
+    /// Create a new foreign key constraint.
+    pub fn new(
+        name: Option<String>,
+        columns: Vec<String>,
+        ref_table: String,
+        ref_columns: Vec<String>,
+        on_delete: ReferentialAction,
+    ) -> Self {
+        Self {
+            name,
+            columns,
+            ref_table,
+            ref_columns,
+            on_delete,
+        }
+    }
+
+    /// Return the constraint name, if one was given.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Return the referencing column names.
+    pub fn columns(&self) -> &Vec<String> {
+        &self.columns
+    }
+
+    /// Return the referenced table name.
+    pub fn ref_table(&self) -> &str {
+        &self.ref_table
+    }
+
+    /// Return the referenced column names, paired with `columns` by position.
+    pub fn ref_columns(&self) -> &Vec<String> {
+        &self.ref_columns
+    }
+
+    /// Return the action taken when a referenced row is deleted.
+    pub fn on_delete(&self) -> ReferentialAction {
+        self.on_delete
+    }
+}
+
+impl ASTNode for ForeignKeyConstraint {}
+
+/// `[CONSTRAINT name] CHECK (<condition>)` table or column constraint.
+#[derive(Clone, Debug)]
+pub struct CheckConstraint {
+    name: Option<String>,
+    text: String,
+    condition: ExprType,
+}
+
+impl CheckConstraint {
+    /// Create a new check constraint; `text` is the source of `condition`.
+    pub fn new(name: Option<String>, text: String, condition: ExprType) -> Self {
+        Self {
+            name,
+            text,
+            condition,
+        }
+    }
+
+    /// Return the constraint name, if one was given.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Return the condition as written, without the enclosing parentheses.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Return the parsed condition.
+    pub fn condition(&self) -> &ExprType {
+        &self.condition
+    }
+}
+
+impl ASTNode for CheckConstraint {}
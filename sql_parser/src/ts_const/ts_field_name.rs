@@ -7,12 +7,17 @@
 
 pub const ALIAS: &str = "alias";
 pub const ALL_FIELDS: &str = "all_fields";
+pub const ANALYZE: &str = "analyze";
 pub const ARGUMENT: &str = "argument";
 pub const ASSIGNMENT: &str = "assignment";
 pub const BEGIN_TRANSACTION: &str = "begin_transaction";
 pub const BETWEEN_EXPRESSION: &str = "between_expression";
 pub const BINARY_EXPRESSION: &str = "binary_expression";
+pub const BRANCH: &str = "branch";
 pub const CACHE: &str = "cache";
+pub const CASE_EXPRESSION: &str = "case_expression";
+pub const CAST_EXPRESSION: &str = "cast_expression";
+pub const CHECK_CONSTRAINT: &str = "check_constraint";
 pub const COL_NAME: &str = "col_name";
 pub const COLUMN: &str = "column";
 pub const COLUMN_CONSTRAINT: &str = "column_constraint";
@@ -20,6 +25,8 @@ pub const COLUMN_DEFINITIONS: &str = "column_definitions";
 pub const COLUMN_LIST: &str = "column_list";
 pub const COLUMN_NAME: &str = "column_name";
 pub const COMMIT_TRANSACTION: &str = "commit_transaction";
+pub const CONCURRENTLY: &str = "concurrently";
+pub const CONDITION: &str = "condition";
 pub const CONFIGURATION_PARAMETER: &str = "configuration_parameter";
 pub const CONNECTION_LIMIT: &str = "connection_limit";
 pub const CONSTRAINT: &str = "constraint";
@@ -27,15 +34,23 @@ pub const CUSTOM_TYPE: &str = "custom_type";
 pub const DATA_TYPE: &str = "data_type";
 pub const DATA_TYPE_KIND: &str = "data_type_kind";
 pub const DECIMAL: &str = "decimal";
+pub const DIRECTION: &str = "direction";
 pub const DISTINCT: &str = "distinct";
+pub const DO_NOTHING: &str = "do_nothing";
+pub const DROP_BEHAVIOR: &str = "drop_behavior";
+pub const ELSE: &str = "else";
 pub const END: &str = "end";
 pub const ENUM_ELEMENT: &str = "enum_element";
 pub const ESCAPED_CHAR: &str = "escaped_char";
+pub const EXPLAIN: &str = "explain";
 pub const EXPRESSION: &str = "expression";
 pub const EXPRESSION_IN_PARENTHESIS: &str = "expression_in_parenthesis";
+pub const EXTRACT_EXPRESSION: &str = "extract_expression";
 pub const FIELDS_TERMINATED_CHAR: &str = "fields_terminated_char";
 pub const FILE_PATH: &str = "file_path";
 pub const FROM: &str = "from";
+pub const GROUP_BY: &str = "group_by";
+pub const HAVING: &str = "having";
 pub const HIGH: &str = "high";
 pub const IDENTIFIER_NAME: &str = "identifier_name";
 pub const IF_EXIST: &str = "if_exist";
@@ -45,26 +60,36 @@ pub const INDEX_NAME: &str = "index_name";
 pub const INSERT_VALUES: &str = "insert_values";
 pub const INTEGER: &str = "integer";
 pub const INVOCATION: &str = "invocation";
+pub const JOIN: &str = "join";
+pub const JOIN_TYPE: &str = "join_type";
 pub const KEY: &str = "key";
 pub const KEYWORD_FALSE: &str = "keyword_false";
 pub const KEYWORD_NULL: &str = "keyword_null";
 pub const KEYWORD_TRUE: &str = "keyword_true";
 pub const LEFT: &str = "left";
 pub const LENGTH: &str = "length";
+pub const LIMIT: &str = "limit";
 pub const LIST: &str = "list";
 pub const LITERAL: &str = "literal";
 pub const LOW: &str = "low";
 pub const NAME: &str = "name";
 pub const NEW_NAME: &str = "new_name";
+pub const NULLS: &str = "nulls";
 pub const OBJECT_NAME: &str = "object_name";
 pub const OBJECT_REFERENCE: &str = "object_reference";
+pub const OFFSET: &str = "offset";
 pub const OLD_NAME: &str = "old_name";
+pub const ON_CONFLICT: &str = "on_conflict";
+pub const ONLY: &str = "only";
 pub const OPERAND: &str = "operand";
 pub const OPERATION: &str = "operation";
 pub const OPERATOR: &str = "operator";
 pub const OPTION: &str = "option";
+pub const ORDER_BY: &str = "order_by";
+pub const ORDERED_COLUMNS: &str = "ordered_columns";
 pub const PARAMETER: &str = "parameter";
 pub const PARAMETER_PLACEHOLDER: &str = "parameter_placeholder";
+pub const PART: &str = "part";
 pub const PASSWORD: &str = "password";
 pub const PATH: &str = "path";
 pub const POOL: &str = "pool";
@@ -75,8 +100,11 @@ pub const PRIMARY_KEY_CONSTRAINT: &str = "primary_key_constraint";
 pub const QUALIFIED_FIELD: &str = "qualified_field";
 pub const RELATION: &str = "relation";
 pub const RESTART: &str = "restart";
+pub const RESULT: &str = "result";
+pub const RETURNING: &str = "returning";
 pub const RIGHT: &str = "right";
 pub const ROLLBACK_TRANSACTION: &str = "rollback_transaction";
+pub const ROW_COUNT: &str = "row_count";
 pub const ROW_TERMINATED_CHAR: &str = "row_terminated_char";
 pub const SCALE: &str = "scale";
 pub const SCHEMA: &str = "schema";
@@ -85,6 +113,7 @@ pub const SELECT: &str = "select";
 pub const SELECT_EXPRESSION: &str = "select_expression";
 pub const SET_VALUES: &str = "set_values";
 pub const SIZE: &str = "size";
+pub const SOURCE: &str = "source";
 pub const START: &str = "start";
 pub const STATEMENT: &str = "statement";
 pub const STMT_GUT: &str = "stmt_gut";
@@ -93,6 +122,10 @@ pub const TABLE_NAME: &str = "table_name";
 pub const TIMEOUT: &str = "timeout";
 pub const TYPE: &str = "type";
 pub const TYPED_ROW_VALUE_EXPR_LIST: &str = "typed_row_value_expr_list";
+pub const UNARY_EXPRESSION: &str = "unary_expression";
+pub const UNIQUE: &str = "unique";
+pub const USING: &str = "using";
 pub const VALID_UNTIL: &str = "valid_until";
 pub const VALUE: &str = "value";
+pub const VERBOSE: &str = "verbose";
 pub const WHERE: &str = "where";
@@ -25,7 +25,6 @@ pub const S__EXCLUDE_NO_OTHERS: &str = "_exclude_no_others";
 pub const S__EXCLUDE_TIES: &str = "_exclude_ties";
 pub const S__FUNCTION_BODY_STATEMENT: &str = "_function_body_statement";
 pub const S__FUNCTION_RETURN: &str = "_function_return";
-pub const S__HAVING: &str = "_having";
 pub const S__IDENTIFIER: &str = "_identifier";
 pub const S__IF_EXISTS: &str = "_if_exists";
 pub const S__IF_NOT_EXISTS: &str = "_if_not_exists";
@@ -77,10 +76,13 @@ pub const S_BINARY: &str = "binary";
 pub const S_BINARY_EXPRESSION: &str = "binary_expression";
 pub const S_BIT: &str = "bit";
 pub const S_CASE: &str = "case";
+pub const S_CASE_EXPRESSION: &str = "case_expression";
 pub const S_CAST: &str = "cast";
+pub const S_CAST_EXPRESSION: &str = "cast_expression";
 pub const S_CHANGE_COLUMN: &str = "change_column";
 pub const S_CHANGE_OWNERSHIP: &str = "change_ownership";
 pub const S_CHAR: &str = "char";
+pub const S_CHECK_CONSTRAINT: &str = "check_constraint";
 pub const S_COLUMN: &str = "column";
 pub const S_COLUMN_CONSTRAINT: &str = "column_constraint";
 pub const S_COLUMN_DEFINITION: &str = "column_definition";
@@ -135,6 +137,7 @@ pub const S_ENUM: &str = "enum";
 pub const S_ENUM_ELEMENTS: &str = "enum_elements";
 pub const S_EXISTS: &str = "exists";
 pub const S_EXPRESSION: &str = "expression";
+pub const S_EXTRACT_EXPRESSION: &str = "extract_expression";
 pub const S_FIELD: &str = "field";
 pub const S_FILE_PATH: &str = "file_path";
 pub const S_FILTER_EXPRESSION: &str = "filter_expression";
@@ -152,6 +155,7 @@ pub const S_FUNCTION_STRICTNESS: &str = "function_strictness";
 pub const S_FUNCTION_SUPPORT: &str = "function_support";
 pub const S_FUNCTION_VOLATILITY: &str = "function_volatility";
 pub const S_GROUP_BY: &str = "group_by";
+pub const S_HAVING: &str = "having";
 pub const S_HUGEINT: &str = "hugeint";
 pub const S_IDENTIFIER: &str = "identifier";
 pub const S_IMPLICIT_CAST: &str = "implicit_cast";
@@ -165,6 +169,8 @@ pub const S_INTERVAL_DEFINITIONS: &str = "interval_definitions";
 pub const S_INVOCATION: &str = "invocation";
 pub const S_IS_NOT: &str = "is_not";
 pub const S_JOIN: &str = "join";
+pub const S_JOIN_CLAUSE: &str = "join_clause";
+pub const S_JOIN_TYPE: &str = "join_type";
 pub const S_KEYWORD_ADD: &str = "keyword_add";
 pub const S_KEYWORD_ADMIN: &str = "keyword_admin";
 pub const S_KEYWORD_AFTER: &str = "keyword_after";
@@ -255,6 +261,7 @@ pub const S_KEYWORD_EXCLUDE: &str = "keyword_exclude";
 pub const S_KEYWORD_EXISTS: &str = "keyword_exists";
 pub const S_KEYWORD_EXPLAIN: &str = "keyword_explain";
 pub const S_KEYWORD_EXTERNAL: &str = "keyword_external";
+pub const S_KEYWORD_EXTRACT: &str = "keyword_extract";
 pub const S_KEYWORD_FALSE: &str = "keyword_false";
 pub const S_KEYWORD_FIELDS: &str = "keyword_fields";
 pub const S_KEYWORD_FILTER: &str = "keyword_filter";
@@ -483,6 +490,7 @@ pub const S_NUMERIC: &str = "numeric";
 pub const S_NVARCHAR: &str = "nvarchar";
 pub const S_OBJECT_REFERENCE: &str = "object_reference";
 pub const S_OFFSET: &str = "offset";
+pub const S_ON_CONFLICT: &str = "on_conflict";
 pub const S_ORDER_BY: &str = "order_by";
 pub const S_ORDER_TARGET: &str = "order_target";
 pub const S_ORDERED_COLUMN: &str = "ordered_column";
@@ -526,6 +534,7 @@ pub const S_UPDATE_STATEMENT: &str = "update_statement";
 pub const S_VALUES: &str = "values";
 pub const S_VARBINARY: &str = "varbinary";
 pub const S_VARCHAR: &str = "varchar";
+pub const S_WHEN_BRANCH: &str = "when_branch";
 pub const S_WHEN_CLAUSE: &str = "when_clause";
 pub const S_WHERE: &str = "where";
 pub const S_WINDOW_CLAUSE: &str = "window_clause";
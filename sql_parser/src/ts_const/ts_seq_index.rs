@@ -71,8 +71,6 @@ pub const _EXCLUDE_TIES_SEQ_SYMBOL_0: usize = 0;
 pub const _EXCLUDE_TIES_SEQ_SYMBOL_1: usize = 1;
 pub const _FUNCTION_RETURN_SEQ_SYMBOL_0: usize = 0;
 pub const _FUNCTION_RETURN_SEQ_SYMBOL_1: usize = 1;
-pub const _HAVING_SEQ_SYMBOL_0: usize = 0;
-pub const _HAVING_SEQ_SYMBOL_1: usize = 1;
 pub const _IF_EXISTS_SEQ_SYMBOL_0: usize = 0;
 pub const _IF_EXISTS_SEQ_SYMBOL_1: usize = 1;
 pub const _IF_NOT_EXISTS_SEQ_SYMBOL_0: usize = 0;
@@ -423,14 +421,29 @@ pub const CASE_SEQ_CHOICE_SEQ_SYMBOL_3: usize = 3;
 pub const CASE_SEQ_CHOICE_SEQ_SYMBOL_4: usize = 4;
 pub const CASE_SEQ_SYMBOL_0: usize = 0;
 pub const CASE_SEQ_SYMBOL_3: usize = 3;
-pub const CAST_SEQ_FIELD: usize = 0;
-pub const CAST_SEQ_SEQ: usize = 1;
-pub const CAST_SEQ_SEQ_SEQ: usize = 1;
-pub const CAST_SEQ_SEQ_SEQ_FIELD: usize = 0;
-pub const CAST_SEQ_SEQ_SEQ_SYMBOL_1: usize = 1;
-pub const CAST_SEQ_SEQ_SEQ_SYMBOL_2: usize = 2;
-pub const CAST_SEQ_SEQ_STRING_0: usize = 0;
-pub const CAST_SEQ_SEQ_STRING_2: usize = 2;
+pub const CASE_EXPRESSION_SEQ_CHOICE_1: usize = 1;
+pub const CASE_EXPRESSION_SEQ_CHOICE_3: usize = 3;
+pub const CASE_EXPRESSION_SEQ_CHOICE_SEQ_FIELD: usize = 1;
+pub const CASE_EXPRESSION_SEQ_CHOICE_SEQ_SYMBOL: usize = 0;
+pub const CASE_EXPRESSION_SEQ_REPEAT1: usize = 2;
+pub const CASE_EXPRESSION_SEQ_SYMBOL_0: usize = 0;
+pub const CASE_EXPRESSION_SEQ_SYMBOL_4: usize = 4;
+pub const CAST_PREC_SEQ_FIELD: usize = 0;
+pub const CAST_PREC_SEQ_SEQ: usize = 1;
+pub const CAST_PREC_SEQ_SEQ_SEQ: usize = 1;
+pub const CAST_PREC_SEQ_SEQ_SEQ_FIELD: usize = 0;
+pub const CAST_PREC_SEQ_SEQ_SEQ_SYMBOL_1: usize = 1;
+pub const CAST_PREC_SEQ_SEQ_SEQ_SYMBOL_2: usize = 2;
+pub const CAST_PREC_SEQ_SEQ_STRING_0: usize = 0;
+pub const CAST_PREC_SEQ_SEQ_STRING_2: usize = 2;
+pub const CAST_EXPRESSION_SEQ_SEQ: usize = 1;
+pub const CAST_EXPRESSION_SEQ_SEQ_SEQ: usize = 1;
+pub const CAST_EXPRESSION_SEQ_SEQ_SEQ_FIELD_0: usize = 0;
+pub const CAST_EXPRESSION_SEQ_SEQ_SEQ_FIELD_2: usize = 2;
+pub const CAST_EXPRESSION_SEQ_SEQ_SEQ_SYMBOL: usize = 1;
+pub const CAST_EXPRESSION_SEQ_SEQ_STRING_0: usize = 0;
+pub const CAST_EXPRESSION_SEQ_SEQ_STRING_2: usize = 2;
+pub const CAST_EXPRESSION_SEQ_SYMBOL: usize = 0;
 pub const CHANGE_COLUMN_SEQ_CHOICE_1: usize = 1;
 pub const CHANGE_COLUMN_SEQ_CHOICE_2: usize = 2;
 pub const CHANGE_COLUMN_SEQ_CHOICE_5: usize = 5;
@@ -444,6 +457,14 @@ pub const CHAR_SEQ_FIELD: usize = 2;
 pub const CHAR_SEQ_STRING_1: usize = 1;
 pub const CHAR_SEQ_STRING_3: usize = 3;
 pub const CHAR_SEQ_SYMBOL: usize = 0;
+pub const CHECK_CONSTRAINT_SEQ_CHOICE: usize = 0;
+pub const CHECK_CONSTRAINT_SEQ_CHOICE_SEQ_FIELD: usize = 1;
+pub const CHECK_CONSTRAINT_SEQ_CHOICE_SEQ_SYMBOL: usize = 0;
+pub const CHECK_CONSTRAINT_SEQ_SEQ: usize = 2;
+pub const CHECK_CONSTRAINT_SEQ_SEQ_FIELD: usize = 1;
+pub const CHECK_CONSTRAINT_SEQ_SEQ_STRING_0: usize = 0;
+pub const CHECK_CONSTRAINT_SEQ_SEQ_STRING_2: usize = 2;
+pub const CHECK_CONSTRAINT_SEQ_SYMBOL: usize = 1;
 pub const COLUMN_CONSTRAINT_CHOICE_SEQ_CHOICE: usize = 0;
 pub const COLUMN_CONSTRAINT_CHOICE_SEQ_CHOICE_SEQ_SYMBOL_0: usize = 0;
 pub const COLUMN_CONSTRAINT_CHOICE_SEQ_CHOICE_SEQ_SYMBOL_1: usize = 1;
@@ -514,10 +535,10 @@ pub const CREATE_INDEX_SEQ_CHOICE_SEQ_CHOICE: usize = 0;
 pub const CREATE_INDEX_SEQ_CHOICE_SEQ_FIELD: usize = 1;
 pub const CREATE_INDEX_SEQ_SEQ: usize = 7;
 pub const CREATE_INDEX_SEQ_SEQ_CHOICE: usize = 1;
-pub const CREATE_INDEX_SEQ_SEQ_CHOICE_SEQ_CHOICE: usize = 1;
+pub const CREATE_INDEX_SEQ_SEQ_CHOICE_SEQ_FIELD: usize = 1;
 pub const CREATE_INDEX_SEQ_SEQ_CHOICE_SEQ_SYMBOL: usize = 0;
-pub const CREATE_INDEX_SEQ_SEQ_SYMBOL_0: usize = 0;
-pub const CREATE_INDEX_SEQ_SEQ_SYMBOL_2: usize = 2;
+pub const CREATE_INDEX_SEQ_SEQ_FIELD_0: usize = 0;
+pub const CREATE_INDEX_SEQ_SEQ_FIELD_2: usize = 2;
 pub const CREATE_INDEX_SEQ_SYMBOL_0: usize = 0;
 pub const CREATE_INDEX_SEQ_SYMBOL_2: usize = 2;
 pub const CREATE_INDEX_SEQ_SYMBOL_5: usize = 5;
@@ -651,6 +672,7 @@ pub const DECIMAL_NUMBER_CHOICE_SEQ_SYMBOL_2: usize = 2;
 pub const DECIMAL_NUMBER_CHOICE_SEQ_SYMBOL_0: usize = 0;
 pub const DELETE_SEQ_CHOICE: usize = 1;
 pub const DELETE_SEQ_SYMBOL: usize = 0;
+pub const DELETE_STATEMENT_SEQ_CHOICE: usize = 4;
 pub const DELETE_STATEMENT_SEQ_FIELD_2: usize = 2;
 pub const DELETE_STATEMENT_SEQ_FIELD_3: usize = 3;
 pub const DELETE_STATEMENT_SEQ_SYMBOL_0: usize = 0;
@@ -690,8 +712,8 @@ pub const DROP_INDEX_SEQ_CHOICE_2: usize = 2;
 pub const DROP_INDEX_SEQ_CHOICE_3: usize = 3;
 pub const DROP_INDEX_SEQ_CHOICE_5: usize = 5;
 pub const DROP_INDEX_SEQ_CHOICE_6: usize = 6;
-pub const DROP_INDEX_SEQ_CHOICE_SEQ_SYMBOL_0: usize = 0;
-pub const DROP_INDEX_SEQ_CHOICE_SEQ_SYMBOL_1: usize = 1;
+pub const DROP_INDEX_SEQ_CHOICE_SEQ_FIELD: usize = 1;
+pub const DROP_INDEX_SEQ_CHOICE_SEQ_SYMBOL: usize = 0;
 pub const DROP_INDEX_SEQ_FIELD: usize = 4;
 pub const DROP_INDEX_SEQ_SYMBOL_0: usize = 0;
 pub const DROP_INDEX_SEQ_SYMBOL_1: usize = 1;
@@ -746,6 +768,14 @@ pub const EXISTS_SEQ_SYMBOL_1: usize = 1;
 pub const EXPRESSION_PREC_CHOICE_SEQ_FIELD: usize = 1;
 pub const EXPRESSION_PREC_CHOICE_SEQ_STRING_0: usize = 0;
 pub const EXPRESSION_PREC_CHOICE_SEQ_STRING_2: usize = 2;
+pub const EXTRACT_EXPRESSION_SEQ_SEQ: usize = 1;
+pub const EXTRACT_EXPRESSION_SEQ_SEQ_SEQ: usize = 1;
+pub const EXTRACT_EXPRESSION_SEQ_SEQ_SEQ_FIELD_0: usize = 0;
+pub const EXTRACT_EXPRESSION_SEQ_SEQ_SEQ_FIELD_2: usize = 2;
+pub const EXTRACT_EXPRESSION_SEQ_SEQ_SEQ_SYMBOL: usize = 1;
+pub const EXTRACT_EXPRESSION_SEQ_SEQ_STRING_0: usize = 0;
+pub const EXTRACT_EXPRESSION_SEQ_SEQ_STRING_2: usize = 2;
+pub const EXTRACT_EXPRESSION_SEQ_SYMBOL: usize = 0;
 pub const FILTER_EXPRESSION_SEQ_SEQ: usize = 1;
 pub const FILTER_EXPRESSION_SEQ_SEQ_STRING_0: usize = 0;
 pub const FILTER_EXPRESSION_SEQ_SEQ_STRING_2: usize = 2;
@@ -776,8 +806,15 @@ pub const FRAME_DEFINITION_SEQ_CHOICE_SEQ_FIELD: usize = 0;
 pub const FRAME_DEFINITION_SEQ_CHOICE_SEQ_SYMBOL_0: usize = 0;
 pub const FRAME_DEFINITION_SEQ_CHOICE_SEQ_SYMBOL_1: usize = 1;
 pub const FROM_SEQ_CHOICE_1: usize = 1;
-pub const FROM_SEQ_CHOICE_3: usize = 3;
+pub const FROM_SEQ_CHOICE_4: usize = 4;
+pub const FROM_SEQ_CHOICE_5: usize = 5;
+pub const FROM_SEQ_CHOICE_6: usize = 6;
+pub const FROM_SEQ_CHOICE_7: usize = 7;
+pub const FROM_SEQ_CHOICE_8: usize = 8;
+pub const FROM_SEQ_CHOICE_CHOICE_SEQ_CHOICE: usize = 1;
+pub const FROM_SEQ_CHOICE_CHOICE_SEQ_FIELD: usize = 0;
 pub const FROM_SEQ_FIELD: usize = 2;
+pub const FROM_SEQ_REPEAT: usize = 3;
 pub const FROM_SEQ_SYMBOL: usize = 0;
 pub const FUNCTION_BODY_CHOICE_SEQ_ALIAS: usize = 1;
 pub const FUNCTION_BODY_CHOICE_SEQ_CHOICE_2: usize = 2;
@@ -822,14 +859,15 @@ pub const FUNCTION_STRICTNESS_CHOICE_SEQ_SYMBOL_2: usize = 2;
 pub const FUNCTION_STRICTNESS_CHOICE_SEQ_SYMBOL_3: usize = 3;
 pub const FUNCTION_SUPPORT_SEQ_ALIAS: usize = 1;
 pub const FUNCTION_SUPPORT_SEQ_SYMBOL: usize = 0;
-pub const GROUP_BY_SEQ_CHOICE: usize = 3;
 pub const GROUP_BY_SEQ_SEQ: usize = 2;
+pub const GROUP_BY_SEQ_SEQ_FIELD: usize = 0;
 pub const GROUP_BY_SEQ_SEQ_REPEAT: usize = 1;
+pub const GROUP_BY_SEQ_SEQ_REPEAT_SEQ_FIELD: usize = 1;
 pub const GROUP_BY_SEQ_SEQ_REPEAT_SEQ_STRING: usize = 0;
-pub const GROUP_BY_SEQ_SEQ_REPEAT_SEQ_SYMBOL: usize = 1;
-pub const GROUP_BY_SEQ_SEQ_SYMBOL: usize = 0;
 pub const GROUP_BY_SEQ_SYMBOL_0: usize = 0;
 pub const GROUP_BY_SEQ_SYMBOL_1: usize = 1;
+pub const HAVING_SEQ_FIELD: usize = 1;
+pub const HAVING_SEQ_SYMBOL: usize = 0;
 pub const IDENTIFIER_CHOICE_SEQ_STRING_0: usize = 0;
 pub const IDENTIFIER_CHOICE_SEQ_STRING_2: usize = 2;
 pub const IDENTIFIER_CHOICE_SEQ_SYMBOL: usize = 1;
@@ -845,6 +883,8 @@ pub const INDEX_HINT_SEQ_SEQ_FIELD: usize = 1;
 pub const INDEX_HINT_SEQ_SEQ_STRING_0: usize = 0;
 pub const INDEX_HINT_SEQ_SEQ_STRING_2: usize = 2;
 pub const INDEX_HINT_SEQ_SYMBOL: usize = 1;
+pub const INSERT_STATEMENT_SEQ_CHOICE_4: usize = 4;
+pub const INSERT_STATEMENT_SEQ_CHOICE_5: usize = 5;
 pub const INSERT_STATEMENT_SEQ_FIELD_2: usize = 2;
 pub const INSERT_STATEMENT_SEQ_FIELD_3: usize = 3;
 pub const INSERT_STATEMENT_SEQ_SYMBOL_0: usize = 0;
@@ -897,6 +937,14 @@ pub const JOIN_SEQ_CHOICE_SEQ_FIELD: usize = 1;
 pub const JOIN_SEQ_CHOICE_SEQ_SYMBOL: usize = 0;
 pub const JOIN_SEQ_SYMBOL_1: usize = 1;
 pub const JOIN_SEQ_SYMBOL_2: usize = 2;
+pub const JOIN_CLAUSE_SEQ_CHOICE_0: usize = 0;
+pub const JOIN_CLAUSE_SEQ_CHOICE_3: usize = 3;
+pub const JOIN_CLAUSE_SEQ_CHOICE_CHOICE_SEQ_FIELD: usize = 1;
+pub const JOIN_CLAUSE_SEQ_CHOICE_CHOICE_SEQ_SYMBOL: usize = 0;
+pub const JOIN_CLAUSE_SEQ_FIELD: usize = 2;
+pub const JOIN_CLAUSE_SEQ_SYMBOL: usize = 1;
+pub const JOIN_TYPE_CHOICE_SEQ_CHOICE: usize = 1;
+pub const JOIN_TYPE_CHOICE_SEQ_SYMBOL: usize = 0;
 pub const KEYWORD_TIMESTAMP_BA_PREC_RIGHT_SEQ_CHOICE: usize = 1;
 pub const KEYWORD_TIMESTAMP_BA_PREC_RIGHT_SEQ_CHOICE_SEQ_PATTERN_0: usize = 0;
 pub const KEYWORD_TIMESTAMP_BA_PREC_RIGHT_SEQ_CHOICE_SEQ_PATTERN_1: usize = 1;
@@ -925,9 +973,8 @@ pub const LATERAL_JOIN_SEQ_CHOICE_CHOICE_SEQ_SYMBOL_1: usize = 1;
 pub const LATERAL_JOIN_SEQ_SYMBOL_1: usize = 1;
 pub const LATERAL_JOIN_SEQ_SYMBOL_2: usize = 2;
 pub const LATERAL_JOIN_SEQ_SYMBOL_5: usize = 5;
-pub const LIMIT_SEQ_CHOICE: usize = 2;
-pub const LIMIT_SEQ_SYMBOL_0: usize = 0;
-pub const LIMIT_SEQ_SYMBOL_1: usize = 1;
+pub const LIMIT_SEQ_FIELD: usize = 1;
+pub const LIMIT_SEQ_SYMBOL: usize = 0;
 pub const LIST_SEQ_CHOICE: usize = 1;
 pub const LIST_SEQ_CHOICE_SEQ_FIELD: usize = 0;
 pub const LIST_SEQ_CHOICE_SEQ_REPEAT: usize = 1;
@@ -979,8 +1026,16 @@ pub const OBJECT_REFERENCE_SEQ_CHOICE: usize = 0;
 pub const OBJECT_REFERENCE_SEQ_CHOICE_SEQ_FIELD: usize = 0;
 pub const OBJECT_REFERENCE_SEQ_CHOICE_SEQ_STRING: usize = 1;
 pub const OBJECT_REFERENCE_SEQ_FIELD: usize = 1;
-pub const OFFSET_SEQ_SYMBOL_0: usize = 0;
-pub const OFFSET_SEQ_SYMBOL_1: usize = 1;
+pub const OFFSET_SEQ_FIELD: usize = 1;
+pub const OFFSET_SEQ_SYMBOL: usize = 0;
+pub const ON_CONFLICT_SEQ_CHOICE_2: usize = 2;
+pub const ON_CONFLICT_SEQ_CHOICE_4: usize = 4;
+pub const ON_CONFLICT_SEQ_CHOICE_SEQ_FIELD: usize = 2;
+pub const ON_CONFLICT_SEQ_CHOICE_SEQ_SYMBOL_0: usize = 0;
+pub const ON_CONFLICT_SEQ_CHOICE_SEQ_SYMBOL_1: usize = 1;
+pub const ON_CONFLICT_SEQ_SYMBOL_0: usize = 0;
+pub const ON_CONFLICT_SEQ_SYMBOL_1: usize = 1;
+pub const ON_CONFLICT_SEQ_SYMBOL_3: usize = 3;
 pub const ORDER_BY_PREC_RIGHT_SEQ_SEQ: usize = 2;
 pub const ORDER_BY_PREC_RIGHT_SEQ_SEQ_REPEAT: usize = 1;
 pub const ORDER_BY_PREC_RIGHT_SEQ_SEQ_REPEAT_SEQ_STRING: usize = 0;
@@ -988,12 +1043,11 @@ pub const ORDER_BY_PREC_RIGHT_SEQ_SEQ_REPEAT_SEQ_SYMBOL: usize = 1;
 pub const ORDER_BY_PREC_RIGHT_SEQ_SEQ_SYMBOL: usize = 0;
 pub const ORDER_BY_PREC_RIGHT_SEQ_SYMBOL_0: usize = 0;
 pub const ORDER_BY_PREC_RIGHT_SEQ_SYMBOL_1: usize = 1;
-pub const ORDER_TARGET_SEQ_CHOICE: usize = 1;
-pub const ORDER_TARGET_SEQ_CHOICE_SEQ_CHOICE_0: usize = 0;
-pub const ORDER_TARGET_SEQ_CHOICE_SEQ_CHOICE_1: usize = 1;
-pub const ORDER_TARGET_SEQ_CHOICE_SEQ_CHOICE_SEQ_CHOICE: usize = 1;
-pub const ORDER_TARGET_SEQ_CHOICE_SEQ_CHOICE_SEQ_SYMBOL: usize = 0;
-pub const ORDER_TARGET_SEQ_SYMBOL: usize = 0;
+pub const ORDER_TARGET_SEQ_CHOICE_1: usize = 1;
+pub const ORDER_TARGET_SEQ_CHOICE_2: usize = 2;
+pub const ORDER_TARGET_SEQ_CHOICE_SEQ_FIELD: usize = 1;
+pub const ORDER_TARGET_SEQ_CHOICE_SEQ_SYMBOL: usize = 0;
+pub const ORDER_TARGET_SEQ_FIELD: usize = 0;
 pub const ORDERED_COLUMN_SEQ_CHOICE: usize = 1;
 pub const ORDERED_COLUMN_SEQ_FIELD: usize = 0;
 pub const ORDERED_COLUMNS_SEQ_SEQ: usize = 1;
@@ -1023,6 +1077,8 @@ pub const QUALIFIED_FIELD_SEQ_CHOICE_SEQ_PREC_RIGHT_CHOICE_SEQ_STRING_2: usize =
 pub const QUALIFIED_FIELD_SEQ_CHOICE_SEQ_PREC_RIGHT_CHOICE_SEQ_SYMBOL: usize = 1;
 pub const QUALIFIED_FIELD_SEQ_CHOICE_SEQ_STRING: usize = 1;
 pub const QUALIFIED_FIELD_SEQ_FIELD: usize = 1;
+pub const RELATION_SEQ_CHOICE: usize = 1;
+pub const RELATION_SEQ_FIELD: usize = 0;
 pub const RENAME_COLUMN_SEQ_CHOICE: usize = 1;
 pub const RENAME_COLUMN_SEQ_FIELD_2: usize = 2;
 pub const RENAME_COLUMN_SEQ_FIELD_4: usize = 4;
@@ -1031,8 +1087,8 @@ pub const RENAME_COLUMN_SEQ_SYMBOL_3: usize = 3;
 pub const RENAME_OBJECT_SEQ_SYMBOL_0: usize = 0;
 pub const RENAME_OBJECT_SEQ_SYMBOL_1: usize = 1;
 pub const RENAME_OBJECT_SEQ_SYMBOL_2: usize = 2;
-pub const RETURNING_SEQ_SYMBOL_0: usize = 0;
-pub const RETURNING_SEQ_SYMBOL_1: usize = 1;
+pub const RETURNING_SEQ_FIELD: usize = 1;
+pub const RETURNING_SEQ_SYMBOL: usize = 0;
 pub const ROLLBACK_TRANSACTION_SEQ_CHOICE: usize = 1;
 pub const ROLLBACK_TRANSACTION_SEQ_SYMBOL: usize = 0;
 pub const ROW_FORMAT_SEQ_CHOICE_3: usize = 3;
@@ -1082,7 +1138,7 @@ pub const SIMILAR_TO_SEQ_SYMBOL_1: usize = 1;
 pub const STATEMENT_SEQ_CHOICE: usize = 0;
 pub const STATEMENT_SEQ_CHOICE_SEQ_CHOICE_1: usize = 1;
 pub const STATEMENT_SEQ_CHOICE_SEQ_CHOICE_2: usize = 2;
-pub const STATEMENT_SEQ_CHOICE_SEQ_SYMBOL: usize = 0;
+pub const STATEMENT_SEQ_CHOICE_SEQ_FIELD: usize = 0;
 pub const STATEMENT_SEQ_FIELD: usize = 1;
 pub const STORAGE_LOCATION_PREC_RIGHT_SEQ_CHOICE: usize = 2;
 pub const STORAGE_LOCATION_PREC_RIGHT_SEQ_CHOICE_SEQ_CHOICE: usize = 3;
@@ -1161,7 +1217,8 @@ pub const TYPED_ROW_VALUE_EXPR_SEQ_REPEAT_SEQ_FIELD: usize = 1;
 pub const TYPED_ROW_VALUE_EXPR_SEQ_REPEAT_SEQ_STRING: usize = 0;
 pub const UNARY_EXPRESSION_CHOICE_PREC_LEFT_SEQ_FIELD_0: usize = 0;
 pub const UNARY_EXPRESSION_CHOICE_PREC_LEFT_SEQ_FIELD_1: usize = 1;
-pub const UPDATE_STATEMENT_SEQ_CHOICE: usize = 4;
+pub const UPDATE_STATEMENT_SEQ_CHOICE_4: usize = 4;
+pub const UPDATE_STATEMENT_SEQ_CHOICE_5: usize = 5;
 pub const UPDATE_STATEMENT_SEQ_FIELD_1: usize = 1;
 pub const UPDATE_STATEMENT_SEQ_FIELD_3: usize = 3;
 pub const UPDATE_STATEMENT_SEQ_SYMBOL_0: usize = 0;
@@ -1181,6 +1238,10 @@ pub const VARCHAR_SEQ_FIELD: usize = 2;
 pub const VARCHAR_SEQ_STRING_1: usize = 1;
 pub const VARCHAR_SEQ_STRING_3: usize = 3;
 pub const VARCHAR_SEQ_SYMBOL: usize = 0;
+pub const WHEN_BRANCH_SEQ_FIELD_1: usize = 1;
+pub const WHEN_BRANCH_SEQ_FIELD_3: usize = 3;
+pub const WHEN_BRANCH_SEQ_SYMBOL_0: usize = 0;
+pub const WHEN_BRANCH_SEQ_SYMBOL_2: usize = 2;
 pub const WHEN_CLAUSE_SEQ_CHOICE_1: usize = 1;
 pub const WHEN_CLAUSE_SEQ_CHOICE_3: usize = 3;
 pub const WHEN_CLAUSE_SEQ_CHOICE_5: usize = 5;
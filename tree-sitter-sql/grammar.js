@@ -17,7 +17,6 @@ module.exports = grammar({
     precedences: $ => [
         [
             'binary_is',
-            'unary_not',
             'binary_exp',
             'binary_times',
             'binary_plus',
@@ -27,6 +26,8 @@ module.exports = grammar({
             'binary_concat',
             'pattern_matching',
             'between',
+            // a prefix NOT applies to the whole comparison that follows it
+            'unary_not',
             'clause_connective',
             'clause_disjunctive',
         ],
@@ -112,6 +113,7 @@ module.exports = grammar({
         keyword_constraint: _ => make_keyword("constraint"),
         keyword_filter: _ => make_keyword("filter"),
         keyword_cast: _ => make_keyword("cast"),
+        keyword_extract: _ => make_keyword("extract"),
         keyword_separator: _ => make_keyword("separator"),
         keyword_max: _ => make_keyword("max"),
         keyword_min: _ => make_keyword("min"),
@@ -604,9 +606,9 @@ module.exports = grammar({
 
         statement: $ => seq(
             optional(seq(
-                $.keyword_explain,
-                optional($.keyword_analyze),
-                optional($.keyword_verbose),
+                field("explain", $.keyword_explain),
+                optional(field("analyze", $.keyword_analyze)),
+                optional(field("verbose", $.keyword_verbose)),
             )),
             field("stmt_gut",
                 choice(
@@ -641,6 +643,7 @@ module.exports = grammar({
 
         ddl_stmt: $ => choice(
             $.create_table_statement,
+            $.create_index,
             $._alter_statement,
             $.drop_statement,
             $._rename_statement,
@@ -748,6 +751,7 @@ module.exports = grammar({
             $.keyword_from,
             field('object_reference', $.object_reference),
             field('where', $.where),
+            optional(field('returning', $.returning)),
         ),
 
         delete: $ => seq(
@@ -1054,36 +1058,38 @@ module.exports = grammar({
 
         create_index: $ => seq(
             $.keyword_create,
-            optional($.keyword_unique),
+            optional(field("unique", $.keyword_unique)),
             $.keyword_index,
-            optional($.keyword_concurrently),
+            optional(field("concurrently", $.keyword_concurrently)),
             optional(
                 seq(
-                    optional($._if_not_exists),
-                    field("column", $.column),
+                    optional(field("if_not_exists", $._if_not_exists)),
+                    field("index_name", $.identifier),
                 ),
             ),
             $.keyword_on,
-            optional($.keyword_only),
+            optional(field("only", $.keyword_only)),
             seq(
-                $.object_reference,
+                field("object_reference", $.object_reference),
                 optional(
                     seq(
                         $.keyword_using,
-                        choice(
-                            $.keyword_btree,
-                            $.keyword_hash,
-                            $.keyword_gist,
-                            $.keyword_spgist,
-                            $.keyword_gin,
-                            $.keyword_brin
+                        field("using",
+                            choice(
+                                $.keyword_btree,
+                                $.keyword_hash,
+                                $.keyword_gist,
+                                $.keyword_spgist,
+                                $.keyword_gin,
+                                $.keyword_brin
+                            ),
                         ),
                     ),
                 ),
-                $.ordered_columns,
+                field("ordered_columns", $.ordered_columns),
             ),
             optional(
-                $.where,
+                field("where", $.where),
             ),
         ),
 
@@ -1705,14 +1711,14 @@ module.exports = grammar({
         drop_index: $ => seq(
             $.keyword_drop,
             $.keyword_index,
-            optional($.keyword_concurrently),
-            optional($._if_exists),
+            optional(field('concurrently', $.keyword_concurrently)),
+            optional(field('if_exist', $._if_exists)),
             field('identifier_name', $.identifier),
-            optional($._drop_behavior),
+            optional(field('drop_behavior', $._drop_behavior)),
             optional(
                 seq(
                     $.keyword_on,
-                    $.object_reference,
+                    field('object_reference', $.object_reference),
                 ),
             ),
         ),
@@ -1750,6 +1756,23 @@ module.exports = grammar({
             $.keyword_into,
             field('object_reference', $.object_reference),
             field('insert_values', $.insert_values),
+            optional(field('on_conflict', $.on_conflict)),
+            optional(field('returning', $.returning)),
+        ),
+
+        on_conflict: $ => seq(
+            $.keyword_on,
+            $.keyword_conflict,
+            optional(field('column_list', $.column_list)),
+            $.keyword_do,
+            choice(
+                field('do_nothing', $.keyword_nothing),
+                seq(
+                    $.keyword_update,
+                    $.keyword_set,
+                    field('set_values', $.set_values),
+                ),
+            ),
         ),
 
         insert_values: $ => seq(
@@ -1775,6 +1798,7 @@ module.exports = grammar({
             $.keyword_set,
             field('set_values', $.set_values),
             optional(field('where', $.where)),
+            optional(field('returning', $.returning)),
         ),
 
 
@@ -2089,6 +2113,7 @@ module.exports = grammar({
             ),
             $._default_expression,
             field('primary_key', $._primary_key),
+            field('check_constraint', $.check_constraint),
             $.keyword_auto_increment,
             $.direction,
             $._column_comment,
@@ -2127,6 +2152,18 @@ module.exports = grammar({
             $._constraint_literal,
             $._key_constraint,
             field('primary_key_constraint', $.primary_key_constraint),
+            field('check_constraint', $.check_constraint),
+        ),
+
+        check_constraint: $ => seq(
+            optional(
+                seq(
+                    $.keyword_constraint,
+                    field('name', $.identifier),
+                ),
+            ),
+            $.keyword_check,
+            wrapped_in_parenthesis(field('predicate', $.expression)),
         ),
 
         _constraint_literal: $ => seq(
@@ -2233,6 +2270,48 @@ module.exports = grammar({
             $.keyword_end,
         ),
 
+        case_expression: $ => seq(
+            $.keyword_case,
+            optional(field("operand", $.expression)),
+            repeat1(field("branch", $.when_branch)),
+            optional(
+                seq(
+                    $.keyword_else,
+                    field("else", $.expression),
+                ),
+            ),
+            $.keyword_end,
+        ),
+
+        when_branch: $ => seq(
+            $.keyword_when,
+            field("condition", $.expression),
+            $.keyword_then,
+            field("result", $.expression),
+        ),
+
+        cast_expression: $ => seq(
+            $.keyword_cast,
+            wrapped_in_parenthesis(
+                seq(
+                    field("expression", $.expression),
+                    $.keyword_as,
+                    field("type", $.data_type),
+                ),
+            ),
+        ),
+
+        extract_expression: $ => seq(
+            $.keyword_extract,
+            wrapped_in_parenthesis(
+                seq(
+                    field("part", $.identifier),
+                    $.keyword_from,
+                    field("source", $.expression),
+                ),
+            ),
+        ),
+
         field: $ => field('identifier_name', $.identifier),
 
         qualified_field: $ => seq(
@@ -2299,13 +2378,17 @@ module.exports = grammar({
             ),
         ),
 
-        cast: $ => seq(
-            field('name', $.keyword_cast),
-            wrapped_in_parenthesis(
-                seq(
-                    field('parameter', $.expression),
-                    $.keyword_as,
-                    $.data_type,
+        // a column DEFAULT keeps its own `cast` node rather than the
+        // `cast_expression` it would otherwise also match
+        cast: $ => prec(1,
+            seq(
+                field('name', $.keyword_cast),
+                wrapped_in_parenthesis(
+                    seq(
+                        field('parameter', $.expression),
+                        $.keyword_as,
+                        $.data_type,
+                    ),
                 ),
             ),
         ),
@@ -2464,11 +2547,30 @@ module.exports = grammar({
                 $.keyword_only,
             ),
             field("relation", $.relation),
+            repeat(field("join", $.join_clause)),
             optional(field("where", $.where)),
+            optional(field("group_by", $.group_by)),
+            optional(field("having", $.having)),
+            optional(field("order_by", $.order_by)),
+            optional(
+                choice(
+                    seq(
+                        field("limit", $.limit),
+                        optional(field("offset", $.offset)),
+                    ),
+                    seq(
+                        field("offset", $.offset),
+                        optional(field("limit", $.limit)),
+                    ),
+                ),
+            ),
         ),
 
 
-        relation: $ => field("object_reference", $.object_reference),
+        relation: $ => seq(
+            field("object_reference", $.object_reference),
+            optional(field("alias", $.alias_name)),
+        ),
 
 
         values: $ => seq(
@@ -2530,6 +2632,32 @@ module.exports = grammar({
             )
         ),
 
+        join_clause: $ => seq(
+            optional(field("join_type", $.join_type)),
+            $.keyword_join,
+            field("relation", $.relation),
+            optional(
+                choice(
+                    seq(
+                        $.keyword_on,
+                        field("predicate", $.expression),
+                    ),
+                    seq(
+                        $.keyword_using,
+                        field("using", $.column_list),
+                    ),
+                ),
+            ),
+        ),
+
+        join_type: $ => choice(
+            $.keyword_inner,
+            seq($.keyword_left, optional($.keyword_outer)),
+            seq($.keyword_right, optional($.keyword_outer)),
+            seq($.keyword_full, optional($.keyword_outer)),
+            $.keyword_cross,
+        ),
+
         cross_join: $ => seq(
             $.keyword_cross,
             $.keyword_join,
@@ -2595,13 +2723,12 @@ module.exports = grammar({
         group_by: $ => seq(
             $.keyword_group,
             $.keyword_by,
-            comma_list($.expression, true),
-            optional($._having),
+            comma_list(field("expression", $.expression), true),
         ),
 
-        _having: $ => seq(
+        having: $ => seq(
             $.keyword_having,
-            $.expression,
+            field("predicate", $.expression),
         ),
 
         order_by: $ => prec.right(seq(
@@ -2611,23 +2738,15 @@ module.exports = grammar({
         )),
 
         order_target: $ => seq(
-            $.expression,
+            field("expression", $.expression),
+            optional(field("direction", $.direction)),
             optional(
                 seq(
-                    choice(
-                        $.direction,
-                        seq(
-                            $.keyword_using,
-                            choice('<', '>', '<=', '>='),
-                        ),
-                    ),
-                    optional(
-                        seq(
-                            $.keyword_nulls,
-                            choice(
-                                $.keyword_first,
-                                $.keyword_last,
-                            ),
+                    $.keyword_nulls,
+                    field("nulls",
+                        choice(
+                            $.keyword_first,
+                            $.keyword_last,
                         ),
                     ),
                 ),
@@ -2636,18 +2755,17 @@ module.exports = grammar({
 
         limit: $ => seq(
             $.keyword_limit,
-            $.literal,
-            optional($.offset),
+            field("row_count", choice($.literal, $.parameter)),
         ),
 
         offset: $ => seq(
             $.keyword_offset,
-            $.literal,
+            field("row_count", choice($.literal, $.parameter)),
         ),
 
         returning: $ => seq(
             $.keyword_returning,
-            $.select_expression,
+            field('select_expression', $.select_expression),
         ),
 
         expression: $ => prec(1,
@@ -2657,7 +2775,11 @@ module.exports = grammar({
                 field('qualified_field', $.qualified_field),
                 field('invocation', $.invocation),
                 field('binary_expression', $.binary_expression),
+                field('unary_expression', $.unary_expression),
                 field('between_expression', $.between_expression),
+                field('case_expression', $.case_expression),
+                field('cast_expression', $.cast_expression),
+                field('extract_expression', $.extract_expression),
                 wrapped_in_parenthesis(field('expression_in_parenthesis', $.expression)),
             )
         ),